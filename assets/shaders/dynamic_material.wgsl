@@ -0,0 +1,16 @@
+#import bevy_pbr::{
+    forward_io::VertexOutput,
+    dynamic_material::{uniforms, texture_0, sampler_0},
+}
+
+@fragment
+fn fragment(
+    mesh: VertexOutput,
+) -> @location(0) vec4<f32> {
+    // The uniforms and textures are in the order they appear in `materials/pulse.material`
+    let tint = uniforms.values[0];
+    let pulse = uniforms.values[1].x;
+    let icon = textureSample(texture_0, sampler_0, mesh.uv);
+    let color = mix(tint.rgb, icon.rgb, icon.a);
+    return vec4(color * (0.25 + 0.75 * pulse), 1.0);
+}
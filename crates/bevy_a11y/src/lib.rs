@@ -8,13 +8,13 @@ use std::sync::{
 };
 
 pub use accesskit;
-use accesskit::NodeBuilder;
-use bevy_app::Plugin;
+use accesskit::{Action, NodeBuilder, Role};
+use bevy_app::{Plugin, PreUpdate};
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::{
-    prelude::{Component, Entity, Event},
-    schedule::SystemSet,
-    system::Resource,
+    prelude::{Component, Entity, Event, EventReader},
+    schedule::{IntoSystemConfigs, SystemSet},
+    system::{Query, ResMut, Resource},
 };
 
 /// Wrapper struct for [`accesskit::ActionRequest`]. Required to allow it to be used as an `Event`.
@@ -77,9 +77,74 @@ impl ManageAccessibilityUpdates {
 ///
 /// If the entity doesn't have a parent, or if the immediate parent doesn't have
 /// an `AccessibilityNode`, its node will be an immediate child of the primary window.
+///
+/// Custom widgets can describe themselves with the builder methods, and will receive the
+/// [`ActionRequest`]s of the actions they declare:
+///
+/// ```
+/// # use bevy_a11y::{accesskit::{Action, Role}, AccessibilityNode};
+/// let slider = AccessibilityNode::new(Role::Slider)
+///     .with_name("Volume")
+///     .with_numeric_value(0.5)
+///     .with_action(Action::Increment)
+///     .with_action(Action::Decrement)
+///     .focusable();
+/// ```
 #[derive(Component, Clone, Deref, DerefMut)]
 pub struct AccessibilityNode(pub NodeBuilder);
 
+impl AccessibilityNode {
+    /// Creates a node with the given `role`.
+    pub fn new(role: Role) -> Self {
+        Self(NodeBuilder::new(role))
+    }
+
+    /// Sets the name read by screen readers, usually the visible label of the widget.
+    pub fn with_name(mut self, name: impl Into<Box<str>>) -> Self {
+        self.0.set_name(name);
+        self
+    }
+
+    /// Sets the textual value of the widget, for example the content of a text field.
+    pub fn with_value(mut self, value: impl Into<Box<str>>) -> Self {
+        self.0.set_value(value);
+        self
+    }
+
+    /// Sets the numeric value of the widget, for example the position of a slider.
+    pub fn with_numeric_value(mut self, value: f64) -> Self {
+        self.0.set_numeric_value(value);
+        self
+    }
+
+    /// Sets a description giving more details than the name.
+    pub fn with_description(mut self, description: impl Into<Box<str>>) -> Self {
+        self.0.set_description(description);
+        self
+    }
+
+    /// Declares that the widget supports the given `action`.
+    ///
+    /// Requests for the action are sent as [`ActionRequest`] events targeting this entity.
+    pub fn with_action(mut self, action: Action) -> Self {
+        self.0.add_action(action);
+        self
+    }
+
+    /// Declares that the widget can receive keyboard focus.
+    ///
+    /// When an assistive technology requests focus for the widget, the [`Focus`] resource
+    /// is set to its entity.
+    pub fn focusable(self) -> Self {
+        self.with_action(Action::Focus)
+    }
+
+    /// Returns `true` if the widget can receive keyboard focus.
+    pub fn is_focusable(&self) -> bool {
+        self.0.supports_action(Action::Focus)
+    }
+}
+
 impl From<NodeBuilder> for AccessibilityNode {
     fn from(node: NodeBuilder) -> Self {
         Self(node)
@@ -95,6 +160,29 @@ pub struct Focus(pub Option<Entity>);
 pub enum AccessibilitySystem {
     /// Update the accessibility tree
     Update,
+    /// Apply the [`ActionRequest`]s handled by Bevy, such as focus requests
+    HandleRequests,
+}
+
+/// Sets [`Focus`] when an assistive technology requests focus for a focusable
+/// [`AccessibilityNode`], and clears it on blur.
+pub fn handle_focus_requests(
+    mut requests: EventReader<ActionRequest>,
+    mut focus: ResMut<Focus>,
+    nodes: Query<&AccessibilityNode>,
+) {
+    for request in requests.read() {
+        let Ok(entity) = Entity::try_from_bits(request.target.0) else {
+            continue;
+        };
+        match request.action {
+            Action::Focus if nodes.get(entity).is_ok_and(AccessibilityNode::is_focusable) => {
+                focus.0 = Some(entity);
+            }
+            Action::Blur if focus.0 == Some(entity) => focus.0 = None,
+            _ => {}
+        }
+    }
 }
 
 /// Plugin managing non-GUI aspects of integrating with accessibility APIs.
@@ -105,6 +193,58 @@ impl Plugin for AccessibilityPlugin {
         app.init_resource::<AccessibilityRequested>()
             .init_resource::<ManageAccessibilityUpdates>()
             .init_resource::<Focus>()
-            .allow_ambiguous_component::<AccessibilityNode>();
+            .add_event::<ActionRequest>()
+            .allow_ambiguous_component::<AccessibilityNode>()
+            .add_systems(
+                PreUpdate,
+                handle_focus_requests.in_set(AccessibilitySystem::HandleRequests),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::App;
+
+    use super::*;
+
+    fn request(action: Action, entity: Entity) -> ActionRequest {
+        ActionRequest(accesskit::ActionRequest {
+            action,
+            target: accesskit::NodeId(entity.to_bits()),
+            data: None,
+        })
+    }
+
+    #[test]
+    fn focus_requests() {
+        let mut app = App::new();
+        app.add_plugins(AccessibilityPlugin);
+        let button = app
+            .world
+            .spawn(AccessibilityNode::new(Role::Button).focusable())
+            .id();
+        let label = app
+            .world
+            .spawn(AccessibilityNode::new(Role::StaticText).with_name("Label"))
+            .id();
+
+        app.world.send_event(request(Action::Focus, button));
+        app.update();
+        assert_eq!(app.world.resource::<Focus>().0, Some(button));
+
+        // Nodes that aren't focusable ignore focus requests
+        app.world.send_event(request(Action::Focus, label));
+        app.update();
+        assert_eq!(app.world.resource::<Focus>().0, Some(button));
+
+        // Blurring another node keeps the focus
+        app.world.send_event(request(Action::Blur, label));
+        app.update();
+        assert_eq!(app.world.resource::<Focus>().0, Some(button));
+
+        app.world.send_event(request(Action::Blur, button));
+        app.update();
+        assert_eq!(app.world.resource::<Focus>().0, None);
     }
 }
@@ -1,6 +1,10 @@
-use crate::{First, Main, MainSchedulePlugin, Plugin, Plugins, StateTransition};
+use crate::{
+    channel::Channel, ChannelDirection, First, Main, MainSchedulePlugin, Plugin, Plugins,
+    StateTransition,
+};
 pub use bevy_derive::AppLabel;
 use bevy_ecs::{
+    event::ManualEventReader,
     prelude::*,
     schedule::{
         apply_state_transition, common_conditions::run_once as run_once_condition,
@@ -83,6 +87,8 @@ pub struct App {
     /// A private counter to prevent incorrect calls to `App::run()` from `Plugin::build()`
     building_plugin_depth: usize,
     plugins_state: PluginsState,
+    /// Reads the [`AppExit`] events for [`App::pump`]
+    app_exit_event_reader: ManualEventReader<AppExit>,
 }
 
 impl Debug for App {
@@ -143,6 +149,9 @@ pub struct SubApp {
     /// A function that allows access to both the main [`App`] [`World`] and the [`SubApp`]. This is
     /// useful for moving data between the sub app and the main app.
     extract: Box<dyn Fn(&mut World, &mut App) + Send>,
+
+    /// The channels between the main app and the [`SubApp`], delivered before `extract`.
+    channels: Vec<Box<dyn Fn(&mut World, &mut World) + Send>>,
 }
 
 impl SubApp {
@@ -156,6 +165,7 @@ impl SubApp {
         Self {
             app,
             extract: Box::new(extract),
+            channels: Vec::new(),
         }
     }
 
@@ -165,10 +175,32 @@ impl SubApp {
         self.app.world.clear_trackers();
     }
 
-    /// Extracts data from main world to this sub-app.
+    /// Delivers the messages of the channels between the main app and this sub-app, then
+    /// extracts data from main world to this sub-app.
     pub fn extract(&mut self, main_world: &mut World) {
+        for deliver in &self.channels {
+            deliver(main_world, &mut self.app.world);
+        }
         (self.extract)(main_world, &mut self.app);
     }
+
+    /// Adds a channel sending messages of type `T` between the main app and this sub-app, in the
+    /// given `direction`.
+    ///
+    /// See [`App::add_sub_app_channel`].
+    pub fn add_channel<T: Send + Sync + 'static>(
+        &mut self,
+        main_world: &mut World,
+        direction: ChannelDirection,
+    ) -> &mut Self {
+        let channel = Channel::<T>::new(direction);
+        channel.init(main_world, &mut self.app.world);
+        self.channels
+            .push(Box::new(move |main_world, sub_app_world| {
+                channel.deliver(main_world, sub_app_world);
+            }));
+        self
+    }
 }
 
 impl Debug for SubApp {
@@ -240,6 +272,7 @@ impl App {
             main_schedule_label: Main.intern(),
             building_plugin_depth: 0,
             plugins_state: PluginsState::Adding,
+            app_exit_event_reader: ManualEventReader::default(),
         }
     }
 
@@ -308,6 +341,77 @@ impl App {
         runner(app);
     }
 
+    /// Runs [`Plugin::finish`] and [`Plugin::cleanup`] once all plugins are ready, without
+    /// blocking. Returns `true` once the app can be updated.
+    ///
+    /// While the plugins aren't ready, the tasks of the main thread are ticked so that the
+    /// plugins waiting on them make progress.
+    pub fn try_finish_plugins(&mut self) -> bool {
+        match self.plugins_state() {
+            PluginsState::Adding => {
+                #[cfg(not(target_arch = "wasm32"))]
+                bevy_tasks::tick_global_task_pools_on_main_thread();
+                false
+            }
+            PluginsState::Ready => {
+                self.finish();
+                self.cleanup();
+                true
+            }
+            PluginsState::Finished => {
+                self.cleanup();
+                true
+            }
+            PluginsState::Cleaned => true,
+        }
+    }
+
+    /// Updates the app once, for apps driven by an external event loop instead of a
+    /// [runner](Self::set_runner), for example when embedding Bevy in another application.
+    ///
+    /// The app isn't updated until its plugins are ready, see [`App::try_finish_plugins`].
+    /// Returns the last [`AppExit`] sent since the previous call, in which case the event
+    /// loop should stop updating the app.
+    ///
+    /// ```
+    /// # use bevy_app::{prelude::*, AppExit};
+    /// let mut app = App::new();
+    /// app.add_systems(Update, |mut exit: bevy_ecs::event::EventWriter<AppExit>| {
+    ///     exit.send(AppExit);
+    /// });
+    ///
+    /// // The event loop of the host application
+    /// loop {
+    ///     if app.pump().is_some() {
+    ///         break;
+    ///     }
+    /// }
+    /// ```
+    pub fn pump(&mut self) -> Option<AppExit> {
+        if !self.try_finish_plugins() {
+            return None;
+        }
+        self.update();
+
+        let app_exit_events = self.world.get_resource::<Events<AppExit>>()?;
+        self.app_exit_event_reader
+            .read(app_exit_events)
+            .last()
+            .cloned()
+    }
+
+    /// Sends `events` to the app's [`World`], then updates the app once with [`App::pump`].
+    ///
+    /// This lets an external event loop forward its events, converted to Bevy events, before
+    /// each update.
+    pub fn update_with_events<E: Event>(
+        &mut self,
+        events: impl IntoIterator<Item = E>,
+    ) -> Option<AppExit> {
+        self.world.send_event_batch(events);
+        self.pump()
+    }
+
     /// Check the state of all plugins already added to this app. This is usually called by the
     /// event loop, but can be useful for situations where you want to use [`App::update`]
     #[inline]
@@ -846,6 +950,31 @@ impl App {
         self.sub_apps.remove(&label.intern())
     }
 
+    /// Adds a channel sending messages of type `T` between the main app and the sub app with the
+    /// given label, in the given `direction`.
+    ///
+    /// The sending app writes messages to the [`ChannelSender<T>`] resource, and the receiving app
+    /// reads them from the [`ChannelReceiver<T>`] resource. Messages are delivered when the sub
+    /// app is extracted, before its extract function runs, so messages sent from the sub app
+    /// are received by the main app during its next update.
+    ///
+    /// Each world can have a single sender and a single receiver for a message type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sub app doesn't exist.
+    pub fn add_sub_app_channel<T: Send + Sync + 'static>(
+        &mut self,
+        label: impl AppLabel,
+        direction: ChannelDirection,
+    ) -> &mut Self {
+        let Some(sub_app) = self.sub_apps.get_mut(&label.intern()) else {
+            panic!("Sub-App with label '{:?}' does not exist", label);
+        };
+        sub_app.add_channel::<T>(&mut self.world, direction);
+        self
+    }
+
     /// Retrieves a `SubApp` inside this [`App`] with the given label, if it exists. Otherwise returns
     /// an [`Err`] containing the given label.
     pub fn get_sub_app(&self, label: impl AppLabel) -> Result<&App, impl AppLabel> {
@@ -1295,4 +1424,28 @@ mod tests {
             .add_systems(PreUpdate, my_system)
             .run();
     }
+
+    #[test]
+    fn update_with_events() {
+        use super::{AppExit, Event, EventReader, EventWriter};
+        use crate::Update;
+
+        #[derive(Event)]
+        struct HostEvent(u32);
+
+        let mut app = App::new();
+        app.add_event::<HostEvent>().add_systems(
+            Update,
+            |mut events: EventReader<HostEvent>, mut exit: EventWriter<AppExit>| {
+                if events.read().any(|event| event.0 == 2) {
+                    exit.send(AppExit);
+                }
+            },
+        );
+
+        assert!(app.update_with_events([HostEvent(1)]).is_none());
+        assert!(app.update_with_events([HostEvent(2)]).is_some());
+        // Each exit is only returned once
+        assert!(app.pump().is_none());
+    }
 }
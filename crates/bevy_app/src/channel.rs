@@ -0,0 +1,193 @@
+//! Typed channels to send data between the main [`App`](crate::App) and its [`SubApp`](crate::SubApp)s.
+
+use bevy_ecs::{system::Resource, world::World};
+use std::marker::PhantomData;
+
+/// The direction of a channel between the main app and a [`SubApp`](crate::SubApp).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChannelDirection {
+    /// Messages are sent from the main app and received in the sub app.
+    ToSubApp,
+    /// Messages are sent from the sub app and received in the main app.
+    FromSubApp,
+}
+
+/// A resource to send messages of type `T` through a channel added with
+/// [`App::add_sub_app_channel`](crate::App::add_sub_app_channel).
+///
+/// Messages are delivered to the [`ChannelReceiver`] of the other app when the sub app is
+/// extracted, which is the sync point between the two apps.
+#[derive(Resource, Debug)]
+pub struct ChannelSender<T: Send + Sync + 'static> {
+    messages: Vec<T>,
+}
+
+impl<T: Send + Sync + 'static> Default for ChannelSender<T> {
+    fn default() -> Self {
+        Self {
+            messages: Vec::new(),
+        }
+    }
+}
+
+impl<T: Send + Sync + 'static> ChannelSender<T> {
+    /// Sends a message, delivered at the next sync point.
+    pub fn send(&mut self, message: T) {
+        self.messages.push(message);
+    }
+
+    /// Returns the number of messages waiting for the next sync point.
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Returns `true` if no messages are waiting for the next sync point.
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+}
+
+impl<T: Send + Sync + 'static> Extend<T> for ChannelSender<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, messages: I) {
+        self.messages.extend(messages);
+    }
+}
+
+/// A resource to receive messages of type `T` through a channel added with
+/// [`App::add_sub_app_channel`](crate::App::add_sub_app_channel).
+///
+/// Delivered messages are kept in the order they were sent until they are drained, even if
+/// several sync points happen in between.
+#[derive(Resource, Debug)]
+pub struct ChannelReceiver<T: Send + Sync + 'static> {
+    messages: Vec<T>,
+}
+
+impl<T: Send + Sync + 'static> Default for ChannelReceiver<T> {
+    fn default() -> Self {
+        Self {
+            messages: Vec::new(),
+        }
+    }
+}
+
+impl<T: Send + Sync + 'static> ChannelReceiver<T> {
+    /// Removes and returns the received messages, in the order they were sent.
+    pub fn drain(&mut self) -> impl Iterator<Item = T> + '_ {
+        self.messages.drain(..)
+    }
+
+    /// Returns an iterator over the received messages, without removing them.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.messages.iter()
+    }
+
+    /// Returns the number of received messages.
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Returns `true` if no messages were received.
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+}
+
+/// Moves the messages of a channel from the [`ChannelSender`] of one world to the
+/// [`ChannelReceiver`] of the other.
+pub(crate) struct Channel<T> {
+    direction: ChannelDirection,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T: Send + Sync + 'static> Channel<T> {
+    pub(crate) fn new(direction: ChannelDirection) -> Self {
+        Self {
+            direction,
+            marker: PhantomData,
+        }
+    }
+
+    pub(crate) fn init(&self, main_world: &mut World, sub_app_world: &mut World) {
+        let (sender, receiver) = self.worlds(main_world, sub_app_world);
+        sender.init_resource::<ChannelSender<T>>();
+        receiver.init_resource::<ChannelReceiver<T>>();
+    }
+
+    pub(crate) fn deliver(&self, main_world: &mut World, sub_app_world: &mut World) {
+        let (sender, receiver) = self.worlds(main_world, sub_app_world);
+        let Some(mut sender) = sender.get_resource_mut::<ChannelSender<T>>() else {
+            return;
+        };
+        if sender.messages.is_empty() {
+            return;
+        }
+        let messages = std::mem::take(&mut sender.messages);
+        receiver
+            .get_resource_or_insert_with(ChannelReceiver::<T>::default)
+            .messages
+            .extend(messages);
+    }
+
+    fn worlds<'a>(
+        &self,
+        main_world: &'a mut World,
+        sub_app_world: &'a mut World,
+    ) -> (&'a mut World, &'a mut World) {
+        match self.direction {
+            ChannelDirection::ToSubApp => (main_world, sub_app_world),
+            ChannelDirection::FromSubApp => (sub_app_world, main_world),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as bevy_app;
+    use crate::{App, AppLabel, ChannelDirection, ChannelReceiver, ChannelSender, SubApp, Update};
+    use bevy_ecs::prelude::*;
+
+    #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, AppLabel)]
+    struct AudioApp;
+
+    #[derive(Debug, PartialEq)]
+    struct PlaySound(u32);
+
+    #[derive(Debug, PartialEq)]
+    struct SoundFinished(u32);
+
+    #[test]
+    fn channels_deliver_at_sync_points() {
+        let mut app = App::new();
+        let mut sub_app = App::new();
+        sub_app.add_systems(
+            Update,
+            |mut requests: ResMut<ChannelReceiver<PlaySound>>,
+             mut finished: ResMut<ChannelSender<SoundFinished>>| {
+                finished.extend(requests.drain().map(|PlaySound(id)| SoundFinished(id)));
+            },
+        );
+        app.insert_sub_app(AudioApp, SubApp::new(sub_app, |_, _| {}));
+        app.add_sub_app_channel::<PlaySound>(AudioApp, ChannelDirection::ToSubApp)
+            .add_sub_app_channel::<SoundFinished>(AudioApp, ChannelDirection::FromSubApp);
+
+        app.world
+            .resource_mut::<ChannelSender<PlaySound>>()
+            .send(PlaySound(1));
+        app.update();
+        assert!(app.world.resource::<ChannelSender<PlaySound>>().is_empty());
+        // The sub app answers after the sync point, so the answer arrives at the next one
+        assert!(app
+            .world
+            .resource::<ChannelReceiver<SoundFinished>>()
+            .is_empty());
+
+        app.update();
+        let finished: Vec<_> = app
+            .world
+            .resource_mut::<ChannelReceiver<SoundFinished>>()
+            .drain()
+            .collect();
+        assert_eq!(finished, vec![SoundFinished(1)]);
+    }
+}
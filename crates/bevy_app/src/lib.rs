@@ -1,6 +1,7 @@
 //! This crate is about everything concerning the highest-level, application layer of a Bevy app.
 
 mod app;
+mod channel;
 mod main_schedule;
 mod plugin;
 mod plugin_group;
@@ -11,6 +12,7 @@ pub mod ci_testing;
 
 pub use app::*;
 pub use bevy_derive::DynamicPlugin;
+pub use channel::*;
 pub use main_schedule::*;
 pub use plugin::*;
 pub use plugin_group::*;
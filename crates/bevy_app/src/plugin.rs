@@ -55,6 +55,43 @@ use std::any::Any;
 /// }
 /// # fn damp_flickering() {}
 /// ````
+///
+/// ## Plugin settings
+///
+/// The fields of a plugin can only be read while it is built. Settings that should be changed
+/// after the plugin is added, before [`Plugin::finish`] or at runtime, are better exposed as a
+/// [`Resource`](bevy_ecs::system::Resource) inserted by the plugin from its fields, unless the
+/// app already contains one, and read by the plugin's systems.
+///
+/// ```
+/// # use bevy_app::*;
+/// # use bevy_ecs::prelude::*;
+/// pub struct AccessibilityPlugin {
+///     pub flicker_damping: bool,
+/// }
+///
+/// #[derive(Resource, Clone)]
+/// pub struct AccessibilitySettings {
+///     pub flicker_damping: bool,
+/// }
+///
+/// impl Plugin for AccessibilityPlugin {
+///     fn build(&self, app: &mut App) {
+///         if !app.world.contains_resource::<AccessibilitySettings>() {
+///             app.insert_resource(AccessibilitySettings {
+///                 flicker_damping: self.flicker_damping,
+///             });
+///         }
+///         app.add_systems(PostUpdate, damp_flickering);
+///     }
+/// }
+///
+/// fn damp_flickering(settings: Res<AccessibilitySettings>) {
+///     if settings.flicker_damping {
+///         // ...
+///     }
+/// }
+/// ```
 pub trait Plugin: Downcast + Any + Send + Sync {
     /// Configures the [`App`] to which this plugin is added.
     fn build(&self, app: &mut App);
@@ -0,0 +1,92 @@
+//! Diagnostics for the number of loaded assets and the memory they use.
+
+use crate::{Asset, Assets};
+use bevy_app::{App, Plugin, Update};
+use bevy_diagnostic::{
+    bytes_to_mebibytes, Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic,
+};
+use bevy_ecs::{schedule::IntoSystemConfigs, system::Res};
+use bevy_time::common_conditions::on_real_timer;
+use bevy_utils::{get_short_name, Duration};
+use std::marker::PhantomData;
+
+/// Adds diagnostics for the number of assets of type `A` and the memory they use,
+/// under `assets/<type name>/count` and `memory/assets/<type name>`.
+///
+/// By default the memory used by an asset is assumed to be the size of `A`,
+/// which doesn't account for heap allocations such as vertex or pixel data.
+/// Use [`with_byte_size`](Self::with_byte_size) to measure it more precisely.
+///
+/// # See also
+///
+/// [`LogDiagnosticsPlugin`](bevy_diagnostic::LogDiagnosticsPlugin) to output diagnostics to the console.
+pub struct AssetCountDiagnosticsPlugin<A: Asset> {
+    /// How often the assets are counted.
+    pub wait_duration: Duration,
+    byte_size: fn(&A) -> usize,
+    marker: PhantomData<fn() -> A>,
+}
+
+impl<A: Asset> Default for AssetCountDiagnosticsPlugin<A> {
+    fn default() -> Self {
+        Self {
+            wait_duration: Duration::from_secs(1),
+            byte_size: |_| std::mem::size_of::<A>(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<A: Asset> AssetCountDiagnosticsPlugin<A> {
+    /// Uses the given function to measure the number of bytes used by each asset.
+    pub fn with_byte_size(mut self, byte_size: fn(&A) -> usize) -> Self {
+        self.byte_size = byte_size;
+        self
+    }
+
+    /// The path of the diagnostic counting the assets of type `A`.
+    pub fn count_path() -> DiagnosticPath {
+        DiagnosticPath::from_components(["assets", &Self::type_name(), "count"])
+    }
+
+    /// The path of the diagnostic measuring the memory used by assets of type `A`, in mebibytes.
+    pub fn bytes_path() -> DiagnosticPath {
+        DiagnosticPath::from_components(["memory", "assets", &Self::type_name()])
+    }
+
+    fn type_name() -> String {
+        get_short_name(std::any::type_name::<A>())
+    }
+}
+
+impl<A: Asset> Plugin for AssetCountDiagnosticsPlugin<A> {
+    fn build(&self, app: &mut App) {
+        let byte_size = self.byte_size;
+        app.register_diagnostic(Diagnostic::new(Self::count_path()).with_smoothing_factor(0.0))
+            .register_diagnostic(Diagnostic::new(Self::bytes_path()).with_suffix("MiB"))
+            .add_systems(
+                Update,
+                (move |diagnostics: Diagnostics, assets: Option<Res<Assets<A>>>| {
+                    asset_count_diagnostic_system(diagnostics, assets, byte_size);
+                })
+                .run_if(on_real_timer(self.wait_duration)),
+            );
+    }
+}
+
+fn asset_count_diagnostic_system<A: Asset>(
+    mut diagnostics: Diagnostics,
+    assets: Option<Res<Assets<A>>>,
+    byte_size: fn(&A) -> usize,
+) {
+    let Some(assets) = assets else {
+        return;
+    };
+
+    diagnostics.add_measurement(&AssetCountDiagnosticsPlugin::<A>::count_path(), || {
+        assets.len() as f64
+    });
+    diagnostics.add_measurement(&AssetCountDiagnosticsPlugin::<A>::bytes_path(), || {
+        bytes_to_mebibytes(assets.iter().map(|(_, asset)| byte_size(asset)).sum())
+    });
+}
@@ -1,6 +1,7 @@
 // FIXME(3492): remove once docs are ready
 #![allow(missing_docs)]
 
+pub mod diagnostic;
 pub mod io;
 pub mod meta;
 pub mod processor;
@@ -1314,6 +1315,90 @@ mod tests {
         });
     }
 
+    #[test]
+    fn asset_graph() {
+        let dir = Dir::default();
+
+        let a_path = "a.cool.ron";
+        let a_ron = r#"
+(
+    text: "a",
+    dependencies: [
+        "b.cool.ron",
+        "c.cool.ron",
+    ],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#;
+        let b_path = "b.cool.ron";
+        let b_ron = r#"
+(
+    text: "b",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#;
+        let c_path = "c.cool.ron";
+        let c_ron = r#"
+(
+    text: "c",
+    dependencies: [
+        "b.cool.ron",
+    ],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#;
+        dir.insert_asset_text(Path::new(a_path), a_ron);
+        dir.insert_asset_text(Path::new(b_path), b_ron);
+        dir.insert_asset_text(Path::new(c_path), c_ron);
+
+        let mut app = App::new();
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSource::build()
+                .with_reader(move || Box::new(MemoryAssetReader { root: dir.clone() })),
+        )
+        .add_plugins((TaskPoolPlugin::default(), AssetPlugin::default()));
+        app.init_asset::<CoolText>()
+            .init_asset::<SubText>()
+            .register_asset_loader(CoolTextLoader);
+        let asset_server = app.world.resource::<AssetServer>().clone();
+        let a_handle: Handle<CoolText> = asset_server.load(a_path);
+        run_app_until(&mut app, |_| {
+            asset_server
+                .is_loaded_with_dependencies(&a_handle)
+                .then_some(())
+        });
+
+        let graph = asset_server.asset_graph();
+        let a_id = a_handle.id().untyped();
+        let b_id = asset_server.get_path_id(b_path).unwrap();
+        let c_id = asset_server.get_path_id(c_path).unwrap();
+        assert_eq!(graph.len(), 3);
+        assert_eq!(graph.get(a_id).unwrap().load_state, LoadState::Loaded);
+        assert_eq!(graph.get_path(b_path).next().unwrap().id, b_id);
+
+        let mut a_dependencies = graph.dependencies(a_id).to_vec();
+        a_dependencies.sort_by_key(ToString::to_string);
+        let mut b_and_c = vec![b_id, c_id];
+        b_and_c.sort_by_key(ToString::to_string);
+        assert_eq!(a_dependencies, b_and_c);
+        assert!(graph.dependencies(b_id).is_empty());
+        assert_eq!(graph.dependants(c_id), &[a_id]);
+        assert_eq!(graph.dependants(b_id).len(), 2);
+        assert_eq!(graph.recursive_dependants(c_id), vec![a_id]);
+        assert_eq!(graph.recursive_dependencies(c_id), vec![b_id]);
+        assert_eq!(graph.recursive_dependencies(a_id).len(), 2);
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph assets {"));
+        assert!(dot.contains("[label=\"a.cool.ron\\nLoaded\"]"));
+        assert_eq!(dot.matches("->").count(), 3);
+        let json = graph.to_json();
+        assert!(json.contains("\"path\": \"c.cool.ron\""));
+        assert!(json.contains("\"load_state\": \"Loaded\""));
+    }
+
     /// Tests that `AssetLoadFailedEvent<A>` events are emitted and can be used to retry failed assets.
     #[test]
     fn load_error_events() {
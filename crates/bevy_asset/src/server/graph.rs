@@ -0,0 +1,286 @@
+use crate::{AssetPath, LoadState, UntypedAssetId};
+use bevy_utils::{HashMap, HashSet};
+use std::fmt::Write;
+
+/// A snapshot of the assets managed by an [`AssetServer`](crate::AssetServer) and the
+/// dependencies between them, taken with [`AssetServer::asset_graph`](crate::AssetServer::asset_graph).
+///
+/// This answers "what does this asset depend on" with [`dependencies`](Self::dependencies), and
+/// "what depends on this asset" with [`dependants`](Self::dependants). Since an asset keeps its
+/// dependencies alive, the dependants of an asset that never unloads are usually the reason why.
+/// [`reload_dependants`](Self::reload_dependants) lists the assets that are reloaded when a file
+/// changes.
+///
+/// The graph can be exported with [`to_dot`](Self::to_dot) to be rendered by Graphviz, or with
+/// [`to_json`](Self::to_json) to be read by other tools.
+///
+/// The snapshot is not updated as assets load and unload. Take a new one to see those changes.
+#[derive(Debug, Clone, Default)]
+pub struct AssetGraph {
+    /// Sorted by path, so exports are stable.
+    nodes: Vec<AssetGraphNode>,
+    indices: HashMap<UntypedAssetId, usize>,
+    dependants: Vec<Vec<UntypedAssetId>>,
+}
+
+/// An asset in an [`AssetGraph`].
+#[derive(Debug, Clone)]
+pub struct AssetGraphNode {
+    /// The id of the asset.
+    pub id: UntypedAssetId,
+    /// The path the asset was loaded from, if any.
+    pub path: Option<AssetPath<'static>>,
+    /// Whether the asset has loaded.
+    pub load_state: LoadState,
+    /// The number of strong [`Handle`](crate::Handle)s keeping the asset alive, including the
+    /// ones held by its dependants.
+    pub strong_handles: usize,
+    /// The assets this asset holds handles to. These are loaded along with it, and kept alive
+    /// while it is.
+    pub dependencies: Vec<UntypedAssetId>,
+    /// The paths read by the loader of this asset. The asset is reloaded when one of them changes.
+    ///
+    /// This is only tracked while the [`AssetServer`](crate::AssetServer) is watching for changes.
+    pub loader_dependencies: Vec<AssetPath<'static>>,
+}
+
+impl AssetGraph {
+    pub(crate) fn new(mut nodes: Vec<AssetGraphNode>) -> Self {
+        nodes.sort_by_cached_key(|node| {
+            (
+                node.path.as_ref().map(ToString::to_string),
+                node.id.to_string(),
+            )
+        });
+        let indices: HashMap<_, _> = nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (node.id, index))
+            .collect();
+        let mut dependants = vec![Vec::new(); nodes.len()];
+        for node in &nodes {
+            for dependency in &node.dependencies {
+                if let Some(&index) = indices.get(dependency) {
+                    dependants[index].push(node.id);
+                }
+            }
+        }
+        Self {
+            nodes,
+            indices,
+            dependants,
+        }
+    }
+
+    /// Returns the asset with the given `id`, if it is managed by the asset server.
+    pub fn get(&self, id: impl Into<UntypedAssetId>) -> Option<&AssetGraphNode> {
+        self.indices
+            .get(&id.into())
+            .map(|&index| &self.nodes[index])
+    }
+
+    /// Returns the assets loaded from the given `path`. There is more than one when the path was
+    /// loaded as different asset types.
+    pub fn get_path<'a>(
+        &self,
+        path: impl Into<AssetPath<'a>>,
+    ) -> impl Iterator<Item = &AssetGraphNode> {
+        let path = path.into().into_owned();
+        self.nodes
+            .iter()
+            .filter(move |node| node.path.as_ref() == Some(&path))
+    }
+
+    /// Iterates over every asset in the graph, ordered by path.
+    pub fn iter(&self) -> impl Iterator<Item = &AssetGraphNode> {
+        self.nodes.iter()
+    }
+
+    /// Returns the number of assets in the graph.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns `true` if the graph contains no assets.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns the assets the asset with the given `id` holds handles to.
+    pub fn dependencies(&self, id: impl Into<UntypedAssetId>) -> &[UntypedAssetId] {
+        self.get(id)
+            .map(|node| node.dependencies.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Returns the assets holding handles to the asset with the given `id`.
+    pub fn dependants(&self, id: impl Into<UntypedAssetId>) -> &[UntypedAssetId] {
+        self.indices
+            .get(&id.into())
+            .map(|&index| self.dependants[index].as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Returns the dependencies of the asset with the given `id`, their dependencies, and so on.
+    pub fn recursive_dependencies(&self, id: impl Into<UntypedAssetId>) -> Vec<UntypedAssetId> {
+        self.walk(id.into(), |id| self.dependencies(id))
+    }
+
+    /// Returns the dependants of the asset with the given `id`, their dependants, and so on.
+    pub fn recursive_dependants(&self, id: impl Into<UntypedAssetId>) -> Vec<UntypedAssetId> {
+        self.walk(id.into(), |id| self.dependants(id))
+    }
+
+    /// Returns the paths of the assets that are reloaded when the file at `path` changes, not
+    /// including `path` itself.
+    ///
+    /// This follows [`AssetGraphNode::loader_dependencies`], so it is only accurate while the
+    /// [`AssetServer`](crate::AssetServer) is watching for changes.
+    pub fn reload_dependants<'a>(&self, path: impl Into<AssetPath<'a>>) -> Vec<AssetPath<'static>> {
+        let mut reloaded = Vec::new();
+        let mut visited = HashSet::new();
+        let mut queue = vec![path.into().into_owned()];
+        while let Some(path) = queue.pop() {
+            for node in &self.nodes {
+                let Some(node_path) = &node.path else {
+                    continue;
+                };
+                if node.loader_dependencies.contains(&path) && visited.insert(node_path.clone()) {
+                    reloaded.push(node_path.clone());
+                    queue.push(node_path.clone());
+                }
+            }
+        }
+        reloaded
+    }
+
+    fn walk<'a>(
+        &'a self,
+        start: UntypedAssetId,
+        next: impl Fn(UntypedAssetId) -> &'a [UntypedAssetId],
+    ) -> Vec<UntypedAssetId> {
+        let mut found = Vec::new();
+        let mut visited = HashSet::new();
+        visited.insert(start);
+        let mut queue = vec![start];
+        while let Some(id) = queue.pop() {
+            for &next_id in next(id) {
+                if visited.insert(next_id) {
+                    found.push(next_id);
+                    queue.push(next_id);
+                }
+            }
+        }
+        found
+    }
+
+    /// Exports the graph in the DOT language of Graphviz.
+    ///
+    /// Assets point to their dependencies with solid edges, and to their loader dependencies
+    /// with dashed edges.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph assets {\n    node [shape=box];\n");
+        for (index, node) in self.nodes.iter().enumerate() {
+            let _ = writeln!(
+                dot,
+                "    n{index} [label=\"{}\\n{:?}\"];",
+                escape(&node.label()),
+                node.load_state
+            );
+        }
+        let mut path_nodes: HashMap<&AssetPath, usize> = HashMap::new();
+        for (index, node) in self.nodes.iter().enumerate() {
+            if let Some(path) = &node.path {
+                path_nodes.entry(path).or_insert(index);
+            }
+        }
+        let mut missing_paths: HashMap<&AssetPath, usize> = HashMap::new();
+        for (index, node) in self.nodes.iter().enumerate() {
+            for dependency in &node.dependencies {
+                if let Some(dependency) = self.indices.get(dependency) {
+                    let _ = writeln!(dot, "    n{index} -> n{dependency};");
+                }
+            }
+            for path in &node.loader_dependencies {
+                // Loader dependencies can be files that aren't assets themselves
+                match path_nodes.get(path) {
+                    Some(dependency) => {
+                        let _ = writeln!(dot, "    n{index} -> n{dependency} [style=dashed];");
+                    }
+                    None => {
+                        let count = missing_paths.len();
+                        let dependency = *missing_paths.entry(path).or_insert_with(|| {
+                            let _ = writeln!(dot, "    p{count} [label=\"{}\"];", escape(path));
+                            count
+                        });
+                        let _ = writeln!(dot, "    n{index} -> p{dependency} [style=dashed];");
+                    }
+                }
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Exports the graph as JSON, with one object per asset.
+    ///
+    /// Assets are referred to by their `id`, formatted as a string.
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("{\n  \"assets\": [");
+        for (index, node) in self.nodes.iter().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+            let path = match &node.path {
+                Some(path) => format!("\"{}\"", escape(path)),
+                None => "null".to_string(),
+            };
+            let dependencies = json_strings(&node.dependencies);
+            let loader_dependencies = json_strings(&node.loader_dependencies);
+            let dependants = json_strings(&self.dependants[index]);
+            let _ = write!(
+                json,
+                "\n    {{\"id\": \"{}\", \"path\": {path}, \"load_state\": \"{:?}\", \"strong_handles\": {}, \"dependencies\": {dependencies}, \"dependants\": {dependants}, \"loader_dependencies\": {loader_dependencies}}}",
+                escape(&node.id),
+                node.load_state,
+                node.strong_handles,
+            );
+        }
+        json.push_str("\n  ]\n}\n");
+        json
+    }
+}
+
+impl AssetGraphNode {
+    fn label(&self) -> String {
+        match &self.path {
+            Some(path) => path.to_string(),
+            None => self.id.to_string(),
+        }
+    }
+}
+
+fn json_strings(values: &[impl std::fmt::Display]) -> String {
+    let values: Vec<_> = values
+        .iter()
+        .map(|value| format!("\"{}\"", escape(value)))
+        .collect();
+    format!("[{}]", values.join(", "))
+}
+
+/// Escapes a value to be written between double quotes, in both DOT and JSON.
+fn escape(value: &impl std::fmt::Display) -> String {
+    let mut escaped = String::new();
+    for c in value.to_string().chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c if c.is_control() => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
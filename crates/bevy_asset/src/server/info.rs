@@ -1,8 +1,8 @@
 use crate::{
     meta::{AssetHash, MetaTransform},
-    Asset, AssetHandleProvider, AssetLoadError, AssetPath, DependencyLoadState, ErasedLoadedAsset,
-    Handle, InternalAssetEvent, LoadState, RecursiveDependencyLoadState, StrongHandle,
-    UntypedAssetId, UntypedHandle,
+    Asset, AssetGraph, AssetGraphNode, AssetHandleProvider, AssetLoadError, AssetPath,
+    DependencyLoadState, ErasedLoadedAsset, Handle, InternalAssetEvent, LoadState,
+    RecursiveDependencyLoadState, StrongHandle, UntypedAssetId, UntypedHandle,
 };
 use bevy_ecs::world::World;
 use bevy_log::warn;
@@ -27,6 +27,8 @@ pub(crate) struct AssetInfo {
     failed_rec_dependencies: HashSet<UntypedAssetId>,
     dependants_waiting_on_load: HashSet<UntypedAssetId>,
     dependants_waiting_on_recursive_dep_load: HashSet<UntypedAssetId>,
+    /// The assets this asset holds handles to, as set by its loader.
+    dependencies: HashSet<UntypedAssetId>,
     /// The asset paths required to load this asset. Hashes will only be set for processed assets.
     /// This is set using the value from [`LoadedAsset`].
     /// This will only be populated if [`AssetInfos::watching_for_changes`] is set to `true` to
@@ -51,6 +53,7 @@ impl AssetInfo {
             failed_dependencies: HashSet::default(),
             loading_rec_dependencies: HashSet::default(),
             failed_rec_dependencies: HashSet::default(),
+            dependencies: HashSet::default(),
             loader_dependencies: HashMap::default(),
             dependants_waiting_on_load: HashSet::default(),
             dependants_waiting_on_recursive_dep_load: HashSet::default(),
@@ -327,6 +330,23 @@ impl AssetInfos {
             .filter_map(|id| self.get_id_handle(id))
     }
 
+    /// Takes a snapshot of the assets and the dependencies between them.
+    pub(crate) fn asset_graph(&self) -> AssetGraph {
+        AssetGraph::new(
+            self.infos
+                .iter()
+                .map(|(&id, info)| AssetGraphNode {
+                    id,
+                    path: info.path.clone(),
+                    load_state: info.load_state,
+                    strong_handles: info.weak_handle.strong_count(),
+                    dependencies: info.dependencies.iter().copied().collect(),
+                    loader_dependencies: info.loader_dependencies.keys().cloned().collect(),
+                })
+                .collect(),
+        )
+    }
+
     pub(crate) fn get_id_handle(&self, id: UntypedAssetId) -> Option<UntypedHandle> {
         let info = self.infos.get(&id)?;
         let strong_handle = info.weak_handle.upgrade()?;
@@ -380,6 +400,7 @@ impl AssetInfos {
     ) {
         loaded_asset.value.insert(loaded_asset_id, world);
         let mut loading_deps = loaded_asset.dependencies;
+        let dependencies = loading_deps.clone();
         let mut failed_deps = HashSet::new();
         let mut loading_rec_deps = loading_deps.clone();
         let mut failed_rec_deps = HashSet::new();
@@ -467,6 +488,7 @@ impl AssetInfos {
             let info = self
                 .get_mut(loaded_asset_id)
                 .expect("Asset info should always exist at this point");
+            info.dependencies = dependencies;
             info.loading_dependencies = loading_deps;
             info.failed_dependencies = failed_deps;
             info.loading_rec_dependencies = loading_rec_deps;
@@ -1,5 +1,8 @@
+mod graph;
 mod info;
 
+pub use graph::*;
+
 use crate::{
     folder::LoadedFolder,
     io::{
@@ -927,6 +930,15 @@ impl AssetServer {
         Some(info.path.as_ref()?.clone())
     }
 
+    /// Returns a snapshot of the assets managed by this [`AssetServer`] and the dependencies
+    /// between them.
+    ///
+    /// This is meant for debugging, like finding out why an asset is never unloaded or which
+    /// assets a change to a file will reload. See [`AssetGraph`] for the available queries.
+    pub fn asset_graph(&self) -> AssetGraph {
+        self.data.infos.read().asset_graph()
+    }
+
     /// Returns the [`AssetServerMode`] this server is currently in.
     pub fn mode(&self) -> AssetServerMode {
         self.data.mode
@@ -25,6 +25,7 @@
 mod audio;
 mod audio_output;
 mod audio_source;
+mod microphone;
 mod pitch;
 mod sinks;
 
@@ -33,12 +34,14 @@ pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
         AudioBundle, AudioSink, AudioSinkPlayback, AudioSource, AudioSourceBundle, Decodable,
-        GlobalVolume, Pitch, PitchBundle, PlaybackSettings, SpatialAudioSink, SpatialListener,
+        GlobalVolume, MicrophoneInput, MicrophonePlugin, MicrophoneSettings, Pitch, PitchBundle,
+        PlaybackSettings, SpatialAudioSink, SpatialListener,
     };
 }
 
 pub use audio::*;
 pub use audio_source::*;
+pub use microphone::*;
 pub use pitch::*;
 
 pub use rodio::cpal::Sample as CpalSample;
@@ -0,0 +1,348 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::{prelude::*, schedule::common_conditions::resource_changed};
+use bevy_reflect::prelude::*;
+use bevy_utils::tracing::{info, warn};
+use rodio::cpal::{
+    self,
+    traits::{DeviceTrait, HostTrait, StreamTrait},
+    Device, FromSample, SampleFormat, SampleRate, SizedSample, Stream, StreamConfig,
+    SupportedStreamConfig,
+};
+
+/// Adds support for capturing audio from a microphone to a Bevy Application.
+///
+/// Capture is configured by the [`MicrophoneSettings`] resource, and the captured samples are
+/// read from the [`MicrophoneInput`] resource.
+///
+/// This plugin is not part of the `DefaultPlugins`, since opening an input device may ask the
+/// user for permission on some platforms.
+#[derive(Default)]
+pub struct MicrophonePlugin {
+    /// The settings the microphone is first opened with.
+    pub settings: MicrophoneSettings,
+}
+
+impl Plugin for MicrophonePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<MicrophoneSettings>()
+            .insert_resource(self.settings.clone())
+            .init_resource::<MicrophoneInput>()
+            .init_non_send_resource::<MicrophoneStream>()
+            .add_systems(
+                PreUpdate,
+                update_microphone_stream.run_if(resource_changed::<MicrophoneSettings>),
+            );
+    }
+}
+
+/// Which input device is captured by the [`MicrophonePlugin`], and how.
+///
+/// Changing this resource reopens the input stream.
+#[derive(Resource, Clone, Debug, Reflect)]
+#[reflect(Resource, Default)]
+pub struct MicrophoneSettings {
+    /// Whether audio is captured. The input device is released while this is `false`.
+    pub enabled: bool,
+    /// The name of the input device to capture, as returned by [`available_input_devices`].
+    ///
+    /// The default input device of the system is used when this is `None`, or when no device
+    /// has this name.
+    pub device: Option<String>,
+    /// The sample rate to capture at, in Hz.
+    ///
+    /// The default sample rate of the device is used when this is `None`, or when the device
+    /// does not support this sample rate.
+    pub sample_rate: Option<u32>,
+    /// How much audio the [`MicrophoneInput`] keeps. Older samples are discarded.
+    pub buffer_duration: Duration,
+}
+
+impl Default for MicrophoneSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            device: None,
+            sample_rate: None,
+            buffer_duration: Duration::from_secs(1),
+        }
+    }
+}
+
+/// The most recent audio captured from the microphone, as mono samples between `-1.0` and `1.0`.
+///
+/// Samples are kept in a ring buffer holding [`MicrophoneSettings::buffer_duration`] of audio.
+/// Systems that only look at the current signal, like a level meter, can read the
+/// [`latest`](Self::latest) samples. Systems that need every sample, like a recorder, can keep a
+/// position and [`read_new`](Self::read_new) samples each frame.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_audio::MicrophoneInput;
+/// fn record(
+///     microphone: Res<MicrophoneInput>,
+///     mut position: Local<u64>,
+///     mut recording: Local<Vec<f32>>,
+/// ) {
+///     recording.extend(microphone.read_new(&mut position));
+/// }
+/// # bevy_ecs::system::assert_is_system(record);
+/// ```
+#[derive(Resource, Default)]
+pub struct MicrophoneInput {
+    buffer: Arc<Mutex<SampleRing>>,
+    device_name: Option<String>,
+    sample_rate: u32,
+}
+
+impl MicrophoneInput {
+    /// Whether an input device is currently being captured.
+    pub fn is_open(&self) -> bool {
+        self.device_name.is_some()
+    }
+
+    /// The name of the input device being captured.
+    pub fn device_name(&self) -> Option<&str> {
+        self.device_name.as_deref()
+    }
+
+    /// The number of samples captured per second, or `0` if no device is open.
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// The number of samples captured since the plugin was added.
+    ///
+    /// This is the position of the next sample to be captured, for use with
+    /// [`read_new`](Self::read_new).
+    pub fn total_samples(&self) -> u64 {
+        self.buffer.lock().unwrap().written
+    }
+
+    /// Returns up to `count` of the most recently captured samples, oldest first.
+    pub fn latest(&self, count: usize) -> Vec<f32> {
+        self.buffer.lock().unwrap().latest(count)
+    }
+
+    /// Returns the samples captured since `position`, oldest first, and moves `position` past
+    /// them.
+    ///
+    /// Samples that have already left the ring buffer are skipped, so this should be called at
+    /// least once every [`MicrophoneSettings::buffer_duration`] to avoid gaps.
+    pub fn read_new(&self, position: &mut u64) -> Vec<f32> {
+        self.buffer.lock().unwrap().read_new(position)
+    }
+}
+
+/// Fixed capacity buffer of mono samples, overwriting the oldest ones when full.
+#[derive(Default)]
+struct SampleRing {
+    samples: VecDeque<f32>,
+    capacity: usize,
+    /// The number of samples pushed since the ring was created.
+    written: u64,
+}
+
+impl SampleRing {
+    fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        let excess = self.samples.len().saturating_sub(capacity);
+        self.samples.drain(..excess);
+    }
+
+    fn push(&mut self, sample: f32) {
+        if self.capacity == 0 {
+            // Still counted, so positions of readers stay meaningful
+            self.written += 1;
+            return;
+        }
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+        self.written += 1;
+    }
+
+    fn latest(&self, count: usize) -> Vec<f32> {
+        let skip = self.samples.len().saturating_sub(count);
+        self.samples.iter().skip(skip).copied().collect()
+    }
+
+    fn read_new(&self, position: &mut u64) -> Vec<f32> {
+        let available = (self.written - (*position).min(self.written)) as usize;
+        let samples = self.latest(available);
+        *position = self.written;
+        samples
+    }
+}
+
+/// The input stream of the [`MicrophonePlugin`], kept alive while capturing.
+///
+/// This is a non-send resource, since [`Stream`] can't be sent across threads on every platform.
+#[derive(Default)]
+struct MicrophoneStream(Option<Stream>);
+
+/// Returns the names of the input devices that can be selected with
+/// [`MicrophoneSettings::device`].
+pub fn available_input_devices() -> Vec<String> {
+    match cpal::default_host().input_devices() {
+        Ok(devices) => devices.filter_map(|device| device.name().ok()).collect(),
+        Err(err) => {
+            warn!("Could not list the audio input devices: {err}");
+            Vec::new()
+        }
+    }
+}
+
+/// (Re)opens the input stream whenever the [`MicrophoneSettings`] change.
+fn update_microphone_stream(
+    settings: Res<MicrophoneSettings>,
+    mut input: ResMut<MicrophoneInput>,
+    mut stream: NonSendMut<MicrophoneStream>,
+) {
+    // Dropping the previous stream first, since some backends only allow a device to be opened once
+    stream.0 = None;
+    input.device_name = None;
+    input.sample_rate = 0;
+    if !settings.enabled {
+        return;
+    }
+
+    let Some(device) = find_input_device(settings.device.as_deref()) else {
+        warn!("No audio input device found.");
+        return;
+    };
+    let Some(config) = input_config(&device, settings.sample_rate) else {
+        return;
+    };
+    let sample_rate = config.sample_rate().0;
+    let capacity = (settings.buffer_duration.as_secs_f64() * sample_rate as f64) as usize;
+    input.buffer.lock().unwrap().set_capacity(capacity);
+
+    let buffer = input.buffer.clone();
+    let stream_config = config.config();
+    let new_stream = match config.sample_format() {
+        SampleFormat::F32 => build_input_stream::<f32>(&device, &stream_config, buffer),
+        SampleFormat::I16 => build_input_stream::<i16>(&device, &stream_config, buffer),
+        SampleFormat::U16 => build_input_stream::<u16>(&device, &stream_config, buffer),
+        SampleFormat::I32 => build_input_stream::<i32>(&device, &stream_config, buffer),
+        format => {
+            warn!("Unsupported audio input sample format: {format:?}");
+            return;
+        }
+    };
+    let new_stream = match new_stream {
+        Ok(new_stream) => new_stream,
+        Err(err) => {
+            warn!("Could not open the audio input stream: {err}");
+            return;
+        }
+    };
+    if let Err(err) = new_stream.play() {
+        warn!("Could not start the audio input stream: {err}");
+        return;
+    }
+
+    let device_name = device.name().unwrap_or_default();
+    info!("Capturing audio from \"{device_name}\" at {sample_rate} Hz");
+    input.device_name = Some(device_name);
+    input.sample_rate = sample_rate;
+    stream.0 = Some(new_stream);
+}
+
+fn find_input_device(name: Option<&str>) -> Option<Device> {
+    let host = cpal::default_host();
+    if let Some(name) = name {
+        let device = host.input_devices().ok().and_then(|mut devices| {
+            devices.find(|device| device.name().is_ok_and(|device_name| device_name == name))
+        });
+        if device.is_some() {
+            return device;
+        }
+        warn!("No audio input device named \"{name}\", using the default one.");
+    }
+    host.default_input_device()
+}
+
+fn input_config(device: &Device, sample_rate: Option<u32>) -> Option<SupportedStreamConfig> {
+    let requested = sample_rate.and_then(|rate| {
+        let config = device
+            .supported_input_configs()
+            .ok()?
+            .find(|range| range.min_sample_rate().0 <= rate && rate <= range.max_sample_rate().0);
+        if config.is_none() {
+            warn!("The audio input device does not support a sample rate of {rate} Hz.");
+        }
+        config.map(|range| range.with_sample_rate(SampleRate(rate)))
+    });
+    match requested {
+        Some(config) => Some(config),
+        None => device
+            .default_input_config()
+            .map_err(|err| warn!("Could not configure the audio input device: {err}"))
+            .ok(),
+    }
+}
+
+fn build_input_stream<T>(
+    device: &Device,
+    config: &StreamConfig,
+    buffer: Arc<Mutex<SampleRing>>,
+) -> Result<Stream, cpal::BuildStreamError>
+where
+    T: SizedSample,
+    f32: FromSample<T>,
+{
+    let channels = config.channels.max(1) as usize;
+    device.build_input_stream(
+        config,
+        move |data: &[T], _: &cpal::InputCallbackInfo| {
+            let mut buffer = buffer.lock().unwrap();
+            // Channels are mixed down to mono
+            for frame in data.chunks(channels) {
+                let sum: f32 = frame.iter().map(|&sample| f32::from_sample(sample)).sum();
+                buffer.push(sum / frame.len() as f32);
+            }
+        },
+        |err| warn!("Audio input stream error: {err}"),
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_ring() {
+        let mut ring = SampleRing::default();
+        ring.set_capacity(3);
+        for sample in [0.1, 0.2, 0.3, 0.4, 0.5] {
+            ring.push(sample);
+        }
+        assert_eq!(ring.latest(2), [0.4, 0.5]);
+        assert_eq!(ring.latest(10), [0.3, 0.4, 0.5]);
+
+        // Samples that already left the ring are skipped
+        let mut position = 0;
+        assert_eq!(ring.read_new(&mut position), [0.3, 0.4, 0.5]);
+        assert_eq!(position, 5);
+        ring.push(0.6);
+        assert_eq!(ring.read_new(&mut position), [0.6]);
+        assert!(ring.read_new(&mut position).is_empty());
+
+        ring.set_capacity(1);
+        assert_eq!(ring.latest(10), [0.6]);
+
+        // Without capacity, positions keep moving
+        ring.set_capacity(0);
+        ring.push(0.7);
+        assert!(ring.read_new(&mut position).is_empty());
+        assert_eq!(position, 7);
+    }
+}
@@ -0,0 +1,379 @@
+use bevy_ecs::{component::Component, reflect::ReflectComponent};
+use bevy_math::{
+    primitives::{Capsule3d, Direction3d},
+    Vec3,
+};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+
+use crate::{ShapeCast, ShapeHit};
+
+/// The maximum number of surfaces a single movement slides along.
+const MAX_SLIDES: usize = 4;
+
+/// Movements shorter than this are ignored.
+const MIN_MOVE_DISTANCE: f32 = 1e-4;
+
+/// Moves an entity through the world as a capsule, sliding along the surfaces it collides with.
+///
+/// Set [`translation`](Self::translation) to the desired movement of the frame, including
+/// gravity: the [`CharacterControllerPlugin`](crate::CharacterControllerPlugin) moves the
+/// entity's [`Transform`](bevy_transform::components::Transform) as far as possible along it,
+/// then resets it to zero.
+///
+/// The character climbs the steps lower than [`step_height`](Self::step_height) and the slopes
+/// gentler than [`max_slope`](Self::max_slope), and is stopped by the rest.
+///
+/// The entity is moved in world space, so it should not have a parent.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Default)]
+pub struct CharacterController {
+    /// The shape of the character, standing upright along the Y axis and centered on the entity.
+    pub shape: Capsule3d,
+    /// The movement to apply during the next update.
+    pub translation: Vec3,
+    /// The maximum height of the steps the character can climb.
+    pub step_height: f32,
+    /// The maximum slope the character can climb, in radians.
+    pub max_slope: f32,
+    /// The gap kept between the character and the surfaces it touches, so that it doesn't get
+    /// stuck in them because of numerical imprecision.
+    pub skin_width: f32,
+    /// Keeps the character on the ground when walking down slopes and steps, instead of
+    /// launching it off them.
+    pub snap_to_ground: bool,
+    grounded: bool,
+    ground_normal: Option<Vec3>,
+}
+
+impl Default for CharacterController {
+    fn default() -> Self {
+        Self {
+            shape: Capsule3d::new(0.4, 1.0),
+            translation: Vec3::ZERO,
+            step_height: 0.3,
+            max_slope: std::f32::consts::FRAC_PI_4,
+            skin_width: 0.02,
+            snap_to_ground: true,
+            grounded: false,
+            ground_normal: None,
+        }
+    }
+}
+
+/// The result of [`CharacterController::compute_movement`].
+#[derive(Clone, Debug, Default)]
+pub struct CharacterMovement {
+    /// The position of the character after the movement.
+    pub position: Vec3,
+    /// Whether the character is standing on walkable ground after the movement.
+    pub grounded: bool,
+    /// The normal of the ground the character is standing on.
+    pub ground_normal: Option<Vec3>,
+    /// The surfaces the character collided with during the movement.
+    pub collisions: Vec<ShapeHit>,
+}
+
+impl CharacterController {
+    /// Returns `true` if the character was standing on walkable ground after its last movement.
+    pub fn is_grounded(&self) -> bool {
+        self.grounded
+    }
+
+    /// Returns the normal of the ground the character was standing on after its last movement.
+    pub fn ground_normal(&self) -> Option<Vec3> {
+        self.ground_normal
+    }
+
+    /// Computes how the character moves from `position` along `translation`, using `cast` to
+    /// find the first collision of its shape.
+    ///
+    /// This doesn't modify the controller: use [`CharacterController::apply_movement`] to update
+    /// its grounded state.
+    pub fn compute_movement(
+        &self,
+        position: Vec3,
+        translation: Vec3,
+        mut cast: impl FnMut(&ShapeCast) -> Option<ShapeHit>,
+    ) -> CharacterMovement {
+        let mut movement = CharacterMovement {
+            position,
+            ..Default::default()
+        };
+        let vertical = Vec3::Y * translation.y;
+        let horizontal = translation - vertical;
+
+        self.slide(&mut movement, horizontal, true, &mut cast);
+        self.slide(&mut movement, vertical, false, &mut cast);
+
+        // Find the ground, and stick to it if the character was walking on it.
+        let snap_distance = if self.grounded && self.snap_to_ground && translation.y <= 0.0 {
+            self.step_height
+        } else {
+            0.0
+        };
+        let ground = self
+            .cast(
+                &mut cast,
+                movement.position,
+                Direction3d::NEG_Y,
+                snap_distance + 2.0 * self.skin_width,
+            )
+            .filter(|hit| self.is_walkable(hit.normal) && translation.y <= 0.0);
+        if let Some(hit) = ground {
+            movement.position.y -= (hit.distance - self.skin_width).max(0.0);
+            movement.grounded = true;
+            movement.ground_normal = Some(hit.normal);
+        }
+        movement
+    }
+
+    /// Updates the grounded state of the controller from the result of
+    /// [`CharacterController::compute_movement`].
+    pub fn apply_movement(&mut self, movement: &CharacterMovement) {
+        self.grounded = movement.grounded;
+        self.ground_normal = movement.ground_normal;
+    }
+
+    fn is_walkable(&self, normal: Vec3) -> bool {
+        normal.y >= self.max_slope.cos()
+    }
+
+    fn cast(
+        &self,
+        cast: &mut impl FnMut(&ShapeCast) -> Option<ShapeHit>,
+        origin: Vec3,
+        direction: Direction3d,
+        max_distance: f32,
+    ) -> Option<ShapeHit> {
+        cast(&ShapeCast {
+            shape: self.shape,
+            origin,
+            direction,
+            max_distance,
+        })
+    }
+
+    /// Moves the character along `translation`, sliding along the surfaces it hits.
+    ///
+    /// When `horizontal`, steep slopes are treated as walls so that the character doesn't climb
+    /// them, and the character tries to climb over the steps it hits.
+    fn slide(
+        &self,
+        movement: &mut CharacterMovement,
+        translation: Vec3,
+        horizontal: bool,
+        cast: &mut impl FnMut(&ShapeCast) -> Option<ShapeHit>,
+    ) {
+        let mut remaining = translation;
+        for _ in 0..MAX_SLIDES {
+            let Ok((direction, distance)) = Direction3d::new_and_length(remaining) else {
+                return;
+            };
+            if distance < MIN_MOVE_DISTANCE {
+                return;
+            }
+            let Some(hit) = self.cast(
+                cast,
+                movement.position,
+                direction,
+                distance + self.skin_width,
+            ) else {
+                movement.position += remaining;
+                return;
+            };
+
+            let travel = (hit.distance - self.skin_width).clamp(0.0, distance);
+            movement.position += *direction * travel;
+            remaining -= *direction * travel;
+            movement.collisions.push(hit);
+
+            let mut normal = hit.normal;
+            if horizontal && !self.is_walkable(normal) {
+                if self.grounded {
+                    if let Some((position, rest)) = self.step(movement.position, remaining, cast) {
+                        movement.position = position;
+                        remaining = rest;
+                        continue;
+                    }
+                }
+                normal = Vec3::new(normal.x, 0.0, normal.z).normalize_or_zero();
+            }
+            remaining -= normal * remaining.dot(normal);
+        }
+    }
+
+    /// Tries to climb over a step in front of the character, moving it up, forward along
+    /// `translation` and back down onto the step.
+    ///
+    /// Returns the position on the step and the part of `translation` that remains, or `None`
+    /// if the character can't climb the step.
+    fn step(
+        &self,
+        position: Vec3,
+        translation: Vec3,
+        cast: &mut impl FnMut(&ShapeCast) -> Option<ShapeHit>,
+    ) -> Option<(Vec3, Vec3)> {
+        let up = self
+            .cast(
+                cast,
+                position,
+                Direction3d::Y,
+                self.step_height + self.skin_width,
+            )
+            .map_or(self.step_height, |hit| {
+                (hit.distance - self.skin_width).max(0.0)
+            });
+        if up < MIN_MOVE_DISTANCE {
+            return None;
+        }
+        let raised = position + Vec3::Y * up;
+
+        let (direction, distance) = Direction3d::new_and_length(translation).ok()?;
+        let forward = self
+            .cast(cast, raised, direction, distance + self.skin_width)
+            .map_or(distance, |hit| {
+                (hit.distance - self.skin_width).clamp(0.0, distance)
+            });
+        if forward < MIN_MOVE_DISTANCE {
+            return None;
+        }
+        let moved = raised + *direction * forward;
+
+        let down = self.cast(cast, moved, Direction3d::NEG_Y, up + self.skin_width)?;
+        if !self.is_walkable(down.normal) {
+            return None;
+        }
+        let landed = moved - Vec3::Y * (down.distance - self.skin_width).max(0.0);
+        Some((landed, translation - *direction * forward))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::entity::Entity;
+    use bevy_math::bounding::{Aabb3d, RayCast3d};
+
+    /// Casts the character as a box against `boxes`, by casting its center against the boxes
+    /// grown by its size.
+    fn box_cast(boxes: &[Aabb3d]) -> impl FnMut(&ShapeCast) -> Option<ShapeHit> + '_ {
+        move |cast| {
+            let half_size = Vec3::new(
+                cast.shape.radius,
+                cast.shape.radius + cast.shape.half_length,
+                cast.shape.radius,
+            );
+            let ray = RayCast3d::new(cast.origin, cast.direction, cast.max_distance);
+            boxes
+                .iter()
+                .enumerate()
+                .filter_map(|(index, aabb)| {
+                    let grown = Aabb3d {
+                        min: aabb.min - half_size,
+                        max: aabb.max + half_size,
+                    };
+                    let distance = ray.aabb_intersection_at(&grown)?;
+                    let point = cast.origin + *cast.direction * distance;
+                    let local = (point - (grown.min + grown.max) / 2.0) / (grown.max - grown.min);
+                    let axis = local.abs().max_element();
+                    let normal = Vec3::select(
+                        local.abs().cmpeq(Vec3::splat(axis)),
+                        local.signum(),
+                        Vec3::ZERO,
+                    );
+                    Some(ShapeHit {
+                        entity: Entity::from_raw(index as u32),
+                        distance,
+                        point,
+                        normal,
+                    })
+                })
+                .min_by(|a, b| a.distance.total_cmp(&b.distance))
+        }
+    }
+
+    fn ground() -> Aabb3d {
+        Aabb3d {
+            min: Vec3::new(-10., -1., -10.),
+            max: Vec3::new(10., 0., 10.),
+        }
+    }
+
+    /// Returns a controller standing on the ground at the origin.
+    fn standing(boxes: &[Aabb3d]) -> (CharacterController, Vec3) {
+        let mut controller = CharacterController::default();
+        let movement = controller.compute_movement(
+            Vec3::new(0., 1.5, 0.),
+            Vec3::new(0., -1., 0.),
+            box_cast(boxes),
+        );
+        controller.apply_movement(&movement);
+        assert!(controller.is_grounded());
+        assert_eq!(controller.ground_normal(), Some(Vec3::Y));
+        (controller, movement.position)
+    }
+
+    #[test]
+    fn slide_along_walls() {
+        let boxes = [
+            ground(),
+            Aabb3d {
+                min: Vec3::new(1., 0., -10.),
+                max: Vec3::new(2., 5., 10.),
+            },
+        ];
+        let (controller, position) = standing(&boxes);
+        assert!((position.y - 0.92).abs() < 1e-4);
+
+        let movement =
+            controller.compute_movement(position, Vec3::new(2., 0., 1.), box_cast(&boxes));
+        assert!(movement.grounded);
+        // The skin is kept along the direction of the movement, before sliding along the wall.
+        let x = 0.6 - 0.02 * 2. / 5f32.sqrt();
+        assert!(movement.position.abs_diff_eq(Vec3::new(x, 0.92, 1.), 1e-4));
+        assert_eq!(movement.collisions.len(), 1);
+        assert_eq!(movement.collisions[0].normal, Vec3::NEG_X);
+
+        // Jumping leaves the ground.
+        let movement =
+            controller.compute_movement(position, Vec3::new(0., 0.5, 0.), box_cast(&boxes));
+        assert!(!movement.grounded);
+        assert!(movement.position.abs_diff_eq(Vec3::new(0., 1.42, 0.), 1e-4));
+    }
+
+    #[test]
+    fn climb_steps() {
+        let step = |height: f32| {
+            [
+                ground(),
+                Aabb3d {
+                    min: Vec3::new(1., 0., -10.),
+                    max: Vec3::new(5., height, 10.),
+                },
+            ]
+        };
+
+        let boxes = step(0.2);
+        let (controller, position) = standing(&boxes);
+        let movement =
+            controller.compute_movement(position, Vec3::new(2., 0., 0.), box_cast(&boxes));
+        assert!(movement.grounded);
+        assert!(movement.position.abs_diff_eq(Vec3::new(2., 1.12, 0.), 1e-4));
+
+        // Walking off the step snaps back to the ground.
+        let mut controller = controller;
+        controller.apply_movement(&movement);
+        let movement =
+            controller.compute_movement(movement.position, Vec3::new(4., 0., 0.), box_cast(&boxes));
+        assert!(movement.grounded);
+        assert!(movement.position.abs_diff_eq(Vec3::new(6., 0.92, 0.), 1e-4));
+
+        let boxes = step(0.5);
+        let (controller, position) = standing(&boxes);
+        let movement =
+            controller.compute_movement(position, Vec3::new(2., 0., 0.), box_cast(&boxes));
+        assert!(movement
+            .position
+            .abs_diff_eq(Vec3::new(0.58, 0.92, 0.), 1e-4));
+    }
+}
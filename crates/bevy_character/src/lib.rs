@@ -0,0 +1,93 @@
+//! Kinematic character controllers for Bevy.
+//!
+//! A [`CharacterController`] moves an entity through the world as a capsule, sliding along the
+//! surfaces it collides with, climbing steps and gentle slopes, and keeping track of whether it
+//! stands on the ground. Characters are moved kinematically: they aren't pushed by anything, and
+//! only move by the [`translation`](CharacterController::translation) they are given.
+//!
+//! Collisions are found by a [`CharacterShapeCaster`], so that characters can be used with any
+//! physics engine. The [`RaycastShapeCaster`] collides with meshes using the ray casting of
+//! [`bevy_render::mesh::Raycast`].
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_character::CharacterController;
+//! # use bevy_math::Vec3;
+//! # #[derive(Resource)]
+//! # struct Input { direction: Vec3, jump: bool }
+//! # #[derive(Component)]
+//! # struct VerticalSpeed(f32);
+//! fn move_player(
+//!     input: Res<Input>,
+//!     mut players: Query<(&mut CharacterController, &mut VerticalSpeed)>,
+//! ) {
+//!     for (mut controller, mut vertical_speed) in &mut players {
+//!         if controller.is_grounded() {
+//!             vertical_speed.0 = if input.jump { 5.0 } else { 0.0 };
+//!         }
+//!         vertical_speed.0 -= 9.81 / 60.0;
+//!         controller.translation = (input.direction * 4.0 + Vec3::Y * vertical_speed.0) / 60.0;
+//!     }
+//! }
+//! # bevy_ecs::system::assert_is_system(move_player);
+//! ```
+
+mod controller;
+mod shape_cast;
+
+pub use controller::*;
+pub use shape_cast::*;
+
+/// The `bevy_character` prelude.
+pub mod prelude {
+    #[doc(hidden)]
+    pub use crate::{CharacterController, CharacterControllerPlugin};
+}
+
+use std::marker::PhantomData;
+
+use bevy_app::prelude::*;
+use bevy_ecs::{prelude::*, system::StaticSystemParam};
+use bevy_transform::{components::Transform, TransformSystem};
+
+/// Moves the entities with a [`CharacterController`], finding their collisions with `C`.
+pub struct CharacterControllerPlugin<C: CharacterShapeCaster = RaycastShapeCaster>(PhantomData<C>);
+
+impl<C: CharacterShapeCaster> Default for CharacterControllerPlugin<C> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<C: CharacterShapeCaster> Plugin for CharacterControllerPlugin<C> {
+    fn build(&self, app: &mut App) {
+        app.register_type::<CharacterController>().add_systems(
+            PostUpdate,
+            move_characters::<C>
+                .in_set(CharacterControllerSystem)
+                .before(TransformSystem::TransformPropagate),
+        );
+    }
+}
+
+/// Label for the system moving the [`CharacterController`]s.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
+pub struct CharacterControllerSystem;
+
+/// Moves each [`CharacterController`] along its [`translation`](CharacterController::translation),
+/// and resets it.
+pub fn move_characters<C: CharacterShapeCaster>(
+    caster: StaticSystemParam<C::Param>,
+    mut characters: Query<(Entity, &mut CharacterController, &mut Transform)>,
+) {
+    for (entity, mut controller, mut transform) in &mut characters {
+        let translation = std::mem::take(&mut controller.translation);
+        let movement = controller.compute_movement(transform.translation, translation, |cast| {
+            C::cast_shape(&*caster, entity, cast)
+        });
+        controller.apply_movement(&movement);
+        if transform.translation != movement.position {
+            transform.translation = movement.position;
+        }
+    }
+}
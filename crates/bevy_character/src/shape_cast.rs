@@ -0,0 +1,132 @@
+use bevy_ecs::{
+    entity::Entity,
+    system::{Query, SystemParam, SystemParamItem},
+};
+use bevy_hierarchy::{HierarchyQueryExt, Parent};
+use bevy_math::{
+    bounding::RayCast3d,
+    primitives::{Capsule3d, Direction3d},
+    Vec3,
+};
+use bevy_render::mesh::Raycast;
+
+/// A capsule moved through the world to find what it collides with.
+#[derive(Clone, Copy, Debug)]
+pub struct ShapeCast {
+    /// The shape of the character, standing upright along the Y axis.
+    pub shape: Capsule3d,
+    /// The position of the center of the shape at the start of the cast.
+    pub origin: Vec3,
+    /// The direction the shape is moved in.
+    pub direction: Direction3d,
+    /// How far the shape is moved.
+    pub max_distance: f32,
+}
+
+/// The first collision of a [`ShapeCast`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ShapeHit {
+    /// The entity that was hit.
+    pub entity: Entity,
+    /// How far the shape moved before touching the entity.
+    pub distance: f32,
+    /// The point of contact, in world space.
+    pub point: Vec3,
+    /// The normal of the surface that was hit, facing the shape.
+    pub normal: Vec3,
+}
+
+/// Finds the collisions of [`CharacterController`](crate::CharacterController)s with the world.
+///
+/// Implement this trait to move characters against the colliders of a physics engine, using
+/// its shape casts. The [`RaycastShapeCaster`] is used by default.
+pub trait CharacterShapeCaster: Send + Sync + 'static {
+    /// The system parameter used to cast shapes, such as the query pipeline of a physics engine.
+    type Param: SystemParam + 'static;
+
+    /// Returns the first collision of `cast`, ignoring the `character` itself.
+    fn cast_shape(
+        param: &SystemParamItem<Self::Param>,
+        character: Entity,
+        cast: &ShapeCast,
+    ) -> Option<ShapeHit>;
+}
+
+/// A [`CharacterShapeCaster`] colliding with meshes and
+/// [`RaycastShape`](bevy_render::mesh::RaycastShape)s, using the [`Raycast`] system parameter.
+///
+/// The capsule is approximated by casting rays from points of its surface facing the direction
+/// of the cast, so geometry thinner than the gaps between these rays can be missed.
+///
+/// The character and its descendants are never hit, so that it can have its own mesh.
+pub struct RaycastShapeCaster;
+
+impl CharacterShapeCaster for RaycastShapeCaster {
+    type Param = (
+        Raycast<'static, 'static>,
+        Query<'static, 'static, &'static Parent>,
+    );
+
+    fn cast_shape(
+        (raycast, parents): &SystemParamItem<Self::Param>,
+        character: Entity,
+        cast: &ShapeCast,
+    ) -> Option<ShapeHit> {
+        let filter = |entity| {
+            entity != character
+                && !parents
+                    .iter_ancestors(entity)
+                    .any(|ancestor| ancestor == character)
+        };
+
+        let direction = *cast.direction;
+        let (u, v) = direction.any_orthonormal_pair();
+        let mut closest: Option<ShapeHit> = None;
+        for (a, b) in SAMPLES {
+            // The points of the capsule furthest along `normal` are the first to touch a
+            // surface with that normal.
+            let normal = (direction + u * a + v * b).normalize();
+            let heights: &[f32] = if normal.y.abs() < 1e-3 {
+                &[-1.0, 0.0, 1.0]
+            } else {
+                &[normal.y.signum()]
+            };
+            for &height in heights {
+                let origin = cast.origin
+                    + Vec3::Y * height * cast.shape.half_length
+                    + normal * cast.shape.radius;
+                let max_distance = closest.map_or(cast.max_distance, |hit| hit.distance);
+                let ray = RayCast3d::new(origin, cast.direction, max_distance);
+                let Some(hit) = raycast.cast_ray_closest(&ray, filter) else {
+                    continue;
+                };
+                // Mesh normals face the front of the triangles, which may face away from the shape.
+                let hit_normal = if hit.normal.dot(direction) > 0.0 {
+                    -hit.normal
+                } else {
+                    hit.normal
+                };
+                closest = Some(ShapeHit {
+                    entity: hit.entity,
+                    distance: hit.distance,
+                    point: hit.point,
+                    normal: hit_normal,
+                });
+            }
+        }
+        closest
+    }
+}
+
+/// The offsets of the sampled normals of the capsule, perpendicular to the direction of the cast.
+const SAMPLES: [(f32, f32); 9] = [
+    (0.0, 0.0),
+    (1.5, 0.0),
+    (-1.5, 0.0),
+    (0.0, 1.5),
+    (0.0, -1.5),
+    (1.0, 1.0),
+    (1.0, -1.0),
+    (-1.0, 1.0),
+    (-1.0, -1.0),
+];
@@ -21,8 +21,10 @@ pub mod prelude {
 use bevy_app::prelude::*;
 use bevy_ecs::component::{ComponentId, ComponentTicks, Tick};
 use bevy_ecs::prelude::*;
-use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+use bevy_math::{Vec2, Vec3, Vec4};
+use bevy_reflect::{std_traits::ReflectValueFormat, Reflect, ReflectDeserialize, ReflectSerialize};
 use bevy_utils::{Duration, HashSet, Instant, Uuid};
+use std::any::TypeId;
 use std::borrow::Cow;
 use std::ffi::OsString;
 use std::marker::PhantomData;
@@ -44,6 +46,7 @@ impl Plugin for TypeRegistrationPlugin {
         register_ecs_types(app);
         register_rust_types(app);
         register_math_types(app);
+        register_value_formats(app);
     }
 }
 
@@ -110,6 +113,62 @@ fn register_math_types(app: &mut App) {
         .register_type::<bevy_math::Rect>();
 }
 
+/// Registers [`ReflectValueFormat`] for the types most often edited as text, such as through
+/// [`World::set_component_value_from_str`].
+fn register_value_formats(app: &mut App) {
+    app.register_type_data::<bool, ReflectValueFormat>()
+        .register_type_data::<u8, ReflectValueFormat>()
+        .register_type_data::<u16, ReflectValueFormat>()
+        .register_type_data::<u32, ReflectValueFormat>()
+        .register_type_data::<u64, ReflectValueFormat>()
+        .register_type_data::<usize, ReflectValueFormat>()
+        .register_type_data::<i8, ReflectValueFormat>()
+        .register_type_data::<i16, ReflectValueFormat>()
+        .register_type_data::<i32, ReflectValueFormat>()
+        .register_type_data::<i64, ReflectValueFormat>()
+        .register_type_data::<isize, ReflectValueFormat>()
+        .register_type_data::<f32, ReflectValueFormat>()
+        .register_type_data::<f64, ReflectValueFormat>()
+        .register_type_data::<String, ReflectValueFormat>();
+
+    // Vectors are written as their comma separated components, like `1, 2.5, 0`.
+    let registry = app.world.resource::<AppTypeRegistry>().clone();
+    let mut registry = registry.write();
+    for (type_id, format) in [
+        (TypeId::of::<Vec2>(), vector_format::<Vec2, 2>()),
+        (TypeId::of::<Vec3>(), vector_format::<Vec3, 3>()),
+        (TypeId::of::<Vec4>(), vector_format::<Vec4, 4>()),
+    ] {
+        if let Some(registration) = registry.get_mut(type_id) {
+            registration.insert(format);
+        }
+    }
+}
+
+fn vector_format<V, const N: usize>() -> ReflectValueFormat
+where
+    V: Reflect + Copy + From<[f32; N]> + Into<[f32; N]>,
+{
+    ReflectValueFormat::new(
+        |text| {
+            let mut components = [0.; N];
+            let mut parts = text.split(',');
+            for component in &mut components {
+                *component = parts.next()?.trim().parse().ok()?;
+            }
+            if parts.next().is_some() {
+                return None;
+            }
+            Some(Box::new(V::from(components)))
+        },
+        |value| {
+            let components: [f32; N] = (*value.downcast_ref::<V>()?).into();
+            let components: Vec<_> = components.iter().map(ToString::to_string).collect();
+            Some(components.join(", "))
+        },
+    )
+}
+
 /// Setup of default task pools: [`AsyncComputeTaskPool`](bevy_tasks::AsyncComputeTaskPool),
 /// [`ComputeTaskPool`](bevy_tasks::ComputeTaskPool), [`IoTaskPool`](bevy_tasks::IoTaskPool).
 #[derive(Default)]
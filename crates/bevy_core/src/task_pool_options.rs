@@ -1,4 +1,7 @@
-use bevy_tasks::{AsyncComputeTaskPool, ComputeTaskPool, IoTaskPool, TaskPoolBuilder};
+use bevy_tasks::{
+    AsyncComputeTaskPool, ComputeTaskPool, CoreAffinity, IoTaskPool, TaskPoolBuilder,
+    ThreadPriority,
+};
 use bevy_utils::tracing::trace;
 
 /// Defines a simple way to determine how many threads to use given the number of remaining cores
@@ -12,6 +15,11 @@ pub struct TaskPoolThreadAssignmentPolicy {
     /// Target using this percentage of total cores, clamped by min_threads and max_threads. It is
     /// permitted to use 1.0 to try to use all remaining threads
     pub percent: f32,
+    /// Scheduling priority of the threads of this pool, for example to keep a compute-heavy pool
+    /// from starving the others
+    pub priority: ThreadPriority,
+    /// Cores the threads of this pool may run on
+    pub core_affinity: CoreAffinity,
 }
 
 impl TaskPoolThreadAssignmentPolicy {
@@ -61,6 +69,8 @@ impl Default for TaskPoolOptions {
                 min_threads: 1,
                 max_threads: 4,
                 percent: 0.25,
+                priority: ThreadPriority::Normal,
+                core_affinity: CoreAffinity::Any,
             },
 
             // Use 25% of cores for async compute, at least 1, no more than 4
@@ -68,6 +78,8 @@ impl Default for TaskPoolOptions {
                 min_threads: 1,
                 max_threads: 4,
                 percent: 0.25,
+                priority: ThreadPriority::Normal,
+                core_affinity: CoreAffinity::Any,
             },
 
             // Use all remaining cores for compute (at least 1)
@@ -75,6 +87,8 @@ impl Default for TaskPoolOptions {
                 min_threads: 1,
                 max_threads: usize::MAX,
                 percent: 1.0, // This 1.0 here means "whatever is left over"
+                priority: ThreadPriority::Normal,
+                core_affinity: CoreAffinity::Any,
             },
         }
     }
@@ -111,6 +125,8 @@ impl TaskPoolOptions {
                 TaskPoolBuilder::default()
                     .num_threads(io_threads)
                     .thread_name("IO Task Pool".to_string())
+                    .priority(self.io.priority)
+                    .core_affinity(self.io.core_affinity.clone())
                     .build()
             });
         }
@@ -128,6 +144,8 @@ impl TaskPoolOptions {
                 TaskPoolBuilder::default()
                     .num_threads(async_compute_threads)
                     .thread_name("Async Compute Task Pool".to_string())
+                    .priority(self.async_compute.priority)
+                    .core_affinity(self.async_compute.core_affinity.clone())
                     .build()
             });
         }
@@ -145,6 +163,8 @@ impl TaskPoolOptions {
                 TaskPoolBuilder::default()
                     .num_threads(compute_threads)
                     .thread_name("Compute Task Pool".to_string())
+                    .priority(self.compute.priority)
+                    .core_affinity(self.compute.core_affinity.clone())
                     .build()
             });
         }
@@ -3,7 +3,36 @@
 @group(0) @binding(0) var in_texture: texture_2d<f32>;
 @group(0) @binding(1) var in_sampler: sampler;
 
+#ifdef OUTPUT_HDR10
+// Converts linear Rec. 709 primaries to linear Rec. 2020 primaries
+const REC709_TO_REC2020 = mat3x3<f32>(
+    vec3(0.6274, 0.0691, 0.0164),
+    vec3(0.3293, 0.9195, 0.0880),
+    vec3(0.0433, 0.0113, 0.8956),
+);
+
+// SMPTE ST 2084 (PQ) encoding, where 1.0 is 10000 nits
+fn pq_encode(linear: vec3<f32>) -> vec3<f32> {
+    let m1 = 0.1593017578125;
+    let m2 = 78.84375;
+    let c1 = 0.8359375;
+    let c2 = 18.8515625;
+    let c3 = 18.6875;
+    let y = pow(max(linear, vec3(0.0)), vec3(m1));
+    return pow((c1 + c2 * y) / (1.0 + c3 * y), vec3(m2));
+}
+#endif
+
 @fragment
 fn fs_main(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
-    return textureSample(in_texture, in_sampler, in.uv);
+    let color = textureSample(in_texture, in_sampler, in.uv);
+#ifdef OUTPUT_SCRGB
+    // scRGB has Rec. 709 primaries, with 1.0 being 80 nits
+    return vec4(color.rgb * (f32(#{PAPER_WHITE_NITS}u) / 80.0), color.a);
+#else ifdef OUTPUT_HDR10
+    let nits = REC709_TO_REC2020 * color.rgb * f32(#{PAPER_WHITE_NITS}u);
+    return vec4(pq_encode(nits / 10000.0), color.a);
+#else
+    return color;
+#endif
 }
@@ -9,6 +9,7 @@ use bevy_render::{
     renderer::RenderDevice,
     RenderApp,
 };
+use bevy_window::HdrOutput;
 
 use crate::fullscreen_vertex_shader::fullscreen_shader_vertex_state;
 
@@ -72,19 +73,38 @@ pub struct BlitPipelineKey {
     pub texture_format: TextureFormat,
     pub blend_state: Option<BlendState>,
     pub samples: u32,
+    /// How the output is encoded for an HDR display. For [`HdrOutput::Sdr`] the texture is
+    /// copied as is.
+    pub hdr_output: HdrOutput,
+    /// The luminance, in nits, of a color value of `1.0` when [`Self::hdr_output`] isn't
+    /// [`HdrOutput::Sdr`].
+    pub paper_white_nits: u32,
 }
 
 impl SpecializedRenderPipeline for BlitPipeline {
     type Key = BlitPipelineKey;
 
     fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let mut shader_defs = Vec::new();
+        match key.hdr_output {
+            HdrOutput::Sdr => {}
+            HdrOutput::ScRgb => shader_defs.push("OUTPUT_SCRGB".into()),
+            HdrOutput::Hdr10 => shader_defs.push("OUTPUT_HDR10".into()),
+        }
+        if key.hdr_output != HdrOutput::Sdr {
+            shader_defs.push(ShaderDefVal::UInt(
+                "PAPER_WHITE_NITS".into(),
+                key.paper_white_nits,
+            ));
+        }
+
         RenderPipelineDescriptor {
             label: Some("blit pipeline".into()),
             layout: vec![self.texture_bind_group.clone()],
             vertex: fullscreen_shader_vertex_state(),
             fragment: Some(FragmentState {
                 shader: BLIT_SHADER_HANDLE,
-                shader_defs: vec![],
+                shader_defs,
                 entry_point: "fs_main".into(),
                 targets: vec![Some(ColorTargetState {
                     format: key.texture_format,
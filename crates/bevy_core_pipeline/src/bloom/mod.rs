@@ -18,10 +18,12 @@ use bevy_render::{
         ComponentUniforms, DynamicUniformIndex, ExtractComponentPlugin, UniformComponentPlugin,
     },
     prelude::Color,
-    render_graph::{NodeRunError, RenderGraphApp, RenderGraphContext, ViewNode, ViewNodeRunner},
+    render_graph::{
+        NodeRunError, RenderGraphApp, RenderGraphContext, RenderSubGraph, ViewNode, ViewNodeRunner,
+    },
     render_resource::*,
     renderer::{RenderContext, RenderDevice},
-    texture::{CachedTexture, TextureCache},
+    texture::{CachedTexture, TextureCache, TextureLifetime},
     view::ViewTarget,
     Render, RenderApp, RenderSet,
 };
@@ -337,6 +339,14 @@ fn prepare_bloom_textures(
             let mip_count = MAX_MIP_DIMENSION.ilog2().max(2) - 1;
             let mip_height_ratio = MAX_MIP_DIMENSION as f32 / height as f32;
 
+            // The bloom texture is only used by the bloom node, so it can share its memory with
+            // the transient textures of the other passes of the view.
+            let lifetime = if camera.render_graph == SubGraph2d.intern() {
+                TextureLifetime::node(entity, SubGraph2d, Labels2d::Bloom)
+            } else {
+                TextureLifetime::node(entity, SubGraph3d, Labels3d::Bloom)
+            };
+
             let texture_descriptor = TextureDescriptor {
                 label: Some("bloom_texture"),
                 size: Extent3d {
@@ -357,11 +367,11 @@ fn prepare_bloom_textures(
                 not(target_arch = "wasm32"),
                 feature = "webgpu"
             ))]
-            let texture = texture_cache.get(&render_device, texture_descriptor);
+            let texture = texture_cache.get_transient(&render_device, texture_descriptor, lifetime);
             #[cfg(all(feature = "webgl", target_arch = "wasm32", not(feature = "webgpu")))]
             let texture: Vec<CachedTexture> = (0..mip_count)
                 .map(|mip| {
-                    texture_cache.get(
+                    texture_cache.get_transient(
                         &render_device,
                         TextureDescriptor {
                             size: Extent3d {
@@ -372,6 +382,7 @@ fn prepare_bloom_textures(
                             mip_level_count: 1,
                             ..texture_descriptor.clone()
                         },
+                        lifetime,
                     )
                 })
                 .collect();
@@ -1,11 +1,14 @@
-use crate::tonemapping::{DebandDither, Tonemapping};
+use crate::{
+    core_3d::{CORE_3D_DEPTH_FORMAT, CORE_3D_DEPTH_STENCIL_FORMAT},
+    tonemapping::{DebandDither, Tonemapping},
+};
 use bevy_ecs::prelude::*;
 use bevy_reflect::{Reflect, ReflectDeserialize, ReflectSerialize};
 use bevy_render::{
     camera::{Camera, CameraMainTextureUsages, CameraRenderGraph, Projection},
     extract_component::ExtractComponent,
     primitives::Frustum,
-    render_resource::{LoadOp, TextureUsages},
+    render_resource::{LoadOp, TextureFormat, TextureUsages},
     view::{ColorGrading, VisibleEntities},
 };
 use bevy_transform::prelude::{GlobalTransform, Transform};
@@ -22,6 +25,15 @@ pub struct Camera3d {
     pub depth_load_op: Camera3dDepthLoadOp,
     /// The texture usages for the depth texture created for the main 3d pass.
     pub depth_texture_usages: Camera3dDepthTextureUsage,
+    /// The stencil buffer of the depth texture of the main 3d pass, if any.
+    ///
+    /// With a stencil buffer, the depth texture has the [`CORE_3D_DEPTH_STENCIL_FORMAT`] format,
+    /// which is supported on every platform, and the pipelines of the main 3d passes are
+    /// specialized for it. Materials can then test and write the stencil buffer by setting the
+    /// `stencil` of the `depth_stencil` state in their `specialize` function.
+    ///
+    /// Defaults to `None`.
+    pub stencil: Option<Camera3dStencil>,
     /// How many individual steps should be performed in the [`Transmissive3d`](crate::core_3d::Transmissive3d) pass.
     ///
     /// Roughly corresponds to how many “layers of transparency” are rendered for screen space
@@ -54,12 +66,24 @@ impl Default for Camera3d {
         Self {
             depth_load_op: Default::default(),
             depth_texture_usages: TextureUsages::RENDER_ATTACHMENT.into(),
+            stencil: None,
             screen_space_specular_transmission_steps: 1,
             screen_space_specular_transmission_quality: Default::default(),
         }
     }
 }
 
+impl Camera3d {
+    /// The format of the depth texture of the main 3d pass.
+    pub fn depth_texture_format(&self) -> TextureFormat {
+        if self.stencil.is_some() {
+            CORE_3D_DEPTH_STENCIL_FORMAT
+        } else {
+            CORE_3D_DEPTH_FORMAT
+        }
+    }
+}
+
 #[derive(Clone, Copy, Reflect, Serialize, Deserialize)]
 #[reflect(Serialize, Deserialize)]
 pub struct Camera3dDepthTextureUsage(u32);
@@ -101,6 +125,45 @@ impl From<Camera3dDepthLoadOp> for LoadOp<f32> {
     }
 }
 
+/// The stencil buffer of the main 3d pass, see [`Camera3d::stencil`].
+#[derive(Reflect, Serialize, Deserialize, Clone, Debug, Default)]
+#[reflect(Serialize, Deserialize)]
+pub struct Camera3dStencil {
+    /// The stencil clear operation to perform for the main 3d pass.
+    pub load_op: Camera3dStencilLoadOp,
+    /// The reference value that the pipelines of the main 3d passes compare the stencil buffer
+    /// against, and write to it with [`StencilOperation::Replace`](bevy_render::render_resource::StencilOperation::Replace).
+    ///
+    /// Pipelines can still use different values by masking it with the `read_mask` and
+    /// `write_mask` of their stencil state.
+    pub reference: u32,
+}
+
+/// The stencil clear operation to perform for the main 3d pass.
+#[derive(Reflect, Serialize, Deserialize, Clone, Copy, Debug)]
+#[reflect(Serialize, Deserialize)]
+pub enum Camera3dStencilLoadOp {
+    /// Clear with a specified value.
+    Clear(u32),
+    /// Load from memory.
+    Load,
+}
+
+impl Default for Camera3dStencilLoadOp {
+    fn default() -> Self {
+        Camera3dStencilLoadOp::Clear(0)
+    }
+}
+
+impl From<Camera3dStencilLoadOp> for LoadOp<u32> {
+    fn from(config: Camera3dStencilLoadOp) -> Self {
+        match config {
+            Camera3dStencilLoadOp::Clear(x) => LoadOp::Clear(x),
+            Camera3dStencilLoadOp::Load => LoadOp::Load,
+        }
+    }
+}
+
 /// The quality of the screen space transmission blur effect, applied to whatever's “behind” transmissive
 /// objects when their `roughness` is greater than `0.0`.
 ///
@@ -167,3 +230,37 @@ impl Default for Camera3dBundle {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stencil_uses_depth_stencil_format() {
+        let mut camera_3d = Camera3d::default();
+        assert_eq!(camera_3d.depth_texture_format(), CORE_3D_DEPTH_FORMAT);
+
+        camera_3d.stencil = Some(Camera3dStencil::default());
+        assert_eq!(
+            camera_3d.depth_texture_format(),
+            CORE_3D_DEPTH_STENCIL_FORMAT
+        );
+        assert!(camera_3d.depth_texture_format().has_stencil_aspect());
+    }
+
+    #[test]
+    fn stencil_load_op() {
+        assert!(matches!(
+            LoadOp::from(Camera3dStencilLoadOp::default()),
+            LoadOp::Clear(0)
+        ));
+        assert!(matches!(
+            LoadOp::from(Camera3dStencilLoadOp::Clear(3)),
+            LoadOp::Clear(3)
+        ));
+        assert!(matches!(
+            LoadOp::<u32>::from(Camera3dStencilLoadOp::Load),
+            LoadOp::Load
+        ));
+    }
+}
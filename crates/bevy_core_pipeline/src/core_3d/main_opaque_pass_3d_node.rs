@@ -51,6 +51,7 @@ impl ViewNode for MainOpaquePass3dNode {
         let depth_stencil_attachment = Some(depth.get_attachment(StoreOp::Store));
 
         let view_entity = graph.view_entity();
+        let gpu_timestamps = render_context.gpu_timestamps().cloned();
         render_context.add_command_buffer_generation_task(move |render_device| {
             #[cfg(feature = "trace")]
             let _main_opaque_pass_3d_span = info_span!("main_opaque_pass_3d").entered();
@@ -66,13 +67,18 @@ impl ViewNode for MainOpaquePass3dNode {
                 label: Some("main_opaque_pass_3d"),
                 color_attachments: &color_attachments,
                 depth_stencil_attachment,
-                timestamp_writes: None,
+                timestamp_writes: gpu_timestamps.as_ref().and_then(|gpu_timestamps| {
+                    gpu_timestamps.render_pass_writes("main_opaque_pass_3d")
+                }),
                 occlusion_query_set: None,
             });
             let mut render_pass = TrackedRenderPass::new(&render_device, render_pass);
             if let Some(viewport) = camera.viewport.as_ref() {
                 render_pass.set_camera_viewport(viewport);
             }
+            if let Some(stencil_reference) = depth.stencil_reference() {
+                render_pass.set_stencil_reference(stencil_reference);
+            }
 
             // Opaque draws
             if !opaque_phase.items.is_empty() {
@@ -37,6 +37,10 @@ pub mod graph {
 
 // PERF: vulkan docs recommend using 24 bit depth for better performance
 pub const CORE_3D_DEPTH_FORMAT: TextureFormat = TextureFormat::Depth32Float;
+/// The format of the depth texture of the cameras with a [stencil buffer](Camera3d::stencil).
+///
+/// Unlike `Depth32FloatStencil8`, this format doesn't require any optional GPU feature.
+pub const CORE_3D_DEPTH_STENCIL_FORMAT: TextureFormat = TextureFormat::Depth24PlusStencil8;
 
 use std::{cmp::Reverse, ops::Range};
 
@@ -60,7 +64,8 @@ use bevy_render::{
     },
     render_resource::{
         CachedRenderPipelineId, Extent3d, FilterMode, Sampler, SamplerDescriptor, Texture,
-        TextureDescriptor, TextureDimension, TextureFormat, TextureUsages, TextureView,
+        TextureAspect, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+        TextureView, TextureViewDescriptor,
     },
     renderer::RenderDevice,
     texture::{BevyDefault, ColorAttachment, TextureCache},
@@ -78,8 +83,8 @@ use crate::{
     },
     prepass::{
         node::PrepassNode, AlphaMask3dPrepass, DeferredPrepass, DepthPrepass, MotionVectorPrepass,
-        NormalPrepass, Opaque3dPrepass, ViewPrepassTextures, MOTION_VECTOR_PREPASS_FORMAT,
-        NORMAL_PREPASS_FORMAT,
+        NormalPrepass, Opaque3dPrepass, Transparent3dPrepass, ViewPrepassTextures,
+        MOTION_VECTOR_PREPASS_FORMAT, NORMAL_PREPASS_FORMAT,
     },
     skybox::SkyboxPlugin,
     tonemapping::TonemappingNode,
@@ -94,6 +99,8 @@ impl Plugin for Core3dPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<Camera3d>()
             .register_type::<Camera3dDepthLoadOp>()
+            .register_type::<Camera3dStencil>()
+            .register_type::<Camera3dStencilLoadOp>()
             .register_type::<Camera3dDepthTextureUsage>()
             .register_type::<ScreenSpaceTransmissionQuality>()
             .add_plugins((SkyboxPlugin, ExtractComponentPlugin::<Camera3d>::default()))
@@ -110,6 +117,7 @@ impl Plugin for Core3dPlugin {
             .init_resource::<DrawFunctions<Transparent3d>>()
             .init_resource::<DrawFunctions<Opaque3dPrepass>>()
             .init_resource::<DrawFunctions<AlphaMask3dPrepass>>()
+            .init_resource::<DrawFunctions<Transparent3dPrepass>>()
             .init_resource::<DrawFunctions<Opaque3dDeferred>>()
             .init_resource::<DrawFunctions<AlphaMask3dDeferred>>()
             .add_systems(ExtractSchedule, extract_core_3d_camera_phases)
@@ -123,6 +131,7 @@ impl Plugin for Core3dPlugin {
                     sort_phase_system::<Transparent3d>.in_set(RenderSet::PhaseSort),
                     sort_phase_system::<Opaque3dPrepass>.in_set(RenderSet::PhaseSort),
                     sort_phase_system::<AlphaMask3dPrepass>.in_set(RenderSet::PhaseSort),
+                    sort_phase_system::<Transparent3dPrepass>.in_set(RenderSet::PhaseSort),
                     sort_phase_system::<Opaque3dDeferred>.in_set(RenderSet::PhaseSort),
                     sort_phase_system::<AlphaMask3dDeferred>.in_set(RenderSet::PhaseSort),
                     prepare_core_3d_depth_textures.in_set(RenderSet::PrepareResources),
@@ -485,6 +494,12 @@ pub fn extract_camera_prepass_phase(
                 ));
             }
 
+            // Transparent meshes are drawn into the `DepthPrepass` texture, which the deferred
+            // prepass overwrites.
+            if depth_prepass && !deferred_prepass {
+                entity.insert(RenderPhase::<Transparent3dPrepass>::default());
+            }
+
             if deferred_prepass {
                 entity.insert((
                     RenderPhase::<Opaque3dDeferred>::default(),
@@ -543,8 +558,9 @@ pub fn prepare_core_3d_depth_textures(
             continue;
         };
 
+        let format = camera_3d.depth_texture_format();
         let cached_texture = textures
-            .entry(camera.target.clone())
+            .entry((camera.target.clone(), format))
             .or_insert_with(|| {
                 // The size of the depth texture
                 let size = Extent3d {
@@ -563,7 +579,7 @@ pub fn prepare_core_3d_depth_textures(
                     mip_level_count: 1,
                     sample_count: msaa.samples(),
                     dimension: TextureDimension::D2,
-                    format: CORE_3D_DEPTH_FORMAT,
+                    format,
                     usage,
                     view_formats: &[],
                 };
@@ -572,13 +588,23 @@ pub fn prepare_core_3d_depth_textures(
             })
             .clone();
 
-        commands.entity(entity).insert(ViewDepthTexture::new(
+        let mut depth_texture = ViewDepthTexture::new(
             cached_texture,
             match camera_3d.depth_load_op {
                 Camera3dDepthLoadOp::Clear(v) => Some(v),
                 Camera3dDepthLoadOp::Load => None,
             },
-        ));
+        );
+        if let Some(stencil) = &camera_3d.stencil {
+            depth_texture = depth_texture.with_stencil(
+                match stencil.load_op {
+                    Camera3dStencilLoadOp::Clear(v) => Some(v),
+                    Camera3dStencilLoadOp::Load => None,
+                },
+                stencil.reference,
+            );
+        }
+        commands.entity(entity).insert(depth_texture);
     }
 }
 
@@ -698,6 +724,7 @@ pub fn prepare_prepass_textures(
         (
             Entity,
             &ExtractedCamera,
+            Option<&Camera3d>,
             Has<DepthPrepass>,
             Has<NormalPrepass>,
             Has<MotionVectorPrepass>,
@@ -716,8 +743,15 @@ pub fn prepare_prepass_textures(
     let mut deferred_textures = HashMap::default();
     let mut deferred_lighting_id_textures = HashMap::default();
     let mut motion_vectors_textures = HashMap::default();
-    for (entity, camera, depth_prepass, normal_prepass, motion_vector_prepass, deferred_prepass) in
-        &views_3d
+    for (
+        entity,
+        camera,
+        camera_3d,
+        depth_prepass,
+        normal_prepass,
+        motion_vector_prepass,
+        deferred_prepass,
+    ) in &views_3d
     {
         let Some(physical_target_size) = camera.physical_target_size else {
             continue;
@@ -729,9 +763,11 @@ pub fn prepare_prepass_textures(
             height: physical_target_size.y,
         };
 
+        // The main depth texture is copied to this one, so they must have the same format.
+        let depth_format = camera_3d.map_or(CORE_3D_DEPTH_FORMAT, Camera3d::depth_texture_format);
         let cached_depth_texture = depth_prepass.then(|| {
             depth_textures
-                .entry(camera.target.clone())
+                .entry((camera.target.clone(), depth_format))
                 .or_insert_with(|| {
                     let descriptor = TextureDescriptor {
                         label: Some("prepass_depth_texture"),
@@ -739,13 +775,23 @@ pub fn prepare_prepass_textures(
                         mip_level_count: 1,
                         sample_count: msaa.samples(),
                         dimension: TextureDimension::D2,
-                        format: CORE_3D_DEPTH_FORMAT,
+                        format: depth_format,
                         usage: TextureUsages::COPY_DST
                             | TextureUsages::RENDER_ATTACHMENT
                             | TextureUsages::TEXTURE_BINDING,
                         view_formats: &[],
                     };
-                    texture_cache.get(&render_device, descriptor)
+                    let mut cached_texture = texture_cache.get(&render_device, descriptor);
+                    if depth_format.has_stencil_aspect() {
+                        // Only the depth aspect of a depth stencil texture can be sampled.
+                        cached_texture.default_view =
+                            cached_texture.texture.create_view(&TextureViewDescriptor {
+                                label: Some("prepass_depth_texture_depth_view"),
+                                aspect: TextureAspect::DepthOnly,
+                                ..Default::default()
+                            });
+                    }
+                    cached_texture
                 })
                 .clone()
         });
@@ -136,6 +136,9 @@ impl ViewNode for DeferredGBufferPrepassNode {
             if let Some(viewport) = camera.viewport.as_ref() {
                 render_pass.set_camera_viewport(viewport);
             }
+            if let Some(stencil_reference) = view_depth_texture.stencil_reference() {
+                render_pass.set_stencil_reference(stencil_reference);
+            }
 
             // Opaque draws
             if !opaque_deferred_phase.items.is_empty() {
@@ -16,7 +16,7 @@ mod taa;
 pub mod tonemapping;
 pub mod upscaling;
 
-pub use skybox::Skybox;
+pub use skybox::{SkyCloudLayer, SkyStarLayer, Skybox};
 
 /// Experimental features that are not yet finished. Please report any issues you encounter!
 pub mod experimental {
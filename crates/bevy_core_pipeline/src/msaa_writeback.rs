@@ -15,6 +15,7 @@ use bevy_render::{
     Render, RenderSet,
 };
 use bevy_render::{render_resource::*, RenderApp};
+use bevy_window::HdrOutput;
 
 /// This enables "msaa writeback" support for the `core_2d` and `core_3d` pipelines, which can be enabled on cameras
 /// using [`bevy_render::camera::Camera::msaa_writeback`]. See the docs on that field for more information.
@@ -145,6 +146,8 @@ fn prepare_msaa_writeback_pipelines(
                 texture_format: view_target.main_texture_format(),
                 samples: msaa.samples(),
                 blend_state: None,
+                hdr_output: HdrOutput::Sdr,
+                paper_white_nits: 0,
             };
 
             let pipeline = pipelines.specialize(&pipeline_cache, &blit_pipeline, key);
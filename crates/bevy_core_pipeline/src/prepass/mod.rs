@@ -1,7 +1,10 @@
 //! Run a prepass before the main pass to generate depth, normals, and/or motion vectors textures, sometimes called a thin g-buffer.
 //! These textures are useful for various screen-space effects and reducing overdraw in the main pass.
 //!
-//! The prepass only runs for opaque meshes or meshes with an alpha mask. Transparent meshes are ignored.
+//! The prepass only runs for opaque meshes or meshes with an alpha mask. Transparent meshes are ignored,
+//! unless they opt into the [`Transparent3dPrepass`] phase to write their depth, normals and motion vectors
+//! after the other meshes of the prepass, for screen-space effects like SSAO and SSR to take them into account.
+//! That phase needs the [`DepthPrepass`] and isn't supported with the [`DeferredPrepass`].
 //!
 //! To enable the prepass, you need to add a prepass component to a [`crate::prelude::Camera3d`].
 //!
@@ -237,3 +240,116 @@ impl CachedRenderPipelinePhaseItem for AlphaMask3dPrepass {
         self.pipeline_id
     }
 }
+
+/// Transparent phase of the 3D prepass.
+///
+/// Sorted front-to-back by the z-distance in front of the camera.
+///
+/// Used to render the meshes with a transparent material that opted into the prepass. They're drawn
+/// after the depth of the other meshes was copied to the [`DepthPrepass`] texture, and only write to
+/// that texture, so they don't occlude anything in the main passes.
+pub struct Transparent3dPrepass {
+    pub distance: f32,
+    pub entity: Entity,
+    pub pipeline_id: CachedRenderPipelineId,
+    pub draw_function: DrawFunctionId,
+    pub batch_range: Range<u32>,
+    pub dynamic_offset: Option<NonMaxU32>,
+}
+
+impl PhaseItem for Transparent3dPrepass {
+    // NOTE: Values increase towards the camera. Front-to-back ordering means we need a descending sort.
+    type SortKey = Reverse<FloatOrd>;
+
+    #[inline]
+    fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    #[inline]
+    fn sort_key(&self) -> Self::SortKey {
+        Reverse(FloatOrd(self.distance))
+    }
+
+    #[inline]
+    fn draw_function(&self) -> DrawFunctionId {
+        self.draw_function
+    }
+
+    #[inline]
+    fn sort(items: &mut [Self]) {
+        // Key negated to match reversed SortKey ordering
+        radsort::sort_by_key(items, |item| -item.distance);
+    }
+
+    #[inline]
+    fn batch_range(&self) -> &Range<u32> {
+        &self.batch_range
+    }
+
+    #[inline]
+    fn batch_range_mut(&mut self) -> &mut Range<u32> {
+        &mut self.batch_range
+    }
+
+    #[inline]
+    fn dynamic_offset(&self) -> Option<NonMaxU32> {
+        self.dynamic_offset
+    }
+
+    #[inline]
+    fn dynamic_offset_mut(&mut self) -> &mut Option<NonMaxU32> {
+        &mut self.dynamic_offset
+    }
+}
+
+impl CachedRenderPipelinePhaseItem for Transparent3dPrepass {
+    #[inline]
+    fn cached_pipeline(&self) -> CachedRenderPipelineId {
+        self.pipeline_id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_render::render_phase::{Draw, DrawFunctions, TrackedRenderPass};
+
+    struct NoopDraw;
+
+    impl Draw<Transparent3dPrepass> for NoopDraw {
+        fn draw<'w>(
+            &mut self,
+            _world: &'w World,
+            _pass: &mut TrackedRenderPass<'w>,
+            _view: Entity,
+            _item: &Transparent3dPrepass,
+        ) {
+        }
+    }
+
+    #[test]
+    fn transparent_prepass_sorts_front_to_back() {
+        let draw_function = DrawFunctions::<Transparent3dPrepass>::default()
+            .write()
+            .add(NoopDraw);
+        let item = |distance| Transparent3dPrepass {
+            distance,
+            entity: Entity::PLACEHOLDER,
+            pipeline_id: CachedRenderPipelineId::INVALID,
+            draw_function,
+            batch_range: 0..1,
+            dynamic_offset: None,
+        };
+
+        // Distances increase towards the camera
+        let mut items = vec![item(-5.0), item(-1.0), item(-10.0)];
+        Transparent3dPrepass::sort(&mut items);
+
+        let distances: Vec<f32> = items.iter().map(|item| item.distance).collect();
+        assert_eq!(distances, [-1.0, -5.0, -10.0]);
+        assert!(items
+            .windows(2)
+            .all(|pair| pair[0].sort_key() <= pair[1].sort_key()));
+    }
+}
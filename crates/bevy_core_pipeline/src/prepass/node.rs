@@ -4,14 +4,19 @@ use bevy_render::{
     camera::ExtractedCamera,
     render_graph::{NodeRunError, RenderGraphContext, ViewNode},
     render_phase::{RenderPhase, TrackedRenderPass},
-    render_resource::{CommandEncoderDescriptor, RenderPassDescriptor, StoreOp},
+    render_resource::{
+        CommandEncoderDescriptor, LoadOp, Operations, RenderPassDepthStencilAttachment,
+        RenderPassDescriptor, StoreOp, TextureViewDescriptor,
+    },
     renderer::RenderContext,
     view::ViewDepthTexture,
 };
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::info_span;
 
-use super::{AlphaMask3dPrepass, DeferredPrepass, Opaque3dPrepass, ViewPrepassTextures};
+use super::{
+    AlphaMask3dPrepass, DeferredPrepass, Opaque3dPrepass, Transparent3dPrepass, ViewPrepassTextures,
+};
 
 /// Render node used by the prepass.
 ///
@@ -24,6 +29,7 @@ impl ViewNode for PrepassNode {
         &'static ExtractedCamera,
         &'static RenderPhase<Opaque3dPrepass>,
         &'static RenderPhase<AlphaMask3dPrepass>,
+        Option<&'static RenderPhase<Transparent3dPrepass>>,
         &'static ViewDepthTexture,
         &'static ViewPrepassTextures,
         Option<&'static DeferredPrepass>,
@@ -37,33 +43,54 @@ impl ViewNode for PrepassNode {
             camera,
             opaque_prepass_phase,
             alpha_mask_prepass_phase,
+            transparent_prepass_phase,
             view_depth_texture,
             view_prepass_textures,
             deferred_prepass,
         ): QueryItem<'w, Self::ViewQuery>,
         world: &'w World,
     ) -> Result<(), NodeRunError> {
-        let mut color_attachments = vec![
-            view_prepass_textures
-                .normal
-                .as_ref()
-                .map(|normals_texture| normals_texture.get_attachment()),
-            view_prepass_textures
-                .motion_vectors
-                .as_ref()
-                .map(|motion_vectors_texture| motion_vectors_texture.get_attachment()),
-            // Use None in place of deferred attachments
-            None,
-            None,
-        ];
-
-        // If all color attachments are none: clear the color attachment list so that no fragment shader is required
-        if color_attachments.iter().all(Option::is_none) {
-            color_attachments.clear();
-        }
+        let color_attachments = || {
+            let mut color_attachments = vec![
+                view_prepass_textures
+                    .normal
+                    .as_ref()
+                    .map(|normals_texture| normals_texture.get_attachment()),
+                view_prepass_textures
+                    .motion_vectors
+                    .as_ref()
+                    .map(|motion_vectors_texture| motion_vectors_texture.get_attachment()),
+                // Use None in place of deferred attachments
+                None,
+                None,
+            ];
+
+            // If all color attachments are none: clear the color attachment list so that no fragment shader is required
+            if color_attachments.iter().all(Option::is_none) {
+                color_attachments.clear();
+            }
+            color_attachments
+        };
 
+        let opaque_color_attachments = color_attachments();
         let depth_stencil_attachment = Some(view_depth_texture.get_attachment(StoreOp::Store));
 
+        // Transparent meshes only write to the prepass depth texture, after the depth of the other
+        // meshes was copied to it, so that they don't occlude anything in the main passes.
+        let transparent_prepass = transparent_prepass_phase
+            .filter(|phase| !phase.items.is_empty() && deferred_prepass.is_none())
+            .zip(view_prepass_textures.depth.as_ref())
+            .map(|(phase, prepass_depth_texture)| {
+                let texture = &prepass_depth_texture.texture.texture;
+                // The default view of a depth stencil texture only has the depth aspect.
+                let view = texture.create_view(&TextureViewDescriptor {
+                    label: Some("prepass_depth_texture_attachment_view"),
+                    ..Default::default()
+                });
+                let stencil = texture.format().has_stencil_aspect();
+                (phase, color_attachments(), view, stencil)
+            });
+
         let view_entity = graph.view_entity();
         render_context.add_command_buffer_generation_task(move |render_device| {
             #[cfg(feature = "trace")]
@@ -78,7 +105,7 @@ impl ViewNode for PrepassNode {
             // Render pass setup
             let render_pass = command_encoder.begin_render_pass(&RenderPassDescriptor {
                 label: Some("prepass"),
-                color_attachments: &color_attachments,
+                color_attachments: &opaque_color_attachments,
                 depth_stencil_attachment,
                 timestamp_writes: None,
                 occlusion_query_set: None,
@@ -87,6 +114,9 @@ impl ViewNode for PrepassNode {
             if let Some(viewport) = camera.viewport.as_ref() {
                 render_pass.set_camera_viewport(viewport);
             }
+            if let Some(stencil_reference) = view_depth_texture.stencil_reference() {
+                render_pass.set_stencil_reference(stencil_reference);
+            }
 
             // Opaque draws
             if !opaque_prepass_phase.items.is_empty() {
@@ -115,6 +145,39 @@ impl ViewNode for PrepassNode {
                 }
             }
 
+            if let Some((transparent_prepass_phase, color_attachments, view, stencil)) =
+                transparent_prepass
+            {
+                #[cfg(feature = "trace")]
+                let _transparent_prepass_span = info_span!("transparent_prepass").entered();
+
+                let render_pass = command_encoder.begin_render_pass(&RenderPassDescriptor {
+                    label: Some("transparent_prepass"),
+                    color_attachments: &color_attachments,
+                    depth_stencil_attachment: Some(RenderPassDepthStencilAttachment {
+                        view: &view,
+                        depth_ops: Some(Operations {
+                            load: LoadOp::Load,
+                            store: StoreOp::Store,
+                        }),
+                        stencil_ops: stencil.then_some(Operations {
+                            load: LoadOp::Load,
+                            store: StoreOp::Store,
+                        }),
+                    }),
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                });
+                let mut render_pass = TrackedRenderPass::new(&render_device, render_pass);
+                if let Some(viewport) = camera.viewport.as_ref() {
+                    render_pass.set_camera_viewport(viewport);
+                }
+                if let Some(stencil_reference) = view_depth_texture.stencil_reference() {
+                    render_pass.set_stencil_reference(stencil_reference);
+                }
+                transparent_prepass_phase.render(&mut render_pass, world, view_entity);
+            }
+
             command_encoder.finish()
         });
 
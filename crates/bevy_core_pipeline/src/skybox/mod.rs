@@ -6,24 +6,27 @@ use bevy_ecs::{
     schedule::IntoSystemConfigs,
     system::{Commands, Query, Res, ResMut, Resource},
 };
+use bevy_math::{Mat3, Quat, Vec2, Vec4};
 use bevy_render::{
     camera::ExposureSettings,
+    color::Color,
     extract_component::{
         ComponentUniforms, DynamicUniformIndex, ExtractComponent, ExtractComponentPlugin,
         UniformComponentPlugin,
     },
+    globals::{GlobalsBuffer, GlobalsUniform},
     render_asset::RenderAssets,
     render_resource::{
         binding_types::{sampler, texture_cube, uniform_buffer},
         *,
     },
     renderer::RenderDevice,
-    texture::{BevyDefault, Image},
+    texture::{BevyDefault, FallbackImageCubemap, Image},
     view::{ExtractedView, Msaa, ViewTarget, ViewUniform, ViewUniforms},
     Render, RenderApp, RenderSet,
 };
 
-use crate::core_3d::CORE_3D_DEPTH_FORMAT;
+use crate::core_3d::{Camera3d, CORE_3D_DEPTH_FORMAT};
 
 const SKYBOX_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(55594763423201);
 
@@ -66,6 +69,15 @@ impl Plugin for SkyboxPlugin {
 
 /// Adds a skybox to a 3D camera, based on a cubemap texture.
 ///
+/// The sky is made of up to three layers, each with its own rotation and intensity, composited
+/// in a single pass:
+/// - the base cubemap of [`Skybox::image`],
+/// - an optional star map, added to the base cubemap, see [`SkyStarLayer`],
+/// - an optional procedural cloud layer, covering both, see [`SkyCloudLayer`].
+///
+/// Time-of-day systems can animate the rotations and intensities of the layers every frame, for
+/// example fading the stars in while dimming the base cubemap at dusk.
+///
 /// Note that this component does not (currently) affect the scene's lighting.
 /// To do so, use `EnvironmentMapLight` alongside this component.
 ///
@@ -77,6 +89,71 @@ pub struct Skybox {
     /// After applying this multiplier to the image samples, the resulting values should
     /// be in units of [cd/m^2](https://en.wikipedia.org/wiki/Candela_per_square_metre).
     pub brightness: f32,
+    /// The rotation of the skybox image around the camera.
+    pub rotation: Quat,
+    /// A star map added to the skybox image.
+    pub stars: Option<SkyStarLayer>,
+    /// A procedural cloud layer drawn over the skybox image and the stars.
+    pub clouds: Option<SkyCloudLayer>,
+}
+
+impl Default for Skybox {
+    fn default() -> Self {
+        Self {
+            image: Handle::default(),
+            brightness: 0.0,
+            rotation: Quat::IDENTITY,
+            stars: None,
+            clouds: None,
+        }
+    }
+}
+
+/// A star map layer of a [`Skybox`], added to its base cubemap.
+#[derive(Clone)]
+pub struct SkyStarLayer {
+    /// The cubemap of the stars, black where there are none.
+    pub image: Handle<Image>,
+    /// Scale factor applied to the star map, in the same units as [`Skybox::brightness`].
+    ///
+    /// Usually `0.0` during the day, and raised at night.
+    pub intensity: f32,
+    /// The rotation of the stars around the camera, following the rotation of the earth.
+    pub rotation: Quat,
+}
+
+/// A procedural cloud layer of a [`Skybox`], drawn over its base cubemap and star map.
+///
+/// The clouds are a noise pattern on a plane above the camera, fading out towards the horizon.
+#[derive(Clone, Debug)]
+pub struct SkyCloudLayer {
+    /// The color of the clouds, its alpha scaling their opacity.
+    pub color: Color,
+    /// Scale factor applied to the color of the clouds, in the same units as
+    /// [`Skybox::brightness`].
+    pub intensity: f32,
+    /// The fraction of the sky covered by the clouds, between `0.0` and `1.0`.
+    pub coverage: f32,
+    /// The number of cloud patterns across the cloud plane, at its unit distance from the
+    /// camera.
+    pub scale: f32,
+    /// The movement of the clouds across the cloud plane, in patterns per second.
+    pub wind: Vec2,
+    /// The rotation of the cloud plane around the camera.
+    pub rotation: Quat,
+}
+
+impl Default for SkyCloudLayer {
+    fn default() -> Self {
+        Self {
+            color: Color::WHITE,
+            intensity: 1000.0,
+            coverage: 0.5,
+            scale: 2.0,
+            wind: Vec2::new(0.02, 0.01),
+            rotation: Quat::IDENTITY,
+        }
+    }
 }
 
 impl ExtractComponent for Skybox {
@@ -90,11 +167,34 @@ impl ExtractComponent for Skybox {
         let exposure = exposure_settings
             .map(|e| e.exposure())
             .unwrap_or_else(|| ExposureSettings::default().exposure());
+        // The layers are sampled with the ray directions rotated back into their own space.
+        let inverse_rotation = |rotation: Quat| Mat3::from_quat(rotation.inverse());
+
+        let (star_rotation, star_intensity) = skybox
+            .stars
+            .as_ref()
+            .map_or((Mat3::IDENTITY, 0.0), |stars| {
+                (inverse_rotation(stars.rotation), stars.intensity * exposure)
+            });
+        let clouds = skybox.clouds.clone().unwrap_or(SkyCloudLayer {
+            color: Color::NONE,
+            ..Default::default()
+        });
+        let [r, g, b, a] = clouds.color.as_linear_rgba_f32();
 
         Some((
             skybox.clone(),
             SkyboxUniforms {
                 brightness: skybox.brightness * exposure,
+                rotation: inverse_rotation(skybox.rotation),
+                star_rotation,
+                star_intensity,
+                cloud_rotation: inverse_rotation(clouds.rotation),
+                cloud_color: Vec4::new(r, g, b, 0.0) * clouds.intensity * exposure
+                    + Vec4::new(0.0, 0.0, 0.0, a),
+                cloud_coverage: clouds.coverage.clamp(0.0, 1.0),
+                cloud_scale: clouds.scale,
+                cloud_wind: clouds.wind,
             },
         ))
     }
@@ -104,6 +204,15 @@ impl ExtractComponent for Skybox {
 #[derive(Component, ShaderType, Clone)]
 pub struct SkyboxUniforms {
     brightness: f32,
+    rotation: Mat3,
+    star_rotation: Mat3,
+    star_intensity: f32,
+    cloud_rotation: Mat3,
+    /// The color of the clouds, premultiplied by their intensity, and their opacity.
+    cloud_color: Vec4,
+    cloud_coverage: f32,
+    cloud_scale: f32,
+    cloud_wind: Vec2,
 }
 
 #[derive(Resource)]
@@ -124,6 +233,8 @@ impl SkyboxPipeline {
                         uniform_buffer::<ViewUniform>(true)
                             .visibility(ShaderStages::VERTEX_FRAGMENT),
                         uniform_buffer::<SkyboxUniforms>(true),
+                        uniform_buffer::<GlobalsUniform>(false),
+                        texture_cube(TextureSampleType::Float { filterable: true }),
                     ),
                 ),
             ),
@@ -202,16 +313,17 @@ fn prepare_skybox_pipelines(
     mut pipelines: ResMut<SpecializedRenderPipelines<SkyboxPipeline>>,
     pipeline: Res<SkyboxPipeline>,
     msaa: Res<Msaa>,
-    views: Query<(Entity, &ExtractedView), With<Skybox>>,
+    views: Query<(Entity, &ExtractedView, Option<&Camera3d>), With<Skybox>>,
 ) {
-    for (entity, view) in &views {
+    for (entity, view, camera_3d) in &views {
         let pipeline_id = pipelines.specialize(
             &pipeline_cache,
             &pipeline,
             SkyboxPipelineKey {
                 hdr: view.hdr,
                 samples: msaa.samples(),
-                depth_format: CORE_3D_DEPTH_FORMAT,
+                depth_format: camera_3d
+                    .map_or(CORE_3D_DEPTH_FORMAT, Camera3d::depth_texture_format),
             },
         );
 
@@ -229,15 +341,30 @@ fn prepare_skybox_bind_groups(
     pipeline: Res<SkyboxPipeline>,
     view_uniforms: Res<ViewUniforms>,
     skybox_uniforms: Res<ComponentUniforms<SkyboxUniforms>>,
+    globals_buffer: Res<GlobalsBuffer>,
     images: Res<RenderAssets<Image>>,
+    fallback_cubemap: Res<FallbackImageCubemap>,
     render_device: Res<RenderDevice>,
     views: Query<(Entity, &Skybox, &DynamicUniformIndex<SkyboxUniforms>)>,
 ) {
     for (entity, skybox, skybox_uniform_index) in &views {
-        if let (Some(skybox), Some(view_uniforms), Some(skybox_uniforms)) = (
+        // Without a star layer, the fallback cubemap is bound and the stars have no intensity.
+        let stars = match &skybox.stars {
+            Some(stars) => images.get(&stars.image),
+            None => Some(&**fallback_cubemap),
+        };
+        if let (
+            Some(skybox),
+            Some(stars),
+            Some(view_uniforms),
+            Some(skybox_uniforms),
+            Some(globals),
+        ) = (
             images.get(&skybox.image),
+            stars,
             view_uniforms.uniforms.binding(),
             skybox_uniforms.binding(),
+            globals_buffer.buffer.binding(),
         ) {
             let bind_group = render_device.create_bind_group(
                 "skybox_bind_group",
@@ -247,6 +374,8 @@ fn prepare_skybox_bind_groups(
                     &skybox.sampler,
                     view_uniforms,
                     skybox_uniforms,
+                    globals,
+                    &stars.texture_view,
                 )),
             );
 
@@ -256,3 +385,57 @@ fn prepare_skybox_bind_groups(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use super::*;
+
+    #[test]
+    fn extract_layer_uniforms() {
+        let exposure_settings = ExposureSettings { ev100: 0.0 };
+        let exposure = exposure_settings.exposure();
+
+        // Without layers, the stars have no intensity and the clouds are transparent.
+        let skybox = Skybox {
+            brightness: 1000.0,
+            rotation: Quat::from_rotation_y(FRAC_PI_2),
+            ..Default::default()
+        };
+        let (_, uniforms) = Skybox::extract_component((&skybox, Some(&exposure_settings))).unwrap();
+        assert_eq!(uniforms.brightness, 1000.0 * exposure);
+        assert_eq!(
+            uniforms.rotation,
+            Mat3::from_quat(Quat::from_rotation_y(-FRAC_PI_2))
+        );
+        assert_eq!(uniforms.star_intensity, 0.0);
+        assert_eq!(uniforms.cloud_color, Vec4::ZERO);
+
+        let skybox = Skybox {
+            stars: Some(SkyStarLayer {
+                image: Handle::default(),
+                intensity: 10.0,
+                rotation: Quat::from_rotation_x(FRAC_PI_2),
+            }),
+            clouds: Some(SkyCloudLayer {
+                color: Color::rgba(1.0, 1.0, 1.0, 0.5),
+                intensity: 100.0,
+                coverage: 2.0,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let (_, uniforms) = Skybox::extract_component((&skybox, Some(&exposure_settings))).unwrap();
+        assert_eq!(uniforms.star_intensity, 10.0 * exposure);
+        assert!(uniforms
+            .star_rotation
+            .abs_diff_eq(Mat3::from_rotation_x(-FRAC_PI_2), 1e-6));
+        // The cloud color is scaled by the intensity, but not its opacity.
+        let cloud_color = 100.0 * exposure;
+        assert!(uniforms
+            .cloud_color
+            .abs_diff_eq(Vec4::new(cloud_color, cloud_color, cloud_color, 0.5), 1e-4));
+        assert_eq!(uniforms.cloud_coverage, 1.0);
+    }
+}
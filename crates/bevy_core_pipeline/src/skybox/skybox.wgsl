@@ -1,10 +1,25 @@
-#import bevy_render::view::View
+#import bevy_render::{view::View, globals::Globals}
 #import bevy_pbr::utils::coords_to_viewport_uv
 
+struct SkyboxUniforms {
+    brightness: f32,
+    rotation: mat3x3<f32>,
+    star_rotation: mat3x3<f32>,
+    star_intensity: f32,
+    cloud_rotation: mat3x3<f32>,
+    // The color of the clouds premultiplied by their intensity, and their opacity.
+    cloud_color: vec4<f32>,
+    cloud_coverage: f32,
+    cloud_scale: f32,
+    cloud_wind: vec2<f32>,
+};
+
 @group(0) @binding(0) var skybox: texture_cube<f32>;
 @group(0) @binding(1) var skybox_sampler: sampler;
 @group(0) @binding(2) var<uniform> view: View;
-@group(0) @binding(3) var<uniform> brightness: f32;
+@group(0) @binding(3) var<uniform> uniforms: SkyboxUniforms;
+@group(0) @binding(4) var<uniform> globals: Globals;
+@group(0) @binding(5) var stars: texture_cube<f32>;
 
 fn coords_to_ray_direction(position: vec2<f32>, viewport: vec4<f32>) -> vec3<f32> {
     // Using world positions of the fragment and camera to calculate a ray direction
@@ -30,6 +45,52 @@ fn coords_to_ray_direction(position: vec2<f32>, viewport: vec4<f32>) -> vec3<f32
     return normalize(ray_direction);
 }
 
+fn hash(p: vec2<f32>) -> f32 {
+    let q = fract(p * vec2(123.34, 456.21));
+    let r = q + dot(q, q + 45.32);
+    return fract(r.x * r.y);
+}
+
+fn value_noise(p: vec2<f32>) -> f32 {
+    let cell = floor(p);
+    let f = fract(p);
+    let u = f * f * (3.0 - 2.0 * f);
+    let a = hash(cell);
+    let b = hash(cell + vec2(1.0, 0.0));
+    let c = hash(cell + vec2(0.0, 1.0));
+    let d = hash(cell + vec2(1.0, 1.0));
+    return mix(mix(a, b, u.x), mix(c, d, u.x), u.y);
+}
+
+// Fractal noise in the range [0, 1].
+fn fbm(p: vec2<f32>) -> f32 {
+    var value = 0.0;
+    var amplitude = 0.5;
+    var position = p;
+    for (var i = 0; i < 5; i += 1) {
+        value += amplitude * value_noise(position);
+        position = position * 2.03 + vec2(17.0, 31.0);
+        amplitude *= 0.5;
+    }
+    return value / 0.96875;
+}
+
+// The opacity of the cloud layer along a ray direction.
+fn cloud_density(direction: vec3<f32>) -> f32 {
+    let cloud_direction = uniforms.cloud_rotation * direction;
+    if cloud_direction.y <= 0.0 {
+        return 0.0;
+    }
+    // Project the ray onto a plane above the camera, offset so the horizon stays bounded.
+    let plane_position = cloud_direction.xz / (cloud_direction.y + 0.1);
+    let noise = fbm(plane_position * uniforms.cloud_scale + globals.time * uniforms.cloud_wind);
+    let coverage = uniforms.cloud_coverage;
+    let density = smoothstep(1.0 - coverage, min(1.0 - coverage + 0.3, 1.0), noise);
+    // Fade the clouds out towards the horizon, where the projection stretches them.
+    let horizon_fade = smoothstep(0.0, 0.2, cloud_direction.y);
+    return density * horizon_fade * uniforms.cloud_color.a;
+}
+
 struct VertexOutput {
     @builtin(position) position: vec4<f32>,
 };
@@ -63,5 +124,14 @@ fn skybox_fragment(in: VertexOutput) -> @location(0) vec4<f32> {
     let ray_direction = coords_to_ray_direction(in.position.xy, view.viewport);
 
     // Cube maps are left-handed so we negate the z coordinate.
-    return textureSample(skybox, skybox_sampler, ray_direction * vec3(1.0, 1.0, -1.0)) * brightness;
+    let flip_z = vec3(1.0, 1.0, -1.0);
+    let base_direction = uniforms.rotation * ray_direction * flip_z;
+    let star_direction = uniforms.star_rotation * ray_direction * flip_z;
+    var color = textureSample(skybox, skybox_sampler, base_direction).rgb * uniforms.brightness;
+    color += textureSample(stars, skybox_sampler, star_direction).rgb * uniforms.star_intensity;
+
+    let density = cloud_density(ray_direction);
+    color = mix(color, uniforms.cloud_color.rgb, density);
+
+    return vec4(color, 1.0);
 }
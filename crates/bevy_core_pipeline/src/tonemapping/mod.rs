@@ -12,12 +12,13 @@ use bevy_render::render_resource::binding_types::{
 };
 use bevy_render::renderer::RenderDevice;
 use bevy_render::texture::{CompressedImageFormats, Image, ImageSampler, ImageType};
-use bevy_render::view::{ViewTarget, ViewUniform};
+use bevy_render::view::{HdrOutputSettings, ViewTarget, ViewUniform};
 use bevy_render::{render_resource::*, Render, RenderApp, RenderSet};
 
 mod node;
 
 use bevy_utils::default;
+use bevy_window::HdrOutput;
 pub use node::TonemappingNode;
 
 const TONEMAPPING_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(17015368199668024512);
@@ -179,6 +180,14 @@ impl Tonemapping {
 pub struct TonemappingPipelineKey {
     deband_dither: DebandDither,
     tonemapping: Tonemapping,
+    hdr_output: Option<HdrOutputKey>,
+}
+
+/// The luminance range of an HDR display, in nits.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+struct HdrOutputKey {
+    paper_white_nits: u32,
+    peak_nits: u32,
 }
 
 impl SpecializedRenderPipeline for TonemappingPipeline {
@@ -189,6 +198,17 @@ impl SpecializedRenderPipeline for TonemappingPipeline {
         if let DebandDither::Enabled = key.deband_dither {
             shader_defs.push("DEBAND_DITHER".into());
         }
+        if let Some(hdr_output) = key.hdr_output {
+            shader_defs.push("HDR_OUTPUT".into());
+            shader_defs.push(ShaderDefVal::UInt(
+                "HDR_OUTPUT_PAPER_WHITE_NITS".into(),
+                hdr_output.paper_white_nits,
+            ));
+            shader_defs.push(ShaderDefVal::UInt(
+                "HDR_OUTPUT_PEAK_NITS".into(),
+                hdr_output.peak_nits,
+            ));
+        }
 
         match key.tonemapping {
             Tonemapping::None => shader_defs.push("TONEMAP_METHOD_NONE".into()),
@@ -288,12 +308,30 @@ pub fn prepare_view_tonemapping_pipelines(
     pipeline_cache: Res<PipelineCache>,
     mut pipelines: ResMut<SpecializedRenderPipelines<TonemappingPipeline>>,
     upscaling_pipeline: Res<TonemappingPipeline>,
-    view_targets: Query<(Entity, Option<&Tonemapping>, Option<&DebandDither>), With<ViewTarget>>,
+    view_targets: Query<(
+        Entity,
+        &ViewTarget,
+        Option<&Tonemapping>,
+        Option<&DebandDither>,
+        Option<&HdrOutputSettings>,
+    )>,
 ) {
-    for (entity, tonemapping, dither) in view_targets.iter() {
+    for (entity, view_target, tonemapping, dither, hdr_output_settings) in view_targets.iter() {
+        let hdr_output = (view_target.out_hdr_output() != HdrOutput::Sdr).then(|| {
+            let settings = hdr_output_settings.copied().unwrap_or_default();
+            HdrOutputKey {
+                paper_white_nits: settings.paper_white_nits.max(1.0) as u32,
+                peak_nits: settings.peak_nits.max(settings.paper_white_nits).max(1.0) as u32,
+            }
+        });
         let key = TonemappingPipelineKey {
-            deband_dither: *dither.unwrap_or(&DebandDither::Disabled),
+            // Banding isn't visible in the higher precision HDR output formats
+            deband_dither: match hdr_output {
+                Some(_) => DebandDither::Disabled,
+                None => *dither.unwrap_or(&DebandDither::Disabled),
+            },
             tonemapping: *tonemapping.unwrap_or(&Tonemapping::None),
+            hdr_output,
         };
         let pipeline = pipelines.specialize(&pipeline_cache, &upscaling_pipeline, key);
 
@@ -273,6 +273,18 @@ fn screen_space_dither(frag_coord: vec2<f32>) -> vec3<f32> {
     return (dither - 0.5) / 255.0;
 }
 
+#ifdef HDR_OUTPUT
+// Rolls off the luminance of highlights towards the peak luminance of the display, which is
+// `peak / paper_white` times brighter than a color value of 1.0. The SDR tonemappers are
+// skipped as they would clip everything brighter than paper white.
+fn tonemapping_hdr_output(color: vec3<f32>) -> vec3<f32> {
+    let headroom = f32(#{HDR_OUTPUT_PEAK_NITS}u) / f32(#{HDR_OUTPUT_PAPER_WHITE_NITS}u);
+    let l_old = tonemapping_luminance(color);
+    let l_new = l_old / (1.0 + l_old / headroom);
+    return tonemapping_change_luminance(color, l_new);
+}
+#endif
+
 fn tone_mapping(in: vec4<f32>, color_grading: ColorGrading) -> vec4<f32> {
     var color = max(in.rgb, vec3(0.0));
 
@@ -291,7 +303,11 @@ fn tone_mapping(in: vec4<f32>, color_grading: ColorGrading) -> vec4<f32> {
     color = max(color, vec3(0.0));
 
     // tone_mapping
-#ifdef TONEMAP_METHOD_NONE
+#ifdef HDR_OUTPUT
+#ifndef TONEMAP_METHOD_NONE
+    color = tonemapping_hdr_output(color);
+#endif
+#else ifdef TONEMAP_METHOD_NONE
     color = color;
 #else ifdef TONEMAP_METHOD_REINHARD
     color = tonemapping_reinhard(color.rgb);
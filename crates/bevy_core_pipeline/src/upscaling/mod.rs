@@ -2,7 +2,7 @@ use crate::blit::{BlitPipeline, BlitPipelineKey};
 use bevy_app::prelude::*;
 use bevy_ecs::prelude::*;
 use bevy_render::camera::{CameraOutputMode, ExtractedCamera};
-use bevy_render::view::ViewTarget;
+use bevy_render::view::{HdrOutputSettings, ViewTarget};
 use bevy_render::{render_resource::*, Render, RenderApp, RenderSet};
 
 mod node;
@@ -30,9 +30,14 @@ fn prepare_view_upscaling_pipelines(
     mut pipeline_cache: ResMut<PipelineCache>,
     mut pipelines: ResMut<SpecializedRenderPipelines<BlitPipeline>>,
     blit_pipeline: Res<BlitPipeline>,
-    view_targets: Query<(Entity, &ViewTarget, Option<&ExtractedCamera>)>,
+    view_targets: Query<(
+        Entity,
+        &ViewTarget,
+        Option<&ExtractedCamera>,
+        Option<&HdrOutputSettings>,
+    )>,
 ) {
-    for (entity, view_target, camera) in view_targets.iter() {
+    for (entity, view_target, camera, hdr_output_settings) in view_targets.iter() {
         let blend_state = if let Some(ExtractedCamera {
             output_mode: CameraOutputMode::Write { blend_state, .. },
             ..
@@ -46,6 +51,12 @@ fn prepare_view_upscaling_pipelines(
             texture_format: view_target.out_texture_format(),
             blend_state,
             samples: 1,
+            hdr_output: view_target.out_hdr_output(),
+            paper_white_nits: hdr_output_settings
+                .copied()
+                .unwrap_or_default()
+                .paper_white_nits
+                .max(1.0) as u32,
         };
         let pipeline = pipelines.specialize(&pipeline_cache, &blit_pipeline, key);
 
@@ -0,0 +1,292 @@
+//! The commands every [developer console](super::DevConsolePlugin) starts with.
+
+use std::collections::BTreeMap;
+
+use bevy_asset::AssetServer;
+use bevy_diagnostic::{DiagnosticPath, DiagnosticsStore};
+use bevy_ecs::{
+    entity::Entity,
+    schedule::{NextState, State, States},
+    system::Resource,
+    world::World,
+};
+use bevy_reflect::{DynamicEnum, FromReflect, TypeInfo, TypePath, Typed, VariantInfo};
+use bevy_scene::{DynamicScene, Scene, SceneSpawner};
+
+use super::{
+    command::complete_component_path, ConsoleArgs, ConsoleCommand, ConsoleCommands, ConsoleResult,
+    DevConsole,
+};
+use crate::diagnostics_overlay::DiagnosticsOverlayConfig;
+
+/// The [states](States) that can be read and set with the `state` and `set_state` console
+/// commands, by short type path.
+///
+/// States are added with [`AppConsoleBuilder::add_console_state`](super::AppConsoleBuilder).
+#[derive(Resource, Default)]
+pub struct ConsoleStates {
+    states: BTreeMap<&'static str, ConsoleState>,
+}
+
+struct ConsoleState {
+    get: fn(&World) -> Option<String>,
+    set: fn(&mut World, &str) -> ConsoleResult,
+    variants: fn() -> Vec<&'static str>,
+}
+
+impl ConsoleStates {
+    /// Makes the state `S` available to the console.
+    pub fn add<S: States + FromReflect + TypePath + Typed>(&mut self) {
+        self.states.insert(
+            S::short_type_path(),
+            ConsoleState {
+                get: |world| {
+                    world
+                        .get_resource::<State<S>>()
+                        .map(|state| format!("{:?}", state.get()))
+                },
+                set: set_state::<S>,
+                variants: unit_variants::<S>,
+            },
+        );
+    }
+}
+
+fn set_state<S: States + FromReflect + TypePath + Typed>(
+    world: &mut World,
+    variant: &str,
+) -> ConsoleResult {
+    // `FromReflect` panics on unknown variants, so they are checked first.
+    let state = unit_variants::<S>()
+        .contains(&variant)
+        .then(|| S::from_reflect(&DynamicEnum::new(variant, ())))
+        .flatten()
+        .ok_or_else(|| format!("`{variant}` is not a variant of `{}`", S::short_type_path()))?;
+    let mut next_state = world
+        .get_resource_mut::<NextState<S>>()
+        .ok_or_else(|| format!("The `{}` state is not initialized", S::short_type_path()))?;
+    next_state.set(state);
+    Ok(format!("{} will be set to {variant}", S::short_type_path()))
+}
+
+fn unit_variants<S: Typed>() -> Vec<&'static str> {
+    match S::type_info() {
+        TypeInfo::Enum(info) => info
+            .iter()
+            .filter(|variant| matches!(variant, VariantInfo::Unit(_)))
+            .map(VariantInfo::name)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+pub(super) fn add_builtin_commands(commands: &mut ConsoleCommands) {
+    commands.add(
+        ConsoleCommand::new("help", help)
+            .with_description("Lists the commands, or describes one")
+            .with_optional_arg::<String>("command")
+            .with_completer(|world, previous, _| match previous {
+                [] => world
+                    .resource::<ConsoleCommands>()
+                    .iter()
+                    .map(|command| command.name().to_string())
+                    .collect(),
+                _ => Vec::new(),
+            }),
+    );
+    commands.add(
+        ConsoleCommand::new("clear", |world, _| {
+            world.resource_mut::<DevConsole>().clear();
+            Ok(String::new())
+        })
+        .with_description("Clears the console output"),
+    );
+    commands.add(
+        ConsoleCommand::new("get", |world, args| {
+            let entity = find_entity(world, args.arg::<String>(0))?;
+            world
+                .format_component_value(entity, args.arg::<String>(1))
+                .map_err(|error| error.to_string())
+        })
+        .with_description("Prints a value in a component, like `get 4v1 Transform.translation`")
+        .with_arg::<String>("entity")
+        .with_arg::<String>("path")
+        .with_completer(complete_entity_path),
+    );
+    commands.add(
+        ConsoleCommand::new("set", |world, args| {
+            let entity = find_entity(world, args.arg::<String>(0))?;
+            let path = args.arg::<String>(1);
+            world
+                .set_component_value_from_str(entity, path, args.arg::<String>(2))
+                .map_err(|error| error.to_string())?;
+            world
+                .format_component_value(entity, path)
+                .map(|value| format!("{path} = {value}"))
+                .map_err(|error| error.to_string())
+        })
+        .with_description("Sets a value in a component, like `set 4v1 Transform.scale \"2, 2, 2\"`")
+        .with_arg::<String>("entity")
+        .with_arg::<String>("path")
+        .with_arg::<String>("value")
+        .with_completer(complete_entity_path),
+    );
+    commands.add(
+        ConsoleCommand::new("spawn_scene", spawn_scene)
+            .with_description(
+                "Spawns a scene asset, either a `.scn.ron` file or a labeled scene like `model.glb#Scene0`",
+            )
+            .with_arg::<String>("path"),
+    );
+    commands.add(
+        ConsoleCommand::new("state", |world, _| {
+            let states = world.resource::<ConsoleStates>();
+            if states.states.is_empty() {
+                return Ok("No states are available to the console".to_string());
+            }
+            let lines: Vec<_> = states
+                .states
+                .iter()
+                .map(|(name, state)| match (state.get)(world) {
+                    Some(value) => format!("{name}: {value}"),
+                    None => format!("{name}: not initialized"),
+                })
+                .collect();
+            Ok(lines.join("\n"))
+        })
+        .with_description("Prints the current value of every state available to the console"),
+    );
+    commands.add(
+        ConsoleCommand::new("set_state", |world, args| {
+            let name = args.arg::<String>(0);
+            let set = world
+                .resource::<ConsoleStates>()
+                .states
+                .get(name.as_str())
+                .map(|state| state.set)
+                .ok_or_else(|| format!("No state named `{name}` is available to the console"))?;
+            set(world, args.arg::<String>(1))
+        })
+        .with_description("Queues a transition of a state to one of its variants")
+        .with_arg::<String>("state")
+        .with_arg::<String>("variant")
+        .with_completer(|world, previous, _| {
+            let states = &world.resource::<ConsoleStates>().states;
+            match previous {
+                [] => states.keys().map(ToString::to_string).collect(),
+                [name] => states
+                    .get(name)
+                    .map(|state| (state.variants)().into_iter().map(String::from).collect())
+                    .unwrap_or_default(),
+                _ => Vec::new(),
+            }
+        }),
+    );
+    commands.add(
+        ConsoleCommand::new("diagnostics", |world, args| {
+            let mut config = world
+                .get_resource_mut::<DiagnosticsOverlayConfig>()
+                .ok_or("The `DiagnosticsOverlayPlugin` is not added")?;
+            let enabled = args.get::<bool>(0).copied().unwrap_or(!config.enabled);
+            config.enabled = enabled;
+            Ok(format!("Diagnostics overlay {}", on_off(enabled)))
+        })
+        .with_description("Shows or hides the diagnostics overlay, toggling it by default")
+        .with_optional_arg::<bool>("enabled"),
+    );
+    commands.add(
+        ConsoleCommand::new("diagnostic", |world, args| {
+            let path = args.arg::<String>(0);
+            let mut store = world
+                .get_resource_mut::<DiagnosticsStore>()
+                .ok_or("The world has no `DiagnosticsStore`")?;
+            let diagnostic = store
+                .get_mut(&DiagnosticPath::new(path.clone()))
+                .ok_or_else(|| format!("No diagnostic at `{path}`"))?;
+            let enabled = args
+                .get::<bool>(1)
+                .copied()
+                .unwrap_or(!diagnostic.is_enabled);
+            diagnostic.is_enabled = enabled;
+            Ok(format!("Diagnostic `{path}` {}", on_off(enabled)))
+        })
+        .with_description("Enables or disables measuring a diagnostic, toggling it by default")
+        .with_arg::<String>("path")
+        .with_optional_arg::<bool>("enabled")
+        .with_completer(|world, previous, _| match previous {
+            [] => world
+                .get_resource::<DiagnosticsStore>()
+                .map(|store| {
+                    store
+                        .iter()
+                        .map(|diagnostic| diagnostic.path().to_string())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            [_] => vec!["false".to_string(), "true".to_string()],
+            _ => Vec::new(),
+        }),
+    );
+}
+
+fn help(world: &mut World, args: &ConsoleArgs) -> ConsoleResult {
+    let commands = world.resource::<ConsoleCommands>();
+    if let Some(name) = args.get::<String>(0) {
+        let command = commands
+            .get(name)
+            .ok_or_else(|| format!("Unknown command `{name}`"))?;
+        return Ok(format!("{}\n  {}", command.usage(), command.description()));
+    }
+    let lines: Vec<_> = commands
+        .iter()
+        .map(|command| format!("{} - {}", command.usage(), command.description()))
+        .collect();
+    Ok(lines.join("\n"))
+}
+
+fn spawn_scene(world: &mut World, args: &ConsoleArgs) -> ConsoleResult {
+    let path = args.arg::<String>(0);
+    let asset_server = world
+        .get_resource::<AssetServer>()
+        .ok_or("The world has no `AssetServer`")?
+        .clone();
+    let mut spawner = world
+        .get_resource_mut::<SceneSpawner>()
+        .ok_or("The `ScenePlugin` is not added")?;
+    if path.ends_with(".scn") || path.ends_with(".scn.ron") {
+        spawner.spawn_dynamic(asset_server.load::<DynamicScene>(path.clone()));
+    } else {
+        spawner.spawn(asset_server.load::<Scene>(path.clone()));
+    }
+    Ok(format!("Spawning `{path}`"))
+}
+
+/// Finds an entity from its debug representation, like `4v1`, or just its index, like `4`.
+fn find_entity(world: &World, text: &str) -> Result<Entity, String> {
+    let (index, has_generation) = match text.split_once('v') {
+        Some((index, _)) => (index, true),
+        None => (text, false),
+    };
+    index
+        .parse()
+        .ok()
+        .and_then(|index| world.entities().resolve_from_id(index))
+        .filter(|&entity| world.get_entity(entity).is_some())
+        .filter(|entity| !has_generation || format!("{entity:?}") == text)
+        .ok_or_else(|| format!("No entity `{text}`"))
+}
+
+fn complete_entity_path(world: &World, previous: &[&str], word: &str) -> Vec<String> {
+    match previous {
+        [_] => complete_component_path(world, word),
+        _ => Vec::new(),
+    }
+}
+
+fn on_off(enabled: bool) -> &'static str {
+    if enabled {
+        "enabled"
+    } else {
+        "disabled"
+    }
+}
@@ -0,0 +1,562 @@
+//! Registration, parsing and completion of [console commands](ConsoleCommand).
+
+use std::{any::TypeId, borrow::Cow, collections::BTreeMap, sync::Arc};
+
+use bevy_ecs::{
+    reflect::{AppTypeRegistry, ReflectComponent},
+    system::Resource,
+    world::World,
+};
+use bevy_reflect::{
+    std_traits::ReflectValueFormat, Reflect, TypeInfo, TypePath, TypeRegistry, VariantInfo,
+};
+
+/// The result of running a [`ConsoleCommand`]: the text printed to the console, or an error.
+pub type ConsoleResult = Result<String, String>;
+
+type ConsoleHandler = dyn Fn(&mut World, &ConsoleArgs) -> ConsoleResult + Send + Sync;
+
+/// Lists completions for an argument, given the arguments before it and the text typed so far
+/// for it.
+pub type ConsoleCompleter = fn(&World, &[&str], &str) -> Vec<String>;
+
+/// A command that can be typed into the [developer console](super::DevConsolePlugin).
+///
+/// The arguments of a command are typed: each one is parsed from its text with the
+/// [`ReflectValueFormat`] registered for its type in the [`AppTypeRegistry`] before the handler
+/// runs, so handlers only deal with valid values.
+///
+/// ```
+/// # use bevy_dev_tools::console::ConsoleCommand;
+/// let command = ConsoleCommand::new("gravity", |_world, args| {
+///     let gravity = args.arg::<f32>(0);
+///     Ok(format!("Gravity set to {gravity}"))
+/// })
+/// .with_description("Sets the strength of gravity")
+/// .with_arg::<f32>("strength");
+/// ```
+pub struct ConsoleCommand {
+    name: Cow<'static, str>,
+    description: Cow<'static, str>,
+    args: Vec<ConsoleArg>,
+    handler: Box<ConsoleHandler>,
+    completer: Option<ConsoleCompleter>,
+}
+
+/// A typed argument of a [`ConsoleCommand`].
+#[derive(Clone, Debug)]
+pub struct ConsoleArg {
+    /// The name of the argument, shown in the help.
+    pub name: Cow<'static, str>,
+    /// The type the argument is parsed into.
+    pub type_id: TypeId,
+    /// The type path of the argument type, used in error messages.
+    pub type_path: &'static str,
+    /// Whether the argument may be left out.
+    pub optional: bool,
+}
+
+impl ConsoleCommand {
+    /// Creates a command without arguments, running `handler` when invoked.
+    pub fn new(
+        name: impl Into<Cow<'static, str>>,
+        handler: impl Fn(&mut World, &ConsoleArgs) -> ConsoleResult + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            description: Cow::Borrowed(""),
+            args: Vec::new(),
+            handler: Box::new(handler),
+            completer: None,
+        }
+    }
+
+    /// Sets the description shown by `help`.
+    #[must_use]
+    pub fn with_description(mut self, description: impl Into<Cow<'static, str>>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    /// Adds a required argument of type `T`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an optional argument was added before.
+    #[must_use]
+    pub fn with_arg<T: Reflect + TypePath>(self, name: impl Into<Cow<'static, str>>) -> Self {
+        assert!(
+            self.args.iter().all(|arg| !arg.optional),
+            "required arguments of the `{}` console command can't follow optional ones",
+            self.name
+        );
+        self.push_arg::<T>(name.into(), false)
+    }
+
+    /// Adds an optional argument of type `T`, after all required arguments.
+    #[must_use]
+    pub fn with_optional_arg<T: Reflect + TypePath>(
+        self,
+        name: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        self.push_arg::<T>(name.into(), true)
+    }
+
+    /// Sets the function listing completions for the arguments of the command.
+    ///
+    /// Without one, `bool` arguments and arguments of enum types with unit variants are completed
+    /// from their [type info](bevy_reflect::Typed).
+    #[must_use]
+    pub fn with_completer(mut self, completer: ConsoleCompleter) -> Self {
+        self.completer = Some(completer);
+        self
+    }
+
+    /// The name the command is invoked with.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The description of the command.
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// The arguments of the command.
+    pub fn args(&self) -> &[ConsoleArg] {
+        &self.args
+    }
+
+    /// The usage of the command, like `name <required> [optional]`.
+    pub fn usage(&self) -> String {
+        let mut usage = self.name.to_string();
+        for arg in &self.args {
+            if arg.optional {
+                usage.push_str(&format!(" [{}]", arg.name));
+            } else {
+                usage.push_str(&format!(" <{}>", arg.name));
+            }
+        }
+        usage
+    }
+
+    fn push_arg<T: Reflect + TypePath>(mut self, name: Cow<'static, str>, optional: bool) -> Self {
+        self.args.push(ConsoleArg {
+            name,
+            type_id: TypeId::of::<T>(),
+            type_path: T::type_path(),
+            optional,
+        });
+        self
+    }
+
+    /// Parses the arguments of the command from `words`.
+    pub fn parse_args(
+        &self,
+        registry: &TypeRegistry,
+        words: &[String],
+    ) -> Result<ConsoleArgs, String> {
+        let required = self.args.iter().filter(|arg| !arg.optional).count();
+        if words.len() < required || words.len() > self.args.len() {
+            return Err(format!("Usage: {}", self.usage()));
+        }
+
+        let mut values = Vec::with_capacity(words.len());
+        for (arg, word) in self.args.iter().zip(words) {
+            let format = registry
+                .get_type_data::<ReflectValueFormat>(arg.type_id)
+                .ok_or_else(|| {
+                    format!(
+                        "The `{}` argument can't be parsed, `{}` has no registered text format",
+                        arg.name, arg.type_path
+                    )
+                })?;
+            let value = format.parse(word).ok_or_else(|| {
+                format!(
+                    "`{word}` is not a valid `{}` for the `{}` argument",
+                    arg.type_path, arg.name
+                )
+            })?;
+            values.push(value);
+        }
+        Ok(ConsoleArgs { values })
+    }
+
+    fn completions(&self, world: &World, previous: &[&str], word: &str) -> Vec<String> {
+        if let Some(completer) = self.completer {
+            return completer(world, previous, word);
+        }
+        let Some(arg) = self.args.get(previous.len()) else {
+            return Vec::new();
+        };
+        if arg.type_id == TypeId::of::<bool>() {
+            return vec!["false".to_string(), "true".to_string()];
+        }
+        let Some(registry) = world.get_resource::<AppTypeRegistry>() else {
+            return Vec::new();
+        };
+        let registry = registry.read();
+        match registry.get_type_info(arg.type_id) {
+            Some(TypeInfo::Enum(info)) => info
+                .iter()
+                .filter(|variant| matches!(variant, VariantInfo::Unit(_)))
+                .map(|variant| variant.name().to_string())
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// The parsed arguments passed to the handler of a [`ConsoleCommand`].
+#[derive(Debug, Default)]
+pub struct ConsoleArgs {
+    values: Vec<Box<dyn Reflect>>,
+}
+
+impl ConsoleArgs {
+    /// Returns the argument at `index`, or `None` if an optional argument was left out.
+    ///
+    /// Also returns `None` if the argument is not of type `T`.
+    pub fn get<T: Reflect>(&self, index: usize) -> Option<&T> {
+        self.values.get(index)?.downcast_ref()
+    }
+
+    /// Returns the required argument at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no argument at `index`, or if it is not of type `T`.
+    pub fn arg<T: Reflect>(&self, index: usize) -> &T {
+        self.get(index).unwrap_or_else(|| {
+            panic!(
+                "console argument {index} is missing or not a `{}`",
+                std::any::type_name::<T>()
+            )
+        })
+    }
+
+    /// The number of arguments given.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if no arguments were given.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+/// The [console commands](ConsoleCommand) that can be run, by name.
+#[derive(Resource, Default, Clone)]
+pub struct ConsoleCommands {
+    commands: BTreeMap<String, Arc<ConsoleCommand>>,
+}
+
+impl ConsoleCommands {
+    /// Adds a command, replacing any command with the same name.
+    pub fn add(&mut self, command: ConsoleCommand) {
+        self.commands
+            .insert(command.name.to_string(), Arc::new(command));
+    }
+
+    /// Returns the command with the given name.
+    pub fn get(&self, name: &str) -> Option<&ConsoleCommand> {
+        self.commands.get(name).map(AsRef::as_ref)
+    }
+
+    /// Iterates over the commands, sorted by name.
+    pub fn iter(&self) -> impl Iterator<Item = &ConsoleCommand> {
+        self.commands.values().map(AsRef::as_ref)
+    }
+}
+
+/// Runs a line of console input as a command.
+///
+/// The first word of `line` names the command, the other words are its arguments. Words are
+/// separated by whitespace, unless quoted with `"`.
+pub fn run_console_command(world: &mut World, line: &str) -> ConsoleResult {
+    let words = split_words(line)?;
+    let Some((name, words)) = words.split_first() else {
+        return Ok(String::new());
+    };
+    let command = world
+        .get_resource::<ConsoleCommands>()
+        .and_then(|commands| commands.commands.get(name))
+        .cloned()
+        .ok_or_else(|| format!("Unknown command `{name}`, type `help` to list the commands"))?;
+    let args = {
+        let registry = world
+            .get_resource::<AppTypeRegistry>()
+            .ok_or("The world has no `AppTypeRegistry` resource")?
+            .read();
+        command.parse_args(&registry, words)?
+    };
+    (command.handler)(world, &args)
+}
+
+/// Lists the completions of the last word of `line`, sorted and without duplicates.
+pub fn complete_console_command(world: &World, line: &str) -> Vec<String> {
+    let words: Vec<_> = line.split_whitespace().collect();
+    let typing_new_word = line.is_empty() || line.ends_with(char::is_whitespace);
+    let (index, word) = match (words.split_last(), typing_new_word) {
+        (Some(_), true) | (None, _) => (words.len(), ""),
+        (Some((last, _)), false) => (words.len() - 1, *last),
+    };
+
+    let mut completions = match index {
+        0 => world
+            .get_resource::<ConsoleCommands>()
+            .map(|commands| commands.commands.keys().cloned().collect())
+            .unwrap_or_default(),
+        _ => world
+            .get_resource::<ConsoleCommands>()
+            .and_then(|commands| commands.get(words[0]))
+            .map(|command| command.completions(world, &words[1..index], word))
+            .unwrap_or_default(),
+    };
+    completions.retain(|completion| completion.starts_with(word));
+    completions.sort();
+    completions.dedup();
+    completions
+}
+
+/// Completes a path to a value in a reflected component, like `Transform.translation.x`.
+///
+/// Components are completed by their short type path, fields of structs by their name.
+pub fn complete_component_path(world: &World, path: &str) -> Vec<String> {
+    let Some(registry) = world.get_resource::<AppTypeRegistry>() else {
+        return Vec::new();
+    };
+    let registry = registry.read();
+
+    let mut segments: Vec<_> = path.split('.').collect();
+    let last = segments.pop().unwrap_or_default();
+    let Some((component, fields)) = segments.split_first() else {
+        return registry
+            .iter()
+            .filter(|registration| registration.data::<ReflectComponent>().is_some())
+            .map(|registration| registration.type_info().type_path_table().short_path())
+            .filter(|name| name.starts_with(last))
+            .map(ToString::to_string)
+            .collect();
+    };
+
+    let Some(mut type_info) = registry
+        .get_with_short_type_path(component)
+        .map(|registration| registration.type_info())
+    else {
+        return Vec::new();
+    };
+    for field in fields {
+        let field = match type_info {
+            TypeInfo::Struct(info) => info.field(field).map(|field| field.type_id()),
+            _ => None,
+        };
+        let Some(field_info) = field.and_then(|type_id| registry.get_type_info(type_id)) else {
+            return Vec::new();
+        };
+        type_info = field_info;
+    }
+
+    let TypeInfo::Struct(info) = type_info else {
+        return Vec::new();
+    };
+    let prefix = &path[..path.len() - last.len()];
+    info.iter()
+        .map(|field| field.name())
+        .filter(|name| name.starts_with(last))
+        .map(|name| format!("{prefix}{name}"))
+        .collect()
+}
+
+/// Splits a line into words at whitespace, keeping whitespace inside of `"` quotes.
+///
+/// Inside of quotes, `\"` and `\\` stand for a quote and a backslash.
+pub fn split_words(line: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut chars = line.chars();
+    let mut word: Option<String> = None;
+    while let Some(char) = chars.next() {
+        match char {
+            '"' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(escaped @ ('"' | '\\')) => word.push(escaped),
+                            Some(other) => {
+                                word.push('\\');
+                                word.push(other);
+                            }
+                            None => return Err("Unterminated quote".to_string()),
+                        },
+                        Some(other) => word.push(other),
+                        None => return Err("Unterminated quote".to_string()),
+                    }
+                }
+            }
+            char if char.is_whitespace() => words.extend(word.take()),
+            char => word.get_or_insert_with(String::new).push(char),
+        }
+    }
+    words.extend(word);
+    Ok(words)
+}
+
+/// Returns the longest prefix shared by all `words`.
+pub(crate) fn common_prefix(words: &[String]) -> &str {
+    let Some((first, rest)) = words.split_first() else {
+        return "";
+    };
+    let mut len = first.len();
+    for word in rest {
+        len = first
+            .char_indices()
+            .zip(word.chars())
+            .take_while(|((_, a), b)| a == b)
+            .last()
+            .map_or(0, |((index, char), _)| index + char.len_utf8())
+            .min(len);
+    }
+    &first[..len]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::component::Component;
+    use bevy_reflect::Reflect;
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Player {
+        speed: f32,
+        position: Position,
+    }
+
+    #[derive(Reflect, Default)]
+    struct Position {
+        x: f32,
+        y: f32,
+    }
+
+    #[derive(Reflect, Debug, Clone, Copy, PartialEq)]
+    enum Difficulty {
+        Easy,
+        Hard,
+        Custom(f32),
+    }
+
+    fn world() -> World {
+        let mut world = World::new();
+        let type_registry = AppTypeRegistry::default();
+        {
+            let mut registry = type_registry.write();
+            registry.register::<Player>();
+            registry.register::<Position>();
+            registry.register::<Difficulty>();
+            registry.register_type_data::<f32, ReflectValueFormat>();
+            registry.register_type_data::<String, ReflectValueFormat>();
+        }
+        world.insert_resource(type_registry);
+
+        let mut commands = ConsoleCommands::default();
+        commands.add(
+            ConsoleCommand::new("greet", |_, args| {
+                let name = args.arg::<String>(0);
+                Ok(match args.get::<f32>(1) {
+                    Some(times) => format!("Hello {name} x{times}"),
+                    None => format!("Hello {name}"),
+                })
+            })
+            .with_arg::<String>("name")
+            .with_optional_arg::<f32>("times"),
+        );
+        commands.add(ConsoleCommand::new("gravity", |_, _| Ok(String::new())));
+        commands.add(
+            ConsoleCommand::new("difficulty", |_, _| Ok(String::new()))
+                .with_arg::<Difficulty>("difficulty")
+                .with_arg::<bool>("hardcore"),
+        );
+        world.insert_resource(commands);
+        world
+    }
+
+    #[test]
+    fn split_line_into_words() {
+        assert_eq!(split_words("  a  bc ").unwrap(), ["a", "bc"]);
+        assert_eq!(
+            split_words(r#"say "hello world" x"y z""#).unwrap(),
+            ["say", "hello world", "xy z"]
+        );
+        assert_eq!(split_words(r#""a \"b\" \\""#).unwrap(), [r#"a "b" \"#]);
+        assert_eq!(split_words(r#""""#).unwrap(), [""]);
+        assert!(split_words(r#"say "hello"#).is_err());
+    }
+
+    #[test]
+    fn run_commands() {
+        let mut world = world();
+        assert_eq!(
+            run_console_command(&mut world, r#"greet "Ada L""#),
+            Ok("Hello Ada L".to_string())
+        );
+        assert_eq!(
+            run_console_command(&mut world, "greet Ada 2.5"),
+            Ok("Hello Ada x2.5".to_string())
+        );
+        assert_eq!(run_console_command(&mut world, ""), Ok(String::new()));
+        assert_eq!(
+            run_console_command(&mut world, "greet"),
+            Err("Usage: greet <name> [times]".to_string())
+        );
+        assert!(run_console_command(&mut world, "greet Ada twice").is_err());
+        assert!(run_console_command(&mut world, "jump").is_err());
+        // `Difficulty` has no text format.
+        assert!(run_console_command(&mut world, "difficulty Easy true").is_err());
+    }
+
+    #[test]
+    fn complete_commands_and_arguments() {
+        let world = world();
+        assert_eq!(complete_console_command(&world, "g"), ["gravity", "greet"]);
+        assert_eq!(complete_console_command(&world, "gra"), ["gravity"]);
+        assert_eq!(
+            complete_console_command(&world, "greet "),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            complete_console_command(&world, "difficulty "),
+            ["Easy", "Hard"]
+        );
+        assert_eq!(
+            complete_console_command(&world, "difficulty Easy t"),
+            ["true"]
+        );
+    }
+
+    #[test]
+    fn complete_component_paths() {
+        let world = world();
+        assert_eq!(complete_component_path(&world, "Pla"), ["Player"]);
+        let mut fields = complete_component_path(&world, "Player.");
+        fields.sort();
+        assert_eq!(fields, ["Player.position", "Player.speed"]);
+        assert_eq!(
+            complete_component_path(&world, "Player.position.y"),
+            ["Player.position.y"]
+        );
+        assert!(complete_component_path(&world, "Player.speed.").is_empty());
+    }
+
+    #[test]
+    fn common_prefix_of_words() {
+        let words = |words: &[&str]| words.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(common_prefix(&words(&["gravity", "greet"])), "gr");
+        assert_eq!(common_prefix(&words(&["gravity"])), "gravity");
+        assert_eq!(common_prefix(&words(&["été", "étage"])), "ét");
+        assert_eq!(common_prefix(&words(&["a", "b"])), "");
+        assert_eq!(common_prefix(&[]), "");
+    }
+}
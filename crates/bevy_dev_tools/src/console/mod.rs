@@ -0,0 +1,570 @@
+//! An in-game developer console, to inspect and tweak a running app by typing commands.
+//!
+//! Add the [`DevConsolePlugin`] and press the <kbd>Backquote</kbd> key to open the console. Type
+//! `help` to list the available commands, <kbd>Tab</kbd> completes the word being typed, and
+//! <kbd>Up</kbd> and <kbd>Down</kbd> go through the previously run commands.
+//!
+//! The console starts with commands to read and set component values
+//! (see [`World::set_component_value`]), spawn scenes, set states and toggle diagnostics.
+//! Apps add their own [`ConsoleCommand`]s with [`AppConsoleBuilder::add_console_command`],
+//! and make their states settable with [`AppConsoleBuilder::add_console_state`].
+//!
+//! ```no_run
+//! # use bevy_app::App;
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_reflect::Reflect;
+//! use bevy_dev_tools::console::{AppConsoleBuilder, ConsoleCommand, DevConsolePlugin};
+//!
+//! #[derive(States, Reflect, Default, Debug, Clone, PartialEq, Eq, Hash)]
+//! enum GameState {
+//!     #[default]
+//!     Playing,
+//!     Paused,
+//! }
+//!
+//! #[derive(Resource)]
+//! struct Gravity(f32);
+//!
+//! App::new()
+//!     .add_plugins(DevConsolePlugin::default())
+//!     .init_state::<GameState>()
+//!     .add_console_state::<GameState>()
+//!     .add_console_command(
+//!         ConsoleCommand::new("gravity", |world, args| {
+//!             world.resource_mut::<Gravity>().0 = *args.arg::<f32>(0);
+//!             Ok(String::new())
+//!         })
+//!         .with_description("Sets the strength of gravity")
+//!         .with_arg::<f32>("strength"),
+//!     );
+//! ```
+//!
+//! Keys typed into the console still reach the rest of the app,
+//! systems reacting to keyboard input can check [`DevConsole::open`] to ignore them.
+//!
+//! [`World::set_component_value`]: bevy_ecs::world::World::set_component_value
+
+mod builtin;
+mod command;
+
+pub use builtin::ConsoleStates;
+pub use command::{
+    complete_component_path, complete_console_command, run_console_command, split_words,
+    ConsoleArg, ConsoleArgs, ConsoleCommand, ConsoleCommands, ConsoleCompleter, ConsoleResult,
+};
+
+use std::collections::VecDeque;
+
+use bevy_app::{App, Plugin, Update};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    event::EventReader,
+    query::{With, Without},
+    schedule::{common_conditions::resource_changed, Condition, IntoSystemConfigs, States},
+    system::{Commands, Query, Res, ResMut, Resource},
+    world::World,
+};
+use bevy_hierarchy::{BuildChildren, DespawnRecursiveExt};
+use bevy_input::{keyboard::KeyCode, ButtonInput};
+use bevy_reflect::{FromReflect, TypePath, Typed};
+use bevy_render::color::Color;
+use bevy_text::{Text, TextSection, TextStyle};
+use bevy_ui::{
+    node_bundles::{NodeBundle, TextBundle},
+    Display, FlexDirection, JustifyContent, Overflow, PositionType, Style, UiRect, Val, ZIndex,
+};
+use bevy_window::ReceivedCharacter;
+
+/// A plugin adding an in-game developer console, opened with the
+/// [toggle key](DevConsoleConfig::toggle_key).
+#[derive(Default)]
+pub struct DevConsolePlugin {
+    /// The initial configuration of the console.
+    pub config: DevConsoleConfig,
+}
+
+impl Plugin for DevConsolePlugin {
+    fn build(&self, app: &mut App) {
+        builtin::add_builtin_commands(
+            &mut app
+                .world
+                .get_resource_or_insert_with::<ConsoleCommands>(Default::default),
+        );
+
+        app.insert_resource(self.config.clone())
+            .init_resource::<DevConsole>()
+            .init_resource::<ConsoleStates>()
+            .add_systems(
+                Update,
+                (
+                    toggle_console,
+                    edit_input,
+                    run_console,
+                    spawn_console.run_if(resource_changed::<DevConsoleConfig>),
+                    update_console.run_if(
+                        resource_changed::<DevConsole>
+                            .or_else(resource_changed::<DevConsoleConfig>),
+                    ),
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// A trait adding console commands and states to the app.
+pub trait AppConsoleBuilder {
+    /// Adds a command to the [developer console](DevConsolePlugin), replacing any command with the
+    /// same name.
+    fn add_console_command(&mut self, command: ConsoleCommand) -> &mut Self;
+
+    /// Makes the state `S` readable and settable from the [developer console](DevConsolePlugin)
+    /// with the `state` and `set_state` commands.
+    ///
+    /// Only the unit variants of `S` can be set.
+    fn add_console_state<S: States + FromReflect + TypePath + Typed>(&mut self) -> &mut Self;
+}
+
+impl AppConsoleBuilder for App {
+    fn add_console_command(&mut self, command: ConsoleCommand) -> &mut Self {
+        self.world
+            .get_resource_or_insert_with::<ConsoleCommands>(Default::default)
+            .add(command);
+        self
+    }
+
+    fn add_console_state<S: States + FromReflect + TypePath + Typed>(&mut self) -> &mut Self {
+        self.world
+            .get_resource_or_insert_with::<ConsoleStates>(Default::default)
+            .add::<S>();
+        self
+    }
+}
+
+/// Configuration of the [`DevConsolePlugin`].
+///
+/// Changing this resource rebuilds the console.
+#[derive(Resource, Clone, Debug)]
+pub struct DevConsoleConfig {
+    /// The key that opens and closes the console.
+    pub toggle_key: KeyCode,
+    /// The height of the console, from the top of the window.
+    pub height: Val,
+    /// The font size of the text.
+    pub text_size: f32,
+    /// The color of the output of commands.
+    pub text_color: Color,
+    /// The color of the input line, and of the commands echoed in the output.
+    pub input_color: Color,
+    /// The color of the errors of commands.
+    pub error_color: Color,
+    /// The color behind the console.
+    pub background_color: Color,
+    /// The number of output lines kept.
+    pub max_lines: usize,
+    /// The number of commands kept in the history.
+    pub max_history: usize,
+}
+
+impl Default for DevConsoleConfig {
+    fn default() -> Self {
+        Self {
+            toggle_key: KeyCode::Backquote,
+            height: Val::Percent(40.0),
+            text_size: 16.0,
+            text_color: Color::WHITE,
+            input_color: Color::rgb(0.6, 0.8, 1.0),
+            error_color: Color::rgb(1.0, 0.4, 0.4),
+            background_color: Color::rgba(0.0, 0.0, 0.0, 0.8),
+            max_lines: 200,
+            max_history: 100,
+        }
+    }
+}
+
+/// The state of the [developer console](DevConsolePlugin): whether it is open, what is typed into
+/// it, its history and its output.
+#[derive(Resource, Default, Debug)]
+pub struct DevConsole {
+    /// Whether the console is shown and receives keyboard input.
+    pub open: bool,
+    /// The text typed on the input line.
+    pub input: String,
+    history: Vec<String>,
+    /// The index in the history of the command shown on the input line, while browsing it.
+    history_index: Option<usize>,
+    lines: VecDeque<ConsoleLine>,
+    submitted: Vec<String>,
+    complete: bool,
+}
+
+/// A line of output of the [`DevConsole`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConsoleLine {
+    /// The text of the line.
+    pub text: String,
+    /// What kind of output the line is.
+    pub kind: ConsoleLineKind,
+}
+
+/// What kind of output a [`ConsoleLine`] is, which decides its color.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConsoleLineKind {
+    /// A command that was run.
+    Command,
+    /// The output of a command.
+    Output,
+    /// The error of a command.
+    Error,
+}
+
+impl DevConsole {
+    /// Queues a line to run as a command, as if it were typed into the console.
+    ///
+    /// Commands are run in [`Update`], in the order they are submitted.
+    pub fn submit(&mut self, line: impl Into<String>) {
+        self.submitted.push(line.into());
+    }
+
+    /// Prints text to the output, one [`ConsoleLine`] per line of `text`.
+    pub fn print(&mut self, text: &str) {
+        self.push_lines(text, ConsoleLineKind::Output);
+    }
+
+    /// Prints an error to the output.
+    pub fn print_error(&mut self, text: &str) {
+        self.push_lines(text, ConsoleLineKind::Error);
+    }
+
+    /// Removes all lines from the output.
+    pub fn clear(&mut self) {
+        self.lines.clear();
+    }
+
+    /// The lines of output, from oldest to newest.
+    pub fn lines(&self) -> impl Iterator<Item = &ConsoleLine> {
+        self.lines.iter()
+    }
+
+    /// The commands run from the console, from oldest to newest.
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    fn push_lines(&mut self, text: &str, kind: ConsoleLineKind) {
+        self.lines.extend(text.lines().map(|line| ConsoleLine {
+            text: line.to_string(),
+            kind,
+        }));
+    }
+
+    /// Shows an older (`-1`) or a more recent (`1`) command of the history on the input line.
+    fn browse_history(&mut self, step: isize) {
+        let index = match self.history_index {
+            Some(index) => index.checked_add_signed(step),
+            None if step < 0 => self.history.len().checked_sub(1),
+            None => return,
+        };
+        match index.filter(|&index| index < self.history.len()) {
+            Some(index) => {
+                self.history_index = Some(index);
+                self.input = self.history[index].clone();
+            }
+            // Going past the most recent command clears the input line.
+            None if step > 0 => {
+                self.history_index = None;
+                self.input.clear();
+            }
+            None => {}
+        }
+    }
+
+    /// Replaces the word being typed with the longest prefix shared by its `completions`,
+    /// listing them in the output if there are more than one.
+    fn apply_completions(&mut self, completions: &[String]) {
+        let word_start = self
+            .input
+            .rfind(char::is_whitespace)
+            .map_or(0, |index| index + 1);
+        match completions {
+            [] => {}
+            [completion] => {
+                self.input.replace_range(word_start.., completion);
+                self.input.push(' ');
+            }
+            _ => {
+                let prefix = command::common_prefix(completions).to_string();
+                self.input.replace_range(word_start.., &prefix);
+                self.print(&completions.join("  "));
+            }
+        }
+    }
+}
+
+/// Marks the root node of the console.
+#[derive(Component)]
+pub struct DevConsoleNode;
+
+/// The text showing the output of the console.
+#[derive(Component)]
+struct ConsoleOutputText;
+
+/// The text showing the input line of the console.
+#[derive(Component)]
+struct ConsoleInputText;
+
+fn toggle_console(
+    mut console: ResMut<DevConsole>,
+    config: Res<DevConsoleConfig>,
+    keyboard: Option<Res<ButtonInput<KeyCode>>>,
+) {
+    let Some(keyboard) = keyboard else {
+        return;
+    };
+    if keyboard.just_pressed(config.toggle_key) {
+        console.open = !console.open;
+    } else if console.open && keyboard.just_pressed(KeyCode::Escape) {
+        console.open = false;
+    }
+}
+
+fn edit_input(
+    mut console: ResMut<DevConsole>,
+    config: Res<DevConsoleConfig>,
+    keyboard: Option<Res<ButtonInput<KeyCode>>>,
+    mut characters: EventReader<ReceivedCharacter>,
+) {
+    let Some(keyboard) = keyboard else {
+        return;
+    };
+    // The character of the toggle key is not part of the input.
+    if !console.open || keyboard.just_pressed(config.toggle_key) {
+        characters.clear();
+        return;
+    }
+
+    for event in characters.read() {
+        let typed = event.char.chars().filter(|char| !char.is_control());
+        for char in typed {
+            console.input.push(char);
+            console.history_index = None;
+        }
+    }
+    if keyboard.just_pressed(KeyCode::Backspace) {
+        console.input.pop();
+    }
+    if keyboard.just_pressed(KeyCode::Tab) {
+        console.complete = true;
+    }
+    if keyboard.just_pressed(KeyCode::ArrowUp) {
+        console.browse_history(-1);
+    }
+    if keyboard.just_pressed(KeyCode::ArrowDown) {
+        console.browse_history(1);
+    }
+    if keyboard.just_pressed(KeyCode::Enter) {
+        let line = std::mem::take(&mut console.input);
+        console.history_index = None;
+        console.submit(line);
+    }
+}
+
+/// Completes the input line and runs the submitted commands, which need exclusive world access.
+fn run_console(world: &mut World) {
+    let mut console = world.resource_mut::<DevConsole>();
+    if !console.complete && console.submitted.is_empty() {
+        return;
+    }
+    let submitted = std::mem::take(&mut console.submitted);
+
+    if std::mem::take(&mut console.complete) {
+        let input = console.input.clone();
+        let completions = complete_console_command(world, &input);
+        world
+            .resource_mut::<DevConsole>()
+            .apply_completions(&completions);
+    }
+
+    let config = world.resource::<DevConsoleConfig>().clone();
+    for line in submitted {
+        let line = line.trim();
+        let mut console = world.resource_mut::<DevConsole>();
+        console.push_lines(&format!("> {line}"), ConsoleLineKind::Command);
+        if line.is_empty() {
+            continue;
+        }
+        if console.history.last().map(String::as_str) != Some(line) {
+            console.history.push(line.to_string());
+            let excess = console.history.len().saturating_sub(config.max_history);
+            console.history.drain(..excess);
+        }
+
+        let result = run_console_command(world, line);
+        let mut console = world.resource_mut::<DevConsole>();
+        match result {
+            Ok(output) => console.print(&output),
+            Err(error) => console.print_error(&error),
+        }
+    }
+
+    let mut console = world.resource_mut::<DevConsole>();
+    let excess = console.lines.len().saturating_sub(config.max_lines);
+    console.lines.drain(..excess);
+}
+
+/// Despawns the current console, if any, and spawns one matching the [`DevConsoleConfig`].
+fn spawn_console(
+    mut commands: Commands,
+    config: Res<DevConsoleConfig>,
+    consoles: Query<Entity, With<DevConsoleNode>>,
+) {
+    for console in &consoles {
+        commands.entity(console).despawn_recursive();
+    }
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    display: Display::None,
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(0.0),
+                    left: Val::Px(0.0),
+                    width: Val::Percent(100.0),
+                    height: config.height,
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::FlexEnd,
+                    overflow: Overflow::clip(),
+                    padding: UiRect::all(Val::Px(4.0)),
+                    row_gap: Val::Px(4.0),
+                    ..Default::default()
+                },
+                background_color: config.background_color.into(),
+                z_index: ZIndex::Global(i32::MAX),
+                ..Default::default()
+            },
+            DevConsoleNode,
+        ))
+        .with_children(|parent| {
+            parent.spawn((TextBundle::from_sections([]), ConsoleOutputText));
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: config.text_size,
+                        color: config.input_color,
+                        ..Default::default()
+                    },
+                ),
+                ConsoleInputText,
+            ));
+        });
+}
+
+fn update_console(
+    console: Res<DevConsole>,
+    config: Res<DevConsoleConfig>,
+    mut nodes: Query<&mut Style, With<DevConsoleNode>>,
+    mut output: Query<&mut Text, With<ConsoleOutputText>>,
+    mut input: Query<&mut Text, (With<ConsoleInputText>, Without<ConsoleOutputText>)>,
+) {
+    let display = if console.open {
+        Display::Flex
+    } else {
+        Display::None
+    };
+    for mut style in &mut nodes {
+        style.display = display;
+    }
+    if !console.open {
+        return;
+    }
+
+    for mut text in &mut output {
+        let count = console.lines.len();
+        text.sections = console
+            .lines
+            .iter()
+            .enumerate()
+            .map(|(index, line)| {
+                let color = match line.kind {
+                    ConsoleLineKind::Command => config.input_color,
+                    ConsoleLineKind::Output => config.text_color,
+                    ConsoleLineKind::Error => config.error_color,
+                };
+                let mut value = line.text.clone();
+                if index + 1 < count {
+                    value.push('\n');
+                }
+                TextSection::new(
+                    value,
+                    TextStyle {
+                        font_size: config.text_size,
+                        color,
+                        ..Default::default()
+                    },
+                )
+            })
+            .collect();
+    }
+    for mut text in &mut input {
+        text.sections[0].value = format!("> {}_", console.input);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn console_with_history() -> DevConsole {
+        DevConsole {
+            history: vec!["a".to_string(), "b".to_string()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn browse_history() {
+        let mut console = console_with_history();
+        console.browse_history(1);
+        assert_eq!(console.input, "");
+        console.browse_history(-1);
+        assert_eq!(console.input, "b");
+        console.browse_history(-1);
+        assert_eq!(console.input, "a");
+        console.browse_history(-1);
+        assert_eq!(console.input, "a");
+        console.browse_history(1);
+        assert_eq!(console.input, "b");
+        console.browse_history(1);
+        assert_eq!(console.input, "");
+        assert_eq!(console.history_index, None);
+    }
+
+    #[test]
+    fn apply_completions() {
+        let mut console = DevConsole {
+            input: "set 1v1 Tra".to_string(),
+            ..Default::default()
+        };
+        console.apply_completions(&["Transform".to_string()]);
+        assert_eq!(console.input, "set 1v1 Transform ");
+
+        console.input = "set 1v1 Transform.".to_string();
+        console.apply_completions(&[
+            "Transform.rotation".to_string(),
+            "Transform.translation".to_string(),
+        ]);
+        assert_eq!(console.input, "set 1v1 Transform.");
+        assert_eq!(
+            console.lines().last().unwrap().text,
+            "Transform.rotation  Transform.translation"
+        );
+
+        console.input = "gr".to_string();
+        console.apply_completions(&["gravity".to_string(), "greet".to_string()]);
+        assert_eq!(console.input, "gr");
+        console.apply_completions(&[]);
+        assert_eq!(console.input, "gr");
+    }
+}
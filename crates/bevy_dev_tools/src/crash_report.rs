@@ -0,0 +1,372 @@
+//! Crash reports for the systems that panic, to attach to bug reports.
+//!
+//! Add the [`CrashReportPlugin`] to catch the panics of systems. The first panic is written to a
+//! report in the [`CrashReportConfig::directory`], with the system that panicked, its schedule,
+//! the panic message and location, and the backtrace when `RUST_BACKTRACE` is set. The entities
+//! and resources that can be reflected are dumped next to it as a scene, so playtesters can send
+//! both files and the state of the world can be loaded back as a
+//! [`DynamicScene`](bevy_scene::DynamicScene).
+//!
+//! By default the app still exits once the report is written. With
+//! [`CrashReportConfig::keep_running`], it keeps running and rendering instead, and shows an
+//! overlay describing the crash. The systems after the one that panicked are skipped for that
+//! run of its schedule, and later panics are not reported again.
+//!
+//! ```no_run
+//! # use bevy_app::App;
+//! use bevy_dev_tools::crash_report::{CrashReportConfig, CrashReportPlugin};
+//!
+//! App::new().add_plugins(CrashReportPlugin {
+//!     config: CrashReportConfig {
+//!         keep_running: true,
+//!         ..Default::default()
+//!     },
+//! });
+//! ```
+
+use std::{
+    backtrace::{Backtrace, BacktraceStatus},
+    fmt::{self, Display, Write as _},
+    fs, io,
+    panic::AssertUnwindSafe,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use bevy_app::{App, Last, Plugin};
+use bevy_ecs::{
+    component::Component,
+    reflect::AppTypeRegistry,
+    schedule::{
+        common_conditions::resource_added, IntoSystemConfigs, SystemPanic, SystemPanicHandler,
+    },
+    system::{Commands, Res, Resource},
+    world::World,
+};
+use bevy_hierarchy::BuildChildren;
+use bevy_reflect::{serde::ReflectSerializer, Reflect};
+use bevy_render::color::Color;
+use bevy_scene::{DynamicSceneBuilder, SceneFilter};
+use bevy_text::TextStyle;
+use bevy_ui::{
+    node_bundles::{NodeBundle, TextBundle},
+    FlexDirection, PositionType, Style, UiRect, Val, ZIndex,
+};
+use bevy_utils::tracing::error;
+
+/// A plugin that writes a [`CrashReport`] when a system panics.
+///
+/// It installs a [`SystemPanicHandler`] in the world, and a panic hook that records where
+/// panics happen before calling the previous hook.
+#[derive(Default)]
+pub struct CrashReportPlugin {
+    /// The initial configuration of the crash reports.
+    pub config: CrashReportConfig,
+}
+
+impl Plugin for CrashReportPlugin {
+    fn build(&self, app: &mut App) {
+        let previous_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            let backtrace = Backtrace::capture();
+            let location = PanicLocation {
+                location: info.location().map(ToString::to_string),
+                backtrace: (backtrace.status() == BacktraceStatus::Captured)
+                    .then(|| backtrace.to_string()),
+            };
+            if let Ok(mut last_panic) = LAST_PANIC.lock() {
+                *last_panic = Some(location);
+            }
+            previous_hook(info);
+        }));
+
+        app.insert_resource(self.config.clone())
+            .insert_resource(SystemPanicHandler(handle_system_panic))
+            .add_systems(Last, spawn_overlay.run_if(resource_added::<CrashReport>));
+    }
+}
+
+/// Configuration of the [`CrashReportPlugin`].
+#[derive(Resource, Clone, Debug)]
+pub struct CrashReportConfig {
+    /// The directory the reports are written to, created if it doesn't exist.
+    pub directory: PathBuf,
+    /// Whether the app keeps running after a crash and shows an overlay, instead of exiting.
+    pub keep_running: bool,
+    /// Whether the entities and resources are dumped as a scene next to the report.
+    pub dump_world: bool,
+    /// The components included in the dump.
+    pub component_filter: SceneFilter,
+    /// The resources included in the dump.
+    pub resource_filter: SceneFilter,
+    /// The font size of the overlay text.
+    pub text_size: f32,
+    /// The color of the overlay text.
+    pub text_color: Color,
+    /// The color behind the overlay.
+    pub background_color: Color,
+}
+
+impl Default for CrashReportConfig {
+    fn default() -> Self {
+        Self {
+            directory: PathBuf::from("crash_reports"),
+            keep_running: false,
+            dump_world: true,
+            component_filter: SceneFilter::default(),
+            resource_filter: SceneFilter::default(),
+            text_size: 16.0,
+            text_color: Color::rgb(1.0, 0.4, 0.4),
+            background_color: Color::rgba(0.0, 0.0, 0.0, 0.8),
+        }
+    }
+}
+
+/// The first panic caught by the [`CrashReportPlugin`], inserted as a resource when it happens.
+#[derive(Resource, Clone, Debug)]
+pub struct CrashReport {
+    /// The name of the system that panicked, if the panic is attributed to one.
+    pub system: Option<String>,
+    /// The schedule that was running.
+    pub schedule: String,
+    /// The panic message.
+    pub message: String,
+    /// The file, line and column the panic was raised at, if known.
+    pub location: Option<String>,
+    /// The backtrace of the panic, if backtraces are enabled with `RUST_BACKTRACE`.
+    pub backtrace: Option<String>,
+    /// The file the report was written to, if it could be written.
+    pub path: Option<PathBuf>,
+    /// The file the world was dumped to, if it could be dumped.
+    pub world_path: Option<PathBuf>,
+}
+
+impl Display for CrashReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "system: {}", self.system.as_deref().unwrap_or("unknown"))?;
+        writeln!(f, "schedule: {}", self.schedule)?;
+        writeln!(f, "message: {}", self.message)?;
+        writeln!(
+            f,
+            "location: {}",
+            self.location.as_deref().unwrap_or("unknown")
+        )?;
+        if let Some(world_path) = &self.world_path {
+            writeln!(f, "world: {}", world_path.display())?;
+        }
+        if let Some(backtrace) = &self.backtrace {
+            write!(f, "\nbacktrace:\n{backtrace}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Where a panic happened, recorded by the panic hook of the [`CrashReportPlugin`] since the
+/// payload caught by the [`SystemPanicHandler`] doesn't carry it.
+struct PanicLocation {
+    location: Option<String>,
+    backtrace: Option<String>,
+}
+
+static LAST_PANIC: Mutex<Option<PanicLocation>> = Mutex::new(None);
+
+/// Marks the root node of the overlay.
+#[derive(Component)]
+pub struct CrashOverlay;
+
+fn handle_system_panic(world: &mut World, panic: SystemPanic) {
+    let config = world
+        .get_resource::<CrashReportConfig>()
+        .cloned()
+        .unwrap_or_default();
+
+    if !world.contains_resource::<CrashReport>() {
+        let last_panic = LAST_PANIC.lock().ok().and_then(|mut last| last.take());
+        let (location, backtrace) = last_panic
+            .map(|last| (last.location, last.backtrace))
+            .unwrap_or_default();
+        let mut report = CrashReport {
+            system: panic.system.as_deref().map(str::to_string),
+            schedule: format!("{:?}", panic.schedule),
+            message: panic.message().unwrap_or("unknown").to_string(),
+            location,
+            backtrace,
+            path: None,
+            world_path: None,
+        };
+        match write_report(world, &config, &mut report) {
+            Ok(path) => error!("A crash report was written to {}", path.display()),
+            Err(error) => error!("Could not write the crash report: {error}\n{report}"),
+        }
+        world.insert_resource(report);
+    }
+
+    if !config.keep_running {
+        std::panic::resume_unwind(panic.payload);
+    }
+}
+
+/// Writes the report to the directory of the `config`, with the dump of the world if enabled,
+/// and records their paths in the `report`.
+fn write_report(
+    world: &World,
+    config: &CrashReportConfig,
+    report: &mut CrashReport,
+) -> io::Result<PathBuf> {
+    fs::create_dir_all(&config.directory)?;
+    let name = (0..)
+        .map(|index| format!("crash_{index}"))
+        .find(|name| !report_path(&config.directory, name, "txt").exists())
+        .unwrap_or_default();
+
+    let mut dump_error = None;
+    if config.dump_world {
+        // the world may be left in an unexpected state by the panic
+        let dump = std::panic::catch_unwind(AssertUnwindSafe(|| dump_world(world, config)))
+            .unwrap_or_else(|_| Err("dumping the world panicked".to_string()));
+        match dump {
+            Ok(ron) => {
+                let path = report_path(&config.directory, &name, "scn.ron");
+                fs::write(&path, ron)?;
+                report.world_path = Some(path);
+            }
+            Err(error) => dump_error = Some(error),
+        }
+    }
+
+    let path = report_path(&config.directory, &name, "txt");
+    let mut contents = report.to_string();
+    if let Some(error) = dump_error {
+        let _ = writeln!(contents, "\nthe world could not be dumped: {error}");
+    }
+    fs::write(&path, contents)?;
+    report.path = Some(path.clone());
+    Ok(path)
+}
+
+fn report_path(directory: &Path, name: &str, extension: &str) -> PathBuf {
+    directory.join(format!("{name}.{extension}"))
+}
+
+/// Serializes the entities and resources allowed by the `config` as a scene.
+fn dump_world(world: &World, config: &CrashReportConfig) -> Result<String, String> {
+    let registry = world
+        .get_resource::<AppTypeRegistry>()
+        .ok_or("the world has no `AppTypeRegistry`")?;
+    let mut scene = DynamicSceneBuilder::from_world(world)
+        .with_filter(config.component_filter.clone())
+        .with_resource_filter(config.resource_filter.clone())
+        .extract_entities(world.iter_entities().map(|entity| entity.id()))
+        .extract_resources()
+        .build();
+
+    // a single value that can't be serialized would fail the whole scene, so they are left out
+    {
+        let registry = registry.read();
+        let serializable =
+            |value: &dyn Reflect| ron::to_string(&ReflectSerializer::new(value, &registry)).is_ok();
+        scene.resources.retain(|value| serializable(value.as_ref()));
+        for entity in &mut scene.entities {
+            entity
+                .components
+                .retain(|value| serializable(value.as_ref()));
+        }
+    }
+
+    scene
+        .serialize_ron(&registry.0)
+        .map_err(|error| error.to_string())
+}
+
+fn spawn_overlay(mut commands: Commands, config: Res<CrashReportConfig>, report: Res<CrashReport>) {
+    let mut text = format!(
+        "The app crashed in `{}`: {}",
+        report.system.as_deref().unwrap_or(&report.schedule),
+        report.message
+    );
+    if let Some(path) = &report.path {
+        let _ = write!(text, "\nA report was written to {}", path.display());
+    }
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(0.0),
+                    left: Val::Px(0.0),
+                    right: Val::Px(0.0),
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(8.0)),
+                    ..Default::default()
+                },
+                background_color: config.background_color.into(),
+                z_index: ZIndex::Global(i32::MAX),
+                ..Default::default()
+            },
+            CrashOverlay,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                text,
+                TextStyle {
+                    font_size: config.text_size,
+                    color: config.text_color,
+                    ..Default::default()
+                },
+            ));
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::Update;
+    use bevy_ecs::{reflect::ReflectComponent, system::Query};
+
+    use super::*;
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Health(u32);
+
+    #[test]
+    fn report_with_world_dump() {
+        let directory = std::env::temp_dir().join("bevy_dev_tools_crash_report");
+        let _ = fs::remove_dir_all(&directory);
+
+        let mut app = App::new();
+        app.register_type::<Health>()
+            .register_type::<u32>()
+            .insert_resource(CrashReportConfig {
+                directory: directory.clone(),
+                keep_running: true,
+                ..Default::default()
+            })
+            .insert_resource(SystemPanicHandler(handle_system_panic))
+            .add_systems(Update, |health: Query<&Health>| {
+                panic!("health is {}", health.single().0);
+            });
+        app.world.spawn(Health(3));
+        app.update();
+        app.update();
+
+        let report = app.world.resource::<CrashReport>();
+        assert_eq!(report.message, "health is 3");
+        assert_eq!(report.schedule, "Update");
+        assert!(report
+            .system
+            .as_ref()
+            .unwrap()
+            .contains("report_with_world_dump"));
+        assert_eq!(report.path, Some(directory.join("crash_0.txt")));
+
+        let contents = fs::read_to_string(directory.join("crash_0.txt")).unwrap();
+        assert!(contents.contains("message: health is 3"));
+        let dump = fs::read_to_string(report.world_path.as_ref().unwrap()).unwrap();
+        assert!(dump.contains("tests::Health"));
+
+        // only the first panic is reported
+        assert!(!directory.join("crash_1.txt").exists());
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -0,0 +1,431 @@
+//! An in-game overlay displaying the values of [diagnostics](bevy_diagnostic).
+//!
+//! Add the [`DiagnosticsOverlayPlugin`] to show the FPS, the frame time with a history graph
+//! and the entity count in the top left corner of the screen.
+//! Which diagnostics are shown, and how, is controlled by the [`DiagnosticsOverlayConfig`] resource,
+//! which can be modified at any time.
+//!
+//! ```no_run
+//! # use bevy_app::App;
+//! # use bevy_diagnostic::DiagnosticPath;
+//! use bevy_dev_tools::diagnostics_overlay::{
+//!     DiagnosticGraph, DiagnosticsOverlayConfig, DiagnosticsOverlayPlugin, OverlayDiagnostic,
+//! };
+//!
+//! const ENEMIES: DiagnosticPath = DiagnosticPath::const_new("game/enemies");
+//!
+//! App::new().add_plugins(DiagnosticsOverlayPlugin {
+//!     config: DiagnosticsOverlayConfig::default().with_diagnostic(
+//!         OverlayDiagnostic::new(ENEMIES)
+//!             .with_label("Enemies")
+//!             .with_graph(DiagnosticGraph::default()),
+//!     ),
+//! });
+//! ```
+
+use std::borrow::Cow;
+
+use bevy_app::{App, Plugin, Update};
+use bevy_diagnostic::{
+    DiagnosticPath, DiagnosticsStore, EntityCountDiagnosticsPlugin, FrameTimeDiagnosticsPlugin,
+};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::With,
+    schedule::{common_conditions::resource_changed, IntoSystemConfigs},
+    system::{Commands, Query, Res, ResMut, Resource},
+};
+use bevy_hierarchy::{BuildChildren, ChildBuilder, Children, DespawnRecursiveExt};
+use bevy_input::{keyboard::KeyCode, ButtonInput};
+use bevy_render::color::Color;
+use bevy_text::{Text, TextStyle};
+use bevy_ui::{
+    node_bundles::{NodeBundle, TextBundle},
+    AlignItems, Display, FlexDirection, PositionType, Style, UiRect, Val, ZIndex,
+};
+
+/// A plugin that displays the values of diagnostics in an overlay on top of the UI.
+///
+/// The [`FrameTimeDiagnosticsPlugin`] and [`EntityCountDiagnosticsPlugin`] are added
+/// if they are not already present, since they provide the default diagnostics.
+/// Diagnostics that are not found in the [`DiagnosticsStore`] are shown as `N/A`.
+#[derive(Default)]
+pub struct DiagnosticsOverlayPlugin {
+    /// The initial configuration of the overlay.
+    pub config: DiagnosticsOverlayConfig,
+}
+
+impl Plugin for DiagnosticsOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+            app.add_plugins(FrameTimeDiagnosticsPlugin);
+        }
+        if !app.is_plugin_added::<EntityCountDiagnosticsPlugin>() {
+            app.add_plugins(EntityCountDiagnosticsPlugin);
+        }
+
+        app.insert_resource(self.config.clone()).add_systems(
+            Update,
+            (
+                toggle_overlay,
+                spawn_overlay.run_if(resource_changed::<DiagnosticsOverlayConfig>),
+                (update_text, update_graphs),
+            )
+                .chain(),
+        );
+    }
+}
+
+/// Configuration of the [`DiagnosticsOverlayPlugin`].
+///
+/// Changing this resource rebuilds the overlay.
+#[derive(Resource, Clone, Debug)]
+pub struct DiagnosticsOverlayConfig {
+    /// Whether the overlay is shown.
+    pub enabled: bool,
+    /// The key that shows and hides the overlay, if any.
+    pub toggle_key: Option<KeyCode>,
+    /// The font size of the text.
+    pub text_size: f32,
+    /// The color of the text.
+    pub text_color: Color,
+    /// The color behind the overlay.
+    pub background_color: Color,
+    /// The diagnostics to display, from top to bottom.
+    pub diagnostics: Vec<OverlayDiagnostic>,
+}
+
+impl Default for DiagnosticsOverlayConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            toggle_key: Some(KeyCode::F12),
+            text_size: 16.0,
+            text_color: Color::WHITE,
+            background_color: Color::rgba(0.0, 0.0, 0.0, 0.6),
+            diagnostics: vec![
+                OverlayDiagnostic::new(FrameTimeDiagnosticsPlugin::FPS)
+                    .with_label("FPS")
+                    .with_precision(0),
+                OverlayDiagnostic::new(FrameTimeDiagnosticsPlugin::FRAME_TIME)
+                    .with_label("Frame time")
+                    .with_graph(DiagnosticGraph::default()),
+                OverlayDiagnostic::new(EntityCountDiagnosticsPlugin::ENTITY_COUNT)
+                    .with_label("Entities")
+                    .with_precision(0),
+            ],
+        }
+    }
+}
+
+impl DiagnosticsOverlayConfig {
+    /// Creates a configuration that displays no diagnostics.
+    pub fn empty() -> Self {
+        Self {
+            diagnostics: Vec::new(),
+            ..Self::default()
+        }
+    }
+
+    /// Adds a diagnostic to the bottom of the overlay.
+    #[must_use]
+    pub fn with_diagnostic(mut self, diagnostic: OverlayDiagnostic) -> Self {
+        self.diagnostics.push(diagnostic);
+        self
+    }
+}
+
+/// How a single diagnostic is displayed by the [`DiagnosticsOverlayPlugin`].
+#[derive(Clone, Debug)]
+pub struct OverlayDiagnostic {
+    /// The path of the diagnostic in the [`DiagnosticsStore`].
+    pub path: DiagnosticPath,
+    /// The label shown before the value. Defaults to the diagnostic path.
+    pub label: Option<Cow<'static, str>>,
+    /// The number of decimal places shown.
+    pub precision: usize,
+    /// The history graph shown below the value, if any.
+    pub graph: Option<DiagnosticGraph>,
+}
+
+impl OverlayDiagnostic {
+    /// Displays the diagnostic at `path` with two decimal places and no graph.
+    pub fn new(path: DiagnosticPath) -> Self {
+        Self {
+            path,
+            label: None,
+            precision: 2,
+            graph: None,
+        }
+    }
+
+    /// Sets the label shown before the value.
+    #[must_use]
+    pub fn with_label(mut self, label: impl Into<Cow<'static, str>>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Sets the number of decimal places shown.
+    #[must_use]
+    pub fn with_precision(mut self, precision: usize) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    /// Shows a history graph below the value.
+    #[must_use]
+    pub fn with_graph(mut self, graph: DiagnosticGraph) -> Self {
+        self.graph = Some(graph);
+        self
+    }
+
+    fn label(&self) -> &str {
+        self.label.as_deref().unwrap_or(self.path.as_str())
+    }
+}
+
+/// A bar graph of the most recent measurements of a diagnostic.
+///
+/// The number of measurements available is limited by the
+/// [maximum history length](bevy_diagnostic::Diagnostic::with_max_history_length) of the diagnostic.
+#[derive(Clone, Debug)]
+pub struct DiagnosticGraph {
+    /// The number of measurements shown.
+    pub samples: usize,
+    /// The width of each bar, in logical pixels.
+    pub bar_width: f32,
+    /// The height of the graph, in logical pixels.
+    pub height: f32,
+    /// The color of the bars.
+    pub color: Color,
+    /// The value corresponding to a full bar.
+    ///
+    /// If `None`, the graph is scaled to the largest visible measurement.
+    pub max: Option<f64>,
+}
+
+impl Default for DiagnosticGraph {
+    fn default() -> Self {
+        Self {
+            samples: 60,
+            bar_width: 3.0,
+            height: 40.0,
+            color: Color::rgb(0.2, 0.8, 0.3),
+            max: None,
+        }
+    }
+}
+
+/// Marks the root node of the overlay.
+#[derive(Component)]
+pub struct DiagnosticsOverlay;
+
+/// The text of the diagnostic at this index in [`DiagnosticsOverlayConfig::diagnostics`].
+#[derive(Component)]
+struct OverlayText(usize);
+
+/// The graph of the diagnostic at this index in [`DiagnosticsOverlayConfig::diagnostics`].
+#[derive(Component)]
+struct OverlayGraph(usize);
+
+/// A bar of an [`OverlayGraph`], where `0` is the oldest measurement shown.
+#[derive(Component)]
+struct OverlayBar(usize);
+
+fn toggle_overlay(
+    mut config: ResMut<DiagnosticsOverlayConfig>,
+    keyboard: Option<Res<ButtonInput<KeyCode>>>,
+) {
+    let (Some(key), Some(keyboard)) = (config.toggle_key, keyboard) else {
+        return;
+    };
+    if keyboard.just_pressed(key) {
+        config.enabled = !config.enabled;
+    }
+}
+
+/// Despawns the current overlay, if any, and spawns one matching the [`DiagnosticsOverlayConfig`].
+fn spawn_overlay(
+    mut commands: Commands,
+    config: Res<DiagnosticsOverlayConfig>,
+    overlays: Query<Entity, With<DiagnosticsOverlay>>,
+) {
+    for overlay in &overlays {
+        commands.entity(overlay).despawn_recursive();
+    }
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    display: if config.enabled {
+                        Display::Flex
+                    } else {
+                        Display::None
+                    },
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(0.0),
+                    left: Val::Px(0.0),
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(4.0)),
+                    row_gap: Val::Px(2.0),
+                    ..Default::default()
+                },
+                background_color: config.background_color.into(),
+                z_index: ZIndex::Global(i32::MAX),
+                ..Default::default()
+            },
+            DiagnosticsOverlay,
+        ))
+        .with_children(|parent| {
+            for (index, diagnostic) in config.diagnostics.iter().enumerate() {
+                parent.spawn((
+                    TextBundle::from_section(
+                        "",
+                        TextStyle {
+                            font_size: config.text_size,
+                            color: config.text_color,
+                            ..Default::default()
+                        },
+                    ),
+                    OverlayText(index),
+                ));
+
+                if let Some(graph) = &diagnostic.graph {
+                    spawn_graph(parent, index, graph);
+                }
+            }
+        });
+}
+
+fn spawn_graph(parent: &mut ChildBuilder, index: usize, graph: &DiagnosticGraph) {
+    parent
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Px(graph.bar_width * graph.samples as f32),
+                    height: Val::Px(graph.height),
+                    align_items: AlignItems::FlexEnd,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            OverlayGraph(index),
+        ))
+        .with_children(|parent| {
+            for bar in 0..graph.samples {
+                parent.spawn((
+                    NodeBundle {
+                        style: Style {
+                            width: Val::Px(graph.bar_width),
+                            height: Val::Percent(0.0),
+                            ..Default::default()
+                        },
+                        background_color: graph.color.into(),
+                        ..Default::default()
+                    },
+                    OverlayBar(bar),
+                ));
+            }
+        });
+}
+
+fn update_text(
+    config: Res<DiagnosticsOverlayConfig>,
+    store: Res<DiagnosticsStore>,
+    mut texts: Query<(&mut Text, &OverlayText)>,
+) {
+    if !config.enabled {
+        return;
+    }
+
+    for (mut text, &OverlayText(index)) in &mut texts {
+        let Some(overlay_diagnostic) = config.diagnostics.get(index) else {
+            continue;
+        };
+        let label = overlay_diagnostic.label();
+        let value = match store.get(&overlay_diagnostic.path) {
+            Some(diagnostic) => match diagnostic.smoothed() {
+                Some(value) => format!(
+                    "{label}: {value:.precision$}{suffix}",
+                    precision = overlay_diagnostic.precision,
+                    suffix = diagnostic.suffix
+                ),
+                None => format!("{label}: -"),
+            },
+            None => format!("{label}: N/A"),
+        };
+        text.sections[0].value = value;
+    }
+}
+
+fn update_graphs(
+    config: Res<DiagnosticsOverlayConfig>,
+    store: Res<DiagnosticsStore>,
+    graphs: Query<(&OverlayGraph, &Children)>,
+    mut bars: Query<(&mut Style, &OverlayBar)>,
+) {
+    if !config.enabled {
+        return;
+    }
+
+    for (&OverlayGraph(index), children) in &graphs {
+        let Some(overlay_diagnostic) = config.diagnostics.get(index) else {
+            continue;
+        };
+        let Some(graph) = &overlay_diagnostic.graph else {
+            continue;
+        };
+        let values = store
+            .get(&overlay_diagnostic.path)
+            .map(|diagnostic| {
+                let skip = diagnostic.history_len().saturating_sub(graph.samples);
+                diagnostic.values().skip(skip).copied().collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        let heights = bar_heights(&values, graph.samples, graph.max);
+
+        let mut iter = bars.iter_many_mut(children);
+        while let Some((mut style, &OverlayBar(bar))) = iter.fetch_next() {
+            let height = Val::Percent(heights[bar]);
+            if style.height != height {
+                style.height = height;
+            }
+        }
+    }
+}
+
+/// Computes the height of each bar as a percentage of the graph height.
+///
+/// The most recent value is shown in the last bar,
+/// and bars without a measurement are left empty.
+fn bar_heights(values: &[f64], samples: usize, max: Option<f64>) -> Vec<f32> {
+    let max = max.unwrap_or_else(|| values.iter().copied().fold(0.0, f64::max));
+    let offset = samples.saturating_sub(values.len());
+
+    let mut heights = vec![0.0; samples];
+    if max <= 0.0 {
+        return heights;
+    }
+    for (bar, value) in values.iter().enumerate() {
+        heights[offset + bar] = ((value / max).clamp(0.0, 1.0) * 100.0) as f32;
+    }
+    heights
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bar_heights_are_right_aligned_and_scaled() {
+        assert_eq!(
+            bar_heights(&[1.0, 2.0], 4, None),
+            vec![0.0, 0.0, 50.0, 100.0]
+        );
+        assert_eq!(bar_heights(&[1.0, 8.0], 2, Some(4.0)), vec![25.0, 100.0]);
+        assert_eq!(bar_heights(&[], 2, None), vec![0.0, 0.0]);
+    }
+}
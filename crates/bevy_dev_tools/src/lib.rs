@@ -0,0 +1,10 @@
+//! This crate provides additional utilities for the [Bevy game engine](https://bevyengine.org),
+//! focused on improving developer experience.
+//!
+//! These tools are meant to be used during development and are usually left out of release builds.
+
+pub mod console;
+pub mod crash_report;
+pub mod diagnostics_overlay;
+pub mod replay;
+pub mod shader_error_overlay;
@@ -0,0 +1,386 @@
+//! Recording and replaying of input, for bug reproduction and automated gameplay tests.
+//!
+//! While recording, the [`ReplayRecorder`] captures every raw input event along with the duration
+//! of each frame and the number of fixed timesteps it ran. The resulting [`Replay`] can be saved
+//! as RON and played back into a fresh run of the app with the [`ReplayPlayer`].
+//!
+//! During playback, live input is discarded through [`InputSource::Injected`] and time is advanced
+//! by the recorded frame durations with [`TimeUpdateStrategy::ManualDuration`], so the app sees
+//! the same input on the same frames and runs the same fixed timesteps regardless of how fast it
+//! actually runs. Playback stops being faithful if the app depends on other sources of
+//! non-determinism, such as unseeded randomness; frames running a different number of fixed
+//! timesteps than recorded are counted as desynchronized.
+//!
+//! ```no_run
+//! # use bevy_app::{App, Update};
+//! # use bevy_ecs::prelude::*;
+//! use bevy_dev_tools::replay::{Replay, ReplayPlayer, ReplayPlugin};
+//!
+//! fn play_bug_report(mut player: ResMut<ReplayPlayer>) {
+//!     let ron = std::fs::read_to_string("bug_report.replay.ron").unwrap();
+//!     player.play(Replay::from_ron(&ron).unwrap());
+//! }
+//!
+//! App::new()
+//!     .add_plugins(ReplayPlugin)
+//!     .add_systems(Update, play_bug_report.run_if(run_once()));
+//! ```
+
+use bevy_app::{App, First, FixedFirst, Last, Plugin};
+use bevy_ecs::{
+    event::{Event, EventReader, EventWriter},
+    schedule::IntoSystemConfigs,
+    system::{Res, ResMut, Resource},
+};
+use bevy_input::{
+    injection::{InjectedInput, InputEvent, InputSource},
+    timestamp::TimestampedInputEvent,
+};
+use bevy_time::{Real, Time, TimeSystem, TimeUpdateStrategy};
+use bevy_utils::{tracing::warn, Duration};
+use serde::{Deserialize, Serialize};
+
+/// Adds the [`ReplayRecorder`] and [`ReplayPlayer`] resources, which are idle until started.
+///
+/// Requires the `InputPlugin` and `TimePlugin`.
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ReplayRecorder>()
+            .init_resource::<ReplayPlayer>()
+            .init_resource::<FixedStepCounter>()
+            .add_event::<ReplayFinished>()
+            .add_systems(First, start_replay_frame.before(TimeSystem))
+            .add_systems(FixedFirst, count_fixed_steps)
+            .add_systems(Last, end_replay_frame);
+    }
+}
+
+/// A recording of the input and timing of consecutive frames.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct Replay {
+    /// The recorded frames, in order.
+    pub frames: Vec<ReplayFrame>,
+}
+
+impl Replay {
+    /// Serializes the replay to RON.
+    pub fn to_ron(&self) -> Result<String, ron::Error> {
+        ron::ser::to_string(self)
+    }
+
+    /// Deserializes a replay from RON.
+    pub fn from_ron(ron: &str) -> Result<Self, ron::error::SpannedError> {
+        ron::de::from_str(ron)
+    }
+
+    /// Returns the total duration of the recorded frames.
+    pub fn duration(&self) -> Duration {
+        self.frames.iter().map(|frame| frame.delta).sum()
+    }
+}
+
+/// The input and timing of a single recorded frame.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ReplayFrame {
+    /// The real time elapsed since the previous frame.
+    pub delta: Duration,
+    /// The number of fixed timesteps run during the frame.
+    pub fixed_steps: u32,
+    /// The raw input events sent during the frame, in the order they were received across all
+    /// devices.
+    pub inputs: Vec<InputEvent>,
+}
+
+/// Records the input and timing of each frame into a [`Replay`] while started.
+#[derive(Resource, Default)]
+pub struct ReplayRecorder {
+    replay: Replay,
+    recording: bool,
+}
+
+impl ReplayRecorder {
+    /// Starts recording, discarding any previous recording.
+    pub fn start(&mut self) {
+        self.replay = Replay::default();
+        self.recording = true;
+    }
+
+    /// Stops recording and returns what was recorded.
+    pub fn stop(&mut self) -> Replay {
+        self.recording = false;
+        std::mem::take(&mut self.replay)
+    }
+
+    /// Returns `true` if frames are being recorded.
+    pub fn is_recording(&self) -> bool {
+        self.recording
+    }
+
+    /// Returns what has been recorded so far.
+    pub fn replay(&self) -> &Replay {
+        &self.replay
+    }
+}
+
+/// Plays a [`Replay`] back, one recorded frame per frame.
+#[derive(Resource, Default)]
+pub struct ReplayPlayer {
+    replay: Replay,
+    next_frame: usize,
+    playing: bool,
+    desynced_frames: usize,
+    previous_time_update_strategy: Option<TimeUpdateStrategy>,
+}
+
+impl ReplayPlayer {
+    /// Starts playing the replay from its first frame on the next update.
+    pub fn play(&mut self, replay: Replay) {
+        *self = Self {
+            replay,
+            playing: true,
+            previous_time_update_strategy: self.previous_time_update_strategy.take(),
+            ..Default::default()
+        };
+    }
+
+    /// Returns `true` if a replay is being played.
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    /// Returns the index of the next recorded frame to be played.
+    pub fn next_frame(&self) -> usize {
+        self.next_frame
+    }
+
+    /// Returns the number of frames played so far that ran a different number of
+    /// fixed timesteps than recorded.
+    pub fn desynced_frames(&self) -> usize {
+        self.desynced_frames
+    }
+}
+
+/// Sent when the [`ReplayPlayer`] has played every frame of its replay.
+#[derive(Event, Clone, Debug, PartialEq, Eq)]
+pub struct ReplayFinished {
+    /// The number of frames that ran a different number of fixed timesteps than recorded.
+    pub desynced_frames: usize,
+}
+
+/// The number of fixed timesteps run during the current frame.
+#[derive(Resource, Default)]
+struct FixedStepCounter(u32);
+
+fn count_fixed_steps(mut counter: ResMut<FixedStepCounter>) {
+    counter.0 += 1;
+}
+
+/// Injects the input of the next recorded frame and sets the duration of the frame,
+/// or restores live input and the previous [`TimeUpdateStrategy`] once the replay is finished.
+fn start_replay_frame(
+    mut player: ResMut<ReplayPlayer>,
+    mut time_update_strategy: ResMut<TimeUpdateStrategy>,
+    mut input_source: ResMut<InputSource>,
+    mut injected_input: ResMut<InjectedInput>,
+    mut finished: EventWriter<ReplayFinished>,
+) {
+    if !player.playing {
+        return;
+    }
+
+    let player = &mut *player;
+    match player.replay.frames.get(player.next_frame) {
+        Some(frame) => {
+            let strategy = std::mem::replace(
+                &mut *time_update_strategy,
+                TimeUpdateStrategy::ManualDuration(frame.delta),
+            );
+            player.previous_time_update_strategy.get_or_insert(strategy);
+            *input_source = InputSource::Injected;
+            injected_input.extend(frame.inputs.iter().cloned());
+        }
+        None => {
+            player.playing = false;
+            *time_update_strategy = player
+                .previous_time_update_strategy
+                .take()
+                .unwrap_or_default();
+            *input_source = InputSource::Live;
+            finished.send(ReplayFinished {
+                desynced_frames: player.desynced_frames,
+            });
+        }
+    }
+}
+
+/// Records the current frame, and checks that the played frame ran as many fixed timesteps as recorded.
+fn end_replay_frame(
+    mut inputs: EventReader<TimestampedInputEvent>,
+    mut recorder: ResMut<ReplayRecorder>,
+    mut player: ResMut<ReplayPlayer>,
+    mut fixed_steps: ResMut<FixedStepCounter>,
+    real_time: Res<Time<Real>>,
+) {
+    let fixed_steps = std::mem::take(&mut fixed_steps.0);
+
+    if recorder.recording {
+        recorder.replay.frames.push(ReplayFrame {
+            delta: real_time.delta(),
+            fixed_steps,
+            inputs: inputs.read().map(|input| input.event.clone()).collect(),
+        });
+    } else {
+        inputs.clear();
+    }
+
+    if player.playing {
+        let index = player.next_frame;
+        let expected = player.replay.frames[index].fixed_steps;
+        if fixed_steps != expected {
+            if player.desynced_frames == 0 {
+                warn!(
+                    "Replay desynchronized on frame {index}: ran {fixed_steps} fixed timesteps instead of {expected}"
+                );
+            }
+            player.desynced_frames += 1;
+        }
+        player.next_frame += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_app::Update;
+    use bevy_ecs::entity::Entity;
+    use bevy_input::{
+        keyboard::ReceivedCharacter,
+        mouse::{CursorMoved, MouseButton, MouseButtonInput},
+        ButtonInput, ButtonState, InputPlugin,
+    };
+    use bevy_math::Vec2;
+    use bevy_time::TimePlugin;
+
+    #[derive(Resource, Default)]
+    struct Clicks(Vec<Duration>);
+
+    fn count_clicks(
+        buttons: Res<ButtonInput<MouseButton>>,
+        time: Res<Time>,
+        mut clicks: ResMut<Clicks>,
+    ) {
+        if buttons.just_pressed(MouseButton::Left) {
+            clicks.0.push(time.elapsed());
+        }
+    }
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins((TimePlugin, InputPlugin, ReplayPlugin))
+            .init_resource::<Clicks>()
+            .add_systems(Update, count_clicks);
+        app
+    }
+
+    fn click(app: &mut App, state: ButtonState) {
+        app.world
+            .resource_mut::<InjectedInput>()
+            .send(InputEvent::MouseButton(MouseButtonInput {
+                button: MouseButton::Left,
+                state,
+                window: Entity::PLACEHOLDER,
+            }));
+    }
+
+    #[test]
+    fn replay_reproduces_input_and_time() {
+        let mut app = app();
+        app.world.resource_mut::<ReplayRecorder>().start();
+        for frame in 0..20u64 {
+            app.world
+                .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                    5 + frame % 3 * 10,
+                )));
+            match frame % 4 {
+                1 => click(&mut app, ButtonState::Pressed),
+                3 => click(&mut app, ButtonState::Released),
+                _ => {}
+            }
+            app.update();
+        }
+        let replay = app.world.resource_mut::<ReplayRecorder>().stop();
+        let recorded_clicks = std::mem::take(&mut app.world.resource_mut::<Clicks>().0);
+        assert_eq!(recorded_clicks.len(), 5);
+
+        let replay = Replay::from_ron(&replay.to_ron().unwrap()).unwrap();
+        let mut replay_app = self::app();
+        replay_app
+            .world
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(1)));
+        replay_app
+            .world
+            .resource_mut::<ReplayPlayer>()
+            .play(replay.clone());
+        while replay_app.world.resource::<ReplayPlayer>().is_playing() {
+            replay_app.update();
+        }
+
+        assert_eq!(replay_app.world.resource::<Clicks>().0, recorded_clicks);
+        assert_eq!(
+            replay_app
+                .world
+                .resource::<ReplayPlayer>()
+                .desynced_frames(),
+            0
+        );
+        assert_eq!(
+            *replay_app.world.resource::<InputSource>(),
+            InputSource::Live
+        );
+        assert!(matches!(
+            replay_app.world.resource::<TimeUpdateStrategy>(),
+            TimeUpdateStrategy::ManualDuration(delta) if *delta == Duration::from_millis(1)
+        ));
+    }
+
+    #[test]
+    fn inputs_are_recorded_in_order_across_devices() {
+        let mut app = app();
+        app.world.resource_mut::<ReplayRecorder>().start();
+        let window = Entity::PLACEHOLDER;
+        let inputs = vec![
+            InputEvent::CursorMoved(CursorMoved {
+                window,
+                position: Vec2::ONE,
+                delta: None,
+            }),
+            InputEvent::MouseButton(MouseButtonInput {
+                button: MouseButton::Left,
+                state: ButtonState::Pressed,
+                window,
+            }),
+            InputEvent::ReceivedCharacter(ReceivedCharacter {
+                window,
+                char: "a".into(),
+            }),
+            InputEvent::CursorMoved(CursorMoved {
+                window,
+                position: Vec2::X,
+                delta: Some(-Vec2::Y),
+            }),
+        ];
+        app.world
+            .resource_mut::<InjectedInput>()
+            .extend(inputs.clone());
+        app.update();
+        app.update();
+
+        let replay = app.world.resource_mut::<ReplayRecorder>().stop();
+        assert_eq!(replay.frames.len(), 2);
+        assert_eq!(replay.frames[0].inputs, inputs);
+        assert!(replay.frames[1].inputs.is_empty());
+        assert_eq!(Replay::from_ron(&replay.to_ron().unwrap()).unwrap(), replay);
+    }
+}
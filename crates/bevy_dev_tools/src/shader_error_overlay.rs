@@ -0,0 +1,213 @@
+//! An in-game overlay listing the shaders that failed to compile.
+//!
+//! Add the [`ShaderErrorOverlayPlugin`] to show the errors of the pipelines that could not be
+//! created in the bottom left corner of the screen, with the file and line of each error when
+//! known. Errors disappear from the overlay as soon as the hot-reloaded shader is fixed.
+//!
+//! ```no_run
+//! # use bevy_app::App;
+//! use bevy_dev_tools::shader_error_overlay::ShaderErrorOverlayPlugin;
+//!
+//! App::new().add_plugins(ShaderErrorOverlayPlugin::default());
+//! ```
+
+use bevy_app::{App, Plugin, Update};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::With,
+    schedule::{
+        common_conditions::{resource_changed, resource_exists},
+        Condition, IntoSystemConfigs,
+    },
+    system::{Commands, Query, Res, Resource},
+};
+use bevy_hierarchy::{BuildChildren, DespawnRecursiveExt};
+use bevy_render::{
+    color::Color,
+    render_resource::{PipelineError, PipelineErrors},
+};
+use bevy_text::{Text, TextStyle};
+use bevy_ui::{
+    node_bundles::{NodeBundle, TextBundle},
+    Display, FlexDirection, PositionType, Style, UiRect, Val, ZIndex,
+};
+
+/// A plugin that lists the [`PipelineErrors`] in an overlay on top of the UI.
+#[derive(Default)]
+pub struct ShaderErrorOverlayPlugin {
+    /// The initial configuration of the overlay.
+    pub config: ShaderErrorOverlayConfig,
+}
+
+impl Plugin for ShaderErrorOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(self.config.clone()).add_systems(
+            Update,
+            (
+                spawn_overlay.run_if(resource_changed::<ShaderErrorOverlayConfig>),
+                update_overlay.run_if(
+                    resource_exists::<PipelineErrors>.and_then(
+                        resource_changed::<PipelineErrors>
+                            .or_else(resource_changed::<ShaderErrorOverlayConfig>),
+                    ),
+                ),
+            )
+                .chain(),
+        );
+    }
+}
+
+/// Configuration of the [`ShaderErrorOverlayPlugin`].
+///
+/// Changing this resource rebuilds the overlay.
+#[derive(Resource, Clone, Debug)]
+pub struct ShaderErrorOverlayConfig {
+    /// Whether the overlay is shown when there are errors.
+    pub enabled: bool,
+    /// Whether the full error messages are shown, instead of their first line.
+    pub show_messages: bool,
+    /// The font size of the text.
+    pub text_size: f32,
+    /// The color of the text.
+    pub text_color: Color,
+    /// The color behind the overlay.
+    pub background_color: Color,
+}
+
+impl Default for ShaderErrorOverlayConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            show_messages: false,
+            text_size: 16.0,
+            text_color: Color::rgb(1.0, 0.4, 0.4),
+            background_color: Color::rgba(0.0, 0.0, 0.0, 0.8),
+        }
+    }
+}
+
+/// Marks the root node of the overlay.
+#[derive(Component)]
+pub struct ShaderErrorOverlay;
+
+/// Marks the text of the overlay.
+#[derive(Component)]
+struct ShaderErrorText;
+
+/// Despawns the current overlay, if any, and spawns one matching the [`ShaderErrorOverlayConfig`].
+fn spawn_overlay(
+    mut commands: Commands,
+    config: Res<ShaderErrorOverlayConfig>,
+    overlays: Query<Entity, With<ShaderErrorOverlay>>,
+) {
+    for overlay in &overlays {
+        commands.entity(overlay).despawn_recursive();
+    }
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    display: Display::None,
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Px(0.0),
+                    left: Val::Px(0.0),
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(4.0)),
+                    ..Default::default()
+                },
+                background_color: config.background_color.into(),
+                z_index: ZIndex::Global(i32::MAX),
+                ..Default::default()
+            },
+            ShaderErrorOverlay,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: config.text_size,
+                        color: config.text_color,
+                        ..Default::default()
+                    },
+                ),
+                ShaderErrorText,
+            ));
+        });
+}
+
+/// Shows the overlay while there are errors, and lists them.
+fn update_overlay(
+    config: Res<ShaderErrorOverlayConfig>,
+    errors: Res<PipelineErrors>,
+    mut overlays: Query<&mut Style, With<ShaderErrorOverlay>>,
+    mut texts: Query<&mut Text, With<ShaderErrorText>>,
+) {
+    let display = if config.enabled && !errors.is_empty() {
+        Display::Flex
+    } else {
+        Display::None
+    };
+    for mut style in &mut overlays {
+        style.display = display;
+    }
+
+    let value = errors
+        .iter()
+        .map(|error| error_summary(error, config.show_messages))
+        .collect::<Vec<_>>()
+        .join("\n");
+    for mut text in &mut texts {
+        text.sections[0].value = value.clone();
+    }
+}
+
+/// Describes an error on one line, starting with its location, or on several lines if
+/// `full_message` is `true`.
+fn error_summary(error: &PipelineError, full_message: bool) -> String {
+    let location = match &error.location {
+        Some(location) => format!("{}:{}:{}", location.path, location.line, location.column),
+        None => error
+            .label
+            .as_deref()
+            .map_or_else(|| format!("pipeline {}", error.pipeline), str::to_string),
+    };
+    let message = if full_message {
+        error.message.trim_end()
+    } else {
+        error.message.lines().next().unwrap_or_default()
+    };
+    format!("{location}: {message}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_render::render_resource::ShaderErrorLocation;
+
+    #[test]
+    fn summary_starts_with_location() {
+        let mut error = PipelineError {
+            pipeline: 3,
+            label: Some("custom_material_pipeline".into()),
+            location: Some(ShaderErrorLocation {
+                path: "shaders/custom_material.wgsl".to_string(),
+                line: 12,
+                column: 5,
+            }),
+            message: "error: expected ';'\n  ┌─ shaders/custom_material.wgsl:12:5".to_string(),
+        };
+        assert_eq!(
+            error_summary(&error, false),
+            "shaders/custom_material.wgsl:12:5: error: expected ';'"
+        );
+
+        error.location = None;
+        assert_eq!(
+            error_summary(&error, false),
+            "custom_material_pipeline: error: expected ';'"
+        );
+    }
+}
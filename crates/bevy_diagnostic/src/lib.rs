@@ -9,14 +9,21 @@ mod diagnostic;
 mod entity_count_diagnostics_plugin;
 mod frame_time_diagnostics_plugin;
 mod log_diagnostics_plugin;
+mod memory_diagnostics_plugin;
 mod system_information_diagnostics_plugin;
+mod system_profiler_plugin;
 
 use bevy_app::prelude::*;
 pub use diagnostic::*;
 pub use entity_count_diagnostics_plugin::EntityCountDiagnosticsPlugin;
 pub use frame_time_diagnostics_plugin::FrameTimeDiagnosticsPlugin;
 pub use log_diagnostics_plugin::LogDiagnosticsPlugin;
+pub use memory_diagnostics_plugin::{bytes_to_mebibytes, MemoryDiagnosticsPlugin};
 pub use system_information_diagnostics_plugin::SystemInformationDiagnosticsPlugin;
+pub use system_profiler_plugin::{
+    profiler_layer, ProfileCategory, ProfileEvent, ProfilerLayer, SystemProfiler,
+    SystemProfilerPlugin,
+};
 
 /// Adds core diagnostics resources to an App.
 #[derive(Default)]
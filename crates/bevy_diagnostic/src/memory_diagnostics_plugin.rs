@@ -0,0 +1,88 @@
+use crate::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic};
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_time::common_conditions::on_real_timer;
+use bevy_utils::Duration;
+
+/// Adds diagnostics for the memory used by the ECS to an App,
+/// specifically the bytes allocated for tables and sparse sets, and the number of archetypes.
+///
+/// Memory is reported in mebibytes, and includes the unused capacity of the storages.
+///
+/// # See also
+///
+/// [`LogDiagnosticsPlugin`](crate::LogDiagnosticsPlugin) to output diagnostics to the console.
+pub struct MemoryDiagnosticsPlugin {
+    /// How often the memory usage is measured.
+    pub wait_duration: Duration,
+}
+
+impl Default for MemoryDiagnosticsPlugin {
+    fn default() -> Self {
+        MemoryDiagnosticsPlugin {
+            wait_duration: Duration::from_secs(1),
+        }
+    }
+}
+
+impl Plugin for MemoryDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_diagnostic(Diagnostic::new(Self::TABLES).with_suffix("MiB"))
+            .register_diagnostic(Diagnostic::new(Self::SPARSE_SETS).with_suffix("MiB"))
+            .register_diagnostic(Diagnostic::new(Self::ARCHETYPE_COUNT).with_smoothing_factor(0.0))
+            .add_systems(
+                Update,
+                Self::diagnostic_system.run_if(on_real_timer(self.wait_duration)),
+            );
+    }
+}
+
+impl MemoryDiagnosticsPlugin {
+    pub const TABLES: DiagnosticPath = DiagnosticPath::const_new("memory/ecs/tables");
+    pub const SPARSE_SETS: DiagnosticPath = DiagnosticPath::const_new("memory/ecs/sparse_sets");
+    pub const ARCHETYPE_COUNT: DiagnosticPath =
+        DiagnosticPath::const_new("memory/ecs/archetype_count");
+
+    pub fn diagnostic_system(mut diagnostics: Diagnostics, world: &World) {
+        let storages = world.storages();
+        diagnostics.add_measurement(&Self::TABLES, || {
+            bytes_to_mebibytes(storages.tables.allocated_bytes())
+        });
+        diagnostics.add_measurement(&Self::SPARSE_SETS, || {
+            bytes_to_mebibytes(storages.sparse_sets.allocated_bytes())
+        });
+        diagnostics.add_measurement(&Self::ARCHETYPE_COUNT, || world.archetypes().len() as f64);
+    }
+}
+
+/// Converts a number of bytes to mebibytes, the unit used by memory diagnostics.
+pub fn bytes_to_mebibytes(bytes: usize) -> f64 {
+    bytes as f64 / (1024.0 * 1024.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DiagnosticsStore;
+
+    #[derive(Component)]
+    struct A([u8; 64]);
+
+    #[test]
+    fn measures_table_memory() {
+        let mut app = App::new();
+        app.add_plugins(MemoryDiagnosticsPlugin::default());
+        app.world.spawn_batch((0..1024).map(|_| A([0; 64])));
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(MemoryDiagnosticsPlugin::diagnostic_system);
+        schedule.run(&mut app.world);
+
+        let store = app.world.resource::<DiagnosticsStore>();
+        let tables = store
+            .get(&MemoryDiagnosticsPlugin::TABLES)
+            .and_then(|diagnostic| diagnostic.value())
+            .unwrap();
+        assert!(tables >= bytes_to_mebibytes(1024 * 64));
+    }
+}
@@ -0,0 +1,502 @@
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    fmt::Write as _,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, OnceLock, RwLock,
+    },
+};
+
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_input::{keyboard::KeyCode, ButtonInput};
+use bevy_log::{
+    error, info,
+    tracing_subscriber::{layer::Context, prelude::*, Layer},
+    BoxedSubscriber,
+};
+use bevy_utils::{
+    tracing::{
+        field::{Field, Visit},
+        span::{Attributes, Id},
+        Subscriber,
+    },
+    Duration, HashMap, Instant,
+};
+
+use crate::{Diagnostic, DiagnosticMeasurement, DiagnosticPath, DiagnosticsStore};
+
+/// Records how long each system and render graph node takes to run, without any external tools.
+///
+/// Timings are collected from the spans emitted when the `trace` feature is enabled,
+/// so the [`profiler_layer`] must be added to the tracing subscriber, for example with
+/// [`LogPlugin::update_subscriber`](bevy_log::LogPlugin::update_subscriber):
+///
+/// ```no_run
+/// # use bevy_app::{App, NoopPluginGroup as DefaultPlugins, PluginGroup};
+/// # use bevy_diagnostic::{profiler_layer, SystemProfilerPlugin};
+/// # use bevy_log::LogPlugin;
+/// App::new()
+///     .add_plugins(DefaultPlugins.set(LogPlugin {
+///         update_subscriber: Some(profiler_layer),
+///         ..Default::default()
+///     }))
+///     .add_plugins(SystemProfilerPlugin::default())
+///     .run();
+/// ```
+///
+/// The most recent timings are kept in the [`SystemProfiler`] ring buffer,
+/// which can be saved in the Chrome trace format by pressing [`dump_key`](Self::dump_key),
+/// or at any time with [`SystemProfiler::save_chrome_trace`].
+/// The resulting file can be opened in `chrome://tracing` or [Perfetto](https://ui.perfetto.dev).
+///
+/// The total time spent in each system and node every frame is also recorded as a diagnostic,
+/// under `profiler/system/<name>` and `profiler/render_node/<name>`.
+pub struct SystemProfilerPlugin {
+    /// The maximum number of timings kept. Older timings are discarded first.
+    pub capacity: usize,
+    /// The key that saves the recorded timings to [`output_path`](Self::output_path), if any.
+    pub dump_key: Option<KeyCode>,
+    /// The file the Chrome trace is written to when [`dump_key`](Self::dump_key) is pressed.
+    pub output_path: PathBuf,
+    /// Whether to record the per-frame timings as diagnostics.
+    pub diagnostics: bool,
+}
+
+impl Default for SystemProfilerPlugin {
+    fn default() -> Self {
+        Self {
+            capacity: SystemProfiler::DEFAULT_CAPACITY,
+            dump_key: Some(KeyCode::F9),
+            output_path: PathBuf::from("trace.json"),
+            diagnostics: true,
+        }
+    }
+}
+
+impl Plugin for SystemProfilerPlugin {
+    fn build(&self, app: &mut App) {
+        let profiler = SystemProfiler::global().clone();
+        profiler.set_capacity(self.capacity);
+
+        app.insert_resource(profiler)
+            .insert_resource(ChromeTraceOutput {
+                dump_key: self.dump_key,
+                path: self.output_path.clone(),
+            })
+            .add_systems(Last, save_chrome_trace_on_key);
+
+        if self.diagnostics {
+            app.init_resource::<DiagnosticsStore>()
+                .add_systems(Last, SystemProfiler::diagnostic_system);
+        }
+    }
+}
+
+/// Where and when the [`SystemProfilerPlugin`] saves the Chrome trace.
+#[derive(Resource)]
+struct ChromeTraceOutput {
+    dump_key: Option<KeyCode>,
+    path: PathBuf,
+}
+
+fn save_chrome_trace_on_key(
+    profiler: Res<SystemProfiler>,
+    output: Res<ChromeTraceOutput>,
+    keyboard: Option<Res<ButtonInput<KeyCode>>>,
+) {
+    let (Some(key), Some(keyboard)) = (output.dump_key, keyboard) else {
+        return;
+    };
+    if !keyboard.just_pressed(key) {
+        return;
+    }
+
+    match profiler.save_chrome_trace(&output.path) {
+        Ok(()) => info!("Saved Chrome trace to {}", output.path.display()),
+        Err(err) => error!(
+            "Failed to save Chrome trace to {}: {err}",
+            output.path.display()
+        ),
+    }
+}
+
+/// What a [`ProfileEvent`] measured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProfileCategory {
+    /// The run of a system.
+    System,
+    /// The run of a render graph node.
+    RenderNode,
+}
+
+impl ProfileCategory {
+    /// The name of the category, as used in diagnostic paths and Chrome traces.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProfileCategory::System => "system",
+            ProfileCategory::RenderNode => "render_node",
+        }
+    }
+}
+
+/// A single timing recorded by the [`SystemProfiler`].
+#[derive(Debug, Clone)]
+pub struct ProfileEvent {
+    /// The name of the system or render graph node.
+    pub name: Arc<str>,
+    /// What was measured.
+    pub category: ProfileCategory,
+    /// An identifier of the thread the event ran on.
+    pub thread: u64,
+    /// When the event started, relative to the creation of the [`SystemProfiler`].
+    pub start: Duration,
+    /// How long the event took.
+    pub duration: Duration,
+}
+
+/// A handle to the timings recorded for the [`SystemProfilerPlugin`].
+///
+/// Timings are recorded into a ring buffer shared by all handles,
+/// usually the [global](Self::global) one written to by the [`profiler_layer`].
+#[derive(Resource, Clone)]
+pub struct SystemProfiler {
+    data: Arc<ProfilerData>,
+}
+
+struct ProfilerData {
+    start: Instant,
+    buffer: Mutex<ProfilerBuffer>,
+}
+
+struct ProfilerBuffer {
+    capacity: usize,
+    events: VecDeque<ProfileEvent>,
+    /// The total duration of each system and node since the last call to `take_frame_totals`.
+    frame_totals: HashMap<(ProfileCategory, Arc<str>), Duration>,
+}
+
+impl SystemProfiler {
+    /// The default maximum number of timings kept.
+    pub const DEFAULT_CAPACITY: usize = 100_000;
+
+    /// Creates a profiler that keeps at most `capacity` timings.
+    ///
+    /// Most apps should use the [global](Self::global) profiler instead.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            data: Arc::new(ProfilerData {
+                start: Instant::now(),
+                buffer: Mutex::new(ProfilerBuffer {
+                    capacity,
+                    events: VecDeque::new(),
+                    frame_totals: HashMap::default(),
+                }),
+            }),
+        }
+    }
+
+    /// The profiler written to by the [`profiler_layer`].
+    pub fn global() -> &'static SystemProfiler {
+        static GLOBAL: OnceLock<SystemProfiler> = OnceLock::new();
+        GLOBAL.get_or_init(|| SystemProfiler::new(Self::DEFAULT_CAPACITY))
+    }
+
+    /// Sets the maximum number of timings kept, discarding the oldest ones if there are too many.
+    pub fn set_capacity(&self, capacity: usize) {
+        let mut buffer = self.data.buffer.lock().unwrap();
+        buffer.capacity = capacity;
+        let excess = buffer.events.len().saturating_sub(capacity);
+        buffer.events.drain(..excess);
+    }
+
+    /// Records a timing of the given category and name that started at `start`.
+    pub fn record(
+        &self,
+        category: ProfileCategory,
+        name: Arc<str>,
+        start: Instant,
+        duration: Duration,
+    ) {
+        let event = ProfileEvent {
+            name: name.clone(),
+            category,
+            thread: thread_index(),
+            start: start.saturating_duration_since(self.data.start),
+            duration,
+        };
+
+        let mut buffer = self.data.buffer.lock().unwrap();
+        *buffer.frame_totals.entry((category, name)).or_default() += duration;
+        if buffer.capacity == 0 {
+            return;
+        }
+        if buffer.events.len() == buffer.capacity {
+            buffer.events.pop_front();
+        }
+        buffer.events.push_back(event);
+    }
+
+    /// Returns a copy of the recorded timings, from oldest to newest.
+    pub fn events(&self) -> Vec<ProfileEvent> {
+        self.data
+            .buffer
+            .lock()
+            .unwrap()
+            .events
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Discards all recorded timings.
+    pub fn clear(&self) {
+        let mut buffer = self.data.buffer.lock().unwrap();
+        buffer.events.clear();
+        buffer.frame_totals.clear();
+    }
+
+    /// Returns the recorded timings in the Chrome trace event format.
+    pub fn chrome_trace(&self) -> String {
+        let buffer = self.data.buffer.lock().unwrap();
+        let mut json = String::from("{\"traceEvents\":[");
+        for (i, event) in buffer.events.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            json.push_str("{\"name\":\"");
+            escape_json(&mut json, &event.name);
+            let _ = write!(
+                json,
+                "\",\"cat\":\"{}\",\"ph\":\"X\",\"ts\":{:.3},\"dur\":{:.3},\"pid\":0,\"tid\":{}}}",
+                event.category.as_str(),
+                event.start.as_secs_f64() * 1_000_000.0,
+                event.duration.as_secs_f64() * 1_000_000.0,
+                event.thread,
+            );
+        }
+        json.push_str("],\"displayTimeUnit\":\"ms\"}");
+        json
+    }
+
+    /// Writes the recorded timings to a file in the Chrome trace event format.
+    pub fn save_chrome_trace(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        std::fs::write(path, self.chrome_trace())
+    }
+
+    /// Takes the total time spent in each system and node since the last call.
+    fn take_frame_totals(&self) -> Vec<(ProfileCategory, Arc<str>, Duration)> {
+        self.data
+            .buffer
+            .lock()
+            .unwrap()
+            .frame_totals
+            .drain()
+            .map(|((category, name), duration)| (category, name, duration))
+            .collect()
+    }
+
+    /// Records the time spent in each system and node since the last frame as diagnostics,
+    /// registering the diagnostics the first time they are measured.
+    pub fn diagnostic_system(profiler: Res<SystemProfiler>, mut store: ResMut<DiagnosticsStore>) {
+        let now = Instant::now();
+        for (category, name, duration) in profiler.take_frame_totals() {
+            let path = DiagnosticPath::from_components(["profiler", category.as_str(), &*name]);
+            if store.get(&path).is_none() {
+                store.add(Diagnostic::new(path.clone()).with_suffix("ms"));
+            }
+            if let Some(diagnostic) = store.get_mut(&path).filter(|d| d.is_enabled) {
+                diagnostic.add_measurement(DiagnosticMeasurement {
+                    time: now,
+                    value: duration.as_secs_f64() * 1000.0,
+                });
+            }
+        }
+    }
+}
+
+fn escape_json(json: &mut String, value: &str) {
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            c if c.is_control() => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+}
+
+/// Returns a small number identifying the current thread.
+fn thread_index() -> u64 {
+    static NEXT: AtomicU64 = AtomicU64::new(0);
+    thread_local! {
+        static INDEX: u64 = NEXT.fetch_add(1, Ordering::Relaxed);
+    }
+    INDEX.with(|index| *index)
+}
+
+/// Adds a [`ProfilerLayer`] writing to the [global](SystemProfiler::global) profiler to a subscriber.
+///
+/// This can be passed directly to [`LogPlugin::update_subscriber`](bevy_log::LogPlugin::update_subscriber).
+pub fn profiler_layer(subscriber: BoxedSubscriber) -> BoxedSubscriber {
+    Box::new(subscriber.with(ProfilerLayer::new(SystemProfiler::global().clone())))
+}
+
+/// A tracing [`Layer`] that records the duration of system and render graph node spans
+/// into a [`SystemProfiler`].
+pub struct ProfilerLayer {
+    profiler: SystemProfiler,
+    spans: RwLock<HashMap<Id, (ProfileCategory, Arc<str>)>>,
+}
+
+thread_local! {
+    /// The profiled spans entered on this thread, innermost last.
+    static ENTERED_SPANS: RefCell<Vec<(Id, Instant)>> = RefCell::new(Vec::new());
+}
+
+impl ProfilerLayer {
+    /// Creates a layer recording into the given profiler.
+    pub fn new(profiler: SystemProfiler) -> Self {
+        Self {
+            profiler,
+            spans: RwLock::default(),
+        }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for ProfilerLayer {
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, _ctx: Context<'_, S>) {
+        let category = match attrs.metadata().name() {
+            "system" => ProfileCategory::System,
+            "node" => ProfileCategory::RenderNode,
+            _ => return,
+        };
+
+        let mut visitor = NameVisitor(None);
+        attrs.record(&mut visitor);
+        if let Some(name) = visitor.0 {
+            self.spans
+                .write()
+                .unwrap()
+                .insert(id.clone(), (category, name.into()));
+        }
+    }
+
+    fn on_enter(&self, id: &Id, _ctx: Context<'_, S>) {
+        if self.spans.read().unwrap().contains_key(id) {
+            ENTERED_SPANS.with(|entered| entered.borrow_mut().push((id.clone(), Instant::now())));
+        }
+    }
+
+    fn on_exit(&self, id: &Id, _ctx: Context<'_, S>) {
+        let Some(start) = ENTERED_SPANS.with(|entered| {
+            let mut entered = entered.borrow_mut();
+            let index = entered
+                .iter()
+                .rposition(|(entered_id, _)| entered_id == id)?;
+            Some(entered.remove(index).1)
+        }) else {
+            return;
+        };
+
+        if let Some((category, name)) = self.spans.read().unwrap().get(id) {
+            self.profiler
+                .record(*category, name.clone(), start, start.elapsed());
+        }
+    }
+
+    fn on_close(&self, id: Id, _ctx: Context<'_, S>) {
+        self.spans.write().unwrap().remove(&id);
+    }
+}
+
+/// Extracts the `name` field of a span.
+struct NameVisitor(Option<String>);
+
+impl Visit for NameVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "name" {
+            self.0 = Some(value.to_owned());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "name" {
+            self.0 = Some(format!("{value:?}"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_log::tracing_subscriber::registry::Registry;
+    use bevy_utils::tracing::{self, info_span};
+
+    #[test]
+    fn ring_buffer_discards_oldest_events() {
+        let profiler = SystemProfiler::new(2);
+        let now = Instant::now();
+        for name in ["a", "b", "c"] {
+            profiler.record(
+                ProfileCategory::System,
+                name.into(),
+                now,
+                Duration::from_millis(1),
+            );
+        }
+
+        let names = profiler
+            .events()
+            .iter()
+            .map(|event| event.name.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["b", "c"]);
+
+        profiler.set_capacity(1);
+        assert_eq!(&*profiler.events()[0].name, "c");
+    }
+
+    #[test]
+    fn layer_records_profiled_spans() {
+        let profiler = SystemProfiler::new(16);
+        let subscriber = Registry::default().with(ProfilerLayer::new(profiler.clone()));
+
+        tracing::subscriber::with_default(subscriber, || {
+            info_span!("system", name = "my_system").in_scope(|| {
+                info_span!("unrelated").in_scope(|| {});
+            });
+            info_span!("node", name = "my_node").in_scope(|| {});
+        });
+
+        let events = profiler.events();
+        assert_eq!(events.len(), 2);
+        assert_eq!(&*events[0].name, "my_system");
+        assert_eq!(events[0].category, ProfileCategory::System);
+        assert_eq!(&*events[1].name, "my_node");
+        assert_eq!(events[1].category, ProfileCategory::RenderNode);
+
+        let trace = profiler.chrome_trace();
+        assert!(trace.starts_with("{\"traceEvents\":[{\"name\":\"my_system\",\"cat\":\"system\""));
+        assert!(trace.contains("\"cat\":\"render_node\""));
+
+        assert_eq!(profiler.take_frame_totals().len(), 2);
+        assert!(profiler.take_frame_totals().is_empty());
+    }
+
+    #[test]
+    fn chrome_trace_escapes_names() {
+        let profiler = SystemProfiler::new(1);
+        profiler.record(
+            ProfileCategory::System,
+            "a\"b\\c".into(),
+            Instant::now(),
+            Duration::ZERO,
+        );
+        assert!(profiler.chrome_trace().contains(r#""name":"a\"b\\c""#));
+    }
+}
@@ -16,6 +16,7 @@ pub mod query;
 #[cfg(feature = "bevy_reflect")]
 pub mod reflect;
 pub mod removal_detection;
+pub mod replication;
 pub mod schedule;
 pub mod storage;
 pub mod system;
@@ -0,0 +1,435 @@
+//! Access to the values of reflected components through string paths.
+//!
+//! This is the entry point for tools that edit a running [`World`] from text, such as consoles,
+//! cheat menus and entity inspectors. A [`ComponentPath`] names a component by its type path,
+//! followed by a [`bevy_reflect`] path into the component: `"Transform.translation.x"`.
+
+use bevy_reflect::{
+    std_traits::ReflectValueFormat, GetPath, ParsedPath, Reflect, TypeInfo, TypeRegistry,
+};
+use thiserror::Error;
+
+use super::{AppTypeRegistry, ReflectComponent};
+use crate::{change_detection::DetectChangesMut, entity::Entity, world::World};
+
+/// A path to a value inside of a reflected component, as in `"Transform.translation.x"`.
+///
+/// The component is named either by its short type path (`Transform`) or by its full type path
+/// (`bevy_transform::components::transform::Transform`). The rest of the path, if any, is a
+/// [reflection path](bevy_reflect::GetPath) into the component. An empty rest addresses the
+/// component itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComponentPath<'a> {
+    /// The type path of the component.
+    pub component: &'a str,
+    /// The reflection path into the component, without the separator joining it to the component.
+    pub field: &'a str,
+}
+
+impl<'a> ComponentPath<'a> {
+    /// Splits `path` into the name of the component and the path into it.
+    ///
+    /// The component name ends at the first `.`, `[` or `#` outside of generic arguments, so
+    /// `"Foo<a::B>.value"` names the component `Foo<a::B>`.
+    pub fn parse(path: &'a str) -> Self {
+        let mut depth = 0usize;
+        for (index, char) in path.char_indices() {
+            match char {
+                '<' => depth += 1,
+                '>' => depth = depth.saturating_sub(1),
+                '.' | '[' | '#' if depth == 0 => {
+                    let field = &path[index..];
+                    return Self {
+                        component: &path[..index],
+                        field: field.strip_prefix('.').unwrap_or(field),
+                    };
+                }
+                _ => {}
+            }
+        }
+        Self {
+            component: path,
+            field: "",
+        }
+    }
+}
+
+/// An error when accessing a component value with [`World::get_component_value`] or
+/// [`World::set_component_value`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ComponentPathError {
+    /// The entity does not exist.
+    #[error("The entity {0:?} does not exist")]
+    NoSuchEntity(Entity),
+    /// The world has no [`AppTypeRegistry`] resource.
+    #[error("The world has no `AppTypeRegistry` resource")]
+    NoTypeRegistry,
+    /// No type is registered under the name of the component.
+    #[error("No type named `{0}` is registered")]
+    UnknownComponent(String),
+    /// The type is registered, but not as a reflected component.
+    #[error("The type `{0}` is not registered with `#[reflect(Component)]`")]
+    NotReflectComponent(String),
+    /// The entity does not have the component.
+    #[error("The entity {entity:?} has no `{component}` component")]
+    MissingComponent {
+        /// The entity being accessed.
+        entity: Entity,
+        /// The type path of the component.
+        component: String,
+    },
+    /// The path into the component is malformed, or doesn't lead to a value.
+    #[error("Invalid path `{path}`: {message}")]
+    InvalidPath {
+        /// The path into the component.
+        path: String,
+        /// Why the path could not be followed.
+        message: String,
+    },
+    /// The value has no [`ReflectValueFormat`] to parse it with.
+    #[error("No text format is registered for values of type `{0}`")]
+    NoValueFormat(String),
+    /// The text could not be parsed into a value.
+    #[error("`{text}` is not a valid `{type_path}`")]
+    InvalidValue {
+        /// The text that was parsed.
+        text: String,
+        /// The type path of the expected value.
+        type_path: String,
+    },
+    /// The new value is of a different type than the value it replaces.
+    #[error("Expected a value of type `{expected}`, found `{found}`")]
+    TypeMismatch {
+        /// The type path of the value being replaced.
+        expected: String,
+        /// The type path of the new value.
+        found: String,
+    },
+}
+
+impl World {
+    /// Returns the value at `path` in a component of `entity`, such as `"Transform.translation"`.
+    ///
+    /// The component type must be registered in the [`AppTypeRegistry`] with
+    /// `#[reflect(Component)]`. See [`ComponentPath`] for the syntax of `path`.
+    pub fn get_component_value(
+        &self,
+        entity: Entity,
+        path: &str,
+    ) -> Result<&dyn Reflect, ComponentPathError> {
+        let path = ComponentPath::parse(path);
+        let (reflect_component, type_path) = self.reflect_component(path.component)?;
+        let entity_ref = self
+            .get_entity(entity)
+            .ok_or(ComponentPathError::NoSuchEntity(entity))?;
+        let component =
+            reflect_component
+                .reflect(entity_ref)
+                .ok_or(ComponentPathError::MissingComponent {
+                    entity,
+                    component: type_path,
+                })?;
+        follow_path(component, path.field)
+    }
+
+    /// Formats the value at `path` in a component of `entity`.
+    ///
+    /// Values with a [`ReflectValueFormat`] use it, everything else falls back to the [`Debug`]
+    /// representation of the value.
+    pub fn format_component_value(
+        &self,
+        entity: Entity,
+        path: &str,
+    ) -> Result<String, ComponentPathError> {
+        let value = self.get_component_value(entity, path)?;
+        let registry = self.type_registry()?.read();
+        Ok(value_format(&registry, value)
+            .and_then(|format| format.format(value))
+            .unwrap_or_else(|| format!("{value:?}")))
+    }
+
+    /// Replaces the value at `path` in a component of `entity` with `value`.
+    ///
+    /// The component is only marked as changed if the new value differs from the old one, which
+    /// is what the returned `bool` reports. Values that can't be compared are always treated as
+    /// different.
+    pub fn set_component_value(
+        &mut self,
+        entity: Entity,
+        path: &str,
+        value: &dyn Reflect,
+    ) -> Result<bool, ComponentPathError> {
+        let path = ComponentPath::parse(path);
+        let (reflect_component, type_path) = self.reflect_component(path.component)?;
+        let mut entity_mut = self
+            .get_entity_mut(entity)
+            .ok_or(ComponentPathError::NoSuchEntity(entity))?;
+        let mut component = reflect_component.reflect_mut(&mut entity_mut).ok_or(
+            ComponentPathError::MissingComponent {
+                entity,
+                component: type_path,
+            },
+        )?;
+
+        // Look at the target without triggering change detection, the value may not change.
+        let target = follow_path_mut(component.bypass_change_detection(), path.field)?;
+        if target.reflect_partial_eq(value) == Some(true) {
+            return Ok(false);
+        }
+        let expected = target.get_represented_type_info().map(TypeInfo::type_id);
+        let found = value.get_represented_type_info().map(TypeInfo::type_id);
+        if expected.is_none() || expected != found {
+            return Err(ComponentPathError::TypeMismatch {
+                expected: target.reflect_type_path().to_string(),
+                found: value.reflect_type_path().to_string(),
+            });
+        }
+        target.apply(value);
+        component.set_changed();
+        Ok(true)
+    }
+
+    /// Parses `text` into a value with the [`ReflectValueFormat`] of the value at `path`, and sets
+    /// it like [`World::set_component_value`].
+    pub fn set_component_value_from_str(
+        &mut self,
+        entity: Entity,
+        path: &str,
+        text: &str,
+    ) -> Result<bool, ComponentPathError> {
+        let current = self.get_component_value(entity, path)?;
+        let type_path = current.reflect_type_path().to_string();
+        let value = {
+            let registry = self.type_registry()?.read();
+            value_format(&registry, current)
+                .ok_or_else(|| ComponentPathError::NoValueFormat(type_path.clone()))?
+                .parse(text)
+                .ok_or_else(|| ComponentPathError::InvalidValue {
+                    text: text.to_string(),
+                    type_path,
+                })?
+        };
+        self.set_component_value(entity, path, value.as_ref())
+    }
+
+    fn type_registry(&self) -> Result<&AppTypeRegistry, ComponentPathError> {
+        self.get_resource::<AppTypeRegistry>()
+            .ok_or(ComponentPathError::NoTypeRegistry)
+    }
+
+    /// Looks up the [`ReflectComponent`] and the full type path of the component named `name`.
+    fn reflect_component(
+        &self,
+        name: &str,
+    ) -> Result<(ReflectComponent, String), ComponentPathError> {
+        let registry = self.type_registry()?.read();
+        let registration = registry
+            .get_with_short_type_path(name)
+            .or_else(|| registry.get_with_type_path(name))
+            .ok_or_else(|| ComponentPathError::UnknownComponent(name.to_string()))?;
+        let type_path = registration.type_info().type_path().to_string();
+        match registration.data::<ReflectComponent>() {
+            Some(reflect_component) => Ok((reflect_component.clone(), type_path)),
+            None => Err(ComponentPathError::NotReflectComponent(type_path)),
+        }
+    }
+}
+
+fn value_format<'a>(
+    registry: &'a TypeRegistry,
+    value: &dyn Reflect,
+) -> Option<&'a ReflectValueFormat> {
+    let type_id = value.get_represented_type_info().map(TypeInfo::type_id)?;
+    registry.get_type_data::<ReflectValueFormat>(type_id)
+}
+
+fn parse_field(field: &str) -> Result<ParsedPath, ComponentPathError> {
+    ParsedPath::parse(field).map_err(|error| ComponentPathError::InvalidPath {
+        path: field.to_string(),
+        message: error.to_string(),
+    })
+}
+
+fn follow_path<'a>(
+    component: &'a dyn Reflect,
+    field: &str,
+) -> Result<&'a dyn Reflect, ComponentPathError> {
+    if field.is_empty() {
+        return Ok(component);
+    }
+    let path = parse_field(field)?;
+    component
+        .reflect_path(&path)
+        .map_err(|error| ComponentPathError::InvalidPath {
+            path: field.to_string(),
+            message: error.to_string(),
+        })
+}
+
+fn follow_path_mut<'a>(
+    component: &'a mut dyn Reflect,
+    field: &str,
+) -> Result<&'a mut dyn Reflect, ComponentPathError> {
+    if field.is_empty() {
+        return Ok(component);
+    }
+    let path = parse_field(field)?;
+    component
+        .reflect_path_mut(&path)
+        .map_err(|error| ComponentPathError::InvalidPath {
+            path: field.to_string(),
+            message: error.to_string(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ComponentPath, ComponentPathError};
+    use crate::prelude::{AppTypeRegistry, ReflectComponent};
+    use crate::{self as bevy_ecs, component::Component, world::World};
+    use bevy_reflect::{std_traits::ReflectValueFormat, Reflect};
+
+    #[derive(Reflect, Default, PartialEq, Debug, Clone, Copy)]
+    struct Vector {
+        x: f32,
+        y: f32,
+    }
+
+    #[derive(Component, Reflect, Default, PartialEq, Debug)]
+    #[reflect(Component)]
+    struct Body {
+        position: Vector,
+        mass: f32,
+        tags: Vec<u32>,
+    }
+
+    #[derive(Component, Reflect, Default)]
+    struct NotReflected;
+
+    fn world() -> World {
+        let mut world = World::new();
+        let type_registry = AppTypeRegistry::default();
+        {
+            let mut registry = type_registry.write();
+            registry.register::<Body>();
+            registry.register::<NotReflected>();
+            registry.register::<f32>();
+            registry.register_type_data::<f32, ReflectValueFormat>();
+        }
+        world.insert_resource(type_registry);
+        world
+    }
+
+    #[test]
+    fn parse_component_path() {
+        let parse = ComponentPath::parse;
+        assert_eq!(parse("Body").component, "Body");
+        assert_eq!(parse("Body").field, "");
+        assert_eq!(parse("Body.position.x").field, "position.x");
+        assert_eq!(parse("Body[0]").field, "[0]");
+        assert_eq!(parse("Foo<a::B>.value").component, "Foo<a::B>");
+        assert_eq!(parse("Foo<a::B>.value").field, "value");
+    }
+
+    #[test]
+    fn get_component_value() {
+        let mut world = world();
+        let entity = world
+            .spawn(Body {
+                mass: 2.,
+                tags: vec![3, 4],
+                ..Default::default()
+            })
+            .id();
+
+        let mass = world.get_component_value(entity, "Body.mass").unwrap();
+        assert_eq!(mass.downcast_ref::<f32>(), Some(&2.));
+        let tag = world.get_component_value(entity, "Body.tags[1]").unwrap();
+        assert_eq!(tag.downcast_ref::<u32>(), Some(&4));
+        let full_path = format!("{}.mass", std::any::type_name::<Body>());
+        assert!(world.get_component_value(entity, &full_path).is_ok());
+
+        assert_eq!(
+            world.format_component_value(entity, "Body.mass").unwrap(),
+            "2"
+        );
+        assert_eq!(
+            world
+                .format_component_value(entity, "Body.tags[0]")
+                .unwrap(),
+            "3"
+        );
+    }
+
+    #[test]
+    fn set_component_value() {
+        let mut world = world();
+        let entity = world.spawn(Body::default()).id();
+        world.clear_trackers();
+        let changed_tick = |world: &World| {
+            world
+                .entity(entity)
+                .get_change_ticks::<Body>()
+                .unwrap()
+                .last_changed_tick()
+        };
+        let spawned = changed_tick(&world);
+
+        // Setting an unchanged value doesn't trigger change detection.
+        assert_eq!(
+            world.set_component_value(entity, "Body.mass", &0f32),
+            Ok(false)
+        );
+        assert_eq!(changed_tick(&world), spawned);
+
+        assert_eq!(
+            world.set_component_value_from_str(entity, "Body.position.y", "1.5"),
+            Ok(true)
+        );
+        assert_ne!(changed_tick(&world), spawned);
+        assert_eq!(world.get::<Body>(entity).unwrap().position.y, 1.5);
+
+        let position = Vector { x: 3., y: 4. };
+        assert_eq!(
+            world.set_component_value(entity, "Body.position", &position),
+            Ok(true)
+        );
+        assert_eq!(world.get::<Body>(entity).unwrap().position, position);
+    }
+
+    #[test]
+    fn component_value_errors() {
+        let mut world = world();
+        let entity = world.spawn(NotReflected).id();
+        let body = world.spawn(Body::default()).id();
+
+        assert!(matches!(
+            world.get_component_value(entity, "Body.mass"),
+            Err(ComponentPathError::MissingComponent { .. })
+        ));
+        assert!(matches!(
+            world.get_component_value(entity, "Missing.mass"),
+            Err(ComponentPathError::UnknownComponent(_))
+        ));
+        assert!(matches!(
+            world.get_component_value(entity, "NotReflected"),
+            Err(ComponentPathError::NotReflectComponent(_))
+        ));
+        assert!(matches!(
+            world.get_component_value(body, "Body.velocity"),
+            Err(ComponentPathError::InvalidPath { .. })
+        ));
+        assert!(matches!(
+            world.set_component_value(body, "Body.mass", &1u32),
+            Err(ComponentPathError::TypeMismatch { .. })
+        ));
+        assert!(matches!(
+            world.set_component_value_from_str(body, "Body.mass", "heavy"),
+            Err(ComponentPathError::InvalidValue { .. })
+        ));
+        assert!(matches!(
+            world.set_component_value_from_str(body, "Body.position", "1, 2"),
+            Err(ComponentPathError::NoValueFormat(_))
+        ));
+    }
+}
@@ -8,6 +8,7 @@ use bevy_reflect::{impl_reflect_value, ReflectDeserialize, ReflectSerialize, Typ
 
 mod bundle;
 mod component;
+mod component_path;
 mod entity_commands;
 mod from_world;
 mod map_entities;
@@ -15,6 +16,7 @@ mod resource;
 
 pub use bundle::{ReflectBundle, ReflectBundleFns};
 pub use component::{ReflectComponent, ReflectComponentFns};
+pub use component_path::{ComponentPath, ComponentPathError};
 pub use entity_commands::ReflectCommandExt;
 pub use from_world::{ReflectFromWorld, ReflectFromWorldFns};
 pub use map_entities::ReflectMapEntities;
@@ -0,0 +1,427 @@
+//! Streams of component changes for replicating a [`World`], for example over the network.
+//!
+//! The [`ReplicationFeed`] collects the components of registered types that were added, changed or
+//! removed since it last collected them. Networking crates read the feed and send the changes to
+//! their clients, filtered by the [`ClientInterest`] of each client, and map the entities of the
+//! server to the entities of the client with [`ReplicatedEntities`].
+
+use crate as bevy_ecs;
+use crate::{
+    change_detection::{DetectChanges, Ref},
+    component::{Component, ComponentId, Tick},
+    entity::{Entity, EntityMapper},
+    removal_detection::RemovedComponents,
+    system::{Query, Resource, SystemState},
+    world::{Mut, World},
+};
+use bevy_utils::{EntityHashMap, EntityHashSet};
+
+/// How a component changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ChangeKind {
+    /// The component was inserted on the entity.
+    Added,
+    /// The component was mutated.
+    Changed,
+    /// The component was removed from the entity, or the entity was despawned.
+    Removed,
+}
+
+/// A change of a component, collected by the [`ReplicationFeed`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ComponentChange {
+    /// The entity of the component.
+    pub entity: Entity,
+    /// The type of the component.
+    pub component: ComponentId,
+    /// How the component changed.
+    pub kind: ChangeKind,
+    /// When the component changed, or when the removal was collected for
+    /// [`ChangeKind::Removed`].
+    pub tick: Tick,
+}
+
+/// Collects the changes of the components of registered types.
+///
+/// Register the types to replicate with [`ReplicationFeed::register`], then call
+/// [`ReplicationFeed::collect`], or add the [`collect_replication_changes`] system, once per
+/// update. The first collection after a type is registered reports all its existing components
+/// as [`ChangeKind::Added`].
+///
+/// Removals are read from [`RemovedComponents`], so they must be collected at least once per
+/// update to not be missed.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::replication::{ChangeKind, ReplicationFeed};
+/// #[derive(Component)]
+/// struct Health(u32);
+///
+/// let mut world = World::new();
+/// let mut feed = ReplicationFeed::default();
+/// feed.register::<Health>(&mut world);
+///
+/// let entity = world.spawn(Health(100)).id();
+/// feed.collect(&mut world);
+/// assert_eq!(feed.changes()[0].entity, entity);
+/// assert_eq!(feed.changes()[0].kind, ChangeKind::Added);
+/// ```
+#[derive(Resource, Default)]
+pub struct ReplicationFeed {
+    trackers: Vec<Box<dyn ComponentTracker>>,
+    changes: Vec<ComponentChange>,
+}
+
+impl ReplicationFeed {
+    /// Registers the component type `C` to be replicated.
+    ///
+    /// Registering the same type again does nothing.
+    pub fn register<C: Component>(&mut self, world: &mut World) -> &mut Self {
+        let component_id = world.init_component::<C>();
+        if !self.is_registered(component_id) {
+            self.trackers.push(Box::new(Tracker::<C> {
+                component_id,
+                state: SystemState::new(world),
+            }));
+        }
+        self
+    }
+
+    /// Returns `true` if the component type with the given id is replicated.
+    pub fn is_registered(&self, component_id: ComponentId) -> bool {
+        self.trackers
+            .iter()
+            .any(|tracker| tracker.component_id() == component_id)
+    }
+
+    /// Returns the ids of the replicated component types, in the order they were registered.
+    pub fn registered(&self) -> impl Iterator<Item = ComponentId> + '_ {
+        self.trackers.iter().map(|tracker| tracker.component_id())
+    }
+
+    /// Replaces the changes of the previous collection with the changes that happened since then.
+    pub fn collect(&mut self, world: &mut World) {
+        self.changes.clear();
+        for tracker in &mut self.trackers {
+            tracker.collect(world, &mut self.changes);
+        }
+    }
+
+    /// Returns the changes of the last collection, grouped by component type.
+    ///
+    /// The removals of each type come before its other changes, so applying the changes in order
+    /// keeps the components that were removed then inserted again.
+    pub fn changes(&self) -> &[ComponentChange] {
+        &self.changes
+    }
+
+    /// Returns the changes of the last collection to the entities the client is interested in.
+    pub fn changes_for<'a>(
+        &'a self,
+        interest: &'a ClientInterest,
+    ) -> impl Iterator<Item = &'a ComponentChange> + 'a {
+        self.changes
+            .iter()
+            .filter(|change| interest.contains(change.entity))
+    }
+
+    /// Returns the replicated components of `entity`, to send the whole entity to a client it
+    /// [entered the interest of](ClientInterest::drain_entered).
+    pub fn components_of<'a>(
+        &'a self,
+        world: &'a World,
+        entity: Entity,
+    ) -> impl Iterator<Item = ComponentId> + 'a {
+        let entity = world.get_entity(entity);
+        self.registered().filter(move |&component_id| {
+            entity.is_some_and(|entity| entity.contains_id(component_id))
+        })
+    }
+}
+
+/// Collects the changes of the [`ReplicationFeed`] resource.
+pub fn collect_replication_changes(world: &mut World) {
+    world.resource_scope(|world, mut feed: Mut<ReplicationFeed>| feed.collect(world));
+}
+
+trait ComponentTracker: Send + Sync {
+    fn component_id(&self) -> ComponentId;
+
+    fn collect(&mut self, world: &mut World, changes: &mut Vec<ComponentChange>);
+}
+
+struct Tracker<C: Component> {
+    component_id: ComponentId,
+    state: SystemState<(
+        Query<'static, 'static, (Entity, Ref<'static, C>)>,
+        RemovedComponents<'static, 'static, C>,
+    )>,
+}
+
+impl<C: Component> ComponentTracker for Tracker<C> {
+    fn component_id(&self) -> ComponentId {
+        self.component_id
+    }
+
+    fn collect(&mut self, world: &mut World, changes: &mut Vec<ComponentChange>) {
+        let removal_tick = world.change_tick();
+        let (query, mut removed) = self.state.get_mut(world);
+        // Removals come first, so that a component removed then inserted again is applied in order
+        changes.extend(removed.read().map(|entity| ComponentChange {
+            entity,
+            component: self.component_id,
+            kind: ChangeKind::Removed,
+            tick: removal_tick,
+        }));
+        for (entity, component) in &query {
+            let kind = if component.is_added() {
+                ChangeKind::Added
+            } else if component.is_changed() {
+                ChangeKind::Changed
+            } else {
+                continue;
+            };
+            changes.push(ComponentChange {
+                entity,
+                component: self.component_id,
+                kind,
+                tick: component.last_changed(),
+            });
+        }
+    }
+}
+
+/// The entities a client is interested in, for interest management.
+///
+/// Only the changes to these entities should be sent to the client, see
+/// [`ReplicationFeed::changes_for`]. Entities entering the interest of the client should be sent
+/// whole, and entities leaving it should be despawned on the client.
+#[derive(Debug, Clone, Default)]
+pub struct ClientInterest {
+    entities: EntityHashSet<Entity>,
+    entered: Vec<Entity>,
+    exited: Vec<Entity>,
+}
+
+impl ClientInterest {
+    /// Adds `entity` to the interest of the client, returning `true` if it wasn't already.
+    pub fn insert(&mut self, entity: Entity) -> bool {
+        let inserted = self.entities.insert(entity);
+        if inserted {
+            self.exited.retain(|&exited| exited != entity);
+            self.entered.push(entity);
+        }
+        inserted
+    }
+
+    /// Removes `entity` from the interest of the client, returning `true` if it was in it.
+    pub fn remove(&mut self, entity: Entity) -> bool {
+        let removed = self.entities.remove(&entity);
+        if removed {
+            self.entered.retain(|&entered| entered != entity);
+            self.exited.push(entity);
+        }
+        removed
+    }
+
+    /// Returns `true` if the client is interested in `entity`.
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.entities.contains(&entity)
+    }
+
+    /// Returns the entities the client is interested in.
+    pub fn iter(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.entities.iter().copied()
+    }
+
+    /// Removes and returns the entities added to the interest of the client since the last call.
+    pub fn drain_entered(&mut self) -> impl Iterator<Item = Entity> + '_ {
+        self.entered.drain(..)
+    }
+
+    /// Removes and returns the entities removed from the interest of the client since the last
+    /// call.
+    pub fn drain_exited(&mut self) -> impl Iterator<Item = Entity> + '_ {
+        self.exited.drain(..)
+    }
+}
+
+/// Maps the entities of a remote [`World`], like the world of a server, to the entities of the
+/// local world they are replicated to.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ReplicatedEntities {
+    to_local: EntityHashMap<Entity, Entity>,
+    to_remote: EntityHashMap<Entity, Entity>,
+}
+
+impl ReplicatedEntities {
+    /// Maps the `remote` entity to the `local` entity.
+    pub fn insert(&mut self, remote: Entity, local: Entity) {
+        if let Some(previous) = self.to_local.insert(remote, local) {
+            self.to_remote.remove(&previous);
+        }
+        if let Some(previous) = self.to_remote.insert(local, remote) {
+            if previous != remote {
+                self.to_local.remove(&previous);
+            }
+        }
+    }
+
+    /// Returns the local entity the `remote` entity is mapped to.
+    pub fn local(&self, remote: Entity) -> Option<Entity> {
+        self.to_local.get(&remote).copied()
+    }
+
+    /// Returns the remote entity the `local` entity is mapped to.
+    pub fn remote(&self, local: Entity) -> Option<Entity> {
+        self.to_remote.get(&local).copied()
+    }
+
+    /// Removes the mapping of the `remote` entity, returning the local entity it was mapped to.
+    pub fn remove_remote(&mut self, remote: Entity) -> Option<Entity> {
+        let local = self.to_local.remove(&remote)?;
+        self.to_remote.remove(&local);
+        Some(local)
+    }
+
+    /// Returns the local entity the `remote` entity is mapped to, spawning an empty entity in
+    /// `world` for it if it isn't mapped yet.
+    pub fn local_or_spawn(&mut self, world: &mut World, remote: Entity) -> Entity {
+        if let Some(local) = self.local(remote) {
+            return local;
+        }
+        let local = world.spawn_empty().id();
+        self.insert(remote, local);
+        local
+    }
+
+    /// Returns an [`EntityMapper`] mapping remote entities to local entities, spawning the
+    /// entities that aren't mapped yet, to use with
+    /// [`MapEntities`](crate::entity::MapEntities) on replicated components.
+    pub fn mapper<'a>(&'a mut self, world: &'a mut World) -> ReplicatedEntityMapper<'a> {
+        ReplicatedEntityMapper {
+            entities: self,
+            world,
+        }
+    }
+}
+
+/// An [`EntityMapper`] for replicated components, see [`ReplicatedEntities::mapper`].
+pub struct ReplicatedEntityMapper<'a> {
+    entities: &'a mut ReplicatedEntities,
+    world: &'a mut World,
+}
+
+impl EntityMapper for ReplicatedEntityMapper<'_> {
+    fn map_entity(&mut self, entity: Entity) -> Entity {
+        self.entities.local_or_spawn(self.world, entity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::MapEntities;
+
+    #[derive(Component)]
+    struct Health(u32);
+
+    #[derive(Component)]
+    struct Target(Entity);
+
+    impl MapEntities for Target {
+        fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+            self.0 = entity_mapper.map_entity(self.0);
+        }
+    }
+
+    #[test]
+    fn replication_feed_collects_changes() {
+        let mut world = World::new();
+        let a = world.spawn(Health(10)).id();
+        let b = world.spawn(Health(20)).id();
+
+        let mut feed = ReplicationFeed::default();
+        feed.register::<Health>(&mut world);
+        feed.collect(&mut world);
+        let kinds = |feed: &ReplicationFeed| {
+            let mut kinds: Vec<_> = feed
+                .changes()
+                .iter()
+                .map(|change| (change.entity, change.kind))
+                .collect();
+            kinds.sort();
+            kinds
+        };
+        assert_eq!(
+            kinds(&feed),
+            vec![(a, ChangeKind::Added), (b, ChangeKind::Added)]
+        );
+
+        feed.collect(&mut world);
+        assert!(feed.changes().is_empty());
+
+        world.get_mut::<Health>(a).unwrap().0 = 5;
+        world.despawn(b);
+        let c = world.spawn(Health(30)).id();
+        feed.collect(&mut world);
+        assert_eq!(
+            kinds(&feed),
+            vec![
+                (a, ChangeKind::Changed),
+                (b, ChangeKind::Removed),
+                (c, ChangeKind::Added)
+            ]
+        );
+
+        let mut interest = ClientInterest::default();
+        interest.insert(c);
+        let visible: Vec<_> = feed.changes_for(&interest).map(|c| c.entity).collect();
+        assert_eq!(visible, vec![c]);
+        assert_eq!(interest.drain_entered().collect::<Vec<_>>(), vec![c]);
+        assert_eq!(
+            feed.components_of(&world, c).collect::<Vec<_>>(),
+            vec![world.component_id::<Health>().unwrap()]
+        );
+    }
+
+    #[test]
+    fn replication_feed_orders_removals_first() {
+        let mut world = World::new();
+        let entity = world.spawn(Health(10)).id();
+
+        let mut feed = ReplicationFeed::default();
+        feed.register::<Health>(&mut world);
+        feed.collect(&mut world);
+
+        world.entity_mut(entity).remove::<Health>();
+        world.entity_mut(entity).insert(Health(20));
+        feed.collect(&mut world);
+        let kinds: Vec<_> = feed
+            .changes()
+            .iter()
+            .map(|change| (change.entity, change.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![(entity, ChangeKind::Removed), (entity, ChangeKind::Added)]
+        );
+    }
+
+    #[test]
+    fn replicated_entities_map_remote_entities() {
+        let mut server = World::new();
+        let remote_target = server.spawn_empty().id();
+
+        let mut client = World::new();
+        let mut entities = ReplicatedEntities::default();
+        let mut target = Target(remote_target);
+        target.map_entities(&mut entities.mapper(&mut client));
+
+        assert_eq!(entities.local(remote_target), Some(target.0));
+        assert_eq!(entities.remote(target.0), Some(remote_target));
+        assert!(client.get_entity(target.0).is_some());
+        assert_eq!(entities.remove_remote(remote_target), Some(target.0));
+        assert_eq!(entities.remote(target.0), None);
+    }
+}
@@ -6,6 +6,8 @@ pub use self::multi_threaded::{MainThreadExecutor, MultiThreadedExecutor};
 pub use self::simple::SimpleExecutor;
 pub use self::single_threaded::SingleThreadedExecutor;
 
+use std::borrow::Cow;
+
 use fixedbitset::FixedBitSet;
 
 use crate::{
@@ -25,6 +27,8 @@ pub(super) trait SystemExecutor: Send + Sync {
         world: &mut World,
     );
     fn set_apply_final_deferred(&mut self, value: bool);
+    /// Takes the name of the system whose panic unwound out of the last call to `run`.
+    fn take_panicked_system(&mut self) -> Option<Cow<'static, str>>;
 }
 
 /// Specifies how a [`Schedule`](super::Schedule) will be run.
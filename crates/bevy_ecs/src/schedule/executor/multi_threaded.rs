@@ -1,5 +1,6 @@
 use std::{
     any::Any,
+    borrow::Cow,
     sync::{Arc, Mutex},
 };
 
@@ -73,6 +74,9 @@ struct SystemResult {
     success: bool,
 }
 
+/// The name of a system that panicked, and the payload it panicked with.
+type SystemPanicPayload = (Cow<'static, str>, Box<dyn Any + Send>);
+
 /// Runs the schedule using a thread pool. Non-conflicting systems can run in parallel.
 pub struct MultiThreadedExecutor {
     /// Sends system completion events.
@@ -111,8 +115,10 @@ pub struct MultiThreadedExecutor {
     unapplied_systems: FixedBitSet,
     /// Setting when true applies deferred system buffers after all systems have run
     apply_final_deferred: bool,
-    /// When set, tells the executor that a thread has panicked.
-    panic_payload: Arc<Mutex<Option<Box<dyn Any + Send>>>>,
+    /// When set, tells the executor that a thread has panicked, and in which system.
+    panic_payload: Arc<Mutex<Option<SystemPanicPayload>>>,
+    /// The name of the system that panicked during the last run.
+    panicked_system: Option<Cow<'static, str>>,
     /// When set, stops the executor from running any more systems.
     stop_spawning: bool,
 }
@@ -144,6 +150,11 @@ impl SystemExecutor for MultiThreadedExecutor {
         self.completed_systems = FixedBitSet::with_capacity(sys_count);
         self.skipped_systems = FixedBitSet::with_capacity(sys_count);
         self.unapplied_systems = FixedBitSet::with_capacity(sys_count);
+        // a run that panicked may have stopped with systems still marked as running
+        self.active_access.clear();
+        self.local_thread_running = false;
+        self.exclusive_running = false;
+        self.stop_spawning = false;
 
         self.system_task_metadata = Vec::with_capacity(sys_count);
         for index in 0..sys_count {
@@ -275,8 +286,9 @@ impl SystemExecutor for MultiThreadedExecutor {
         }
 
         // check to see if there was a panic
-        let mut payload = self.panic_payload.lock().unwrap();
-        if let Some(payload) = payload.take() {
+        let payload = self.panic_payload.lock().unwrap().take();
+        if let Some((system, payload)) = payload {
+            self.panicked_system = Some(system);
             std::panic::resume_unwind(payload);
         }
 
@@ -291,6 +303,10 @@ impl SystemExecutor for MultiThreadedExecutor {
     fn set_apply_final_deferred(&mut self, value: bool) {
         self.apply_final_deferred = value;
     }
+
+    fn take_panicked_system(&mut self) -> Option<Cow<'static, str>> {
+        self.panicked_system.take()
+    }
 }
 
 impl MultiThreadedExecutor {
@@ -319,6 +335,7 @@ impl MultiThreadedExecutor {
             unapplied_systems: FixedBitSet::new(),
             apply_final_deferred: true,
             panic_payload: Arc::new(Mutex::new(None)),
+            panicked_system: None,
             stop_spawning: false,
         }
     }
@@ -546,7 +563,7 @@ impl MultiThreadedExecutor {
                 // set the payload to propagate the error
                 {
                     let mut panic_payload = panic_payload.lock().unwrap();
-                    *panic_payload = Some(payload);
+                    *panic_payload = Some((system.name(), payload));
                 }
             }
         };
@@ -630,7 +647,7 @@ impl MultiThreadedExecutor {
                     );
                     // set the payload to propagate the error
                     let mut panic_payload = panic_payload.lock().unwrap();
-                    *panic_payload = Some(payload);
+                    *panic_payload = Some((system.name(), payload));
                 }
             };
 
@@ -713,7 +730,7 @@ fn apply_deferred(
     unapplied_systems: &FixedBitSet,
     systems: &[SyncUnsafeCell<BoxedSystem>],
     world: &mut World,
-) -> Result<(), Box<dyn Any + Send>> {
+) -> Result<(), SystemPanicPayload> {
     for system_index in unapplied_systems.ones() {
         // SAFETY: none of these systems are running, no other references exist
         let system = unsafe { &mut *systems[system_index].get() };
@@ -725,7 +742,7 @@ fn apply_deferred(
                 "Encountered a panic when applying buffers for system `{}`!",
                 &*system.name()
             );
-            return Err(payload);
+            return Err((system.name(), payload));
         }
     }
     Ok(())
@@ -1,7 +1,7 @@
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::info_span;
 use fixedbitset::FixedBitSet;
-use std::panic::AssertUnwindSafe;
+use std::{borrow::Cow, panic::AssertUnwindSafe};
 
 use crate::{
     schedule::{BoxedCondition, ExecutorKind, SystemExecutor, SystemSchedule},
@@ -16,6 +16,8 @@ pub struct SimpleExecutor {
     evaluated_sets: FixedBitSet,
     /// Systems that have run or been skipped.
     completed_systems: FixedBitSet,
+    /// The name of the system that panicked during the last run.
+    panicked_system: Option<Cow<'static, str>>,
 }
 
 impl SystemExecutor for SimpleExecutor {
@@ -91,6 +93,7 @@ impl SystemExecutor for SimpleExecutor {
             }));
             if let Err(payload) = res {
                 eprintln!("Encountered a panic in system `{}`!", &*system.name());
+                self.panicked_system = Some(system.name());
                 std::panic::resume_unwind(payload);
             }
 
@@ -104,6 +107,10 @@ impl SystemExecutor for SimpleExecutor {
     fn set_apply_final_deferred(&mut self, _: bool) {
         // do nothing. simple executor does not do a final sync
     }
+
+    fn take_panicked_system(&mut self) -> Option<Cow<'static, str>> {
+        self.panicked_system.take()
+    }
 }
 
 impl SimpleExecutor {
@@ -113,6 +120,7 @@ impl SimpleExecutor {
         Self {
             evaluated_sets: FixedBitSet::new(),
             completed_systems: FixedBitSet::new(),
+            panicked_system: None,
         }
     }
 }
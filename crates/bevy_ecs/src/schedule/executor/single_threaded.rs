@@ -1,7 +1,7 @@
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::info_span;
 use fixedbitset::FixedBitSet;
-use std::panic::AssertUnwindSafe;
+use std::{borrow::Cow, panic::AssertUnwindSafe};
 
 use crate::{
     schedule::{is_apply_deferred, BoxedCondition, ExecutorKind, SystemExecutor, SystemSchedule},
@@ -22,6 +22,8 @@ pub struct SingleThreadedExecutor {
     unapplied_systems: FixedBitSet,
     /// Setting when true applies deferred system buffers after all systems have run
     apply_final_deferred: bool,
+    /// The name of the system that panicked during the last run.
+    panicked_system: Option<Cow<'static, str>>,
 }
 
 impl SystemExecutor for SingleThreadedExecutor {
@@ -102,6 +104,7 @@ impl SystemExecutor for SingleThreadedExecutor {
                 }));
                 if let Err(payload) = res {
                     eprintln!("Encountered a panic in system `{}`!", &*system.name());
+                    self.panicked_system = Some(system.name());
                     std::panic::resume_unwind(payload);
                 }
                 self.unapplied_systems.insert(system_index);
@@ -118,6 +121,10 @@ impl SystemExecutor for SingleThreadedExecutor {
     fn set_apply_final_deferred(&mut self, apply_final_deferred: bool) {
         self.apply_final_deferred = apply_final_deferred;
     }
+
+    fn take_panicked_system(&mut self) -> Option<Cow<'static, str>> {
+        self.panicked_system.take()
+    }
 }
 
 impl SingleThreadedExecutor {
@@ -130,6 +137,7 @@ impl SingleThreadedExecutor {
             completed_systems: FixedBitSet::new(),
             unapplied_systems: FixedBitSet::new(),
             apply_final_deferred: true,
+            panicked_system: None,
         }
     }
 
@@ -1,6 +1,9 @@
 use std::{
+    any::Any,
+    borrow::Cow,
     collections::BTreeSet,
     fmt::{Debug, Write},
+    panic::AssertUnwindSafe,
     result::Result,
 };
 
@@ -218,6 +221,67 @@ pub struct Schedule {
 #[derive(ScheduleLabel, Hash, PartialEq, Eq, Debug, Clone)]
 struct DefaultSchedule;
 
+/// A resource that catches the panics of systems in the [schedules](Schedule) run on its world.
+///
+/// Without it, a panicking system unwinds out of [`Schedule::run`]. With it, the panic is caught
+/// when it leaves the innermost running schedule, the function is called with the world and the
+/// [`SystemPanic`], and the schedule returns as if it had finished, skipping the systems that
+/// had not run yet. A handler that can't recover from the panic can continue it with
+/// [`std::panic::resume_unwind`].
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::schedule::{SystemPanic, SystemPanicHandler};
+/// fn report(world: &mut World, panic: SystemPanic) {
+///     eprintln!("{:?} panicked: {:?}", panic.system, panic.message());
+///     world.insert_resource(Crashed);
+/// }
+///
+/// #[derive(Resource)]
+/// struct Crashed;
+///
+/// let mut world = World::new();
+/// world.insert_resource(SystemPanicHandler(report));
+///
+/// let mut schedule = Schedule::default();
+/// schedule.add_systems(|| panic!("oh no"));
+/// schedule.run(&mut world);
+/// assert!(world.contains_resource::<Crashed>());
+/// ```
+#[derive(Resource, Clone, Copy)]
+pub struct SystemPanicHandler(pub fn(&mut World, SystemPanic));
+
+/// A panic in a system, caught by a [`SystemPanicHandler`].
+pub struct SystemPanic {
+    /// The schedule the system was run in.
+    pub schedule: InternedScheduleLabel,
+    /// The name of the system that panicked, or `None` for panics that aren't attributed to a
+    /// system, like those in run conditions.
+    pub system: Option<Cow<'static, str>>,
+    /// The value the system panicked with.
+    pub payload: Box<dyn Any + Send>,
+}
+
+impl SystemPanic {
+    /// Returns the panic message, if the panic was raised with one, like [`panic!`] does.
+    pub fn message(&self) -> Option<&str> {
+        self.payload
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| self.payload.downcast_ref::<String>().map(String::as_str))
+    }
+}
+
+impl Debug for SystemPanic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SystemPanic")
+            .field("schedule", &self.schedule)
+            .field("system", &self.system)
+            .field("message", &self.message())
+            .finish()
+    }
+}
+
 impl Default for Schedule {
     /// Creates a schedule with a default label. Only use in situations where
     /// you don't care about the [`ScheduleLabel`]. Inserting a default schedule
@@ -324,6 +388,9 @@ impl Schedule {
     }
 
     /// Runs all systems in this schedule on the `world`, using its current execution strategy.
+    ///
+    /// A panic in a system unwinds out of this method, unless the `world` has a
+    /// [`SystemPanicHandler`].
     pub fn run(&mut self, world: &mut World) {
         #[cfg(feature = "trace")]
         let _span = info_span!("schedule", name = ?self.label).entered();
@@ -341,7 +408,26 @@ impl Schedule {
             Some(mut stepping) => stepping.skipped_systems(self),
         };
 
-        self.executor.run(&mut self.executable, skip_systems, world);
+        let Some(&SystemPanicHandler(handler)) = world.get_resource::<SystemPanicHandler>() else {
+            self.executor.run(&mut self.executable, skip_systems, world);
+            return;
+        };
+        let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+            self.executor.run(&mut self.executable, skip_systems, world);
+        }));
+        if let Err(payload) = result {
+            let system = self.executor.take_panicked_system();
+            // the executor stopped partway through its run
+            self.executor.init(&self.executable);
+            handler(
+                world,
+                SystemPanic {
+                    schedule: self.label,
+                    system,
+                    payload,
+                },
+            );
+        }
     }
 
     /// Initializes any newly-added systems and conditions, rebuilds the executable schedule,
@@ -2003,7 +2089,8 @@ mod tests {
         self as bevy_ecs,
         prelude::{Res, Resource},
         schedule::{
-            IntoSystemConfigs, IntoSystemSetConfigs, Schedule, ScheduleBuildSettings, SystemSet,
+            ExecutorKind, IntoSystemConfigs, IntoSystemSetConfigs, Schedule, ScheduleBuildSettings,
+            SystemPanicHandler, SystemSet,
         },
         system::Commands,
         world::World,
@@ -2015,6 +2102,44 @@ mod tests {
     #[derive(Resource)]
     struct Resource2;
 
+    #[test]
+    fn system_panic_handler() {
+        #[derive(Resource, Default)]
+        struct Panics(Vec<(String, Option<String>)>);
+
+        fn failing_system() {
+            panic!("failing");
+        }
+
+        for kind in [
+            ExecutorKind::Simple,
+            ExecutorKind::SingleThreaded,
+            ExecutorKind::MultiThreaded,
+        ] {
+            let mut world = World::new();
+            world.init_resource::<Panics>();
+            world.insert_resource(SystemPanicHandler(|world, panic| {
+                let system = panic.system.as_deref().map(str::to_string);
+                let message = panic.message().unwrap().to_string();
+                world.resource_mut::<Panics>().0.push((message, system));
+            }));
+            let mut schedule = Schedule::default();
+            schedule.set_executor_kind(kind);
+            schedule.add_systems(failing_system);
+
+            // the schedule can run again after a panic
+            schedule.run(&mut world);
+            schedule.run(&mut world);
+
+            let system = Some(std::any::type_name_of_val(&failing_system).to_string());
+            assert_eq!(
+                world.resource::<Panics>().0,
+                vec![("failing".to_string(), system.clone()); 2],
+                "{kind:?}"
+            );
+        }
+    }
+
     // regression test for https://github.com/bevyengine/bevy/issues/9114
     #[test]
     fn ambiguous_with_not_breaking_run_conditions() {
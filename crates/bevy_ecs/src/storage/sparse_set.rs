@@ -36,6 +36,11 @@ impl<I, V> SparseArray<I, V> {
             marker: PhantomData,
         }
     }
+
+    /// Returns the number of bytes allocated for the values, including unused capacity.
+    pub(crate) fn allocated_bytes(&self) -> usize {
+        self.values.capacity() * std::mem::size_of::<Option<V>>()
+    }
 }
 
 macro_rules! impl_sparse_array {
@@ -157,6 +162,20 @@ impl ComponentSparseSet {
         self.dense.len() == 0
     }
 
+    /// Returns the number of bytes allocated for the component values and the entity mappings.
+    ///
+    /// This includes unused capacity.
+    pub fn allocated_bytes(&self) -> usize {
+        // The type of `entities` depends on whether debug assertions are enabled.
+        fn vec_allocated_bytes<T>(vec: &Vec<T>) -> usize {
+            vec.capacity() * std::mem::size_of::<T>()
+        }
+
+        self.dense.allocated_bytes()
+            + vec_allocated_bytes(&self.entities)
+            + self.sparse.allocated_bytes()
+    }
+
     /// Inserts the `entity` key and component `value` pair into this sparse
     /// set.
     ///
@@ -570,6 +589,16 @@ impl SparseSets {
         self.sets.is_empty()
     }
 
+    /// Returns the number of bytes allocated for all of the [`ComponentSparseSet`]s in this collection.
+    ///
+    /// This includes unused capacity.
+    pub fn allocated_bytes(&self) -> usize {
+        self.sets
+            .values()
+            .map(ComponentSparseSet::allocated_bytes)
+            .sum()
+    }
+
     /// An Iterator visiting all ([`ComponentId`], [`ComponentSparseSet`]) pairs.
     /// NOTE: Order is not guaranteed.
     pub fn iter(&self) -> impl Iterator<Item = (ComponentId, &ComponentSparseSet)> {
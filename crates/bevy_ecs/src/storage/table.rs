@@ -210,6 +210,15 @@ impl Column {
         self.data.len()
     }
 
+    /// Returns the number of bytes allocated for the column's components and change detection ticks.
+    ///
+    /// This includes unused capacity.
+    pub fn allocated_bytes(&self) -> usize {
+        self.data.capacity() * self.data.layout().size()
+            + (self.added_ticks.capacity() + self.changed_ticks.capacity())
+                * std::mem::size_of::<UnsafeCell<Tick>>()
+    }
+
     /// Checks if the column is empty. Returns `true` if there are no elements, `false` otherwise.
     #[inline]
     pub fn is_empty(&self) -> bool {
@@ -763,6 +772,14 @@ impl Table {
         self.entities.capacity()
     }
 
+    /// Returns the number of bytes allocated for the table's entities and [`Column`]s.
+    ///
+    /// This includes unused capacity.
+    pub fn allocated_bytes(&self) -> usize {
+        self.entities.capacity() * std::mem::size_of::<Entity>()
+            + self.iter().map(Column::allocated_bytes).sum::<usize>()
+    }
+
     /// Checks if the [`Table`] is empty or not.
     ///
     /// Returns `true` if the table contains no entities, `false` otherwise.
@@ -827,6 +844,13 @@ impl Tables {
         self.tables.is_empty()
     }
 
+    /// Returns the number of bytes allocated for all of the [`Table`]s in this collection.
+    ///
+    /// This includes unused capacity.
+    pub fn allocated_bytes(&self) -> usize {
+        self.tables.iter().map(Table::allocated_bytes).sum()
+    }
+
     /// Fetches a [`Table`] by its [`TableId`].
     ///
     /// Returns `None` if `id` is invalid.
@@ -957,5 +981,9 @@ mod tests {
 
         assert_eq!(table.entity_capacity(), 256);
         assert_eq!(table.entity_count(), 200);
+        assert!(
+            table.allocated_bytes()
+                >= 256 * (std::mem::size_of::<Entity>() + std::mem::size_of::<W<TableRow>>())
+        );
     }
 }
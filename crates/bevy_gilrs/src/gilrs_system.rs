@@ -1,27 +1,40 @@
 use crate::converter::{convert_axis, convert_button, convert_gamepad_id};
-use bevy_ecs::event::EventWriter;
+use bevy_ecs::event::{EventReader, EventWriter};
 use bevy_ecs::system::{NonSend, NonSendMut, Res, ResMut};
 use bevy_input::gamepad::{
     GamepadAxisChangedEvent, GamepadButtonChangedEvent, GamepadConnection, GamepadConnectionEvent,
     GamepadSettings,
 };
-use bevy_input::gamepad::{GamepadEvent, GamepadInfo};
+use bevy_input::gamepad::{
+    GamepadCapabilities, GamepadEvent, GamepadInfo, GamepadLightRequest,
+    GamepadTriggerEffectRequest,
+};
 use bevy_input::prelude::{GamepadAxis, GamepadButton};
+use bevy_input::timestamp::{send_timestamped_input_event, InputClock, TimestampedInputEvent};
 use bevy_input::Axis;
+use bevy_log::debug;
 use gilrs::{ev::filter::axis_dpad_to_button, EventType, Filter, Gilrs};
 
+fn gamepad_info(gamepad: gilrs::Gamepad<'_>) -> GamepadInfo {
+    GamepadInfo {
+        name: gamepad.name().into(),
+        // Gilrs can't set lights or trigger effects.
+        capabilities: GamepadCapabilities {
+            rumble: gamepad.is_ff_supported(),
+            light: false,
+            trigger_effects: false,
+        },
+    }
+}
+
 pub fn gilrs_event_startup_system(
     gilrs: NonSend<Gilrs>,
     mut connection_events: EventWriter<GamepadConnectionEvent>,
 ) {
     for (id, gamepad) in gilrs.gamepads() {
-        let info = GamepadInfo {
-            name: gamepad.name().into(),
-        };
-
         connection_events.send(GamepadConnectionEvent {
             gamepad: convert_gamepad_id(id),
-            connection: GamepadConnection::Connected(info),
+            connection: GamepadConnection::Connected(gamepad_info(gamepad)),
         });
     }
 }
@@ -29,10 +42,19 @@ pub fn gilrs_event_startup_system(
 pub fn gilrs_event_system(
     mut gilrs: NonSendMut<Gilrs>,
     mut events: EventWriter<GamepadEvent>,
+    mut input_clock: ResMut<InputClock>,
+    mut timestamped_events: EventWriter<TimestampedInputEvent>,
     mut gamepad_buttons: ResMut<Axis<GamepadButton>>,
     gamepad_axis: Res<Axis<GamepadAxis>>,
     gamepad_settings: Res<GamepadSettings>,
 ) {
+    // The events are timestamped when they are drained rather than with the time gilrs received
+    // them, which comes from the system clock: it isn't monotonic, and backdating them would
+    // order them before the events of other devices received in between.
+    let mut send = |event: GamepadEvent| {
+        send_timestamped_input_event(&mut input_clock, &mut timestamped_events, event.clone());
+        events.send(event);
+    };
     while let Some(gilrs_event) = gilrs
         .next_event()
         .filter_ev(&axis_dpad_to_button, &mut gilrs)
@@ -42,19 +64,14 @@ pub fn gilrs_event_system(
         let gamepad = convert_gamepad_id(gilrs_event.id);
         match gilrs_event.event {
             EventType::Connected => {
-                let pad = gilrs.gamepad(gilrs_event.id);
-                let info = GamepadInfo {
-                    name: pad.name().into(),
-                };
+                let info = gamepad_info(gilrs.gamepad(gilrs_event.id));
 
-                events.send(
+                send(
                     GamepadConnectionEvent::new(gamepad, GamepadConnection::Connected(info)).into(),
                 );
             }
             EventType::Disconnected => {
-                events.send(
-                    GamepadConnectionEvent::new(gamepad, GamepadConnection::Disconnected).into(),
-                );
+                send(GamepadConnectionEvent::new(gamepad, GamepadConnection::Disconnected).into());
             }
             EventType::ButtonChanged(gilrs_button, raw_value, _) => {
                 if let Some(button_type) = convert_button(gilrs_button) {
@@ -64,7 +81,7 @@ pub fn gilrs_event_system(
 
                     // Only send events that pass the user-defined change threshold
                     if let Some(filtered_value) = button_settings.filter(raw_value, old_value) {
-                        events.send(
+                        send(
                             GamepadButtonChangedEvent::new(gamepad, button_type, filtered_value)
                                 .into(),
                         );
@@ -82,7 +99,7 @@ pub fn gilrs_event_system(
 
                     // Only send events that pass the user-defined change threshold
                     if let Some(filtered_value) = axis_settings.filter(raw_value, old_value) {
-                        events.send(
+                        send(
                             GamepadAxisChangedEvent::new(gamepad, axis_type, filtered_value).into(),
                         );
                     }
@@ -93,3 +110,22 @@ pub fn gilrs_event_system(
     }
     gilrs.inc();
 }
+
+/// Reports the requests for features no gamepad has with gilrs, see [`gamepad_info`].
+pub fn unsupported_gamepad_requests_system(
+    mut light_requests: EventReader<GamepadLightRequest>,
+    mut trigger_requests: EventReader<GamepadTriggerEffectRequest>,
+) {
+    for request in light_requests.read() {
+        debug!(
+            "Tried to set the light of {:?}, but gilrs doesn't support lights",
+            request.gamepad
+        );
+    }
+    for request in trigger_requests.read() {
+        debug!(
+            "Tried to set a trigger effect on {:?}, but gilrs doesn't support trigger effects",
+            request.gamepad
+        );
+    }
+}
@@ -9,10 +9,12 @@ mod rumble;
 
 use bevy_app::{App, Plugin, PostUpdate, PreStartup, PreUpdate};
 use bevy_ecs::prelude::*;
-use bevy_input::InputSystem;
+use bevy_input::{injection::InputInjectionSystem, InputSystem};
 use bevy_utils::tracing::error;
 use gilrs::GilrsBuilder;
-use gilrs_system::{gilrs_event_startup_system, gilrs_event_system};
+use gilrs_system::{
+    gilrs_event_startup_system, gilrs_event_system, unsupported_gamepad_requests_system,
+};
 use rumble::{play_gilrs_rumble, RunningRumbleEffects};
 
 /// Plugin that provides gamepad handling to an [`App`].
@@ -34,8 +36,19 @@ impl Plugin for GilrsPlugin {
                 app.insert_non_send_resource(gilrs)
                     .init_non_send_resource::<RunningRumbleEffects>()
                     .add_systems(PreStartup, gilrs_event_startup_system)
-                    .add_systems(PreUpdate, gilrs_event_system.before(InputSystem))
-                    .add_systems(PostUpdate, play_gilrs_rumble.in_set(RumbleSystem));
+                    .add_systems(
+                        PreUpdate,
+                        gilrs_event_system
+                            .before(InputInjectionSystem)
+                            .before(InputSystem),
+                    )
+                    .add_systems(
+                        PostUpdate,
+                        (
+                            play_gilrs_rumble.in_set(RumbleSystem),
+                            unsupported_gamepad_requests_system,
+                        ),
+                    );
             }
             Err(err) => error!("Failed to start Gilrs. {}", err),
         }
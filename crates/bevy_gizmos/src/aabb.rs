@@ -1,22 +1,27 @@
-//! A module adding debug visualization of [`Aabb`]s.
+//! A module adding debug visualization of [`Aabb`]s, and of the bounding spheres and oriented
+//! bounding boxes of entities.
 
 use crate as bevy_gizmos;
 
 use bevy_app::{Plugin, PostUpdate};
+use bevy_asset::{AssetEvent, AssetId, Assets, Handle};
 use bevy_ecs::{
     component::Component,
     entity::Entity,
+    event::EventReader,
     query::Without,
     reflect::ReflectComponent,
     schedule::IntoSystemConfigs,
-    system::{Query, Res},
+    system::{Query, Res, ResMut, Resource, SystemParam},
 };
+use bevy_math::{bounding::BoundingSphere, Mat3, Quat, Vec3};
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
-use bevy_render::{color::Color, primitives::Aabb};
+use bevy_render::{color::Color, mesh::Mesh, primitives::Aabb};
 use bevy_transform::{
     components::{GlobalTransform, Transform},
     TransformSystem,
 };
+use bevy_utils::HashMap;
 
 use crate::{
     config::{GizmoConfigGroup, GizmoConfigStore},
@@ -24,13 +29,17 @@ use crate::{
     AppGizmoBuilder,
 };
 
-/// A [`Plugin`] that provides visualization of [`Aabb`]s for debugging.
+/// A [`Plugin`] that provides visualization of [`Aabb`]s, bounding spheres and oriented
+/// bounding boxes for debugging.
 pub struct AabbGizmoPlugin;
 
 impl Plugin for AabbGizmoPlugin {
     fn build(&self, app: &mut bevy_app::App) {
         app.register_type::<AabbGizmoConfigGroup>()
+            .register_type::<ShowBoundingSphereGizmo>()
+            .register_type::<ShowObbGizmo>()
             .init_gizmo_group::<AabbGizmoConfigGroup>()
+            .init_resource::<MeshBoundingVolumes>()
             .add_systems(
                 PostUpdate,
                 (
@@ -38,9 +47,22 @@ impl Plugin for AabbGizmoPlugin {
                     draw_all_aabbs.run_if(|config: Res<GizmoConfigStore>| {
                         config.config::<AabbGizmoConfigGroup>().1.draw_all
                     }),
+                    draw_bounding_spheres,
+                    draw_all_bounding_spheres.run_if(|config: Res<GizmoConfigStore>| {
+                        config
+                            .config::<AabbGizmoConfigGroup>()
+                            .1
+                            .draw_all_bounding_spheres
+                    }),
+                    draw_obbs,
+                    draw_all_obbs.run_if(|config: Res<GizmoConfigStore>| {
+                        config.config::<AabbGizmoConfigGroup>().1.draw_all_obbs
+                    }),
                 )
-                    .after(TransformSystem::TransformPropagate),
-            );
+                    .after(TransformSystem::TransformPropagate)
+                    .after(invalidate_mesh_bounding_volumes),
+            )
+            .add_systems(PostUpdate, invalidate_mesh_bounding_volumes);
     }
 }
 /// The [`GizmoConfigGroup`] used for debug visualizations of [`Aabb`] components on entities
@@ -52,6 +74,20 @@ pub struct AabbGizmoConfigGroup {
     ///
     /// Defaults to `false`.
     pub draw_all: bool,
+    /// Draws the bounding spheres of all entities with an [`Aabb`] when set to `true`.
+    ///
+    /// To draw a specific entity's bounding sphere, you can add the [`ShowBoundingSphereGizmo`]
+    /// component.
+    ///
+    /// Defaults to `false`.
+    pub draw_all_bounding_spheres: bool,
+    /// Draws the oriented bounding boxes of all entities with an [`Aabb`] when set to `true`.
+    ///
+    /// To draw a specific entity's oriented bounding box, you can add the [`ShowObbGizmo`]
+    /// component.
+    ///
+    /// Defaults to `false`.
+    pub draw_all_obbs: bool,
     /// The default color for bounding box gizmos.
     ///
     /// A random color is chosen per box if `None`.
@@ -70,6 +106,34 @@ pub struct ShowAabbGizmo {
     pub color: Option<Color>,
 }
 
+/// Add this [`Component`] to an entity to draw its bounding sphere.
+///
+/// The sphere is fitted to the vertices of the entity's [`Mesh`] if it has one, and encloses its
+/// [`Aabb`] otherwise. Spheres are fitted to the vertices in the local space of the mesh, so a
+/// non-uniform scale makes them larger than needed.
+#[derive(Component, Reflect, Default, Debug)]
+#[reflect(Component, Default)]
+pub struct ShowBoundingSphereGizmo {
+    /// The color of the sphere.
+    ///
+    /// The default color from the [`AabbGizmoConfigGroup`] config is used if `None`,
+    pub color: Option<Color>,
+}
+
+/// Add this [`Component`] to an entity to draw its oriented bounding box.
+///
+/// The box is aligned with the principal axes of the vertices of the entity's [`Mesh`] if it
+/// has one, which fits elongated shapes rotated within their mesh much tighter than the
+/// [`Aabb`]. Entities without a mesh draw their [`Aabb`] instead.
+#[derive(Component, Reflect, Default, Debug)]
+#[reflect(Component, Default)]
+pub struct ShowObbGizmo {
+    /// The color of the box.
+    ///
+    /// The default color from the [`AabbGizmoConfigGroup`] config is used if `None`,
+    pub color: Option<Color>,
+}
+
 fn draw_aabbs(
     query: Query<(Entity, &Aabb, &GlobalTransform, &ShowAabbGizmo)>,
     mut gizmos: Gizmos<AabbGizmoConfigGroup>,
@@ -96,6 +160,96 @@ fn draw_all_aabbs(
     }
 }
 
+fn draw_bounding_spheres(
+    query: Query<(
+        Entity,
+        &Aabb,
+        &GlobalTransform,
+        Option<&Handle<Mesh>>,
+        &ShowBoundingSphereGizmo,
+    )>,
+    mut volumes: BoundingVolumes,
+    mut gizmos: Gizmos<AabbGizmoConfigGroup>,
+) {
+    for (entity, aabb, &transform, mesh, gizmo) in &query {
+        let color = gizmo
+            .color
+            .or(gizmos.config_ext.default_color)
+            .unwrap_or_else(|| color_from_entity(entity));
+        let (sphere, _) = volumes.get(aabb, mesh);
+        draw_bounding_sphere(&mut gizmos, sphere, transform, color);
+    }
+}
+
+fn draw_all_bounding_spheres(
+    query: Query<
+        (Entity, &Aabb, &GlobalTransform, Option<&Handle<Mesh>>),
+        Without<ShowBoundingSphereGizmo>,
+    >,
+    mut volumes: BoundingVolumes,
+    mut gizmos: Gizmos<AabbGizmoConfigGroup>,
+) {
+    for (entity, aabb, &transform, mesh) in &query {
+        let color = gizmos
+            .config_ext
+            .default_color
+            .unwrap_or_else(|| color_from_entity(entity));
+        let (sphere, _) = volumes.get(aabb, mesh);
+        draw_bounding_sphere(&mut gizmos, sphere, transform, color);
+    }
+}
+
+fn draw_obbs(
+    query: Query<(
+        Entity,
+        &Aabb,
+        &GlobalTransform,
+        Option<&Handle<Mesh>>,
+        &ShowObbGizmo,
+    )>,
+    mut volumes: BoundingVolumes,
+    mut gizmos: Gizmos<AabbGizmoConfigGroup>,
+) {
+    for (entity, aabb, &transform, mesh, gizmo) in &query {
+        let color = gizmo
+            .color
+            .or(gizmos.config_ext.default_color)
+            .unwrap_or_else(|| color_from_entity(entity));
+        let (_, obb) = volumes.get(aabb, mesh);
+        gizmos.cuboid(obb.transform(transform), color);
+    }
+}
+
+fn draw_all_obbs(
+    query: Query<(Entity, &Aabb, &GlobalTransform, Option<&Handle<Mesh>>), Without<ShowObbGizmo>>,
+    mut volumes: BoundingVolumes,
+    mut gizmos: Gizmos<AabbGizmoConfigGroup>,
+) {
+    for (entity, aabb, &transform, mesh) in &query {
+        let color = gizmos
+            .config_ext
+            .default_color
+            .unwrap_or_else(|| color_from_entity(entity));
+        let (_, obb) = volumes.get(aabb, mesh);
+        gizmos.cuboid(obb.transform(transform), color);
+    }
+}
+
+fn draw_bounding_sphere(
+    gizmos: &mut Gizmos<AabbGizmoConfigGroup>,
+    sphere: BoundingSphere,
+    transform: GlobalTransform,
+    color: Color,
+) {
+    let (scale, rotation, _) = transform.to_scale_rotation_translation();
+    gizmos.sphere(
+        transform.transform_point(sphere.center),
+        rotation,
+        sphere.radius() * scale.abs().max_element(),
+        color,
+    );
+}
+
 fn color_from_entity(entity: Entity) -> Color {
     let index = entity.index();
 
@@ -118,3 +272,169 @@ fn aabb_transform(aabb: Aabb, transform: GlobalTransform) -> GlobalTransform {
                 .with_scale((aabb.half_extents * 2.).into()),
         )
 }
+
+/// An oriented bounding box, in the local space of an entity.
+#[derive(Clone, Copy, Debug)]
+struct Obb {
+    center: Vec3,
+    rotation: Quat,
+    half_extents: Vec3,
+}
+
+impl Obb {
+    fn from_aabb(aabb: &Aabb) -> Self {
+        Self {
+            center: aabb.center.into(),
+            rotation: Quat::IDENTITY,
+            half_extents: aabb.half_extents.into(),
+        }
+    }
+
+    /// Fits a box aligned with the principal axes of `points`, the eigenvectors of their
+    /// covariance matrix.
+    ///
+    /// Returns `None` if there are no points.
+    fn from_points(points: &[Vec3]) -> Option<Self> {
+        if points.is_empty() {
+            return None;
+        }
+        let mean = points.iter().sum::<Vec3>() / points.len() as f32;
+        let covariance = points.iter().fold(Mat3::ZERO, |covariance, &point| {
+            let offset = point - mean;
+            covariance + Mat3::from_cols(offset * offset.x, offset * offset.y, offset * offset.z)
+        });
+        let rotation = Quat::from_mat3(&symmetric_eigenvectors(covariance)).normalize();
+
+        let inverse_rotation = rotation.inverse();
+        let (min, max) = points.iter().fold(
+            (Vec3::splat(f32::INFINITY), Vec3::splat(f32::NEG_INFINITY)),
+            |(min, max), &point| {
+                let local = inverse_rotation * (point - mean);
+                (min.min(local), max.max(local))
+            },
+        );
+        Some(Self {
+            center: mean + rotation * (min + max) / 2.,
+            rotation,
+            half_extents: (max - min) / 2.,
+        })
+    }
+
+    /// The transform of the unit cube drawn for this box on an entity with `transform`.
+    fn transform(&self, transform: GlobalTransform) -> GlobalTransform {
+        transform
+            * GlobalTransform::from(
+                Transform::from_translation(self.center)
+                    .with_rotation(self.rotation)
+                    .with_scale(self.half_extents * 2.),
+            )
+    }
+}
+
+/// Returns the eigenvectors of the symmetric matrix `matrix`, as the columns of a rotation
+/// matrix, with the Jacobi eigenvalue algorithm.
+fn symmetric_eigenvectors(mut matrix: Mat3) -> Mat3 {
+    let mut eigenvectors = Mat3::IDENTITY;
+    // A few sweeps are enough to converge for 3x3 matrices.
+    for _ in 0..8 {
+        for (p, q) in [(0, 1), (0, 2), (1, 2)] {
+            let off_diagonal = matrix.col(q)[p];
+            if off_diagonal == 0. {
+                continue;
+            }
+            // The rotation in the (p, q) plane zeroing this off-diagonal element.
+            let theta = (matrix.col(q)[q] - matrix.col(p)[p]) / (2. * off_diagonal);
+            let t = theta.signum() / (theta.abs() + (theta * theta + 1.).sqrt());
+            let cos = 1. / (t * t + 1.).sqrt();
+            let sin = t * cos;
+            let mut rotation = Mat3::IDENTITY;
+            rotation.col_mut(p)[p] = cos;
+            rotation.col_mut(q)[q] = cos;
+            rotation.col_mut(q)[p] = sin;
+            rotation.col_mut(p)[q] = -sin;
+
+            matrix = rotation.transpose() * matrix * rotation;
+            eigenvectors *= rotation;
+        }
+    }
+    eigenvectors
+}
+
+/// The bounding volumes fitted to the vertices of each [`Mesh`], computed when first drawn.
+///
+/// `None` for meshes without vertex positions.
+#[derive(Resource, Default)]
+struct MeshBoundingVolumes(HashMap<AssetId<Mesh>, Option<(BoundingSphere, Obb)>>);
+
+fn invalidate_mesh_bounding_volumes(
+    mut events: EventReader<AssetEvent<Mesh>>,
+    mut volumes: ResMut<MeshBoundingVolumes>,
+) {
+    for event in events.read() {
+        if let AssetEvent::Modified { id } | AssetEvent::Removed { id } = event {
+            volumes.0.remove(id);
+        }
+    }
+}
+
+/// Finds the bounding volumes of entities, in their local space.
+#[derive(SystemParam)]
+struct BoundingVolumes<'w> {
+    meshes: Option<Res<'w, Assets<Mesh>>>,
+    cache: ResMut<'w, MeshBoundingVolumes>,
+}
+
+impl BoundingVolumes<'_> {
+    /// Returns the volumes fitted to the vertices of `mesh`, or enclosing `aabb` if there is no
+    /// mesh or it has no vertex positions.
+    fn get(&mut self, aabb: &Aabb, mesh: Option<&Handle<Mesh>>) -> (BoundingSphere, Obb) {
+        let mesh_volumes = mesh.and_then(|handle| {
+            let mesh = self.meshes.as_deref()?.get(handle)?;
+            *self.cache.0.entry(handle.id()).or_insert_with(|| {
+                let positions: Vec<Vec3> = mesh
+                    .attribute(Mesh::ATTRIBUTE_POSITION)?
+                    .as_float3()?
+                    .iter()
+                    .map(|&position| Vec3::from(position))
+                    .collect();
+                let obb = Obb::from_points(&positions)?;
+                let sphere =
+                    BoundingSphere::from_point_cloud(Vec3::ZERO, Quat::IDENTITY, &positions);
+                Some((sphere, obb))
+            })
+        });
+        mesh_volumes.unwrap_or_else(|| {
+            let center = Vec3::from(aabb.center);
+            let radius = aabb.half_extents.length();
+            (BoundingSphere::new(center, radius), Obb::from_aabb(aabb))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::EulerRot;
+
+    use super::*;
+
+    #[test]
+    fn obb_fits_rotated_points() {
+        let rotation = Quat::from_euler(EulerRot::XYZ, 0.4, 1.1, -0.7);
+        let translation = Vec3::new(1., 2., 3.);
+        let mut points = Vec::new();
+        for x in [-4., 4.] {
+            for y in [-1., 1.] {
+                for z in [-0.5, 0.5] {
+                    points.push(rotation * Vec3::new(x, y, z) + translation);
+                }
+            }
+        }
+
+        let obb = Obb::from_points(&points).unwrap();
+        assert!(obb.center.abs_diff_eq(translation, 1e-4));
+        // The axes of the box are found in any order.
+        let mut half_extents = obb.half_extents.to_array();
+        half_extents.sort_by(f32::total_cmp);
+        assert!(Vec3::from(half_extents).abs_diff_eq(Vec3::new(0.5, 1., 4.), 1e-4));
+    }
+}
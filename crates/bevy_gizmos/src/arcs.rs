@@ -59,6 +59,97 @@ impl<'w, 's, T: GizmoConfigGroup> Gizmos<'w, 's, T> {
             segments: None,
         }
     }
+
+    /// Draw a filled sector in 2D, the area between an arc and the center of its circle.
+    ///
+    /// Filled gizmos are drawn below the lines, so a translucent `color` keeps overlapping
+    /// shapes readable.
+    ///
+    /// This should be called for each frame the sector needs to be rendered.
+    ///
+    /// # Arguments
+    /// - `position` sets the center of the circle.
+    /// - `radius` sets the distance from `position` to the arc of the sector.
+    /// - `direction_angle` sets the clockwise  angle in radians between `Vec2::Y` and
+    /// the vector from `position` to the midpoint of the arc.
+    /// - `arc_angle` sets the length of the arc, in radians.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use std::f32::consts::PI;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.sector_2d(Vec2::ZERO, 0., PI / 4., 1., Color::GREEN.with_a(0.3));
+    ///
+    ///     // Sectors have 32 segments for a full circle by default.
+    ///     gizmos
+    ///         .sector_2d(Vec2::ZERO, 0., PI / 4., 5., Color::RED.with_a(0.3))
+    ///         .segments(64);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn sector_2d(
+        &mut self,
+        position: Vec2,
+        direction_angle: f32,
+        arc_angle: f32,
+        radius: f32,
+        color: Color,
+    ) -> Sector2dBuilder<'_, 'w, 's, T> {
+        Sector2dBuilder {
+            gizmos: self,
+            position,
+            direction_angle,
+            arc_angle,
+            radius,
+            color,
+            segments: None,
+        }
+    }
+}
+
+/// A builder returned by [`Gizmos::sector_2d`].
+pub struct Sector2dBuilder<'a, 'w, 's, T: GizmoConfigGroup> {
+    gizmos: &'a mut Gizmos<'w, 's, T>,
+    position: Vec2,
+    direction_angle: f32,
+    arc_angle: f32,
+    radius: f32,
+    color: Color,
+    segments: Option<usize>,
+}
+
+impl<T: GizmoConfigGroup> Sector2dBuilder<'_, '_, '_, T> {
+    /// Set the number of segments of the arc of this sector.
+    pub fn segments(mut self, segments: usize) -> Self {
+        self.segments.replace(segments);
+        self
+    }
+}
+
+impl<T: GizmoConfigGroup> Drop for Sector2dBuilder<'_, '_, '_, T> {
+    fn drop(&mut self) {
+        if !self.gizmos.enabled {
+            return;
+        }
+
+        let segments = self
+            .segments
+            .unwrap_or_else(|| segments_from_angle(self.arc_angle))
+            .max(1);
+
+        let arc: Vec<Vec2> =
+            arc_2d_inner(self.direction_angle, self.arc_angle, self.radius, segments)
+                .map(|vec2| vec2 + self.position)
+                .collect();
+        let triangles = arc
+            .windows(2)
+            .flat_map(|points| [self.position, points[0], points[1]]);
+        self.gizmos.triangles_2d(triangles, self.color);
+    }
 }
 
 /// A builder returned by [`Gizmos::arc_2d`].
@@ -365,7 +456,7 @@ impl<T: GizmoConfigGroup> Drop for Arc3dBuilder<'_, '_, '_, T> {
     }
 }
 
-fn arc_3d_inner(
+pub(crate) fn arc_3d_inner(
     start_vertex: Vec3,
     center: Vec3,
     rotation: Quat,
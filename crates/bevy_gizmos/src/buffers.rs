@@ -0,0 +1,310 @@
+//! The GPU vertex buffers of [`LineGizmo`] and [`FilledGizmo`] assets.
+//!
+//! Gizmo assets are rewritten every frame, so instead of creating new buffers each time they
+//! change like [`RenderAsset`](bevy_render::render_asset::RenderAsset)s do, their buffers are
+//! kept across frames and only the vertices that changed are written to them. The buffers only
+//! grow when the vertices no longer fit, and shrink when a small fraction of them is used.
+
+use std::ops::Range;
+
+use bevy_asset::{Asset, AssetEvent, AssetId, Assets};
+use bevy_core::cast_slice;
+use bevy_ecs::{
+    event::EventReader,
+    system::{Commands, Res, ResMut, Resource},
+};
+use bevy_render::{
+    render_resource::{Buffer, BufferDescriptor, BufferSlice, BufferUsages, VertexFormat},
+    renderer::{RenderDevice, RenderQueue},
+    Extract,
+};
+use bevy_utils::{HashMap, HashSet};
+
+use crate::{diagnostic::GizmoUploadedBytes, FilledGizmo, LineGizmo};
+
+/// The minimum number of vertices the buffers of a gizmo can hold.
+const MIN_CAPACITY: usize = 64;
+
+/// A gizmo asset drawn from position and color vertex buffers.
+pub(crate) trait GizmoVertices: Asset + Clone {
+    /// The label of the buffers of this type of gizmo.
+    const LABEL: &'static str;
+
+    /// Returns the positions and colors of the vertices, and whether they form line strips.
+    fn into_vertices(self) -> (Vec<[f32; 3]>, Vec<[f32; 4]>, bool);
+}
+
+impl GizmoVertices for LineGizmo {
+    const LABEL: &'static str = "LineGizmo";
+
+    fn into_vertices(self) -> (Vec<[f32; 3]>, Vec<[f32; 4]>, bool) {
+        (self.positions, self.colors, self.strip)
+    }
+}
+
+impl GizmoVertices for FilledGizmo {
+    const LABEL: &'static str = "FilledGizmo";
+
+    fn into_vertices(self) -> (Vec<[f32; 3]>, Vec<[f32; 4]>, bool) {
+        (self.positions, self.colors, false)
+    }
+}
+
+/// The gizmo assets of type `A` added, changed or removed since the last frame.
+#[derive(Resource)]
+pub(crate) struct ExtractedGizmos<A: GizmoVertices> {
+    extracted: Vec<(AssetId<A>, A)>,
+    removed: Vec<AssetId<A>>,
+}
+
+impl<A: GizmoVertices> Default for ExtractedGizmos<A> {
+    fn default() -> Self {
+        Self {
+            extracted: Default::default(),
+            removed: Default::default(),
+        }
+    }
+}
+
+/// The GPU vertex buffers of each gizmo asset of type `A`.
+#[derive(Resource)]
+pub(crate) struct RenderGizmos<A: GizmoVertices>(HashMap<AssetId<A>, GpuGizmo>);
+
+impl<A: GizmoVertices> Default for RenderGizmos<A> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+impl<A: GizmoVertices> RenderGizmos<A> {
+    pub fn get(&self, id: impl Into<AssetId<A>>) -> Option<&GpuGizmo> {
+        self.0.get(&id.into())
+    }
+}
+
+/// The vertex buffers of a gizmo, kept across frames.
+pub(crate) struct GpuGizmo {
+    position_buffer: Buffer,
+    color_buffer: Buffer,
+    /// The number of vertices the buffers can hold.
+    capacity: usize,
+    /// The vertices currently in the buffers, to only write the ones that changed.
+    positions: Vec<[f32; 3]>,
+    colors: Vec<[f32; 4]>,
+    pub strip: bool,
+}
+
+impl GpuGizmo {
+    /// Creates buffers holding the given vertices, returning them with the number of bytes
+    /// written.
+    fn new(
+        render_device: &RenderDevice,
+        render_queue: &RenderQueue,
+        label: &str,
+        positions: Vec<[f32; 3]>,
+        colors: Vec<[f32; 4]>,
+        strip: bool,
+    ) -> (Self, u64) {
+        let capacity = positions.len().max(MIN_CAPACITY).next_power_of_two();
+        let create_buffer = |name: &str, format: VertexFormat| {
+            render_device.create_buffer(&BufferDescriptor {
+                label: Some(&format!("{label} {name} Buffer")),
+                size: capacity as u64 * format.size(),
+                usage: BufferUsages::VERTEX | BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            })
+        };
+        let position_buffer = create_buffer("Position", VertexFormat::Float32x3);
+        let color_buffer = create_buffer("Color", VertexFormat::Float32x4);
+
+        let positions_data: &[u8] = cast_slice(&positions);
+        let colors_data: &[u8] = cast_slice(&colors);
+        render_queue.write_buffer(&position_buffer, 0, positions_data);
+        render_queue.write_buffer(&color_buffer, 0, colors_data);
+        let written = (positions_data.len() + colors_data.len()) as u64;
+
+        let gizmo = Self {
+            position_buffer,
+            color_buffer,
+            capacity,
+            positions,
+            colors,
+            strip,
+        };
+        (gizmo, written)
+    }
+
+    /// Returns `true` if the buffers can be reused for `vertex_count` vertices.
+    fn fits(&self, vertex_count: usize) -> bool {
+        vertex_count <= self.capacity
+            && (self.capacity <= MIN_CAPACITY || vertex_count * 4 >= self.capacity)
+    }
+
+    /// Writes the vertices that changed to the buffers, returning the number of bytes written.
+    fn update(
+        &mut self,
+        render_queue: &RenderQueue,
+        positions: Vec<[f32; 3]>,
+        colors: Vec<[f32; 4]>,
+        strip: bool,
+    ) -> u64 {
+        let mut written = 0;
+        for (buffer, old, new) in [
+            (
+                &self.position_buffer,
+                cast_slice::<_, u8>(&self.positions),
+                cast_slice(&positions),
+            ),
+            (
+                &self.color_buffer,
+                cast_slice(&self.colors),
+                cast_slice(&colors),
+            ),
+        ] {
+            if let Some(range) = changed_range(old, new) {
+                render_queue.write_buffer(buffer, range.start as u64, &new[range.clone()]);
+                written += range.len() as u64;
+            }
+        }
+        self.positions = positions;
+        self.colors = colors;
+        self.strip = strip;
+        written
+    }
+
+    /// Returns the number of vertices of this gizmo.
+    pub fn vertex_count(&self) -> u32 {
+        self.positions.len() as u32
+    }
+
+    /// Returns the slice of the position buffer holding the vertices in `range`.
+    pub fn positions(&self, range: Range<u32>) -> BufferSlice<'_> {
+        let size = VertexFormat::Float32x3.size();
+        self.position_buffer
+            .slice(range.start as u64 * size..range.end as u64 * size)
+    }
+
+    /// Returns the slice of the color buffer holding the vertices in `range`.
+    pub fn colors(&self, range: Range<u32>) -> BufferSlice<'_> {
+        let size = VertexFormat::Float32x4.size();
+        self.color_buffer
+            .slice(range.start as u64 * size..range.end as u64 * size)
+    }
+}
+
+/// Returns the range of bytes of `new` that differ from `old`, aligned to 4 bytes as required
+/// by buffer writes, or `None` if `new` matches the start of `old`.
+///
+/// The lengths of `old` and `new` must be multiples of 4.
+fn changed_range(old: &[u8], new: &[u8]) -> Option<Range<usize>> {
+    let common = old.len().min(new.len());
+    let start = old[..common]
+        .iter()
+        .zip(new)
+        .position(|(old, new)| old != new)
+        .unwrap_or(common);
+    if start == new.len() {
+        return None;
+    }
+    let end = if new.len() > common {
+        new.len()
+    } else {
+        let unchanged = old[start..common]
+            .iter()
+            .rev()
+            .zip(new[start..].iter().rev())
+            .take_while(|(old, new)| old == new)
+            .count();
+        common - unchanged
+    };
+    Some(start & !3..(end + 3) & !3)
+}
+
+/// Extracts the gizmo assets of type `A` added, changed or removed since the last frame.
+pub(crate) fn extract_gizmos<A: GizmoVertices>(
+    mut commands: Commands,
+    mut events: Extract<EventReader<AssetEvent<A>>>,
+    assets: Extract<Res<Assets<A>>>,
+) {
+    let mut changed_assets = HashSet::default();
+    let mut removed = Vec::new();
+    for event in events.read() {
+        match event {
+            AssetEvent::Added { id } | AssetEvent::Modified { id } => {
+                changed_assets.insert(*id);
+            }
+            AssetEvent::Removed { id } => {
+                changed_assets.remove(id);
+                removed.push(*id);
+            }
+            AssetEvent::Unused { .. } | AssetEvent::LoadedWithDependencies { .. } => {}
+        }
+    }
+
+    let mut extracted = Vec::new();
+    for id in changed_assets.drain() {
+        if let Some(asset) = assets.get(id) {
+            extracted.push((id, asset.clone()));
+        }
+    }
+
+    commands.insert_resource(ExtractedGizmos { extracted, removed });
+}
+
+/// Writes the extracted gizmo assets of type `A` to their vertex buffers, creating new buffers
+/// for the new gizmos and the ones that no longer fit in theirs.
+pub(crate) fn prepare_gizmos<A: GizmoVertices>(
+    mut extracted_gizmos: ResMut<ExtractedGizmos<A>>,
+    mut render_gizmos: ResMut<RenderGizmos<A>>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    uploaded_bytes: Res<GizmoUploadedBytes>,
+) {
+    for id in extracted_gizmos.removed.drain(..) {
+        render_gizmos.0.remove(&id);
+    }
+
+    let mut written = 0;
+    for (id, gizmo) in extracted_gizmos.extracted.drain(..) {
+        let (positions, colors, strip) = gizmo.into_vertices();
+        match render_gizmos.0.get_mut(&id) {
+            Some(gpu_gizmo) if gpu_gizmo.fits(positions.len()) => {
+                written += gpu_gizmo.update(&render_queue, positions, colors, strip);
+            }
+            _ => {
+                let (gpu_gizmo, bytes) = GpuGizmo::new(
+                    &render_device,
+                    &render_queue,
+                    A::LABEL,
+                    positions,
+                    colors,
+                    strip,
+                );
+                render_gizmos.0.insert(id, gpu_gizmo);
+                written += bytes;
+            }
+        }
+    }
+    uploaded_bytes.add(written);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changed_ranges() {
+        let old = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
+        assert_eq!(changed_range(&old, &old), None);
+        assert_eq!(changed_range(&old, &old[..8]), None);
+
+        let mut new = old;
+        new[5] = 0;
+        assert_eq!(changed_range(&old, &new), Some(4..8));
+        new[10] = 0;
+        assert_eq!(changed_range(&old, &new), Some(4..12));
+
+        assert_eq!(changed_range(&old[..4], &old), Some(4..12));
+        assert_eq!(changed_range(&[], &old[..4]), Some(0..4));
+    }
+}
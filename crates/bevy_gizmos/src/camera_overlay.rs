@@ -0,0 +1,214 @@
+//! A module adding a debug overlay of the cameras and lights of a scene, to tune shadow
+//! distances and culling.
+
+use crate as bevy_gizmos;
+
+use bevy_app::{Plugin, PostUpdate};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::With,
+    reflect::ReflectResource,
+    schedule::IntoSystemConfigs,
+    system::{Query, Res, Resource},
+};
+use bevy_pbr::{Cascades, DirectionalLight, PointLight, SimulationLightSystems, SpotLight};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    camera::{Camera, CameraProjection, OrthographicProjection, PerspectiveProjection, Projection},
+    color::Color,
+};
+use bevy_transform::{components::GlobalTransform, TransformSystem};
+
+use crate::{
+    config::GizmoConfigGroup,
+    frustum::{draw_box_edges, frustum_corners},
+    gizmos::Gizmos,
+    light::{clip_space_box, draw_spot_light},
+    AppGizmoBuilder,
+};
+
+/// A [`Plugin`] that draws the [`DebugCameraOverlay`] when it is enabled.
+pub struct DebugCameraOverlayPlugin;
+
+impl Plugin for DebugCameraOverlayPlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        app.register_type::<DebugCameraOverlay>()
+            .register_type::<DebugCameraOverlayGizmoConfigGroup>()
+            .init_resource::<DebugCameraOverlay>()
+            .init_gizmo_group::<DebugCameraOverlayGizmoConfigGroup>()
+            .add_systems(
+                PostUpdate,
+                (
+                    draw_camera_frusta::<Projection>,
+                    draw_camera_frusta::<PerspectiveProjection>,
+                    draw_camera_frusta::<OrthographicProjection>,
+                    draw_light_overlay,
+                )
+                    .run_if(|overlay: Res<DebugCameraOverlay>| overlay.enabled)
+                    .after(TransformSystem::TransformPropagate)
+                    .after(SimulationLightSystems::UpdateDirectionalLightCascades),
+            );
+    }
+}
+
+/// The [`GizmoConfigGroup`] used to draw the [`DebugCameraOverlay`].
+#[derive(Clone, Default, Reflect, GizmoConfigGroup)]
+pub struct DebugCameraOverlayGizmoConfigGroup;
+
+/// Draws the frusta of the cameras, the shadow cascades of the directional lights and the ranges
+/// of the point and spot lights when `enabled`.
+///
+/// This shows what each camera sees and which parts of the scene cast shadows into its view,
+/// from another camera set as the `viewer`. Unlike the [`ShowFrustumGizmo`] and
+/// [`ShowLightGizmo`] components, this needs no change to the entities of the scene.
+///
+/// [`ShowFrustumGizmo`]: crate::frustum::ShowFrustumGizmo
+/// [`ShowLightGizmo`]: crate::light::ShowLightGizmo
+///
+/// # Example
+/// ```
+/// # use bevy_core_pipeline::core_3d::Camera3dBundle;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_gizmos::camera_overlay::DebugCameraOverlay;
+/// fn setup(mut commands: Commands, mut overlay: ResMut<DebugCameraOverlay>) {
+///     // A camera flying around the scene to look at the other cameras.
+///     let debug_camera = commands.spawn(Camera3dBundle::default()).id();
+///     overlay.enabled = true;
+///     overlay.viewer = Some(debug_camera);
+/// }
+/// # bevy_ecs::system::assert_is_system(setup);
+/// ```
+#[derive(Resource, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Resource, Default)]
+pub struct DebugCameraOverlay {
+    /// Draws the overlay when set to `true`.
+    ///
+    /// Defaults to `false`.
+    pub enabled: bool,
+    /// The camera the overlay is looked at from, whose frustum and shadow cascades aren't drawn.
+    ///
+    /// Defaults to `None`, drawing them for all the cameras.
+    pub viewer: Option<Entity>,
+    /// Draws the frusta of the cameras, from their near plane to their far plane.
+    ///
+    /// Defaults to `true`.
+    pub frusta: bool,
+    /// Draws the bounds of the shadow cascades of the directional lights, for each camera.
+    ///
+    /// Defaults to `true`.
+    pub cascades: bool,
+    /// Draws the ranges of the point and spot lights, with the color of each light.
+    ///
+    /// Defaults to `true`.
+    pub light_ranges: bool,
+    /// The color of the frusta of the cameras.
+    ///
+    /// Defaults to [`Color::WHITE`].
+    pub frustum_color: Color,
+    /// The colors of the shadow cascades, from the nearest to the farthest, repeated if there
+    /// are more cascades than colors.
+    ///
+    /// Defaults to red, yellow, green and cyan.
+    pub cascade_colors: Vec<Color>,
+}
+
+impl Default for DebugCameraOverlay {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            viewer: None,
+            frusta: true,
+            cascades: true,
+            light_ranges: true,
+            frustum_color: Color::WHITE,
+            cascade_colors: vec![Color::RED, Color::YELLOW, Color::GREEN, Color::CYAN],
+        }
+    }
+}
+
+fn draw_camera_frusta<P: CameraProjection + Component>(
+    overlay: Res<DebugCameraOverlay>,
+    cameras: Query<(Entity, &P, &GlobalTransform), With<Camera>>,
+    mut gizmos: Gizmos<DebugCameraOverlayGizmoConfigGroup>,
+) {
+    if !overlay.frusta {
+        return;
+    }
+    for (entity, projection, transform) in &cameras {
+        if overlay.viewer == Some(entity) {
+            continue;
+        }
+        let corners = frustum_corners(projection).map(|corner| transform.transform_point(corner));
+        draw_box_edges(&mut gizmos, corners, overlay.frustum_color);
+    }
+}
+
+fn draw_light_overlay(
+    overlay: Res<DebugCameraOverlay>,
+    directional_lights: Query<&Cascades, With<DirectionalLight>>,
+    point_lights: Query<(&PointLight, &GlobalTransform)>,
+    spot_lights: Query<(&SpotLight, &GlobalTransform)>,
+    mut gizmos: Gizmos<DebugCameraOverlayGizmoConfigGroup>,
+) {
+    if overlay.cascades && !overlay.cascade_colors.is_empty() {
+        for cascades in &directional_lights {
+            for (view, cascades) in cascades.iter() {
+                if overlay.viewer == Some(view) {
+                    continue;
+                }
+                for (cascade, &color) in cascades.iter().zip(overlay.cascade_colors.iter().cycle())
+                {
+                    let world_from_clip = cascade.view_transform() * cascade.projection().inverse();
+                    draw_box_edges(&mut gizmos, clip_space_box(world_from_clip), color);
+                }
+            }
+        }
+    }
+
+    if overlay.light_ranges {
+        for (light, transform) in &point_lights {
+            let (_, rotation, translation) = transform.to_scale_rotation_translation();
+            gizmos.sphere(translation, rotation, light.range, light.color);
+        }
+        for (light, transform) in &spot_lights {
+            draw_spot_light(&mut gizmos, light, transform, light.color);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{system::RunSystemOnce, world::World};
+
+    use super::*;
+    use crate::{config::GizmoConfigStore, gizmos::GizmoStorage};
+
+    #[test]
+    fn skips_viewer() {
+        let mut world = World::new();
+        let mut config_store = GizmoConfigStore::default();
+        config_store.register::<DebugCameraOverlayGizmoConfigGroup>();
+        world.insert_resource(config_store);
+        world.init_resource::<GizmoStorage<DebugCameraOverlayGizmoConfigGroup>>();
+
+        let camera = (
+            Camera::default(),
+            Projection::default(),
+            GlobalTransform::default(),
+        );
+        let viewer = world.spawn(camera.clone()).id();
+        world.spawn(camera);
+        world.insert_resource(DebugCameraOverlay {
+            enabled: true,
+            viewer: Some(viewer),
+            ..Default::default()
+        });
+        world.run_system_once(draw_camera_frusta::<Projection>);
+
+        // Two closed line strips for the near and far faces, and four lines between them.
+        let storage = world.resource::<GizmoStorage<DebugCameraOverlayGizmoConfigGroup>>();
+        assert_eq!(storage.lines.strip_positions.len(), 12);
+        assert_eq!(storage.lines.list_positions.len(), 8);
+    }
+}
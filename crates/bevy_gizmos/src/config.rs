@@ -4,7 +4,7 @@ use crate as bevy_gizmos;
 pub use bevy_gizmos_macros::GizmoConfigGroup;
 
 use bevy_ecs::{component::Component, system::Resource};
-use bevy_reflect::{Reflect, TypePath};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect, TypePath};
 use bevy_render::view::RenderLayers;
 use bevy_utils::TypeIdMap;
 use core::panic;
@@ -98,7 +98,7 @@ impl GizmoConfigStore {
 }
 
 /// A struct that stores configuration for gizmos.
-#[derive(Clone, Reflect)]
+#[derive(Clone, Debug, Reflect)]
 pub struct GizmoConfig {
     /// Set to `false` to stop drawing gizmos.
     ///
@@ -129,9 +129,21 @@ pub struct GizmoConfig {
     /// and your wireframe is z-fighting (flickering on/off) with your main model.
     /// You would set this value to a negative number close to 0.
     pub depth_bias: f32,
+    /// How the lines are drawn where they are hidden behind other geometry.
+    ///
+    /// In 2D this setting has no effect.
+    ///
+    /// Defaults to [`GizmoOcclusion::DepthTested`].
+    pub occlusion: GizmoOcclusion,
+    /// The style of the lines, solid or broken in dashes or dots.
+    ///
+    /// Defaults to [`GizmoLineStyle::Solid`].
+    pub line_style: GizmoLineStyle,
     /// Describes which rendering layers gizmos will be rendered to.
     ///
-    /// Gizmos will only be rendered to cameras with intersecting layers.
+    /// Gizmos will only be rendered to cameras with intersecting layers. Use
+    /// [`Gizmos::with_render_layers`](crate::gizmos::Gizmos::with_render_layers) to render some
+    /// of the gizmos of a group to other layers.
     pub render_layers: RenderLayers,
 }
 
@@ -142,15 +154,90 @@ impl Default for GizmoConfig {
             line_width: 2.,
             line_perspective: false,
             depth_bias: 0.,
+            occlusion: GizmoOcclusion::DepthTested,
+            line_style: GizmoLineStyle::Solid,
             render_layers: Default::default(),
         }
     }
 }
 
-#[derive(Component)]
+/// The style of the lines drawn by gizmos.
+///
+/// Lengths are specified in pixels and the pattern restarts at the beginning of each segment.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[reflect(Default)]
+pub enum GizmoLineStyle {
+    /// A continuous line.
+    #[default]
+    Solid,
+    /// Dashes of `dash_length` pixels, separated by `gap_length` pixels.
+    Dashed {
+        /// The length of a dash in pixels.
+        dash_length: f32,
+        /// The length of the gap between two dashes in pixels.
+        gap_length: f32,
+    },
+    /// Round dots as wide as the line, separated by `gap_length` pixels.
+    Dotted {
+        /// The length of the gap between two dots in pixels.
+        gap_length: f32,
+    },
+}
+
+impl GizmoLineStyle {
+    /// Returns the dash and gap lengths stored in the line uniform.
+    ///
+    /// A gap of zero draws a solid line and a dash of zero draws dots as wide as the line.
+    pub(crate) fn dash_and_gap_lengths(&self) -> (f32, f32) {
+        match *self {
+            GizmoLineStyle::Solid => (0., 0.),
+            GizmoLineStyle::Dashed {
+                dash_length,
+                gap_length,
+            } => (dash_length.max(f32::EPSILON), gap_length.max(0.)),
+            // A gap of zero would draw a solid line instead of touching dots.
+            GizmoLineStyle::Dotted { gap_length } => (0., gap_length.max(f32::EPSILON)),
+        }
+    }
+}
+
+/// How gizmo lines are drawn where they are hidden behind other geometry.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[reflect(Default)]
+pub enum GizmoOcclusion {
+    /// The hidden parts are not drawn, like any other geometry.
+    #[default]
+    DepthTested,
+    /// The lines are drawn over everything else, as if nothing was in front of them.
+    AlwaysOnTop,
+    /// The visible parts are drawn as with [`DepthTested`](Self::DepthTested), and the hidden
+    /// parts are drawn in a second pass over the geometry in front of them, dimmed and in their
+    /// own style, like editors show selected objects behind walls.
+    XRay {
+        /// The factor the alpha of the hidden parts is multiplied by, like `0.3`.
+        occluded_alpha: f32,
+        /// The style of the hidden parts, usually dashed or dotted to tell them apart from the
+        /// visible parts.
+        occluded_style: GizmoLineStyle,
+    },
+}
+
+/// The depth test of a pass drawing a gizmo, derived from its [`GizmoOcclusion`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum GizmoDepthTest {
+    /// Draws the fragments in front of the geometry.
+    Visible,
+    /// Draws every fragment.
+    Always,
+    /// Draws the fragments behind the geometry, for [`GizmoOcclusion::XRay`].
+    Occluded,
+}
+
+#[derive(Component, Clone, Copy)]
 pub(crate) struct GizmoMeshConfig {
     pub line_perspective: bool,
     pub render_layers: RenderLayers,
+    pub depth_test: GizmoDepthTest,
 }
 
 impl From<&GizmoConfig> for GizmoMeshConfig {
@@ -158,6 +245,42 @@ impl From<&GizmoConfig> for GizmoMeshConfig {
         GizmoMeshConfig {
             line_perspective: item.line_perspective,
             render_layers: item.render_layers,
+            depth_test: match item.occlusion {
+                GizmoOcclusion::DepthTested | GizmoOcclusion::XRay { .. } => {
+                    GizmoDepthTest::Visible
+                }
+                GizmoOcclusion::AlwaysOnTop => GizmoDepthTest::Always,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_style_dash_and_gap_lengths() {
+        assert_eq!(GizmoLineStyle::Solid.dash_and_gap_lengths(), (0., 0.));
+        assert_eq!(
+            GizmoLineStyle::Dashed {
+                dash_length: 8.,
+                gap_length: 4.,
+            }
+            .dash_and_gap_lengths(),
+            (8., 4.)
+        );
+
+        // Dashes and dots don't degenerate into other styles
+        let (dash_length, _) = GizmoLineStyle::Dashed {
+            dash_length: 0.,
+            gap_length: 4.,
         }
+        .dash_and_gap_lengths();
+        assert!(dash_length > 0.);
+        let (dash_length, gap_length) =
+            GizmoLineStyle::Dotted { gap_length: 0. }.dash_and_gap_lengths();
+        assert_eq!(dash_length, 0.);
+        assert!(gap_length > 0.);
     }
 }
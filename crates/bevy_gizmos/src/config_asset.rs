@@ -0,0 +1,497 @@
+//! Saving and loading the contents of the [`GizmoConfigStore`] as a [`GizmoConfigAsset`].
+//!
+//! Gizmo configurations are serialized through reflection, as a map from the type path of each
+//! [`GizmoConfigGroup`](crate::config::GizmoConfigGroup) to its [`GizmoConfig`] and group value:
+//!
+//! ```ron
+//! {
+//!     "bevy_gizmos::config::DefaultGizmoConfigGroup": (
+//!         config: (
+//!             enabled: true,
+//!             line_width: 2.0,
+//!             line_perspective: false,
+//!             depth_bias: 0.0,
+//!             occlusion: DepthTested,
+//!             line_style: Solid,
+//!             render_layers: (1),
+//!         ),
+//!         group: (),
+//!     ),
+//! }
+//! ```
+
+use crate::config::{GizmoConfig, GizmoConfigStore};
+use bevy_asset::{
+    io::Reader, ron, Asset, AssetEvent, AssetLoader, Assets, AsyncReadExt, Handle, LoadContext,
+};
+use bevy_ecs::{
+    change_detection::DetectChanges,
+    event::EventReader,
+    reflect::AppTypeRegistry,
+    system::{Res, ResMut, Resource},
+    world::{FromWorld, World},
+};
+use bevy_reflect::{
+    serde::{TypeRegistrationDeserializer, TypedReflectDeserializer, TypedReflectSerializer},
+    FromReflect, Reflect, TypePath, TypeRegistry, TypeRegistryArc,
+};
+use bevy_utils::{BoxedFuture, HashSet};
+use serde::{
+    de::{DeserializeSeed, Error, MapAccess, SeqAccess, Visitor},
+    ser::{SerializeMap, SerializeStruct},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::{any::TypeId, fmt::Formatter};
+
+const ENTRY_STRUCT: &str = "GizmoConfigEntry";
+const ENTRY_CONFIG: &str = "config";
+const ENTRY_GROUP: &str = "group";
+
+/// The [`GizmoConfig`] and group value of a [`GizmoConfigGroup`](crate::config::GizmoConfigGroup)
+/// in a [`GizmoConfigAsset`].
+#[derive(Debug)]
+pub struct GizmoConfigEntry {
+    /// The [`TypeId`] of the group.
+    pub type_id: TypeId,
+    /// The configuration of the group.
+    pub config: GizmoConfig,
+    /// The group value, usually a dynamic representation of it.
+    pub group: Box<dyn Reflect>,
+}
+
+/// Gizmo configurations, loaded from a `.gizmos.ron` file by the [`GizmoConfigLoader`], or
+/// captured from a [`GizmoConfigStore`] to be saved.
+///
+/// To apply it to the [`GizmoConfigStore`], as soon as it is loaded and whenever it changes,
+/// insert its handle in the [`ActiveGizmoConfig`] resource. Groups it does not contain keep their
+/// current configuration, so a level can ship only the groups it overrides.
+#[derive(Asset, TypePath, Debug, Default)]
+pub struct GizmoConfigAsset {
+    /// The configuration of each group.
+    pub entries: Vec<GizmoConfigEntry>,
+}
+
+impl GizmoConfigAsset {
+    /// Captures the current configuration of every group of `store`.
+    pub fn from_store(store: &GizmoConfigStore) -> Self {
+        Self {
+            entries: store
+                .iter()
+                .map(|(type_id, config, group)| GizmoConfigEntry {
+                    type_id: *type_id,
+                    config: config.clone(),
+                    group: group.clone_value(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Applies the configurations of this asset to the groups of `store` that it contains.
+    ///
+    /// Groups that aren't registered in `store` are ignored.
+    pub fn apply(&self, store: &mut GizmoConfigStore) {
+        for entry in &self.entries {
+            if let Some((config, group)) = store.get_config_mut_dyn(&entry.type_id) {
+                *config = entry.config.clone();
+                group.apply(&*entry.group);
+            }
+        }
+    }
+
+    /// Serializes this asset to the RON format of `.gizmos.ron` files.
+    pub fn serialize_ron(&self, registry: &TypeRegistryArc) -> Result<String, ron::Error> {
+        let serializer = GizmoConfigSerializer {
+            asset: self,
+            registry: &registry.read(),
+        };
+        ron::ser::to_string_pretty(&serializer, ron::ser::PrettyConfig::default())
+    }
+}
+
+impl GizmoConfigStore {
+    /// Serializes the configuration of every group to the RON format of `.gizmos.ron` files,
+    /// which can be loaded back as a [`GizmoConfigAsset`].
+    pub fn serialize_ron(&self, registry: &TypeRegistryArc) -> Result<String, ron::Error> {
+        GizmoConfigAsset::from_store(self).serialize_ron(registry)
+    }
+}
+
+/// The [`GizmoConfigAsset`] applied to the [`GizmoConfigStore`] when it is loaded or modified.
+///
+/// Replace the handle to switch configurations, for example when loading another level.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ActiveGizmoConfig(pub Handle<GizmoConfigAsset>);
+
+pub(crate) fn apply_active_gizmo_config(
+    active: Option<Res<ActiveGizmoConfig>>,
+    mut events: EventReader<AssetEvent<GizmoConfigAsset>>,
+    assets: Res<Assets<GizmoConfigAsset>>,
+    mut store: ResMut<GizmoConfigStore>,
+) {
+    let Some(active) = active else {
+        events.clear();
+        return;
+    };
+
+    let changed = events.read().any(|event| match event {
+        AssetEvent::Added { id } | AssetEvent::Modified { id } => *id == active.0.id(),
+        _ => false,
+    });
+
+    if changed || active.is_changed() {
+        if let Some(asset) = assets.get(&active.0) {
+            asset.apply(&mut store);
+        }
+    }
+}
+
+/// [`AssetLoader`] for `.gizmos.ron` files, loading them as [`GizmoConfigAsset`]s.
+#[derive(Debug)]
+pub struct GizmoConfigLoader {
+    type_registry: TypeRegistryArc,
+}
+
+impl FromWorld for GizmoConfigLoader {
+    fn from_world(world: &mut World) -> Self {
+        GizmoConfigLoader {
+            type_registry: world.resource::<AppTypeRegistry>().0.clone(),
+        }
+    }
+}
+
+/// Possible errors that can be produced by [`GizmoConfigLoader`]
+#[non_exhaustive]
+#[derive(Debug, thiserror::Error)]
+pub enum GizmoConfigLoaderError {
+    /// An [IO Error](std::io::Error)
+    #[error("Error while trying to read the gizmo config file: {0}")]
+    Io(#[from] std::io::Error),
+    /// A [RON Error](ron::error::SpannedError)
+    #[error("Could not parse RON: {0}")]
+    RonSpannedError(#[from] ron::error::SpannedError),
+}
+
+impl AssetLoader for GizmoConfigLoader {
+    type Asset = GizmoConfigAsset;
+    type Settings = ();
+    type Error = GizmoConfigLoaderError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            let mut deserializer = ron::de::Deserializer::from_bytes(&bytes)?;
+            let config_deserializer = GizmoConfigDeserializer {
+                registry: &self.type_registry.read(),
+            };
+            Ok(config_deserializer
+                .deserialize(&mut deserializer)
+                .map_err(|e| deserializer.span_error(e))?)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["gizmos.ron"]
+    }
+}
+
+/// Handles serialization of a [`GizmoConfigAsset`] as a map from group type paths to entries.
+pub struct GizmoConfigSerializer<'a> {
+    /// The asset to serialize.
+    pub asset: &'a GizmoConfigAsset,
+    /// Type registry in which [`GizmoConfig`] and the groups of the asset are registered.
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for GizmoConfigSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_map(Some(self.asset.entries.len()))?;
+        for entry in &self.asset.entries {
+            let type_path = self
+                .registry
+                .get_type_info(entry.type_id)
+                .map(|info| info.type_path())
+                .ok_or_else(|| {
+                    serde::ser::Error::custom(format_args!(
+                        "gizmo config group `{}` is not registered",
+                        entry.group.reflect_type_path()
+                    ))
+                })?;
+            state.serialize_entry(
+                type_path,
+                &GizmoConfigEntrySerializer {
+                    entry,
+                    registry: self.registry,
+                },
+            )?;
+        }
+        state.end()
+    }
+}
+
+struct GizmoConfigEntrySerializer<'a> {
+    entry: &'a GizmoConfigEntry,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for GizmoConfigEntrySerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct(ENTRY_STRUCT, 2)?;
+        state.serialize_field(
+            ENTRY_CONFIG,
+            &TypedReflectSerializer::new(&self.entry.config, self.registry),
+        )?;
+        state.serialize_field(
+            ENTRY_GROUP,
+            &TypedReflectSerializer::new(&*self.entry.group, self.registry),
+        )?;
+        state.end()
+    }
+}
+
+/// Handles deserialization of a [`GizmoConfigAsset`].
+pub struct GizmoConfigDeserializer<'a> {
+    /// Type registry in which [`GizmoConfig`] and the deserialized groups are registered.
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for GizmoConfigDeserializer<'a> {
+    type Value = GizmoConfigAsset;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(GizmoConfigVisitor {
+            registry: self.registry,
+        })
+    }
+}
+
+struct GizmoConfigVisitor<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for GizmoConfigVisitor<'a> {
+    type Value = GizmoConfigAsset;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("map of gizmo config groups")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut added = HashSet::new();
+        let mut entries = Vec::new();
+        while let Some(registration) =
+            map.next_key_seed(TypeRegistrationDeserializer::new(self.registry))?
+        {
+            if !added.insert(registration.type_id()) {
+                return Err(Error::custom(format_args!(
+                    "duplicate gizmo config group: `{}`",
+                    registration.type_info().type_path(),
+                )));
+            }
+
+            let (config, group) = map.next_value_seed(GizmoConfigEntryDeserializer {
+                group_registration: registration,
+                registry: self.registry,
+            })?;
+            entries.push(GizmoConfigEntry {
+                type_id: registration.type_id(),
+                config,
+                group,
+            });
+        }
+
+        Ok(GizmoConfigAsset { entries })
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum EntryField {
+    Config,
+    Group,
+}
+
+struct GizmoConfigEntryDeserializer<'a> {
+    group_registration: &'a bevy_reflect::TypeRegistration,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> GizmoConfigEntryDeserializer<'a> {
+    fn deserialize_config<'de, A: SeqAccess<'de>>(
+        &self,
+        seq: &mut A,
+    ) -> Result<Option<GizmoConfig>, A::Error> {
+        let registration = self.config_registration()?;
+        let Some(value) =
+            seq.next_element_seed(TypedReflectDeserializer::new(registration, self.registry))?
+        else {
+            return Ok(None);
+        };
+        from_reflect_config(&*value).map(Some)
+    }
+
+    fn config_registration<E: Error>(&self) -> Result<&'a bevy_reflect::TypeRegistration, E> {
+        self.registry
+            .get(TypeId::of::<GizmoConfig>())
+            .ok_or_else(|| Error::custom("`GizmoConfig` is not registered"))
+    }
+}
+
+fn from_reflect_config<E: Error>(value: &dyn Reflect) -> Result<GizmoConfig, E> {
+    GizmoConfig::from_reflect(value).ok_or_else(|| Error::custom("invalid `GizmoConfig` value"))
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for GizmoConfigEntryDeserializer<'a> {
+    type Value = (GizmoConfig, Box<dyn Reflect>);
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(ENTRY_STRUCT, &[ENTRY_CONFIG, ENTRY_GROUP], self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for GizmoConfigEntryDeserializer<'a> {
+    type Value = (GizmoConfig, Box<dyn Reflect>);
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("gizmo config entry struct")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let config = self
+            .deserialize_config(&mut seq)?
+            .ok_or_else(|| Error::missing_field(ENTRY_CONFIG))?;
+        let group = seq
+            .next_element_seed(TypedReflectDeserializer::new(
+                self.group_registration,
+                self.registry,
+            ))?
+            .ok_or_else(|| Error::missing_field(ENTRY_GROUP))?;
+        Ok((config, group))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut config = None;
+        let mut group = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                EntryField::Config => {
+                    if config.is_some() {
+                        return Err(Error::duplicate_field(ENTRY_CONFIG));
+                    }
+                    let value = map.next_value_seed(TypedReflectDeserializer::new(
+                        self.config_registration()?,
+                        self.registry,
+                    ))?;
+                    config = Some(from_reflect_config(&*value)?);
+                }
+                EntryField::Group => {
+                    if group.is_some() {
+                        return Err(Error::duplicate_field(ENTRY_GROUP));
+                    }
+                    group = Some(map.next_value_seed(TypedReflectDeserializer::new(
+                        self.group_registration,
+                        self.registry,
+                    ))?);
+                }
+            }
+        }
+
+        let config = config.ok_or_else(|| Error::missing_field(ENTRY_CONFIG))?;
+        let group = group.ok_or_else(|| Error::missing_field(ENTRY_GROUP))?;
+        Ok((config, group))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{DefaultGizmoConfigGroup, GizmoLineStyle, GizmoOcclusion};
+    use bevy_render::view::RenderLayers;
+
+    #[test]
+    fn gizmo_config_ron_round_trip() {
+        let registry = TypeRegistryArc::default();
+        {
+            let mut registry = registry.write();
+            registry.register::<GizmoConfig>();
+            registry.register::<GizmoLineStyle>();
+            registry.register::<GizmoOcclusion>();
+            registry.register::<RenderLayers>();
+            registry.register::<DefaultGizmoConfigGroup>();
+        }
+
+        let mut store = GizmoConfigStore::default();
+        store.insert(
+            GizmoConfig {
+                line_width: 5.0,
+                depth_bias: -0.5,
+                occlusion: GizmoOcclusion::XRay {
+                    occluded_alpha: 0.25,
+                    occluded_style: GizmoLineStyle::Dotted { gap_length: 6.0 },
+                },
+                line_style: GizmoLineStyle::Dashed {
+                    dash_length: 8.0,
+                    gap_length: 4.0,
+                },
+                ..Default::default()
+            },
+            DefaultGizmoConfigGroup,
+        );
+        let serialized = store.serialize_ron(&registry).unwrap();
+
+        let mut deserializer = ron::de::Deserializer::from_str(&serialized).unwrap();
+        let asset = GizmoConfigDeserializer {
+            registry: &registry.read(),
+        }
+        .deserialize(&mut deserializer)
+        .unwrap();
+
+        let mut loaded = GizmoConfigStore::default();
+        loaded.insert(GizmoConfig::default(), DefaultGizmoConfigGroup);
+        asset.apply(&mut loaded);
+
+        let (config, _) = loaded.config::<DefaultGizmoConfigGroup>();
+        assert_eq!(config.line_width, 5.0);
+        assert_eq!(config.depth_bias, -0.5);
+        assert_eq!(
+            config.occlusion,
+            GizmoOcclusion::XRay {
+                occluded_alpha: 0.25,
+                occluded_style: GizmoLineStyle::Dotted { gap_length: 6.0 },
+            }
+        );
+        assert_eq!(
+            config.line_style,
+            GizmoLineStyle::Dashed {
+                dash_length: 8.0,
+                gap_length: 4.0,
+            }
+        );
+    }
+}
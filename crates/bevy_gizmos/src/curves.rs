@@ -0,0 +1,360 @@
+//! Additional [`Gizmos`] Functions -- Curves
+//!
+//! Includes the implementation of [`Gizmos::curve_2d`] and [`Gizmos::curve_3d`],
+//! and assorted support items.
+
+use crate::prelude::{GizmoConfigGroup, Gizmos};
+use bevy_math::cubic_splines::{CubicCurve, CubicSegment, Point};
+use bevy_math::{Quat, Vec2, Vec3};
+use bevy_render::color::Color;
+
+/// The default tolerance of [`Gizmos::curve_2d`], half a pixel with the default 2D camera.
+pub const DEFAULT_CURVE_TOLERANCE_2D: f32 = 0.5;
+/// The default tolerance of [`Gizmos::curve_3d`].
+pub const DEFAULT_CURVE_TOLERANCE_3D: f32 = 0.005;
+
+// the most lines drawn for a single segment of a curve
+const MAX_LINES_PER_SEGMENT: usize = 256;
+
+/// A cubic curve drawn by [`Gizmos::curve_2d`] and [`Gizmos::curve_3d`].
+pub trait GizmoCurve<P: Point> {
+    /// Returns the segments of the curve, in order.
+    fn cubic_segments(&self) -> &[CubicSegment<P>];
+}
+
+impl<P: Point> GizmoCurve<P> for CubicCurve<P> {
+    fn cubic_segments(&self) -> &[CubicSegment<P>] {
+        self.segments()
+    }
+}
+
+impl<P: Point> GizmoCurve<P> for CubicSegment<P> {
+    fn cubic_segments(&self) -> &[CubicSegment<P>] {
+        std::slice::from_ref(self)
+    }
+}
+
+/// A builder returned by [`Gizmos::curve_2d`] and [`Gizmos::curve_3d`]
+pub struct CurveBuilder<'a, 'w, 's, T: GizmoConfigGroup> {
+    gizmos: &'a mut Gizmos<'w, 's, T>,
+    // The Bézier control points of each segment of the curve
+    segments: Vec<[Vec3; 4]>,
+    color: Color,
+    tolerance: f32,
+    control_points: Option<Color>,
+    tangents: Option<(usize, Color)>,
+    // Whether the curve is drawn on the XY plane, with circles as markers
+    is_2d: bool,
+}
+
+impl<T: GizmoConfigGroup> CurveBuilder<'_, '_, '_, T> {
+    /// Set the largest distance between the curve and the lines drawn for it.
+    ///
+    /// Each segment of the curve is drawn with as many lines as its curvature needs to stay
+    /// within the tolerance, up to 256.
+    pub fn tolerance(mut self, tolerance: f32) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Also draw the Bézier control points of each segment of the curve in `color`, and the
+    /// lines between them.
+    ///
+    /// The markers of the points are sized after the extent of the curve.
+    pub fn control_points(mut self, color: Color) -> Self {
+        self.control_points = Some(color);
+        self
+    }
+
+    /// Also draw `count` arrows in `color` along the curve, evenly spaced in its parameter,
+    /// showing the tangent of the curve.
+    ///
+    /// The arrows are a third of the velocity of the curve long, so at the ends of a segment
+    /// they match the lines to its Bézier control points.
+    pub fn tangents(mut self, count: usize, color: Color) -> Self {
+        self.tangents = Some((count, color));
+        self
+    }
+}
+
+impl<T: GizmoConfigGroup> Drop for CurveBuilder<'_, '_, '_, T> {
+    fn drop(&mut self) {
+        if !self.gizmos.enabled {
+            return;
+        }
+
+        for polyline in flatten(&self.segments, self.tolerance) {
+            self.gizmos.linestrip(polyline, self.color);
+        }
+
+        if let Some(color) = self.control_points {
+            let radius = marker_radius(&self.segments);
+            for &points in &self.segments {
+                self.gizmos.linestrip(points, color);
+                for point in points {
+                    if self.is_2d {
+                        self.gizmos.circle_2d(point.truncate(), radius, color);
+                    } else {
+                        self.gizmos.sphere(point, Quat::IDENTITY, radius, color);
+                    }
+                }
+            }
+        }
+
+        if let Some((count, color)) = self.tangents {
+            for (position, velocity) in tangent_samples(&self.segments, count) {
+                // arrows can't point anywhere at the cusps of a curve
+                if velocity.length_squared() > f32::EPSILON {
+                    self.gizmos.arrow(position, position + velocity / 3., color);
+                }
+            }
+        }
+    }
+}
+
+impl<'w, 's, T: GizmoConfigGroup> Gizmos<'w, 's, T> {
+    /// Draw a cubic curve in 3D, like a [`CubicCurve`] built by any of the generators of
+    /// [`bevy_math::cubic_splines`], or a single [`CubicSegment`].
+    ///
+    /// The curve is drawn with lines closer to it than [`DEFAULT_CURVE_TOLERANCE_3D`], adapting
+    /// their number to the curvature of each segment.
+    ///
+    /// This should be called for each frame the curve needs to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     let curve = CubicBezier::new([[Vec3::ZERO, Vec3::Y, Vec3::ONE, Vec3::X]]).to_curve();
+    ///     gizmos.curve_3d(&curve, Color::GREEN);
+    ///
+    ///     // Curves can also show their control points and tangents
+    ///     gizmos
+    ///         .curve_3d(&curve, Color::GREEN)
+    ///         .tolerance(0.001)
+    ///         .control_points(Color::GRAY)
+    ///         .tangents(8, Color::RED);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn curve_3d(
+        &mut self,
+        curve: &impl GizmoCurve<Vec3>,
+        color: Color,
+    ) -> CurveBuilder<'_, 'w, 's, T> {
+        CurveBuilder {
+            gizmos: self,
+            segments: curve.cubic_segments().iter().map(bezier_points).collect(),
+            color,
+            tolerance: DEFAULT_CURVE_TOLERANCE_3D,
+            control_points: None,
+            tangents: None,
+            is_2d: false,
+        }
+    }
+
+    /// Draw a cubic curve in 2D (on the xy plane), like a [`CubicCurve`] built by any of the
+    /// generators of [`bevy_math::cubic_splines`], or a single [`CubicSegment`].
+    ///
+    /// The curve is drawn with lines closer to it than [`DEFAULT_CURVE_TOLERANCE_2D`], adapting
+    /// their number to the curvature of each segment.
+    ///
+    /// This should be called for each frame the curve needs to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     let points = [Vec2::ZERO, Vec2::new(100., 100.), Vec2::new(200., 0.), Vec2::X * 300.];
+    ///     let curve = CubicCardinalSpline::new_catmull_rom(points).to_curve();
+    ///     gizmos.curve_2d(&curve, Color::GREEN);
+    ///
+    ///     // Curves can also show their control points and tangents
+    ///     gizmos
+    ///         .curve_2d(&curve, Color::GREEN)
+    ///         .control_points(Color::GRAY)
+    ///         .tangents(8, Color::RED);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn curve_2d(
+        &mut self,
+        curve: &impl GizmoCurve<Vec2>,
+        color: Color,
+    ) -> CurveBuilder<'_, 'w, 's, T> {
+        let segments = curve
+            .cubic_segments()
+            .iter()
+            .map(|segment| bezier_points(segment).map(|point| point.extend(0.)))
+            .collect();
+        CurveBuilder {
+            gizmos: self,
+            segments,
+            color,
+            tolerance: DEFAULT_CURVE_TOLERANCE_2D,
+            control_points: None,
+            tangents: None,
+            is_2d: true,
+        }
+    }
+}
+
+/// Returns the Bézier control points of a cubic segment, from its positions and velocities at
+/// its ends.
+fn bezier_points<P: Point>(segment: &CubicSegment<P>) -> [P; 4] {
+    let [start, end] = [0., 1.].map(|t| segment.position(t));
+    [
+        start,
+        start + segment.velocity(0.) * (1. / 3.),
+        end - segment.velocity(1.) * (1. / 3.),
+        end,
+    ]
+}
+
+/// Evaluates the position of a cubic Bézier segment at `t`.
+fn bezier_position([p0, p1, p2, p3]: [Vec3; 4], t: f32) -> Vec3 {
+    let u = 1. - t;
+    u * u * u * p0 + 3. * u * u * t * p1 + 3. * u * t * t * p2 + t * t * t * p3
+}
+
+/// Evaluates the velocity of a cubic Bézier segment at `t`.
+fn bezier_velocity([p0, p1, p2, p3]: [Vec3; 4], t: f32) -> Vec3 {
+    let u = 1. - t;
+    3. * (u * u * (p1 - p0) + 2. * u * t * (p2 - p1) + t * t * (p3 - p2))
+}
+
+/// Splits the segments of a curve into lines no further than `tolerance` from it, returning
+/// the points of a linestrip for each run of connected segments.
+fn flatten(segments: &[[Vec3; 4]], tolerance: f32) -> Vec<Vec<Vec3>> {
+    let tolerance = tolerance.max(f32::EPSILON);
+    let mut polylines: Vec<Vec<Vec3>> = Vec::new();
+    for &points @ [p0, p1, p2, p3] in segments {
+        // Wang's formula for the number of lines.
+        let deviation = (p0 - 2. * p1 + p2)
+            .length()
+            .max((p1 - 2. * p2 + p3).length());
+        let lines =
+            ((0.75 * deviation / tolerance).sqrt().ceil() as usize).clamp(1, MAX_LINES_PER_SEGMENT);
+
+        let connected = polylines
+            .last()
+            .and_then(|polyline| polyline.last())
+            .is_some_and(|end| end.abs_diff_eq(p0, 1e-5));
+        if !connected {
+            polylines.push(vec![p0]);
+        }
+        if let Some(polyline) = polylines.last_mut() {
+            polyline.extend((1..=lines).map(|i| bezier_position(points, i as f32 / lines as f32)));
+        }
+    }
+    polylines
+}
+
+/// Returns the positions and velocities of the curve at `count` values of its parameter,
+/// evenly spaced from its start to its end.
+fn tangent_samples(
+    segments: &[[Vec3; 4]],
+    count: usize,
+) -> impl Iterator<Item = (Vec3, Vec3)> + '_ {
+    let length = segments.len() as f32;
+    (0..count).filter_map(move |i| {
+        let t = if count == 1 {
+            length * 0.5
+        } else {
+            length * i as f32 / (count - 1) as f32
+        };
+        // the end of the curve is the end of its last segment
+        let index = (t as usize).min(segments.len().checked_sub(1)?);
+        let points = segments[index];
+        let t = t - index as f32;
+        Some((bezier_position(points, t), bezier_velocity(points, t)))
+    })
+}
+
+/// Returns the radius of the markers of control points, relative to the extent of the curve.
+fn marker_radius(segments: &[[Vec3; 4]]) -> f32 {
+    let (min, max) = segments.iter().flatten().fold(
+        (Vec3::splat(f32::INFINITY), Vec3::splat(f32::NEG_INFINITY)),
+        |(min, max), &point| (min.min(point), max.max(point)),
+    );
+    ((max - min).length() * 0.01).max(f32::EPSILON)
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::cubic_splines::{CubicBezier, CubicGenerator};
+
+    use super::*;
+
+    fn quarter_circle() -> [Vec3; 4] {
+        // the usual approximation of a quarter of a unit circle
+        let k = 0.552_284_8;
+        [Vec3::X, Vec3::new(1., k, 0.), Vec3::new(k, 1., 0.), Vec3::Y]
+    }
+
+    #[test]
+    fn bezier_points_round_trip() {
+        let points = quarter_circle();
+        let curve = CubicBezier::new([points]).to_curve();
+        let [segment] = curve.segments() else {
+            panic!("the curve has a single segment");
+        };
+        for (point, expected) in bezier_points(segment).into_iter().zip(points) {
+            assert!(point.abs_diff_eq(expected, 1e-6));
+        }
+    }
+
+    #[test]
+    fn adaptive_resolution() {
+        let line = [Vec3::ZERO, Vec3::X, Vec3::X * 2., Vec3::X * 3.];
+        assert_eq!(
+            flatten(&[line], 0.001),
+            vec![vec![Vec3::ZERO, Vec3::X * 3.]]
+        );
+
+        let points = quarter_circle();
+        let coarse = &flatten(&[points], 0.01)[0];
+        let fine = &flatten(&[points], 0.0001)[0];
+        assert!(fine.len() > coarse.len());
+        assert!(flatten(&[points], 0.)[0].len() <= MAX_LINES_PER_SEGMENT + 1);
+
+        // the middle of each line is close to the curve
+        for tolerance in [0.01, 0.0001] {
+            let polyline = &flatten(&[points], tolerance)[0];
+            let lines = polyline.len() - 1;
+            for (i, line) in polyline.windows(2).enumerate() {
+                let t = (i as f32 + 0.5) / lines as f32;
+                let distance = bezier_position(points, t).distance(line[0].lerp(line[1], 0.5));
+                assert!(distance <= tolerance);
+            }
+        }
+    }
+
+    #[test]
+    fn disconnected_segments() {
+        let points = quarter_circle();
+        let next = points.map(|point| point * Vec3::new(-1., 1., 1.));
+        // the second segment starts where the first one ends
+        let mut reversed = next;
+        reversed.reverse();
+        assert_eq!(flatten(&[points, reversed], 0.01).len(), 1);
+        assert_eq!(flatten(&[points, next], 0.01).len(), 2);
+        assert!(flatten(&[], 0.01).is_empty());
+    }
+
+    #[test]
+    fn tangents() {
+        let points = quarter_circle();
+        let samples: Vec<_> = tangent_samples(&[points, points], 3).collect();
+        assert_eq!(samples.len(), 3);
+        // the ends of the curve, and the end of its first segment
+        assert_eq!(samples[0], (Vec3::X, 3. * (points[1] - points[0])));
+        assert_eq!(samples[1].0, Vec3::X);
+        assert!(samples[2].0.abs_diff_eq(Vec3::Y, 1e-6));
+        assert_eq!(tangent_samples(&[], 3).count(), 0);
+    }
+}
@@ -0,0 +1,56 @@
+//! Diagnostics of the gizmo vertices written to GPU buffers.
+
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+use bevy_app::{App, Plugin, Update};
+use bevy_diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic};
+use bevy_ecs::system::{Res, Resource};
+
+/// Adds a diagnostic reporting the number of bytes of gizmo vertices written to GPU buffers each
+/// frame.
+///
+/// Gizmo vertex buffers are kept across frames and only the vertices that changed are written,
+/// so gizmos that are drawn the same way every frame don't add to this measurement.
+pub struct GizmoDiagnosticsPlugin;
+
+impl Plugin for GizmoDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_diagnostic(Diagnostic::new(Self::UPLOADED_BYTES).with_suffix("B"))
+            .add_systems(Update, Self::diagnostic_system);
+    }
+}
+
+impl GizmoDiagnosticsPlugin {
+    /// The number of bytes of gizmo vertices written to GPU buffers during the frame.
+    pub const UPLOADED_BYTES: DiagnosticPath = DiagnosticPath::const_new("gizmos/uploaded_bytes");
+
+    /// Adds the bytes counted in [`GizmoUploadedBytes`] since the last frame as a measurement of
+    /// [`Self::UPLOADED_BYTES`].
+    pub fn diagnostic_system(
+        mut diagnostics: Diagnostics,
+        uploaded_bytes: Res<GizmoUploadedBytes>,
+    ) {
+        let bytes = uploaded_bytes.take();
+        diagnostics.add_measurement(&Self::UPLOADED_BYTES, || bytes as f64);
+    }
+}
+
+/// The number of bytes of gizmo vertices written to GPU buffers since the last measurement.
+///
+/// This resource is shared between the main world and the render world.
+#[derive(Resource, Clone, Default)]
+pub struct GizmoUploadedBytes(Arc<AtomicU64>);
+
+impl GizmoUploadedBytes {
+    pub(crate) fn add(&self, bytes: u64) {
+        self.0.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// Returns the number of bytes written since the last call, and resets it.
+    pub fn take(&self) -> u64 {
+        self.0.swap(0, Ordering::Relaxed)
+    }
+}
@@ -0,0 +1,32 @@
+#import bevy_render::view::View
+
+@group(0) @binding(0) var<uniform> view: View;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) color: vec4<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+};
+
+@vertex
+fn vertex(vertex: VertexInput) -> VertexOutput {
+    let clip_position = view.view_proj * vec4(vertex.position, 1.);
+    return VertexOutput(clip_position, vertex.color);
+}
+
+struct FragmentInput {
+    @location(0) color: vec4<f32>,
+};
+
+struct FragmentOutput {
+    @location(0) color: vec4<f32>,
+};
+
+@fragment
+fn fragment(in: FragmentInput) -> FragmentOutput {
+    return FragmentOutput(in.color);
+}
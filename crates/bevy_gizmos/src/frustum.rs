@@ -0,0 +1,165 @@
+//! A module adding debug visualization of camera frusta.
+
+use crate as bevy_gizmos;
+
+use bevy_app::{Plugin, PostUpdate};
+use bevy_ecs::{
+    component::Component,
+    query::Without,
+    reflect::ReflectComponent,
+    schedule::IntoSystemConfigs,
+    system::{Query, Res},
+};
+use bevy_math::Vec3;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    camera::{CameraProjection, OrthographicProjection, PerspectiveProjection, Projection},
+    color::Color,
+};
+use bevy_transform::{components::GlobalTransform, TransformSystem};
+
+use crate::{
+    config::{GizmoConfigGroup, GizmoConfigStore},
+    gizmos::Gizmos,
+    AppGizmoBuilder,
+};
+
+/// A [`Plugin`] that provides visualization of camera frusta for debugging.
+pub struct FrustumGizmoPlugin;
+
+impl Plugin for FrustumGizmoPlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        app.register_type::<FrustumGizmoConfigGroup>()
+            .register_type::<ShowFrustumGizmo>()
+            .init_gizmo_group::<FrustumGizmoConfigGroup>()
+            .add_systems(
+                PostUpdate,
+                (
+                    draw_frusta::<Projection>,
+                    draw_frusta::<PerspectiveProjection>,
+                    draw_frusta::<OrthographicProjection>,
+                    (
+                        draw_all_frusta::<Projection>,
+                        draw_all_frusta::<PerspectiveProjection>,
+                        draw_all_frusta::<OrthographicProjection>,
+                    )
+                        .run_if(|config: Res<GizmoConfigStore>| {
+                            config.config::<FrustumGizmoConfigGroup>().1.draw_all
+                        }),
+                )
+                    .after(TransformSystem::TransformPropagate),
+            );
+    }
+}
+
+/// The [`GizmoConfigGroup`] used for debug visualizations of the frusta of cameras.
+#[derive(Clone, Default, Reflect, GizmoConfigGroup)]
+pub struct FrustumGizmoConfigGroup {
+    /// Draws the frusta of all cameras in the scene when set to `true`.
+    ///
+    /// To draw a specific camera's frustum, you can add the [`ShowFrustumGizmo`] component.
+    ///
+    /// Defaults to `false`.
+    pub draw_all: bool,
+    /// The default color for frustum gizmos.
+    ///
+    /// White is used if `None`.
+    ///
+    /// Defaults to `None`.
+    pub default_color: Option<Color>,
+}
+
+/// Add this [`Component`] to a camera to draw its frustum.
+///
+/// The frustum spans from the near plane to the far plane of the projection of the camera.
+#[derive(Component, Reflect, Default, Debug)]
+#[reflect(Component, Default)]
+pub struct ShowFrustumGizmo {
+    /// The color of the frustum.
+    ///
+    /// The default color from the [`FrustumGizmoConfigGroup`] config is used if `None`,
+    pub color: Option<Color>,
+}
+
+fn draw_frusta<P: CameraProjection + Component>(
+    query: Query<(&P, &GlobalTransform, &ShowFrustumGizmo)>,
+    mut gizmos: Gizmos<FrustumGizmoConfigGroup>,
+) {
+    for (projection, transform, gizmo) in &query {
+        let color = gizmo
+            .color
+            .or(gizmos.config_ext.default_color)
+            .unwrap_or(Color::WHITE);
+        draw_frustum(&mut gizmos, projection, transform, color);
+    }
+}
+
+fn draw_all_frusta<P: CameraProjection + Component>(
+    query: Query<(&P, &GlobalTransform), Without<ShowFrustumGizmo>>,
+    mut gizmos: Gizmos<FrustumGizmoConfigGroup>,
+) {
+    for (projection, transform) in &query {
+        let color = gizmos.config_ext.default_color.unwrap_or(Color::WHITE);
+        draw_frustum(&mut gizmos, projection, transform, color);
+    }
+}
+
+fn draw_frustum(
+    gizmos: &mut Gizmos<FrustumGizmoConfigGroup>,
+    projection: &impl CameraProjection,
+    transform: &GlobalTransform,
+    color: Color,
+) {
+    let corners = frustum_corners(projection).map(|corner| transform.transform_point(corner));
+    draw_box_edges(gizmos, corners, color);
+}
+
+/// Returns the corners of the frustum of `projection` in view space, as ordered by
+/// [`CameraProjection::get_frustum_corners`].
+///
+/// The near plane is found from the projection matrix, as projections only expose their far
+/// plane. Bevy uses reversed depth, so the near plane is at a depth of 1 in clip space.
+pub(crate) fn frustum_corners(projection: &impl CameraProjection) -> [Vec3; 8] {
+    let near = projection
+        .get_projection_matrix()
+        .inverse()
+        .project_point3(Vec3::Z)
+        .z;
+    projection
+        .get_frustum_corners(near, -projection.far())
+        .map(Vec3::from)
+}
+
+/// Draws the 12 edges of a box from its 8 corners, ordered by
+/// [`CameraProjection::get_frustum_corners`]: the 4 corners of the near face going around it,
+/// then the corresponding corners of the far face.
+pub(crate) fn draw_box_edges<T: GizmoConfigGroup>(
+    gizmos: &mut Gizmos<T>,
+    corners: [Vec3; 8],
+    color: Color,
+) {
+    let [near, far] = [[0, 1, 2, 3], [4, 5, 6, 7]].map(|face| face.map(|index| corners[index]));
+    gizmos.linestrip([near[0], near[1], near[2], near[3], near[0]], color);
+    gizmos.linestrip([far[0], far[1], far[2], far[3], far[0]], color);
+    for (near, far) in near.into_iter().zip(far) {
+        gizmos.line(near, far, color);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn perspective_frustum_corners() {
+        let projection = PerspectiveProjection {
+            fov: std::f32::consts::FRAC_PI_2,
+            aspect_ratio: 2.,
+            near: 0.5,
+            far: 10.,
+        };
+        let corners = frustum_corners(&projection);
+        assert!(corners[1].abs_diff_eq(Vec3::new(1., 0.5, -0.5), 1e-4));
+        assert!(corners[7].abs_diff_eq(Vec3::new(-20., -10., -10.), 1e-4));
+    }
+}
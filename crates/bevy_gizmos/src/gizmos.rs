@@ -1,39 +1,175 @@
 //! A module for the [`Gizmos`] [`SystemParam`].
 
-use std::{iter, marker::PhantomData};
+#[cfg(feature = "bevy_picking")]
+use std::ops::Range;
+use std::{iter, marker::PhantomData, mem};
 
 use crate::circles::DEFAULT_CIRCLE_SEGMENTS;
 use bevy_ecs::{
     component::Tick,
-    system::{Deferred, ReadOnlySystemParam, Res, Resource, SystemBuffer, SystemMeta, SystemParam},
+    system::{
+        Deferred, ReadOnlySystemParam, Res, ResMut, Resource, SystemBuffer, SystemMeta, SystemParam,
+    },
     world::{unsafe_world_cell::UnsafeWorldCell, World},
 };
 use bevy_math::{primitives::Direction3d, Mat2, Quat, Vec2, Vec3};
-use bevy_render::color::Color;
+use bevy_render::{
+    color::{Color, ColorSpace, Gradient},
+    view::RenderLayers,
+};
+use bevy_time::Time;
 use bevy_transform::TransformPoint;
 
 use crate::{
     config::GizmoConfigGroup,
     config::{DefaultGizmoConfigGroup, GizmoConfigStore},
+    mesh::GizmoMesh,
     prelude::GizmoConfig,
+    LineGizmo,
 };
 
+#[cfg(feature = "bevy_text")]
+use crate::text::GizmoText;
+
 type PositionItem = [f32; 3];
 type ColorItem = [f32; 4];
 
-#[derive(Resource, Default)]
-pub(crate) struct GizmoStorage<T: GizmoConfigGroup> {
+/// The vertices drawn with [`Gizmos`] to one set of [`RenderLayers`].
+#[derive(Default, Clone)]
+pub(crate) struct GizmoLines {
     pub list_positions: Vec<PositionItem>,
     pub list_colors: Vec<ColorItem>,
     pub strip_positions: Vec<PositionItem>,
     pub strip_colors: Vec<ColorItem>,
+    pub triangle_positions: Vec<PositionItem>,
+    pub triangle_colors: Vec<ColorItem>,
+    #[cfg(feature = "bevy_text")]
+    pub texts: Vec<GizmoText>,
+    pub meshes: Vec<GizmoMesh>,
+    /// The ranges of vertices drawn with [`Gizmos::with_pick_id`], innermost first.
+    #[cfg(feature = "bevy_picking")]
+    pub pick_ids: Vec<GizmoPickIdRange>,
+}
+
+/// The vertices of [`GizmoLines`] drawn with a pick id, see [`Gizmos::with_pick_id`].
+#[cfg(feature = "bevy_picking")]
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct GizmoPickIdRange {
+    pub pick_id: u64,
+    pub list: Range<usize>,
+    pub strip: Range<usize>,
+}
+
+impl GizmoLines {
+    fn append(&mut self, other: &mut Self) {
+        #[cfg(feature = "bevy_picking")]
+        {
+            let (list_offset, strip_offset) =
+                (self.list_positions.len(), self.strip_positions.len());
+            self.pick_ids
+                .extend(other.pick_ids.drain(..).map(|range| GizmoPickIdRange {
+                    pick_id: range.pick_id,
+                    list: range.list.start + list_offset..range.list.end + list_offset,
+                    strip: range.strip.start + strip_offset..range.strip.end + strip_offset,
+                }));
+        }
+        self.list_positions.append(&mut other.list_positions);
+        self.list_colors.append(&mut other.list_colors);
+        self.strip_positions.append(&mut other.strip_positions);
+        self.strip_colors.append(&mut other.strip_colors);
+        self.triangle_positions
+            .append(&mut other.triangle_positions);
+        self.triangle_colors.append(&mut other.triangle_colors);
+        #[cfg(feature = "bevy_text")]
+        self.texts.append(&mut other.texts);
+        self.meshes.append(&mut other.meshes);
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        #[cfg(feature = "bevy_text")]
+        if !self.texts.is_empty() {
+            return false;
+        }
+        self.list_positions.is_empty()
+            && self.strip_positions.is_empty()
+            && self.triangle_positions.is_empty()
+            && self.meshes.is_empty()
+    }
+
+    /// Gives the `pick_id` to all the lines that don't have one yet.
+    #[cfg(feature = "bevy_picking")]
+    fn set_pick_id(&mut self, pick_id: u64) {
+        if self.list_positions.is_empty() && self.strip_positions.is_empty() {
+            return;
+        }
+        self.pick_ids.push(GizmoPickIdRange {
+            pick_id,
+            list: 0..self.list_positions.len(),
+            strip: 0..self.strip_positions.len(),
+        });
+    }
+}
+
+/// Appends `lines` to the entry of `layered` drawn to `render_layers`, adding it if needed.
+fn append_layered(
+    layered: &mut Vec<(RenderLayers, GizmoLines)>,
+    render_layers: RenderLayers,
+    lines: &mut GizmoLines,
+) {
+    match layered
+        .iter_mut()
+        .find(|(layers, _)| *layers == render_layers)
+    {
+        Some((_, target)) => target.append(lines),
+        None => layered.push((render_layers, mem::take(lines))),
+    }
+}
+
+/// Lines drawn with [`Gizmos::with_duration`], drawn again each frame until they expire.
+pub(crate) struct RetainedGizmoLines {
+    /// The render layers the lines were drawn to, or `None` for the ones of the [`GizmoConfig`].
+    render_layers: Option<RenderLayers>,
+    lines: GizmoLines,
+    /// The time left before the lines expire, in seconds.
+    remaining: f32,
+}
+
+#[derive(Resource, Default)]
+pub(crate) struct GizmoStorage<T: GizmoConfigGroup> {
+    /// The lines drawn to the render layers of the [`GizmoConfig`].
+    pub lines: GizmoLines,
+    /// The lines drawn with [`Gizmos::with_render_layers`], by render layers.
+    pub layered: Vec<(RenderLayers, GizmoLines)>,
+    /// The lines drawn with [`Gizmos::with_duration`] that haven't expired yet.
+    pub retained: Vec<RetainedGizmoLines>,
     marker: PhantomData<T>,
 }
 
+/// Adds the lines drawn with [`Gizmos::with_duration`] to the ones drawn this frame, and drops
+/// the expired ones.
+pub(crate) fn draw_retained_gizmos<T: GizmoConfigGroup>(
+    time: Option<Res<Time>>,
+    mut storage: ResMut<GizmoStorage<T>>,
+) {
+    let delta = time.map_or(0., |time| time.delta_seconds());
+    let storage = &mut *storage;
+    storage.retained.retain_mut(|retained| {
+        let mut lines = retained.lines.clone();
+        match retained.render_layers {
+            Some(render_layers) => append_layered(&mut storage.layered, render_layers, &mut lines),
+            None => storage.lines.append(&mut lines),
+        }
+        // Lines are drawn at least once, even with a duration of zero
+        retained.remaining -= delta;
+        retained.remaining > 0.
+    });
+}
+
 /// A [`SystemParam`] for drawing gizmos.
 ///
 /// They are drawn in immediate mode, which means they will be rendered only for
 /// the frames in which they are spawned.
+/// Use [`Gizmos::with_duration`] to keep drawing them for longer.
 /// Gizmos should be spawned before the [`Last`](bevy_app::Last) schedule to ensure they are drawn.
 pub struct Gizmos<'w, 's, T: GizmoConfigGroup = DefaultGizmoConfigGroup> {
     buffer: Deferred<'s, GizmoBuffer<T>>,
@@ -103,20 +239,22 @@ where
 
 #[derive(Default)]
 struct GizmoBuffer<T: GizmoConfigGroup> {
-    list_positions: Vec<PositionItem>,
-    list_colors: Vec<ColorItem>,
-    strip_positions: Vec<PositionItem>,
-    strip_colors: Vec<ColorItem>,
+    lines: GizmoLines,
+    layered: Vec<(RenderLayers, GizmoLines)>,
+    retained: Vec<RetainedGizmoLines>,
+    /// The render layers of the innermost [`Gizmos::with_render_layers`] being drawn.
+    render_layers: Option<RenderLayers>,
     marker: PhantomData<T>,
 }
 
 impl<T: GizmoConfigGroup> SystemBuffer for GizmoBuffer<T> {
     fn apply(&mut self, _system_meta: &SystemMeta, world: &mut World) {
         let mut storage = world.resource_mut::<GizmoStorage<T>>();
-        storage.list_positions.append(&mut self.list_positions);
-        storage.list_colors.append(&mut self.list_colors);
-        storage.strip_positions.append(&mut self.strip_positions);
-        storage.strip_colors.append(&mut self.strip_colors);
+        storage.lines.append(&mut self.lines);
+        for (render_layers, mut lines) in self.layered.drain(..) {
+            append_layered(&mut storage.layered, render_layers, &mut lines);
+        }
+        storage.retained.append(&mut self.retained);
     }
 }
 
@@ -237,11 +375,12 @@ impl<'w, 's, T: GizmoConfigGroup> Gizmos<'w, 's, T> {
             return;
         }
         self.extend_strip_positions(positions);
-        let len = self.buffer.strip_positions.len();
+        let len = self.buffer.lines.strip_positions.len();
         self.buffer
+            .lines
             .strip_colors
             .resize(len - 1, color.as_linear_rgba_f32());
-        self.buffer.strip_colors.push([f32::NAN; 4]);
+        self.buffer.lines.strip_colors.push([f32::NAN; 4]);
     }
 
     /// Draw a line in 3D made of straight segments between the points, with a color gradient.
@@ -269,11 +408,11 @@ impl<'w, 's, T: GizmoConfigGroup> Gizmos<'w, 's, T> {
         }
         let points = points.into_iter();
 
-        let GizmoBuffer {
+        let GizmoLines {
             strip_positions,
             strip_colors,
             ..
-        } = &mut *self.buffer;
+        } = &mut self.buffer.lines;
 
         let (min, _) = points.size_hint();
         strip_positions.reserve(min);
@@ -288,6 +427,36 @@ impl<'w, 's, T: GizmoConfigGroup> Gizmos<'w, 's, T> {
         strip_colors.push([f32::NAN; 4]);
     }
 
+    /// Draw a line in 3D made of straight segments between the points, colored by sampling
+    /// `gradient` at evenly spaced positions from its first stop to its last.
+    ///
+    /// This should be called for each frame the lines need to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::{color::{Gradient, Oklch}, prelude::*};
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     let gradient = Gradient::<Oklch>::even([Color::GREEN, Color::RED, Color::BLUE]);
+    ///     gizmos.linestrip_sampled_gradient([Vec3::ZERO, Vec3::X, Vec3::Y, Vec3::Z], &gradient);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn linestrip_sampled_gradient<C: ColorSpace>(
+        &mut self,
+        positions: impl IntoIterator<Item = Vec3>,
+        gradient: &Gradient<C>,
+    ) {
+        if !self.enabled {
+            return;
+        }
+        let positions: Vec<Vec3> = positions.into_iter().collect();
+        let colors = gradient.samples(positions.len());
+        self.linestrip_gradient(positions.into_iter().zip(colors));
+    }
+
     /// Draw a wireframe sphere in 3D made out of 3 circles around the axes.
     ///
     /// This should be called for each frame the sphere needs to be rendered.
@@ -563,9 +732,222 @@ impl<'w, 's, T: GizmoConfigGroup> Gizmos<'w, 's, T> {
         self.linestrip_2d([tl, tr, br, bl, tl], color);
     }
 
+    /// Draw filled triangles in 2D, each made of three consecutive points.
+    ///
+    /// Trailing points that don't make a full triangle are ignored. Filled gizmos are drawn
+    /// below the lines, so a translucent `color` keeps overlapping shapes readable.
+    ///
+    /// This should be called for each frame the triangles need to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.triangles_2d([Vec2::ZERO, Vec2::X, Vec2::Y], Color::GREEN.with_a(0.3));
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn triangles_2d(&mut self, positions: impl IntoIterator<Item = Vec2>, color: Color) {
+        if !self.enabled {
+            return;
+        }
+        let start = self.buffer.lines.triangle_positions.len();
+        self.buffer
+            .lines
+            .triangle_positions
+            .extend(positions.into_iter().map(|vec2| vec2.extend(0.).to_array()));
+        let len = start + (self.buffer.lines.triangle_positions.len() - start) / 3 * 3;
+        self.buffer.lines.triangle_positions.truncate(len);
+        self.buffer
+            .lines
+            .triangle_colors
+            .resize(len, color.as_linear_rgba_f32());
+    }
+
+    /// Draws the gizmos of `draw` to the cameras sharing a layer with `render_layers`, instead
+    /// of the [`GizmoConfig::render_layers`] of the group.
+    ///
+    /// This lets a single group draw some gizmos to editor cameras only and others to gameplay
+    /// cameras. The rest of the [`GizmoConfig`] still applies. To draw retained gizmos to other
+    /// layers, insert [`RenderLayers`] on their [`Gizmo`](crate::retained::Gizmo) entities.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::{prelude::*, view::RenderLayers};
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.line(Vec3::ZERO, Vec3::X, Color::GREEN);
+    ///     // Only seen by the cameras on layer 1, like an editor camera.
+    ///     gizmos.with_render_layers(RenderLayers::layer(1), |gizmos| {
+    ///         gizmos.sphere(Vec3::ZERO, Quat::IDENTITY, 1., Color::RED);
+    ///     });
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn with_render_layers(
+        &mut self,
+        render_layers: RenderLayers,
+        draw: impl FnOnce(&mut Self),
+    ) {
+        if !self.enabled {
+            return;
+        }
+        let outer = mem::take(&mut self.buffer.lines);
+        let outer_layers = self.buffer.render_layers.replace(render_layers);
+        draw(self);
+        self.buffer.render_layers = outer_layers;
+        let mut lines = mem::replace(&mut self.buffer.lines, outer);
+        append_layered(&mut self.buffer.layered, render_layers, &mut lines);
+    }
+
+    /// Keeps drawing the gizmos drawn by `draw` for the given number of `seconds`, starting from
+    /// this frame.
+    ///
+    /// This makes one-shot events visible, like a collision impact, which would only be shown for
+    /// a single frame otherwise. The gizmos are drawn at least once, and keep the render layers
+    /// they were drawn with. The duration is measured with the [`Time`] resource, so it doesn't
+    /// run out while the game is paused.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use bevy_ecs::prelude::*;
+    /// # #[derive(Event)]
+    /// # struct Impact { position: Vec3 }
+    /// fn system(mut gizmos: Gizmos, mut impacts: EventReader<Impact>) {
+    ///     for impact in impacts.read() {
+    ///         gizmos.with_duration(2., |gizmos| {
+    ///             gizmos.sphere(impact.position, Quat::IDENTITY, 0.2, Color::RED);
+    ///         });
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn with_duration(&mut self, seconds: f32, draw: impl FnOnce(&mut Self)) {
+        if !self.enabled {
+            return;
+        }
+        let outer = mem::take(&mut self.buffer.lines);
+        let outer_layered = mem::take(&mut self.buffer.layered);
+        draw(self);
+        let lines = mem::replace(&mut self.buffer.lines, outer);
+        let layered = mem::replace(&mut self.buffer.layered, outer_layered);
+        let render_layers = self.buffer.render_layers;
+        self.buffer.retained.extend(
+            iter::once((render_layers, lines))
+                .chain(
+                    layered
+                        .into_iter()
+                        .map(|(render_layers, lines)| (Some(render_layers), lines)),
+                )
+                .filter(|(_, lines)| !lines.is_empty())
+                .map(|(render_layers, lines)| RetainedGizmoLines {
+                    render_layers,
+                    lines,
+                    remaining: seconds,
+                }),
+        );
+    }
+
+    /// Gives the lines drawn by `draw` a `pick_id`, reported by the
+    /// [`GizmoPickingPlugin`](crate::picking::GizmoPickingPlugin) when they are hovered or
+    /// clicked.
+    ///
+    /// This tells apart the gizmos of a same [`GizmoConfigGroup`], for example the items of an
+    /// in-game editor. Inner calls take precedence over outer ones, and the id is kept by the
+    /// gizmos drawn with [`Gizmos::with_render_layers`] and [`Gizmos::with_duration`].
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     for (index, position) in [Vec3::ZERO, Vec3::X * 2.].into_iter().enumerate() {
+    ///         gizmos.with_pick_id(index as u64, |gizmos| {
+    ///             gizmos.sphere(position, Quat::IDENTITY, 0.5, Color::GREEN);
+    ///         });
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[cfg(feature = "bevy_picking")]
+    pub fn with_pick_id(&mut self, pick_id: u64, draw: impl FnOnce(&mut Self)) {
+        if !self.enabled {
+            return;
+        }
+        let outer = mem::take(&mut self.buffer.lines);
+        let outer_layered = mem::take(&mut self.buffer.layered);
+        let outer_retained = self.buffer.retained.len();
+        draw(self);
+        let mut lines = mem::replace(&mut self.buffer.lines, outer);
+        let layered = mem::replace(&mut self.buffer.layered, outer_layered);
+
+        lines.set_pick_id(pick_id);
+        self.buffer.lines.append(&mut lines);
+        for (render_layers, mut lines) in layered {
+            lines.set_pick_id(pick_id);
+            append_layered(&mut self.buffer.layered, render_layers, &mut lines);
+        }
+        for retained in &mut self.buffer.retained[outer_retained..] {
+            retained.lines.set_pick_id(pick_id);
+        }
+    }
+
+    /// Takes the lines drawn so far by this system, so they aren't rendered as gizmos.
+    ///
+    /// This returns a [`LineGizmo`] for the line-list and the line-strip output, skipping empty
+    /// ones, which can then be baked into [`Mesh`](bevy_render::mesh::Mesh)es with
+    /// [`LineGizmo::to_meshes`] for cheap retained debug geometry.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_render::render_asset::RenderAssetUsages;
+    /// # use bevy_math::prelude::*;
+    /// # use bevy_asset::Assets;
+    /// # use bevy_ecs::prelude::*;
+    /// fn system(mut gizmos: Gizmos, mut meshes: ResMut<Assets<Mesh>>) {
+    ///     gizmos.circle(Vec3::ZERO, Direction3d::Y, 1., Color::GREEN);
+    ///     for line_gizmo in gizmos.take_line_gizmos() {
+    ///         for mesh in line_gizmo.to_meshes(RenderAssetUsages::RENDER_WORLD) {
+    ///             // Spawn the mesh with a material to draw it every frame.
+    ///             let _handle = meshes.add(mesh);
+    ///         }
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn take_line_gizmos(&mut self) -> Vec<LineGizmo> {
+        #[cfg(feature = "bevy_picking")]
+        self.buffer.lines.pick_ids.clear();
+        let list = LineGizmo {
+            positions: mem::take(&mut self.buffer.lines.list_positions),
+            colors: mem::take(&mut self.buffer.lines.list_colors),
+            strip: false,
+        };
+        let strip = LineGizmo {
+            positions: mem::take(&mut self.buffer.lines.strip_positions),
+            colors: mem::take(&mut self.buffer.lines.strip_colors),
+            strip: true,
+        };
+        [list, strip]
+            .into_iter()
+            .filter(|line_gizmo| !line_gizmo.is_empty())
+            .collect()
+    }
+
     #[inline]
     fn extend_list_positions(&mut self, positions: impl IntoIterator<Item = Vec3>) {
         self.buffer
+            .lines
             .list_positions
             .extend(positions.into_iter().map(|vec3| vec3.to_array()));
     }
@@ -573,6 +955,7 @@ impl<'w, 's, T: GizmoConfigGroup> Gizmos<'w, 's, T> {
     #[inline]
     fn extend_list_colors(&mut self, colors: impl IntoIterator<Item = Color>) {
         self.buffer
+            .lines
             .list_colors
             .extend(colors.into_iter().map(|color| color.as_linear_rgba_f32()));
     }
@@ -580,13 +963,25 @@ impl<'w, 's, T: GizmoConfigGroup> Gizmos<'w, 's, T> {
     #[inline]
     fn add_list_color(&mut self, color: Color, count: usize) {
         self.buffer
+            .lines
             .list_colors
             .extend(iter::repeat(color.as_linear_rgba_f32()).take(count));
     }
 
+    #[cfg(feature = "bevy_text")]
+    #[inline]
+    pub(crate) fn push_text(&mut self, text: GizmoText) {
+        self.buffer.lines.texts.push(text);
+    }
+
+    #[inline]
+    pub(crate) fn push_mesh(&mut self, mesh: GizmoMesh) {
+        self.buffer.lines.meshes.push(mesh);
+    }
+
     #[inline]
     fn extend_strip_positions(&mut self, positions: impl IntoIterator<Item = Vec3>) {
-        self.buffer.strip_positions.extend(
+        self.buffer.lines.strip_positions.extend(
             positions
                 .into_iter()
                 .map(|vec3| vec3.to_array())
@@ -639,3 +1034,202 @@ fn rect_inner(size: Vec2) -> [Vec2; 4] {
     let br = Vec2::new(half_size.x, -half_size.y);
     [tl, tr, br, bl]
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy_ecs::system::RunSystemOnce;
+    use bevy_render::{render_asset::RenderAssetUsages, render_resource::PrimitiveTopology};
+
+    use super::*;
+
+    #[test]
+    fn with_render_layers() {
+        let mut world = World::new();
+        let mut config_store = GizmoConfigStore::default();
+        config_store.register::<DefaultGizmoConfigGroup>();
+        world.insert_resource(config_store);
+        world.init_resource::<GizmoStorage<DefaultGizmoConfigGroup>>();
+
+        world.run_system_once(|mut gizmos: Gizmos| {
+            gizmos.line(Vec3::ZERO, Vec3::X, Color::GREEN);
+            gizmos.with_render_layers(RenderLayers::layer(1), |gizmos| {
+                gizmos.line(Vec3::ZERO, Vec3::Y, Color::RED);
+                gizmos.with_render_layers(RenderLayers::layer(2), |gizmos| {
+                    gizmos.line(Vec3::ZERO, Vec3::Z, Color::BLUE);
+                });
+            });
+            gizmos.with_render_layers(RenderLayers::layer(1), |gizmos| {
+                gizmos.line(Vec3::ZERO, Vec3::NEG_Y, Color::RED);
+            });
+            gizmos.line(Vec3::ZERO, Vec3::NEG_X, Color::GREEN);
+        });
+
+        let storage = world.resource::<GizmoStorage<DefaultGizmoConfigGroup>>();
+        assert_eq!(
+            storage.lines.list_positions,
+            [[0.; 3], [1., 0., 0.], [0.; 3], [-1., 0., 0.]]
+        );
+        let layers: Vec<_> = storage
+            .layered
+            .iter()
+            .map(|(render_layers, lines)| (*render_layers, lines.list_positions.clone()))
+            .collect();
+        assert_eq!(
+            layers,
+            [
+                (RenderLayers::layer(2), vec![[0.; 3], [0., 0., 1.]]),
+                (
+                    RenderLayers::layer(1),
+                    vec![[0.; 3], [0., 1., 0.], [0.; 3], [0., -1., 0.]]
+                ),
+            ]
+        );
+    }
+
+    #[cfg(feature = "bevy_picking")]
+    #[test]
+    fn with_pick_id() {
+        let mut world = World::new();
+        let mut config_store = GizmoConfigStore::default();
+        config_store.register::<DefaultGizmoConfigGroup>();
+        world.insert_resource(config_store);
+        world.init_resource::<GizmoStorage<DefaultGizmoConfigGroup>>();
+
+        world.run_system_once(|mut gizmos: Gizmos| {
+            gizmos.line(Vec3::ZERO, Vec3::X, Color::GREEN);
+            gizmos.with_pick_id(1, |gizmos| {
+                gizmos.line(Vec3::ZERO, Vec3::Y, Color::RED);
+                gizmos.with_pick_id(2, |gizmos| {
+                    gizmos.linestrip([Vec3::ZERO, Vec3::Z, Vec3::ONE], Color::BLUE);
+                    gizmos.with_render_layers(RenderLayers::layer(1), |gizmos| {
+                        gizmos.line(Vec3::ZERO, Vec3::Z, Color::BLUE);
+                    });
+                });
+                gizmos.line(Vec3::ZERO, Vec3::NEG_Y, Color::RED);
+            });
+        });
+
+        let storage = world.resource::<GizmoStorage<DefaultGizmoConfigGroup>>();
+        assert_eq!(
+            storage.lines.pick_ids,
+            [
+                GizmoPickIdRange {
+                    pick_id: 2,
+                    list: 4..4,
+                    strip: 0..4,
+                },
+                GizmoPickIdRange {
+                    pick_id: 1,
+                    list: 2..6,
+                    strip: 0..4,
+                },
+            ]
+        );
+        let (_, layered) = &storage.layered[0];
+        assert_eq!(
+            layered.pick_ids,
+            [
+                GizmoPickIdRange {
+                    pick_id: 2,
+                    list: 0..2,
+                    strip: 0..0,
+                },
+                GizmoPickIdRange {
+                    pick_id: 1,
+                    list: 0..2,
+                    strip: 0..0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn with_duration() {
+        let mut world = World::new();
+        let mut config_store = GizmoConfigStore::default();
+        config_store.register::<DefaultGizmoConfigGroup>();
+        world.insert_resource(config_store);
+        world.init_resource::<GizmoStorage<DefaultGizmoConfigGroup>>();
+        world.insert_resource(Time::<()>::default());
+
+        world.run_system_once(|mut gizmos: Gizmos| {
+            gizmos.with_duration(1., |gizmos| {
+                gizmos.line(Vec3::ZERO, Vec3::X, Color::GREEN);
+                gizmos.with_render_layers(RenderLayers::layer(1), |gizmos| {
+                    gizmos.line(Vec3::ZERO, Vec3::Y, Color::RED);
+                });
+            });
+        });
+        let storage = world.resource::<GizmoStorage<DefaultGizmoConfigGroup>>();
+        assert!(storage.lines.is_empty());
+        assert!(storage.layered.is_empty());
+        assert_eq!(storage.retained.len(), 2);
+
+        for frame in 0..3 {
+            let mut storage = world.resource_mut::<GizmoStorage<DefaultGizmoConfigGroup>>();
+            storage.lines = GizmoLines::default();
+            storage.layered.clear();
+            world
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_millis(600));
+            world.run_system_once(draw_retained_gizmos::<DefaultGizmoConfigGroup>);
+
+            // Drawn for the first two frames, then expired
+            let drawn = frame < 2;
+            let storage = world.resource::<GizmoStorage<DefaultGizmoConfigGroup>>();
+            assert_eq!(
+                storage.lines.list_positions.len(),
+                if drawn { 2 } else { 0 }
+            );
+            assert_eq!(storage.layered.len(), if drawn { 1 } else { 0 });
+            assert_eq!(storage.retained.len(), if frame == 0 { 2 } else { 0 });
+        }
+    }
+
+    #[test]
+    fn take_line_gizmos_bakes_meshes() {
+        let mut world = World::new();
+        let mut config_store = GizmoConfigStore::default();
+        config_store.register::<DefaultGizmoConfigGroup>();
+        world.insert_resource(config_store);
+        world.init_resource::<GizmoStorage<DefaultGizmoConfigGroup>>();
+
+        let line_gizmos = world.run_system_once(|mut gizmos: Gizmos| {
+            gizmos.line(Vec3::ZERO, Vec3::X, Color::GREEN);
+            gizmos.linestrip([Vec3::ZERO, Vec3::Y, Vec3::ONE], Color::RED);
+            gizmos.linestrip([Vec3::ZERO, Vec3::Z], Color::BLUE);
+            gizmos.take_line_gizmos()
+        });
+
+        // The taken lines aren't rendered as gizmos
+        let storage = world.resource::<GizmoStorage<DefaultGizmoConfigGroup>>();
+        assert!(storage.lines.is_empty());
+
+        let [list, strip] = &line_gizmos[..] else {
+            panic!("expected a line-list and a line-strip gizmo");
+        };
+        assert!(!list.is_strip());
+        assert!(strip.is_strip());
+
+        let list_meshes = list.to_meshes(RenderAssetUsages::default());
+        assert_eq!(list_meshes.len(), 1);
+        assert_eq!(
+            list_meshes[0].primitive_topology(),
+            PrimitiveTopology::LineList
+        );
+        assert_eq!(list_meshes[0].count_vertices(), 2);
+
+        // Each strip becomes its own mesh, without the separating vertices
+        let strip_meshes = strip.to_meshes(RenderAssetUsages::default());
+        let vertex_counts: Vec<_> = strip_meshes
+            .iter()
+            .map(|mesh| {
+                assert_eq!(mesh.primitive_topology(), PrimitiveTopology::LineStrip);
+                mesh.count_vertices()
+            })
+            .collect();
+        assert_eq!(vertex_counts, [3, 2]);
+    }
+}
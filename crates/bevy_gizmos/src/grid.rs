@@ -0,0 +1,722 @@
+//! Additional [`Gizmos`] Functions -- Grids
+//!
+//! Includes the implementation of [`Gizmos::grid`], [`Gizmos::grid_2d`], [`Gizmos::grid_3d`]
+//! and [`Gizmos::polar_grid_2d`], and assorted support items.
+
+use std::f32::consts::TAU;
+
+use crate::{
+    circles::DEFAULT_CIRCLE_SEGMENTS,
+    prelude::{GizmoConfigGroup, Gizmos},
+};
+use bevy_math::{Mat2, Quat, UVec2, UVec3, Vec2, Vec3};
+use bevy_render::color::Color;
+
+impl<'w, 's, T: GizmoConfigGroup> Gizmos<'w, 's, T> {
+    /// Draw a grid in 3D at `position`, made of `cell_count` cells of size `spacing`.
+    ///
+    /// The grid lies in the local XY plane, rotated by `rotation`.
+    ///
+    /// This should be called for each frame the grid needs to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use std::f32::consts::FRAC_PI_2;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.grid(
+    ///         Vec3::ZERO,
+    ///         Quat::from_rotation_x(FRAC_PI_2),
+    ///         UVec2::new(10, 10),
+    ///         Vec2::splat(2.),
+    ///         Color::GRAY,
+    ///     );
+    ///
+    ///     // Grids can highlight every Nth line, color their center lines like the axes,
+    ///     // and fade out their lines with distance.
+    ///     gizmos
+    ///         .grid(
+    ///             Vec3::ZERO,
+    ///             Quat::from_rotation_x(FRAC_PI_2),
+    ///             UVec2::new(100, 100),
+    ///             Vec2::ONE,
+    ///             Color::GRAY,
+    ///         )
+    ///         .major_lines(10, Color::WHITE)
+    ///         .axis_colors(Color::RED, Color::BLUE)
+    ///         .fade(Vec3::new(0., 5., 10.), 10., 20.);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn grid(
+        &mut self,
+        position: Vec3,
+        rotation: Quat,
+        cell_count: UVec2,
+        spacing: Vec2,
+        color: Color,
+    ) -> GridBuilder<'_, 'w, 's, T> {
+        GridBuilder {
+            gizmos: self,
+            position,
+            rotation,
+            settings: GridSettings::new(cell_count, spacing, color),
+        }
+    }
+
+    /// Draw a grid in 2D at `position`, made of `cell_count` cells of size `spacing`.
+    ///
+    /// This should be called for each frame the grid needs to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos
+    ///         .grid_2d(Vec2::ZERO, 0., UVec2::new(16, 9), Vec2::splat(50.), Color::GRAY)
+    ///         .major_lines(4, Color::WHITE)
+    ///         .axis_colors(Color::RED, Color::GREEN);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn grid_2d(
+        &mut self,
+        position: Vec2,
+        rotation: f32,
+        cell_count: UVec2,
+        spacing: Vec2,
+        color: Color,
+    ) -> Grid2dBuilder<'_, 'w, 's, T> {
+        Grid2dBuilder {
+            gizmos: self,
+            position,
+            rotation: Mat2::from_angle(rotation),
+            settings: GridSettings::new(cell_count, spacing, color),
+        }
+    }
+
+    /// Draw a 3D grid at `position`, made of `cell_count` cells of size `spacing` along the local
+    /// X, Y and Z axes, rotated by `rotation`.
+    ///
+    /// This should be called for each frame the grid needs to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos
+    ///         .grid_3d(Vec3::ZERO, Quat::IDENTITY, UVec3::splat(4), Vec3::ONE, Color::GRAY)
+    ///         .major_lines(2, Color::WHITE)
+    ///         .axis_colors(Color::RED, Color::GREEN, Color::BLUE);
+    ///
+    ///     // Skipping the lines along an axis leaves the planes of cells perpendicular to it.
+    ///     gizmos
+    ///         .grid_3d(Vec3::ZERO, Quat::IDENTITY, UVec3::new(8, 2, 8), Vec3::ONE, Color::GRAY)
+    ///         .skip_x(true)
+    ///         .skip_z(true);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn grid_3d(
+        &mut self,
+        position: Vec3,
+        rotation: Quat,
+        cell_count: UVec3,
+        spacing: Vec3,
+        color: Color,
+    ) -> Grid3dBuilder<'_, 'w, 's, T> {
+        Grid3dBuilder {
+            gizmos: self,
+            position,
+            rotation,
+            cell_count,
+            spacing,
+            colors: GridColors::new(color),
+            axis_colors: None,
+            skip: [false; 3],
+        }
+    }
+
+    /// Draw a polar grid in 2D centered at `position`, made of `ring_count` rings spaced by
+    /// `ring_spacing`, and `spoke_count` spokes going from the center to the outer ring.
+    ///
+    /// The first spoke points along the X axis rotated by `rotation`.
+    ///
+    /// This should be called for each frame the grid needs to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos
+    ///         .polar_grid_2d(Vec2::ZERO, 0., 10, 20., 24, Color::GRAY)
+    ///         .major_lines(5, Color::WHITE)
+    ///         .segments(64);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn polar_grid_2d(
+        &mut self,
+        position: Vec2,
+        rotation: f32,
+        ring_count: u32,
+        ring_spacing: f32,
+        spoke_count: u32,
+        color: Color,
+    ) -> PolarGrid2dBuilder<'_, 'w, 's, T> {
+        PolarGrid2dBuilder {
+            gizmos: self,
+            position,
+            rotation,
+            ring_count,
+            ring_spacing,
+            spoke_count,
+            colors: GridColors::new(color),
+            segments: DEFAULT_CIRCLE_SEGMENTS,
+        }
+    }
+}
+
+struct GridFade {
+    origin: Vec3,
+    start: f32,
+    end: f32,
+}
+
+/// The colors of the lines of a grid.
+struct GridColors {
+    color: Color,
+    major_every: u32,
+    major_color: Color,
+}
+
+impl GridColors {
+    fn new(color: Color) -> Self {
+        Self {
+            color,
+            major_every: 0,
+            major_color: color,
+        }
+    }
+
+    /// Returns `true` if the `index`th of the `count + 1` lines crossing an axis is the center
+    /// one.
+    fn is_center(index: u32, count: u32) -> bool {
+        2 * index == count
+    }
+
+    /// Returns `true` if the `index`th of the `count + 1` lines crossing an axis is a major one.
+    ///
+    /// Major lines are counted from the center line if there is one, so that they line up with
+    /// the axes.
+    fn is_major(&self, index: u32, count: u32) -> bool {
+        let offset = 2 * index as i64 - count as i64;
+        let major_index = if offset % 2 == 0 {
+            offset / 2
+        } else {
+            index as i64
+        };
+        self.major_every > 1 && major_index % self.major_every as i64 == 0
+    }
+
+    /// The color of the `index`th line of a set of lines counted from the first one, with every
+    /// `major_every`th line being a major one.
+    fn nth_color(&self, index: u32) -> Color {
+        if self.major_every > 1 && index % self.major_every == 0 {
+            self.major_color
+        } else {
+            self.color
+        }
+    }
+
+    /// The color of the `index`th of the `count + 1` lines crossing an axis, and the factor by
+    /// which its fade distances are scaled.
+    fn line_style(&self, index: u32, count: u32, axis_color: Option<Color>) -> (Color, f32) {
+        if let Some(axis_color) = axis_color.filter(|_| Self::is_center(index, count)) {
+            (axis_color, self.major_every.max(1) as f32)
+        } else if self.is_major(index, count) {
+            (self.major_color, self.major_every as f32)
+        } else {
+            (self.color, 1.)
+        }
+    }
+}
+
+struct GridSettings {
+    cell_count: UVec2,
+    spacing: Vec2,
+    colors: GridColors,
+    axis_colors: Option<(Color, Color)>,
+    fade: Option<GridFade>,
+}
+
+impl GridSettings {
+    fn new(cell_count: UVec2, spacing: Vec2, color: Color) -> Self {
+        Self {
+            cell_count,
+            spacing,
+            colors: GridColors::new(color),
+            axis_colors: None,
+            fade: None,
+        }
+    }
+
+    /// Draws the grid, mapping its local positions to the world with `to_world`.
+    fn draw<T: GizmoConfigGroup>(
+        &self,
+        gizmos: &mut Gizmos<'_, '_, T>,
+        to_world: impl Fn(Vec2) -> Vec3,
+    ) {
+        let half_size = self.cell_count.as_vec2() * self.spacing / 2.;
+        let (x_axis_color, y_axis_color) = self.axis_colors.unzip();
+
+        // Lines along the Y axis, then along the X axis. The center line along the Y axis is the
+        // Y axis, so it takes the Y axis color.
+        for (axis, line_count, segment_count, axis_color) in [
+            (0, self.cell_count.x, self.cell_count.y, y_axis_color),
+            (1, self.cell_count.y, self.cell_count.x, x_axis_color),
+        ] {
+            for index in 0..=line_count {
+                let (color, fade_scale) = self.colors.line_style(index, line_count, axis_color);
+                let point = |segment: u32| {
+                    let cell = if axis == 0 {
+                        Vec2::new(index as f32, segment as f32)
+                    } else {
+                        Vec2::new(segment as f32, index as f32)
+                    };
+                    to_world(cell * self.spacing - half_size)
+                };
+
+                let Some(fade) = &self.fade else {
+                    gizmos.line(point(0), point(segment_count), color);
+                    continue;
+                };
+
+                // Split the line at every cell so its color can fade along it.
+                let (start, end) = (fade.start * fade_scale, fade.end * fade_scale);
+                gizmos.linestrip_gradient((0..=segment_count).map(|segment| {
+                    let position = point(segment);
+                    let distance = position.distance(fade.origin);
+                    let alpha = 1. - ((distance - start) / (end - start).max(f32::EPSILON));
+                    (position, color.with_a(color.a() * alpha.clamp(0., 1.)))
+                }));
+            }
+        }
+    }
+}
+
+/// A builder returned by [`Gizmos::grid`].
+pub struct GridBuilder<'a, 'w, 's, T: GizmoConfigGroup> {
+    gizmos: &'a mut Gizmos<'w, 's, T>,
+    position: Vec3,
+    rotation: Quat,
+    settings: GridSettings,
+}
+
+impl<T: GizmoConfigGroup> GridBuilder<'_, '_, '_, T> {
+    /// Draw every `every`th line, counted from the center, with `color`.
+    ///
+    /// When fading, major lines fade `every` times farther away than the others.
+    pub fn major_lines(mut self, every: u32, color: Color) -> Self {
+        self.settings.colors.major_every = every;
+        self.settings.colors.major_color = color;
+        self
+    }
+
+    /// Draw the center lines along the local X and Y axes with `x_color` and `y_color`.
+    ///
+    /// This only affects grids with an even number of cells along the other axis.
+    pub fn axis_colors(mut self, x_color: Color, y_color: Color) -> Self {
+        self.settings.axis_colors = Some((x_color, y_color));
+        self
+    }
+
+    /// Fade the lines out from `start` to `end` distance from `origin`, usually the camera.
+    pub fn fade(mut self, origin: Vec3, start: f32, end: f32) -> Self {
+        self.settings.fade = Some(GridFade { origin, start, end });
+        self
+    }
+}
+
+impl<T: GizmoConfigGroup> Drop for GridBuilder<'_, '_, '_, T> {
+    fn drop(&mut self) {
+        if !self.gizmos.enabled {
+            return;
+        }
+        let (position, rotation) = (self.position, self.rotation);
+        self.settings
+            .draw(self.gizmos, |point| position + rotation * point.extend(0.));
+    }
+}
+
+/// A builder returned by [`Gizmos::grid_2d`].
+pub struct Grid2dBuilder<'a, 'w, 's, T: GizmoConfigGroup> {
+    gizmos: &'a mut Gizmos<'w, 's, T>,
+    position: Vec2,
+    rotation: Mat2,
+    settings: GridSettings,
+}
+
+impl<T: GizmoConfigGroup> Grid2dBuilder<'_, '_, '_, T> {
+    /// Draw every `every`th line, counted from the center, with `color`.
+    ///
+    /// When fading, major lines fade `every` times farther away than the others.
+    pub fn major_lines(mut self, every: u32, color: Color) -> Self {
+        self.settings.colors.major_every = every;
+        self.settings.colors.major_color = color;
+        self
+    }
+
+    /// Draw the center lines along the local X and Y axes with `x_color` and `y_color`.
+    ///
+    /// This only affects grids with an even number of cells along the other axis.
+    pub fn axis_colors(mut self, x_color: Color, y_color: Color) -> Self {
+        self.settings.axis_colors = Some((x_color, y_color));
+        self
+    }
+
+    /// Fade the lines out from `start` to `end` distance from `origin`, usually the camera.
+    pub fn fade(mut self, origin: Vec2, start: f32, end: f32) -> Self {
+        self.settings.fade = Some(GridFade {
+            origin: origin.extend(0.),
+            start,
+            end,
+        });
+        self
+    }
+}
+
+impl<T: GizmoConfigGroup> Drop for Grid2dBuilder<'_, '_, '_, T> {
+    fn drop(&mut self) {
+        if !self.gizmos.enabled {
+            return;
+        }
+        let (position, rotation) = (self.position, self.rotation);
+        self.settings.draw(self.gizmos, |point| {
+            (position + rotation * point).extend(0.)
+        });
+    }
+}
+
+/// A builder returned by [`Gizmos::grid_3d`].
+pub struct Grid3dBuilder<'a, 'w, 's, T: GizmoConfigGroup> {
+    gizmos: &'a mut Gizmos<'w, 's, T>,
+    position: Vec3,
+    rotation: Quat,
+    cell_count: UVec3,
+    spacing: Vec3,
+    colors: GridColors,
+    axis_colors: Option<[Color; 3]>,
+    skip: [bool; 3],
+}
+
+impl<T: GizmoConfigGroup> Grid3dBuilder<'_, '_, '_, T> {
+    /// Draw the lines that lie on every `every`th plane along both of the other axes, counted
+    /// from the center, with `color`.
+    pub fn major_lines(mut self, every: u32, color: Color) -> Self {
+        self.colors.major_every = every;
+        self.colors.major_color = color;
+        self
+    }
+
+    /// Draw the center lines along the local X, Y and Z axes with `x_color`, `y_color` and
+    /// `z_color`.
+    ///
+    /// This only affects grids with an even number of cells along the other axes.
+    pub fn axis_colors(mut self, x_color: Color, y_color: Color, z_color: Color) -> Self {
+        self.axis_colors = Some([x_color, y_color, z_color]);
+        self
+    }
+
+    /// Skip the lines along the local X axis, leaving the YZ planes of the grid.
+    pub fn skip_x(mut self, skip: bool) -> Self {
+        self.skip[0] = skip;
+        self
+    }
+
+    /// Skip the lines along the local Y axis, leaving the XZ planes of the grid.
+    pub fn skip_y(mut self, skip: bool) -> Self {
+        self.skip[1] = skip;
+        self
+    }
+
+    /// Skip the lines along the local Z axis, leaving the XY planes of the grid.
+    pub fn skip_z(mut self, skip: bool) -> Self {
+        self.skip[2] = skip;
+        self
+    }
+}
+
+impl<T: GizmoConfigGroup> Drop for Grid3dBuilder<'_, '_, '_, T> {
+    fn drop(&mut self) {
+        if !self.gizmos.enabled {
+            return;
+        }
+        let cell_count = self.cell_count.to_array();
+        let half_size = self.cell_count.as_vec3() * self.spacing / 2.;
+        let (position, rotation, spacing) = (self.position, self.rotation, self.spacing);
+        let to_world = |cell: Vec3| position + rotation * (cell * spacing - half_size);
+
+        for axis in (0..3).filter(|&axis| !self.skip[axis]) {
+            let (b, c) = ((axis + 1) % 3, (axis + 2) % 3);
+            for i in 0..=cell_count[b] {
+                for j in 0..=cell_count[c] {
+                    // Lines crossing the center of both other axes lie on this axis, and lines
+                    // crossing major lines of both other axes are major.
+                    let is_center = GridColors::is_center(i, cell_count[b])
+                        && GridColors::is_center(j, cell_count[c]);
+                    let color = if let Some(axis_colors) = self.axis_colors.filter(|_| is_center) {
+                        axis_colors[axis]
+                    } else if self.colors.is_major(i, cell_count[b])
+                        && self.colors.is_major(j, cell_count[c])
+                    {
+                        self.colors.major_color
+                    } else {
+                        self.colors.color
+                    };
+
+                    let mut start = Vec3::ZERO;
+                    start[b] = i as f32;
+                    start[c] = j as f32;
+                    let mut end = start;
+                    end[axis] = cell_count[axis] as f32;
+                    self.gizmos.line(to_world(start), to_world(end), color);
+                }
+            }
+        }
+    }
+}
+
+/// A builder returned by [`Gizmos::polar_grid_2d`].
+pub struct PolarGrid2dBuilder<'a, 'w, 's, T: GizmoConfigGroup> {
+    gizmos: &'a mut Gizmos<'w, 's, T>,
+    position: Vec2,
+    rotation: f32,
+    ring_count: u32,
+    ring_spacing: f32,
+    spoke_count: u32,
+    colors: GridColors,
+    segments: usize,
+}
+
+impl<T: GizmoConfigGroup> PolarGrid2dBuilder<'_, '_, '_, T> {
+    /// Draw every `every`th ring, counted from the center, and every `every`th spoke, counted
+    /// from the first one, with `color`.
+    pub fn major_lines(mut self, every: u32, color: Color) -> Self {
+        self.colors.major_every = every;
+        self.colors.major_color = color;
+        self
+    }
+
+    /// Set the number of line-segments of each ring.
+    pub fn segments(mut self, segments: usize) -> Self {
+        self.segments = segments;
+        self
+    }
+}
+
+impl<T: GizmoConfigGroup> Drop for PolarGrid2dBuilder<'_, '_, '_, T> {
+    fn drop(&mut self) {
+        if !self.gizmos.enabled {
+            return;
+        }
+        for ring in 1..=self.ring_count {
+            let radius = ring as f32 * self.ring_spacing;
+            self.gizmos
+                .circle_2d(self.position, radius, self.colors.nth_color(ring))
+                .segments(self.segments);
+        }
+
+        let radius = self.ring_count as f32 * self.ring_spacing;
+        for spoke in 0..self.spoke_count {
+            let angle = self.rotation + spoke as f32 / self.spoke_count as f32 * TAU;
+            let end = self.position + Vec2::from_angle(angle) * radius;
+            let color = self.colors.nth_color(spoke);
+            self.gizmos.line_2d(self.position, end, color);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{system::RunSystemOnce, world::World};
+
+    use super::*;
+    use crate::{
+        config::{DefaultGizmoConfigGroup, GizmoConfigStore},
+        gizmos::GizmoStorage,
+    };
+
+    fn world() -> World {
+        let mut world = World::new();
+        let mut config_store = GizmoConfigStore::default();
+        config_store.register::<DefaultGizmoConfigGroup>();
+        world.insert_resource(config_store);
+        world.init_resource::<GizmoStorage<DefaultGizmoConfigGroup>>();
+        world
+    }
+
+    #[test]
+    fn grid_major_and_axis_lines() {
+        let mut world = world();
+        world.run_system_once(|mut gizmos: Gizmos| {
+            gizmos
+                .grid_2d(Vec2::ZERO, 0., UVec2::splat(4), Vec2::ONE, Color::GRAY)
+                .major_lines(2, Color::WHITE)
+                .axis_colors(Color::RED, Color::GREEN);
+        });
+
+        let storage = world.resource::<GizmoStorage<DefaultGizmoConfigGroup>>();
+        let line_colors: Vec<_> = storage.lines.list_colors.iter().step_by(2).collect();
+        let [gray, white, red, green] =
+            [Color::GRAY, Color::WHITE, Color::RED, Color::GREEN].map(|c| c.as_linear_rgba_f32());
+        // Lines along the Y axis, then along the X axis, with major lines counted from the center
+        assert_eq!(
+            line_colors,
+            [&white, &gray, &green, &gray, &white, &white, &gray, &red, &gray, &white]
+        );
+    }
+
+    #[test]
+    fn grid_fades_with_distance() {
+        let mut world = world();
+        world.run_system_once(|mut gizmos: Gizmos| {
+            gizmos
+                .grid_2d(Vec2::ZERO, 0., UVec2::splat(2), Vec2::ONE, Color::GRAY)
+                .fade(Vec2::ZERO, 0.5, 1.);
+        });
+
+        // Each line is split at every cell, and fades out from the origin.
+        let storage = world.resource::<GizmoStorage<DefaultGizmoConfigGroup>>();
+        assert!(storage.lines.list_positions.is_empty());
+        let alphas: Vec<_> = storage
+            .lines
+            .strip_colors
+            .chunks(4)
+            .map(|strip| [strip[0][3], strip[1][3], strip[2][3]])
+            .collect();
+        assert_eq!(alphas, [[0., 0., 0.], [0., 1., 0.], [0., 0., 0.]].repeat(2));
+    }
+
+    #[test]
+    fn grid_3d_axis_lines() {
+        let mut world = world();
+        world.run_system_once(|mut gizmos: Gizmos| {
+            gizmos
+                .grid_3d(
+                    Vec3::ZERO,
+                    Quat::IDENTITY,
+                    UVec3::splat(2),
+                    Vec3::ONE,
+                    Color::GRAY,
+                )
+                .axis_colors(Color::RED, Color::GREEN, Color::BLUE);
+        });
+
+        // 3 by 3 lines along each axis, the center ones crossing the origin
+        let storage = world.resource::<GizmoStorage<DefaultGizmoConfigGroup>>();
+        assert_eq!(storage.lines.list_positions.len(), 2 * 27);
+        let axis_lines: Vec<_> = storage
+            .lines
+            .list_positions
+            .chunks(2)
+            .zip(storage.lines.list_colors.iter().step_by(2))
+            .filter(|(_, color)| **color != Color::GRAY.as_linear_rgba_f32())
+            .map(|(line, color)| (line[0], line[1], *color))
+            .collect();
+        assert_eq!(
+            axis_lines,
+            [
+                ([-1., 0., 0.], [1., 0., 0.], Color::RED.as_linear_rgba_f32()),
+                (
+                    [0., -1., 0.],
+                    [0., 1., 0.],
+                    Color::GREEN.as_linear_rgba_f32()
+                ),
+                (
+                    [0., 0., -1.],
+                    [0., 0., 1.],
+                    Color::BLUE.as_linear_rgba_f32()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn grid_3d_skipped_axes() {
+        let mut world = world();
+        world.run_system_once(|mut gizmos: Gizmos| {
+            gizmos
+                .grid_3d(
+                    Vec3::ZERO,
+                    Quat::IDENTITY,
+                    UVec3::splat(2),
+                    Vec3::ONE,
+                    Color::GRAY,
+                )
+                .skip_y(true)
+                .skip_z(true);
+        });
+
+        // Only the lines along the X axis are left
+        let storage = world.resource::<GizmoStorage<DefaultGizmoConfigGroup>>();
+        assert_eq!(storage.lines.list_positions.len(), 2 * 9);
+        assert!(storage
+            .lines
+            .list_positions
+            .chunks(2)
+            .all(|line| line[0][0] == -1. && line[1][0] == 1. && line[0][1..] == line[1][1..]));
+    }
+
+    #[test]
+    fn polar_grid_rings_and_spokes() {
+        let mut world = world();
+        world.run_system_once(|mut gizmos: Gizmos| {
+            gizmos
+                .polar_grid_2d(Vec2::ZERO, 0., 2, 1., 4, Color::GRAY)
+                .major_lines(2, Color::WHITE)
+                .segments(8);
+        });
+
+        let storage = world.resource::<GizmoStorage<DefaultGizmoConfigGroup>>();
+        let [gray, white] = [Color::GRAY, Color::WHITE].map(|c| c.as_linear_rgba_f32());
+
+        // Each ring is a strip of 9 points, every other one being major
+        let rings: Vec<_> = storage
+            .lines
+            .strip_positions
+            .chunks(10)
+            .zip(storage.lines.strip_colors.chunks(10))
+            .collect();
+        assert_eq!(rings.len(), 2);
+        for ((positions, colors), (radius, color)) in
+            rings.into_iter().zip([(1., gray), (2., white)])
+        {
+            assert!(positions[..9]
+                .iter()
+                .all(|p| (Vec3::from(*p).length() - radius).abs() < 1e-5));
+            assert_eq!(colors[0], color);
+        }
+
+        // The spokes start from the first one along the X axis, and reach the outer ring
+        let spokes = &storage.lines.list_positions;
+        assert_eq!(spokes.len(), 2 * 4);
+        assert_eq!(spokes[0], [0., 0., 0.]);
+        assert_eq!(spokes[1], [2., 0., 0.]);
+        assert!(Vec3::from(spokes[3]).abs_diff_eq(Vec3::new(0., 2., 0.), 1e-5));
+        let spoke_colors: Vec<_> = storage.lines.list_colors.iter().step_by(2).collect();
+        assert_eq!(spoke_colors, [&white, &gray, &white, &gray]);
+    }
+}
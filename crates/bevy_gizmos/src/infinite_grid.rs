@@ -0,0 +1,385 @@
+//! An infinite grid, drawn on a plane by a full-screen pass of a 3D camera.
+//!
+//! Unlike [`Gizmos::grid`](crate::gizmos::Gizmos::grid), which draws a finite number of lines,
+//! the [`InfiniteGrid`] is shaded per pixel, so it extends to the horizon at a constant cost and
+//! stays anti-aliased at any distance.
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, Handle};
+use bevy_core_pipeline::{
+    core_3d::{
+        graph::{Labels3d, SubGraph3d},
+        Camera3d,
+    },
+    fullscreen_vertex_shader::fullscreen_shader_vertex_state,
+    prepass::ViewPrepassTextures,
+};
+use bevy_ecs::{
+    prelude::{Component, Entity},
+    query::{QueryItem, With},
+    reflect::ReflectComponent,
+    schedule::IntoSystemConfigs,
+    system::{Commands, Query, Res, ResMut, Resource},
+    world::{FromWorld, World},
+};
+use bevy_math::{Quat, Vec3, Vec4};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    color::Color,
+    extract_component::{
+        ComponentUniforms, DynamicUniformIndex, ExtractComponent, ExtractComponentPlugin,
+        UniformComponentPlugin,
+    },
+    render_graph::{
+        NodeRunError, RenderGraphApp, RenderGraphContext, RenderLabel, ViewNode, ViewNodeRunner,
+    },
+    render_resource::{
+        binding_types::{texture_depth_2d, texture_depth_2d_multisampled, uniform_buffer},
+        *,
+    },
+    renderer::{RenderContext, RenderDevice},
+    texture::BevyDefault,
+    view::{ExtractedView, Msaa, ViewTarget, ViewUniform, ViewUniformOffset, ViewUniforms},
+    Render, RenderApp, RenderSet,
+};
+
+const INFINITE_GRID_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(8315436710223390741);
+
+/// A [`Plugin`] that draws the [`InfiniteGrid`] of 3D cameras.
+pub struct InfiniteGridPlugin;
+
+impl Plugin for InfiniteGridPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            INFINITE_GRID_SHADER_HANDLE,
+            "infinite_grid.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.register_type::<InfiniteGrid>().add_plugins((
+            ExtractComponentPlugin::<InfiniteGrid>::default(),
+            UniformComponentPlugin::<InfiniteGridUniform>::default(),
+        ));
+
+        let Ok(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .init_resource::<SpecializedRenderPipelines<InfiniteGridPipeline>>()
+            .add_systems(
+                Render,
+                (
+                    prepare_infinite_grid_pipelines.in_set(RenderSet::Prepare),
+                    prepare_infinite_grid_bind_groups.in_set(RenderSet::PrepareBindGroups),
+                ),
+            )
+            .add_render_graph_node::<ViewNodeRunner<InfiniteGridNode>>(
+                SubGraph3d,
+                InfiniteGridLabel,
+            )
+            .add_render_graph_edges(
+                SubGraph3d,
+                (
+                    Labels3d::MainTransparentPass,
+                    InfiniteGridLabel,
+                    Labels3d::EndMainPass,
+                ),
+            );
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Ok(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app.init_resource::<InfiniteGridPipeline>();
+    }
+}
+
+/// The render graph node drawing the [`InfiniteGrid`], after the main transparent pass.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, RenderLabel)]
+pub struct InfiniteGridLabel;
+
+/// Draws an infinite grid on a plane, with major lines and axis-colored center lines.
+///
+/// Add this component to a 3D camera, along with a
+/// [`DepthPrepass`](bevy_core_pipeline::prepass::DepthPrepass), whose depth hides the grid behind
+/// the scene geometry. The grid isn't drawn for cameras without a depth prepass.
+///
+/// The grid lies in the XZ plane of its [`origin`](Self::origin) and
+/// [`rotation`](Self::rotation). Minor lines fade out where they would get too dense to tell apart,
+/// and the whole grid fades out towards [`fade_distance`](Self::fade_distance).
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component, Default)]
+pub struct InfiniteGrid {
+    /// A point on the grid, where its center lines cross.
+    pub origin: Vec3,
+    /// The rotation of the grid, which lies in the rotated XZ plane.
+    pub rotation: Quat,
+    /// The size of the cells between minor lines.
+    pub spacing: f32,
+    /// The number of cells between major lines.
+    pub major_every: u32,
+    /// The color of the minor lines.
+    pub minor_color: Color,
+    /// The color of the major lines.
+    pub major_color: Color,
+    /// The color of the center line along the X axis.
+    pub x_axis_color: Color,
+    /// The color of the center line along the Z axis.
+    pub z_axis_color: Color,
+    /// The distance from the camera at which the grid has completely faded out.
+    pub fade_distance: f32,
+}
+
+impl Default for InfiniteGrid {
+    fn default() -> Self {
+        Self {
+            origin: Vec3::ZERO,
+            rotation: Quat::IDENTITY,
+            spacing: 1.0,
+            major_every: 10,
+            minor_color: Color::rgba(0.5, 0.5, 0.5, 0.5),
+            major_color: Color::rgba(0.75, 0.75, 0.75, 0.75),
+            x_axis_color: Color::rgb(1.0, 0.2, 0.2),
+            z_axis_color: Color::rgb(0.2, 0.2, 1.0),
+            fade_distance: 200.0,
+        }
+    }
+}
+
+impl ExtractComponent for InfiniteGrid {
+    type QueryData = &'static Self;
+    type QueryFilter = With<Camera3d>;
+    type Out = InfiniteGridUniform;
+
+    fn extract_component(grid: QueryItem<'_, Self::QueryData>) -> Option<Self::Out> {
+        Some(InfiniteGridUniform {
+            x_axis: grid.rotation * Vec3::X,
+            normal: grid.rotation * Vec3::Y,
+            z_axis: grid.rotation * Vec3::Z,
+            origin: grid.origin,
+            spacing: grid.spacing,
+            major_spacing: grid.spacing * grid.major_every.max(1) as f32,
+            fade_distance: grid.fade_distance,
+            minor_color: Vec4::from(grid.minor_color.as_linear_rgba_f32()),
+            major_color: Vec4::from(grid.major_color.as_linear_rgba_f32()),
+            x_axis_color: Vec4::from(grid.x_axis_color.as_linear_rgba_f32()),
+            z_axis_color: Vec4::from(grid.z_axis_color.as_linear_rgba_f32()),
+        })
+    }
+}
+
+/// The GPU representation of an [`InfiniteGrid`].
+#[derive(Component, ShaderType, Clone)]
+pub struct InfiniteGridUniform {
+    x_axis: Vec3,
+    normal: Vec3,
+    z_axis: Vec3,
+    origin: Vec3,
+    spacing: f32,
+    major_spacing: f32,
+    fade_distance: f32,
+    minor_color: Vec4,
+    major_color: Vec4,
+    x_axis_color: Vec4,
+    z_axis_color: Vec4,
+}
+
+#[derive(Resource)]
+struct InfiniteGridPipeline {
+    layout: BindGroupLayout,
+    layout_multisampled: BindGroupLayout,
+}
+
+impl FromWorld for InfiniteGridPipeline {
+    fn from_world(render_world: &mut World) -> Self {
+        let render_device = render_world.resource::<RenderDevice>();
+        let create_layout = |label: &'static str, depth_texture: BindGroupLayoutEntryBuilder| {
+            render_device.create_bind_group_layout(
+                label,
+                &BindGroupLayoutEntries::sequential(
+                    ShaderStages::FRAGMENT,
+                    (
+                        uniform_buffer::<ViewUniform>(true),
+                        uniform_buffer::<InfiniteGridUniform>(true),
+                        depth_texture,
+                    ),
+                ),
+            )
+        };
+
+        InfiniteGridPipeline {
+            layout: create_layout("infinite_grid_bind_group_layout", texture_depth_2d()),
+            layout_multisampled: create_layout(
+                "infinite_grid_multisampled_bind_group_layout",
+                texture_depth_2d_multisampled(),
+            ),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+struct InfiniteGridPipelineKey {
+    hdr: bool,
+    samples: u32,
+}
+
+impl SpecializedRenderPipeline for InfiniteGridPipeline {
+    type Key = InfiniteGridPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let (layout, shader_defs) = if key.samples > 1 {
+            (&self.layout_multisampled, vec!["MULTISAMPLED".into()])
+        } else {
+            (&self.layout, vec![])
+        };
+
+        RenderPipelineDescriptor {
+            label: Some("infinite_grid_pipeline".into()),
+            layout: vec![layout.clone()],
+            vertex: fullscreen_shader_vertex_state(),
+            fragment: Some(FragmentState {
+                shader: INFINITE_GRID_SHADER_HANDLE,
+                shader_defs,
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format: if key.hdr {
+                        ViewTarget::TEXTURE_FORMAT_HDR
+                    } else {
+                        TextureFormat::bevy_default()
+                    },
+                    blend: Some(BlendState::ALPHA_BLENDING),
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState {
+                count: key.samples,
+                ..Default::default()
+            },
+            push_constant_ranges: Vec::new(),
+        }
+    }
+}
+
+#[derive(Component)]
+struct InfiniteGridPipelineId(CachedRenderPipelineId);
+
+fn prepare_infinite_grid_pipelines(
+    mut commands: Commands,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<InfiniteGridPipeline>>,
+    pipeline: Res<InfiniteGridPipeline>,
+    msaa: Res<Msaa>,
+    views: Query<(Entity, &ExtractedView), With<InfiniteGridUniform>>,
+) {
+    for (entity, view) in &views {
+        let pipeline_id = pipelines.specialize(
+            &pipeline_cache,
+            &pipeline,
+            InfiniteGridPipelineKey {
+                hdr: view.hdr,
+                samples: msaa.samples(),
+            },
+        );
+
+        commands
+            .entity(entity)
+            .insert(InfiniteGridPipelineId(pipeline_id));
+    }
+}
+
+#[derive(Component)]
+struct InfiniteGridBindGroup(BindGroup);
+
+fn prepare_infinite_grid_bind_groups(
+    mut commands: Commands,
+    pipeline: Res<InfiniteGridPipeline>,
+    render_device: Res<RenderDevice>,
+    msaa: Res<Msaa>,
+    view_uniforms: Res<ViewUniforms>,
+    grid_uniforms: Res<ComponentUniforms<InfiniteGridUniform>>,
+    views: Query<(Entity, &ViewPrepassTextures), With<InfiniteGridUniform>>,
+) {
+    let (Some(view_uniforms), Some(grid_uniforms)) =
+        (view_uniforms.uniforms.binding(), grid_uniforms.binding())
+    else {
+        return;
+    };
+
+    let layout = if msaa.samples() > 1 {
+        &pipeline.layout_multisampled
+    } else {
+        &pipeline.layout
+    };
+
+    for (entity, prepass_textures) in &views {
+        let Some(depth_view) = prepass_textures.depth_view() else {
+            continue;
+        };
+
+        let bind_group = render_device.create_bind_group(
+            "infinite_grid_bind_group",
+            layout,
+            &BindGroupEntries::sequential((
+                view_uniforms.clone(),
+                grid_uniforms.clone(),
+                depth_view,
+            )),
+        );
+
+        commands
+            .entity(entity)
+            .insert(InfiniteGridBindGroup(bind_group));
+    }
+}
+
+#[derive(Default)]
+struct InfiniteGridNode;
+
+impl ViewNode for InfiniteGridNode {
+    type ViewQuery = (
+        &'static ViewTarget,
+        &'static ViewUniformOffset,
+        &'static DynamicUniformIndex<InfiniteGridUniform>,
+        &'static InfiniteGridPipelineId,
+        &'static InfiniteGridBindGroup,
+    );
+
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        (target, view_uniform_offset, grid_uniform_index, pipeline_id, bind_group): QueryItem<
+            Self::ViewQuery,
+        >,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let Some(pipeline) = pipeline_cache.get_render_pipeline(pipeline_id.0) else {
+            return Ok(());
+        };
+
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("infinite_grid_pass"),
+            color_attachments: &[Some(target.get_color_attachment())],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        render_pass.set_render_pipeline(pipeline);
+        render_pass.set_bind_group(
+            0,
+            &bind_group.0,
+            &[view_uniform_offset.offset, grid_uniform_index.index()],
+        );
+        render_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
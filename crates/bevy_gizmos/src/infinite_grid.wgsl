@@ -0,0 +1,91 @@
+// Draws an infinite grid on a plane with a full-screen triangle, intersecting each view ray with
+// the plane and testing the hit against the prepass depth.
+
+#import bevy_render::view::View
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+struct InfiniteGrid {
+    x_axis: vec3<f32>,
+    normal: vec3<f32>,
+    z_axis: vec3<f32>,
+    origin: vec3<f32>,
+    spacing: f32,
+    major_spacing: f32,
+    fade_distance: f32,
+    minor_color: vec4<f32>,
+    major_color: vec4<f32>,
+    x_axis_color: vec4<f32>,
+    z_axis_color: vec4<f32>,
+}
+
+@group(0) @binding(0) var<uniform> view: View;
+@group(0) @binding(1) var<uniform> grid: InfiniteGrid;
+#ifdef MULTISAMPLED
+@group(0) @binding(2) var depth_prepass_texture: texture_depth_multisampled_2d;
+#else
+@group(0) @binding(2) var depth_prepass_texture: texture_depth_2d;
+#endif
+
+// The coverage of the lines of a grid of `spacing` at `coordinates`, anti-aliased over a pixel.
+// Also returns how much of a pixel a cell covers, used to fade out lines that get too dense.
+fn grid_lines(coordinates: vec2<f32>, spacing: f32) -> vec2<f32> {
+    let cell = coordinates / spacing;
+    let derivative = max(fwidth(cell), vec2(0.0001));
+    let distance_in_pixels = abs(fract(cell - 0.5) - 0.5) / derivative;
+    let coverage = 1.0 - min(min(distance_in_pixels.x, distance_in_pixels.y), 1.0);
+    return vec2(coverage, max(derivative.x, derivative.y));
+}
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    // Reconstruct the view ray through this pixel. Depth is reversed, so 1 is the near plane.
+    let ndc = vec2(in.uv.x * 2.0 - 1.0, 1.0 - in.uv.y * 2.0);
+    let near = view.inverse_view_proj * vec4(ndc, 1.0, 1.0);
+    let middle = view.inverse_view_proj * vec4(ndc, 0.5, 1.0);
+    let ray_origin = near.xyz / near.w;
+    let ray_direction = normalize(middle.xyz / middle.w - ray_origin);
+
+    let denominator = dot(ray_direction, grid.normal);
+    let distance = dot(grid.origin - ray_origin, grid.normal) / select(denominator, 0.0001, abs(denominator) < 0.0001);
+    let hit = ray_origin + ray_direction * distance;
+
+    let local = hit - grid.origin;
+    let coordinates = vec2(dot(local, grid.x_axis), dot(local, grid.z_axis));
+
+    // Derivatives are taken before anything is discarded, in uniform control flow.
+    let minor = grid_lines(coordinates, grid.spacing);
+    let major = grid_lines(coordinates, grid.major_spacing);
+    let axis_width = fwidth(coordinates);
+
+    // Minor lines fade out as their cells shrink to a few pixels, leaving only the major lines.
+    let minor_lod_fade = 1.0 - smoothstep(0.1, 0.3, minor.y);
+    let major_lod_fade = 1.0 - smoothstep(0.1, 0.3, major.y);
+
+    var color = vec4(grid.minor_color.rgb, grid.minor_color.a * minor.x * minor_lod_fade);
+    color = mix(color, grid.major_color, major.x * major_lod_fade);
+
+    // The center lines along the grid's X and Z axes.
+    let z_axis_coverage = 1.0 - min(abs(coordinates.x) / max(axis_width.x, 0.0001), 1.0);
+    let x_axis_coverage = 1.0 - min(abs(coordinates.y) / max(axis_width.y, 0.0001), 1.0);
+    color = mix(color, grid.z_axis_color, z_axis_coverage);
+    color = mix(color, grid.x_axis_color, x_axis_coverage);
+
+    // Fade the grid out with distance from the camera, and at grazing angles.
+    let camera_distance = length(hit - view.world_position);
+    color.a *= 1.0 - smoothstep(grid.fade_distance * 0.5, grid.fade_distance, camera_distance);
+    color.a *= smoothstep(0.0, 0.1, abs(denominator));
+
+    if (distance <= 0.0) {
+        discard;
+    }
+
+    // Hide the grid behind the scene geometry.
+    let clip_position = view.view_proj * vec4(hit, 1.0);
+    let grid_depth = clip_position.z / clip_position.w;
+    let scene_depth = textureLoad(depth_prepass_texture, vec2<i32>(in.position.xy), 0);
+    if (grid_depth < scene_depth) {
+        discard;
+    }
+
+    return color;
+}
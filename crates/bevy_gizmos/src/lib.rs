@@ -1,5 +1,8 @@
 //! This crate adds an immediate mode drawing api to Bevy for visual debugging.
 //!
+//! Static lines can also be built once as a [`GizmoAsset`](crate::retained::GizmoAsset) and
+//! drawn by entities with a [`Gizmo`](crate::retained::Gizmo) component.
+//!
 //! # Example
 //! ```
 //! # use bevy_gizmos::prelude::*;
@@ -27,11 +30,31 @@ pub enum GizmoRenderSystem {
 pub mod aabb;
 pub mod arcs;
 pub mod arrows;
+#[cfg(feature = "bevy_pbr")]
+pub mod camera_overlay;
 pub mod circles;
 pub mod config;
+pub mod config_asset;
+pub mod curves;
+pub mod diagnostic;
+pub mod frustum;
 pub mod gizmos;
+pub mod grid;
+#[cfg(feature = "bevy_pbr")]
+pub mod infinite_grid;
+#[cfg(feature = "bevy_pbr")]
+pub mod light;
+pub mod mesh;
+#[cfg(feature = "bevy_picking")]
+pub mod picking;
 pub mod primitives;
+pub mod retained;
+#[cfg(feature = "bevy_text")]
+pub mod text;
+#[cfg(feature = "bevy_picking")]
+pub mod transform_gizmo;
 
+mod buffers;
 #[cfg(feature = "bevy_sprite")]
 mod pipeline_2d;
 #[cfg(feature = "bevy_pbr")]
@@ -41,18 +64,41 @@ mod pipeline_3d;
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        aabb::{AabbGizmoConfigGroup, ShowAabbGizmo},
-        config::{DefaultGizmoConfigGroup, GizmoConfig, GizmoConfigGroup, GizmoConfigStore},
+        aabb::{AabbGizmoConfigGroup, ShowAabbGizmo, ShowBoundingSphereGizmo, ShowObbGizmo},
+        config::{
+            DefaultGizmoConfigGroup, GizmoConfig, GizmoConfigGroup, GizmoConfigStore,
+            GizmoLineStyle, GizmoOcclusion,
+        },
+        frustum::{FrustumGizmoConfigGroup, ShowFrustumGizmo},
         gizmos::Gizmos,
-        primitives::{dim2::GizmoPrimitive2d, dim3::GizmoPrimitive3d},
+        primitives::{
+            dim2::{GizmoFilledPrimitive2d, GizmoGradientPrimitive2d, GizmoPrimitive2d},
+            dim3::GizmoPrimitive3d,
+            projection::GizmoPrimitiveProjection,
+        },
+        retained::{Gizmo, GizmoAsset, GizmoBundle},
         AppGizmoBuilder,
     };
+
+    #[doc(hidden)]
+    #[cfg(feature = "bevy_pbr")]
+    pub use crate::{
+        camera_overlay::DebugCameraOverlay,
+        infinite_grid::InfiniteGrid,
+        light::{LightGizmoConfigGroup, ShowLightGizmo},
+    };
+
+    #[doc(hidden)]
+    #[cfg(feature = "bevy_picking")]
+    pub use crate::{
+        picking::GizmoPickingPlugin,
+        transform_gizmo::{TransformGizmo, TransformGizmoMode},
+    };
 }
 
 use aabb::AabbGizmoPlugin;
-use bevy_app::{App, Last, Plugin};
+use bevy_app::{App, Last, Plugin, PostUpdate};
 use bevy_asset::{load_internal_asset, Asset, AssetApp, Assets, Handle};
-use bevy_core::cast_slice;
 use bevy_ecs::{
     component::Component,
     query::ROQueryItem,
@@ -62,29 +108,40 @@ use bevy_ecs::{
         Commands, Res, ResMut, Resource, SystemParamItem,
     },
 };
+use bevy_math::Mat4;
 use bevy_reflect::TypePath;
 use bevy_render::{
     extract_component::{ComponentUniforms, DynamicUniformIndex, UniformComponentPlugin},
-    render_asset::{
-        PrepareAssetError, RenderAsset, RenderAssetPlugin, RenderAssetUsages, RenderAssets,
-    },
+    mesh::Mesh,
+    render_asset::RenderAssetUsages,
     render_phase::{PhaseItem, RenderCommand, RenderCommandResult, TrackedRenderPass},
     render_resource::{
         binding_types::uniform_buffer, BindGroup, BindGroupEntries, BindGroupLayout,
-        BindGroupLayoutEntries, Buffer, BufferInitDescriptor, BufferUsages, Shader, ShaderStages,
-        ShaderType, VertexAttribute, VertexBufferLayout, VertexFormat, VertexStepMode,
+        BindGroupLayoutEntries, PrimitiveTopology, Shader, ShaderStages, ShaderType,
+        VertexAttribute, VertexBufferLayout, VertexFormat, VertexStepMode,
     },
     renderer::RenderDevice,
+    view::RenderLayers,
     Extract, ExtractSchedule, Render, RenderApp, RenderSet,
 };
 use bevy_utils::TypeIdMap;
+use buffers::{extract_gizmos, prepare_gizmos, ExtractedGizmos, RenderGizmos};
 use config::{
-    DefaultGizmoConfigGroup, GizmoConfig, GizmoConfigGroup, GizmoConfigStore, GizmoMeshConfig,
+    DefaultGizmoConfigGroup, GizmoConfig, GizmoConfigGroup, GizmoConfigStore, GizmoDepthTest,
+    GizmoLineStyle, GizmoMeshConfig, GizmoOcclusion,
 };
-use gizmos::GizmoStorage;
-use std::{any::TypeId, mem};
+use config_asset::{apply_active_gizmo_config, GizmoConfigAsset, GizmoConfigLoader};
+use diagnostic::GizmoUploadedBytes;
+use frustum::FrustumGizmoPlugin;
+use gizmos::{draw_retained_gizmos, GizmoLines, GizmoStorage};
+use mesh::{draw_mesh_gizmos, invalidate_mesh_gizmo_edges, MeshGizmoEdges};
+use retained::{
+    extract_retained_gizmos, update_retained_gizmos, Gizmo, GizmoAsset, RetainedGizmoHandles,
+};
+use std::{any::TypeId, iter, mem};
 
 const LINE_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(7414812689238026784);
+const FILLED_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(2793512437890463891);
 
 /// A [`Plugin`] that provides an immediate mode drawing api for visual debugging.
 pub struct GizmoPlugin;
@@ -96,29 +153,73 @@ impl Plugin for GizmoPlugin {
         bevy_log::error!("bevy_gizmos requires either bevy_pbr or bevy_sprite. Please enable one.");
 
         load_internal_asset!(app, LINE_SHADER_HANDLE, "lines.wgsl", Shader::from_wgsl);
+        load_internal_asset!(app, FILLED_SHADER_HANDLE, "filled.wgsl", Shader::from_wgsl);
 
         app.register_type::<GizmoConfig>()
+            .register_type::<GizmoLineStyle>()
+            .register_type::<GizmoOcclusion>()
             .add_plugins(UniformComponentPlugin::<LineGizmoUniform>::default())
             .init_asset::<LineGizmo>()
-            .add_plugins(RenderAssetPlugin::<LineGizmo>::default())
+            .init_asset::<FilledGizmo>()
+            .init_resource::<GizmoUploadedBytes>()
             .init_resource::<LineGizmoHandles>()
+            .init_resource::<MeshGizmoEdges>()
+            .add_systems(PostUpdate, invalidate_mesh_gizmo_edges)
+            .init_asset::<GizmoAsset>()
+            .register_type::<Gizmo>()
+            .init_resource::<RetainedGizmoHandles>()
+            .add_systems(Last, update_retained_gizmos)
+            .init_asset::<GizmoConfigAsset>()
+            .init_asset_loader::<GizmoConfigLoader>()
+            .add_systems(Last, apply_active_gizmo_config)
             // We insert the Resource GizmoConfigStore into the world implicitly here if it does not exist.
             .init_gizmo_group::<DefaultGizmoConfigGroup>()
-            .add_plugins(AabbGizmoPlugin);
+            .add_plugins((AabbGizmoPlugin, FrustumGizmoPlugin));
+
+        #[cfg(feature = "bevy_pbr")]
+        app.add_plugins((
+            light::LightGizmoPlugin,
+            camera_overlay::DebugCameraOverlayPlugin,
+        ));
 
+        #[cfg(feature = "bevy_picking")]
+        app.add_plugins(transform_gizmo::TransformGizmoPlugin);
+
+        let uploaded_bytes = app.world.resource::<GizmoUploadedBytes>().clone();
         let Ok(render_app) = app.get_sub_app_mut(RenderApp) else {
             return;
         };
 
-        render_app.add_systems(
-            Render,
-            prepare_line_gizmo_bind_group.in_set(RenderSet::PrepareBindGroups),
-        );
+        render_app
+            .insert_resource(uploaded_bytes)
+            .init_resource::<ExtractedGizmos<LineGizmo>>()
+            .init_resource::<RenderGizmos<LineGizmo>>()
+            .init_resource::<ExtractedGizmos<FilledGizmo>>()
+            .init_resource::<RenderGizmos<FilledGizmo>>()
+            .add_systems(
+                ExtractSchedule,
+                (
+                    extract_retained_gizmos,
+                    extract_gizmos::<LineGizmo>,
+                    extract_gizmos::<FilledGizmo>,
+                ),
+            )
+            .add_systems(
+                Render,
+                (
+                    (prepare_gizmos::<LineGizmo>, prepare_gizmos::<FilledGizmo>)
+                        .in_set(RenderSet::PrepareAssets),
+                    prepare_line_gizmo_bind_group.in_set(RenderSet::PrepareBindGroups),
+                ),
+            );
 
         #[cfg(feature = "bevy_sprite")]
         app.add_plugins(pipeline_2d::LineGizmo2dPlugin);
         #[cfg(feature = "bevy_pbr")]
-        app.add_plugins(pipeline_3d::LineGizmo3dPlugin);
+        app.add_plugins((
+            pipeline_3d::LineGizmo3dPlugin,
+            infinite_grid::InfiniteGridPlugin,
+        ));
     }
 
     fn finish(&self, app: &mut bevy_app::App) {
@@ -130,7 +231,7 @@ impl Plugin for GizmoPlugin {
         let layout = render_device.create_bind_group_layout(
             "LineGizmoUniform layout",
             &BindGroupLayoutEntries::single(
-                ShaderStages::VERTEX,
+                ShaderStages::VERTEX_FRAGMENT,
                 uniform_buffer::<LineGizmoUniform>(true),
             ),
         );
@@ -162,8 +263,19 @@ impl AppGizmoBuilder for App {
             return self;
         }
 
-        self.init_resource::<GizmoStorage<T>>()
-            .add_systems(Last, update_gizmo_meshes::<T>);
+        self.init_resource::<GizmoStorage<T>>().add_systems(
+            Last,
+            (
+                draw_retained_gizmos::<T>,
+                draw_mesh_gizmos::<T>,
+                update_gizmo_meshes::<T>,
+            )
+                .chain(),
+        );
+        #[cfg(feature = "bevy_text")]
+        text::add_gizmo_text_systems::<T>(self);
+        #[cfg(feature = "bevy_picking")]
+        picking::add_gizmo_picking_systems::<T>(self);
 
         self.world
             .get_resource_or_insert_with::<GizmoConfigStore>(Default::default)
@@ -187,8 +299,19 @@ impl AppGizmoBuilder for App {
             return self;
         }
 
-        self.init_resource::<GizmoStorage<T>>()
-            .add_systems(Last, update_gizmo_meshes::<T>);
+        self.init_resource::<GizmoStorage<T>>().add_systems(
+            Last,
+            (
+                draw_retained_gizmos::<T>,
+                draw_mesh_gizmos::<T>,
+                update_gizmo_meshes::<T>,
+            )
+                .chain(),
+        );
+        #[cfg(feature = "bevy_text")]
+        text::add_gizmo_text_systems::<T>(self);
+        #[cfg(feature = "bevy_picking")]
+        picking::add_gizmo_picking_systems::<T>(self);
 
         self.world
             .get_resource_or_insert_with::<GizmoConfigStore>(Default::default)
@@ -204,57 +327,119 @@ impl AppGizmoBuilder for App {
     }
 }
 
+/// The gizmo assets of each [`GizmoConfigGroup`], updated with the lines drawn every frame.
 #[derive(Resource, Default)]
-struct LineGizmoHandles {
-    list: TypeIdMap<Handle<LineGizmo>>,
-    strip: TypeIdMap<Handle<LineGizmo>>,
+struct LineGizmoHandles(TypeIdMap<GroupGizmoHandles>);
+
+#[derive(Default)]
+struct GroupGizmoHandles {
+    /// The gizmos drawn to the render layers of the [`GizmoConfig`].
+    lines: GizmoHandles,
+    /// The gizmos drawn with [`Gizmos::with_render_layers`](crate::gizmos::Gizmos::with_render_layers).
+    layered: Vec<(RenderLayers, GizmoHandles)>,
+}
+
+/// The assets of the lines drawn to one set of render layers, `None` when there are none.
+#[derive(Default)]
+struct GizmoHandles {
+    list: Option<Handle<LineGizmo>>,
+    strip: Option<Handle<LineGizmo>>,
+    filled: Option<Handle<FilledGizmo>>,
 }
 
 fn update_gizmo_meshes<T: GizmoConfigGroup>(
     mut line_gizmos: ResMut<Assets<LineGizmo>>,
+    mut filled_gizmos: ResMut<Assets<FilledGizmo>>,
     mut handles: ResMut<LineGizmoHandles>,
     mut storage: ResMut<GizmoStorage<T>>,
 ) {
-    if storage.list_positions.is_empty() {
-        handles.list.remove(&TypeId::of::<T>());
-    } else if let Some(handle) = handles.list.get(&TypeId::of::<T>()) {
-        let list = line_gizmos.get_mut(handle).unwrap();
-
-        list.positions = mem::take(&mut storage.list_positions);
-        list.colors = mem::take(&mut storage.list_colors);
-    } else {
-        let mut list = LineGizmo {
-            strip: false,
-            ..Default::default()
+    let storage = &mut *storage;
+    let handles = handles.0.entry(TypeId::of::<T>()).or_default();
+    update_gizmo_handles(
+        &mut handles.lines,
+        &mut storage.lines,
+        &mut line_gizmos,
+        &mut filled_gizmos,
+    );
+
+    // Dropping the assets of the layers that weren't drawn to this frame.
+    handles
+        .layered
+        .retain(|(layers, _)| storage.layered.iter().any(|(drawn, _)| drawn == layers));
+    for (render_layers, lines) in &mut storage.layered {
+        let index = match handles
+            .layered
+            .iter()
+            .position(|(layers, _)| *layers == *render_layers)
+        {
+            Some(index) => index,
+            None => {
+                handles
+                    .layered
+                    .push((*render_layers, GizmoHandles::default()));
+                handles.layered.len() - 1
+            }
         };
-
-        list.positions = mem::take(&mut storage.list_positions);
-        list.colors = mem::take(&mut storage.list_colors);
-
-        handles
-            .list
-            .insert(TypeId::of::<T>(), line_gizmos.add(list));
+        update_gizmo_handles(
+            &mut handles.layered[index].1,
+            lines,
+            &mut line_gizmos,
+            &mut filled_gizmos,
+        );
     }
+    // Text labels are shown earlier in the frame, the ones drawn since then are kept.
+    storage.layered.retain(|(_, lines)| !lines.is_empty());
+}
 
-    if storage.strip_positions.is_empty() {
-        handles.strip.remove(&TypeId::of::<T>());
-    } else if let Some(handle) = handles.strip.get(&TypeId::of::<T>()) {
-        let strip = line_gizmos.get_mut(handle).unwrap();
-
-        strip.positions = mem::take(&mut storage.strip_positions);
-        strip.colors = mem::take(&mut storage.strip_colors);
-    } else {
-        let mut strip = LineGizmo {
-            strip: true,
-            ..Default::default()
+/// Moves the lines drawn since the last frame to their assets.
+fn update_gizmo_handles(
+    handles: &mut GizmoHandles,
+    lines: &mut GizmoLines,
+    line_gizmos: &mut Assets<LineGizmo>,
+    filled_gizmos: &mut Assets<FilledGizmo>,
+) {
+    #[cfg(feature = "bevy_picking")]
+    lines.pick_ids.clear();
+    for (handle, positions, colors, strip) in [
+        (
+            &mut handles.list,
+            &mut lines.list_positions,
+            &mut lines.list_colors,
+            false,
+        ),
+        (
+            &mut handles.strip,
+            &mut lines.strip_positions,
+            &mut lines.strip_colors,
+            true,
+        ),
+    ] {
+        if positions.is_empty() {
+            *handle = None;
+            continue;
+        }
+        let line_gizmo = LineGizmo {
+            positions: mem::take(positions),
+            colors: mem::take(colors),
+            strip,
         };
+        match handle {
+            Some(handle) => *line_gizmos.get_mut(&*handle).unwrap() = line_gizmo,
+            None => *handle = Some(line_gizmos.add(line_gizmo)),
+        }
+    }
 
-        strip.positions = mem::take(&mut storage.strip_positions);
-        strip.colors = mem::take(&mut storage.strip_colors);
-
-        handles
-            .strip
-            .insert(TypeId::of::<T>(), line_gizmos.add(strip));
+    if lines.triangle_positions.is_empty() {
+        handles.filled = None;
+        return;
+    }
+    let filled = FilledGizmo {
+        positions: mem::take(&mut lines.triangle_positions),
+        colors: mem::take(&mut lines.triangle_colors),
+    };
+    match &handles.filled {
+        Some(handle) => *filled_gizmos.get_mut(handle).unwrap() = filled,
+        None => handles.filled = Some(filled_gizmos.add(filled)),
     }
 }
 
@@ -268,81 +453,179 @@ fn extract_gizmo_data<T: GizmoConfigGroup>(
     if !config.enabled {
         return;
     }
+    let Some(handles) = handles.0.get(&TypeId::of::<T>()) else {
+        return;
+    };
 
-    for map in [&handles.list, &handles.strip].into_iter() {
-        let Some(handle) = map.get(&TypeId::of::<T>()) else {
-            continue;
+    let layered = handles
+        .layered
+        .iter()
+        .map(|(render_layers, handles)| (*render_layers, handles));
+    let all_layers = iter::once((config.render_layers, &handles.lines)).chain(layered);
+    for (render_layers, handles) in all_layers {
+        let mesh_config = GizmoMeshConfig {
+            render_layers,
+            ..GizmoMeshConfig::from(config)
         };
-        commands.spawn((
-            LineGizmoUniform {
-                line_width: config.line_width,
-                depth_bias: config.depth_bias,
-                #[cfg(feature = "webgl")]
-                _padding: Default::default(),
-            },
-            (*handle).clone_weak(),
-            GizmoMeshConfig::from(config),
-        ));
+
+        for handle in [&handles.list, &handles.strip].into_iter().flatten() {
+            let (dash_length, gap_length) = config.line_style.dash_and_gap_lengths();
+            commands.spawn((
+                LineGizmoUniform {
+                    transform: Mat4::IDENTITY,
+                    line_width: config.line_width,
+                    depth_bias: config.depth_bias,
+                    dash_length,
+                    gap_length,
+                    alpha: 1.,
+                },
+                handle.clone_weak(),
+                mesh_config,
+            ));
+
+            // The hidden parts are drawn by a second pass with the inverse depth test.
+            if let GizmoOcclusion::XRay {
+                occluded_alpha,
+                occluded_style,
+            } = config.occlusion
+            {
+                let (dash_length, gap_length) = occluded_style.dash_and_gap_lengths();
+                commands.spawn((
+                    LineGizmoUniform {
+                        transform: Mat4::IDENTITY,
+                        line_width: config.line_width,
+                        depth_bias: config.depth_bias,
+                        dash_length,
+                        gap_length,
+                        alpha: occluded_alpha,
+                    },
+                    handle.clone_weak(),
+                    GizmoMeshConfig {
+                        depth_test: GizmoDepthTest::Occluded,
+                        ..mesh_config
+                    },
+                ));
+            }
+        }
+
+        if let Some(handle) = &handles.filled {
+            commands.spawn((handle.clone_weak(), mesh_config));
+        }
     }
 }
 
 #[derive(Component, ShaderType, Clone, Copy)]
 struct LineGizmoUniform {
+    /// The transform of the lines, the identity for [`Gizmos`](crate::gizmos::Gizmos).
+    transform: Mat4,
     line_width: f32,
     depth_bias: f32,
-    /// WebGL2 structs must be 16 byte aligned.
-    #[cfg(feature = "webgl")]
-    _padding: bevy_math::Vec2,
+    /// The length of the dashes in pixels, zero to draw dots as wide as the line.
+    dash_length: f32,
+    /// The length of the gaps between dashes in pixels, zero to draw a solid line.
+    gap_length: f32,
+    /// The factor the alpha of the lines is multiplied by.
+    alpha: f32,
 }
 
+/// The lines drawn with [`Gizmos`](crate::gizmos::Gizmos) in one frame, for one topology.
+///
+/// Line strips are stored back to back, each followed by a vertex with `NaN` position and color.
+///
+/// Use [`Gizmos::take_line_gizmos`](crate::gizmos::Gizmos::take_line_gizmos) to capture the lines
+/// a system draws, and [`LineGizmo::to_meshes`] to bake them into persistent [`Mesh`]es.
 #[derive(Asset, Debug, Default, Clone, TypePath)]
-struct LineGizmo {
+pub struct LineGizmo {
     positions: Vec<[f32; 3]>,
     colors: Vec<[f32; 4]>,
     /// Whether this gizmo's topology is a line-strip or line-list
     strip: bool,
 }
 
-#[derive(Debug, Clone)]
-struct GpuLineGizmo {
-    position_buffer: Buffer,
-    color_buffer: Buffer,
-    vertex_count: u32,
-    strip: bool,
-}
+impl LineGizmo {
+    /// Whether this gizmo's topology is a line-strip or line-list.
+    pub fn is_strip(&self) -> bool {
+        self.strip
+    }
 
-impl RenderAsset for LineGizmo {
-    type PreparedAsset = GpuLineGizmo;
-    type Param = SRes<RenderDevice>;
+    /// Returns `true` if this gizmo has no lines.
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
 
-    fn asset_usage(&self) -> RenderAssetUsages {
-        RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD
+    /// Returns the positions and colors of each line strip of this gizmo.
+    ///
+    /// A line-list gizmo is returned as a single strip of vertex pairs.
+    pub fn linestrips(&self) -> impl Iterator<Item = (&[[f32; 3]], &[[f32; 4]])> {
+        let mut start = 0;
+        let strip = self.strip;
+        iter::from_fn(move || {
+            if start >= self.positions.len() {
+                return None;
+            }
+            let end = if strip {
+                self.positions[start..]
+                    .iter()
+                    .position(|position| position[0].is_nan())
+                    .map_or(self.positions.len(), |offset| start + offset)
+            } else {
+                self.positions.len()
+            };
+            let range = start..end;
+            start = end + 1;
+            Some((&self.positions[range.clone()], &self.colors[range]))
+        })
+        .filter(|(positions, _)| positions.len() >= 2)
     }
 
-    fn prepare_asset(
-        self,
-        render_device: &mut SystemParamItem<Self::Param>,
-    ) -> Result<Self::PreparedAsset, PrepareAssetError<Self>> {
-        let position_buffer_data = cast_slice(&self.positions);
-        let position_buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
-            usage: BufferUsages::VERTEX,
-            label: Some("LineGizmo Position Buffer"),
-            contents: position_buffer_data,
-        });
+    /// Bakes this gizmo into [`Mesh`]es with [`Mesh::ATTRIBUTE_POSITION`] and
+    /// [`Mesh::ATTRIBUTE_COLOR`] attributes.
+    ///
+    /// A line-list gizmo becomes a single [`PrimitiveTopology::LineList`] mesh, while each strip
+    /// of a line-strip gizmo becomes its own [`PrimitiveTopology::LineStrip`] mesh, as meshes
+    /// can't restart strips.
+    ///
+    /// Note that meshes are rendered with the usual mesh pipelines, so lines are one pixel wide
+    /// and ignore the [`GizmoConfig`] line width and depth bias.
+    pub fn to_meshes(&self, asset_usage: RenderAssetUsages) -> Vec<Mesh> {
+        let topology = if self.strip {
+            PrimitiveTopology::LineStrip
+        } else {
+            PrimitiveTopology::LineList
+        };
+        self.linestrips()
+            .map(|(positions, colors)| {
+                Mesh::new(topology, asset_usage)
+                    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions.to_vec())
+                    .with_inserted_attribute(Mesh::ATTRIBUTE_COLOR, colors.to_vec())
+            })
+            .collect()
+    }
+}
 
-        let color_buffer_data = cast_slice(&self.colors);
-        let color_buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
-            usage: BufferUsages::VERTEX,
-            label: Some("LineGizmo Color Buffer"),
-            contents: color_buffer_data,
-        });
+/// The filled triangles drawn with [`Gizmos`](crate::gizmos::Gizmos) in one frame, as a
+/// triangle list.
+///
+/// See [`Gizmos::triangles_2d`](crate::gizmos::Gizmos::triangles_2d) and
+/// [`GizmoFilledPrimitive2d`](crate::primitives::dim2::GizmoFilledPrimitive2d).
+#[derive(Asset, Debug, Default, Clone, TypePath)]
+pub struct FilledGizmo {
+    positions: Vec<[f32; 3]>,
+    colors: Vec<[f32; 4]>,
+}
 
-        Ok(GpuLineGizmo {
-            position_buffer,
-            color_buffer,
-            vertex_count: self.positions.len() as u32,
-            strip: self.strip,
-        })
+impl FilledGizmo {
+    /// Returns `true` if this gizmo has no triangles.
+    pub fn is_empty(&self) -> bool {
+        self.positions.len() < 3
+    }
+
+    /// Bakes this gizmo into a [`PrimitiveTopology::TriangleList`] [`Mesh`] with
+    /// [`Mesh::ATTRIBUTE_POSITION`] and [`Mesh::ATTRIBUTE_COLOR`] attributes.
+    pub fn to_mesh(&self, asset_usage: RenderAssetUsages) -> Mesh {
+        Mesh::new(PrimitiveTopology::TriangleList, asset_usage)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, self.positions.clone())
+            .with_inserted_attribute(Mesh::ATTRIBUTE_COLOR, self.colors.clone())
     }
 }
 
@@ -401,7 +684,7 @@ impl<const I: usize, P: PhaseItem> RenderCommand<P> for SetLineGizmoBindGroup<I>
 
 struct DrawLineGizmo;
 impl<P: PhaseItem> RenderCommand<P> for DrawLineGizmo {
-    type Param = SRes<RenderAssets<LineGizmo>>;
+    type Param = SRes<RenderGizmos<LineGizmo>>;
     type ViewQuery = ();
     type ItemQuery = Read<Handle<LineGizmo>>;
 
@@ -420,27 +703,24 @@ impl<P: PhaseItem> RenderCommand<P> for DrawLineGizmo {
             return RenderCommandResult::Failure;
         };
 
-        if line_gizmo.vertex_count < 2 {
+        let vertex_count = line_gizmo.vertex_count();
+        if vertex_count < 2 {
             return RenderCommandResult::Success;
         }
 
         let instances = if line_gizmo.strip {
-            let item_size = VertexFormat::Float32x3.size();
-            let buffer_size = line_gizmo.position_buffer.size() - item_size;
-            pass.set_vertex_buffer(0, line_gizmo.position_buffer.slice(..buffer_size));
-            pass.set_vertex_buffer(1, line_gizmo.position_buffer.slice(item_size..));
+            pass.set_vertex_buffer(0, line_gizmo.positions(0..vertex_count - 1));
+            pass.set_vertex_buffer(1, line_gizmo.positions(1..vertex_count));
 
-            let item_size = VertexFormat::Float32x4.size();
-            let buffer_size = line_gizmo.color_buffer.size() - item_size;
-            pass.set_vertex_buffer(2, line_gizmo.color_buffer.slice(..buffer_size));
-            pass.set_vertex_buffer(3, line_gizmo.color_buffer.slice(item_size..));
+            pass.set_vertex_buffer(2, line_gizmo.colors(0..vertex_count - 1));
+            pass.set_vertex_buffer(3, line_gizmo.colors(1..vertex_count));
 
-            u32::max(line_gizmo.vertex_count, 1) - 1
+            vertex_count - 1
         } else {
-            pass.set_vertex_buffer(0, line_gizmo.position_buffer.slice(..));
-            pass.set_vertex_buffer(1, line_gizmo.color_buffer.slice(..));
+            pass.set_vertex_buffer(0, line_gizmo.positions(0..vertex_count));
+            pass.set_vertex_buffer(1, line_gizmo.colors(0..vertex_count));
 
-            line_gizmo.vertex_count / 2
+            vertex_count / 2
         };
 
         pass.draw(0..6, 0..instances);
@@ -449,6 +729,67 @@ impl<P: PhaseItem> RenderCommand<P> for DrawLineGizmo {
     }
 }
 
+#[cfg(feature = "bevy_sprite")]
+struct DrawFilledGizmo;
+#[cfg(feature = "bevy_sprite")]
+impl<P: PhaseItem> RenderCommand<P> for DrawFilledGizmo {
+    type Param = SRes<RenderGizmos<FilledGizmo>>;
+    type ViewQuery = ();
+    type ItemQuery = Read<Handle<FilledGizmo>>;
+
+    #[inline]
+    fn render<'w>(
+        _item: &P,
+        _view: ROQueryItem<'w, Self::ViewQuery>,
+        handle: Option<ROQueryItem<'w, Self::ItemQuery>>,
+        filled_gizmos: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let Some(handle) = handle else {
+            return RenderCommandResult::Failure;
+        };
+        let Some(filled_gizmo) = filled_gizmos.into_inner().get(handle) else {
+            return RenderCommandResult::Failure;
+        };
+
+        let vertex_count = filled_gizmo.vertex_count();
+        if vertex_count < 3 {
+            return RenderCommandResult::Success;
+        }
+
+        pass.set_vertex_buffer(0, filled_gizmo.positions(0..vertex_count));
+        pass.set_vertex_buffer(1, filled_gizmo.colors(0..vertex_count));
+        pass.draw(0..vertex_count, 0..1);
+
+        RenderCommandResult::Success
+    }
+}
+
+#[cfg(feature = "bevy_sprite")]
+fn filled_gizmo_vertex_buffer_layouts() -> Vec<VertexBufferLayout> {
+    use VertexFormat::*;
+    vec![
+        VertexBufferLayout {
+            array_stride: Float32x3.size(),
+            step_mode: VertexStepMode::Vertex,
+            attributes: vec![VertexAttribute {
+                format: Float32x3,
+                offset: 0,
+                shader_location: 0,
+            }],
+        },
+        VertexBufferLayout {
+            array_stride: Float32x4.size(),
+            step_mode: VertexStepMode::Vertex,
+            attributes: vec![VertexAttribute {
+                format: Float32x4,
+                offset: 0,
+                shader_location: 1,
+            }],
+        },
+    ]
+}
+
 fn line_gizmo_vertex_buffer_layouts(strip: bool) -> Vec<VertexBufferLayout> {
     use VertexFormat::*;
     let mut position_layout = VertexBufferLayout {
@@ -502,3 +843,75 @@ fn line_gizmo_vertex_buffer_layouts(strip: bool) -> Vec<VertexBufferLayout> {
         vec![position_layout, color_layout]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{system::RunSystemOnce, world::World};
+    use bevy_render::MainWorld;
+
+    use super::*;
+
+    #[test]
+    fn x_ray_gizmos_are_drawn_twice() {
+        let mut main_world = MainWorld::default();
+        let mut config_store = GizmoConfigStore::default();
+        config_store.register::<DefaultGizmoConfigGroup>();
+        let (config, _) = config_store.config_mut::<DefaultGizmoConfigGroup>();
+        config.occlusion = GizmoOcclusion::XRay {
+            occluded_alpha: 0.3,
+            occluded_style: GizmoLineStyle::Dotted { gap_length: 4. },
+        };
+        main_world.insert_resource(config_store);
+        let mut handles = LineGizmoHandles::default();
+        handles.0.insert(
+            TypeId::of::<DefaultGizmoConfigGroup>(),
+            GroupGizmoHandles {
+                lines: GizmoHandles {
+                    list: Some(Handle::weak_from_u128(1)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        );
+        main_world.insert_resource(handles);
+
+        let mut render_world = World::new();
+        render_world.insert_resource(main_world);
+        render_world.run_system_once(extract_gizmo_data::<DefaultGizmoConfigGroup>);
+
+        let mut passes = render_world
+            .query::<(&LineGizmoUniform, &GizmoMeshConfig)>()
+            .iter(&render_world)
+            .map(|(uniform, config)| {
+                (
+                    config.depth_test,
+                    uniform.alpha,
+                    uniform.dash_length,
+                    uniform.gap_length,
+                )
+            })
+            .collect::<Vec<_>>();
+        passes.sort_by_key(|(depth_test, ..)| *depth_test != GizmoDepthTest::Visible);
+        assert_eq!(
+            passes,
+            [
+                (GizmoDepthTest::Visible, 1., 0., 0.),
+                (GizmoDepthTest::Occluded, 0.3, 0., 4.),
+            ]
+        );
+
+        // The other modes only draw the lines once.
+        let mut main_world = render_world.resource_mut::<MainWorld>();
+        let mut config_store = main_world.resource_mut::<GizmoConfigStore>();
+        let (config, _) = config_store.config_mut::<DefaultGizmoConfigGroup>();
+        config.occlusion = GizmoOcclusion::AlwaysOnTop;
+        render_world.clear_entities();
+        render_world.run_system_once(extract_gizmo_data::<DefaultGizmoConfigGroup>);
+        let depth_tests = render_world
+            .query::<&GizmoMeshConfig>()
+            .iter(&render_world)
+            .map(|config| config.depth_test)
+            .collect::<Vec<_>>();
+        assert_eq!(depth_tests, [GizmoDepthTest::Always]);
+    }
+}
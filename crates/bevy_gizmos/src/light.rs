@@ -0,0 +1,175 @@
+//! A module adding debug visualization of the shapes of lights: the cones of spot lights, the
+//! ranges of point lights and the shadow cascades of directional lights.
+
+use crate as bevy_gizmos;
+
+use bevy_app::{Plugin, PostUpdate};
+use bevy_ecs::{
+    component::Component,
+    query::Without,
+    reflect::ReflectComponent,
+    schedule::IntoSystemConfigs,
+    system::{Query, Res},
+};
+use bevy_math::{primitives::Direction3d, Mat4, Vec3};
+use bevy_pbr::{Cascades, DirectionalLight, PointLight, SimulationLightSystems, SpotLight};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::color::Color;
+use bevy_transform::{components::GlobalTransform, TransformSystem};
+
+use crate::{
+    config::{GizmoConfigGroup, GizmoConfigStore},
+    frustum::draw_box_edges,
+    gizmos::Gizmos,
+    AppGizmoBuilder,
+};
+
+/// A [`Plugin`] that provides visualization of the shapes of lights for debugging.
+pub struct LightGizmoPlugin;
+
+impl Plugin for LightGizmoPlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        app.register_type::<LightGizmoConfigGroup>()
+            .register_type::<ShowLightGizmo>()
+            .init_gizmo_group::<LightGizmoConfigGroup>()
+            .add_systems(
+                PostUpdate,
+                (
+                    draw_lights,
+                    draw_all_lights.run_if(|config: Res<GizmoConfigStore>| {
+                        config.config::<LightGizmoConfigGroup>().1.draw_all
+                    }),
+                )
+                    .after(TransformSystem::TransformPropagate)
+                    .after(SimulationLightSystems::UpdateDirectionalLightCascades),
+            );
+    }
+}
+
+/// The [`GizmoConfigGroup`] used for debug visualizations of the shapes of lights.
+///
+/// Spot lights draw the cone they light up to their range, point lights draw a sphere of the
+/// radius of their range, and directional lights draw the bounds of their shadow cascades.
+#[derive(Clone, Default, Reflect, GizmoConfigGroup)]
+pub struct LightGizmoConfigGroup {
+    /// Draws the shapes of all lights in the scene when set to `true`.
+    ///
+    /// To draw a specific light's shape, you can add the [`ShowLightGizmo`] component.
+    ///
+    /// Defaults to `false`.
+    pub draw_all: bool,
+    /// The default color for light gizmos.
+    ///
+    /// The color of each light is used if `None`.
+    ///
+    /// Defaults to `None`.
+    pub default_color: Option<Color>,
+}
+
+/// Add this [`Component`] to a [`SpotLight`], [`PointLight`] or [`DirectionalLight`] to draw its
+/// shape.
+///
+/// Directional lights only have cascades once they cast shadows on the view of a camera.
+#[derive(Component, Reflect, Default, Debug)]
+#[reflect(Component, Default)]
+pub struct ShowLightGizmo {
+    /// The color of the shape.
+    ///
+    /// The default color from the [`LightGizmoConfigGroup`] config is used if `None`,
+    pub color: Option<Color>,
+}
+
+type LightQueryData<'a> = (
+    &'a GlobalTransform,
+    Option<&'a SpotLight>,
+    Option<&'a PointLight>,
+    Option<(&'a DirectionalLight, &'a Cascades)>,
+);
+
+fn draw_lights(
+    query: Query<(LightQueryData, &ShowLightGizmo)>,
+    mut gizmos: Gizmos<LightGizmoConfigGroup>,
+) {
+    for (light, gizmo) in &query {
+        let color = gizmo.color.or(gizmos.config_ext.default_color);
+        draw_light(&mut gizmos, light, color);
+    }
+}
+
+fn draw_all_lights(
+    query: Query<LightQueryData, Without<ShowLightGizmo>>,
+    mut gizmos: Gizmos<LightGizmoConfigGroup>,
+) {
+    for light in &query {
+        let color = gizmos.config_ext.default_color;
+        draw_light(&mut gizmos, light, color);
+    }
+}
+
+fn draw_light(
+    gizmos: &mut Gizmos<LightGizmoConfigGroup>,
+    (transform, spot_light, point_light, directional_light): LightQueryData,
+    color: Option<Color>,
+) {
+    if let Some(light) = spot_light {
+        draw_spot_light(gizmos, light, transform, color.unwrap_or(light.color));
+    }
+    if let Some(light) = point_light {
+        let (_, rotation, translation) = transform.to_scale_rotation_translation();
+        gizmos.sphere(
+            translation,
+            rotation,
+            light.range,
+            color.unwrap_or(light.color),
+        );
+    }
+    if let Some((light, cascades)) = directional_light {
+        let color = color.unwrap_or(light.color);
+        for (_, cascades) in cascades.iter() {
+            for cascade in cascades {
+                let world_from_clip = cascade.view_transform() * cascade.projection().inverse();
+                draw_box_edges(gizmos, clip_space_box(world_from_clip), color);
+            }
+        }
+    }
+}
+
+/// Returns the corners of the box spanned by clip space, transformed by `world_from_clip`, in
+/// the order expected by [`draw_box_edges`].
+///
+/// Bevy uses reversed depth, so the near plane is at a depth of 1 and the far plane at 0.
+pub(crate) fn clip_space_box(world_from_clip: Mat4) -> [Vec3; 8] {
+    const CORNERS: [(f32, f32); 4] = [(1., -1.), (1., 1.), (-1., 1.), (-1., -1.)];
+    std::array::from_fn(|index| {
+        let (x, y) = CORNERS[index % 4];
+        let depth = if index < 4 { 1. } else { 0. };
+        world_from_clip.project_point3(Vec3::new(x, y, depth))
+    })
+}
+
+/// Draws the cone lit by `light`: the lines from the light to the rim of the cone, and the
+/// spherical cap at the end of its range.
+pub(crate) fn draw_spot_light<T: GizmoConfigGroup>(
+    gizmos: &mut Gizmos<T>,
+    light: &SpotLight,
+    transform: &GlobalTransform,
+    color: Color,
+) {
+    let position = transform.translation();
+    let Ok(direction) = Direction3d::new(transform.forward()) else {
+        return;
+    };
+    let (sin, cos) = light.outer_angle.sin_cos();
+    let rim_center = position + *direction * light.range * cos;
+    let rim_radius = light.range * sin;
+    gizmos.circle(rim_center, direction, rim_radius, color);
+
+    let right = transform.right();
+    let up = direction.cross(right);
+    let rim = [right, up, -right, -up].map(|axis| rim_center + axis * rim_radius);
+    for point in rim {
+        gizmos.line(position, point, color);
+    }
+    gizmos.short_arc_3d_between(position, rim[0], rim[2], color);
+    gizmos.short_arc_3d_between(position, rim[1], rim[3], color);
+}
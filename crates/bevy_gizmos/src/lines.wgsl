@@ -5,12 +5,14 @@
 
 
 struct LineGizmoUniform {
+    transform: mat4x4<f32>,
     line_width: f32,
     depth_bias: f32,
-#ifdef SIXTEEN_BYTE_ALIGNMENT
-    // WebGL2 structs must be 16 byte aligned.
-    _padding: vec2<f32>,
-#endif
+    // Zero to draw dots as wide as the line.
+    dash_length: f32,
+    // Zero to draw a solid line.
+    gap_length: f32,
+    alpha: f32,
 }
 
 @group(1) @binding(0) var<uniform> line_gizmo: LineGizmoUniform;
@@ -26,6 +28,9 @@ struct VertexInput {
 struct VertexOutput {
     @builtin(position) clip_position: vec4<f32>,
     @location(0) color: vec4<f32>,
+    // The position on the line in pixels, along it from its start and across it from its center.
+    @location(1) @interpolate(linear) line_position: vec2<f32>,
+    @location(2) @interpolate(flat) line_width: f32,
 };
 
 const EPSILON: f32 = 4.88e-04;
@@ -43,8 +48,8 @@ fn vertex(vertex: VertexInput) -> VertexOutput {
     let position = positions[vertex.index];
 
     // algorithm based on https://wwwtyro.net/2019/11/18/instanced-lines.html
-    var clip_a = view.view_proj * vec4(vertex.position_a, 1.);
-    var clip_b = view.view_proj * vec4(vertex.position_b, 1.);
+    var clip_a = view.view_proj * line_gizmo.transform * vec4(vertex.position_a, 1.);
+    var clip_b = view.view_proj * line_gizmo.transform * vec4(vertex.position_b, 1.);
 
     // Manual near plane clipping to avoid errors when doing the perspective divide inside this shader.
     clip_a = clip_near_plane(clip_a, clip_b);
@@ -60,6 +65,7 @@ fn vertex(vertex: VertexInput) -> VertexOutput {
     let y_basis = vec2(-x_basis.y, x_basis.x);
 
     var color = mix(vertex.color_a, vertex.color_b, position.z);
+    color.a *= line_gizmo.alpha;
 
     var line_width = line_gizmo.line_width;
     var alpha = 1.;
@@ -93,7 +99,9 @@ fn vertex(vertex: VertexInput) -> VertexOutput {
 
     var clip_position = vec4(clip.w * ((2. * screen) / resolution - 1.), depth, clip.w);
 
-    return VertexOutput(clip_position, color);
+    let line_position = vec2(position.z * length(screen_b - screen_a), position.y * line_width);
+
+    return VertexOutput(clip_position, color, line_position, line_width);
 }
 
 fn clip_near_plane(a: vec4<f32>, b: vec4<f32>) -> vec4<f32> {
@@ -112,6 +120,8 @@ fn clip_near_plane(a: vec4<f32>, b: vec4<f32>) -> vec4<f32> {
 
 struct FragmentInput {
     @location(0) color: vec4<f32>,
+    @location(1) @interpolate(linear) line_position: vec2<f32>,
+    @location(2) @interpolate(flat) line_width: f32,
 };
 
 struct FragmentOutput {
@@ -120,5 +130,18 @@ struct FragmentOutput {
 
 @fragment
 fn fragment(in: FragmentInput) -> FragmentOutput {
+    if line_gizmo.gap_length > 0. {
+        let dots = line_gizmo.dash_length <= 0.;
+        let dash_length = select(line_gizmo.dash_length, in.line_width, dots);
+        let along = in.line_position.x % (dash_length + line_gizmo.gap_length);
+        if along > dash_length {
+            discard;
+        }
+        // Round the dots, which are as long as the line is wide.
+        if dots && length(vec2(along - 0.5 * dash_length, in.line_position.y)) > 0.5 * dash_length {
+            discard;
+        }
+    }
+
     return FragmentOutput(in.color);
 }
@@ -0,0 +1,200 @@
+//! Wireframe gizmos of [`Mesh`] assets, drawn with [`Gizmos::mesh`].
+
+use std::{iter, mem};
+
+use bevy_asset::{AssetEvent, AssetId, Assets, Handle};
+use bevy_ecs::{
+    event::EventReader,
+    system::{Res, ResMut, Resource},
+};
+use bevy_math::{Affine3A, Vec3};
+use bevy_render::{
+    color::Color,
+    mesh::{Mesh, VertexAttributeValues},
+    render_resource::PrimitiveTopology,
+};
+use bevy_transform::components::Transform;
+use bevy_utils::{HashMap, HashSet};
+
+use crate::{
+    config::GizmoConfigGroup,
+    gizmos::{GizmoStorage, Gizmos},
+};
+
+/// A mesh wireframe drawn this frame, turned into lines before the lines are moved to their
+/// assets.
+#[derive(Clone, Debug)]
+pub(crate) struct GizmoMesh {
+    mesh: AssetId<Mesh>,
+    transform: Affine3A,
+    color: Color,
+}
+
+impl<'w, 's, T: GizmoConfigGroup> Gizmos<'w, 's, T> {
+    /// Draw the wireframe of a [`Mesh`] asset, placed by `transform`.
+    ///
+    /// The edges are found from the indices of the mesh the first time it is drawn, and cached
+    /// until it changes. Each edge is drawn once, even when it is shared by several triangles or
+    /// its vertices are split at UV seams.
+    ///
+    /// Unlike the `WireframePlugin`, this doesn't change the material of any entity. Nothing is
+    /// drawn while the mesh is loading, or if it was only kept in the render world by its
+    /// [`RenderAssetUsages`](bevy_render::render_asset::RenderAssetUsages).
+    ///
+    /// This should be called for each frame the wireframe needs to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_asset::Handle;
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_transform::prelude::*;
+    /// fn system(mut gizmos: Gizmos, meshes: Query<(&Handle<Mesh>, &GlobalTransform)>) {
+    ///     for (mesh, transform) in &meshes {
+    ///         gizmos.mesh(mesh, transform.compute_transform(), Color::WHITE);
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn mesh(&mut self, mesh: &Handle<Mesh>, transform: Transform, color: Color) {
+        if !self.enabled {
+            return;
+        }
+        self.push_mesh(GizmoMesh {
+            mesh: mesh.id(),
+            transform: transform.compute_affine(),
+            color,
+        });
+    }
+}
+
+/// The edges of each [`Mesh`] drawn with [`Gizmos::mesh`], found when first drawn.
+#[derive(Resource, Default)]
+pub(crate) struct MeshGizmoEdges(HashMap<AssetId<Mesh>, Vec<[Vec3; 2]>>);
+
+pub(crate) fn invalidate_mesh_gizmo_edges(
+    mut events: EventReader<AssetEvent<Mesh>>,
+    mut edges: ResMut<MeshGizmoEdges>,
+) {
+    for event in events.read() {
+        if let AssetEvent::Modified { id } | AssetEvent::Removed { id } = event {
+            edges.0.remove(id);
+        }
+    }
+}
+
+/// Turns the meshes drawn with the [`GizmoConfigGroup`] `T` into lines.
+pub(crate) fn draw_mesh_gizmos<T: GizmoConfigGroup>(
+    mut storage: ResMut<GizmoStorage<T>>,
+    meshes: Option<Res<Assets<Mesh>>>,
+    mut edges: ResMut<MeshGizmoEdges>,
+) {
+    let storage = &mut *storage;
+    let all_lines =
+        iter::once(&mut storage.lines).chain(storage.layered.iter_mut().map(|(_, lines)| lines));
+    for lines in all_lines {
+        for gizmo_mesh in mem::take(&mut lines.meshes) {
+            let Some(mesh) = meshes
+                .as_deref()
+                .and_then(|meshes| meshes.get(gizmo_mesh.mesh))
+            else {
+                continue;
+            };
+            let segments = edges
+                .0
+                .entry(gizmo_mesh.mesh)
+                .or_insert_with(|| mesh_edges(mesh));
+            lines.list_positions.extend(
+                segments
+                    .iter()
+                    .flatten()
+                    .map(|&point| gizmo_mesh.transform.transform_point3(point).to_array()),
+            );
+            lines.list_colors.extend(
+                iter::repeat(gizmo_mesh.color.as_linear_rgba_f32()).take(segments.len() * 2),
+            );
+        }
+    }
+}
+
+/// Finds the edges of the triangles or lines of `mesh`, each once.
+fn mesh_edges(mesh: &Mesh) -> Vec<[Vec3; 2]> {
+    let Some(positions) = mesh
+        .attribute(Mesh::ATTRIBUTE_POSITION)
+        .and_then(VertexAttributeValues::as_float3)
+    else {
+        return Vec::new();
+    };
+    let indices: Vec<usize> = match mesh.indices() {
+        Some(indices) => indices.iter().collect(),
+        None => (0..positions.len()).collect(),
+    };
+    let index_pairs: Vec<(usize, usize)> = match mesh.primitive_topology() {
+        PrimitiveTopology::TriangleList => {
+            indices.chunks_exact(3).flat_map(triangle_edges).collect()
+        }
+        PrimitiveTopology::TriangleStrip => indices.windows(3).flat_map(triangle_edges).collect(),
+        PrimitiveTopology::LineList => indices
+            .chunks_exact(2)
+            .map(|line| (line[0], line[1]))
+            .collect(),
+        PrimitiveTopology::LineStrip => indices.windows(2).map(|line| (line[0], line[1])).collect(),
+        PrimitiveTopology::PointList => Vec::new(),
+    };
+    unique_edges(positions, index_pairs)
+}
+
+fn triangle_edges(triangle: &[usize]) -> [(usize, usize); 3] {
+    [
+        (triangle[0], triangle[1]),
+        (triangle[1], triangle[2]),
+        (triangle[2], triangle[0]),
+    ]
+}
+
+/// Looks up the positions of the edges between `index_pairs`, skipping the repeated ones.
+///
+/// Edges are compared by position rather than index, since vertices are often duplicated to
+/// hold different normals or UVs.
+fn unique_edges(
+    positions: &[[f32; 3]],
+    index_pairs: impl IntoIterator<Item = (usize, usize)>,
+) -> Vec<[Vec3; 2]> {
+    let mut seen = HashSet::new();
+    let mut edges = Vec::new();
+    for (a, b) in index_pairs {
+        let (Some(&start), Some(&end)) = (positions.get(a), positions.get(b)) else {
+            continue;
+        };
+        // Adding zero turns `-0.0` into `0.0`, so they compare equal.
+        let start_bits = start.map(|x| (x + 0.).to_bits());
+        let end_bits = end.map(|x| (x + 0.).to_bits());
+        if start_bits == end_bits {
+            continue;
+        }
+        let key = if start_bits < end_bits {
+            (start_bits, end_bits)
+        } else {
+            (end_bits, start_bits)
+        };
+        if seen.insert(key) {
+            edges.push([Vec3::from(start), Vec3::from(end)]);
+        }
+    }
+    edges
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::primitives::Cuboid;
+
+    use super::*;
+
+    #[test]
+    fn cuboid_edges() {
+        let edges = mesh_edges(&Mesh::from(Cuboid::default()));
+        // The 12 edges of the box, and a diagonal across each face.
+        assert_eq!(edges.len(), 18);
+    }
+}
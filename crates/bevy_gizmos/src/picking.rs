@@ -0,0 +1,470 @@
+//! Picking of the lines drawn with [`Gizmos`](crate::gizmos::Gizmos), with the pointers of
+//! `bevy_picking`.
+//!
+//! Add the [`GizmoPickingPlugin`] to send [`GizmoPointer`] events when the pointers hover or
+//! click the gizmo lines. Gizmos aren't entities, so the events target a [`GizmoPickTarget`]
+//! instead: the [`GizmoConfigGroup`] the lines were drawn with, and the id given to them with
+//! [`Gizmos::with_pick_id`](crate::gizmos::Gizmos::with_pick_id).
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_gizmos::{picking::GizmoPointer, prelude::*};
+//! # use bevy_math::prelude::*;
+//! # use bevy_picking::events::Click;
+//! # use bevy_render::prelude::*;
+//! fn draw(mut gizmos: Gizmos) {
+//!     gizmos.with_pick_id(7, |gizmos| {
+//!         gizmos.circle(Vec3::ZERO, Direction3d::Y, 1., Color::GREEN);
+//!     });
+//! }
+//!
+//! fn clicked(mut clicks: EventReader<GizmoPointer<Click>>) {
+//!     for click in clicks.read() {
+//!         if click.target.is_group::<DefaultGizmoConfigGroup>() && click.target.pick_id == Some(7) {
+//!             println!("Clicked the circle at {:?}", click.hit.position);
+//!         }
+//!     }
+//! }
+//! # bevy_ecs::system::assert_is_system(draw);
+//! # bevy_ecs::system::assert_is_system(clicked);
+//! ```
+
+use std::{any::TypeId, iter, ops::Deref};
+
+use bevy_app::{App, Last, Plugin, PreUpdate};
+use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::{
+    entity::Entity,
+    event::{Event, EventReader, EventWriter},
+    query::With,
+    schedule::IntoSystemConfigs,
+    system::{Local, Query, Res, ResMut, Resource},
+};
+use bevy_math::{Mat4, Vec2, Vec3, Vec4, Vec4Swizzles};
+use bevy_picking::{
+    backend::HitData,
+    events::{Click, Out, Over},
+    pointer::{Location, PointerAction, PointerButton, PointerId, PointerInput, PointerLocation},
+    PickSet,
+};
+use bevy_render::{camera::Camera, view::RenderLayers};
+use bevy_transform::components::GlobalTransform;
+use bevy_utils::{HashMap, Instant, TypeIdMap};
+use bevy_window::PrimaryWindow;
+
+use crate::{
+    config::{GizmoConfigGroup, GizmoConfigStore},
+    gizmos::{GizmoLines, GizmoStorage},
+    mesh::draw_mesh_gizmos,
+    update_gizmo_meshes,
+};
+
+/// The distance in logical pixels from the edge of a line within which it is hit by a pointer.
+const PICK_MARGIN: f32 = 4.;
+
+/// A [`Plugin`] that sends [`GizmoPointer`] events when the pointers hover or click the lines
+/// drawn with [`Gizmos`](crate::gizmos::Gizmos).
+///
+/// This isn't added by the [`GizmoPlugin`](crate::GizmoPlugin), as testing every line drawn
+/// against every pointer can be expensive: add it if your app needs to pick gizmos, along with
+/// the `PickingPlugin` of `bevy_picking`.
+///
+/// Lines are hit when they pass within a few logical pixels of a pointer, and the nearest line
+/// is hovered. Gizmo picking is separate from the picking of entities: gizmos and entities don't
+/// block each other.
+#[derive(Default)]
+pub struct GizmoPickingPlugin;
+
+impl Plugin for GizmoPickingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PickableGizmos>()
+            .init_resource::<GizmoHoverMap>()
+            .add_event::<GizmoPointer<Over>>()
+            .add_event::<GizmoPointer<Out>>()
+            .add_event::<GizmoPointer<Click>>()
+            .add_systems(PreUpdate, gizmo_picking.in_set(PickSet::Events));
+    }
+}
+
+/// Keeps the lines of the [`GizmoConfigGroup`] `T` for picking, before they are rendered.
+pub(crate) fn add_gizmo_picking_systems<T: GizmoConfigGroup>(app: &mut App) {
+    app.add_systems(
+        Last,
+        collect_pickable_gizmos::<T>
+            .after(draw_mesh_gizmos::<T>)
+            .before(update_gizmo_meshes::<T>),
+    );
+}
+
+/// Which gizmo lines a [`GizmoPointer`] event targets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GizmoPickTarget {
+    /// The [`TypeId`] of the [`GizmoConfigGroup`] the lines were drawn with.
+    pub group: TypeId,
+    /// The id given to the lines with [`Gizmos::with_pick_id`](crate::gizmos::Gizmos::with_pick_id),
+    /// if any.
+    pub pick_id: Option<u64>,
+}
+
+impl GizmoPickTarget {
+    /// Returns `true` if the lines were drawn with the [`GizmoConfigGroup`] `T`.
+    pub fn is_group<T: GizmoConfigGroup>(&self) -> bool {
+        self.group == TypeId::of::<T>()
+    }
+}
+
+/// An event sent by a pointer to the gizmo lines it hovers or clicks.
+///
+/// `E` is one of the [`Over`], [`Out`] and [`Click`] events of `bevy_picking`, and this
+/// dereferences to it.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct GizmoPointer<E> {
+    /// The lines targeted by the event.
+    pub target: GizmoPickTarget,
+    /// The pointer that sent the event.
+    pub pointer_id: PointerId,
+    /// The location of the pointer when the event was sent.
+    pub pointer_location: Location,
+    /// The data of the event.
+    pub event: E,
+}
+
+impl<E> Deref for GizmoPointer<E> {
+    type Target = E;
+
+    fn deref(&self) -> &Self::Target {
+        &self.event
+    }
+}
+
+/// The gizmo lines hovered by a pointer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GizmoHover {
+    /// The hovered lines.
+    pub target: GizmoPickTarget,
+    /// Where the lines were hit.
+    pub hit: HitData,
+    /// The location of the pointer.
+    pub pointer_location: Location,
+}
+
+/// The gizmo lines hovered by each pointer, for the pointers that hover any.
+#[derive(Resource, Debug, Default, Clone, Deref, DerefMut)]
+pub struct GizmoHoverMap(pub HashMap<PointerId, GizmoHover>);
+
+/// The lines drawn last frame with each [`GizmoConfigGroup`], which are the ones rendered.
+#[derive(Resource, Default)]
+pub(crate) struct PickableGizmos(TypeIdMap<Vec<PickableLines>>);
+
+/// The lines of a group drawn to one set of render layers.
+struct PickableLines {
+    render_layers: RenderLayers,
+    /// The distance from the lines within which they are hit, in logical pixels.
+    radius: f32,
+    segments: Vec<PickableSegment>,
+}
+
+struct PickableSegment {
+    start: Vec3,
+    end: Vec3,
+    pick_id: Option<u64>,
+}
+
+/// Copies the lines drawn with the [`GizmoConfigGroup`] `T` to the [`PickableGizmos`], if the
+/// [`GizmoPickingPlugin`] was added.
+pub(crate) fn collect_pickable_gizmos<T: GizmoConfigGroup>(
+    pickable: Option<ResMut<PickableGizmos>>,
+    storage: Res<GizmoStorage<T>>,
+    config_store: Res<GizmoConfigStore>,
+) {
+    let Some(mut pickable) = pickable else {
+        return;
+    };
+    let group = pickable.0.entry(TypeId::of::<T>()).or_default();
+    group.clear();
+
+    let (config, _) = config_store.config::<T>();
+    if !config.enabled {
+        return;
+    }
+    let layered = storage
+        .layered
+        .iter()
+        .map(|(render_layers, lines)| (*render_layers, lines));
+    for (render_layers, lines) in iter::once((config.render_layers, &storage.lines)).chain(layered)
+    {
+        let segments = pickable_segments(lines);
+        if !segments.is_empty() {
+            group.push(PickableLines {
+                render_layers,
+                radius: config.line_width / 2. + PICK_MARGIN,
+                segments,
+            });
+        }
+    }
+}
+
+/// Returns the segments of the line-list and line-strip vertices of `lines`, with their pick id.
+fn pickable_segments(lines: &GizmoLines) -> Vec<PickableSegment> {
+    // The pick id of each vertex, with the innermost ranges written last.
+    let mut list_ids = vec![None; lines.list_positions.len()];
+    let mut strip_ids = vec![None; lines.strip_positions.len()];
+    for range in lines.pick_ids.iter().rev() {
+        list_ids[range.list.clone()].fill(Some(range.pick_id));
+        strip_ids[range.strip.clone()].fill(Some(range.pick_id));
+    }
+
+    let list = lines
+        .list_positions
+        .chunks_exact(2)
+        .zip(list_ids.iter().step_by(2))
+        .map(|(segment, &pick_id)| PickableSegment {
+            start: segment[0].into(),
+            end: segment[1].into(),
+            pick_id,
+        });
+    // Strips are separated by NaN vertices.
+    let strip = lines
+        .strip_positions
+        .windows(2)
+        .zip(&strip_ids)
+        .filter(|(segment, _)| !segment[0][0].is_nan() && !segment[1][0].is_nan())
+        .map(|(segment, &pick_id)| PickableSegment {
+            start: segment[0].into(),
+            end: segment[1].into(),
+            pick_id,
+        });
+    list.chain(strip).collect()
+}
+
+/// Updates the [`GizmoHoverMap`] with the nearest gizmo lines under each pointer, and sends the
+/// [`GizmoPointer`] events.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn gizmo_picking(
+    pointers: Query<(&PointerId, &PointerLocation)>,
+    cameras: Query<(Entity, &Camera, &GlobalTransform, Option<&RenderLayers>)>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    pickable: Res<PickableGizmos>,
+    mut inputs: EventReader<PointerInput>,
+    mut hover_map: ResMut<GizmoHoverMap>,
+    mut presses: Local<HashMap<(PointerId, PointerButton), (GizmoPickTarget, Instant)>>,
+    mut over_events: EventWriter<GizmoPointer<Over>>,
+    mut out_events: EventWriter<GizmoPointer<Out>>,
+    mut click_events: EventWriter<GizmoPointer<Click>>,
+) {
+    let primary_window = primary_window.iter().next();
+
+    let mut hovered = HashMap::new();
+    for (pointer_id, pointer_location) in &pointers {
+        let Some(location) = pointer_location.location() else {
+            continue;
+        };
+        // The nearest hit, from the camera with the highest order.
+        let mut nearest: Option<(isize, GizmoPickTarget, HitData)> = None;
+        for (camera_entity, camera, camera_transform, camera_layers) in &cameras {
+            if !camera.is_active || !location.is_in_viewport(camera, primary_window) {
+                continue;
+            }
+            let (Some(viewport), Some(viewport_size)) = (
+                camera.logical_viewport_rect(),
+                camera.logical_viewport_size(),
+            ) else {
+                continue;
+            };
+            let pointer = location.position - viewport.min;
+            let world_to_clip =
+                camera.projection_matrix() * camera_transform.compute_matrix().inverse();
+            let camera_layers = camera_layers.copied().unwrap_or_default();
+
+            for (&group, lines) in &pickable.0 {
+                for lines in lines
+                    .iter()
+                    .filter(|lines| lines.render_layers.intersects(&camera_layers))
+                {
+                    for segment in &lines.segments {
+                        let Some(along) = segment_hit(
+                            world_to_clip,
+                            viewport_size,
+                            pointer,
+                            segment.start,
+                            segment.end,
+                            lines.radius,
+                        ) else {
+                            continue;
+                        };
+                        let position = segment.start.lerp(segment.end, along);
+                        let depth = position.distance(camera_transform.translation());
+                        let is_nearer = nearest.as_ref().map_or(true, |(order, _, hit)| {
+                            camera.order > *order || (camera.order == *order && depth < hit.depth)
+                        });
+                        if is_nearer {
+                            let target = GizmoPickTarget {
+                                group,
+                                pick_id: segment.pick_id,
+                            };
+                            let hit = HitData::new(camera_entity, depth, Some(position), None);
+                            nearest = Some((camera.order, target, hit));
+                        }
+                    }
+                }
+            }
+        }
+        if let Some((_, target, hit)) = nearest {
+            let hover = GizmoHover {
+                target,
+                hit,
+                pointer_location: location.clone(),
+            };
+            hovered.insert(*pointer_id, hover);
+        }
+    }
+
+    for (pointer_id, previous) in hover_map.iter() {
+        if hovered.get(pointer_id).map(|hover| hover.target) != Some(previous.target) {
+            out_events.send(GizmoPointer {
+                target: previous.target,
+                pointer_id: *pointer_id,
+                pointer_location: previous.pointer_location.clone(),
+                event: Out {
+                    hit: previous.hit.clone(),
+                },
+            });
+        }
+    }
+    for (pointer_id, hover) in &hovered {
+        if hover_map.get(pointer_id).map(|previous| previous.target) != Some(hover.target) {
+            over_events.send(GizmoPointer {
+                target: hover.target,
+                pointer_id: *pointer_id,
+                pointer_location: hover.pointer_location.clone(),
+                event: Over {
+                    hit: hover.hit.clone(),
+                },
+            });
+        }
+    }
+
+    // A click is a press and a release of a button on the same gizmo lines.
+    for input in inputs.read() {
+        let hover = hovered.get(&input.pointer_id);
+        match input.action {
+            PointerAction::Pressed(button) => {
+                if let Some(hover) = hover {
+                    presses.insert((input.pointer_id, button), (hover.target, Instant::now()));
+                }
+            }
+            PointerAction::Released(button) => {
+                let Some((target, pressed_at)) = presses.remove(&(input.pointer_id, button)) else {
+                    continue;
+                };
+                let Some(hover) = hover.filter(|hover| hover.target == target) else {
+                    continue;
+                };
+                click_events.send(GizmoPointer {
+                    target,
+                    pointer_id: input.pointer_id,
+                    pointer_location: input.location.clone(),
+                    event: Click {
+                        button,
+                        hit: hover.hit.clone(),
+                        duration: pressed_at.elapsed(),
+                    },
+                });
+            }
+            PointerAction::Canceled => {
+                presses.retain(|(pointer_id, _), _| *pointer_id != input.pointer_id);
+            }
+            PointerAction::Moved { .. } => {}
+        }
+    }
+
+    hover_map.0 = hovered;
+}
+
+/// Returns where the segment from `start` to `end` passes within `radius` of `pointer`, as the
+/// fraction of the segment from `start`, if it does.
+///
+/// The segment is transformed to clip space by `world_to_clip`, and `pointer` is relative to the
+/// top-left corner of the viewport of size `viewport_size`, both in logical pixels.
+fn segment_hit(
+    world_to_clip: Mat4,
+    viewport_size: Vec2,
+    pointer: Vec2,
+    start: Vec3,
+    end: Vec3,
+    radius: f32,
+) -> Option<f32> {
+    // Only keep the part of the segment between the near and far planes, where the depth is
+    // between 0 and 1.
+    let mut clip = [
+        world_to_clip * start.extend(1.),
+        world_to_clip * end.extend(1.),
+    ];
+    let mut fractions = [0., 1.];
+    let planes: [fn(Vec4) -> f32; 2] = [|clip| clip.w - clip.z, |clip| clip.z];
+    for plane in planes {
+        let [a, b] = clip.map(plane);
+        if a < 0. && b < 0. {
+            return None;
+        }
+        if a < 0. || b < 0. {
+            let fraction = a / (a - b);
+            let index = if a < 0. { 0 } else { 1 };
+            clip[index] = clip[0].lerp(clip[1], fraction);
+            fractions[index] = fractions[0] + (fractions[1] - fractions[0]) * fraction;
+        }
+    }
+
+    let to_viewport = |clip: Vec4| {
+        let ndc = clip.xy() / clip.w;
+        Vec2::new(ndc.x + 1., 1. - ndc.y) / 2. * viewport_size
+    };
+    let [a, b] = clip.map(to_viewport);
+    let segment = b - a;
+    let along = if segment.length_squared() > 0. {
+        ((pointer - a).dot(segment) / segment.length_squared()).clamp(0., 1.)
+    } else {
+        0.
+    };
+    if (a + segment * along).distance(pointer) > radius {
+        return None;
+    }
+
+    // Positions are interpolated linearly in clip space, not in viewport space.
+    let along = along * clip[0].w / ((1. - along) * clip[1].w + along * clip[0].w);
+    Some(fractions[0] + (fractions[1] - fractions[0]) * along)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_render::camera::{CameraProjection, PerspectiveProjection};
+    use bevy_transform::components::Transform;
+
+    #[test]
+    fn segment_hits() {
+        let viewport_size = Vec2::new(800., 600.);
+        let mut projection = PerspectiveProjection::default();
+        projection.update(viewport_size.x, viewport_size.y);
+        let camera = Transform::from_xyz(0., 0., 10.).looking_at(Vec3::ZERO, Vec3::Y);
+        let world_to_clip = projection.get_projection_matrix() * camera.compute_matrix().inverse();
+        let center = viewport_size / 2.;
+        let hit = |pointer, start, end| {
+            segment_hit(world_to_clip, viewport_size, pointer, start, end, 4.)
+        };
+
+        let along = hit(center, Vec3::new(-1., 0., 0.), Vec3::new(3., 0., 0.)).unwrap();
+        assert!((along - 0.25).abs() < 1e-4);
+        assert!(hit(center + Vec2::new(0., 10.), Vec3::NEG_X, Vec3::X).is_none());
+        assert!(hit(center + Vec2::new(0., 3.), Vec3::NEG_X, Vec3::X).is_some());
+
+        // The hit point is perspective correct on segments going away from the camera.
+        let along = hit(center, Vec3::new(-1., 0., 5.), Vec3::new(3., 0., -5.)).unwrap();
+        assert!((along - 0.25).abs() < 1e-4);
+
+        // Behind the camera, or crossing it.
+        assert!(hit(center, Vec3::new(-1., 0., 12.), Vec3::new(1., 0., 12.)).is_none());
+        let along = hit(center, Vec3::new(0., 0., 20.), Vec3::new(0., 0., -20.));
+        assert!(along.is_some_and(|along| along > 10. / 40.));
+    }
+}
@@ -1,6 +1,9 @@
 use crate::{
-    config::GizmoMeshConfig, line_gizmo_vertex_buffer_layouts, DrawLineGizmo, GizmoRenderSystem,
-    LineGizmo, LineGizmoUniformBindgroupLayout, SetLineGizmoBindGroup, LINE_SHADER_HANDLE,
+    buffers::{prepare_gizmos, RenderGizmos},
+    config::{GizmoDepthTest, GizmoMeshConfig},
+    filled_gizmo_vertex_buffer_layouts, line_gizmo_vertex_buffer_layouts, DrawFilledGizmo,
+    DrawLineGizmo, FilledGizmo, GizmoRenderSystem, LineGizmo, LineGizmoUniformBindgroupLayout,
+    SetLineGizmoBindGroup, FILLED_SHADER_HANDLE, LINE_SHADER_HANDLE,
 };
 use bevy_app::{App, Plugin};
 use bevy_asset::Handle;
@@ -13,7 +16,6 @@ use bevy_ecs::{
     world::{FromWorld, World},
 };
 use bevy_render::{
-    render_asset::{prepare_assets, RenderAssets},
     render_phase::{AddRenderCommand, DrawFunctions, RenderPhase, SetItemPipeline},
     render_resource::*,
     texture::BevyDefault,
@@ -33,16 +35,20 @@ impl Plugin for LineGizmo2dPlugin {
 
         render_app
             .add_render_command::<Transparent2d, DrawLineGizmo2d>()
+            .add_render_command::<Transparent2d, DrawFilledGizmo2d>()
             .init_resource::<SpecializedRenderPipelines<LineGizmoPipeline>>()
+            .init_resource::<SpecializedRenderPipelines<FilledGizmoPipeline>>()
             .configure_sets(
                 Render,
                 GizmoRenderSystem::QueueLineGizmos2d.in_set(RenderSet::Queue),
             )
             .add_systems(
                 Render,
-                queue_line_gizmos_2d
-                    .in_set(GizmoRenderSystem::QueueLineGizmos2d)
-                    .after(prepare_assets::<LineGizmo>),
+                (
+                    queue_line_gizmos_2d.after(prepare_gizmos::<LineGizmo>),
+                    queue_filled_gizmos_2d.after(prepare_gizmos::<FilledGizmo>),
+                )
+                    .in_set(GizmoRenderSystem::QueueLineGizmos2d),
             );
     }
 
@@ -51,7 +57,9 @@ impl Plugin for LineGizmo2dPlugin {
             return;
         };
 
-        render_app.init_resource::<LineGizmoPipeline>();
+        render_app
+            .init_resource::<LineGizmoPipeline>()
+            .init_resource::<FilledGizmoPipeline>();
     }
 }
 
@@ -145,7 +153,7 @@ fn queue_line_gizmos_2d(
     pipeline_cache: Res<PipelineCache>,
     msaa: Res<Msaa>,
     line_gizmos: Query<(Entity, &Handle<LineGizmo>, &GizmoMeshConfig)>,
-    line_gizmo_assets: Res<RenderAssets<LineGizmo>>,
+    line_gizmo_assets: Res<RenderGizmos<LineGizmo>>,
     mut views: Query<(
         &ExtractedView,
         &mut RenderPhase<Transparent2d>,
@@ -160,7 +168,10 @@ fn queue_line_gizmos_2d(
 
         for (entity, handle, config) in &line_gizmos {
             let render_layers = render_layers.copied().unwrap_or_default();
-            if !config.render_layers.intersects(&render_layers) {
+            // 2D has no depth, so nothing is hidden
+            if config.depth_test == GizmoDepthTest::Occluded
+                || !config.render_layers.intersects(&render_layers)
+            {
                 continue;
             }
 
@@ -188,3 +199,109 @@ fn queue_line_gizmos_2d(
         }
     }
 }
+
+#[derive(Clone, Resource)]
+struct FilledGizmoPipeline {
+    mesh_pipeline: Mesh2dPipeline,
+}
+
+impl FromWorld for FilledGizmoPipeline {
+    fn from_world(render_world: &mut World) -> Self {
+        FilledGizmoPipeline {
+            mesh_pipeline: render_world.resource::<Mesh2dPipeline>().clone(),
+        }
+    }
+}
+
+impl SpecializedRenderPipeline for FilledGizmoPipeline {
+    type Key = Mesh2dPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let format = if key.contains(Mesh2dPipelineKey::HDR) {
+            ViewTarget::TEXTURE_FORMAT_HDR
+        } else {
+            TextureFormat::bevy_default()
+        };
+
+        RenderPipelineDescriptor {
+            vertex: VertexState {
+                shader: FILLED_SHADER_HANDLE,
+                entry_point: "vertex".into(),
+                shader_defs: vec![],
+                buffers: filled_gizmo_vertex_buffer_layouts(),
+            },
+            fragment: Some(FragmentState {
+                shader: FILLED_SHADER_HANDLE,
+                shader_defs: vec![],
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: Some(BlendState::ALPHA_BLENDING),
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            layout: vec![self.mesh_pipeline.view_layout.clone()],
+            primitive: PrimitiveState {
+                topology: PrimitiveTopology::TriangleList,
+                cull_mode: None,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: MultisampleState {
+                count: key.msaa_samples(),
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            label: Some("FilledGizmo Pipeline 2D".into()),
+            push_constant_ranges: vec![],
+        }
+    }
+}
+
+type DrawFilledGizmo2d = (SetItemPipeline, SetMesh2dViewBindGroup<0>, DrawFilledGizmo);
+
+#[allow(clippy::too_many_arguments)]
+fn queue_filled_gizmos_2d(
+    draw_functions: Res<DrawFunctions<Transparent2d>>,
+    pipeline: Res<FilledGizmoPipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<FilledGizmoPipeline>>,
+    pipeline_cache: Res<PipelineCache>,
+    msaa: Res<Msaa>,
+    filled_gizmos: Query<(Entity, &Handle<FilledGizmo>, &GizmoMeshConfig)>,
+    filled_gizmo_assets: Res<RenderGizmos<FilledGizmo>>,
+    mut views: Query<(
+        &ExtractedView,
+        &mut RenderPhase<Transparent2d>,
+        Option<&RenderLayers>,
+    )>,
+) {
+    let draw_function = draw_functions.read().get_id::<DrawFilledGizmo2d>().unwrap();
+
+    for (view, mut transparent_phase, render_layers) in &mut views {
+        let mesh_key = Mesh2dPipelineKey::from_msaa_samples(msaa.samples())
+            | Mesh2dPipelineKey::from_hdr(view.hdr);
+
+        for (entity, handle, config) in &filled_gizmos {
+            let render_layers = render_layers.copied().unwrap_or_default();
+            if !config.render_layers.intersects(&render_layers) {
+                continue;
+            }
+
+            if filled_gizmo_assets.get(handle).is_none() {
+                continue;
+            }
+
+            let pipeline = pipelines.specialize(&pipeline_cache, &pipeline, mesh_key);
+
+            transparent_phase.add(Transparent2d {
+                entity,
+                draw_function,
+                pipeline,
+                // Filled gizmos are drawn before the line gizmos, sorted at infinity
+                sort_key: FloatOrd(f32::MAX),
+                batch_range: 0..1,
+                dynamic_offset: None,
+            });
+        }
+    }
+}
@@ -1,11 +1,13 @@
 use crate::{
-    config::GizmoMeshConfig, line_gizmo_vertex_buffer_layouts, DrawLineGizmo, GizmoRenderSystem,
-    LineGizmo, LineGizmoUniformBindgroupLayout, SetLineGizmoBindGroup, LINE_SHADER_HANDLE,
+    buffers::{prepare_gizmos, RenderGizmos},
+    config::{GizmoDepthTest, GizmoMeshConfig},
+    line_gizmo_vertex_buffer_layouts, DrawLineGizmo, GizmoRenderSystem, LineGizmo,
+    LineGizmoUniformBindgroupLayout, SetLineGizmoBindGroup, LINE_SHADER_HANDLE,
 };
 use bevy_app::{App, Plugin};
 use bevy_asset::Handle;
 use bevy_core_pipeline::{
-    core_3d::{Transparent3d, CORE_3D_DEPTH_FORMAT},
+    core_3d::{Camera3d, Transparent3d},
     prepass::{DeferredPrepass, DepthPrepass, MotionVectorPrepass, NormalPrepass},
 };
 
@@ -18,7 +20,6 @@ use bevy_ecs::{
 };
 use bevy_pbr::{MeshPipeline, MeshPipelineKey, SetMeshViewBindGroup};
 use bevy_render::{
-    render_asset::{prepare_assets, RenderAssets},
     render_phase::{AddRenderCommand, DrawFunctions, RenderPhase, SetItemPipeline},
     render_resource::*,
     texture::BevyDefault,
@@ -44,7 +45,7 @@ impl Plugin for LineGizmo3dPlugin {
                 Render,
                 queue_line_gizmos_3d
                     .in_set(GizmoRenderSystem::QueueLineGizmos3d)
-                    .after(prepare_assets::<LineGizmo>),
+                    .after(prepare_gizmos::<LineGizmo>),
             );
     }
 
@@ -80,6 +81,7 @@ struct LineGizmoPipelineKey {
     view_key: MeshPipelineKey,
     strip: bool,
     perspective: bool,
+    depth_test: GizmoDepthTest,
 }
 
 impl SpecializedRenderPipeline for LineGizmoPipeline {
@@ -108,6 +110,13 @@ impl SpecializedRenderPipeline for LineGizmoPipeline {
 
         let layout = vec![view_layout, self.uniform_layout.clone()];
 
+        // reversed Z, greater depths are closer to the camera
+        let (depth_compare, depth_write_enabled) = match key.depth_test {
+            GizmoDepthTest::Visible => (CompareFunction::Greater, true),
+            GizmoDepthTest::Always => (CompareFunction::Always, false),
+            GizmoDepthTest::Occluded => (CompareFunction::LessEqual, false),
+        };
+
         RenderPipelineDescriptor {
             vertex: VertexState {
                 shader: LINE_SHADER_HANDLE,
@@ -128,9 +137,9 @@ impl SpecializedRenderPipeline for LineGizmoPipeline {
             layout,
             primitive: PrimitiveState::default(),
             depth_stencil: Some(DepthStencilState {
-                format: CORE_3D_DEPTH_FORMAT,
-                depth_write_enabled: true,
-                depth_compare: CompareFunction::Greater,
+                format: key.view_key.depth_stencil_format(),
+                depth_write_enabled,
+                depth_compare,
                 stencil: StencilState::default(),
                 bias: DepthBiasState::default(),
             }),
@@ -160,7 +169,7 @@ fn queue_line_gizmos_3d(
     pipeline_cache: Res<PipelineCache>,
     msaa: Res<Msaa>,
     line_gizmos: Query<(Entity, &Handle<LineGizmo>, &GizmoMeshConfig)>,
-    line_gizmo_assets: Res<RenderAssets<LineGizmo>>,
+    line_gizmo_assets: Res<RenderGizmos<LineGizmo>>,
     mut views: Query<(
         &ExtractedView,
         &mut RenderPhase<Transparent3d>,
@@ -171,6 +180,7 @@ fn queue_line_gizmos_3d(
             Has<MotionVectorPrepass>,
             Has<DeferredPrepass>,
         ),
+        Option<&Camera3d>,
     )>,
 ) {
     let draw_function = draw_functions.read().get_id::<DrawLineGizmo3d>().unwrap();
@@ -180,6 +190,7 @@ fn queue_line_gizmos_3d(
         mut transparent_phase,
         render_layers,
         (normal_prepass, depth_prepass, motion_vector_prepass, deferred_prepass),
+        camera_3d,
     ) in &mut views
     {
         let render_layers = render_layers.copied().unwrap_or_default();
@@ -203,6 +214,10 @@ fn queue_line_gizmos_3d(
             view_key |= MeshPipelineKey::DEFERRED_PREPASS;
         }
 
+        if camera_3d.is_some_and(|camera_3d| camera_3d.stencil.is_some()) {
+            view_key |= MeshPipelineKey::DEPTH_STENCIL;
+        }
+
         for (entity, handle, config) in &line_gizmos {
             if !config.render_layers.intersects(&render_layers) {
                 continue;
@@ -219,6 +234,7 @@ fn queue_line_gizmos_3d(
                     view_key,
                     strip: line_gizmo.strip,
                     perspective: config.line_perspective,
+                    depth_test: config.depth_test,
                 },
             );
 
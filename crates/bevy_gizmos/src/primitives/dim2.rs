@@ -1,6 +1,6 @@
 //! A module for rendering each of the 2D [`bevy_math::primitives`] with [`Gizmos`].
 
-use std::f32::consts::PI;
+use std::f32::consts::{FRAC_PI_2, PI};
 
 use super::helpers::*;
 
@@ -11,6 +11,7 @@ use bevy_math::primitives::{
 use bevy_math::{Mat2, Vec2};
 use bevy_render::color::Color;
 
+use crate::circles::DEFAULT_CIRCLE_SEGMENTS;
 use crate::prelude::{GizmoConfigGroup, Gizmos};
 
 // some magic number since using directions as offsets will result in lines of length 1 pixel
@@ -543,3 +544,526 @@ impl<'w, 's, T: GizmoConfigGroup> GizmoPrimitive2d<RegularPolygon> for Gizmos<'w
         self.linestrip_2d(points, color);
     }
 }
+
+/// A trait for rendering filled 2D geometric primitives (`P`) with [`Gizmos`].
+///
+/// The primitives are triangulated and drawn below the lines. Use a translucent color to keep
+/// overlaps and coverage readable, for example when debugging colliders:
+///
+/// ```
+/// # use bevy_gizmos::prelude::*;
+/// # use bevy_math::prelude::*;
+/// # use bevy_math::primitives::Circle;
+/// # use bevy_render::prelude::*;
+/// fn system(mut gizmos: Gizmos) {
+///     let collider = Circle { radius: 1.0 };
+///     gizmos.primitive_2d_filled(collider, Vec2::ZERO, 0.0, Color::GREEN.with_a(0.3));
+///     gizmos.primitive_2d(collider, Vec2::ZERO, 0.0, Color::GREEN);
+/// }
+/// # bevy_ecs::system::assert_is_system(system);
+/// ```
+pub trait GizmoFilledPrimitive2d<P: Primitive2d> {
+    /// The output of `primitive_2d_filled`. This is a builder to set non-default values.
+    type Output<'a>
+    where
+        Self: 'a;
+
+    /// Renders a filled 2D primitive with its associated details.
+    fn primitive_2d_filled(
+        &mut self,
+        primitive: P,
+        position: Vec2,
+        angle: f32,
+        color: Color,
+    ) -> Self::Output<'_>;
+}
+
+// filled circle 2d
+
+impl<'w, 's, T: GizmoConfigGroup> GizmoFilledPrimitive2d<Circle> for Gizmos<'w, 's, T> {
+    type Output<'a> = () where Self: 'a;
+
+    fn primitive_2d_filled(
+        &mut self,
+        primitive: Circle,
+        position: Vec2,
+        angle: f32,
+        color: Color,
+    ) -> Self::Output<'_> {
+        self.primitive_2d_filled(
+            Ellipse {
+                half_size: Vec2::splat(primitive.radius),
+            },
+            position,
+            angle,
+            color,
+        );
+    }
+}
+
+// filled ellipse 2d
+
+impl<'w, 's, T: GizmoConfigGroup> GizmoFilledPrimitive2d<Ellipse> for Gizmos<'w, 's, T> {
+    type Output<'a> = () where Self: 'a;
+
+    fn primitive_2d_filled(
+        &mut self,
+        primitive: Ellipse,
+        position: Vec2,
+        angle: f32,
+        color: Color,
+    ) -> Self::Output<'_> {
+        if !self.enabled {
+            return;
+        }
+
+        let outline: Vec<Vec2> = circle_coordinates(1.0, DEFAULT_CIRCLE_SEGMENTS)
+            .map(|point| point * primitive.half_size)
+            .map(rotate_then_translate_2d(angle, position))
+            .collect();
+        self.triangles_2d(fan_triangles(position, &outline), color);
+    }
+}
+
+// filled capsule 2d
+
+impl<'w, 's, T: GizmoConfigGroup> GizmoFilledPrimitive2d<Capsule2d> for Gizmos<'w, 's, T> {
+    type Output<'a> = () where Self: 'a;
+
+    fn primitive_2d_filled(
+        &mut self,
+        primitive: Capsule2d,
+        position: Vec2,
+        angle: f32,
+        color: Color,
+    ) -> Self::Output<'_> {
+        if !self.enabled {
+            return;
+        }
+
+        // the two half circles, going around from the left side of the top one
+        let half_segments = DEFAULT_CIRCLE_SEGMENTS / 2;
+        let outline: Vec<Vec2> = [(-FRAC_PI_2, 1.0), (FRAC_PI_2, -1.0)]
+            .into_iter()
+            .flat_map(|(start_angle, sign)| {
+                (0..=half_segments).map(move |i| {
+                    let angle = start_angle + i as f32 * PI / half_segments as f32;
+                    Vec2::from(angle.sin_cos()) * primitive.radius
+                        + Vec2::Y * sign * primitive.half_length
+                })
+            })
+            .map(rotate_then_translate_2d(angle, position))
+            .collect();
+        self.triangles_2d(fan_triangles(position, &outline), color);
+    }
+}
+
+// filled triangle 2d
+
+impl<'w, 's, T: GizmoConfigGroup> GizmoFilledPrimitive2d<Triangle2d> for Gizmos<'w, 's, T> {
+    type Output<'a> = () where Self: 'a;
+
+    fn primitive_2d_filled(
+        &mut self,
+        primitive: Triangle2d,
+        position: Vec2,
+        angle: f32,
+        color: Color,
+    ) -> Self::Output<'_> {
+        if !self.enabled {
+            return;
+        }
+
+        let positions = primitive
+            .vertices
+            .map(rotate_then_translate_2d(angle, position));
+        self.triangles_2d(positions, color);
+    }
+}
+
+// filled rectangle 2d
+
+impl<'w, 's, T: GizmoConfigGroup> GizmoFilledPrimitive2d<Rectangle> for Gizmos<'w, 's, T> {
+    type Output<'a> = () where Self: 'a;
+
+    fn primitive_2d_filled(
+        &mut self,
+        primitive: Rectangle,
+        position: Vec2,
+        angle: f32,
+        color: Color,
+    ) -> Self::Output<'_> {
+        if !self.enabled {
+            return;
+        }
+
+        let [a, b, c, d] = [(1.0, 1.0), (1.0, -1.0), (-1.0, -1.0), (-1.0, 1.0)]
+            .map(|(sign_x, sign_y)| primitive.half_size * Vec2::new(sign_x, sign_y))
+            .map(rotate_then_translate_2d(angle, position));
+        self.triangles_2d([a, b, c, a, c, d], color);
+    }
+}
+
+// filled polygon 2d
+
+impl<'w, 's, const N: usize, T: GizmoConfigGroup> GizmoFilledPrimitive2d<Polygon<N>>
+    for Gizmos<'w, 's, T>
+{
+    type Output<'a> = () where Self: 'a;
+
+    fn primitive_2d_filled(
+        &mut self,
+        primitive: Polygon<N>,
+        position: Vec2,
+        angle: f32,
+        color: Color,
+    ) -> Self::Output<'_> {
+        if !self.enabled {
+            return;
+        }
+
+        let vertices = primitive
+            .vertices
+            .map(rotate_then_translate_2d(angle, position));
+        self.triangles_2d(triangulate_polygon(&vertices), color);
+    }
+}
+
+// filled boxed polygon 2d
+
+impl<'w, 's, T: GizmoConfigGroup> GizmoFilledPrimitive2d<BoxedPolygon> for Gizmos<'w, 's, T> {
+    type Output<'a> = () where Self: 'a;
+
+    fn primitive_2d_filled(
+        &mut self,
+        primitive: BoxedPolygon,
+        position: Vec2,
+        angle: f32,
+        color: Color,
+    ) -> Self::Output<'_> {
+        if !self.enabled {
+            return;
+        }
+
+        let vertices: Vec<Vec2> = primitive
+            .vertices
+            .iter()
+            .copied()
+            .map(rotate_then_translate_2d(angle, position))
+            .collect();
+        self.triangles_2d(triangulate_polygon(&vertices), color);
+    }
+}
+
+// filled regular polygon 2d
+
+impl<'w, 's, T: GizmoConfigGroup> GizmoFilledPrimitive2d<RegularPolygon> for Gizmos<'w, 's, T> {
+    type Output<'a> = () where Self: 'a;
+
+    fn primitive_2d_filled(
+        &mut self,
+        primitive: RegularPolygon,
+        position: Vec2,
+        angle: f32,
+        color: Color,
+    ) -> Self::Output<'_> {
+        if !self.enabled {
+            return;
+        }
+
+        let outline: Vec<Vec2> = circle_coordinates(primitive.circumcircle.radius, primitive.sides)
+            .map(rotate_then_translate_2d(angle, position))
+            .collect();
+        self.triangles_2d(fan_triangles(position, &outline), color);
+    }
+}
+
+/// A trait for rendering 2D geometric primitives (`P`) with [`Gizmos`], coloring each vertex of
+/// their outline separately.
+///
+/// The colors are blended along the lines between vertices, which makes for heatmap-style debug
+/// views, for example of the speed along a trajectory:
+///
+/// ```
+/// # use bevy_gizmos::prelude::*;
+/// # use bevy_math::prelude::*;
+/// # use bevy_math::primitives::Polyline2d;
+/// # use bevy_render::prelude::*;
+/// fn system(mut gizmos: Gizmos) {
+///     let trajectory = Polyline2d::<3>::new([Vec2::ZERO, Vec2::X, Vec2::new(3.0, 1.0)]);
+///     let speeds = [0.0, 1.0, 2.0];
+///     let colors = speeds.map(|speed| Color::hsl(240.0 - speed * 120.0, 1.0, 0.5));
+///     gizmos.primitive_2d_gradient(trajectory, Vec2::ZERO, 0.0, colors);
+/// }
+/// # bevy_ecs::system::assert_is_system(system);
+/// ```
+pub trait GizmoGradientPrimitive2d<P: Primitive2d> {
+    /// The output of `primitive_2d_gradient`. This is a builder to set non-default values.
+    type Output<'a>
+    where
+        Self: 'a;
+
+    /// Renders the outline of a 2D primitive with one color per vertex.
+    ///
+    /// The colors are matched to the vertices in order, and the last color is repeated if there
+    /// are fewer colors than vertices. Closed outlines return to their first vertex in its
+    /// color. Nothing is drawn without any color.
+    fn primitive_2d_gradient(
+        &mut self,
+        primitive: P,
+        position: Vec2,
+        angle: f32,
+        colors: impl IntoIterator<Item = Color>,
+    ) -> Self::Output<'_>;
+}
+
+/// Pairs each vertex with its color for [`GizmoGradientPrimitive2d`], repeating the last color
+/// and closing the strip on its first vertex if `closed` is set.
+fn gradient_strip(
+    vertices: impl IntoIterator<Item = Vec2>,
+    colors: impl IntoIterator<Item = Color>,
+    closed: bool,
+) -> Vec<(Vec2, Color)> {
+    let mut colors = colors.into_iter();
+    let mut color = None;
+    let mut strip: Vec<_> = vertices
+        .into_iter()
+        .map_while(|vertex| {
+            color = colors.next().or(color);
+            color.map(|color| (vertex, color))
+        })
+        .collect();
+    if closed {
+        if let Some(&first) = strip.first() {
+            strip.push(first);
+        }
+    }
+    strip
+}
+
+// gradient segment 2d
+
+impl<'w, 's, T: GizmoConfigGroup> GizmoGradientPrimitive2d<Segment2d> for Gizmos<'w, 's, T> {
+    type Output<'a> = () where Self: 'a;
+
+    fn primitive_2d_gradient(
+        &mut self,
+        primitive: Segment2d,
+        position: Vec2,
+        angle: f32,
+        colors: impl IntoIterator<Item = Color>,
+    ) -> Self::Output<'_> {
+        if !self.enabled {
+            return;
+        }
+
+        let vertices =
+            [primitive.point1(), primitive.point2()].map(rotate_then_translate_2d(angle, position));
+        self.linestrip_gradient_2d(gradient_strip(vertices, colors, false));
+    }
+}
+
+// gradient polyline 2d
+
+impl<'w, 's, const N: usize, T: GizmoConfigGroup> GizmoGradientPrimitive2d<Polyline2d<N>>
+    for Gizmos<'w, 's, T>
+{
+    type Output<'a> = () where Self: 'a;
+
+    fn primitive_2d_gradient(
+        &mut self,
+        primitive: Polyline2d<N>,
+        position: Vec2,
+        angle: f32,
+        colors: impl IntoIterator<Item = Color>,
+    ) -> Self::Output<'_> {
+        if !self.enabled {
+            return;
+        }
+
+        let vertices = primitive
+            .vertices
+            .map(rotate_then_translate_2d(angle, position));
+        self.linestrip_gradient_2d(gradient_strip(vertices, colors, false));
+    }
+}
+
+// gradient boxed polyline 2d
+
+impl<'w, 's, T: GizmoConfigGroup> GizmoGradientPrimitive2d<BoxedPolyline2d> for Gizmos<'w, 's, T> {
+    type Output<'a> = () where Self: 'a;
+
+    fn primitive_2d_gradient(
+        &mut self,
+        primitive: BoxedPolyline2d,
+        position: Vec2,
+        angle: f32,
+        colors: impl IntoIterator<Item = Color>,
+    ) -> Self::Output<'_> {
+        if !self.enabled {
+            return;
+        }
+
+        let vertices = primitive
+            .vertices
+            .iter()
+            .copied()
+            .map(rotate_then_translate_2d(angle, position));
+        self.linestrip_gradient_2d(gradient_strip(vertices, colors, false));
+    }
+}
+
+// gradient triangle 2d
+
+impl<'w, 's, T: GizmoConfigGroup> GizmoGradientPrimitive2d<Triangle2d> for Gizmos<'w, 's, T> {
+    type Output<'a> = () where Self: 'a;
+
+    fn primitive_2d_gradient(
+        &mut self,
+        primitive: Triangle2d,
+        position: Vec2,
+        angle: f32,
+        colors: impl IntoIterator<Item = Color>,
+    ) -> Self::Output<'_> {
+        if !self.enabled {
+            return;
+        }
+
+        let vertices = primitive
+            .vertices
+            .map(rotate_then_translate_2d(angle, position));
+        self.linestrip_gradient_2d(gradient_strip(vertices, colors, true));
+    }
+}
+
+// gradient rectangle 2d
+
+impl<'w, 's, T: GizmoConfigGroup> GizmoGradientPrimitive2d<Rectangle> for Gizmos<'w, 's, T> {
+    type Output<'a> = () where Self: 'a;
+
+    fn primitive_2d_gradient(
+        &mut self,
+        primitive: Rectangle,
+        position: Vec2,
+        angle: f32,
+        colors: impl IntoIterator<Item = Color>,
+    ) -> Self::Output<'_> {
+        if !self.enabled {
+            return;
+        }
+
+        let vertices = [(1.0, 1.0), (1.0, -1.0), (-1.0, -1.0), (-1.0, 1.0)]
+            .map(|(sign_x, sign_y)| primitive.half_size * Vec2::new(sign_x, sign_y))
+            .map(rotate_then_translate_2d(angle, position));
+        self.linestrip_gradient_2d(gradient_strip(vertices, colors, true));
+    }
+}
+
+// gradient polygon 2d
+
+impl<'w, 's, const N: usize, T: GizmoConfigGroup> GizmoGradientPrimitive2d<Polygon<N>>
+    for Gizmos<'w, 's, T>
+{
+    type Output<'a> = () where Self: 'a;
+
+    fn primitive_2d_gradient(
+        &mut self,
+        primitive: Polygon<N>,
+        position: Vec2,
+        angle: f32,
+        colors: impl IntoIterator<Item = Color>,
+    ) -> Self::Output<'_> {
+        if !self.enabled {
+            return;
+        }
+
+        // Polygons that already end on their first vertex are not closed again
+        let closed = primitive.vertices.last() != primitive.vertices.first();
+        let vertices = primitive
+            .vertices
+            .map(rotate_then_translate_2d(angle, position));
+        self.linestrip_gradient_2d(gradient_strip(vertices, colors, closed));
+    }
+}
+
+// gradient boxed polygon 2d
+
+impl<'w, 's, T: GizmoConfigGroup> GizmoGradientPrimitive2d<BoxedPolygon> for Gizmos<'w, 's, T> {
+    type Output<'a> = () where Self: 'a;
+
+    fn primitive_2d_gradient(
+        &mut self,
+        primitive: BoxedPolygon,
+        position: Vec2,
+        angle: f32,
+        colors: impl IntoIterator<Item = Color>,
+    ) -> Self::Output<'_> {
+        if !self.enabled {
+            return;
+        }
+
+        let closed = primitive.vertices.last() != primitive.vertices.first();
+        let vertices = primitive
+            .vertices
+            .iter()
+            .copied()
+            .map(rotate_then_translate_2d(angle, position));
+        self.linestrip_gradient_2d(gradient_strip(vertices, colors, closed));
+    }
+}
+
+// gradient regular polygon 2d
+
+impl<'w, 's, T: GizmoConfigGroup> GizmoGradientPrimitive2d<RegularPolygon> for Gizmos<'w, 's, T> {
+    type Output<'a> = () where Self: 'a;
+
+    fn primitive_2d_gradient(
+        &mut self,
+        primitive: RegularPolygon,
+        position: Vec2,
+        angle: f32,
+        colors: impl IntoIterator<Item = Color>,
+    ) -> Self::Output<'_> {
+        if !self.enabled {
+            return;
+        }
+
+        let vertices = (0..primitive.sides)
+            .map(|p| single_circle_coordinate(primitive.circumcircle.radius, primitive.sides, p))
+            .map(rotate_then_translate_2d(angle, position));
+        self.linestrip_gradient_2d(gradient_strip(vertices, colors, true));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gradient_strip_colors() {
+        let vertices = [Vec2::ZERO, Vec2::X, Vec2::Y];
+
+        assert_eq!(
+            gradient_strip(vertices, [Color::RED, Color::GREEN], false),
+            vec![
+                (Vec2::ZERO, Color::RED),
+                (Vec2::X, Color::GREEN),
+                (Vec2::Y, Color::GREEN),
+            ]
+        );
+        assert_eq!(
+            gradient_strip(
+                vertices,
+                [Color::RED, Color::GREEN, Color::BLUE, Color::WHITE],
+                true
+            ),
+            vec![
+                (Vec2::ZERO, Color::RED),
+                (Vec2::X, Color::GREEN),
+                (Vec2::Y, Color::BLUE),
+                (Vec2::ZERO, Color::RED),
+            ]
+        );
+        assert!(gradient_strip(vertices, [], true).is_empty());
+    }
+}
@@ -33,6 +33,115 @@ pub trait GizmoPrimitive3d<P: Primitive3d> {
     ) -> Self::Output<'_>;
 }
 
+/// A trait for the drawing details of a 3D primitive, used by [`Primitive3dBuilder`].
+///
+/// The details describe the primitive in its local space, centered on the origin and in its
+/// default orientation. The builder applies the position, rotation and color when it is dropped,
+/// so implementing this trait is enough to draw a custom primitive:
+///
+/// ```
+/// # use bevy_gizmos::prelude::*;
+/// # use bevy_gizmos::primitives::dim3::{GizmoBuilder3d, GizmoPrimitive3d, Primitive3dBuilder};
+/// # use bevy_math::prelude::*;
+/// # use bevy_math::primitives::Primitive3d;
+/// # use bevy_render::prelude::*;
+/// struct Tetrahedron {
+///     size: f32,
+/// }
+///
+/// impl Primitive3d for Tetrahedron {}
+///
+/// struct TetrahedronDetails {
+///     size: f32,
+/// }
+///
+/// impl GizmoBuilder3d for TetrahedronDetails {
+///     fn linestrips(&self) -> Vec<Vec<Vec3>> {
+///         let [a, b, c, d] = [
+///             Vec3::new(1.0, 1.0, 1.0),
+///             Vec3::new(1.0, -1.0, -1.0),
+///             Vec3::new(-1.0, 1.0, -1.0),
+///             Vec3::new(-1.0, -1.0, 1.0),
+///         ]
+///         .map(|vertex| vertex * self.size);
+///         vec![vec![a, b, c, a, d, b], vec![c, d]]
+///     }
+/// }
+///
+/// impl<'w, 's, T: GizmoConfigGroup> GizmoPrimitive3d<Tetrahedron> for Gizmos<'w, 's, T> {
+///     type Output<'a> = Primitive3dBuilder<'a, 'w, 's, T, TetrahedronDetails> where Self: 'a;
+///
+///     fn primitive_3d(
+///         &mut self,
+///         primitive: Tetrahedron,
+///         position: Vec3,
+///         rotation: Quat,
+///         color: Color,
+///     ) -> Self::Output<'_> {
+///         let details = TetrahedronDetails { size: primitive.size };
+///         Primitive3dBuilder::new(self, details, position, rotation, color)
+///     }
+/// }
+/// ```
+pub trait GizmoBuilder3d {
+    /// Returns the linestrips of the primitive, in its local space.
+    fn linestrips(&self) -> Vec<Vec<Vec3>>;
+}
+
+/// Builder for configuring the drawing options of a 3D primitive, drawn with the linestrips of
+/// its [`GizmoBuilder3d`] details when dropped.
+pub struct Primitive3dBuilder<'a, 'w, 's, T: GizmoConfigGroup, B: GizmoBuilder3d> {
+    gizmos: &'a mut Gizmos<'w, 's, T>,
+
+    // Drawing details of the primitive
+    details: B,
+
+    // Center position of the primitive in 3D space
+    position: Vec3,
+    // Rotation of the primitive around its center
+    rotation: Quat,
+    // Color of the primitive
+    color: Color,
+}
+
+impl<'a, 'w, 's, T: GizmoConfigGroup, B: GizmoBuilder3d> Primitive3dBuilder<'a, 'w, 's, T, B> {
+    /// Creates a builder drawing `details` at `position` with `rotation` and `color`.
+    pub fn new(
+        gizmos: &'a mut Gizmos<'w, 's, T>,
+        details: B,
+        position: Vec3,
+        rotation: Quat,
+        color: Color,
+    ) -> Self {
+        Self {
+            gizmos,
+            details,
+            position,
+            rotation,
+            color,
+        }
+    }
+
+    /// Returns the drawing details of the primitive, to change them before it is drawn.
+    pub fn details_mut(&mut self) -> &mut B {
+        &mut self.details
+    }
+}
+
+impl<T: GizmoConfigGroup, B: GizmoBuilder3d> Drop for Primitive3dBuilder<'_, '_, '_, T, B> {
+    fn drop(&mut self) {
+        if !self.gizmos.enabled {
+            return;
+        }
+
+        let transform = rotate_then_translate_3d(self.rotation, self.position);
+        for linestrip in self.details.linestrips() {
+            self.gizmos
+                .linestrip(linestrip.into_iter().map(&transform), self.color);
+        }
+    }
+}
+
 // direction 3d
 
 impl<'w, 's, T: GizmoConfigGroup> GizmoPrimitive3d<Direction3d> for Gizmos<'w, 's, T> {
@@ -51,28 +160,23 @@ impl<'w, 's, T: GizmoConfigGroup> GizmoPrimitive3d<Direction3d> for Gizmos<'w, '
 
 // sphere
 
-/// Builder for configuring the drawing options of [`Sphere`].
-pub struct SphereBuilder<'a, 'w, 's, T: GizmoConfigGroup> {
-    gizmos: &'a mut Gizmos<'w, 's, T>,
-
+/// Drawing details of a [`Sphere`].
+#[derive(Debug, Clone, Copy)]
+pub struct SphereDetails {
     // Radius of the sphere
     radius: f32,
 
-    // Rotation of the sphere around the origin in 3D space
-    rotation: Quat,
-    // Center position of the sphere in 3D space
-    position: Vec3,
-    // Color of the sphere
-    color: Color,
-
     // Number of segments used to approximate the sphere geometry
     segments: usize,
 }
 
+/// Builder for configuring the drawing options of [`Sphere`].
+pub type SphereBuilder<'a, 'w, 's, T> = Primitive3dBuilder<'a, 'w, 's, T, SphereDetails>;
+
 impl<T: GizmoConfigGroup> SphereBuilder<'_, '_, '_, T> {
     /// Set the number of segments used to approximate the sphere geometry.
     pub fn segments(mut self, segments: usize) -> Self {
-        self.segments = segments;
+        self.details.segments = segments;
         self
     }
 }
@@ -87,67 +191,40 @@ impl<'w, 's, T: GizmoConfigGroup> GizmoPrimitive3d<Sphere> for Gizmos<'w, 's, T>
         rotation: Quat,
         color: Color,
     ) -> Self::Output<'_> {
-        SphereBuilder {
-            gizmos: self,
+        let details = SphereDetails {
             radius: primitive.radius,
-            position,
-            rotation,
-            color,
             segments: DEFAULT_NUMBER_SEGMENTS,
-        }
+        };
+        Primitive3dBuilder::new(self, details, position, rotation, color)
     }
 }
 
-impl<T: GizmoConfigGroup> Drop for SphereBuilder<'_, '_, '_, T> {
-    fn drop(&mut self) {
-        if !self.gizmos.enabled {
-            return;
-        }
-
-        let SphereBuilder {
-            radius,
-            position: center,
-            rotation,
-            color,
-            segments,
-            ..
-        } = self;
-
-        // draws the upper and lower semi spheres
-        [-1.0, 1.0].into_iter().for_each(|sign| {
-            let top = *center + (*rotation * Vec3::Y) * sign * *radius;
-            draw_semi_sphere(
-                self.gizmos,
-                *radius,
-                *segments,
-                *rotation,
-                *center,
-                top,
-                *color,
-            );
-        });
+impl GizmoBuilder3d for SphereDetails {
+    fn linestrips(&self) -> Vec<Vec<Vec3>> {
+        let SphereDetails { radius, segments } = *self;
 
-        // draws one great circle of the sphere
-        draw_circle_3d(self.gizmos, *radius, *segments, *rotation, *center, *color);
+        // the upper and lower semi spheres
+        [-1.0, 1.0]
+            .into_iter()
+            .flat_map(|sign| {
+                semi_sphere_linestrips(radius, segments, Vec3::ZERO, sign * radius * Vec3::Y)
+            })
+            // one great circle of the sphere
+            .chain(std::iter::once(circle_3d_coordinates(
+                radius, segments, 0.0,
+            )))
+            .collect()
     }
 }
 
 // plane 3d
 
-/// Builder for configuring the drawing options of [`Sphere`].
-pub struct Plane3dBuilder<'a, 'w, 's, T: GizmoConfigGroup> {
-    gizmos: &'a mut Gizmos<'w, 's, T>,
-
+/// Drawing details of a [`Plane3d`].
+#[derive(Debug, Clone, Copy)]
+pub struct Plane3dDetails {
     // direction of the normal orthogonal to the plane
     normal: Direction3d,
 
-    // Rotation of the sphere around the origin in 3D space
-    rotation: Quat,
-    // Center position of the sphere in 3D space
-    position: Vec3,
-    // Color of the sphere
-    color: Color,
-
     // Number of axis to hint the plane
     axis_count: usize,
     // Number of segments used to hint the plane
@@ -156,22 +233,25 @@ pub struct Plane3dBuilder<'a, 'w, 's, T: GizmoConfigGroup> {
     segment_length: f32,
 }
 
+/// Builder for configuring the drawing options of [`Plane3d`].
+pub type Plane3dBuilder<'a, 'w, 's, T> = Primitive3dBuilder<'a, 'w, 's, T, Plane3dDetails>;
+
 impl<T: GizmoConfigGroup> Plane3dBuilder<'_, '_, '_, T> {
     /// Set the number of segments used to hint the plane.
     pub fn segment_count(mut self, count: usize) -> Self {
-        self.segment_count = count;
+        self.details.segment_count = count;
         self
     }
 
     /// Set the length of segments used to hint the plane.
     pub fn segment_length(mut self, length: f32) -> Self {
-        self.segment_length = length;
+        self.details.segment_length = length;
         self
     }
 
     /// Set the number of axis used to hint the plane.
     pub fn axis_count(mut self, count: usize) -> Self {
-        self.axis_count = count;
+        self.details.axis_count = count;
         self
     }
 }
@@ -186,58 +266,43 @@ impl<'w, 's, T: GizmoConfigGroup> GizmoPrimitive3d<Plane3d> for Gizmos<'w, 's, T
         rotation: Quat,
         color: Color,
     ) -> Self::Output<'_> {
-        Plane3dBuilder {
-            gizmos: self,
+        let details = Plane3dDetails {
             normal: primitive.normal,
-            rotation,
-            position,
-            color,
             axis_count: 4,
             segment_count: 3,
             segment_length: 0.25,
-        }
+        };
+        Primitive3dBuilder::new(self, details, position, rotation, color)
     }
 }
 
-impl<T: GizmoConfigGroup> Drop for Plane3dBuilder<'_, '_, '_, T> {
-    fn drop(&mut self) {
-        if !self.gizmos.enabled {
-            return;
-        }
-
-        // draws the normal
-        let normal = self.rotation * *self.normal;
-        self.gizmos
-            .primitive_3d(self.normal, self.position, self.rotation, self.color);
+impl GizmoBuilder3d for Plane3dDetails {
+    fn linestrips(&self) -> Vec<Vec<Vec3>> {
+        let normal = *self.normal;
         let normals_normal = normal.any_orthonormal_vector();
 
-        // draws the axes
+        // the axes
         // get rotation for each direction
-        (0..self.axis_count)
+        let axes = (0..self.axis_count)
             .map(|i| i as f32 * (1.0 / self.axis_count as f32) * TAU)
             .map(|angle| Quat::from_axis_angle(normal, angle))
-            .for_each(|quat| {
+            .flat_map(|quat| {
                 let axis_direction = quat * normals_normal;
-                let direction = Direction3d::new_unchecked(axis_direction);
 
-                // for each axis draw dotted line
+                // for each axis a dotted line
                 (0..)
                     .filter(|i| i % 2 != 0)
-                    .map(|percent| (percent as f32 + 0.5) * self.segment_length * axis_direction)
-                    .map(|position| position + self.position)
+                    .map(move |percent| (percent as f32 + 0.5) * self.segment_length)
                     .take(self.segment_count)
-                    .for_each(|position| {
-                        self.gizmos.primitive_3d(
-                            Segment3d {
-                                direction,
-                                half_length: self.segment_length * 0.5,
-                            },
-                            position,
-                            Quat::IDENTITY,
-                            self.color,
-                        );
-                    });
+                    .map(move |distance| {
+                        [-0.5, 0.5]
+                            .map(|sign| (distance + sign * self.segment_length) * axis_direction)
+                            .to_vec()
+                    })
             });
+
+        // the normal
+        arrow_linestrips(Vec3::ZERO, normal).chain(axes).collect()
     }
 }
 
@@ -401,32 +466,27 @@ impl<'w, 's, T: GizmoConfigGroup> GizmoPrimitive3d<Cuboid> for Gizmos<'w, 's, T>
 
 // cylinder 3d
 
-/// Builder for configuring the drawing options of [`Cylinder`].
-pub struct Cylinder3dBuilder<'a, 'w, 's, T: GizmoConfigGroup> {
-    gizmos: &'a mut Gizmos<'w, 's, T>,
-
+/// Drawing details of a [`Cylinder`].
+#[derive(Debug, Clone, Copy)]
+pub struct Cylinder3dDetails {
     // Radius of the cylinder
     radius: f32,
     // Half height of the cylinder
-    half_height: f32,
-
-    // Center position of the cylinder
-    position: Vec3,
-    // Rotation of the cylinder
     //
     // default orientation is: the cylinder is aligned with `Vec3::Y` axis
-    rotation: Quat,
-    // Color of the cylinder
-    color: Color,
+    half_height: f32,
 
     // Number of segments used to approximate the cylinder geometry
     segments: usize,
 }
 
+/// Builder for configuring the drawing options of [`Cylinder`].
+pub type Cylinder3dBuilder<'a, 'w, 's, T> = Primitive3dBuilder<'a, 'w, 's, T, Cylinder3dDetails>;
+
 impl<T: GizmoConfigGroup> Cylinder3dBuilder<'_, '_, '_, T> {
     /// Set the number of segments used to approximate the cylinder geometry.
     pub fn segments(mut self, segments: usize) -> Self {
-        self.segments = segments;
+        self.details.segments = segments;
         self
     }
 }
@@ -441,89 +501,56 @@ impl<'w, 's, T: GizmoConfigGroup> GizmoPrimitive3d<Cylinder> for Gizmos<'w, 's,
         rotation: Quat,
         color: Color,
     ) -> Self::Output<'_> {
-        Cylinder3dBuilder {
-            gizmos: self,
+        let details = Cylinder3dDetails {
             radius: primitive.radius,
             half_height: primitive.half_height,
-            position,
-            rotation,
-            color,
             segments: DEFAULT_NUMBER_SEGMENTS,
-        }
+        };
+        Primitive3dBuilder::new(self, details, position, rotation, color)
     }
 }
 
-impl<T: GizmoConfigGroup> Drop for Cylinder3dBuilder<'_, '_, '_, T> {
-    fn drop(&mut self) {
-        if !self.gizmos.enabled {
-            return;
-        }
-
-        let Cylinder3dBuilder {
-            gizmos,
+impl GizmoBuilder3d for Cylinder3dDetails {
+    fn linestrips(&self) -> Vec<Vec<Vec3>> {
+        let Cylinder3dDetails {
             radius,
             half_height,
-            position,
-            rotation,
-            color,
             segments,
-        } = self;
-
-        let normal = *rotation * Vec3::Y;
-
-        // draw upper and lower circle of the cylinder
-        [-1.0, 1.0].into_iter().for_each(|sign| {
-            draw_circle_3d(
-                gizmos,
-                *radius,
-                *segments,
-                *rotation,
-                *position + sign * *half_height * normal,
-                *color,
-            );
-        });
+        } = *self;
 
-        // draw lines connecting the two cylinder circles
-        draw_cylinder_vertical_lines(
-            gizmos,
-            *radius,
-            *segments,
-            *half_height,
-            *rotation,
-            *position,
-            *color,
-        );
+        // upper and lower circle of the cylinder
+        [-1.0, 1.0]
+            .into_iter()
+            .map(|sign| circle_3d_coordinates(radius, segments, sign * half_height))
+            // lines connecting the two cylinder circles
+            .chain(cylinder_vertical_lines(radius, segments, half_height))
+            .collect()
     }
 }
 
 // capsule 3d
 
-/// Builder for configuring the drawing options of [`Capsule3d`].
-pub struct Capsule3dBuilder<'a, 'w, 's, T: GizmoConfigGroup> {
-    gizmos: &'a mut Gizmos<'w, 's, T>,
-
+/// Drawing details of a [`Capsule3d`].
+#[derive(Debug, Clone, Copy)]
+pub struct Capsule3dDetails {
     // Radius of the capsule
     radius: f32,
     // Half length of the capsule
-    half_length: f32,
-
-    // Center position of the capsule
-    position: Vec3,
-    // Rotation of the capsule
     //
     // default orientation is: the capsule is aligned with `Vec3::Y` axis
-    rotation: Quat,
-    // Color of the capsule
-    color: Color,
+    half_length: f32,
 
     // Number of segments used to approximate the capsule geometry
     segments: usize,
 }
 
+/// Builder for configuring the drawing options of [`Capsule3d`].
+pub type Capsule3dBuilder<'a, 'w, 's, T> = Primitive3dBuilder<'a, 'w, 's, T, Capsule3dDetails>;
+
 impl<T: GizmoConfigGroup> Capsule3dBuilder<'_, '_, '_, T> {
     /// Set the number of segments used to approximate the capsule geometry.
     pub fn segments(mut self, segments: usize) -> Self {
-        self.segments = segments;
+        self.details.segments = segments;
         self
     }
 }
@@ -538,85 +565,62 @@ impl<'w, 's, T: GizmoConfigGroup> GizmoPrimitive3d<Capsule3d> for Gizmos<'w, 's,
         rotation: Quat,
         color: Color,
     ) -> Self::Output<'_> {
-        Capsule3dBuilder {
-            gizmos: self,
+        let details = Capsule3dDetails {
             radius: primitive.radius,
             half_length: primitive.half_length,
-            position,
-            rotation,
-            color,
             segments: DEFAULT_NUMBER_SEGMENTS,
-        }
+        };
+        Primitive3dBuilder::new(self, details, position, rotation, color)
     }
 }
 
-impl<T: GizmoConfigGroup> Drop for Capsule3dBuilder<'_, '_, '_, T> {
-    fn drop(&mut self) {
-        if !self.gizmos.enabled {
-            return;
-        }
-
-        let Capsule3dBuilder {
-            gizmos,
+impl GizmoBuilder3d for Capsule3dDetails {
+    fn linestrips(&self) -> Vec<Vec<Vec3>> {
+        let Capsule3dDetails {
             radius,
             half_length,
-            position,
-            rotation,
-            color,
             segments,
-        } = self;
-
-        let normal = *rotation * Vec3::Y;
+        } = *self;
 
-        // draw two semi spheres for the capsule
-        [1.0, -1.0].into_iter().for_each(|sign| {
-            let center = *position + sign * *half_length * normal;
-            let top = center + sign * *radius * normal;
-            draw_semi_sphere(gizmos, *radius, *segments, *rotation, center, top, *color);
-            draw_circle_3d(gizmos, *radius, *segments, *rotation, center, *color);
-        });
-
-        // connect the two semi spheres with lines
-        draw_cylinder_vertical_lines(
-            gizmos,
-            *radius,
-            *segments,
-            *half_length,
-            *rotation,
-            *position,
-            *color,
-        );
+        // two semi spheres for the capsule
+        [1.0, -1.0]
+            .into_iter()
+            .flat_map(|sign| {
+                let center = sign * half_length * Vec3::Y;
+                semi_sphere_linestrips(radius, segments, center, center + sign * radius * Vec3::Y)
+                    .chain(std::iter::once(circle_3d_coordinates(
+                        radius, segments, center.y,
+                    )))
+            })
+            // connect the two semi spheres with lines
+            .chain(cylinder_vertical_lines(radius, segments, half_length))
+            .collect()
     }
 }
 
 // cone 3d
 
-/// Builder for configuring the drawing options of [`Cone`].
-pub struct Cone3dBuilder<'a, 'w, 's, T: GizmoConfigGroup> {
-    gizmos: &'a mut Gizmos<'w, 's, T>,
-
+/// Drawing details of a [`Cone`].
+#[derive(Debug, Clone, Copy)]
+pub struct Cone3dDetails {
     // Radius of the cone
     radius: f32,
     // Height of the cone
-    height: f32,
-
-    // Center of the cone, half-way between the tip and the base
-    position: Vec3,
-    // Rotation of the cone
     //
     // default orientation is: cone base normal is aligned with the `Vec3::Y` axis
-    rotation: Quat,
-    // Color of the cone
-    color: Color,
+    height: f32,
 
     // Number of segments used to approximate the cone geometry
     segments: usize,
 }
 
+/// Builder for configuring the drawing options of [`Cone`].
+pub type Cone3dBuilder<'a, 'w, 's, T> = Primitive3dBuilder<'a, 'w, 's, T, Cone3dDetails>;
+
 impl<T: GizmoConfigGroup> Cone3dBuilder<'_, '_, '_, T> {
     /// Set the number of segments used to approximate the cone geometry.
     pub fn segments(mut self, segments: usize) -> Self {
-        self.segments = segments;
+        self.details.segments = segments;
         self
     }
 }
@@ -631,88 +635,65 @@ impl<'w, 's, T: GizmoConfigGroup> GizmoPrimitive3d<Cone> for Gizmos<'w, 's, T> {
         rotation: Quat,
         color: Color,
     ) -> Self::Output<'_> {
-        Cone3dBuilder {
-            gizmos: self,
+        let details = Cone3dDetails {
             radius: primitive.radius,
             height: primitive.height,
-            position,
-            rotation,
-            color,
             segments: DEFAULT_NUMBER_SEGMENTS,
-        }
+        };
+        Primitive3dBuilder::new(self, details, position, rotation, color)
     }
 }
 
-impl<T: GizmoConfigGroup> Drop for Cone3dBuilder<'_, '_, '_, T> {
-    fn drop(&mut self) {
-        if !self.gizmos.enabled {
-            return;
-        }
-
-        let Cone3dBuilder {
-            gizmos,
+impl GizmoBuilder3d for Cone3dDetails {
+    fn linestrips(&self) -> Vec<Vec<Vec3>> {
+        let Cone3dDetails {
             radius,
             height,
-            position,
-            rotation,
-            color,
             segments,
-        } = self;
+        } = *self;
 
-        let half_height = *height * 0.5;
+        let half_height = height * 0.5;
 
-        // draw the base circle of the cone
-        draw_circle_3d(
-            gizmos,
-            *radius,
-            *segments,
-            *rotation,
-            *position - *rotation * Vec3::Y * half_height,
-            *color,
-        );
+        // the base circle of the cone
+        let base = circle_3d_coordinates(radius, segments, -half_height);
 
         // connect the base circle with the tip of the cone
-        let end = Vec3::Y * half_height;
-        circle_coordinates(*radius, *segments)
-            .map(|p| Vec3::new(p.x, -half_height, p.y))
-            .map(move |p| [p, end])
-            .map(|ps| ps.map(rotate_then_translate_3d(*rotation, *position)))
-            .for_each(|[start, end]| {
-                gizmos.line(start, end, *color);
-            });
+        let tip = Vec3::Y * half_height;
+        std::iter::once(base)
+            .chain(
+                circle_coordinates(radius, segments)
+                    .map(|p| vec![Vec3::new(p.x, -half_height, p.y), tip]),
+            )
+            .collect()
     }
 }
 
 // conical frustum 3d
 
-/// Builder for configuring the drawing options of [`ConicalFrustum`].
-pub struct ConicalFrustum3dBuilder<'a, 'w, 's, T: GizmoConfigGroup> {
-    gizmos: &'a mut Gizmos<'w, 's, T>,
-
+/// Drawing details of a [`ConicalFrustum`].
+#[derive(Debug, Clone, Copy)]
+pub struct ConicalFrustum3dDetails {
     // Radius of the top circle
     radius_top: f32,
     // Radius of the bottom circle
     radius_bottom: f32,
     // Height of the conical frustum
-    height: f32,
-
-    // Center of conical frustum, half-way between the top and the bottom
-    position: Vec3,
-    // Rotation of the conical frustrum
     //
     // default orientation is: conical frustrum base shape normals are aligned with `Vec3::Y` axis
-    rotation: Quat,
-    // Color of the conical frustum
-    color: Color,
+    height: f32,
 
     // Number of segments used to approximate the curved surfaces
     segments: usize,
 }
 
+/// Builder for configuring the drawing options of [`ConicalFrustum`].
+pub type ConicalFrustum3dBuilder<'a, 'w, 's, T> =
+    Primitive3dBuilder<'a, 'w, 's, T, ConicalFrustum3dDetails>;
+
 impl<T: GizmoConfigGroup> ConicalFrustum3dBuilder<'_, '_, '_, T> {
     /// Set the number of segments used to approximate the curved surfaces.
     pub fn segments(mut self, segments: usize) -> Self {
-        self.segments = segments;
+        self.details.segments = segments;
         self
     }
 }
@@ -727,87 +708,57 @@ impl<'w, 's, T: GizmoConfigGroup> GizmoPrimitive3d<ConicalFrustum> for Gizmos<'w
         rotation: Quat,
         color: Color,
     ) -> Self::Output<'_> {
-        ConicalFrustum3dBuilder {
-            gizmos: self,
+        let details = ConicalFrustum3dDetails {
             radius_top: primitive.radius_top,
             radius_bottom: primitive.radius_bottom,
             height: primitive.height,
-            position,
-            rotation,
-            color,
             segments: DEFAULT_NUMBER_SEGMENTS,
-        }
+        };
+        Primitive3dBuilder::new(self, details, position, rotation, color)
     }
 }
 
-impl<T: GizmoConfigGroup> Drop for ConicalFrustum3dBuilder<'_, '_, '_, T> {
-    fn drop(&mut self) {
-        if !self.gizmos.enabled {
-            return;
-        }
-
-        let ConicalFrustum3dBuilder {
-            gizmos,
+impl GizmoBuilder3d for ConicalFrustum3dDetails {
+    fn linestrips(&self) -> Vec<Vec<Vec3>> {
+        let ConicalFrustum3dDetails {
             radius_top,
             radius_bottom,
             height,
-            position,
-            rotation,
-            color,
             segments,
-        } = self;
+        } = *self;
 
-        let half_height = *height * 0.5;
-        let normal = *rotation * Vec3::Y;
+        let half_height = height * 0.5;
 
-        // draw the two circles of the conical frustrum
-        [(*radius_top, half_height), (*radius_bottom, -half_height)]
+        // the two circles of the conical frustrum
+        let circles = [(radius_top, half_height), (radius_bottom, -half_height)]
             .into_iter()
-            .for_each(|(radius, height)| {
-                draw_circle_3d(
-                    gizmos,
-                    radius,
-                    *segments,
-                    *rotation,
-                    *position + height * normal,
-                    *color,
-                );
-            });
+            .map(|(radius, height)| circle_3d_coordinates(radius, segments, height));
 
         // connect the two circles of the conical frustrum
-        circle_coordinates(*radius_top, *segments)
-            .map(move |p| Vec3::new(p.x, half_height, p.y))
-            .zip(
-                circle_coordinates(*radius_bottom, *segments)
-                    .map(|p| Vec3::new(p.x, -half_height, p.y)),
-            )
-            .map(|(start, end)| [start, end])
-            .map(|ps| ps.map(rotate_then_translate_3d(*rotation, *position)))
-            .for_each(|[start, end]| {
-                gizmos.line(start, end, *color);
+        let connections = circle_coordinates(radius_top, segments)
+            .zip(circle_coordinates(radius_bottom, segments))
+            .map(|(top, bottom)| {
+                vec![
+                    Vec3::new(top.x, half_height, top.y),
+                    Vec3::new(bottom.x, -half_height, bottom.y),
+                ]
             });
+
+        circles.chain(connections).collect()
     }
 }
 
 // torus 3d
 
-/// Builder for configuring the drawing options of [`Torus`].
-pub struct Torus3dBuilder<'a, 'w, 's, T: GizmoConfigGroup> {
-    gizmos: &'a mut Gizmos<'w, 's, T>,
-
+/// Drawing details of a [`Torus`].
+#[derive(Debug, Clone, Copy)]
+pub struct Torus3dDetails {
     // Radius of the minor circle (tube)
     minor_radius: f32,
     // Radius of the major circle (ring)
-    major_radius: f32,
-
-    // Center of the torus
-    position: Vec3,
-    // Rotation of the conical frustrum
     //
     // default orientation is: major circle normal is aligned with `Vec3::Y` axis
-    rotation: Quat,
-    // Color of the torus
-    color: Color,
+    major_radius: f32,
 
     // Number of segments in the minor (tube) direction
     minor_segments: usize,
@@ -815,16 +766,19 @@ pub struct Torus3dBuilder<'a, 'w, 's, T: GizmoConfigGroup> {
     major_segments: usize,
 }
 
+/// Builder for configuring the drawing options of [`Torus`].
+pub type Torus3dBuilder<'a, 'w, 's, T> = Primitive3dBuilder<'a, 'w, 's, T, Torus3dDetails>;
+
 impl<T: GizmoConfigGroup> Torus3dBuilder<'_, '_, '_, T> {
     /// Set the number of segments in the minor (tube) direction.
     pub fn minor_segments(mut self, minor_segments: usize) -> Self {
-        self.minor_segments = minor_segments;
+        self.details.minor_segments = minor_segments;
         self
     }
 
     /// Set the number of segments in the major (ring) direction.
     pub fn major_segments(mut self, major_segments: usize) -> Self {
-        self.major_segments = major_segments;
+        self.details.major_segments = major_segments;
         self
     }
 }
@@ -839,81 +793,123 @@ impl<'w, 's, T: GizmoConfigGroup> GizmoPrimitive3d<Torus> for Gizmos<'w, 's, T>
         rotation: Quat,
         color: Color,
     ) -> Self::Output<'_> {
-        Torus3dBuilder {
-            gizmos: self,
+        let details = Torus3dDetails {
             minor_radius: primitive.minor_radius,
             major_radius: primitive.major_radius,
-            position,
-            rotation,
-            color,
             minor_segments: DEFAULT_NUMBER_SEGMENTS,
             major_segments: DEFAULT_NUMBER_SEGMENTS,
-        }
+        };
+        Primitive3dBuilder::new(self, details, position, rotation, color)
     }
 }
 
-impl<T: GizmoConfigGroup> Drop for Torus3dBuilder<'_, '_, '_, T> {
-    fn drop(&mut self) {
-        if !self.gizmos.enabled {
-            return;
-        }
-
-        let Torus3dBuilder {
-            gizmos,
+impl GizmoBuilder3d for Torus3dDetails {
+    fn linestrips(&self) -> Vec<Vec<Vec3>> {
+        let Torus3dDetails {
             minor_radius,
             major_radius,
-            position,
-            rotation,
-            color,
             minor_segments,
             major_segments,
-        } = self;
-
-        let normal = *rotation * Vec3::Y;
-
-        // draw 4 circles with major_radius
-        [
-            (*major_radius - *minor_radius, 0.0),
-            (*major_radius + *minor_radius, 0.0),
-            (*major_radius, *minor_radius),
-            (*major_radius, -*minor_radius),
+        } = *self;
+
+        // 4 circles with major_radius
+        let major_circles = [
+            (major_radius - minor_radius, 0.0),
+            (major_radius + minor_radius, 0.0),
+            (major_radius, minor_radius),
+            (major_radius, -minor_radius),
         ]
         .into_iter()
-        .for_each(|(radius, height)| {
-            draw_circle_3d(
-                gizmos,
-                radius,
-                *major_segments,
-                *rotation,
-                *position + height * normal,
-                *color,
-            );
-        });
+        .map(|(radius, height)| circle_3d_coordinates(radius, major_segments, height));
 
-        // along the major circle draw orthogonal minor circles
-        let affine = rotate_then_translate_3d(*rotation, *position);
-        circle_coordinates(*major_radius, *major_segments)
+        // along the major circle orthogonal minor circles
+        let minor_circles = circle_coordinates(major_radius, major_segments)
             .map(|p| Vec3::new(p.x, 0.0, p.y))
-            .flat_map(|major_circle_point| {
-                let minor_center = affine(major_circle_point);
-
+            .flat_map(|minor_center| {
                 // direction facing from the center of the torus towards the minor circles center
-                let dir_to_translation = (minor_center - *position).normalize();
+                let dir_to_translation = minor_center.normalize();
 
-                // the minor circle is draw with 4 arcs this is done to make the minor circle
+                // the minor circle is drawn with 4 arcs this is done to make the minor circle
                 // connect properly with each of the major circles
-                let circle_points = [dir_to_translation, normal, -dir_to_translation, -normal]
-                    .map(|offset| minor_center + offset.normalize() * *minor_radius);
+                let circle_points = [
+                    dir_to_translation,
+                    Vec3::Y,
+                    -dir_to_translation,
+                    Vec3::NEG_Y,
+                ]
+                .map(|offset| minor_center + offset * minor_radius);
                 circle_points
                     .into_iter()
                     .zip(circle_points.into_iter().cycle().skip(1))
-                    .map(move |(from, to)| (minor_center, from, to))
+                    .map(move |(from, to)| {
+                        short_arc_3d_coordinates(minor_center, from, to, minor_segments).collect()
+                    })
                     .collect::<Vec<_>>()
-            })
-            .for_each(|(center, from, to)| {
-                gizmos
-                    .short_arc_3d_between(center, from, to, *color)
-                    .segments(*minor_segments);
             });
+
+        major_circles.chain(minor_circles).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{system::RunSystemOnce, world::World};
+
+    use super::*;
+    use crate::{
+        config::{DefaultGizmoConfigGroup, GizmoConfigStore},
+        gizmos::GizmoStorage,
+    };
+
+    #[test]
+    fn sphere_details_lie_on_sphere() {
+        let details = SphereDetails {
+            radius: 2.0,
+            segments: 8,
+        };
+        for point in details.linestrips().into_iter().flatten() {
+            assert!((point.length() - 2.0).abs() < 1e-4, "{point}");
+        }
+    }
+
+    #[test]
+    fn builder_transforms_details() {
+        let mut world = World::new();
+        let mut config_store = GizmoConfigStore::default();
+        config_store.register::<DefaultGizmoConfigGroup>();
+        world.insert_resource(config_store);
+        world.init_resource::<GizmoStorage<DefaultGizmoConfigGroup>>();
+
+        let position = Vec3::new(1.0, 2.0, 3.0);
+        let rotation = Quat::from_rotation_x(1.0);
+        world.run_system_once(move |mut gizmos: Gizmos| {
+            gizmos
+                .primitive_3d(Cylinder::new(0.5, 2.0), position, rotation, Color::WHITE)
+                .segments(6);
+        });
+
+        let details = Cylinder3dDetails {
+            radius: 0.5,
+            half_height: 1.0,
+            segments: 6,
+        };
+        let expected: Vec<_> = details
+            .linestrips()
+            .into_iter()
+            .flatten()
+            .map(|point| position + rotation * point)
+            .collect();
+        let storage = world.resource::<GizmoStorage<DefaultGizmoConfigGroup>>();
+        let drawn: Vec<_> = storage
+            .lines
+            .strip_positions
+            .iter()
+            .filter(|position| !position[0].is_nan())
+            .map(|&position| Vec3::from(position))
+            .collect();
+        assert_eq!(drawn.len(), expected.len());
+        for (drawn, expected) in drawn.iter().zip(&expected) {
+            assert!(drawn.abs_diff_eq(*expected, 1e-5), "{drawn} != {expected}");
+        }
     }
 }
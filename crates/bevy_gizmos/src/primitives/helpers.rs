@@ -1,9 +1,8 @@
 use std::f32::consts::TAU;
 
 use bevy_math::{Mat2, Quat, Vec2, Vec3};
-use bevy_render::color::Color;
 
-use crate::prelude::{GizmoConfigGroup, Gizmos};
+use crate::arcs::arc_3d_inner;
 
 /// Performs an isometric transformation on 2D vectors.
 ///
@@ -46,70 +45,203 @@ pub(crate) fn circle_coordinates(radius: f32, segments: usize) -> impl Iterator<
         .take(segments)
 }
 
-/// Draws a semi-sphere.
+/// Generates the linestrips of a semi-sphere.
 ///
-/// This function draws a semi-sphere at the specified `center` point with the given `rotation`,
-/// `radius`, and `color`. The `segments` parameter determines the level of detail, and the `top`
-/// argument specifies the shape of the semi-sphere's tip.
-pub(crate) fn draw_semi_sphere<T: GizmoConfigGroup>(
-    gizmos: &mut Gizmos<'_, '_, T>,
+/// The semi-sphere is centered at the `center` point in the local space of a 3D primitive, with
+/// its base in the XZ plane, and the given `radius`. The `segments` parameter determines the
+/// level of detail, and the `top` argument specifies the shape of the semi-sphere's tip.
+pub(crate) fn semi_sphere_linestrips(
     radius: f32,
     segments: usize,
-    rotation: Quat,
     center: Vec3,
     top: Vec3,
-    color: Color,
-) {
+) -> impl Iterator<Item = Vec<Vec3>> {
     circle_coordinates(radius, segments)
-        .map(|p| Vec3::new(p.x, 0.0, p.y))
-        .map(rotate_then_translate_3d(rotation, center))
-        .for_each(|from| {
-            gizmos
-                .short_arc_3d_between(center, from, top, color)
-                .segments(segments / 2);
-        });
+        .map(move |p| Vec3::new(p.x, 0.0, p.y) + center)
+        .map(move |from| short_arc_3d_coordinates(center, from, top, segments / 2).collect())
 }
 
-/// Draws a circle in 3D space.
+/// Generates the coordinates of a closed circle parallel to the XZ plane, at the given `height`
+/// in the local space of a 3D primitive.
 ///
 /// # Note
 ///
 /// This function is necessary to use instead of `gizmos.circle` for certain primitives to ensure that points align correctly. For example, the major circles of a torus are drawn with this method, and using `gizmos.circle` would result in the minor circles not being positioned precisely on the major circles' segment points.
-pub(crate) fn draw_circle_3d<T: GizmoConfigGroup>(
-    gizmos: &mut Gizmos<'_, '_, T>,
-    radius: f32,
-    segments: usize,
-    rotation: Quat,
-    translation: Vec3,
-    color: Color,
-) {
-    let positions = (0..=segments)
+pub(crate) fn circle_3d_coordinates(radius: f32, segments: usize, height: f32) -> Vec<Vec3> {
+    (0..=segments)
         .map(|frac| frac as f32 / segments as f32)
         .map(|percentage| percentage * TAU)
         .map(|angle| Vec2::from(angle.sin_cos()) * radius)
-        .map(|p| Vec3::new(p.x, 0.0, p.y))
-        .map(rotate_then_translate_3d(rotation, translation));
-    gizmos.linestrip(positions, color);
+        .map(|p| Vec3::new(p.x, height, p.y))
+        .collect()
 }
 
-/// Draws the connecting lines of a cylinder between the top circle and the bottom circle.
-pub(crate) fn draw_cylinder_vertical_lines<T: GizmoConfigGroup>(
-    gizmos: &mut Gizmos<'_, '_, T>,
+/// Generates the connecting lines of a cylinder aligned with the Y axis between the top circle
+/// and the bottom circle.
+pub(crate) fn cylinder_vertical_lines(
     radius: f32,
     segments: usize,
     half_height: f32,
-    rotation: Quat,
+) -> impl Iterator<Item = Vec<Vec3>> {
+    circle_coordinates(radius, segments).map(move |point_2d| {
+        [1.0, -1.0]
+            .map(|sign| sign * half_height)
+            .map(|height| Vec3::new(point_2d.x, height, point_2d.y))
+            .to_vec()
+    })
+}
+
+/// Generates the coordinates of the shortest arc between two points (`from` and `to`) relative
+/// to a specified `center` point, like [`Gizmos::short_arc_3d_between`](crate::prelude::Gizmos::short_arc_3d_between).
+pub(crate) fn short_arc_3d_coordinates(
     center: Vec3,
-    color: Color,
-) {
-    circle_coordinates(radius, segments)
-        .map(move |point_2d| {
-            [1.0, -1.0]
-                .map(|sign| sign * half_height)
-                .map(|height| Vec3::new(point_2d.x, height, point_2d.y))
-        })
-        .map(|ps| ps.map(rotate_then_translate_3d(rotation, center)))
-        .for_each(|[start, end]| {
-            gizmos.line(start, end, color);
+    from: Vec3,
+    to: Vec3,
+    segments: usize,
+) -> impl Iterator<Item = Vec3> {
+    let from_axis = (from - center).normalize_or_zero();
+    let to_axis = (to - center).normalize_or_zero();
+    let (up, angle) = Quat::from_rotation_arc(from_axis, to_axis).to_axis_angle();
+
+    let radius = center.distance(from);
+    let rotation = Quat::from_rotation_arc(Vec3::Y, up);
+    let start_vertex = rotation.inverse() * from_axis;
+
+    arc_3d_inner(start_vertex, center, rotation, angle, radius, segments)
+}
+
+/// Generates the linestrips of an arrow from `start` to `end`, like
+/// [`Gizmos::arrow`](crate::prelude::Gizmos::arrow) with its default tip length.
+pub(crate) fn arrow_linestrips(start: Vec3, end: Vec3) -> impl Iterator<Item = Vec<Vec3>> {
+    let tip_length = start.distance(end) / 10.;
+    let rotation = Quat::from_rotation_arc(Vec3::X, (end - start).normalize());
+    let tips = [
+        Vec3::new(-1., 1., 0.),
+        Vec3::new(-1., 0., 1.),
+        Vec3::new(-1., -1., 0.),
+        Vec3::new(-1., 0., -1.),
+    ]
+    .map(|v| rotation * (v.normalize() * tip_length) + end);
+
+    std::iter::once(vec![start, end]).chain(tips.into_iter().map(move |tip| vec![end, tip]))
+}
+
+/// Triangulates a convex shape as a fan around its `center`.
+///
+/// The `outline` is the closed ring of points around the center, without repeating the first
+/// point at the end. The triangles are returned as a triangle list.
+pub(crate) fn fan_triangles(center: Vec2, outline: &[Vec2]) -> Vec<Vec2> {
+    (0..outline.len())
+        .flat_map(|i| [center, outline[i], outline[(i + 1) % outline.len()]])
+        .collect()
+}
+
+/// Triangulates a simple polygon, which can be concave, by ear clipping.
+///
+/// The first vertex may be repeated at the end to close the polygon. The triangles are returned
+/// as a triangle list.
+pub(crate) fn triangulate_polygon(vertices: &[Vec2]) -> Vec<Vec2> {
+    let vertices = match vertices {
+        [first, .., last] if first == last => &vertices[..vertices.len() - 1],
+        _ => vertices,
+    };
+    if vertices.len() < 3 {
+        return Vec::new();
+    }
+
+    // ears are convex in the winding order of the polygon
+    let winding = (0..vertices.len())
+        .map(|i| vertices[i].perp_dot(vertices[(i + 1) % vertices.len()]))
+        .sum::<f32>()
+        .signum();
+
+    let mut remaining: Vec<Vec2> = vertices.to_vec();
+    let mut triangles = Vec::with_capacity((vertices.len() - 2) * 3);
+    while remaining.len() > 3 {
+        let len = remaining.len();
+        let corner = |i: usize| {
+            [
+                remaining[(i + len - 1) % len],
+                remaining[i],
+                remaining[(i + 1) % len],
+            ]
+        };
+        let ear = (0..len).find(|&i| {
+            let [a, b, c] = corner(i);
+            (b - a).perp_dot(c - b) * winding > 0.0
+                && !remaining
+                    .iter()
+                    .filter(|&&p| p != a && p != b && p != c)
+                    .any(|&p| point_in_triangle(p, a, b, c))
         });
+
+        // degenerate polygons without any ear are clipped at their first vertex
+        let ear = ear.unwrap_or(0);
+        triangles.extend(corner(ear));
+        remaining.remove(ear);
+    }
+    triangles.extend(remaining);
+    triangles
+}
+
+/// Returns `true` if `point` is inside or on the edges of the triangle `a`, `b`, `c`.
+fn point_in_triangle(point: Vec2, a: Vec2, b: Vec2, c: Vec2) -> bool {
+    let sides = [
+        (b - a).perp_dot(point - a),
+        (c - b).perp_dot(point - b),
+        (a - c).perp_dot(point - c),
+    ];
+    !(sides.iter().any(|&side| side < 0.0) && sides.iter().any(|&side| side > 0.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn area(triangles: &[Vec2]) -> f32 {
+        triangles
+            .chunks_exact(3)
+            .map(|t| (t[1] - t[0]).perp_dot(t[2] - t[0]).abs() / 2.)
+            .sum()
+    }
+
+    #[test]
+    fn triangulate_concave_polygon() {
+        // An L shape, closed by repeating its first vertex
+        let vertices = [
+            Vec2::ZERO,
+            Vec2::new(2., 0.),
+            Vec2::new(2., 1.),
+            Vec2::new(1., 1.),
+            Vec2::new(1., 2.),
+            Vec2::new(0., 2.),
+            Vec2::ZERO,
+        ];
+        let triangles = triangulate_polygon(&vertices);
+        assert_eq!(triangles.len(), 4 * 3);
+        assert_eq!(area(&triangles), 3.);
+
+        // No triangle covers the notch of the L
+        let notch = Vec2::new(1.5, 1.5);
+        assert!(!triangles
+            .chunks_exact(3)
+            .any(|t| point_in_triangle(notch, t[0], t[1], t[2])));
+
+        // The winding order doesn't matter
+        let reversed: Vec<_> = vertices.into_iter().rev().collect();
+        assert_eq!(area(&triangulate_polygon(&reversed)), 3.);
+    }
+
+    #[test]
+    fn fan_triangles_cover_convex_shape() {
+        let square = [
+            Vec2::new(-1., -1.),
+            Vec2::new(1., -1.),
+            Vec2::new(1., 1.),
+            Vec2::new(-1., 1.),
+        ];
+        let triangles = fan_triangles(Vec2::ZERO, &square);
+        assert_eq!(triangles.len(), 4 * 3);
+        assert_eq!(area(&triangles), 4.);
+    }
 }
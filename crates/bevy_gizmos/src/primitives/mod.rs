@@ -3,3 +3,4 @@
 pub mod dim2;
 pub mod dim3;
 pub(crate) mod helpers;
+pub mod projection;
@@ -0,0 +1,445 @@
+//! A module for rendering the 2D silhouettes of the 3D [`bevy_math::primitives`] with [`Gizmos`].
+//!
+//! The primitives are rotated in 3D, then projected along the Z axis onto the XY plane of the 2D
+//! gizmos, the way an orthographic camera looking down the Z axis sees them.
+
+use std::f32::consts::TAU;
+
+use bevy_math::primitives::{Capsule3d, Cone, Cuboid, Cylinder, Primitive3d, Sphere, Torus};
+use bevy_math::{Quat, Vec2, Vec3};
+use bevy_render::color::Color;
+
+use crate::circles::DEFAULT_CIRCLE_SEGMENTS;
+use crate::prelude::{GizmoConfigGroup, Gizmos};
+
+/// A trait for rendering the silhouette of 3D geometric primitives (`P`) with 2D [`Gizmos`].
+pub trait GizmoPrimitiveProjection<P: Primitive3d> {
+    /// The output of `primitive_projection`. This is a builder to set non-default values.
+    type Output<'a>
+    where
+        Self: 'a;
+
+    /// Renders the silhouette of a 3D primitive rotated by `rotation`, projected onto the XY
+    /// plane and centered on `position`.
+    fn primitive_projection(
+        &mut self,
+        primitive: P,
+        position: Vec2,
+        rotation: Quat,
+        color: Color,
+    ) -> Self::Output<'_>;
+}
+
+/// A 3D primitive whose silhouette can be drawn with [`GizmoPrimitiveProjection`].
+pub trait Projectable: Primitive3d {
+    /// Returns the closed outlines of the silhouette of the primitive rotated by `rotation` and
+    /// projected onto the XY plane, around the origin. Curved outlines are approximated with
+    /// `segments` segments.
+    ///
+    /// The first point of an outline is not repeated at its end.
+    fn silhouette(&self, rotation: Quat, segments: usize) -> Vec<Vec<Vec2>>;
+}
+
+/// Builder for configuring the drawing options of the silhouette of a 3D primitive.
+pub struct PrimitiveProjectionBuilder<'a, 'w, 's, T: GizmoConfigGroup, P: Projectable> {
+    gizmos: &'a mut Gizmos<'w, 's, T>,
+
+    // The projected primitive
+    primitive: P,
+
+    // Center position of the silhouette in 2D space
+    position: Vec2,
+    // Rotation of the primitive around its center, before the projection
+    rotation: Quat,
+    // Color of the silhouette
+    color: Color,
+
+    // Number of segments used to approximate curved outlines
+    segments: usize,
+}
+
+impl<T: GizmoConfigGroup, P: Projectable> PrimitiveProjectionBuilder<'_, '_, '_, T, P> {
+    /// Set the number of segments used to approximate curved outlines.
+    pub fn segments(mut self, segments: usize) -> Self {
+        self.segments = segments;
+        self
+    }
+}
+
+impl<T: GizmoConfigGroup, P: Projectable> Drop for PrimitiveProjectionBuilder<'_, '_, '_, T, P> {
+    fn drop(&mut self) {
+        if !self.gizmos.enabled {
+            return;
+        }
+
+        for outline in self.primitive.silhouette(self.rotation, self.segments) {
+            let position = self.position;
+            self.gizmos.linestrip_2d(
+                outline
+                    .iter()
+                    .chain(outline.first())
+                    .map(|&point| point + position),
+                self.color,
+            );
+        }
+    }
+}
+
+impl<'w, 's, T: GizmoConfigGroup, P: Projectable> GizmoPrimitiveProjection<P>
+    for Gizmos<'w, 's, T>
+{
+    type Output<'a> = PrimitiveProjectionBuilder<'a, 'w, 's, T, P> where Self: 'a;
+
+    fn primitive_projection(
+        &mut self,
+        primitive: P,
+        position: Vec2,
+        rotation: Quat,
+        color: Color,
+    ) -> Self::Output<'_> {
+        PrimitiveProjectionBuilder {
+            gizmos: self,
+            primitive,
+            position,
+            rotation,
+            color,
+            segments: DEFAULT_CIRCLE_SEGMENTS,
+        }
+    }
+}
+
+// sphere
+
+impl Projectable for Sphere {
+    fn silhouette(&self, _rotation: Quat, segments: usize) -> Vec<Vec<Vec2>> {
+        let outline = directions(segments).map(|direction| direction * self.radius);
+        vec![convex_hull(outline.collect())]
+    }
+}
+
+// cuboid
+
+impl Projectable for Cuboid {
+    fn silhouette(&self, rotation: Quat, _segments: usize) -> Vec<Vec<Vec2>> {
+        let corners = [
+            [1.0, 1.0, 1.0],
+            [-1.0, 1.0, 1.0],
+            [-1.0, -1.0, 1.0],
+            [1.0, -1.0, 1.0],
+            [1.0, 1.0, -1.0],
+            [-1.0, 1.0, -1.0],
+            [-1.0, -1.0, -1.0],
+            [1.0, -1.0, -1.0],
+        ]
+        .map(|sign| project(rotation, Vec3::from_array(sign) * self.half_size));
+        vec![convex_hull(corners.to_vec())]
+    }
+}
+
+// cylinder
+
+impl Projectable for Cylinder {
+    fn silhouette(&self, rotation: Quat, segments: usize) -> Vec<Vec<Vec2>> {
+        // the outlines of the two caps, and the lines between them
+        let caps = [self.half_height, -self.half_height].map(|height| {
+            directions(segments).map(move |direction| {
+                let rim = rim(to_local(rotation, direction), self.radius);
+                project(rotation, height * Vec3::Y + rim)
+            })
+        });
+        vec![convex_hull(caps.into_iter().flatten().collect())]
+    }
+}
+
+// capsule 3d
+
+impl Projectable for Capsule3d {
+    fn silhouette(&self, rotation: Quat, segments: usize) -> Vec<Vec<Vec2>> {
+        // the outlines of the two hemispheres, and the lines between them
+        let hemispheres = [self.half_length, -self.half_length].map(|height| {
+            let center = project(rotation, height * Vec3::Y);
+            directions(segments).map(move |direction| center + direction * self.radius)
+        });
+        vec![convex_hull(hemispheres.into_iter().flatten().collect())]
+    }
+}
+
+// cone
+
+impl Projectable for Cone {
+    fn silhouette(&self, rotation: Quat, segments: usize) -> Vec<Vec<Vec2>> {
+        let half_height = self.height * 0.5;
+        // the outline of the base, and the lines to the tip
+        let base = directions(segments).map(|direction| {
+            let rim = rim(to_local(rotation, direction), self.radius);
+            project(rotation, -half_height * Vec3::Y + rim)
+        });
+        let tip = project(rotation, half_height * Vec3::Y);
+        vec![convex_hull(base.chain([tip]).collect())]
+    }
+}
+
+// torus
+
+impl Projectable for Torus {
+    fn silhouette(&self, rotation: Quat, segments: usize) -> Vec<Vec<Vec2>> {
+        // The projection of the torus is the projection of its major circle, an ellipse,
+        // swept by a disk of the minor radius.
+        let axes = [Vec3::X, Vec3::Z].map(|axis| project(rotation, axis * self.major_radius));
+        // The point of the filled ellipse furthest in a direction, and its distance along it.
+        let support = |direction: Vec2| {
+            let [x, y] = axes.map(|axis| direction.dot(axis));
+            let distance = x.hypot(y);
+            if distance <= f32::EPSILON {
+                (Vec2::ZERO, 0.0)
+            } else {
+                ((axes[0] * x + axes[1] * y) / distance, distance)
+            }
+        };
+
+        let outer = directions(segments)
+            .map(|direction| support(direction).0 + direction * self.minor_radius);
+        let mut outlines = vec![convex_hull(outer.collect())];
+
+        // The hole is what remains of the filled ellipse after moving each of its sides
+        // inwards by the minor radius.
+        let mut hole = convex_hull(directions(segments).map(|d| support(d).0).collect());
+        for direction in directions(segments) {
+            if hole.len() < 3 {
+                break;
+            }
+            hole = clip(&hole, direction, support(direction).1 - self.minor_radius);
+        }
+        // Edge-on or thin tori leave a sliver of the ellipse from rounding errors.
+        if area(&hole) > 1e-6 * self.major_radius * self.major_radius {
+            outlines.push(hole);
+        }
+        outlines
+    }
+}
+
+/// Rotates a `direction` of the XY plane into the local space of a primitive rotated by
+/// `rotation`.
+fn to_local(rotation: Quat, direction: Vec2) -> Vec3 {
+    rotation.inverse() * direction.extend(0.0)
+}
+
+/// Rotates a `point` in the local space of a primitive by `rotation` and projects it onto the XY
+/// plane.
+fn project(rotation: Quat, point: Vec3) -> Vec2 {
+    (rotation * point).truncate()
+}
+
+/// The point of a circle of `radius` in the XZ plane furthest in the local `direction`, or its
+/// center if the direction is perpendicular to the circle.
+fn rim(direction: Vec3, radius: f32) -> Vec3 {
+    Vec3::new(direction.x, 0.0, direction.z).normalize_or_zero() * radius
+}
+
+/// Iterates over `segments` unit directions, counter-clockwise from `Vec2::X`.
+fn directions(segments: usize) -> impl Iterator<Item = Vec2> {
+    let segments = segments.max(3);
+    (0..segments).map(move |i| Vec2::from_angle(i as f32 * TAU / segments as f32))
+}
+
+/// Returns the counter-clockwise convex hull of `points`, without collinear points.
+fn convex_hull(mut points: Vec<Vec2>) -> Vec<Vec2> {
+    points.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+    points.dedup();
+    if points.len() < 3 {
+        return points;
+    }
+
+    // Andrew's monotone chain: the lower half of the hull, then the upper half.
+    let mut hull: Vec<Vec2> = Vec::with_capacity(points.len() + 1);
+    for half in [points.clone(), points.into_iter().rev().collect()] {
+        let start = hull.len();
+        for point in half {
+            while hull.len() >= start + 2 {
+                let [a, b] = [hull[hull.len() - 2], hull[hull.len() - 1]];
+                if (b - a).perp_dot(point - b) > 0.0 {
+                    break;
+                }
+                hull.pop();
+            }
+            hull.push(point);
+        }
+        // The last point of each half is the first of the other.
+        hull.pop();
+    }
+    hull
+}
+
+/// Returns the signed area of a closed `outline`, positive when it is counter-clockwise.
+fn area(outline: &[Vec2]) -> f32 {
+    (0..outline.len())
+        .map(|i| outline[i].perp_dot(outline[(i + 1) % outline.len()]))
+        .sum::<f32>()
+        * 0.5
+}
+
+/// Clips a convex `polygon` to the half-plane of points `p` where `p.dot(normal) <= offset`.
+fn clip(polygon: &[Vec2], normal: Vec2, offset: f32) -> Vec<Vec2> {
+    let mut clipped = Vec::with_capacity(polygon.len() + 1);
+    for (i, &start) in polygon.iter().enumerate() {
+        let end = polygon[(i + 1) % polygon.len()];
+        let start_distance = start.dot(normal) - offset;
+        let end_distance = end.dot(normal) - offset;
+        if start_distance <= 0.0 {
+            clipped.push(start);
+        }
+        if (start_distance < 0.0 && end_distance > 0.0)
+            || (start_distance > 0.0 && end_distance < 0.0)
+        {
+            let t = start_distance / (start_distance - end_distance);
+            clipped.push(start.lerp(end, t));
+        }
+    }
+    clipped
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use super::*;
+
+    fn silhouette(primitive: impl Projectable, rotation: Quat) -> Vec<Vec<Vec2>> {
+        let outlines = primitive.silhouette(rotation, 64);
+        for outline in &outlines {
+            assert!(outline.iter().all(|point| point.is_finite()));
+        }
+        outlines
+    }
+
+    fn assert_circle(outline: &[Vec2], radius: f32) {
+        for point in outline {
+            assert!(
+                (point.length() - radius).abs() < 1e-4,
+                "{point} off a circle"
+            );
+        }
+    }
+
+    /// Rotates the Y axis of the primitives onto the Z axis, looking down their axis.
+    fn along_axis() -> Quat {
+        Quat::from_rotation_x(FRAC_PI_2)
+    }
+
+    #[test]
+    fn cuboid() {
+        let cuboid = Cuboid::new(2.0, 4.0, 6.0);
+
+        let [front] = &silhouette(cuboid, Quat::IDENTITY)[..] else {
+            panic!("a cuboid has a single outline");
+        };
+        assert_eq!(front.len(), 4);
+        assert_eq!(area(front), 8.0);
+
+        // Viewing the cuboid from its top, with the corners of the faces on top of each other.
+        let [top] = &silhouette(cuboid, along_axis())[..] else {
+            panic!("a cuboid has a single outline");
+        };
+        assert!((area(top) - 12.0).abs() < 1e-4);
+
+        let [corner] = &silhouette(cuboid, Quat::from_rotation_y(0.3))[..] else {
+            panic!("a cuboid has a single outline");
+        };
+        assert_eq!(corner.len(), 4);
+        assert!(area(corner) > 8.0);
+    }
+
+    #[test]
+    fn round_primitives_along_their_axis() {
+        let rotation = along_axis();
+        assert_circle(&silhouette(Sphere { radius: 2.0 }, rotation)[0], 2.0);
+        assert_circle(&silhouette(Cylinder::new(2.0, 5.0), rotation)[0], 2.0);
+        assert_circle(&silhouette(Capsule3d::new(2.0, 5.0), rotation)[0], 2.0);
+        // The tip of the cone is in the middle of its base.
+        let cone = Cone {
+            radius: 2.0,
+            height: 5.0,
+        };
+        assert_circle(&silhouette(cone, rotation)[0], 2.0);
+        assert_circle(&silhouette(cone, rotation.inverse())[0], 2.0);
+    }
+
+    #[test]
+    fn round_primitives_from_their_side() {
+        // A rectangle, with the points along its straight sides.
+        let cylinder = &silhouette(Cylinder::new(1.0, 4.0), Quat::IDENTITY)[0];
+        assert!((area(cylinder) - 8.0).abs() < 1e-4);
+
+        // A rectangle between two half circles.
+        let capsule = &silhouette(Capsule3d::new(1.0, 4.0), Quat::IDENTITY)[0];
+        let expected = 8.0 + std::f32::consts::PI;
+        assert!((area(capsule) - expected).abs() < 0.01);
+
+        // A triangle.
+        let cone = Cone {
+            radius: 1.0,
+            height: 2.0,
+        };
+        let cone = &silhouette(cone, Quat::IDENTITY)[0];
+        assert!((area(cone) - 2.0).abs() < 1e-4);
+        assert!(cone.contains(&Vec2::Y));
+    }
+
+    #[test]
+    fn torus() {
+        let torus = Torus::new(1.0, 3.0);
+
+        // Looking through the hole of the torus.
+        let [outer, hole] = &silhouette(torus, along_axis())[..] else {
+            panic!("the hole of the torus should be visible");
+        };
+        assert_circle(outer, 3.0);
+        for point in hole {
+            // The hole is approximated from the outside.
+            let distance = point.length();
+            assert!(
+                (1.0 - 1e-4..1.01).contains(&distance),
+                "{point} off the hole"
+            );
+        }
+        assert!(area(hole) > 0.0);
+
+        // From the side the hole is hidden, leaving the rectangle between two half circles.
+        let [side] = &silhouette(torus, Quat::IDENTITY)[..] else {
+            panic!("the hole of the torus should be hidden");
+        };
+        for point in side {
+            assert!(point.x.abs() <= 3.0 + 1e-4 && point.y.abs() <= 1.0 + 1e-4);
+        }
+
+        // Almost from the side, the hole is still hidden by the tube.
+        let outlines = silhouette(torus, Quat::from_rotation_x(0.1));
+        assert_eq!(outlines.len(), 1);
+
+        // A torus without a hole.
+        let outlines = silhouette(Torus::new(0.0, 2.0), along_axis());
+        assert_eq!(outlines.len(), 1);
+    }
+
+    #[test]
+    fn degenerate_primitives() {
+        fn assert_point(primitive: impl Projectable + Copy) {
+            for rotation in [Quat::IDENTITY, along_axis(), Quat::from_rotation_z(1.0)] {
+                for outline in silhouette(primitive, rotation) {
+                    assert!(outline.len() <= 1 || area(&outline).abs() < 1e-6);
+                }
+            }
+        }
+
+        assert_point(Sphere { radius: 0.0 });
+        assert_point(Cuboid::new(0.0, 0.0, 0.0));
+        assert_point(Cylinder::new(0.0, 0.0));
+        assert_point(Capsule3d::new(0.0, 0.0));
+        assert_point(Torus::new(0.0, 0.0));
+
+        // A flat cylinder is a disk, seen from its side as a line.
+        let disk = &silhouette(Cylinder::new(1.0, 0.0), Quat::IDENTITY)[0];
+        assert!(area(disk).abs() < 1e-6);
+        assert!(disk.iter().all(|point| point.y == 0.0));
+    }
+}
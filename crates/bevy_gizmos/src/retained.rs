@@ -0,0 +1,380 @@
+//! Retained mode gizmos: lines built once, stored as a [`GizmoAsset`] and drawn every frame by
+//! the entities with a [`Gizmo`] component.
+
+use std::iter;
+
+use bevy_asset::{Asset, AssetEvent, AssetId, Assets, Handle};
+use bevy_ecs::{
+    bundle::Bundle,
+    component::Component,
+    event::EventReader,
+    reflect::ReflectComponent,
+    system::{Commands, Query, Res, ResMut, Resource},
+};
+use bevy_math::Vec3;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect, TypePath};
+use bevy_render::{
+    color::Color,
+    view::{InheritedVisibility, RenderLayers, ViewVisibility, Visibility},
+    Extract,
+};
+use bevy_transform::components::{GlobalTransform, Transform};
+use bevy_utils::HashMap;
+
+use crate::{
+    config::{GizmoDepthTest, GizmoLineStyle, GizmoMeshConfig},
+    LineGizmo, LineGizmoUniform,
+};
+
+/// A set of lines built once and drawn every frame by the entities with a [`Gizmo`] component
+/// referencing it, in their local space.
+///
+/// Unlike [`Gizmos`](crate::gizmos::Gizmos), the vertex buffers of the lines are only uploaded
+/// when the asset is modified, which suits large static overlays like navigation meshes.
+///
+/// # Example
+/// ```
+/// # use bevy_gizmos::prelude::*;
+/// # use bevy_render::prelude::*;
+/// # use bevy_math::prelude::*;
+/// # use bevy_asset::Assets;
+/// # use bevy_ecs::prelude::*;
+/// fn setup(mut commands: Commands, mut gizmo_assets: ResMut<Assets<GizmoAsset>>) {
+///     let mut gizmo = GizmoAsset::new();
+///     gizmo.linestrip([Vec3::ZERO, Vec3::X, Vec3::Y, Vec3::ZERO], Color::GREEN);
+///     commands.spawn(GizmoBundle {
+///         gizmo: Gizmo {
+///             handle: gizmo_assets.add(gizmo),
+///             ..Default::default()
+///         },
+///         ..Default::default()
+///     });
+/// }
+/// # bevy_ecs::system::assert_is_system(setup);
+/// ```
+#[derive(Asset, Debug, Clone, TypePath)]
+pub struct GizmoAsset {
+    list: LineGizmo,
+    strip: LineGizmo,
+}
+
+impl Default for GizmoAsset {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GizmoAsset {
+    /// Creates a gizmo without lines.
+    pub fn new() -> Self {
+        Self {
+            list: LineGizmo {
+                strip: false,
+                ..Default::default()
+            },
+            strip: LineGizmo {
+                strip: true,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Returns `true` if this gizmo has no lines.
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty() && self.strip.is_empty()
+    }
+
+    /// Adds a line from `start` to `end`.
+    pub fn line(&mut self, start: Vec3, end: Vec3, color: Color) {
+        self.line_gradient(start, end, color, color);
+    }
+
+    /// Adds a line with a color gradient from `start` to `end`.
+    pub fn line_gradient(&mut self, start: Vec3, end: Vec3, start_color: Color, end_color: Color) {
+        self.list
+            .positions
+            .extend([start.to_array(), end.to_array()]);
+        self.list.colors.extend([
+            start_color.as_linear_rgba_f32(),
+            end_color.as_linear_rgba_f32(),
+        ]);
+    }
+
+    /// Adds a line made of straight segments between the points.
+    pub fn linestrip(&mut self, positions: impl IntoIterator<Item = Vec3>, color: Color) {
+        self.linestrip_gradient(positions.into_iter().zip(iter::repeat(color)));
+    }
+
+    /// Adds a line made of straight segments between the points, with a color gradient.
+    pub fn linestrip_gradient(&mut self, points: impl IntoIterator<Item = (Vec3, Color)>) {
+        for (position, color) in points {
+            self.strip.positions.push(position.to_array());
+            self.strip.colors.push(color.as_linear_rgba_f32());
+        }
+        self.strip.positions.push([f32::NAN; 3]);
+        self.strip.colors.push([f32::NAN; 4]);
+    }
+
+    /// Adds the lines of [`LineGizmo`]s, like those captured with
+    /// [`Gizmos::take_line_gizmos`](crate::gizmos::Gizmos::take_line_gizmos), to draw them with
+    /// the whole immediate mode API.
+    pub fn extend_from_line_gizmos(&mut self, line_gizmos: impl IntoIterator<Item = LineGizmo>) {
+        for mut line_gizmo in line_gizmos {
+            let target = if line_gizmo.strip {
+                &mut self.strip
+            } else {
+                &mut self.list
+            };
+            target.positions.append(&mut line_gizmo.positions);
+            target.colors.append(&mut line_gizmo.colors);
+        }
+    }
+}
+
+/// Draws the lines of a [`GizmoAsset`] every frame, with the [`GlobalTransform`] of the entity.
+///
+/// The lines are drawn to the cameras sharing a layer with the [`RenderLayers`] of the entity.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Default)]
+pub struct Gizmo {
+    /// The lines to draw.
+    pub handle: Handle<GizmoAsset>,
+    /// Line width specified in pixels.
+    ///
+    /// If `line_perspective` is `true` then this is the size in pixels at the camera's near plane.
+    ///
+    /// Defaults to `2.0`.
+    pub line_width: f32,
+    /// Apply perspective to the lines.
+    ///
+    /// This setting only affects 3D, non-orthographic cameras.
+    ///
+    /// Defaults to `false`.
+    pub line_perspective: bool,
+    /// How closer to the camera than real geometry the lines should be.
+    ///
+    /// See [`GizmoConfig::depth_bias`](crate::config::GizmoConfig::depth_bias).
+    ///
+    /// Defaults to `0.0`.
+    pub depth_bias: f32,
+    /// The style of the lines, solid or broken in dashes or dots.
+    ///
+    /// Defaults to [`GizmoLineStyle::Solid`].
+    pub line_style: GizmoLineStyle,
+}
+
+impl Default for Gizmo {
+    fn default() -> Self {
+        Self {
+            handle: Default::default(),
+            line_width: 2.,
+            line_perspective: false,
+            depth_bias: 0.,
+            line_style: GizmoLineStyle::Solid,
+        }
+    }
+}
+
+/// A component bundle for entities drawing a [`GizmoAsset`].
+#[derive(Bundle, Clone, Debug, Default)]
+pub struct GizmoBundle {
+    /// The [`GizmoAsset`] to draw, and how to draw its lines.
+    pub gizmo: Gizmo,
+    /// The local transform of the gizmo, relative to its parent.
+    pub transform: Transform,
+    /// The global transform of the gizmo, computed from its [`Transform`].
+    pub global_transform: GlobalTransform,
+    /// User indication of whether an entity is visible
+    pub visibility: Visibility,
+    /// Inherited visibility of an entity.
+    pub inherited_visibility: InheritedVisibility,
+    /// Algorithmically-computed indication of whether an entity is visible and should be extracted for rendering
+    pub view_visibility: ViewVisibility,
+}
+
+/// The [`LineGizmo`]s of each [`GizmoAsset`], one per topology with lines.
+#[derive(Resource, Default)]
+pub(crate) struct RetainedGizmoHandles(HashMap<AssetId<GizmoAsset>, RetainedLineGizmos>);
+
+#[derive(Default)]
+pub(crate) struct RetainedLineGizmos {
+    list: Option<Handle<LineGizmo>>,
+    strip: Option<Handle<LineGizmo>>,
+}
+
+pub(crate) fn update_retained_gizmos(
+    mut events: EventReader<AssetEvent<GizmoAsset>>,
+    gizmo_assets: Res<Assets<GizmoAsset>>,
+    mut line_gizmos: ResMut<Assets<LineGizmo>>,
+    mut handles: ResMut<RetainedGizmoHandles>,
+) {
+    fn update(
+        handle: &mut Option<Handle<LineGizmo>>,
+        line_gizmo: &LineGizmo,
+        line_gizmos: &mut Assets<LineGizmo>,
+    ) {
+        if line_gizmo.is_empty() {
+            *handle = None;
+        } else if let Some(handle) = handle {
+            line_gizmos.insert(handle.id(), line_gizmo.clone());
+        } else {
+            *handle = Some(line_gizmos.add(line_gizmo.clone()));
+        }
+    }
+
+    for event in events.read() {
+        match event {
+            AssetEvent::Added { id } | AssetEvent::Modified { id } => {
+                let Some(gizmo_asset) = gizmo_assets.get(*id) else {
+                    continue;
+                };
+                let retained = handles.0.entry(*id).or_default();
+                update(&mut retained.list, &gizmo_asset.list, &mut line_gizmos);
+                update(&mut retained.strip, &gizmo_asset.strip, &mut line_gizmos);
+            }
+            AssetEvent::Removed { id } => {
+                handles.0.remove(id);
+            }
+            _ => {}
+        }
+    }
+}
+
+pub(crate) fn extract_retained_gizmos(
+    mut commands: Commands,
+    handles: Extract<Res<RetainedGizmoHandles>>,
+    gizmos: Extract<
+        Query<(
+            &Gizmo,
+            &GlobalTransform,
+            &ViewVisibility,
+            Option<&RenderLayers>,
+        )>,
+    >,
+) {
+    for (gizmo, transform, view_visibility, render_layers) in &gizmos {
+        if !view_visibility.get() {
+            continue;
+        }
+        let Some(retained) = handles.0.get(&gizmo.handle.id()) else {
+            continue;
+        };
+
+        let transform = transform.compute_matrix();
+        let (dash_length, gap_length) = gizmo.line_style.dash_and_gap_lengths();
+        for handle in [&retained.list, &retained.strip].into_iter().flatten() {
+            commands.spawn((
+                LineGizmoUniform {
+                    transform,
+                    line_width: gizmo.line_width,
+                    depth_bias: gizmo.depth_bias,
+                    dash_length,
+                    gap_length,
+                    alpha: 1.,
+                },
+                handle.clone_weak(),
+                GizmoMeshConfig {
+                    line_perspective: gizmo.line_perspective,
+                    render_layers: render_layers.copied().unwrap_or_default(),
+                    depth_test: GizmoDepthTest::Visible,
+                },
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::{
+        entity::Entity,
+        event::{Events, ManualEventReader},
+        schedule::{IntoSystemConfigs, Schedule},
+        world::World,
+    };
+    use bevy_math::{Mat4, Quat};
+    use bevy_render::MainWorld;
+
+    #[test]
+    fn retained_gizmo_is_uploaded_once_and_drawn_every_frame() {
+        let mut main_world = World::new();
+        main_world.init_resource::<Assets<GizmoAsset>>();
+        main_world.init_resource::<Assets<LineGizmo>>();
+        main_world.init_resource::<Events<AssetEvent<GizmoAsset>>>();
+        main_world.init_resource::<Events<AssetEvent<LineGizmo>>>();
+        main_world.init_resource::<RetainedGizmoHandles>();
+
+        let mut gizmo_asset = GizmoAsset::new();
+        gizmo_asset.line(Vec3::ZERO, Vec3::X, Color::RED);
+        gizmo_asset.linestrip([Vec3::ZERO, Vec3::Y, Vec3::Z], Color::GREEN);
+        let handle = main_world
+            .resource_mut::<Assets<GizmoAsset>>()
+            .add(gizmo_asset);
+        let mut view_visibility = ViewVisibility::HIDDEN;
+        view_visibility.set();
+        let transform = Transform::from_xyz(1., 2., 3.).with_rotation(Quat::from_rotation_y(1.));
+        let entity = main_world
+            .spawn((
+                Gizmo {
+                    handle,
+                    ..Default::default()
+                },
+                GlobalTransform::from(transform),
+                view_visibility,
+            ))
+            .id();
+
+        let mut render_world = World::new();
+        render_world.init_resource::<MainWorld>();
+        **render_world.resource_mut::<MainWorld>() = main_world;
+        let mut main_schedule = Schedule::default();
+        main_schedule.add_systems(
+            (
+                Assets::<GizmoAsset>::asset_events,
+                update_retained_gizmos,
+                Assets::<LineGizmo>::asset_events,
+            )
+                .chain(),
+        );
+        let mut extract_schedule = Schedule::default();
+        extract_schedule.add_systems(extract_retained_gizmos);
+        let mut line_gizmo_events = ManualEventReader::<AssetEvent<LineGizmo>>::default();
+
+        // Runs a frame, returning the number of line gizmos uploaded to the render world, and the
+        // transforms of the lines drawn.
+        let mut frame = |render_world: &mut World| {
+            render_world.clear_entities();
+            let mut main_world = render_world.resource_mut::<MainWorld>();
+            main_schedule.run(&mut main_world);
+            let uploads = line_gizmo_events
+                .read(main_world.resource::<Events<AssetEvent<LineGizmo>>>())
+                .filter(|event| matches!(event, AssetEvent::Added { .. }))
+                .count();
+            extract_schedule.run(render_world);
+            let transforms: Vec<Mat4> = render_world
+                .query::<(&LineGizmoUniform, &Handle<LineGizmo>)>()
+                .iter(render_world)
+                .map(|(uniform, _)| uniform.transform)
+                .collect();
+            (uploads, transforms)
+        };
+
+        // Both the line list and the line strip are uploaded once.
+        let matrix = transform.compute_matrix();
+        assert_eq!(frame(&mut render_world), (2, vec![matrix; 2]));
+        assert_eq!(frame(&mut render_world), (0, vec![matrix; 2]));
+
+        // Moving the entity moves the lines without uploading them again.
+        let moved = transform.with_translation(Vec3::new(-4., 0., 1.));
+        let set_transform = |render_world: &mut World, entity: Entity, transform: Transform| {
+            render_world
+                .resource_mut::<MainWorld>()
+                .entity_mut(entity)
+                .insert(GlobalTransform::from(transform));
+        };
+        set_transform(&mut render_world, entity, moved);
+        let moved_matrix = moved.compute_matrix();
+        assert_eq!(frame(&mut render_world), (0, vec![moved_matrix; 2]));
+        assert_eq!(frame(&mut render_world), (0, vec![moved_matrix; 2]));
+    }
+}
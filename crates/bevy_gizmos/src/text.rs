@@ -0,0 +1,308 @@
+//! Text label gizmos, drawn with [`Gizmos::text_2d`] and [`Gizmos::text_3d`].
+//!
+//! The labels are laid out by `bevy_text` into its glyph atlases, and rendered by a pool of
+//! [`Text2dBundle`](bevy_text::Text2dBundle) and billboarded [`Text3d`] entities reused from
+//! frame to frame.
+
+use std::marker::PhantomData;
+
+use bevy_app::{App, PostUpdate};
+use bevy_ecs::{
+    change_detection::DetectChangesMut,
+    component::Component,
+    schedule::IntoSystemConfigs,
+    system::{Commands, Query, Res, ResMut},
+};
+use bevy_math::{Vec2, Vec3};
+use bevy_render::{
+    color::Color,
+    view::{RenderLayers, Visibility, VisibilitySystems},
+};
+use bevy_text::{
+    update_text2d_layout, Text, Text2dBundle, Text3d, Text3dAlphaMode, Text3dBillboard,
+    Text3dBundle, TextStyle,
+};
+use bevy_transform::{components::Transform, TransformSystem};
+
+use crate::{
+    config::{GizmoConfigGroup, GizmoConfigStore},
+    gizmos::{GizmoStorage, Gizmos},
+};
+
+/// The font size 3D labels are laid out with, before being scaled to their height.
+const TEXT_3D_FONT_SIZE: f32 = 32.;
+
+/// The depth of 2D labels, in front of the sprites seen by a default `Camera2dBundle`.
+const TEXT_2D_Z: f32 = 999.;
+
+/// A text label drawn this frame.
+#[derive(Clone, Debug)]
+pub(crate) struct GizmoText {
+    position: Vec3,
+    value: String,
+    /// The font size of 2D labels, or the height of 3D labels in world units.
+    size: f32,
+    color: Color,
+    is_3d: bool,
+}
+
+impl<'w, 's, T: GizmoConfigGroup> Gizmos<'w, 's, T> {
+    /// Draw a text label in 2D, centered on `position`.
+    ///
+    /// `font_size` is in logical pixels, which are world units for a default `Camera2dBundle`.
+    ///
+    /// This should be called for each frame the label needs to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.text_2d(Vec2::new(0., 40.), "player", 16., Color::WHITE);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn text_2d(
+        &mut self,
+        position: Vec2,
+        value: impl Into<String>,
+        font_size: f32,
+        color: Color,
+    ) {
+        if !self.enabled {
+            return;
+        }
+        self.push_text(GizmoText {
+            position: position.extend(TEXT_2D_Z),
+            value: value.into(),
+            size: font_size,
+            color,
+            is_3d: false,
+        });
+    }
+
+    /// Draw a text label in 3D, centered on `position` and always facing the camera.
+    ///
+    /// `height` is the height of a line of text in world units.
+    ///
+    /// This should be called for each frame the label needs to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     let velocity = Vec3::new(1., 0., 2.);
+    ///     gizmos.text_3d(Vec3::Y, format!("{velocity:.1}"), 0.2, Color::YELLOW);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn text_3d(&mut self, position: Vec3, value: impl Into<String>, height: f32, color: Color) {
+        if !self.enabled {
+            return;
+        }
+        self.push_text(GizmoText {
+            position,
+            value: value.into(),
+            size: height,
+            color,
+            is_3d: true,
+        });
+    }
+}
+
+/// Adds the system showing the labels of the [`GizmoConfigGroup`] `T`.
+///
+/// It runs before the text is laid out, so labels drawn after [`Update`](bevy_app::Update) are
+/// shown a frame late.
+pub(crate) fn add_gizmo_text_systems<T: GizmoConfigGroup>(app: &mut App) {
+    app.add_systems(
+        PostUpdate,
+        update_gizmo_texts::<T>
+            .before(update_text2d_layout)
+            .before(TransformSystem::TransformPropagate)
+            .before(VisibilitySystems::VisibilityPropagate),
+    );
+}
+
+/// Marks the text entities showing the labels of the [`GizmoConfigGroup`] `T`.
+#[derive(Component)]
+pub(crate) struct GizmoTextLabel<T: GizmoConfigGroup> {
+    is_3d: bool,
+    marker: PhantomData<T>,
+}
+
+/// Shows the labels drawn since the last frame with the text entities of the group, spawning
+/// the missing ones and hiding the unused ones.
+pub(crate) fn update_gizmo_texts<T: GizmoConfigGroup>(
+    mut commands: Commands,
+    mut storage: ResMut<GizmoStorage<T>>,
+    config_store: Res<GizmoConfigStore>,
+    mut labels: Query<(
+        &GizmoTextLabel<T>,
+        &mut Text,
+        &mut Transform,
+        &mut Visibility,
+        &mut RenderLayers,
+    )>,
+) {
+    let (config, _) = config_store.config::<T>();
+    let storage = &mut *storage;
+    // Labels drawn with `Gizmos::with_render_layers` are shown to their own layers.
+    let layered = storage
+        .layered
+        .iter_mut()
+        .flat_map(|(render_layers, lines)| {
+            let render_layers = *render_layers;
+            lines
+                .texts
+                .drain(..)
+                .map(move |gizmo_text| (render_layers, gizmo_text))
+        });
+    let (texts_3d, texts_2d): (Vec<_>, Vec<_>) = storage
+        .lines
+        .texts
+        .drain(..)
+        .map(|gizmo_text| (config.render_layers, gizmo_text))
+        .chain(layered)
+        .partition(|(_, gizmo_text)| gizmo_text.is_3d);
+    let mut texts_3d = texts_3d.into_iter();
+    let mut texts_2d = texts_2d.into_iter();
+
+    for (label, mut text, mut transform, mut visibility, mut render_layers) in &mut labels {
+        let next = if label.is_3d {
+            texts_3d.next()
+        } else {
+            texts_2d.next()
+        };
+        let Some((layers, gizmo_text)) = next else {
+            visibility.set_if_neq(Visibility::Hidden);
+            continue;
+        };
+
+        // Only touch the text when it changes, as it is laid out again when it does.
+        let new_transform = label_transform(&gizmo_text);
+        let section = &text.sections[0];
+        if section.value != gizmo_text.value
+            || section.style.color != gizmo_text.color
+            || (!label.is_3d && section.style.font_size != gizmo_text.size)
+        {
+            let section = &mut text.sections[0];
+            section.value = gizmo_text.value;
+            section.style.color = gizmo_text.color;
+            if !label.is_3d {
+                section.style.font_size = gizmo_text.size;
+            }
+        }
+        transform.set_if_neq(new_transform);
+        visibility.set_if_neq(Visibility::Inherited);
+        render_layers.set_if_neq(layers);
+    }
+
+    for (render_layers, gizmo_text) in texts_2d.chain(texts_3d) {
+        let transform = label_transform(&gizmo_text);
+        let text = Text::from_section(
+            gizmo_text.value,
+            TextStyle {
+                font_size: if gizmo_text.is_3d {
+                    TEXT_3D_FONT_SIZE
+                } else {
+                    gizmo_text.size
+                },
+                color: gizmo_text.color,
+                ..Default::default()
+            },
+        );
+        let label = GizmoTextLabel::<T> {
+            is_3d: gizmo_text.is_3d,
+            marker: PhantomData,
+        };
+        let mut entity = if gizmo_text.is_3d {
+            commands.spawn(Text3dBundle {
+                text,
+                text_3d: Text3d {
+                    world_scale: 1. / TEXT_3D_FONT_SIZE,
+                    billboard: Text3dBillboard::Full,
+                    alpha_mode: Text3dAlphaMode::Blend,
+                },
+                transform,
+                ..Default::default()
+            })
+        } else {
+            commands.spawn(Text2dBundle {
+                text,
+                transform,
+                ..Default::default()
+            })
+        };
+        entity.insert((label, render_layers));
+    }
+}
+
+fn label_transform(gizmo_text: &GizmoText) -> Transform {
+    let transform = Transform::from_translation(gizmo_text.position);
+    if gizmo_text.is_3d {
+        // The glyphs are laid out one world unit high, see `TEXT_3D_FONT_SIZE`.
+        transform.with_scale(Vec3::splat(gizmo_text.size))
+    } else {
+        transform
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{system::RunSystemOnce, world::World};
+
+    use super::*;
+    use crate::config::DefaultGizmoConfigGroup;
+
+    type Label = GizmoTextLabel<DefaultGizmoConfigGroup>;
+
+    #[test]
+    fn labels_are_reused_and_hidden() {
+        let mut world = World::new();
+        let mut config_store = GizmoConfigStore::default();
+        config_store.register::<DefaultGizmoConfigGroup>();
+        world.insert_resource(config_store);
+        world.init_resource::<GizmoStorage<DefaultGizmoConfigGroup>>();
+
+        world.run_system_once(|mut gizmos: Gizmos| {
+            gizmos.text_2d(Vec2::ZERO, "2d", 16., Color::WHITE);
+            gizmos.text_3d(Vec3::Y, "3d", 0.5, Color::RED);
+        });
+        world.run_system_once(update_gizmo_texts::<DefaultGizmoConfigGroup>);
+        let storage = world.resource::<GizmoStorage<DefaultGizmoConfigGroup>>();
+        assert!(storage.lines.texts.is_empty());
+
+        let mut labels = world.query::<(&Label, &Text, &Transform, &Visibility)>();
+        assert_eq!(labels.iter(&world).count(), 2);
+        for (label, text, transform, _) in labels.iter(&world) {
+            if label.is_3d {
+                assert_eq!(text.sections[0].value, "3d");
+                assert_eq!(transform.scale, Vec3::splat(0.5));
+            } else {
+                assert_eq!(text.sections[0].value, "2d");
+                assert_eq!(text.sections[0].style.font_size, 16.);
+            }
+        }
+
+        // The next frame reuses the 2D label and hides the unused 3D label
+        world.run_system_once(|mut gizmos: Gizmos| {
+            gizmos.text_2d(Vec2::X, "moved", 16., Color::WHITE);
+        });
+        world.run_system_once(update_gizmo_texts::<DefaultGizmoConfigGroup>);
+
+        assert_eq!(labels.iter(&world).count(), 2);
+        for (label, text, transform, visibility) in labels.iter(&world) {
+            if label.is_3d {
+                assert_eq!(*visibility, Visibility::Hidden);
+            } else {
+                assert_eq!(text.sections[0].value, "moved");
+                assert_eq!(transform.translation, Vec3::new(1., 0., TEXT_2D_Z));
+                assert_eq!(*visibility, Visibility::Inherited);
+            }
+        }
+    }
+}
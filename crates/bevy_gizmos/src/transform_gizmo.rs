@@ -0,0 +1,959 @@
+//! Interactive handles to move, rotate and scale entities with a pointer.
+//!
+//! Add a [`TransformGizmo`] to an entity to draw the handles of its [`TransformGizmoMode`]
+//! around it. The handles are picked by the pointers of `bevy_picking`, and dragging one of them
+//! with the primary button updates the [`Transform`] of the entity.
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_gizmos::transform_gizmo::{TransformGizmo, TransformGizmoMode};
+//! # use bevy_transform::prelude::*;
+//! fn setup(mut commands: Commands) {
+//!     commands.spawn((
+//!         TransformBundle::default(),
+//!         TransformGizmo::new(TransformGizmoMode::Rotate),
+//!     ));
+//! }
+//! # bevy_ecs::system::assert_is_system(setup);
+//! ```
+
+use crate as bevy_gizmos;
+
+use bevy_app::{App, Plugin, PostUpdate, PreUpdate};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    event::{EventReader, EventWriter},
+    query::With,
+    reflect::ReflectComponent,
+    schedule::IntoSystemConfigs,
+    system::{Query, Res},
+};
+use bevy_math::{primitives::Plane3d, Affine3A, Mat3, Quat, Ray3d, Vec2, Vec3};
+use bevy_picking::{
+    backend::{camera_ray, HitData, PointerHits},
+    events::{Down, Pointer},
+    pointer::{PointerButton, PointerId, PointerLocation, PointerPress},
+    PickSet,
+};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{camera::Camera, color::Color};
+use bevy_transform::{
+    components::{GlobalTransform, Transform},
+    TransformSystem,
+};
+use bevy_utils::{default, HashMap};
+use bevy_window::PrimaryWindow;
+
+use crate::{
+    config::{GizmoConfig, GizmoConfigGroup, GizmoConfigStore},
+    gizmos::Gizmos,
+    AppGizmoBuilder,
+};
+
+/// The distance from a handle within which it is hit by a pointer, relative to the gizmo size.
+const PICK_RADIUS: f32 = 0.08;
+/// The distance of the plane handles from the center, relative to the gizmo size.
+const PLANE_HANDLE_OFFSET: f32 = 0.3;
+/// The half size of the plane handles, relative to the gizmo size.
+const PLANE_HANDLE_HALF_SIZE: f32 = 0.1;
+/// The size of the boxes at the end of the scale handles, relative to the gizmo size.
+const SCALE_HANDLE_SIZE: f32 = 0.1;
+/// The radius of the uniform scale handle, relative to the gizmo size.
+const UNIFORM_SCALE_HANDLE_RADIUS: f32 = 0.15;
+/// How much the uniform scale handle scales the entity per logical pixel dragged.
+const UNIFORM_SCALE_SPEED: f32 = 0.01;
+
+/// A [`Plugin`] that draws the handles of [`TransformGizmo`]s, and lets pointers drag them.
+///
+/// Added by the [`GizmoPlugin`](crate::GizmoPlugin) when the `bevy_picking` feature is enabled.
+/// The handles are only picked if the `PickingPlugin` of `bevy_picking` is added to the app.
+pub struct TransformGizmoPlugin;
+
+impl Plugin for TransformGizmoPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<TransformGizmo>()
+            .register_type::<TransformGizmoConfigGroup>()
+            .insert_gizmo_group(
+                TransformGizmoConfigGroup::default(),
+                GizmoConfig {
+                    // The handles are drawn on top of the entity they move.
+                    depth_bias: -1.,
+                    ..default()
+                },
+            )
+            .add_systems(
+                PreUpdate,
+                (
+                    transform_gizmo_picking.in_set(PickSet::Backend),
+                    drag_transform_gizmos.after(PickSet::Events),
+                ),
+            )
+            .add_systems(
+                PostUpdate,
+                draw_transform_gizmos.after(TransformSystem::TransformPropagate),
+            );
+    }
+}
+
+/// The [`GizmoConfigGroup`] used to draw the handles of [`TransformGizmo`]s.
+#[derive(Clone, Reflect, GizmoConfigGroup)]
+pub struct TransformGizmoConfigGroup {
+    /// The color of the handles of the X axis.
+    ///
+    /// Defaults to [`Color::RED`].
+    pub x_color: Color,
+    /// The color of the handles of the Y axis.
+    ///
+    /// Defaults to [`Color::GREEN`].
+    pub y_color: Color,
+    /// The color of the handles of the Z axis.
+    ///
+    /// Defaults to [`Color::BLUE`].
+    pub z_color: Color,
+    /// The color of the handles that aren't tied to an axis.
+    ///
+    /// Defaults to [`Color::WHITE`].
+    pub center_color: Color,
+    /// The color of the hovered or dragged handle.
+    ///
+    /// Defaults to [`Color::YELLOW`].
+    pub highlight_color: Color,
+}
+
+impl Default for TransformGizmoConfigGroup {
+    fn default() -> Self {
+        Self {
+            x_color: Color::RED,
+            y_color: Color::GREEN,
+            z_color: Color::BLUE,
+            center_color: Color::WHITE,
+            highlight_color: Color::YELLOW,
+        }
+    }
+}
+
+/// Add this [`Component`] to an entity to draw handles that move, rotate or scale it when
+/// dragged by a pointer.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Default)]
+pub struct TransformGizmo {
+    /// Which handles are drawn, and thus how the entity is transformed.
+    pub mode: TransformGizmoMode,
+    /// Whether the handles are aligned with the axes of the entity or with the world axes.
+    ///
+    /// Scale handles are always aligned with the axes of the entity.
+    pub space: TransformGizmoSpace,
+    /// The length of the handles, in world units.
+    pub size: f32,
+    #[reflect(ignore)]
+    hovered: Option<(PointerId, TransformGizmoHandle)>,
+    #[reflect(ignore)]
+    drag: Option<GizmoDrag>,
+}
+
+impl Default for TransformGizmo {
+    fn default() -> Self {
+        Self {
+            mode: TransformGizmoMode::Translate,
+            space: TransformGizmoSpace::Local,
+            size: 1.,
+            hovered: None,
+            drag: None,
+        }
+    }
+}
+
+impl TransformGizmo {
+    /// Creates a [`TransformGizmo`] with the handles of `mode`.
+    pub fn new(mode: TransformGizmoMode) -> Self {
+        Self { mode, ..default() }
+    }
+
+    /// The handle hovered by a pointer, if any.
+    pub fn hovered_handle(&self) -> Option<TransformGizmoHandle> {
+        self.hovered.map(|(_, handle)| handle)
+    }
+
+    /// The handle being dragged by a pointer, if any.
+    pub fn dragged_handle(&self) -> Option<TransformGizmoHandle> {
+        self.drag.as_ref().map(|drag| drag.handle)
+    }
+}
+
+/// Which handles a [`TransformGizmo`] draws.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+pub enum TransformGizmoMode {
+    /// Arrows moving the entity along each axis, and squares moving it along each plane.
+    #[default]
+    Translate,
+    /// Circles rotating the entity around each axis.
+    Rotate,
+    /// Lines scaling the entity along each axis, and a sphere scaling it uniformly.
+    Scale,
+}
+
+impl TransformGizmoMode {
+    /// The handles drawn in this mode.
+    pub fn handles(self) -> &'static [TransformGizmoHandle] {
+        use TransformGizmoAxis::*;
+        use TransformGizmoHandle::*;
+        match self {
+            Self::Translate => &[
+                Translate(X),
+                Translate(Y),
+                Translate(Z),
+                TranslatePlane(X),
+                TranslatePlane(Y),
+                TranslatePlane(Z),
+            ],
+            Self::Rotate => &[Rotate(X), Rotate(Y), Rotate(Z)],
+            Self::Scale => &[Scale(X), Scale(Y), Scale(Z), ScaleUniform],
+        }
+    }
+}
+
+/// The axes the handles of a [`TransformGizmo`] are aligned with.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+pub enum TransformGizmoSpace {
+    /// The axes of the entity, from its [`GlobalTransform`].
+    #[default]
+    Local,
+    /// The axes of the world.
+    World,
+}
+
+/// An axis of a [`TransformGizmo`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Reflect)]
+pub enum TransformGizmoAxis {
+    /// The X axis.
+    X,
+    /// The Y axis.
+    Y,
+    /// The Z axis.
+    Z,
+}
+
+impl TransformGizmoAxis {
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// A handle of a [`TransformGizmo`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Reflect)]
+pub enum TransformGizmoHandle {
+    /// Moves the entity along an axis.
+    Translate(TransformGizmoAxis),
+    /// Moves the entity along the plane perpendicular to an axis.
+    TranslatePlane(TransformGizmoAxis),
+    /// Rotates the entity around an axis.
+    Rotate(TransformGizmoAxis),
+    /// Scales the entity along an axis.
+    Scale(TransformGizmoAxis),
+    /// Scales the entity along all of its axes.
+    ScaleUniform,
+}
+
+impl TransformGizmoHandle {
+    fn axis(self) -> Option<TransformGizmoAxis> {
+        match self {
+            Self::Translate(axis)
+            | Self::TranslatePlane(axis)
+            | Self::Rotate(axis)
+            | Self::Scale(axis) => Some(axis),
+            Self::ScaleUniform => None,
+        }
+    }
+}
+
+/// The position and axes of the handles of a [`TransformGizmo`] in world space.
+#[derive(Clone, Debug)]
+struct GizmoFrame {
+    origin: Vec3,
+    axes: [Vec3; 3],
+    size: f32,
+}
+
+impl GizmoFrame {
+    fn new(gizmo: &TransformGizmo, transform: &GlobalTransform) -> Self {
+        let (_, rotation, origin) = transform.to_scale_rotation_translation();
+        let rotation = match (gizmo.space, gizmo.mode) {
+            (TransformGizmoSpace::World, TransformGizmoMode::Translate)
+            | (TransformGizmoSpace::World, TransformGizmoMode::Rotate) => Quat::IDENTITY,
+            _ => rotation,
+        };
+        Self {
+            origin,
+            axes: [Vec3::X, Vec3::Y, Vec3::Z].map(|axis| rotation * axis),
+            size: gizmo.size,
+        }
+    }
+
+    fn axis(&self, axis: TransformGizmoAxis) -> Vec3 {
+        self.axes[axis.index()]
+    }
+
+    /// The two axes spanning the plane perpendicular to `axis`.
+    fn plane_axes(&self, axis: TransformGizmoAxis) -> (Vec3, Vec3) {
+        let index = axis.index();
+        (self.axes[(index + 1) % 3], self.axes[(index + 2) % 3])
+    }
+
+    /// The distance along `ray` at which it hits `handle`, if it does.
+    fn hit(&self, handle: TransformGizmoHandle, ray: Ray3d) -> Option<f32> {
+        let radius = PICK_RADIUS * self.size;
+        match handle {
+            TransformGizmoHandle::Translate(axis) | TransformGizmoHandle::Scale(axis) => {
+                let (distance, along_ray) =
+                    ray_segment_distance(ray, self.origin, self.axis(axis) * self.size);
+                (distance < radius).then_some(along_ray)
+            }
+            TransformGizmoHandle::TranslatePlane(axis) => {
+                let along_ray = ray.intersect_plane(self.origin, Plane3d::new(self.axis(axis)))?;
+                let offset = ray.get_point(along_ray) - self.origin;
+                let (u, v) = self.plane_axes(axis);
+                let center = PLANE_HANDLE_OFFSET * self.size;
+                let half_size = PLANE_HANDLE_HALF_SIZE * self.size;
+                ((offset.dot(u) - center).abs() < half_size
+                    && (offset.dot(v) - center).abs() < half_size)
+                    .then_some(along_ray)
+            }
+            TransformGizmoHandle::Rotate(axis) => {
+                let along_ray = ray.intersect_plane(self.origin, Plane3d::new(self.axis(axis)))?;
+                let offset = ray.get_point(along_ray) - self.origin;
+                ((offset.length() - self.size).abs() < radius).then_some(along_ray)
+            }
+            TransformGizmoHandle::ScaleUniform => {
+                let along_ray = (self.origin - ray.origin).dot(*ray.direction);
+                let distance = ray.get_point(along_ray).distance(self.origin);
+                (along_ray > 0. && distance < UNIFORM_SCALE_HANDLE_RADIUS * self.size)
+                    .then_some(along_ray)
+            }
+        }
+    }
+
+    /// The point where `ray` grabs `handle` while dragging it.
+    ///
+    /// This is the closest point of the axis of the axis handles, and the intersection with their
+    /// plane for the plane and rotation handles.
+    fn drag_point(&self, handle: TransformGizmoHandle, ray: Ray3d) -> Option<Vec3> {
+        match handle {
+            TransformGizmoHandle::Translate(axis) | TransformGizmoHandle::Scale(axis) => {
+                let axis = self.axis(axis);
+                closest_point_on_line(ray, self.origin, axis)
+                    .map(|along_axis| self.origin + axis * along_axis)
+            }
+            TransformGizmoHandle::TranslatePlane(axis) | TransformGizmoHandle::Rotate(axis) => {
+                let along_ray = ray.intersect_plane(self.origin, Plane3d::new(self.axis(axis)))?;
+                Some(ray.get_point(along_ray))
+            }
+            TransformGizmoHandle::ScaleUniform => Some(self.origin),
+        }
+    }
+}
+
+/// The parameter along the line going through `origin` in the normalized `direction` of its
+/// closest point to `ray`, or `None` if they are parallel.
+fn closest_point_on_line(ray: Ray3d, origin: Vec3, direction: Vec3) -> Option<f32> {
+    let offset = origin - ray.origin;
+    let cos = direction.dot(*ray.direction);
+    let denominator = 1. - cos * cos;
+    if denominator < f32::EPSILON {
+        return None;
+    }
+    Some((cos * ray.direction.dot(offset) - direction.dot(offset)) / denominator)
+}
+
+/// The distance between `ray` and the segment from `start` to `start + vector`, and the
+/// distance along the ray of its closest point to the segment.
+fn ray_segment_distance(ray: Ray3d, start: Vec3, vector: Vec3) -> (f32, f32) {
+    let length = vector.length();
+    let direction = vector / length;
+    let along_segment = closest_point_on_line(ray, start, direction)
+        .unwrap_or(0.)
+        .clamp(0., length);
+    let point = start + direction * along_segment;
+    let along_ray = (point - ray.origin).dot(*ray.direction).max(0.);
+    (ray.get_point(along_ray).distance(point), along_ray)
+}
+
+/// A handle of a [`TransformGizmo`] being dragged by a pointer.
+#[derive(Clone, Debug)]
+struct GizmoDrag {
+    pointer: PointerId,
+    camera: Entity,
+    handle: TransformGizmoHandle,
+    /// The frame of the handles when the drag started.
+    frame: GizmoFrame,
+    /// Where the handle was grabbed, see [`GizmoFrame::drag_point`].
+    grab_point: Vec3,
+    /// The position of the pointer when the drag started.
+    pointer_start: Vec2,
+    start_transform: Transform,
+    /// Converts world space vectors to the space of the parent of the entity.
+    world_to_parent: Affine3A,
+    /// The rotation of the parent of the entity in world space.
+    parent_rotation: Quat,
+}
+
+impl GizmoDrag {
+    /// The transform of the entity when the pointer is at `pointer_position`, casting `ray`.
+    fn transform(&self, ray: Ray3d, pointer_position: Vec2) -> Option<Transform> {
+        let frame = &self.frame;
+        let mut transform = self.start_transform;
+        match self.handle {
+            TransformGizmoHandle::Translate(_) | TransformGizmoHandle::TranslatePlane(_) => {
+                let delta = frame.drag_point(self.handle, ray)? - self.grab_point;
+                transform.translation += self.world_to_parent.transform_vector3(delta);
+            }
+            TransformGizmoHandle::Rotate(axis) => {
+                let axis = frame.axis(axis);
+                let from = self.grab_point - frame.origin;
+                let to = frame.drag_point(self.handle, ray)? - frame.origin;
+                let angle = from.cross(to).dot(axis).atan2(from.dot(to));
+                let local_axis = (self.parent_rotation.inverse() * axis).normalize();
+                transform.rotation = Quat::from_axis_angle(local_axis, angle) * transform.rotation;
+            }
+            TransformGizmoHandle::Scale(axis) => {
+                let direction = frame.axis(axis);
+                let start = (self.grab_point - frame.origin).dot(direction);
+                let current = (frame.drag_point(self.handle, ray)? - frame.origin).dot(direction);
+                if start.abs() < f32::EPSILON {
+                    return None;
+                }
+                transform.scale[axis.index()] *= current / start;
+            }
+            TransformGizmoHandle::ScaleUniform => {
+                // Dragging right or up grows the entity, left or down shrinks it.
+                let delta = pointer_position - self.pointer_start;
+                transform.scale *= (1. + (delta.x - delta.y) * UNIFORM_SCALE_SPEED).max(0.01);
+            }
+        }
+        Some(transform)
+    }
+}
+
+/// Sends the [`PointerHits`] of the handles of the [`TransformGizmo`]s under each pointer, and
+/// updates their hovered handle.
+///
+/// The handles are drawn on top of the entities, so they are reported in front of the other
+/// entities of each camera, but behind its UI.
+pub fn transform_gizmo_picking(
+    pointers: Query<(&PointerId, &PointerLocation)>,
+    cameras: Query<(Entity, &Camera, &GlobalTransform)>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    config_store: Res<GizmoConfigStore>,
+    mut gizmos: Query<(Entity, &mut TransformGizmo, &GlobalTransform)>,
+    mut output: EventWriter<PointerHits>,
+) {
+    let primary_window = primary_window.iter().next();
+    let enabled = config_store.config::<TransformGizmoConfigGroup>().0.enabled;
+
+    let mut nearest_handles = HashMap::<Entity, (f32, PointerId, TransformGizmoHandle)>::new();
+    for (pointer_id, pointer_location) in pointers.iter().filter(|_| enabled) {
+        let Some(location) = pointer_location.location() else {
+            continue;
+        };
+        for (camera_entity, camera, camera_transform) in &cameras {
+            let Some(ray) = camera_ray(location, camera, camera_transform, primary_window) else {
+                continue;
+            };
+
+            let mut picks = Vec::new();
+            for (entity, gizmo, transform) in &gizmos {
+                let frame = GizmoFrame::new(gizmo, transform);
+                let Some((depth, handle)) = gizmo
+                    .mode
+                    .handles()
+                    .iter()
+                    .filter_map(|&handle| Some((frame.hit(handle, ray)?, handle)))
+                    .min_by(|(a, _), (b, _)| a.total_cmp(b))
+                else {
+                    continue;
+                };
+
+                let position = ray.get_point(depth);
+                picks.push((
+                    entity,
+                    HitData::new(camera_entity, depth, Some(position), None),
+                ));
+                let nearest = nearest_handles
+                    .entry(entity)
+                    .or_insert((depth, *pointer_id, handle));
+                if depth < nearest.0 {
+                    *nearest = (depth, *pointer_id, handle);
+                }
+            }
+            if !picks.is_empty() {
+                output.send(PointerHits::new(
+                    *pointer_id,
+                    picks,
+                    camera.order as f32 + 0.25,
+                ));
+            }
+        }
+    }
+
+    for (entity, mut gizmo, _) in &mut gizmos {
+        let hovered = nearest_handles
+            .get(&entity)
+            .map(|&(_, pointer_id, handle)| (pointer_id, handle));
+        if gizmo.hovered != hovered {
+            gizmo.hovered = hovered;
+        }
+    }
+}
+
+/// Starts dragging the hovered handles of [`TransformGizmo`]s when pressed with the primary
+/// button, and updates the [`Transform`] of the dragged entities until the button is released.
+pub fn drag_transform_gizmos(
+    mut downs: EventReader<Pointer<Down>>,
+    pointers: Query<(&PointerId, &PointerLocation, &PointerPress)>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    mut gizmos: Query<(&mut TransformGizmo, &mut Transform, &GlobalTransform)>,
+) {
+    let primary_window = primary_window.iter().next();
+
+    for down in downs.read() {
+        if down.button != PointerButton::Primary {
+            continue;
+        }
+        let Ok((mut gizmo, transform, global_transform)) = gizmos.get_mut(down.target) else {
+            continue;
+        };
+        let Some((pointer, handle)) = gizmo.hovered else {
+            continue;
+        };
+        if pointer != down.pointer_id || gizmo.drag.is_some() {
+            continue;
+        }
+        let Ok((camera, camera_transform)) = cameras.get(down.hit.camera) else {
+            continue;
+        };
+        let Some(ray) = camera_ray(
+            &down.pointer_location,
+            camera,
+            camera_transform,
+            primary_window,
+        ) else {
+            continue;
+        };
+
+        let frame = GizmoFrame::new(&gizmo, global_transform);
+        let Some(grab_point) = frame.drag_point(handle, ray) else {
+            continue;
+        };
+        let parent_to_world = global_transform.affine() * transform.compute_affine().inverse();
+        let (_, global_rotation, _) = global_transform.to_scale_rotation_translation();
+        gizmo.drag = Some(GizmoDrag {
+            pointer,
+            camera: down.hit.camera,
+            handle,
+            frame,
+            grab_point,
+            pointer_start: down.pointer_location.position,
+            start_transform: *transform,
+            world_to_parent: parent_to_world.inverse(),
+            parent_rotation: global_rotation * transform.rotation.inverse(),
+        });
+    }
+
+    for (mut gizmo, mut transform, _) in &mut gizmos {
+        let Some(drag) = &gizmo.drag else {
+            continue;
+        };
+        let pointer = pointers
+            .iter()
+            .find(|(pointer_id, ..)| **pointer_id == drag.pointer);
+        let Some((_, pointer_location, _)) =
+            pointer.filter(|(.., press)| press.is_pressed(PointerButton::Primary))
+        else {
+            gizmo.drag = None;
+            continue;
+        };
+
+        let Some(location) = pointer_location.location() else {
+            continue;
+        };
+        let Ok((camera, camera_transform)) = cameras.get(drag.camera) else {
+            continue;
+        };
+        let Some(ray) = camera_ray(location, camera, camera_transform, primary_window) else {
+            continue;
+        };
+        if let Some(new_transform) = drag.transform(ray, location.position) {
+            if *transform != new_transform {
+                *transform = new_transform;
+            }
+        }
+    }
+}
+
+/// Draws the handles of the [`TransformGizmo`]s, highlighting the hovered or dragged one.
+fn draw_transform_gizmos(
+    query: Query<(&TransformGizmo, &GlobalTransform)>,
+    mut gizmos: Gizmos<TransformGizmoConfigGroup>,
+) {
+    for (gizmo, transform) in &query {
+        let frame = GizmoFrame::new(gizmo, transform);
+        let highlighted = gizmo.dragged_handle().or(gizmo.hovered_handle());
+        for &handle in gizmo.mode.handles() {
+            let config = &gizmos.config_ext;
+            let color = if highlighted == Some(handle) {
+                config.highlight_color
+            } else {
+                match handle.axis() {
+                    Some(TransformGizmoAxis::X) => config.x_color,
+                    Some(TransformGizmoAxis::Y) => config.y_color,
+                    Some(TransformGizmoAxis::Z) => config.z_color,
+                    None => config.center_color,
+                }
+            };
+            draw_handle(&mut gizmos, &frame, handle, color);
+        }
+    }
+}
+
+fn draw_handle(
+    gizmos: &mut Gizmos<'_, '_, TransformGizmoConfigGroup>,
+    frame: &GizmoFrame,
+    handle: TransformGizmoHandle,
+    color: Color,
+) {
+    let size = frame.size;
+    match handle {
+        TransformGizmoHandle::Translate(axis) => {
+            let end = frame.origin + frame.axis(axis) * size;
+            gizmos
+                .arrow(frame.origin, end, color)
+                .with_tip_length(size * 0.2);
+        }
+        TransformGizmoHandle::TranslatePlane(axis) => {
+            let (u, v) = frame.plane_axes(axis);
+            let center = frame.origin + (u + v) * PLANE_HANDLE_OFFSET * size;
+            let rotation = Quat::from_mat3(&Mat3::from_cols(u, v, frame.axis(axis)));
+            gizmos.rect(
+                center,
+                rotation,
+                Vec2::splat(2. * PLANE_HANDLE_HALF_SIZE * size),
+                color,
+            );
+        }
+        TransformGizmoHandle::Rotate(axis) => {
+            let (u, v) = frame.plane_axes(axis);
+            let rotation = Quat::from_mat3(&Mat3::from_cols(u, v, frame.axis(axis)));
+            gizmos
+                .ellipse(frame.origin, rotation, Vec2::splat(size), color)
+                .segments(64);
+        }
+        TransformGizmoHandle::Scale(axis) => {
+            let axis_index = axis.index();
+            let end = frame.origin + frame.axes[axis_index] * size;
+            gizmos.line(frame.origin, end, color);
+            let rotation = Quat::from_mat3(&Mat3::from_cols(
+                frame.axes[0],
+                frame.axes[1],
+                frame.axes[2],
+            ));
+            gizmos.cuboid(
+                Transform::from_translation(end)
+                    .with_rotation(rotation)
+                    .with_scale(Vec3::splat(SCALE_HANDLE_SIZE * size)),
+                color,
+            );
+        }
+        TransformGizmoHandle::ScaleUniform => {
+            gizmos.sphere(
+                frame.origin,
+                Quat::IDENTITY,
+                UNIFORM_SCALE_HANDLE_RADIUS * size,
+                color,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_app::Update;
+    use bevy_asset::{AssetEvent, Assets};
+    use bevy_picking::{
+        backend::HitData,
+        events::Down,
+        pointer::{update_pointers, Location, PointerAction, PointerBundle, PointerInput},
+    };
+    use bevy_render::{
+        camera::{
+            camera_system, ManualTextureViews, NormalizedRenderTarget, PerspectiveProjection,
+        },
+        texture::Image,
+    };
+    use bevy_window::{
+        Window, WindowCreated, WindowRef, WindowResized, WindowResolution, WindowScaleFactorChanged,
+    };
+    use std::f32::consts::FRAC_PI_2;
+    use TransformGizmoAxis::*;
+    use TransformGizmoHandle::*;
+
+    /// A gizmo at `(1, 0, 0)`, rotated by a quarter turn around Z.
+    fn rotated_frame(space: TransformGizmoSpace, size: f32) -> GizmoFrame {
+        let gizmo = TransformGizmo {
+            space,
+            size,
+            ..default()
+        };
+        let transform =
+            Transform::from_xyz(1., 0., 0.).with_rotation(Quat::from_rotation_z(FRAC_PI_2));
+        GizmoFrame::new(&gizmo, &transform.into())
+    }
+
+    /// A ray going down the Z axis, through `(x, y, 0)`.
+    fn ray(x: f32, y: f32) -> Ray3d {
+        Ray3d::new(Vec3::new(x, y, 5.), Vec3::NEG_Z)
+    }
+
+    #[test]
+    fn handle_hits() {
+        let frame = rotated_frame(TransformGizmoSpace::Local, 1.);
+
+        // The local X axis points up in the world.
+        assert_eq!(frame.hit(Translate(X), ray(1., 0.5)), Some(5.));
+        assert_eq!(frame.hit(Translate(Y), ray(1., 0.5)), None);
+        assert_eq!(frame.hit(Translate(Y), ray(0.5, 0.)), Some(5.));
+        assert_eq!(frame.hit(Scale(X), ray(1., 0.9)), Some(5.));
+        assert_eq!(frame.hit(Scale(X), ray(1., 1.2)), None);
+
+        // The plane handle of Z spans the local Y and X axes, pointing left and up.
+        assert_eq!(frame.hit(TranslatePlane(Z), ray(0.7, 0.3)), Some(5.));
+        assert_eq!(frame.hit(TranslatePlane(Z), ray(1.3, 0.3)), None);
+
+        assert_eq!(frame.hit(Rotate(Z), ray(1., 1.)), Some(5.));
+        assert_eq!(frame.hit(Rotate(Z), ray(1., 0.5)), None);
+
+        assert_eq!(frame.hit(ScaleUniform, ray(1., 0.1)), Some(5.));
+        let away = Ray3d::new(Vec3::new(1., 0., 5.), Vec3::Z);
+        assert_eq!(frame.hit(ScaleUniform, away), None);
+    }
+
+    #[test]
+    fn handle_hits_follow_space_and_size() {
+        let world = rotated_frame(TransformGizmoSpace::World, 1.);
+        assert_eq!(world.hit(Translate(X), ray(1.5, 0.)), Some(5.));
+        assert_eq!(world.hit(Translate(X), ray(1., 0.5)), None);
+
+        let large = rotated_frame(TransformGizmoSpace::Local, 2.);
+        assert_eq!(large.hit(Translate(X), ray(1., 1.9)), Some(5.));
+        assert_eq!(large.hit(Translate(X), ray(1.15, 1.)), Some(5.));
+        let small = rotated_frame(TransformGizmoSpace::Local, 1.);
+        assert_eq!(small.hit(Translate(X), ray(1., 1.9)), None);
+        assert_eq!(small.hit(Translate(X), ray(1.15, 1.)), None);
+    }
+
+    #[test]
+    fn drag_points() {
+        let frame = rotated_frame(TransformGizmoSpace::Local, 1.);
+
+        let point = frame.drag_point(Translate(X), ray(3., 0.5)).unwrap();
+        assert!(point.abs_diff_eq(Vec3::new(1., 0.5, 0.), 1e-5));
+        let point = frame.drag_point(TranslatePlane(Z), ray(2., 3.)).unwrap();
+        assert!(point.abs_diff_eq(Vec3::new(2., 3., 0.), 1e-5));
+        let point = frame.drag_point(Rotate(Z), ray(2., 3.)).unwrap();
+        assert!(point.abs_diff_eq(Vec3::new(2., 3., 0.), 1e-5));
+        assert_eq!(
+            frame.drag_point(ScaleUniform, ray(2., 3.)),
+            Some(frame.origin)
+        );
+
+        // Rays parallel to the axis or to the plane don't grab it.
+        let world = rotated_frame(TransformGizmoSpace::World, 1.);
+        assert_eq!(world.drag_point(Translate(Z), ray(2., 3.)), None);
+        let sideways = Ray3d::new(Vec3::new(0., 0., 1.), Vec3::X);
+        assert_eq!(world.drag_point(Rotate(Z), sideways), None);
+    }
+
+    /// Drags `handle` of `gizmo` from where `from` is seen by a camera to where `to` is seen.
+    ///
+    /// The gizmo is on an entity with an identity [`Transform`], whose parent has
+    /// `parent_transform`, and the returned [`Transform`] is the one of the entity after the drag.
+    fn drag(
+        parent_transform: Transform,
+        gizmo: TransformGizmo,
+        handle: TransformGizmoHandle,
+        from: Vec3,
+        to: Vec3,
+    ) -> Transform {
+        let mut app = App::new();
+        app.add_event::<WindowCreated>()
+            .add_event::<WindowResized>()
+            .add_event::<WindowScaleFactorChanged>()
+            .add_event::<AssetEvent<Image>>()
+            .add_event::<PointerInput>()
+            .add_event::<Pointer<Down>>()
+            .init_resource::<Assets<Image>>()
+            .init_resource::<ManualTextureViews>()
+            .add_systems(
+                Update,
+                (
+                    camera_system::<PerspectiveProjection>,
+                    update_pointers,
+                    drag_transform_gizmos,
+                )
+                    .chain(),
+            );
+
+        let window = app
+            .world
+            .spawn((
+                Window {
+                    resolution: WindowResolution::new(800., 600.),
+                    ..default()
+                },
+                PrimaryWindow,
+            ))
+            .id();
+        let camera_transform = Transform::from_xyz(3., 4., 10.).looking_at(Vec3::ZERO, Vec3::Y);
+        let camera = app
+            .world
+            .spawn((
+                Camera::default(),
+                PerspectiveProjection::default(),
+                GlobalTransform::from(camera_transform),
+            ))
+            .id();
+        app.world.spawn(PointerBundle::new(PointerId::Mouse));
+        let entity = app
+            .world
+            .spawn((
+                TransformGizmo {
+                    hovered: Some((PointerId::Mouse, handle)),
+                    ..gizmo
+                },
+                Transform::IDENTITY,
+                GlobalTransform::from(parent_transform),
+            ))
+            .id();
+        // Computes the viewport of the camera.
+        app.update();
+
+        let location = |point| {
+            let camera = app.world.get::<Camera>(camera).unwrap();
+            let target = WindowRef::Primary.normalize(Some(window)).unwrap();
+            Location {
+                target: NormalizedRenderTarget::Window(target),
+                position: camera
+                    .world_to_viewport(&camera_transform.into(), point)
+                    .unwrap(),
+            }
+        };
+        let (from, to) = (location(from), location(to));
+        app.world.send_event(PointerInput::new(
+            PointerId::Mouse,
+            from.clone(),
+            PointerAction::Pressed(PointerButton::Primary),
+        ));
+        app.world.send_event(Pointer::new(
+            entity,
+            PointerId::Mouse,
+            from.clone(),
+            Down {
+                button: PointerButton::Primary,
+                hit: HitData::new(camera, 0., None, None),
+            },
+        ));
+        app.update();
+        assert_eq!(
+            app.world.get::<Transform>(entity),
+            Some(&Transform::IDENTITY)
+        );
+
+        app.world.send_event(PointerInput::new(
+            PointerId::Mouse,
+            to.clone(),
+            PointerAction::Moved {
+                delta: to.position - from.position,
+            },
+        ));
+        app.update();
+        *app.world.get::<Transform>(entity).unwrap()
+    }
+
+    #[test]
+    fn translate_under_transformed_parent() {
+        let parent =
+            Transform::from_rotation(Quat::from_rotation_y(FRAC_PI_2)).with_scale(Vec3::splat(2.));
+        let gizmo = TransformGizmo {
+            space: TransformGizmoSpace::World,
+            ..default()
+        };
+
+        let transform = drag(parent, gizmo.clone(), Translate(X), Vec3::ZERO, Vec3::X);
+        let global = GlobalTransform::from(parent) * transform;
+        assert!(global.translation().abs_diff_eq(Vec3::X, 1e-4));
+        assert!(transform
+            .translation
+            .abs_diff_eq(Vec3::new(0., 0., 0.5), 1e-4));
+
+        let to = Vec3::new(1., 0., -2.);
+        let transform = drag(parent, gizmo, TranslatePlane(Y), Vec3::ZERO, to);
+        let global = GlobalTransform::from(parent) * transform;
+        assert!(global.translation().abs_diff_eq(to, 1e-4));
+    }
+
+    #[test]
+    fn rotate_under_transformed_parent() {
+        let parent_rotation = Quat::from_rotation_y(FRAC_PI_2);
+        let parent = Transform::from_rotation(parent_rotation).with_scale(Vec3::splat(2.));
+        let gizmo = TransformGizmo {
+            mode: TransformGizmoMode::Rotate,
+            space: TransformGizmoSpace::World,
+            ..default()
+        };
+
+        let transform = drag(parent, gizmo, Rotate(Z), Vec3::X, Vec3::Y);
+        let global = GlobalTransform::from(parent) * transform;
+        let (_, rotation, translation) = global.to_scale_rotation_translation();
+        let expected = Quat::from_rotation_z(FRAC_PI_2) * parent_rotation;
+        assert!(rotation.angle_between(expected) < 1e-3);
+        assert!(translation.abs_diff_eq(Vec3::ZERO, 1e-4));
+    }
+
+    #[test]
+    fn scale_under_transformed_parent() {
+        // The X axis of the entity points up in the world.
+        let parent =
+            Transform::from_rotation(Quat::from_rotation_z(FRAC_PI_2)).with_scale(Vec3::splat(2.));
+        let gizmo = TransformGizmo::new(TransformGizmoMode::Scale);
+
+        let from = Vec3::new(0., 1., 0.);
+        let to = Vec3::new(0., 1.5, 0.);
+        let transform = drag(parent, gizmo, Scale(X), from, to);
+        assert!(transform.scale.abs_diff_eq(Vec3::new(1.5, 1., 1.), 1e-4));
+        assert_eq!(transform.translation, Vec3::ZERO);
+        assert_eq!(transform.rotation, Quat::IDENTITY);
+    }
+
+    #[test]
+    fn drags_from_start_transform() {
+        let frame = rotated_frame(TransformGizmoSpace::Local, 1.);
+        let drag = GizmoDrag {
+            pointer: PointerId::Mouse,
+            camera: Entity::PLACEHOLDER,
+            handle: ScaleUniform,
+            grab_point: frame.origin,
+            frame,
+            pointer_start: Vec2::new(100., 100.),
+            start_transform: Transform::from_scale(Vec3::splat(2.)),
+            world_to_parent: Affine3A::IDENTITY,
+            parent_rotation: Quat::IDENTITY,
+        };
+
+        // Dragging right and up grows the entity, whatever the ray.
+        let transform = drag.transform(ray(0., 0.), Vec2::new(110., 90.)).unwrap();
+        assert!(transform.scale.abs_diff_eq(Vec3::splat(2.4), 1e-5));
+        let transform = drag.transform(ray(0., 0.), Vec2::new(90., 100.)).unwrap();
+        assert!(transform.scale.abs_diff_eq(Vec3::splat(1.8), 1e-5));
+    }
+}
@@ -8,8 +8,10 @@ use bevy_animation::AnimationClip;
 use bevy_utils::HashMap;
 
 mod loader;
+mod processor;
 mod vertex_attributes;
 pub use loader::*;
+pub use processor::*;
 
 use bevy_app::prelude::*;
 use bevy_asset::{Asset, AssetApp, Handle};
@@ -53,7 +55,8 @@ impl Plugin for GltfPlugin {
             .init_asset::<GltfNode>()
             .init_asset::<GltfPrimitive>()
             .init_asset::<GltfMesh>()
-            .preregister_asset_loader::<GltfLoader>(&["gltf", "glb"]);
+            .preregister_asset_loader::<GltfLoader>(&["gltf", "glb"])
+            .register_asset_processor(GltfLightmapUvProcessor);
     }
 
     fn finish(&self, app: &mut App) {
@@ -18,7 +18,8 @@ use bevy_render::{
     mesh::{
         morph::{MeshMorphWeights, MorphAttributes, MorphTargetImage, MorphWeights},
         skinning::{SkinnedMesh, SkinnedMeshInverseBindposes},
-        Indices, Mesh, MeshVertexAttribute, VertexAttributeValues,
+        Indices, LightmapUvSettings, Mesh, MeshCleanupSettings, MeshVertexAttribute,
+        VertexAttributeValues,
     },
     prelude::SpatialBundle,
     primitives::Aabb,
@@ -133,6 +134,31 @@ pub struct GltfLoaderSettings {
     pub load_lights: bool,
     /// If true, the loader will include the root of the gltf root node.
     pub include_source: bool,
+    /// If set, the loader will generate lightmap UVs ([`Mesh::ATTRIBUTE_UV_1`]) for the
+    /// triangle meshes that don't have them, with [`Mesh::generate_lightmap_uvs`].
+    pub lightmap_uvs: Option<LightmapUvSettings>,
+    /// Which meshes the loader will generate tangents ([`Mesh::ATTRIBUTE_TANGENT`]) for, with
+    /// [`Mesh::generate_tangents`].
+    pub tangents: GltfTangentGeneration,
+    /// If set, the loader will weld the vertices and remove the degenerate triangles of the
+    /// meshes with [`Mesh::clean_up`], before generating their tangents.
+    pub mesh_cleanup: Option<MeshCleanupSettings>,
+}
+
+/// Which meshes the [`GltfLoader`] generates tangents for, see [`GltfLoaderSettings::tangents`].
+///
+/// Tangents are only generated for meshes with normals.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum GltfTangentGeneration {
+    /// Never generate tangents.
+    Never,
+    /// Generate tangents for the meshes missing them whose material has a normal map.
+    #[default]
+    NormalMapped,
+    /// Generate tangents for all the meshes missing them.
+    Missing,
+    /// Generate tangents for all the meshes, replacing the ones in the file.
+    Always,
 }
 
 impl Default for GltfLoaderSettings {
@@ -142,6 +168,9 @@ impl Default for GltfLoaderSettings {
             load_cameras: true,
             load_lights: true,
             include_source: false,
+            lightmap_uvs: None,
+            tangents: GltfTangentGeneration::default(),
+            mesh_cleanup: None,
         }
     }
 }
@@ -304,7 +333,7 @@ async fn load_gltf<'a, 'b, 'c>(
                 sampler_descriptor,
             } => {
                 load_context.load_with_settings(path, move |settings: &mut ImageLoaderSettings| {
-                    settings.is_srgb = is_srgb;
+                    settings.color_space = is_srgb.into();
                     settings.sampler = ImageSampler::Descriptor(sampler_descriptor.clone());
                 })
             }
@@ -469,17 +498,32 @@ async fn load_gltf<'a, 'b, 'c>(
                 }
             }
 
-            if let Some(vertex_attribute) = reader
+            let has_tangents = if let Some(vertex_attribute) = reader
                 .read_tangents()
                 .map(|v| VertexAttributeValues::Float32x4(v.collect()))
             {
                 mesh.insert_attribute(Mesh::ATTRIBUTE_TANGENT, vertex_attribute);
-            } else if mesh.attribute(Mesh::ATTRIBUTE_NORMAL).is_some()
-                && primitive.material().normal_texture().is_some()
-            {
-                bevy_log::debug!(
-                    "Missing vertex tangents, computing them using the mikktspace algorithm"
-                );
+                true
+            } else {
+                false
+            };
+
+            if let Some(mesh_cleanup_settings) = &settings.mesh_cleanup {
+                if let Err(err) = mesh.clean_up(mesh_cleanup_settings) {
+                    warn!("Failed to clean up mesh {:?}: {}", primitive_label, err);
+                }
+            }
+
+            let generate_tangents = match settings.tangents {
+                GltfTangentGeneration::Never => false,
+                GltfTangentGeneration::NormalMapped => {
+                    !has_tangents && primitive.material().normal_texture().is_some()
+                }
+                GltfTangentGeneration::Missing => !has_tangents,
+                GltfTangentGeneration::Always => true,
+            };
+            if generate_tangents && mesh.attribute(Mesh::ATTRIBUTE_NORMAL).is_some() {
+                bevy_log::debug!("Computing vertex tangents using the mikktspace algorithm");
                 if let Err(err) = mesh.generate_tangents() {
                     warn!(
                         "Failed to generate vertex tangents using the mikktspace algorithm: {:?}",
@@ -488,6 +532,17 @@ async fn load_gltf<'a, 'b, 'c>(
                 }
             }
 
+            if let Some(lightmap_uv_settings) = &settings.lightmap_uvs {
+                if !mesh.contains_attribute(Mesh::ATTRIBUTE_UV_1)
+                    && matches!(mesh.primitive_topology(), PrimitiveTopology::TriangleList)
+                {
+                    bevy_log::debug!("Missing lightmap UVs, generating them");
+                    if let Err(err) = mesh.generate_lightmap_uvs(lightmap_uv_settings) {
+                        warn!("Failed to generate lightmap UVs: {}", err);
+                    }
+                }
+            }
+
             let mesh = load_context.add_labeled_asset(primitive_label, mesh);
             primitives.push(super::GltfPrimitive {
                 mesh,
@@ -0,0 +1,66 @@
+use bevy_asset::{
+    io::Writer,
+    meta::{AssetAction, AssetMeta},
+    processor::{Process, ProcessContext, ProcessError},
+};
+use bevy_render::mesh::LightmapUvSettings;
+use bevy_utils::BoxedFuture;
+use futures_lite::AsyncWriteExt;
+use serde::{Deserialize, Serialize};
+
+use crate::{GltfLoader, GltfLoaderSettings};
+
+/// An asset [`Process`] step that sets up glTF files to have lightmap UVs
+/// ([`Mesh::ATTRIBUTE_UV_1`](bevy_render::mesh::Mesh::ATTRIBUTE_UV_1)) generated for the meshes
+/// that don't have them.
+///
+/// The glTF data is written unchanged, and the processed file is loaded with
+/// [`GltfLoaderSettings::lightmap_uvs`] set, which generates the UVs with
+/// [`Mesh::generate_lightmap_uvs`](bevy_render::mesh::Mesh::generate_lightmap_uvs). This lets
+/// the UVs be generated for all the glTF files of a project by making this the default processor
+/// of their extensions:
+///
+/// ```no_run
+/// # use bevy_app::App;
+/// # use bevy_asset::AssetApp;
+/// # use bevy_gltf::GltfLightmapUvProcessor;
+/// # let mut app = App::new();
+/// app.set_default_asset_processor::<GltfLightmapUvProcessor>("glb");
+/// ```
+pub struct GltfLightmapUvProcessor;
+
+/// Settings for the [`GltfLightmapUvProcessor`].
+#[derive(Serialize, Deserialize, Default)]
+pub struct GltfLightmapUvProcessorSettings {
+    /// The settings used to load the processed file, whose
+    /// [`lightmap_uvs`](GltfLoaderSettings::lightmap_uvs) are replaced by the ones below.
+    pub loader_settings: GltfLoaderSettings,
+    /// The settings used to generate the lightmap UVs.
+    pub lightmap_uvs: LightmapUvSettings,
+}
+
+impl Process for GltfLightmapUvProcessor {
+    type Settings = GltfLightmapUvProcessorSettings;
+    type OutputLoader = GltfLoader;
+
+    fn process<'a>(
+        &'a self,
+        context: &'a mut ProcessContext,
+        meta: AssetMeta<(), Self>,
+        writer: &'a mut Writer,
+    ) -> BoxedFuture<'a, Result<GltfLoaderSettings, ProcessError>> {
+        Box::pin(async move {
+            let AssetAction::Process { settings, .. } = meta.asset else {
+                return Err(ProcessError::WrongMetaType);
+            };
+            writer
+                .write_all(context.asset_bytes())
+                .await
+                .map_err(|error| ProcessError::AssetSaveError(error.into()))?;
+            Ok(GltfLoaderSettings {
+                lightmap_uvs: Some(settings.lightmap_uvs),
+                ..settings.loader_settings
+            })
+        })
+    }
+}
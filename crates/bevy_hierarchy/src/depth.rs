@@ -0,0 +1,135 @@
+use bevy_ecs::prelude::*;
+#[cfg(feature = "reflect")]
+use bevy_ecs::reflect::ReflectComponent;
+use bevy_utils::EntityHashSet;
+
+use crate::{Children, HierarchyQueryExt, Parent};
+
+/// The number of ancestors of an entity in its hierarchy:
+/// `0` for a root entity, `1` for its children, and so on.
+///
+/// This component is added to every entity with a [`Parent`] or [`Children`],
+/// and kept up to date by [`update_depth_system`] when the hierarchy changes.
+/// It should not be inserted or mutated manually.
+///
+/// Useful to sort entities by their position in the hierarchy without traversing it,
+/// for example to process parents before their children.
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+#[cfg_attr(feature = "reflect", reflect(Component, PartialEq))]
+pub struct Depth(u32);
+
+impl Depth {
+    /// Gets the number of ancestors of the entity.
+    #[inline]
+    pub fn get(self) -> u32 {
+        self.0
+    }
+
+    /// Returns `true` if the entity has no parent.
+    #[inline]
+    pub fn is_root(self) -> bool {
+        self.0 == 0
+    }
+}
+
+/// Label for [`update_depth_system`].
+///
+/// Runs in [`PostUpdate`](bevy_app::PostUpdate) when using the [`HierarchyPlugin`](crate::HierarchyPlugin).
+#[derive(Debug, PartialEq, Eq, Clone, Hash, SystemSet)]
+pub struct DepthSystem;
+
+/// Updates the [`Depth`] of the entities whose [`Parent`] changed, along with their descendants.
+///
+/// Newly added [`Depth`] components are only visible once commands are applied.
+#[allow(clippy::too_many_arguments)]
+pub fn update_depth_system(
+    mut commands: Commands,
+    changed_query: Query<Entity, Or<(Changed<Parent>, Added<Children>)>>,
+    mut orphaned: RemovedComponents<Parent>,
+    parent_query: Query<&Parent>,
+    children_query: Query<&Children>,
+    mut depth_query: Query<&mut Depth>,
+    mut changed: Local<EntityHashSet<Entity>>,
+    mut stack: Local<Vec<(Entity, u32)>>,
+) {
+    changed.clear();
+    changed.extend(changed_query.iter().chain(orphaned.read()));
+
+    for &entity in changed.iter() {
+        // Subtrees of changed entities are updated along with their changed ancestor.
+        let mut depth = 0;
+        let mut has_changed_ancestor = false;
+        for ancestor in parent_query.iter_ancestors(entity) {
+            if changed.contains(&ancestor) {
+                has_changed_ancestor = true;
+                break;
+            }
+            depth += 1;
+        }
+        if has_changed_ancestor {
+            continue;
+        }
+
+        stack.push((entity, depth));
+        while let Some((entity, depth)) = stack.pop() {
+            match depth_query.get_mut(entity) {
+                Ok(mut current) => {
+                    current.set_if_neq(Depth(depth));
+                }
+                Err(_) => {
+                    // Despawned entities are reported as orphaned.
+                    let Some(mut entity_commands) = commands.get_entity(entity) else {
+                        continue;
+                    };
+                    entity_commands.insert(Depth(depth));
+                }
+            }
+            if let Ok(children) = children_query.get(entity) {
+                stack.extend(children.iter().map(|&child| (child, depth + 1)));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::prelude::*;
+
+    use crate::{update_depth_system, BuildWorldChildren, Depth};
+
+    fn depths<const N: usize>(world: &World, entities: [Entity; N]) -> [Option<u32>; N] {
+        entities.map(|entity| world.get::<Depth>(entity).map(|depth| depth.get()))
+    }
+
+    #[test]
+    fn depth_follows_hierarchy() {
+        let mut world = World::new();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(update_depth_system);
+
+        let [a, b, c, d, e] = std::array::from_fn(|_| world.spawn_empty().id());
+        world.entity_mut(a).push_children(&[b]);
+        world.entity_mut(b).push_children(&[c]);
+        world.entity_mut(d).push_children(&[e]);
+        schedule.run(&mut world);
+        assert_eq!(
+            depths(&world, [a, b, c, d, e]),
+            [Some(0), Some(1), Some(2), Some(0), Some(1)]
+        );
+
+        world.entity_mut(d).set_parent(c);
+        schedule.run(&mut world);
+        assert_eq!(
+            depths(&world, [a, b, c, d, e]),
+            [Some(0), Some(1), Some(2), Some(3), Some(4)]
+        );
+
+        world.entity_mut(b).remove_parent();
+        schedule.run(&mut world);
+        assert_eq!(
+            depths(&world, [a, b, c, d, e]),
+            [Some(0), Some(0), Some(1), Some(2), Some(3)]
+        );
+    }
+}
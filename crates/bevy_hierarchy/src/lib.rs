@@ -15,6 +15,7 @@
 //! More advanced users may also appreciate
 //! [query extension methods] to traverse hierarchies,
 //! and [events] to notify hierarchical changes.
+//! The [`Depth`] of each entity in a hierarchy is also cached in a component.
 //! There is also a [diagnostic plugin] to validate property propagation.
 //!
 //! # Hierarchy management
@@ -63,10 +64,15 @@ pub use valid_parent_check_plugin::*;
 mod query_extension;
 pub use query_extension::*;
 
+mod depth;
+pub use depth::*;
+
 #[doc(hidden)]
 pub mod prelude {
     #[doc(hidden)]
-    pub use crate::{child_builder::*, components::*, hierarchy::*, query_extension::*};
+    pub use crate::{
+        child_builder::*, components::*, depth::Depth, hierarchy::*, query_extension::*,
+    };
 
     #[doc(hidden)]
     #[cfg(feature = "bevy_app")]
@@ -84,13 +90,17 @@ use bevy_app::prelude::*;
 #[derive(Default)]
 pub struct HierarchyPlugin;
 
+#[cfg(feature = "bevy_app")]
+use bevy_ecs::schedule::IntoSystemConfigs;
 #[cfg(feature = "bevy_app")]
 use bevy_utils::smallvec::SmallVec;
 impl Plugin for HierarchyPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<Children>()
             .register_type::<Parent>()
+            .register_type::<Depth>()
             .register_type::<SmallVec<[bevy_ecs::entity::Entity; 8]>>()
-            .add_event::<HierarchyEvent>();
+            .add_event::<HierarchyEvent>()
+            .add_systems(PostUpdate, update_depth_system.in_set(DepthSystem));
     }
 }
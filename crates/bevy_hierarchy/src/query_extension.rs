@@ -55,6 +55,26 @@ pub trait HierarchyQueryExt<'w, 's, D: QueryData, F: QueryFilter> {
     fn iter_ancestors(&'w self, entity: Entity) -> AncestorIter<'w, 's, D, F>
     where
         D::ReadOnly: WorldQuery<Item<'w> = &'w Parent>;
+
+    /// Returns the topmost ancestor of `entity`, or `entity` itself if it has no parent.
+    ///
+    /// Can only be called on a [`Query`] of [`Parent`] (i.e. `Query<&Parent>`).
+    ///
+    /// # Examples
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_hierarchy::prelude::*;
+    /// # #[derive(Component)]
+    /// # struct Marker;
+    /// fn system(query: Query<Entity, With<Marker>>, parent_query: Query<&Parent>) {
+    ///     let entity = query.single();
+    ///     let root = parent_query.root_ancestor(entity);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    fn root_ancestor(&'w self, entity: Entity) -> Entity
+    where
+        D::ReadOnly: WorldQuery<Item<'w> = &'w Parent>;
 }
 
 impl<'w, 's, D: QueryData, F: QueryFilter> HierarchyQueryExt<'w, 's, D, F> for Query<'w, 's, D, F> {
@@ -71,6 +91,13 @@ impl<'w, 's, D: QueryData, F: QueryFilter> HierarchyQueryExt<'w, 's, D, F> for Q
     {
         AncestorIter::new(self, entity)
     }
+
+    fn root_ancestor(&'w self, entity: Entity) -> Entity
+    where
+        D::ReadOnly: WorldQuery<Item<'w> = &'w Parent>,
+    {
+        self.iter_ancestors(entity).last().unwrap_or(entity)
+    }
 }
 
 /// An [`Iterator`] of [`Entity`]s over the descendants of an [`Entity`].
@@ -201,4 +228,21 @@ mod tests {
 
         assert_eq!([&A(1), &A(0)], result.as_slice());
     }
+
+    #[test]
+    fn root_ancestor() {
+        let world = &mut World::new();
+
+        let [a, b, c, d] = std::array::from_fn(|i| world.spawn(A(i)).id());
+
+        world.entity_mut(a).push_children(&[b]);
+        world.entity_mut(b).push_children(&[c]);
+
+        let mut system_state = SystemState::<Query<&Parent>>::new(world);
+        let parent_query = system_state.get(world);
+
+        assert_eq!(parent_query.root_ancestor(c), a);
+        assert_eq!(parent_query.root_ancestor(a), a);
+        assert_eq!(parent_query.root_ancestor(d), d);
+    }
 }
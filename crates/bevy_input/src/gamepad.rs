@@ -114,6 +114,32 @@ pub struct GamepadInfo {
     ///
     /// For example on Windows the name may be "HID-compliant game controller".
     pub name: String,
+    /// The optional features of the gamepad that the input backend can drive.
+    pub capabilities: GamepadCapabilities,
+}
+
+/// The optional features of a [`Gamepad`] that the input backend can drive, detected when it
+/// connects.
+///
+/// Requests for features a gamepad doesn't have are ignored, so they can be sent
+/// unconditionally, but games can check these to adapt their feedback, for example by
+/// replacing trigger resistance with a rumble.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+#[reflect(Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct GamepadCapabilities {
+    /// Whether the gamepad plays [`GamepadRumbleRequest`]s.
+    pub rumble: bool,
+    /// Whether the color of the gamepad's light can be set with [`GamepadLightRequest`]s, like
+    /// the light bars of Sony controllers.
+    pub light: bool,
+    /// Whether the gamepad's triggers play [`GamepadTriggerEffectRequest`]s, like the adaptive
+    /// triggers of recent Sony controllers.
+    pub trigger_effects: bool,
 }
 
 /// A collection of connected [`Gamepad`]s.
@@ -148,6 +174,11 @@ impl Gamepads {
         self.gamepads.get(&gamepad).map(|g| g.name.as_str())
     }
 
+    /// The capabilities of the gamepad if this one is connected.
+    pub fn capabilities(&self, gamepad: Gamepad) -> Option<GamepadCapabilities> {
+        self.gamepads.get(&gamepad).map(|g| g.capabilities)
+    }
+
     /// Registers the `gamepad`, marking it as connected.
     fn register(&mut self, gamepad: Gamepad, info: GamepadInfo) {
         self.gamepads.insert(gamepad, info);
@@ -1430,11 +1461,139 @@ impl GamepadRumbleRequest {
     }
 }
 
+/// An event that sets the color of the light of a [`Gamepad`], like the light bars of Sony
+/// controllers.
+///
+/// # Notes
+///
+/// Does nothing if the gamepad or backend does not support it, see
+/// [`GamepadCapabilities::light`].
+///
+/// # Example
+///
+/// ```
+/// # use bevy_input::gamepad::{Gamepads, GamepadLightRequest};
+/// # use bevy_ecs::prelude::{EventWriter, Res};
+/// fn show_low_health(
+///     mut light_requests: EventWriter<GamepadLightRequest>,
+///     gamepads: Res<Gamepads>
+/// ) {
+///     for gamepad in gamepads.iter() {
+///         light_requests.send(GamepadLightRequest {
+///             gamepad,
+///             color: [1.0, 0.0, 0.0],
+///         });
+///     }
+/// }
+/// ```
+#[doc(alias = "led")]
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct GamepadLightRequest {
+    /// The gamepad to set the light of.
+    pub gamepad: Gamepad,
+    /// The red, green and blue components of the color of the light, from `0.0` to `1.0`.
+    pub color: [f32; 3],
+}
+
+/// One of the two analog triggers of a [`Gamepad`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum GamepadTrigger {
+    /// The trigger under the left index finger, [`GamepadButtonType::LeftTrigger2`].
+    Left,
+    /// The trigger under the right index finger, [`GamepadButtonType::RightTrigger2`].
+    Right,
+}
+
+/// A force applied by an adaptive trigger when it is pressed.
+///
+/// Positions range from `0.0`, the trigger at rest, to `1.0`, the trigger fully pressed.
+/// Strengths and amplitudes range from `0.0` to `1.0`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum GamepadTriggerEffect {
+    /// The trigger moves freely.
+    Off,
+    /// The trigger resists being pressed past `start`, like a spring or a brake pedal.
+    Resistance {
+        /// Where the resistance starts.
+        start: f32,
+        /// How hard the trigger is to press.
+        strength: f32,
+    },
+    /// The trigger resists being pressed between `start` and `end` then gives way, like the
+    /// trigger of a gun.
+    Weapon {
+        /// Where the resistance starts.
+        start: f32,
+        /// Where the trigger gives way.
+        end: f32,
+        /// How hard the trigger is to press before it gives way.
+        strength: f32,
+    },
+    /// The trigger vibrates once pressed past `start`, like an engine or a machine gun.
+    Vibration {
+        /// Where the vibration starts.
+        start: f32,
+        /// How strongly the trigger vibrates.
+        amplitude: f32,
+        /// The frequency of the vibration in Hz.
+        frequency: f32,
+    },
+}
+
+/// An event that sets the effect of an adaptive trigger of a [`Gamepad`], which lasts until
+/// another effect is set.
+///
+/// # Notes
+///
+/// Does nothing if the gamepad or backend does not support it, see
+/// [`GamepadCapabilities::trigger_effects`].
+///
+/// # Example
+///
+/// ```
+/// # use bevy_input::gamepad::{
+/// #     Gamepads, GamepadTrigger, GamepadTriggerEffect, GamepadTriggerEffectRequest,
+/// # };
+/// # use bevy_ecs::prelude::{EventWriter, Res};
+/// fn brake_pedal(
+///     mut trigger_requests: EventWriter<GamepadTriggerEffectRequest>,
+///     gamepads: Res<Gamepads>
+/// ) {
+///     for gamepad in gamepads.iter() {
+///         trigger_requests.send(GamepadTriggerEffectRequest {
+///             gamepad,
+///             trigger: GamepadTrigger::Left,
+///             effect: GamepadTriggerEffect::Resistance {
+///                 start: 0.2,
+///                 strength: 0.8,
+///             },
+///         });
+///     }
+/// }
+/// ```
+#[doc(alias = "adaptive trigger")]
+#[doc(alias = "haptic feedback")]
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct GamepadTriggerEffectRequest {
+    /// The gamepad to set the trigger effect of.
+    pub gamepad: Gamepad,
+    /// The trigger to set the effect of.
+    pub trigger: GamepadTrigger,
+    /// The effect of the trigger.
+    pub effect: GamepadTriggerEffect,
+}
+
 #[cfg(test)]
 mod tests {
     use crate::gamepad::{AxisSettingsError, ButtonSettingsError};
 
-    use super::{AxisSettings, ButtonAxisSettings, ButtonSettings};
+    use super::{
+        gamepad_connection_system, AxisSettings, ButtonAxisSettings, ButtonSettings, Gamepad,
+        GamepadAxis, GamepadButton, GamepadCapabilities, GamepadConnection, GamepadConnectionEvent,
+        GamepadInfo, Gamepads,
+    };
+    use crate::{Axis, ButtonInput};
+    use bevy_ecs::{event::Events, system::RunSystemOnce, world::World};
 
     fn test_button_axis_settings_filter(
         settings: ButtonAxisSettings,
@@ -1764,4 +1923,39 @@ mod tests {
             axis_settings.try_set_livezone_upperbound(0.1)
         );
     }
+
+    #[test]
+    fn test_gamepad_capabilities_follow_connection() {
+        let mut world = World::new();
+        world.init_resource::<Gamepads>();
+        world.init_resource::<Events<GamepadConnectionEvent>>();
+        world.init_resource::<Axis<GamepadAxis>>();
+        world.init_resource::<Axis<GamepadButton>>();
+        world.init_resource::<ButtonInput<GamepadButton>>();
+
+        let gamepad = Gamepad::new(0);
+        let capabilities = GamepadCapabilities {
+            rumble: true,
+            light: true,
+            trigger_effects: false,
+        };
+        world.send_event(GamepadConnectionEvent::new(
+            gamepad,
+            GamepadConnection::Connected(GamepadInfo {
+                name: "Gamepad".to_string(),
+                capabilities,
+            }),
+        ));
+        world.run_system_once(gamepad_connection_system);
+        let gamepads = world.resource::<Gamepads>();
+        assert_eq!(gamepads.capabilities(gamepad), Some(capabilities));
+        assert_eq!(gamepads.capabilities(Gamepad::new(1)), None);
+
+        world.send_event(GamepadConnectionEvent::new(
+            gamepad,
+            GamepadConnection::Disconnected,
+        ));
+        world.run_system_once(gamepad_connection_system);
+        assert_eq!(world.resource::<Gamepads>().capabilities(gamepad), None);
+    }
 }
@@ -0,0 +1,183 @@
+//! Injection of input events from sources other than the windowing backend,
+//! such as replays or automated tests.
+
+use crate::{
+    gamepad::GamepadEvent,
+    keyboard::{KeyboardInput, ReceivedCharacter},
+    mouse::{CursorMoved, MouseButtonInput, MouseMotion, MouseWheel},
+    timestamp::{send_input_event, TimestampedInputEvent},
+    touch::TouchInput,
+    touchpad::{TouchpadMagnify, TouchpadRotate},
+};
+use bevy_ecs::{
+    event::Events,
+    schedule::SystemSet,
+    system::{Res, Resource},
+    world::World,
+};
+use bevy_reflect::Reflect;
+
+#[cfg(feature = "serialize")]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+
+/// Label for the system that injects the events queued in [`InjectedInput`].
+///
+/// Runs in [`PreUpdate`](bevy_app::PreUpdate) before [`InputSystem`](crate::InputSystem).
+#[derive(Debug, PartialEq, Eq, Clone, Hash, SystemSet)]
+pub struct InputInjectionSystem;
+
+/// Where the input events processed by the [`InputSystem`](crate::InputSystem) come from.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Debug, PartialEq, Hash)]
+pub enum InputSource {
+    /// Events from the windowing and gamepad backends are processed,
+    /// along with any events queued in [`InjectedInput`].
+    #[default]
+    Live,
+    /// Events from the windowing and gamepad backends are discarded,
+    /// so that only events queued in [`InjectedInput`] are processed.
+    Injected,
+}
+
+/// Any raw input event, as sent by the windowing and gamepad backends.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub enum InputEvent {
+    /// A [`KeyboardInput`] event.
+    Keyboard(KeyboardInput),
+    /// A [`ReceivedCharacter`] event.
+    ReceivedCharacter(ReceivedCharacter),
+    /// A [`MouseButtonInput`] event.
+    MouseButton(MouseButtonInput),
+    /// A [`MouseMotion`] event.
+    MouseMotion(MouseMotion),
+    /// A [`MouseWheel`] event.
+    MouseWheel(MouseWheel),
+    /// A [`CursorMoved`] event.
+    CursorMoved(CursorMoved),
+    /// A [`TouchpadMagnify`] event.
+    TouchpadMagnify(TouchpadMagnify),
+    /// A [`TouchpadRotate`] event.
+    TouchpadRotate(TouchpadRotate),
+    /// A [`TouchInput`] event.
+    Touch(TouchInput),
+    /// A [`GamepadEvent`].
+    Gamepad(GamepadEvent),
+}
+
+impl InputEvent {
+    /// Sends the event wrapped by this [`InputEvent`], without timestamping it.
+    pub fn send(self, world: &mut World) {
+        match self {
+            InputEvent::Keyboard(event) => {
+                world.send_event(event);
+            }
+            InputEvent::ReceivedCharacter(event) => {
+                world.send_event(event);
+            }
+            InputEvent::MouseButton(event) => {
+                world.send_event(event);
+            }
+            InputEvent::MouseMotion(event) => {
+                world.send_event(event);
+            }
+            InputEvent::MouseWheel(event) => {
+                world.send_event(event);
+            }
+            InputEvent::CursorMoved(event) => {
+                world.send_event(event);
+            }
+            InputEvent::TouchpadMagnify(event) => {
+                world.send_event(event);
+            }
+            InputEvent::TouchpadRotate(event) => {
+                world.send_event(event);
+            }
+            InputEvent::Touch(event) => {
+                world.send_event(event);
+            }
+            InputEvent::Gamepad(event) => {
+                world.send_event(event);
+            }
+        }
+    }
+}
+
+macro_rules! impl_from_input_event {
+    ($($variant:ident($event:ty)),* $(,)?) => {
+        $(
+            impl From<$event> for InputEvent {
+                fn from(event: $event) -> Self {
+                    InputEvent::$variant(event)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_input_event!(
+    Keyboard(KeyboardInput),
+    ReceivedCharacter(ReceivedCharacter),
+    MouseButton(MouseButtonInput),
+    MouseMotion(MouseMotion),
+    MouseWheel(MouseWheel),
+    CursorMoved(CursorMoved),
+    TouchpadMagnify(TouchpadMagnify),
+    TouchpadRotate(TouchpadRotate),
+    Touch(TouchInput),
+    Gamepad(GamepadEvent),
+);
+
+/// A queue of input events sent during the next [`InputInjectionSystem`] run,
+/// as if they came from the windowing and gamepad backends.
+#[derive(Resource, Debug, Default)]
+pub struct InjectedInput {
+    events: Vec<InputEvent>,
+}
+
+impl InjectedInput {
+    /// Queues an event to be sent during the next [`InputInjectionSystem`] run.
+    pub fn send(&mut self, event: InputEvent) {
+        self.events.push(event);
+    }
+
+    /// Queues several events to be sent during the next [`InputInjectionSystem`] run, in order.
+    pub fn extend(&mut self, events: impl IntoIterator<Item = InputEvent>) {
+        self.events.extend(events);
+    }
+}
+
+/// Discards live events if the [`InputSource`] is [`Injected`](InputSource::Injected),
+/// and sends the events queued in [`InjectedInput`].
+pub fn inject_input_system(world: &mut World) {
+    if *world.resource::<InputSource>() == InputSource::Injected {
+        world.resource_mut::<Events<KeyboardInput>>().clear();
+        world.resource_mut::<Events<ReceivedCharacter>>().clear();
+        world.resource_mut::<Events<MouseButtonInput>>().clear();
+        world.resource_mut::<Events<MouseMotion>>().clear();
+        world.resource_mut::<Events<MouseWheel>>().clear();
+        world.resource_mut::<Events<CursorMoved>>().clear();
+        world.resource_mut::<Events<TouchpadMagnify>>().clear();
+        world.resource_mut::<Events<TouchpadRotate>>().clear();
+        world.resource_mut::<Events<TouchInput>>().clear();
+        world.resource_mut::<Events<GamepadEvent>>().clear();
+        world
+            .resource_mut::<Events<TimestampedInputEvent>>()
+            .clear();
+    }
+
+    let events = std::mem::take(&mut world.resource_mut::<InjectedInput>().events);
+    for event in events {
+        send_input_event(world, event);
+    }
+}
+
+/// Returns `true` if [`inject_input_system`] has anything to do.
+pub(crate) fn should_inject_input(source: Res<InputSource>, injected: Res<InjectedInput>) -> bool {
+    *source == InputSource::Injected || !injected.events.is_empty()
+}
@@ -105,6 +105,21 @@ pub struct KeyboardInput {
     pub window: Entity,
 }
 
+/// An event that is sent whenever a window receives a character from the OS or underlying system.
+#[derive(Event, Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct ReceivedCharacter {
+    /// Window that received the character.
+    pub window: Entity,
+    /// Received character.
+    pub char: SmolStr,
+}
+
 /// Updates the [`ButtonInput<KeyCode>`] resource with the latest [`KeyboardInput`] events.
 ///
 /// ## Differences
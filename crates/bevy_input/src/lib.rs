@@ -9,8 +9,10 @@ mod button_input;
 /// Common run conditions
 pub mod common_conditions;
 pub mod gamepad;
+pub mod injection;
 pub mod keyboard;
 pub mod mouse;
+pub mod timestamp;
 pub mod touch;
 pub mod touchpad;
 
@@ -34,20 +36,29 @@ pub mod prelude {
 use bevy_app::prelude::*;
 use bevy_ecs::prelude::*;
 use bevy_reflect::Reflect;
-use keyboard::{keyboard_input_system, Key, KeyCode, KeyboardInput, NativeKey, NativeKeyCode};
+use keyboard::{
+    keyboard_input_system, Key, KeyCode, KeyboardInput, NativeKey, NativeKeyCode, ReceivedCharacter,
+};
 use mouse::{
-    mouse_button_input_system, MouseButton, MouseButtonInput, MouseMotion, MouseScrollUnit,
-    MouseWheel,
+    mouse_button_input_system, CursorMoved, MouseButton, MouseButtonInput, MouseMotion,
+    MouseScrollUnit, MouseWheel,
 };
+use timestamp::{InputClock, InputTimestamp, TimestampedInputEvent};
 use touch::{touch_screen_input_system, ForceTouch, TouchInput, TouchPhase, Touches};
 use touchpad::{TouchpadMagnify, TouchpadRotate};
 
+use injection::{
+    inject_input_system, should_inject_input, InjectedInput, InputEvent, InputInjectionSystem,
+    InputSource,
+};
+
 use gamepad::{
     gamepad_axis_event_system, gamepad_button_event_system, gamepad_connection_system,
     gamepad_event_system, AxisSettings, ButtonAxisSettings, ButtonSettings, Gamepad, GamepadAxis,
     GamepadAxisChangedEvent, GamepadAxisType, GamepadButton, GamepadButtonChangedEvent,
-    GamepadButtonInput, GamepadButtonType, GamepadConnection, GamepadConnectionEvent, GamepadEvent,
-    GamepadRumbleRequest, GamepadSettings, Gamepads,
+    GamepadButtonInput, GamepadButtonType, GamepadCapabilities, GamepadConnection,
+    GamepadConnectionEvent, GamepadEvent, GamepadLightRequest, GamepadRumbleRequest,
+    GamepadSettings, GamepadTriggerEffectRequest, Gamepads,
 };
 
 #[cfg(feature = "serialize")]
@@ -66,12 +77,14 @@ impl Plugin for InputPlugin {
         app
             // keyboard
             .add_event::<KeyboardInput>()
+            .add_event::<ReceivedCharacter>()
             .init_resource::<ButtonInput<KeyCode>>()
             .add_systems(PreUpdate, keyboard_input_system.in_set(InputSystem))
             // mouse
             .add_event::<MouseButtonInput>()
             .add_event::<MouseMotion>()
             .add_event::<MouseWheel>()
+            .add_event::<CursorMoved>()
             .init_resource::<ButtonInput<MouseButton>>()
             .add_systems(PreUpdate, mouse_button_input_system.in_set(InputSystem))
             .add_event::<TouchpadMagnify>()
@@ -83,6 +96,8 @@ impl Plugin for InputPlugin {
             .add_event::<GamepadAxisChangedEvent>()
             .add_event::<GamepadEvent>()
             .add_event::<GamepadRumbleRequest>()
+            .add_event::<GamepadLightRequest>()
+            .add_event::<GamepadTriggerEffectRequest>()
             .init_resource::<GamepadSettings>()
             .init_resource::<Gamepads>()
             .init_resource::<ButtonInput<GamepadButton>>()
@@ -105,13 +120,31 @@ impl Plugin for InputPlugin {
             // touch
             .add_event::<TouchInput>()
             .init_resource::<Touches>()
-            .add_systems(PreUpdate, touch_screen_input_system.in_set(InputSystem));
+            .add_systems(PreUpdate, touch_screen_input_system.in_set(InputSystem))
+            // timestamps
+            .add_event::<TimestampedInputEvent>()
+            .init_resource::<InputClock>()
+            // injection
+            .init_resource::<InputSource>()
+            .init_resource::<InjectedInput>()
+            .configure_sets(PreUpdate, InputInjectionSystem.before(InputSystem))
+            .add_systems(
+                PreUpdate,
+                inject_input_system
+                    .run_if(should_inject_input)
+                    .in_set(InputInjectionSystem),
+            );
 
         // Register common types
-        app.register_type::<ButtonState>();
+        app.register_type::<ButtonState>()
+            .register_type::<InputSource>()
+            .register_type::<InputEvent>()
+            .register_type::<InputTimestamp>()
+            .register_type::<TimestampedInputEvent>();
 
         // Register keyboard types
         app.register_type::<KeyboardInput>()
+            .register_type::<ReceivedCharacter>()
             .register_type::<KeyCode>()
             .register_type::<NativeKeyCode>()
             .register_type::<Key>()
@@ -121,6 +154,7 @@ impl Plugin for InputPlugin {
         app.register_type::<MouseButtonInput>()
             .register_type::<MouseButton>()
             .register_type::<MouseMotion>()
+            .register_type::<CursorMoved>()
             .register_type::<MouseScrollUnit>()
             .register_type::<MouseWheel>();
 
@@ -136,6 +170,7 @@ impl Plugin for InputPlugin {
         // Register gamepad types
         app.register_type::<Gamepad>()
             .register_type::<GamepadConnection>()
+            .register_type::<GamepadCapabilities>()
             .register_type::<GamepadButtonType>()
             .register_type::<GamepadButton>()
             .register_type::<GamepadButtonInput>()
@@ -0,0 +1,162 @@
+//! Timestamps of the raw input events, and their order across devices.
+
+use crate::injection::InputEvent;
+use bevy_ecs::{
+    event::{Event, EventWriter},
+    system::Resource,
+    world::World,
+};
+use bevy_reflect::Reflect;
+use bevy_utils::{Duration, Instant};
+
+#[cfg(feature = "serialize")]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+
+/// When an input event was received.
+///
+/// Timestamps are ordered like the events they are attached to, even across devices: of two
+/// events received during the same frame, the one with the lower timestamp happened first.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Reflect)]
+#[reflect(Debug, PartialEq, Hash)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct InputTimestamp {
+    /// The time elapsed since the [`InputClock`] started when the event was received,
+    /// from a monotonic clock.
+    pub time: Duration,
+    /// The number of events received before this one, across all devices.
+    pub sequence: u64,
+}
+
+/// The clock used to timestamp input events.
+///
+/// Backends should timestamp events with [`InputClock::stamp`] as soon as they are received from
+/// the platform, or send them with [`send_input_event`] which does it for them.
+#[derive(Resource, Debug)]
+pub struct InputClock {
+    start: Instant,
+    next_sequence: u64,
+}
+
+impl Default for InputClock {
+    fn default() -> Self {
+        Self {
+            start: Instant::now(),
+            next_sequence: 0,
+        }
+    }
+}
+
+impl InputClock {
+    /// Returns the timestamp of an event received now.
+    pub fn stamp(&mut self) -> InputTimestamp {
+        let sequence = self.next_sequence;
+        self.next_sequence += 1;
+        InputTimestamp {
+            time: self.elapsed(),
+            sequence,
+        }
+    }
+
+    /// Returns the time elapsed since the clock started, to compare with [`InputTimestamp::time`],
+    /// for example to measure input latency.
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// Returns the [`Instant`] the clock started at.
+    pub fn start(&self) -> Instant {
+        self.start
+    }
+}
+
+/// A raw input event and its [`InputTimestamp`].
+///
+/// Every raw input event, sent by the windowing and gamepad backends or injected with
+/// [`InjectedInput`](crate::injection::InjectedInput), is also sent as a
+/// [`TimestampedInputEvent`]. Unlike the events of each device, which can only be read in order
+/// by type, these events are sent in the order the inputs were received across all devices,
+/// which input buffers that combine devices depend on.
+#[derive(Event, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct TimestampedInputEvent {
+    /// The raw input event.
+    pub event: InputEvent,
+    /// When the event was received.
+    pub timestamp: InputTimestamp,
+}
+
+/// Sends a raw input event, and the same event as a [`TimestampedInputEvent`] stamped with the
+/// [`InputClock`].
+///
+/// This is how backends should send input events.
+pub fn send_input_event(world: &mut World, event: impl Into<InputEvent>) {
+    let event = event.into();
+    if let Some(mut clock) = world.get_resource_mut::<InputClock>() {
+        let timestamp = clock.stamp();
+        world.send_event(TimestampedInputEvent {
+            event: event.clone(),
+            timestamp,
+        });
+    }
+    event.send(world);
+}
+
+/// Sends a [`TimestampedInputEvent`] stamped with `clock`, for backends sending their raw input
+/// events from a system with [`EventWriter`]s.
+pub fn send_timestamped_input_event(
+    clock: &mut InputClock,
+    writer: &mut EventWriter<TimestampedInputEvent>,
+    event: impl Into<InputEvent>,
+) {
+    writer.send(TimestampedInputEvent {
+        event: event.into(),
+        timestamp: clock.stamp(),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        keyboard::{Key, KeyCode, KeyboardInput},
+        mouse::MouseMotion,
+        ButtonState, InputPlugin,
+    };
+    use bevy_app::App;
+    use bevy_ecs::{entity::Entity, event::Events};
+    use bevy_math::Vec2;
+
+    #[test]
+    fn timestamped_events_keep_order_across_devices() {
+        let mut app = App::new();
+        app.add_plugins(InputPlugin);
+
+        let key = KeyboardInput {
+            key_code: KeyCode::KeyA,
+            logical_key: Key::Character("a".into()),
+            state: ButtonState::Pressed,
+            window: Entity::PLACEHOLDER,
+        };
+        send_input_event(&mut app.world, MouseMotion { delta: Vec2::X });
+        send_input_event(&mut app.world, key.clone());
+        send_input_event(&mut app.world, MouseMotion { delta: Vec2::Y });
+
+        let events = app.world.resource::<Events<TimestampedInputEvent>>();
+        let events: Vec<_> = events.get_reader().read(events).cloned().collect();
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[1].event, InputEvent::Keyboard(key));
+        assert!(events
+            .windows(2)
+            .all(|pair| pair[0].timestamp < pair[1].timestamp));
+        assert_eq!(app.world.resource::<Events<MouseMotion>>().len(), 2);
+    }
+}
@@ -18,6 +18,7 @@ use bevy_app::{Plugin, PluginGroup, PluginGroupBuilder};
 /// * [`RenderPlugin`](crate::render::RenderPlugin) - with feature `bevy_render`
 /// * [`ImagePlugin`](crate::render::texture::ImagePlugin) - with feature `bevy_render`
 /// * [`PipelinedRenderingPlugin`](crate::render::pipelined_rendering::PipelinedRenderingPlugin) - with feature `bevy_render` when not targeting `wasm32`
+/// * [`PickingPlugin`](crate::picking::PickingPlugin) - with feature `bevy_picking`
 /// * [`CorePipelinePlugin`](crate::core_pipeline::CorePipelinePlugin) - with feature `bevy_core_pipeline`
 /// * [`SpritePlugin`](crate::sprite::SpritePlugin) - with feature `bevy_sprite`
 /// * [`TextPlugin`](crate::text::TextPlugin) - with feature `bevy_text`
@@ -34,7 +35,8 @@ use bevy_app::{Plugin, PluginGroup, PluginGroupBuilder};
 ///
 /// [`DefaultPlugins`] contains all the plugins typically required to build
 /// a *Bevy* application which includes a *window* and presentation components.
-/// For *headless* cases – without a *window* or presentation, see [`MinimalPlugins`].
+/// For *headless* cases – without a *window* or presentation, see [`MinimalPlugins`] and
+/// [`HeadlessPlugins`].
 pub struct DefaultPlugins;
 
 impl PluginGroup for DefaultPlugins {
@@ -82,6 +84,11 @@ impl PluginGroup for DefaultPlugins {
             }
         }
 
+        #[cfg(feature = "bevy_picking")]
+        {
+            group = group.add(bevy_picking::PickingPlugin);
+        }
+
         #[cfg(feature = "bevy_core_pipeline")]
         {
             group = group.add(bevy_core_pipeline::CorePipelinePlugin);
@@ -195,6 +202,8 @@ impl Plugin for IgnoreAmbiguitiesPlugin {
 ///
 /// Windowed applications that wish to use a reduced set of plugins should consider the
 /// [`DefaultPlugins`] plugin group which can be controlled with *Cargo* *feature* flags.
+/// Headless programs that need assets or scenes, like dedicated game servers, should consider
+/// [`HeadlessPlugins`].
 pub struct MinimalPlugins;
 
 impl PluginGroup for MinimalPlugins {
@@ -207,3 +216,84 @@ impl PluginGroup for MinimalPlugins {
             .add(bevy_app::ScheduleRunnerPlugin::default())
     }
 }
+
+/// This plugin group will add the plugins for a *headless* *Bevy* application, like a dedicated
+/// game server:
+/// * [`LogPlugin`](crate::log::LogPlugin)
+/// * [`TaskPoolPlugin`](crate::core::TaskPoolPlugin)
+/// * [`TypeRegistrationPlugin`](crate::core::TypeRegistrationPlugin)
+/// * [`FrameCountPlugin`](crate::core::FrameCountPlugin)
+/// * [`TimePlugin`](crate::time::TimePlugin)
+/// * [`TransformPlugin`](crate::transform::TransformPlugin)
+/// * [`HierarchyPlugin`](crate::hierarchy::HierarchyPlugin)
+/// * [`DiagnosticsPlugin`](crate::diagnostic::DiagnosticsPlugin)
+/// * [`ScheduleRunnerPlugin`](crate::app::ScheduleRunnerPlugin), running the app at the default
+///   rate of [`FixedUpdate`](crate::app::FixedUpdate)
+/// * [`AssetPlugin`](crate::asset::AssetPlugin) - with feature `bevy_asset`
+/// * [`ScenePlugin`](crate::scene::ScenePlugin) - with feature `bevy_scene`
+///
+/// Unlike [`DefaultPlugins`], it never adds plugins for windows, input, rendering or audio, even
+/// when their features are enabled, so a server and a client can share the same crates and build
+/// with the same features. Types of those crates, like [`Handle`](crate::asset::Handle) or
+/// `Visibility`, can still be used by the shared code. To avoid compiling the render, window and
+/// audio crates, build the server with `default-features = false`: scenes can still be loaded,
+/// without the visibility components of `SpatialBundle`.
+///
+/// States are part of the app and don't need a plugin, see
+/// [`App::init_state`](crate::app::App::init_state).
+pub struct HeadlessPlugins;
+
+impl PluginGroup for HeadlessPlugins {
+    fn build(self) -> PluginGroupBuilder {
+        let mut group = PluginGroupBuilder::start::<Self>();
+        group = group
+            .add(bevy_log::LogPlugin::default())
+            .add(bevy_core::TaskPoolPlugin::default())
+            .add(bevy_core::TypeRegistrationPlugin)
+            .add(bevy_core::FrameCountPlugin)
+            .add(bevy_time::TimePlugin)
+            .add(bevy_transform::TransformPlugin)
+            .add(bevy_hierarchy::HierarchyPlugin)
+            .add(bevy_diagnostic::DiagnosticsPlugin)
+            // The default rate of `FixedUpdate`
+            .add(bevy_app::ScheduleRunnerPlugin::run_loop(
+                std::time::Duration::from_secs_f64(1.0 / 64.0),
+            ));
+
+        #[cfg(feature = "bevy_asset")]
+        {
+            group = group.add(bevy_asset::AssetPlugin::default());
+        }
+
+        #[cfg(feature = "bevy_scene")]
+        {
+            group = group.add(bevy_scene::ScenePlugin);
+        }
+
+        group
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::App;
+
+    use super::*;
+
+    #[test]
+    fn headless_plugins_skip_presentation() {
+        let mut app = App::new();
+        app.add_plugins(HeadlessPlugins.build().disable::<bevy_log::LogPlugin>());
+
+        assert!(app.is_plugin_added::<bevy_time::TimePlugin>());
+        assert!(app.is_plugin_added::<bevy_transform::TransformPlugin>());
+        assert!(!app.is_plugin_added::<bevy_window::WindowPlugin>());
+        assert!(!app.is_plugin_added::<bevy_input::InputPlugin>());
+        #[cfg(feature = "bevy_render")]
+        assert!(!app.is_plugin_added::<bevy_render::RenderPlugin>());
+
+        app.finish();
+        app.cleanup();
+        app.update();
+    }
+}
@@ -128,12 +128,36 @@ pub mod gltf {
     pub use bevy_gltf::*;
 }
 
+#[cfg(feature = "bevy_character")]
+pub mod character {
+    //! Kinematic character controllers sliding along the geometry they collide with.
+    pub use bevy_character::*;
+}
+
+#[cfg(feature = "bevy_navigation")]
+pub mod navigation {
+    //! Navigation meshes baked from the level geometry, and pathfinding on them.
+    pub use bevy_navigation::*;
+}
+
 #[cfg(feature = "bevy_pbr")]
 pub mod pbr {
     //! Physically based rendering.
     pub use bevy_pbr::*;
 }
 
+#[cfg(feature = "bevy_picking")]
+pub mod picking {
+    //! Finding and interacting with the entities under the mouse and touch pointers.
+    pub use bevy_picking::*;
+}
+
+#[cfg(feature = "bevy_remote")]
+pub mod remote {
+    //! Inspection and modification of a running app by external processes.
+    pub use bevy_remote::*;
+}
+
 #[cfg(feature = "bevy_render")]
 pub mod render {
     //! Cameras, meshes, textures, shaders, and pipelines.
@@ -187,6 +211,12 @@ pub mod gizmos {
     pub use bevy_gizmos::*;
 }
 
+#[cfg(feature = "bevy_dev_tools")]
+pub mod dev_tools {
+    //! Collection of developer tools
+    pub use bevy_dev_tools::*;
+}
+
 #[cfg(feature = "bevy_dynamic_plugin")]
 pub mod dynamic_plugin {
     //! Dynamic linking of plugins
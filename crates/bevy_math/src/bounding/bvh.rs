@@ -0,0 +1,215 @@
+use super::{Aabb3d, BoundingVolume, RayCast3d};
+
+/// The maximum number of volumes in a leaf of a [`Bvh3d`].
+const MAX_LEAF_SIZE: usize = 4;
+
+/// A bounding volume hierarchy over a set of [`Aabb3d`]s, used to quickly find
+/// which of them may be hit by a ray without testing each of them.
+///
+/// The volumes are referred to by their index in the slice the hierarchy was built from,
+/// typically the bounds of the triangles of a mesh.
+///
+/// ```
+/// # use bevy_math::{bounding::{Aabb3d, Bvh3d, RayCast3d}, primitives::Direction3d, Vec3};
+/// let volumes: Vec<_> = (0..100)
+///     .map(|i| Aabb3d::new(Vec3::X * i as f32, Vec3::splat(0.25)))
+///     .collect();
+/// let bvh = Bvh3d::new(&volumes);
+///
+/// let ray = RayCast3d::new(Vec3::new(10., 5., 0.), -Direction3d::Y, 100.);
+/// let hit = bvh.cast_ray(&ray, |index, ray| ray.aabb_intersection_at(&volumes[index]));
+/// assert_eq!(hit, Some((10, 4.75)));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Bvh3d {
+    nodes: Vec<BvhNode>,
+    /// The indices of the volumes, ordered so that the volumes of each leaf are contiguous.
+    indices: Vec<u32>,
+}
+
+#[derive(Clone, Debug)]
+struct BvhNode {
+    aabb: Aabb3d,
+    /// The index of the first of the two child nodes, or of the first volume for leaves.
+    start: u32,
+    /// The number of volumes of a leaf, or 0 for other nodes.
+    count: u32,
+}
+
+impl Bvh3d {
+    /// Builds a hierarchy over the given volumes.
+    pub fn new(volumes: &[Aabb3d]) -> Self {
+        let mut bvh = Self {
+            nodes: Vec::with_capacity(2 * volumes.len() / MAX_LEAF_SIZE + 1),
+            indices: (0..volumes.len() as u32).collect(),
+        };
+        if !volumes.is_empty() {
+            bvh.nodes.push(BvhNode {
+                aabb: volumes[0],
+                start: 0,
+                count: 0,
+            });
+            bvh.build(volumes, 0, 0, volumes.len());
+        }
+        bvh
+    }
+
+    fn build(&mut self, volumes: &[Aabb3d], node: usize, start: usize, end: usize) {
+        let indices = &mut self.indices[start..end];
+        let volume = |index: &u32| volumes[*index as usize];
+        let aabb = indices
+            .iter()
+            .map(volume)
+            .reduce(|a, b| a.merge(&b))
+            .unwrap();
+
+        if indices.len() <= MAX_LEAF_SIZE {
+            self.nodes[node] = BvhNode {
+                aabb,
+                start: start as u32,
+                count: indices.len() as u32,
+            };
+            return;
+        }
+
+        // Split the volumes in half along the axis their centers are the most spread on.
+        let (min, max) = indices
+            .iter()
+            .map(|index| volume(index).center())
+            .fold((aabb.max, aabb.min), |(min, max), center| {
+                (min.min(center), max.max(center))
+            });
+        let extent = max - min;
+        let axis = if extent.x >= extent.y && extent.x >= extent.z {
+            0
+        } else if extent.y >= extent.z {
+            1
+        } else {
+            2
+        };
+        let middle = indices.len() / 2;
+        indices.select_nth_unstable_by(middle, |a, b| {
+            volume(a).center()[axis].total_cmp(&volume(b).center()[axis])
+        });
+
+        let children = self.nodes.len();
+        self.nodes
+            .extend([self.nodes[node].clone(), self.nodes[node].clone()]);
+        self.nodes[node] = BvhNode {
+            aabb,
+            start: children as u32,
+            count: 0,
+        };
+        self.build(volumes, children, start, start + middle);
+        self.build(volumes, children + 1, start + middle, end);
+    }
+
+    /// Returns `true` if the hierarchy was built from an empty set of volumes.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns the [`Aabb3d`] containing all of the volumes, if any.
+    pub fn aabb(&self) -> Option<Aabb3d> {
+        self.nodes.first().map(|node| node.aabb)
+    }
+
+    /// Finds the closest hit along `ray`.
+    ///
+    /// `intersect` is called with the index of each volume the ray may hit, and a copy of `ray`
+    /// whose max distance is shortened to the closest hit found so far. It must return the
+    /// distance at which the ray hits the object bounded by that volume, if it does.
+    ///
+    /// Returns the index of the closest volume hit along with the distance of the hit.
+    pub fn cast_ray(
+        &self,
+        ray: &RayCast3d,
+        mut intersect: impl FnMut(usize, &RayCast3d) -> Option<f32>,
+    ) -> Option<(usize, f32)> {
+        let mut ray = ray.clone();
+        let mut closest = None;
+        let mut stack = Vec::with_capacity(32);
+        if !self.nodes.is_empty() {
+            stack.push(0);
+        }
+
+        while let Some(node) = stack.pop() {
+            let node = &self.nodes[node];
+            if ray.aabb_intersection_at(&node.aabb).is_none() {
+                continue;
+            }
+
+            let start = node.start as usize;
+            if node.count > 0 {
+                for &index in &self.indices[start..start + node.count as usize] {
+                    if let Some(distance) = intersect(index as usize, &ray) {
+                        if distance <= ray.max {
+                            ray.max = distance;
+                            closest = Some((index as usize, distance));
+                        }
+                    }
+                }
+                continue;
+            }
+
+            // Visit the closest child first, so that the other one can be skipped more often.
+            let left = ray.aabb_intersection_at(&self.nodes[start].aabb);
+            let right = ray.aabb_intersection_at(&self.nodes[start + 1].aabb);
+            match (left, right) {
+                (Some(left), Some(right)) if left <= right => stack.extend([start + 1, start]),
+                (Some(_), Some(_)) => stack.extend([start, start + 1]),
+                (Some(_), None) => stack.push(start),
+                (None, Some(_)) => stack.push(start + 1),
+                (None, None) => {}
+            }
+        }
+
+        closest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{primitives::Direction3d, Vec3};
+
+    #[test]
+    fn bvh_matches_brute_force() {
+        let volumes: Vec<_> = (0..500)
+            .map(|i| {
+                let i = i as f32;
+                let center = Vec3::new((i * 7.3) % 20., (i * 3.1) % 20., (i * 5.7) % 20.);
+                Aabb3d::new(center, Vec3::splat(0.2 + (i % 3.) * 0.1))
+            })
+            .collect();
+        let bvh = Bvh3d::new(&volumes);
+        assert_eq!(
+            bvh.aabb().unwrap().min,
+            volumes.iter().fold(Vec3::MAX, |min, v| min.min(v.min))
+        );
+
+        for i in 0..50 {
+            let i = i as f32;
+            let origin = Vec3::new(-5., (i * 1.3) % 20., (i * 2.9) % 20.);
+            let direction = Direction3d::new(Vec3::new(1., (i % 5.) * 0.1 - 0.2, 0.05)).unwrap();
+            let ray = RayCast3d::new(origin, direction, 100.);
+
+            let expected = volumes
+                .iter()
+                .enumerate()
+                .filter_map(|(index, volume)| Some((index, ray.aabb_intersection_at(volume)?)))
+                .min_by(|(_, a), (_, b)| a.total_cmp(b))
+                .map(|(_, distance)| distance);
+            let hit = bvh.cast_ray(&ray, |index, ray| ray.aabb_intersection_at(&volumes[index]));
+            assert_eq!(hit.map(|(_, distance)| distance), expected);
+        }
+    }
+
+    #[test]
+    fn empty_bvh() {
+        let bvh = Bvh3d::new(&[]);
+        assert!(bvh.is_empty());
+        let ray = RayCast3d::new(Vec3::ZERO, Direction3d::X, 100.);
+        assert_eq!(bvh.cast_ray(&ray, |_, _| Some(0.)), None);
+    }
+}
@@ -64,3 +64,6 @@ mod raycast2d;
 pub use raycast2d::*;
 mod raycast3d;
 pub use raycast3d::*;
+
+mod bvh;
+pub use bvh::*;
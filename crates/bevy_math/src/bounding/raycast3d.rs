@@ -91,6 +91,36 @@ impl RayCast3d {
             }
         }
     }
+
+    /// Get the distance of an intersection with the triangle `a`, `b`, `c`, if any.
+    ///
+    /// Both faces of the triangle can be hit.
+    pub fn triangle_intersection_at(&self, a: Vec3, b: Vec3, c: Vec3) -> Option<f32> {
+        // Möller–Trumbore intersection algorithm
+        let edge_ab = b - a;
+        let edge_ac = c - a;
+        let p = self.ray.direction.cross(edge_ac);
+        let determinant = edge_ab.dot(p);
+        if determinant == 0. {
+            // The ray is parallel to the triangle.
+            return None;
+        }
+        let inverse_determinant = determinant.recip();
+
+        let origin_offset = self.ray.origin - a;
+        let u = origin_offset.dot(p) * inverse_determinant;
+        if !(0. ..=1.).contains(&u) {
+            return None;
+        }
+        let q = origin_offset.cross(edge_ab);
+        let v = self.ray.direction.dot(q) * inverse_determinant;
+        if v < 0. || u + v > 1. {
+            return None;
+        }
+
+        let distance = edge_ac.dot(q) * inverse_determinant;
+        (0. ..=self.max).contains(&distance).then_some(distance)
+    }
 }
 
 impl IntersectsVolume<Aabb3d> for RayCast3d {
@@ -185,6 +215,36 @@ mod tests {
 
     const EPSILON: f32 = 0.001;
 
+    #[test]
+    fn test_ray_intersection_triangle() {
+        let [a, b, c] = [
+            Vec3::new(-1., 0., -1.),
+            Vec3::new(1., 0., -1.),
+            Vec3::new(0., 0., 1.),
+        ];
+
+        // Hit from above and below
+        let test = RayCast3d::new(Vec3::Y * 5., -Direction3d::Y, 90.);
+        let distance = test.triangle_intersection_at(a, b, c).unwrap();
+        assert!((distance - 5.).abs() < EPSILON);
+        let test = RayCast3d::new(Vec3::new(0.5, -2., -0.5), Direction3d::Y, 90.);
+        let distance = test.triangle_intersection_at(a, b, c).unwrap();
+        assert!((distance - 2.).abs() < EPSILON);
+
+        // Beyond the max distance
+        let test = RayCast3d::new(Vec3::Y * 5., -Direction3d::Y, 4.);
+        assert!(test.triangle_intersection_at(a, b, c).is_none());
+        // Pointing away
+        let test = RayCast3d::new(Vec3::Y * 5., Direction3d::Y, 90.);
+        assert!(test.triangle_intersection_at(a, b, c).is_none());
+        // Outside of the triangle
+        let test = RayCast3d::new(Vec3::new(0.9, 5., 0.9), -Direction3d::Y, 90.);
+        assert!(test.triangle_intersection_at(a, b, c).is_none());
+        // Parallel to the triangle
+        let test = RayCast3d::new(Vec3::new(-5., 0., 0.), Direction3d::X, 90.);
+        assert!(test.triangle_intersection_at(a, b, c).is_none());
+    }
+
     #[test]
     fn test_ray_intersection_sphere_hits() {
         for (test, volume, expected_distance) in &[
@@ -0,0 +1,79 @@
+use bevy_math::{bounding::Aabb3d, Vec3};
+use bevy_render::mesh::{Mesh, PrimitiveTopology};
+use bevy_transform::components::GlobalTransform;
+
+/// The level geometry a [`NavMesh`](crate::NavMesh) is baked from, in world space.
+#[derive(Clone, Debug, Default)]
+pub struct NavMeshGeometry {
+    /// The triangles of the level.
+    ///
+    /// Triangles are walkable on their front side, as defined by a counter-clockwise winding,
+    /// if their slope is below [`NavMeshSettings::agent_max_slope`](crate::NavMeshSettings).
+    pub triangles: Vec<[Vec3; 3]>,
+    /// The volumes blocking movement, such as the bounds of dynamic obstacles.
+    pub obstacles: Vec<Aabb3d>,
+}
+
+impl NavMeshGeometry {
+    /// Adds the triangles of `mesh`, placed in the world by `transform`.
+    ///
+    /// Meshes that don't use the [`PrimitiveTopology::TriangleList`] topology, or don't have
+    /// [`Mesh::ATTRIBUTE_POSITION`] in the `Float32x3` format, are ignored.
+    pub fn add_mesh(&mut self, mesh: &Mesh, transform: &GlobalTransform) {
+        if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+            return;
+        }
+        let Some(positions) = mesh
+            .attribute(Mesh::ATTRIBUTE_POSITION)
+            .and_then(|positions| positions.as_float3())
+        else {
+            return;
+        };
+        let position =
+            |index: usize| Some(transform.transform_point(Vec3::from(*positions.get(index)?)));
+
+        match mesh.indices() {
+            Some(indices) => {
+                let indices: Vec<usize> = indices.iter().collect();
+                self.triangles
+                    .extend(indices.chunks_exact(3).filter_map(|triangle| {
+                        Some([
+                            position(triangle[0])?,
+                            position(triangle[1])?,
+                            position(triangle[2])?,
+                        ])
+                    }));
+            }
+            None => {
+                self.triangles
+                    .extend((0..positions.len() / 3).filter_map(|i| {
+                        Some([position(3 * i)?, position(3 * i + 1)?, position(3 * i + 2)?])
+                    }));
+            }
+        }
+    }
+
+    /// Adds a volume blocking movement.
+    pub fn add_obstacle(&mut self, obstacle: Aabb3d) {
+        self.obstacles.push(obstacle);
+    }
+
+    /// Returns the bounds of the triangles, or `None` if there are no triangles.
+    pub fn bounds(&self) -> Option<Aabb3d> {
+        self.triangles
+            .iter()
+            .flatten()
+            .fold(None, |bounds: Option<Aabb3d>, &point| {
+                Some(bounds.map_or(
+                    Aabb3d {
+                        min: point,
+                        max: point,
+                    },
+                    |bounds| Aabb3d {
+                        min: bounds.min.min(point),
+                        max: bounds.max.max(point),
+                    },
+                ))
+            })
+    }
+}
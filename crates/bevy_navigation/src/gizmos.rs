@@ -0,0 +1,121 @@
+//! Debug visualization of the [`NavMesh`] and of paths.
+
+use bevy_app::{Plugin, PostUpdate};
+use bevy_ecs::{
+    component::Component,
+    reflect::ReflectComponent,
+    schedule::IntoSystemConfigs,
+    system::{Query, Res},
+};
+use bevy_gizmos::{
+    config::{GizmoConfigGroup, GizmoConfigStore},
+    gizmos::Gizmos,
+    AppGizmoBuilder,
+};
+use bevy_math::Vec3;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::color::Color;
+
+use crate::{NavMesh, NavigationSystem};
+
+/// How high above the navigation mesh its gizmos are drawn, so that they aren't hidden by the
+/// level geometry.
+const GIZMO_OFFSET: Vec3 = Vec3::new(0.0, 0.05, 0.0);
+
+/// A [`Plugin`] that provides visualization of the [`NavMesh`] and of paths for debugging.
+///
+/// Added by the [`NavigationPlugin`](crate::NavigationPlugin) when the `bevy_gizmos` feature is
+/// enabled.
+pub struct NavMeshGizmoPlugin;
+
+impl Plugin for NavMeshGizmoPlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        app.register_type::<NavMeshGizmoConfigGroup>()
+            .register_type::<NavPathGizmo>()
+            .init_gizmo_group::<NavMeshGizmoConfigGroup>()
+            .add_systems(
+                PostUpdate,
+                (
+                    draw_navmesh.run_if(|config: Res<GizmoConfigStore>| {
+                        config.config::<NavMeshGizmoConfigGroup>().1.draw_navmesh
+                    }),
+                    draw_paths,
+                )
+                    .after(NavigationSystem::UpdateNavMesh),
+            );
+    }
+}
+
+/// The [`GizmoConfigGroup`] used for debug visualizations of the [`NavMesh`] and of paths.
+#[derive(Clone, Reflect, GizmoConfigGroup)]
+pub struct NavMeshGizmoConfigGroup {
+    /// Draws the polygons of the navigation mesh and the links between them when set to `true`.
+    ///
+    /// Defaults to `false`.
+    pub draw_navmesh: bool,
+    /// The color of the outlines of the polygons.
+    ///
+    /// Defaults to [`Color::CYAN`].
+    pub polygon_color: Color,
+    /// The color of the links between the polygons.
+    ///
+    /// Defaults to [`Color::BLUE`].
+    pub link_color: Color,
+    /// The default color for path gizmos.
+    ///
+    /// Defaults to [`Color::YELLOW`].
+    pub path_color: Color,
+}
+
+impl Default for NavMeshGizmoConfigGroup {
+    fn default() -> Self {
+        Self {
+            draw_navmesh: false,
+            polygon_color: Color::CYAN,
+            link_color: Color::BLUE,
+            path_color: Color::YELLOW,
+        }
+    }
+}
+
+/// Add this [`Component`] to an entity to draw a path, such as one found with
+/// [`NavMesh::find_path`].
+#[derive(Component, Reflect, Default, Debug)]
+#[reflect(Component, Default)]
+pub struct NavPathGizmo {
+    /// The points of the path.
+    pub path: Vec<Vec3>,
+    /// The color of the path.
+    ///
+    /// The default color from the [`NavMeshGizmoConfigGroup`] config is used if `None`.
+    pub color: Option<Color>,
+}
+
+fn draw_navmesh(navmesh: Res<NavMesh>, mut gizmos: Gizmos<NavMeshGizmoConfigGroup>) {
+    let (polygon_color, link_color) = (
+        gizmos.config_ext.polygon_color,
+        gizmos.config_ext.link_color,
+    );
+    for (_, tile) in navmesh.tiles() {
+        for polygon in &tile.polygons {
+            let [a, b, c, d] = polygon.vertices.map(|vertex| vertex + GIZMO_OFFSET);
+            gizmos.linestrip([a, b, c, d, a], polygon_color);
+
+            for link in &polygon.links {
+                let portal_center = (link.portal[0] + link.portal[1]) / 2.0;
+                gizmos.line(
+                    polygon.center() + GIZMO_OFFSET,
+                    portal_center + GIZMO_OFFSET,
+                    link_color,
+                );
+            }
+        }
+    }
+}
+
+fn draw_paths(query: Query<&NavPathGizmo>, mut gizmos: Gizmos<NavMeshGizmoConfigGroup>) {
+    for gizmo in &query {
+        let color = gizmo.color.unwrap_or(gizmos.config_ext.path_color);
+        gizmos.linestrip(gizmo.path.iter().map(|point| *point + GIZMO_OFFSET), color);
+    }
+}
@@ -0,0 +1,432 @@
+//! Voxelization of the level geometry, and polygonization of the walkable surface of a tile.
+//!
+//! The geometry around a tile is rasterized into columns of solid voxels. The top of the solid
+//! spans with enough clearance above them become walkable cells, which are eroded by the agent
+//! radius, grouped into connected regions, and merged into rectangles that form the polygons of
+//! the tile.
+
+use bevy_math::{bounding::Aabb3d, IVec2, Vec3};
+
+use crate::{NavMeshGeometry, NavMeshSettings, NavPolygon};
+
+/// A vertical range of solid voxels in a column.
+#[derive(Clone, Copy, Debug)]
+struct Span {
+    min: i32,
+    max: i32,
+    walkable: bool,
+}
+
+/// The solid voxels of a rectangle of columns.
+struct Heightfield {
+    min: IVec2,
+    size: IVec2,
+    /// The spans of each column, sorted from bottom to top and not overlapping.
+    columns: Vec<Vec<Span>>,
+}
+
+impl Heightfield {
+    fn new(min: IVec2, size: IVec2) -> Self {
+        Self {
+            min,
+            size,
+            columns: vec![Vec::new(); (size.x * size.y) as usize],
+        }
+    }
+
+    fn column_index(&self, x: i32, z: i32) -> Option<usize> {
+        let local = IVec2::new(x, z) - self.min;
+        if local.cmplt(IVec2::ZERO).any() || local.cmpge(self.size).any() {
+            return None;
+        }
+        Some((local.y * self.size.x + local.x) as usize)
+    }
+
+    /// Adds a span to a column, merging it with the spans it overlaps.
+    ///
+    /// When the tops of the merged spans are within `merge_threshold` voxels, the merged span
+    /// is walkable if any of them is. Otherwise, the highest top decides.
+    fn add_span(&mut self, x: i32, z: i32, mut span: Span, merge_threshold: i32) {
+        let Some(index) = self.column_index(x, z) else {
+            return;
+        };
+        let column = &mut self.columns[index];
+        let mut i = 0;
+        while i < column.len() {
+            let current = column[i];
+            if current.min > span.max {
+                break;
+            }
+            if current.max < span.min {
+                i += 1;
+                continue;
+            }
+            if (current.max - span.max).abs() <= merge_threshold {
+                span.walkable |= current.walkable;
+            } else if current.max > span.max {
+                span.walkable = current.walkable;
+            }
+            span.min = span.min.min(current.min);
+            span.max = span.max.max(current.max);
+            column.remove(i);
+        }
+        column.insert(i, span);
+    }
+
+    fn rasterize_triangle(
+        &mut self,
+        triangle: &[Vec3; 3],
+        walkable: bool,
+        settings: &NavMeshSettings,
+        merge_threshold: i32,
+    ) {
+        let [a, b, c] = *triangle;
+        let cell_size = settings.cell_size;
+        let min = a.min(b).min(c);
+        let max = a.max(b).max(c);
+        let z_range = (min.z / cell_size).floor() as i32..=(max.z / cell_size).floor() as i32;
+        let z_range =
+            (*z_range.start()).max(self.min.y)..=(*z_range.end()).min(self.min.y + self.size.y - 1);
+
+        for z in z_range {
+            let row = clip(&[a, b, c], 2, z as f32 * cell_size, true);
+            let row = clip(&row, 2, (z + 1) as f32 * cell_size, false);
+            if row.len() < 3 {
+                continue;
+            }
+            let (row_min, row_max) = row.iter().fold((f32::MAX, f32::MIN), |(min, max), p| {
+                (min.min(p.x), max.max(p.x))
+            });
+            let x_start = ((row_min / cell_size).floor() as i32).max(self.min.x);
+            let x_end = ((row_max / cell_size).floor() as i32).min(self.min.x + self.size.x - 1);
+
+            for x in x_start..=x_end {
+                let cell = clip(&row, 0, x as f32 * cell_size, true);
+                let cell = clip(&cell, 0, (x + 1) as f32 * cell_size, false);
+                if cell.len() < 3 {
+                    continue;
+                }
+                let (y_min, y_max) = cell.iter().fold((f32::MAX, f32::MIN), |(min, max), p| {
+                    (min.min(p.y), max.max(p.y))
+                });
+                let span = Span {
+                    min: (y_min / settings.cell_height).floor() as i32,
+                    max: (y_max / settings.cell_height).ceil() as i32,
+                    walkable,
+                };
+                self.add_span(x, z, span, merge_threshold);
+            }
+        }
+    }
+
+    fn rasterize_obstacle(&mut self, obstacle: &Aabb3d, settings: &NavMeshSettings) {
+        let min_cell = (obstacle.min / settings.cell_size).floor().as_ivec3();
+        let max_cell = (obstacle.max / settings.cell_size).ceil().as_ivec3();
+        let span = Span {
+            min: (obstacle.min.y / settings.cell_height).floor() as i32,
+            max: (obstacle.max.y / settings.cell_height).ceil() as i32,
+            walkable: false,
+        };
+        for z in min_cell.z..max_cell.z {
+            for x in min_cell.x..max_cell.x {
+                self.add_span(x, z, span, 0);
+            }
+        }
+    }
+}
+
+/// Clips a convex polygon by an axis-aligned plane, keeping the part where the coordinate
+/// along `axis` is greater than `value` if `keep_greater`, or lower otherwise.
+fn clip(polygon: &[Vec3], axis: usize, value: f32, keep_greater: bool) -> Vec<Vec3> {
+    let distance = |point: Vec3| {
+        if keep_greater {
+            point[axis] - value
+        } else {
+            value - point[axis]
+        }
+    };
+    let mut clipped = Vec::with_capacity(polygon.len() + 1);
+    for (i, &a) in polygon.iter().enumerate() {
+        let b = polygon[(i + 1) % polygon.len()];
+        let (distance_a, distance_b) = (distance(a), distance(b));
+        if distance_a >= 0.0 {
+            clipped.push(a);
+        }
+        if (distance_a >= 0.0) != (distance_b >= 0.0) {
+            clipped.push(a + (b - a) * (distance_a / (distance_a - distance_b)));
+        }
+    }
+    clipped
+}
+
+/// The directions to the neighbors of a cell: -x, +z, +x, -z.
+const DIRECTIONS: [IVec2; 4] = [
+    IVec2::new(-1, 0),
+    IVec2::new(0, 1),
+    IVec2::new(1, 0),
+    IVec2::new(0, -1),
+];
+const POSITIVE_X: usize = 2;
+const POSITIVE_Z: usize = 1;
+
+/// The walkable top of a solid span.
+struct Cell {
+    position: IVec2,
+    /// The height of the floor, in voxels.
+    floor: i32,
+    /// The height of the next solid span above, in voxels.
+    ceiling: i32,
+    /// The walkable cells that can be reached from this one, in each of the [`DIRECTIONS`].
+    neighbors: [Option<u32>; 4],
+}
+
+/// Builds the polygons of the walkable surface of a tile.
+pub(crate) fn build_tile(
+    settings: &NavMeshSettings,
+    tile: IVec2,
+    geometry: &NavMeshGeometry,
+) -> Vec<NavPolygon> {
+    let walkable_height = (settings.agent_height / settings.cell_height).ceil() as i32;
+    let walkable_climb = (settings.agent_max_climb / settings.cell_height).floor() as i32;
+    let walkable_radius = (settings.agent_radius / settings.cell_size).ceil() as i32;
+    let min_walkable_normal_y = settings.agent_max_slope.cos();
+
+    // The cells around the tile are rasterized too, so that the erosion near its borders takes
+    // the neighboring geometry into account.
+    let border = walkable_radius + 1;
+    let core_min = tile * settings.tile_size as i32;
+    let core_max = core_min + IVec2::splat(settings.tile_size as i32);
+    let mut heightfield = Heightfield::new(
+        core_min - IVec2::splat(border),
+        core_max - core_min + IVec2::splat(2 * border),
+    );
+
+    let bounds_min = heightfield.min.as_vec2() * settings.cell_size;
+    let bounds_max = (heightfield.min + heightfield.size).as_vec2() * settings.cell_size;
+    for triangle in &geometry.triangles {
+        let min = triangle[0].min(triangle[1]).min(triangle[2]);
+        let max = triangle[0].max(triangle[1]).max(triangle[2]);
+        if max.x < bounds_min.x
+            || max.z < bounds_min.y
+            || min.x > bounds_max.x
+            || min.z > bounds_max.y
+        {
+            continue;
+        }
+        let normal = (triangle[1] - triangle[0])
+            .cross(triangle[2] - triangle[0])
+            .normalize_or_zero();
+        let walkable = normal.y >= min_walkable_normal_y;
+        heightfield.rasterize_triangle(triangle, walkable, settings, walkable_climb);
+    }
+    for obstacle in &geometry.obstacles {
+        heightfield.rasterize_obstacle(obstacle, settings);
+    }
+
+    let mut cells = walkable_cells(&heightfield, walkable_height, walkable_climb);
+    erode(&mut cells, walkable_radius);
+
+    let in_core =
+        |position: IVec2| position.cmpge(core_min).all() && position.cmplt(core_max).all();
+    let regions = build_regions(&cells, in_core);
+    merge_rectangles(&cells, &regions, settings)
+}
+
+/// Finds the walkable cells of the heightfield, and the cells each of them can move to.
+fn walkable_cells(
+    heightfield: &Heightfield,
+    walkable_height: i32,
+    walkable_climb: i32,
+) -> Vec<Cell> {
+    let mut cells = Vec::new();
+    // The range of the cells of each column.
+    let mut column_cells = Vec::with_capacity(heightfield.columns.len());
+    for (index, column) in heightfield.columns.iter().enumerate() {
+        let start = cells.len();
+        let position = heightfield.min
+            + IVec2::new(
+                index as i32 % heightfield.size.x,
+                index as i32 / heightfield.size.x,
+            );
+        for (i, span) in column.iter().enumerate() {
+            let ceiling = column.get(i + 1).map_or(i32::MAX, |next| next.min);
+            if span.walkable && ceiling - span.max >= walkable_height {
+                cells.push(Cell {
+                    position,
+                    floor: span.max,
+                    ceiling,
+                    neighbors: [None; 4],
+                });
+            }
+        }
+        column_cells.push(start..cells.len());
+    }
+
+    for index in 0..cells.len() {
+        for (direction, offset) in DIRECTIONS.iter().enumerate() {
+            let position = cells[index].position + *offset;
+            let Some(column) = heightfield.column_index(position.x, position.y) else {
+                continue;
+            };
+            let cell = &cells[index];
+            let neighbor = column_cells[column]
+                .clone()
+                .find(|&neighbor| {
+                    let neighbor = &cells[neighbor];
+                    (neighbor.floor - cell.floor).abs() <= walkable_climb
+                        && neighbor.ceiling.min(cell.ceiling) - neighbor.floor.max(cell.floor)
+                            >= walkable_height
+                })
+                .map(|neighbor| neighbor as u32);
+            cells[index].neighbors[direction] = neighbor;
+        }
+    }
+    cells
+}
+
+/// Removes the walkable cells closer than `radius` cells to an obstacle or a ledge.
+fn erode(cells: &mut [Cell], radius: i32) {
+    if radius <= 0 {
+        return;
+    }
+    let mut distances = vec![i32::MAX; cells.len()];
+    let mut queue = std::collections::VecDeque::new();
+    for (index, cell) in cells.iter().enumerate() {
+        if cell.neighbors.iter().any(Option::is_none) {
+            distances[index] = 0;
+            queue.push_back(index);
+        }
+    }
+    while let Some(index) = queue.pop_front() {
+        for neighbor in cells[index].neighbors.into_iter().flatten() {
+            let neighbor = neighbor as usize;
+            if distances[neighbor] > distances[index] + 1 {
+                distances[neighbor] = distances[index] + 1;
+                queue.push_back(neighbor);
+            }
+        }
+    }
+
+    for cell in cells.iter_mut() {
+        for neighbor in &mut cell.neighbors {
+            if neighbor.is_some_and(|neighbor| distances[neighbor as usize] < radius) {
+                *neighbor = None;
+            }
+        }
+    }
+    for (cell, distance) in cells.iter_mut().zip(distances) {
+        if distance < radius {
+            cell.neighbors = [None; 4];
+            cell.floor = i32::MIN;
+        }
+    }
+}
+
+/// Groups the connected walkable cells inside the tile into regions.
+///
+/// Returns the region of each cell, or `None` for cells that are outside the tile or eroded.
+fn build_regions(cells: &[Cell], in_core: impl Fn(IVec2) -> bool) -> Vec<Option<u32>> {
+    let is_walkable =
+        |index: usize| cells[index].floor != i32::MIN && in_core(cells[index].position);
+    let mut regions = vec![None; cells.len()];
+    let mut region_count = 0;
+    let mut stack = Vec::new();
+    for seed in 0..cells.len() {
+        if regions[seed].is_some() || !is_walkable(seed) {
+            continue;
+        }
+        regions[seed] = Some(region_count);
+        stack.push(seed);
+        while let Some(index) = stack.pop() {
+            for neighbor in cells[index].neighbors.into_iter().flatten() {
+                let neighbor = neighbor as usize;
+                if regions[neighbor].is_none() && is_walkable(neighbor) {
+                    regions[neighbor] = Some(region_count);
+                    stack.push(neighbor);
+                }
+            }
+        }
+        region_count += 1;
+    }
+    regions
+}
+
+/// Greedily merges the cells of each region into flat rectangles.
+fn merge_rectangles(
+    cells: &[Cell],
+    regions: &[Option<u32>],
+    settings: &NavMeshSettings,
+) -> Vec<NavPolygon> {
+    let mut used = vec![false; cells.len()];
+    let mut polygons = Vec::new();
+
+    // Cells are sorted by row then column, so seeds are the bottom left corners of rectangles.
+    for seed in 0..cells.len() {
+        let Some(region) = regions[seed] else {
+            continue;
+        };
+        if used[seed] {
+            continue;
+        }
+        let floor = cells[seed].floor;
+        let can_merge = |index: u32, used: &[bool]| {
+            let index = index as usize;
+            !used[index]
+                && regions[index] == Some(region)
+                && (cells[index].floor - floor).abs() <= 1
+        };
+
+        let mut row = vec![seed];
+        used[seed] = true;
+        while let Some(next) = cells[*row.last().unwrap()].neighbors[POSITIVE_X] {
+            if !can_merge(next, &used) {
+                break;
+            }
+            used[next as usize] = true;
+            row.push(next as usize);
+        }
+
+        let mut rows = 1;
+        let mut floor_sum: i64 = row.iter().map(|&index| cells[index].floor as i64).sum();
+        let mut current = row;
+        loop {
+            let next: Option<Vec<usize>> = current
+                .iter()
+                .map(|&index| {
+                    cells[index].neighbors[POSITIVE_Z]
+                        .filter(|&next| can_merge(next, &used))
+                        .map(|next| next as usize)
+                })
+                .collect();
+            let Some(next) = next else {
+                break;
+            };
+            let connected = next
+                .windows(2)
+                .all(|pair| cells[pair[0]].neighbors[POSITIVE_X] == Some(pair[1] as u32));
+            if !connected {
+                break;
+            }
+            for &index in &next {
+                used[index] = true;
+            }
+            floor_sum += next
+                .iter()
+                .map(|&index| cells[index].floor as i64)
+                .sum::<i64>();
+            rows += 1;
+            current = next;
+        }
+
+        let cell_min = cells[seed].position;
+        let cell_max = cell_min + IVec2::new(current.len() as i32, rows);
+        let height = floor_sum as f32 / (current.len() as i32 * rows) as f32 * settings.cell_height;
+        polygons.push(NavPolygon::new(
+            cell_min,
+            cell_max,
+            height,
+            settings.cell_size,
+        ));
+    }
+    polygons
+}
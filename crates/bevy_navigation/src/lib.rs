@@ -0,0 +1,363 @@
+//! Navigation meshes and pathfinding for Bevy.
+//!
+//! A [`NavMesh`] describes the surface of the level agents can walk on, as connected polygons.
+//! It is baked from the level geometry:
+//!
+//! 1. The triangles of the level are voxelized into a heightfield, and the tops of the solid
+//!    voxels with a gentle enough slope and enough room above them become walkable cells.
+//! 2. The walkable cells are eroded by the radius of the agents, so that paths keep away from
+//!    walls and ledges.
+//! 3. The remaining cells are grouped into connected regions, which are merged into polygons.
+//!
+//! The navigation mesh is split into tiles which are baked independently, so that moving a
+//! [`NavMeshObstacle`] only rebuilds the tiles around it.
+//!
+//! Paths are found with [`NavMesh::find_path`], which searches the polygons to go through with
+//! A*, then finds the shortest path through them with a funnel algorithm.
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_math::Vec3;
+//! # use bevy_navigation::NavMesh;
+//! fn find_path(navmesh: Res<NavMesh>) {
+//!     match navmesh.find_path(Vec3::ZERO, Vec3::new(10.0, 0.0, 5.0)) {
+//!         Ok(path) => println!("go through {path:?}"),
+//!         Err(error) => println!("no way: {error}"),
+//!     }
+//! }
+//! # bevy_ecs::system::assert_is_system(find_path);
+//! ```
+
+mod geometry;
+mod heightfield;
+mod navmesh;
+mod path;
+
+#[cfg(feature = "bevy_gizmos")]
+pub mod gizmos;
+
+pub use geometry::*;
+pub use navmesh::*;
+pub use path::*;
+
+/// The `bevy_navigation` prelude.
+pub mod prelude {
+    #[doc(hidden)]
+    pub use crate::{
+        NavMesh, NavMeshObstacle, NavMeshSettings, NavMeshSource, NavMeshUpdated, NavigationPlugin,
+        PathError,
+    };
+}
+
+use bevy_app::prelude::*;
+use bevy_asset::{AssetEvent, Assets, Handle};
+use bevy_ecs::prelude::*;
+use bevy_math::{bounding::Aabb3d, IVec2, Vec3};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::mesh::Mesh;
+use bevy_transform::{components::GlobalTransform, TransformSystem};
+use bevy_utils::{HashMap, HashSet};
+
+/// Adds a [`NavMesh`] kept up to date with the [`NavMeshSource`] meshes and the
+/// [`NavMeshObstacle`]s of the world, baked with the [`NavMeshSettings`].
+#[derive(Default)]
+pub struct NavigationPlugin;
+
+impl Plugin for NavigationPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<NavMeshSettings>()
+            .register_type::<NavMeshSource>()
+            .register_type::<NavMeshObstacle>()
+            .init_resource::<NavMeshSettings>()
+            .init_resource::<NavMesh>()
+            .add_event::<NavMeshUpdated>()
+            .add_systems(
+                PostUpdate,
+                update_navmesh
+                    .in_set(NavigationSystem::UpdateNavMesh)
+                    .after(TransformSystem::TransformPropagate),
+            );
+
+        #[cfg(feature = "bevy_gizmos")]
+        app.add_plugins(gizmos::NavMeshGizmoPlugin);
+    }
+}
+
+/// Label for the system updating the [`NavMesh`].
+#[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
+pub enum NavigationSystem {
+    /// Rebuilds the tiles of the [`NavMesh`] affected by changes to its geometry.
+    UpdateNavMesh,
+}
+
+/// Marks an entity whose mesh is part of the level geometry the [`NavMesh`] is baked from.
+///
+/// Adding, moving or removing a source rebuilds the whole navigation mesh, so sources should be
+/// the static geometry of the level: use [`NavMeshObstacle`]s for the objects that move.
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct NavMeshSource;
+
+/// A box blocking movement, centered on the entity.
+///
+/// Adding, moving or removing an obstacle only rebuilds the tiles of the [`NavMesh`] around it.
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct NavMeshObstacle {
+    /// The half size of the box along each axis, before the entity is rotated and scaled.
+    pub half_extents: Vec3,
+}
+
+impl NavMeshObstacle {
+    /// Returns the world space bounds of the obstacle, placed by `transform`.
+    pub fn bounds(&self, transform: &GlobalTransform) -> Aabb3d {
+        let matrix = transform.affine().matrix3;
+        let half_size = Vec3::from(matrix.x_axis.abs()) * self.half_extents.x
+            + Vec3::from(matrix.y_axis.abs()) * self.half_extents.y
+            + Vec3::from(matrix.z_axis.abs()) * self.half_extents.z;
+        Aabb3d::new(transform.translation(), half_size)
+    }
+}
+
+/// Sent when tiles of the [`NavMesh`] are rebuilt.
+///
+/// Paths going through these tiles may no longer be valid.
+#[derive(Event, Clone, Debug)]
+pub struct NavMeshUpdated {
+    /// The coordinates of the rebuilt tiles.
+    pub tiles: Vec<IVec2>,
+}
+
+/// The geometry the [`NavMesh`] was last built from.
+#[derive(Default)]
+pub struct NavMeshState {
+    geometry: NavMeshGeometry,
+    obstacles: HashMap<Entity, Aabb3d>,
+}
+
+/// Rebuilds the [`NavMesh`] when its geometry changes.
+///
+/// Changes to the [`NavMeshSettings`] or the [`NavMeshSource`]s rebuild the whole navigation
+/// mesh, while changes to the [`NavMeshObstacle`]s only rebuild the tiles around them.
+#[allow(clippy::too_many_arguments)]
+pub fn update_navmesh(
+    mut navmesh: ResMut<NavMesh>,
+    settings: Res<NavMeshSettings>,
+    meshes: Res<Assets<Mesh>>,
+    mut mesh_events: EventReader<AssetEvent<Mesh>>,
+    sources: Query<(&Handle<Mesh>, &GlobalTransform), With<NavMeshSource>>,
+    changed_sources: Query<
+        (),
+        (
+            With<NavMeshSource>,
+            Or<(
+                Changed<NavMeshSource>,
+                Changed<Handle<Mesh>>,
+                Changed<GlobalTransform>,
+            )>,
+        ),
+    >,
+    mut removed_sources: RemovedComponents<NavMeshSource>,
+    obstacles: Query<(Entity, &NavMeshObstacle, &GlobalTransform)>,
+    mut state: Local<NavMeshState>,
+    mut updated: EventWriter<NavMeshUpdated>,
+) {
+    let changed_meshes: HashSet<_> = mesh_events
+        .read()
+        .map(|event| match *event {
+            AssetEvent::Added { id }
+            | AssetEvent::Modified { id }
+            | AssetEvent::Removed { id }
+            | AssetEvent::Unused { id }
+            | AssetEvent::LoadedWithDependencies { id } => id,
+        })
+        .collect();
+    let rebuild_all = settings.is_changed()
+        || !changed_sources.is_empty()
+        || removed_sources.read().count() > 0
+        || sources
+            .iter()
+            .any(|(mesh, _)| changed_meshes.contains(&mesh.id()));
+
+    let current_obstacles: HashMap<_, _> = obstacles
+        .iter()
+        .map(|(entity, obstacle, transform)| (entity, obstacle.bounds(transform)))
+        .collect();
+
+    if rebuild_all {
+        state.geometry = NavMeshGeometry::default();
+        for (mesh, transform) in &sources {
+            if let Some(mesh) = meshes.get(mesh) {
+                state.geometry.add_mesh(mesh, transform);
+            }
+        }
+        state.geometry.obstacles = current_obstacles.values().copied().collect();
+        state.obstacles = current_obstacles;
+
+        *navmesh = NavMesh::build(settings.clone(), &state.geometry);
+        updated.send(NavMeshUpdated {
+            tiles: navmesh.tiles().map(|(tile, _)| tile).collect(),
+        });
+        return;
+    }
+
+    // The bounds of the obstacles that were added, moved or removed, before and after the change.
+    let mut changed_bounds: Vec<Aabb3d> = Vec::new();
+    for (entity, bounds) in &current_obstacles {
+        match state.obstacles.get(entity) {
+            Some(old_bounds) if old_bounds.min == bounds.min && old_bounds.max == bounds.max => {}
+            Some(old_bounds) => changed_bounds.extend([*old_bounds, *bounds]),
+            None => changed_bounds.push(*bounds),
+        }
+    }
+    for (entity, old_bounds) in &state.obstacles {
+        if !current_obstacles.contains_key(entity) {
+            changed_bounds.push(*old_bounds);
+        }
+    }
+    if changed_bounds.is_empty() {
+        return;
+    }
+
+    // Obstacles affect the cells within the erosion radius around them.
+    let margin = Vec3::splat(settings.agent_radius + settings.cell_size);
+    let mut tiles = HashSet::new();
+    for bounds in changed_bounds {
+        let min = settings.tile_at(bounds.min - margin);
+        let max = settings.tile_at(bounds.max + margin);
+        for z in min.y..=max.y {
+            for x in min.x..=max.x {
+                tiles.insert(IVec2::new(x, z));
+            }
+        }
+    }
+
+    state.geometry.obstacles = current_obstacles.values().copied().collect();
+    state.obstacles = current_obstacles;
+    let tiles: Vec<_> = tiles.into_iter().collect();
+    for &tile in &tiles {
+        navmesh.rebuild_tile(tile, &state.geometry);
+    }
+    updated.send(NavMeshUpdated { tiles });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_render::mesh::PrimitiveTopology;
+    use bevy_render::{mesh::Indices, render_asset::RenderAssetUsages};
+
+    /// A flat floor covering `[-size, size]` on the X and Z axes.
+    fn floor(size: f32) -> Mesh {
+        Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            vec![
+                [-size, 0., -size],
+                [size, 0., -size],
+                [size, 0., size],
+                [-size, 0., size],
+            ],
+        )
+        .with_inserted_indices(Indices::U32(vec![0, 2, 1, 0, 3, 2]))
+    }
+
+    fn settings() -> NavMeshSettings {
+        NavMeshSettings {
+            cell_size: 0.25,
+            cell_height: 0.1,
+            agent_radius: 0.25,
+            agent_height: 1.0,
+            agent_max_climb: 0.2,
+            tile_size: 16,
+            ..Default::default()
+        }
+    }
+
+    fn path_length(path: &[Vec3]) -> f32 {
+        path.windows(2).map(|pair| pair[0].distance(pair[1])).sum()
+    }
+
+    #[test]
+    fn path_around_obstacle() {
+        let mut geometry = NavMeshGeometry::default();
+        geometry.add_mesh(&floor(5.), &GlobalTransform::IDENTITY);
+        let navmesh = NavMesh::build(settings(), &geometry);
+
+        let start = Vec3::new(-3., 0., 0.5);
+        let end = Vec3::new(3., 0., 0.5);
+        let path = navmesh.find_path(start, end).unwrap();
+        assert_eq!(path.len(), 2);
+        assert!(path[0].abs_diff_eq(start, 1e-4));
+        assert!(path[1].abs_diff_eq(end, 1e-4));
+
+        // A wall between the start and the end, with a way around it on the positive Z side.
+        geometry.add_obstacle(Aabb3d {
+            min: Vec3::new(-0.5, -1., -5.),
+            max: Vec3::new(0.5, 2., 2.),
+        });
+        let navmesh = NavMesh::build(settings(), &geometry);
+        let path = navmesh.find_path(start, end).unwrap();
+        assert!(path.len() > 2);
+        assert!(path.iter().all(|point| point.z >= 0.5 - 1e-4));
+        assert!(path.iter().any(|point| point.z >= 2.));
+        // The path hugs the corners of the wall rather than going anywhere near the edge.
+        assert!(path_length(&path) < 8.);
+
+        assert_eq!(
+            navmesh.find_path(start, Vec3::new(20., 0., 0.)),
+            Err(PathError::EndNotOnNavMesh(Vec3::new(20., 0., 0.)))
+        );
+    }
+
+    #[test]
+    fn obstacle_rebuilds_tiles() {
+        let mut app = App::new();
+        app.add_plugins(NavigationPlugin)
+            .init_resource::<Assets<Mesh>>()
+            .add_event::<AssetEvent<Mesh>>()
+            .insert_resource(settings());
+
+        let floor = app.world.resource_mut::<Assets<Mesh>>().add(floor(8.));
+        app.world
+            .spawn((floor, GlobalTransform::IDENTITY, NavMeshSource));
+        app.update();
+
+        let start = Vec3::new(-6., 0., 0.);
+        let end = Vec3::new(6., 0., 0.);
+        let navmesh = app.world.resource::<NavMesh>();
+        let tile_count = navmesh.tiles().count();
+        assert_eq!(navmesh.find_path(start, end).unwrap().len(), 2);
+        app.world.resource_mut::<Events<NavMeshUpdated>>().clear();
+
+        // A wall cutting the floor in two.
+        let wall = app
+            .world
+            .spawn((
+                NavMeshObstacle {
+                    half_extents: Vec3::new(0.5, 1., 10.),
+                },
+                GlobalTransform::IDENTITY,
+            ))
+            .id();
+        app.update();
+
+        let updated = app.world.resource::<Events<NavMeshUpdated>>();
+        let rebuilt = &updated.iter_current_update_events().next().unwrap().tiles;
+        assert!(!rebuilt.is_empty() && rebuilt.len() < tile_count);
+        assert!(rebuilt.iter().all(|tile| tile.x == -1 || tile.x == 0));
+        let navmesh = app.world.resource::<NavMesh>();
+        assert!(matches!(
+            navmesh.find_path(start, end),
+            Err(PathError::NoPath(..))
+        ));
+
+        app.world.despawn(wall);
+        app.update();
+        let navmesh = app.world.resource::<NavMesh>();
+        assert_eq!(navmesh.find_path(start, end).unwrap().len(), 2);
+    }
+}
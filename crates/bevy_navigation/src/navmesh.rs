@@ -0,0 +1,349 @@
+use std::cmp::Ordering;
+
+use bevy_ecs::system::Resource;
+use bevy_math::{IVec2, Vec2, Vec3};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_utils::HashMap;
+
+use crate::{heightfield, NavMeshGeometry};
+
+/// The settings used to bake the [`NavMesh`].
+///
+/// Changing this resource rebuilds the whole navigation mesh.
+#[derive(Resource, Clone, Debug, Reflect)]
+#[reflect(Default)]
+pub struct NavMeshSettings {
+    /// The horizontal size of the voxels the geometry is rasterized into.
+    ///
+    /// Smaller cells follow the geometry more closely, but are slower to bake.
+    pub cell_size: f32,
+    /// The vertical size of the voxels the geometry is rasterized into.
+    pub cell_height: f32,
+    /// The radius of the agents, used to keep paths away from walls and ledges.
+    pub agent_radius: f32,
+    /// The height of the agents, the minimum clearance of walkable areas.
+    pub agent_height: f32,
+    /// The maximum height of the steps agents can climb.
+    pub agent_max_climb: f32,
+    /// The maximum slope of walkable surfaces, in radians.
+    pub agent_max_slope: f32,
+    /// The number of cells along each side of a tile.
+    ///
+    /// Tiles are the unit of incremental rebuilds: smaller tiles are faster to rebuild when an
+    /// obstacle moves, but produce more polygons.
+    pub tile_size: u32,
+}
+
+impl Default for NavMeshSettings {
+    fn default() -> Self {
+        Self {
+            cell_size: 0.25,
+            cell_height: 0.1,
+            agent_radius: 0.5,
+            agent_height: 2.0,
+            agent_max_climb: 0.4,
+            agent_max_slope: std::f32::consts::FRAC_PI_4,
+            tile_size: 32,
+        }
+    }
+}
+
+impl NavMeshSettings {
+    /// Returns the size of the side of a tile, in world units.
+    pub fn tile_world_size(&self) -> f32 {
+        self.tile_size as f32 * self.cell_size
+    }
+
+    /// Returns the coordinates of the tile containing `point`.
+    pub fn tile_at(&self, point: Vec3) -> IVec2 {
+        (Vec2::new(point.x, point.z) / self.tile_world_size())
+            .floor()
+            .as_ivec2()
+    }
+}
+
+/// A reference to a [`NavPolygon`] of a [`NavMesh`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PolyRef {
+    /// The coordinates of the tile of the polygon.
+    pub tile: IVec2,
+    /// The index of the polygon in the [`NavMeshTile::polygons`] of its tile.
+    pub index: u32,
+}
+
+impl PartialOrd for PolyRef {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PolyRef {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // `IVec2` isn't `Ord`: order by tile row, then column, then index.
+        (self.tile.x, self.tile.y, self.index).cmp(&(other.tile.x, other.tile.y, other.index))
+    }
+}
+
+/// A connection from a [`NavPolygon`] to a neighboring one.
+#[derive(Clone, Debug)]
+pub struct NavLink {
+    /// The neighboring polygon.
+    pub target: PolyRef,
+    /// The endpoints of the shared edge of the two polygons.
+    pub portal: [Vec3; 2],
+}
+
+/// A flat, axis-aligned rectangle of walkable surface.
+#[derive(Clone, Debug)]
+pub struct NavPolygon {
+    /// The corners of the rectangle, in world space.
+    pub vertices: [Vec3; 4],
+    /// The polygons that can be reached from this one.
+    pub links: Vec<NavLink>,
+    /// The first cell covered by the rectangle.
+    cell_min: IVec2,
+    /// The cell after the last one covered by the rectangle.
+    cell_max: IVec2,
+    height: f32,
+}
+
+impl NavPolygon {
+    pub(crate) fn new(cell_min: IVec2, cell_max: IVec2, height: f32, cell_size: f32) -> Self {
+        let min = cell_min.as_vec2() * cell_size;
+        let max = cell_max.as_vec2() * cell_size;
+        Self {
+            vertices: [
+                Vec3::new(min.x, height, min.y),
+                Vec3::new(min.x, height, max.y),
+                Vec3::new(max.x, height, max.y),
+                Vec3::new(max.x, height, min.y),
+            ],
+            links: Vec::new(),
+            cell_min,
+            cell_max,
+            height,
+        }
+    }
+
+    /// Returns the center of the polygon.
+    pub fn center(&self) -> Vec3 {
+        (self.vertices[0] + self.vertices[2]) / 2.0
+    }
+
+    /// Returns the point of the polygon closest to `point`.
+    pub fn closest_point(&self, point: Vec3) -> Vec3 {
+        let clamped = point.clamp(self.vertices[0], self.vertices[2]);
+        Vec3::new(clamped.x, self.height, clamped.z)
+    }
+
+    /// Returns the portal between this polygon and `other`, if they share an edge and the
+    /// difference in height between them is below `max_step`.
+    fn portal(&self, other: &NavPolygon, cell_size: f32, max_step: f32) -> Option<[Vec3; 2]> {
+        if (self.height - other.height).abs() > max_step {
+            return None;
+        }
+        let height = (self.height + other.height) / 2.0;
+        let min = self.cell_min.max(other.cell_min);
+        let max = self.cell_max.min(other.cell_max);
+        let x_edge = if self.cell_max.x == other.cell_min.x {
+            Some(self.cell_max.x)
+        } else if self.cell_min.x == other.cell_max.x {
+            Some(self.cell_min.x)
+        } else {
+            None
+        };
+        if let Some(x) = x_edge.filter(|_| min.y < max.y) {
+            let x = x as f32 * cell_size;
+            return Some([
+                Vec3::new(x, height, min.y as f32 * cell_size),
+                Vec3::new(x, height, max.y as f32 * cell_size),
+            ]);
+        }
+        let z_edge = if self.cell_max.y == other.cell_min.y {
+            Some(self.cell_max.y)
+        } else if self.cell_min.y == other.cell_max.y {
+            Some(self.cell_min.y)
+        } else {
+            None
+        };
+        if let Some(z) = z_edge.filter(|_| min.x < max.x) {
+            let z = z as f32 * cell_size;
+            return Some([
+                Vec3::new(min.x as f32 * cell_size, height, z),
+                Vec3::new(max.x as f32 * cell_size, height, z),
+            ]);
+        }
+        None
+    }
+}
+
+/// The polygons of a tile of a [`NavMesh`].
+#[derive(Clone, Debug, Default)]
+pub struct NavMeshTile {
+    /// The walkable polygons of the tile.
+    pub polygons: Vec<NavPolygon>,
+}
+
+/// A navigation mesh: the walkable surface of the level, as connected polygons split into tiles.
+///
+/// The [`NavigationPlugin`](crate::NavigationPlugin) keeps this resource up to date with the
+/// [`NavMeshSource`](crate::NavMeshSource) meshes and [`NavMeshObstacle`](crate::NavMeshObstacle)s
+/// of the world. It can also be baked manually with [`NavMesh::build`].
+#[derive(Resource, Clone, Debug, Default)]
+pub struct NavMesh {
+    settings: NavMeshSettings,
+    tiles: HashMap<IVec2, NavMeshTile>,
+}
+
+impl NavMesh {
+    /// Bakes the navigation mesh of `geometry`.
+    pub fn build(settings: NavMeshSettings, geometry: &NavMeshGeometry) -> Self {
+        let mut navmesh = Self {
+            settings,
+            tiles: HashMap::default(),
+        };
+        let Some(bounds) = geometry.bounds() else {
+            return navmesh;
+        };
+        let min = navmesh.settings.tile_at(bounds.min);
+        let max = navmesh.settings.tile_at(bounds.max);
+        for z in min.y..=max.y {
+            for x in min.x..=max.x {
+                navmesh.rebuild_tile(IVec2::new(x, z), geometry);
+            }
+        }
+        navmesh
+    }
+
+    /// Returns the settings the navigation mesh was baked with.
+    pub fn settings(&self) -> &NavMeshSettings {
+        &self.settings
+    }
+
+    /// Returns the tile at the given coordinates.
+    pub fn tile(&self, tile: IVec2) -> Option<&NavMeshTile> {
+        self.tiles.get(&tile)
+    }
+
+    /// Returns an iterator over the coordinates and contents of the tiles.
+    pub fn tiles(&self) -> impl Iterator<Item = (IVec2, &NavMeshTile)> {
+        self.tiles
+            .iter()
+            .map(|(&coordinates, tile)| (coordinates, tile))
+    }
+
+    /// Returns the polygon referenced by `poly`.
+    pub fn polygon(&self, poly: PolyRef) -> Option<&NavPolygon> {
+        self.tiles
+            .get(&poly.tile)?
+            .polygons
+            .get(poly.index as usize)
+    }
+
+    /// Bakes a single tile again from `geometry`, and reconnects it to its neighbors.
+    ///
+    /// This is much faster than rebuilding the whole navigation mesh when the geometry only
+    /// changed locally, such as when an obstacle moved.
+    pub fn rebuild_tile(&mut self, tile: IVec2, geometry: &NavMeshGeometry) {
+        // Disconnect the neighbors from the old polygons of the tile.
+        for neighbor in Self::neighbors(tile) {
+            if let Some(neighbor) = self.tiles.get_mut(&neighbor) {
+                for polygon in &mut neighbor.polygons {
+                    polygon.links.retain(|link| link.target.tile != tile);
+                }
+            }
+        }
+
+        let mut polygons = heightfield::build_tile(&self.settings, tile, geometry);
+        if polygons.is_empty() {
+            self.tiles.remove(&tile);
+            return;
+        }
+
+        let cell_size = self.settings.cell_size;
+        let max_step = self.settings.agent_max_climb + self.settings.cell_height;
+        for i in 0..polygons.len() {
+            for j in 0..polygons.len() {
+                if i == j {
+                    continue;
+                }
+                if let Some(portal) = polygons[i].portal(&polygons[j], cell_size, max_step) {
+                    polygons[i].links.push(NavLink {
+                        target: PolyRef {
+                            tile,
+                            index: j as u32,
+                        },
+                        portal,
+                    });
+                }
+            }
+        }
+        for neighbor_tile in Self::neighbors(tile) {
+            let Some(neighbor) = self.tiles.get_mut(&neighbor_tile) else {
+                continue;
+            };
+            for (i, polygon) in polygons.iter_mut().enumerate() {
+                for (j, other) in neighbor.polygons.iter_mut().enumerate() {
+                    let Some(portal) = polygon.portal(other, cell_size, max_step) else {
+                        continue;
+                    };
+                    polygon.links.push(NavLink {
+                        target: PolyRef {
+                            tile: neighbor_tile,
+                            index: j as u32,
+                        },
+                        portal,
+                    });
+                    other.links.push(NavLink {
+                        target: PolyRef {
+                            tile,
+                            index: i as u32,
+                        },
+                        portal,
+                    });
+                }
+            }
+        }
+        self.tiles.insert(tile, NavMeshTile { polygons });
+    }
+
+    /// Finds the polygon closest to `point`, and the closest point on it.
+    ///
+    /// Returns `None` if there is no polygon within `max_distance` of `point`.
+    pub fn find_nearest(&self, point: Vec3, max_distance: f32) -> Option<(PolyRef, Vec3)> {
+        let min = self.settings.tile_at(point - Vec3::splat(max_distance));
+        let max = self.settings.tile_at(point + Vec3::splat(max_distance));
+        let mut nearest = None;
+        let mut nearest_distance = max_distance;
+        for z in min.y..=max.y {
+            for x in min.x..=max.x {
+                let tile = IVec2::new(x, z);
+                let Some(polygons) = self.tiles.get(&tile).map(|tile| &tile.polygons) else {
+                    continue;
+                };
+                for (index, polygon) in polygons.iter().enumerate() {
+                    let closest = polygon.closest_point(point);
+                    let distance = closest.distance(point);
+                    if distance <= nearest_distance {
+                        nearest_distance = distance;
+                        let poly = PolyRef {
+                            tile,
+                            index: index as u32,
+                        };
+                        nearest = Some((poly, closest));
+                    }
+                }
+            }
+        }
+        nearest
+    }
+
+    fn neighbors(tile: IVec2) -> [IVec2; 4] {
+        [
+            tile - IVec2::X,
+            tile + IVec2::X,
+            tile - IVec2::Y,
+            tile + IVec2::Y,
+        ]
+    }
+}
@@ -0,0 +1,240 @@
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+use bevy_math::Vec3;
+use bevy_utils::HashMap;
+use thiserror::Error;
+
+use crate::{NavMesh, PolyRef};
+
+/// An error that occurs when finding a path with [`NavMesh::find_path`].
+#[derive(Error, Debug, Clone, Copy, PartialEq)]
+pub enum PathError {
+    /// The start of the path isn't close to any walkable polygon.
+    #[error("the start of the path {0} is not on the navigation mesh")]
+    StartNotOnNavMesh(Vec3),
+    /// The end of the path isn't close to any walkable polygon.
+    #[error("the end of the path {0} is not on the navigation mesh")]
+    EndNotOnNavMesh(Vec3),
+    /// The end of the path can't be reached from its start.
+    #[error("there is no path between {0} and {1}")]
+    NoPath(Vec3, Vec3),
+}
+
+/// A polygon to visit during the A* search, with its estimated total cost.
+struct Candidate {
+    poly: PolyRef,
+    estimated_cost: f32,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // `BinaryHeap` is a max-heap: reverse the order to pop the cheapest candidate first.
+        other
+            .estimated_cost
+            .total_cmp(&self.estimated_cost)
+            .then_with(|| self.poly.cmp(&other.poly))
+    }
+}
+
+/// The best known way to reach a polygon during the A* search.
+struct Visit {
+    /// The cost to reach `position` from the start.
+    cost: f32,
+    /// The point the polygon is entered at.
+    position: Vec3,
+    previous: Option<PolyRef>,
+}
+
+impl NavMesh {
+    /// Finds the shortest path from `start` to `end` on the navigation mesh.
+    ///
+    /// `start` and `end` are moved to the closest point of the navigation mesh, as long as they
+    /// are within [`NavMeshSettings::agent_height`](crate::NavMeshSettings::agent_height) of it.
+    ///
+    /// The returned path starts and ends with these points, and only turns at the corners of
+    /// the navigation mesh.
+    pub fn find_path(&self, start: Vec3, end: Vec3) -> Result<Vec<Vec3>, PathError> {
+        let max_distance = self.settings().agent_height;
+        let (start_poly, start) = self
+            .find_nearest(start, max_distance)
+            .ok_or(PathError::StartNotOnNavMesh(start))?;
+        let (end_poly, end) = self
+            .find_nearest(end, max_distance)
+            .ok_or(PathError::EndNotOnNavMesh(end))?;
+
+        let corridor = self
+            .find_corridor(start_poly, start, end_poly, end)
+            .ok_or(PathError::NoPath(start, end))?;
+        Ok(self.string_pull(&corridor, start, end))
+    }
+
+    /// Finds the polygons a path from `start` to `end` goes through, with A*.
+    fn find_corridor(
+        &self,
+        start_poly: PolyRef,
+        start: Vec3,
+        end_poly: PolyRef,
+        end: Vec3,
+    ) -> Option<Vec<PolyRef>> {
+        let mut visits = HashMap::default();
+        visits.insert(
+            start_poly,
+            Visit {
+                cost: 0.0,
+                position: start,
+                previous: None,
+            },
+        );
+        let mut open = BinaryHeap::new();
+        open.push(Candidate {
+            poly: start_poly,
+            estimated_cost: start.distance(end),
+        });
+
+        while let Some(Candidate {
+            poly,
+            estimated_cost,
+        }) = open.pop()
+        {
+            let visit = &visits[&poly];
+            let (cost, position) = (visit.cost, visit.position);
+            if poly == end_poly {
+                break;
+            }
+            // Skip the outdated entries of polygons that were reached again more cheaply.
+            if estimated_cost > cost + position.distance(end) {
+                continue;
+            }
+
+            for link in &self.polygon(poly)?.links {
+                let next_position = if link.target == end_poly {
+                    end
+                } else {
+                    (link.portal[0] + link.portal[1]) / 2.0
+                };
+                let next_cost = cost + position.distance(next_position);
+                if visits
+                    .get(&link.target)
+                    .is_some_and(|visit| visit.cost <= next_cost)
+                {
+                    continue;
+                }
+                visits.insert(
+                    link.target,
+                    Visit {
+                        cost: next_cost,
+                        position: next_position,
+                        previous: Some(poly),
+                    },
+                );
+                open.push(Candidate {
+                    poly: link.target,
+                    estimated_cost: next_cost + next_position.distance(end),
+                });
+            }
+        }
+
+        let mut corridor = vec![end_poly];
+        let mut current = visits.get(&end_poly)?;
+        while let Some(previous) = current.previous {
+            corridor.push(previous);
+            current = &visits[&previous];
+        }
+        corridor.reverse();
+        Some(corridor)
+    }
+
+    /// Finds the shortest path through the portals of `corridor`, with the simple stupid
+    /// funnel algorithm.
+    fn string_pull(&self, corridor: &[PolyRef], start: Vec3, end: Vec3) -> Vec<Vec3> {
+        // The left and right endpoints of the portals, as seen when walking along the corridor.
+        let mut portals = vec![(start, start)];
+        for pair in corridor.windows(2) {
+            let Some(polygon) = self.polygon(pair[0]) else {
+                continue;
+            };
+            let Some(link) = polygon.links.iter().find(|link| link.target == pair[1]) else {
+                continue;
+            };
+            let center = polygon.center();
+            let [a, b] = link.portal;
+            if cross(center, (a + b) / 2.0, a) > 0.0 {
+                portals.push((a, b));
+            } else {
+                portals.push((b, a));
+            }
+        }
+        portals.push((end, end));
+
+        let mut path = vec![start];
+        let (mut apex, mut left, mut right) = (start, start, start);
+        let (mut left_index, mut right_index) = (0, 0);
+        let mut i = 1;
+        while i < portals.len() {
+            let (portal_left, portal_right) = portals[i];
+
+            // Tighten the right side of the funnel.
+            if cross(apex, right, portal_right) >= 0.0 {
+                if apex == right || cross(apex, left, portal_right) < 0.0 {
+                    right = portal_right;
+                    right_index = i;
+                } else {
+                    // The right side crossed the left one: the left corner is on the path.
+                    apex = left;
+                    let apex_index = left_index;
+                    path.push(apex);
+                    (left, right) = (apex, apex);
+                    (left_index, right_index) = (apex_index, apex_index);
+                    i = apex_index + 1;
+                    continue;
+                }
+            }
+
+            // Tighten the left side of the funnel.
+            if cross(apex, left, portal_left) <= 0.0 {
+                if apex == left || cross(apex, right, portal_left) > 0.0 {
+                    left = portal_left;
+                    left_index = i;
+                } else {
+                    // The left side crossed the right one: the right corner is on the path.
+                    apex = right;
+                    let apex_index = right_index;
+                    path.push(apex);
+                    (left, right) = (apex, apex);
+                    (left_index, right_index) = (apex_index, apex_index);
+                    i = apex_index + 1;
+                    continue;
+                }
+            }
+
+            i += 1;
+        }
+
+        if path.last() != Some(&end) {
+            path.push(end);
+        }
+        path
+    }
+}
+
+/// Returns twice the signed area of the triangle `a`, `b`, `c` projected on the XZ plane.
+///
+/// The area is positive if `c` is on the left of `a` to `b`, when looking from above with the
+/// X axis to the right and the Z axis upward.
+fn cross(a: Vec3, b: Vec3, c: Vec3) -> f32 {
+    (b.x - a.x) * (c.z - a.z) - (b.z - a.z) * (c.x - a.x)
+}
@@ -1,5 +1,5 @@
 use crate::{
-    CascadeShadowConfig, Cascades, DirectionalLight, Material, PointLight, SpotLight,
+    CascadeShadowConfig, Cascades, DirectionalLight, FogVolume, Material, PointLight, SpotLight,
     StandardMaterial,
 };
 use bevy_asset::Handle;
@@ -127,3 +127,17 @@ pub struct DirectionalLightBundle {
     /// Algorithmically-computed indication of whether an entity is visible and should be extracted for rendering
     pub view_visibility: ViewVisibility,
 }
+
+/// A component bundle for [`FogVolume`] entities.
+#[derive(Debug, Bundle, Clone, Default)]
+pub struct FogVolumeBundle {
+    pub fog_volume: FogVolume,
+    pub transform: Transform,
+    pub global_transform: GlobalTransform,
+    /// Enables or disables the fog volume
+    pub visibility: Visibility,
+    /// Inherited visibility of an entity.
+    pub inherited_visibility: InheritedVisibility,
+    /// Algorithmically-computed indication of whether an entity is visible and should be extracted for rendering
+    pub view_visibility: ViewVisibility,
+}
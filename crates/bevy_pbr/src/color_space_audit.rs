@@ -0,0 +1,90 @@
+use bevy_asset::{Asset, AssetEvent, AssetId, Assets, Handle};
+use bevy_ecs::prelude::*;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::texture::Image;
+use bevy_utils::{tracing::warn, HashSet};
+
+use crate::StandardMaterial;
+
+/// Controls the audit of the color spaces of the textures used by [`StandardMaterial`]s.
+///
+/// When enabled, a warning is logged for every normal, metallic/roughness, occlusion or depth
+/// map texture with an sRGB format. These textures hold linear data, so sampling them as sRGB
+/// gives incorrect lighting. The color space of loaded images is set with
+/// [`ImageLoaderSettings::color_space`](bevy_render::texture::ImageLoaderSettings::color_space).
+///
+/// Enabled by default in debug builds.
+#[derive(Resource, Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+#[reflect(Resource, Default)]
+pub struct TextureColorSpaceAudit {
+    pub enabled: bool,
+}
+
+impl Default for TextureColorSpaceAudit {
+    fn default() -> Self {
+        Self {
+            enabled: cfg!(debug_assertions),
+        }
+    }
+}
+
+fn is_change<A: Asset>(event: &AssetEvent<A>) -> bool {
+    matches!(
+        event,
+        AssetEvent::Added { .. }
+            | AssetEvent::Modified { .. }
+            | AssetEvent::LoadedWithDependencies { .. }
+    )
+}
+
+/// Warns about linear [`StandardMaterial`] textures with an sRGB format, see
+/// [`TextureColorSpaceAudit`].
+pub fn audit_texture_color_spaces(
+    audit: Res<TextureColorSpaceAudit>,
+    mut material_events: EventReader<AssetEvent<StandardMaterial>>,
+    mut image_events: EventReader<AssetEvent<Image>>,
+    materials: Res<Assets<StandardMaterial>>,
+    images: Res<Assets<Image>>,
+    mut reported: Local<HashSet<AssetId<Image>>>,
+) {
+    // Read every event so they aren't seen again in the next frame
+    let materials_changed = material_events.read().filter(|e| is_change(e)).count() > 0;
+    let images_changed = image_events.read().filter(|e| is_change(e)).count() > 0;
+    if !audit.enabled || !(materials_changed || images_changed) {
+        return;
+    }
+
+    for (_, material) in materials.iter() {
+        let linear_textures: [(&Option<Handle<Image>>, &str); 4] = [
+            (&material.normal_map_texture, "normal_map_texture"),
+            (
+                &material.metallic_roughness_texture,
+                "metallic_roughness_texture",
+            ),
+            (&material.occlusion_texture, "occlusion_texture"),
+            (&material.depth_map, "depth_map"),
+        ];
+        for (texture, field) in linear_textures {
+            let Some(texture) = texture else {
+                continue;
+            };
+            let Some(image) = images.get(texture) else {
+                continue;
+            };
+            if image.texture_descriptor.format.is_srgb() && reported.insert(texture.id()) {
+                match texture.path() {
+                    Some(path) => warn!(
+                        "The image \"{path}\" is used as the `{field}` of a `StandardMaterial` but \
+                        was loaded as sRGB, which will make the material look wrong. Set \
+                        `color_space: Linear` in the image's loader settings."
+                    ),
+                    None => warn!(
+                        "The image {:?} is used as the `{field}` of a `StandardMaterial` but has \
+                        an sRGB format, which will make the material look wrong.",
+                        texture.id()
+                    ),
+                }
+            }
+        }
+    }
+}
@@ -9,8 +9,11 @@
 }
 
 #ifdef SCREEN_SPACE_AMBIENT_OCCLUSION
-#import bevy_pbr::mesh_view_bindings::screen_space_ambient_occlusion_texture
-#import bevy_pbr::gtao_utils::gtao_multibounce
+#import bevy_pbr::mesh_view_bindings::{
+    screen_space_ambient_occlusion_texture,
+    screen_space_bent_normals_texture,
+}
+#import bevy_pbr::gtao_utils::{gtao_multibounce, specular_occlusion_from_bent_normal}
 #endif
 
 struct FullscreenVertexOutput {
@@ -36,8 +39,14 @@ var<uniform> depth_id: PbrDeferredLightingDepthId;
 fn vertex(@builtin(vertex_index) vertex_index: u32) -> FullscreenVertexOutput {
     // See the full screen vertex shader for explanation above for how this works.
     let uv = vec2<f32>(f32(vertex_index >> 1u), f32(vertex_index & 1u)) * 2.0;
+#ifdef DEFERRED_LIGHTING_PASS_ID
+    // Custom lighting passes shade the pixels of their own pass id.
+    let pass_id = #{DEFERRED_LIGHTING_PASS_ID}u;
+#else
+    let pass_id = depth_id.depth_id;
+#endif
     // Depth is stored as unorm, so we are dividing the u8 depth_id by 255.0 here.
-    let clip_position = vec4<f32>(uv * vec2<f32>(2.0, -2.0) + vec2<f32>(-1.0, 1.0), f32(depth_id.depth_id) / 255.0, 1.0);
+    let clip_position = vec4<f32>(uv * vec2<f32>(2.0, -2.0) + vec2<f32>(-1.0, 1.0), f32(pass_id) / 255.0, 1.0);
 
     return FullscreenVertexOutput(clip_position, uv);
 }
@@ -74,6 +83,11 @@ fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
         // Use SSAO to estimate the specular occlusion.
         // Lagarde and Rousiers 2014, "Moving Frostbite to Physically Based Rendering"
         pbr_input.specular_occlusion =  saturate(pow(NdotV + ssao, exp2(-16.0 * roughness - 1.0)) - 1.0 + ssao);
+        // Occlude it further by the cone of directions left visible around the bent normal.
+        let bent_normal = normalize(textureLoad(screen_space_bent_normals_texture, vec2<i32>(in.position.xy), 0i).xyz * 2.0 - 1.0);
+        let R = reflect(-pbr_input.V, pbr_input.N);
+        pbr_input.specular_occlusion = min(pbr_input.specular_occlusion, specular_occlusion_from_bent_normal(ssao, bent_normal, R, roughness));
+        pbr_input.bent_normal = bent_normal;
 #endif // SCREEN_SPACE_AMBIENT_OCCLUSION
 
         output_color = pbr_functions::apply_pbr_lighting(pbr_input);
@@ -26,9 +26,11 @@ use bevy_render::{
     view::{ExtractedView, ViewTarget, ViewUniformOffset},
     Render, RenderApp, RenderSet,
 };
+use bevy_utils::HashMap;
 
 use crate::{
-    MeshPipelineKey, ShadowFilteringMethod, ViewFogUniformOffset, ViewLightsUniformOffset,
+    CascadeDebugVisualization, MeshPipelineKey, ShadowFilteringMethod, ViewFogUniformOffset,
+    ViewLightsUniformOffset,
 };
 
 pub struct DeferredPbrLightingPlugin;
@@ -38,6 +40,88 @@ pub const DEFERRED_LIGHTING_SHADER_HANDLE: Handle<Shader> =
 
 pub const DEFAULT_PBR_DEFERRED_LIGHTING_PASS_ID: u8 = 1;
 
+/// The deferred lighting passes of custom lighting models, keyed by their pass id.
+///
+/// The deferred lighting pass of a view shades the pixels of the materials whose
+/// `deferred_lighting_pass_id` matches its [`PbrDeferredLightingDepthId`], with the PBR lighting
+/// model. Each pass registered here shades the pixels of the materials writing its pass id with
+/// its own shader instead, so that materials can use their own lighting models with the deferred
+/// renderer.
+///
+/// The shader of a pass only needs a `fragment` entry point, taking a
+/// `bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput`. It uses the same
+/// bindings and shader defs as the PBR deferred lighting shader: the mesh view bindings in group
+/// 0, from which it can read the gbuffer with
+/// `bevy_pbr::pbr_deferred_functions::pbr_input_from_deferred_gbuffer`. Its pass id is available
+/// in the `DEFERRED_LIGHTING_PASS_ID` shader def.
+///
+/// This resource lives in the render world: passes are usually registered with
+/// [`DeferredLightingApp::register_deferred_lighting_pass`].
+#[derive(Resource, Clone, Debug, Default)]
+pub struct DeferredLightingPasses {
+    shaders: HashMap<u8, Handle<Shader>>,
+}
+
+impl DeferredLightingPasses {
+    /// Shades the pixels of the materials writing `pass_id` with the `fragment` entry point of
+    /// `shader`, replacing the shader previously registered for `pass_id`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pass_id` is 0, which is the pass id of the pixels without any material.
+    pub fn register(&mut self, pass_id: u8, shader: Handle<Shader>) {
+        assert_ne!(
+            pass_id, 0,
+            "the deferred lighting pass id 0 is reserved for pixels without any material"
+        );
+        self.shaders.insert(pass_id, shader);
+    }
+
+    /// Removes the pass registered for `pass_id`, returning its shader.
+    pub fn remove(&mut self, pass_id: u8) -> Option<Handle<Shader>> {
+        self.shaders.remove(&pass_id)
+    }
+
+    /// Returns the shader registered for `pass_id`.
+    pub fn get(&self, pass_id: u8) -> Option<&Handle<Shader>> {
+        self.shaders.get(&pass_id)
+    }
+
+    /// Iterates over the registered pass ids and their shaders, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (u8, &Handle<Shader>)> {
+        self.shaders
+            .iter()
+            .map(|(pass_id, shader)| (*pass_id, shader))
+    }
+}
+
+/// Adds custom deferred lighting passes to an [`App`].
+pub trait DeferredLightingApp {
+    /// Shades the pixels of the materials writing `pass_id` with the `fragment` entry point of
+    /// `shader` in the deferred lighting pass.
+    ///
+    /// See [`DeferredLightingPasses`] for the requirements of the shader.
+    fn register_deferred_lighting_pass(&mut self, pass_id: u8, shader: Handle<Shader>)
+        -> &mut Self;
+}
+
+impl DeferredLightingApp for App {
+    fn register_deferred_lighting_pass(
+        &mut self,
+        pass_id: u8,
+        shader: Handle<Shader>,
+    ) -> &mut Self {
+        if let Ok(render_app) = self.get_sub_app_mut(RenderApp) {
+            render_app
+                .init_resource::<DeferredLightingPasses>()
+                .world
+                .resource_mut::<DeferredLightingPasses>()
+                .register(pass_id, shader);
+        }
+        self
+    }
+}
+
 /// Component with a `depth_id` for specifying which corresponding materials should be rendered by this specific PBR deferred lighting pass.
 /// Will be automatically added to entities with the [`DeferredPrepass`] component that don't already have a [`PbrDeferredLightingDepthId`].
 #[derive(Component, Clone, Copy, ExtractComponent, ShaderType)]
@@ -111,6 +195,7 @@ impl Plugin for DeferredPbrLightingPlugin {
 
         render_app
             .init_resource::<SpecializedRenderPipelines<DeferredLightingLayout>>()
+            .init_resource::<DeferredLightingPasses>()
             .add_systems(
                 Render,
                 (prepare_deferred_lighting_pipelines.in_set(RenderSet::Prepare),),
@@ -221,25 +306,53 @@ impl ViewNode for DeferredOpaquePass3dPbrLightingNode {
         render_pass.set_bind_group(1, &bind_group_1, &[]);
         render_pass.draw(0..3, 0..1);
 
+        // Each pass only shades the pixels of its pass id, thanks to the depth test.
+        for pipeline_id in &deferred_lighting_pipeline.custom_pipeline_ids {
+            let Some(pipeline) = pipeline_cache.get_render_pipeline(*pipeline_id) else {
+                continue;
+            };
+            render_pass.set_render_pipeline(pipeline);
+            render_pass.draw(0..3, 0..1);
+        }
+
         Ok(())
     }
 }
 
+/// The layout of the deferred lighting pipelines, specialized for the PBR lighting pass and the
+/// custom passes registered in [`DeferredLightingPasses`].
 #[derive(Resource)]
 pub struct DeferredLightingLayout {
     mesh_pipeline: MeshPipeline,
     bind_group_layout_1: BindGroupLayout,
 }
 
+/// The deferred lighting pipelines of a view.
 #[derive(Component)]
 pub struct DeferredLightingPipeline {
+    /// The PBR lighting pipeline.
     pub pipeline_id: CachedRenderPipelineId,
+    /// The pipelines of the passes registered in [`DeferredLightingPasses`].
+    pub custom_pipeline_ids: Vec<CachedRenderPipelineId>,
+}
+
+/// The key of a [`DeferredLightingLayout`] pipeline.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DeferredLightingPipelineKey {
+    /// The features of the view.
+    pub mesh_key: MeshPipelineKey,
+    /// The pass id and the shader of a custom pass, or `None` for the PBR lighting pass.
+    pub custom_pass: Option<(u8, Handle<Shader>)>,
 }
 
 impl SpecializedRenderPipeline for DeferredLightingLayout {
-    type Key = MeshPipelineKey;
+    type Key = DeferredLightingPipelineKey;
 
     fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let DeferredLightingPipelineKey {
+            mesh_key: key,
+            custom_pass,
+        } = key;
         let mut shader_defs = Vec::new();
 
         // Let the shader code know that it's running in a deferred pipeline.
@@ -304,6 +417,10 @@ impl SpecializedRenderPipeline for DeferredLightingLayout {
         // Always true, since we're in the deferred lighting pipeline
         shader_defs.push("DEFERRED_PREPASS".into());
 
+        if key.contains(MeshPipelineKey::DEBUG_SHADOW_CASCADES) {
+            shader_defs.push("DIRECTIONAL_LIGHT_SHADOW_MAP_DEBUG_CASCADES".into());
+        }
+
         let shadow_filter_method =
             key.intersection(MeshPipelineKey::SHADOW_FILTER_METHOD_RESERVED_BITS);
         if shadow_filter_method == MeshPipelineKey::SHADOW_FILTER_METHOD_HARDWARE_2X2 {
@@ -317,6 +434,19 @@ impl SpecializedRenderPipeline for DeferredLightingLayout {
         #[cfg(all(feature = "webgl", target_arch = "wasm32", not(feature = "webgpu")))]
         shader_defs.push("SIXTEEN_BYTE_ALIGNMENT".into());
 
+        // Custom passes run the fragment shader of their lighting model after the vertex shader
+        // of the PBR pass, which places the triangle at the depth of their pass id.
+        let fragment_shader = match custom_pass {
+            Some((pass_id, shader)) => {
+                shader_defs.push(ShaderDefVal::UInt(
+                    "DEFERRED_LIGHTING_PASS_ID".into(),
+                    pass_id as u32,
+                ));
+                shader
+            }
+            None => DEFERRED_LIGHTING_SHADER_HANDLE,
+        };
+
         RenderPipelineDescriptor {
             label: Some("deferred_lighting_pipeline".into()),
             layout: vec![
@@ -330,7 +460,7 @@ impl SpecializedRenderPipeline for DeferredLightingLayout {
                 buffers: Vec::new(),
             },
             fragment: Some(FragmentState {
-                shader: DEFERRED_LIGHTING_SHADER_HANDLE,
+                shader: fragment_shader,
                 shader_defs,
                 entry_point: "fragment".into(),
                 targets: vec![Some(ColorTargetState {
@@ -399,10 +529,13 @@ pub fn prepare_deferred_lighting_pipelines(
     pipeline_cache: Res<PipelineCache>,
     mut pipelines: ResMut<SpecializedRenderPipelines<DeferredLightingLayout>>,
     deferred_lighting_layout: Res<DeferredLightingLayout>,
+    deferred_lighting_passes: Res<DeferredLightingPasses>,
+    cascade_debug_visualization: Res<CascadeDebugVisualization>,
     views: Query<
         (
             Entity,
             &ExtractedView,
+            Option<&PbrDeferredLightingDepthId>,
             Option<&Tonemapping>,
             Option<&DebandDither>,
             Option<&ShadowFilteringMethod>,
@@ -421,6 +554,7 @@ pub fn prepare_deferred_lighting_pipelines(
     for (
         entity,
         view,
+        depth_id,
         tonemapping,
         dither,
         shadow_filter_method,
@@ -485,6 +619,10 @@ pub fn prepare_deferred_lighting_pipelines(
             view_key |= MeshPipelineKey::IRRADIANCE_VOLUME;
         }
 
+        if cascade_debug_visualization.enabled {
+            view_key |= MeshPipelineKey::DEBUG_SHADOW_CASCADES;
+        }
+
         match shadow_filter_method.unwrap_or(&ShadowFilteringMethod::default()) {
             ShadowFilteringMethod::Hardware2x2 => {
                 view_key |= MeshPipelineKey::SHADOW_FILTER_METHOD_HARDWARE_2X2;
@@ -497,11 +635,59 @@ pub fn prepare_deferred_lighting_pipelines(
             }
         }
 
-        let pipeline_id =
-            pipelines.specialize(&pipeline_cache, &deferred_lighting_layout, view_key);
+        let pipeline_id = pipelines.specialize(
+            &pipeline_cache,
+            &deferred_lighting_layout,
+            DeferredLightingPipelineKey {
+                mesh_key: view_key,
+                custom_pass: None,
+            },
+        );
 
-        commands
-            .entity(entity)
-            .insert(DeferredLightingPipeline { pipeline_id });
+        // The PBR pass of the view takes precedence over a custom pass with the same id.
+        let pbr_pass_id = depth_id.copied().unwrap_or_default().get();
+        let custom_pipeline_ids = deferred_lighting_passes
+            .iter()
+            .filter(|(pass_id, _)| *pass_id != pbr_pass_id)
+            .map(|(pass_id, shader)| {
+                pipelines.specialize(
+                    &pipeline_cache,
+                    &deferred_lighting_layout,
+                    DeferredLightingPipelineKey {
+                        mesh_key: view_key,
+                        custom_pass: Some((pass_id, shader.clone())),
+                    },
+                )
+            })
+            .collect();
+
+        commands.entity(entity).insert(DeferredLightingPipeline {
+            pipeline_id,
+            custom_pipeline_ids,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registering_a_pass_replaces_its_shader() {
+        let first = Handle::weak_from_u128(1);
+        let second = Handle::weak_from_u128(2);
+        let mut passes = DeferredLightingPasses::default();
+        passes.register(2, first);
+        passes.register(2, second.clone());
+        assert_eq!(passes.get(2), Some(&second));
+        assert_eq!(passes.iter().count(), 1);
+        assert_eq!(passes.remove(2), Some(second));
+        assert_eq!(passes.get(2), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn pass_id_zero_is_reserved() {
+        DeferredLightingPasses::default().register(0, Handle::weak_from_u128(1));
     }
 }
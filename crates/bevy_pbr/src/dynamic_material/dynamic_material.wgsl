@@ -0,0 +1,19 @@
+#define_import_path bevy_pbr::dynamic_material
+
+// The uniforms of the material, in the order they were set. Scalars are stored in `x`, and
+// colors in linear RGBA.
+struct DynamicMaterialUniforms {
+    values: array<vec4<f32>, 16>,
+}
+
+@group(2) @binding(0) var<uniform> uniforms: DynamicMaterialUniforms;
+
+// The textures of the material, in the order they were set. Unused slots hold a white texture.
+@group(2) @binding(1) var texture_0: texture_2d<f32>;
+@group(2) @binding(2) var sampler_0: sampler;
+@group(2) @binding(3) var texture_1: texture_2d<f32>;
+@group(2) @binding(4) var sampler_1: sampler;
+@group(2) @binding(5) var texture_2: texture_2d<f32>;
+@group(2) @binding(6) var sampler_2: sampler;
+@group(2) @binding(7) var texture_3: texture_2d<f32>;
+@group(2) @binding(8) var sampler_3: sampler;
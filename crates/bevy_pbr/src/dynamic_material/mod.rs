@@ -0,0 +1,660 @@
+//! Materials defined by data rather than by a Rust type, loaded from `.material` files.
+//!
+//! A `.material` file names a fragment shader, and optionally a vertex shader, along with the
+//! textures and uniform values passed to them. Uniform values are read through reflection, as a
+//! map from the type path of the value to the value itself:
+//!
+//! ```ron
+//! (
+//!     shader: "shaders/lava.wgsl",
+//!     vertex_shader: None,
+//!     alpha_mode: Opaque,
+//!     textures: {
+//!         "noise": "textures/noise.png",
+//!     },
+//!     uniforms: {
+//!         "tint": {"bevy_render::color::Color": Rgba(red: 1.0, green: 0.3, blue: 0.0, alpha: 1.0)},
+//!         "speed": {"f32": 0.5},
+//!     },
+//! )
+//! ```
+//!
+//! Paths are relative to the asset folder. The shaders read these values through the bindings of
+//! the `bevy_pbr::dynamic_material` shader import, in the order they appear in the file: here
+//! `uniforms.values[0]` is the tint, `uniforms.values[1].x` is the speed, and `texture_0` with
+//! `sampler_0` is the noise texture.
+
+use std::{any::TypeId, fmt::Formatter, marker::PhantomData};
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{
+    io::Reader, load_internal_asset, ron, Asset, AssetApp, AssetLoader, AsyncReadExt, Handle,
+    LoadContext,
+};
+use bevy_ecs::{
+    reflect::AppTypeRegistry,
+    world::{FromWorld, World},
+};
+use bevy_math::{Vec2, Vec3, Vec4};
+use bevy_reflect::{
+    serde::{TypedReflectDeserializer, UntypedReflectDeserializer},
+    FromReflect, Reflect, TypePath, TypeRegistry, TypeRegistryArc,
+};
+use bevy_render::{
+    color::Color,
+    mesh::MeshVertexBufferLayout,
+    render_asset::RenderAssets,
+    render_resource::{
+        binding_types::{sampler, texture_2d, uniform_buffer},
+        encase::UniformBuffer,
+        AsBindGroup, AsBindGroupError, BindGroupLayout, BindGroupLayoutEntry, BufferInitDescriptor,
+        BufferUsages, OwnedBindingResource, RenderPipelineDescriptor, SamplerBindingType, Shader,
+        ShaderStages, ShaderType, SpecializedMeshPipelineError, TextureSampleType,
+        UnpreparedBindGroup,
+    },
+    renderer::RenderDevice,
+    texture::{FallbackImage, Image},
+};
+use bevy_utils::BoxedFuture;
+use serde::{
+    de::{DeserializeSeed, Error, MapAccess, Visitor},
+    Deserialize, Deserializer,
+};
+
+use crate::{AlphaMode, Material, MaterialPipeline, MaterialPipelineKey, MaterialPlugin};
+
+const DYNAMIC_MATERIAL_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(6393561822419427078);
+
+/// Adds support for [`DynamicMaterial`]s, and for loading them from `.material` files.
+pub struct DynamicMaterialPlugin;
+
+impl Plugin for DynamicMaterialPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            DYNAMIC_MATERIAL_SHADER_HANDLE,
+            "dynamic_material.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.add_plugins(MaterialPlugin::<DynamicMaterial> {
+            // The prepass shaders can't read the bindings of an arbitrary shader
+            prepass_enabled: false,
+            ..Default::default()
+        })
+        .init_asset_loader::<DynamicMaterialLoader>();
+    }
+}
+
+/// A [`Material`] whose shaders, textures and uniform values are chosen at runtime, usually
+/// loaded from a `.material` file by the [`DynamicMaterialLoader`].
+///
+/// This lets artists define new materials without recompiling, at the cost of a fixed layout:
+/// at most [`MAX_UNIFORMS`](Self::MAX_UNIFORMS) uniforms, each stored as a `vec4<f32>`, and
+/// [`MAX_TEXTURES`](Self::MAX_TEXTURES) 2D textures. See the
+/// [module documentation](crate::dynamic_material) for how the shaders access them.
+///
+/// Uniforms can be changed while the game runs with [`set_uniform`](Self::set_uniform), for
+/// example to animate them.
+///
+/// Meshes with this material cast shadows of their whole geometry: the shadow pass doesn't run
+/// the fragment shader, so fragments discarded by it still cast shadows.
+#[derive(Asset, TypePath, Clone, Debug)]
+pub struct DynamicMaterial {
+    /// The fragment shader of the material.
+    #[dependency]
+    pub fragment_shader: Handle<Shader>,
+    /// The vertex shader of the material. The default mesh vertex shader is used when this is
+    /// `None`.
+    #[dependency]
+    pub vertex_shader: Option<Handle<Shader>>,
+    /// How the material blends with what is behind it.
+    pub alpha_mode: AlphaMode,
+    uniform_names: Vec<String>,
+    uniforms: Vec<Vec4>,
+    texture_names: Vec<String>,
+    #[dependency]
+    textures: Vec<Handle<Image>>,
+}
+
+/// An error returned when setting a value of a [`DynamicMaterial`].
+#[derive(Debug, thiserror::Error)]
+pub enum DynamicMaterialError {
+    /// The value of a uniform is not a `f32`, [`Vec2`], [`Vec3`], [`Vec4`] or [`Color`].
+    #[error("Uniform `{name}` has unsupported type `{type_path}`")]
+    UnsupportedUniform { name: String, type_path: String },
+    /// The material already has [`DynamicMaterial::MAX_UNIFORMS`] uniforms.
+    #[error("Cannot add uniform `{0}`: a material has at most {max} uniforms", max = DynamicMaterial::MAX_UNIFORMS)]
+    TooManyUniforms(String),
+    /// The material already has [`DynamicMaterial::MAX_TEXTURES`] textures.
+    #[error("Cannot add texture `{0}`: a material has at most {max} textures", max = DynamicMaterial::MAX_TEXTURES)]
+    TooManyTextures(String),
+}
+
+impl DynamicMaterial {
+    /// The number of uniforms a material can hold.
+    pub const MAX_UNIFORMS: usize = 16;
+    /// The number of textures a material can hold.
+    pub const MAX_TEXTURES: usize = 4;
+
+    /// Creates an opaque material with the given fragment shader, and no textures or uniforms.
+    pub fn new(fragment_shader: Handle<Shader>) -> Self {
+        Self {
+            fragment_shader,
+            vertex_shader: None,
+            alpha_mode: AlphaMode::Opaque,
+            uniform_names: Vec::new(),
+            uniforms: Vec::new(),
+            texture_names: Vec::new(),
+            textures: Vec::new(),
+        }
+    }
+
+    /// Returns the value of the uniform `name`, as passed to the shaders.
+    pub fn uniform(&self, name: &str) -> Option<Vec4> {
+        let index = self.uniform_names.iter().position(|n| n == name)?;
+        Some(self.uniforms[index])
+    }
+
+    /// Sets the uniform `name` to `value`, adding it after the existing uniforms if the material
+    /// doesn't have it yet.
+    ///
+    /// The value must be a `f32`, [`Vec2`], [`Vec3`], [`Vec4`] or [`Color`], or a dynamic
+    /// representation of one. Vectors are padded with zeros, and colors are converted to linear
+    /// RGBA.
+    pub fn set_uniform(
+        &mut self,
+        name: impl Into<String>,
+        value: &dyn Reflect,
+    ) -> Result<(), DynamicMaterialError> {
+        let name = name.into();
+        let Some(uniform) = uniform_value(value) else {
+            return Err(DynamicMaterialError::UnsupportedUniform {
+                name,
+                type_path: value.get_represented_type_info().map_or_else(
+                    || value.reflect_type_path().to_owned(),
+                    |info| info.type_path().to_owned(),
+                ),
+            });
+        };
+        match self.uniform_names.iter().position(|n| *n == name) {
+            Some(index) => self.uniforms[index] = uniform,
+            None if self.uniforms.len() == Self::MAX_UNIFORMS => {
+                return Err(DynamicMaterialError::TooManyUniforms(name));
+            }
+            None => {
+                self.uniform_names.push(name);
+                self.uniforms.push(uniform);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the texture `name`.
+    pub fn texture(&self, name: &str) -> Option<&Handle<Image>> {
+        let index = self.texture_names.iter().position(|n| n == name)?;
+        Some(&self.textures[index])
+    }
+
+    /// Sets the texture `name`, adding it after the existing textures if the material doesn't
+    /// have it yet.
+    pub fn set_texture(
+        &mut self,
+        name: impl Into<String>,
+        texture: Handle<Image>,
+    ) -> Result<(), DynamicMaterialError> {
+        let name = name.into();
+        match self.texture_names.iter().position(|n| *n == name) {
+            Some(index) => self.textures[index] = texture,
+            None if self.textures.len() == Self::MAX_TEXTURES => {
+                return Err(DynamicMaterialError::TooManyTextures(name));
+            }
+            None => {
+                self.texture_names.push(name);
+                self.textures.push(texture);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Converts the value of a uniform to the `vec4<f32>` it is stored as.
+fn uniform_value(value: &dyn Reflect) -> Option<Vec4> {
+    let type_id = value.get_represented_type_info()?.type_id();
+    if type_id == TypeId::of::<f32>() {
+        f32::from_reflect(value).map(|x| Vec4::new(x, 0., 0., 0.))
+    } else if type_id == TypeId::of::<Vec2>() {
+        Vec2::from_reflect(value).map(|v| v.extend(0.).extend(0.))
+    } else if type_id == TypeId::of::<Vec3>() {
+        Vec3::from_reflect(value).map(|v| v.extend(0.))
+    } else if type_id == TypeId::of::<Vec4>() {
+        Vec4::from_reflect(value)
+    } else if type_id == TypeId::of::<Color>() {
+        Color::from_reflect(value).map(|color| Vec4::from(color.as_linear_rgba_f32()))
+    } else {
+        None
+    }
+}
+
+#[derive(ShaderType)]
+struct DynamicMaterialUniforms {
+    values: [Vec4; DynamicMaterial::MAX_UNIFORMS],
+}
+
+/// The shaders of a [`DynamicMaterial`], which its pipelines are specialized on.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DynamicMaterialKey {
+    fragment_shader: Handle<Shader>,
+    vertex_shader: Option<Handle<Shader>>,
+}
+
+impl AsBindGroup for DynamicMaterial {
+    type Data = DynamicMaterialKey;
+
+    fn unprepared_bind_group(
+        &self,
+        _layout: &BindGroupLayout,
+        render_device: &RenderDevice,
+        images: &RenderAssets<Image>,
+        fallback_image: &FallbackImage,
+    ) -> Result<UnpreparedBindGroup<Self::Data>, AsBindGroupError> {
+        let mut values = [Vec4::ZERO; Self::MAX_UNIFORMS];
+        values[..self.uniforms.len()].copy_from_slice(&self.uniforms);
+        let mut buffer = UniformBuffer::new(Vec::new());
+        buffer.write(&DynamicMaterialUniforms { values }).unwrap();
+        let mut bindings = vec![(
+            0,
+            OwnedBindingResource::Buffer(render_device.create_buffer_with_data(
+                &BufferInitDescriptor {
+                    label: Some("dynamic_material_uniforms"),
+                    usage: BufferUsages::COPY_DST | BufferUsages::UNIFORM,
+                    contents: buffer.as_ref(),
+                },
+            )),
+        )];
+
+        for slot in 0..Self::MAX_TEXTURES {
+            let image = match self.textures.get(slot) {
+                Some(texture) => images
+                    .get(texture)
+                    .ok_or(AsBindGroupError::RetryNextUpdate)?,
+                None => &fallback_image.d2,
+            };
+            let binding = 1 + 2 * slot as u32;
+            bindings.push((
+                binding,
+                OwnedBindingResource::TextureView(image.texture_view.clone()),
+            ));
+            bindings.push((
+                binding + 1,
+                OwnedBindingResource::Sampler(image.sampler.clone()),
+            ));
+        }
+
+        Ok(UnpreparedBindGroup {
+            bindings,
+            data: DynamicMaterialKey {
+                fragment_shader: self.fragment_shader.clone(),
+                vertex_shader: self.vertex_shader.clone(),
+            },
+        })
+    }
+
+    fn bind_group_layout_entries(_render_device: &RenderDevice) -> Vec<BindGroupLayoutEntry>
+    where
+        Self: Sized,
+    {
+        let mut entries = vec![uniform_buffer::<DynamicMaterialUniforms>(false)
+            .build(0, ShaderStages::VERTEX_FRAGMENT)];
+        for slot in 0..Self::MAX_TEXTURES as u32 {
+            entries.push(
+                texture_2d(TextureSampleType::Float { filterable: true })
+                    .build(1 + 2 * slot, ShaderStages::VERTEX_FRAGMENT),
+            );
+            entries.push(
+                sampler(SamplerBindingType::Filtering)
+                    .build(2 + 2 * slot, ShaderStages::VERTEX_FRAGMENT),
+            );
+        }
+        entries
+    }
+}
+
+impl Material for DynamicMaterial {
+    fn alpha_mode(&self) -> AlphaMode {
+        self.alpha_mode
+    }
+
+    fn specialize(
+        _pipeline: &MaterialPipeline<Self>,
+        descriptor: &mut RenderPipelineDescriptor,
+        _layout: &MeshVertexBufferLayout,
+        key: MaterialPipelineKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        // The prepass pipeline is only used for shadows, which are cast by the whole mesh
+        if descriptor.label.as_deref() == Some("prepass_pipeline") {
+            descriptor.fragment = None;
+            return Ok(());
+        }
+        if let Some(vertex_shader) = key.bind_group_data.vertex_shader {
+            descriptor.vertex.shader = vertex_shader;
+        }
+        if let Some(fragment) = descriptor.fragment.as_mut() {
+            fragment.shader = key.bind_group_data.fragment_shader;
+        }
+        Ok(())
+    }
+}
+
+/// [`AssetLoader`] for `.material` files, loading them as [`DynamicMaterial`]s.
+///
+/// The shaders and textures of the material are loaded as dependencies of it.
+#[derive(Debug)]
+pub struct DynamicMaterialLoader {
+    type_registry: TypeRegistryArc,
+}
+
+impl FromWorld for DynamicMaterialLoader {
+    fn from_world(world: &mut World) -> Self {
+        DynamicMaterialLoader {
+            type_registry: world.resource::<AppTypeRegistry>().0.clone(),
+        }
+    }
+}
+
+/// Possible errors that can be produced by [`DynamicMaterialLoader`]
+#[non_exhaustive]
+#[derive(Debug, thiserror::Error)]
+pub enum DynamicMaterialLoaderError {
+    /// An [IO Error](std::io::Error)
+    #[error("Error while trying to read the material file: {0}")]
+    Io(#[from] std::io::Error),
+    /// A [RON Error](ron::error::SpannedError)
+    #[error("Could not parse RON: {0}")]
+    RonSpannedError(#[from] ron::error::SpannedError),
+    /// A value of the file that the material can't hold.
+    #[error(transparent)]
+    Material(#[from] DynamicMaterialError),
+}
+
+impl AssetLoader for DynamicMaterialLoader {
+    type Asset = DynamicMaterial;
+    type Settings = ();
+    type Error = DynamicMaterialLoaderError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            let mut deserializer = ron::de::Deserializer::from_bytes(&bytes)?;
+            let file = DynamicMaterialFileDeserializer {
+                registry: &self.type_registry.read(),
+            }
+            .deserialize(&mut deserializer)
+            .map_err(|e| deserializer.span_error(e))?;
+
+            let mut material = DynamicMaterial::new(load_context.load(file.shader));
+            material.vertex_shader = file.vertex_shader.map(|path| load_context.load(path));
+            material.alpha_mode = file.alpha_mode;
+            for (name, path) in file.textures {
+                material.set_texture(name, load_context.load(path))?;
+            }
+            for (name, value) in file.uniforms {
+                material.set_uniform(name, &*value)?;
+            }
+            Ok(material)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["material", "material.ron"]
+    }
+}
+
+/// The contents of a `.material` file, before its assets are loaded.
+struct DynamicMaterialFile {
+    shader: String,
+    vertex_shader: Option<String>,
+    alpha_mode: AlphaMode,
+    textures: Vec<(String, String)>,
+    uniforms: Vec<(String, Box<dyn Reflect>)>,
+}
+
+const MATERIAL_STRUCT: &str = "DynamicMaterial";
+const MATERIAL_FIELDS: &[&str] = &[
+    "shader",
+    "vertex_shader",
+    "alpha_mode",
+    "textures",
+    "uniforms",
+];
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "snake_case")]
+enum MaterialField {
+    Shader,
+    VertexShader,
+    AlphaMode,
+    Textures,
+    Uniforms,
+}
+
+struct DynamicMaterialFileDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for DynamicMaterialFileDeserializer<'a> {
+    type Value = DynamicMaterialFile;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(MATERIAL_STRUCT, MATERIAL_FIELDS, self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for DynamicMaterialFileDeserializer<'a> {
+    type Value = DynamicMaterialFile;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("material struct")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut shader = None;
+        let mut vertex_shader = None;
+        let mut alpha_mode = None;
+        let mut textures = None;
+        let mut uniforms = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                MaterialField::Shader => {
+                    if shader.is_some() {
+                        return Err(Error::duplicate_field("shader"));
+                    }
+                    shader = Some(map.next_value()?);
+                }
+                MaterialField::VertexShader => {
+                    if vertex_shader.is_some() {
+                        return Err(Error::duplicate_field("vertex_shader"));
+                    }
+                    vertex_shader = Some(map.next_value()?);
+                }
+                MaterialField::AlphaMode => {
+                    if alpha_mode.is_some() {
+                        return Err(Error::duplicate_field("alpha_mode"));
+                    }
+                    let registration = self
+                        .registry
+                        .get(TypeId::of::<AlphaMode>())
+                        .ok_or_else(|| A::Error::custom("`AlphaMode` is not registered"))?;
+                    let value = map.next_value_seed(TypedReflectDeserializer::new(
+                        registration,
+                        self.registry,
+                    ))?;
+                    alpha_mode = Some(
+                        AlphaMode::from_reflect(&*value)
+                            .ok_or_else(|| A::Error::custom("invalid `AlphaMode` value"))?,
+                    );
+                }
+                MaterialField::Textures => {
+                    if textures.is_some() {
+                        return Err(Error::duplicate_field("textures"));
+                    }
+                    textures = Some(
+                        map.next_value_seed(EntriesDeserializer(PhantomData::<String>::default))?,
+                    );
+                }
+                MaterialField::Uniforms => {
+                    if uniforms.is_some() {
+                        return Err(Error::duplicate_field("uniforms"));
+                    }
+                    uniforms = Some(map.next_value_seed(EntriesDeserializer(|| {
+                        UntypedReflectDeserializer::new(self.registry)
+                    }))?);
+                }
+            }
+        }
+
+        Ok(DynamicMaterialFile {
+            shader: shader.ok_or_else(|| A::Error::missing_field("shader"))?,
+            vertex_shader: vertex_shader.flatten(),
+            alpha_mode: alpha_mode.unwrap_or_default(),
+            textures: textures.unwrap_or_default(),
+            uniforms: uniforms.unwrap_or_default(),
+        })
+    }
+}
+
+/// Deserializes a map from names to values, keeping the order of the entries, since it decides
+/// where the shaders find each value. Each value is deserialized with a seed made by `F`.
+struct EntriesDeserializer<F>(F);
+
+impl<'de, S: DeserializeSeed<'de>, F: Fn() -> S> DeserializeSeed<'de> for EntriesDeserializer<F> {
+    type Value = Vec<(String, S::Value)>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, S: DeserializeSeed<'de>, F: Fn() -> S> Visitor<'de> for EntriesDeserializer<F> {
+    type Value = Vec<(String, S::Value)>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("map of names to values")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut entries: Self::Value = Vec::new();
+        while let Some(name) = map.next_key::<String>()? {
+            if entries.iter().any(|(existing, _)| *existing == name) {
+                return Err(Error::custom(format_args!("duplicate entry: `{name}`")));
+            }
+            let value = map.next_value_seed((self.0)())?;
+            entries.push((name, value));
+        }
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_asset::ron;
+    use bevy_math::{Vec3, Vec4};
+    use bevy_reflect::TypeRegistry;
+    use bevy_render::color::Color;
+    use serde::de::DeserializeSeed;
+
+    use super::{DynamicMaterial, DynamicMaterialError, DynamicMaterialFileDeserializer};
+    use crate::AlphaMode;
+
+    #[test]
+    fn deserialize_material_file() {
+        let mut registry = TypeRegistry::default();
+        registry.register::<f32>();
+        registry.register::<Vec3>();
+        registry.register::<Color>();
+        registry.register::<AlphaMode>();
+
+        let input = r#"(
+            shader: "shaders/lava.wgsl",
+            alpha_mode: Mask(0.5),
+            textures: {
+                "noise": "textures/noise.png",
+                "gradient": "textures/gradient.png",
+            },
+            uniforms: {
+                "speed": {"f32": 0.5},
+                "direction": {"glam::Vec3": (x: 1.0, y: 2.0, z: 3.0)},
+                "tint": {"bevy_render::color::Color": Rgba(red: 1.0, green: 0.0, blue: 0.0, alpha: 1.0)},
+            },
+        )"#;
+        let mut deserializer = ron::de::Deserializer::from_str(input).unwrap();
+        let file = DynamicMaterialFileDeserializer {
+            registry: &registry,
+        }
+        .deserialize(&mut deserializer)
+        .unwrap();
+
+        assert_eq!(file.shader, "shaders/lava.wgsl");
+        assert_eq!(file.vertex_shader, None);
+        assert_eq!(file.alpha_mode, AlphaMode::Mask(0.5));
+        let textures: Vec<_> = file
+            .textures
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect();
+        assert_eq!(textures, ["noise", "gradient"]);
+
+        let mut material = DynamicMaterial::new(Default::default());
+        for (name, value) in &file.uniforms {
+            material.set_uniform(name.clone(), &**value).unwrap();
+        }
+        assert_eq!(
+            material.uniforms,
+            [
+                Vec4::new(0.5, 0., 0., 0.),
+                Vec4::new(1., 2., 3., 0.),
+                Vec4::new(1., 0., 0., 1.),
+            ]
+        );
+    }
+
+    #[test]
+    fn set_uniform() {
+        let mut material = DynamicMaterial::new(Default::default());
+        material.set_uniform("speed", &1.0_f32).unwrap();
+        material.set_uniform("speed", &2.0_f32).unwrap();
+        assert_eq!(material.uniform("speed"), Some(Vec4::new(2., 0., 0., 0.)));
+        assert!(matches!(
+            material.set_uniform("count", &1_u32),
+            Err(DynamicMaterialError::UnsupportedUniform { .. })
+        ));
+
+        for i in 1..DynamicMaterial::MAX_UNIFORMS {
+            material
+                .set_uniform(format!("value_{i}"), &0.0_f32)
+                .unwrap();
+        }
+        assert!(matches!(
+            material.set_uniform("one_too_many", &0.0_f32),
+            Err(DynamicMaterialError::TooManyUniforms(_))
+        ));
+    }
+}
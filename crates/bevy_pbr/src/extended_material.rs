@@ -4,12 +4,14 @@ use bevy_render::{
     mesh::MeshVertexBufferLayout,
     render_asset::RenderAssets,
     render_resource::{
-        AsBindGroup, AsBindGroupError, BindGroupLayout, RenderPipelineDescriptor, Shader,
-        ShaderRef, SpecializedMeshPipelineError, UnpreparedBindGroup,
+        AsBindGroup, AsBindGroupError, BindGroupLayout, BindGroupLayoutEntry,
+        RenderPipelineDescriptor, Shader, ShaderRef, SpecializedMeshPipelineError,
+        UnpreparedBindGroup,
     },
     renderer::RenderDevice,
     texture::{FallbackImage, Image},
 };
+use bevy_utils::HashSet;
 
 use crate::{Material, MaterialPipeline, MaterialPipelineKey, MeshPipeline, MeshPipelineKey};
 
@@ -97,6 +99,25 @@ pub trait MaterialExtension: Asset + AsBindGroup + Clone + Sized {
 /// When used with `StandardMaterial` as the base, all the standard material fields are
 /// present, so the `pbr_fragment` shader functions can be called from the extension shader (see
 /// the `extended_material` example).
+///
+/// # Stacking extensions
+///
+/// An `ExtendedMaterial` is itself a [`Material`], so several extensions can be layered on top
+/// of the same base with [`ExtendedMaterial::extend`]:
+///
+/// ```ignore
+/// type SnowyDissolveMaterial =
+///     ExtendedMaterial<ExtendedMaterial<StandardMaterial, SnowLayer>, Dissolve>;
+///
+/// let material: SnowyDissolveMaterial =
+///     ExtendedMaterial::new(StandardMaterial::default(), SnowLayer::default())
+///         .extend(Dissolve::default());
+/// ```
+///
+/// The bindings of all the layers are merged into the same bind group, so each extension must
+/// use its own binding indices. The `specialize` functions of all the layers are called, from
+/// the base to the outermost extension, so the shader defs they add are merged. Each shader is
+/// taken from the outermost layer that doesn't return [`ShaderRef::Default`] for it.
 #[derive(Asset, Clone, Reflect)]
 #[reflect(type_path = false)]
 pub struct ExtendedMaterial<B: Material, E: MaterialExtension> {
@@ -108,6 +129,21 @@ pub struct ExtendedMaterial<B: Material, E: MaterialExtension> {
 // causes the `TypePath` derive to not generate an implementation.
 impl_type_path!((in bevy_pbr::extended_material) ExtendedMaterial<B: Material, E: MaterialExtension>);
 
+impl<B: Material, E: MaterialExtension> ExtendedMaterial<B, E> {
+    /// Extends `base` with `extension`.
+    pub fn new(base: B, extension: E) -> Self {
+        Self { base, extension }
+    }
+
+    /// Adds another `extension` on top of this material.
+    ///
+    /// The new extension's specialization runs after the current ones, and its shaders
+    /// replace the current ones unless they are [`ShaderRef::Default`].
+    pub fn extend<E2: MaterialExtension>(self, extension: E2) -> ExtendedMaterial<Self, E2> {
+        ExtendedMaterial::new(self, extension)
+    }
+}
+
 impl<B: Material, E: MaterialExtension> AsBindGroup for ExtendedMaterial<B, E> {
     type Data = (<B as AsBindGroup>::Data, <E as AsBindGroup>::Data);
 
@@ -139,19 +175,34 @@ impl<B: Material, E: MaterialExtension> AsBindGroup for ExtendedMaterial<B, E> {
         })
     }
 
-    fn bind_group_layout_entries(
-        render_device: &RenderDevice,
-    ) -> Vec<bevy_render::render_resource::BindGroupLayoutEntry>
+    fn bind_group_layout_entries(render_device: &RenderDevice) -> Vec<BindGroupLayoutEntry>
     where
         Self: Sized,
     {
         // add together the bindings of the standard material and the user material
         let mut entries = B::bind_group_layout_entries(render_device);
         entries.extend(E::bind_group_layout_entries(render_device));
+        if let Some(binding) = duplicate_binding(&entries) {
+            panic!(
+                "The binding {binding} of the material extension {} is already used by its base \
+                material {}. Each extension of a material must use its own binding indices.",
+                std::any::type_name::<E>(),
+                std::any::type_name::<B>(),
+            );
+        }
         entries
     }
 }
 
+/// Returns the first binding index used by several of the `entries`.
+fn duplicate_binding(entries: &[BindGroupLayoutEntry]) -> Option<u32> {
+    let mut bindings = HashSet::new();
+    entries
+        .iter()
+        .map(|entry| entry.binding)
+        .find(|binding| !bindings.insert(*binding))
+}
+
 impl<B: Material, E: MaterialExtension> Material for ExtendedMaterial<B, E> {
     fn vertex_shader() -> ShaderRef {
         match E::vertex_shader() {
@@ -263,3 +314,36 @@ impl<B: Material, E: MaterialExtension> Material for ExtendedMaterial<B, E> {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_render::render_resource::{
+        binding_types::{sampler, texture_2d, uniform_buffer_sized},
+        BindGroupLayoutEntryBuilder, SamplerBindingType, ShaderStages, TextureSampleType,
+    };
+
+    fn entries(bindings: &[(u32, BindGroupLayoutEntryBuilder)]) -> Vec<BindGroupLayoutEntry> {
+        bindings
+            .iter()
+            .map(|(binding, builder)| builder.build(*binding, ShaderStages::FRAGMENT))
+            .collect()
+    }
+
+    #[test]
+    fn stacked_extensions_need_distinct_bindings() {
+        let texture = texture_2d(TextureSampleType::Float { filterable: true });
+        let snow = [
+            (100, uniform_buffer_sized(false, None)),
+            (101, texture),
+            (102, sampler(SamplerBindingType::Filtering)),
+        ];
+        let dissolve = [(110, uniform_buffer_sized(false, None)), (111, texture)];
+        let mut stacked = entries(&snow);
+        stacked.extend(entries(&dissolve));
+        assert_eq!(duplicate_binding(&stacked), None);
+
+        stacked.extend(entries(&[(101, texture)]));
+        assert_eq!(duplicate_binding(&stacked), Some(101));
+    }
+}
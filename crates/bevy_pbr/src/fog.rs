@@ -475,3 +475,82 @@ impl Default for FogSettings {
         }
     }
 }
+
+/// The maximum number of visible [`FogVolume`]s per view. Past this limit, the volumes farthest
+/// from the view are ignored.
+pub const MAX_FOG_VOLUMES: usize = 16;
+
+/// A region of local fog, like mist over a pond or haze inside a building, that is added on top
+/// of the [`FogSettings`] of the camera.
+///
+/// The fog covers the [`FogVolumeShape`] of unit size, scaled, rotated and placed by the
+/// [`GlobalTransform`](bevy_transform::components::GlobalTransform) of the entity. Each fragment
+/// is blended with the fog color according to the distance the view ray travels inside the
+/// volume before reaching it, so the fog thickens towards the far side of the volume and
+/// fragments behind it are fogged too.
+///
+/// Fog volumes are assigned to the clusters of the views, along with point and spot lights, so
+/// fragments only evaluate the volumes in front of them. On platforms without storage buffers
+/// every fragment evaluates all the visible volumes.
+///
+/// Like [`FogSettings`], fog volumes can be disabled for individual
+/// [`StandardMaterial`](crate::StandardMaterial) instances via the `fog_enabled` flag.
+///
+/// ## Example
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_render::prelude::*;
+/// # use bevy_math::prelude::*;
+/// # use bevy_transform::prelude::*;
+/// # use bevy_pbr::prelude::*;
+/// # fn system(mut commands: Commands) {
+/// commands.spawn(FogVolumeBundle {
+///     fog_volume: FogVolume {
+///         shape: FogVolumeShape::Box,
+///         color: Color::rgb(0.6, 0.65, 0.7),
+///         density: 0.5,
+///     },
+///     transform: Transform::from_xyz(0.0, 1.0, 0.0).with_scale(Vec3::new(10.0, 2.0, 10.0)),
+///     ..Default::default()
+/// });
+/// # }
+/// # bevy_ecs::system::assert_is_system(system);
+/// ```
+#[derive(Debug, Clone, Component, Reflect)]
+#[reflect(Component, Default)]
+pub struct FogVolume {
+    /// The shape of the volume, before its transform is applied.
+    pub shape: FogVolumeShape,
+
+    /// The color of the fog inside the volume.
+    ///
+    /// The alpha channel scales the amount of fog without changing its density.
+    pub color: Color,
+
+    /// How quickly the fog builds up along the view ray inside the volume, per world unit.
+    ///
+    /// After traveling a distance `d` through the volume, the fog covers `1 - exp(-density * d)`
+    /// of the color of the fragment.
+    pub density: f32,
+}
+
+impl Default for FogVolume {
+    fn default() -> Self {
+        Self {
+            shape: FogVolumeShape::Box,
+            color: Color::WHITE,
+            density: 0.1,
+        }
+    }
+}
+
+/// The shape of a [`FogVolume`], in its local space.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+pub enum FogVolumeShape {
+    /// A cube from `-0.5` to `0.5` on each axis.
+    #[default]
+    Box,
+    /// A sphere of radius `0.5` centered on the origin.
+    Sphere,
+}
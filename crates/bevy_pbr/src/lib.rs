@@ -5,52 +5,65 @@ pub mod wireframe;
 
 mod alpha;
 mod bundle;
+mod color_space_audit;
 pub mod deferred;
+pub mod dynamic_material;
 mod extended_material;
 mod fog;
 mod light;
 mod light_probe;
 mod lightmap;
 mod material;
+mod medium;
 mod parallax;
 mod pbr_material;
 mod prepass;
 mod render;
 mod ssao;
+mod view_material_override;
 
 pub use alpha::*;
 use bevy_core_pipeline::core_3d::graph::{Labels3d, SubGraph3d};
 pub use bundle::*;
+pub use color_space_audit::*;
+pub use dynamic_material::*;
 pub use extended_material::*;
 pub use fog::*;
 pub use light::*;
 pub use light_probe::*;
 pub use lightmap::*;
 pub use material::*;
+pub use medium::*;
 pub use parallax::*;
 pub use pbr_material::*;
 pub use prepass::*;
 pub use render::*;
 pub use ssao::*;
+pub use view_material_override::*;
 
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
         alpha::AlphaMode,
         bundle::{
-            DirectionalLightBundle, MaterialMeshBundle, PbrBundle, PointLightBundle,
-            SpotLightBundle,
+            DirectionalLightBundle, FogVolumeBundle, MaterialMeshBundle, PbrBundle,
+            PointLightBundle, SpotLightBundle,
         },
-        fog::{FogFalloff, FogSettings},
+        dynamic_material::DynamicMaterial,
+        fog::{FogFalloff, FogSettings, FogVolume, FogVolumeShape},
         light::{AmbientLight, DirectionalLight, PointLight, SpotLight},
         light_probe::{
             environment_map::{EnvironmentMapLight, ReflectionProbeBundle},
-            LightProbe,
+            generated_environment_map::GeneratedEnvironmentMapLight,
+            reflection_probe::ReflectionProbe,
+            LightProbe, LightProbeBlend,
         },
         material::{Material, MaterialPlugin},
+        medium::{CameraMedium, WaterSettings},
         parallax::ParallaxMappingMethod,
         pbr_material::StandardMaterial,
         ssao::ScreenSpaceAmbientOcclusionPlugin,
+        view_material_override::{MaterialOverrideTag, ViewMaterialOverride},
     };
 }
 
@@ -64,6 +77,8 @@ pub mod graph {
         /// Label for the screen space ambient occlusion render node.
         ScreenSpaceAmbientOcclusion,
         DeferredLightingPass,
+        /// Label for the distortion post pass of the cameras under water.
+        WaterDistortion,
     }
 }
 
@@ -73,6 +88,7 @@ use bevy_ecs::prelude::*;
 use bevy_render::{
     camera::{CameraUpdateSystem, Projection},
     extract_component::ExtractComponentPlugin,
+    extract_instances::ExtractInstancesPlugin,
     extract_resource::ExtractResourcePlugin,
     prelude::Color,
     render_asset::prepare_assets,
@@ -240,6 +256,7 @@ impl Plugin for PbrPlugin {
             .register_type::<AmbientLight>()
             .register_type::<Cascade>()
             .register_type::<CascadeShadowConfig>()
+            .register_type::<CascadeStabilization>()
             .register_type::<Cascades>()
             .register_type::<CascadesVisibleEntities>()
             .register_type::<ClusterConfig>()
@@ -248,11 +265,16 @@ impl Plugin for PbrPlugin {
             .register_type::<CubemapVisibleEntities>()
             .register_type::<DirectionalLight>()
             .register_type::<DirectionalLightShadowMap>()
+            .register_type::<CascadeDebugVisualization>()
+            .register_type::<GpuShadowCulling>()
             .register_type::<NotShadowCaster>()
             .register_type::<NotShadowReceiver>()
+            .register_type::<TransparentPrepass>()
             .register_type::<PointLight>()
             .register_type::<PointLightShadowMap>()
             .register_type::<SpotLight>()
+            .register_type::<ShadowResolution>()
+            .register_type::<SpotLightShadowAtlas>()
             .register_type::<FogSettings>()
             .register_type::<FogFalloff>()
             .register_type::<ShadowFilteringMethod>()
@@ -261,9 +283,14 @@ impl Plugin for PbrPlugin {
             .init_resource::<AmbientLight>()
             .init_resource::<GlobalVisiblePointLights>()
             .init_resource::<DirectionalLightShadowMap>()
+            .init_resource::<CascadeDebugVisualization>()
             .init_resource::<PointLightShadowMap>()
+            .init_resource::<SpotLightShadowAtlas>()
             .register_type::<DefaultOpaqueRendererMethod>()
+            .register_type::<MaterialOverrideTag>()
             .init_resource::<DefaultOpaqueRendererMethod>()
+            .register_type::<TextureColorSpaceAudit>()
+            .init_resource::<TextureColorSpaceAudit>()
             .add_plugins((
                 MeshRenderPlugin,
                 MaterialPlugin::<StandardMaterial> {
@@ -275,8 +302,14 @@ impl Plugin for PbrPlugin {
                 FogPlugin,
                 ExtractResourcePlugin::<DefaultOpaqueRendererMethod>::default(),
                 ExtractComponentPlugin::<ShadowFilteringMethod>::default(),
+                ExtractComponentPlugin::<CascadeShadowConfig>::default(),
+                ExtractResourcePlugin::<CascadeDebugVisualization>::default(),
+                ExtractInstancesPlugin::<MaterialOverrideTag>::retained(),
                 LightmapPlugin,
                 LightProbePlugin,
+                GpuShadowCullingPlugin,
+                MediumPlugin,
+                DynamicMaterialPlugin,
             ))
             .configure_sets(
                 PostUpdate,
@@ -330,6 +363,7 @@ impl Plugin for PbrPlugin {
                         // because that resets entity `ViewVisibility` for the first view
                         // which would override any results from this otherwise
                         .after(VisibilitySystems::CheckVisibility),
+                    audit_texture_color_spaces,
                 ),
             );
 
@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-use bevy_ecs::prelude::*;
+use bevy_ecs::{prelude::*, query::QueryItem};
 use bevy_math::{
     AspectRatio, Mat4, UVec2, UVec3, Vec2, Vec3, Vec3A, Vec3Swizzles, Vec4, Vec4Swizzles,
 };
@@ -72,6 +72,9 @@ impl PointLight {
     pub const DEFAULT_SHADOW_NORMAL_BIAS: f32 = 0.6;
 }
 
+/// Controls the resolution of [`PointLight`] shadow maps.
+///
+/// Inserted by the [`PbrPlugin`](crate::PbrPlugin), and can be changed at runtime.
 #[derive(Resource, Clone, Debug, Reflect)]
 #[reflect(Resource)]
 pub struct PointLightShadowMap {
@@ -137,6 +140,44 @@ impl Default for SpotLight {
     }
 }
 
+/// The resolution requested for the shadow map of a [`SpotLight`], in texels.
+///
+/// Spot light shadow maps are packed in the [`SpotLightShadowAtlas`], and are rounded up to a
+/// power of two fraction of its page size. Without this component, a spot light requests a
+/// whole page. When the atlas is full, the resolution of the shadow maps with the least
+/// importance, based on the intensity of the light and its distance to the cameras, is halved
+/// first.
+///
+/// Point lights render their shadows to cube maps sized by the [`PointLightShadowMap`].
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct ShadowResolution(pub u32);
+
+/// Controls the atlas the [`SpotLight`] shadow maps are packed in.
+///
+/// The pages of the atlas are layers of the [`DirectionalLightShadowMap`] texture, following the
+/// directional light cascades, and have the same size. Only the pages used by the shadow maps of
+/// the current frame are allocated.
+#[derive(Resource, Clone, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct SpotLightShadowAtlas {
+    /// The maximum number of pages. It is also limited by the number of texture array layers
+    /// supported by the device.
+    pub max_pages: u32,
+    /// The resolution shadow maps are reduced to, at most, before the shadows of the least
+    /// important spot lights are disabled.
+    pub min_resolution: u32,
+}
+
+impl Default for SpotLightShadowAtlas {
+    fn default() -> Self {
+        Self {
+            max_pages: 4,
+            min_resolution: 128,
+        }
+    }
+}
+
 /// A Directional light.
 ///
 /// Directional lights don't exist in reality but they are a good
@@ -174,7 +215,8 @@ impl Default for SpotLight {
 /// Shadows are produced via [cascaded shadow maps](https://developer.download.nvidia.com/SDK/10.5/opengl/src/cascaded_shadow_maps/doc/cascaded_shadow_maps.pdf).
 ///
 /// To modify the cascade set up, such as the number of cascades or the maximum shadow distance,
-/// change the [`CascadeShadowConfig`] component of the [`DirectionalLightBundle`].
+/// change the [`CascadeShadowConfig`] component of the [`DirectionalLightBundle`]. A camera with
+/// its own [`CascadeShadowConfig`] uses it instead for all the directional lights it sees.
 ///
 /// To control the resolution of the shadow maps, use the [`DirectionalLightShadowMap`] resource:
 ///
@@ -220,6 +262,8 @@ impl DirectionalLight {
 }
 
 /// Controls the resolution of [`DirectionalLight`] shadow maps.
+///
+/// Inserted by the [`PbrPlugin`](crate::PbrPlugin), and can be changed at runtime.
 #[derive(Resource, Clone, Debug, Reflect)]
 #[reflect(Resource)]
 pub struct DirectionalLightShadowMap {
@@ -232,9 +276,33 @@ impl Default for DirectionalLightShadowMap {
     }
 }
 
+/// Tints the lit surfaces by the index of the [`DirectionalLight`] shadow cascade they sample,
+/// which helps tuning the [`CascadeShadowConfig`] of lights and cameras.
+///
+/// Toggling it re-specializes the pipelines of all the materials, so it is meant for debugging.
+///
+/// ```
+/// # use bevy_app::prelude::*;
+/// # use bevy_pbr::CascadeDebugVisualization;
+/// App::new()
+///     .insert_resource(CascadeDebugVisualization { enabled: true });
+/// ```
+#[derive(Resource, Clone, Debug, Default, Reflect, ExtractResource)]
+#[reflect(Resource, Default)]
+pub struct CascadeDebugVisualization {
+    /// Whether the lit surfaces are tinted by the index of their cascade.
+    ///
+    /// Defaults to `false`.
+    pub enabled: bool,
+}
+
 /// Controls how cascaded shadow mapping works.
 /// Prefer using [`CascadeShadowConfigBuilder`] to construct an instance.
 ///
+/// Add it to a [`DirectionalLight`] to configure its cascades for all cameras, or to a [`Camera`]
+/// to override the configuration of every directional light for that camera, for example for a
+/// scope camera that needs tighter cascades than the main view.
+///
 /// ```
 /// # use bevy_pbr::CascadeShadowConfig;
 /// # use bevy_pbr::CascadeShadowConfigBuilder;
@@ -254,6 +322,8 @@ pub struct CascadeShadowConfig {
     pub overlap_proportion: f32,
     /// The (positive) distance to the near boundary of the first cascade.
     pub minimum_distance: f32,
+    /// How the cascades are fitted to the view frustum and snapped to texels.
+    pub stabilization: CascadeStabilization,
 }
 
 impl Default for CascadeShadowConfig {
@@ -262,6 +332,38 @@ impl Default for CascadeShadowConfig {
     }
 }
 
+/// Only the configurations overriding the ones of the lights for a camera are extracted, the
+/// configurations of the lights are extracted along with them.
+impl ExtractComponent for CascadeShadowConfig {
+    type QueryData = &'static Self;
+    type QueryFilter = With<Camera>;
+    type Out = Self;
+
+    fn extract_component(item: QueryItem<'_, Self::QueryData>) -> Option<Self::Out> {
+        Some(item.clone())
+    }
+}
+
+/// How the cascades of a [`CascadeShadowConfig`] are fitted to the slices of the view frustum.
+///
+/// Moving the shadow map of a cascade by a fraction of a texel from one frame to the next
+/// changes which texels the edges of the shadows fall on, so they flicker as the camera moves.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Default)]
+pub enum CascadeStabilization {
+    /// The cascades have a constant size, the diameter of the bounding sphere of their slice,
+    /// and move in whole texels: the shadows don't flicker when the camera moves or rotates.
+    #[default]
+    Stable,
+    /// The cascades are fitted to the bounds of their slice in light space and move in whole
+    /// texels: the shadows are sharper and don't flicker when the camera moves, but their texel
+    /// size changes when the camera rotates.
+    SnapToTexels,
+    /// The cascades are fitted to the bounds of their slice in light space, for the sharpest
+    /// shadows, but they flicker when the camera moves or rotates.
+    None,
+}
+
 fn calculate_cascade_bounds(
     num_cascades: usize,
     nearest_bound: f32,
@@ -311,6 +413,8 @@ pub struct CascadeShadowConfigBuilder {
     /// The overlap is used to make the transition from one cascade's shadow map to the next
     /// less abrupt by blending between both shadow maps.
     pub overlap_proportion: f32,
+    /// How the cascades are fitted to the view frustum and snapped to texels.
+    pub stabilization: CascadeStabilization,
 }
 
 impl CascadeShadowConfigBuilder {
@@ -349,6 +453,7 @@ impl CascadeShadowConfigBuilder {
             ),
             overlap_proportion: self.overlap_proportion,
             minimum_distance: self.minimum_distance,
+            stabilization: self.stabilization,
         }
     }
 }
@@ -367,6 +472,7 @@ impl Default for CascadeShadowConfigBuilder {
                 maximum_distance: 100.0,
                 first_cascade_far_bound: 5.0,
                 overlap_proportion: 0.2,
+                stabilization: CascadeStabilization::default(),
             }
         } else {
             Self {
@@ -375,6 +481,7 @@ impl Default for CascadeShadowConfigBuilder {
                 maximum_distance: 1000.0,
                 first_cascade_far_bound: 5.0,
                 overlap_proportion: 0.2,
+                stabilization: CascadeStabilization::default(),
             }
         }
     }
@@ -407,6 +514,32 @@ pub struct Cascade {
     pub(crate) texel_size: f32,
 }
 
+impl Cascades {
+    /// Returns the cascades of the light for the view `view`, from nearest to farthest.
+    pub fn get(&self, view: Entity) -> Option<&[Cascade]> {
+        self.cascades.get(&view).map(Vec::as_slice)
+    }
+
+    /// Iterates over the views lit by the light, and their cascades.
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, &[Cascade])> {
+        self.cascades
+            .iter()
+            .map(|(&view, cascades)| (view, cascades.as_slice()))
+    }
+}
+
+impl Cascade {
+    /// The transform of the light, i.e. the view to world matrix.
+    pub fn view_transform(&self) -> Mat4 {
+        self.view_transform
+    }
+
+    /// The orthographic projection for this cascade.
+    pub fn projection(&self) -> Mat4 {
+        self.projection
+    }
+}
+
 pub fn clear_directional_light_cascades(mut lights: Query<(&DirectionalLight, &mut Cascades)>) {
     for (directional_light, mut cascades) in lights.iter_mut() {
         if !directional_light.shadows_enabled {
@@ -418,7 +551,13 @@ pub fn clear_directional_light_cascades(mut lights: Query<(&DirectionalLight, &m
 
 pub fn build_directional_light_cascades<P: CameraProjection + Component>(
     directional_light_shadow_map: Res<DirectionalLightShadowMap>,
-    views: Query<(Entity, &GlobalTransform, &P, &Camera)>,
+    views: Query<(
+        Entity,
+        &GlobalTransform,
+        &P,
+        &Camera,
+        Option<&CascadeShadowConfig>,
+    )>,
     mut lights: Query<(
         &GlobalTransform,
         &DirectionalLight,
@@ -428,16 +567,21 @@ pub fn build_directional_light_cascades<P: CameraProjection + Component>(
 ) {
     let views = views
         .iter()
-        .filter_map(|(entity, transform, projection, camera)| {
+        .filter_map(|(entity, transform, projection, camera, cascades_config)| {
             if camera.is_active {
-                Some((entity, projection, transform.compute_matrix()))
+                Some((
+                    entity,
+                    projection,
+                    transform.compute_matrix(),
+                    cascades_config,
+                ))
             } else {
                 None
             }
         })
         .collect::<Vec<_>>();
 
-    for (transform, directional_light, cascades_config, mut cascades) in &mut lights {
+    for (transform, directional_light, light_cascades_config, mut cascades) in &mut lights {
         if !directional_light.shadows_enabled {
             continue;
         }
@@ -451,7 +595,8 @@ pub fn build_directional_light_cascades<P: CameraProjection + Component>(
         let light_to_world = Mat4::from_quat(transform.compute_transform().rotation);
         let light_to_world_inverse = light_to_world.inverse();
 
-        for (view_entity, projection, view_to_world) in views.iter().copied() {
+        for &(view_entity, projection, view_to_world, view_cascades_config) in &views {
+            let cascades_config = view_cascades_config.unwrap_or(light_cascades_config);
             let camera_to_light_view = light_to_world_inverse * view_to_world;
             let view_cascades = cascades_config
                 .bounds
@@ -474,6 +619,7 @@ pub fn build_directional_light_cascades<P: CameraProjection + Component>(
                         directional_light_shadow_map.size as f32,
                         light_to_world,
                         camera_to_light_view,
+                        cascades_config.stabilization,
                     )
                 })
                 .collect();
@@ -490,6 +636,7 @@ fn calculate_cascade(
     cascade_texture_size: f32,
     light_to_world: Mat4,
     camera_to_light: Mat4,
+    stabilization: CascadeStabilization,
 ) -> Cascade {
     let mut min = Vec3A::splat(f32::MAX);
     let mut max = Vec3A::splat(f32::MIN);
@@ -506,10 +653,18 @@ fn calculate_cascade(
     //       as even though the lengths using corner_light_view above should be the same, precision can
     //       introduce small but significant differences.
     // NOTE: The size remains the same unless the view frustum or cascade configuration is modified.
-    let cascade_diameter = (frustum_corners[0] - frustum_corners[6])
-        .length()
-        .max((frustum_corners[4] - frustum_corners[6]).length())
-        .ceil();
+    let cascade_diameter = match stabilization {
+        CascadeStabilization::Stable => (frustum_corners[0] - frustum_corners[6])
+            .length()
+            .max((frustum_corners[4] - frustum_corners[6]).length())
+            .ceil(),
+        // The light space bounds change with the orientation of the camera. Snapping moves the
+        // cascade by up to a texel, so it is one texel larger on each side.
+        CascadeStabilization::SnapToTexels => {
+            (max.x - min.x).max(max.y - min.y) * (1.0 + 2.0 / cascade_texture_size)
+        }
+        CascadeStabilization::None => (max.x - min.x).max(max.y - min.y),
+    };
 
     // NOTE: If we ensure that cascade_texture_size is a power of 2, then as we made cascade_diameter an
     //       integer, cascade_texel_size is then an integer multiple of a power of 2 and can be
@@ -517,9 +672,15 @@ fn calculate_cascade(
     let cascade_texel_size = cascade_diameter / cascade_texture_size;
     // NOTE: For shadow stability it is very important that the near_plane_center is at integer
     //       multiples of the texel size to be exactly representable in a floating point value.
+    let snap = |coordinate: f32| match stabilization {
+        CascadeStabilization::Stable | CascadeStabilization::SnapToTexels => {
+            (coordinate / cascade_texel_size).floor() * cascade_texel_size
+        }
+        CascadeStabilization::None => coordinate,
+    };
     let near_plane_center = Vec3A::new(
-        (0.5 * (min.x + max.x) / cascade_texel_size).floor() * cascade_texel_size,
-        (0.5 * (min.y + max.y) / cascade_texel_size).floor() * cascade_texel_size,
+        snap(0.5 * (min.x + max.x)),
+        snap(0.5 * (min.y + max.y)),
         // NOTE: max.z is the near plane for right-handed y-up
         max.z,
     );
@@ -609,6 +770,24 @@ pub struct NotShadowReceiver;
 #[reflect(Component, Default)]
 pub struct TransmittedShadowReceiver;
 
+/// Add this component to a [`DirectionalLight`] to cull its shadow casters against its cascades
+/// on the GPU.
+///
+/// Without it, the shadow casters are culled on the CPU against each cascade of each view. With
+/// it, all the shadow casters are queued to each cascade and a compute shader hides the ones
+/// outside of the cascade, by writing the indirect draws of the visible instances of each batch.
+/// When the device supports
+/// [`WgpuFeatures::MULTI_DRAW_INDIRECT_COUNT`](bevy_render::settings::WgpuFeatures::MULTI_DRAW_INDIRECT_COUNT),
+/// each batch is then drawn with a single command, which saves CPU time in scenes with many
+/// shadow casters.
+///
+/// This requires compute shaders, storage buffers and indirect draws starting at an instance
+/// ([`WgpuFeatures::INDIRECT_FIRST_INSTANCE`](bevy_render::settings::WgpuFeatures::INDIRECT_FIRST_INSTANCE)):
+/// when they aren't supported, the shadow casters are culled on the CPU.
+#[derive(Component, Reflect, Clone, Copy, Debug, Default)]
+#[reflect(Component, Default)]
+pub struct GpuShadowCulling;
+
 /// Add this component to a [`Camera3d`](bevy_core_pipeline::core_3d::Camera3d)
 /// to control how to anti-alias shadow edges.
 ///
@@ -870,9 +1049,11 @@ pub fn add_clusters(
 
 #[derive(Clone, Component, Debug, Default)]
 pub struct VisiblePointLights {
+    /// The point lights, then the spot lights, then the [`FogVolume`]s affecting the cluster.
     pub(crate) entities: Vec<Entity>,
     pub point_light_count: usize,
     pub spot_light_count: usize,
+    pub fog_volume_count: usize,
 }
 
 impl VisiblePointLights {
@@ -1177,6 +1358,83 @@ impl PointLightAssignmentData {
     }
 }
 
+#[derive(Clone)]
+// data required for assigning fog volumes to clusters
+pub(crate) struct FogVolumeAssignmentData {
+    pub(crate) entity: Entity,
+    sphere: Sphere,
+}
+
+impl FogVolumeAssignmentData {
+    fn new(entity: Entity, transform: &GlobalTransform, shape: FogVolumeShape) -> Self {
+        let matrix3 = transform.affine().matrix3;
+        let radius = match shape {
+            // The farthest corner of the unit cube.
+            FogVolumeShape::Box => [
+                matrix3.x_axis + matrix3.y_axis + matrix3.z_axis,
+                matrix3.x_axis + matrix3.y_axis - matrix3.z_axis,
+                matrix3.x_axis - matrix3.y_axis + matrix3.z_axis,
+                matrix3.x_axis - matrix3.y_axis - matrix3.z_axis,
+            ]
+            .iter()
+            .map(|diagonal| diagonal.length())
+            .fold(0.0, f32::max),
+            // The longest axis of the ellipsoid.
+            FogVolumeShape::Sphere => matrix3
+                .x_axis
+                .length()
+                .max(matrix3.y_axis.length())
+                .max(matrix3.z_axis.length()),
+        } * 0.5;
+        Self {
+            entity,
+            sphere: Sphere {
+                center: transform.translation_vec3a(),
+                radius,
+            },
+        }
+    }
+}
+
+/// Collects the visible [`FogVolume`]s nearest to a view at `view_translation`, keeping at most
+/// [`MAX_FOG_VOLUMES`].
+///
+/// The volumes are sorted by their distance from the view, which is the order the render world
+/// uses to index them, so that the volumes past the limit are the farthest ones.
+pub(crate) fn collect_visible_fog_volumes<'a>(
+    fog_volumes: impl Iterator<
+        Item = (
+            Entity,
+            &'a GlobalTransform,
+            &'a FogVolume,
+            &'a ViewVisibility,
+        ),
+    >,
+    view_translation: Vec3A,
+) -> Vec<(FogVolumeAssignmentData, &'a GlobalTransform, &'a FogVolume)> {
+    let mut visible_fog_volumes: Vec<_> = fog_volumes
+        .filter(|(.., visibility)| visibility.get())
+        .map(|(entity, transform, fog_volume, _)| {
+            let data = FogVolumeAssignmentData::new(entity, transform, fog_volume.shape);
+            // Zero inside the volume.
+            let distance =
+                (data.sphere.center.distance(view_translation) - data.sphere.radius).max(0.0);
+            (distance, data, transform, fog_volume)
+        })
+        .collect();
+    // Ties are broken by entity to keep the order stable between the worlds.
+    visible_fog_volumes.sort_by(|(distance_1, data_1, ..), (distance_2, data_2, ..)| {
+        distance_1
+            .total_cmp(distance_2)
+            .then_with(|| data_1.entity.cmp(&data_2.entity))
+    });
+    visible_fog_volumes.truncate(MAX_FOG_VOLUMES);
+    visible_fog_volumes
+        .into_iter()
+        .map(|(_, data, transform, fog_volume)| (data, transform, fog_volume))
+        .collect()
+}
+
 #[derive(Resource, Default)]
 pub struct GlobalVisiblePointLights {
     entities: HashSet<Entity>,
@@ -1223,7 +1481,9 @@ pub(crate) fn assign_lights_to_clusters(
         Option<&RenderLayers>,
         &ViewVisibility,
     )>,
+    fog_volumes_query: Query<(Entity, &GlobalTransform, &FogVolume, &ViewVisibility)>,
     mut lights: Local<Vec<PointLightAssignmentData>>,
+    mut fog_volumes: Local<Vec<FogVolumeAssignmentData>>,
     mut cluster_aabb_spheres: Local<Vec<Option<Sphere>>>,
     mut max_point_lights_warning_emitted: Local<bool>,
     render_device: Option<Res<RenderDevice>>,
@@ -1276,6 +1536,7 @@ pub(crate) fn assign_lights_to_clusters(
         clustered_forward_buffer_binding_type,
         BufferBindingType::Storage { .. }
     );
+
     if lights.len() > MAX_UNIFORM_BUFFER_POINT_LIGHTS && !supports_storage_buffers {
         lights.sort_by(|light_1, light_2| {
             point_light_order(
@@ -1490,6 +1751,7 @@ pub(crate) fn assign_lights_to_clusters(
             lights.entities.clear();
             lights.point_light_count = 0;
             lights.spot_light_count = 0;
+            lights.fog_volume_count = 0;
         }
         let cluster_count =
             (clusters.dimensions.x * clusters.dimensions.y * clusters.dimensions.z) as usize;
@@ -1795,6 +2057,70 @@ pub(crate) fn assign_lights_to_clusters(
                 ..Default::default()
             });
         }
+
+        // The fog volume counts of the clusters only fit in the storage buffer layout, otherwise
+        // the fragments evaluate all the fog volumes.
+        fog_volumes.clear();
+        if supports_storage_buffers {
+            fog_volumes.extend(
+                collect_visible_fog_volumes(
+                    fog_volumes_query.iter(),
+                    camera_transform.translation_vec3a(),
+                )
+                .into_iter()
+                .map(|(data, ..)| data),
+            );
+        }
+
+        // A fog volume affects the fragments inside it and the ones behind it, so it is assigned
+        // to all the clusters of its screen space bounds from its nearest depth slice to the
+        // farthest one. The volumes come after the lights in the lists of the clusters.
+        for fog_volume in fog_volumes.iter() {
+            if !frustum.intersects_sphere(&fog_volume.sphere, true) {
+                continue;
+            }
+
+            let (aabb_xy_ndc_z_view_min, aabb_xy_ndc_z_view_max) = cluster_space_light_aabb(
+                inverse_view_transform,
+                view_inv_scale,
+                camera.projection_matrix(),
+                &fog_volume.sphere,
+            );
+            let min_cluster = ndc_position_to_cluster(
+                clusters.dimensions,
+                cluster_factors,
+                is_orthographic,
+                aabb_xy_ndc_z_view_min,
+                aabb_xy_ndc_z_view_min.z,
+            );
+            let max_cluster = ndc_position_to_cluster(
+                clusters.dimensions,
+                cluster_factors,
+                is_orthographic,
+                aabb_xy_ndc_z_view_max,
+                aabb_xy_ndc_z_view_max.z,
+            );
+            let (min_cluster, max_cluster) =
+                (min_cluster.min(max_cluster), min_cluster.max(max_cluster));
+
+            for y in min_cluster.y..=max_cluster.y {
+                for x in min_cluster.x..=max_cluster.x {
+                    let first_cluster_index = ((y * clusters.dimensions.x + x)
+                        * clusters.dimensions.z
+                        + min_cluster.z) as usize;
+                    let last_cluster_index = ((y * clusters.dimensions.x + x)
+                        * clusters.dimensions.z
+                        + clusters.dimensions.z
+                        - 1) as usize;
+                    for cluster_lights in
+                        &mut clusters.lights[first_cluster_index..=last_cluster_index]
+                    {
+                        cluster_lights.entities.push(fog_volume.entity);
+                        cluster_lights.fog_volume_count += 1;
+                    }
+                }
+            }
+        }
     }
 }
 
@@ -1994,6 +2320,7 @@ pub fn check_light_mesh_visibility(
             &mut CascadesVisibleEntities,
             Option<&RenderLayers>,
             &mut ViewVisibility,
+            Has<GpuShadowCulling>,
         ),
         Without<SpotLight>,
     >,
@@ -2008,6 +2335,7 @@ pub fn check_light_mesh_visibility(
         ),
         (Without<NotShadowCaster>, Without<DirectionalLight>),
     >,
+    render_device: Option<Res<RenderDevice>>,
 ) {
     fn shrink_entities(visible_entities: &mut VisibleEntities) {
         // Check that visible entities capacity() is no more than two times greater than len()
@@ -2025,9 +2353,19 @@ pub fn check_light_mesh_visibility(
         visible_entities.entities.shrink_to(reserved);
     }
 
+    let gpu_shadow_culling_supported = render_device
+        .as_deref()
+        .is_some_and(gpu_shadow_culling_supported);
+
     // Directional lights
-    for (directional_light, frusta, mut visible_entities, maybe_view_mask, light_view_visibility) in
-        &mut directional_lights
+    for (
+        directional_light,
+        frusta,
+        mut visible_entities,
+        maybe_view_mask,
+        light_view_visibility,
+        gpu_shadow_culling,
+    ) in &mut directional_lights
     {
         // Re-use already allocated entries where possible.
         let mut views_to_remove = Vec::new();
@@ -2058,6 +2396,8 @@ pub fn check_light_mesh_visibility(
         }
 
         let view_mask = maybe_view_mask.copied().unwrap_or_default();
+        // With GPU culling, all the shadow casters are added to each cascade.
+        let cpu_culling = !(gpu_shadow_culling && gpu_shadow_culling_supported);
 
         for (
             entity,
@@ -2077,8 +2417,9 @@ pub fn check_light_mesh_visibility(
                 continue;
             }
 
-            // If we have an aabb and transform, do frustum culling
-            if let (Some(aabb), Some(transform)) = (maybe_aabb, maybe_transform) {
+            // If we have an aabb and transform, do frustum culling, unless it is done on the GPU
+            if let (Some(aabb), Some(transform), true) = (maybe_aabb, maybe_transform, cpu_culling)
+            {
                 for (view, view_frusta) in &frusta.frusta {
                     let view_visible_entities = visible_entities
                         .entities
@@ -2253,6 +2594,18 @@ pub fn check_light_mesh_visibility(
 #[cfg(test)]
 mod test {
     use super::*;
+    use bevy_app::{App, Update};
+    use bevy_asset::{AssetEvent, Assets};
+    use bevy_math::Quat;
+    use bevy_render::{
+        camera::{camera_system, ManualTextureViews, PerspectiveProjection, Projection},
+        texture::Image,
+        view::update_frusta,
+    };
+    use bevy_window::{
+        PrimaryWindow, Window, WindowCreated, WindowResized, WindowResolution,
+        WindowScaleFactorChanged,
+    };
 
     fn test_cluster_tiling(config: ClusterConfig, screen_size: UVec2) -> Clusters {
         let dims = config.dimensions_for_screen_size(screen_size);
@@ -2310,4 +2663,326 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn cascade_stabilization() {
+        let offset = Vec3A::new(0.3, 0.3, 0.0);
+        let frustum_corners = [
+            Vec3A::new(-1.0, -1.0, -1.0),
+            Vec3A::new(1.0, -1.0, -1.0),
+            Vec3A::new(1.0, 1.0, -1.0),
+            Vec3A::new(-1.0, 1.0, -1.0),
+            Vec3A::new(-1.0, -1.0, -3.0),
+            Vec3A::new(1.0, -1.0, -3.0),
+            Vec3A::new(1.0, 1.0, -3.0),
+            Vec3A::new(-1.0, 1.0, -3.0),
+        ]
+        .map(|corner| corner + offset);
+        let cascade = |camera_to_light, stabilization| {
+            calculate_cascade(
+                frustum_corners,
+                256.0,
+                Mat4::IDENTITY,
+                camera_to_light,
+                stabilization,
+            )
+        };
+        let rotated = Mat4::from_rotation_z(std::f32::consts::FRAC_PI_4);
+
+        // Stable cascades fit the bounding sphere of the slice, whatever the camera orientation
+        for camera_to_light in [Mat4::IDENTITY, rotated] {
+            let stable = cascade(camera_to_light, CascadeStabilization::Stable);
+            assert_eq!(stable.texel_size, 4.0 / 256.0);
+        }
+
+        // Fitted cascades follow the light space bounds of the slice
+        let fitted = cascade(Mat4::IDENTITY, CascadeStabilization::None);
+        assert!((fitted.texel_size - 2.0 / 256.0).abs() < 1e-6);
+        assert!((fitted.view_transform.w_axis.x - 0.3).abs() < 1e-6);
+        let fitted_rotated = cascade(rotated, CascadeStabilization::None);
+        assert!(fitted_rotated.texel_size > fitted.texel_size * 1.4);
+
+        // and can be snapped to texels, with a margin for the snapping
+        let snapped = cascade(Mat4::IDENTITY, CascadeStabilization::SnapToTexels);
+        assert!(snapped.texel_size > fitted.texel_size);
+        let texels = snapped.view_transform.w_axis.x / snapped.texel_size;
+        assert!((texels - texels.round()).abs() < 1e-3);
+    }
+
+    #[test]
+    fn camera_cascade_config_overrides_light_config() {
+        let mut app = App::new();
+        app.init_resource::<DirectionalLightShadowMap>()
+            .add_systems(
+                Update,
+                (
+                    clear_directional_light_cascades,
+                    build_directional_light_cascades::<Projection>,
+                )
+                    .chain(),
+            );
+
+        let camera_transform = GlobalTransform::from(
+            Transform::from_xyz(0.0, 2.0, 10.0).looking_at(Vec3::ZERO, Vec3::Y),
+        );
+        let camera = (Camera::default(), Projection::default(), camera_transform);
+        let camera_config = CascadeShadowConfigBuilder {
+            num_cascades: 2,
+            first_cascade_far_bound: 2.0,
+            maximum_distance: 10.0,
+            ..Default::default()
+        }
+        .build();
+        let main_camera = app.world.spawn(camera.clone()).id();
+        let scope_camera = app.world.spawn((camera, camera_config.clone())).id();
+        let light = app
+            .world
+            .spawn((
+                DirectionalLight {
+                    shadows_enabled: true,
+                    ..Default::default()
+                },
+                CascadeShadowConfigBuilder {
+                    num_cascades: 4,
+                    ..Default::default()
+                }
+                .build(),
+                Cascades::default(),
+                GlobalTransform::from(
+                    Transform::from_xyz(1.0, 4.0, 2.0).looking_at(Vec3::ZERO, Vec3::Y),
+                ),
+            ))
+            .id();
+        app.update();
+
+        let view_projections = |app: &App, view| {
+            let cascades = app.world.get::<Cascades>(light).unwrap();
+            cascades
+                .get(view)
+                .unwrap()
+                .iter()
+                .map(|cascade| cascade.view_projection)
+                .collect::<Vec<_>>()
+        };
+        let scope_cascades = view_projections(&app, scope_camera);
+        assert_eq!(view_projections(&app, main_camera).len(), 4);
+        assert_eq!(scope_cascades.len(), 2);
+
+        // The scope camera gets the cascades the main camera gets with its config on the light.
+        app.world.entity_mut(light).insert(camera_config);
+        app.update();
+        assert_eq!(view_projections(&app, main_camera), scope_cascades);
+        assert_eq!(view_projections(&app, scope_camera), scope_cascades);
+    }
+
+    fn visible_fog_volume(
+        transform: Transform,
+        shape: FogVolumeShape,
+    ) -> (GlobalTransform, FogVolume, ViewVisibility) {
+        let mut visibility = ViewVisibility::HIDDEN;
+        visibility.set();
+        (
+            transform.into(),
+            FogVolume {
+                shape,
+                ..Default::default()
+            },
+            visibility,
+        )
+    }
+
+    #[test]
+    fn fog_volume_bounding_spheres() {
+        let radius = |transform: Transform, shape| {
+            FogVolumeAssignmentData::new(Entity::PLACEHOLDER, &transform.into(), shape)
+                .sphere
+                .radius
+        };
+
+        let scale = Vec3::new(2.0, 4.0, 4.0);
+        assert_eq!(
+            radius(Transform::from_scale(scale), FogVolumeShape::Box),
+            3.0
+        );
+        let rotated = Transform::from_rotation(Quat::from_rotation_y(1.0)).with_scale(scale);
+        assert!((radius(rotated, FogVolumeShape::Box) - 3.0).abs() < 1e-5);
+
+        let scale = Vec3::new(2.0, 4.0, 6.0);
+        assert_eq!(
+            radius(Transform::from_scale(scale), FogVolumeShape::Sphere),
+            3.0
+        );
+    }
+
+    #[test]
+    fn nearest_fog_volumes_are_kept() {
+        let mut world = World::new();
+        // Spawned from the farthest to the nearest, so that the entity order is the reverse of
+        // the distance order.
+        let mut volumes: Vec<_> = (0..MAX_FOG_VOLUMES + 2)
+            .rev()
+            .map(|i| {
+                let transform = Transform::from_xyz(0.0, 0.0, -10.0 * (i + 1) as f32);
+                let volume = visible_fog_volume(transform, FogVolumeShape::Sphere);
+                (world.spawn_empty().id(), volume.0, volume.1, volume.2)
+            })
+            .collect();
+        // A large volume around the view, whose center is farther than all the others.
+        let transform = Transform::from_xyz(0.0, 0.0, 1000.0).with_scale(Vec3::splat(2100.0));
+        let around = visible_fog_volume(transform, FogVolumeShape::Sphere);
+        let around_entity = world.spawn_empty().id();
+        volumes.push((around_entity, around.0, around.1, around.2));
+        // A hidden volume right in front of the view.
+        let hidden_entity = world.spawn_empty().id();
+        volumes.push((
+            hidden_entity,
+            Transform::from_xyz(0.0, 0.0, -1.0).into(),
+            FogVolume::default(),
+            ViewVisibility::HIDDEN,
+        ));
+
+        let collected: Vec<_> = collect_visible_fog_volumes(
+            volumes
+                .iter()
+                .map(|(entity, transform, volume, visibility)| {
+                    (*entity, transform, volume, visibility)
+                }),
+            Vec3A::ZERO,
+        )
+        .into_iter()
+        .map(|(data, ..)| data.entity)
+        .collect();
+
+        let mut expected = vec![around_entity];
+        expected.extend(
+            volumes[..MAX_FOG_VOLUMES + 2]
+                .iter()
+                .rev()
+                .take(MAX_FOG_VOLUMES - 1)
+                .map(|(entity, ..)| *entity),
+        );
+        assert_eq!(collected, expected);
+    }
+
+    /// Creates a device for the clusters, if there is an adapter supporting their storage buffers.
+    fn render_device() -> Option<RenderDevice> {
+        let instance = wgpu::Instance::default();
+        let adapter = futures_lite::future::block_on(
+            instance.request_adapter(&wgpu::RequestAdapterOptions::default()),
+        )?;
+        let (device, _) = futures_lite::future::block_on(
+            adapter.request_device(&wgpu::DeviceDescriptor::default(), None),
+        )
+        .ok()?;
+        let render_device = RenderDevice::from(device);
+        matches!(
+            render_device
+                .get_supported_read_only_binding_type(CLUSTERED_FORWARD_STORAGE_BUFFER_COUNT),
+            BufferBindingType::Storage { .. }
+        )
+        .then_some(render_device)
+    }
+
+    #[test]
+    fn fog_volumes_are_assigned_to_clusters() {
+        let Some(render_device) = render_device() else {
+            // The fog volumes are only clustered with storage buffers.
+            return;
+        };
+
+        let mut app = App::new();
+        app.add_event::<WindowCreated>()
+            .add_event::<WindowResized>()
+            .add_event::<WindowScaleFactorChanged>()
+            .add_event::<AssetEvent<Image>>()
+            .init_resource::<Assets<Image>>()
+            .init_resource::<ManualTextureViews>()
+            .init_resource::<GlobalVisiblePointLights>()
+            .insert_resource(render_device)
+            .add_systems(
+                Update,
+                (
+                    camera_system::<PerspectiveProjection>,
+                    update_frusta::<PerspectiveProjection>,
+                    assign_lights_to_clusters,
+                )
+                    .chain(),
+            );
+        app.world.spawn((
+            Window {
+                resolution: WindowResolution::new(800.0, 600.0),
+                ..Default::default()
+            },
+            PrimaryWindow,
+        ));
+        let camera = app
+            .world
+            .spawn((
+                Camera::default(),
+                PerspectiveProjection::default(),
+                GlobalTransform::default(),
+                Frustum::default(),
+                ClusterConfig::FixedZ {
+                    total: 4096,
+                    z_slices: 24,
+                    z_config: ClusterZConfig {
+                        first_slice_depth: 5.0,
+                        far_z_mode: ClusterFarZMode::Constant(100.0),
+                    },
+                    dynamic_resizing: false,
+                },
+                Clusters::default(),
+            ))
+            .id();
+        // A sphere in the middle of the screen, and a box on its right side.
+        let sphere = app
+            .world
+            .spawn(visible_fog_volume(
+                Transform::from_xyz(0.0, 0.0, -20.0).with_scale(Vec3::splat(4.0)),
+                FogVolumeShape::Sphere,
+            ))
+            .id();
+        let cube = app
+            .world
+            .spawn(visible_fog_volume(
+                Transform::from_xyz(10.0, 0.0, -20.0)
+                    .with_rotation(Quat::from_rotation_y(0.5))
+                    .with_scale(Vec3::splat(2.0)),
+                FogVolumeShape::Box,
+            ))
+            .id();
+        app.update();
+
+        let clusters = app.world.get::<Clusters>(camera).unwrap();
+        let dimensions = clusters.dimensions;
+        let cluster = |x: u32, y: u32, z: u32| {
+            let cluster_lights =
+                &clusters.lights[((y * dimensions.x + x) * dimensions.z + z) as usize];
+            assert_eq!(
+                cluster_lights.point_light_count + cluster_lights.spot_light_count,
+                0
+            );
+            assert_eq!(
+                cluster_lights.fog_volume_count,
+                cluster_lights.entities.len()
+            );
+            &cluster_lights.entities
+        };
+
+        // Each volume covers the clusters of its screen space bounds, from its nearest depth slice
+        // to the farthest one.
+        let cluster_factors =
+            calculate_cluster_factors(clusters.near, clusters.far, dimensions.z as f32, false);
+        let near_z = view_z_to_z_slice(cluster_factors, dimensions.z, -18.0, false);
+        let center = dimensions.xy() / 2;
+        assert!(cluster(center.x, center.y, near_z - 1).is_empty());
+        assert_eq!(cluster(center.x, center.y, near_z), &[sphere]);
+        assert_eq!(cluster(center.x, center.y, dimensions.z - 1), &[sphere]);
+        assert_eq!(
+            cluster(dimensions.x - 1, center.y, dimensions.z - 1),
+            &[cube]
+        );
+        assert!(cluster(0, center.y, dimensions.z - 1).is_empty());
+        assert!(cluster(center.x, 0, dimensions.z - 1).is_empty());
+    }
 }
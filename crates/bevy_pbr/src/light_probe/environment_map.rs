@@ -32,6 +32,13 @@
 //! The Khronos Group has [several pre-filtered environment maps] available for
 //! you to use.
 //!
+//! Alternatively, a
+//! [`GeneratedEnvironmentMapLight`](super::generated_environment_map::GeneratedEnvironmentMapLight)
+//! filters an equirectangular image, a cubemap or a scene captured at runtime
+//! into an environment map on the GPU.
+//! A [`ReflectionProbe`](super::reflection_probe::ReflectionProbe) captures
+//! the scene around it at runtime and filters it this way.
+//!
 //! Currently, reflection probes (i.e. environment maps attached to light
 //! probes) use binding arrays (also known as bindless textures) and
 //! consequently aren't supported on WebGL2 or WebGPU. Reflection probes are
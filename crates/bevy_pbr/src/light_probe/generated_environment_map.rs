@@ -0,0 +1,837 @@
+//! Environment maps filtered at runtime.
+//!
+//! A [`GeneratedEnvironmentMapLight`] fills the diffuse and specular cubemaps
+//! of an [`EnvironmentMapLight`] from an unfiltered environment: an
+//! equirectangular image, such as an HDR panorama, a cubemap, or the six faces
+//! rendered by cameras placed at a point of the scene. The filtering runs in a
+//! compute pass before the cameras are rendered, whenever the source changes,
+//! so the environment map can follow a time-of-day cycle.
+//!
+//! Generating environment maps requires compute shaders and storage textures,
+//! so it isn't supported on WebGL2.
+
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_asset::{load_internal_asset, AssetEvent, AssetId, Assets, Handle};
+use bevy_ecs::prelude::*;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    render_asset::{RenderAssetUsages, RenderAssets},
+    render_graph::{Node, NodeRunError, RenderGraph, RenderGraphContext, RenderLabel},
+    render_resource::{
+        binding_types::{
+            sampler, texture_2d, texture_cube, texture_storage_2d_array, uniform_buffer,
+        },
+        *,
+    },
+    renderer::{RenderContext, RenderDevice, RenderQueue},
+    texture::{Image, TextureFormatPixelInfo},
+    Extract, ExtractSchedule, Render, RenderApp, RenderSet,
+};
+use bevy_utils::{tracing::error, EntityHashMap, HashSet};
+
+use super::environment_map::EnvironmentMapLight;
+
+/// A handle to the shader filtering generated environment maps.
+pub const GENERATED_ENVIRONMENT_MAP_SHADER_HANDLE: Handle<Shader> =
+    Handle::weak_from_u128(2956718390218456381);
+
+/// The format of the generated cubemaps.
+const GENERATED_ENVIRONMENT_MAP_FORMAT: TextureFormat = TextureFormat::Rgba16Float;
+
+/// The number of samples taken for each texel of the diffuse map.
+const DIFFUSE_SAMPLE_COUNT: u32 = 256;
+
+/// The number of samples taken for each texel of the rough mip levels of the
+/// specular map.
+const SPECULAR_SAMPLE_COUNT: u32 = 128;
+
+/// Generates the [`EnvironmentMapLight`] of the entity by filtering an
+/// unfiltered environment on the GPU.
+///
+/// The [`EnvironmentMapLight`] is added to the entity with black cubemaps,
+/// which are filled once the source is loaded. Like an
+/// [`EnvironmentMapLight`], this can be added to a camera or to a
+/// [`LightProbe`](crate::LightProbe).
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_asset::Handle;
+/// # use bevy_pbr::generated_environment_map::GeneratedEnvironmentMapLight;
+/// # use bevy_render::{camera::Camera, texture::Image};
+/// fn spawn_camera(mut commands: Commands, sky: Handle<Image>) {
+///     commands.spawn((
+///         Camera::default(),
+///         GeneratedEnvironmentMapLight::from_image(sky),
+///     ));
+/// }
+/// ```
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Default)]
+pub struct GeneratedEnvironmentMapLight {
+    /// The environment to filter.
+    pub source: EnvironmentMapSource,
+    /// Scale factor applied to the diffuse and specular light of the generated
+    /// [`EnvironmentMapLight`].
+    pub intensity: f32,
+    /// The size of the faces of the diffuse cubemap, in texels.
+    ///
+    /// The diffuse light varies slowly, so a small cubemap is enough.
+    pub diffuse_size: u32,
+    /// The size of the faces of the specular cubemap, in texels. It should be
+    /// a power of two: each of its mip levels is filtered for a roughness.
+    pub specular_size: u32,
+    /// When the environment map is filtered again.
+    pub refresh: EnvironmentMapRefresh,
+}
+
+impl Default for GeneratedEnvironmentMapLight {
+    fn default() -> Self {
+        Self {
+            source: EnvironmentMapSource::default(),
+            intensity: 1.0,
+            diffuse_size: 32,
+            specular_size: 256,
+            refresh: EnvironmentMapRefresh::default(),
+        }
+    }
+}
+
+impl GeneratedEnvironmentMapLight {
+    /// Generates the environment map from an equirectangular image or a
+    /// cubemap.
+    pub fn from_image(image: Handle<Image>) -> Self {
+        Self {
+            source: EnvironmentMapSource::Image(image),
+            ..Default::default()
+        }
+    }
+
+    /// Generates the environment map from the images of the six faces of a
+    /// cubemap, in the order of [`EnvironmentMapSource::Faces`].
+    pub fn from_faces(faces: [Handle<Image>; 6]) -> Self {
+        Self {
+            source: EnvironmentMapSource::Faces(faces),
+            ..Default::default()
+        }
+    }
+}
+
+/// The unfiltered environment of a [`GeneratedEnvironmentMapLight`].
+#[derive(Clone, Debug, Reflect)]
+pub enum EnvironmentMapSource {
+    /// A cubemap, if the image has six array layers, or else an
+    /// equirectangular image whose top row looks up.
+    Image(Handle<Image>),
+    /// The faces of a cubemap in the +X, -X, +Y, -Y, +Z, -Z order, for example
+    /// the render targets of six cameras with a 90° field of view capturing
+    /// the scene around a point. The faces must have the same size and format.
+    ///
+    /// As for cubemaps, the +Z face looks toward -Z in world space, and the -Z
+    /// face toward +Z.
+    Faces([Handle<Image>; 6]),
+}
+
+impl Default for EnvironmentMapSource {
+    fn default() -> Self {
+        Self::Image(Handle::default())
+    }
+}
+
+/// When a [`GeneratedEnvironmentMapLight`] is filtered.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Default)]
+pub enum EnvironmentMapRefresh {
+    /// When the [`GeneratedEnvironmentMapLight`] or its source images change.
+    ///
+    /// Render targets don't emit change events when they are rendered to:
+    /// mark the component as changed to filter a captured scene again.
+    #[default]
+    OnChange,
+    /// Every frame, for sources changing continuously.
+    EveryFrame,
+}
+
+/// The sizes of the cubemaps created for a [`GeneratedEnvironmentMapLight`].
+///
+/// The cubemaps only live in the render world, so their sizes can't be read
+/// back from their assets.
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+struct GeneratedEnvironmentMapSizes {
+    diffuse: u32,
+    specular: u32,
+}
+
+/// The render graph node filtering the generated environment maps, in the root
+/// graph before the cameras are rendered.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, RenderLabel)]
+pub struct GenerateEnvironmentMapsLabel;
+
+/// Adds support for [`GeneratedEnvironmentMapLight`].
+pub struct GeneratedEnvironmentMapPlugin;
+
+impl Plugin for GeneratedEnvironmentMapPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            GENERATED_ENVIRONMENT_MAP_SHADER_HANDLE,
+            "generated_environment_map.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.register_type::<GeneratedEnvironmentMapLight>()
+            .add_systems(PostUpdate, add_generated_environment_map_images);
+
+        let Ok(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .init_resource::<PendingEnvironmentMapFilters>()
+            .init_resource::<PreparedEnvironmentMapFilters>()
+            .add_systems(ExtractSchedule, extract_generated_environment_maps)
+            .add_systems(
+                Render,
+                prepare_environment_map_filters.in_set(RenderSet::PrepareBindGroups),
+            );
+
+        let mut graph = render_app.world.resource_mut::<RenderGraph>();
+        graph.add_node(GenerateEnvironmentMapsLabel, GenerateEnvironmentMapsNode);
+        graph.add_node_edge(
+            GenerateEnvironmentMapsLabel,
+            bevy_render::graph::CameraDriverLabel,
+        );
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Ok(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app.init_resource::<EnvironmentMapFilterPipelines>();
+    }
+}
+
+/// The number of mip levels of a cubemap with faces of `size` texels, down to
+/// 1×1 faces.
+fn mip_level_count(size: u32) -> u32 {
+    size.max(1).ilog2() + 1
+}
+
+/// The perceptual roughness the mip level `mip_level` of a specular map with
+/// `mip_level_count` levels is filtered for, as sampled by
+/// `environment_map.wgsl`.
+fn mip_level_roughness(mip_level: u32, mip_level_count: u32) -> f32 {
+    if mip_level_count <= 1 {
+        return 0.0;
+    }
+    mip_level as f32 / (mip_level_count - 1) as f32
+}
+
+/// Creates a black cubemap that the filter writes to.
+fn create_generated_cubemap(size: u32, mip_level_count: u32) -> Image {
+    let mut image = Image::new_fill(
+        Extent3d {
+            width: size,
+            height: size,
+            depth_or_array_layers: 6,
+        },
+        TextureDimension::D2,
+        &[0; 8],
+        GENERATED_ENVIRONMENT_MAP_FORMAT,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    // The image is uploaded with its data, so it needs zeroes for all its mip
+    // levels.
+    let pixel_count = (0..mip_level_count)
+        .map(|mip_level| ((size >> mip_level).max(1) as usize).pow(2) * 6)
+        .sum::<usize>();
+    image.data = vec![0; pixel_count * GENERATED_ENVIRONMENT_MAP_FORMAT.pixel_size()];
+    image.texture_descriptor.mip_level_count = mip_level_count;
+    image.texture_descriptor.usage |= TextureUsages::STORAGE_BINDING;
+    image.texture_view_descriptor = Some(TextureViewDescriptor {
+        dimension: Some(TextureViewDimension::Cube),
+        ..Default::default()
+    });
+    image
+}
+
+/// Adds the [`EnvironmentMapLight`] filled by each
+/// [`GeneratedEnvironmentMapLight`], creating its cubemaps when their sizes
+/// change.
+fn add_generated_environment_map_images(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    query: Query<
+        (
+            Entity,
+            &GeneratedEnvironmentMapLight,
+            Option<&EnvironmentMapLight>,
+            Option<&GeneratedEnvironmentMapSizes>,
+        ),
+        Changed<GeneratedEnvironmentMapLight>,
+    >,
+) {
+    for (entity, generated, environment_map, sizes) in &query {
+        let new_sizes = GeneratedEnvironmentMapSizes {
+            diffuse: generated.diffuse_size.max(1),
+            specular: generated.specular_size.max(1),
+        };
+        let (diffuse_map, specular_map) = match environment_map {
+            Some(environment_map) if sizes == Some(&new_sizes) => (
+                environment_map.diffuse_map.clone(),
+                environment_map.specular_map.clone(),
+            ),
+            _ => (
+                images.add(create_generated_cubemap(new_sizes.diffuse, 1)),
+                images.add(create_generated_cubemap(
+                    new_sizes.specular,
+                    mip_level_count(new_sizes.specular),
+                )),
+            ),
+        };
+
+        commands.entity(entity).insert((
+            EnvironmentMapLight {
+                diffuse_map,
+                specular_map,
+                intensity: generated.intensity,
+            },
+            new_sizes,
+        ));
+    }
+}
+
+/// The source of a pending filter, in the render world.
+#[derive(Clone, Copy)]
+enum ExtractedEnvironmentMapSource {
+    Image(AssetId<Image>),
+    Faces([AssetId<Image>; 6]),
+}
+
+impl ExtractedEnvironmentMapSource {
+    fn contains(&self, id: &AssetId<Image>) -> bool {
+        match self {
+            Self::Image(image) => image == id,
+            Self::Faces(faces) => faces.contains(id),
+        }
+    }
+}
+
+/// A filter waiting for its source and destination images to be uploaded.
+struct EnvironmentMapFilterJob {
+    source: ExtractedEnvironmentMapSource,
+    diffuse_map: AssetId<Image>,
+    specular_map: AssetId<Image>,
+}
+
+/// The environment maps to filter, by entity. A filter stays pending until its
+/// images and pipelines are ready.
+#[derive(Resource, Default)]
+struct PendingEnvironmentMapFilters(EntityHashMap<Entity, EnvironmentMapFilterJob>);
+
+/// A face of a [`EnvironmentMapSource::Faces`] source copied into a cubemap.
+struct FaceCopy {
+    source: Texture,
+    destination: Texture,
+    layer: u32,
+    size: Extent3d,
+}
+
+/// A dispatch of the filter shader, writing one mip level of a cubemap.
+struct FilterDispatch {
+    pipeline: CachedComputePipelineId,
+    bind_group: BindGroup,
+    size: u32,
+}
+
+/// The commands filtering an environment map this frame.
+struct PreparedEnvironmentMapFilter {
+    copies: Vec<FaceCopy>,
+    dispatches: Vec<FilterDispatch>,
+}
+
+/// The environment maps filtered this frame.
+#[derive(Resource, Default)]
+struct PreparedEnvironmentMapFilters(Vec<PreparedEnvironmentMapFilter>);
+
+#[derive(Clone, Copy, ShaderType)]
+struct FilterSettings {
+    roughness: f32,
+    sample_count: u32,
+    source_level: f32,
+    source_size: f32,
+    source_mip_count: f32,
+}
+
+#[derive(Resource)]
+struct EnvironmentMapFilterPipelines {
+    cube_layout: BindGroupLayout,
+    equirectangular_layout: BindGroupLayout,
+    sampler: Sampler,
+    resample_cube: CachedComputePipelineId,
+    resample_equirectangular: CachedComputePipelineId,
+    filter: CachedComputePipelineId,
+}
+
+impl FromWorld for EnvironmentMapFilterPipelines {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let pipeline_cache = world.resource::<PipelineCache>();
+
+        let create_layout = |label, source| {
+            render_device.create_bind_group_layout(
+                label,
+                &BindGroupLayoutEntries::sequential(
+                    ShaderStages::COMPUTE,
+                    (
+                        source,
+                        sampler(SamplerBindingType::Filtering),
+                        texture_storage_2d_array(
+                            GENERATED_ENVIRONMENT_MAP_FORMAT,
+                            StorageTextureAccess::WriteOnly,
+                        ),
+                        uniform_buffer::<FilterSettings>(false),
+                    ),
+                ),
+            )
+        };
+        let cube_layout = create_layout(
+            "environment_map_filter_cube_bind_group_layout",
+            texture_cube(TextureSampleType::Float { filterable: true }),
+        );
+        let equirectangular_layout = create_layout(
+            "environment_map_filter_equirectangular_bind_group_layout",
+            texture_2d(TextureSampleType::Float { filterable: true }),
+        );
+
+        let sampler = render_device.create_sampler(&SamplerDescriptor {
+            label: Some("environment_map_filter_sampler"),
+            // Wraps the equirectangular images horizontally.
+            address_mode_u: AddressMode::Repeat,
+            address_mode_v: AddressMode::ClampToEdge,
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            mipmap_filter: FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let queue_pipeline = |label: &'static str,
+                              layout: &BindGroupLayout,
+                              shader_defs,
+                              entry_point: &'static str| {
+            pipeline_cache.queue_compute_pipeline(ComputePipelineDescriptor {
+                label: Some(label.into()),
+                layout: vec![layout.clone()],
+                push_constant_ranges: vec![],
+                shader: GENERATED_ENVIRONMENT_MAP_SHADER_HANDLE,
+                shader_defs,
+                entry_point: entry_point.into(),
+            })
+        };
+        let resample_cube = queue_pipeline(
+            "environment_map_resample_cube_pipeline",
+            &cube_layout,
+            vec![],
+            "resample",
+        );
+        let resample_equirectangular = queue_pipeline(
+            "environment_map_resample_equirectangular_pipeline",
+            &equirectangular_layout,
+            vec!["EQUIRECTANGULAR".into()],
+            "resample",
+        );
+        let filter = queue_pipeline(
+            "environment_map_filter_pipeline",
+            &cube_layout,
+            vec![],
+            "filter",
+        );
+
+        Self {
+            cube_layout,
+            equirectangular_layout,
+            sampler,
+            resample_cube,
+            resample_equirectangular,
+            filter,
+        }
+    }
+}
+
+/// Queues the filters of the environment maps that changed.
+fn extract_generated_environment_maps(
+    mut pending: ResMut<PendingEnvironmentMapFilters>,
+    mut image_events: Extract<EventReader<AssetEvent<Image>>>,
+    query: Extract<
+        Query<(
+            Entity,
+            Ref<GeneratedEnvironmentMapLight>,
+            Ref<EnvironmentMapLight>,
+        )>,
+    >,
+) {
+    let changed_images = image_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Added { id } | AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect::<HashSet<_>>();
+
+    pending.0.retain(|entity, _| query.contains(*entity));
+
+    for (entity, generated, environment_map) in &query {
+        let source = match &generated.source {
+            EnvironmentMapSource::Image(image) => ExtractedEnvironmentMapSource::Image(image.id()),
+            EnvironmentMapSource::Faces(faces) => {
+                ExtractedEnvironmentMapSource::Faces(faces.each_ref().map(Handle::id))
+            }
+        };
+        if generated.refresh == EnvironmentMapRefresh::EveryFrame
+            || generated.is_changed()
+            || environment_map.is_changed()
+            || changed_images.iter().any(|id| source.contains(id))
+        {
+            pending.0.insert(
+                entity,
+                EnvironmentMapFilterJob {
+                    source,
+                    diffuse_map: environment_map.diffuse_map.id(),
+                    specular_map: environment_map.specular_map.id(),
+                },
+            );
+        }
+    }
+}
+
+/// Creates the textures and bind groups of the pending filters whose images
+/// are ready.
+fn prepare_environment_map_filters(
+    mut pending: ResMut<PendingEnvironmentMapFilters>,
+    mut prepared: ResMut<PreparedEnvironmentMapFilters>,
+    pipelines: Res<EnvironmentMapFilterPipelines>,
+    pipeline_cache: Res<PipelineCache>,
+    images: Res<RenderAssets<Image>>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+) {
+    prepared.0.clear();
+
+    let pipelines_ready = [
+        pipelines.resample_cube,
+        pipelines.resample_equirectangular,
+        pipelines.filter,
+    ]
+    .into_iter()
+    .all(|id| pipeline_cache.get_compute_pipeline(id).is_some());
+    if !pipelines_ready {
+        return;
+    }
+
+    pending.0.retain(|entity, job| {
+        match prepare_environment_map_filter(
+            job,
+            &pipelines,
+            &images,
+            &render_device,
+            &render_queue,
+        ) {
+            Ok(Some(filter)) => {
+                prepared.0.push(filter);
+                false
+            }
+            Ok(None) => true,
+            Err(message) => {
+                error!("Can't generate the environment map of {entity:?}: {message}");
+                false
+            }
+        }
+    });
+}
+
+/// Prepares the filter of `job`, or returns `None` if its images aren't
+/// uploaded yet.
+fn prepare_environment_map_filter(
+    job: &EnvironmentMapFilterJob,
+    pipelines: &EnvironmentMapFilterPipelines,
+    images: &RenderAssets<Image>,
+    render_device: &RenderDevice,
+    render_queue: &RenderQueue,
+) -> Result<Option<PreparedEnvironmentMapFilter>, &'static str> {
+    let (Some(diffuse_map), Some(specular_map)) =
+        (images.get(job.diffuse_map), images.get(job.specular_map))
+    else {
+        return Ok(None);
+    };
+
+    // The source as a cubemap, or an equirectangular image.
+    let mut copies = Vec::new();
+    let (source_view, source_size, layout, resample_pipeline) = match job.source {
+        ExtractedEnvironmentMapSource::Image(id) => {
+            let Some(image) = images.get(id) else {
+                return Ok(None);
+            };
+            if image.texture.depth_or_array_layers() == 6 {
+                (
+                    create_cube_view(&image.texture, 0, None),
+                    image.texture.width(),
+                    &pipelines.cube_layout,
+                    pipelines.resample_cube,
+                )
+            } else {
+                let view = image.texture.create_view(&TextureViewDescriptor {
+                    dimension: Some(TextureViewDimension::D2),
+                    array_layer_count: Some(1),
+                    ..Default::default()
+                });
+                // A face of a cubemap covers a quarter of the width of the
+                // equirectangular image.
+                (
+                    view,
+                    image.texture.width() / 4,
+                    &pipelines.equirectangular_layout,
+                    pipelines.resample_equirectangular,
+                )
+            }
+        }
+        ExtractedEnvironmentMapSource::Faces(ids) => {
+            let mut faces = Vec::with_capacity(6);
+            for id in ids {
+                let Some(face) = images.get(id) else {
+                    return Ok(None);
+                };
+                faces.push(face);
+            }
+            let size = faces[0].texture.size();
+            let format = faces[0].texture_format;
+            if faces
+                .iter()
+                .any(|face| face.texture.size() != size || face.texture_format != format)
+            {
+                return Err("the faces don't have the same size and format");
+            }
+
+            let cubemap = render_device.create_texture(&TextureDescriptor {
+                label: Some("environment_map_filter_faces_texture"),
+                size: Extent3d {
+                    depth_or_array_layers: 6,
+                    ..size
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format,
+                usage: TextureUsages::COPY_DST | TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            });
+            copies.extend(faces.iter().zip(0..).map(|(face, layer)| FaceCopy {
+                source: face.texture.clone(),
+                destination: cubemap.clone(),
+                layer,
+                size,
+            }));
+            (
+                create_cube_view(&cubemap, 0, None),
+                size.width,
+                &pipelines.cube_layout,
+                pipelines.resample_cube,
+            )
+        }
+    };
+
+    // The source resampled to the size of the specular map, with a full mip
+    // chain so that the filter can sample it without aliasing.
+    let radiance_size = specular_map.texture.width();
+    let radiance_mip_count = mip_level_count(radiance_size);
+    let radiance = render_device.create_texture(&TextureDescriptor {
+        label: Some("environment_map_filter_radiance_texture"),
+        size: Extent3d {
+            width: radiance_size,
+            height: radiance_size,
+            depth_or_array_layers: 6,
+        },
+        mip_level_count: radiance_mip_count,
+        sample_count: 1,
+        dimension: TextureDimension::D2,
+        format: GENERATED_ENVIRONMENT_MAP_FORMAT,
+        usage: TextureUsages::TEXTURE_BINDING | TextureUsages::STORAGE_BINDING,
+        view_formats: &[],
+    });
+
+    let mut dispatches = Vec::new();
+    let mut add_dispatch = |pipeline,
+                            layout: &BindGroupLayout,
+                            source: &TextureView,
+                            output: &Texture,
+                            mip_level: u32,
+                            settings: FilterSettings| {
+        let mut settings = UniformBuffer::from(settings);
+        settings.write_buffer(render_device, render_queue);
+        let output_view = output.create_view(&TextureViewDescriptor {
+            label: Some("environment_map_filter_output_view"),
+            dimension: Some(TextureViewDimension::D2Array),
+            base_mip_level: mip_level,
+            mip_level_count: Some(1),
+            ..Default::default()
+        });
+        let bind_group = render_device.create_bind_group(
+            "environment_map_filter_bind_group",
+            layout,
+            &BindGroupEntries::sequential((source, &pipelines.sampler, &output_view, &settings)),
+        );
+        dispatches.push(FilterDispatch {
+            pipeline,
+            bind_group,
+            size: (output.width() >> mip_level).max(1),
+        });
+    };
+    let resample_settings = |source_level| FilterSettings {
+        roughness: 0.0,
+        sample_count: 1,
+        source_level,
+        source_size: 0.0,
+        source_mip_count: 0.0,
+    };
+
+    add_dispatch(
+        resample_pipeline,
+        layout,
+        &source_view,
+        &radiance,
+        0,
+        resample_settings((source_size as f32 / radiance_size as f32).log2().max(0.0)),
+    );
+    // Each mip level of the radiance cubemap averages 2×2 texels of the
+    // previous one with a bilinear sample.
+    for mip_level in 1..radiance_mip_count {
+        add_dispatch(
+            pipelines.resample_cube,
+            &pipelines.cube_layout,
+            &create_cube_view(&radiance, mip_level - 1, Some(1)),
+            &radiance,
+            mip_level,
+            resample_settings(0.0),
+        );
+    }
+
+    let radiance_view = create_cube_view(&radiance, 0, None);
+    let filter_settings = |roughness, sample_count| FilterSettings {
+        roughness,
+        sample_count,
+        source_level: 0.0,
+        source_size: radiance_size as f32,
+        source_mip_count: radiance_mip_count as f32,
+    };
+    add_dispatch(
+        pipelines.filter,
+        &pipelines.cube_layout,
+        &radiance_view,
+        &diffuse_map.texture,
+        0,
+        filter_settings(-1.0, DIFFUSE_SAMPLE_COUNT),
+    );
+    for mip_level in 0..specular_map.mip_level_count {
+        let roughness = mip_level_roughness(mip_level, specular_map.mip_level_count);
+        let sample_count = if roughness == 0.0 {
+            1
+        } else {
+            SPECULAR_SAMPLE_COUNT
+        };
+        add_dispatch(
+            pipelines.filter,
+            &pipelines.cube_layout,
+            &radiance_view,
+            &specular_map.texture,
+            mip_level,
+            filter_settings(roughness, sample_count),
+        );
+    }
+
+    Ok(Some(PreparedEnvironmentMapFilter { copies, dispatches }))
+}
+
+fn create_cube_view(
+    texture: &Texture,
+    base_mip_level: u32,
+    mip_level_count: Option<u32>,
+) -> TextureView {
+    texture.create_view(&TextureViewDescriptor {
+        label: Some("environment_map_filter_cube_view"),
+        dimension: Some(TextureViewDimension::Cube),
+        base_mip_level,
+        mip_level_count,
+        ..Default::default()
+    })
+}
+
+/// Records the filters prepared this frame.
+struct GenerateEnvironmentMapsNode;
+
+impl Node for GenerateEnvironmentMapsNode {
+    fn run<'w>(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext<'w>,
+        world: &'w World,
+    ) -> Result<(), NodeRunError> {
+        let prepared = world.resource::<PreparedEnvironmentMapFilters>();
+        if prepared.0.is_empty() {
+            return Ok(());
+        }
+        let pipeline_cache = world.resource::<PipelineCache>();
+
+        let command_encoder = render_context.command_encoder();
+        for filter in &prepared.0 {
+            for copy in &filter.copies {
+                command_encoder.copy_texture_to_texture(
+                    copy.source.as_image_copy(),
+                    ImageCopyTexture {
+                        texture: &copy.destination,
+                        mip_level: 0,
+                        origin: Origin3d {
+                            x: 0,
+                            y: 0,
+                            z: copy.layer,
+                        },
+                        aspect: TextureAspect::All,
+                    },
+                    Extent3d {
+                        depth_or_array_layers: 1,
+                        ..copy.size
+                    },
+                );
+            }
+        }
+
+        let mut pass = command_encoder.begin_compute_pass(&ComputePassDescriptor {
+            label: Some("generate_environment_maps_pass"),
+            timestamp_writes: None,
+        });
+        for dispatch in prepared.0.iter().flat_map(|filter| &filter.dispatches) {
+            let Some(pipeline) = pipeline_cache.get_compute_pipeline(dispatch.pipeline) else {
+                continue;
+            };
+            let workgroups = dispatch.size.div_ceil(8);
+            pass.set_pipeline(pipeline);
+            pass.set_bind_group(0, &dispatch.bind_group, &[]);
+            pass.dispatch_workgroups(workgroups, workgroups, 6);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn specular_mip_levels_span_all_roughnesses() {
+        let mip_level_count = mip_level_count(256);
+        assert_eq!(mip_level_count, 9);
+        assert_eq!(mip_level_roughness(0, mip_level_count), 0.0);
+        assert_eq!(mip_level_roughness(4, mip_level_count), 0.5);
+        assert_eq!(mip_level_roughness(8, mip_level_count), 1.0);
+        assert_eq!(mip_level_roughness(0, 1), 0.0);
+    }
+}
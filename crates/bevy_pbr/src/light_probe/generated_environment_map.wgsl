@@ -0,0 +1,165 @@
+// Filters a source environment into the diffuse and specular cubemaps of an
+// `EnvironmentMapLight`.
+//
+// The source is first resampled into a cubemap with a full mip chain, the
+// radiance cubemap, which the `filter` entry point then convolves with the
+// Lambertian and GGX distributions, as the glTF IBL Sampler does.
+
+#import bevy_pbr::utils::PI
+
+struct FilterSettings {
+    // The perceptual roughness of the specular mip level being written, or a
+    // negative value when writing the diffuse map.
+    roughness: f32,
+    sample_count: u32,
+    // The mip level of the source sampled by `resample`.
+    source_level: f32,
+    // The size of the faces of the radiance cubemap, in texels.
+    source_size: f32,
+    source_mip_count: f32,
+}
+
+#ifdef EQUIRECTANGULAR
+@group(0) @binding(0) var source: texture_2d<f32>;
+#else
+@group(0) @binding(0) var source: texture_cube<f32>;
+#endif
+@group(0) @binding(1) var source_sampler: sampler;
+@group(0) @binding(2) var output: texture_storage_2d_array<rgba16float, write>;
+@group(0) @binding(3) var<uniform> settings: FilterSettings;
+
+// The direction of the center of texel `texel` of face `face` of a cubemap
+// with faces of `size` texels.
+fn cube_direction(face: u32, texel: vec2<u32>, size: u32) -> vec3<f32> {
+    let uv = (vec2<f32>(texel) + 0.5) / f32(size) * 2.0 - 1.0;
+    var direction: vec3<f32>;
+    switch face {
+        case 0u: { direction = vec3(1.0, -uv.y, -uv.x); }
+        case 1u: { direction = vec3(-1.0, -uv.y, uv.x); }
+        case 2u: { direction = vec3(uv.x, 1.0, uv.y); }
+        case 3u: { direction = vec3(uv.x, -1.0, -uv.y); }
+        case 4u: { direction = vec3(uv.x, -uv.y, 1.0); }
+        default: { direction = vec3(-uv.x, -uv.y, -1.0); }
+    }
+    return normalize(direction);
+}
+
+fn sample_source(direction: vec3<f32>, level: f32) -> vec3<f32> {
+#ifdef EQUIRECTANGULAR
+    // Cubemaps are sampled with a flipped z, see `environment_map.wgsl`, so
+    // flip it back to look up the equirectangular image in world space.
+    let world_direction = vec3(direction.xy, -direction.z);
+    let uv = vec2(
+        atan2(world_direction.z, world_direction.x) / (2.0 * PI) + 0.5,
+        acos(clamp(world_direction.y, -1.0, 1.0)) / PI,
+    );
+    return textureSampleLevel(source, source_sampler, uv, level).rgb;
+#else
+    return textureSampleLevel(source, source_sampler, direction, level).rgb;
+#endif
+}
+
+fn hammersley(index: u32, count: u32) -> vec2<f32> {
+    return vec2(f32(index) / f32(count), f32(reverseBits(index)) * 2.3283064365386963e-10);
+}
+
+// Rotates `direction`, given around the z axis, to be around `normal`.
+fn to_world(direction: vec3<f32>, normal: vec3<f32>) -> vec3<f32> {
+    var up = vec3(0.0, 1.0, 0.0);
+    if abs(normal.y) > 0.999 {
+        up = vec3(1.0, 0.0, 0.0);
+    }
+    let tangent = normalize(cross(up, normal));
+    let bitangent = cross(normal, tangent);
+    return tangent * direction.x + bitangent * direction.y + normal * direction.z;
+}
+
+// The mip level of the radiance cubemap whose texels cover the solid angle of
+// one of `settings.sample_count` samples drawn with probability density `pdf`,
+// as described in "GPU-Based Importance Sampling", GPU Gems 3, chapter 20.
+fn sample_level(pdf: f32) -> f32 {
+    let sample_solid_angle = 1.0 / (f32(settings.sample_count) * pdf + 0.0001);
+    let texel_solid_angle = 4.0 * PI / (6.0 * settings.source_size * settings.source_size);
+    let level = 0.5 * log2(sample_solid_angle / texel_solid_angle) + 1.0;
+    return clamp(level, 0.0, settings.source_mip_count - 1.0);
+}
+
+fn filter_diffuse(normal: vec3<f32>) -> vec3<f32> {
+    // Cosine-weighted samples make the estimator the plain average of the
+    // radiance.
+    var radiance = vec3(0.0);
+    for (var index = 0u; index < settings.sample_count; index += 1u) {
+        let xi = hammersley(index, settings.sample_count);
+        let phi = 2.0 * PI * xi.x;
+        let cos_theta = sqrt(1.0 - xi.y);
+        let sin_theta = sqrt(xi.y);
+        let light = to_world(vec3(cos(phi) * sin_theta, sin(phi) * sin_theta, cos_theta), normal);
+        let pdf = cos_theta / PI;
+        radiance += sample_source(light, sample_level(pdf));
+    }
+    return radiance / f32(settings.sample_count);
+}
+
+fn filter_specular(normal: vec3<f32>) -> vec3<f32> {
+    let alpha = settings.roughness * settings.roughness;
+    if alpha == 0.0 {
+        return sample_source(normal, 0.0);
+    }
+
+    // The view and normal directions are assumed to be the reflection
+    // direction, as in the split-sum approximation.
+    let alpha_2 = alpha * alpha;
+    var radiance = vec3(0.0);
+    var weight = 0.0;
+    for (var index = 0u; index < settings.sample_count; index += 1u) {
+        let xi = hammersley(index, settings.sample_count);
+        let phi = 2.0 * PI * xi.x;
+        let cos_theta = sqrt((1.0 - xi.y) / (1.0 + (alpha_2 - 1.0) * xi.y));
+        let sin_theta = sqrt(1.0 - cos_theta * cos_theta);
+        let half_vector =
+            to_world(vec3(cos(phi) * sin_theta, sin(phi) * sin_theta, cos_theta), normal);
+        let light = 2.0 * dot(normal, half_vector) * half_vector - normal;
+        let n_dot_l = dot(normal, light);
+        if n_dot_l <= 0.0 {
+            continue;
+        }
+
+        // With the view along the normal, the PDF of the light direction
+        // reduces to D(h) / 4.
+        let d = alpha_2 / (PI * pow(cos_theta * cos_theta * (alpha_2 - 1.0) + 1.0, 2.0));
+        radiance += sample_source(light, sample_level(d / 4.0)) * n_dot_l;
+        weight += n_dot_l;
+    }
+    return radiance / max(weight, 0.0001);
+}
+
+// Resamples the source into a mip level of the radiance cubemap.
+@compute @workgroup_size(8, 8, 1)
+fn resample(@builtin(global_invocation_id) id: vec3<u32>) {
+    let size = textureDimensions(output).x;
+    if any(id.xy >= vec2(size)) {
+        return;
+    }
+
+    let direction = cube_direction(id.z, id.xy, size);
+    textureStore(output, id.xy, id.z, vec4(sample_source(direction, settings.source_level), 1.0));
+}
+
+// Convolves the radiance cubemap into a mip level of the diffuse or specular
+// cubemap.
+@compute @workgroup_size(8, 8, 1)
+fn filter(@builtin(global_invocation_id) id: vec3<u32>) {
+    let size = textureDimensions(output).x;
+    if any(id.xy >= vec2(size)) {
+        return;
+    }
+
+    let normal = cube_direction(id.z, id.xy, size);
+    var radiance: vec3<f32>;
+    if settings.roughness < 0.0 {
+        radiance = filter_diffuse(normal);
+    } else {
+        radiance = filter_specular(normal);
+    }
+    textureStore(output, id.xy, id.z, vec4(radiance, 1.0));
+}
@@ -25,6 +25,31 @@ fn transpose_affine_matrix(matrix: mat3x4<f32>) -> mat4x4<f32> {
     return transpose(matrix4x4);
 }
 
+// Returns how much a light probe contributes to a fragment: 1 inside it,
+// fading out to 0 over its blend distance from its faces, and 0 outside.
+fn light_probe_weight(light_probe: LightProbe, world_position: vec3<f32>) -> f32 {
+    let inverse_transform =
+        transpose_affine_matrix(light_probe.inverse_transpose_transform);
+    let probe_space_pos = (inverse_transform * vec4<f32>(world_position, 1.0f)).xyz;
+    let edge_distance = vec3(0.5f) - abs(probe_space_pos);
+    if (any(edge_distance < vec3(0.0f))) {
+        return 0.0f;
+    }
+    if (light_probe.blend_distance <= 0.0f) {
+        return 1.0f;
+    }
+
+    // The rows of the inverse transform are scaled by the inverse of the size
+    // of the light probe along each axis, which converts the blend distance to
+    // light probe space.
+    let blend_extent = light_probe.blend_distance * vec3(
+        length(light_probe.inverse_transpose_transform[0].xyz),
+        length(light_probe.inverse_transpose_transform[1].xyz),
+        length(light_probe.inverse_transpose_transform[2].xyz));
+    let falloff = saturate(edge_distance / blend_extent);
+    return min(falloff.x, min(falloff.y, falloff.z));
+}
+
 // Searches for a light probe that contains the fragment.
 //
 // TODO: Interpolate between multiple light probes.
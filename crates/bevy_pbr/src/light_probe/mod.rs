@@ -38,12 +38,17 @@ use crate::{
     },
 };
 
-use self::irradiance_volume::IrradianceVolume;
+use self::{
+    generated_environment_map::GeneratedEnvironmentMapPlugin, irradiance_volume::IrradianceVolume,
+    reflection_probe::ReflectionProbePlugin,
+};
 
 pub const LIGHT_PROBE_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(8954249792581071582);
 
 pub mod environment_map;
+pub mod generated_environment_map;
 pub mod irradiance_volume;
+pub mod reflection_probe;
 
 /// The maximum number of each type of light probe that each view will consider.
 ///
@@ -106,6 +111,26 @@ pub struct LightProbePlugin;
 #[reflect(Component, Default)]
 pub struct LightProbe;
 
+/// Fades the light of a [`LightProbe`] out near the faces of its cuboid.
+///
+/// Without this component, the light of a light probe stops abruptly at its
+/// faces. With it, the reflection probes containing a fragment are blended
+/// together, and with the view environment map, which hides the seams between
+/// adjacent reflection probes.
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component, Default)]
+pub struct LightProbeBlend {
+    /// The distance from the faces of the cuboid over which the light fades
+    /// out, in world units.
+    pub distance: f32,
+}
+
+impl Default for LightProbeBlend {
+    fn default() -> Self {
+        Self { distance: 0.5 }
+    }
+}
+
 /// A GPU type that stores information about a light probe.
 #[derive(Clone, Copy, ShaderType, Default)]
 struct RenderLightProbe {
@@ -124,6 +149,10 @@ struct RenderLightProbe {
     ///
     /// See the comment in [`EnvironmentMapLight`] for details.
     intensity: f32,
+
+    /// The distance over which the light fades out near the faces of the
+    /// light probe, in world units, from its [`LightProbeBlend`].
+    blend_distance: f32,
 }
 
 /// A per-view shader uniform that specifies all the light probes that the view
@@ -189,6 +218,10 @@ where
     // See the comment in [`EnvironmentMapLight`] for details.
     intensity: f32,
 
+    // The distance over which the light fades out near the faces of the light
+    // probe, from its [`LightProbeBlend`].
+    blend_distance: f32,
+
     // The IDs of all assets associated with this light probe.
     //
     // Because each type of light probe component may reference different types
@@ -318,7 +351,9 @@ impl Plugin for LightProbePlugin {
 
         app.register_type::<LightProbe>()
             .register_type::<EnvironmentMapLight>()
-            .register_type::<IrradianceVolume>();
+            .register_type::<LightProbeBlend>()
+            .register_type::<IrradianceVolume>()
+            .add_plugins((GeneratedEnvironmentMapPlugin, ReflectionProbePlugin));
     }
 
     fn finish(&self, app: &mut App) {
@@ -342,7 +377,9 @@ impl Plugin for LightProbePlugin {
 /// to views, performing frustum culling and distance sorting in the process.
 fn gather_light_probes<C>(
     image_assets: Res<RenderAssets<Image>>,
-    light_probe_query: Extract<Query<(&GlobalTransform, &C), With<LightProbe>>>,
+    light_probe_query: Extract<
+        Query<(&GlobalTransform, &C, Option<&LightProbeBlend>), With<LightProbe>>,
+    >,
     view_query: Extract<Query<(Entity, &GlobalTransform, &Frustum, Option<&C>), With<Camera3d>>>,
     mut reflection_probes: Local<Vec<LightProbeInfo<C>>>,
     mut view_reflection_probes: Local<Vec<LightProbeInfo<C>>>,
@@ -503,7 +540,11 @@ where
     /// [`LightProbeInfo`]. This is done for every light probe in the scene
     /// every frame.
     fn new(
-        (light_probe_transform, environment_map): (&GlobalTransform, &C),
+        (light_probe_transform, environment_map, blend): (
+            &GlobalTransform,
+            &C,
+            Option<&LightProbeBlend>,
+        ),
         image_assets: &RenderAssets<Image>,
     ) -> Option<LightProbeInfo<C>> {
         environment_map.id(image_assets).map(|id| LightProbeInfo {
@@ -511,6 +552,7 @@ where
             inverse_transform: light_probe_transform.compute_matrix().inverse(),
             asset_id: id,
             intensity: environment_map.intensity(),
+            blend_distance: blend.map_or(0.0, |blend| blend.distance),
         })
     }
 
@@ -608,6 +650,7 @@ where
                 ],
                 texture_index: cubemap_index as i32,
                 intensity: light_probe.intensity,
+                blend_distance: light_probe.blend_distance,
             });
         }
     }
@@ -622,6 +665,7 @@ where
             inverse_transform: self.inverse_transform,
             affine_transform: self.affine_transform,
             intensity: self.intensity,
+            blend_distance: self.blend_distance,
             asset_id: self.asset_id.clone(),
         }
     }
@@ -0,0 +1,374 @@
+//! Reflection probes captured at runtime.
+//!
+//! A [`ReflectionProbe`] renders the scene around its position with six
+//! cameras, one per face of a cubemap, and filters the faces with a
+//! [`GeneratedEnvironmentMapLight`]. Unlike a view environment map, the
+//! resulting reflection probe only lights the fragments inside its
+//! [`LightProbe`] cuboid, so that interiors don't reflect the sky.
+
+use std::f32::consts::FRAC_PI_2;
+
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_asset::{Assets, Handle};
+use bevy_core_pipeline::{
+    core_3d::Camera3dBundle,
+    tonemapping::{DebandDither, Tonemapping},
+};
+use bevy_ecs::prelude::*;
+use bevy_math::Vec3;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    camera::{Camera, PerspectiveProjection, Projection, RenderTarget},
+    render_resource::{Extent3d, TextureDimension, TextureFormat, TextureUsages},
+    texture::Image,
+};
+use bevy_transform::{
+    prelude::{GlobalTransform, Transform},
+    TransformSystem,
+};
+
+use super::{
+    generated_environment_map::{EnvironmentMapRefresh, GeneratedEnvironmentMapLight},
+    LightProbe,
+};
+
+/// The size of the faces of the diffuse cubemaps of the captured reflection
+/// probes, in texels.
+const CAPTURED_DIFFUSE_SIZE: u32 = 32;
+
+/// A reflection probe whose environment map is rendered from its position at
+/// runtime, rather than baked offline.
+///
+/// The reflection probe adds a [`LightProbe`] to its entity, whose transform
+/// sets the region lit by the probe as for a [`ReflectionProbeBundle`], and a
+/// [`GeneratedEnvironmentMapLight`] filling its
+/// [`EnvironmentMapLight`](super::environment_map::EnvironmentMapLight). Add a
+/// [`LightProbeBlend`](super::LightProbeBlend) to fade it into the reflection
+/// probes around it.
+///
+/// The scene is captured when the probe is spawned, and again after each call
+/// to [`ReflectionProbe::recapture`], for example when the time of day changes.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_math::Vec3;
+/// # use bevy_pbr::reflection_probe::ReflectionProbe;
+/// # use bevy_render::prelude::SpatialBundle;
+/// # use bevy_transform::prelude::Transform;
+/// fn spawn_room_probe(mut commands: Commands) {
+///     commands.spawn((
+///         ReflectionProbe::capture(),
+///         // The room is 8×3×6 meters.
+///         SpatialBundle::from_transform(
+///             Transform::from_xyz(0.0, 1.5, 0.0).with_scale(Vec3::new(8.0, 3.0, 6.0)),
+///         ),
+///     ));
+/// }
+/// ```
+///
+/// [`ReflectionProbeBundle`]: super::environment_map::ReflectionProbeBundle
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Default)]
+pub struct ReflectionProbe {
+    /// The size of the faces of the captured cubemap, in texels. It should be
+    /// a power of two.
+    pub resolution: u32,
+    /// The near plane of the capture cameras.
+    ///
+    /// Geometry closer to the position of the probe isn't captured.
+    pub near: f32,
+    /// The far plane of the capture cameras, which bounds the lights and
+    /// shadows they take into account.
+    pub far: f32,
+    /// Scale factor applied to the light of the reflection probe.
+    pub intensity: f32,
+    /// When the scene is captured.
+    pub refresh: ReflectionProbeRefresh,
+    /// Set by [`ReflectionProbe::recapture`], and cleared once the capture
+    /// cameras are activated.
+    #[reflect(ignore)]
+    capture_requested: bool,
+}
+
+impl Default for ReflectionProbe {
+    fn default() -> Self {
+        Self {
+            resolution: 256,
+            near: 0.1,
+            far: 1000.0,
+            intensity: 1.0,
+            refresh: ReflectionProbeRefresh::default(),
+            capture_requested: true,
+        }
+    }
+}
+
+impl ReflectionProbe {
+    /// Creates a reflection probe capturing the scene around it once spawned.
+    pub fn capture() -> Self {
+        Self::default()
+    }
+
+    /// Captures the scene around the reflection probe again.
+    pub fn recapture(&mut self) {
+        self.capture_requested = true;
+    }
+}
+
+/// When a [`ReflectionProbe`] captures the scene.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Default)]
+pub enum ReflectionProbeRefresh {
+    /// When spawned, and after each call to [`ReflectionProbe::recapture`].
+    #[default]
+    OnRequest,
+    /// Every frame, for dynamic scenes. This renders the scene six more times
+    /// per frame.
+    EveryFrame,
+}
+
+/// The progress of a capture of a [`ReflectionProbe`].
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum CaptureState {
+    Idle,
+    /// The capture cameras render the faces this frame.
+    Rendering,
+    /// The faces rendered last frame are filtered this frame.
+    Filtering,
+}
+
+/// The capture cameras of a [`ReflectionProbe`].
+#[derive(Component)]
+struct ReflectionProbeCapture {
+    cameras: [Entity; 6],
+    resolution: u32,
+    state: CaptureState,
+}
+
+/// A capture camera of the [`ReflectionProbe`] on the `probe` entity, despawned
+/// with it.
+#[derive(Component)]
+struct ReflectionProbeCamera {
+    probe: Entity,
+}
+
+/// The directions and up vectors of the capture cameras, in the order of the
+/// faces of a cubemap.
+///
+/// Cubemaps are sampled with a flipped z, so the +Z face looks toward -Z.
+const FACE_ORIENTATIONS: [(Vec3, Vec3); 6] = [
+    (Vec3::X, Vec3::Y),
+    (Vec3::NEG_X, Vec3::Y),
+    (Vec3::Y, Vec3::Z),
+    (Vec3::NEG_Y, Vec3::NEG_Z),
+    (Vec3::NEG_Z, Vec3::Y),
+    (Vec3::Z, Vec3::Y),
+];
+
+/// Adds support for [`ReflectionProbe`].
+pub struct ReflectionProbePlugin;
+
+impl Plugin for ReflectionProbePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<ReflectionProbe>().add_systems(
+            PostUpdate,
+            (
+                despawn_reflection_probe_cameras,
+                spawn_reflection_probe_cameras,
+                update_reflection_probe_captures,
+            )
+                .chain()
+                .before(TransformSystem::TransformPropagate),
+        );
+    }
+}
+
+/// Creates the render target of a capture camera.
+fn create_face_image(resolution: u32) -> Image {
+    let mut image = Image::new_fill(
+        Extent3d {
+            width: resolution,
+            height: resolution,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0; 8],
+        TextureFormat::Rgba16Float,
+        Default::default(),
+    );
+    image.texture_descriptor.usage = TextureUsages::TEXTURE_BINDING
+        | TextureUsages::COPY_SRC
+        | TextureUsages::COPY_DST
+        | TextureUsages::RENDER_ATTACHMENT;
+    image
+}
+
+/// Despawns the capture cameras of the despawned reflection probes.
+fn despawn_reflection_probe_cameras(
+    mut commands: Commands,
+    cameras: Query<(Entity, &ReflectionProbeCamera)>,
+    probes: Query<(), With<ReflectionProbe>>,
+) {
+    for (entity, camera) in &cameras {
+        if !probes.contains(camera.probe) {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// Spawns the capture cameras of the new reflection probes, and respawns them
+/// when their resolution changes.
+fn spawn_reflection_probe_cameras(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    mut probes: Query<
+        (
+            Entity,
+            &ReflectionProbe,
+            Option<&ReflectionProbeCapture>,
+            Option<&mut GeneratedEnvironmentMapLight>,
+        ),
+        Changed<ReflectionProbe>,
+    >,
+) {
+    for (entity, probe, capture, generated) in &mut probes {
+        let resolution = probe.resolution.max(1);
+        if let (Some(capture), Some(mut generated)) = (capture, generated) {
+            if capture.resolution == resolution {
+                if generated.intensity != probe.intensity {
+                    generated.intensity = probe.intensity;
+                }
+                continue;
+            }
+        }
+        if let Some(capture) = capture {
+            for camera in capture.cameras {
+                if let Some(mut camera) = commands.get_entity(camera) {
+                    camera.despawn();
+                }
+            }
+        }
+
+        let faces: [Handle<Image>; 6] =
+            std::array::from_fn(|_| images.add(create_face_image(resolution)));
+        let cameras = faces.each_ref().map(|face| {
+            commands
+                .spawn((
+                    Camera3dBundle {
+                        camera: Camera {
+                            // The faces are filtered the frame after they are
+                            // rendered, so the order doesn't matter.
+                            order: -1,
+                            is_active: false,
+                            target: RenderTarget::Image(face.clone()),
+                            hdr: true,
+                            ..Default::default()
+                        },
+                        projection: Projection::Perspective(PerspectiveProjection {
+                            fov: FRAC_PI_2,
+                            aspect_ratio: 1.0,
+                            near: probe.near,
+                            far: probe.far,
+                        }),
+                        // The filter expects linear radiance.
+                        tonemapping: Tonemapping::None,
+                        dither: DebandDither::Disabled,
+                        ..Default::default()
+                    },
+                    ReflectionProbeCamera { probe: entity },
+                ))
+                .id()
+        });
+
+        commands.entity(entity).insert((
+            LightProbe,
+            ReflectionProbeCapture {
+                cameras,
+                resolution,
+                state: CaptureState::Idle,
+            },
+            GeneratedEnvironmentMapLight {
+                intensity: probe.intensity,
+                diffuse_size: CAPTURED_DIFFUSE_SIZE,
+                specular_size: resolution,
+                // Filtered when the capture is done.
+                refresh: EnvironmentMapRefresh::OnChange,
+                ..GeneratedEnvironmentMapLight::from_faces(faces)
+            },
+        ));
+    }
+}
+
+/// Activates the capture cameras of the reflection probes to capture, and
+/// filters the faces they rendered the frame after.
+fn update_reflection_probe_captures(
+    mut probes: Query<(
+        &mut ReflectionProbe,
+        &GlobalTransform,
+        &mut ReflectionProbeCapture,
+        &mut GeneratedEnvironmentMapLight,
+    )>,
+    mut cameras: Query<(&mut Camera, &mut Transform, &mut Projection)>,
+) {
+    for (mut probe, probe_transform, mut capture, mut generated) in &mut probes {
+        let every_frame = probe.refresh == ReflectionProbeRefresh::EveryFrame;
+        if capture.state == CaptureState::Rendering {
+            generated.set_changed();
+            capture.state = CaptureState::Filtering;
+        } else {
+            capture.state = CaptureState::Idle;
+        }
+        if probe.capture_requested || every_frame {
+            probe.bypass_change_detection().capture_requested = false;
+            capture.state = CaptureState::Rendering;
+        }
+
+        let rendering = capture.state == CaptureState::Rendering;
+        let position = probe_transform.translation();
+        for (&camera_entity, &(direction, up)) in capture.cameras.iter().zip(&FACE_ORIENTATIONS) {
+            let Ok((mut camera, mut transform, mut projection)) = cameras.get_mut(camera_entity)
+            else {
+                continue;
+            };
+            if camera.is_active != rendering {
+                camera.is_active = rendering;
+            }
+            if !rendering {
+                continue;
+            }
+            // The cameras aren't children of the probe, whose scale sets the
+            // region it lights.
+            *transform = Transform::from_translation(position).looking_to(direction, up);
+            if let Projection::Perspective(perspective) = &mut *projection {
+                if perspective.near != probe.near || perspective.far != probe.far {
+                    perspective.near = probe.near;
+                    perspective.far = probe.far;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn face_orientations_match_cubemap_lookups() {
+        // The center of each face, as looked up by `environment_map.wgsl`,
+        // which flips the z axis of world space directions.
+        let face_centers = [
+            Vec3::X,
+            Vec3::NEG_X,
+            Vec3::Y,
+            Vec3::NEG_Y,
+            Vec3::Z,
+            Vec3::NEG_Z,
+        ];
+        for ((direction, up), center) in FACE_ORIENTATIONS.into_iter().zip(face_centers) {
+            let forward = *Transform::IDENTITY.looking_to(direction, up).forward();
+            let lookup = Vec3::new(forward.x, forward.y, -forward.z);
+            assert!(lookup.abs_diff_eq(center, 1e-6), "{lookup} != {center}");
+        }
+    }
+}
@@ -60,7 +60,9 @@ pub struct LightmapPlugin;
 /// When assigned to an entity that contains a [`Mesh`] and a
 /// [`StandardMaterial`](crate::StandardMaterial), if the mesh has a second UV
 /// layer ([`ATTRIBUTE_UV_1`](bevy_render::mesh::Mesh::ATTRIBUTE_UV_1)), then
-/// the lightmap will render using those UVs.
+/// the lightmap will render using those UVs. Meshes without them can have them
+/// generated with
+/// [`Mesh::generate_lightmap_uvs`](bevy_render::mesh::Mesh::generate_lightmap_uvs).
 #[derive(Component, Clone, Reflect)]
 #[reflect(Component, Default)]
 pub struct Lightmap {
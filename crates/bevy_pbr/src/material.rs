@@ -12,12 +12,16 @@ use bevy_core_pipeline::{
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::{
     prelude::*,
-    system::{lifetimeless::SRes, SystemParamItem},
+    system::{
+        lifetimeless::{Read, SRes},
+        SystemParamItem,
+    },
 };
 use bevy_reflect::Reflect;
 use bevy_render::{
     camera::Projection,
     camera::TemporalJitter,
+    extract_component::ExtractComponentPlugin,
     extract_instances::{ExtractInstancesPlugin, ExtractedInstances},
     extract_resource::ExtractResource,
     mesh::{Mesh, MeshVertexBufferLayout},
@@ -172,6 +176,10 @@ pub trait Material: Asset + AsBindGroup + Clone + Sized {
 
     /// Customizes the default [`RenderPipelineDescriptor`] for a specific entity using the entity's
     /// [`MaterialPipelineKey`] and [`MeshVertexBufferLayout`] as input.
+    ///
+    /// When the view's camera has a stencil buffer, the key's mesh key contains
+    /// [`MeshPipelineKey::DEPTH_STENCIL`] and the descriptor's depth stencil state can be
+    /// given stencil compare functions, operations and masks here.
     #[allow(unused_variables)]
     #[inline]
     fn specialize(
@@ -193,6 +201,13 @@ pub struct MaterialPlugin<M: Material> {
     /// When it is enabled, it will automatically add the [`PrepassPlugin`]
     /// required to make the prepass work on this Material.
     pub prepass_enabled: bool,
+    /// Controls if the meshes with a transparent [`AlphaMode`] of this Material are drawn in the
+    /// [`Transparent3dPrepass`](bevy_core_pipeline::prepass::Transparent3dPrepass), for their depth and
+    /// normals to be taken into account by screen-space effects like SSAO.
+    ///
+    /// This requires `prepass_enabled`. The prepass can also be enabled for individual meshes with
+    /// the [`TransparentPrepass`] component.
+    pub transparent_prepass_enabled: bool,
     pub _marker: PhantomData<M>,
 }
 
@@ -200,6 +215,7 @@ impl<M: Material> Default for MaterialPlugin<M> {
     fn default() -> Self {
         Self {
             prepass_enabled: true,
+            transparent_prepass_enabled: false,
             _marker: Default::default(),
         }
     }
@@ -210,13 +226,16 @@ where
     M::Data: PartialEq + Eq + Hash + Clone,
 {
     fn build(&self, app: &mut App) {
-        app.init_asset::<M>()
-            .add_plugins(ExtractInstancesPlugin::<AssetId<M>>::extract_visible());
+        app.init_asset::<M>().add_plugins((
+            ExtractInstancesPlugin::<AssetId<M>>::retained(),
+            ExtractComponentPlugin::<ViewMaterialOverride<M>>::default(),
+        ));
 
         if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app
                 .init_resource::<DrawFunctions<Shadow>>()
                 .add_render_command::<Shadow, DrawPrepass<M>>()
+                .add_render_command::<Shadow, DrawGpuCulledShadow<M>>()
                 .add_render_command::<Transmissive3d, DrawMaterial<M>>()
                 .add_render_command::<Transparent3d, DrawMaterial<M>>()
                 .add_render_command::<Opaque3d, DrawMaterial<M>>()
@@ -249,6 +268,12 @@ where
         if self.prepass_enabled {
             app.add_plugins(PrepassPlugin::<M>::default());
         }
+
+        if self.transparent_prepass_enabled {
+            if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+                render_app.init_resource::<TransparentPrepassEnabled<M>>();
+            }
+        }
     }
 
     fn finish(&self, app: &mut App) {
@@ -380,16 +405,24 @@ type DrawMaterial<M> = (
 /// Sets the bind group for a given [`Material`] at the configured `I` index.
 pub struct SetMaterialBindGroup<M: Material, const I: usize>(PhantomData<M>);
 impl<P: PhaseItem, M: Material, const I: usize> RenderCommand<P> for SetMaterialBindGroup<M, I> {
-    type Param = (SRes<RenderMaterials<M>>, SRes<RenderMaterialInstances<M>>);
-    type ViewQuery = ();
+    type Param = (
+        SRes<RenderMaterials<M>>,
+        SRes<RenderMaterialInstances<M>>,
+        SRes<ExtractedInstances<MaterialOverrideTag>>,
+    );
+    type ViewQuery = Option<Read<ExtractedViewMaterialOverride<M>>>;
     type ItemQuery = ();
 
     #[inline]
     fn render<'w>(
         item: &P,
-        _view: (),
+        material_override: Option<&'w ExtractedViewMaterialOverride<M>>,
         _item_query: Option<()>,
-        (materials, material_instances): SystemParamItem<'w, '_, Self::Param>,
+        (materials, material_instances, material_override_tags): SystemParamItem<
+            'w,
+            '_,
+            Self::Param,
+        >,
         pass: &mut TrackedRenderPass<'w>,
     ) -> RenderCommandResult {
         let materials = materials.into_inner();
@@ -398,7 +431,15 @@ impl<P: PhaseItem, M: Material, const I: usize> RenderCommand<P> for SetMaterial
         let Some(material_asset_id) = material_instances.get(&item.entity()) else {
             return RenderCommandResult::Failure;
         };
-        let Some(material) = materials.get(material_asset_id) else {
+        let Some(material_asset_id) = view_material(
+            material_override,
+            &material_override_tags,
+            &item.entity(),
+            *material_asset_id,
+        ) else {
+            return RenderCommandResult::Failure;
+        };
+        let Some(material) = materials.get(&material_asset_id) else {
             return RenderCommandResult::Failure;
         };
         pass.set_bind_group(I, &material.bind_group, &[]);
@@ -469,6 +510,8 @@ pub fn queue_material_meshes<M: Material>(
     mut render_mesh_instances: ResMut<RenderMeshInstances>,
     render_material_instances: Res<RenderMaterialInstances<M>>,
     render_lightmaps: Res<RenderLightmaps>,
+    material_override_tags: Res<ExtractedInstances<MaterialOverrideTag>>,
+    cascade_debug_visualization: Res<CascadeDebugVisualization>,
     mut views: Query<(
         &ExtractedView,
         &VisibleEntities,
@@ -492,6 +535,7 @@ pub fn queue_material_meshes<M: Material>(
         (
             Has<RenderViewLightProbes<EnvironmentMapLight>>,
             Has<RenderViewLightProbes<IrradianceVolume>>,
+            Option<&ExtractedViewMaterialOverride<M>>,
         ),
     )>,
 ) where
@@ -512,7 +556,7 @@ pub fn queue_material_meshes<M: Material>(
         mut alpha_mask_phase,
         mut transmissive_phase,
         mut transparent_phase,
-        (has_environment_maps, has_irradiance_volumes),
+        (has_environment_maps, has_irradiance_volumes, material_override),
     ) in &mut views
     {
         let draw_opaque_pbr = opaque_draw_functions.read().id::<DrawMaterial<M>>();
@@ -551,6 +595,18 @@ pub fn queue_material_meshes<M: Material>(
             view_key |= MeshPipelineKey::IRRADIANCE_VOLUME;
         }
 
+        if material_override.is_some_and(|material_override| material_override.unlit) {
+            view_key |= MeshPipelineKey::FORCE_UNLIT;
+        }
+
+        if camera_3d.is_some_and(|camera_3d| camera_3d.stencil.is_some()) {
+            view_key |= MeshPipelineKey::DEPTH_STENCIL;
+        }
+
+        if cascade_debug_visualization.enabled {
+            view_key |= MeshPipelineKey::DEBUG_SHADOW_CASCADES;
+        }
+
         if let Some(projection) = projection {
             view_key |= match projection {
                 Projection::Perspective(_) => MeshPipelineKey::VIEW_PROJECTION_PERSPECTIVE,
@@ -592,13 +648,21 @@ pub fn queue_material_meshes<M: Material>(
             let Some(material_asset_id) = render_material_instances.get(visible_entity) else {
                 continue;
             };
+            let Some(view_material_asset_id) = view_material(
+                material_override,
+                &material_override_tags,
+                visible_entity,
+                *material_asset_id,
+            ) else {
+                continue;
+            };
             let Some(mesh_instance) = render_mesh_instances.get_mut(visible_entity) else {
                 continue;
             };
             let Some(mesh) = render_meshes.get(mesh_instance.mesh_asset_id) else {
                 continue;
             };
-            let Some(material) = render_materials.get(material_asset_id) else {
+            let Some(material) = render_materials.get(&view_material_asset_id) else {
                 continue;
             };
 
@@ -647,6 +711,11 @@ pub fn queue_material_meshes<M: Material>(
             };
 
             mesh_instance.material_bind_group_id = material.get_bind_group_id();
+            // The batches are built from the material bind group of the entity, which is shared
+            // by all the views: substituted materials can't be batched.
+            if view_material_asset_id != *material_asset_id {
+                mesh_instance.automatic_batching = false;
+            }
 
             match material.properties.alpha_mode {
                 AlphaMode::Opaque => {
@@ -0,0 +1,51 @@
+#define_import_path bevy_pbr::caustics
+
+#import bevy_pbr::mesh_view_bindings::{fog, globals}
+
+const TAU: f32 = 6.28318530718;
+
+fn hash2(p: vec2<f32>) -> vec2<f32> {
+    let q = vec2<f32>(dot(p, vec2<f32>(127.1, 311.7)), dot(p, vec2<f32>(269.5, 183.3)));
+    return fract(sin(q) * 43758.5453);
+}
+
+// Animated caustics in [0, 1]: bright lines along the edges of cells whose centers move over
+// time, which is how light focused by waves looks on the bottom of shallow water.
+fn caustics_pattern(position: vec2<f32>, time: f32) -> f32 {
+    let cell = floor(position);
+    let local_position = fract(position);
+
+    // The distances to the closest and second closest cell centers
+    var closest = vec2<f32>(8.0);
+    for (var y = -1; y <= 1; y += 1) {
+        for (var x = -1; x <= 1; x += 1) {
+            let neighbor = vec2<f32>(f32(x), f32(y));
+            let center = neighbor + 0.5 + 0.4 * sin(time + TAU * hash2(cell + neighbor));
+            let distance = length(center - local_position);
+            if distance < closest.x {
+                closest = vec2<f32>(distance, closest.x);
+            } else if distance < closest.y {
+                closest.y = distance;
+            }
+        }
+    }
+
+    return 1.0 - smoothstep(0.0, 0.15, closest.y - closest.x);
+}
+
+// Returns the factor applied to the light of a directional light reaching `world_position`
+// through the water surface of the view, `1.0` above the surface or without caustics.
+fn directional_light_caustics(world_position: vec3<f32>, direction_to_light: vec3<f32>) -> f32 {
+    let depth = fog.water_surface_height - world_position.y;
+    if fog.caustics_intensity <= 0.0 || depth <= 0.0 || direction_to_light.y <= 0.0 {
+        return 1.0;
+    }
+
+    // Project the position on the water surface along the direction of the light.
+    let surface_position = world_position.xz + direction_to_light.xz * (depth / direction_to_light.y);
+    let pattern = caustics_pattern(surface_position * fog.caustics_scale, globals.time * fog.caustics_speed);
+
+    // The waves only focus the light a little under the surface.
+    let intensity = fog.caustics_intensity * saturate(2.0 * depth);
+    return max(1.0 + intensity * (2.0 * pattern - 0.5), 0.0);
+}
@@ -0,0 +1,393 @@
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, Handle};
+use bevy_core_pipeline::{
+    core_3d::graph::{Labels3d, SubGraph3d},
+    fullscreen_vertex_shader::fullscreen_shader_vertex_state,
+};
+use bevy_ecs::{prelude::*, query::QueryItem};
+use bevy_math::Vec3;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    color::Color,
+    extract_component::{
+        ComponentUniforms, DynamicUniformIndex, ExtractComponent, ExtractComponentPlugin,
+        UniformComponentPlugin,
+    },
+    globals::{GlobalsBuffer, GlobalsUniform},
+    prelude::Camera,
+    render_graph::{NodeRunError, RenderGraphApp, RenderGraphContext, ViewNode, ViewNodeRunner},
+    render_resource::{
+        binding_types::{sampler, texture_2d, uniform_buffer},
+        *,
+    },
+    renderer::{RenderContext, RenderDevice},
+    texture::BevyDefault,
+    view::{ExtractedView, ViewTarget},
+    Extract, ExtractSchedule, Render, RenderApp, RenderSet,
+};
+
+use crate::{graph::LabelsPbr, FogFalloff, FogSettings};
+
+const CAUSTICS_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(5180379217648219553);
+const WATER_DISTORTION_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(8924163570492181267);
+
+/// Plugin rendering the [`CameraMedium`] of cameras and the caustics of their [`WaterSettings`].
+pub struct MediumPlugin;
+
+impl Plugin for MediumPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            CAUSTICS_SHADER_HANDLE,
+            "caustics.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            WATER_DISTORTION_SHADER_HANDLE,
+            "water_distortion.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.register_type::<CameraMedium>()
+            .register_type::<WaterSettings>()
+            .add_plugins((
+                ExtractComponentPlugin::<CameraMedium>::default(),
+                ExtractComponentPlugin::<WaterSettings>::default(),
+                UniformComponentPlugin::<WaterDistortionUniform>::default(),
+            ));
+
+        let Ok(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        render_app
+            .init_resource::<SpecializedRenderPipelines<WaterDistortionPipeline>>()
+            .add_systems(ExtractSchedule, extract_water_distortion)
+            .add_systems(
+                Render,
+                prepare_water_distortion_pipelines.in_set(RenderSet::Prepare),
+            )
+            .add_render_graph_node::<ViewNodeRunner<WaterDistortionNode>>(
+                SubGraph3d,
+                LabelsPbr::WaterDistortion,
+            )
+            .add_render_graph_edges(
+                SubGraph3d,
+                (
+                    Labels3d::Bloom,
+                    LabelsPbr::WaterDistortion,
+                    Labels3d::Tonemapping,
+                ),
+            );
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Ok(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        render_app.init_resource::<WaterDistortionPipeline>();
+    }
+}
+
+/// The medium a camera is in.
+///
+/// Under [`CameraMedium::Water`], a camera with [`WaterSettings`] uses the fog of the water
+/// instead of its [`FogSettings`], and distorts its view as if seen through moving water.
+/// Use [`WaterSettings::medium_at`] to pick the medium from the position of the camera.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq, Reflect, ExtractComponent)]
+#[extract_component_filter(With<Camera>)]
+#[reflect(Component, Default)]
+pub enum CameraMedium {
+    /// The camera sees the scene through the air, with its [`FogSettings`].
+    #[default]
+    Air,
+    /// The camera is under the water of its [`WaterSettings`].
+    Water,
+}
+
+/// Configures the water a 3D camera sees, under a horizontal water surface.
+///
+/// Meshes rendered via the PBR [`StandardMaterial`](crate::StandardMaterial) under the surface
+/// receive animated caustics on the light of directional lights, projected from the surface along
+/// the direction of the light. The fog and distortion only apply when the [`CameraMedium`] of
+/// the camera is [`CameraMedium::Water`].
+///
+/// The water surface itself isn't rendered, spawn a mesh for it if it should be visible.
+///
+/// ## Example
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_core_pipeline::prelude::*;
+/// # use bevy_pbr::prelude::*;
+/// # fn system(mut commands: Commands) {
+/// commands.spawn((
+///     Camera3dBundle::default(),
+///     WaterSettings {
+///         surface_height: 2.0,
+///         ..Default::default()
+///     },
+///     CameraMedium::Water,
+/// ));
+/// # }
+/// # bevy_ecs::system::assert_is_system(system);
+/// ```
+#[derive(Component, Clone, Debug, Reflect, ExtractComponent)]
+#[extract_component_filter(With<Camera>)]
+#[reflect(Component, Default)]
+pub struct WaterSettings {
+    /// The height of the water surface, in world space.
+    pub surface_height: f32,
+    /// The fog of the view under water, replacing the [`FogSettings`] of the camera.
+    ///
+    /// The default [`FogFalloff::Atmospheric`] fog absorbs red light faster than blue light,
+    /// like clear water.
+    pub fog: FogSettings,
+    /// How far the distortion under water offsets the view, as a fraction of its size.
+    ///
+    /// Set to `0.0` to disable the distortion.
+    pub distortion_strength: f32,
+    /// The number of distortion waves across the view.
+    pub distortion_scale: f32,
+    /// How fast the distortion waves move.
+    pub distortion_speed: f32,
+    /// How much the caustics brighten and darken the light of directional lights.
+    ///
+    /// Set to `0.0` to disable the caustics.
+    pub caustics_intensity: f32,
+    /// The number of caustics cells per world unit on the water surface.
+    pub caustics_scale: f32,
+    /// How fast the caustics move.
+    pub caustics_speed: f32,
+}
+
+impl WaterSettings {
+    /// Returns the medium of a camera at `position`.
+    pub fn medium_at(&self, position: Vec3) -> CameraMedium {
+        if position.y < self.surface_height {
+            CameraMedium::Water
+        } else {
+            CameraMedium::Air
+        }
+    }
+}
+
+impl Default for WaterSettings {
+    fn default() -> Self {
+        WaterSettings {
+            surface_height: 0.0,
+            fog: FogSettings {
+                color: Color::rgb(0.0, 0.25, 0.35),
+                falloff: FogFalloff::Atmospheric {
+                    extinction: Vec3::new(0.35, 0.1, 0.08),
+                    inscattering: Vec3::new(0.06, 0.12, 0.15),
+                },
+                ..Default::default()
+            },
+            distortion_strength: 0.004,
+            distortion_scale: 4.0,
+            distortion_speed: 1.5,
+            caustics_intensity: 0.8,
+            caustics_scale: 0.5,
+            caustics_speed: 0.6,
+        }
+    }
+}
+
+/// The uniform of the distortion post pass of the cameras under water.
+#[doc(hidden)]
+#[derive(Component, ShaderType, Clone)]
+pub struct WaterDistortionUniform {
+    strength: f32,
+    scale: f32,
+    speed: f32,
+}
+
+fn extract_water_distortion(
+    mut commands: Commands,
+    cameras: Extract<Query<(Entity, &Camera, &CameraMedium, &WaterSettings)>>,
+) {
+    for (entity, camera, medium, water) in &cameras {
+        if !camera.is_active || *medium != CameraMedium::Water || water.distortion_strength == 0.0 {
+            continue;
+        }
+        commands
+            .get_or_spawn(entity)
+            .insert(WaterDistortionUniform {
+                strength: water.distortion_strength,
+                scale: water.distortion_scale,
+                speed: water.distortion_speed,
+            });
+    }
+}
+
+#[derive(Resource)]
+pub struct WaterDistortionPipeline {
+    layout: BindGroupLayout,
+    sampler: Sampler,
+}
+
+impl FromWorld for WaterDistortionPipeline {
+    fn from_world(render_world: &mut World) -> Self {
+        let render_device = render_world.resource::<RenderDevice>();
+        let layout = render_device.create_bind_group_layout(
+            "water_distortion_bind_group_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    sampler(SamplerBindingType::Filtering),
+                    uniform_buffer::<WaterDistortionUniform>(true),
+                    uniform_buffer::<GlobalsUniform>(false),
+                ),
+            ),
+        );
+
+        let sampler = render_device.create_sampler(&SamplerDescriptor {
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..Default::default()
+        });
+
+        WaterDistortionPipeline { layout, sampler }
+    }
+}
+
+impl SpecializedRenderPipeline for WaterDistortionPipeline {
+    type Key = TextureFormat;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            label: Some("water_distortion".into()),
+            layout: vec![self.layout.clone()],
+            vertex: fullscreen_shader_vertex_state(),
+            fragment: Some(FragmentState {
+                shader: WATER_DISTORTION_SHADER_HANDLE,
+                shader_defs: vec![],
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format: key,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            push_constant_ranges: Vec::new(),
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct ViewWaterDistortionPipeline(CachedRenderPipelineId);
+
+fn prepare_water_distortion_pipelines(
+    mut commands: Commands,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<WaterDistortionPipeline>>,
+    water_distortion_pipeline: Res<WaterDistortionPipeline>,
+    views: Query<(Entity, &ExtractedView), With<WaterDistortionUniform>>,
+) {
+    for (entity, view) in &views {
+        let pipeline_id = pipelines.specialize(
+            &pipeline_cache,
+            &water_distortion_pipeline,
+            if view.hdr {
+                ViewTarget::TEXTURE_FORMAT_HDR
+            } else {
+                TextureFormat::bevy_default()
+            },
+        );
+
+        commands
+            .entity(entity)
+            .insert(ViewWaterDistortionPipeline(pipeline_id));
+    }
+}
+
+#[derive(Default)]
+pub struct WaterDistortionNode;
+
+impl ViewNode for WaterDistortionNode {
+    type ViewQuery = (
+        &'static ViewTarget,
+        &'static ViewWaterDistortionPipeline,
+        &'static DynamicUniformIndex<WaterDistortionUniform>,
+    );
+
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        (target, pipeline_id, uniform_index): QueryItem<Self::ViewQuery>,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let water_distortion_pipeline = world.resource::<WaterDistortionPipeline>();
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let (Some(pipeline), Some(uniforms), Some(globals)) = (
+            pipeline_cache.get_render_pipeline(pipeline_id.0),
+            world
+                .resource::<ComponentUniforms<WaterDistortionUniform>>()
+                .binding(),
+            world.resource::<GlobalsBuffer>().buffer.binding(),
+        ) else {
+            return Ok(());
+        };
+
+        let post_process = target.post_process_write();
+        let bind_group = render_context.render_device().create_bind_group(
+            "water_distortion_bind_group",
+            &water_distortion_pipeline.layout,
+            &BindGroupEntries::sequential((
+                post_process.source,
+                &water_distortion_pipeline.sampler,
+                uniforms,
+                globals,
+            )),
+        );
+
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("water_distortion_pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: post_process.destination,
+                resolve_target: None,
+                ops: Operations::default(),
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        render_pass.set_render_pipeline(pipeline);
+        render_pass.set_bind_group(0, &bind_group, &[uniform_index.index()]);
+        render_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn medium_at_surface_height() {
+        let water = WaterSettings {
+            surface_height: 2.0,
+            ..Default::default()
+        };
+        assert_eq!(
+            water.medium_at(Vec3::new(5.0, 1.9, -3.0)),
+            CameraMedium::Water
+        );
+        assert_eq!(
+            water.medium_at(Vec3::new(5.0, 2.0, -3.0)),
+            CameraMedium::Air
+        );
+        assert_eq!(
+            water.medium_at(Vec3::new(0.0, 10.0, 0.0)),
+            CameraMedium::Air
+        );
+    }
+}
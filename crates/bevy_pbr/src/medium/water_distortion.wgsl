@@ -0,0 +1,32 @@
+// Distorts the view of the cameras under water, like seen through moving water.
+
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+#import bevy_render::globals::Globals
+
+struct WaterDistortion {
+    strength: f32,
+    scale: f32,
+    speed: f32,
+};
+
+@group(0) @binding(0) var screen_texture: texture_2d<f32>;
+@group(0) @binding(1) var texture_sampler: sampler;
+@group(0) @binding(2) var<uniform> distortion: WaterDistortion;
+@group(0) @binding(3) var<uniform> globals: Globals;
+
+const TAU: f32 = 6.28318530718;
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let time = globals.time * distortion.speed;
+    let p = in.uv * distortion.scale * TAU;
+
+    // Two pairs of crossing waves, offsetting the sampled position by up to `strength`.
+    let offset = vec2<f32>(
+        sin(p.y + time) + sin(0.7 * (p.x + p.y) + 1.3 * time),
+        cos(p.x + 0.8 * time) + cos(0.8 * (p.x - p.y) + 1.1 * time),
+    ) * 0.5 * distortion.strength;
+
+    // The sampler clamps the positions pushed outside of the view to its edges.
+    return textureSample(screen_texture, texture_sampler, in.uv + offset);
+}
@@ -5,7 +5,7 @@ pub use prepass_bindings::*;
 
 use bevy_app::{Plugin, PreUpdate};
 use bevy_asset::{load_internal_asset, AssetServer, Handle};
-use bevy_core_pipeline::{core_3d::CORE_3D_DEPTH_FORMAT, prelude::Camera3d};
+use bevy_core_pipeline::prelude::Camera3d;
 use bevy_core_pipeline::{deferred::*, prepass::*};
 use bevy_ecs::{
     prelude::*,
@@ -15,8 +15,10 @@ use bevy_ecs::{
     },
 };
 use bevy_math::{Affine3A, Mat4};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 use bevy_render::{
     batching::batch_and_prepare_render_phase,
+    extract_instances::ExtractedInstances,
     globals::{GlobalsBuffer, GlobalsUniform},
     mesh::MeshVertexBufferLayout,
     prelude::{Camera, Mesh},
@@ -155,6 +157,7 @@ where
                         prepare_previous_view_projection_uniforms,
                         batch_and_prepare_render_phase::<Opaque3dPrepass, MeshPipeline>,
                         batch_and_prepare_render_phase::<AlphaMask3dPrepass, MeshPipeline>,
+                        batch_and_prepare_render_phase::<Transparent3dPrepass, MeshPipeline>,
                     )
                         .in_set(RenderSet::PrepareResources),
                 );
@@ -163,6 +166,7 @@ where
         render_app
             .add_render_command::<Opaque3dPrepass, DrawPrepass<M>>()
             .add_render_command::<AlphaMask3dPrepass, DrawPrepass<M>>()
+            .add_render_command::<Transparent3dPrepass, DrawPrepass<M>>()
             .add_render_command::<Opaque3dDeferred, DrawPrepass<M>>()
             .add_render_command::<AlphaMask3dDeferred, DrawPrepass<M>>()
             .add_systems(
@@ -179,6 +183,29 @@ where
 #[derive(Resource)]
 struct AnyPrepassPluginLoaded;
 
+/// Add this component to a [`Mesh`] with a transparent [`AlphaMode`] to draw it in the
+/// [`Transparent3dPrepass`], writing its depth, normals and motion vectors to the prepass textures
+/// of the cameras with a [`DepthPrepass`].
+///
+/// This lets screen-space effects like SSAO and SSR take the mesh into account, at the cost of
+/// hiding what's behind it from them. The prepass can be enabled for all the meshes of a
+/// [`Material`] with [`MaterialPlugin::transparent_prepass_enabled`].
+#[derive(Component, Reflect, Default)]
+#[reflect(Component, Default)]
+pub struct TransparentPrepass;
+
+/// A render world resource present when [`MaterialPlugin::transparent_prepass_enabled`] is set,
+/// drawing all the meshes with a transparent [`AlphaMode`] of the [`Material`] in the
+/// [`Transparent3dPrepass`].
+#[derive(Resource)]
+pub struct TransparentPrepassEnabled<M: Material>(PhantomData<M>);
+
+impl<M: Material> Default for TransparentPrepassEnabled<M> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
 #[derive(Component, ShaderType, Clone)]
 pub struct PreviousViewProjection {
     pub view_proj: Mat4,
@@ -401,6 +428,10 @@ where
             shader_defs.push("DEFERRED_PREPASS".into());
         }
 
+        if key.mesh_key.contains(MeshPipelineKey::FORCE_UNLIT) {
+            shader_defs.push("FORCE_UNLIT".into());
+        }
+
         if layout.contains(Mesh::ATTRIBUTE_COLOR) {
             shader_defs.push("VERTEX_COLORS".into());
             vertex_attributes.push(Mesh::ATTRIBUTE_COLOR.at_shader_location(7));
@@ -548,7 +579,7 @@ where
                 conservative: false,
             },
             depth_stencil: Some(DepthStencilState {
-                format: CORE_3D_DEPTH_FORMAT,
+                format: key.mesh_key.depth_stencil_format(),
                 depth_write_enabled: true,
                 depth_compare: CompareFunction::GreaterEqual,
                 stencil: StencilState {
@@ -694,18 +725,26 @@ pub fn queue_prepass_material_meshes<M: Material>(
     render_materials: Res<RenderMaterials<M>>,
     render_material_instances: Res<RenderMaterialInstances<M>>,
     render_lightmaps: Res<RenderLightmaps>,
+    material_override_tags: Res<ExtractedInstances<MaterialOverrideTag>>,
+    (transparent_draw_functions, transparent_prepass_enabled): (
+        Res<DrawFunctions<Transparent3dPrepass>>,
+        Option<Res<TransparentPrepassEnabled<M>>>,
+    ),
     mut views: Query<
         (
             &ExtractedView,
             &VisibleEntities,
             Option<&mut RenderPhase<Opaque3dPrepass>>,
             Option<&mut RenderPhase<AlphaMask3dPrepass>>,
+            Option<&mut RenderPhase<Transparent3dPrepass>>,
             Option<&mut RenderPhase<Opaque3dDeferred>>,
             Option<&mut RenderPhase<AlphaMask3dDeferred>>,
             Option<&DepthPrepass>,
             Option<&NormalPrepass>,
             Option<&MotionVectorPrepass>,
             Option<&DeferredPrepass>,
+            Option<&ExtractedViewMaterialOverride<M>>,
+            Option<&Camera3d>,
         ),
         Or<(
             With<RenderPhase<Opaque3dPrepass>>,
@@ -725,6 +764,10 @@ pub fn queue_prepass_material_meshes<M: Material>(
         .read()
         .get_id::<DrawPrepass<M>>()
         .unwrap();
+    let transparent_draw_prepass = transparent_draw_functions
+        .read()
+        .get_id::<DrawPrepass<M>>()
+        .unwrap();
     let opaque_draw_deferred = opaque_deferred_draw_functions
         .read()
         .get_id::<DrawPrepass<M>>()
@@ -738,12 +781,15 @@ pub fn queue_prepass_material_meshes<M: Material>(
         visible_entities,
         mut opaque_phase,
         mut alpha_mask_phase,
+        mut transparent_phase,
         mut opaque_deferred_phase,
         mut alpha_mask_deferred_phase,
         depth_prepass,
         normal_prepass,
         motion_vector_prepass,
         deferred_prepass,
+        material_override,
+        camera_3d,
     ) in &mut views
     {
         let mut view_key = MeshPipelineKey::from_msaa_samples(msaa.samples());
@@ -756,6 +802,12 @@ pub fn queue_prepass_material_meshes<M: Material>(
         if motion_vector_prepass.is_some() {
             view_key |= MeshPipelineKey::MOTION_VECTOR_PREPASS;
         }
+        if material_override.is_some_and(|material_override| material_override.unlit) {
+            view_key |= MeshPipelineKey::FORCE_UNLIT;
+        }
+        if camera_3d.is_some_and(|camera_3d| camera_3d.stencil.is_some()) {
+            view_key |= MeshPipelineKey::DEPTH_STENCIL;
+        }
 
         let rangefinder = view.rangefinder3d();
 
@@ -763,10 +815,18 @@ pub fn queue_prepass_material_meshes<M: Material>(
             let Some(material_asset_id) = render_material_instances.get(visible_entity) else {
                 continue;
             };
+            let Some(material_asset_id) = view_material(
+                material_override,
+                &material_override_tags,
+                visible_entity,
+                *material_asset_id,
+            ) else {
+                continue;
+            };
             let Some(mesh_instance) = render_mesh_instances.get(visible_entity) else {
                 continue;
             };
-            let Some(material) = render_materials.get(material_asset_id) else {
+            let Some(material) = render_materials.get(&material_asset_id) else {
                 continue;
             };
             let Some(mesh) = render_meshes.get(mesh_instance.mesh_asset_id) else {
@@ -785,7 +845,15 @@ pub fn queue_prepass_material_meshes<M: Material>(
                 AlphaMode::Blend
                 | AlphaMode::Premultiplied
                 | AlphaMode::Add
-                | AlphaMode::Multiply => continue,
+                | AlphaMode::Multiply => {
+                    let opted_in =
+                        transparent_prepass_enabled.is_some() || mesh_instance.transparent_prepass;
+                    if !opted_in || transparent_phase.is_none() {
+                        continue;
+                    }
+                    // Discards the nearly invisible fragments.
+                    mesh_key |= MeshPipelineKey::MAY_DISCARD;
+                }
             }
 
             if material.properties.reads_view_transmission_texture {
@@ -890,7 +958,21 @@ pub fn queue_prepass_material_meshes<M: Material>(
                 AlphaMode::Blend
                 | AlphaMode::Premultiplied
                 | AlphaMode::Add
-                | AlphaMode::Multiply => {}
+                | AlphaMode::Multiply => {
+                    let distance = rangefinder
+                        .distance_translation(&mesh_instance.transforms.transform.translation)
+                        + material.properties.depth_bias;
+                    if let Some(transparent_phase) = transparent_phase.as_mut() {
+                        transparent_phase.add(Transparent3dPrepass {
+                            entity: *visible_entity,
+                            draw_function: transparent_draw_prepass,
+                            pipeline_id,
+                            distance,
+                            batch_range: 0..1,
+                            dynamic_offset: None,
+                        });
+                    }
+                }
             }
         }
     }
@@ -1,16 +1,22 @@
 use bevy_app::{App, Plugin};
 use bevy_asset::{load_internal_asset, Handle};
 use bevy_ecs::prelude::*;
-use bevy_math::{Vec3, Vec4};
+use bevy_math::{Mat4, Vec3, Vec4};
 use bevy_render::{
+    camera::Camera,
     extract_component::ExtractComponentPlugin,
     render_resource::{DynamicUniformBuffer, Shader, ShaderType},
     renderer::{RenderDevice, RenderQueue},
-    view::ExtractedView,
-    Render, RenderApp, RenderSet,
+    view::{ExtractedView, ViewVisibility},
+    Extract, ExtractSchedule, Render, RenderApp, RenderSet,
 };
+use bevy_transform::components::GlobalTransform;
+use bevy_utils::EntityHashMap;
 
-use crate::{FogFalloff, FogSettings};
+use crate::{
+    collect_visible_fog_volumes, CameraMedium, FogFalloff, FogSettings, FogVolume, FogVolumeShape,
+    WaterSettings, MAX_FOG_VOLUMES,
+};
 
 /// The GPU-side representation of the fog configuration that's sent as a uniform to the shader
 #[derive(Copy, Clone, ShaderType, Default, Debug)]
@@ -29,6 +35,26 @@ pub struct GpuFog {
     bi: Vec3,
     /// Unsigned int representation of the active fog falloff mode
     mode: u32,
+    /// The number of fog volumes in `volumes`
+    volume_count: u32,
+    /// The height of the water surface of the [`WaterSettings`] of the view
+    water_surface_height: f32,
+    /// The intensity of the caustics under the water surface, zero without [`WaterSettings`]
+    caustics_intensity: f32,
+    caustics_scale: f32,
+    caustics_speed: f32,
+    volumes: [GpuFogVolume; MAX_FOG_VOLUMES],
+}
+
+/// The GPU-side representation of a [`FogVolume`]
+#[derive(Copy, Clone, ShaderType, Default, Debug)]
+pub struct GpuFogVolume {
+    /// Transforms world space positions to the local space of the volume shape
+    world_to_local: Mat4,
+    color: Vec4,
+    density: f32,
+    /// Unsigned int representation of the [`FogVolumeShape`]
+    shape: u32,
 }
 
 // Important: These must be kept in sync with `mesh_view_types.wgsl`
@@ -38,6 +64,54 @@ const GPU_FOG_MODE_EXPONENTIAL: u32 = 2;
 const GPU_FOG_MODE_EXPONENTIAL_SQUARED: u32 = 3;
 const GPU_FOG_MODE_ATMOSPHERIC: u32 = 4;
 
+const GPU_FOG_VOLUME_SHAPE_BOX: u32 = 0;
+const GPU_FOG_VOLUME_SHAPE_SPHERE: u32 = 1;
+
+/// The visible fog volumes nearest to a view
+#[derive(Component, Default)]
+pub struct ExtractedFogVolumes {
+    volumes: Vec<GpuFogVolume>,
+    /// Maps the fog volume entities to their index in the `volumes` of [`GpuFog`], which is
+    /// written in the cluster index lists
+    pub(crate) entity_to_index: EntityHashMap<Entity, usize>,
+}
+
+pub fn extract_fog_volumes(
+    mut commands: Commands,
+    views: Extract<Query<(Entity, &Camera, &GlobalTransform)>>,
+    fog_volumes: Extract<Query<(Entity, &GlobalTransform, &FogVolume, &ViewVisibility)>>,
+) {
+    for (view_entity, camera, view_transform) in &views {
+        if !camera.is_active {
+            continue;
+        }
+
+        // NOTE: The volumes must be ordered like in `assign_lights_to_clusters`.
+        let mut extracted_fog_volumes = ExtractedFogVolumes::default();
+        for (data, transform, fog_volume) in
+            collect_visible_fog_volumes(fog_volumes.iter(), view_transform.translation_vec3a())
+        {
+            let ExtractedFogVolumes {
+                volumes,
+                entity_to_index,
+            } = &mut extracted_fog_volumes;
+            entity_to_index.insert(data.entity, volumes.len());
+            volumes.push(GpuFogVolume {
+                world_to_local: transform.compute_matrix().inverse(),
+                color: fog_volume.color.as_linear_rgba_f32().into(),
+                density: fog_volume.density,
+                shape: match fog_volume.shape {
+                    FogVolumeShape::Box => GPU_FOG_VOLUME_SHAPE_BOX,
+                    FogVolumeShape::Sphere => GPU_FOG_VOLUME_SHAPE_SPHERE,
+                },
+            });
+        }
+        commands
+            .get_or_spawn(view_entity)
+            .insert(extracted_fog_volumes);
+    }
+}
+
 /// Metadata for fog
 #[derive(Default, Resource)]
 pub struct FogMeta {
@@ -50,7 +124,16 @@ pub fn prepare_fog(
     render_device: Res<RenderDevice>,
     render_queue: Res<RenderQueue>,
     mut fog_meta: ResMut<FogMeta>,
-    views: Query<(Entity, Option<&FogSettings>), With<ExtractedView>>,
+    views: Query<
+        (
+            Entity,
+            Option<&FogSettings>,
+            Option<&CameraMedium>,
+            Option<&WaterSettings>,
+            Option<&ExtractedFogVolumes>,
+        ),
+        With<ExtractedView>,
+    >,
 ) {
     let views_iter = views.iter();
     let view_count = views_iter.len();
@@ -60,8 +143,14 @@ pub fn prepare_fog(
     else {
         return;
     };
-    for (entity, fog) in views_iter {
-        let gpu_fog = if let Some(fog) = fog {
+    for (entity, fog, medium, water, fog_volumes) in views_iter {
+        // Under water, the fog of the water replaces the fog of the view.
+        let fog = match (medium, water) {
+            (Some(CameraMedium::Water), Some(water)) => Some(&water.fog),
+            _ => fog,
+        };
+
+        let mut gpu_fog = if let Some(fog) = fog {
             match &fog.falloff {
                 FogFalloff::Linear { start, end } => GpuFog {
                     mode: GPU_FOG_MODE_LINEAR,
@@ -109,6 +198,7 @@ pub fn prepare_fog(
                     directional_light_exponent: fog.directional_light_exponent,
                     be: *extinction,
                     bi: *inscattering,
+                    ..Default::default()
                 },
             }
         } else {
@@ -118,6 +208,16 @@ pub fn prepare_fog(
                 ..Default::default()
             }
         };
+        if let Some(fog_volumes) = fog_volumes {
+            gpu_fog.volume_count = fog_volumes.volumes.len() as u32;
+            gpu_fog.volumes[..fog_volumes.volumes.len()].copy_from_slice(&fog_volumes.volumes);
+        }
+        if let Some(water) = water {
+            gpu_fog.water_surface_height = water.surface_height;
+            gpu_fog.caustics_intensity = water.caustics_intensity;
+            gpu_fog.caustics_scale = water.caustics_scale;
+            gpu_fog.caustics_speed = water.caustics_speed;
+        }
 
         // This is later read by `SetMeshViewBindGroup<I>`
         commands.entity(entity).insert(ViewFogUniformOffset {
@@ -144,11 +244,14 @@ impl Plugin for FogPlugin {
         load_internal_asset!(app, FOG_SHADER_HANDLE, "fog.wgsl", Shader::from_wgsl);
 
         app.register_type::<FogSettings>();
+        app.register_type::<FogVolume>();
+        app.register_type::<FogVolumeShape>();
         app.add_plugins(ExtractComponentPlugin::<FogSettings>::default());
 
         if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app
                 .init_resource::<FogMeta>()
+                .add_systems(ExtractSchedule, extract_fog_volumes)
                 .add_systems(Render, prepare_fog.in_set(RenderSet::PrepareResources));
         }
     }
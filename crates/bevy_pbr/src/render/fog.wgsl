@@ -1,8 +1,10 @@
 #define_import_path bevy_pbr::fog
 
 #import bevy_pbr::{
+    mesh_view_bindings as bindings,
     mesh_view_bindings::fog,
-    mesh_view_types::Fog,
+    mesh_view_types::{Fog, FogVolume, FOG_VOLUME_SHAPE_SPHERE},
+    clustered_forward as clustering,
 }
 
 // Fog formulas adapted from:
@@ -77,3 +79,74 @@ fn atmospheric_fog(
         input_color.a
     );
 }
+
+// Returns the length of the part of the segment from `start` to `end` inside the fog volume.
+fn fog_volume_distance(volume: FogVolume, start: vec3<f32>, end: vec3<f32>) -> f32 {
+    let local_start = (volume.world_to_local * vec4<f32>(start, 1.0)).xyz;
+    let local_ray = (volume.world_to_local * vec4<f32>(end - start, 0.0)).xyz;
+
+    // The range of the segment parameter inside the shape, of half size 0.5
+    var t: vec2<f32>;
+    if volume.shape == FOG_VOLUME_SHAPE_SPHERE {
+        let a = dot(local_ray, local_ray);
+        let b = dot(local_start, local_ray);
+        let c = dot(local_start, local_start) - 0.25;
+        let discriminant = b * b - a * c;
+        if discriminant <= 0.0 {
+            return 0.0;
+        }
+        let root = sqrt(discriminant);
+        t = vec2<f32>(-b - root, -b + root) / a;
+    } else {
+        let inverse_ray = 1.0 / local_ray;
+        let t_0 = (vec3<f32>(-0.5) - local_start) * inverse_ray;
+        let t_1 = (vec3<f32>(0.5) - local_start) * inverse_ray;
+        let t_min = min(t_0, t_1);
+        let t_max = max(t_0, t_1);
+        t = vec2<f32>(max(max(t_min.x, t_min.y), t_min.z), min(min(t_max.x, t_max.y), t_max.z));
+    }
+
+    // Only the part of the segment between the view and the fragment is fogged.
+    t = clamp(t, vec2<f32>(0.0), vec2<f32>(1.0));
+    return max(t.y - t.x, 0.0) * length(end - start);
+}
+
+fn blend_fog_volume(volume: FogVolume, input_color: vec4<f32>, start: vec3<f32>, end: vec3<f32>) -> vec4<f32> {
+    let distance = fog_volume_distance(volume, start, end);
+    let fog_amount = (1.0 - exp(-volume.density * distance)) * volume.color.a;
+    return vec4<f32>(mix(input_color.rgb, volume.color.rgb, fog_amount), input_color.a);
+}
+
+// Blends the fog volumes crossed by the view ray before reaching the fragment.
+fn apply_fog_volumes(
+    input_color: vec4<f32>,
+    fragment_world_position: vec3<f32>,
+    view_world_position: vec3<f32>,
+    frag_coord: vec2<f32>,
+    is_orthographic: bool,
+) -> vec4<f32> {
+    var output_color = input_color;
+#if AVAILABLE_STORAGE_BUFFER_BINDINGS >= 3
+    // The fog volumes affecting the cluster follow its point and spot lights in the index lists.
+    let view_z = dot(vec4<f32>(
+        bindings::view.inverse_view[0].z,
+        bindings::view.inverse_view[1].z,
+        bindings::view.inverse_view[2].z,
+        bindings::view.inverse_view[3].z
+    ), vec4<f32>(fragment_world_position, 1.0));
+    let cluster_index = clustering::fragment_cluster_index(frag_coord, view_z, is_orthographic);
+    let offset_and_counts = clustering::unpack_offset_and_counts(cluster_index);
+    let fog_volume_count = bindings::cluster_offsets_and_counts.data[cluster_index].w;
+    let first = offset_and_counts[0] + offset_and_counts[1] + offset_and_counts[2];
+    for (var i: u32 = first; i < first + fog_volume_count; i = i + 1u) {
+        let volume_id = clustering::get_light_id(i);
+        output_color = blend_fog_volume(fog.volumes[volume_id], output_color, view_world_position, fragment_world_position);
+    }
+#else
+    // The fog volumes are not clustered without storage buffers.
+    for (var i: u32 = 0u; i < fog.volume_count; i = i + 1u) {
+        output_color = blend_fog_volume(fog.volumes[i], output_color, view_world_position, fragment_world_position);
+    }
+#endif
+    return output_color;
+}
@@ -0,0 +1,852 @@
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, Handle};
+use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::{
+    prelude::*,
+    query::ROQueryItem,
+    system::{lifetimeless::*, SystemParamItem},
+};
+use bevy_math::{Vec3, Vec4};
+use bevy_render::{
+    batching::batch_and_prepare_render_phase,
+    mesh::{GpuBufferInfo, Mesh},
+    primitives::{Aabb, Frustum},
+    render_asset::RenderAssets,
+    render_phase::{
+        PhaseItem, RenderCommand, RenderCommandResult, RenderPhase, SetItemPipeline,
+        TrackedRenderPass,
+    },
+    render_resource::{
+        binding_types::{storage_buffer_read_only_sized, storage_buffer_sized},
+        *,
+    },
+    renderer::{RenderContext, RenderDevice, RenderQueue},
+    settings::WgpuFeatures,
+    view::ViewVisibility,
+    Extract, ExtractSchedule, Render, RenderApp, RenderSet,
+};
+use bevy_utils::EntityHashMap;
+use bytemuck::{Pod, Zeroable};
+use std::num::NonZeroU64;
+
+use crate::{
+    GpuShadowCulling, MeshPipeline, MeshUniform, NotShadowCaster, RenderMeshInstances,
+    SetMaterialBindGroup, SetMeshBindGroup, SetPrepassViewBindGroup, Shadow, ViewLightEntities,
+};
+
+pub const GPU_SHADOW_CULLING_SHADER_HANDLE: Handle<Shader> =
+    Handle::weak_from_u128(7305812391738652170);
+
+/// The frustum index of shadow casters without bounds, which are never culled.
+// This must match UNBOUNDED_FRUSTUM_INDEX in gpu_shadow_culling.wgsl
+const UNBOUNDED_FRUSTUM_INDEX: u32 = u32::MAX;
+
+const WORKGROUP_SIZE: u32 = 64;
+
+/// Culls the shadow casters of the cascades of the directional lights with [`GpuShadowCulling`]
+/// in a compute shader.
+pub struct GpuShadowCullingPlugin;
+
+impl Plugin for GpuShadowCullingPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            GPU_SHADOW_CULLING_SHADER_HANDLE,
+            "gpu_shadow_culling.wgsl",
+            Shader::from_wgsl
+        );
+
+        let Ok(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .init_resource::<RenderShadowCasterAabbs>()
+            .init_resource::<GpuShadowCullingBuffers>()
+            .add_systems(ExtractSchedule, extract_shadow_caster_aabbs)
+            .add_systems(
+                Render,
+                (
+                    prepare_gpu_shadow_culling
+                        .in_set(RenderSet::PrepareResources)
+                        .after(batch_and_prepare_render_phase::<Shadow, MeshPipeline>),
+                    prepare_gpu_shadow_culling_bind_groups.in_set(RenderSet::PrepareBindGroups),
+                ),
+            );
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Ok(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        if gpu_shadow_culling_supported(render_app.world.resource::<RenderDevice>()) {
+            render_app.init_resource::<GpuShadowCullingPipeline>();
+        }
+    }
+}
+
+/// Whether the render device supports culling shadow casters on the GPU: it requires compute
+/// shaders, storage buffers and indirect draws starting at an instance.
+///
+/// The culled instances of a batch are then skipped with a single indirect draw of its visible
+/// instances if the device also supports [`WgpuFeatures::MULTI_DRAW_INDIRECT_COUNT`], or with a
+/// single indirect draw of all its instances if it supports
+/// [`WgpuFeatures::MULTI_DRAW_INDIRECT`], or with an indirect draw per instance otherwise.
+pub fn gpu_shadow_culling_supported(render_device: &RenderDevice) -> bool {
+    let limits = render_device.limits();
+    render_device
+        .features()
+        .contains(WgpuFeatures::INDIRECT_FIRST_INSTANCE)
+        && limits.max_storage_buffers_per_shader_stage >= 6
+        && limits.max_compute_workgroups_per_dimension > 0
+}
+
+/// Marks the shadow views of the cascades whose shadow casters are culled on the GPU.
+#[derive(Component)]
+pub struct GpuCulledShadowView;
+
+/// The bounds of the visible shadow casters, extracted when a light uses [`GpuShadowCulling`].
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct RenderShadowCasterAabbs(EntityHashMap<Entity, Aabb>);
+
+pub fn extract_shadow_caster_aabbs(
+    mut shadow_caster_aabbs: ResMut<RenderShadowCasterAabbs>,
+    gpu_culled_lights: Extract<Query<(), With<GpuShadowCulling>>>,
+    shadow_casters: Extract<
+        Query<(Entity, &ViewVisibility, &Aabb), (With<Handle<Mesh>>, Without<NotShadowCaster>)>,
+    >,
+) {
+    shadow_caster_aabbs.clear();
+    if gpu_culled_lights.is_empty() {
+        return;
+    }
+    shadow_caster_aabbs.extend(
+        shadow_casters
+            .iter()
+            .filter(|(_, view_visibility, _)| view_visibility.get())
+            .map(|(entity, _, aabb)| (entity, *aabb)),
+    );
+}
+
+#[derive(Resource)]
+pub struct GpuShadowCullingPipeline {
+    bind_group_layout: BindGroupLayout,
+    pipeline: CachedComputePipelineId,
+}
+
+fn culling_bind_group_layout(render_device: &RenderDevice) -> BindGroupLayout {
+    render_device.create_bind_group_layout(
+        "gpu_shadow_culling_bind_group_layout",
+        &BindGroupLayoutEntries::sequential(
+            ShaderStages::COMPUTE,
+            (
+                GpuArrayBuffer::<MeshUniform>::binding_layout(render_device),
+                storage_buffer_read_only_sized(false, None),
+                storage_buffer_read_only_sized(false, None),
+                storage_buffer_sized(false, None),
+                storage_buffer_sized(false, None),
+                storage_buffer_sized(false, None),
+            ),
+        ),
+    )
+}
+
+impl FromWorld for GpuShadowCullingPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let pipeline_cache = world.resource::<PipelineCache>();
+
+        let bind_group_layout = culling_bind_group_layout(render_device);
+        let pipeline = pipeline_cache.queue_compute_pipeline(ComputePipelineDescriptor {
+            label: Some("gpu_shadow_culling_pipeline".into()),
+            layout: vec![bind_group_layout.clone()],
+            push_constant_ranges: vec![],
+            shader: GPU_SHADOW_CULLING_SHADER_HANDLE,
+            shader_defs: Vec::new(),
+            entry_point: "cull_shadow_casters".into(),
+        });
+
+        Self {
+            bind_group_layout,
+            pipeline,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct GpuShadowCaster {
+    aabb_center: Vec3,
+    mesh_index: u32,
+    aabb_half_extents: Vec3,
+    frustum_index: u32,
+    /// The index of the draw count of the batch of the instance.
+    batch_index: u32,
+    /// The offset of the first draw of the batch in the indirect args, in words.
+    batch_offset: u32,
+    /// The offset of the draw of the instance in the indirect args, in words.
+    args_offset: u32,
+    /// The length of the draw of the instance, which depends on whether its mesh is indexed.
+    args_words: u32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct GpuCascadeFrustum {
+    half_spaces: [Vec4; 6],
+}
+
+/// The culling buffers of the shadow views of a main view, with one shadow caster and one
+/// indirect draw per instance of the [`Shadow`] phases, and one draw count per batch.
+///
+/// The culling shader sets the instance count of the draws of the culled instances to 0 in
+/// `indirect_args`, and packs the draws of the visible instances of each batch at its start in
+/// `compacted_args`, counting them in `draw_counts`.
+pub struct ViewShadowCullingBuffers {
+    shadow_casters: BufferVec<GpuShadowCaster>,
+    cascade_frusta: BufferVec<GpuCascadeFrustum>,
+    indirect_args: BufferVec<u32>,
+    compacted_args: Option<Buffer>,
+    draw_counts: BufferVec<u32>,
+    /// Set when the culling pipeline is ready, in which case the shadow casters are culled before
+    /// the shadow passes.
+    bind_group: Option<BindGroup>,
+}
+
+impl Default for ViewShadowCullingBuffers {
+    fn default() -> Self {
+        let mut shadow_casters = BufferVec::new(BufferUsages::STORAGE);
+        shadow_casters.set_label(Some("gpu_shadow_culling_shadow_casters_buffer"));
+        let mut cascade_frusta = BufferVec::new(BufferUsages::STORAGE);
+        cascade_frusta.set_label(Some("gpu_shadow_culling_cascade_frusta_buffer"));
+        let mut indirect_args = BufferVec::new(BufferUsages::STORAGE | BufferUsages::INDIRECT);
+        indirect_args.set_label(Some("gpu_shadow_culling_indirect_args_buffer"));
+        let mut draw_counts = BufferVec::new(BufferUsages::STORAGE | BufferUsages::INDIRECT);
+        draw_counts.set_label(Some("gpu_shadow_culling_draw_counts_buffer"));
+        Self {
+            shadow_casters,
+            cascade_frusta,
+            indirect_args,
+            compacted_args: None,
+            draw_counts,
+            bind_group: None,
+        }
+    }
+}
+
+/// The [`ViewShadowCullingBuffers`] of each main view, kept across frames to reuse the buffers.
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct GpuShadowCullingBuffers(EntityHashMap<Entity, ViewShadowCullingBuffers>);
+
+/// The indirect draws of each item of the [`Shadow`] phase of a [`GpuCulledShadowView`], in the
+/// buffers of its main view.
+#[derive(Component)]
+pub struct ShadowCullingDraws {
+    pub main_view: Entity,
+    pub batches: EntityHashMap<Entity, ShadowCullingBatch>,
+}
+
+/// The indirect draws of the instances of a batch.
+#[derive(Clone, Copy, Debug)]
+pub struct ShadowCullingBatch {
+    /// The index of the draw count of the batch.
+    pub index: u32,
+    /// The offset of the first draw of the batch in the indirect args, in words.
+    pub offset: u32,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn prepare_gpu_shadow_culling(
+    mut commands: Commands,
+    mut culling_buffers: ResMut<GpuShadowCullingBuffers>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    render_meshes: Res<RenderAssets<Mesh>>,
+    render_mesh_instances: Res<RenderMeshInstances>,
+    shadow_caster_aabbs: Res<RenderShadowCasterAabbs>,
+    views: Query<(Entity, &ViewLightEntities)>,
+    shadow_views: Query<(&Frustum, &RenderPhase<Shadow>), With<GpuCulledShadowView>>,
+) {
+    culling_buffers.retain(|view_entity, _| views.contains(*view_entity));
+
+    for (view_entity, view_lights) in &views {
+        if !view_lights
+            .lights
+            .iter()
+            .any(|light| shadow_views.contains(*light))
+        {
+            culling_buffers.remove(&view_entity);
+            continue;
+        }
+
+        let view_buffers = culling_buffers.entry(view_entity).or_default();
+        view_buffers.shadow_casters.clear();
+        view_buffers.cascade_frusta.clear();
+        view_buffers.indirect_args.clear();
+        view_buffers.draw_counts.clear();
+
+        for &shadow_view_entity in &view_lights.lights {
+            let Ok((frustum, shadow_phase)) = shadow_views.get(shadow_view_entity) else {
+                continue;
+            };
+            let frustum_index = view_buffers.cascade_frusta.push(GpuCascadeFrustum {
+                half_spaces: frustum.half_spaces.map(|half_space| half_space.normal_d()),
+            }) as u32;
+
+            let mut batches = EntityHashMap::default();
+            for item in &shadow_phase.items {
+                let Some(mesh_instance) = render_mesh_instances.get(&item.entity()) else {
+                    continue;
+                };
+                let Some(gpu_mesh) = render_meshes.get(mesh_instance.mesh_asset_id) else {
+                    continue;
+                };
+                // The draw counts are reset every frame, before the culling shader increments
+                // them.
+                let batch = ShadowCullingBatch {
+                    index: view_buffers.draw_counts.push(0) as u32,
+                    offset: view_buffers.indirect_args.len() as u32,
+                };
+                batches.insert(item.entity(), batch);
+
+                // Instances batched together share a mesh, and so its bounds.
+                let (aabb_center, aabb_half_extents, frustum_index) =
+                    match shadow_caster_aabbs.get(&item.entity()) {
+                        Some(aabb) => (aabb.center.into(), aabb.half_extents.into(), frustum_index),
+                        None => (Vec3::ZERO, Vec3::ZERO, UNBOUNDED_FRUSTUM_INDEX),
+                    };
+                for mesh_index in item.batch_range().clone() {
+                    let args_offset = view_buffers.indirect_args.len() as u32;
+                    // The instance count, the second word, is overwritten by the culling shader.
+                    // It is 1 so that the instances are drawn until the pipeline is ready.
+                    match &gpu_mesh.buffer_info {
+                        GpuBufferInfo::Indexed { count, .. } => {
+                            view_buffers
+                                .indirect_args
+                                .extend([*count, 1, 0, 0, mesh_index]);
+                        }
+                        GpuBufferInfo::NonIndexed => {
+                            view_buffers.indirect_args.extend([
+                                gpu_mesh.vertex_count,
+                                1,
+                                0,
+                                mesh_index,
+                            ]);
+                        }
+                    }
+                    view_buffers.shadow_casters.push(GpuShadowCaster {
+                        aabb_center,
+                        mesh_index,
+                        aabb_half_extents,
+                        frustum_index,
+                        batch_index: batch.index,
+                        batch_offset: batch.offset,
+                        args_offset,
+                        args_words: view_buffers.indirect_args.len() as u32 - args_offset,
+                    });
+                }
+            }
+
+            commands
+                .entity(shadow_view_entity)
+                .insert(ShadowCullingDraws {
+                    main_view: view_entity,
+                    batches,
+                });
+        }
+
+        // The compacted args are only written by the culling shader, so the buffer only needs to
+        // be large enough.
+        let args_size = (view_buffers.indirect_args.len() * std::mem::size_of::<u32>()) as u64;
+        if args_size > 0
+            && view_buffers
+                .compacted_args
+                .as_ref()
+                .map_or(true, |buffer| buffer.size() < args_size)
+        {
+            view_buffers.compacted_args = Some(render_device.create_buffer(&BufferDescriptor {
+                label: Some("gpu_shadow_culling_compacted_args_buffer"),
+                size: args_size,
+                usage: BufferUsages::STORAGE | BufferUsages::INDIRECT,
+                mapped_at_creation: false,
+            }));
+        }
+
+        view_buffers
+            .shadow_casters
+            .write_buffer(&render_device, &render_queue);
+        view_buffers
+            .cascade_frusta
+            .write_buffer(&render_device, &render_queue);
+        view_buffers
+            .indirect_args
+            .write_buffer(&render_device, &render_queue);
+        view_buffers
+            .draw_counts
+            .write_buffer(&render_device, &render_queue);
+    }
+}
+
+pub fn prepare_gpu_shadow_culling_bind_groups(
+    render_device: Res<RenderDevice>,
+    pipeline_cache: Res<PipelineCache>,
+    culling_pipeline: Option<Res<GpuShadowCullingPipeline>>,
+    mesh_uniforms: Res<GpuArrayBuffer<MeshUniform>>,
+    mut culling_buffers: ResMut<GpuShadowCullingBuffers>,
+) {
+    for view_buffers in culling_buffers.values_mut() {
+        view_buffers.bind_group = None;
+    }
+
+    let (Some(culling_pipeline), Some(mesh_uniforms)) = (culling_pipeline, mesh_uniforms.binding())
+    else {
+        return;
+    };
+    // The shadow casters are drawn without culling until the pipeline is ready.
+    if pipeline_cache
+        .get_compute_pipeline(culling_pipeline.pipeline)
+        .is_none()
+    {
+        return;
+    }
+
+    for view_buffers in culling_buffers.values_mut() {
+        // Storage buffers can't be empty.
+        if view_buffers.shadow_casters.is_empty() {
+            continue;
+        }
+        let (
+            Some(shadow_casters),
+            Some(cascade_frusta),
+            Some(indirect_args),
+            Some(compacted_args),
+            Some(draw_counts),
+        ) = (
+            view_buffers.shadow_casters.buffer(),
+            view_buffers.cascade_frusta.buffer(),
+            view_buffers.indirect_args.buffer(),
+            view_buffers.compacted_args.as_ref(),
+            view_buffers.draw_counts.buffer(),
+        )
+        else {
+            continue;
+        };
+
+        // Bind the written part of the buffers only, as the culling shader uses their length.
+        view_buffers.bind_group = Some(render_device.create_bind_group(
+            "gpu_shadow_culling_bind_group",
+            &culling_pipeline.bind_group_layout,
+            &BindGroupEntries::sequential((
+                mesh_uniforms.clone(),
+                sized_binding::<GpuShadowCaster>(shadow_casters, view_buffers.shadow_casters.len()),
+                sized_binding::<GpuCascadeFrustum>(
+                    cascade_frusta,
+                    view_buffers.cascade_frusta.len(),
+                ),
+                sized_binding::<u32>(indirect_args, view_buffers.indirect_args.len()),
+                sized_binding::<u32>(compacted_args, view_buffers.indirect_args.len()),
+                sized_binding::<u32>(draw_counts, view_buffers.draw_counts.len()),
+            )),
+        ));
+    }
+}
+
+/// Binds the first `len` elements of type `T` of `buffer`.
+fn sized_binding<T>(buffer: &Buffer, len: usize) -> BufferBinding {
+    BufferBinding {
+        buffer,
+        offset: 0,
+        size: NonZeroU64::new((len * std::mem::size_of::<T>()) as u64),
+    }
+}
+
+/// Culls the shadow casters of the [`GpuCulledShadowView`]s of the main view `view_entity`.
+///
+/// This must be encoded before their shadow passes, which draw with the culled indirect args.
+pub fn cull_shadow_casters(render_context: &mut RenderContext, world: &World, view_entity: Entity) {
+    let (Some(culling_pipeline), Some(culling_buffers)) = (
+        world.get_resource::<GpuShadowCullingPipeline>(),
+        world.get_resource::<GpuShadowCullingBuffers>(),
+    ) else {
+        return;
+    };
+    let Some(view_buffers) = culling_buffers.get(&view_entity) else {
+        return;
+    };
+    let Some(bind_group) = &view_buffers.bind_group else {
+        return;
+    };
+    let Some(pipeline) = world
+        .resource::<PipelineCache>()
+        .get_compute_pipeline(culling_pipeline.pipeline)
+    else {
+        return;
+    };
+
+    let mut culling_pass =
+        render_context
+            .command_encoder()
+            .begin_compute_pass(&ComputePassDescriptor {
+                label: Some("gpu_shadow_culling_pass"),
+                timestamp_writes: None,
+            });
+    culling_pass.set_pipeline(pipeline);
+    culling_pass.set_bind_group(0, bind_group, &[]);
+    culling_pass.dispatch_workgroups(
+        (view_buffers.shadow_casters.len() as u32).div_ceil(WORKGROUP_SIZE),
+        1,
+        1,
+    );
+}
+
+pub type DrawGpuCulledShadow<M> = (
+    SetItemPipeline,
+    SetPrepassViewBindGroup<0>,
+    SetMeshBindGroup<1>,
+    SetMaterialBindGroup<M, 2>,
+    DrawGpuCulledMesh,
+);
+
+/// Draws the instances of a batch with the indirect draws written by the culling shader.
+///
+/// With [`WgpuFeatures::MULTI_DRAW_INDIRECT_COUNT`], the draws of the visible instances are issued
+/// with a single command. Otherwise, the draws of all the instances are issued, the culled ones
+/// drawing no instance, with a single command with [`WgpuFeatures::MULTI_DRAW_INDIRECT`] or with
+/// a command per instance.
+pub struct DrawGpuCulledMesh;
+impl<P: PhaseItem> RenderCommand<P> for DrawGpuCulledMesh {
+    type Param = (
+        SRes<RenderDevice>,
+        SRes<RenderAssets<Mesh>>,
+        SRes<RenderMeshInstances>,
+        SRes<GpuShadowCullingBuffers>,
+    );
+    type ViewQuery = Read<ShadowCullingDraws>;
+    type ItemQuery = ();
+    #[inline]
+    fn render<'w>(
+        item: &P,
+        culling_draws: ROQueryItem<'w, Self::ViewQuery>,
+        _item_query: Option<()>,
+        (render_device, meshes, mesh_instances, culling_buffers): SystemParamItem<
+            'w,
+            '_,
+            Self::Param,
+        >,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let meshes = meshes.into_inner();
+        let mesh_instances = mesh_instances.into_inner();
+        let culling_buffers = culling_buffers.into_inner();
+
+        let Some(mesh_instance) = mesh_instances.get(&item.entity()) else {
+            return RenderCommandResult::Failure;
+        };
+        let Some(gpu_mesh) = meshes.get(mesh_instance.mesh_asset_id) else {
+            return RenderCommandResult::Failure;
+        };
+        let Some(&batch) = culling_draws.batches.get(&item.entity()) else {
+            return RenderCommandResult::Failure;
+        };
+        let Some(view_buffers) = culling_buffers.get(&culling_draws.main_view) else {
+            return RenderCommandResult::Failure;
+        };
+        let Some(indirect_args) = view_buffers.indirect_args.buffer() else {
+            return RenderCommandResult::Failure;
+        };
+
+        pass.set_vertex_buffer(0, gpu_mesh.vertex_buffer.slice(..));
+
+        let indexed = match &gpu_mesh.buffer_info {
+            GpuBufferInfo::Indexed {
+                buffer,
+                index_format,
+                ..
+            } => {
+                pass.set_index_buffer(buffer.slice(..), 0, *index_format);
+                true
+            }
+            GpuBufferInfo::NonIndexed => false,
+        };
+        let draw_size = if indexed {
+            std::mem::size_of::<DrawIndexedIndirectArgs>() as u64
+        } else {
+            std::mem::size_of::<DrawIndirectArgs>() as u64
+        };
+        let batch_offset = batch.offset as u64 * std::mem::size_of::<u32>() as u64;
+        let max_draw_count = item.batch_range().len() as u32;
+
+        let features = render_device.features();
+        // The compacted args are only written when the culling shader runs.
+        let compacted = view_buffers
+            .bind_group
+            .as_ref()
+            .and(view_buffers.compacted_args.as_ref())
+            .zip(view_buffers.draw_counts.buffer())
+            .filter(|_| features.contains(WgpuFeatures::MULTI_DRAW_INDIRECT_COUNT));
+        if let Some((compacted_args, draw_counts)) = compacted {
+            let count_offset = batch.index as u64 * std::mem::size_of::<u32>() as u64;
+            if indexed {
+                pass.multi_draw_indexed_indirect_count(
+                    compacted_args,
+                    batch_offset,
+                    draw_counts,
+                    count_offset,
+                    max_draw_count,
+                );
+            } else {
+                pass.multi_draw_indirect_count(
+                    compacted_args,
+                    batch_offset,
+                    draw_counts,
+                    count_offset,
+                    max_draw_count,
+                );
+            }
+        } else if features.contains(WgpuFeatures::MULTI_DRAW_INDIRECT) {
+            if indexed {
+                pass.multi_draw_indexed_indirect(indirect_args, batch_offset, max_draw_count);
+            } else {
+                pass.multi_draw_indirect(indirect_args, batch_offset, max_draw_count);
+            }
+        } else {
+            for draw in 0..max_draw_count as u64 {
+                let offset = batch_offset + draw * draw_size;
+                if indexed {
+                    pass.draw_indexed_indirect(indirect_args, offset);
+                } else {
+                    pass.draw_indirect(indirect_args, offset);
+                }
+            }
+        }
+        RenderCommandResult::Success
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{borrow::Cow, sync::Arc};
+
+    use bevy_math::{Mat4, UVec2};
+    use bevy_render::render_resource::encase;
+    use naga_oil::compose::{ComposableModuleDescriptor, Composer, NagaModuleDescriptor};
+
+    use super::*;
+
+    /// Creates a device to run the culling shader on, if there is an adapter supporting it.
+    fn render_device() -> Option<(RenderDevice, RenderQueue)> {
+        let instance = wgpu::Instance::default();
+        let adapter = futures_lite::future::block_on(
+            instance.request_adapter(&wgpu::RequestAdapterOptions::default()),
+        )?;
+        let (device, queue) = futures_lite::future::block_on(adapter.request_device(
+            &wgpu::DeviceDescriptor {
+                label: None,
+                required_features: WgpuFeatures::empty(),
+                required_limits: adapter.limits(),
+            },
+            None,
+        ))
+        .ok()?;
+        (device.limits().max_storage_buffers_per_shader_stage >= 6)
+            .then(|| (RenderDevice::from(device), RenderQueue(Arc::new(queue))))
+    }
+
+    fn culling_pipeline(render_device: &RenderDevice, layout: &BindGroupLayout) -> ComputePipeline {
+        let mut composer = Composer::default();
+        for (source, file_path) in [
+            (include_str!("mesh_types.wgsl"), "mesh_types.wgsl"),
+            (
+                include_str!("../../../bevy_render/src/maths.wgsl"),
+                "maths.wgsl",
+            ),
+        ] {
+            composer
+                .add_composable_module(ComposableModuleDescriptor {
+                    source,
+                    file_path,
+                    ..Default::default()
+                })
+                .unwrap();
+        }
+        let module = composer
+            .make_naga_module(NagaModuleDescriptor {
+                source: include_str!("gpu_shadow_culling.wgsl"),
+                file_path: "gpu_shadow_culling.wgsl",
+                ..Default::default()
+            })
+            .unwrap();
+
+        let module = render_device.create_shader_module(ShaderModuleDescriptor {
+            label: None,
+            source: ShaderSource::Naga(Cow::Owned(module)),
+        });
+        let layout = render_device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: None,
+            bind_group_layouts: &[&**layout],
+            push_constant_ranges: &[],
+        });
+        render_device.create_compute_pipeline(&RawComputePipelineDescriptor {
+            label: None,
+            layout: Some(&layout),
+            module: &module,
+            entry_point: "cull_shadow_casters",
+        })
+    }
+
+    fn translated_mesh(translation: Vec3) -> MeshUniform {
+        MeshUniform {
+            transform: [
+                Vec4::new(1., 0., 0., translation.x),
+                Vec4::new(0., 1., 0., translation.y),
+                Vec4::new(0., 0., 1., translation.z),
+            ],
+            previous_transform: [Vec4::ZERO; 3],
+            lightmap_uv_rect: UVec2::ZERO,
+            inverse_transpose_model_a: [Vec4::ZERO; 2],
+            inverse_transpose_model_b: 0.,
+            flags: 0,
+        }
+    }
+
+    /// Reads back the words of a buffer created with [`BufferUsages::MAP_READ`].
+    fn read_words(render_device: &RenderDevice, buffer: &Buffer) -> Vec<u32> {
+        let (bytes, _) =
+            futures_lite::future::block_on(futures_lite::future::zip(buffer.read(), async {
+                render_device.poll(Maintain::Wait);
+            }));
+        bytes
+            .unwrap()
+            .chunks_exact(4)
+            .map(|word| u32::from_le_bytes(word.try_into().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn culls_and_compacts_draws() {
+        let Some((render_device, render_queue)) = render_device() else {
+            // There is no GPU to run the culling shader on.
+            return;
+        };
+
+        // A cascade looking down -Z, from z = 0 to z = -10, with reversed Z like the cascades
+        let frustum =
+            Frustum::from_view_projection(&Mat4::orthographic_rh(-1., 1., -1., 1., 10., 0.));
+        let meshes = [
+            Vec3::new(0., 0., -5.),
+            // Outside of the sides of the cascade
+            Vec3::new(5., 0., -5.),
+            // Before the near plane, between the light and the cascade
+            Vec3::new(0., 0., 5.),
+            // Beyond the far plane
+            Vec3::new(0., 0., -20.),
+            // Outside of the cascade, but without bounds
+            Vec3::new(100., 0., -5.),
+        ]
+        .map(translated_mesh);
+
+        // An indexed batch of the first three meshes, and a non-indexed batch of the last two
+        let batches = [(0, 5, 0..3, 0), (1, 4, 3..5, 15)];
+        let mut shadow_casters = Vec::new();
+        let mut indirect_args = Vec::new();
+        for (batch_index, args_words, mesh_indices, batch_offset) in batches {
+            for mesh_index in mesh_indices {
+                let args_offset = indirect_args.len() as u32;
+                if args_words == 5 {
+                    indirect_args.extend([36, 1, 0, 0, mesh_index]);
+                } else {
+                    indirect_args.extend([24, 1, 0, mesh_index]);
+                }
+                shadow_casters.push(GpuShadowCaster {
+                    aabb_center: Vec3::ZERO,
+                    mesh_index,
+                    aabb_half_extents: Vec3::splat(0.5),
+                    frustum_index: if mesh_index == 4 {
+                        UNBOUNDED_FRUSTUM_INDEX
+                    } else {
+                        0
+                    },
+                    batch_index,
+                    batch_offset,
+                    args_offset,
+                    args_words,
+                });
+            }
+        }
+        let cascade_frusta = [GpuCascadeFrustum {
+            half_spaces: frustum.half_spaces.map(|half_space| half_space.normal_d()),
+        }];
+
+        let mut mesh_bytes = encase::StorageBuffer::new(Vec::new());
+        mesh_bytes.write(&meshes.to_vec()).unwrap();
+        let create_buffer = |contents: &[u8]| {
+            render_device.create_buffer_with_data(&BufferInitDescriptor {
+                label: None,
+                contents,
+                usage: BufferUsages::STORAGE | BufferUsages::COPY_SRC,
+            })
+        };
+        let buffers = [
+            create_buffer(&mesh_bytes.into_inner()),
+            create_buffer(bytemuck::cast_slice(&shadow_casters)),
+            create_buffer(bytemuck::cast_slice(&cascade_frusta)),
+            create_buffer(bytemuck::cast_slice(&indirect_args)),
+            create_buffer(&vec![0; indirect_args.len() * 4]),
+            create_buffer(bytemuck::cast_slice(&[0u32; 2])),
+        ];
+
+        let layout = culling_bind_group_layout(&render_device);
+        let pipeline = culling_pipeline(&render_device, &layout);
+        let bind_group = render_device.create_bind_group(
+            None,
+            &layout,
+            &BindGroupEntries::sequential((
+                buffers[0].as_entire_binding(),
+                buffers[1].as_entire_binding(),
+                buffers[2].as_entire_binding(),
+                buffers[3].as_entire_binding(),
+                buffers[4].as_entire_binding(),
+                buffers[5].as_entire_binding(),
+            )),
+        );
+
+        let mut encoder =
+            render_device.create_command_encoder(&CommandEncoderDescriptor::default());
+        {
+            let mut pass = encoder.begin_compute_pass(&ComputePassDescriptor::default());
+            pass.set_pipeline(&pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(1, 1, 1);
+        }
+        let read_backs: Vec<_> = buffers[3..]
+            .iter()
+            .map(|buffer| {
+                let read_back = render_device.create_buffer(&BufferDescriptor {
+                    label: None,
+                    size: buffer.size(),
+                    usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+                    mapped_at_creation: false,
+                });
+                encoder.copy_buffer_to_buffer(buffer, 0, &read_back, 0, buffer.size());
+                read_back
+            })
+            .collect();
+        render_queue.submit([encoder.finish()]);
+        let [indirect_args, compacted_args, draw_counts] =
+            [0, 1, 2].map(|index| read_words(&render_device, &read_backs[index]));
+
+        let instance_counts: Vec<_> = shadow_casters
+            .iter()
+            .map(|caster| indirect_args[caster.args_offset as usize + 1])
+            .collect();
+        assert_eq!(instance_counts, [1, 0, 1, 0, 1]);
+        assert_eq!(draw_counts, [2, 1]);
+
+        // The visible draws are packed in any order at the start of their batch
+        let mut indexed_draws = [&compacted_args[0..5], &compacted_args[5..10]];
+        indexed_draws.sort_by_key(|args| args[4]);
+        assert_eq!(indexed_draws, [[36, 1, 0, 0, 0], [36, 1, 0, 0, 2]]);
+        assert_eq!(compacted_args[15..19], [24, 1, 0, 4]);
+    }
+}
@@ -0,0 +1,75 @@
+// Culls the shadow casters queued to the cascades of directional lights with `GpuShadowCulling`,
+// by writing the instance count of their indirect draws, and packs the draws of the visible
+// instances of each batch together.
+
+#import bevy_pbr::mesh_types::Mesh
+#import bevy_render::maths::affine_to_square
+
+struct ShadowCaster {
+    aabb_center: vec3<f32>,
+    mesh_index: u32,
+    aabb_half_extents: vec3<f32>,
+    frustum_index: u32,
+    batch_index: u32,
+    batch_offset: u32,
+    args_offset: u32,
+    args_words: u32,
+};
+
+struct CascadeFrustum {
+    half_spaces: array<vec4<f32>, 6>,
+};
+
+// This must match UNBOUNDED_FRUSTUM_INDEX in gpu_shadow_culling.rs
+const UNBOUNDED_FRUSTUM_INDEX: u32 = 0xFFFFFFFFu;
+
+@group(0) @binding(0) var<storage> meshes: array<Mesh>;
+@group(0) @binding(1) var<storage> shadow_casters: array<ShadowCaster>;
+@group(0) @binding(2) var<storage> cascade_frusta: array<CascadeFrustum>;
+@group(0) @binding(3) var<storage, read_write> indirect_args: array<u32>;
+@group(0) @binding(4) var<storage, read_write> compacted_args: array<u32>;
+@group(0) @binding(5) var<storage, read_write> draw_counts: array<atomic<u32>>;
+
+@compute
+@workgroup_size(64, 1, 1)
+fn cull_shadow_casters(@builtin(global_invocation_id) global_id: vec3<u32>) {
+    let caster_index = global_id.x;
+    if caster_index >= arrayLength(&shadow_casters) {
+        return;
+    }
+
+    let caster = shadow_casters[caster_index];
+    var visible = true;
+    if caster.frustum_index != UNBOUNDED_FRUSTUM_INDEX {
+        let model = affine_to_square(meshes[caster.mesh_index].model);
+        let model_3x3 = mat3x3<f32>(model[0].xyz, model[1].xyz, model[2].xyz);
+        let center = model * vec4<f32>(caster.aabb_center, 1.0);
+        for (var i = 0u; i < 6u; i += 1u) {
+            // Skip the near plane, as a shadow caster could lie before it.
+            if i == 4u {
+                continue;
+            }
+            let half_space = cascade_frusta[caster.frustum_index].half_spaces[i];
+            // The radius of the oriented bounding box projected on the normal of the half space.
+            let relative_radius = dot(abs(half_space.xyz * model_3x3), caster.aabb_half_extents);
+            if dot(half_space, center) + relative_radius <= 0.0 {
+                visible = false;
+                break;
+            }
+        }
+    }
+
+    // The instance count is the second word of the arguments of both indexed and non-indexed draws.
+    indirect_args[caster.args_offset + 1u] = select(0u, 1u, visible);
+    if !visible {
+        return;
+    }
+
+    // The draws of the visible instances of a batch are packed in any order at the start of the
+    // batch, so that the batch is drawn with `draw_counts[caster.batch_index]` draws.
+    let draw = atomicAdd(&draw_counts[caster.batch_index], 1u);
+    let compacted_offset = caster.batch_offset + draw * caster.args_words;
+    for (var i = 0u; i < caster.args_words; i += 1u) {
+        compacted_args[compacted_offset + i] = indirect_args[caster.args_offset + i];
+    }
+}
@@ -25,6 +25,7 @@ use bevy_utils::{
 };
 use std::{hash::Hash, num::NonZeroU64, ops::Range};
 
+use super::shadow_atlas::{allocate_shadow_atlas, ShadowAtlasRequest};
 use crate::*;
 
 #[derive(Component)]
@@ -39,6 +40,7 @@ pub struct ExtractedPointLight {
     shadow_depth_bias: f32,
     shadow_normal_bias: f32,
     spot_light_angles: Option<(f32, f32)>,
+    shadow_resolution: Option<u32>,
 }
 
 #[derive(Component, Debug)]
@@ -53,6 +55,7 @@ pub struct ExtractedDirectionalLight {
     cascades: EntityHashMap<Entity, Vec<Cascade>>,
     frusta: EntityHashMap<Entity, Vec<Frustum>>,
     render_layers: RenderLayers,
+    gpu_shadow_culling: bool,
 }
 
 #[derive(Copy, Clone, ShaderType, Default, Debug)]
@@ -156,6 +159,10 @@ bitflags::bitflags! {
     }
 }
 
+// The shadow atlas tile of a spot light is packed in the bits of its flags above this offset.
+// NOTE: This must match POINT_LIGHT_FLAGS_SHADOW_ATLAS_TILE_SHIFT in bevy_pbr/src/render/mesh_view_types.wgsl!
+const POINT_LIGHT_FLAGS_SHADOW_ATLAS_TILE_SHIFT: u32 = 2;
+
 #[derive(Copy, Clone, ShaderType, Default, Debug)]
 pub struct GpuDirectionalCascade {
     view_projection: Mat4,
@@ -198,8 +205,8 @@ pub struct GpuLights {
     // w is cluster_dimensions.z * log(near) / log(far / near)
     cluster_factors: Vec4,
     n_directional_lights: u32,
-    // offset from spot light's light index to spot light's shadow map index
-    spot_light_shadowmap_offset: i32,
+    // index of the first page of the spot light shadow atlas in the directional light shadow map array
+    spot_light_shadow_atlas_layer: u32,
 }
 
 // NOTE: this must be kept in sync with the same constants in pbr.frag
@@ -270,8 +277,9 @@ pub struct ExtractedClusterConfig {
 }
 
 enum ExtractedClustersPointLightsElement {
-    ClusterHeader(u32, u32),
+    ClusterHeader(u32, u32, u32),
     LightEntity(Entity),
+    FogVolumeEntity(Entity),
 }
 
 #[derive(Component)]
@@ -294,10 +302,20 @@ pub fn extract_clusters(
             data.push(ExtractedClustersPointLightsElement::ClusterHeader(
                 cluster_lights.point_light_count as u32,
                 cluster_lights.spot_light_count as u32,
+                cluster_lights.fog_volume_count as u32,
             ));
-            for l in &cluster_lights.entities {
+            // The fog volumes come after the point and spot lights.
+            let (lights, fog_volumes) = cluster_lights
+                .entities
+                .split_at(cluster_lights.point_light_count + cluster_lights.spot_light_count);
+            for l in lights {
                 data.push(ExtractedClustersPointLightsElement::LightEntity(*l));
             }
+            for fog_volume in fog_volumes {
+                data.push(ExtractedClustersPointLightsElement::FogVolumeEntity(
+                    *fog_volume,
+                ));
+            }
         }
 
         commands.get_or_spawn(entity).insert((
@@ -316,6 +334,7 @@ pub fn extract_lights(
     mut commands: Commands,
     point_light_shadow_map: Extract<Res<PointLightShadowMap>>,
     directional_light_shadow_map: Extract<Res<DirectionalLightShadowMap>>,
+    spot_light_shadow_atlas: Extract<Res<SpotLightShadowAtlas>>,
     global_point_lights: Extract<Res<GlobalVisiblePointLights>>,
     point_lights: Extract<
         Query<(
@@ -333,6 +352,7 @@ pub fn extract_lights(
             &GlobalTransform,
             &ViewVisibility,
             &Frustum,
+            Option<&ShadowResolution>,
         )>,
     >,
     directional_lights: Extract<
@@ -347,6 +367,7 @@ pub fn extract_lights(
                 &GlobalTransform,
                 &ViewVisibility,
                 Option<&RenderLayers>,
+                Has<GpuShadowCulling>,
             ),
             Without<SpotLight>,
         >,
@@ -362,6 +383,9 @@ pub fn extract_lights(
     if directional_light_shadow_map.is_changed() {
         commands.insert_resource(directional_light_shadow_map.clone());
     }
+    if spot_light_shadow_atlas.is_changed() {
+        commands.insert_resource(spot_light_shadow_atlas.clone());
+    }
     // This is the point light shadow map texel size for one face of the cube as a distance of 1.0
     // world unit from the light.
     // point_light_texel_size = 2.0 * 1.0 * tan(PI / 4.0) / cube face width in texels
@@ -400,6 +424,7 @@ pub fn extract_lights(
                 * point_light_texel_size
                 * std::f32::consts::SQRT_2,
             spot_light_angles: None,
+            shadow_resolution: None,
         };
         point_lights_values.push((
             entity,
@@ -415,8 +440,14 @@ pub fn extract_lights(
 
     let mut spot_lights_values = Vec::with_capacity(*previous_spot_lights_len);
     for entity in global_point_lights.iter().copied() {
-        if let Ok((spot_light, visible_entities, transform, view_visibility, frustum)) =
-            spot_lights.get(entity)
+        if let Ok((
+            spot_light,
+            visible_entities,
+            transform,
+            view_visibility,
+            frustum,
+            shadow_resolution,
+        )) = spot_lights.get(entity)
         {
             if !view_visibility.get() {
                 continue;
@@ -424,6 +455,8 @@ pub fn extract_lights(
             // TODO: This is very much not ideal. We should be able to re-use the vector memory.
             // However, since exclusive access to the main world in extract is ill-advised, we just clone here.
             let render_visible_entities = visible_entities.clone();
+            // The texel size of a whole page of the shadow atlas, scaled to the size of the tile
+            // of the light in `prepare_lights`.
             let texel_size =
                 2.0 * spot_light.outer_angle.tan() / directional_light_shadow_map.size as f32;
 
@@ -449,6 +482,7 @@ pub fn extract_lights(
                             * texel_size
                             * std::f32::consts::SQRT_2,
                         spot_light_angles: Some((spot_light.inner_angle, spot_light.outer_angle)),
+                        shadow_resolution: shadow_resolution.map(|resolution| resolution.0),
                     },
                     render_visible_entities,
                     *frustum,
@@ -469,6 +503,7 @@ pub fn extract_lights(
         transform,
         view_visibility,
         maybe_layers,
+        gpu_shadow_culling,
     ) in &directional_lights
     {
         if !view_visibility.get() {
@@ -490,6 +525,7 @@ pub fn extract_lights(
                 cascades: cascades.cascades.clone(),
                 frusta: frusta.frusta.clone(),
                 render_layers: maybe_layers.copied().unwrap_or_default(),
+                gpu_shadow_culling,
             },
             render_visible_entities,
         ));
@@ -685,12 +721,18 @@ pub fn prepare_lights(
     mut global_light_meta: ResMut<GlobalLightMeta>,
     mut light_meta: ResMut<LightMeta>,
     views: Query<
-        (Entity, &ExtractedView, &ExtractedClusterConfig),
+        (
+            Entity,
+            &ExtractedView,
+            &ExtractedClusterConfig,
+            Option<&CascadeShadowConfig>,
+        ),
         With<RenderPhase<Transparent3d>>,
     >,
     ambient_light: Res<AmbientLight>,
     point_light_shadow_map: Res<PointLightShadowMap>,
     directional_light_shadow_map: Res<DirectionalLightShadowMap>,
+    spot_light_shadow_atlas: Res<SpotLightShadowAtlas>,
     mut max_directional_lights_warning_emitted: Local<bool>,
     mut max_cascades_per_light_warning_emitted: Local<bool>,
     point_lights: Query<(
@@ -751,9 +793,12 @@ pub fn prepare_lights(
     }
 
     if !*max_cascades_per_light_warning_emitted
-        && directional_lights
+        && (directional_lights
             .iter()
             .any(|(_, light)| light.cascade_shadow_config.bounds.len() > MAX_CASCADES_PER_LIGHT)
+            || views.iter().any(|(.., cascade_config)| {
+                cascade_config.is_some_and(|config| config.bounds.len() > MAX_CASCADES_PER_LIGHT)
+            }))
     {
         warn!(
             "The number of cascades configured for a directional light exceeds the supported limit of {}.",
@@ -783,13 +828,12 @@ pub fn prepare_lights(
     let spot_light_shadow_maps_count = point_lights
         .iter()
         .filter(|(_, light, _)| light.shadows_enabled && light.spot_light_angles.is_some())
-        .count()
-        .min(max_texture_array_layers - directional_shadow_enabled_count * MAX_CASCADES_PER_LIGHT);
+        .count();
 
     // Sort lights by
     // - point-light vs spot-light, so that we can iterate point lights and spot lights in contiguous blocks in the fragment shader,
     // - then those with shadows enabled first, so that the index can be used to render at most `point_light_shadow_maps_count`
-    //   point light shadows and `spot_light_shadow_maps_count` spot light shadow maps,
+    //   point light shadows and to find the shadow atlas tiles of the `spot_light_shadow_maps_count` spot lights with shadows,
     // - then by entity as a stable key to ensure that a consistent set of lights are chosen if the light count limit is exceeded.
     point_lights.sort_by(|(entity_1, light_1, _), (entity_2, light_2, _)| {
         point_light_order(
@@ -817,6 +861,42 @@ pub fn prepare_lights(
         )
     });
 
+    // Spot light shadow maps are packed in the pages of the shadow atlas, which follow the
+    // directional light cascades in the directional light shadow map array.
+    let shadow_atlas_page_size = (directional_light_shadow_map.size as u32)
+        .min(render_device.limits().max_texture_dimension_2d);
+    let view_positions = views
+        .iter()
+        .map(|(_, view, _, _)| view.transform.translation())
+        .collect::<Vec<_>>();
+    let shadow_atlas_requests = point_lights
+        .iter()
+        .skip(point_light_count)
+        .take(spot_light_shadow_maps_count)
+        .map(|(_, light, _)| {
+            let position = light.transform.translation();
+            let distance_squared = view_positions
+                .iter()
+                .map(|view_position| view_position.distance_squared(position))
+                .fold(f32::INFINITY, f32::min);
+            ShadowAtlasRequest {
+                resolution: light.shadow_resolution.unwrap_or(shadow_atlas_page_size),
+                // The intensity of the light at the closest camera.
+                importance: light.intensity / (1.0 + distance_squared),
+            }
+        })
+        .collect::<Vec<_>>();
+    let shadow_atlas = allocate_shadow_atlas(
+        shadow_atlas_page_size,
+        spot_light_shadow_atlas.max_pages.min(
+            max_texture_array_layers
+                .saturating_sub(directional_shadow_enabled_count * MAX_CASCADES_PER_LIGHT)
+                as u32,
+        ),
+        spot_light_shadow_atlas.min_resolution,
+        &shadow_atlas_requests,
+    );
+
     if global_light_meta.entity_to_index.capacity() < point_lights.len() {
         global_light_meta
             .entity_to_index
@@ -828,10 +908,12 @@ pub fn prepare_lights(
         let mut flags = PointLightFlags::NONE;
 
         // Lights are sorted, shadow enabled lights are first
+        let shadow_atlas_tile = match light.spot_light_angles {
+            Some(_) if light.shadows_enabled => shadow_atlas.tiles[index - point_light_count],
+            _ => None,
+        };
         if light.shadows_enabled
-            && (index < point_light_shadow_maps_count
-                || (light.spot_light_angles.is_some()
-                    && index - point_light_count < spot_light_shadow_maps_count))
+            && (index < point_light_shadow_maps_count || shadow_atlas_tile.is_some())
         {
             flags |= PointLightFlags::SHADOWS_ENABLED;
         }
@@ -877,61 +959,65 @@ pub fn prepare_lights(
                 .xyz()
                 .extend(1.0 / (light.range * light.range)),
             position_radius: light.transform.translation().extend(light.radius),
-            flags: flags.bits(),
+            flags: flags.bits()
+                | shadow_atlas_tile.map_or(0, |tile| {
+                    tile.pack() << POINT_LIGHT_FLAGS_SHADOW_ATLAS_TILE_SHIFT
+                }),
             shadow_depth_bias: light.shadow_depth_bias,
-            shadow_normal_bias: light.shadow_normal_bias,
+            // The normal bias of spot lights was scaled to the texel size of a whole atlas page
+            // on extraction, and is scaled here to the texel size of their tile.
+            shadow_normal_bias: light.shadow_normal_bias
+                * shadow_atlas_tile.map_or(1.0, |tile| (1u32 << tile.level) as f32),
             spot_light_tan_angle,
         });
         global_light_meta.entity_to_index.insert(entity, index);
     }
 
-    let mut gpu_directional_lights = [GpuDirectionalLight::default(); MAX_DIRECTIONAL_LIGHTS];
-    let mut num_directional_cascades_enabled = 0usize;
-    for (index, (_light_entity, light)) in directional_lights
-        .iter()
-        .enumerate()
-        .take(MAX_DIRECTIONAL_LIGHTS)
-    {
-        let mut flags = DirectionalLightFlags::NONE;
-
-        // Lights are sorted, shadow enabled lights are first
-        if light.shadows_enabled && (index < directional_shadow_enabled_count) {
-            flags |= DirectionalLightFlags::SHADOWS_ENABLED;
-        }
-
-        let num_cascades = light
-            .cascade_shadow_config
-            .bounds
-            .len()
-            .min(MAX_CASCADES_PER_LIGHT);
-        gpu_directional_lights[index] = GpuDirectionalLight {
-            // Filled in later.
-            cascades: [GpuDirectionalCascade::default(); MAX_CASCADES_PER_LIGHT],
-            // premultiply color by illuminance
-            // we don't use the alpha at all, so no reason to multiply only [0..3]
-            color: Vec4::from_slice(&light.color.as_linear_rgba_f32()) * light.illuminance,
-            // direction is negated to be ready for N.L
-            dir_to_light: light.transform.back(),
-            flags: flags.bits(),
-            shadow_depth_bias: light.shadow_depth_bias,
-            shadow_normal_bias: light.shadow_normal_bias,
-            num_cascades: num_cascades as u32,
-            cascades_overlap_proportion: light.cascade_shadow_config.overlap_proportion,
-            depth_texture_base_index: num_directional_cascades_enabled as u32,
-            render_layers: light.render_layers.bits(),
-        };
-        if index < directional_shadow_enabled_count {
-            num_directional_cascades_enabled += num_cascades;
-        }
-    }
-
     global_light_meta.gpu_point_lights.set(gpu_point_lights);
     global_light_meta
         .gpu_point_lights
         .write_buffer(&render_device, &render_queue);
 
     // set up light data for each view
-    for (entity, extracted_view, clusters) in &views {
+    for (entity, extracted_view, clusters, view_cascade_config) in &views {
+        let mut gpu_directional_lights = [GpuDirectionalLight::default(); MAX_DIRECTIONAL_LIGHTS];
+        let mut num_directional_cascades_enabled = 0usize;
+        for (index, (_light_entity, light)) in directional_lights
+            .iter()
+            .enumerate()
+            .take(MAX_DIRECTIONAL_LIGHTS)
+        {
+            let mut flags = DirectionalLightFlags::NONE;
+
+            // Lights are sorted, shadow enabled lights are first
+            if light.shadows_enabled && (index < directional_shadow_enabled_count) {
+                flags |= DirectionalLightFlags::SHADOWS_ENABLED;
+            }
+
+            // A camera with a `CascadeShadowConfig` overrides the ones of the lights.
+            let cascade_config = view_cascade_config.unwrap_or(&light.cascade_shadow_config);
+            let num_cascades = cascade_config.bounds.len().min(MAX_CASCADES_PER_LIGHT);
+            gpu_directional_lights[index] = GpuDirectionalLight {
+                // Filled in later.
+                cascades: [GpuDirectionalCascade::default(); MAX_CASCADES_PER_LIGHT],
+                // premultiply color by illuminance
+                // we don't use the alpha at all, so no reason to multiply only [0..3]
+                color: Vec4::from_slice(&light.color.as_linear_rgba_f32()) * light.illuminance,
+                // direction is negated to be ready for N.L
+                dir_to_light: light.transform.back(),
+                flags: flags.bits(),
+                shadow_depth_bias: light.shadow_depth_bias,
+                shadow_normal_bias: light.shadow_normal_bias,
+                num_cascades: num_cascades as u32,
+                cascades_overlap_proportion: cascade_config.overlap_proportion,
+                depth_texture_base_index: num_directional_cascades_enabled as u32,
+                render_layers: light.render_layers.bits(),
+            };
+            if index < directional_shadow_enabled_count {
+                num_directional_cascades_enabled += num_cascades;
+            }
+        }
+
         let point_light_depth_texture = texture_cache.get(
             &render_device,
             TextureDescriptor {
@@ -953,13 +1039,11 @@ pub fn prepare_lights(
             &render_device,
             TextureDescriptor {
                 size: Extent3d {
-                    width: (directional_light_shadow_map.size as u32)
-                        .min(render_device.limits().max_texture_dimension_2d),
-                    height: (directional_light_shadow_map.size as u32)
-                        .min(render_device.limits().max_texture_dimension_2d),
-                    depth_or_array_layers: (num_directional_cascades_enabled
-                        + spot_light_shadow_maps_count)
-                        .max(1) as u32,
+                    width: shadow_atlas_page_size,
+                    height: shadow_atlas_page_size,
+                    depth_or_array_layers: (num_directional_cascades_enabled as u32
+                        + shadow_atlas.page_count)
+                        .max(1),
                 },
                 mip_level_count: 1,
                 sample_count: 1,
@@ -993,11 +1077,9 @@ pub fn prepare_lights(
             ),
             cluster_dimensions: clusters.dimensions.extend(n_clusters),
             n_directional_lights: directional_lights.iter().len() as u32,
-            // spotlight shadow maps are stored in the directional light array, starting at num_directional_cascades_enabled.
-            // the spot lights themselves start in the light array at point_light_count. so to go from light
-            // index to shadow map index, we need to subtract point light count and add directional shadowmap count.
-            spot_light_shadowmap_offset: num_directional_cascades_enabled as i32
-                - point_light_count as i32,
+            // the pages of the spot light shadow atlas are stored in the directional light array,
+            // starting at num_directional_cascades_enabled.
+            spot_light_shadow_atlas_layer: num_directional_cascades_enabled as u32,
         };
 
         // TODO: this should select lights based on relevance to the view instead of the first ones that show up in a query
@@ -1071,12 +1153,19 @@ pub fn prepare_lights(
         }
 
         // spot lights
-        for (light_index, &(light_entity, light, (_, spot_light_frustum))) in point_lights
+        // All the tiles of an atlas page share its attachment, so that only the first shadow
+        // pass rendering to the page clears it.
+        let mut shadow_atlas_pages: Vec<Option<DepthAttachment>> =
+            vec![None; shadow_atlas.page_count as usize];
+        for (light_index, (&(light_entity, light, (_, spot_light_frustum)), tile)) in point_lights
             .iter()
             .skip(point_light_count)
-            .take(spot_light_shadow_maps_count)
+            .zip(&shadow_atlas.tiles)
             .enumerate()
         {
+            let Some(tile) = tile else {
+                continue;
+            };
             let spot_view_matrix = spot_light_view_matrix(&light.transform);
             let spot_view_transform = spot_view_matrix.into();
 
@@ -1084,33 +1173,32 @@ pub fn prepare_lights(
                 [point_light_count..point_light_count + spot_light_shadow_maps_count] are spot lights").1;
             let spot_projection = spot_light_projection_matrix(angle);
 
-            let depth_texture_view =
-                directional_light_depth_texture
-                    .texture
-                    .create_view(&TextureViewDescriptor {
-                        label: Some("spot_light_shadow_map_texture_view"),
-                        format: None,
-                        dimension: Some(TextureViewDimension::D2),
-                        aspect: TextureAspect::All,
-                        base_mip_level: 0,
-                        mip_level_count: None,
-                        base_array_layer: (num_directional_cascades_enabled + light_index) as u32,
-                        array_layer_count: Some(1u32),
-                    });
+            let depth_attachment = shadow_atlas_pages[tile.page as usize]
+                .get_or_insert_with(|| {
+                    let depth_texture_view = directional_light_depth_texture.texture.create_view(
+                        &TextureViewDescriptor {
+                            label: Some("spot_light_shadow_atlas_texture_view"),
+                            format: None,
+                            dimension: Some(TextureViewDimension::D2),
+                            aspect: TextureAspect::All,
+                            base_mip_level: 0,
+                            mip_level_count: None,
+                            base_array_layer: num_directional_cascades_enabled as u32 + tile.page,
+                            array_layer_count: Some(1u32),
+                        },
+                    );
+                    DepthAttachment::new(depth_texture_view, Some(0.0))
+                })
+                .clone();
 
             let view_light_entity = commands
                 .spawn((
                     ShadowView {
-                        depth_attachment: DepthAttachment::new(depth_texture_view, Some(0.0)),
+                        depth_attachment,
                         pass_name: format!("shadow pass spot light {light_index}"),
                     },
                     ExtractedView {
-                        viewport: UVec4::new(
-                            0,
-                            0,
-                            directional_light_shadow_map.size as u32,
-                            directional_light_shadow_map.size as u32,
-                        ),
+                        viewport: tile.viewport(shadow_atlas_page_size),
                         transform: spot_view_transform,
                         projection: spot_projection,
                         view_projection: None,
@@ -1127,6 +1215,7 @@ pub fn prepare_lights(
         }
 
         // directional lights
+        let gpu_shadow_culling_supported = gpu_shadow_culling_supported(&render_device);
         let mut directional_depth_texture_array_index = 0u32;
         for (light_index, &(light_entity, light)) in directional_lights
             .iter()
@@ -1147,7 +1236,11 @@ pub fn prepare_lights(
                 .take(MAX_CASCADES_PER_LIGHT);
             for (cascade_index, ((cascade, frusta), bound)) in cascades
                 .zip(frusta)
-                .zip(&light.cascade_shadow_config.bounds)
+                .zip(
+                    &view_cascade_config
+                        .unwrap_or(&light.cascade_shadow_config)
+                        .bounds,
+                )
                 .enumerate()
             {
                 gpu_lights.directional_lights[light_index].cascades[cascade_index] =
@@ -1183,8 +1276,8 @@ pub fn prepare_lights(
                             viewport: UVec4::new(
                                 0,
                                 0,
-                                directional_light_shadow_map.size as u32,
-                                directional_light_shadow_map.size as u32,
+                                shadow_atlas_page_size,
+                                shadow_atlas_page_size,
                             ),
                             transform: GlobalTransform::from(cascade.view_transform),
                             projection: cascade.projection,
@@ -1200,6 +1293,11 @@ pub fn prepare_lights(
                         },
                     ))
                     .id();
+                if light.gpu_shadow_culling && gpu_shadow_culling_supported {
+                    commands
+                        .entity(view_light_entity)
+                        .insert(GpuCulledShadowView);
+                }
                 view_lights.push(view_light_entity);
             }
         }
@@ -1284,6 +1382,7 @@ const CLUSTER_COUNT_MASK: u32 = (1 << CLUSTER_COUNT_SIZE) - 1;
 // the point light count into bits 9-17, and the spot light count into bits 0-8.
 //  [ 31     ..     18 | 17      ..      9 | 8       ..     0 ]
 //  [      offset      | point light count | spot light count ]
+// There is no room left for the fog volume count, so fog volumes are not clustered in this layout.
 // NOTE: This assumes CPU and GPU endianness are the same which is true
 // for all common and tested x86/ARM CPUs and AMD/NVIDIA/Intel/Apple/etc GPUs
 fn pack_offset_and_counts(offset: usize, point_count: usize, spot_count: usize) -> u32 {
@@ -1410,7 +1509,13 @@ impl ViewClusterBindings {
         }
     }
 
-    pub fn push_offset_and_counts(&mut self, offset: usize, point_count: usize, spot_count: usize) {
+    pub fn push_offset_and_counts(
+        &mut self,
+        offset: usize,
+        point_count: usize,
+        spot_count: usize,
+        fog_volume_count: usize,
+    ) {
         match &mut self.buffers {
             ViewClusterBuffers::Uniform {
                 cluster_offsets_and_counts,
@@ -1434,7 +1539,7 @@ impl ViewClusterBindings {
                     offset as u32,
                     point_count as u32,
                     spot_count as u32,
-                    0,
+                    fog_volume_count as u32,
                 ));
             }
         }
@@ -1541,14 +1646,21 @@ pub fn prepare_clusters(
     render_queue: Res<RenderQueue>,
     mesh_pipeline: Res<MeshPipeline>,
     global_light_meta: Res<GlobalLightMeta>,
-    views: Query<(Entity, &ExtractedClustersPointLights), With<RenderPhase<Transparent3d>>>,
+    views: Query<
+        (
+            Entity,
+            &ExtractedClustersPointLights,
+            Option<&ExtractedFogVolumes>,
+        ),
+        With<RenderPhase<Transparent3d>>,
+    >,
 ) {
     let render_device = render_device.into_inner();
     let supports_storage_buffers = matches!(
         mesh_pipeline.clustered_forward_buffer_binding_type,
         BufferBindingType::Storage { .. }
     );
-    for (entity, extracted_clusters) in &views {
+    for (entity, extracted_clusters, extracted_fog_volumes) in &views {
         let mut view_clusters_bindings =
             ViewClusterBindings::new(mesh_pipeline.clustered_forward_buffer_binding_type);
         view_clusters_bindings.clear();
@@ -1558,12 +1670,14 @@ pub fn prepare_clusters(
                 ExtractedClustersPointLightsElement::ClusterHeader(
                     point_light_count,
                     spot_light_count,
+                    fog_volume_count,
                 ) => {
                     let offset = view_clusters_bindings.n_indices();
                     view_clusters_bindings.push_offset_and_counts(
                         offset,
                         *point_light_count as usize,
                         *spot_light_count as usize,
+                        *fog_volume_count as usize,
                     );
                 }
                 ExtractedClustersPointLightsElement::LightEntity(entity) => {
@@ -1577,6 +1691,13 @@ pub fn prepare_clusters(
                         view_clusters_bindings.push_index(*light_index);
                     }
                 }
+                ExtractedClustersPointLightsElement::FogVolumeEntity(entity) => {
+                    if let Some(fog_volume_index) = extracted_fog_volumes
+                        .and_then(|fog_volumes| fog_volumes.entity_to_index.get(entity))
+                    {
+                        view_clusters_bindings.push_index(*fog_volume_index);
+                    }
+                }
             }
         }
 
@@ -1597,7 +1718,11 @@ pub fn queue_shadows<M: Material>(
     mut pipelines: ResMut<SpecializedMeshPipelines<PrepassPipeline<M>>>,
     pipeline_cache: Res<PipelineCache>,
     view_lights: Query<(Entity, &ViewLightEntities)>,
-    mut view_light_shadow_phases: Query<(&LightEntity, &mut RenderPhase<Shadow>)>,
+    mut view_light_shadow_phases: Query<(
+        &LightEntity,
+        &mut RenderPhase<Shadow>,
+        Has<GpuCulledShadowView>,
+    )>,
     point_light_entities: Query<&CubemapVisibleEntities, With<ExtractedPointLight>>,
     directional_light_entities: Query<&CascadesVisibleEntities, With<ExtractedDirectionalLight>>,
     spot_light_entities: Query<&VisibleEntities, With<ExtractedPointLight>>,
@@ -1606,9 +1731,16 @@ pub fn queue_shadows<M: Material>(
 {
     for (entity, view_lights) in &view_lights {
         let draw_shadow_mesh = shadow_draw_functions.read().id::<DrawPrepass<M>>();
+        let draw_gpu_culled_shadow_mesh =
+            shadow_draw_functions.read().id::<DrawGpuCulledShadow<M>>();
         for view_light_entity in view_lights.lights.iter().copied() {
-            let (light_entity, mut shadow_phase) =
+            let (light_entity, mut shadow_phase, gpu_culled) =
                 view_light_shadow_phases.get_mut(view_light_entity).unwrap();
+            let draw_function = if gpu_culled {
+                draw_gpu_culled_shadow_mesh
+            } else {
+                draw_shadow_mesh
+            };
             let is_directional_light = matches!(light_entity, LightEntity::Directional { .. });
             let visible_entities = match light_entity {
                 LightEntity::Directional {
@@ -1687,7 +1819,7 @@ pub fn queue_shadows<M: Material>(
                 };
 
                 shadow_phase.add(Shadow {
-                    draw_function: draw_shadow_mesh,
+                    draw_function,
                     pipeline: pipeline_id,
                     entity,
                     distance: 0.0, // TODO: sort front-to-back
@@ -1764,7 +1896,11 @@ impl CachedRenderPipelinePhaseItem for Shadow {
 
 pub struct ShadowPassNode {
     main_view_query: QueryState<&'static ViewLightEntities>,
-    view_light_query: QueryState<(&'static ShadowView, &'static RenderPhase<Shadow>)>,
+    view_light_query: QueryState<(
+        &'static ShadowView,
+        &'static ExtractedView,
+        &'static RenderPhase<Shadow>,
+    )>,
 }
 
 impl ShadowPassNode {
@@ -1790,8 +1926,10 @@ impl Node for ShadowPassNode {
     ) -> Result<(), NodeRunError> {
         let view_entity = graph.view_entity();
         if let Ok(view_lights) = self.main_view_query.get_manual(world, view_entity) {
+            cull_shadow_casters(render_context, world, view_entity);
+
             for view_light_entity in view_lights.lights.iter().copied() {
-                let (view_light, shadow_phase) = self
+                let (view_light, extracted_view, shadow_phase) = self
                     .view_light_query
                     .get_manual(world, view_light_entity)
                     .unwrap();
@@ -1816,6 +1954,10 @@ impl Node for ShadowPassNode {
                         occlusion_query_set: None,
                     });
                     let mut render_pass = TrackedRenderPass::new(&render_device, render_pass);
+                    // Spot light shadow maps are rendered to a tile of an atlas page.
+                    let viewport = extracted_view.viewport.as_vec4();
+                    render_pass
+                        .set_viewport(viewport.x, viewport.y, viewport.z, viewport.w, 0.0, 1.0);
 
                     shadow_phase.render(&mut render_pass, world, view_light_entity);
 
@@ -1828,3 +1970,41 @@ impl Node for ShadowPassNode {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fog_volume_counts_are_packed_with_storage_buffers() {
+        let mut bindings = ViewClusterBindings::new(BufferBindingType::Storage { read_only: true });
+        bindings.push_offset_and_counts(0, 2, 1, 3);
+        bindings.push_offset_and_counts(6, 0, 0, 1);
+        let ViewClusterBuffers::Storage {
+            cluster_offsets_and_counts,
+            ..
+        } = &bindings.buffers
+        else {
+            unreachable!();
+        };
+        assert_eq!(
+            cluster_offsets_and_counts.get().data,
+            [UVec4::new(0, 2, 1, 3), UVec4::new(6, 0, 0, 1)]
+        );
+
+        // The packed uniform layout has no room for the fog volume count.
+        let mut bindings = ViewClusterBindings::new(BufferBindingType::Uniform);
+        bindings.push_offset_and_counts(0, 2, 1, 3);
+        let ViewClusterBuffers::Uniform {
+            cluster_offsets_and_counts,
+            ..
+        } = &bindings.buffers
+        else {
+            unreachable!();
+        };
+        assert_eq!(
+            cluster_offsets_and_counts.get().data[0].x,
+            pack_offset_and_counts(0, 2, 1)
+        );
+    }
+}
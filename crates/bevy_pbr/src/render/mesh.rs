@@ -1,12 +1,16 @@
 use crate::{
     MaterialBindGroupId, NotShadowCaster, NotShadowReceiver, PreviousGlobalTransform, Shadow,
-    ViewFogUniformOffset, ViewLightProbesUniformOffset, ViewLightsUniformOffset,
-    CLUSTERED_FORWARD_STORAGE_BUFFER_COUNT, MAX_CASCADES_PER_LIGHT, MAX_DIRECTIONAL_LIGHTS,
+    TransparentPrepass, ViewFogUniformOffset, ViewLightProbesUniformOffset,
+    ViewLightsUniformOffset, CLUSTERED_FORWARD_STORAGE_BUFFER_COUNT, MAX_CASCADES_PER_LIGHT,
+    MAX_DIRECTIONAL_LIGHTS,
 };
 use bevy_app::{Plugin, PostUpdate};
 use bevy_asset::{load_internal_asset, AssetId, Handle};
 use bevy_core_pipeline::{
-    core_3d::{AlphaMask3d, Opaque3d, Transmissive3d, Transparent3d, CORE_3D_DEPTH_FORMAT},
+    core_3d::{
+        AlphaMask3d, Opaque3d, Transmissive3d, Transparent3d, CORE_3D_DEPTH_FORMAT,
+        CORE_3D_DEPTH_STENCIL_FORMAT,
+    },
     deferred::{AlphaMask3dDeferred, Opaque3dDeferred},
 };
 use bevy_derive::{Deref, DerefMut};
@@ -21,7 +25,7 @@ use bevy_render::{
         batch_and_prepare_render_phase, write_batched_instance_buffer, GetBatchData,
         NoAutomaticBatching,
     },
-    mesh::*,
+    mesh::{skinning::SkinnedMeshInstance, *},
     render_asset::RenderAssets,
     render_phase::{PhaseItem, RenderCommand, RenderCommandResult, TrackedRenderPass},
     render_resource::*,
@@ -49,7 +53,7 @@ use crate::render::{
 };
 use crate::*;
 
-use super::skin::SkinIndices;
+use super::skin::{SkinIndex, SkinIndices};
 
 #[derive(Default)]
 pub struct MeshRenderPlugin;
@@ -237,6 +241,9 @@ bitflags::bitflags! {
     pub struct MeshFlags: u32 {
         const SHADOW_RECEIVER             = 1 << 0;
         const TRANSMITTED_SHADOW_RECEIVER = 1 << 1;
+        // The joint matrices of the mesh are relative to its model matrix, set for
+        // `SkinnedMeshInstance`s.
+        const SKINNED_INSTANCE            = 1 << 2;
         // Indicates the sign of the determinant of the 3x3 model matrix. If the sign is positive,
         // then the flag should be set, else it should not be set.
         const SIGN_DETERMINANT_MODEL_3X3  = 1 << 31;
@@ -251,6 +258,7 @@ pub struct RenderMeshInstance {
     pub material_bind_group_id: MaterialBindGroupId,
     pub shadow_caster: bool,
     pub automatic_batching: bool,
+    pub transparent_prepass: bool,
 }
 
 #[derive(Default, Resource, Deref, DerefMut)]
@@ -270,6 +278,8 @@ pub fn extract_meshes(
             Has<TransmittedShadowReceiver>,
             Has<NotShadowCaster>,
             Has<NoAutomaticBatching>,
+            Has<SkinnedMeshInstance>,
+            Has<TransparentPrepass>,
         )>,
     >,
 ) {
@@ -284,6 +294,8 @@ pub fn extract_meshes(
             transmitted_receiver,
             not_shadow_caster,
             no_automatic_batching,
+            skinned_instance,
+            transparent_prepass,
         )| {
             if !view_visibility.get() {
                 return;
@@ -298,6 +310,9 @@ pub fn extract_meshes(
             if transmitted_receiver {
                 flags |= MeshFlags::TRANSMITTED_SHADOW_RECEIVER;
             }
+            if skinned_instance {
+                flags |= MeshFlags::SKINNED_INSTANCE;
+            }
             if transform.matrix3.determinant().is_sign_positive() {
                 flags |= MeshFlags::SIGN_DETERMINANT_MODEL_3X3;
             }
@@ -316,6 +331,7 @@ pub fn extract_meshes(
                     shadow_caster: !not_shadow_caster,
                     material_bind_group_id: MaterialBindGroupId::default(),
                     automatic_batching: !no_automatic_batching,
+                    transparent_prepass,
                 },
             ));
             tls.set(queue);
@@ -361,8 +377,10 @@ impl FromWorld for MeshPipeline {
             Res<RenderDevice>,
             Res<DefaultImageSampler>,
             Res<RenderQueue>,
+            Option<Res<MeshInstanceDataLayout>>,
         )> = SystemState::new(world);
-        let (render_device, default_sampler, render_queue) = system_state.get_mut(world);
+        let (render_device, default_sampler, render_queue, instance_data_layout) =
+            system_state.get_mut(world);
         let clustered_forward_buffer_binding_type = render_device
             .get_supported_read_only_binding_type(CLUSTERED_FORWARD_STORAGE_BUFFER_COUNT);
 
@@ -403,12 +421,20 @@ impl FromWorld for MeshPipeline {
             }
         };
 
+        let per_object_buffer_batch_size =
+            GpuArrayBuffer::<MeshUniform>::batch_size(&render_device);
+        // Per-instance data is only supported when the mesh uniforms are in a storage buffer,
+        // so that both are indexed by the instance index.
+        let instance_data = instance_data_layout
+            .filter(|_| per_object_buffer_batch_size.is_none())
+            .map(|layout| layout.min_binding_size);
+
         MeshPipeline {
             view_layouts,
             clustered_forward_buffer_binding_type,
             dummy_white_gpu_image,
-            mesh_layouts: MeshLayouts::new(&render_device),
-            per_object_buffer_batch_size: GpuArrayBuffer::<MeshUniform>::batch_size(&render_device),
+            mesh_layouts: MeshLayouts::with_instance_data(&render_device, instance_data),
+            per_object_buffer_batch_size,
             binding_arrays_are_usable: binding_arrays_are_usable(&render_device),
         }
     }
@@ -446,15 +472,25 @@ impl MeshPipeline {
 }
 
 impl GetBatchData for MeshPipeline {
-    type Param = (SRes<RenderMeshInstances>, SRes<RenderLightmaps>);
-    // The material bind group ID, the mesh ID, and the lightmap ID,
-    // respectively.
-    type CompareData = (MaterialBindGroupId, AssetId<Mesh>, Option<AssetId<Image>>);
+    type Param = (
+        SRes<RenderMeshInstances>,
+        SRes<RenderLightmaps>,
+        SRes<SkinIndices>,
+    );
+    // The material bind group ID, the mesh ID, the lightmap ID, and the
+    // skin index, respectively. Only `SkinnedMeshInstance`s sharing a pose
+    // can be batched with a skin index.
+    type CompareData = (
+        MaterialBindGroupId,
+        AssetId<Mesh>,
+        Option<AssetId<Image>>,
+        Option<SkinIndex>,
+    );
 
     type BufferData = MeshUniform;
 
     fn get_batch_data(
-        (mesh_instances, lightmaps): &SystemParamItem<Self::Param>,
+        (mesh_instances, lightmaps, skin_indices): &SystemParamItem<Self::Param>,
         entity: Entity,
     ) -> Option<(Self::BufferData, Option<Self::CompareData>)> {
         let mesh_instance = mesh_instances.get(&entity)?;
@@ -469,6 +505,7 @@ impl GetBatchData for MeshPipeline {
                 mesh_instance.material_bind_group_id,
                 mesh_instance.mesh_asset_id,
                 maybe_lightmap.map(|lightmap| lightmap.image),
+                skin_indices.get(&entity).copied(),
             )),
         ))
     }
@@ -479,7 +516,7 @@ bitflags::bitflags! {
     #[repr(transparent)]
     // NOTE: Apparently quadro drivers support up to 64x MSAA.
     /// MSAA uses the highest 3 bits for the MSAA log2(sample count) to support up to 128x MSAA.
-    pub struct MeshPipelineKey: u32 {
+    pub struct MeshPipelineKey: u64 {
         const NONE                              = 0;
         const HDR                               = 1 << 0;
         const TONEMAP_IN_SHADER                 = 1 << 1;
@@ -498,6 +535,9 @@ bitflags::bitflags! {
         const READS_VIEW_TRANSMISSION_TEXTURE   = 1 << 13;
         const LIGHTMAPPED                       = 1 << 14;
         const IRRADIANCE_VOLUME                 = 1 << 15;
+        const FORCE_UNLIT                       = 1 << 16;
+        const DEPTH_STENCIL                     = 1 << 17; // ← The depth texture of the view has a stencil buffer
+        const DEBUG_SHADOW_CASCADES             = 1 << 18; // ← Tint by directional light shadow cascade, see `CascadeDebugVisualization`
         const BLEND_RESERVED_BITS               = Self::BLEND_MASK_BITS << Self::BLEND_SHIFT_BITS; // ← Bitmask reserving bits for the blend state
         const BLEND_OPAQUE                      = 0 << Self::BLEND_SHIFT_BITS;                   // ← Values are just sequential within the mask, and can range from 0 to 3
         const BLEND_PREMULTIPLIED_ALPHA         = 1 << Self::BLEND_SHIFT_BITS;                   //
@@ -532,36 +572,36 @@ bitflags::bitflags! {
 }
 
 impl MeshPipelineKey {
-    const MSAA_MASK_BITS: u32 = 0b111;
-    const MSAA_SHIFT_BITS: u32 = 32 - Self::MSAA_MASK_BITS.count_ones();
+    const MSAA_MASK_BITS: u64 = 0b111;
+    const MSAA_SHIFT_BITS: u32 = 64 - Self::MSAA_MASK_BITS.count_ones();
 
-    const PRIMITIVE_TOPOLOGY_MASK_BITS: u32 = 0b111;
+    const PRIMITIVE_TOPOLOGY_MASK_BITS: u64 = 0b111;
     const PRIMITIVE_TOPOLOGY_SHIFT_BITS: u32 =
         Self::MSAA_SHIFT_BITS - Self::PRIMITIVE_TOPOLOGY_MASK_BITS.count_ones();
 
-    const BLEND_MASK_BITS: u32 = 0b11;
+    const BLEND_MASK_BITS: u64 = 0b11;
     const BLEND_SHIFT_BITS: u32 =
         Self::PRIMITIVE_TOPOLOGY_SHIFT_BITS - Self::BLEND_MASK_BITS.count_ones();
 
-    const TONEMAP_METHOD_MASK_BITS: u32 = 0b111;
+    const TONEMAP_METHOD_MASK_BITS: u64 = 0b111;
     const TONEMAP_METHOD_SHIFT_BITS: u32 =
         Self::BLEND_SHIFT_BITS - Self::TONEMAP_METHOD_MASK_BITS.count_ones();
 
-    const SHADOW_FILTER_METHOD_MASK_BITS: u32 = 0b11;
+    const SHADOW_FILTER_METHOD_MASK_BITS: u64 = 0b11;
     const SHADOW_FILTER_METHOD_SHIFT_BITS: u32 =
         Self::TONEMAP_METHOD_SHIFT_BITS - Self::SHADOW_FILTER_METHOD_MASK_BITS.count_ones();
 
-    const VIEW_PROJECTION_MASK_BITS: u32 = 0b11;
+    const VIEW_PROJECTION_MASK_BITS: u64 = 0b11;
     const VIEW_PROJECTION_SHIFT_BITS: u32 =
         Self::SHADOW_FILTER_METHOD_SHIFT_BITS - Self::VIEW_PROJECTION_MASK_BITS.count_ones();
 
-    const SCREEN_SPACE_SPECULAR_TRANSMISSION_MASK_BITS: u32 = 0b11;
+    const SCREEN_SPACE_SPECULAR_TRANSMISSION_MASK_BITS: u64 = 0b11;
     const SCREEN_SPACE_SPECULAR_TRANSMISSION_SHIFT_BITS: u32 = Self::VIEW_PROJECTION_SHIFT_BITS
         - Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_MASK_BITS.count_ones();
 
     pub fn from_msaa_samples(msaa_samples: u32) -> Self {
         let msaa_bits =
-            (msaa_samples.trailing_zeros() & Self::MSAA_MASK_BITS) << Self::MSAA_SHIFT_BITS;
+            (msaa_samples.trailing_zeros() as u64 & Self::MSAA_MASK_BITS) << Self::MSAA_SHIFT_BITS;
         Self::from_bits_retain(msaa_bits)
     }
 
@@ -573,12 +613,21 @@ impl MeshPipelineKey {
         }
     }
 
+    /// The format of the depth texture of the view.
+    pub fn depth_stencil_format(&self) -> TextureFormat {
+        if self.contains(MeshPipelineKey::DEPTH_STENCIL) {
+            CORE_3D_DEPTH_STENCIL_FORMAT
+        } else {
+            CORE_3D_DEPTH_FORMAT
+        }
+    }
+
     pub fn msaa_samples(&self) -> u32 {
-        1 << ((self.bits() >> Self::MSAA_SHIFT_BITS) & Self::MSAA_MASK_BITS)
+        1 << ((self.bits() >> Self::MSAA_SHIFT_BITS) & Self::MSAA_MASK_BITS) as u32
     }
 
     pub fn from_primitive_topology(primitive_topology: PrimitiveTopology) -> Self {
-        let primitive_topology_bits = ((primitive_topology as u32)
+        let primitive_topology_bits = ((primitive_topology as u64)
             & Self::PRIMITIVE_TOPOLOGY_MASK_BITS)
             << Self::PRIMITIVE_TOPOLOGY_SHIFT_BITS;
         Self::from_bits_retain(primitive_topology_bits)
@@ -588,11 +637,11 @@ impl MeshPipelineKey {
         let primitive_topology_bits = (self.bits() >> Self::PRIMITIVE_TOPOLOGY_SHIFT_BITS)
             & Self::PRIMITIVE_TOPOLOGY_MASK_BITS;
         match primitive_topology_bits {
-            x if x == PrimitiveTopology::PointList as u32 => PrimitiveTopology::PointList,
-            x if x == PrimitiveTopology::LineList as u32 => PrimitiveTopology::LineList,
-            x if x == PrimitiveTopology::LineStrip as u32 => PrimitiveTopology::LineStrip,
-            x if x == PrimitiveTopology::TriangleList as u32 => PrimitiveTopology::TriangleList,
-            x if x == PrimitiveTopology::TriangleStrip as u32 => PrimitiveTopology::TriangleStrip,
+            x if x == PrimitiveTopology::PointList as u64 => PrimitiveTopology::PointList,
+            x if x == PrimitiveTopology::LineList as u64 => PrimitiveTopology::LineList,
+            x if x == PrimitiveTopology::LineStrip as u64 => PrimitiveTopology::LineStrip,
+            x if x == PrimitiveTopology::TriangleList as u64 => PrimitiveTopology::TriangleList,
+            x if x == PrimitiveTopology::TriangleStrip as u64 => PrimitiveTopology::TriangleStrip,
             _ => PrimitiveTopology::default(),
         }
     }
@@ -826,6 +875,14 @@ impl SpecializedMeshPipeline for MeshPipeline {
             shader_defs.push("LIGHTMAP".into());
         }
 
+        if key.contains(MeshPipelineKey::FORCE_UNLIT) {
+            shader_defs.push("FORCE_UNLIT".into());
+        }
+
+        if key.contains(MeshPipelineKey::DEBUG_SHADOW_CASCADES) {
+            shader_defs.push("DIRECTIONAL_LIGHT_SHADOW_MAP_DEBUG_CASCADES".into());
+        }
+
         if key.contains(MeshPipelineKey::TEMPORAL_JITTER) {
             shader_defs.push("TEMPORAL_JITTER".into());
         }
@@ -915,7 +972,7 @@ impl SpecializedMeshPipeline for MeshPipeline {
                 strip_index_format: None,
             },
             depth_stencil: Some(DepthStencilState {
-                format: CORE_3D_DEPTH_FORMAT,
+                format: key.depth_stencil_format(),
                 depth_write_enabled,
                 depth_compare: CompareFunction::GreaterEqual,
                 stencil: StencilState {
@@ -984,26 +1041,38 @@ pub fn prepare_mesh_bind_group(
     skins_uniform: Res<SkinUniform>,
     weights_uniform: Res<MorphUniform>,
     render_lightmaps: Res<RenderLightmaps>,
+    instance_data: Option<Res<MeshInstanceDataBinding>>,
 ) {
     groups.reset();
     let layouts = &mesh_pipeline.mesh_layouts;
     let Some(model) = mesh_uniforms.binding() else {
         return;
     };
-    groups.model_only = Some(layouts.model_only(&render_device, &model));
+    let instance_data = instance_data.as_ref().and_then(|data| data.buffer.as_ref());
+    if layouts.has_instance_data() && instance_data.is_none() {
+        return;
+    }
+    groups.model_only = Some(layouts.model_only(&render_device, &model, instance_data));
 
     let skin = skins_uniform.buffer.buffer();
     if let Some(skin) = skin {
-        groups.skinned = Some(layouts.skinned(&render_device, &model, skin));
+        groups.skinned = Some(layouts.skinned(&render_device, &model, skin, instance_data));
     }
 
     if let Some(weights) = weights_uniform.buffer.buffer() {
         for (id, gpu_mesh) in meshes.iter() {
             if let Some(targets) = gpu_mesh.morph_targets.as_ref() {
                 let group = if let Some(skin) = skin.filter(|_| is_skinned(&gpu_mesh.layout)) {
-                    layouts.morphed_skinned(&render_device, &model, skin, weights, targets)
+                    layouts.morphed_skinned(
+                        &render_device,
+                        &model,
+                        skin,
+                        weights,
+                        targets,
+                        instance_data,
+                    )
                 } else {
-                    layouts.morphed(&render_device, &model, weights, targets)
+                    layouts.morphed(&render_device, &model, weights, targets, instance_data)
                 };
                 groups.morph_targets.insert(id, group);
             }
@@ -1015,7 +1084,7 @@ pub fn prepare_mesh_bind_group(
         if let (Entry::Vacant(entry), Some(image)) =
             (groups.lightmaps.entry(image_id), images.get(image_id))
         {
-            entry.insert(layouts.lightmapped(&render_device, &model, image));
+            entry.insert(layouts.lightmapped(&render_device, &model, image, instance_data));
         }
     }
 }
@@ -19,7 +19,10 @@ mod layout_entry {
     use crate::MeshUniform;
     use bevy_render::{
         render_resource::{
-            binding_types::{sampler, texture_2d, texture_3d, uniform_buffer_sized},
+            binding_types::{
+                sampler, storage_buffer_read_only_sized, texture_2d, texture_3d,
+                uniform_buffer_sized,
+            },
             BindGroupLayoutEntryBuilder, BufferSize, GpuArrayBuffer, SamplerBindingType,
             ShaderStages, TextureSampleType,
         },
@@ -45,6 +48,10 @@ mod layout_entry {
     pub(super) fn lightmaps_sampler() -> BindGroupLayoutEntryBuilder {
         sampler(SamplerBindingType::Filtering).visibility(ShaderStages::FRAGMENT)
     }
+    pub(super) fn instance_data(min_binding_size: BufferSize) -> BindGroupLayoutEntryBuilder {
+        storage_buffer_read_only_sized(false, Some(min_binding_size))
+            .visibility(ShaderStages::VERTEX_FRAGMENT)
+    }
 }
 
 /// Individual [`BindGroupEntry`]
@@ -92,8 +99,17 @@ mod entry {
             resource: BindingResource::Sampler(sampler),
         }
     }
+    pub(super) fn instance_data(binding: u32, buffer: &Buffer) -> BindGroupEntry {
+        BindGroupEntry {
+            binding,
+            resource: buffer.as_entire_binding(),
+        }
+    }
 }
 
+/// The binding of the [`MeshInstanceData`](crate::MeshInstanceData) buffer, in all layouts.
+const INSTANCE_DATA_BINDING: u32 = 6;
+
 /// All possible [`BindGroupLayout`]s in bevy's default mesh shader (`mesh.wgsl`).
 #[derive(Clone)]
 pub struct MeshLayouts {
@@ -116,6 +132,10 @@ pub struct MeshLayouts {
     ///
     /// [`MorphAttributes`]: bevy_render::mesh::morph::MorphAttributes
     pub morphed_skinned: BindGroupLayout,
+
+    /// The minimum size of the per-instance [`MeshInstanceData`](crate::MeshInstanceData)
+    /// buffer, if all layouts include it.
+    instance_data: Option<BufferSize>,
 }
 
 impl MeshLayouts {
@@ -123,28 +143,72 @@ impl MeshLayouts {
     ///
     /// [`Mesh`]: bevy_render::prelude::Mesh
     pub fn new(render_device: &RenderDevice) -> Self {
+        Self::with_instance_data(render_device, None)
+    }
+
+    /// Prepare the layouts used by the default bevy [`Mesh`], with a per-instance
+    /// [`MeshInstanceData`](crate::MeshInstanceData) buffer at binding 6 if
+    /// `instance_data` is the minimum size of its elements.
+    ///
+    /// [`Mesh`]: bevy_render::prelude::Mesh
+    pub fn with_instance_data(
+        render_device: &RenderDevice,
+        instance_data: Option<BufferSize>,
+    ) -> Self {
         MeshLayouts {
-            model_only: Self::model_only_layout(render_device),
-            lightmapped: Self::lightmapped_layout(render_device),
-            skinned: Self::skinned_layout(render_device),
-            morphed: Self::morphed_layout(render_device),
-            morphed_skinned: Self::morphed_skinned_layout(render_device),
+            model_only: Self::model_only_layout(render_device, instance_data),
+            lightmapped: Self::lightmapped_layout(render_device, instance_data),
+            skinned: Self::skinned_layout(render_device, instance_data),
+            morphed: Self::morphed_layout(render_device, instance_data),
+            morphed_skinned: Self::morphed_skinned_layout(render_device, instance_data),
+            instance_data,
         }
     }
 
+    /// Returns `true` if the layouts include the [`MeshInstanceData`](crate::MeshInstanceData)
+    /// buffer.
+    pub fn has_instance_data(&self) -> bool {
+        self.instance_data.is_some()
+    }
+
     // ---------- create individual BindGroupLayouts ----------
 
-    fn model_only_layout(render_device: &RenderDevice) -> BindGroupLayout {
-        render_device.create_bind_group_layout(
+    fn create_layout(
+        render_device: &RenderDevice,
+        label: &'static str,
+        entries: &[BindGroupLayoutEntry],
+        instance_data: Option<BufferSize>,
+    ) -> BindGroupLayout {
+        let mut entries = entries.to_vec();
+        if let Some(min_binding_size) = instance_data {
+            entries.push(
+                layout_entry::instance_data(min_binding_size)
+                    .build(INSTANCE_DATA_BINDING, ShaderStages::VERTEX_FRAGMENT),
+            );
+        }
+        render_device.create_bind_group_layout(label, &entries)
+    }
+
+    fn model_only_layout(
+        render_device: &RenderDevice,
+        instance_data: Option<BufferSize>,
+    ) -> BindGroupLayout {
+        Self::create_layout(
+            render_device,
             "mesh_layout",
             &BindGroupLayoutEntries::single(
                 ShaderStages::empty(),
                 layout_entry::model(render_device),
             ),
+            instance_data,
         )
     }
-    fn skinned_layout(render_device: &RenderDevice) -> BindGroupLayout {
-        render_device.create_bind_group_layout(
+    fn skinned_layout(
+        render_device: &RenderDevice,
+        instance_data: Option<BufferSize>,
+    ) -> BindGroupLayout {
+        Self::create_layout(
+            render_device,
             "skinned_mesh_layout",
             &BindGroupLayoutEntries::with_indices(
                 ShaderStages::VERTEX,
@@ -153,10 +217,15 @@ impl MeshLayouts {
                     (1, layout_entry::skinning()),
                 ),
             ),
+            instance_data,
         )
     }
-    fn morphed_layout(render_device: &RenderDevice) -> BindGroupLayout {
-        render_device.create_bind_group_layout(
+    fn morphed_layout(
+        render_device: &RenderDevice,
+        instance_data: Option<BufferSize>,
+    ) -> BindGroupLayout {
+        Self::create_layout(
+            render_device,
             "morphed_mesh_layout",
             &BindGroupLayoutEntries::with_indices(
                 ShaderStages::VERTEX,
@@ -166,10 +235,15 @@ impl MeshLayouts {
                     (3, layout_entry::targets()),
                 ),
             ),
+            instance_data,
         )
     }
-    fn morphed_skinned_layout(render_device: &RenderDevice) -> BindGroupLayout {
-        render_device.create_bind_group_layout(
+    fn morphed_skinned_layout(
+        render_device: &RenderDevice,
+        instance_data: Option<BufferSize>,
+    ) -> BindGroupLayout {
+        Self::create_layout(
+            render_device,
             "morphed_skinned_mesh_layout",
             &BindGroupLayoutEntries::with_indices(
                 ShaderStages::VERTEX,
@@ -180,10 +254,15 @@ impl MeshLayouts {
                     (3, layout_entry::targets()),
                 ),
             ),
+            instance_data,
         )
     }
-    fn lightmapped_layout(render_device: &RenderDevice) -> BindGroupLayout {
-        render_device.create_bind_group_layout(
+    fn lightmapped_layout(
+        render_device: &RenderDevice,
+        instance_data: Option<BufferSize>,
+    ) -> BindGroupLayout {
+        Self::create_layout(
+            render_device,
             "lightmapped_mesh_layout",
             &BindGroupLayoutEntries::with_indices(
                 ShaderStages::VERTEX,
@@ -193,16 +272,43 @@ impl MeshLayouts {
                     (5, layout_entry::lightmaps_sampler()),
                 ),
             ),
+            instance_data,
         )
     }
 
     // ---------- BindGroup methods ----------
 
-    pub fn model_only(&self, render_device: &RenderDevice, model: &BindingResource) -> BindGroup {
-        render_device.create_bind_group(
+    /// Creates a bind group with the `entries`, followed by the `instance_data` buffer if the
+    /// layouts include it.
+    fn create_bind_group(
+        &self,
+        render_device: &RenderDevice,
+        label: &'static str,
+        layout: &BindGroupLayout,
+        entries: &[BindGroupEntry],
+        instance_data: Option<&Buffer>,
+    ) -> BindGroup {
+        let mut entries = entries.to_vec();
+        if self.instance_data.is_some() {
+            let buffer = instance_data
+                .expect("the mesh layouts include a MeshInstanceData buffer, but none was given");
+            entries.push(entry::instance_data(INSTANCE_DATA_BINDING, buffer));
+        }
+        render_device.create_bind_group(label, layout, &entries)
+    }
+
+    pub fn model_only(
+        &self,
+        render_device: &RenderDevice,
+        model: &BindingResource,
+        instance_data: Option<&Buffer>,
+    ) -> BindGroup {
+        self.create_bind_group(
+            render_device,
             "model_only_mesh_bind_group",
             &self.model_only,
             &[entry::model(0, model.clone())],
+            instance_data,
         )
     }
     pub fn lightmapped(
@@ -210,8 +316,10 @@ impl MeshLayouts {
         render_device: &RenderDevice,
         model: &BindingResource,
         lightmap: &GpuImage,
+        instance_data: Option<&Buffer>,
     ) -> BindGroup {
-        render_device.create_bind_group(
+        self.create_bind_group(
+            render_device,
             "lightmapped_mesh_bind_group",
             &self.lightmapped,
             &[
@@ -219,6 +327,7 @@ impl MeshLayouts {
                 entry::lightmaps_texture_view(4, &lightmap.texture_view),
                 entry::lightmaps_sampler(5, &lightmap.sampler),
             ],
+            instance_data,
         )
     }
     pub fn skinned(
@@ -226,11 +335,14 @@ impl MeshLayouts {
         render_device: &RenderDevice,
         model: &BindingResource,
         skin: &Buffer,
+        instance_data: Option<&Buffer>,
     ) -> BindGroup {
-        render_device.create_bind_group(
+        self.create_bind_group(
+            render_device,
             "skinned_mesh_bind_group",
             &self.skinned,
             &[entry::model(0, model.clone()), entry::skinning(1, skin)],
+            instance_data,
         )
     }
     pub fn morphed(
@@ -239,8 +351,10 @@ impl MeshLayouts {
         model: &BindingResource,
         weights: &Buffer,
         targets: &TextureView,
+        instance_data: Option<&Buffer>,
     ) -> BindGroup {
-        render_device.create_bind_group(
+        self.create_bind_group(
+            render_device,
             "morphed_mesh_bind_group",
             &self.morphed,
             &[
@@ -248,6 +362,7 @@ impl MeshLayouts {
                 entry::weights(2, weights),
                 entry::targets(3, targets),
             ],
+            instance_data,
         )
     }
     pub fn morphed_skinned(
@@ -257,8 +372,10 @@ impl MeshLayouts {
         skin: &Buffer,
         weights: &Buffer,
         targets: &TextureView,
+        instance_data: Option<&Buffer>,
     ) -> BindGroup {
-        render_device.create_bind_group(
+        self.create_bind_group(
+            render_device,
             "morphed_skinned_mesh_bind_group",
             &self.morphed_skinned,
             &[
@@ -267,6 +384,7 @@ impl MeshLayouts {
                 entry::weights(2, weights),
                 entry::targets(3, targets),
             ],
+            instance_data,
         )
     }
 }
@@ -7,3 +7,11 @@
 #else
 @group(1) @binding(0) var<storage> mesh: array<Mesh>;
 #endif // PER_OBJECT_BUFFER_BATCH_SIZE
+
+#ifdef MESH_INSTANCE_DATA
+#ifndef PER_OBJECT_BUFFER_BATCH_SIZE
+#import bevy_pbr::mesh_instance_data::MeshInstanceData
+
+@group(1) @binding(6) var<storage> mesh_instance_data: array<MeshInstanceData>;
+#endif // PER_OBJECT_BUFFER_BATCH_SIZE
+#endif // MESH_INSTANCE_DATA
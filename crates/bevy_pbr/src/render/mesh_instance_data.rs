@@ -0,0 +1,327 @@
+use std::{borrow::Cow, marker::PhantomData};
+
+use bevy_app::{App, Plugin};
+use bevy_core_pipeline::{
+    core_3d::{AlphaMask3d, Opaque3d, Transmissive3d, Transparent3d},
+    deferred::{AlphaMask3dDeferred, Opaque3dDeferred},
+    prepass::{AlphaMask3dPrepass, Opaque3dPrepass, Transparent3dPrepass},
+};
+use bevy_ecs::prelude::*;
+use bevy_render::{
+    batching::batch_and_prepare_render_phase,
+    render_phase::{PhaseItem, RenderPhase},
+    render_resource::{
+        encase::private::WriteInto, Buffer, BufferSize, ShaderApp, ShaderSize, ShaderType,
+        StorageBuffer,
+    },
+    renderer::{RenderDevice, RenderQueue},
+    view::ViewVisibility,
+    Extract, ExtractSchedule, Render, RenderApp, RenderSet,
+};
+use bevy_utils::EntityHashMap;
+
+use crate::{MeshPipeline, RenderMeshInstances, Shadow};
+
+/// The import path of the WGSL definition of the [`MeshInstanceData`] struct.
+pub const MESH_INSTANCE_DATA_IMPORT_PATH: &str = "bevy_pbr::mesh_instance_data";
+
+/// Custom data of a mesh instance, uploaded next to its transform and available to the mesh
+/// shaders of all the passes drawing it.
+///
+/// Requires the [`MeshInstanceDataPlugin<T>`]. Meshes without this component get the
+/// [`Default`] value of `T`.
+///
+/// In WGSL, the data of an instance is read from `bevy_pbr::mesh_bindings::mesh_instance_data`
+/// with the same index as its transform:
+///
+/// ```wgsl
+/// #import bevy_pbr::mesh_bindings::mesh_instance_data
+///
+/// let tint = mesh_instance_data[vertex.instance_index].tint;
+/// ```
+///
+/// Instances with different data are still batched together, as the data is indexed per
+/// instance like the transforms.
+#[derive(Component, Clone, Debug, Default)]
+pub struct MeshInstanceData<T: ShaderType>(pub T);
+
+/// Adds support for the [`MeshInstanceData<T>`] component.
+///
+/// `wgsl_struct` is the WGSL definition of `T`, a struct that must be named
+/// `MeshInstanceData`. The `MESH_INSTANCE_DATA` shader def is registered for all shaders.
+///
+/// Only one type of instance data can be used in an app, as it is bound at binding 6 of the
+/// mesh bind group. It requires storage buffers: on WebGL 2, the data isn't uploaded and the
+/// `mesh_instance_data` binding isn't defined.
+///
+/// ```
+/// # use bevy_app::App;
+/// # use bevy_math::Vec4;
+/// # use bevy_pbr::MeshInstanceDataPlugin;
+/// # use bevy_render::render_resource::ShaderType;
+/// #[derive(ShaderType, Clone, Default)]
+/// struct Tint {
+///     color: Vec4,
+/// }
+///
+/// fn build(app: &mut App) {
+///     app.add_plugins(MeshInstanceDataPlugin::<Tint>::new(
+///         "struct MeshInstanceData { color: vec4<f32> }",
+///     ));
+/// }
+/// ```
+pub struct MeshInstanceDataPlugin<T> {
+    wgsl_struct: Cow<'static, str>,
+    marker: PhantomData<T>,
+}
+
+impl<T> MeshInstanceDataPlugin<T> {
+    /// Creates the plugin for the instance data defined in WGSL by `wgsl_struct`.
+    pub fn new(wgsl_struct: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            wgsl_struct: wgsl_struct.into(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Plugin for MeshInstanceDataPlugin<T>
+where
+    T: ShaderType + ShaderSize + WriteInto + Clone + Default + Send + Sync + 'static,
+{
+    fn build(&self, app: &mut App) {
+        app.register_shader_def("MESH_INSTANCE_DATA")
+            .register_virtual_shader(
+                MESH_INSTANCE_DATA_IMPORT_PATH,
+                format!(
+                    "#define_import_path {MESH_INSTANCE_DATA_IMPORT_PATH}\n\n{}\n",
+                    self.wgsl_struct
+                ),
+            );
+
+        let Ok(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .insert_resource(MeshInstanceDataLayout {
+                min_binding_size: T::min_size(),
+            })
+            .init_resource::<RenderMeshInstanceData<T>>()
+            .init_resource::<GpuMeshInstanceData<T>>()
+            .init_resource::<MeshInstanceDataBinding>()
+            .add_systems(ExtractSchedule, extract_mesh_instance_data::<T>)
+            .add_systems(
+                Render,
+                (
+                    (
+                        collect_mesh_instance_data::<T, Opaque3d>
+                            .after(batch_and_prepare_render_phase::<Opaque3d, MeshPipeline>),
+                        collect_mesh_instance_data::<T, Transmissive3d>
+                            .after(batch_and_prepare_render_phase::<Transmissive3d, MeshPipeline>),
+                        collect_mesh_instance_data::<T, Transparent3d>
+                            .after(batch_and_prepare_render_phase::<Transparent3d, MeshPipeline>),
+                        collect_mesh_instance_data::<T, AlphaMask3d>
+                            .after(batch_and_prepare_render_phase::<AlphaMask3d, MeshPipeline>),
+                        collect_mesh_instance_data::<T, Shadow>
+                            .after(batch_and_prepare_render_phase::<Shadow, MeshPipeline>),
+                        collect_mesh_instance_data::<T, Opaque3dDeferred>.after(
+                            batch_and_prepare_render_phase::<Opaque3dDeferred, MeshPipeline>,
+                        ),
+                        collect_mesh_instance_data::<T, AlphaMask3dDeferred>.after(
+                            batch_and_prepare_render_phase::<AlphaMask3dDeferred, MeshPipeline>,
+                        ),
+                        collect_mesh_instance_data::<T, Opaque3dPrepass>
+                            .after(batch_and_prepare_render_phase::<Opaque3dPrepass, MeshPipeline>),
+                        collect_mesh_instance_data::<T, AlphaMask3dPrepass>.after(
+                            batch_and_prepare_render_phase::<AlphaMask3dPrepass, MeshPipeline>,
+                        ),
+                        collect_mesh_instance_data::<T, Transparent3dPrepass>.after(
+                            batch_and_prepare_render_phase::<Transparent3dPrepass, MeshPipeline>,
+                        ),
+                    )
+                        // Each phase writes the data of its own instances.
+                        .ambiguous_with_all()
+                        .in_set(RenderSet::PrepareResources),
+                    write_mesh_instance_data::<T>.in_set(RenderSet::PrepareResourcesFlush),
+                ),
+            );
+    }
+}
+
+/// The minimum size of the elements of the [`MeshInstanceData`] buffer, used to create the mesh
+/// bind group layouts.
+#[derive(Resource, Clone, Copy)]
+pub struct MeshInstanceDataLayout {
+    pub min_binding_size: BufferSize,
+}
+
+/// The GPU buffer of the [`MeshInstanceData`] of the current frame, bound to the mesh bind group.
+#[derive(Resource, Default)]
+pub struct MeshInstanceDataBinding {
+    pub buffer: Option<Buffer>,
+}
+
+/// The [`MeshInstanceData`] of the visible meshes, in the render world.
+#[derive(Resource)]
+struct RenderMeshInstanceData<T>(EntityHashMap<Entity, T>);
+
+impl<T> Default for RenderMeshInstanceData<T> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+/// The [`MeshInstanceData`] of the current frame, indexed like the mesh uniforms.
+#[derive(Resource)]
+struct GpuMeshInstanceData<T: ShaderType + ShaderSize + WriteInto>(StorageBuffer<Vec<T>>);
+
+impl<T: ShaderType + ShaderSize + WriteInto> Default for GpuMeshInstanceData<T> {
+    fn default() -> Self {
+        let mut buffer = StorageBuffer::default();
+        buffer.set_label(Some("mesh_instance_data_buffer"));
+        Self(buffer)
+    }
+}
+
+fn extract_mesh_instance_data<T: ShaderType + Clone + Send + Sync + 'static>(
+    mut render_instance_data: ResMut<RenderMeshInstanceData<T>>,
+    query: Extract<Query<(Entity, &ViewVisibility, &MeshInstanceData<T>)>>,
+) {
+    render_instance_data.0.clear();
+    for (entity, view_visibility, instance_data) in &query {
+        if view_visibility.get() {
+            render_instance_data
+                .0
+                .insert(entity, instance_data.0.clone());
+        }
+    }
+}
+
+/// Writes the data of the meshes of the `I` phases at the index of their mesh uniform.
+fn collect_mesh_instance_data<T, I: PhaseItem>(
+    mut gpu_instance_data: ResMut<GpuMeshInstanceData<T>>,
+    render_instance_data: Res<RenderMeshInstanceData<T>>,
+    render_mesh_instances: Res<RenderMeshInstances>,
+    phases: Query<&RenderPhase<I>>,
+) where
+    T: ShaderType + ShaderSize + WriteInto + Clone + Default + Send + Sync + 'static,
+{
+    let values = gpu_instance_data.0.get_mut();
+    for phase in &phases {
+        for item in &phase.items {
+            // Items that aren't meshes have no mesh uniform.
+            if !render_mesh_instances.contains_key(&item.entity()) {
+                continue;
+            }
+            let index = item.batch_range().start as usize;
+            if values.len() <= index {
+                values.resize(index + 1, T::default());
+            }
+            values[index] = render_instance_data
+                .0
+                .get(&item.entity())
+                .cloned()
+                .unwrap_or_default();
+        }
+    }
+}
+
+fn write_mesh_instance_data<T>(
+    mut gpu_instance_data: ResMut<GpuMeshInstanceData<T>>,
+    mut binding: ResMut<MeshInstanceDataBinding>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+) where
+    T: ShaderType + ShaderSize + WriteInto + Default + Send + Sync + 'static,
+{
+    // Storage buffers can't be empty.
+    if gpu_instance_data.0.get().is_empty() {
+        gpu_instance_data.0.get_mut().push(T::default());
+    }
+    gpu_instance_data
+        .0
+        .write_buffer(&render_device, &render_queue);
+    binding.buffer = gpu_instance_data.0.buffer().cloned();
+    gpu_instance_data.0.get_mut().clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_asset::AssetId;
+    use bevy_ecs::system::RunSystemOnce;
+    use bevy_math::Affine3A;
+    use bevy_render::render_phase::{Draw, DrawFunctions, TrackedRenderPass};
+    use bevy_render::render_resource::CachedRenderPipelineId;
+
+    use crate::{MaterialBindGroupId, MeshTransforms, RenderMeshInstance};
+
+    struct NoopDraw;
+
+    impl Draw<Opaque3d> for NoopDraw {
+        fn draw<'w>(
+            &mut self,
+            _world: &'w World,
+            _pass: &mut TrackedRenderPass<'w>,
+            _view: Entity,
+            _item: &Opaque3d,
+        ) {
+        }
+    }
+
+    fn render_mesh_instance() -> RenderMeshInstance {
+        RenderMeshInstance {
+            transforms: MeshTransforms {
+                transform: (&Affine3A::IDENTITY).into(),
+                previous_transform: (&Affine3A::IDENTITY).into(),
+                flags: 0,
+            },
+            mesh_asset_id: AssetId::default(),
+            material_bind_group_id: MaterialBindGroupId::default(),
+            shadow_caster: true,
+            automatic_batching: true,
+            transparent_prepass: false,
+        }
+    }
+
+    #[test]
+    fn instance_data_is_written_at_batch_index() {
+        let mut world = World::new();
+        world.init_resource::<GpuMeshInstanceData<u32>>();
+        world.init_resource::<RenderMeshInstanceData<u32>>();
+        world.init_resource::<RenderMeshInstances>();
+
+        let with_data = world.spawn_empty().id();
+        let without_data = world.spawn_empty().id();
+        let not_a_mesh = world.spawn_empty().id();
+        world
+            .resource_mut::<RenderMeshInstanceData<u32>>()
+            .0
+            .insert(with_data, 7);
+        let mut render_mesh_instances = world.resource_mut::<RenderMeshInstances>();
+        render_mesh_instances.insert(with_data, render_mesh_instance());
+        render_mesh_instances.insert(without_data, render_mesh_instance());
+
+        let draw_function = DrawFunctions::<Opaque3d>::default().write().add(NoopDraw);
+        let item = |entity, index| Opaque3d {
+            asset_id: AssetId::default(),
+            pipeline: CachedRenderPipelineId::INVALID,
+            entity,
+            draw_function,
+            batch_range: index..index + 1,
+            dynamic_offset: None,
+        };
+        let mut phase = RenderPhase::<Opaque3d>::default();
+        phase.add(item(with_data, 2));
+        phase.add(item(without_data, 0));
+        phase.add(item(not_a_mesh, 5));
+        world.spawn(phase);
+
+        world.run_system_once(collect_mesh_instance_data::<u32, Opaque3d>);
+
+        // Meshes without data get the default value, and items that aren't meshes are skipped.
+        let gpu_instance_data = world.resource::<GpuMeshInstanceData<u32>>();
+        assert_eq!(*gpu_instance_data.0.get(), [0, 0, 7]);
+    }
+}
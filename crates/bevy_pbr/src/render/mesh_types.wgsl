@@ -31,5 +31,6 @@ struct MorphWeights {
 
 const MESH_FLAGS_SHADOW_RECEIVER_BIT: u32 = 1u;
 const MESH_FLAGS_TRANSMITTED_SHADOW_RECEIVER_BIT: u32 = 2u;
+const MESH_FLAGS_SKINNED_INSTANCE_BIT: u32 = 4u;
 // 2^31 - if the flag is set, the sign is positive, else it is negative
 const MESH_FLAGS_SIGN_DETERMINANT_MODEL_3X3_BIT: u32 = 2147483648u;
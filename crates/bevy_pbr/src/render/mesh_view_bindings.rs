@@ -306,6 +306,12 @@ fn layout_entries(
         (25, sampler(SamplerBindingType::Filtering)),
     ));
 
+    // Screen space bent normals texture
+    entries = entries.extend_with_indices(((
+        26,
+        texture_2d(TextureSampleType::Float { filterable: false }),
+    ),));
+
     entries.to_vec()
 }
 
@@ -405,6 +411,9 @@ pub fn prepare_mesh_view_bind_groups(
             let ssao_view = ssao_textures
                 .map(|t| &t.screen_space_ambient_occlusion_texture.default_view)
                 .unwrap_or(&fallback_ssao);
+            let bent_normals_view = ssao_textures
+                .map(|t| &t.bent_normals_texture.default_view)
+                .unwrap_or(&fallback_ssao);
 
             let layout = &mesh_pipeline.get_view_layout(
                 MeshPipelineViewLayoutKey::from(*msaa)
@@ -511,6 +520,7 @@ pub fn prepare_mesh_view_bind_groups(
 
             entries =
                 entries.extend_with_indices(((24, transmission_view), (25, transmission_sampler)));
+            entries = entries.extend_with_indices(((26, bent_normals_view),));
 
             commands.entity(entity).insert(MeshViewBindGroup {
                 value: render_device.create_bind_group("mesh_view_bind_group", layout, &entries),
@@ -88,3 +88,5 @@
 
 @group(0) @binding(24) var view_transmission_texture: texture_2d<f32>;
 @group(0) @binding(25) var view_transmission_sampler: sampler;
+
+@group(0) @binding(26) var screen_space_bent_normals_texture: texture_2d<f32>;
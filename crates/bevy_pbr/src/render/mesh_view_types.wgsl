@@ -15,6 +15,9 @@ struct PointLight {
 
 const POINT_LIGHT_FLAGS_SHADOWS_ENABLED_BIT: u32   = 1u;
 const POINT_LIGHT_FLAGS_SPOT_LIGHT_Y_NEGATIVE: u32 = 2u;
+// The shadow atlas tile of a spot light is packed in the bits above the flags: 3 bits for the
+// level of the tile, 7 bits for each coordinate of its position in the page, and the page index.
+const POINT_LIGHT_FLAGS_SHADOW_ATLAS_TILE_SHIFT: u32 = 2u;
 
 struct DirectionalCascade {
     view_projection: mat4x4<f32>,
@@ -56,7 +59,7 @@ struct Lights {
     // w is cluster_dimensions.z / (-far - -near)
     cluster_factors: vec4<f32>,
     n_directional_lights: u32,
-    spot_light_shadowmap_offset: i32,
+    spot_light_shadow_atlas_layer: u32,
     environment_map_smallest_specular_mip_level: u32,
     environment_map_intensity: f32,
 };
@@ -77,6 +80,21 @@ struct Fog {
     directional_light_exponent: f32,
     bi: vec3<f32>,
     mode: u32,
+    volume_count: u32,
+    // The water surface of `WaterSettings`, under which the caustics are projected
+    water_surface_height: f32,
+    caustics_intensity: f32,
+    caustics_scale: f32,
+    caustics_speed: f32,
+    // This must match MAX_FOG_VOLUMES in fog.rs
+    volumes: array<FogVolume, 16u>,
+}
+
+struct FogVolume {
+    world_to_local: mat4x4<f32>,
+    color: vec4<f32>,
+    density: f32,
+    shape: u32,
 }
 
 // Important: These must be kept in sync with `fog.rs`
@@ -86,6 +104,9 @@ const FOG_MODE_EXPONENTIAL: u32           = 2u;
 const FOG_MODE_EXPONENTIAL_SQUARED: u32   = 3u;
 const FOG_MODE_ATMOSPHERIC: u32           = 4u;
 
+const FOG_VOLUME_SHAPE_BOX: u32           = 0u;
+const FOG_VOLUME_SHAPE_SPHERE: u32        = 1u;
+
 #if AVAILABLE_STORAGE_BUFFER_BINDINGS >= 3
 struct PointLights {
     data: array<PointLight>,
@@ -117,6 +138,9 @@ struct LightProbe {
     inverse_transpose_transform: mat3x4<f32>,
     cubemap_index: i32,
     intensity: f32,
+    // The distance over which the light fades out near the faces of the light
+    // probe, in world units.
+    blend_distance: f32,
 };
 
 struct LightProbes {
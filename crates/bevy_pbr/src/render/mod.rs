@@ -1,14 +1,19 @@
 mod fog;
+mod gpu_shadow_culling;
 mod light;
 pub(crate) mod mesh;
 mod mesh_bindings;
+mod mesh_instance_data;
 mod mesh_view_bindings;
 mod morph;
+mod shadow_atlas;
 mod skin;
 
 pub use fog::*;
+pub use gpu_shadow_culling::*;
 pub use light::*;
 pub use mesh::*;
 pub use mesh_bindings::MeshLayouts;
+pub use mesh_instance_data::*;
 pub use mesh_view_bindings::*;
-pub use skin::{extract_skins, prepare_skins, SkinIndex, SkinUniform, MAX_JOINTS};
+pub use skin::{extract_skins, prepare_skins, SkinIndex, SkinPoseHistory, SkinUniform, MAX_JOINTS};
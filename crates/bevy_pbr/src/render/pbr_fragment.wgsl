@@ -13,8 +13,11 @@
 }
 
 #ifdef SCREEN_SPACE_AMBIENT_OCCLUSION
-#import bevy_pbr::mesh_view_bindings::screen_space_ambient_occlusion_texture
-#import bevy_pbr::gtao_utils::gtao_multibounce
+#import bevy_pbr::mesh_view_bindings::{
+    screen_space_ambient_occlusion_texture,
+    screen_space_bent_normals_texture,
+}
+#import bevy_pbr::gtao_utils::{gtao_multibounce, specular_occlusion_from_bent_normal}
 #endif
 
 #ifdef PREPASS_PIPELINE
@@ -102,6 +105,10 @@ fn pbr_input_from_standard_material(
 #endif // VERTEX_UVS
 
     pbr_input.material.flags = pbr_bindings::material.flags;
+#ifdef FORCE_UNLIT
+    // Set for the views with a `ViewMaterialOverride` rendering everything unlit.
+    pbr_input.material.flags |= pbr_types::STANDARD_MATERIAL_FLAGS_UNLIT_BIT;
+#endif
 
     // NOTE: Unlit bit not set means == 0 is true, so the true case is if lit
     if ((pbr_bindings::material.flags & pbr_types::STANDARD_MATERIAL_FLAGS_UNLIT_BIT) == 0u) {
@@ -178,6 +185,11 @@ fn pbr_input_from_standard_material(
         // Use SSAO to estimate the specular occlusion.
         // Lagarde and Rousiers 2014, "Moving Frostbite to Physically Based Rendering"
         specular_occlusion =  saturate(pow(NdotV + ssao, exp2(-16.0 * roughness - 1.0)) - 1.0 + ssao);
+        // Occlude it further by the cone of directions left visible around the bent normal.
+        let bent_normal = normalize(textureLoad(screen_space_bent_normals_texture, vec2<i32>(in.position.xy), 0i).xyz * 2.0 - 1.0);
+        let R = reflect(-pbr_input.V, pbr_input.N);
+        specular_occlusion = min(specular_occlusion, specular_occlusion_from_bent_normal(ssao, bent_normal, R, roughness));
+        pbr_input.bent_normal = bent_normal;
 #endif
         pbr_input.diffuse_occlusion = diffuse_occlusion;
         pbr_input.specular_occlusion = specular_occlusion;
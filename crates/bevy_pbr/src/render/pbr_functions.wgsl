@@ -11,6 +11,7 @@
     shadows,
     ambient,
     irradiance_volume,
+    caustics,
     mesh_types::{MESH_FLAGS_SHADOW_RECEIVER_BIT, MESH_FLAGS_TRANSMITTED_SHADOW_RECEIVER_BIT},
     utils::E,
 }
@@ -167,6 +168,8 @@ fn apply_pbr_lighting(
 
     let diffuse_occlusion = in.diffuse_occlusion;
     let specular_occlusion = in.specular_occlusion;
+    // Indirect diffuse light arrives mostly along the bent normal, when SSAO provides one.
+    let diffuse_N = select(in.N, in.bent_normal, any(in.bent_normal != vec3(0.0)));
 
     // Neubelt and Pettineo 2013, "Crafting a Next-gen Material Pipeline for The Order: 1886"
     let NdotV = max(dot(in.N, in.V), 0.0001);
@@ -282,6 +285,7 @@ fn apply_pbr_lighting(
             shadow = shadows::fetch_directional_shadow(i, in.world_position, in.world_normal, view_z);
         }
         var light_contrib = lighting::directional_light(i, roughness, NdotV, in.N, in.V, R, F0, f_ab, diffuse_color);
+        light_contrib *= caustics::directional_light_caustics(in.world_position.xyz, (*light).direction_to_light);
 #ifdef DIRECTIONAL_LIGHT_SHADOW_MAP_DEBUG_CASCADES
         light_contrib = shadows::cascade_debug_visualization(light_contrib, i, view_z);
 #endif
@@ -342,7 +346,7 @@ fn apply_pbr_lighting(
     // Irradiance volume light (indirect)
     if (all(indirect_light == vec3(0.0f))) {
         let irradiance_volume_light = irradiance_volume::irradiance_volume_light(
-            in.world_position.xyz, in.N);
+            in.world_position.xyz, diffuse_N);
         indirect_light += irradiance_volume_light * diffuse_color * diffuse_occlusion;
     }
 #endif
@@ -358,7 +362,7 @@ fn apply_pbr_lighting(
         diffuse_color,
         NdotV,
         f_ab,
-        in.N,
+        diffuse_N,
         R,
         F0,
         in.world_position.xyz,
@@ -555,8 +559,14 @@ fn main_pass_post_lighting_processing(
     var output_color = input_color;
 
     // fog
-    if (view_bindings::fog.mode != mesh_view_types::FOG_MODE_OFF && (pbr_input.material.flags & pbr_types::STANDARD_MATERIAL_FLAGS_FOG_ENABLED_BIT) != 0u) {
-        output_color = apply_fog(view_bindings::fog, output_color, pbr_input.world_position.xyz, view_bindings::view.world_position.xyz);
+    if ((pbr_input.material.flags & pbr_types::STANDARD_MATERIAL_FLAGS_FOG_ENABLED_BIT) != 0u) {
+        // local fog volumes first, then the distance fog of the view over them
+        if (view_bindings::fog.volume_count != 0u) {
+            output_color = bevy_pbr::fog::apply_fog_volumes(output_color, pbr_input.world_position.xyz, view_bindings::view.world_position.xyz, pbr_input.frag_coord.xy, pbr_input.is_orthographic);
+        }
+        if (view_bindings::fog.mode != mesh_view_types::FOG_MODE_OFF) {
+            output_color = apply_fog(view_bindings::fog, output_color, pbr_input.world_position.xyz, view_bindings::view.world_position.xyz);
+        }
     }
 
 #ifdef TONEMAP_IN_SHADER
@@ -91,6 +91,9 @@ struct PbrInput {
     world_normal: vec3<f32>,
     // Normalized normal-mapped world normal used for lighting
     N: vec3<f32>,
+    // Normalized world space bent normal (the average unoccluded direction) from SSAO, used for
+    // indirect diffuse lighting. Note: this is zero when SSAO is off, in which case N is used.
+    bent_normal: vec3<f32>,
     // Normalized view vector in world space, pointing from the fragment world position toward the
     // view world position
     V: vec3<f32>,
@@ -111,6 +114,7 @@ fn pbr_input_new() -> PbrInput {
     pbr_input.frag_coord = vec4<f32>(0.0, 0.0, 0.0, 1.0);
     pbr_input.world_position = vec4<f32>(0.0, 0.0, 0.0, 1.0);
     pbr_input.world_normal = vec3<f32>(0.0, 0.0, 1.0);
+    pbr_input.bent_normal = vec3<f32>(0.0);
 
     pbr_input.is_orthographic = false;
 
@@ -0,0 +1,210 @@
+use bevy_math::UVec4;
+
+/// The deepest level of the atlas quadtree: the position of a tile in its page is packed in 7
+/// bits per axis in the light flags.
+pub(crate) const MAX_SHADOW_ATLAS_LEVEL: u32 = 7;
+
+/// The shadow map requested by a light.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct ShadowAtlasRequest {
+    /// The requested resolution, in texels.
+    pub resolution: u32,
+    /// How much the shadows of the light contribute to the views. The shadow maps with the
+    /// largest area per importance are reduced first when the atlas is full.
+    pub importance: f32,
+}
+
+/// A square tile of a page of the shadow atlas, `page_size >> level` texels wide.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct ShadowAtlasTile {
+    pub page: u32,
+    pub level: u32,
+    /// The position of the tile in its page, in tiles.
+    pub x: u32,
+    pub y: u32,
+}
+
+impl ShadowAtlasTile {
+    /// The width of the tile, in texels.
+    pub fn size(&self, page_size: u32) -> u32 {
+        page_size >> self.level
+    }
+
+    /// The viewport of the tile in its page.
+    pub fn viewport(&self, page_size: u32) -> UVec4 {
+        let size = self.size(page_size);
+        UVec4::new(self.x * size, self.y * size, size, size)
+    }
+
+    /// Packs the tile in 30 bits, as decoded by `fetch_spot_shadow` in `shadows.wgsl`.
+    pub fn pack(&self) -> u32 {
+        self.level | self.x << 3 | self.y << 10 | self.page << 17
+    }
+}
+
+/// The tiles allocated to a list of [`ShadowAtlasRequest`]s.
+#[derive(Debug)]
+pub(crate) struct ShadowAtlasAllocation {
+    /// The tile of each request, or `None` if there was no room left for it.
+    pub tiles: Vec<Option<ShadowAtlasTile>>,
+    /// The number of pages used by the tiles.
+    pub page_count: u32,
+}
+
+/// Packs the shadow maps of `requests` in at most `max_pages` pages of `page_size` texels.
+///
+/// Requested resolutions are rounded up to the next tile size. While the tiles don't fit, the
+/// tile with the largest area per importance is halved, down to `min_resolution`. If they still
+/// don't fit, the least important shadow maps are dropped.
+pub(crate) fn allocate_shadow_atlas(
+    page_size: u32,
+    max_pages: u32,
+    min_resolution: u32,
+    requests: &[ShadowAtlasRequest],
+) -> ShadowAtlasAllocation {
+    let page_size = page_size.max(1);
+    let level_of = |resolution: u32| (page_size / resolution.clamp(1, page_size)).ilog2();
+    let max_level = level_of(min_resolution).min(MAX_SHADOW_ATLAS_LEVEL);
+    // Areas are counted in tiles of the deepest level.
+    let area = |level: u32| 1u64 << (2 * (max_level - level));
+    let page_area = area(0);
+    let capacity = max_pages as u64 * page_area;
+
+    // Most important first.
+    let mut order = (0..requests.len()).collect::<Vec<_>>();
+    order.sort_by(|&a, &b| requests[b].importance.total_cmp(&requests[a].importance));
+
+    let mut levels = requests
+        .iter()
+        .map(|request| Some(level_of(request.resolution).min(max_level)))
+        .collect::<Vec<_>>();
+    let mut total_area = levels
+        .iter()
+        .flatten()
+        .map(|&level| area(level))
+        .sum::<u64>();
+
+    while total_area > capacity {
+        let area_per_importance = |index: usize| {
+            area(levels[index].unwrap()) as f32 / requests[index].importance.max(f32::MIN_POSITIVE)
+        };
+        // On ties, `max_by` picks the last, least important candidate.
+        let Some(index) = order
+            .iter()
+            .copied()
+            .filter(|&index| levels[index].is_some_and(|level| level < max_level))
+            .max_by(|&a, &b| area_per_importance(a).total_cmp(&area_per_importance(b)))
+        else {
+            break;
+        };
+        let level = levels[index].as_mut().unwrap();
+        total_area -= area(*level) - area(*level + 1);
+        *level += 1;
+    }
+    for &index in order.iter().rev() {
+        if total_area <= capacity {
+            break;
+        }
+        if let Some(level) = levels[index].take() {
+            total_area -= area(level);
+        }
+    }
+
+    // Placing the tiles from the largest to the smallest along a Z-order curve keeps each of
+    // them aligned to its size, so they never overlap nor straddle two pages.
+    let mut placement = order
+        .into_iter()
+        .filter(|&index| levels[index].is_some())
+        .collect::<Vec<_>>();
+    placement.sort_by_key(|&index| levels[index]);
+
+    let mut tiles = vec![None; requests.len()];
+    let mut offset = 0u64;
+    for index in placement {
+        let level = levels[index].unwrap();
+        let position = (offset % page_area) / area(level);
+        tiles[index] = Some(ShadowAtlasTile {
+            page: (offset / page_area) as u32,
+            level,
+            x: deinterleave(position),
+            y: deinterleave(position >> 1),
+        });
+        offset += area(level);
+    }
+
+    ShadowAtlasAllocation {
+        tiles,
+        page_count: offset.div_ceil(page_area) as u32,
+    }
+}
+
+/// Gathers the even bits of a Z-order curve position.
+fn deinterleave(position: u64) -> u32 {
+    (0..MAX_SHADOW_ATLAS_LEVEL)
+        .map(|bit| (((position >> (2 * bit)) & 1) as u32) << bit)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(resolution: u32, importance: f32) -> ShadowAtlasRequest {
+        ShadowAtlasRequest {
+            resolution,
+            importance,
+        }
+    }
+
+    #[test]
+    fn tiles_are_packed_without_overlap() {
+        let requests = [
+            request(512, 1.0),
+            request(1024, 1.0),
+            request(256, 1.0),
+            request(600, 1.0),
+            request(256, 1.0),
+        ];
+        let allocation = allocate_shadow_atlas(1024, 4, 128, &requests);
+        let tiles = allocation
+            .tiles
+            .iter()
+            .map(|tile| tile.unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(allocation.page_count, 3);
+        assert_eq!(
+            tiles.iter().map(|tile| tile.size(1024)).collect::<Vec<_>>(),
+            [512, 1024, 256, 1024, 256]
+        );
+        for (i, a) in tiles.iter().enumerate() {
+            for b in &tiles[i + 1..] {
+                let (a_rect, b_rect) = (a.viewport(1024), b.viewport(1024));
+                let overlap = a_rect.x < b_rect.x + b_rect.z
+                    && b_rect.x < a_rect.x + a_rect.z
+                    && a_rect.y < b_rect.y + b_rect.w
+                    && b_rect.y < a_rect.y + a_rect.w;
+                assert!(a.page != b.page || !overlap, "{a:?} overlaps {b:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn least_important_shadow_maps_are_reduced_first() {
+        let requests = [request(1024, 1.0), request(1024, 100.0), request(1024, 0.5)];
+        let allocation = allocate_shadow_atlas(1024, 2, 128, &requests);
+        assert_eq!(allocation.page_count, 2);
+        let sizes = allocation
+            .tiles
+            .iter()
+            .map(|tile| tile.map(|tile| tile.size(1024)))
+            .collect::<Vec<_>>();
+        assert_eq!(sizes, [Some(512), Some(1024), Some(512)]);
+
+        // Without room for the smallest tiles, the least important shadow maps are dropped.
+        let requests = [request(1024, 1.0), request(1024, 2.0)];
+        let allocation = allocate_shadow_atlas(256, 1, 256, &requests);
+        assert_eq!(allocation.tiles[0], None);
+        assert!(allocation.tiles[1].is_some());
+    }
+}
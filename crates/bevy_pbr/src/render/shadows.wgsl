@@ -1,7 +1,7 @@
 #define_import_path bevy_pbr::shadows
 
 #import bevy_pbr::{
-    mesh_view_types::POINT_LIGHT_FLAGS_SPOT_LIGHT_Y_NEGATIVE,
+    mesh_view_types::{POINT_LIGHT_FLAGS_SPOT_LIGHT_Y_NEGATIVE, POINT_LIGHT_FLAGS_SHADOW_ATLAS_TILE_SHIFT},
     mesh_view_bindings as view_bindings,
     utils::hsv2rgb,
     shadow_sampling::sample_shadow_map
@@ -99,9 +99,20 @@ fn fetch_spot_shadow(light_id: u32, frag_position: vec4<f32>, surface_normal: ve
     // 0.1 must match POINT_LIGHT_NEAR_Z
     let depth = 0.1 / -projected_position.z;
 
+    // find the tile of the light in the shadow atlas, packed in its flags by prepare_lights
+    let tile = (*light).flags >> POINT_LIGHT_FLAGS_SHADOW_ATLAS_TILE_SHIFT;
+    let tile_scale = 1.0 / f32(1u << (tile & 7u));
+    let tile_position = vec2<f32>(f32((tile >> 3u) & 127u), f32((tile >> 10u) & 127u));
+    let tile_page = tile >> 17u;
+
+    // keep the filter footprint inside the tile so that neighboring shadow maps don't bleed in
+    let page_size = vec2<f32>(textureDimensions(view_bindings::directional_shadow_textures));
+    let margin = 3.0 / (page_size * tile_scale);
+    let atlas_uv = (tile_position + clamp(shadow_uv, margin, vec2<f32>(1.0) - margin)) * tile_scale;
+
      // Number determined by trial and error that gave nice results.
      let texel_size = 0.0134277345;
-    return sample_shadow_map(shadow_uv, depth, i32(light_id) + view_bindings::lights.spot_light_shadowmap_offset, texel_size);
+    return sample_shadow_map(atlas_uv, depth, i32(view_bindings::lights.spot_light_shadow_atlas_layer + tile_page), texel_size);
 }
 
 fn get_cascade_index(light_id: u32, view_z: f32) -> u32 {
@@ -1,22 +1,26 @@
+use std::collections::VecDeque;
+
 use bevy_asset::Assets;
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::prelude::*;
 use bevy_math::Mat4;
 use bevy_render::{
     batching::NoAutomaticBatching,
-    mesh::skinning::{SkinnedMesh, SkinnedMeshInverseBindposes},
+    mesh::skinning::{
+        SkinnedMesh, SkinnedMeshInstance, SkinnedMeshInverseBindposes, MAX_SKIN_PHASE_OFFSET,
+    },
     render_resource::{BufferUsages, BufferVec},
     renderer::{RenderDevice, RenderQueue},
     view::ViewVisibility,
     Extract,
 };
 use bevy_transform::prelude::GlobalTransform;
-use bevy_utils::EntityHashMap;
+use bevy_utils::{EntityHashMap, HashMap};
 
 /// Maximum number of joints supported for skinned meshes.
 pub const MAX_JOINTS: usize = 256;
 
-#[derive(Component)]
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
 pub struct SkinIndex {
     pub index: u32,
 }
@@ -87,10 +91,19 @@ pub fn prepare_skins(
 // In this way, we can pack ‘variable sized arrays’ into uniform buffer bindings
 // which normally only support fixed size arrays. You just have to make sure
 // in the shader that you only read the values that are valid for that binding.
+//
+// The joint matrices of the sources of `SkinnedMeshInstance`s are relative to the source mesh, and
+// `MeshFlags::SKINNED_INSTANCE` tells the shaders to apply the model matrix of the instance to
+// them. The last `MAX_SKIN_PHASE_OFFSET + 1` poses of each source are kept in `SkinPoseHistory`,
+// and each pose used by visible instances is written once.
+#[allow(clippy::too_many_arguments)]
 pub fn extract_skins(
     mut skin_indices: ResMut<SkinIndices>,
     mut uniform: ResMut<SkinUniform>,
+    mut pose_history: Local<SkinPoseHistory>,
     query: Extract<Query<(Entity, &ViewVisibility, &SkinnedMesh)>>,
+    instances: Extract<Query<(Entity, &ViewVisibility, &SkinnedMeshInstance)>>,
+    sources: Extract<Query<(&GlobalTransform, &SkinnedMesh)>>,
     inverse_bindposes: Extract<Res<Assets<SkinnedMeshInverseBindposes>>>,
     joints: Extract<Query<&GlobalTransform>>,
 ) {
@@ -133,14 +146,89 @@ pub fn extract_skins(
         skin_indices.insert(entity, SkinIndex::new(start));
     }
 
+    pose_history.update(&instances, &sources, &inverse_bindposes, &joints);
+    let mut instanced_poses = HashMap::<(Entity, usize), SkinIndex>::new();
+    for (entity, view_visibility, instance) in &instances {
+        if !view_visibility.get() {
+            continue;
+        }
+        let Some(poses) = pose_history.get(&instance.source) else {
+            continue;
+        };
+        let age = (instance.phase_offset as usize).min(poses.len() - 1);
+        let skin_index = *instanced_poses
+            .entry((instance.source, age))
+            .or_insert_with(|| {
+                let buffer = &mut uniform.buffer;
+                let start = buffer.len();
+                buffer.extend(poses[age].iter().copied());
+                last_start = last_start.max(start);
+
+                // Pad to 256 byte alignment
+                while buffer.len() % 4 != 0 {
+                    buffer.push(Mat4::ZERO);
+                }
+                SkinIndex::new(start)
+            });
+        skin_indices.insert(entity, skin_index);
+    }
+
     // Pad out the buffer to ensure that there's enough space for bindings
     while uniform.buffer.len() - last_start < MAX_JOINTS {
         uniform.buffer.push(Mat4::ZERO);
     }
 }
 
+/// The last poses of the sources of [`SkinnedMeshInstance`]s, from the newest to the oldest, as
+/// joint matrices relative to the source mesh.
+#[derive(Default, Deref, DerefMut)]
+pub struct SkinPoseHistory(EntityHashMap<Entity, VecDeque<Vec<Mat4>>>);
+
+impl SkinPoseHistory {
+    /// Records the current pose of every source of `instances`, and forgets the sources that
+    /// aren't instanced anymore.
+    fn update(
+        &mut self,
+        instances: &Query<(Entity, &ViewVisibility, &SkinnedMeshInstance)>,
+        sources: &Query<(&GlobalTransform, &SkinnedMesh)>,
+        inverse_bindposes: &Assets<SkinnedMeshInverseBindposes>,
+        joints: &Query<&GlobalTransform>,
+    ) {
+        let mut updated = EntityHashMap::<Entity, VecDeque<Vec<Mat4>>>::default();
+        for (_, _, instance) in instances {
+            if updated.contains_key(&instance.source) {
+                continue;
+            }
+            let Ok((source_transform, skin)) = sources.get(instance.source) else {
+                continue;
+            };
+            let Some(inverse_bindposes) = inverse_bindposes.get(&skin.inverse_bindposes) else {
+                continue;
+            };
+            let mesh_from_world = source_transform.compute_matrix().inverse();
+            let pose = joints
+                .iter_many(&skin.joints)
+                .zip(inverse_bindposes.iter())
+                .take(MAX_JOINTS)
+                .map(|(joint, bindpose)| mesh_from_world * joint.compute_matrix() * *bindpose)
+                .collect::<Vec<_>>();
+            // As above, bail on missing joints rather than assigning the wrong bones.
+            if pose.len() != skin.joints.len().min(MAX_JOINTS) {
+                continue;
+            }
+
+            let mut poses = self.remove(&instance.source).unwrap_or_default();
+            poses.truncate(MAX_SKIN_PHASE_OFFSET as usize);
+            poses.push_front(pose);
+            updated.insert(instance.source, poses);
+        }
+        self.0 = updated;
+    }
+}
+
 // NOTE: The skinned joints uniform buffer has to be bound at a dynamic offset per
-// entity and so cannot currently be batched.
+// entity and so cannot currently be batched. `SkinnedMeshInstance`s sharing a pose share
+// the offset, and are batched by comparing their `SkinIndex`.
 pub fn no_automatic_skin_batching(
     mut commands: Commands,
     query: Query<Entity, (With<SkinnedMesh>, Without<NoAutomaticBatching>)>,
@@ -149,3 +237,84 @@ pub fn no_automatic_skin_batching(
         commands.entity(entity).insert(NoAutomaticBatching);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::system::{
+        lifetimeless::{Read, SQuery, SRes},
+        SystemState,
+    };
+    use bevy_math::Vec3;
+
+    type PoseParams = (
+        SQuery<(Entity, Read<ViewVisibility>, Read<SkinnedMeshInstance>)>,
+        SQuery<(Read<GlobalTransform>, Read<SkinnedMesh>)>,
+        SRes<Assets<SkinnedMeshInverseBindposes>>,
+        SQuery<Read<GlobalTransform>>,
+    );
+
+    fn update(world: &mut World, pose_history: &mut SkinPoseHistory) {
+        let mut state = SystemState::<PoseParams>::new(world);
+        let (instances, sources, inverse_bindposes, joints) = state.get(world);
+        pose_history.update(&instances, &sources, &inverse_bindposes, &joints);
+    }
+
+    #[test]
+    fn pose_history_is_relative_to_source_and_bounded() {
+        let mut world = World::new();
+        world.init_resource::<Assets<SkinnedMeshInverseBindposes>>();
+        let inverse_bindposes = world
+            .resource_mut::<Assets<SkinnedMeshInverseBindposes>>()
+            .add(vec![Mat4::IDENTITY]);
+        let joint = world
+            .spawn(GlobalTransform::from_translation(Vec3::new(11.0, 0.0, 0.0)))
+            .id();
+        let source = world
+            .spawn((
+                GlobalTransform::from_translation(Vec3::new(10.0, 0.0, 0.0)),
+                SkinnedMesh {
+                    inverse_bindposes,
+                    joints: vec![joint],
+                },
+            ))
+            .id();
+        let instances = [
+            world
+                .spawn((ViewVisibility::default(), SkinnedMeshInstance::new(source)))
+                .id(),
+            world
+                .spawn((
+                    ViewVisibility::default(),
+                    SkinnedMeshInstance::new(source).with_phase_offset(3),
+                ))
+                .id(),
+        ];
+
+        let mut pose_history = SkinPoseHistory::default();
+        update(&mut world, &mut pose_history);
+        *world.get_mut::<GlobalTransform>(joint).unwrap() =
+            GlobalTransform::from_translation(Vec3::new(12.0, 0.0, 0.0));
+        update(&mut world, &mut pose_history);
+
+        // The instances share the poses of their source, from the newest to the oldest.
+        let poses = &pose_history[&source];
+        assert_eq!(poses.len(), 2);
+        assert_eq!(poses[0], [Mat4::from_translation(Vec3::new(2.0, 0.0, 0.0))]);
+        assert_eq!(poses[1], [Mat4::from_translation(Vec3::new(1.0, 0.0, 0.0))]);
+
+        for _ in 0..MAX_SKIN_PHASE_OFFSET * 2 {
+            update(&mut world, &mut pose_history);
+        }
+        assert_eq!(
+            pose_history[&source].len(),
+            MAX_SKIN_PHASE_OFFSET as usize + 1
+        );
+
+        for instance in instances {
+            world.despawn(instance);
+        }
+        update(&mut world, &mut pose_history);
+        assert!(pose_history.is_empty());
+    }
+}
@@ -1,6 +1,10 @@
 #define_import_path bevy_pbr::skinning
 
-#import bevy_pbr::mesh_types::SkinnedMesh
+#import bevy_pbr::{
+    mesh_bindings::mesh,
+    mesh_functions::get_model_matrix,
+    mesh_types::{SkinnedMesh, MESH_FLAGS_SKINNED_INSTANCE_BIT},
+}
 
 #ifdef SKINNED
 
@@ -16,6 +20,20 @@ fn skin_model(
         + weights.w * joint_matrices.data[indexes.w];
 }
 
+// The joint matrices of a `SkinnedMeshInstance` are shared with other instances, relative to
+// the model matrix of each instance.
+fn instance_skin_model(
+    instance_index: u32,
+    indexes: vec4<u32>,
+    weights: vec4<f32>,
+) -> mat4x4<f32> {
+    let model = skin_model(indexes, weights);
+    if (mesh[instance_index].flags & MESH_FLAGS_SKINNED_INSTANCE_BIT) != 0u {
+        return get_model_matrix(instance_index) * model;
+    }
+    return model;
+}
+
 fn inverse_transpose_3x3m(in: mat3x3<f32>) -> mat3x3<f32> {
     let x = cross(in[1], in[2]);
     let y = cross(in[2], in[0]);
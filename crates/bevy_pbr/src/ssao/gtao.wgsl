@@ -19,7 +19,8 @@
 @group(0) @binding(2) var hilbert_index_lut: texture_2d<u32>;
 @group(0) @binding(3) var ambient_occlusion: texture_storage_2d<r16float, write>;
 @group(0) @binding(4) var depth_differences: texture_storage_2d<r32uint, write>;
-@group(0) @binding(5) var<uniform> globals: Globals;
+@group(0) @binding(5) var bent_normals: texture_storage_2d<rgba8unorm, write>;
+@group(0) @binding(6) var<uniform> globals: Globals;
 @group(1) @binding(0) var point_clamp_sampler: sampler;
 @group(1) @binding(1) var<uniform> view: View;
 
@@ -66,12 +67,12 @@ fn calculate_neighboring_depth_differences(pixel_coordinates: vec2<i32>) -> f32
 fn load_normal_view_space(uv: vec2<f32>) -> vec3<f32> {
     var world_normal = textureSampleLevel(normals, point_clamp_sampler, uv, 0.0).xyz;
     world_normal = (world_normal * 2.0) - 1.0;
-    let inverse_view = mat3x3<f32>(
-        view.inverse_view[0].xyz,
-        view.inverse_view[1].xyz,
-        view.inverse_view[2].xyz,
+    let view_from_world = mat3x3<f32>(
+        view.view[0].xyz,
+        view.view[1].xyz,
+        view.view[2].xyz,
     );
-    return inverse_view * world_normal;
+    return view_from_world * world_normal;
 }
 
 fn reconstruct_view_space_position(depth: f32, uv: vec2<f32>) -> vec3<f32> {
@@ -111,6 +112,7 @@ fn gtao(@builtin(global_invocation_id) global_id: vec3<u32>) {
     let sample_scale = (-0.5 * effect_radius * view.projection[0][0]) / pixel_position.z;
 
     var visibility = 0.0;
+    var bent_normal = vec3<f32>(0.0);
     for (var slice_t = 0.0; slice_t < slice_count; slice_t += 1.0) {
         let slice = slice_t + noise.x;
         let phi = (PI / slice_count) * slice;
@@ -165,9 +167,25 @@ fn gtao(@builtin(global_invocation_id) global_id: vec3<u32>) {
         let v1 = (cos_norm + 2.0 * horizon_1 * sin(n) - cos(2.0 * horizon_1 - n)) / 4.0;
         let v2 = (cos_norm + 2.0 * horizon_2 * sin(n) - cos(2.0 * horizon_2 - n)) / 4.0;
         visibility += projected_normal_length * (v1 + v2);
+
+        // Bent normal, the average unoccluded direction within the slice
+        // https://github.com/GameTechDev/XeGTAO#implementation-details
+        let t0 = (6.0 * sin(horizon_2 - n) - sin(3.0 * horizon_2 - n) + 6.0 * sin(horizon_1 - n) - sin(3.0 * horizon_1 - n) + 16.0 * sin(n) - 3.0 * (sin(horizon_2 + n) + sin(horizon_1 + n))) / 12.0;
+        let t1 = (-cos(3.0 * horizon_2 - n) - cos(3.0 * horizon_1 - n) + 8.0 * cos(n) - 3.0 * (cos(horizon_2 + n) + cos(horizon_1 + n))) / 12.0;
+        bent_normal += (normalize(orthographic_direction) * t0 + view_vec * t1) * projected_normal_length;
     }
     visibility /= slice_count;
     visibility = clamp(visibility, 0.03, 1.0);
 
+    // Fall back to the surface normal if the slices cancelled each other out
+    bent_normal = select(pixel_normal, normalize(bent_normal), dot(bent_normal, bent_normal) > 0.0001);
+    let inverse_view = mat3x3<f32>(
+        view.inverse_view[0].xyz,
+        view.inverse_view[1].xyz,
+        view.inverse_view[2].xyz,
+    );
+    let world_bent_normal = normalize(inverse_view * bent_normal);
+
     textureStore(ambient_occlusion, pixel_coordinates, vec4<f32>(visibility, 0.0, 0.0, 0.0));
+    textureStore(bent_normals, pixel_coordinates, vec4<f32>(world_bent_normal * 0.5 + 0.5, 1.0));
 }
@@ -22,3 +22,29 @@ fn fast_acos(in_x: f32) -> f32 {
     res *= fast_sqrt(1.0 - x);
     return select(PI - res, res, in_x >= 0.0);
 }
+
+// Estimates specular occlusion as the fraction of the specular cone, around the reflection vector,
+// that lies within the visibility cone around the bent normal
+// https://blog.selfshadow.com/publications/s2016-shading-course/activision/s2016_pbs_activision_occlusion.pdf#page=95
+fn specular_occlusion_from_bent_normal(visibility: f32, bent_normal: vec3<f32>, R: vec3<f32>, roughness: f32) -> f32 {
+    let cos_visibility = sqrt(saturate(1.0 - visibility));
+    let cos_specular = exp2(-3.321928 * roughness * roughness);
+    let cos_between = dot(bent_normal, R);
+
+    // Intersection of the two spherical caps, smoothly approximated
+    let visibility_angle = fast_acos(cos_visibility);
+    let specular_angle = fast_acos(cos_specular);
+    let angle_between = fast_acos(cos_between);
+    var intersection = 0.0;
+    if min(visibility_angle, specular_angle) <= max(visibility_angle, specular_angle) - angle_between {
+        intersection = 1.0 - max(cos_visibility, cos_specular);
+    } else if visibility_angle + specular_angle > angle_between {
+        let delta = abs(visibility_angle - specular_angle);
+        let x = 1.0 - saturate((angle_between - delta) / max(visibility_angle + specular_angle - delta, 0.0001));
+        intersection = x * x * (3.0 - 2.0 * x) * (1.0 - max(cos_visibility, cos_specular));
+    }
+
+    let occlusion = saturate(intersection / max(1.0 - cos_specular, 0.0001));
+    // The cones degenerate for mirror-like surfaces, so fade the occlusion in with roughness
+    return mix(1.0, occlusion, smoothstep(0.01, 0.09, roughness));
+}
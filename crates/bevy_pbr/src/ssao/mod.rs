@@ -27,7 +27,7 @@ use bevy_render::{
         *,
     },
     renderer::{RenderAdapter, RenderContext, RenderDevice, RenderQueue},
-    texture::{CachedTexture, TextureCache},
+    texture::{CachedTexture, TextureCache, TextureLifetime},
     view::{Msaa, ViewUniform, ViewUniformOffset, ViewUniforms},
     Extract, ExtractSchedule, Render, RenderApp, RenderSet,
 };
@@ -144,6 +144,11 @@ pub struct ScreenSpaceAmbientOcclusionBundle {
 /// This darkens creases, e.g. on staircases, and gives nice contact shadows
 /// where objects meet, giving entities a more "grounded" feel.
 ///
+/// Alongside the occlusion, SSAO outputs bent normals: the average unoccluded
+/// direction around each pixel. Environment map and irradiance volume lighting
+/// is sampled along them, and their specular lighting is occluded by the cone
+/// of visible directions, which reduces light leaking into enclosed spaces.
+///
 /// # Usage Notes
 ///
 /// Requires that you add [`ScreenSpaceAmbientOcclusionPlugin`] to your app,
@@ -385,6 +390,7 @@ impl FromWorld for SsaoPipelines {
                     texture_2d(TextureSampleType::Uint),
                     texture_storage_2d(TextureFormat::R16Float, StorageTextureAccess::WriteOnly),
                     texture_storage_2d(TextureFormat::R32Uint, StorageTextureAccess::WriteOnly),
+                    texture_storage_2d(TextureFormat::Rgba8Unorm, StorageTextureAccess::WriteOnly),
                     uniform_buffer::<GlobalsUniform>(false),
                 ),
             ),
@@ -398,6 +404,8 @@ impl FromWorld for SsaoPipelines {
                     texture_2d(TextureSampleType::Float { filterable: false }),
                     texture_2d(TextureSampleType::Uint),
                     texture_storage_2d(TextureFormat::R16Float, StorageTextureAccess::WriteOnly),
+                    texture_2d(TextureSampleType::Float { filterable: false }),
+                    texture_storage_2d(TextureFormat::Rgba8Unorm, StorageTextureAccess::WriteOnly),
                 ),
             ),
         );
@@ -512,6 +520,9 @@ pub struct ScreenSpaceAmbientOcclusionTextures {
     ssao_noisy_texture: CachedTexture, // Pre-spatially denoised texture
     pub screen_space_ambient_occlusion_texture: CachedTexture, // Spatially denoised texture
     depth_differences_texture: CachedTexture,
+    bent_normals_noisy_texture: CachedTexture, // Pre-spatially denoised texture
+    /// World space bent normals, encoded as `normal * 0.5 + 0.5`.
+    pub bent_normals_texture: CachedTexture, // Spatially denoised texture
 }
 
 fn prepare_ssao_textures(
@@ -530,7 +541,11 @@ fn prepare_ssao_textures(
             depth_or_array_layers: 1,
         };
 
-        let preprocessed_depth_texture = texture_cache.get(
+        // Only the SSAO node uses these textures, the main pass reads the denoised one.
+        let lifetime =
+            TextureLifetime::node(entity, SubGraph3d, LabelsPbr::ScreenSpaceAmbientOcclusion);
+
+        let preprocessed_depth_texture = texture_cache.get_transient(
             &render_device,
             TextureDescriptor {
                 label: Some("ssao_preprocessed_depth_texture"),
@@ -542,9 +557,10 @@ fn prepare_ssao_textures(
                 usage: TextureUsages::STORAGE_BINDING | TextureUsages::TEXTURE_BINDING,
                 view_formats: &[],
             },
+            lifetime,
         );
 
-        let ssao_noisy_texture = texture_cache.get(
+        let ssao_noisy_texture = texture_cache.get_transient(
             &render_device,
             TextureDescriptor {
                 label: Some("ssao_noisy_texture"),
@@ -556,6 +572,7 @@ fn prepare_ssao_textures(
                 usage: TextureUsages::STORAGE_BINDING | TextureUsages::TEXTURE_BINDING,
                 view_formats: &[],
             },
+            lifetime,
         );
 
         let ssao_texture = texture_cache.get(
@@ -572,7 +589,7 @@ fn prepare_ssao_textures(
             },
         );
 
-        let depth_differences_texture = texture_cache.get(
+        let depth_differences_texture = texture_cache.get_transient(
             &render_device,
             TextureDescriptor {
                 label: Some("ssao_depth_differences_texture"),
@@ -584,6 +601,36 @@ fn prepare_ssao_textures(
                 usage: TextureUsages::STORAGE_BINDING | TextureUsages::TEXTURE_BINDING,
                 view_formats: &[],
             },
+            lifetime,
+        );
+
+        let bent_normals_noisy_texture = texture_cache.get_transient(
+            &render_device,
+            TextureDescriptor {
+                label: Some("ssao_bent_normals_noisy_texture"),
+                size,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: TextureFormat::Rgba8Unorm,
+                usage: TextureUsages::STORAGE_BINDING | TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+            lifetime,
+        );
+
+        let bent_normals_texture = texture_cache.get(
+            &render_device,
+            TextureDescriptor {
+                label: Some("ssao_bent_normals_texture"),
+                size,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: TextureFormat::Rgba8Unorm,
+                usage: TextureUsages::STORAGE_BINDING | TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
         );
 
         commands
@@ -593,6 +640,8 @@ fn prepare_ssao_textures(
                 ssao_noisy_texture,
                 screen_space_ambient_occlusion_texture: ssao_texture,
                 depth_differences_texture,
+                bent_normals_noisy_texture,
+                bent_normals_texture,
             });
     }
 }
@@ -695,6 +744,7 @@ fn prepare_ssao_bind_groups(
                 &pipelines.hilbert_index_lut,
                 &ssao_textures.ssao_noisy_texture.default_view,
                 &ssao_textures.depth_differences_texture.default_view,
+                &ssao_textures.bent_normals_noisy_texture.default_view,
                 globals_uniforms.clone(),
             )),
         );
@@ -708,6 +758,8 @@ fn prepare_ssao_bind_groups(
                 &ssao_textures
                     .screen_space_ambient_occlusion_texture
                     .default_view,
+                &ssao_textures.bent_normals_noisy_texture.default_view,
+                &ssao_textures.bent_normals_texture.default_view,
             )),
         );
 
@@ -763,3 +815,43 @@ fn hilbert_index(mut x: u16, mut y: u16) -> u16 {
 fn div_ceil(numerator: u32, denominator: u32) -> u32 {
     (numerator + denominator - 1) / denominator
 }
+
+#[cfg(test)]
+mod tests {
+    use naga_oil::compose::{ComposableModuleDescriptor, Composer, NagaModuleDescriptor};
+
+    #[test]
+    fn bent_normal_specular_occlusion_compiles() {
+        let mut composer = Composer::default();
+        for (source, file_path) in [
+            (include_str!("../render/rgb9e5.wgsl"), "rgb9e5.wgsl"),
+            (include_str!("../render/utils.wgsl"), "utils.wgsl"),
+            (include_str!("gtao_utils.wgsl"), "gtao_utils.wgsl"),
+        ] {
+            composer
+                .add_composable_module(ComposableModuleDescriptor {
+                    source,
+                    file_path,
+                    ..Default::default()
+                })
+                .unwrap();
+        }
+
+        let source = "
+#import bevy_pbr::gtao_utils::specular_occlusion_from_bent_normal
+
+@fragment
+fn fragment() -> @location(0) vec4<f32> {
+    let N = vec3(0.0, 1.0, 0.0);
+    return vec4(specular_occlusion_from_bent_normal(0.5, N, N, 0.5));
+}
+";
+        composer
+            .make_naga_module(NagaModuleDescriptor {
+                source,
+                file_path: "test.wgsl",
+                ..Default::default()
+            })
+            .unwrap();
+    }
+}
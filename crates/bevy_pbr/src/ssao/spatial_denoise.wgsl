@@ -14,9 +14,16 @@
 @group(0) @binding(0) var ambient_occlusion_noisy: texture_2d<f32>;
 @group(0) @binding(1) var depth_differences: texture_2d<u32>;
 @group(0) @binding(2) var ambient_occlusion: texture_storage_2d<r16float, write>;
+@group(0) @binding(3) var bent_normals_noisy: texture_2d<f32>;
+@group(0) @binding(4) var bent_normals: texture_storage_2d<rgba8unorm, write>;
 @group(1) @binding(0) var point_clamp_sampler: sampler;
 @group(1) @binding(1) var<uniform> view: View;
 
+fn load_bent_normal(pixel_coordinates: vec2<i32>) -> vec3<f32> {
+    let clamped_coordinates = clamp(pixel_coordinates, vec2(0i), vec2<i32>(textureDimensions(bent_normals_noisy)) - 1i);
+    return textureLoad(bent_normals_noisy, clamped_coordinates, 0).xyz * 2.0 - 1.0;
+}
+
 @compute
 @workgroup_size(8, 8, 1)
 fn spatial_denoise(@builtin(global_invocation_id) global_id: vec3<u32>) {
@@ -80,5 +87,18 @@ fn spatial_denoise(@builtin(global_invocation_id) global_id: vec3<u32>) {
 
     let denoised_visibility = sum / sum_weight;
 
+    // Bent normals are filtered with the same weights, then renormalized
+    var bent_normal_sum = load_bent_normal(pixel_coordinates) * center_weight;
+    bent_normal_sum += load_bent_normal(pixel_coordinates + vec2<i32>(-1i, 0i)) * left_weight;
+    bent_normal_sum += load_bent_normal(pixel_coordinates + vec2<i32>(1i, 0i)) * right_weight;
+    bent_normal_sum += load_bent_normal(pixel_coordinates + vec2<i32>(0i, -1i)) * top_weight;
+    bent_normal_sum += load_bent_normal(pixel_coordinates + vec2<i32>(0i, 1i)) * bottom_weight;
+    bent_normal_sum += load_bent_normal(pixel_coordinates + vec2<i32>(-1i, -1i)) * top_left_weight;
+    bent_normal_sum += load_bent_normal(pixel_coordinates + vec2<i32>(1i, -1i)) * top_right_weight;
+    bent_normal_sum += load_bent_normal(pixel_coordinates + vec2<i32>(-1i, 1i)) * bottom_left_weight;
+    bent_normal_sum += load_bent_normal(pixel_coordinates + vec2<i32>(1i, 1i)) * bottom_right_weight;
+    let denoised_bent_normal = normalize(bent_normal_sum);
+
     textureStore(ambient_occlusion, pixel_coordinates, vec4<f32>(denoised_visibility, 0.0, 0.0, 0.0));
+    textureStore(bent_normals, pixel_coordinates, vec4<f32>(denoised_bent_normal * 0.5 + 0.5, 1.0));
 }
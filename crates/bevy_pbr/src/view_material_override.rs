@@ -0,0 +1,196 @@
+use bevy_asset::{AssetId, Handle};
+use bevy_ecs::{prelude::*, query::QueryItem, system::lifetimeless::Read};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    extract_component::ExtractComponent,
+    extract_instances::{ExtractInstance, ExtractRetainedInstance, ExtractedInstances},
+};
+use bevy_utils::{HashMap, HashSet};
+
+use crate::{Material, StandardMaterial};
+
+/// Groups meshes so that a [`ViewMaterialOverride`] can substitute their material or hide them,
+/// for example to draw each team of a minimap with a flat color.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Component, Default, PartialEq, Hash)]
+pub struct MaterialOverrideTag(pub u32);
+
+impl ExtractInstance for MaterialOverrideTag {
+    type QueryData = Read<MaterialOverrideTag>;
+    type QueryFilter = ();
+
+    fn extract(item: QueryItem<'_, Self::QueryData>) -> Option<Self> {
+        Some(*item)
+    }
+}
+
+impl ExtractRetainedInstance for MaterialOverrideTag {
+    type Source = MaterialOverrideTag;
+}
+
+/// Substitutes or modifies the materials of type `M` of the meshes rendered by a camera, for
+/// example to render a minimap unlit, or a thermal vision mode with flat colors.
+///
+/// The overrides are applied while queueing the meshes of the view: the other views, and the
+/// shadow maps, render the meshes with their own materials.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_asset::Handle;
+/// # use bevy_pbr::{MaterialOverrideTag, StandardMaterial, ViewMaterialOverride};
+/// # use bevy_render::camera::Camera;
+/// const DECALS: MaterialOverrideTag = MaterialOverrideTag(0);
+/// const RED_TEAM: MaterialOverrideTag = MaterialOverrideTag(1);
+///
+/// fn spawn_minimap(mut commands: Commands, red: Handle<StandardMaterial>) {
+///     let mut material_override = ViewMaterialOverride::<StandardMaterial>::unlit();
+///     material_override.tagged_materials.insert(RED_TEAM, red);
+///     material_override.hidden_tags.insert(DECALS);
+///     commands.spawn((Camera::default(), material_override));
+/// }
+/// ```
+#[derive(Component, Clone, Debug)]
+pub struct ViewMaterialOverride<M: Material = StandardMaterial> {
+    /// Renders the meshes without lighting, as if [`StandardMaterial::unlit`] was set.
+    ///
+    /// This adds the `FORCE_UNLIT` shader def to the pipelines of the view, which is only read
+    /// by the shaders using the PBR fragment functions.
+    pub unlit: bool,
+    /// The material drawn instead of the materials of the meshes without a
+    /// [`MaterialOverrideTag`] in [`tagged_materials`](Self::tagged_materials).
+    pub material: Option<Handle<M>>,
+    /// The materials drawn instead of the materials of the meshes with a given
+    /// [`MaterialOverrideTag`].
+    pub tagged_materials: HashMap<MaterialOverrideTag, Handle<M>>,
+    /// The meshes with one of these [`MaterialOverrideTag`]s aren't drawn by the view.
+    pub hidden_tags: HashSet<MaterialOverrideTag>,
+}
+
+impl<M: Material> Default for ViewMaterialOverride<M> {
+    fn default() -> Self {
+        Self {
+            unlit: false,
+            material: None,
+            tagged_materials: HashMap::default(),
+            hidden_tags: HashSet::default(),
+        }
+    }
+}
+
+impl<M: Material> ViewMaterialOverride<M> {
+    /// Renders all the meshes of the view unlit, with their own materials.
+    pub fn unlit() -> Self {
+        Self {
+            unlit: true,
+            ..Default::default()
+        }
+    }
+
+    /// Renders all the meshes of the view with `material`.
+    pub fn material(material: Handle<M>) -> Self {
+        Self {
+            material: Some(material),
+            ..Default::default()
+        }
+    }
+}
+
+impl<M: Material> ExtractComponent for ViewMaterialOverride<M> {
+    type QueryData = Read<ViewMaterialOverride<M>>;
+    type QueryFilter = ();
+    type Out = ExtractedViewMaterialOverride<M>;
+
+    fn extract_component(item: QueryItem<'_, Self::QueryData>) -> Option<Self::Out> {
+        Some(ExtractedViewMaterialOverride {
+            unlit: item.unlit,
+            material: item.material.as_ref().map(Handle::id),
+            tagged_materials: item
+                .tagged_materials
+                .iter()
+                .map(|(tag, material)| (*tag, material.id()))
+                .collect(),
+            hidden_tags: item.hidden_tags.clone(),
+        })
+    }
+}
+
+/// The [`ViewMaterialOverride`] of a view, in the render world.
+#[derive(Component)]
+pub struct ExtractedViewMaterialOverride<M: Material> {
+    pub unlit: bool,
+    pub material: Option<AssetId<M>>,
+    pub tagged_materials: HashMap<MaterialOverrideTag, AssetId<M>>,
+    pub hidden_tags: HashSet<MaterialOverrideTag>,
+}
+
+impl<M: Material> ExtractedViewMaterialOverride<M> {
+    /// Returns the material drawn by the view for a mesh with the material `material` and the
+    /// tag `tag`, or `None` if the mesh is hidden from the view.
+    pub fn resolve(
+        &self,
+        material: AssetId<M>,
+        tag: Option<&MaterialOverrideTag>,
+    ) -> Option<AssetId<M>> {
+        let Some(tag) = tag else {
+            return Some(self.material.unwrap_or(material));
+        };
+        if self.hidden_tags.contains(tag) {
+            return None;
+        }
+        Some(
+            self.tagged_materials
+                .get(tag)
+                .copied()
+                .or(self.material)
+                .unwrap_or(material),
+        )
+    }
+}
+
+/// Returns the material drawn by a view for `entity`, or `None` if the mesh is hidden from the
+/// view.
+pub(crate) fn view_material<M: Material>(
+    material_override: Option<&ExtractedViewMaterialOverride<M>>,
+    material_override_tags: &ExtractedInstances<MaterialOverrideTag>,
+    entity: &Entity,
+    material: AssetId<M>,
+) -> Option<AssetId<M>> {
+    match material_override {
+        Some(material_override) => {
+            material_override.resolve(material, material_override_tags.get(entity))
+        }
+        None => Some(material),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tagged_materials_take_precedence() {
+        let own = Handle::<StandardMaterial>::weak_from_u128(1).id();
+        let fallback = Handle::weak_from_u128(2).id();
+        let team = Handle::weak_from_u128(3).id();
+        let material_override = ExtractedViewMaterialOverride::<StandardMaterial> {
+            unlit: false,
+            material: Some(fallback),
+            tagged_materials: [(MaterialOverrideTag(1), team)].into_iter().collect(),
+            hidden_tags: [MaterialOverrideTag(2)].into_iter().collect(),
+        };
+
+        assert_eq!(material_override.resolve(own, None), Some(fallback));
+        assert_eq!(
+            material_override.resolve(own, Some(&MaterialOverrideTag(1))),
+            Some(team)
+        );
+        assert_eq!(
+            material_override.resolve(own, Some(&MaterialOverrideTag(0))),
+            Some(fallback)
+        );
+        assert_eq!(
+            material_override.resolve(own, Some(&MaterialOverrideTag(2))),
+            None
+        );
+    }
+}
@@ -0,0 +1,90 @@
+//! Interface of the picking backends, which find the entities under the pointers.
+//!
+//! A backend is a system running in [`PickSet::Backend`](crate::PickSet::Backend), which sends a
+//! [`PointerHits`] event for each pointer and camera, listing the entities under the pointer in
+//! the view of that camera. Backends only need to report *what* is under the pointers: sorting
+//! the hits of all the backends and deciding which entities are hovered is done afterwards.
+//!
+//! Backends that cast rays from the cameras can use [`camera_ray`] to find the ray of a pointer.
+
+use bevy_ecs::prelude::*;
+use bevy_math::{Ray3d, Vec3};
+use bevy_reflect::Reflect;
+use bevy_render::camera::Camera;
+use bevy_transform::components::GlobalTransform;
+
+use crate::pointer::{Location, PointerId};
+
+/// The entities under a pointer in the view of a camera, sent by a picking backend.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct PointerHits {
+    /// The pointer the entities are under.
+    pub pointer: PointerId,
+    /// The entities under the pointer, with the data of each hit.
+    ///
+    /// The hits don't need to be sorted.
+    pub picks: Vec<(Entity, HitData)>,
+    /// The order of these hits relative to the hits of other cameras and backends:
+    /// hits with a higher order are in front of hits with a lower order.
+    ///
+    /// This is usually the [`Camera::order`] of the camera the entities were hit with. Backends
+    /// for entities drawn on top of the other entities of a camera, such as the UI, may add a
+    /// fraction to it.
+    pub order: f32,
+}
+
+impl PointerHits {
+    /// Creates a new [`PointerHits`].
+    pub fn new(pointer: PointerId, picks: Vec<(Entity, HitData)>, order: f32) -> Self {
+        Self {
+            pointer,
+            picks,
+            order,
+        }
+    }
+}
+
+/// Where an entity was hit by a pointer.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct HitData {
+    /// The camera the entity was hit with.
+    pub camera: Entity,
+    /// The distance from the camera to the hit, used to sort the hits of a same [`PointerHits`]:
+    /// hits with a lower depth are in front.
+    ///
+    /// Backends that don't work in world space may use any other value with the same ordering.
+    pub depth: f32,
+    /// The position of the hit in world space, if known.
+    pub position: Option<Vec3>,
+    /// The normal of the surface at the hit in world space, if known.
+    pub normal: Option<Vec3>,
+}
+
+impl HitData {
+    /// Creates a new [`HitData`].
+    pub fn new(camera: Entity, depth: f32, position: Option<Vec3>, normal: Option<Vec3>) -> Self {
+        Self {
+            camera,
+            depth,
+            position,
+            normal,
+        }
+    }
+}
+
+/// Returns the ray going from `camera` through the pointer at `location`, in world space.
+///
+/// Returns `None` if the pointer isn't in the viewport of the camera, or if the camera is
+/// inactive.
+pub fn camera_ray(
+    location: &Location,
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    primary_window: Option<Entity>,
+) -> Option<Ray3d> {
+    if !camera.is_active || !location.is_in_viewport(camera, primary_window) {
+        return None;
+    }
+    let viewport_min = camera.logical_viewport_rect()?.min;
+    camera.viewport_to_world(camera_transform, location.position - viewport_min)
+}
@@ -0,0 +1,367 @@
+//! Pointer events, targeting the entities hovered, pressed or dragged by the pointers.
+//!
+//! Each kind of event is a separate [`Pointer<E>`] event, where `E` is one of [`Over`], [`Out`],
+//! [`Down`], [`Up`], [`Click`], [`Move`], [`DragStart`], [`Drag`] and [`DragEnd`]. They can be
+//! read with an [`EventReader`], or listened to by entities and their ancestors with
+//! [`On`](crate::listener::On).
+
+use std::ops::Deref;
+
+use bevy_ecs::{prelude::*, system::SystemParam};
+use bevy_math::Vec2;
+use bevy_reflect::Reflect;
+use bevy_utils::{Duration, HashMap, Instant};
+
+use crate::{
+    backend::HitData,
+    focus::{HoverMap, PreviousHoverMap},
+    listener::EntityEvent,
+    pointer::{Location, PointerAction, PointerButton, PointerId, PointerInput},
+};
+
+/// An event sent by a pointer to an entity.
+///
+/// Dereferences to the event data `E`.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct Pointer<E> {
+    /// The entity targeted by the event.
+    pub target: Entity,
+    /// The pointer that sent the event.
+    pub pointer_id: PointerId,
+    /// The location of the pointer when the event was sent.
+    pub pointer_location: Location,
+    /// The data of the event.
+    pub event: E,
+}
+
+impl<E> Pointer<E> {
+    /// Creates a new [`Pointer`] event.
+    pub fn new(
+        target: Entity,
+        pointer_id: PointerId,
+        pointer_location: Location,
+        event: E,
+    ) -> Self {
+        Self {
+            target,
+            pointer_id,
+            pointer_location,
+            event,
+        }
+    }
+}
+
+impl<E> Deref for Pointer<E> {
+    type Target = E;
+
+    fn deref(&self) -> &Self::Target {
+        &self.event
+    }
+}
+
+impl<E: Clone + Send + Sync + 'static> EntityEvent for Pointer<E> {
+    fn target(&self) -> Entity {
+        self.target
+    }
+}
+
+/// The pointer started hovering the entity.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct Over {
+    /// Where the entity was hit.
+    pub hit: HitData,
+}
+
+/// The pointer stopped hovering the entity.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct Out {
+    /// Where the entity was last hit.
+    pub hit: HitData,
+}
+
+/// A button was pressed while the pointer was hovering the entity.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct Down {
+    /// The button that was pressed.
+    pub button: PointerButton,
+    /// Where the entity was hit.
+    pub hit: HitData,
+}
+
+/// A button was released while the pointer was hovering the entity.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct Up {
+    /// The button that was released.
+    pub button: PointerButton,
+    /// Where the entity was hit.
+    pub hit: HitData,
+}
+
+/// A button was pressed then released while the pointer was hovering the entity.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct Click {
+    /// The button that was clicked.
+    pub button: PointerButton,
+    /// Where the entity was hit when the button was released.
+    pub hit: HitData,
+    /// How long the button was pressed.
+    pub duration: Duration,
+}
+
+/// The pointer moved while hovering the entity.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct Move {
+    /// Where the entity was hit.
+    pub hit: HitData,
+    /// How much the pointer moved, in logical pixels.
+    pub delta: Vec2,
+}
+
+/// The pointer moved after a button was pressed on the entity, starting a drag.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct DragStart {
+    /// The button that is dragging the entity.
+    pub button: PointerButton,
+    /// Where the entity was hit when the button was pressed.
+    pub hit: HitData,
+}
+
+/// The pointer moved while dragging the entity.
+///
+/// Drags continue even when the pointer doesn't hover the entity anymore.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct Drag {
+    /// The button that is dragging the entity.
+    pub button: PointerButton,
+    /// How much the pointer moved since the drag started, in logical pixels.
+    pub distance: Vec2,
+    /// How much the pointer moved since the last [`Drag`] event, in logical pixels.
+    pub delta: Vec2,
+}
+
+/// The button that was dragging the entity was released.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct DragEnd {
+    /// The button that was dragging the entity.
+    pub button: PointerButton,
+    /// How much the pointer moved during the drag, in logical pixels.
+    pub distance: Vec2,
+}
+
+/// The entities pressed by a pointer button, with when they were pressed.
+struct Press {
+    time: Instant,
+    start_position: Vec2,
+    entities: HashMap<Entity, HitData>,
+    /// The last position of the pointer once the entities are being dragged.
+    dragging: Option<Vec2>,
+}
+
+fn pointer_event<E>(input: &PointerInput, target: Entity, event: E) -> Pointer<E> {
+    Pointer::new(target, input.pointer_id, input.location.clone(), event)
+}
+
+/// The state of [`send_pointer_events`].
+#[derive(Default)]
+pub struct PointerEventState {
+    presses: HashMap<(PointerId, PointerButton), Press>,
+    locations: HashMap<PointerId, Location>,
+}
+
+/// The writers of every [`Pointer`] event.
+#[derive(SystemParam)]
+pub struct PointerEventWriters<'w> {
+    over: EventWriter<'w, Pointer<Over>>,
+    out: EventWriter<'w, Pointer<Out>>,
+    down: EventWriter<'w, Pointer<Down>>,
+    up: EventWriter<'w, Pointer<Up>>,
+    click: EventWriter<'w, Pointer<Click>>,
+    moves: EventWriter<'w, Pointer<Move>>,
+    drag_start: EventWriter<'w, Pointer<DragStart>>,
+    drag: EventWriter<'w, Pointer<Drag>>,
+    drag_end: EventWriter<'w, Pointer<DragEnd>>,
+}
+
+/// Sends the [`Pointer`] events from the changes of the [`HoverMap`] and the [`PointerInput`]s.
+pub fn send_pointer_events(
+    mut inputs: EventReader<PointerInput>,
+    hover_map: Res<HoverMap>,
+    previous_hover_map: Res<PreviousHoverMap>,
+    mut state: Local<PointerEventState>,
+    mut writers: PointerEventWriters,
+) {
+    let PointerEventState { presses, locations } = &mut *state;
+    let inputs: Vec<&PointerInput> = inputs.read().collect();
+    for input in &inputs {
+        locations.insert(input.pointer_id, input.location.clone());
+    }
+
+    // Entities that are not hovered anymore, including by pointers that were despawned.
+    for (pointer_id, previous) in previous_hover_map.iter() {
+        let Some(location) = locations.get(pointer_id) else {
+            continue;
+        };
+        let hovered = hover_map.get(pointer_id);
+        for (entity, hit) in previous {
+            if !hovered.is_some_and(|hovered| hovered.contains_key(entity)) {
+                writers.out.send(Pointer::new(
+                    *entity,
+                    *pointer_id,
+                    location.clone(),
+                    Out { hit: hit.clone() },
+                ));
+            }
+        }
+    }
+    for (pointer_id, hovered) in hover_map.iter() {
+        let Some(location) = locations.get(pointer_id) else {
+            continue;
+        };
+        let previous = previous_hover_map.get(pointer_id);
+        for (entity, hit) in hovered {
+            if !previous.is_some_and(|previous| previous.contains_key(entity)) {
+                writers.over.send(Pointer::new(
+                    *entity,
+                    *pointer_id,
+                    location.clone(),
+                    Over { hit: hit.clone() },
+                ));
+            }
+        }
+    }
+    locations.retain(|pointer_id, _| hover_map.contains_key(pointer_id));
+
+    let empty = HashMap::new();
+    for input in inputs {
+        let pointer_id = input.pointer_id;
+        let location = &input.location;
+        let hovered = hover_map.get(&pointer_id).unwrap_or(&empty);
+
+        match input.action {
+            PointerAction::Pressed(button) => {
+                for (entity, hit) in hovered {
+                    writers.down.send(pointer_event(
+                        input,
+                        *entity,
+                        Down {
+                            button,
+                            hit: hit.clone(),
+                        },
+                    ));
+                }
+                presses.insert(
+                    (pointer_id, button),
+                    Press {
+                        time: Instant::now(),
+                        start_position: location.position,
+                        entities: hovered.clone(),
+                        dragging: None,
+                    },
+                );
+            }
+            PointerAction::Released(button) => {
+                let press = presses.remove(&(pointer_id, button));
+                for (entity, hit) in hovered {
+                    writers.up.send(pointer_event(
+                        input,
+                        *entity,
+                        Up {
+                            button,
+                            hit: hit.clone(),
+                        },
+                    ));
+                    if let Some(press) = &press {
+                        if press.entities.contains_key(entity) {
+                            writers.click.send(pointer_event(
+                                input,
+                                *entity,
+                                Click {
+                                    button,
+                                    hit: hit.clone(),
+                                    duration: press.time.elapsed(),
+                                },
+                            ));
+                        }
+                    }
+                }
+                if let Some(press) = press.filter(|press| press.dragging.is_some()) {
+                    for entity in press.entities.keys() {
+                        writers.drag_end.send(pointer_event(
+                            input,
+                            *entity,
+                            DragEnd {
+                                button,
+                                distance: location.position - press.start_position,
+                            },
+                        ));
+                    }
+                }
+            }
+            PointerAction::Moved { delta } => {
+                for (entity, hit) in hovered {
+                    writers.moves.send(pointer_event(
+                        input,
+                        *entity,
+                        Move {
+                            hit: hit.clone(),
+                            delta,
+                        },
+                    ));
+                }
+                for button in PointerButton::iter() {
+                    let Some(press) = presses.get_mut(&(pointer_id, button)) else {
+                        continue;
+                    };
+                    let last_position = match press.dragging {
+                        Some(last_position) => last_position,
+                        None => {
+                            for (entity, hit) in &press.entities {
+                                writers.drag_start.send(pointer_event(
+                                    input,
+                                    *entity,
+                                    DragStart {
+                                        button,
+                                        hit: hit.clone(),
+                                    },
+                                ));
+                            }
+                            press.start_position
+                        }
+                    };
+                    for entity in press.entities.keys() {
+                        writers.drag.send(pointer_event(
+                            input,
+                            *entity,
+                            Drag {
+                                button,
+                                distance: location.position - press.start_position,
+                                delta: location.position - last_position,
+                            },
+                        ));
+                    }
+                    press.dragging = Some(location.position);
+                }
+            }
+            PointerAction::Canceled => {
+                for button in PointerButton::iter() {
+                    let Some(press) = presses.remove(&(pointer_id, button)) else {
+                        continue;
+                    };
+                    if press.dragging.is_none() {
+                        continue;
+                    }
+                    for entity in press.entities.keys() {
+                        writers.drag_end.send(pointer_event(
+                            input,
+                            *entity,
+                            DragEnd {
+                                button,
+                                distance: location.position - press.start_position,
+                            },
+                        ));
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,70 @@
+//! Finds the entities hovered by each pointer from the hits of the picking backends.
+
+use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::prelude::*;
+use bevy_utils::HashMap;
+
+use crate::{
+    backend::{HitData, PointerHits},
+    pointer::PointerId,
+    Pickable,
+};
+
+/// The entities hovered by each pointer, with where they were hit.
+///
+/// An entity is hovered if it was hit by a backend, is [hoverable](Pickable::is_hoverable),
+/// and isn't behind another entity that [blocks](Pickable::should_block_lower) lower entities.
+/// Each existing pointer has an entry, even if it doesn't hover anything.
+#[derive(Resource, Debug, Default, Clone, Deref, DerefMut)]
+pub struct HoverMap(pub HashMap<PointerId, HashMap<Entity, HitData>>);
+
+/// The [`HoverMap`] of the previous frame, used to find which entities stopped being hovered.
+#[derive(Resource, Debug, Default, Clone, Deref, DerefMut)]
+pub struct PreviousHoverMap(pub HashMap<PointerId, HashMap<Entity, HitData>>);
+
+/// Updates the [`HoverMap`] from the [`PointerHits`] sent by the backends.
+pub fn update_focus(
+    mut hits: EventReader<PointerHits>,
+    pointers: Query<&PointerId>,
+    pickables: Query<&Pickable>,
+    mut hover_map: ResMut<HoverMap>,
+    mut previous_hover_map: ResMut<PreviousHoverMap>,
+    mut sorted_hits: Local<HashMap<PointerId, Vec<(f32, Entity, HitData)>>>,
+) {
+    std::mem::swap(&mut hover_map.0, &mut previous_hover_map.0);
+    hover_map.clear();
+    for pointer in &pointers {
+        hover_map.insert(*pointer, HashMap::new());
+    }
+
+    for hits in sorted_hits.values_mut() {
+        hits.clear();
+    }
+    for event in hits.read() {
+        sorted_hits.entry(event.pointer).or_default().extend(
+            event
+                .picks
+                .iter()
+                .map(|(entity, hit)| (event.order, *entity, hit.clone())),
+        );
+    }
+
+    for (pointer, hits) in sorted_hits.iter_mut() {
+        let Some(hovered) = hover_map.get_mut(pointer) else {
+            continue;
+        };
+        // From front to back: highest order first, then lowest depth first.
+        hits.sort_by(|(a_order, _, a), (b_order, _, b)| {
+            b_order.total_cmp(a_order).then(a.depth.total_cmp(&b.depth))
+        });
+        for (_, entity, hit) in hits.drain(..) {
+            let pickable = pickables.get(entity).copied().unwrap_or_default();
+            if pickable.is_hoverable {
+                hovered.entry(entity).or_insert(hit);
+            }
+            if pickable.should_block_lower {
+                break;
+            }
+        }
+    }
+}
@@ -0,0 +1,144 @@
+//! Turns the mouse and touch inputs into [`PointerInput`]s.
+
+use bevy_ecs::prelude::*;
+use bevy_input::{
+    mouse::{MouseButton, MouseButtonInput},
+    touch::{ForceTouch, TouchInput, TouchPhase},
+    ButtonState,
+};
+use bevy_math::Vec2;
+use bevy_render::camera::NormalizedRenderTarget;
+use bevy_utils::HashMap;
+use bevy_window::{CursorLeft, CursorMoved, WindowRef};
+
+use crate::pointer::{
+    Location, PointerAction, PointerBundle, PointerButton, PointerId, PointerInput,
+};
+
+fn window_location(window: Entity, position: Vec2) -> Option<Location> {
+    Some(Location {
+        target: NormalizedRenderTarget::Window(WindowRef::Entity(window).normalize(None)?),
+        position,
+    })
+}
+
+/// Sends the [`PointerInput`]s of the mouse pointer.
+pub fn mouse_pick_events(
+    mut cursor_moves: EventReader<CursorMoved>,
+    mut cursor_leaves: EventReader<CursorLeft>,
+    mut button_inputs: EventReader<MouseButtonInput>,
+    mut cursor_location: Local<Option<Location>>,
+    mut pointer_inputs: EventWriter<PointerInput>,
+) {
+    for event in cursor_moves.read() {
+        let Some(location) = window_location(event.window, event.position) else {
+            continue;
+        };
+        pointer_inputs.send(PointerInput::new(
+            PointerId::Mouse,
+            location.clone(),
+            PointerAction::Moved {
+                delta: event.delta.unwrap_or_default(),
+            },
+        ));
+        *cursor_location = Some(location);
+    }
+
+    for event in button_inputs.read() {
+        let Some(location) = cursor_location.clone() else {
+            continue;
+        };
+        let button = match event.button {
+            MouseButton::Left => PointerButton::Primary,
+            MouseButton::Right => PointerButton::Secondary,
+            MouseButton::Middle => PointerButton::Middle,
+            _ => continue,
+        };
+        let action = match event.state {
+            ButtonState::Pressed => PointerAction::Pressed(button),
+            ButtonState::Released => PointerAction::Released(button),
+        };
+        pointer_inputs.send(PointerInput::new(PointerId::Mouse, location, action));
+    }
+
+    for event in cursor_leaves.read() {
+        let left_window = |location: &Location| matches!(&location.target, NormalizedRenderTarget::Window(window) if window.entity() == event.window);
+        if let Some(location) = cursor_location.clone().filter(left_window) {
+            *cursor_location = None;
+            pointer_inputs.send(PointerInput::new(
+                PointerId::Mouse,
+                location,
+                PointerAction::Canceled,
+            ));
+        }
+    }
+}
+
+/// Sends the [`PointerInput`]s of the touch pointers, and spawns a pointer for each new touch.
+pub fn touch_pick_events(
+    mut commands: Commands,
+    mut touch_inputs: EventReader<TouchInput>,
+    mut touches: Local<HashMap<u64, (PointerId, Location)>>,
+    mut pointer_inputs: EventWriter<PointerInput>,
+) {
+    for touch in touch_inputs.read() {
+        let Some(location) = window_location(touch.window, touch.position) else {
+            continue;
+        };
+        match touch.phase {
+            TouchPhase::Started => {
+                // Pens are only reported as such with their altitude.
+                let pointer_id = match touch.force {
+                    Some(ForceTouch::Calibrated {
+                        altitude_angle: Some(_),
+                        ..
+                    }) => PointerId::Pen(touch.id),
+                    _ => PointerId::Touch(touch.id),
+                };
+                commands.spawn(PointerBundle::new(pointer_id));
+                pointer_inputs.send(PointerInput::new(
+                    pointer_id,
+                    location.clone(),
+                    PointerAction::Pressed(PointerButton::Primary),
+                ));
+                touches.insert(touch.id, (pointer_id, location));
+            }
+            TouchPhase::Moved => {
+                let Some((pointer_id, last_location)) = touches.get_mut(&touch.id) else {
+                    continue;
+                };
+                if last_location.position == location.position {
+                    continue;
+                }
+                pointer_inputs.send(PointerInput::new(
+                    *pointer_id,
+                    location.clone(),
+                    PointerAction::Moved {
+                        delta: location.position - last_location.position,
+                    },
+                ));
+                *last_location = location;
+            }
+            TouchPhase::Ended => {
+                let Some((pointer_id, _)) = touches.remove(&touch.id) else {
+                    continue;
+                };
+                pointer_inputs.send(PointerInput::new(
+                    pointer_id,
+                    location,
+                    PointerAction::Released(PointerButton::Primary),
+                ));
+            }
+            TouchPhase::Canceled => {
+                let Some((pointer_id, _)) = touches.remove(&touch.id) else {
+                    continue;
+                };
+                pointer_inputs.send(PointerInput::new(
+                    pointer_id,
+                    location,
+                    PointerAction::Canceled,
+                ));
+            }
+        }
+    }
+}
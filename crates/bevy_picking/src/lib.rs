@@ -0,0 +1,242 @@
+//! Provides screen picking functionality for Bevy Engine.
+//!
+//! Picking finds out which entities are under each pointer (the mouse, fingers on a touchscreen,
+//! pens, or custom pointers) and sends events targeting those entities when the pointers move,
+//! press or drag them.
+//!
+//! # Overview
+//!
+//! Picking happens in [`PreUpdate`], in the following [`PickSet`]s:
+//!
+//! 1. [`PickSet::Input`]: raw input events are turned into [`PointerInput`](pointer::PointerInput)
+//!    events, which update the state of the pointer entities.
+//! 2. [`PickSet::Backend`]: picking backends report which entities each pointer is over, for each
+//!    camera, with [`PointerHits`](backend::PointerHits) events. Backends are provided for UI
+//!    nodes and sprites by `bevy_ui` and `bevy_sprite`, and for meshes by [`MeshPickingPlugin`].
+//! 3. [`PickSet::Focus`]: the hits of all backends are sorted to find the entities each pointer
+//!    is hovering, stored in the [`HoverMap`](focus::HoverMap).
+//! 4. [`PickSet::Events`]: [`Pointer`](events::Pointer) events such as `Pointer<Over>`,
+//!    `Pointer<Click>` or `Pointer<Drag>` are sent for the hovered entities.
+//! 5. [`PickSet::Listeners`]: the [`On`](listener::On) listeners of the targeted entities and of
+//!    their ancestors are run, bubbling up the hierarchy.
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_picking::prelude::*;
+//! fn setup(mut commands: Commands) {
+//!     commands.spawn(On::<Pointer<Click>>::run(|event, commands| {
+//!         commands.entity(event.target).despawn();
+//!     }));
+//! }
+//! # bevy_ecs::system::assert_is_system(setup);
+//! ```
+//!
+//! Pointer events are also regular [`Event`](bevy_ecs::event::Event)s, that can be read with an
+//! [`EventReader`](bevy_ecs::event::EventReader).
+
+pub mod backend;
+pub mod events;
+pub mod focus;
+pub mod input;
+pub mod listener;
+mod mesh_picking;
+pub mod pointer;
+
+pub use mesh_picking::*;
+
+/// Most commonly used re-exported types.
+pub mod prelude {
+    #[doc(hidden)]
+    pub use crate::{
+        events::{Click, Down, Drag, DragEnd, DragStart, Move, Out, Over, Pointer, Up},
+        listener::{ListenerInput, On},
+        pointer::{PointerButton, PointerId},
+        MeshPickingPlugin, Pickable, PickingPlugin,
+    };
+}
+
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_ecs::reflect::ReflectComponent;
+use bevy_input::InputSystem;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+
+use events::*;
+use pointer::*;
+
+/// Controls how an entity interacts with picking.
+///
+/// Entities without this component are hoverable, and block the entities behind them.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct Pickable {
+    /// Whether the entities behind this one can be hovered by the same pointer.
+    ///
+    /// UI nodes usually block the entities behind them, while 3d gizmos or overlays may
+    /// let pointers hover the objects they are drawn on top of.
+    pub should_block_lower: bool,
+    /// Whether this entity can be hovered, and thus be the target of [`Pointer`] events.
+    ///
+    /// Entities that are not hoverable may still block the entities behind them.
+    pub is_hoverable: bool,
+}
+
+impl Pickable {
+    /// This entity is ignored by picking: it is not hovered and doesn't block lower entities.
+    pub const IGNORE: Self = Self {
+        should_block_lower: false,
+        is_hoverable: false,
+    };
+}
+
+impl Default for Pickable {
+    fn default() -> Self {
+        Self {
+            should_block_lower: true,
+            is_hoverable: true,
+        }
+    }
+}
+
+/// The steps of picking, which all run in [`PreUpdate`] in this order.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
+pub enum PickSet {
+    /// Turns raw input into [`PointerInput`] events and updates the pointers.
+    Input,
+    /// Runs the picking backends, which send [`PointerHits`](backend::PointerHits) events.
+    Backend,
+    /// Finds the entities hovered by each pointer.
+    Focus,
+    /// Sends the [`Pointer`] events.
+    Events,
+    /// Runs the [`On`](listener::On) listeners of the entities targeted by [`Pointer`] events.
+    Listeners,
+}
+
+/// Adds picking support for the mouse and touch inputs.
+///
+/// Picking backends are added separately, see the [crate documentation](crate).
+#[derive(Default)]
+pub struct PickingPlugin;
+
+impl Plugin for PickingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PointerMap>()
+            .init_resource::<focus::HoverMap>()
+            .init_resource::<focus::PreviousHoverMap>()
+            .add_event::<PointerInput>()
+            .add_event::<backend::PointerHits>()
+            .register_type::<Pickable>()
+            .register_type::<PointerId>()
+            .register_type::<PointerLocation>()
+            .register_type::<PointerPress>()
+            .configure_sets(
+                PreUpdate,
+                (
+                    PickSet::Input.after(InputSystem),
+                    PickSet::Backend,
+                    PickSet::Focus,
+                    PickSet::Events,
+                    PickSet::Listeners,
+                )
+                    .chain(),
+            )
+            .add_systems(Startup, spawn_mouse_pointer)
+            .add_systems(
+                PreUpdate,
+                (
+                    despawn_inactive_touch_pointers,
+                    (input::mouse_pick_events, input::touch_pick_events),
+                    update_pointer_map,
+                    update_pointers,
+                )
+                    .chain()
+                    .in_set(PickSet::Input),
+            )
+            .add_systems(PreUpdate, focus::update_focus.in_set(PickSet::Focus))
+            .add_systems(PreUpdate, send_pointer_events.in_set(PickSet::Events));
+
+        add_pointer_event::<Over>(app);
+        add_pointer_event::<Out>(app);
+        add_pointer_event::<Down>(app);
+        add_pointer_event::<Up>(app);
+        add_pointer_event::<Click>(app);
+        add_pointer_event::<Move>(app);
+        add_pointer_event::<DragStart>(app);
+        add_pointer_event::<Drag>(app);
+        add_pointer_event::<DragEnd>(app);
+    }
+}
+
+/// Registers the `Pointer<E>` event, and runs its listeners.
+fn add_pointer_event<E: Clone + Send + Sync + 'static>(app: &mut App) {
+    app.add_event::<Pointer<E>>().add_systems(
+        PreUpdate,
+        listener::dispatch_to_listeners::<Pointer<E>>.in_set(PickSet::Listeners),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{backend::*, prelude::*, *};
+    use bevy_hierarchy::BuildWorldChildren;
+    use bevy_input::{mouse::MouseButtonInput, touch::TouchInput};
+    use bevy_math::Vec2;
+    use bevy_render::camera::NormalizedRenderTarget;
+    use bevy_window::{CursorLeft, CursorMoved, WindowRef};
+
+    #[derive(Component)]
+    struct Clicked(Entity);
+
+    #[test]
+    fn click_bubbles_to_ancestors() {
+        let mut app = App::new();
+        app.add_event::<CursorMoved>()
+            .add_event::<CursorLeft>()
+            .add_event::<MouseButtonInput>()
+            .add_event::<TouchInput>()
+            .add_plugins(PickingPlugin);
+
+        let window = app.world.spawn_empty().id();
+        let camera = app.world.spawn_empty().id();
+        let parent = app
+            .world
+            .spawn(On::<Pointer<Click>>::run(|event, commands| {
+                commands
+                    .entity(event.listener())
+                    .insert(Clicked(event.target));
+            }))
+            .id();
+        let child = app.world.spawn_empty().set_parent(parent).id();
+        let location = Location {
+            target: NormalizedRenderTarget::Window(
+                WindowRef::Entity(window).normalize(None).unwrap(),
+            ),
+            position: Vec2::new(10.0, 20.0),
+        };
+
+        let frame = |app: &mut App, action| {
+            app.world.send_event(PointerInput::new(
+                PointerId::Mouse,
+                location.clone(),
+                action,
+            ));
+            app.world.send_event(PointerHits::new(
+                PointerId::Mouse,
+                vec![(child, HitData::new(camera, 1.0, None, None))],
+                0.0,
+            ));
+            app.update();
+        };
+
+        frame(&mut app, PointerAction::Moved { delta: Vec2::ZERO });
+        let hover_map = app.world.resource::<focus::HoverMap>();
+        assert!(hover_map[&PointerId::Mouse].contains_key(&child));
+
+        frame(&mut app, PointerAction::Pressed(PointerButton::Primary));
+        assert!(app.world.get::<Clicked>(parent).is_none());
+
+        frame(&mut app, PointerAction::Released(PointerButton::Primary));
+        assert_eq!(app.world.get::<Clicked>(parent).unwrap().0, child);
+    }
+}
@@ -0,0 +1,117 @@
+//! Entity-targeted event listeners, that bubble up the hierarchy.
+//!
+//! An entity with an [`On<E>`] component runs its callback for each event `E` targeting this
+//! entity or one of its descendants, starting from the target and going up through its
+//! ancestors until a listener [stops the propagation](ListenerInput::stop_propagation).
+//! This lets a button react to clicks on its label, or a panel to drags of any of its widgets.
+
+use std::ops::Deref;
+
+use bevy_ecs::prelude::*;
+use bevy_hierarchy::Parent;
+
+/// An [`Event`] targeting an entity, which can be listened to with [`On`].
+pub trait EntityEvent: Event + Clone {
+    /// The entity targeted by the event.
+    fn target(&self) -> Entity;
+
+    /// Whether the event is also delivered to the ancestors of its target.
+    fn can_bubble(&self) -> bool {
+        true
+    }
+}
+
+/// The event given to an [`On`] callback.
+///
+/// Dereferences to the event `E`.
+pub struct ListenerInput<E> {
+    listener: Entity,
+    event: E,
+    propagate: bool,
+}
+
+impl<E> ListenerInput<E> {
+    /// The entity whose listener is running: either the target of the event or one of its
+    /// ancestors.
+    pub fn listener(&self) -> Entity {
+        self.listener
+    }
+
+    /// Prevents the listeners of the next ancestors from running for this event.
+    pub fn stop_propagation(&mut self) {
+        self.propagate = false;
+    }
+}
+
+impl<E> Deref for ListenerInput<E> {
+    type Target = E;
+
+    fn deref(&self) -> &Self::Target {
+        &self.event
+    }
+}
+
+type ListenerCallback<E> = Box<dyn Fn(&mut ListenerInput<E>, &mut Commands) + Send + Sync>;
+
+/// Listens to the events `E` targeting this entity or its descendants.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_picking::prelude::*;
+/// #[derive(Component)]
+/// struct Selected;
+///
+/// fn spawn_button(mut commands: Commands) {
+///     commands.spawn(On::<Pointer<Click>>::run(|event, commands| {
+///         commands.entity(event.listener()).insert(Selected);
+///     }));
+/// }
+/// # bevy_ecs::system::assert_is_system(spawn_button);
+/// ```
+#[derive(Component)]
+pub struct On<E: EntityEvent> {
+    callback: ListenerCallback<E>,
+}
+
+impl<E: EntityEvent> On<E> {
+    /// Creates a listener running `callback` for each event.
+    ///
+    /// The callback can queue [`Commands`] to act on the world.
+    pub fn run(
+        callback: impl Fn(&mut ListenerInput<E>, &mut Commands) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            callback: Box::new(callback),
+        }
+    }
+}
+
+/// Runs the [`On`] listeners of the targets of the events `E` and of their ancestors.
+pub fn dispatch_to_listeners<E: EntityEvent>(
+    mut commands: Commands,
+    mut events: EventReader<E>,
+    listeners: Query<&On<E>>,
+    parents: Query<&Parent>,
+) {
+    for event in events.read() {
+        let mut input = ListenerInput {
+            listener: event.target(),
+            event: event.clone(),
+            propagate: true,
+        };
+        let mut next = Some(event.target());
+        while let Some(entity) = next {
+            if let Ok(on) = listeners.get(entity) {
+                input.listener = entity;
+                (on.callback)(&mut input, &mut commands);
+                if !input.propagate {
+                    break;
+                }
+            }
+            if !event.can_bubble() {
+                break;
+            }
+            next = parents.get(entity).ok().map(Parent::get);
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_math::bounding::RayCast3d;
+use bevy_render::{camera::Camera, mesh::Raycast, view::ViewVisibility};
+use bevy_transform::components::GlobalTransform;
+use bevy_window::PrimaryWindow;
+
+use crate::{
+    backend::{camera_ray, HitData, PointerHits},
+    pointer::{PointerId, PointerLocation},
+    PickSet,
+};
+
+/// Adds a picking backend for meshes and [`RaycastShape`](bevy_render::mesh::RaycastShape)s,
+/// using CPU ray casting.
+///
+/// This isn't added by the [`PickingPlugin`](crate::PickingPlugin), as ray casting against
+/// every mesh of a scene can be expensive: add it if your app needs to pick 3d objects.
+#[derive(Default)]
+pub struct MeshPickingPlugin;
+
+impl Plugin for MeshPickingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PreUpdate, mesh_picking.in_set(PickSet::Backend));
+    }
+}
+
+/// Sends the [`PointerHits`] of the visible meshes under each pointer, for each camera.
+pub fn mesh_picking(
+    pointers: Query<(&PointerId, &PointerLocation)>,
+    cameras: Query<(Entity, &Camera, &GlobalTransform)>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    visibility: Query<&ViewVisibility>,
+    raycast: Raycast,
+    mut output: EventWriter<PointerHits>,
+) {
+    let primary_window = primary_window.iter().next();
+    for (pointer_id, pointer_location) in &pointers {
+        let Some(location) = pointer_location.location() else {
+            continue;
+        };
+        for (camera_entity, camera, camera_transform) in &cameras {
+            let Some(ray) = camera_ray(location, camera, camera_transform, primary_window) else {
+                continue;
+            };
+            // Entities without visibility, such as `RaycastShape` hitboxes, are always pickable.
+            let is_visible = |entity| {
+                visibility
+                    .get(entity)
+                    .map_or(true, |visibility| visibility.get())
+            };
+            let picks: Vec<_> = raycast
+                .cast_ray(&RayCast3d::from_ray(ray, f32::MAX), is_visible)
+                .into_iter()
+                .map(|hit| {
+                    let data = HitData::new(
+                        camera_entity,
+                        hit.distance,
+                        Some(hit.point),
+                        Some(hit.normal),
+                    );
+                    (hit.entity, data)
+                })
+                .collect();
+            if !picks.is_empty() {
+                output.send(PointerHits::new(*pointer_id, picks, camera.order as f32));
+            }
+        }
+    }
+}
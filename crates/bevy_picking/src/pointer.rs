@@ -0,0 +1,270 @@
+//! Pointers, such as the mouse or fingers on a touchscreen, and their input.
+//!
+//! Each pointer is an entity with a [`PointerId`], a [`PointerLocation`] and a [`PointerPress`].
+//! The mouse pointer always exists, while touch pointers are spawned when a finger or a pen
+//! touches the screen, and despawned the frame after it is lifted.
+
+use bevy_ecs::prelude::*;
+use bevy_ecs::reflect::ReflectComponent;
+use bevy_math::Vec2;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::camera::{Camera, NormalizedRenderTarget};
+use bevy_utils::HashMap;
+
+/// Identifies a pointer.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Component, PartialEq)]
+pub enum PointerId {
+    /// The mouse cursor.
+    Mouse,
+    /// A finger on a touchscreen, with the id given by the platform.
+    Touch(u64),
+    /// A pen or stylus on a touchscreen, with the id given by the platform.
+    ///
+    /// Pens are only told apart from fingers on platforms that report their altitude angle.
+    Pen(u64),
+    /// A pointer driven by custom input, such as a gamepad-controlled cursor.
+    Custom(u64),
+}
+
+impl PointerId {
+    /// Returns `true` if this is the mouse pointer.
+    pub fn is_mouse(&self) -> bool {
+        matches!(self, PointerId::Mouse)
+    }
+
+    /// Returns `true` if this is a finger or a pen on a touchscreen.
+    pub fn is_touch(&self) -> bool {
+        matches!(self, PointerId::Touch(_) | PointerId::Pen(_))
+    }
+
+    /// Returns `true` if this is a custom pointer.
+    pub fn is_custom(&self) -> bool {
+        matches!(self, PointerId::Custom(_))
+    }
+
+    /// Returns the touch id of this pointer, if it is a finger or a pen.
+    pub fn get_touch_id(&self) -> Option<u64> {
+        match self {
+            PointerId::Touch(id) | PointerId::Pen(id) => Some(*id),
+            _ => None,
+        }
+    }
+}
+
+/// Maps the [`PointerId`] of each pointer to its entity.
+#[derive(Resource, Debug, Default)]
+pub struct PointerMap {
+    inner: HashMap<PointerId, Entity>,
+}
+
+impl PointerMap {
+    /// Returns the entity of the pointer, if it exists.
+    pub fn get_entity(&self, pointer_id: PointerId) -> Option<Entity> {
+        self.inner.get(&pointer_id).copied()
+    }
+}
+
+/// Updates the [`PointerMap`] with the pointers that were spawned or despawned.
+pub fn update_pointer_map(pointers: Query<(Entity, &PointerId)>, mut map: ResMut<PointerMap>) {
+    map.inner.clear();
+    for (entity, id) in &pointers {
+        map.inner.insert(*id, entity);
+    }
+}
+
+/// A button of a pointer.
+///
+/// Touches only use the [`Primary`](PointerButton::Primary) button.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[reflect(PartialEq)]
+pub enum PointerButton {
+    /// The left mouse button, or a touch.
+    Primary,
+    /// The right mouse button.
+    Secondary,
+    /// The middle mouse button.
+    Middle,
+}
+
+impl PointerButton {
+    /// Iterates over all the buttons.
+    pub fn iter() -> impl Iterator<Item = PointerButton> {
+        [Self::Primary, Self::Secondary, Self::Middle].into_iter()
+    }
+}
+
+/// The buttons of a pointer that are currently pressed.
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct PointerPress {
+    primary: bool,
+    secondary: bool,
+    middle: bool,
+}
+
+impl PointerPress {
+    /// Returns `true` if `button` is pressed.
+    pub fn is_pressed(&self, button: PointerButton) -> bool {
+        match button {
+            PointerButton::Primary => self.primary,
+            PointerButton::Secondary => self.secondary,
+            PointerButton::Middle => self.middle,
+        }
+    }
+
+    /// Returns `true` if any button is pressed.
+    pub fn is_any_pressed(&self) -> bool {
+        self.primary || self.secondary || self.middle
+    }
+
+    fn set(&mut self, button: PointerButton, pressed: bool) {
+        match button {
+            PointerButton::Primary => self.primary = pressed,
+            PointerButton::Secondary => self.secondary = pressed,
+            PointerButton::Middle => self.middle = pressed,
+        }
+    }
+}
+
+/// A position on a render target, such as a window.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct Location {
+    /// The render target the pointer is on.
+    pub target: NormalizedRenderTarget,
+    /// The position of the pointer on the target, in logical pixels from its top-left corner.
+    pub position: Vec2,
+}
+
+impl Location {
+    /// Returns `true` if this location is inside the viewport of `camera`.
+    ///
+    /// `primary_window` is used to resolve cameras that render to the primary window.
+    pub fn is_in_viewport(&self, camera: &Camera, primary_window: Option<Entity>) -> bool {
+        camera.target.normalize(primary_window).as_ref() == Some(&self.target)
+            && camera
+                .logical_viewport_rect()
+                .is_some_and(|viewport| viewport.contains(self.position))
+    }
+}
+
+/// The location of a pointer, if it is on a render target.
+#[derive(Component, Debug, Default, Clone, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct PointerLocation {
+    /// The location of the pointer, or `None` if it isn't on any render target.
+    pub location: Option<Location>,
+}
+
+impl PointerLocation {
+    /// Returns the location of the pointer, if it is on a render target.
+    pub fn location(&self) -> Option<&Location> {
+        self.location.as_ref()
+    }
+}
+
+/// The components of a pointer entity.
+#[derive(Bundle, Debug, Clone)]
+pub struct PointerBundle {
+    /// The id of the pointer.
+    pub id: PointerId,
+    /// The location of the pointer.
+    pub location: PointerLocation,
+    /// The pressed buttons of the pointer.
+    pub press: PointerPress,
+}
+
+impl PointerBundle {
+    /// Creates a pointer with the given id, without location and with no pressed buttons.
+    pub fn new(id: PointerId) -> Self {
+        Self {
+            id,
+            location: PointerLocation::default(),
+            press: PointerPress::default(),
+        }
+    }
+}
+
+/// What a pointer did.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub enum PointerAction {
+    /// A button was pressed.
+    Pressed(PointerButton),
+    /// A button was released.
+    Released(PointerButton),
+    /// The pointer moved.
+    Moved {
+        /// How much the pointer moved, in logical pixels.
+        delta: Vec2,
+    },
+    /// The pointer stopped being tracked, for example because it left the window.
+    Canceled,
+}
+
+/// An input from a pointer, sent during [`PickSet::Input`](crate::PickSet::Input).
+///
+/// The mouse and touch inputs are sent by the [`PickingPlugin`](crate::PickingPlugin).
+/// Custom pointers can be driven by sending this event for a spawned [`PointerBundle`].
+#[derive(Event, Debug, Clone, PartialEq, Reflect)]
+pub struct PointerInput {
+    /// The pointer that had the input.
+    pub pointer_id: PointerId,
+    /// The location of the pointer when the input happened.
+    pub location: Location,
+    /// What the pointer did.
+    pub action: PointerAction,
+}
+
+impl PointerInput {
+    /// Creates a new [`PointerInput`].
+    pub fn new(pointer_id: PointerId, location: Location, action: PointerAction) -> Self {
+        Self {
+            pointer_id,
+            location,
+            action,
+        }
+    }
+}
+
+/// Spawns the mouse pointer.
+pub fn spawn_mouse_pointer(mut commands: Commands) {
+    commands.spawn(PointerBundle::new(PointerId::Mouse));
+}
+
+/// Updates the location and the pressed buttons of the pointers from the [`PointerInput`]s.
+pub fn update_pointers(
+    mut inputs: EventReader<PointerInput>,
+    mut pointers: Query<(&PointerId, &mut PointerLocation, &mut PointerPress)>,
+) {
+    for input in inputs.read() {
+        for (id, mut location, mut press) in &mut pointers {
+            if *id != input.pointer_id {
+                continue;
+            }
+            match input.action {
+                PointerAction::Pressed(button) => press.set(button, true),
+                PointerAction::Released(button) => press.set(button, false),
+                PointerAction::Moved { .. } => {}
+                PointerAction::Canceled => {
+                    *press = PointerPress::default();
+                    location.location = None;
+                    continue;
+                }
+            }
+            location.location = Some(input.location.clone());
+        }
+    }
+}
+
+/// Despawns the touch pointers that were lifted or canceled during the last frame,
+/// after their last events were sent.
+pub fn despawn_inactive_touch_pointers(
+    mut commands: Commands,
+    pointers: Query<(Entity, &PointerId, &PointerPress)>,
+) {
+    for (entity, id, press) in &pointers {
+        if id.is_touch() && !press.is_any_pressed() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
@@ -5,6 +5,7 @@
 //! the derive helper attribute for `Reflect`, which looks like:
 //! `#[reflect(PartialEq, Default, ...)]` and `#[reflect_value(PartialEq, Default, ...)]`.
 
+use crate::custom_attributes::CustomAttributes;
 use crate::derive_data::ReflectTraitToImpl;
 use crate::utility;
 use bevy_macro_utils::fq_std::{FQAny, FQOption};
@@ -14,7 +15,7 @@ use syn::parse::ParseStream;
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 use syn::token::Comma;
-use syn::{Expr, LitBool, Meta, MetaList, Path, WhereClause};
+use syn::{Expr, LitBool, Meta, MetaList, Path, Token, WhereClause};
 
 // The "special" trait idents that are used internally for reflection.
 // Received via attributes like `#[reflect(PartialEq, Hash, ...)]`
@@ -217,6 +218,7 @@ pub(crate) struct ReflectTraits {
     type_path_attrs: TypePathAttrs,
     custom_where: Option<WhereClause>,
     no_field_bounds: bool,
+    custom_attributes: CustomAttributes,
     idents: Vec<Ident>,
 }
 
@@ -228,10 +230,30 @@ impl ReflectTraits {
                 custom_where: Some(meta.parse_args::<WhereClause>()?),
                 ..Self::default()
             }),
-            _ => Self::from_metas(
-                meta.parse_args_with(Punctuated::<Meta, Comma>::parse_terminated)?,
-                trait_,
-            ),
+            _ => {
+                let mut custom_attributes = CustomAttributes::default();
+                let metas = meta.parse_args_with(|input: ParseStream| {
+                    let mut metas = Punctuated::<Meta, Comma>::new();
+                    while !input.is_empty() {
+                        // Handles `#[reflect( @Foo, @Bar(123) )]`
+                        if input.peek(Token![@]) {
+                            custom_attributes.parse_custom_attribute(input)?;
+                        } else {
+                            metas.push(input.parse()?);
+                        }
+
+                        if input.is_empty() {
+                            break;
+                        }
+                        input.parse::<Comma>()?;
+                    }
+                    Ok(metas)
+                })?;
+
+                let mut traits = Self::from_metas(metas, trait_)?;
+                traits.custom_attributes = custom_attributes;
+                Ok(traits)
+            }
         }
     }
 
@@ -435,6 +457,11 @@ impl ReflectTraits {
         self.no_field_bounds
     }
 
+    /// The custom attributes declared with `#[reflect(@...)]` on this type.
+    pub fn custom_attributes(&self) -> &CustomAttributes {
+        &self.custom_attributes
+    }
+
     /// Merges the trait implementations of this [`ReflectTraits`] with another one.
     ///
     /// An error is returned if the two [`ReflectTraits`] have conflicting implementations.
@@ -449,6 +476,8 @@ impl ReflectTraits {
 
         self.no_field_bounds |= other.no_field_bounds;
 
+        self.custom_attributes.merge(other.custom_attributes);
+
         for ident in other.idents {
             add_unique_ident(&mut self.idents, ident)?;
         }
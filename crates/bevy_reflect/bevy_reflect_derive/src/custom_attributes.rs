@@ -0,0 +1,43 @@
+//! Contains code related to custom attributes for reflected types.
+//!
+//! A custom attribute is an arbitrary expression that is attached to a type or field
+//! using the `@` prefix, such as `#[reflect(@Range(0.0..=1.0))]`.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::ParseStream;
+use syn::{Expr, Path, Token};
+
+/// A collection of custom attribute expressions declared on a type or field.
+#[derive(Default, Clone)]
+pub(crate) struct CustomAttributes {
+    attributes: Vec<Expr>,
+}
+
+impl CustomAttributes {
+    /// Parses a single custom attribute of the form `@expr`.
+    pub fn parse_custom_attribute(&mut self, input: ParseStream) -> syn::Result<()> {
+        input.parse::<Token![@]>()?;
+        self.attributes.push(input.parse()?);
+        Ok(())
+    }
+
+    /// Appends the attributes of another collection to this one.
+    pub fn merge(&mut self, other: CustomAttributes) {
+        self.attributes.extend(other.attributes);
+    }
+
+    /// Returns `true` if no custom attributes were declared.
+    pub fn is_empty(&self) -> bool {
+        self.attributes.is_empty()
+    }
+
+    /// Returns an expression constructing the runtime `CustomAttributes` collection.
+    pub fn to_tokens(&self, bevy_reflect_path: &Path) -> TokenStream {
+        let attributes = &self.attributes;
+        quote! {
+            #bevy_reflect_path::attributes::CustomAttributes::default()
+                #(.with_attribute(#attributes))*
+        }
+    }
+}
@@ -3,7 +3,7 @@ use core::fmt;
 use crate::container_attributes::{FromReflectAttrs, ReflectTraits, TypePathAttrs};
 use crate::field_attributes::{parse_field_attrs, ReflectFieldAttr};
 use crate::type_path::parse_path_no_leading_colon;
-use crate::utility::{StringExpr, WhereClauseOptions};
+use crate::utility::{ident_or_index, StringExpr, WhereClauseOptions};
 use quote::{quote, ToTokens};
 use syn::token::Comma;
 
@@ -16,7 +16,7 @@ use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 use syn::{
     parse_str, Data, DeriveInput, Field, Fields, GenericParam, Generics, Ident, LitStr, Meta, Path,
-    PathSegment, Type, TypeParam, Variant,
+    PathArguments, PathSegment, Type, TypeParam, TypePath, Variant,
 };
 
 pub(crate) enum ReflectDerive<'a> {
@@ -47,6 +47,8 @@ pub(crate) struct ReflectMeta<'a> {
     type_path: ReflectTypePath<'a>,
     /// A cached instance of the path to the `bevy_reflect` crate.
     bevy_reflect_path: Path,
+    /// The remote type this type wraps, if generated by `#[reflect_remote]`.
+    remote_ty: Option<&'a TypePath>,
     /// The documentation for this type, if any
     #[cfg(feature = "documentation")]
     docs: crate::documentation::Documentation,
@@ -117,7 +119,6 @@ pub(crate) struct EnumVariant<'a> {
     /// The fields within this variant.
     pub fields: EnumVariantFields<'a>,
     /// The reflection-based attributes on the variant.
-    #[allow(dead_code)]
     pub attrs: ReflectFieldAttr,
     /// The index of this variant within the enum.
     #[allow(dead_code)]
@@ -147,6 +148,7 @@ enum ReflectMode {
 pub(crate) enum ReflectImplSource {
     ImplRemoteType,
     DeriveLocalType,
+    RemoteReflect,
 }
 
 /// Which trait the macro explicitly implements.
@@ -172,7 +174,8 @@ impl fmt::Display for ReflectProvenance {
             (S::DeriveLocalType, T::Reflect) => "`#[derive(Reflect)]`",
             (S::DeriveLocalType, T::FromReflect) => "`#[derive(FromReflect)]`",
             (S::DeriveLocalType, T::TypePath) => "`#[derive(TypePath)]`",
-            (S::ImplRemoteType, T::FromReflect | T::TypePath) => unreachable!(),
+            (S::RemoteReflect, T::Reflect) => "`#[reflect_remote]`",
+            (S::ImplRemoteType | S::RemoteReflect, T::FromReflect | T::TypePath) => unreachable!(),
         };
         f.write_str(str)
     }
@@ -354,6 +357,18 @@ impl<'a> ReflectDerive<'a> {
         }
     }
 
+    /// Marks this type as a wrapper around the given remote type.
+    pub fn set_remote(&mut self, remote_ty: Option<&'a TypePath>) {
+        let meta = match self {
+            ReflectDerive::Struct(data)
+            | ReflectDerive::TupleStruct(data)
+            | ReflectDerive::UnitStruct(data) => &mut data.meta,
+            ReflectDerive::Enum(data) => &mut data.meta,
+            ReflectDerive::Value(meta) => meta,
+        };
+        meta.remote_ty = remote_ty;
+    }
+
     fn collect_struct_fields(fields: &'a Fields) -> Result<Vec<StructField<'a>>, syn::Error> {
         let mut active_index = 0;
         let sifter: utility::ResultSifter<StructField<'a>> = fields
@@ -397,6 +412,14 @@ impl<'a> ReflectDerive<'a> {
             .map(|(index, variant)| -> Result<EnumVariant, syn::Error> {
                 let fields = Self::collect_struct_fields(&variant.fields)?;
 
+                if let Some(field) = fields.iter().find(|field| field.attrs.remote.is_some()) {
+                    return Err(syn::Error::new(
+                        field.data.span(),
+                        "`#[reflect(remote = ...)]` is not supported on enum variant fields",
+                    ));
+                }
+
+                let attrs = parse_field_attrs(&variant.attrs)?;
                 let fields = match variant.fields {
                     Fields::Named(..) => EnumVariantFields::Named(fields),
                     Fields::Unnamed(..) => EnumVariantFields::Unnamed(fields),
@@ -404,7 +427,7 @@ impl<'a> ReflectDerive<'a> {
                 };
                 Ok(EnumVariant {
                     fields,
-                    attrs: parse_field_attrs(&variant.attrs)?,
+                    attrs,
                     data: variant,
                     index,
                     #[cfg(feature = "documentation")]
@@ -426,6 +449,7 @@ impl<'a> ReflectMeta<'a> {
             traits,
             type_path,
             bevy_reflect_path: utility::get_bevy_reflect_path(),
+            remote_ty: None,
             #[cfg(feature = "documentation")]
             docs: Default::default(),
         }
@@ -463,12 +487,28 @@ impl<'a> ReflectMeta<'a> {
         &self.bevy_reflect_path
     }
 
+    /// The remote type this type wraps, if any.
+    pub fn remote_ty(&self) -> Option<&'a TypePath> {
+        self.remote_ty
+    }
+
+    /// The path to the remote type this type wraps without its generic arguments, if any.
+    ///
+    /// Generic arguments cannot appear in struct expressions or patterns, so they are left to inference.
+    pub fn remote_path(&self) -> Option<Path> {
+        let mut path = self.remote_ty?.path.clone();
+        if let Some(segment) = path.segments.last_mut() {
+            segment.arguments = PathArguments::None;
+        }
+        Some(path)
+    }
+
     /// Returns the `GetTypeRegistration` impl as a `TokenStream`.
     pub fn get_type_registration(
         &self,
         where_clause_options: &WhereClauseOptions,
     ) -> proc_macro2::TokenStream {
-        crate::registration::impl_get_type_registration(self, where_clause_options, None)
+        crate::registration::impl_get_type_registration(self, where_clause_options, None, &[], &[])
     }
 
     /// The collection of docstrings for this type, if any.
@@ -500,13 +540,15 @@ impl<'a> ReflectStruct<'a> {
             self.meta(),
             where_clause_options,
             self.serialization_data(),
+            &self.fields,
+            &[],
         )
     }
 
     /// Get a collection of types which are exposed to the reflection API
     pub fn active_types(&self) -> Vec<Type> {
         self.active_fields()
-            .map(|field| field.data.ty.clone())
+            .map(|field| field.reflected_type().clone())
             .collect()
     }
 
@@ -533,6 +575,45 @@ impl<'a> ReflectStruct<'a> {
     pub fn where_clause_options(&self) -> WhereClauseOptions {
         WhereClauseOptions::new_with_fields(self.meta(), self.active_types().into_boxed_slice())
     }
+
+    /// Returns an expression that borrows the given field from `self` as a reflected value.
+    ///
+    /// Fields of a `#[reflect_remote]` wrapper are accessed through the wrapped remote value,
+    /// and fields marked with `#[reflect(remote = ...)]` are borrowed as their wrapper type.
+    pub fn access_for_field(
+        &self,
+        field: &StructField<'a>,
+        is_mut: bool,
+    ) -> proc_macro2::TokenStream {
+        let bevy_reflect_path = self.meta.bevy_reflect_path();
+        let member = ident_or_index(field.data.ident.as_ref(), field.declaration_index);
+        let this = if self.meta.remote_ty().is_some() {
+            quote!(self.0)
+        } else {
+            quote!(self)
+        };
+
+        match (&field.attrs.remote, is_mut) {
+            (Some(wrapper), false) => quote! {
+                <#wrapper as #bevy_reflect_path::ReflectRemote>::as_wrapper(&#this.#member)
+            },
+            (Some(wrapper), true) => quote! {
+                <#wrapper as #bevy_reflect_path::ReflectRemote>::as_wrapper_mut(&mut #this.#member)
+            },
+            (None, false) => quote!(&#this.#member),
+            (None, true) => quote!(&mut #this.#member),
+        }
+    }
+}
+
+impl<'a> StructField<'a> {
+    /// The type of this field as seen by the reflection API.
+    ///
+    /// This is the remote wrapper type if one was given with `#[reflect(remote = ...)]`,
+    /// or the declared type of the field otherwise.
+    pub fn reflected_type(&self) -> &Type {
+        self.attrs.remote.as_ref().unwrap_or(&self.data.ty)
+    }
 }
 
 impl<'a> ReflectEnum<'a> {
@@ -542,22 +623,57 @@ impl<'a> ReflectEnum<'a> {
     }
 
     /// Returns the given ident as a qualified unit variant of this enum.
+    ///
+    /// For a `#[reflect_remote]` wrapper, this is the variant of the remote enum.
     pub fn get_unit(&self, variant: &Ident) -> proc_macro2::TokenStream {
+        if let Some(remote_path) = self.meta.remote_path() {
+            return quote! {
+                #remote_path::#variant
+            };
+        }
+
         let name = self.meta.type_path();
         quote! {
             #name::#variant
         }
     }
 
+    /// Returns the expression matched against the variants of this enum.
+    ///
+    /// For a `#[reflect_remote]` wrapper, this borrows the wrapped remote value.
+    pub fn match_target(&self, is_mut: bool) -> proc_macro2::TokenStream {
+        match (self.meta.remote_ty().is_some(), is_mut) {
+            (true, false) => quote!(&self.0),
+            (true, true) => quote!(&mut self.0),
+            (false, _) => quote!(self),
+        }
+    }
+
     /// The complete set of variants in this enum.
     pub fn variants(&self) -> &[EnumVariant<'a>] {
         &self.variants
     }
 
+    /// Returns the `GetTypeRegistration` impl as a `TokenStream`.
+    ///
+    /// Returns a specific implementation for enums and this method should be preferred over the generic [`get_type_registration`](ReflectMeta) method
+    pub fn get_type_registration(
+        &self,
+        where_clause_options: &WhereClauseOptions,
+    ) -> proc_macro2::TokenStream {
+        crate::registration::impl_get_type_registration(
+            self.meta(),
+            where_clause_options,
+            None,
+            &[],
+            &self.variants,
+        )
+    }
+
     /// Get a collection of types which are exposed to the reflection API
     pub fn active_types(&self) -> Vec<Type> {
         self.active_fields()
-            .map(|field| field.data.ty.clone())
+            .map(|field| field.reflected_type().clone())
             .collect()
     }
 
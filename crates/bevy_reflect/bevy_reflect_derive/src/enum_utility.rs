@@ -99,8 +99,14 @@ pub(crate) fn get_variant_constructors(
             };
             quote! { #field_ident : #field_value }
         });
-        variant_constructors.push(quote! {
+        let variant_constructor = quote! {
             #variant_constructor { #( #constructor_fields ),* }
+        };
+        // A `#[reflect_remote]` wrapper is constructed around the remote variant
+        variant_constructors.push(if reflect_enum.meta().remote_ty().is_some() {
+            quote!(Self(#variant_constructor))
+        } else {
+            variant_constructor
         });
         variant_names.push(name);
     }
@@ -4,15 +4,18 @@
 //! as opposed to an entire struct or enum. An example of such an attribute is
 //! the derive helper attribute for `Reflect`, which looks like: `#[reflect(ignore)]`.
 
+use crate::custom_attributes::CustomAttributes;
 use crate::REFLECT_ATTRIBUTE_NAME;
-use syn::meta::ParseNestedMeta;
-use syn::{Attribute, LitStr, Token};
+use syn::parse::ParseStream;
+use syn::{Attribute, LitStr, Path, Token, Type};
 
 pub(crate) static IGNORE_SERIALIZATION_ATTR: &str = "skip_serializing";
 pub(crate) static IGNORE_ALL_ATTR: &str = "ignore";
 
 pub(crate) static DEFAULT_ATTR: &str = "default";
 
+pub(crate) static REMOTE_ATTR: &str = "remote";
+
 /// Stores data about if the field should be visible via the Reflect and serialization interfaces
 ///
 /// Note the relationship between serialization and reflection is such that a member must be reflected in order to be serialized.
@@ -51,6 +54,12 @@ pub(crate) struct ReflectFieldAttr {
     pub ignore: ReflectIgnoreBehavior,
     /// Sets the default behavior of this field.
     pub default: DefaultBehavior,
+    /// The remote wrapper type used to reflect this field, if any.
+    ///
+    /// This is set with `#[reflect(remote = path::to::Wrapper)]`.
+    pub remote: Option<Type>,
+    /// Custom attributes declared with `#[reflect(@...)]`.
+    pub custom_attributes: CustomAttributes,
 }
 
 /// Controls how the default value is determined for a field.
@@ -77,7 +86,23 @@ pub(crate) fn parse_field_attrs(attrs: &[Attribute]) -> Result<ReflectFieldAttr,
         .iter()
         .filter(|a| a.path().is_ident(REFLECT_ATTRIBUTE_NAME));
     for attr in attrs {
-        let result = attr.parse_nested_meta(|meta| parse_meta(&mut args, meta));
+        let result = attr.parse_args_with(|input: ParseStream| {
+            while !input.is_empty() {
+                // Handles `#[reflect( @Foo, @Bar(123) )]`
+                if input.peek(Token![@]) {
+                    args.custom_attributes.parse_custom_attribute(input)?;
+                } else {
+                    let path = input.call(Path::parse_mod_style)?;
+                    parse_meta(&mut args, &path, input)?;
+                }
+
+                if input.is_empty() {
+                    break;
+                }
+                input.parse::<Token![,]>()?;
+            }
+            Ok(())
+        });
         if let Err(err) = result {
             if let Some(ref mut error) = errors {
                 error.combine(err);
@@ -94,53 +119,89 @@ pub(crate) fn parse_field_attrs(attrs: &[Attribute]) -> Result<ReflectFieldAttr,
     }
 }
 
-fn parse_meta(args: &mut ReflectFieldAttr, meta: ParseNestedMeta) -> Result<(), syn::Error> {
-    if meta.path.is_ident(DEFAULT_ATTR) {
+fn parse_meta(
+    args: &mut ReflectFieldAttr,
+    path: &Path,
+    input: ParseStream,
+) -> Result<(), syn::Error> {
+    if path.is_ident(DEFAULT_ATTR) {
         // Allow:
         // - `#[reflect(default)]`
         // - `#[reflect(default = "path::to::func")]`
         if !matches!(args.default, DefaultBehavior::Required) {
-            return Err(meta.error(format!("only one of [{:?}] is allowed", [DEFAULT_ATTR])));
+            return Err(syn::Error::new_spanned(
+                path,
+                format!("only one of [{:?}] is allowed", [DEFAULT_ATTR]),
+            ));
         }
 
-        if meta.input.peek(Token![=]) {
-            let lit = meta.value()?.parse::<LitStr>()?;
+        if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            let lit = input.parse::<LitStr>()?;
             args.default = DefaultBehavior::Func(lit.parse()?);
         } else {
             args.default = DefaultBehavior::Default;
         }
 
         Ok(())
-    } else if meta.path.is_ident(IGNORE_ALL_ATTR) {
+    } else if path.is_ident(IGNORE_ALL_ATTR) {
         // Allow:
         // - `#[reflect(ignore)]`
         if args.ignore != ReflectIgnoreBehavior::None {
-            return Err(meta.error(format!(
-                "only one of [{:?}] is allowed",
-                [IGNORE_ALL_ATTR, IGNORE_SERIALIZATION_ATTR]
-            )));
+            return Err(syn::Error::new_spanned(
+                path,
+                format!(
+                    "only one of [{:?}] is allowed",
+                    [IGNORE_ALL_ATTR, IGNORE_SERIALIZATION_ATTR]
+                ),
+            ));
         }
 
         args.ignore = ReflectIgnoreBehavior::IgnoreAlways;
 
         Ok(())
-    } else if meta.path.is_ident(IGNORE_SERIALIZATION_ATTR) {
+    } else if path.is_ident(IGNORE_SERIALIZATION_ATTR) {
         // Allow:
         // - `#[reflect(skip_serializing)]`
         if args.ignore != ReflectIgnoreBehavior::None {
-            return Err(meta.error(format!(
-                "only one of [{:?}] is allowed",
-                [IGNORE_ALL_ATTR, IGNORE_SERIALIZATION_ATTR]
-            )));
+            return Err(syn::Error::new_spanned(
+                path,
+                format!(
+                    "only one of [{:?}] is allowed",
+                    [IGNORE_ALL_ATTR, IGNORE_SERIALIZATION_ATTR]
+                ),
+            ));
         }
 
         args.ignore = ReflectIgnoreBehavior::IgnoreSerialization;
 
+        Ok(())
+    } else if path.is_ident(REMOTE_ATTR) {
+        // Allow:
+        // - `#[reflect(remote = path::to::Wrapper)]`
+        if args.remote.is_some() {
+            return Err(syn::Error::new_spanned(
+                path,
+                format!("only one of [{:?}] is allowed", [REMOTE_ATTR]),
+            ));
+        }
+
+        input.parse::<Token![=]>()?;
+        args.remote = Some(input.parse::<Type>()?);
+
         Ok(())
     } else {
-        Err(meta.error(format!(
-            "unknown attribute, expected {:?}",
-            [DEFAULT_ATTR, IGNORE_ALL_ATTR, IGNORE_SERIALIZATION_ATTR]
-        )))
+        Err(syn::Error::new_spanned(
+            path,
+            format!(
+                "unknown attribute, expected {:?}",
+                [
+                    DEFAULT_ATTR,
+                    IGNORE_ALL_ATTR,
+                    IGNORE_SERIALIZATION_ATTR,
+                    REMOTE_ATTR
+                ]
+            ),
+        ))
     }
 }
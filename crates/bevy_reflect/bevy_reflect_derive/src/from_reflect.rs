@@ -98,14 +98,22 @@ fn impl_struct_internal(
     let MemberValuePair(active_members, active_values) =
         get_active_fields(reflect_struct, &ref_struct, &ref_struct_type, is_tuple);
 
+    let remote_ty = reflect_struct.meta().remote_ty();
+
     let is_defaultable = reflect_struct.meta().traits().contains(REFLECT_DEFAULT);
     let constructor = if is_defaultable {
+        let this = if remote_ty.is_some() {
+            quote!(__this.0)
+        } else {
+            quote!(__this)
+        };
+
         quote!(
             let mut __this: Self = #FQDefault::default();
             #(
                 if let #fqoption::Some(__field) = #active_values() {
                     // Iff field exists -> use its value
-                    __this.#active_members = __field;
+                    #this.#active_members = __field;
                 }
             )*
             #FQOption::Some(__this)
@@ -113,14 +121,17 @@ fn impl_struct_internal(
     } else {
         let MemberValuePair(ignored_members, ignored_values) = get_ignored_fields(reflect_struct);
 
-        quote!(
-            #FQOption::Some(
-                Self {
-                    #(#active_members: #active_values()?,)*
-                    #(#ignored_members: #ignored_values,)*
-                }
-            )
-        )
+        let fields = quote! {
+            {
+                #(#active_members: #active_values()?,)*
+                #(#ignored_members: #ignored_values,)*
+            }
+        };
+
+        match reflect_struct.meta().remote_path() {
+            Some(remote_path) => quote!(#FQOption::Some(Self(#remote_path #fields))),
+            None => quote!(#FQOption::Some(Self #fields)),
+        }
     };
 
     let (impl_generics, ty_generics, where_clause) = reflect_struct
@@ -191,17 +202,22 @@ fn get_active_fields(
                     field.reflection_index.expect("field should be active"),
                     is_tuple,
                 );
-                let ty = field.data.ty.clone();
+                let ty = field.reflected_type();
 
                 let get_field = quote! {
                     #bevy_reflect_path::#struct_type::field(#dyn_struct_name, #accessor)
                 };
 
+                // Fields reflected through a remote wrapper are unwrapped back into their declared type
+                let into_remote = field.attrs.remote.as_ref().map(|wrapper| {
+                    quote!(.map(<#wrapper as #bevy_reflect_path::ReflectRemote>::into_remote))
+                });
+
                 let value = match &field.attrs.default {
                     DefaultBehavior::Func(path) => quote! {
                         (||
                             if let #FQOption::Some(field) = #get_field {
-                                <#ty as #bevy_reflect_path::FromReflect>::from_reflect(field)
+                                <#ty as #bevy_reflect_path::FromReflect>::from_reflect(field)#into_remote
                             } else {
                                 #FQOption::Some(#path())
                             }
@@ -210,14 +226,14 @@ fn get_active_fields(
                     DefaultBehavior::Default => quote! {
                         (||
                             if let #FQOption::Some(field) = #get_field {
-                                <#ty as #bevy_reflect_path::FromReflect>::from_reflect(field)
+                                <#ty as #bevy_reflect_path::FromReflect>::from_reflect(field)#into_remote
                             } else {
                                 #FQOption::Some(#FQDefault::default())
                             }
                         )
                     },
                     DefaultBehavior::Required => quote! {
-                        (|| <#ty as #bevy_reflect_path::FromReflect>::from_reflect(#get_field?))
+                        (|| <#ty as #bevy_reflect_path::FromReflect>::from_reflect(#get_field?)#into_remote)
                     },
                 };
 
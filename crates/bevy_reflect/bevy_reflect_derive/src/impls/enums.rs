@@ -84,15 +84,16 @@ pub(crate) fn impl_enum(reflect_enum: &ReflectEnum) -> proc_macro2::TokenStream
 
     let type_path_impl = impl_type_path(reflect_enum.meta());
 
-    let get_type_registration_impl = reflect_enum
-        .meta()
-        .get_type_registration(&where_clause_options);
+    let get_type_registration_impl = reflect_enum.get_type_registration(&where_clause_options);
 
     let (impl_generics, ty_generics, where_clause) =
         reflect_enum.meta().type_path().generics().split_for_impl();
 
     let where_reflect_clause = where_clause_options.extend_where_clause(where_clause);
 
+    let match_ref = reflect_enum.match_target(false);
+    let match_mut = reflect_enum.match_target(true);
+
     quote! {
         #get_type_registration_impl
 
@@ -102,42 +103,42 @@ pub(crate) fn impl_enum(reflect_enum: &ReflectEnum) -> proc_macro2::TokenStream
 
         impl #impl_generics #bevy_reflect_path::Enum for #enum_path #ty_generics #where_reflect_clause {
             fn field(&self, #ref_name: &str) -> #FQOption<&dyn #bevy_reflect_path::Reflect> {
-                 match self {
+                 match #match_ref {
                     #(#enum_field,)*
                     _ => #FQOption::None,
                 }
             }
 
             fn field_at(&self, #ref_index: usize) -> #FQOption<&dyn #bevy_reflect_path::Reflect> {
-                match self {
+                match #match_ref {
                     #(#enum_field_at,)*
                     _ => #FQOption::None,
                 }
             }
 
             fn field_mut(&mut self, #ref_name: &str) -> #FQOption<&mut dyn #bevy_reflect_path::Reflect> {
-                 match self {
+                 match #match_mut {
                     #(#enum_field,)*
                     _ => #FQOption::None,
                 }
             }
 
             fn field_at_mut(&mut self, #ref_index: usize) -> #FQOption<&mut dyn #bevy_reflect_path::Reflect> {
-                match self {
+                match #match_mut {
                     #(#enum_field_at,)*
                     _ => #FQOption::None,
                 }
             }
 
             fn index_of(&self, #ref_name: &str) -> #FQOption<usize> {
-                 match self {
+                 match #match_ref {
                     #(#enum_index_of,)*
                     _ => #FQOption::None,
                 }
             }
 
             fn name_at(&self, #ref_index: usize) -> #FQOption<&str> {
-                 match self {
+                 match #match_ref {
                     #(#enum_name_at,)*
                     _ => #FQOption::None,
                 }
@@ -149,7 +150,7 @@ pub(crate) fn impl_enum(reflect_enum: &ReflectEnum) -> proc_macro2::TokenStream
 
             #[inline]
             fn field_len(&self) -> usize {
-                 match self {
+                 match #match_ref {
                     #(#enum_field_len,)*
                     _ => 0,
                 }
@@ -157,7 +158,7 @@ pub(crate) fn impl_enum(reflect_enum: &ReflectEnum) -> proc_macro2::TokenStream
 
             #[inline]
             fn variant_name(&self) -> &str {
-                 match self {
+                 match #match_ref {
                     #(#enum_variant_name,)*
                     _ => unreachable!(),
                 }
@@ -165,7 +166,7 @@ pub(crate) fn impl_enum(reflect_enum: &ReflectEnum) -> proc_macro2::TokenStream
 
             #[inline]
             fn variant_index(&self) -> usize {
-                 match self {
+                 match #match_ref {
                     #(#enum_variant_index,)*
                     _ => unreachable!(),
                 }
@@ -173,7 +174,7 @@ pub(crate) fn impl_enum(reflect_enum: &ReflectEnum) -> proc_macro2::TokenStream
 
             #[inline]
             fn variant_type(&self) -> #bevy_reflect_path::VariantType {
-                 match self {
+                 match #match_ref {
                     #(#enum_variant_type,)*
                     _ => unreachable!(),
                 }
@@ -5,16 +5,18 @@
 //! such as `Struct`, `GetTypeRegistration`, and more— all with a single derive!
 //!
 //! Some other noteworthy exports include the derive macros for [`FromReflect`] and
-//! [`TypePath`], as well as the [`reflect_trait`] attribute macro.
+//! [`TypePath`], as well as the [`reflect_trait`] and [`reflect_remote`] attribute macros.
 //!
 //! [`Reflect`]: crate::derive_reflect
 //! [`FromReflect`]: crate::derive_from_reflect
 //! [`TypePath`]: crate::derive_type_path
 //! [`reflect_trait`]: macro@reflect_trait
+//! [`reflect_remote`]: macro@reflect_remote
 
 extern crate proc_macro;
 
 mod container_attributes;
+mod custom_attributes;
 mod derive_data;
 #[cfg(feature = "documentation")]
 mod documentation;
@@ -24,6 +26,7 @@ mod from_reflect;
 mod impls;
 mod reflect_value;
 mod registration;
+mod remote;
 mod serialization;
 mod trait_reflection;
 mod type_path;
@@ -271,6 +274,14 @@ fn match_reflect_impls(ast: DeriveInput, source: ReflectImplSource) -> TokenStre
 /// // {/* ... */}
 /// ```
 ///
+/// ## `#[reflect(@expr)]`
+///
+/// Any expression prefixed with `@` is evaluated and stored as a custom attribute of the type.
+/// The value must implement `Reflect`, and at most one value of each type is kept.
+///
+/// Custom attributes are collected into the `ReflectAttributes` type data,
+/// which is only registered if the type or one of its fields or variants declares at least one.
+///
 /// # Field Attributes
 ///
 /// Along with the container attributes, this macro comes with some attributes that may be applied
@@ -294,6 +305,22 @@ fn match_reflect_impls(ast: DeriveInput, source: ReflectImplSource) -> TokenStre
 /// What this does is register the `SerializationData` type within the `GetTypeRegistration` implementation,
 /// which will be used by the reflection serializers to determine whether or not the field is serializable.
 ///
+/// ## `#[reflect(remote = path::to::Wrapper)]`
+///
+/// This attribute reflects a field whose type does not implement `Reflect`
+/// through a wrapper generated by the [`#[reflect_remote]`](macro@reflect_remote) attribute.
+///
+/// The field keeps its declared type, but is exposed to the reflection API as the wrapper type.
+/// This is not yet supported on the fields of enum variants.
+///
+/// ## `#[reflect(@expr)]`
+///
+/// Like the container attribute of the same form, this stores the given value
+/// as a custom attribute of the field within the `ReflectAttributes` type data.
+/// This is commonly used to provide hints to inspectors, such as `#[reflect(@Range(0.0..=1.0))]`.
+/// It may also be applied to enum variants and their fields,
+/// whose attributes are stored per variant.
+///
 /// [`reflect_trait`]: macro@reflect_trait
 #[proc_macro_derive(Reflect, attributes(reflect, reflect_value, type_path, type_name))]
 pub fn derive_reflect(input: TokenStream) -> TokenStream {
@@ -449,6 +476,50 @@ pub fn reflect_trait(args: TokenStream, input: TokenStream) -> TokenStream {
     trait_reflection::reflect_trait(&args, input)
 }
 
+/// Generates a wrapper type that can be used to reflect a type defined in another crate.
+///
+/// Rust's orphan rule prevents implementing `Reflect` for types from other crates,
+/// which makes it impossible to reflect fields of those types without changing them.
+/// This attribute works around that by taking a mirror of the remote type's definition
+/// and generating a `#[repr(transparent)]` wrapper around the remote type,
+/// along with `Reflect`, `FromReflect`, `GetTypeRegistration`, and `ReflectRemote` implementations
+/// that access the remote type's fields directly.
+///
+/// The mirror must declare every field of the remote type with the same name and type,
+/// and all of those fields must be visible from the calling module.
+/// All attributes supported by [`#[derive(Reflect)]`](Reflect) may be used on the mirror.
+/// For enums, the mirror must declare every variant of the remote type along with its fields.
+///
+/// Fields of other types can then use the wrapper with the `#[reflect(remote = Wrapper)]`
+/// field attribute, which reflects the field as the wrapper while keeping its declared type.
+///
+/// # Example
+///
+/// ```ignore (bevy_reflect is not accessible from this crate)
+/// // In `external_crate`:
+/// pub struct Timer {
+///     pub elapsed: f32,
+///     pub duration: f32,
+/// }
+///
+/// // In the local crate:
+/// #[reflect_remote(external_crate::Timer)]
+/// struct TimerWrapper {
+///     elapsed: f32,
+///     duration: f32,
+/// }
+///
+/// #[derive(Reflect)]
+/// struct Cooldown {
+///     #[reflect(remote = TimerWrapper)]
+///     timer: external_crate::Timer,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn reflect_remote(args: TokenStream, input: TokenStream) -> TokenStream {
+    remote::reflect_remote(args, input)
+}
+
 /// A macro used to generate reflection trait implementations for the given type.
 ///
 /// This is functionally the same as [deriving `Reflect`] using the `#[reflect_value]` container attribute.
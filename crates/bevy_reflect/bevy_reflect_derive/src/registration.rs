@@ -1,6 +1,6 @@
 //! Contains code related specifically to Bevy's type registration.
 
-use crate::derive_data::ReflectMeta;
+use crate::derive_data::{EnumVariant, ReflectMeta, StructField};
 use crate::serialization::SerializationDataDef;
 use crate::utility::WhereClauseOptions;
 use quote::quote;
@@ -11,6 +11,8 @@ pub(crate) fn impl_get_type_registration(
     meta: &ReflectMeta,
     where_clause_options: &WhereClauseOptions,
     serialization_data: Option<&SerializationDataDef>,
+    fields: &[StructField],
+    variants: &[EnumVariant],
 ) -> proc_macro2::TokenStream {
     let type_path = meta.type_path();
     let bevy_reflect_path = meta.bevy_reflect_path();
@@ -33,6 +35,8 @@ pub(crate) fn impl_get_type_registration(
         }
     });
 
+    let attributes_data = impl_attributes_data(meta, fields, variants);
+
     quote! {
         #[allow(unused_mut)]
         impl #impl_generics #bevy_reflect_path::GetTypeRegistration for #type_path #ty_generics #where_reflect_clause {
@@ -41,9 +45,81 @@ pub(crate) fn impl_get_type_registration(
                 registration.insert::<#bevy_reflect_path::ReflectFromPtr>(#bevy_reflect_path::FromType::<Self>::from_type());
                 #from_reflect_data
                 #serialization_data
+                #attributes_data
                 #(registration.insert::<#registration_data>(#bevy_reflect_path::FromType::<Self>::from_type());)*
                 registration
             }
         }
     }
 }
+
+/// Creates the registration of the `ReflectAttributes` type data,
+/// or `None` if neither the type nor any of its active fields or variants declare custom attributes.
+fn impl_attributes_data(
+    meta: &ReflectMeta,
+    fields: &[StructField],
+    variants: &[EnumVariant],
+) -> Option<proc_macro2::TokenStream> {
+    let container = meta.traits().custom_attributes();
+
+    if container.is_empty()
+        && !has_field_attributes(fields)
+        && variants.iter().all(|variant| {
+            variant.attrs.custom_attributes.is_empty() && !has_field_attributes(variant.fields())
+        })
+    {
+        return None;
+    }
+
+    let bevy_reflect_path = meta.bevy_reflect_path();
+    let container = container.to_tokens(bevy_reflect_path);
+    let fields = impl_field_attributes(bevy_reflect_path, fields);
+    let variants = variants.iter().map(|variant| {
+        let name = variant.data.ident.to_string();
+        let attributes = variant.attrs.custom_attributes.to_tokens(bevy_reflect_path);
+        let fields = impl_field_attributes(bevy_reflect_path, variant.fields());
+        quote! {
+            .with_variant(
+                #name,
+                #bevy_reflect_path::attributes::VariantAttributes::new(#attributes)
+                    #(#fields)*
+            )
+        }
+    });
+
+    Some(quote! {
+        registration.insert::<#bevy_reflect_path::attributes::ReflectAttributes>(
+            #bevy_reflect_path::attributes::ReflectAttributes::new(#container)
+                #(#fields)*
+                #(#variants)*
+        );
+    })
+}
+
+/// Returns `true` if any of the active fields declare custom attributes.
+fn has_field_attributes(fields: &[StructField]) -> bool {
+    fields
+        .iter()
+        .any(|field| field.attrs.ignore.is_active() && !field.attrs.custom_attributes.is_empty())
+}
+
+/// Creates the builder calls adding the custom attributes of each active field.
+fn impl_field_attributes(
+    bevy_reflect_path: &syn::Path,
+    fields: &[StructField],
+) -> Vec<proc_macro2::TokenStream> {
+    fields
+        .iter()
+        .filter(|field| field.attrs.ignore.is_active())
+        .map(|field| {
+            let attributes = field.attrs.custom_attributes.to_tokens(bevy_reflect_path);
+            match &field.data.ident {
+                Some(ident) => {
+                    let name = ident.to_string();
+                    quote!(.with_named_field(#name, #attributes))
+                }
+                None => quote!(.with_unnamed_field(#attributes)),
+            }
+        })
+        .collect()
+}
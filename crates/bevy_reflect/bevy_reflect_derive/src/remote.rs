@@ -0,0 +1,181 @@
+use crate::derive_data::{ReflectImplSource, ReflectProvenance, ReflectTraitToImpl};
+use crate::{
+    from_reflect, impls, ReflectDerive, REFLECT_ATTRIBUTE_NAME, REFLECT_VALUE_ATTRIBUTE_NAME,
+    TYPE_NAME_ATTRIBUTE_NAME, TYPE_PATH_ATTRIBUTE_NAME,
+};
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DeriveInput, TypePath};
+
+/// Generates a transparent wrapper around a remote type along with its reflection impls.
+///
+/// The input is a mirror of the remote type's definition,
+/// which is used to generate the field accessors for the wrapper.
+pub(crate) fn reflect_remote(args: TokenStream, input: TokenStream) -> TokenStream {
+    let remote_ty = parse_macro_input!(args as TypePath);
+    let ast = parse_macro_input!(input as DeriveInput);
+
+    let mut derive_data = match ReflectDerive::from_input(
+        &ast,
+        ReflectProvenance {
+            source: ReflectImplSource::RemoteReflect,
+            trait_: ReflectTraitToImpl::Reflect,
+        },
+    ) {
+        Ok(data) => data,
+        Err(err) => return err.into_compile_error().into(),
+    };
+    derive_data.set_remote(Some(&remote_ty));
+
+    let (reflect_impls, from_reflect_impl) = match &derive_data {
+        ReflectDerive::Struct(struct_data) | ReflectDerive::UnitStruct(struct_data) => (
+            impls::impl_struct(struct_data),
+            struct_data
+                .meta()
+                .from_reflect()
+                .should_auto_derive()
+                .then(|| from_reflect::impl_struct(struct_data)),
+        ),
+        ReflectDerive::TupleStruct(struct_data) => (
+            impls::impl_tuple_struct(struct_data),
+            struct_data
+                .meta()
+                .from_reflect()
+                .should_auto_derive()
+                .then(|| from_reflect::impl_tuple_struct(struct_data)),
+        ),
+        ReflectDerive::Enum(enum_data) => (
+            impls::impl_enum(enum_data),
+            enum_data
+                .meta()
+                .from_reflect()
+                .should_auto_derive()
+                .then(|| from_reflect::impl_enum(enum_data)),
+        ),
+        ReflectDerive::Value(_) => {
+            return syn::Error::new(
+                Span::call_site(),
+                format_args!(
+                    "`#[reflect_remote]` cannot be used with `#[{REFLECT_VALUE_ATTRIBUTE_NAME}]`"
+                ),
+            )
+            .into_compile_error()
+            .into();
+        }
+    };
+
+    let bevy_reflect_path = derive_data.meta().bevy_reflect_path();
+    let vis = &ast.vis;
+    let ident = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let generics = &ast.generics;
+
+    // Helper attributes are consumed here, since the wrapper does not derive `Reflect` itself
+    let attrs = ast.attrs.iter().filter(|attr| {
+        let path = attr.path();
+        !path.is_ident(REFLECT_ATTRIBUTE_NAME)
+            && !path.is_ident(TYPE_PATH_ATTRIBUTE_NAME)
+            && !path.is_ident(TYPE_NAME_ATTRIBUTE_NAME)
+    });
+
+    // Every mirrored field must match the type of the corresponding remote field,
+    // and the variants of a mirrored enum must cover all variants of the remote enum
+    let field_assertions = match &ast.data {
+        Data::Struct(data) => data
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| {
+                let member = crate::utility::ident_or_index(field.ident.as_ref(), index);
+                let ty = &field.ty;
+                quote!(let _: &#ty = &remote.#member;)
+            })
+            .collect::<Vec<_>>(),
+        Data::Enum(data) => {
+            let remote_path = derive_data.meta().remote_path();
+            let arms = data.variants.iter().map(|variant| {
+                let ident = &variant.ident;
+                let members = variant.fields.iter().enumerate().map(|(index, field)| {
+                    crate::utility::ident_or_index(field.ident.as_ref(), index)
+                });
+                let bindings = (0..variant.fields.len())
+                    .map(|index| format_ident!("__field_{}", index))
+                    .collect::<Vec<_>>();
+                let types = variant.fields.iter().map(|field| &field.ty);
+                quote! {
+                    #remote_path::#ident { #(#members: #bindings,)* .. } => {
+                        #(let _: &#types = #bindings;)*
+                    }
+                }
+            });
+            vec![quote!(match remote { #(#arms)* })]
+        }
+        Data::Union(_) => Vec::new(),
+    };
+
+    let where_reflect_clause = match &derive_data {
+        ReflectDerive::Struct(data)
+        | ReflectDerive::TupleStruct(data)
+        | ReflectDerive::UnitStruct(data) => data
+            .where_clause_options()
+            .extend_where_clause(where_clause),
+        ReflectDerive::Enum(data) => data
+            .where_clause_options()
+            .extend_where_clause(where_clause),
+        ReflectDerive::Value(_) => unreachable!(),
+    };
+
+    TokenStream::from(quote! {
+        #(#attrs)*
+        #[repr(transparent)]
+        #vis struct #ident #generics (pub #remote_ty) #where_clause;
+
+        const _: () = {
+            #reflect_impls
+
+            #from_reflect_impl
+
+            impl #impl_generics #bevy_reflect_path::ReflectRemote for #ident #ty_generics #where_reflect_clause {
+                type Remote = #remote_ty;
+
+                fn as_remote(&self) -> &Self::Remote {
+                    &self.0
+                }
+
+                fn as_remote_mut(&mut self) -> &mut Self::Remote {
+                    &mut self.0
+                }
+
+                fn into_remote(self) -> Self::Remote {
+                    self.0
+                }
+
+                fn as_wrapper(remote: &Self::Remote) -> &Self {
+                    // SAFETY: `Self` is `repr(transparent)` over `Self::Remote`
+                    unsafe { &*(remote as *const Self::Remote).cast::<Self>() }
+                }
+
+                fn as_wrapper_mut(remote: &mut Self::Remote) -> &mut Self {
+                    // SAFETY: `Self` is `repr(transparent)` over `Self::Remote`
+                    unsafe { &mut *(remote as *mut Self::Remote).cast::<Self>() }
+                }
+
+                fn into_wrapper(remote: Self::Remote) -> Self {
+                    Self(remote)
+                }
+            }
+
+            impl #impl_generics ::core::convert::From<#remote_ty> for #ident #ty_generics #where_clause {
+                fn from(remote: #remote_ty) -> Self {
+                    Self(remote)
+                }
+            }
+
+            #[allow(dead_code, unused_variables)]
+            fn assert_remote_fields #impl_generics (remote: &#remote_ty) #where_clause {
+                #(#field_assertions)*
+            }
+        };
+    })
+}
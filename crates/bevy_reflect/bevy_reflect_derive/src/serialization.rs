@@ -77,12 +77,20 @@ impl SkippedFieldDef {
     pub fn new(field: &StructField<'_>) -> Result<Self, syn::Error> {
         let ty = &field.data.ty;
 
-        let default_fn = match &field.attrs.default {
-            DefaultBehavior::Func(func) => quote! {
-              || { #FQBox::new(#func()) }
-            },
-            _ => quote! {
-              || { #FQBox::new(<#ty as #FQDefault>::default()) }
+        let default_value = match &field.attrs.default {
+            DefaultBehavior::Func(func) => quote!(#func()),
+            _ => quote!(<#ty as #FQDefault>::default()),
+        };
+
+        let default_fn = match &field.attrs.remote {
+            Some(wrapper) => {
+                let bevy_reflect_path = crate::utility::get_bevy_reflect_path();
+                quote! {
+                  || { #FQBox::new(<#wrapper as #bevy_reflect_path::ReflectRemote>::into_wrapper(#default_value)) }
+                }
+            }
+            None => quote! {
+              || { #FQBox::new(#default_value) }
             },
         };
 
@@ -0,0 +1,348 @@
+//! Custom attributes declared on reflected types, their fields, and their enum variants.
+//!
+//! Any value implementing [`Reflect`] can be attached to a type, field, or enum variant
+//! with the `#[reflect(@...)]` attribute when deriving `Reflect`.
+//! These attributes are stored in the [`ReflectAttributes`] type data
+//! and can be retrieved from the [type registry] at runtime.
+//!
+//! This is most commonly used to give inspectors and editors hints about how a value
+//! should be displayed or edited.
+//! A set of standard hints is provided in the [`hints`] module.
+//!
+//! # Example
+//!
+//! ```
+//! # use std::any::TypeId;
+//! # use bevy_reflect::{Reflect, TypeRegistry};
+//! # use bevy_reflect::attributes::ReflectAttributes;
+//! use bevy_reflect::attributes::hints::{Range, Step};
+//!
+//! #[derive(Reflect)]
+//! struct Volume {
+//!     #[reflect(@Range(0.0..=1.0), @Step(0.05))]
+//!     level: f32,
+//! }
+//!
+//! let mut registry = TypeRegistry::default();
+//! registry.register::<Volume>();
+//!
+//! let attributes = registry
+//!     .get_type_data::<ReflectAttributes>(TypeId::of::<Volume>())
+//!     .unwrap();
+//! let level = attributes.field("level").unwrap();
+//!
+//! assert_eq!(level.get::<Range>(), Some(&Range(0.0..=1.0)));
+//! assert_eq!(level.get::<Step>(), Some(&Step(0.05)));
+//! ```
+//!
+//! [type registry]: crate::TypeRegistry
+
+use crate::Reflect;
+use bevy_utils::HashMap;
+use std::any::TypeId;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+/// A collection of custom attributes, keyed by their type.
+///
+/// At most one attribute of each type can be stored.
+#[derive(Clone, Default)]
+pub struct CustomAttributes {
+    attributes: HashMap<TypeId, Arc<dyn Reflect>>,
+}
+
+impl CustomAttributes {
+    /// Adds the given attribute, replacing any existing attribute of the same type.
+    pub fn with_attribute<T: Reflect>(mut self, value: T) -> Self {
+        self.attributes.insert(TypeId::of::<T>(), Arc::new(value));
+        self
+    }
+
+    /// Returns the attribute of type `T`, if it exists.
+    pub fn get<T: Reflect>(&self) -> Option<&T> {
+        self.attributes.get(&TypeId::of::<T>())?.downcast_ref::<T>()
+    }
+
+    /// Returns the attribute with the given [`TypeId`], if it exists.
+    pub fn get_by_id(&self, id: TypeId) -> Option<&dyn Reflect> {
+        self.attributes.get(&id).map(|value| value.as_ref())
+    }
+
+    /// Returns `true` if an attribute of type `T` exists.
+    pub fn contains<T: Reflect>(&self) -> bool {
+        self.attributes.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Returns an iterator over all attributes and their [`TypeId`]s.
+    pub fn iter(&self) -> impl Iterator<Item = (TypeId, &dyn Reflect)> {
+        self.attributes
+            .iter()
+            .map(|(id, value)| (*id, value.as_ref()))
+    }
+
+    /// Returns the number of attributes.
+    pub fn len(&self) -> usize {
+        self.attributes.len()
+    }
+
+    /// Returns `true` if there are no attributes.
+    pub fn is_empty(&self) -> bool {
+        self.attributes.is_empty()
+    }
+}
+
+impl Debug for CustomAttributes {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_set()
+            .entries(self.attributes.values().map(|value| value as &dyn Debug))
+            .finish()
+    }
+}
+
+/// Type data containing the custom attributes declared on a type, its fields, and its variants.
+///
+/// This is registered automatically by `#[derive(Reflect)]`
+/// whenever the type or any of its fields or variants use `#[reflect(@...)]`.
+///
+/// Field attributes are stored for every reflected field in declaration order,
+/// skipping fields marked `#[reflect(ignore)]`.
+/// For enums, the attributes of every variant are stored in declaration order,
+/// along with the attributes of their own fields.
+#[derive(Clone, Debug, Default)]
+pub struct ReflectAttributes {
+    container: CustomAttributes,
+    fields: Vec<(Option<&'static str>, CustomAttributes)>,
+    variants: Vec<(&'static str, VariantAttributes)>,
+}
+
+impl ReflectAttributes {
+    /// Creates a new [`ReflectAttributes`] with the given attributes for the type itself.
+    pub fn new(container: CustomAttributes) -> Self {
+        Self {
+            container,
+            fields: Vec::new(),
+            variants: Vec::new(),
+        }
+    }
+
+    /// Adds the attributes for the next named field.
+    pub fn with_named_field(mut self, name: &'static str, attributes: CustomAttributes) -> Self {
+        self.fields.push((Some(name), attributes));
+        self
+    }
+
+    /// Adds the attributes for the next unnamed field.
+    pub fn with_unnamed_field(mut self, attributes: CustomAttributes) -> Self {
+        self.fields.push((None, attributes));
+        self
+    }
+
+    /// The attributes declared on the type itself.
+    pub fn container(&self) -> &CustomAttributes {
+        &self.container
+    }
+
+    /// The attributes declared on the field with the given name.
+    pub fn field(&self, name: &str) -> Option<&CustomAttributes> {
+        self.fields
+            .iter()
+            .find(|(field_name, _)| *field_name == Some(name))
+            .map(|(_, attributes)| attributes)
+    }
+
+    /// The attributes declared on the field at the given reflection index.
+    pub fn field_at(&self, index: usize) -> Option<&CustomAttributes> {
+        self.fields.get(index).map(|(_, attributes)| attributes)
+    }
+
+    /// Adds the attributes for the next enum variant.
+    pub fn with_variant(mut self, name: &'static str, attributes: VariantAttributes) -> Self {
+        self.variants.push((name, attributes));
+        self
+    }
+
+    /// The attributes declared on the enum variant with the given name and its fields.
+    pub fn variant(&self, name: &str) -> Option<&VariantAttributes> {
+        self.variants
+            .iter()
+            .find(|(variant_name, _)| *variant_name == name)
+            .map(|(_, attributes)| attributes)
+    }
+
+    /// The attributes declared on the enum variant at the given index and its fields.
+    pub fn variant_at(&self, index: usize) -> Option<&VariantAttributes> {
+        self.variants.get(index).map(|(_, attributes)| attributes)
+    }
+}
+
+/// The custom attributes declared on an enum variant and its fields.
+///
+/// Like the fields of a struct in [`ReflectAttributes`], field attributes are stored
+/// for every reflected field of the variant in declaration order.
+#[derive(Clone, Debug, Default)]
+pub struct VariantAttributes {
+    attributes: CustomAttributes,
+    fields: Vec<(Option<&'static str>, CustomAttributes)>,
+}
+
+impl VariantAttributes {
+    /// Creates a new [`VariantAttributes`] with the given attributes for the variant itself.
+    pub fn new(attributes: CustomAttributes) -> Self {
+        Self {
+            attributes,
+            fields: Vec::new(),
+        }
+    }
+
+    /// Adds the attributes for the next named field of a struct variant.
+    pub fn with_named_field(mut self, name: &'static str, attributes: CustomAttributes) -> Self {
+        self.fields.push((Some(name), attributes));
+        self
+    }
+
+    /// Adds the attributes for the next field of a tuple variant.
+    pub fn with_unnamed_field(mut self, attributes: CustomAttributes) -> Self {
+        self.fields.push((None, attributes));
+        self
+    }
+
+    /// The attributes declared on the variant itself.
+    pub fn attributes(&self) -> &CustomAttributes {
+        &self.attributes
+    }
+
+    /// The attributes declared on the field with the given name.
+    pub fn field(&self, name: &str) -> Option<&CustomAttributes> {
+        self.fields
+            .iter()
+            .find(|(field_name, _)| *field_name == Some(name))
+            .map(|(_, attributes)| attributes)
+    }
+
+    /// The attributes declared on the field at the given reflection index.
+    pub fn field_at(&self, index: usize) -> Option<&CustomAttributes> {
+        self.fields.get(index).map(|(_, attributes)| attributes)
+    }
+}
+
+/// Standard attributes describing how a value should be displayed in an inspector.
+///
+/// These carry no behavior of their own,
+/// but give any inspector built on `bevy_reflect` a shared vocabulary for choosing widgets.
+pub mod hints {
+    use crate as bevy_reflect;
+    use crate::Reflect;
+    use std::ops::RangeInclusive;
+
+    /// The inclusive range of values a numeric field may take, usually shown as a slider.
+    ///
+    /// Integer fields should still use floating point bounds, such as `@Range(0.0..=10.0)`.
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    pub struct Range(pub RangeInclusive<f64>);
+
+    /// The amount a numeric field should change by per increment when dragged or stepped.
+    #[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+    pub struct Step(pub f64);
+
+    /// Marks a string field as containing multiple lines of text.
+    #[derive(Reflect, Clone, Copy, Debug, Default, PartialEq)]
+    pub struct Multiline;
+
+    /// Marks a field as representing a color, such as an `[f32; 4]` or a `Vec3`.
+    #[derive(Reflect, Clone, Copy, Debug, Default, PartialEq)]
+    pub struct Color {
+        /// Whether the color has an alpha channel that should be editable.
+        pub alpha: bool,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::hints::{Multiline, Range, Step};
+    use super::*;
+    use crate as bevy_reflect;
+    use crate::TypeRegistry;
+
+    #[derive(Reflect)]
+    #[reflect(@Multiline)]
+    struct Settings {
+        #[reflect(@Range(0.0..=1.0), @Step(0.1))]
+        volume: f32,
+        #[reflect(ignore)]
+        _cache: u32,
+        #[reflect(@Multiline)]
+        notes: String,
+    }
+
+    #[derive(Reflect)]
+    struct Plain {
+        value: f32,
+    }
+
+    #[test]
+    fn should_register_custom_attributes() {
+        let mut registry = TypeRegistry::default();
+        registry.register::<Settings>();
+        registry.register::<Plain>();
+
+        let attributes = registry
+            .get_type_data::<ReflectAttributes>(TypeId::of::<Settings>())
+            .unwrap();
+
+        assert!(attributes.container().contains::<Multiline>());
+
+        let volume = attributes.field("volume").unwrap();
+        assert_eq!(volume.get::<Range>(), Some(&Range(0.0..=1.0)));
+        assert_eq!(volume.get::<Step>(), Some(&Step(0.1)));
+        assert_eq!(volume.len(), 2);
+
+        let notes = attributes.field_at(1).unwrap();
+        assert!(notes.contains::<Multiline>());
+        assert!(!notes.contains::<Range>());
+
+        assert!(registry
+            .get_type_data::<ReflectAttributes>(TypeId::of::<Plain>())
+            .is_none());
+    }
+
+    #[derive(Reflect)]
+    #[allow(dead_code)]
+    enum Shape {
+        Point,
+        #[reflect(@Multiline)]
+        Circle {
+            #[reflect(@Range(0.0..=10.0))]
+            radius: f32,
+        },
+        Label(#[reflect(ignore)] u32, #[reflect(@Multiline)] String),
+    }
+
+    #[test]
+    fn should_register_enum_variant_attributes() {
+        let mut registry = TypeRegistry::default();
+        registry.register::<Shape>();
+
+        let attributes = registry
+            .get_type_data::<ReflectAttributes>(TypeId::of::<Shape>())
+            .unwrap();
+
+        assert!(attributes.container().is_empty());
+
+        let point = attributes.variant_at(0).unwrap();
+        assert!(point.attributes().is_empty());
+        assert!(point.field_at(0).is_none());
+
+        let circle = attributes.variant("Circle").unwrap();
+        assert!(circle.attributes().contains::<Multiline>());
+        assert_eq!(
+            circle.field("radius").unwrap().get::<Range>(),
+            Some(&Range(0.0..=10.0))
+        );
+
+        let label = attributes.variant_at(2).unwrap();
+        assert!(label.attributes().is_empty());
+        assert!(label.field_at(0).unwrap().contains::<Multiline>());
+        assert!(label.field_at(1).is_none());
+        assert!(attributes.variant("Missing").is_none());
+    }
+}
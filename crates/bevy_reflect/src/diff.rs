@@ -0,0 +1,792 @@
+use crate::{Array, Enum, List, Map, Reflect, ReflectKind, ReflectMut, ReflectRef, VariantType};
+use std::fmt::{Debug, Formatter};
+use thiserror::Error;
+
+/// A field-level description of the changes needed to turn one reflected value into another.
+///
+/// A `ReflectPatch` is created with [`Reflect::diff`] (or [`DiffOptions::diff`])
+/// and applied with [`Reflect::apply_patch`] (or [`ReflectPatch::apply`]).
+/// Only the parts of a value that actually changed are stored,
+/// making patches suitable for network delta-compression, undo systems, and scene overrides.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_reflect::{Reflect, ReflectPatch};
+/// #[derive(Reflect, Clone, Debug, PartialEq)]
+/// struct Player {
+///     name: String,
+///     health: u32,
+///     inventory: Vec<String>,
+/// }
+///
+/// let before = Player {
+///     name: String::from("Ferris"),
+///     health: 100,
+///     inventory: vec![String::from("sword")],
+/// };
+/// let after = Player {
+///     health: 80,
+///     inventory: vec![String::from("sword"), String::from("shield")],
+///     ..before.clone()
+/// };
+///
+/// let patch = before.diff(&after);
+/// assert!(matches!(patch, ReflectPatch::Struct(ref fields) if fields.len() == 2));
+///
+/// let mut value = before.clone();
+/// value.apply_patch(&patch).unwrap();
+/// assert_eq!(value, after);
+/// ```
+pub enum ReflectPatch {
+    /// The value did not change.
+    Unchanged,
+    /// The value should be replaced entirely.
+    ///
+    /// This is used for [value types], for enums whose variant changed,
+    /// and for any values whose kinds or shapes differ.
+    ///
+    /// [value types]: ReflectKind::Value
+    Replace(Box<dyn Reflect>),
+    /// Changes to the named fields of a [`Struct`](crate::Struct).
+    Struct(Vec<(String, ReflectPatch)>),
+    /// Changes to the fields of a [`TupleStruct`](crate::TupleStruct), by index.
+    TupleStruct(Vec<(usize, ReflectPatch)>),
+    /// Changes to the fields of a [`Tuple`](crate::Tuple), by index.
+    Tuple(Vec<(usize, ReflectPatch)>),
+    /// Changes to the elements of an [`Array`], by index.
+    Array(Vec<(usize, ReflectPatch)>),
+    /// Changes to the fields of an [`Enum`] whose variant did not change.
+    Enum {
+        /// The name of the variant the fields belong to.
+        variant: String,
+        /// The changed fields of the variant, by index.
+        fields: Vec<(usize, ReflectPatch)>,
+    },
+    /// Edits to a [`List`], applied in order.
+    List(Vec<ListEdit>),
+    /// Edits to a [`Map`], applied in order.
+    Map(Vec<MapEdit>),
+}
+
+/// A single edit to a [`List`] within a [`ReflectPatch`].
+///
+/// Indices refer to the state of the list at the time the edit is applied,
+/// i.e. after all previous edits in the patch.
+pub enum ListEdit {
+    /// Patch the element at the given index.
+    Patch(usize, ReflectPatch),
+    /// Insert a new element at the given index.
+    Insert(usize, Box<dyn Reflect>),
+    /// Remove the element at the given index.
+    Remove(usize),
+}
+
+/// A single edit to a [`Map`] within a [`ReflectPatch`].
+pub enum MapEdit {
+    /// Patch the value associated with the given key.
+    Patch(Box<dyn Reflect>, ReflectPatch),
+    /// Insert the given key-value pair, replacing any existing value.
+    Insert(Box<dyn Reflect>, Box<dyn Reflect>),
+    /// Remove the entry with the given key.
+    Remove(Box<dyn Reflect>),
+}
+
+/// The strategy used to compute the differences between two [`List`]s.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ListDiffStrategy {
+    /// Compare elements at the same index, then remove or append any excess elements.
+    ///
+    /// This produces compact patches when elements are modified in place.
+    #[default]
+    Index,
+    /// Compute the longest common subsequence of equal elements
+    /// and describe the changes as a series of insertions and removals.
+    ///
+    /// This produces compact patches when elements are inserted or removed in the middle of a list,
+    /// but requires elements to support [`Reflect::reflect_partial_eq`]
+    /// and takes `O(n * m)` time.
+    LongestCommonSubsequence,
+    /// Replace the entire list whenever any element differs.
+    Replace,
+}
+
+/// The strategy used to compute the differences between two [`Map`]s.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum MapDiffStrategy {
+    /// Compare values with the same key, and insert or remove entries with missing keys.
+    #[default]
+    Key,
+    /// Replace the entire map whenever any entry differs.
+    Replace,
+}
+
+/// Options used to configure how [`ReflectPatch`]es are computed.
+///
+/// [`Reflect::diff`] uses the default options.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_reflect::{DiffOptions, ListDiffStrategy, Reflect, ReflectPatch};
+/// let before = vec![1, 2, 3];
+/// let after = vec![0, 1, 2, 3];
+///
+/// let options = DiffOptions {
+///     list: ListDiffStrategy::LongestCommonSubsequence,
+///     ..Default::default()
+/// };
+/// let patch = options.diff(&before, &after);
+/// assert!(matches!(patch, ReflectPatch::List(ref edits) if edits.len() == 1));
+///
+/// let mut value = before.clone();
+/// value.apply_patch(&patch).unwrap();
+/// assert_eq!(value, after);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DiffOptions {
+    /// The strategy used for [`List`]s.
+    pub list: ListDiffStrategy,
+    /// The strategy used for [`Map`]s.
+    pub map: MapDiffStrategy,
+}
+
+/// An error that occurs when applying a [`ReflectPatch`].
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum PatchError {
+    /// The patch describes a different kind of value than the one it was applied to.
+    #[error("expected a {expected} but found a {received}")]
+    KindMismatch {
+        expected: ReflectKind,
+        received: ReflectKind,
+    },
+    /// A replacement value could not be assigned to a value of a different type.
+    #[error("expected a value of type `{expected}` but found `{received}`")]
+    TypeMismatch { expected: String, received: String },
+    /// An enum patch was applied to a different variant.
+    #[error("expected variant `{expected}` but found `{received}`")]
+    VariantMismatch { expected: String, received: String },
+    /// A struct did not contain the patched field.
+    #[error("missing field `{0}`")]
+    MissingField(String),
+    /// A value did not contain the patched index.
+    #[error("missing index {0}")]
+    MissingIndex(usize),
+    /// A map did not contain the patched key.
+    #[error("missing key `{0}`")]
+    MissingKey(String),
+}
+
+impl DiffOptions {
+    /// Computes the [`ReflectPatch`] needed to turn `from` into `to`.
+    pub fn diff(&self, from: &dyn Reflect, to: &dyn Reflect) -> ReflectPatch {
+        if from.reflect_kind() != to.reflect_kind() || !represents_same_type(from, to) {
+            return ReflectPatch::Replace(to.clone_value());
+        }
+
+        match (from.reflect_ref(), to.reflect_ref()) {
+            (ReflectRef::Struct(from_struct), ReflectRef::Struct(to_struct)) => {
+                let mut fields = Vec::new();
+                for (index, to_field) in to_struct.iter_fields().enumerate() {
+                    let Some(name) = to_struct.name_at(index) else {
+                        continue;
+                    };
+                    let patch = match from_struct.field(name) {
+                        Some(from_field) => self.diff(from_field, to_field),
+                        None => ReflectPatch::Replace(to_field.clone_value()),
+                    };
+                    if !patch.is_unchanged() {
+                        fields.push((name.to_owned(), patch));
+                    }
+                }
+                ReflectPatch::from_changes(fields, ReflectPatch::Struct)
+            }
+            (ReflectRef::TupleStruct(from_struct), ReflectRef::TupleStruct(to_struct)) => {
+                if from_struct.field_len() != to_struct.field_len() {
+                    return ReflectPatch::Replace(to.clone_value());
+                }
+                let fields = self.diff_indexed(to_struct.field_len(), |index| {
+                    (from_struct.field(index), to_struct.field(index))
+                });
+                ReflectPatch::from_changes(fields, ReflectPatch::TupleStruct)
+            }
+            (ReflectRef::Tuple(from_tuple), ReflectRef::Tuple(to_tuple)) => {
+                if from_tuple.field_len() != to_tuple.field_len() {
+                    return ReflectPatch::Replace(to.clone_value());
+                }
+                let fields = self.diff_indexed(to_tuple.field_len(), |index| {
+                    (from_tuple.field(index), to_tuple.field(index))
+                });
+                ReflectPatch::from_changes(fields, ReflectPatch::Tuple)
+            }
+            (ReflectRef::Array(from_array), ReflectRef::Array(to_array)) => {
+                if from_array.len() != to_array.len() {
+                    return ReflectPatch::Replace(to.clone_value());
+                }
+                let elements = self.diff_indexed(to_array.len(), |index| {
+                    (from_array.get(index), to_array.get(index))
+                });
+                ReflectPatch::from_changes(elements, ReflectPatch::Array)
+            }
+            (ReflectRef::Enum(from_enum), ReflectRef::Enum(to_enum)) => {
+                self.diff_enum(from_enum, to_enum)
+            }
+            (ReflectRef::List(from_list), ReflectRef::List(to_list)) => {
+                let edits = match self.list {
+                    ListDiffStrategy::Index => self.diff_list_by_index(from_list, to_list),
+                    ListDiffStrategy::LongestCommonSubsequence => {
+                        diff_list_by_lcs(from_list, to_list)
+                    }
+                    ListDiffStrategy::Replace => {
+                        if self.diff_list_by_index(from_list, to_list).is_empty() {
+                            Vec::new()
+                        } else {
+                            return ReflectPatch::Replace(to.clone_value());
+                        }
+                    }
+                };
+                ReflectPatch::from_changes(edits, ReflectPatch::List)
+            }
+            (ReflectRef::Map(from_map), ReflectRef::Map(to_map)) => {
+                let edits = self.diff_map(from_map, to_map);
+                if self.map == MapDiffStrategy::Replace && !edits.is_empty() {
+                    return ReflectPatch::Replace(to.clone_value());
+                }
+                ReflectPatch::from_changes(edits, ReflectPatch::Map)
+            }
+            _ => match from.reflect_partial_eq(to) {
+                Some(true) => ReflectPatch::Unchanged,
+                _ => ReflectPatch::Replace(to.clone_value()),
+            },
+        }
+    }
+
+    fn diff_indexed<'a>(
+        &self,
+        len: usize,
+        get: impl Fn(usize) -> (Option<&'a dyn Reflect>, Option<&'a dyn Reflect>),
+    ) -> Vec<(usize, ReflectPatch)> {
+        (0..len)
+            .filter_map(|index| match get(index) {
+                (Some(from), Some(to)) => Some((index, self.diff(from, to))),
+                (None, Some(to)) => Some((index, ReflectPatch::Replace(to.clone_value()))),
+                _ => None,
+            })
+            .filter(|(_, patch)| !patch.is_unchanged())
+            .collect()
+    }
+
+    fn diff_enum(&self, from: &dyn Enum, to: &dyn Enum) -> ReflectPatch {
+        if from.variant_name() != to.variant_name()
+            || from.variant_type() != to.variant_type()
+            || from.field_len() != to.field_len()
+        {
+            return ReflectPatch::Replace(to.clone_value());
+        }
+
+        if to.variant_type() == VariantType::Unit {
+            return ReflectPatch::Unchanged;
+        }
+
+        let fields = self.diff_indexed(to.field_len(), |index| {
+            (from.field_at(index), to.field_at(index))
+        });
+        if fields.is_empty() {
+            ReflectPatch::Unchanged
+        } else {
+            ReflectPatch::Enum {
+                variant: to.variant_name().to_owned(),
+                fields,
+            }
+        }
+    }
+
+    fn diff_list_by_index(&self, from: &dyn List, to: &dyn List) -> Vec<ListEdit> {
+        let shared_len = from.len().min(to.len());
+        let mut edits: Vec<ListEdit> = self
+            .diff_indexed(shared_len, |index| (from.get(index), to.get(index)))
+            .into_iter()
+            .map(|(index, patch)| ListEdit::Patch(index, patch))
+            .collect();
+
+        // Remove from the back so that earlier indices remain valid.
+        edits.extend((shared_len..from.len()).rev().map(ListEdit::Remove));
+        edits.extend(
+            (shared_len..to.len())
+                .filter_map(|index| Some(ListEdit::Insert(index, to.get(index)?.clone_value()))),
+        );
+        edits
+    }
+
+    fn diff_map(&self, from: &dyn Map, to: &dyn Map) -> Vec<MapEdit> {
+        let mut edits = Vec::new();
+        for (key, from_value) in from.iter() {
+            match to.get(key) {
+                Some(to_value) => {
+                    let patch = self.diff(from_value, to_value);
+                    if !patch.is_unchanged() {
+                        edits.push(MapEdit::Patch(key.clone_value(), patch));
+                    }
+                }
+                None => edits.push(MapEdit::Remove(key.clone_value())),
+            }
+        }
+        for (key, to_value) in to.iter() {
+            if from.get(key).is_none() {
+                edits.push(MapEdit::Insert(key.clone_value(), to_value.clone_value()));
+            }
+        }
+        edits
+    }
+}
+
+/// Returns `false` if both values represent known types, and those types differ.
+fn represents_same_type(a: &dyn Reflect, b: &dyn Reflect) -> bool {
+    match (a.get_represented_type_info(), b.get_represented_type_info()) {
+        (Some(a), Some(b)) => a.type_id() == b.type_id(),
+        _ => true,
+    }
+}
+
+fn elements_eq(a: &dyn Reflect, b: &dyn Reflect) -> bool {
+    a.reflect_partial_eq(b).unwrap_or(false)
+}
+
+/// Computes a minimal series of insertions and removals using the longest common subsequence
+/// of the two lists.
+fn diff_list_by_lcs(from: &dyn List, to: &dyn List) -> Vec<ListEdit> {
+    let from: Vec<&dyn Reflect> = from.iter().collect();
+    let to: Vec<&dyn Reflect> = to.iter().collect();
+    let (n, m) = (from.len(), to.len());
+
+    // `lcs[i][j]` is the length of the longest common subsequence of `from[i..]` and `to[j..]`.
+    let mut lcs = vec![vec![0_usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if elements_eq(from[i], to[j]) {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut edits = Vec::new();
+    let (mut i, mut j, mut position) = (0, 0, 0);
+    while i < n && j < m {
+        if elements_eq(from[i], to[j]) {
+            i += 1;
+            j += 1;
+            position += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            edits.push(ListEdit::Remove(position));
+            i += 1;
+        } else {
+            edits.push(ListEdit::Insert(position, to[j].clone_value()));
+            j += 1;
+            position += 1;
+        }
+    }
+    edits.extend((i..n).map(|_| ListEdit::Remove(position)));
+    edits.extend(
+        to[j..]
+            .iter()
+            .enumerate()
+            .map(|(offset, value)| ListEdit::Insert(position + offset, value.clone_value())),
+    );
+    edits
+}
+
+impl ReflectPatch {
+    fn from_changes<T>(changes: Vec<T>, variant: impl FnOnce(Vec<T>) -> Self) -> Self {
+        if changes.is_empty() {
+            ReflectPatch::Unchanged
+        } else {
+            variant(changes)
+        }
+    }
+
+    /// Returns `true` if this patch makes no changes.
+    pub fn is_unchanged(&self) -> bool {
+        matches!(self, ReflectPatch::Unchanged)
+    }
+
+    /// Applies this patch to the given value.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`PatchError`] if the value does not have the shape described by the patch.
+    /// Edits preceding the error will have already been applied.
+    pub fn apply(&self, target: &mut dyn Reflect) -> Result<(), PatchError> {
+        let received = target.reflect_kind();
+        let mismatch = |expected| PatchError::KindMismatch { expected, received };
+
+        match self {
+            ReflectPatch::Unchanged => Ok(()),
+            ReflectPatch::Replace(value) => {
+                if value.reflect_kind() != received {
+                    return Err(mismatch(value.reflect_kind()));
+                }
+                replace(target, value.as_reflect())
+            }
+            ReflectPatch::Struct(fields) => {
+                let ReflectMut::Struct(target) = target.reflect_mut() else {
+                    return Err(mismatch(ReflectKind::Struct));
+                };
+                for (name, patch) in fields {
+                    let field = target
+                        .field_mut(name)
+                        .ok_or_else(|| PatchError::MissingField(name.clone()))?;
+                    patch.apply(field)?;
+                }
+                Ok(())
+            }
+            ReflectPatch::TupleStruct(fields) => {
+                let ReflectMut::TupleStruct(target) = target.reflect_mut() else {
+                    return Err(mismatch(ReflectKind::TupleStruct));
+                };
+                for (index, patch) in fields {
+                    let field = target
+                        .field_mut(*index)
+                        .ok_or(PatchError::MissingIndex(*index))?;
+                    patch.apply(field)?;
+                }
+                Ok(())
+            }
+            ReflectPatch::Tuple(fields) => {
+                let ReflectMut::Tuple(target) = target.reflect_mut() else {
+                    return Err(mismatch(ReflectKind::Tuple));
+                };
+                for (index, patch) in fields {
+                    let field = target
+                        .field_mut(*index)
+                        .ok_or(PatchError::MissingIndex(*index))?;
+                    patch.apply(field)?;
+                }
+                Ok(())
+            }
+            ReflectPatch::Array(elements) => {
+                let ReflectMut::Array(target) = target.reflect_mut() else {
+                    return Err(mismatch(ReflectKind::Array));
+                };
+                apply_array(target, elements)
+            }
+            ReflectPatch::Enum { variant, fields } => {
+                let ReflectMut::Enum(target) = target.reflect_mut() else {
+                    return Err(mismatch(ReflectKind::Enum));
+                };
+                if target.variant_name() != variant {
+                    return Err(PatchError::VariantMismatch {
+                        expected: variant.clone(),
+                        received: target.variant_name().to_owned(),
+                    });
+                }
+                for (index, patch) in fields {
+                    let field = target
+                        .field_at_mut(*index)
+                        .ok_or(PatchError::MissingIndex(*index))?;
+                    patch.apply(field)?;
+                }
+                Ok(())
+            }
+            ReflectPatch::List(edits) => {
+                let ReflectMut::List(target) = target.reflect_mut() else {
+                    return Err(mismatch(ReflectKind::List));
+                };
+                apply_list(target, edits)
+            }
+            ReflectPatch::Map(edits) => {
+                let ReflectMut::Map(target) = target.reflect_mut() else {
+                    return Err(mismatch(ReflectKind::Map));
+                };
+                apply_map(target, edits)
+            }
+        }
+    }
+}
+
+/// Replaces `target` with `value`, which must be of the same kind.
+///
+/// Unlike [`Reflect::apply`], this removes any list elements or map entries
+/// which are not present in `value`.
+fn replace(target: &mut dyn Reflect, value: &dyn Reflect) -> Result<(), PatchError> {
+    if target.reflect_kind() == ReflectKind::Value {
+        return target
+            .set(value.clone_value())
+            .map_err(|value| PatchError::TypeMismatch {
+                expected: target.reflect_type_path().to_owned(),
+                received: value.reflect_type_path().to_owned(),
+            });
+    }
+
+    match (target.reflect_mut(), value.reflect_ref()) {
+        (ReflectMut::List(target), ReflectRef::List(value)) => {
+            while target.pop().is_some() {}
+            for element in value.iter() {
+                target.push(element.clone_value());
+            }
+            return Ok(());
+        }
+        (ReflectMut::Map(target), ReflectRef::Map(value)) => {
+            let stale_keys: Vec<Box<dyn Reflect>> = target
+                .iter()
+                .filter(|(key, _)| value.get(*key).is_none())
+                .map(|(key, _)| key.clone_value())
+                .collect();
+            for key in stale_keys {
+                target.remove(key.as_reflect());
+            }
+        }
+        _ => {}
+    }
+
+    target.apply(value);
+    Ok(())
+}
+
+fn apply_array(
+    target: &mut dyn Array,
+    elements: &[(usize, ReflectPatch)],
+) -> Result<(), PatchError> {
+    for (index, patch) in elements {
+        let element = target
+            .get_mut(*index)
+            .ok_or(PatchError::MissingIndex(*index))?;
+        patch.apply(element)?;
+    }
+    Ok(())
+}
+
+fn apply_list(target: &mut dyn List, edits: &[ListEdit]) -> Result<(), PatchError> {
+    for edit in edits {
+        match edit {
+            ListEdit::Patch(index, patch) => {
+                let element = target
+                    .get_mut(*index)
+                    .ok_or(PatchError::MissingIndex(*index))?;
+                patch.apply(element)?;
+            }
+            ListEdit::Insert(index, value) => {
+                if *index > target.len() {
+                    return Err(PatchError::MissingIndex(*index));
+                }
+                target.insert(*index, value.clone_value());
+            }
+            ListEdit::Remove(index) => {
+                if *index >= target.len() {
+                    return Err(PatchError::MissingIndex(*index));
+                }
+                target.remove(*index);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn apply_map(target: &mut dyn Map, edits: &[MapEdit]) -> Result<(), PatchError> {
+    for edit in edits {
+        match edit {
+            MapEdit::Patch(key, patch) => {
+                let value = target
+                    .get_mut(key.as_reflect())
+                    .ok_or_else(|| PatchError::MissingKey(format!("{key:?}")))?;
+                patch.apply(value)?;
+            }
+            MapEdit::Insert(key, value) => {
+                target.insert_boxed(key.clone_value(), value.clone_value());
+            }
+            MapEdit::Remove(key) => {
+                target
+                    .remove(key.as_reflect())
+                    .ok_or_else(|| PatchError::MissingKey(format!("{key:?}")))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+impl Debug for ReflectPatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReflectPatch::Unchanged => f.write_str("Unchanged"),
+            ReflectPatch::Replace(value) => f.debug_tuple("Replace").field(value).finish(),
+            ReflectPatch::Struct(fields) => f
+                .debug_map()
+                .entries(fields.iter().map(|(k, v)| (k, v)))
+                .finish(),
+            ReflectPatch::TupleStruct(fields)
+            | ReflectPatch::Tuple(fields)
+            | ReflectPatch::Array(fields) => f
+                .debug_map()
+                .entries(fields.iter().map(|(k, v)| (k, v)))
+                .finish(),
+            ReflectPatch::Enum { variant, fields } => f
+                .debug_struct("Enum")
+                .field("variant", variant)
+                .field("fields", fields)
+                .finish(),
+            ReflectPatch::List(edits) => f.debug_list().entries(edits).finish(),
+            ReflectPatch::Map(edits) => f.debug_list().entries(edits).finish(),
+        }
+    }
+}
+
+impl Debug for ListEdit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ListEdit::Patch(index, patch) => {
+                f.debug_tuple("Patch").field(index).field(patch).finish()
+            }
+            ListEdit::Insert(index, value) => {
+                f.debug_tuple("Insert").field(index).field(value).finish()
+            }
+            ListEdit::Remove(index) => f.debug_tuple("Remove").field(index).finish(),
+        }
+    }
+}
+
+impl Debug for MapEdit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MapEdit::Patch(key, patch) => f.debug_tuple("Patch").field(key).field(patch).finish(),
+            MapEdit::Insert(key, value) => f.debug_tuple("Insert").field(key).field(value).finish(),
+            MapEdit::Remove(key) => f.debug_tuple("Remove").field(key).finish(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as bevy_reflect;
+    use bevy_utils::HashMap;
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    struct Foo {
+        a: i32,
+        b: String,
+        c: (f32, bool),
+        d: Option<u8>,
+        e: Vec<u32>,
+        f: HashMap<u32, String>,
+    }
+
+    fn foo() -> Foo {
+        Foo {
+            a: 1,
+            b: String::from("hello"),
+            c: (1.0, true),
+            d: Some(1),
+            e: vec![1, 2, 3],
+            f: [(1, String::from("one")), (2, String::from("two"))]
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn should_be_unchanged_for_equal_values() {
+        assert!(foo().diff(&foo()).is_unchanged());
+    }
+
+    #[test]
+    fn should_only_patch_changed_fields() {
+        let from = foo();
+        let to = Foo { a: 2, ..foo() };
+
+        let ReflectPatch::Struct(fields) = from.diff(&to) else {
+            panic!("expected struct patch");
+        };
+        assert_eq!(fields.len(), 1);
+        assert_eq!(fields[0].0, "a");
+        assert!(matches!(fields[0].1, ReflectPatch::Replace(_)));
+    }
+
+    #[test]
+    fn should_round_trip() {
+        let from = foo();
+        let to = Foo {
+            a: 5,
+            b: String::from("world"),
+            c: (1.0, false),
+            d: None,
+            e: vec![1, 4],
+            f: [(2, String::from("deux")), (3, String::from("three"))]
+                .into_iter()
+                .collect(),
+        };
+
+        let mut value = from.clone();
+        value.apply_patch(&from.diff(&to)).unwrap();
+        assert_eq!(value, to);
+
+        let mut value = to.clone();
+        value.apply_patch(&to.diff(&from)).unwrap();
+        assert_eq!(value, from);
+    }
+
+    #[test]
+    fn should_patch_enum_fields() {
+        let from = Some(Foo { a: 1, ..foo() });
+        let to = Some(Foo { a: 2, ..foo() });
+
+        let patch = from.diff(&to);
+        assert!(matches!(patch, ReflectPatch::Enum { ref variant, .. } if variant == "Some"));
+
+        let mut value = from.clone();
+        value.apply_patch(&patch).unwrap();
+        assert_eq!(value, to);
+
+        let mut none: Option<Foo> = None;
+        assert_eq!(
+            none.apply_patch(&patch),
+            Err(PatchError::VariantMismatch {
+                expected: String::from("Some"),
+                received: String::from("None"),
+            })
+        );
+    }
+
+    #[test]
+    fn should_diff_lists_with_each_strategy() {
+        let from = vec![1, 2, 3, 4];
+        let to = vec![0, 1, 3, 4, 5];
+
+        for list in [
+            ListDiffStrategy::Index,
+            ListDiffStrategy::LongestCommonSubsequence,
+            ListDiffStrategy::Replace,
+        ] {
+            let options = DiffOptions {
+                list,
+                ..Default::default()
+            };
+            let mut value = from.clone();
+            value.apply_patch(&options.diff(&from, &to)).unwrap();
+            assert_eq!(value, to, "failed with strategy {list:?}");
+        }
+
+        let options = DiffOptions {
+            list: ListDiffStrategy::LongestCommonSubsequence,
+            ..Default::default()
+        };
+        let ReflectPatch::List(edits) = options.diff(&from, &to) else {
+            panic!("expected list patch");
+        };
+        // Insert `0`, remove `2`, and append `5`.
+        assert_eq!(edits.len(), 3);
+    }
+
+    #[test]
+    fn should_error_on_mismatched_kinds() {
+        let patch = foo().diff(&Foo { a: 2, ..foo() });
+        let mut value = vec![1, 2, 3];
+        assert_eq!(
+            value.apply_patch(&patch),
+            Err(PatchError::KindMismatch {
+                expected: ReflectKind::Struct,
+                received: ReflectKind::List,
+            })
+        );
+    }
+}
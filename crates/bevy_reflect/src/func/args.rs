@@ -0,0 +1,87 @@
+use crate::TypePath;
+use std::{any::TypeId, borrow::Cow};
+
+/// The way in which an argument is passed to a [`DynamicFunction`].
+///
+/// [`DynamicFunction`]: crate::func::DynamicFunction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ownership {
+    /// The argument is passed by reference (e.g. `&self` receivers).
+    Ref,
+    /// The argument is passed by value and is created from the provided
+    /// reflected value using [`FromReflect`](crate::FromReflect).
+    Owned,
+}
+
+/// Type information for an argument of a [`DynamicFunction`].
+///
+/// [`DynamicFunction`]: crate::func::DynamicFunction
+#[derive(Debug, Clone)]
+pub struct ArgInfo {
+    index: usize,
+    name: Option<Cow<'static, str>>,
+    ownership: Ownership,
+    type_path: &'static str,
+    type_id: TypeId,
+}
+
+impl ArgInfo {
+    /// Create a new [`ArgInfo`] for an argument of type `T` at the given index.
+    pub fn new<T: TypePath + 'static>(index: usize, ownership: Ownership) -> Self {
+        Self {
+            index,
+            name: None,
+            ownership,
+            type_path: T::type_path(),
+            type_id: TypeId::of::<T>(),
+        }
+    }
+
+    /// Sets the name of the argument.
+    pub fn with_name(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.set_name(name);
+        self
+    }
+
+    pub(super) fn set_name(&mut self, name: impl Into<Cow<'static, str>>) {
+        self.name = Some(name.into());
+    }
+
+    /// The index of the argument within the function's argument list.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The name of the argument, if one was provided.
+    ///
+    /// Argument names cannot be determined automatically,
+    /// so they must be supplied with [`ArgInfo::with_name`]
+    /// or [`DynamicFunction::with_arg_names`].
+    ///
+    /// [`DynamicFunction::with_arg_names`]: crate::func::DynamicFunction::with_arg_names
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// How the argument is passed to the function.
+    pub fn ownership(&self) -> Ownership {
+        self.ownership
+    }
+
+    /// The [type path] of the argument.
+    ///
+    /// [type path]: TypePath::type_path
+    pub fn type_path(&self) -> &'static str {
+        self.type_path
+    }
+
+    /// The [`TypeId`] of the argument.
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    /// Check if the given type matches the argument type.
+    pub fn is<T: 'static>(&self) -> bool {
+        TypeId::of::<T>() == self.type_id
+    }
+}
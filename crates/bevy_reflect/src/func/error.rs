@@ -0,0 +1,19 @@
+use std::borrow::Cow;
+use thiserror::Error;
+
+/// An error that occurs when calling a [`DynamicFunction`].
+///
+/// [`DynamicFunction`]: crate::func::DynamicFunction
+#[derive(Debug, Error, PartialEq)]
+pub enum FunctionError {
+    /// The number of arguments provided does not match the function's signature.
+    #[error("expected {expected} arguments but received {received}")]
+    ArgCount { expected: usize, received: usize },
+    /// An argument could not be converted into the type expected by the function.
+    #[error("expected argument {index} to be of type `{expected}` but received `{received}`")]
+    InvalidArgType {
+        index: usize,
+        expected: Cow<'static, str>,
+        received: Cow<'static, str>,
+    },
+}
@@ -0,0 +1,131 @@
+use crate::func::{FunctionError, FunctionInfo, IntoFunction, Ownership};
+use crate::Reflect;
+use std::{borrow::Cow, fmt::Debug, sync::Arc};
+
+/// The result of calling a [`DynamicFunction`].
+///
+/// On success, the function's return value is boxed as a reflected value.
+/// Functions returning nothing will return a boxed `()`.
+pub type FunctionResult = Result<Box<dyn Reflect>, FunctionError>;
+
+/// A dynamic representation of a Rust function or closure.
+///
+/// A [`DynamicFunction`] can be created from most functions and closures
+/// using [`IntoFunction::into_function`],
+/// or manually by providing the function body and its [`FunctionInfo`] to [`DynamicFunction::new`].
+///
+/// Calling the function is done with [`DynamicFunction::call`],
+/// which takes its arguments as a slice of [`&dyn Reflect`](Reflect).
+///
+/// # Example
+///
+/// ```
+/// # use bevy_reflect::func::IntoFunction;
+/// fn greet(name: String) -> String {
+///     format!("Hello, {name}!")
+/// }
+///
+/// let func = greet.into_function().with_arg_names(["name"]);
+/// assert_eq!(func.info().args()[0].name(), Some("name"));
+///
+/// let result = func.call(&[&String::from("world")]).unwrap();
+/// assert_eq!(result.downcast_ref::<String>().unwrap(), "Hello, world!");
+/// ```
+#[derive(Clone)]
+pub struct DynamicFunction {
+    info: FunctionInfo,
+    func: Arc<dyn Fn(&[&dyn Reflect]) -> FunctionResult + Send + Sync + 'static>,
+}
+
+impl DynamicFunction {
+    /// Create a new [`DynamicFunction`].
+    ///
+    /// The given function is only ever called with exactly as many arguments
+    /// as are described by `info`, but is responsible for checking their types.
+    pub fn new<F: Fn(&[&dyn Reflect]) -> FunctionResult + Send + Sync + 'static>(
+        func: F,
+        info: FunctionInfo,
+    ) -> Self {
+        Self {
+            info,
+            func: Arc::new(func),
+        }
+    }
+
+    /// Sets the name of the function.
+    pub fn with_name(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.info = self.info.with_name(name);
+        self
+    }
+
+    /// Sets the names of the function's arguments, in order.
+    pub fn with_arg_names<N: Into<Cow<'static, str>>>(
+        mut self,
+        names: impl IntoIterator<Item = N>,
+    ) -> Self {
+        self.info = self.info.with_arg_names(names);
+        self
+    }
+
+    /// Call the function with the given arguments.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FunctionError::ArgCount`] if the wrong number of arguments is provided,
+    /// and [`FunctionError::InvalidArgType`] if an argument could not be converted
+    /// into the type expected by the function.
+    pub fn call(&self, args: &[&dyn Reflect]) -> FunctionResult {
+        let expected = self.info.arg_count();
+        if args.len() != expected {
+            return Err(FunctionError::ArgCount {
+                expected,
+                received: args.len(),
+            });
+        }
+
+        (self.func)(args)
+    }
+
+    /// Returns the function's [`FunctionInfo`].
+    pub fn info(&self) -> &FunctionInfo {
+        &self.info
+    }
+
+    /// The name of the function, if it has one.
+    pub fn name(&self) -> Option<&str> {
+        self.info.name()
+    }
+}
+
+/// Outputs the function signature.
+///
+/// This takes the format: `DynamicFunction(fn {name}({arg_name}: {arg_type}, ...) -> {return_type})`.
+///
+/// Names for arguments and the function itself are optional and will default to `_` if not provided.
+impl Debug for DynamicFunction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = self.info.name().unwrap_or("_");
+        write!(f, "DynamicFunction(fn {name}(")?;
+
+        for (index, arg) in self.info.args().iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            let name = arg.name().unwrap_or("_");
+            let prefix = match arg.ownership() {
+                Ownership::Ref => "&",
+                Ownership::Owned => "",
+            };
+            write!(f, "{name}: {prefix}{}", arg.type_path())?;
+        }
+
+        write!(f, ") -> {})", self.info.return_info().type_path())
+    }
+}
+
+impl IntoFunction<()> for DynamicFunction {
+    #[inline]
+    fn into_function(self) -> DynamicFunction {
+        self
+    }
+}
@@ -0,0 +1,104 @@
+use crate::func::ArgInfo;
+use crate::TypePath;
+use std::{any::TypeId, borrow::Cow};
+
+/// Type information for a [`DynamicFunction`].
+///
+/// [`DynamicFunction`]: crate::func::DynamicFunction
+#[derive(Debug, Clone)]
+pub struct FunctionInfo {
+    name: Option<Cow<'static, str>>,
+    args: Vec<ArgInfo>,
+    return_info: ReturnInfo,
+}
+
+impl FunctionInfo {
+    /// Create a new [`FunctionInfo`] from the given argument and return information.
+    pub fn new(args: Vec<ArgInfo>, return_info: ReturnInfo) -> Self {
+        Self {
+            name: None,
+            args,
+            return_info,
+        }
+    }
+
+    /// Sets the name of the function.
+    pub fn with_name(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets the names of the arguments, in order.
+    ///
+    /// Any names beyond the number of arguments are ignored.
+    pub fn with_arg_names<N: Into<Cow<'static, str>>>(
+        mut self,
+        names: impl IntoIterator<Item = N>,
+    ) -> Self {
+        for (arg, name) in self.args.iter_mut().zip(names) {
+            arg.set_name(name);
+        }
+        self
+    }
+
+    /// The name of the function, if it has one.
+    ///
+    /// Functions created from named `fn` items are given their [type name] automatically,
+    /// while closures are unnamed by default.
+    ///
+    /// [type name]: std::any::type_name
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// The arguments of the function.
+    pub fn args(&self) -> &[ArgInfo] {
+        &self.args
+    }
+
+    /// The number of arguments the function takes.
+    pub fn arg_count(&self) -> usize {
+        self.args.len()
+    }
+
+    /// The return information of the function.
+    pub fn return_info(&self) -> &ReturnInfo {
+        &self.return_info
+    }
+}
+
+/// Type information for the return value of a [`DynamicFunction`].
+///
+/// [`DynamicFunction`]: crate::func::DynamicFunction
+#[derive(Debug, Clone)]
+pub struct ReturnInfo {
+    type_path: &'static str,
+    type_id: TypeId,
+}
+
+impl ReturnInfo {
+    /// Create a new [`ReturnInfo`] for a return value of type `T`.
+    pub fn new<T: TypePath + 'static>() -> Self {
+        Self {
+            type_path: T::type_path(),
+            type_id: TypeId::of::<T>(),
+        }
+    }
+
+    /// The [type path] of the return value.
+    ///
+    /// [type path]: TypePath::type_path
+    pub fn type_path(&self) -> &'static str {
+        self.type_path
+    }
+
+    /// The [`TypeId`] of the return value.
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    /// Check if the given type matches the return type.
+    pub fn is<T: 'static>(&self) -> bool {
+        TypeId::of::<T>() == self.type_id
+    }
+}
@@ -0,0 +1,141 @@
+use crate::func::{ArgInfo, DynamicFunction, FunctionError, FunctionInfo, Ownership, ReturnInfo};
+use crate::{FromReflect, Reflect, TypePath};
+use bevy_utils::all_tuples;
+use std::{borrow::Cow, marker::PhantomData};
+
+/// A trait for types that can be converted into a [`DynamicFunction`].
+///
+/// This trait is implemented for functions and closures with up to 12 arguments
+/// where every argument implements [`FromReflect`] and the return type implements [`Reflect`].
+/// It is also implemented for functions whose first argument is a shared reference to a
+/// reflected type, such as methods taking `&self`, followed by up to 12 such arguments.
+///
+/// The `Marker` type parameter is used to disambiguate between these implementations
+/// and can be ignored in most cases.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_reflect::func::IntoFunction;
+/// let func = (|a: f32, b: f32| a * b).into_function();
+/// let result = func.call(&[&2.0_f32, &3.0_f32]).unwrap();
+/// assert_eq!(result.downcast_ref::<f32>(), Some(&6.0));
+/// ```
+pub trait IntoFunction<Marker> {
+    /// Converts [`Self`] into a [`DynamicFunction`].
+    fn into_function(self) -> DynamicFunction;
+}
+
+/// Marker type used by [`IntoFunction`] for functions that take their first argument by reference.
+#[doc(hidden)]
+pub struct Method<Receiver, Signature>(PhantomData<fn() -> (Receiver, Signature)>);
+
+/// Returns the [type name] of `F` if it refers to a named function rather than a closure.
+///
+/// [type name]: std::any::type_name
+fn function_name<F>() -> Option<Cow<'static, str>> {
+    let name = std::any::type_name::<F>();
+    if name.contains("{{closure}}") {
+        None
+    } else {
+        Some(Cow::Borrowed(name))
+    }
+}
+
+fn invalid_arg<T: TypePath>(index: usize, arg: &dyn Reflect) -> FunctionError {
+    FunctionError::InvalidArgType {
+        index,
+        expected: Cow::Borrowed(T::type_path()),
+        received: Cow::Owned(arg.reflect_type_path().to_owned()),
+    }
+}
+
+fn create_info<F, R: TypePath + 'static>(args: Vec<ArgInfo>) -> FunctionInfo {
+    let info = FunctionInfo::new(args, ReturnInfo::new::<R>());
+    match function_name::<F>() {
+        Some(name) => info.with_name(name),
+        None => info,
+    }
+}
+
+macro_rules! impl_into_function {
+    ($($Arg:ident),*) => {
+        // === Owned Arguments === //
+        impl<$($Arg,)* R, F> IntoFunction<fn($($Arg),*) -> R> for F
+        where
+            $($Arg: FromReflect + TypePath,)*
+            R: Reflect + TypePath,
+            F: Fn($($Arg),*) -> R + Send + Sync + 'static,
+        {
+            #[allow(non_snake_case, unused_mut)]
+            fn into_function(self) -> DynamicFunction {
+                let mut _index = 0;
+                let info = create_info::<F, R>(vec![
+                    $({
+                        _index += 1;
+                        ArgInfo::new::<$Arg>(_index - 1, Ownership::Owned)
+                    }),*
+                ]);
+
+                DynamicFunction::new(
+                    move |args| {
+                        let mut _args = args.iter().enumerate();
+                        $(
+                            let (index, arg) = _args
+                                .next()
+                                .expect("argument count should be checked by `DynamicFunction::call`");
+                            let $Arg = <$Arg as FromReflect>::from_reflect(*arg)
+                                .ok_or_else(|| invalid_arg::<$Arg>(index, *arg))?;
+                        )*
+                        Ok(Box::new((self)($($Arg),*)) as Box<dyn Reflect>)
+                    },
+                    info,
+                )
+            }
+        }
+
+        // === Reference Receiver === //
+        impl<Receiver, $($Arg,)* R, F> IntoFunction<Method<Receiver, fn($($Arg),*) -> R>> for F
+        where
+            Receiver: Reflect + TypePath,
+            $($Arg: FromReflect + TypePath,)*
+            R: Reflect + TypePath,
+            F: Fn(&Receiver, $($Arg),*) -> R + Send + Sync + 'static,
+        {
+            #[allow(non_snake_case, unused_mut)]
+            fn into_function(self) -> DynamicFunction {
+                let mut _index = 1;
+                let info = create_info::<F, R>(vec![
+                    ArgInfo::new::<Receiver>(0, Ownership::Ref),
+                    $({
+                        _index += 1;
+                        ArgInfo::new::<$Arg>(_index - 1, Ownership::Owned)
+                    }),*
+                ]);
+
+                DynamicFunction::new(
+                    move |args| {
+                        let mut _args = args.iter().enumerate();
+                        let (_, receiver) = _args
+                            .next()
+                            .expect("argument count should be checked by `DynamicFunction::call`");
+                        let receiver = receiver
+                            .downcast_ref::<Receiver>()
+                            .ok_or_else(|| invalid_arg::<Receiver>(0, *receiver))?;
+                        $(
+                            let (index, arg) = _args
+                                .next()
+                                .expect("argument count should be checked by `DynamicFunction::call`");
+                            let $Arg = <$Arg as FromReflect>::from_reflect(*arg)
+                                .ok_or_else(|| invalid_arg::<$Arg>(index, *arg))?;
+                        )*
+                        Ok(Box::new((self)(receiver, $($Arg),*)) as Box<dyn Reflect>)
+                    },
+                    info,
+                )
+            }
+        }
+    };
+}
+
+all_tuples!(impl_into_function, 0, 12, A);
@@ -0,0 +1,89 @@
+use crate::func::{DynamicFunction, FunctionResult};
+use crate::Reflect;
+use bevy_utils::HashMap;
+use std::borrow::Cow;
+
+/// Type data containing the reflected methods registered for a type.
+///
+/// Methods are added with [`TypeRegistry::register_method`]
+/// and are stored as [`DynamicFunction`]s whose first argument is the receiver.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_reflect::{Reflect, TypeRegistry};
+/// # use bevy_reflect::func::ReflectMethods;
+/// #[derive(Reflect)]
+/// struct Counter(u32);
+///
+/// impl Counter {
+///     fn get(&self) -> u32 {
+///         self.0
+///     }
+/// }
+///
+/// let mut registry = TypeRegistry::default();
+/// registry.register::<Counter>();
+/// registry.register_method::<Counter, _>("get", Counter::get);
+///
+/// let counter: Box<dyn Reflect> = Box::new(Counter(5));
+/// let methods = registry
+///     .get_type_data::<ReflectMethods>(std::any::TypeId::of::<Counter>())
+///     .unwrap();
+/// let result = methods.call("get", counter.as_reflect(), &[]).unwrap().unwrap();
+/// assert_eq!(result.downcast_ref::<u32>(), Some(&5));
+/// ```
+///
+/// [`TypeRegistry::register_method`]: crate::TypeRegistry::register_method
+#[derive(Clone, Default)]
+pub struct ReflectMethods {
+    methods: HashMap<Cow<'static, str>, DynamicFunction>,
+}
+
+impl ReflectMethods {
+    /// Inserts a method with the given name, replacing any existing method with that name.
+    pub fn insert(&mut self, name: impl Into<Cow<'static, str>>, method: DynamicFunction) {
+        let name = name.into();
+        self.methods.insert(name.clone(), method.with_name(name));
+    }
+
+    /// Returns the method with the given name, if it exists.
+    pub fn get(&self, name: &str) -> Option<&DynamicFunction> {
+        self.methods.get(name)
+    }
+
+    /// Returns an iterator over the names and functions of all registered methods.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &DynamicFunction)> {
+        self.methods
+            .iter()
+            .map(|(name, method)| (name.as_ref(), method))
+    }
+
+    /// Returns the number of registered methods.
+    pub fn len(&self) -> usize {
+        self.methods.len()
+    }
+
+    /// Returns `true` if no methods are registered.
+    pub fn is_empty(&self) -> bool {
+        self.methods.is_empty()
+    }
+
+    /// Calls the method with the given name on `receiver`.
+    ///
+    /// The receiver is passed as the first argument, followed by `args`.
+    ///
+    /// Returns `None` if no method with the given name exists.
+    pub fn call(
+        &self,
+        name: &str,
+        receiver: &dyn Reflect,
+        args: &[&dyn Reflect],
+    ) -> Option<FunctionResult> {
+        let method = self.get(name)?;
+        let mut full_args = Vec::with_capacity(args.len() + 1);
+        full_args.push(receiver);
+        full_args.extend_from_slice(args);
+        Some(method.call(&full_args))
+    }
+}
@@ -0,0 +1,181 @@
+//! Reflection-based dynamic functions.
+//!
+//! This module provides a way to convert Rust functions and closures into [`DynamicFunction`]s,
+//! which can be called with a list of [`&dyn Reflect`](crate::Reflect) arguments
+//! and return a boxed [`Reflect`](crate::Reflect) value.
+//!
+//! This allows functions to be stored, inspected, and invoked entirely at runtime,
+//! which is useful for scripting bridges and editor-exposed callbacks.
+//!
+//! # Example
+//!
+//! ```
+//! # use bevy_reflect::Reflect;
+//! # use bevy_reflect::func::{DynamicFunction, IntoFunction};
+//! fn add(a: i32, b: i32) -> i32 {
+//!     a + b
+//! }
+//!
+//! let func: DynamicFunction = add.into_function();
+//! assert_eq!(func.info().arg_count(), 2);
+//!
+//! let result = func.call(&[&25_i32, &75_i32]).unwrap();
+//! assert_eq!(result.downcast_ref::<i32>(), Some(&100));
+//! ```
+//!
+//! # Methods
+//!
+//! Functions whose first argument is a shared reference, such as methods taking `&self`,
+//! can also be converted into a [`DynamicFunction`].
+//! The receiver is passed by reference as the first argument and is never cloned.
+//!
+//! These can be registered per type with [`TypeRegistry::register_method`]
+//! and later looked up through the [`ReflectMethods`] type data.
+//!
+//! # Limitations
+//!
+//! All arguments other than the receiver are passed by value,
+//! and so must implement [`FromReflect`](crate::FromReflect).
+//! Return values must implement [`Reflect`](crate::Reflect).
+//! Functions may take at most 12 arguments, not counting the receiver.
+//!
+//! [`TypeRegistry::register_method`]: crate::TypeRegistry::register_method
+
+mod args;
+mod error;
+mod function;
+mod info;
+mod into_function;
+mod methods;
+
+pub use args::*;
+pub use error::*;
+pub use function::*;
+pub use info::*;
+pub use into_function::*;
+pub use methods::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as bevy_reflect;
+    use crate::{Reflect, TypeRegistry};
+
+    #[derive(Reflect, Debug, PartialEq)]
+    struct Foo {
+        value: i32,
+    }
+
+    impl Foo {
+        fn value_plus(&self, amount: i32) -> i32 {
+            self.value + amount
+        }
+    }
+
+    #[test]
+    fn should_call_function() {
+        fn add(a: i32, b: i32) -> i32 {
+            a + b
+        }
+
+        let func = add.into_function();
+        let result = func.call(&[&1_i32, &2_i32]).unwrap();
+        assert_eq!(result.downcast_ref::<i32>(), Some(&3));
+    }
+
+    #[test]
+    fn should_call_closure() {
+        let offset = 10_i32;
+        let func = (move |a: i32| a + offset).into_function();
+        let result = func.call(&[&5_i32]).unwrap();
+        assert_eq!(result.downcast_ref::<i32>(), Some(&15));
+    }
+
+    #[test]
+    fn should_return_unit() {
+        fn noop() {}
+
+        let func = noop.into_function();
+        let result = func.call(&[]).unwrap();
+        assert!(result.is::<()>());
+    }
+
+    #[test]
+    fn should_call_method() {
+        let func = Foo::value_plus.into_function();
+        let foo = Foo { value: 123 };
+        let result = func.call(&[&foo, &2_i32]).unwrap();
+        assert_eq!(result.downcast_ref::<i32>(), Some(&125));
+    }
+
+    #[test]
+    fn should_contain_info() {
+        fn concat(a: String, b: String) -> String {
+            a + &b
+        }
+
+        let func = concat.into_function().with_arg_names(["a", "b"]);
+        let info = func.info();
+
+        assert!(info.name().unwrap().ends_with("concat"));
+        assert_eq!(info.arg_count(), 2);
+        assert_eq!(info.args()[0].name(), Some("a"));
+        assert_eq!(info.args()[1].index(), 1);
+        assert!(info.args()[1].is::<String>());
+        assert!(info.return_info().is::<String>());
+
+        let info = Foo::value_plus.into_function().info().clone();
+        assert_eq!(info.args()[0].ownership(), Ownership::Ref);
+        assert_eq!(info.args()[1].ownership(), Ownership::Owned);
+    }
+
+    #[test]
+    fn should_error_on_invalid_args() {
+        fn add(a: i32, b: i32) -> i32 {
+            a + b
+        }
+
+        let func = add.into_function();
+        assert_eq!(
+            func.call(&[&1_i32]).unwrap_err(),
+            FunctionError::ArgCount {
+                expected: 2,
+                received: 1
+            }
+        );
+
+        let result = func.call(&[&1_i32, &String::from("2")]);
+        assert!(matches!(
+            result,
+            Err(FunctionError::InvalidArgType { index: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn should_register_functions_and_methods() {
+        fn double(value: i32) -> i32 {
+            value * 2
+        }
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<Foo>();
+        registry.register_function("double", double);
+        registry.register_method::<Foo, _>("value_plus", Foo::value_plus);
+
+        let func = registry.get_function("double").unwrap();
+        assert_eq!(func.name(), Some("double"));
+        let result = func.call(&[&4_i32]).unwrap();
+        assert_eq!(result.downcast_ref::<i32>(), Some(&8));
+
+        let foo: Box<dyn Reflect> = Box::new(Foo { value: 1 });
+        let methods = registry
+            .get_type_data::<ReflectMethods>(std::any::TypeId::of::<Foo>())
+            .unwrap();
+        let result = methods
+            .call("value_plus", foo.as_reflect(), &[&2_i32])
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.downcast_ref::<i32>(), Some(&3));
+        assert!(methods.call("missing", foo.as_reflect(), &[]).is_none());
+    }
+}
@@ -0,0 +1,252 @@
+use crate::utility::GenericTypeInfoCell;
+use crate::{
+    self as bevy_reflect, impl_type_path, map_apply, map_partial_eq, DynamicMap, FromReflect,
+    FromType, GetTypeRegistration, Map, MapInfo, MapIter, Reflect, ReflectFromPtr, ReflectKind,
+    ReflectMut, ReflectOwned, ReflectRef, TypeInfo, TypePath, TypeRegistration, Typed,
+};
+use indexmap::IndexMap;
+use std::any::Any;
+use std::hash::{BuildHasher, Hash};
+
+impl<K, V, S> Map for IndexMap<K, V, S>
+where
+    K: FromReflect + TypePath + Eq + Hash,
+    V: FromReflect + TypePath,
+    S: TypePath + BuildHasher + Send + Sync,
+{
+    fn get(&self, key: &dyn Reflect) -> Option<&dyn Reflect> {
+        key.downcast_ref::<K>()
+            .and_then(|key| IndexMap::get(self, key))
+            .map(|value| value as &dyn Reflect)
+    }
+
+    fn get_mut(&mut self, key: &dyn Reflect) -> Option<&mut dyn Reflect> {
+        key.downcast_ref::<K>()
+            .and_then(move |key| IndexMap::get_mut(self, key))
+            .map(|value| value as &mut dyn Reflect)
+    }
+
+    fn get_at(&self, index: usize) -> Option<(&dyn Reflect, &dyn Reflect)> {
+        self.get_index(index)
+            .map(|(key, value)| (key as &dyn Reflect, value as &dyn Reflect))
+    }
+
+    fn get_at_mut(&mut self, index: usize) -> Option<(&dyn Reflect, &mut dyn Reflect)> {
+        self.get_index_mut(index)
+            .map(|(key, value)| (key as &dyn Reflect, value as &mut dyn Reflect))
+    }
+
+    fn len(&self) -> usize {
+        IndexMap::len(self)
+    }
+
+    fn iter(&self) -> MapIter {
+        MapIter::new(self)
+    }
+
+    fn drain(self: Box<Self>) -> Vec<(Box<dyn Reflect>, Box<dyn Reflect>)> {
+        self.into_iter()
+            .map(|(key, value)| {
+                (
+                    Box::new(key) as Box<dyn Reflect>,
+                    Box::new(value) as Box<dyn Reflect>,
+                )
+            })
+            .collect()
+    }
+
+    fn clone_dynamic(&self) -> DynamicMap {
+        let mut dynamic_map = DynamicMap::default();
+        dynamic_map.set_represented_type(self.get_represented_type_info());
+        for (k, v) in self {
+            let key = K::from_reflect(k).unwrap_or_else(|| {
+                panic!(
+                    "Attempted to clone invalid key of type {}.",
+                    k.reflect_type_path()
+                )
+            });
+            dynamic_map.insert_boxed(Box::new(key), v.clone_value());
+        }
+        dynamic_map
+    }
+
+    fn insert_boxed(
+        &mut self,
+        key: Box<dyn Reflect>,
+        value: Box<dyn Reflect>,
+    ) -> Option<Box<dyn Reflect>> {
+        let key = K::take_from_reflect(key).unwrap_or_else(|key| {
+            panic!(
+                "Attempted to insert invalid key of type {}.",
+                key.reflect_type_path()
+            )
+        });
+        let value = V::take_from_reflect(value).unwrap_or_else(|value| {
+            panic!(
+                "Attempted to insert invalid value of type {}.",
+                value.reflect_type_path()
+            )
+        });
+        self.insert(key, value)
+            .map(|old_value| Box::new(old_value) as Box<dyn Reflect>)
+    }
+
+    fn remove(&mut self, key: &dyn Reflect) -> Option<Box<dyn Reflect>> {
+        let mut from_reflect = None;
+        key.downcast_ref::<K>()
+            .or_else(|| {
+                from_reflect = K::from_reflect(key);
+                from_reflect.as_ref()
+            })
+            // Shift the remaining entries so that insertion order is preserved
+            .and_then(|key| self.shift_remove(key))
+            .map(|value| Box::new(value) as Box<dyn Reflect>)
+    }
+}
+
+impl<K, V, S> Reflect for IndexMap<K, V, S>
+where
+    K: FromReflect + TypePath + Eq + Hash,
+    V: FromReflect + TypePath,
+    S: TypePath + BuildHasher + Send + Sync,
+{
+    fn get_represented_type_info(&self) -> Option<&'static TypeInfo> {
+        Some(<Self as Typed>::type_info())
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[inline]
+    fn into_reflect(self: Box<Self>) -> Box<dyn Reflect> {
+        self
+    }
+
+    fn as_reflect(&self) -> &dyn Reflect {
+        self
+    }
+
+    fn as_reflect_mut(&mut self) -> &mut dyn Reflect {
+        self
+    }
+
+    fn apply(&mut self, value: &dyn Reflect) {
+        map_apply(self, value);
+    }
+
+    fn set(&mut self, value: Box<dyn Reflect>) -> Result<(), Box<dyn Reflect>> {
+        *self = value.take()?;
+        Ok(())
+    }
+
+    fn reflect_kind(&self) -> ReflectKind {
+        ReflectKind::Map
+    }
+
+    fn reflect_ref(&self) -> ReflectRef {
+        ReflectRef::Map(self)
+    }
+
+    fn reflect_mut(&mut self) -> ReflectMut {
+        ReflectMut::Map(self)
+    }
+
+    fn reflect_owned(self: Box<Self>) -> ReflectOwned {
+        ReflectOwned::Map(self)
+    }
+
+    fn clone_value(&self) -> Box<dyn Reflect> {
+        Box::new(self.clone_dynamic())
+    }
+
+    fn reflect_partial_eq(&self, value: &dyn Reflect) -> Option<bool> {
+        map_partial_eq(self, value)
+    }
+}
+
+impl<K, V, S> Typed for IndexMap<K, V, S>
+where
+    K: FromReflect + TypePath + Eq + Hash,
+    V: FromReflect + TypePath,
+    S: TypePath + BuildHasher + Send + Sync,
+{
+    fn type_info() -> &'static TypeInfo {
+        static CELL: GenericTypeInfoCell = GenericTypeInfoCell::new();
+        CELL.get_or_insert::<Self, _>(|| TypeInfo::Map(MapInfo::new::<Self, K, V>()))
+    }
+}
+
+impl<K, V, S> GetTypeRegistration for IndexMap<K, V, S>
+where
+    K: FromReflect + TypePath + Eq + Hash,
+    V: FromReflect + TypePath,
+    S: TypePath + BuildHasher + Send + Sync,
+{
+    fn get_type_registration() -> TypeRegistration {
+        let mut registration = TypeRegistration::of::<Self>();
+        registration.insert::<ReflectFromPtr>(FromType::<Self>::from_type());
+        registration
+    }
+}
+
+impl<K, V, S> FromReflect for IndexMap<K, V, S>
+where
+    K: FromReflect + TypePath + Eq + Hash,
+    V: FromReflect + TypePath,
+    S: TypePath + BuildHasher + Default + Send + Sync,
+{
+    fn from_reflect(reflect: &dyn Reflect) -> Option<Self> {
+        if let ReflectRef::Map(ref_map) = reflect.reflect_ref() {
+            let mut new_map = Self::with_capacity_and_hasher(ref_map.len(), S::default());
+            for (key, value) in ref_map.iter() {
+                let new_key = K::from_reflect(key)?;
+                let new_value = V::from_reflect(value)?;
+                new_map.insert(new_key, new_value);
+            }
+            Some(new_map)
+        } else {
+            None
+        }
+    }
+}
+
+impl_type_path!(::indexmap::IndexMap<K, V, S>);
+
+#[cfg(test)]
+mod tests {
+    use crate::{DynamicMap, FromReflect, Map, Reflect};
+    use indexmap::IndexMap;
+
+    #[test]
+    fn should_preserve_insertion_order() {
+        let mut map: IndexMap<String, usize> = IndexMap::new();
+        map.insert(String::from("c"), 0);
+        map.insert(String::from("a"), 1);
+        map.insert(String::from("b"), 2);
+
+        let (key, value) = Map::get_at(&map, 1).unwrap();
+        assert_eq!(key.downcast_ref::<String>().unwrap(), "a");
+        assert_eq!(value.downcast_ref::<usize>(), Some(&1));
+
+        Map::remove(&mut map, &String::from("c"));
+        let keys = map.keys().cloned().collect::<Vec<_>>();
+        assert_eq!(keys, vec![String::from("a"), String::from("b")]);
+
+        let mut dynamic = DynamicMap::default();
+        dynamic.insert(String::from("z"), 3_usize);
+        let converted = IndexMap::<String, usize>::from_reflect(&dynamic).unwrap();
+        assert_eq!(converted.get("z"), Some(&3));
+
+        map.apply(&dynamic);
+        assert_eq!(map.get_index(2), Some((&String::from("z"), &3)));
+    }
+}
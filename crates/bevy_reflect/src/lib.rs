@@ -177,6 +177,28 @@
 //! assert_eq!(None, value);
 //! ```
 //!
+//! ## Diffing
+//!
+//! Two values of the same type can be compared using [`Reflect::diff`],
+//! which produces a [`ReflectPatch`] containing only the fields that changed.
+//! The patch can then be applied to another value using [`Reflect::apply_patch`].
+//!
+//! ```
+//! # use bevy_reflect::Reflect;
+//! # #[derive(Reflect, Clone, Debug, PartialEq)]
+//! # struct MyStruct {
+//! #   foo: i32
+//! # }
+//! let original = MyStruct { foo: 123 };
+//! let modified = MyStruct { foo: 456 };
+//!
+//! let patch = original.diff(&modified);
+//!
+//! let mut value = original.clone();
+//! value.apply_patch(&patch).unwrap();
+//! assert_eq!(value, modified);
+//! ```
+//!
 //! ## `FromReflect`
 //!
 //! It's important to remember that dynamic types are _not_ the concrete type they may be representing.
@@ -320,6 +342,29 @@
 //! See the [trait reflection example](https://github.com/bevyengine/bevy/blob/latest/examples/reflection/trait_reflection.rs)
 //! for more information and usage details.
 //!
+//! ## Reflecting Remote Types
+//!
+//! Rust's [orphan rule] prevents implementing [`Reflect`] for types defined in other crates.
+//! Such types can still be reflected using the [`#[reflect_remote]`](reflect_remote) attribute,
+//! which generates a transparent wrapper around the remote type from a mirror of its definition.
+//!
+//! Both remote structs and remote enums are supported.
+//! Fields of remote types can then be reflected through that wrapper
+//! using the `#[reflect(remote = Wrapper)]` field attribute.
+//! See [`ReflectRemote`] for an example.
+//!
+//! Since the mirror must name every field of the remote type,
+//! this does not work for containers with private internals, such as most arenas.
+//! Those should implement [`List`] or [`Map`] directly,
+//! as is done for [`SmallVec`] and [`IndexMap`] behind the `smallvec` and `indexmap` features.
+//!
+//! ## Custom Attributes
+//!
+//! Arbitrary reflected values can be attached to a type, its fields, or its enum variants using `#[reflect(@...)]`.
+//! The derive macro stores these in the [`ReflectAttributes`](attributes::ReflectAttributes) type data,
+//! allowing tools such as inspectors to read hints like value ranges without relying on external conventions.
+//! See the [`attributes`] module for more information.
+//!
 //! # Serialization
 //!
 //! By using reflection, we are also able to get serialization capabilities for free.
@@ -399,13 +444,13 @@
 //!
 //! ## Function Reflection
 //!
-//! Another limitation is the inability to fully reflect functions and methods.
-//! Most languages offer some way of calling methods dynamically,
-//! but Rust makes this very difficult to do.
-//! For non-generic methods, this can be done by registering custom [type data] that
-//! contains function pointers.
-//! For generic methods, the same can be done but will typically require manual monomorphization
-//! (i.e. manually specifying the types the generic method can take).
+//! Rust offers no built-in way of calling functions and methods dynamically.
+//! The [`func`] module works around this by converting functions and closures into
+//! [`DynamicFunction`]s, which can be called with reflected arguments.
+//! However, all non-receiver arguments must be passed by value (and so implement [`FromReflect`]),
+//! receivers can only be taken by shared reference,
+//! and generic functions must be manually monomorphized
+//! (i.e. manually specifying the types the generic function can take).
 //!
 //! ## Manual Registration
 //!
@@ -428,6 +473,15 @@
 //! These dependencies are used by the [Bevy] game engine and must define their reflection implementations
 //! within this crate due to Rust's [orphan rule].
 //!
+//! ## `indexmap`
+//!
+//! | Default | Dependencies  |
+//! | :-----: | :-----------: |
+//! | ❌      | [`indexmap`]  |
+//!
+//! This feature implements the reflection traits for [`IndexMap`],
+//! which is reflected as a [`Map`] that preserves the insertion order of its entries.
+//!
 //! ## `documentation`
 //!
 //! | Default | Dependencies                                  |
@@ -464,17 +518,23 @@
 //! [`bevy_math`]: https://docs.rs/bevy_math/latest/bevy_math/
 //! [`glam`]: https://docs.rs/glam/latest/glam/
 //! [`smallvec`]: https://docs.rs/smallvec/latest/smallvec/
+//! [`indexmap`]: https://docs.rs/indexmap/latest/indexmap/
+//! [`IndexMap`]: https://docs.rs/indexmap/latest/indexmap/map/struct.IndexMap.html
+//! [`SmallVec`]: https://docs.rs/smallvec/latest/smallvec/struct.SmallVec.html
 //! [orphan rule]: https://doc.rust-lang.org/book/ch10-02-traits.html#implementing-a-trait-on-a-type:~:text=But%20we%20can%E2%80%99t,implementation%20to%20use.
 //! [`bevy_reflect_derive/documentation`]: bevy_reflect_derive
 //! [derive `Reflect`]: derive@crate::Reflect
+//! [`DynamicFunction`]: func::DynamicFunction
 
 mod array;
+mod diff;
 mod fields;
 mod from_reflect;
 mod list;
 mod map;
 mod path;
 mod reflect;
+mod remote;
 mod struct_trait;
 mod tuple;
 mod tuple_struct;
@@ -484,6 +544,8 @@ mod type_registry;
 mod impls {
     #[cfg(feature = "glam")]
     mod glam;
+    #[cfg(feature = "indexmap")]
+    mod indexmap;
     #[cfg(feature = "bevy_math")]
     mod math {
         mod primitives2d;
@@ -500,6 +562,8 @@ mod impls {
 }
 
 mod enums;
+pub mod attributes;
+pub mod func;
 pub mod serde;
 pub mod std_traits;
 pub mod utility;
@@ -515,6 +579,7 @@ pub mod prelude {
 }
 
 pub use array::*;
+pub use diff::*;
 pub use enums::*;
 pub use fields::*;
 pub use from_reflect::*;
@@ -522,6 +587,7 @@ pub use list::*;
 pub use map::*;
 pub use path::*;
 pub use reflect::*;
+pub use remote::*;
 pub use struct_trait::*;
 pub use tuple::*;
 pub use tuple_struct::*;
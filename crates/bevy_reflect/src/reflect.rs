@@ -1,7 +1,7 @@
 use crate::{
     array_debug, enum_debug, list_debug, map_debug, serde::Serializable, struct_debug, tuple_debug,
-    tuple_struct_debug, Array, DynamicTypePath, Enum, List, Map, Struct, Tuple, TupleStruct,
-    TypeInfo, TypePath, Typed, ValueInfo,
+    tuple_struct_debug, Array, DiffOptions, DynamicTypePath, Enum, List, Map, PatchError,
+    ReflectPatch, Struct, Tuple, TupleStruct, TypeInfo, TypePath, Typed, ValueInfo,
 };
 use std::{
     any::{Any, TypeId},
@@ -225,6 +225,26 @@ pub trait Reflect: DynamicTypePath + Any + Send + Sync {
     /// containing the trait object.
     fn set(&mut self, value: Box<dyn Reflect>) -> Result<(), Box<dyn Reflect>>;
 
+    /// Computes a [`ReflectPatch`] describing the changes needed to turn this value into `other`.
+    ///
+    /// Only fields and elements which differ are included in the patch.
+    /// Lists and maps are compared using the default [`DiffOptions`];
+    /// use [`DiffOptions::diff`] to choose a different strategy.
+    fn diff(&self, other: &dyn Reflect) -> ReflectPatch {
+        DiffOptions::default().diff(self.as_reflect(), other)
+    }
+
+    /// Applies a [`ReflectPatch`] to this value.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`PatchError`] if this value does not have the shape described by the patch,
+    /// such as when a patched field or key is missing.
+    /// Any changes made before the error was encountered are not rolled back.
+    fn apply_patch(&mut self, patch: &ReflectPatch) -> Result<(), PatchError> {
+        patch.apply(self.as_reflect_mut())
+    }
+
     /// Returns a zero-sized enumeration of "kinds" of type.
     ///
     /// See [`ReflectKind`].
@@ -0,0 +1,222 @@
+use crate::Reflect;
+
+/// A trait used to access a remote type through its reflected wrapper.
+///
+/// Because of Rust's orphan rule, `Reflect` cannot be implemented for types defined in other crates.
+/// The [`#[reflect_remote]`](crate::reflect_remote) attribute works around this by generating
+/// a `#[repr(transparent)]` wrapper around the remote type which implements `Reflect` on its behalf.
+/// This trait is implemented for those wrappers and converts between the wrapper and the remote type.
+///
+/// Fields containing a remote type can be reflected through its wrapper
+/// using the `#[reflect(remote = Wrapper)]` field attribute.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_reflect::{reflect_remote, Reflect, ReflectRemote, Struct};
+/// mod external_crate {
+///     pub struct Timer {
+///         pub elapsed: f32,
+///         pub duration: f32,
+///     }
+/// }
+///
+/// #[reflect_remote(external_crate::Timer)]
+/// struct TimerWrapper {
+///     elapsed: f32,
+///     duration: f32,
+/// }
+///
+/// #[derive(Reflect)]
+/// struct Cooldown {
+///     #[reflect(remote = TimerWrapper)]
+///     timer: external_crate::Timer,
+/// }
+///
+/// let cooldown = Cooldown {
+///     timer: external_crate::Timer {
+///         elapsed: 0.5,
+///         duration: 2.0,
+///     },
+/// };
+///
+/// let timer = cooldown.field("timer").unwrap();
+/// let timer = timer.downcast_ref::<TimerWrapper>().unwrap();
+/// assert_eq!(timer.as_remote().duration, 2.0);
+/// ```
+pub trait ReflectRemote: Reflect {
+    /// The remote type this wrapper represents.
+    type Remote;
+
+    /// Returns a reference to the wrapped remote value.
+    fn as_remote(&self) -> &Self::Remote;
+    /// Returns a mutable reference to the wrapped remote value.
+    fn as_remote_mut(&mut self) -> &mut Self::Remote;
+    /// Unwraps the remote value.
+    fn into_remote(self) -> Self::Remote;
+
+    /// Reinterprets a reference to the remote value as a reference to the wrapper.
+    fn as_wrapper(remote: &Self::Remote) -> &Self;
+    /// Reinterprets a mutable reference to the remote value as a mutable reference to the wrapper.
+    fn as_wrapper_mut(remote: &mut Self::Remote) -> &mut Self;
+    /// Wraps the remote value.
+    fn into_wrapper(remote: Self::Remote) -> Self;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as bevy_reflect;
+    use crate::{
+        reflect_remote, DynamicEnum, DynamicStruct, DynamicTuple, DynamicVariant, Enum,
+        FromReflect, Reflect, ReflectRemote, Struct, TupleStruct, TypeInfo, TypePath, Typed,
+    };
+
+    mod external_crate {
+        pub struct Timer {
+            pub elapsed: f32,
+            pub duration: f32,
+        }
+
+        pub struct Pair<T>(pub T, pub T);
+
+        pub enum Either<L, R> {
+            Left(L),
+            Right { value: R },
+            Neither,
+        }
+    }
+
+    #[reflect_remote(external_crate::Timer)]
+    struct TimerWrapper {
+        elapsed: f32,
+        duration: f32,
+    }
+
+    #[reflect_remote(external_crate::Pair<T>)]
+    struct PairWrapper<T: FromReflect + TypePath>(T, T);
+
+    #[reflect_remote(external_crate::Either<L, R>)]
+    enum EitherWrapper<L: FromReflect + TypePath, R: FromReflect + TypePath> {
+        Left(L),
+        Right { value: R },
+        Neither,
+    }
+
+    #[derive(Reflect)]
+    struct Cooldown {
+        #[reflect(remote = TimerWrapper)]
+        timer: external_crate::Timer,
+        charges: u32,
+    }
+
+    #[test]
+    fn should_reflect_remote_struct() {
+        let mut wrapper = TimerWrapper(external_crate::Timer {
+            elapsed: 1.0,
+            duration: 3.0,
+        });
+
+        assert_eq!(
+            wrapper.field("duration").unwrap().downcast_ref(),
+            Some(&3.0_f32)
+        );
+
+        wrapper.field_mut("elapsed").unwrap().apply(&2.0_f32);
+        assert_eq!(wrapper.as_remote().elapsed, 2.0);
+
+        let TypeInfo::Struct(info) = TimerWrapper::type_info() else {
+            panic!("expected struct info");
+        };
+        assert!(info.field("elapsed").unwrap().is::<f32>());
+    }
+
+    #[test]
+    fn should_reflect_remote_tuple_struct() {
+        let mut wrapper = PairWrapper(external_crate::Pair(1_i32, 2_i32));
+        wrapper.field_mut(1).unwrap().apply(&5_i32);
+
+        let remote = wrapper.into_remote();
+        assert_eq!((remote.0, remote.1), (1, 5));
+    }
+
+    #[test]
+    fn should_reflect_remote_enum() {
+        let mut wrapper = EitherWrapper(external_crate::Either::<i32, f32>::Right { value: 1.0 });
+        assert_eq!(wrapper.variant_name(), "Right");
+        assert_eq!(wrapper.variant_index(), 1);
+
+        wrapper.field_mut("value").unwrap().apply(&2.0_f32);
+        assert_eq!(
+            wrapper.field("value").unwrap().downcast_ref(),
+            Some(&2.0_f32)
+        );
+
+        // Applying another variant replaces the remote value
+        let mut left = DynamicTuple::default();
+        left.insert(5_i32);
+        wrapper.apply(&DynamicEnum::new("Left", left));
+        let external_crate::Either::Left(left) = wrapper.as_remote() else {
+            panic!("expected the left variant");
+        };
+        assert_eq!(*left, 5);
+
+        let cloned = EitherWrapper::<i32, f32>::from_reflect(&wrapper).unwrap();
+        assert!(matches!(
+            cloned.into_remote(),
+            external_crate::Either::Left(5)
+        ));
+
+        let neither = EitherWrapper::<i32, f32>::from_reflect(&DynamicEnum::new(
+            "Neither",
+            DynamicVariant::Unit,
+        ))
+        .unwrap();
+        assert!(matches!(
+            neither.into_remote(),
+            external_crate::Either::Neither
+        ));
+
+        let TypeInfo::Enum(info) = EitherWrapper::<i32, f32>::type_info() else {
+            panic!("expected enum info");
+        };
+        assert_eq!(info.variant_names(), ["Left", "Right", "Neither"]);
+    }
+
+    #[test]
+    fn should_reflect_remote_field() {
+        let mut cooldown = Cooldown {
+            timer: external_crate::Timer {
+                elapsed: 0.0,
+                duration: 1.0,
+            },
+            charges: 2,
+        };
+
+        let mut timer = DynamicStruct::default();
+        timer.insert("elapsed", 0.25_f32);
+        let mut patch = DynamicStruct::default();
+        patch.insert("timer", timer);
+        cooldown.apply(&patch);
+
+        assert_eq!(cooldown.timer.elapsed, 0.25);
+        assert_eq!(cooldown.timer.duration, 1.0);
+
+        let cloned = Cooldown::from_reflect(&*cooldown.clone_value()).unwrap();
+        assert_eq!(cloned.timer.elapsed, 0.25);
+        assert_eq!(cloned.charges, 2);
+    }
+
+    #[test]
+    fn should_convert_between_wrapper_and_remote() {
+        let mut remote = external_crate::Timer {
+            elapsed: 0.0,
+            duration: 1.0,
+        };
+
+        TimerWrapper::as_wrapper_mut(&mut remote).0.elapsed = 0.5;
+        assert_eq!(TimerWrapper::as_wrapper(&remote).as_remote().elapsed, 0.5);
+
+        let wrapper = TimerWrapper::from(remote);
+        assert_eq!(wrapper.into_remote().duration, 1.0);
+    }
+}
@@ -1,4 +1,6 @@
 use crate::{FromType, Reflect};
+use std::fmt::Display;
+use std::str::FromStr;
 
 /// A struct used to provide the default value of a type.
 ///
@@ -21,3 +23,49 @@ impl<T: Reflect + Default> FromType<T> for ReflectDefault {
         }
     }
 }
+
+/// A struct used to parse a value of a type from a string, and to format it back into one.
+///
+/// A [`ReflectValueFormat`] for any type implementing [`FromStr`] and [`Display`] can be obtained
+/// via [`FromType::from_type`]. Types with a different textual representation can use
+/// [`ReflectValueFormat::new`] instead.
+#[derive(Clone)]
+pub struct ReflectValueFormat {
+    parse: fn(&str) -> Option<Box<dyn Reflect>>,
+    format: fn(&dyn Reflect) -> Option<String>,
+}
+
+impl ReflectValueFormat {
+    /// Creates a [`ReflectValueFormat`] from a pair of parsing and formatting functions.
+    ///
+    /// `format` is only ever called with values of the type this is registered for.
+    pub fn new(
+        parse: fn(&str) -> Option<Box<dyn Reflect>>,
+        format: fn(&dyn Reflect) -> Option<String>,
+    ) -> Self {
+        Self { parse, format }
+    }
+
+    /// Parses a value from `text`, returning `None` if it isn't valid.
+    pub fn parse(&self, text: &str) -> Option<Box<dyn Reflect>> {
+        (self.parse)(text)
+    }
+
+    /// Formats `value`, returning `None` if it isn't of the registered type.
+    pub fn format(&self, value: &dyn Reflect) -> Option<String> {
+        (self.format)(value)
+    }
+}
+
+impl<T: Reflect + FromStr + Display> FromType<T> for ReflectValueFormat {
+    fn from_type() -> Self {
+        ReflectValueFormat {
+            parse: |text| {
+                T::from_str(text)
+                    .ok()
+                    .map(|value| Box::new(value) as Box<dyn Reflect>)
+            },
+            format: |value| value.downcast_ref::<T>().map(ToString::to_string),
+        }
+    }
+}
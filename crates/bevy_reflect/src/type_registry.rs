@@ -1,3 +1,4 @@
+use crate::func::{DynamicFunction, IntoFunction, ReflectMethods};
 use crate::{serde::Serializable, Reflect, TypeInfo, TypePath, Typed};
 use bevy_ptr::{Ptr, PtrMut};
 use bevy_utils::{HashMap, HashSet, TypeIdMap};
@@ -5,6 +6,7 @@ use downcast_rs::{impl_downcast, Downcast};
 use serde::Deserialize;
 use std::{
     any::TypeId,
+    borrow::Cow,
     fmt::Debug,
     sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard},
 };
@@ -26,6 +28,7 @@ pub struct TypeRegistry {
     short_path_to_id: HashMap<&'static str, TypeId>,
     type_path_to_id: HashMap<&'static str, TypeId>,
     ambiguous_names: HashSet<&'static str>,
+    functions: HashMap<Cow<'static, str>, DynamicFunction>,
 }
 
 // TODO:  remove this wrapper once we migrate to Atelier Assets and the Scene AssetLoader doesn't
@@ -74,6 +77,7 @@ impl TypeRegistry {
             short_path_to_id: Default::default(),
             type_path_to_id: Default::default(),
             ambiguous_names: Default::default(),
+            functions: Default::default(),
         }
     }
 
@@ -162,6 +166,80 @@ impl TypeRegistry {
         data.insert(D::from_type());
     }
 
+    /// Registers a reflected function under the given name.
+    ///
+    /// The function can be any type implementing [`IntoFunction`],
+    /// such as a plain `fn` item or a closure.
+    /// If a function with the same name was already registered, it is replaced.
+    ///
+    /// # Example
+    /// ```
+    /// use bevy_reflect::TypeRegistry;
+    ///
+    /// fn add(a: i32, b: i32) -> i32 {
+    ///     a + b
+    /// }
+    ///
+    /// let mut type_registry = TypeRegistry::default();
+    /// type_registry.register_function("add", add);
+    ///
+    /// let add = type_registry.get_function("add").unwrap();
+    /// let result = add.call(&[&1_i32, &2_i32]).unwrap();
+    /// assert_eq!(result.downcast_ref::<i32>(), Some(&3));
+    /// ```
+    pub fn register_function<F: IntoFunction<Marker>, Marker>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        function: F,
+    ) {
+        let name = name.into();
+        self.functions
+            .insert(name.clone(), function.into_function().with_name(name));
+    }
+
+    /// Registers a reflected method for type `T` under the given name.
+    ///
+    /// The method is stored in the [`ReflectMethods`] type data of `T`,
+    /// which is inserted if it doesn't already exist.
+    /// Its first argument should be the receiver (e.g. `&self`).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` has not been registered.
+    pub fn register_method<T: Reflect + TypePath, Marker>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        method: impl IntoFunction<Marker>,
+    ) {
+        let registration = self.get_mut(TypeId::of::<T>()).unwrap_or_else(|| {
+            panic!(
+                "attempted to call `TypeRegistry::register_method` for type `{T}` without registering `{T}` first",
+                T = T::type_path(),
+            )
+        });
+        if registration.data::<ReflectMethods>().is_none() {
+            registration.insert(ReflectMethods::default());
+        }
+        registration
+            .data_mut::<ReflectMethods>()
+            .unwrap()
+            .insert(name, method.into_function());
+    }
+
+    /// Returns the reflected function registered under the given name.
+    ///
+    /// If no function with the given name has been registered, returns `None`.
+    pub fn get_function(&self, name: &str) -> Option<&DynamicFunction> {
+        self.functions.get(name)
+    }
+
+    /// Returns an iterator over the names and values of all registered functions.
+    pub fn iter_functions(&self) -> impl Iterator<Item = (&str, &DynamicFunction)> {
+        self.functions
+            .iter()
+            .map(|(name, function)| (name.as_ref(), function))
+    }
+
     /// Returns a reference to the [`TypeRegistration`] of the type with the
     /// given [`TypeId`].
     ///
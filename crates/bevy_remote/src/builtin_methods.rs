@@ -0,0 +1,806 @@
+//! The methods registered by default by the [`RemotePlugin`](crate::RemotePlugin).
+//!
+//! Each method has a name constant, a parameter type and, where it returns structured data,
+//! a response type, all of which (de)serialize to the JSON used by the protocol.
+
+use bevy_ecs::{
+    entity::Entity,
+    reflect::{AppTypeRegistry, ReflectComponent, ReflectResource},
+    system::In,
+    world::{EntityRef, EntityWorldMut, World},
+};
+use bevy_hierarchy::DespawnRecursiveExt;
+use bevy_reflect::{
+    serde::{TypedReflectDeserializer, TypedReflectSerializer},
+    GetPath, Reflect, TypeRegistration, TypeRegistry,
+};
+use bevy_utils::HashMap;
+use serde::{de::DeserializeOwned, de::DeserializeSeed, Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{error_codes, BrpError, BrpResult};
+
+/// The method name for [`process_remote_get_request`].
+pub const BRP_GET_METHOD: &str = "bevy/get";
+/// The method name for [`process_remote_query_request`].
+pub const BRP_QUERY_METHOD: &str = "bevy/query";
+/// The method name for [`process_remote_spawn_request`].
+pub const BRP_SPAWN_METHOD: &str = "bevy/spawn";
+/// The method name for [`process_remote_insert_request`].
+pub const BRP_INSERT_METHOD: &str = "bevy/insert";
+/// The method name for [`process_remote_remove_request`].
+pub const BRP_REMOVE_METHOD: &str = "bevy/remove";
+/// The method name for [`process_remote_destroy_request`].
+pub const BRP_DESTROY_METHOD: &str = "bevy/destroy";
+/// The method name for [`process_remote_list_request`].
+pub const BRP_LIST_METHOD: &str = "bevy/list";
+/// The method name for [`process_remote_mutate_component_request`].
+pub const BRP_MUTATE_COMPONENT_METHOD: &str = "bevy/mutate_component";
+/// The method name for [`process_remote_get_resource_request`].
+pub const BRP_GET_RESOURCE_METHOD: &str = "bevy/get_resource";
+/// The method name for [`process_remote_insert_resource_request`].
+pub const BRP_INSERT_RESOURCE_METHOD: &str = "bevy/insert_resource";
+
+/// The parameters of `bevy/get`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BrpGetParams {
+    /// The entity to read components from.
+    pub entity: Entity,
+    /// The type paths of the components to read.
+    pub components: Vec<String>,
+    /// If `true`, the request fails if any component cannot be read.
+    ///
+    /// Otherwise, the failures are reported per component in [`BrpGetResponse::errors`].
+    #[serde(default)]
+    pub strict: bool,
+}
+
+/// The response of `bevy/get`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BrpGetResponse {
+    /// The serialized values of the components that could be read, keyed by type path.
+    pub components: HashMap<String, Value>,
+    /// The errors for the components that could not be read, keyed by type path.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub errors: HashMap<String, BrpError>,
+}
+
+/// The parameters of `bevy/query`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BrpQueryParams {
+    /// The data to fetch for each matching entity.
+    #[serde(default)]
+    pub data: BrpQuery,
+    /// Additional conditions an entity must meet to match.
+    #[serde(default)]
+    pub filter: BrpQueryFilter,
+}
+
+/// The data fetched by `bevy/query`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BrpQuery {
+    /// Components that must be present, and whose values are returned.
+    #[serde(default)]
+    pub components: Vec<String>,
+    /// Components whose values are returned if present.
+    #[serde(default)]
+    pub option: Vec<String>,
+    /// Components whose presence is reported as a boolean.
+    #[serde(default)]
+    pub has: Vec<String>,
+}
+
+/// The filters applied by `bevy/query`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BrpQueryFilter {
+    /// Components that must be present.
+    #[serde(default)]
+    pub with: Vec<String>,
+    /// Components that must not be present.
+    #[serde(default)]
+    pub without: Vec<String>,
+}
+
+/// A single entity returned by `bevy/query`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BrpQueryRow {
+    /// The matching entity.
+    pub entity: Entity,
+    /// The values of the requested components, keyed by type path.
+    pub components: HashMap<String, Value>,
+    /// Whether the entity has each of the components in [`BrpQuery::has`].
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub has: HashMap<String, bool>,
+}
+
+/// The parameters of `bevy/spawn`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BrpSpawnParams {
+    /// The serialized values of the components to spawn the entity with, keyed by type path.
+    pub components: HashMap<String, Value>,
+}
+
+/// The response of `bevy/spawn`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BrpSpawnResponse {
+    /// The newly spawned entity.
+    pub entity: Entity,
+}
+
+/// The parameters of `bevy/insert`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BrpInsertParams {
+    /// The entity to insert the components into.
+    pub entity: Entity,
+    /// The serialized values of the components to insert, keyed by type path.
+    ///
+    /// Components already present on the entity are replaced.
+    pub components: HashMap<String, Value>,
+}
+
+/// The parameters of `bevy/remove`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BrpRemoveParams {
+    /// The entity to remove the components from.
+    pub entity: Entity,
+    /// The type paths of the components to remove.
+    pub components: Vec<String>,
+}
+
+/// The parameters of `bevy/destroy`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BrpDestroyParams {
+    /// The entity to despawn, along with its descendants.
+    pub entity: Entity,
+}
+
+/// The parameters of `bevy/list`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BrpListParams {
+    /// The entity to list the components of.
+    ///
+    /// If `None`, all registered components are listed instead.
+    #[serde(default)]
+    pub entity: Option<Entity>,
+}
+
+/// The parameters of `bevy/mutate_component`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BrpMutateComponentParams {
+    /// The entity that has the component.
+    pub entity: Entity,
+    /// The type path of the component.
+    pub component: String,
+    /// The [reflection path](bevy_reflect::GetPath) of the field to set, such as `.translation.x`.
+    ///
+    /// An empty path sets the whole component.
+    pub path: String,
+    /// The serialized value of the field.
+    pub value: Value,
+}
+
+/// The parameters of `bevy/get_resource`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BrpGetResourceParams {
+    /// The type path of the resource.
+    pub resource: String,
+}
+
+/// The response of `bevy/get_resource`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BrpGetResourceResponse {
+    /// The serialized value of the resource.
+    pub value: Value,
+}
+
+/// The parameters of `bevy/insert_resource`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BrpInsertResourceParams {
+    /// The type path of the resource.
+    pub resource: String,
+    /// The serialized value of the resource.
+    pub value: Value,
+}
+
+/// Handles a `bevy/get` request.
+pub fn process_remote_get_request(In(params): In<Option<Value>>, world: &mut World) -> BrpResult {
+    let BrpGetParams {
+        entity,
+        components,
+        strict,
+    } = parse_some(params)?;
+
+    let app_type_registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = app_type_registry.read();
+    let entity_ref = get_entity(world, entity)?;
+
+    let mut response = BrpGetResponse {
+        components: HashMap::default(),
+        errors: HashMap::default(),
+    };
+    for type_path in components {
+        match serialize_component(entity_ref, &registry, &type_path) {
+            Ok(value) => {
+                response.components.insert(type_path, value);
+            }
+            Err(error) if !strict => {
+                response.errors.insert(type_path, error);
+            }
+            Err(error) => return Err(error),
+        }
+    }
+
+    to_value(&response)
+}
+
+/// Handles a `bevy/query` request.
+pub fn process_remote_query_request(In(params): In<Option<Value>>, world: &mut World) -> BrpResult {
+    let BrpQueryParams {
+        data: BrpQuery {
+            components,
+            option,
+            has,
+        },
+        filter: BrpQueryFilter { with, without },
+    } = parse(params.unwrap_or(Value::Null))?;
+
+    let app_type_registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = app_type_registry.read();
+
+    let components = get_reflect_components(&registry, &components)?;
+    let option = get_reflect_components(&registry, &option)?;
+    let has = get_reflect_components(&registry, &has)?;
+    let with = get_reflect_components(&registry, &with)?;
+    let without = get_reflect_components(&registry, &without)?;
+
+    let mut rows = Vec::new();
+    for entity_ref in world.iter_entities() {
+        if !components
+            .iter()
+            .chain(&with)
+            .all(|(_, reflect_component)| reflect_component.contains(entity_ref))
+            || without
+                .iter()
+                .any(|(_, reflect_component)| reflect_component.contains(entity_ref))
+        {
+            continue;
+        }
+
+        let mut row = BrpQueryRow {
+            entity: entity_ref.id(),
+            components: HashMap::default(),
+            has: HashMap::default(),
+        };
+        for (type_path, reflect_component) in components.iter().chain(&option) {
+            if let Some(value) = reflect_component.reflect(entity_ref) {
+                row.components.insert(
+                    (*type_path).to_owned(),
+                    serialize_reflect(value, &registry)?,
+                );
+            }
+        }
+        for (type_path, reflect_component) in &has {
+            row.has.insert(
+                (*type_path).to_owned(),
+                reflect_component.contains(entity_ref),
+            );
+        }
+        rows.push(row);
+    }
+
+    to_value(&rows)
+}
+
+/// Handles a `bevy/spawn` request.
+pub fn process_remote_spawn_request(In(params): In<Option<Value>>, world: &mut World) -> BrpResult {
+    let BrpSpawnParams { components } = parse_some(params)?;
+
+    let app_type_registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = app_type_registry.read();
+    let components = deserialize_components(&registry, components)?;
+
+    let mut entity_world_mut = world.spawn_empty();
+    insert_reflected_components(&registry, &mut entity_world_mut, &components);
+
+    to_value(&BrpSpawnResponse {
+        entity: entity_world_mut.id(),
+    })
+}
+
+/// Handles a `bevy/insert` request.
+pub fn process_remote_insert_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    let BrpInsertParams { entity, components } = parse_some(params)?;
+
+    let app_type_registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = app_type_registry.read();
+    let components = deserialize_components(&registry, components)?;
+
+    let mut entity_world_mut = get_entity_mut(world, entity)?;
+    insert_reflected_components(&registry, &mut entity_world_mut, &components);
+
+    Ok(Value::Null)
+}
+
+/// Handles a `bevy/remove` request.
+pub fn process_remote_remove_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    let BrpRemoveParams { entity, components } = parse_some(params)?;
+
+    let app_type_registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = app_type_registry.read();
+    let components = get_reflect_components(&registry, &components)?;
+
+    let mut entity_world_mut = get_entity_mut(world, entity)?;
+    for (_, reflect_component) in components {
+        reflect_component.remove(&mut entity_world_mut);
+    }
+
+    Ok(Value::Null)
+}
+
+/// Handles a `bevy/destroy` request.
+pub fn process_remote_destroy_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    let BrpDestroyParams { entity } = parse_some(params)?;
+
+    get_entity_mut(world, entity)?.despawn_recursive();
+
+    Ok(Value::Null)
+}
+
+/// Handles a `bevy/list` request, returning a list of component type paths.
+pub fn process_remote_list_request(In(params): In<Option<Value>>, world: &mut World) -> BrpResult {
+    let BrpListParams { entity } = parse(params.unwrap_or(Value::Null))?;
+
+    let app_type_registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = app_type_registry.read();
+
+    let mut type_paths = match entity {
+        Some(entity) => {
+            let entity_ref = get_entity(world, entity)?;
+            entity_ref
+                .archetype()
+                .components()
+                .filter_map(|component_id| world.components().get_info(component_id))
+                .filter_map(|info| registry.get(info.type_id()?))
+                .map(|registration| registration.type_info().type_path().to_owned())
+                .collect::<Vec<_>>()
+        }
+        None => registry
+            .iter()
+            .filter(|registration| registration.data::<ReflectComponent>().is_some())
+            .map(|registration| registration.type_info().type_path().to_owned())
+            .collect(),
+    };
+    type_paths.sort_unstable();
+
+    to_value(&type_paths)
+}
+
+/// Handles a `bevy/mutate_component` request.
+pub fn process_remote_mutate_component_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    let BrpMutateComponentParams {
+        entity,
+        component,
+        path,
+        value,
+    } = parse_some(params)?;
+
+    let app_type_registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = app_type_registry.read();
+    let (_, reflect_component) = get_reflect_component(&registry, &component)?;
+
+    let mut entity_world_mut = get_entity_mut(world, entity)?;
+    let mut reflected = reflect_component
+        .reflect_mut(&mut entity_world_mut)
+        .ok_or_else(|| component_not_present(entity, &component))?;
+
+    let target = if path.is_empty() {
+        &mut *reflected
+    } else {
+        reflected
+            .reflect_path_mut(path.as_str())
+            .map_err(|error| BrpError::new(error_codes::INVALID_PARAMS, error.to_string()))?
+    };
+
+    let type_path = target
+        .get_represented_type_info()
+        .map(|info| info.type_path())
+        .ok_or_else(|| {
+            BrpError::new(
+                error_codes::INVALID_PARAMS,
+                format!("The value at `{path}` has no type information"),
+            )
+        })?;
+    let registration = get_registration(&registry, type_path)?;
+    let value = deserialize_reflect(&registry, registration, value)?;
+    target.apply(&*value);
+
+    Ok(Value::Null)
+}
+
+/// Handles a `bevy/get_resource` request.
+pub fn process_remote_get_resource_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    let BrpGetResourceParams { resource } = parse_some(params)?;
+
+    let app_type_registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = app_type_registry.read();
+    let reflect_resource = get_reflect_resource(&registry, &resource)?;
+
+    let value = reflect_resource.reflect(world).ok_or_else(|| {
+        BrpError::new(
+            error_codes::RESOURCE_ERROR,
+            format!("Resource `{resource}` does not exist"),
+        )
+    })?;
+
+    to_value(&BrpGetResourceResponse {
+        value: serialize_reflect(value, &registry)?,
+    })
+}
+
+/// Handles a `bevy/insert_resource` request.
+pub fn process_remote_insert_resource_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    let BrpInsertResourceParams { resource, value } = parse_some(params)?;
+
+    let app_type_registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = app_type_registry.read();
+    let registration = get_registration(&registry, &resource)?;
+    let reflect_resource = get_reflect_resource(&registry, &resource)?;
+    let value = deserialize_reflect(&registry, registration, value)?;
+
+    reflect_resource.apply_or_insert(world, &*value);
+
+    Ok(Value::Null)
+}
+
+/// Deserializes the parameters of a request.
+fn parse<T: DeserializeOwned>(value: Value) -> Result<T, BrpError> {
+    serde_json::from_value(value)
+        .map_err(|error| BrpError::new(error_codes::INVALID_PARAMS, error.to_string()))
+}
+
+/// Deserializes the parameters of a request that requires them.
+fn parse_some<T: DeserializeOwned>(value: Option<Value>) -> Result<T, BrpError> {
+    match value {
+        Some(value) => parse(value),
+        None => Err(BrpError::new(
+            error_codes::INVALID_PARAMS,
+            "Params not provided",
+        )),
+    }
+}
+
+fn to_value<T: Serialize>(value: &T) -> BrpResult {
+    serde_json::to_value(value).map_err(|error| BrpError::internal(error.to_string()))
+}
+
+fn get_entity(world: &World, entity: Entity) -> Result<EntityRef<'_>, BrpError> {
+    world
+        .get_entity(entity)
+        .ok_or_else(|| entity_not_found(entity))
+}
+
+fn get_entity_mut(world: &mut World, entity: Entity) -> Result<EntityWorldMut<'_>, BrpError> {
+    world
+        .get_entity_mut(entity)
+        .ok_or_else(|| entity_not_found(entity))
+}
+
+fn entity_not_found(entity: Entity) -> BrpError {
+    BrpError::new(
+        error_codes::ENTITY_NOT_FOUND,
+        format!("Entity {entity:?} does not exist"),
+    )
+}
+
+fn component_not_present(entity: Entity, type_path: &str) -> BrpError {
+    BrpError::new(
+        error_codes::COMPONENT_NOT_PRESENT,
+        format!("Component `{type_path}` is not present on entity {entity:?}"),
+    )
+}
+
+fn get_registration<'r>(
+    registry: &'r TypeRegistry,
+    type_path: &str,
+) -> Result<&'r TypeRegistration, BrpError> {
+    registry.get_with_type_path(type_path).ok_or_else(|| {
+        BrpError::new(
+            error_codes::COMPONENT_ERROR,
+            format!("Type `{type_path}` is not registered"),
+        )
+    })
+}
+
+fn get_reflect_component<'r>(
+    registry: &'r TypeRegistry,
+    type_path: &str,
+) -> Result<(&'r TypeRegistration, &'r ReflectComponent), BrpError> {
+    let registration = get_registration(registry, type_path)?;
+    let reflect_component = registration.data::<ReflectComponent>().ok_or_else(|| {
+        BrpError::new(
+            error_codes::COMPONENT_ERROR,
+            format!("Type `{type_path}` does not reflect `Component`"),
+        )
+    })?;
+    Ok((registration, reflect_component))
+}
+
+/// Looks up the [`ReflectComponent`] of each type path, keeping the type path alongside it.
+fn get_reflect_components<'p, 'r>(
+    registry: &'r TypeRegistry,
+    type_paths: &'p [String],
+) -> Result<Vec<(&'p str, &'r ReflectComponent)>, BrpError> {
+    type_paths
+        .iter()
+        .map(|type_path| {
+            let (_, reflect_component) = get_reflect_component(registry, type_path)?;
+            Ok((type_path.as_str(), reflect_component))
+        })
+        .collect()
+}
+
+fn get_reflect_resource<'r>(
+    registry: &'r TypeRegistry,
+    type_path: &str,
+) -> Result<&'r ReflectResource, BrpError> {
+    registry
+        .get_with_type_path(type_path)
+        .and_then(|registration| registration.data::<ReflectResource>())
+        .ok_or_else(|| {
+            BrpError::new(
+                error_codes::RESOURCE_ERROR,
+                format!("Type `{type_path}` is not registered or does not reflect `Resource`"),
+            )
+        })
+}
+
+fn serialize_reflect(value: &dyn Reflect, registry: &TypeRegistry) -> BrpResult {
+    serde_json::to_value(TypedReflectSerializer::new(value, registry))
+        .map_err(|error| BrpError::internal(error.to_string()))
+}
+
+fn serialize_component(
+    entity_ref: EntityRef,
+    registry: &TypeRegistry,
+    type_path: &str,
+) -> BrpResult {
+    let (_, reflect_component) = get_reflect_component(registry, type_path)?;
+    let value = reflect_component
+        .reflect(entity_ref)
+        .ok_or_else(|| component_not_present(entity_ref.id(), type_path))?;
+    serialize_reflect(value, registry)
+}
+
+fn deserialize_reflect(
+    registry: &TypeRegistry,
+    registration: &TypeRegistration,
+    value: Value,
+) -> Result<Box<dyn Reflect>, BrpError> {
+    TypedReflectDeserializer::new(registration, registry)
+        .deserialize(value)
+        .map_err(|error| BrpError::new(error_codes::INVALID_PARAMS, error.to_string()))
+}
+
+/// Deserializes a map of serialized components, keyed by type path.
+fn deserialize_components(
+    registry: &TypeRegistry,
+    components: HashMap<String, Value>,
+) -> Result<Vec<(Box<dyn Reflect>, &ReflectComponent)>, BrpError> {
+    components
+        .into_iter()
+        .map(|(type_path, value)| {
+            let (registration, reflect_component) = get_reflect_component(registry, &type_path)?;
+            let value = deserialize_reflect(registry, registration, value)?;
+            Ok((value, reflect_component))
+        })
+        .collect()
+}
+
+fn insert_reflected_components(
+    registry: &TypeRegistry,
+    entity_world_mut: &mut EntityWorldMut,
+    components: &[(Box<dyn Reflect>, &ReflectComponent)],
+) {
+    for (value, reflect_component) in components {
+        reflect_component.insert(entity_world_mut, &**value, registry);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BrpMessage, BrpSender, RemotePlugin};
+    use bevy_app::App;
+    use bevy_ecs::{component::Component, system::Resource};
+    use serde_json::json;
+
+    #[derive(Component, Reflect, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Health {
+        current: u32,
+        max: u32,
+    }
+
+    #[derive(Component, Reflect, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Player;
+
+    #[derive(Resource, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Resource)]
+    struct Score(u32);
+
+    const HEALTH: &str = "bevy_remote::builtin_methods::tests::Health";
+    const PLAYER: &str = "bevy_remote::builtin_methods::tests::Player";
+    const SCORE: &str = "bevy_remote::builtin_methods::tests::Score";
+
+    fn setup() -> App {
+        let mut app = App::new();
+        app.init_resource::<AppTypeRegistry>()
+            .register_type::<Health>()
+            .register_type::<Player>()
+            .register_type::<Score>()
+            .add_plugins(RemotePlugin::default());
+        app
+    }
+
+    fn send(app: &mut App, method: &str, params: Value) -> BrpResult {
+        let (sender, receiver) = async_channel::bounded(1);
+        app.world
+            .resource::<BrpSender>()
+            .try_send(BrpMessage {
+                method: method.to_owned(),
+                params: Some(params),
+                sender,
+            })
+            .unwrap();
+        app.update();
+        receiver.try_recv().unwrap()
+    }
+
+    #[test]
+    fn spawn_get_and_mutate() {
+        let mut app = setup();
+
+        let response = send(
+            &mut app,
+            BRP_SPAWN_METHOD,
+            json!({ "components": { HEALTH: { "current": 5, "max": 10 } } }),
+        )
+        .unwrap();
+        let BrpSpawnResponse { entity } = serde_json::from_value(response).unwrap();
+        assert_eq!(
+            app.world.get::<Health>(entity),
+            Some(&Health {
+                current: 5,
+                max: 10
+            })
+        );
+
+        send(
+            &mut app,
+            BRP_MUTATE_COMPONENT_METHOD,
+            json!({ "entity": entity, "component": HEALTH, "path": ".current", "value": 8 }),
+        )
+        .unwrap();
+
+        let response = send(
+            &mut app,
+            BRP_GET_METHOD,
+            json!({ "entity": entity, "components": [HEALTH, PLAYER] }),
+        )
+        .unwrap();
+        let response: BrpGetResponse = serde_json::from_value(response).unwrap();
+        assert_eq!(
+            response.components[HEALTH],
+            json!({ "current": 8, "max": 10 })
+        );
+        assert_eq!(
+            response.errors[PLAYER].code,
+            error_codes::COMPONENT_NOT_PRESENT
+        );
+
+        let error = send(
+            &mut app,
+            BRP_GET_METHOD,
+            json!({ "entity": entity, "components": [PLAYER], "strict": true }),
+        )
+        .unwrap_err();
+        assert_eq!(error.code, error_codes::COMPONENT_NOT_PRESENT);
+    }
+
+    #[test]
+    fn query_and_modify_entities() {
+        let mut app = setup();
+        let player = app
+            .world
+            .spawn((Health { current: 1, max: 3 }, Player))
+            .id();
+        let enemy = app.world.spawn(Health { current: 2, max: 2 }).id();
+
+        let response = send(
+            &mut app,
+            BRP_QUERY_METHOD,
+            json!({
+                "data": { "components": [HEALTH], "has": [PLAYER] },
+                "filter": { "without": [PLAYER] }
+            }),
+        )
+        .unwrap();
+        let rows: Vec<BrpQueryRow> = serde_json::from_value(response).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].entity, enemy);
+        assert!(!rows[0].has[PLAYER]);
+
+        send(
+            &mut app,
+            BRP_REMOVE_METHOD,
+            json!({ "entity": player, "components": [PLAYER] }),
+        )
+        .unwrap();
+        assert!(app.world.get::<Player>(player).is_none());
+
+        send(
+            &mut app,
+            BRP_INSERT_METHOD,
+            json!({ "entity": enemy, "components": { PLAYER: {} } }),
+        )
+        .unwrap();
+        assert!(app.world.get::<Player>(enemy).is_some());
+
+        let response = send(&mut app, BRP_LIST_METHOD, json!({ "entity": enemy })).unwrap();
+        assert_eq!(response, json!([HEALTH, PLAYER]));
+
+        send(&mut app, BRP_DESTROY_METHOD, json!({ "entity": enemy })).unwrap();
+        assert!(app.world.get_entity(enemy).is_none());
+
+        let error = send(&mut app, BRP_DESTROY_METHOD, json!({ "entity": enemy })).unwrap_err();
+        assert_eq!(error.code, error_codes::ENTITY_NOT_FOUND);
+    }
+
+    #[test]
+    fn resources() {
+        let mut app = setup();
+
+        let error = send(
+            &mut app,
+            BRP_GET_RESOURCE_METHOD,
+            json!({ "resource": SCORE }),
+        )
+        .unwrap_err();
+        assert_eq!(error.code, error_codes::RESOURCE_ERROR);
+
+        send(
+            &mut app,
+            BRP_INSERT_RESOURCE_METHOD,
+            json!({ "resource": SCORE, "value": [7] }),
+        )
+        .unwrap();
+        assert_eq!(app.world.resource::<Score>(), &Score(7));
+
+        let response = send(
+            &mut app,
+            BRP_GET_RESOURCE_METHOD,
+            json!({ "resource": SCORE }),
+        )
+        .unwrap();
+        assert_eq!(response, json!({ "value": [7] }));
+    }
+}
@@ -0,0 +1,685 @@
+//! A minimal HTTP and WebSocket transport for the Bevy Remote Protocol.
+//!
+//! Requests are sent as the body of an HTTP `POST` to any path,
+//! and the JSON-RPC response is returned as the body of the HTTP response.
+//! A JSON array of requests is processed as a [batch].
+//!
+//! A `GET` request asking to upgrade to a [WebSocket] instead keeps the connection open:
+//! each text or binary message is processed as a request (or batch),
+//! and its response is sent back as a text message.
+//! This avoids opening a connection per request for tools that poll the app.
+//!
+//! Connections are served by a fixed number of worker threads,
+//! set with [`RemoteHttpPlugin::with_max_connections`].
+//! An HTTP connection is closed after a single response,
+//! while a WebSocket connection holds its worker until it's closed.
+//! Connections that stay idle for longer than the [timeout](RemoteHttpPlugin::with_timeout)
+//! are dropped, so they can't keep the workers busy.
+//!
+//! Requests sent by web pages carry an `Origin` header, and are rejected unless their origin
+//! was allowed with [`RemoteHttpPlugin::with_allowed_origin`].
+//! This keeps any website open in a browser on the same machine from controlling the app.
+//! Allowed origins are answered with the matching [CORS] headers.
+//!
+//! [batch]: https://www.jsonrpc.org/specification#batch
+//! [WebSocket]: https://www.rfc-editor.org/rfc/rfc6455
+//! [CORS]: https://developer.mozilla.org/en-US/docs/Web/HTTP/CORS
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{IpAddr, Ipv4Addr, TcpListener, TcpStream};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use bevy_app::{App, Plugin, Startup};
+use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::system::{Res, Resource};
+use bevy_utils::tracing::{error, info, warn};
+use serde_json::Value;
+use tungstenite::{
+    handshake::derive_accept_key,
+    protocol::{Role, WebSocketConfig},
+    Message, WebSocket,
+};
+
+use crate::{error_codes, BrpError, BrpMessage, BrpRequest, BrpResponse, BrpSender};
+
+/// The default address the HTTP server listens on: `127.0.0.1`.
+pub const DEFAULT_ADDRESS: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+/// The default port the HTTP server listens on.
+pub const DEFAULT_PORT: u16 = 15702;
+
+/// The default number of connections the server handles at the same time.
+pub const DEFAULT_MAX_CONNECTIONS: usize = 8;
+
+/// The default time a connection may stay idle before it's dropped: 30 seconds.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The largest request body or WebSocket message the server accepts, in bytes.
+const MAX_BODY_SIZE: usize = 16 * 1024 * 1024;
+
+/// Add this plugin alongside the [`RemotePlugin`](crate::RemotePlugin)
+/// to accept remote requests over HTTP.
+///
+/// The server only listens on the local machine by default.
+/// Listening on other interfaces exposes full control of the [`World`](bevy_ecs::world::World)
+/// to anything that can reach the port, so do so with care.
+pub struct RemoteHttpPlugin {
+    /// The address the server listens on.
+    address: IpAddr,
+    /// The port the server listens on.
+    port: u16,
+    /// The number of connections handled at the same time.
+    max_connections: usize,
+    /// The time a connection may stay idle before it's dropped.
+    timeout: Duration,
+    /// The origins of the web pages allowed to send requests.
+    allowed_origins: Vec<String>,
+}
+
+impl Default for RemoteHttpPlugin {
+    fn default() -> Self {
+        Self {
+            address: DEFAULT_ADDRESS,
+            port: DEFAULT_PORT,
+            max_connections: DEFAULT_MAX_CONNECTIONS,
+            timeout: DEFAULT_TIMEOUT,
+            allowed_origins: Vec::new(),
+        }
+    }
+}
+
+impl RemoteHttpPlugin {
+    /// Sets the address the server listens on.
+    #[must_use]
+    pub fn with_address(mut self, address: impl Into<IpAddr>) -> Self {
+        self.address = address.into();
+        self
+    }
+
+    /// Sets the port the server listens on.
+    #[must_use]
+    pub fn with_port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    /// Sets the number of connections handled at the same time, which is the number of worker
+    /// threads of the server.
+    ///
+    /// Further connections wait to be accepted until a worker is free.
+    /// Each open WebSocket connection holds a worker, so this should be larger than the
+    /// number of tools expected to stay connected.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_connections` is zero.
+    #[must_use]
+    pub fn with_max_connections(mut self, max_connections: usize) -> Self {
+        assert!(
+            max_connections > 0,
+            "the remote HTTP server needs at least one connection"
+        );
+        self.max_connections = max_connections;
+        self
+    }
+
+    /// Sets the time a connection may wait for the client to send or receive data
+    /// before it's dropped.
+    ///
+    /// WebSocket clients that stay connected without sending requests should send pings
+    /// more often than this.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `timeout` is zero.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        assert!(
+            !timeout.is_zero(),
+            "the remote HTTP server timeout must not be zero"
+        );
+        self.timeout = timeout;
+        self
+    }
+
+    /// Allows the web pages served from `origin`, such as `http://localhost:3000`,
+    /// to send requests and open WebSocket connections.
+    ///
+    /// Requests carrying an `Origin` header are rejected unless their origin was allowed,
+    /// which no origin is by default.
+    /// Requests without one, as sent by tools outside of a browser, are always accepted.
+    #[must_use]
+    pub fn with_allowed_origin(mut self, origin: impl Into<String>) -> Self {
+        self.allowed_origins.push(origin.into());
+        self
+    }
+}
+
+impl Plugin for RemoteHttpPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(HostAddress(self.address))
+            .insert_resource(HostPort(self.port))
+            .insert_resource(MaxConnections(self.max_connections))
+            .insert_resource(ConnectionTimeout(self.timeout))
+            .insert_resource(AllowedOrigins(self.allowed_origins.clone()))
+            .add_systems(Startup, start_http_server);
+    }
+}
+
+/// The address the HTTP server listens on.
+#[derive(Resource, Debug, Clone, Copy, Deref, DerefMut)]
+pub struct HostAddress(pub IpAddr);
+
+/// The port the HTTP server listens on.
+#[derive(Resource, Debug, Clone, Copy, Deref, DerefMut)]
+pub struct HostPort(pub u16);
+
+/// The number of connections the HTTP server handles at the same time.
+#[derive(Resource, Debug, Clone, Copy, Deref, DerefMut)]
+pub struct MaxConnections(pub usize);
+
+/// The time a connection to the HTTP server may stay idle before it's dropped.
+#[derive(Resource, Debug, Clone, Copy, Deref, DerefMut)]
+pub struct ConnectionTimeout(pub Duration);
+
+/// The origins of the web pages allowed to send requests to the HTTP server.
+#[derive(Resource, Debug, Clone, Default, Deref, DerefMut)]
+pub struct AllowedOrigins(pub Vec<String>);
+
+/// Binds the HTTP server and starts accepting connections.
+fn start_http_server(
+    address: Res<HostAddress>,
+    port: Res<HostPort>,
+    max_connections: Res<MaxConnections>,
+    timeout: Res<ConnectionTimeout>,
+    allowed_origins: Res<AllowedOrigins>,
+    sender: Res<BrpSender>,
+) {
+    let listener = match TcpListener::bind((address.0, port.0)) {
+        Ok(listener) => listener,
+        Err(err) => {
+            error!(
+                "Failed to start the remote HTTP server on {}:{}: {err}",
+                address.0, port.0
+            );
+            return;
+        }
+    };
+    info!("Remote HTTP server listening on {}:{}", address.0, port.0);
+    let settings = ServerSettings {
+        timeout: timeout.0,
+        allowed_origins: allowed_origins.0.clone().into(),
+    };
+    serve(listener, max_connections.0, settings, sender.clone());
+}
+
+/// The settings shared by the worker threads of the server.
+#[derive(Clone)]
+struct ServerSettings {
+    timeout: Duration,
+    allowed_origins: Arc<[String]>,
+}
+
+/// Accepts the connections of `listener` on a background thread,
+/// handing them to `max_connections` worker threads.
+fn serve(
+    listener: TcpListener,
+    max_connections: usize,
+    settings: ServerSettings,
+    sender: BrpSender,
+) {
+    // The accepting thread blocks once all the workers are busy and this channel is full,
+    // leaving further connections in the listener's backlog.
+    let max_connections = max_connections.max(1);
+    let (stream_sender, stream_receiver) = async_channel::bounded::<TcpStream>(max_connections);
+    for index in 0..max_connections {
+        let stream_receiver = stream_receiver.clone();
+        let settings = settings.clone();
+        let sender = sender.clone();
+        thread::Builder::new()
+            .name(format!("BRP HTTP worker {index}"))
+            .spawn(move || {
+                while let Ok(stream) = stream_receiver.recv_blocking() {
+                    let result = stream
+                        .set_read_timeout(Some(settings.timeout))
+                        .and_then(|()| stream.set_write_timeout(Some(settings.timeout)))
+                        .and_then(|()| {
+                            handle_connection(stream, &settings.allowed_origins, &sender)
+                        });
+                    if let Err(err) = result {
+                        warn!("Failed to handle a remote HTTP connection: {err}");
+                    }
+                }
+            })
+            .expect("failed to spawn a remote HTTP worker thread");
+    }
+
+    thread::Builder::new()
+        .name("BRP HTTP server".to_owned())
+        .spawn(move || {
+            for stream in listener.incoming() {
+                let stream = match stream {
+                    Ok(stream) => stream,
+                    Err(err) => {
+                        warn!("Failed to accept a remote HTTP connection: {err}");
+                        continue;
+                    }
+                };
+                if stream_sender.send_blocking(stream).is_err() {
+                    break;
+                }
+            }
+        })
+        .expect("failed to spawn the remote HTTP server thread");
+}
+
+/// Reads a single HTTP request from the stream and writes its response,
+/// or serves the connection as a WebSocket if the request asks for an upgrade.
+///
+/// Requests from an origin missing from `allowed_origins` are answered with an error.
+fn handle_connection(
+    stream: TcpStream,
+    allowed_origins: &[String],
+    sender: &BrpSender,
+) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut stream = stream;
+
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line)? == 0 {
+        return Ok(());
+    }
+    let method = request_line.split_whitespace().next().unwrap_or_default();
+
+    let mut content_length = 0;
+    let mut upgrade_to_websocket = false;
+    let mut websocket_key = None;
+    let mut origin = None;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 {
+            break;
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            let (name, value) = (name.trim(), value.trim());
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.parse().map_err(|_| {
+                    io::Error::new(io::ErrorKind::InvalidData, "invalid Content-Length")
+                })?;
+            } else if name.eq_ignore_ascii_case("upgrade") {
+                upgrade_to_websocket = value.eq_ignore_ascii_case("websocket");
+            } else if name.eq_ignore_ascii_case("sec-websocket-key") {
+                websocket_key = Some(value.to_owned());
+            } else if name.eq_ignore_ascii_case("origin") {
+                origin = Some(value.to_owned());
+            }
+        }
+    }
+
+    // Browsers send the origin of the page making the request, even for WebSocket upgrades,
+    // which aren't subject to CORS.
+    if let Some(origin) = &origin {
+        if !allowed_origins.contains(origin) {
+            return write_response(&mut stream, "403 Forbidden", None, &[]);
+        }
+    }
+    let origin = origin.as_deref();
+
+    match method {
+        "GET" if upgrade_to_websocket => match websocket_key {
+            // The client waits for the handshake response before sending any message,
+            // so `reader` hasn't buffered any of them.
+            Some(key) => serve_websocket(stream, &key, sender),
+            None => write_response(&mut stream, "400 Bad Request", origin, &[]),
+        },
+        "POST" if content_length > MAX_BODY_SIZE => {
+            write_response(&mut stream, "413 Payload Too Large", origin, &[])
+        }
+        "POST" => {
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body)?;
+            let response = process_body(&body, sender);
+            write_response(&mut stream, "200 OK", origin, &response)
+        }
+        // Answer the CORS preflight requests of the allowed origins.
+        "OPTIONS" if origin.is_some() => write_response(&mut stream, "204 No Content", origin, &[]),
+        _ => write_response(&mut stream, "405 Method Not Allowed", origin, &[]),
+    }
+}
+
+/// Writes a response closing the connection,
+/// with the CORS headers allowing `allowed_origin` to read it if there is one.
+fn write_response(
+    stream: &mut TcpStream,
+    status: &str,
+    allowed_origin: Option<&str>,
+    body: &[u8],
+) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\n\
+         Content-Type: application/json\r\n\
+         Content-Length: {}\r\n",
+        body.len()
+    )?;
+    if let Some(origin) = allowed_origin {
+        write!(
+            stream,
+            "Access-Control-Allow-Origin: {origin}\r\n\
+             Access-Control-Allow-Methods: GET, POST, OPTIONS\r\n\
+             Access-Control-Allow-Headers: Content-Type\r\n\
+             Vary: Origin\r\n"
+        )?;
+    }
+    write!(stream, "Connection: close\r\n\r\n")?;
+    stream.write_all(body)?;
+    stream.flush()
+}
+
+/// Completes the WebSocket handshake, then answers each message of the connection until it's closed.
+fn serve_websocket(mut stream: TcpStream, key: &str, sender: &BrpSender) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 101 Switching Protocols\r\n\
+         Upgrade: websocket\r\n\
+         Connection: Upgrade\r\n\
+         Sec-WebSocket-Accept: {}\r\n\r\n",
+        derive_accept_key(key.as_bytes())
+    )?;
+    stream.flush()?;
+
+    let config = WebSocketConfig {
+        max_message_size: Some(MAX_BODY_SIZE),
+        ..Default::default()
+    };
+    let mut socket = WebSocket::from_raw_socket(stream, Role::Server, Some(config));
+    loop {
+        let body = match socket.read() {
+            Ok(Message::Text(text)) => text.into_bytes(),
+            Ok(Message::Binary(data)) => data,
+            // Pings are answered and closes acknowledged by the socket itself.
+            Ok(Message::Ping(_) | Message::Pong(_) | Message::Close(_) | Message::Frame(_)) => {
+                continue;
+            }
+            Err(tungstenite::Error::ConnectionClosed | tungstenite::Error::AlreadyClosed) => {
+                return Ok(());
+            }
+            Err(err) => return Err(websocket_error(err)),
+        };
+        // `process_body` always serializes JSON, which is valid UTF-8.
+        let response = String::from_utf8(process_body(&body, sender)).unwrap_or_default();
+        socket
+            .send(Message::Text(response))
+            .map_err(websocket_error)?;
+    }
+}
+
+fn websocket_error(err: tungstenite::Error) -> io::Error {
+    match err {
+        tungstenite::Error::Io(err) => err,
+        err => io::Error::new(io::ErrorKind::InvalidData, err),
+    }
+}
+
+/// Processes a request body, returning the serialized response.
+fn process_body(body: &[u8], sender: &BrpSender) -> Vec<u8> {
+    let response = match serde_json::from_slice::<Value>(body) {
+        Ok(Value::Array(requests)) if !requests.is_empty() => Value::Array(
+            requests
+                .into_iter()
+                .map(|request| response_to_value(process_request(request, sender)))
+                .collect(),
+        ),
+        Ok(request) => response_to_value(process_request(request, sender)),
+        Err(err) => response_to_value(BrpResponse::new(
+            None,
+            Err(BrpError::new(error_codes::PARSE_ERROR, err.to_string())),
+        )),
+    };
+    serde_json::to_vec(&response).unwrap_or_default()
+}
+
+fn response_to_value(response: BrpResponse) -> Value {
+    serde_json::to_value(response).unwrap_or(Value::Null)
+}
+
+/// Sends a single request to the app and waits for its result.
+fn process_request(request: Value, sender: &BrpSender) -> BrpResponse {
+    let request = match serde_json::from_value::<BrpRequest>(request) {
+        Ok(request) => request,
+        Err(err) => {
+            return BrpResponse::new(
+                None,
+                Err(BrpError::new(error_codes::INVALID_REQUEST, err.to_string())),
+            );
+        }
+    };
+
+    if request.jsonrpc != crate::JSONRPC_VERSION {
+        return BrpResponse::new(
+            request.id,
+            Err(BrpError::new(
+                error_codes::INVALID_REQUEST,
+                "JSON-RPC request requires `\"jsonrpc\": \"2.0\"`",
+            )),
+        );
+    }
+
+    let (result_sender, result_receiver) = async_channel::bounded(1);
+    let message = BrpMessage {
+        method: request.method,
+        params: request.params,
+        sender: result_sender,
+    };
+
+    let result = if sender.send_blocking(message).is_err() {
+        Err(BrpError::internal(
+            "The app is no longer accepting requests",
+        ))
+    } else {
+        result_receiver.recv_blocking().unwrap_or_else(|_| {
+            Err(BrpError::internal(
+                "The app stopped before the request was processed",
+            ))
+        })
+    };
+
+    BrpResponse::new(request.id, result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// Returns a sender standing in for the app, echoing the parameters of each request.
+    fn echo_sender() -> BrpSender {
+        let (sender, receiver) = async_channel::unbounded::<BrpMessage>();
+        thread::spawn(move || {
+            while let Ok(message) = receiver.recv_blocking() {
+                let _ = message
+                    .sender
+                    .send_blocking(Ok(message.params.unwrap_or_default()));
+            }
+        });
+        BrpSender(sender)
+    }
+
+    fn echo_request(id: u32) -> String {
+        json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "echo",
+            "params": { "value": id },
+        })
+        .to_string()
+    }
+
+    fn settings(timeout: Duration, allowed_origins: &[&str]) -> ServerSettings {
+        ServerSettings {
+            timeout,
+            allowed_origins: allowed_origins
+                .iter()
+                .map(|&origin| origin.to_owned())
+                .collect(),
+        }
+    }
+
+    /// Serves a single worker listening on a free port, returning the port.
+    fn serve_one(settings: ServerSettings) -> u16 {
+        let listener = TcpListener::bind((DEFAULT_ADDRESS, 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        serve(listener, 1, settings, echo_sender());
+        port
+    }
+
+    /// Sends a raw HTTP request, returning the head and body of its response.
+    fn send_raw(port: u16, request: &str) -> (String, String) {
+        let mut stream = TcpStream::connect((DEFAULT_ADDRESS, port)).unwrap();
+        stream
+            .set_read_timeout(Some(Duration::from_secs(10)))
+            .unwrap();
+        stream.write_all(request.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        (head.to_owned(), body.to_owned())
+    }
+
+    fn post(origin: &str) -> String {
+        let body = echo_request(1);
+        format!(
+            "POST / HTTP/1.1\r\nOrigin: {origin}\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        )
+    }
+
+    #[test]
+    fn websocket_round_trip() {
+        let listener = TcpListener::bind((DEFAULT_ADDRESS, 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let sender = echo_sender();
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            handle_connection(stream, &[], &sender).unwrap();
+        });
+
+        let (mut socket, _) = tungstenite::connect(format!("ws://127.0.0.1:{port}")).unwrap();
+        for id in 0..2 {
+            socket.send(Message::Text(echo_request(id))).unwrap();
+            let Message::Text(response) = socket.read().unwrap() else {
+                panic!("expected a text message");
+            };
+            let response: Value = serde_json::from_str(&response).unwrap();
+            assert_eq!(response["id"], json!(id));
+            assert_eq!(response["result"], json!({ "value": id }));
+        }
+        socket.close(None).unwrap();
+    }
+
+    #[test]
+    fn connections_wait_for_a_free_worker() {
+        let listener = TcpListener::bind((DEFAULT_ADDRESS, 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        serve(listener, 1, settings(DEFAULT_TIMEOUT, &[]), echo_sender());
+
+        // The WebSocket holds the only worker while it's open
+        let (mut socket, _) = tungstenite::connect(format!("ws://127.0.0.1:{port}")).unwrap();
+        let body = echo_request(1);
+        let mut stream = TcpStream::connect((DEFAULT_ADDRESS, port)).unwrap();
+        write!(
+            stream,
+            "POST / HTTP/1.1\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+        stream
+            .set_read_timeout(Some(Duration::from_millis(200)))
+            .unwrap();
+        let mut response = String::new();
+        assert!(stream.read_to_string(&mut response).is_err());
+
+        socket.close(None).unwrap();
+        while socket.read().is_ok() {}
+        stream
+            .set_read_timeout(Some(Duration::from_secs(10)))
+            .unwrap();
+        stream.read_to_string(&mut response).unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        assert!(head.starts_with("HTTP/1.1 200 OK"));
+        let response: Value = serde_json::from_str(body).unwrap();
+        assert_eq!(response["result"], json!({ "value": 1 }));
+    }
+
+    #[test]
+    fn cross_origin_requests_are_rejected() {
+        let port = serve_one(settings(DEFAULT_TIMEOUT, &[]));
+
+        let (head, body) = send_raw(port, &post("http://example.com"));
+        assert!(head.starts_with("HTTP/1.1 403 Forbidden"));
+        assert!(!head.contains("Access-Control-Allow-Origin"));
+        assert!(body.is_empty());
+
+        let (head, _) = send_raw(
+            port,
+            "OPTIONS / HTTP/1.1\r\nOrigin: http://example.com\r\n\r\n",
+        );
+        assert!(head.starts_with("HTTP/1.1 403 Forbidden"));
+
+        let (head, _) = send_raw(
+            port,
+            "GET / HTTP/1.1\r\n\
+             Origin: http://example.com\r\n\
+             Upgrade: websocket\r\n\
+             Connection: Upgrade\r\n\
+             Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\
+             Sec-WebSocket-Version: 13\r\n\r\n",
+        );
+        assert!(head.starts_with("HTTP/1.1 403 Forbidden"));
+    }
+
+    #[test]
+    fn allowed_origins_get_cors_headers() {
+        let port = serve_one(settings(DEFAULT_TIMEOUT, &["http://localhost:3000"]));
+
+        let (head, _) = send_raw(
+            port,
+            "OPTIONS / HTTP/1.1\r\nOrigin: http://localhost:3000\r\n\r\n",
+        );
+        assert!(head.starts_with("HTTP/1.1 204 No Content"));
+        assert!(head.contains("Access-Control-Allow-Origin: http://localhost:3000\r\n"));
+
+        let (head, body) = send_raw(port, &post("http://localhost:3000"));
+        assert!(head.starts_with("HTTP/1.1 200 OK"));
+        assert!(head.contains("Access-Control-Allow-Origin: http://localhost:3000\r\n"));
+        let response: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(response["result"], json!({ "value": 1 }));
+
+        // Another origin is still rejected
+        let (head, _) = send_raw(port, &post("http://localhost:8080"));
+        assert!(head.starts_with("HTTP/1.1 403 Forbidden"));
+    }
+
+    #[test]
+    fn idle_connections_time_out() {
+        let port = serve_one(settings(Duration::from_millis(100), &[]));
+
+        // This connection never sends its request, but only holds the worker until the timeout
+        let _idle = TcpStream::connect((DEFAULT_ADDRESS, port)).unwrap();
+        let body = echo_request(1);
+        let (head, _) = send_raw(
+            port,
+            &format!(
+                "POST / HTTP/1.1\r\nContent-Length: {}\r\n\r\n{body}",
+                body.len()
+            ),
+        );
+        assert!(head.starts_with("HTTP/1.1 200 OK"));
+    }
+}
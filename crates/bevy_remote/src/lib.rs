@@ -0,0 +1,458 @@
+//! The Bevy Remote Protocol (BRP): a way for external processes to inspect and modify
+//! a running Bevy [`App`].
+//!
+//! Tools such as editors, inspectors and test harnesses send requests to the app,
+//! which are processed once per frame against the [`World`].
+//! Components and resources are read and written through [reflection],
+//! so any type registered with the [`AppTypeRegistry`](bevy_ecs::reflect::AppTypeRegistry)
+//! that reflects [`ReflectComponent`](bevy_ecs::reflect::ReflectComponent) or
+//! [`ReflectResource`](bevy_ecs::reflect::ReflectResource) can be accessed remotely.
+//!
+//! # Protocol
+//!
+//! The protocol follows [JSON-RPC 2.0]. A request looks like this:
+//!
+//! ```json
+//! {
+//!     "jsonrpc": "2.0",
+//!     "id": 0,
+//!     "method": "bevy/get",
+//!     "params": {
+//!         "entity": 4294967298,
+//!         "components": ["bevy_transform::components::transform::Transform"]
+//!     }
+//! }
+//! ```
+//!
+//! Entities are identified by their [`Entity::to_bits`](bevy_ecs::entity::Entity::to_bits)
+//! representation, and components and resources by their full type path.
+//! Component values use the same format as [`TypedReflectSerializer`](bevy_reflect::serde::TypedReflectSerializer).
+//!
+//! A successful response carries a `result`, while a failed one carries an `error`
+//! with one of the codes in [`error_codes`].
+//!
+//! # Methods
+//!
+//! The [`RemotePlugin`] registers the built-in methods listed in [`builtin_methods`]:
+//!
+//! - `bevy/get`: get the values of one or more components on an entity.
+//! - `bevy/query`: query entities by the components they have.
+//! - `bevy/spawn`: spawn an entity with the given components.
+//! - `bevy/insert`: insert components into an existing entity.
+//! - `bevy/remove`: remove components from an entity.
+//! - `bevy/destroy`: despawn an entity along with its descendants.
+//! - `bevy/list`: list the registered components, or the components on an entity.
+//! - `bevy/mutate_component`: set a single field of a component using a [reflection path].
+//! - `bevy/get_resource`: get the value of a resource.
+//! - `bevy/insert_resource`: insert or overwrite a resource.
+//!
+//! Custom methods can be added with [`RemotePlugin::with_method`].
+//! Each method is a system taking its parameters as [`In<Option<Value>>`](bevy_ecs::system::In)
+//! and returning a [`BrpResult`].
+//!
+//! # Transports
+//!
+//! The [`RemotePlugin`] itself does not open any connections.
+//! Requests are delivered to the app through the [`BrpSender`] channel,
+//! so any transport can be layered on top of it.
+//! The [`RemoteHttpPlugin`](http::RemoteHttpPlugin) provides a simple HTTP server
+//! that accepts requests as `POST` bodies, or as messages over a WebSocket connection.
+//!
+//! ```no_run
+//! use bevy_app::App;
+//! use bevy_remote::{http::RemoteHttpPlugin, RemotePlugin};
+//!
+//! App::new()
+//!     .add_plugins(RemotePlugin::default())
+//!     .add_plugins(RemoteHttpPlugin::default())
+//!     .run();
+//! ```
+//!
+//! [reflection]: bevy_reflect
+//! [JSON-RPC 2.0]: https://www.jsonrpc.org/specification
+//! [reflection path]: bevy_reflect::GetPath
+
+use std::sync::Mutex;
+
+use bevy_app::{App, Last, Plugin};
+use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::{
+    schedule::{IntoSystemConfigs, SystemSet},
+    system::{BoxedSystem, IntoSystem, Resource, SystemId},
+    world::World,
+};
+use bevy_utils::{tracing::warn, HashMap};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+pub mod builtin_methods;
+pub mod http;
+
+/// The JSON-RPC version used by the Bevy Remote Protocol.
+pub const JSONRPC_VERSION: &str = "2.0";
+
+/// Add this plugin to your [`App`] to allow remote connections to inspect and modify its [`World`].
+///
+/// By default, all of the [built-in methods](builtin_methods) are registered.
+/// Use [`RemotePlugin::empty`] to start without any methods.
+///
+/// This plugin does not open any connections by itself;
+/// see the [crate-level documentation](crate) for the available transports.
+pub struct RemotePlugin {
+    /// The methods to register when the plugin is built.
+    ///
+    /// This is wrapped in a [`Mutex`] so the systems can be moved into the [`World`] from [`Plugin::build`].
+    methods: Mutex<Vec<(String, BoxedSystem<Option<Value>, BrpResult>)>>,
+}
+
+impl RemotePlugin {
+    /// Creates a [`RemotePlugin`] without any methods.
+    pub fn empty() -> Self {
+        Self {
+            methods: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Adds a method with the given `name` to the plugin.
+    ///
+    /// If a method with the same name was already added, it is replaced.
+    #[must_use]
+    pub fn with_method<M>(
+        mut self,
+        name: impl Into<String>,
+        handler: impl IntoSystem<Option<Value>, BrpResult, M>,
+    ) -> Self {
+        self.methods
+            .get_mut()
+            .unwrap()
+            .push((name.into(), Box::new(IntoSystem::into_system(handler))));
+        self
+    }
+}
+
+impl Default for RemotePlugin {
+    fn default() -> Self {
+        use builtin_methods::*;
+
+        Self::empty()
+            .with_method(BRP_GET_METHOD, process_remote_get_request)
+            .with_method(BRP_QUERY_METHOD, process_remote_query_request)
+            .with_method(BRP_SPAWN_METHOD, process_remote_spawn_request)
+            .with_method(BRP_INSERT_METHOD, process_remote_insert_request)
+            .with_method(BRP_REMOVE_METHOD, process_remote_remove_request)
+            .with_method(BRP_DESTROY_METHOD, process_remote_destroy_request)
+            .with_method(BRP_LIST_METHOD, process_remote_list_request)
+            .with_method(
+                BRP_MUTATE_COMPONENT_METHOD,
+                process_remote_mutate_component_request,
+            )
+            .with_method(BRP_GET_RESOURCE_METHOD, process_remote_get_resource_request)
+            .with_method(
+                BRP_INSERT_RESOURCE_METHOD,
+                process_remote_insert_resource_request,
+            )
+    }
+}
+
+impl Plugin for RemotePlugin {
+    fn build(&self, app: &mut App) {
+        let mut remote_methods = RemoteMethods::new();
+        for (name, system) in self.methods.lock().unwrap().drain(..) {
+            remote_methods.insert(name, app.world.register_boxed_system(system));
+        }
+
+        let (sender, receiver) = async_channel::unbounded();
+
+        app.insert_resource(remote_methods)
+            .insert_resource(BrpSender(sender))
+            .insert_resource(BrpReceiver(receiver))
+            .add_systems(
+                Last,
+                process_remote_requests.in_set(RemoteSet::ProcessRequests),
+            );
+    }
+}
+
+/// The systems sets used by the [`RemotePlugin`].
+#[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
+pub enum RemoteSet {
+    /// Processes the requests received since the last frame.
+    ProcessRequests,
+}
+
+/// The identifier of a registered remote method.
+pub type RemoteMethod = SystemId<Option<Value>, BrpResult>;
+
+/// Holds all of the methods that can be invoked remotely, keyed by name.
+#[derive(Resource, Debug, Default)]
+pub struct RemoteMethods(HashMap<String, RemoteMethod>);
+
+impl RemoteMethods {
+    /// Creates an empty [`RemoteMethods`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a method, returning the previous method with the same name, if any.
+    pub fn insert(
+        &mut self,
+        name: impl Into<String>,
+        method: RemoteMethod,
+    ) -> Option<RemoteMethod> {
+        self.0.insert(name.into(), method)
+    }
+
+    /// Returns the method with the given name, if it exists.
+    pub fn get(&self, name: &str) -> Option<&RemoteMethod> {
+        self.0.get(name)
+    }
+
+    /// Returns an iterator over the names of all registered methods.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(String::as_str)
+    }
+}
+
+/// A JSON-RPC request sent to the app.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BrpRequest {
+    /// The JSON-RPC version. Must be `"2.0"`.
+    pub jsonrpc: String,
+    /// The name of the method to invoke.
+    pub method: String,
+    /// An identifier chosen by the client, echoed back in the response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
+    /// The parameters of the method, if it takes any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub params: Option<Value>,
+}
+
+/// A JSON-RPC response sent back to the client.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BrpResponse {
+    /// The JSON-RPC version. Always `"2.0"`.
+    pub jsonrpc: String,
+    /// The identifier of the request this is a response to.
+    ///
+    /// This is `null` if the request could not be parsed.
+    pub id: Option<Value>,
+    /// The result or error of the request.
+    #[serde(flatten)]
+    pub payload: BrpPayload,
+}
+
+impl BrpResponse {
+    /// Creates a response to the request with the given `id`.
+    pub fn new(id: Option<Value>, result: BrpResult) -> Self {
+        Self {
+            jsonrpc: JSONRPC_VERSION.to_owned(),
+            id,
+            payload: result.into(),
+        }
+    }
+}
+
+/// The outcome of a request, as it appears in a [`BrpResponse`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BrpPayload {
+    /// The request succeeded.
+    Result(Value),
+    /// The request failed.
+    Error(BrpError),
+}
+
+impl From<BrpResult> for BrpPayload {
+    fn from(result: BrpResult) -> Self {
+        match result {
+            Ok(value) => BrpPayload::Result(value),
+            Err(error) => BrpPayload::Error(error),
+        }
+    }
+}
+
+/// An error returned by a remote method.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BrpError {
+    /// The error code, usually one of the constants in [`error_codes`].
+    pub code: i16,
+    /// A short description of the error.
+    pub message: String,
+    /// Additional information about the error.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+impl BrpError {
+    /// Creates an error with the given code and message.
+    pub fn new(code: i16, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            data: None,
+        }
+    }
+
+    /// Creates an [`INTERNAL_ERROR`](error_codes::INTERNAL_ERROR) with the given message.
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::new(error_codes::INTERNAL_ERROR, message)
+    }
+
+    /// Attaches additional information to the error.
+    #[must_use]
+    pub fn with_data(mut self, data: Value) -> Self {
+        self.data = Some(data);
+        self
+    }
+}
+
+/// Error codes used by the Bevy Remote Protocol.
+///
+/// Codes between `-32768` and `-32000` are reserved by JSON-RPC.
+pub mod error_codes {
+    /// The request body is not valid JSON.
+    pub const PARSE_ERROR: i16 = -32700;
+    /// The request is not a valid JSON-RPC request.
+    pub const INVALID_REQUEST: i16 = -32600;
+    /// The requested method does not exist.
+    pub const METHOD_NOT_FOUND: i16 = -32601;
+    /// The parameters of the request are invalid.
+    pub const INVALID_PARAMS: i16 = -32602;
+    /// An internal error occurred while processing the request.
+    pub const INTERNAL_ERROR: i16 = -32603;
+
+    /// The requested entity does not exist.
+    pub const ENTITY_NOT_FOUND: i16 = -23401;
+    /// The requested component type is not registered or cannot be used as a component.
+    pub const COMPONENT_ERROR: i16 = -23402;
+    /// The requested component is not present on the entity.
+    pub const COMPONENT_NOT_PRESENT: i16 = -23403;
+    /// The requested resource type is not registered, cannot be used as a resource, or does not exist.
+    pub const RESOURCE_ERROR: i16 = -23404;
+}
+
+/// The result of a remote method.
+pub type BrpResult = Result<Value, BrpError>;
+
+/// A request delivered to the app by a transport.
+#[derive(Debug, Clone)]
+pub struct BrpMessage {
+    /// The name of the method to invoke.
+    pub method: String,
+    /// The parameters of the method.
+    pub params: Option<Value>,
+    /// The channel the result is sent on once the request has been processed.
+    pub sender: async_channel::Sender<BrpResult>,
+}
+
+/// The sending end of the channel transports use to deliver requests to the app.
+///
+/// This can be cloned freely and moved to other threads.
+#[derive(Resource, Debug, Clone, Deref, DerefMut)]
+pub struct BrpSender(pub async_channel::Sender<BrpMessage>);
+
+/// The receiving end of the channel requests are delivered on.
+///
+/// Requests are drained from this every frame by [`process_remote_requests`].
+#[derive(Resource, Debug, Clone, Deref, DerefMut)]
+pub struct BrpReceiver(pub async_channel::Receiver<BrpMessage>);
+
+/// Runs the method of every request received since the last frame,
+/// sending each result back to its transport.
+pub fn process_remote_requests(world: &mut World) {
+    if !world.contains_resource::<BrpReceiver>() {
+        return;
+    }
+
+    while let Ok(message) = world.resource::<BrpReceiver>().try_recv() {
+        let Some(&handler) = world.resource::<RemoteMethods>().get(&message.method) else {
+            let _ = message.sender.try_send(Err(BrpError::new(
+                error_codes::METHOD_NOT_FOUND,
+                format!("Method `{}` not found", message.method),
+            )));
+            continue;
+        };
+
+        let result = match world.run_system_with_input(handler, message.params) {
+            Ok(result) => result,
+            Err(error) => Err(BrpError::internal(format!(
+                "Failed to run method `{}`: {error}",
+                message.method
+            ))),
+        };
+
+        if message.sender.try_send(result).is_err() {
+            warn!(
+                "Failed to send the result of remote method `{}`: the transport disconnected",
+                message.method
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::system::In;
+    use serde_json::json;
+
+    fn echo(In(params): In<Option<Value>>) -> BrpResult {
+        params.ok_or_else(|| BrpError::new(error_codes::INVALID_PARAMS, "Params not provided"))
+    }
+
+    fn send(app: &mut App, method: &str, params: Option<Value>) -> BrpResult {
+        let (sender, receiver) = async_channel::bounded(1);
+        app.world
+            .resource::<BrpSender>()
+            .try_send(BrpMessage {
+                method: method.to_owned(),
+                params,
+                sender,
+            })
+            .unwrap();
+        app.update();
+        receiver.try_recv().unwrap()
+    }
+
+    #[test]
+    fn custom_method() {
+        let mut app = App::new();
+        app.add_plugins(RemotePlugin::empty().with_method("test/echo", echo));
+
+        assert_eq!(
+            send(&mut app, "test/echo", Some(json!([1, 2]))),
+            Ok(json!([1, 2]))
+        );
+        assert_eq!(
+            send(&mut app, "test/echo", None).unwrap_err().code,
+            error_codes::INVALID_PARAMS
+        );
+        assert_eq!(
+            send(&mut app, "test/missing", None).unwrap_err().code,
+            error_codes::METHOD_NOT_FOUND
+        );
+    }
+
+    #[test]
+    fn response_format() {
+        let response = BrpResponse::new(Some(json!(3)), Ok(json!("ok")));
+        assert_eq!(
+            serde_json::to_value(&response).unwrap(),
+            json!({ "jsonrpc": "2.0", "id": 3, "result": "ok" })
+        );
+
+        let response = BrpResponse::new(
+            None,
+            Err(BrpError::new(error_codes::PARSE_ERROR, "Invalid JSON")),
+        );
+        assert_eq!(
+            serde_json::to_value(&response).unwrap(),
+            json!({
+                "jsonrpc": "2.0",
+                "id": null,
+                "error": { "code": -32700, "message": "Invalid JSON" }
+            })
+        );
+    }
+}
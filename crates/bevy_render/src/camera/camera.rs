@@ -17,10 +17,11 @@ use bevy_ecs::{
     entity::Entity,
     event::EventReader,
     prelude::With,
+    query::Has,
     reflect::ReflectComponent,
-    system::{Commands, Query, Res, ResMut, Resource},
+    system::{Commands, Local, Query, Res, ResMut, Resource},
 };
-use bevy_log::warn;
+use bevy_log::{error, warn};
 use bevy_math::{
     primitives::Direction3d, vec2, Mat4, Ray3d, Rect, URect, UVec2, UVec4, Vec2, Vec3,
 };
@@ -29,13 +30,16 @@ use bevy_render_macros::ExtractComponent;
 use bevy_transform::components::GlobalTransform;
 use bevy_utils::{HashMap, HashSet};
 use bevy_window::{
-    NormalizedWindowRef, PrimaryWindow, Window, WindowCreated, WindowRef, WindowResized,
+    HdrOutput, NormalizedWindowRef, PrimaryWindow, Window, WindowCreated, WindowRef, WindowResized,
     WindowScaleFactorChanged,
 };
 use std::ops::Range;
 use wgpu::{BlendState, LoadOp, TextureFormat, TextureUsages};
 
-use super::{ClearColorConfig, Projection};
+use super::{
+    camera_dependencies::order_by_dependencies, CameraDependencyCycle, CameraInputs,
+    ClearColorConfig, Projection, XrViews,
+};
 
 /// Render viewport configuration for the [`Camera`] component.
 ///
@@ -169,6 +173,9 @@ pub struct Camera {
     /// If set, this camera will render to the given [`Viewport`] rectangle within the configured [`RenderTarget`].
     pub viewport: Option<Viewport>,
     /// Cameras with a higher order are rendered later, and thus on top of lower order cameras.
+    ///
+    /// Cameras reading the render targets of other cameras through [`CameraInputs`] are
+    /// rendered after them, whatever their order.
     pub order: isize,
     /// If this is set to `true`, this camera will be rendered to its specified [`RenderTarget`]. If `false`, this
     /// camera will not be rendered.
@@ -477,7 +484,7 @@ impl Default for CameraOutputMode {
 }
 
 /// Configures the [`RenderGraph`](crate::render_graph::RenderGraph) name assigned to be run for a given [`Camera`] entity.
-#[derive(Component, Deref, DerefMut)]
+#[derive(Component, Clone, Deref, DerefMut)]
 pub struct CameraRenderGraph(InternedRenderSubGraph);
 
 impl CameraRenderGraph {
@@ -596,6 +603,20 @@ impl NormalizedRenderTarget {
         }
     }
 
+    /// Retrieves the [`HdrOutput`] the window of this render target was configured with.
+    ///
+    /// Images and texture views are always [`HdrOutput::Sdr`].
+    pub fn get_hdr_output(&self, windows: &ExtractedWindows) -> HdrOutput {
+        match self {
+            NormalizedRenderTarget::Window(window_ref) => windows
+                .get(&window_ref.entity())
+                .map_or(HdrOutput::Sdr, |window| window.hdr_output),
+            NormalizedRenderTarget::Image(_) | NormalizedRenderTarget::TextureView(_) => {
+                HdrOutput::Sdr
+            }
+        }
+    }
+
     pub fn get_render_target_info<'a>(
         &self,
         resolutions: impl IntoIterator<Item = (Entity, &'a Window)>,
@@ -777,6 +798,8 @@ pub struct ExtractedCamera {
     pub clear_color: ClearColorConfig,
     pub sorted_camera_index_for_target: usize,
     pub exposure: f32,
+    /// The render targets of other cameras read by this camera, from its [`CameraInputs`].
+    pub inputs: Vec<NormalizedRenderTarget>,
 }
 
 pub fn extract_cameras(
@@ -794,6 +817,8 @@ pub fn extract_cameras(
             Option<&TemporalJitter>,
             Option<&RenderLayers>,
             Option<&Projection>,
+            Option<&CameraInputs>,
+            Has<XrViews>,
         )>,
     >,
     primary_window: Extract<Query<Entity, With<PrimaryWindow>>>,
@@ -811,11 +836,14 @@ pub fn extract_cameras(
         temporal_jitter,
         render_layers,
         projection,
+        inputs,
+        has_xr_views,
     ) in query.iter()
     {
         let color_grading = *color_grading.unwrap_or(&ColorGrading::default());
 
-        if !camera.is_active {
+        // Cameras with views are rendered by their view cameras
+        if !camera.is_active || has_xr_views {
             continue;
         }
 
@@ -853,6 +881,11 @@ pub fn extract_cameras(
                     exposure: exposure_settings
                         .map(|e| e.exposure())
                         .unwrap_or_else(|| ExposureSettings::default().exposure()),
+                    inputs: inputs
+                        .iter()
+                        .flat_map(|inputs| &inputs.0)
+                        .filter_map(|input| input.normalize(primary_window))
+                        .collect(),
                 },
                 ExtractedView {
                     projection: camera.projection_matrix(),
@@ -886,7 +919,8 @@ pub fn extract_cameras(
     }
 }
 
-/// Cameras sorted by their order field. This is updated in the [`sort_cameras`] system.
+/// Cameras sorted by their [`CameraInputs`] dependencies, then by their order field. This is
+/// updated in the [`sort_cameras`] system.
 #[derive(Resource, Default)]
 pub struct SortedCameras(pub Vec<SortedCamera>);
 
@@ -894,11 +928,13 @@ pub struct SortedCamera {
     pub entity: Entity,
     pub order: isize,
     pub target: Option<NormalizedRenderTarget>,
+    pub inputs: Vec<NormalizedRenderTarget>,
 }
 
 pub fn sort_cameras(
     mut sorted_cameras: ResMut<SortedCameras>,
     mut cameras: Query<(Entity, &mut ExtractedCamera)>,
+    mut reported_cycle: Local<Option<CameraDependencyCycle>>,
 ) {
     sorted_cameras.0.clear();
     for (entity, camera) in cameras.iter() {
@@ -906,6 +942,7 @@ pub fn sort_cameras(
             entity,
             order: camera.order,
             target: camera.target.clone(),
+            inputs: camera.inputs.clone(),
         });
     }
     // sort by order and ensure within an order, RenderTargets of the same type are packed together
@@ -917,21 +954,47 @@ pub fn sort_cameras(
         });
     let mut previous_order_target = None;
     let mut ambiguities = HashSet::new();
-    let mut target_counts = HashMap::new();
-    for sorted_camera in &mut sorted_cameras.0 {
+    for sorted_camera in &sorted_cameras.0 {
         let new_order_target = (sorted_camera.order, sorted_camera.target.clone());
         if let Some(previous_order_target) = previous_order_target {
             if previous_order_target == new_order_target {
                 ambiguities.insert(new_order_target.clone());
             }
         }
+        previous_order_target = Some(new_order_target);
+    }
+
+    // render the cameras after the cameras rendering to their inputs, falling back to the order
+    // field if they depend on each other in a cycle
+    match order_by_dependencies(&sorted_cameras.0) {
+        Ok(order) => {
+            let mut unordered = std::mem::take(&mut sorted_cameras.0)
+                .into_iter()
+                .map(Some)
+                .collect::<Vec<_>>();
+            sorted_cameras.0.extend(
+                order
+                    .into_iter()
+                    .filter_map(|index| unordered[index].take()),
+            );
+            *reported_cycle = None;
+        }
+        Err(cycle) => {
+            if reported_cycle.as_ref() != Some(&cycle) {
+                error!("{cycle}");
+                *reported_cycle = Some(cycle);
+            }
+        }
+    }
+
+    let mut target_counts = HashMap::new();
+    for sorted_camera in &sorted_cameras.0 {
         if let Some(target) = &sorted_camera.target {
             let count = target_counts.entry(target.clone()).or_insert(0usize);
             let (_, mut camera) = cameras.get_mut(sorted_camera.entity).unwrap();
             camera.sorted_camera_index_for_target = *count;
             *count += 1;
         }
-        previous_order_target = Some(new_order_target);
     }
 
     if !ambiguities.is_empty() {
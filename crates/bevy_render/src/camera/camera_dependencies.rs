@@ -0,0 +1,182 @@
+use std::{cmp::Reverse, collections::BinaryHeap, fmt::Write};
+
+use bevy_ecs::{component::Component, entity::Entity, reflect::ReflectComponent};
+use bevy_reflect::prelude::*;
+use bevy_utils::HashMap;
+use thiserror::Error;
+
+use super::{NormalizedRenderTarget, RenderTarget, SortedCamera};
+
+/// The render targets of other cameras read by a [`Camera`](super::Camera), for example the
+/// images sampled by the materials it renders.
+///
+/// A camera is rendered after all the cameras rendering to its inputs, whatever their
+/// [`Camera::order`](super::Camera::order). The order is only used between cameras that don't
+/// depend on each other.
+///
+/// ```
+/// # use bevy_asset::Handle;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_render::{camera::{Camera, CameraInputs, RenderTarget}, texture::Image};
+/// fn spawn_main_camera(mut commands: Commands, mirror_image: Handle<Image>) {
+///     // The scene has a mirror textured with the image rendered by another camera.
+///     commands.spawn((
+///         Camera::default(),
+///         CameraInputs(vec![RenderTarget::Image(mirror_image)]),
+///     ));
+/// }
+/// ```
+#[derive(Component, Debug, Clone, Default, Reflect)]
+#[reflect(Component)]
+pub struct CameraInputs(pub Vec<RenderTarget>);
+
+/// An error returned when cameras read each other's render targets in a cycle, so that no
+/// camera of the cycle can be rendered first.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("cameras depend on each other through their CameraInputs: {}", describe_cycle(.cycle))]
+pub struct CameraDependencyCycle {
+    /// The cameras of the cycle, each with the render target it writes that is read by the
+    /// next camera. The last camera writes a render target read by the first.
+    pub cycle: Vec<(Entity, NormalizedRenderTarget)>,
+}
+
+fn describe_cycle(cycle: &[(Entity, NormalizedRenderTarget)]) -> String {
+    let mut description = String::new();
+    for (index, (camera, target)) in cycle.iter().enumerate() {
+        let (reader, _) = &cycle[(index + 1) % cycle.len()];
+        if index > 0 {
+            description.push_str(", ");
+        }
+        let _ = write!(
+            description,
+            "{camera:?} renders to {target:?} read by {reader:?}"
+        );
+    }
+    description
+}
+
+/// Returns the indices of `cameras` ordered so that each camera comes after the cameras writing
+/// to its inputs. Cameras that don't depend on each other keep their relative order.
+pub(crate) fn order_by_dependencies(
+    cameras: &[SortedCamera],
+) -> Result<Vec<usize>, CameraDependencyCycle> {
+    let mut writers = HashMap::<&NormalizedRenderTarget, Vec<usize>>::new();
+    for (index, camera) in cameras.iter().enumerate() {
+        if let Some(target) = &camera.target {
+            writers.entry(target).or_default().push(index);
+        }
+    }
+
+    // The cameras each camera must be rendered after, and the reverse edges.
+    let mut dependencies = vec![Vec::new(); cameras.len()];
+    let mut dependents = vec![Vec::new(); cameras.len()];
+    for (reader, camera) in cameras.iter().enumerate() {
+        for input in &camera.inputs {
+            for &writer in writers.get(input).into_iter().flatten() {
+                if writer != reader && !dependencies[reader].contains(&writer) {
+                    dependencies[reader].push(writer);
+                    dependents[writer].push(reader);
+                }
+            }
+        }
+    }
+
+    let mut remaining_dependencies = dependencies.iter().map(Vec::len).collect::<Vec<_>>();
+    let mut ready = (0..cameras.len())
+        .filter(|&index| remaining_dependencies[index] == 0)
+        .map(Reverse)
+        .collect::<BinaryHeap<_>>();
+    let mut order = Vec::with_capacity(cameras.len());
+    while let Some(Reverse(index)) = ready.pop() {
+        order.push(index);
+        for &dependent in &dependents[index] {
+            remaining_dependencies[dependent] -= 1;
+            if remaining_dependencies[dependent] == 0 {
+                ready.push(Reverse(dependent));
+            }
+        }
+    }
+    if order.len() == cameras.len() {
+        return Ok(order);
+    }
+
+    // Every camera left waits for another one left, so walking their dependencies loops.
+    let mut visited = vec![false; cameras.len()];
+    let mut path = Vec::new();
+    let mut index = (0..cameras.len())
+        .find(|&index| remaining_dependencies[index] > 0)
+        .unwrap();
+    while !visited[index] {
+        visited[index] = true;
+        path.push(index);
+        index = dependencies[index]
+            .iter()
+            .copied()
+            .find(|&writer| remaining_dependencies[writer] > 0)
+            .unwrap();
+    }
+    let start = path.iter().position(|&visited| visited == index).unwrap();
+    Err(CameraDependencyCycle {
+        cycle: path[start..]
+            .iter()
+            .rev()
+            .map(|&index| {
+                let camera = &cameras[index];
+                (camera.entity, camera.target.clone().unwrap())
+            })
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_asset::{AssetId, Handle};
+
+    fn image(index: u128) -> NormalizedRenderTarget {
+        NormalizedRenderTarget::Image(Handle::Weak(AssetId::Uuid {
+            uuid: bevy_utils::Uuid::from_u128(index),
+        }))
+    }
+
+    fn camera(
+        index: u32,
+        target: NormalizedRenderTarget,
+        inputs: Vec<NormalizedRenderTarget>,
+    ) -> SortedCamera {
+        SortedCamera {
+            entity: Entity::from_raw(index),
+            order: 0,
+            target: Some(target),
+            inputs,
+        }
+    }
+
+    #[test]
+    fn cameras_are_rendered_after_their_inputs() {
+        let cameras = [
+            camera(0, image(0), vec![image(1)]),
+            camera(1, image(2), vec![]),
+            camera(2, image(1), vec![image(3)]),
+            camera(3, image(3), vec![]),
+        ];
+        assert_eq!(order_by_dependencies(&cameras), Ok(vec![1, 3, 2, 0]));
+    }
+
+    #[test]
+    fn cycles_are_reported() {
+        let cameras = [
+            camera(0, image(0), vec![]),
+            camera(1, image(1), vec![image(2)]),
+            camera(2, image(2), vec![image(1)]),
+        ];
+        let error = order_by_dependencies(&cameras).unwrap_err();
+        assert_eq!(
+            error.cycle,
+            vec![
+                (Entity::from_raw(2), image(2)),
+                (Entity::from_raw(1), image(1)),
+            ]
+        );
+    }
+}
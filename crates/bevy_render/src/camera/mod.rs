@@ -1,22 +1,27 @@
 #[allow(clippy::module_inception)]
 mod camera;
+mod camera_dependencies;
 mod camera_driver_node;
 mod clear_color;
 mod manual_texture_view;
 mod projection;
+mod xr;
 
 pub use camera::*;
+pub use camera_dependencies::*;
 pub use camera_driver_node::*;
 pub use clear_color::*;
 pub use manual_texture_view::*;
 pub use projection::*;
+pub use xr::*;
 
 use crate::{
     extract_component::ExtractComponentPlugin, extract_resource::ExtractResourcePlugin,
     render_graph::RenderGraph, ExtractSchedule, Render, RenderApp, RenderSet,
 };
-use bevy_app::{App, Plugin};
+use bevy_app::{App, Plugin, PostUpdate};
 use bevy_ecs::schedule::IntoSystemConfigs;
+use bevy_transform::TransformSystem;
 
 #[derive(Default)]
 pub struct CameraPlugin;
@@ -24,22 +29,33 @@ pub struct CameraPlugin;
 impl Plugin for CameraPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<Camera>()
+            .register_type::<CameraInputs>()
             .register_type::<Viewport>()
             .register_type::<Option<Viewport>>()
             .register_type::<ScalingMode>()
             .register_type::<RenderTarget>()
             .register_type::<ClearColor>()
             .register_type::<ClearColorConfig>()
+            .register_type::<XrViews>()
+            .register_type::<XrViewCamera>()
             .init_resource::<ManualTextureViews>()
             .init_resource::<ClearColor>()
             .add_plugins((
                 CameraProjectionPlugin::<Projection>::default(),
                 CameraProjectionPlugin::<OrthographicProjection>::default(),
                 CameraProjectionPlugin::<PerspectiveProjection>::default(),
+                CameraProjectionPlugin::<XrProjection>::default(),
                 ExtractResourcePlugin::<ManualTextureViews>::default(),
                 ExtractResourcePlugin::<ClearColor>::default(),
                 ExtractComponentPlugin::<CameraMainTextureUsages>::default(),
-            ));
+            ))
+            .add_systems(
+                PostUpdate,
+                (spawn_xr_view_cameras, update_xr_view_cameras)
+                    .chain()
+                    .before(CameraUpdateSystem)
+                    .before(TransformSystem::TransformPropagate),
+            );
 
         if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app
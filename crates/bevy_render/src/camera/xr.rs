@@ -0,0 +1,348 @@
+use crate::{
+    camera::{
+        Camera, CameraProjection, CameraRenderGraph, ManualTextureView, OrthographicProjection,
+        PerspectiveProjection, Projection, RenderTarget,
+    },
+    primitives::Frustum,
+    render_resource::Texture,
+    view::VisibleEntities,
+};
+use bevy_ecs::{prelude::*, reflect::ReflectComponent};
+use bevy_hierarchy::{BuildWorldChildren, Children, DespawnRecursiveExt, Parent};
+use bevy_math::{Mat4, UVec2, Vec3A};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_transform::components::{GlobalTransform, Transform};
+use bevy_utils::HashSet;
+use std::any::TypeId;
+use wgpu::{TextureFormat, TextureViewDescriptor, TextureViewDimension};
+
+/// Renders one logical [`Camera`] as several views, each with its own pose, projection and
+/// render target, for example the eyes of an XR headset.
+///
+/// Each view is rendered by a child camera entity with an [`XrViewCamera`] component, spawned
+/// when the views are added. The settings of the logical camera, like its render graph, `hdr` or
+/// reflected components such as tonemapping, are copied to the view cameras when they are
+/// spawned, and the [`Camera`] itself is kept in sync. The logical camera doesn't render on its
+/// own.
+///
+/// The views are typically rendered to the layers of an array texture, with one
+/// [`ManualTextureView`] per layer created by [`ManualTextureView::array_layers`].
+/// Each view is currently rendered in its own pass: single pass rendering with the multiview
+/// extension ([`WgpuFeatures::MULTIVIEW`](crate::settings::WgpuFeatures::MULTIVIEW)) isn't used
+/// yet.
+///
+/// An XR integration updates the views from the tracking data every frame, before
+/// [`PostUpdate`](bevy_app::PostUpdate).
+#[derive(Component, Debug, Clone, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct XrViews {
+    /// The views rendered by this camera.
+    pub views: Vec<XrView>,
+}
+
+/// A view of an [`XrViews`] camera.
+#[derive(Debug, Clone, Reflect)]
+pub struct XrView {
+    /// The pose of the view relative to the logical camera, for example the offset of an eye
+    /// from the center of the head.
+    pub pose: Transform,
+    /// The projection of the view.
+    pub projection: XrProjection,
+    /// The render target of the view, usually a [`RenderTarget::TextureView`] of one layer of
+    /// the swapchain texture.
+    pub target: RenderTarget,
+}
+
+/// A camera rendering the view at `index` of the [`XrViews`] of its parent.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct XrViewCamera {
+    /// The index of the view in [`XrViews::views`].
+    pub index: usize,
+}
+
+/// A [`CameraProjection`] with an asymmetric field of view, as reported by XR runtimes for each
+/// eye.
+///
+/// The angles are in radians from the forward direction: `angle_left` and `angle_down` are
+/// usually negative.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component, Default)]
+pub struct XrProjection {
+    /// The angle of the left side of the field of view.
+    pub angle_left: f32,
+    /// The angle of the right side of the field of view.
+    pub angle_right: f32,
+    /// The angle of the top side of the field of view.
+    pub angle_up: f32,
+    /// The angle of the bottom side of the field of view.
+    pub angle_down: f32,
+    /// The distance from the camera in world units of the viewing frustum's near plane.
+    pub near: f32,
+    /// The distance from the camera in world units of the viewing frustum's far plane, used for
+    /// culling and shadow cascades. The projection itself has an infinite far plane.
+    pub far: f32,
+}
+
+impl Default for XrProjection {
+    fn default() -> Self {
+        let half_fov = std::f32::consts::FRAC_PI_4;
+        Self {
+            angle_left: -half_fov,
+            angle_right: half_fov,
+            angle_up: half_fov,
+            angle_down: -half_fov,
+            near: 0.1,
+            far: 1000.0,
+        }
+    }
+}
+
+impl CameraProjection for XrProjection {
+    fn get_projection_matrix(&self) -> Mat4 {
+        let [left, right, up, down] = [
+            self.angle_left,
+            self.angle_right,
+            self.angle_up,
+            self.angle_down,
+        ]
+        .map(f32::tan);
+        let width = right - left;
+        let height = up - down;
+        // Infinite reverse z, like `Mat4::perspective_infinite_reverse_rh` with an off-center
+        // frustum
+        Mat4::from_cols_array(&[
+            2.0 / width,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            2.0 / height,
+            0.0,
+            0.0,
+            (right + left) / width,
+            (up + down) / height,
+            0.0,
+            -1.0,
+            0.0,
+            0.0,
+            self.near,
+            0.0,
+        ])
+    }
+
+    fn update(&mut self, _width: f32, _height: f32) {
+        // The field of view is given by the XR runtime, independently of the target size
+    }
+
+    fn far(&self) -> f32 {
+        self.far
+    }
+
+    fn get_frustum_corners(&self, z_near: f32, z_far: f32) -> [Vec3A; 8] {
+        let [left, right, up, down] = [
+            self.angle_left,
+            self.angle_right,
+            self.angle_up,
+            self.angle_down,
+        ]
+        .map(f32::tan);
+        let corners = |z: f32| {
+            let distance = z.abs();
+            // NOTE: These vertices are in the specific order required by [`calculate_cascade`].
+            [
+                Vec3A::new(right * distance, down * distance, z), // bottom right
+                Vec3A::new(right * distance, up * distance, z),   // top right
+                Vec3A::new(left * distance, up * distance, z),    // top left
+                Vec3A::new(left * distance, down * distance, z),  // bottom left
+            ]
+        };
+        let [a, b, c, d] = corners(z_near);
+        let [e, f, g, h] = corners(z_far);
+        [a, b, c, d, e, f, g, h]
+    }
+}
+
+impl ManualTextureView {
+    /// Creates one [`ManualTextureView`] per layer of a 2D array `texture`, to render the
+    /// [`XrViews`] of a camera to the layers of a swapchain texture.
+    pub fn array_layers(
+        texture: &Texture,
+        size: UVec2,
+        format: TextureFormat,
+        layer_count: u32,
+    ) -> Vec<ManualTextureView> {
+        (0..layer_count)
+            .map(|layer| ManualTextureView {
+                texture_view: texture
+                    .create_view(&TextureViewDescriptor {
+                        label: Some("xr_view_layer"),
+                        dimension: Some(TextureViewDimension::D2),
+                        base_array_layer: layer,
+                        array_layer_count: Some(1),
+                        ..Default::default()
+                    })
+                    .into(),
+                size,
+                format,
+            })
+            .collect()
+    }
+}
+
+/// Spawns the [`XrViewCamera`]s of the [`XrViews`] cameras, copying the components of the
+/// logical camera to them, and despawns the view cameras of removed views.
+pub fn spawn_xr_view_cameras(world: &mut World) {
+    let mut rigs = world.query::<(Entity, &XrViews, Option<&Children>)>();
+    let mut view_cameras = world.query::<&XrViewCamera>();
+    let mut to_spawn = Vec::new();
+    let mut to_despawn = Vec::new();
+    for (rig, xr_views, children) in rigs.iter(world) {
+        let existing: HashSet<usize> = children
+            .into_iter()
+            .flatten()
+            .filter_map(|&child| {
+                let index = view_cameras.get(world, child).ok()?.index;
+                if index >= xr_views.views.len() {
+                    to_despawn.push(child);
+                }
+                Some(index)
+            })
+            .collect();
+        to_spawn.extend(
+            (0..xr_views.views.len())
+                .filter(|index| !existing.contains(index))
+                .map(|index| (rig, index)),
+        );
+    }
+
+    for view_camera in to_despawn {
+        world.entity_mut(view_camera).despawn_recursive();
+    }
+    if to_spawn.is_empty() {
+        return;
+    }
+
+    // Components that are specific to each view, or to the logical camera
+    let excluded: HashSet<TypeId> = [
+        TypeId::of::<Camera>(),
+        TypeId::of::<XrViews>(),
+        TypeId::of::<Projection>(),
+        TypeId::of::<PerspectiveProjection>(),
+        TypeId::of::<OrthographicProjection>(),
+        TypeId::of::<Transform>(),
+        TypeId::of::<GlobalTransform>(),
+        TypeId::of::<Frustum>(),
+        TypeId::of::<VisibleEntities>(),
+        TypeId::of::<Parent>(),
+        TypeId::of::<Children>(),
+    ]
+    .into_iter()
+    .collect();
+
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    for (rig, index) in to_spawn {
+        let rig_entity = world.entity(rig);
+        let (Some(mut camera), Some(render_graph), Some(view)) = (
+            rig_entity.get::<Camera>().cloned(),
+            rig_entity.get::<CameraRenderGraph>().cloned(),
+            rig_entity
+                .get::<XrViews>()
+                .and_then(|xr_views| xr_views.views.get(index))
+                .cloned(),
+        ) else {
+            continue;
+        };
+        camera.target = view.target;
+        let components: Vec<_> = rig_entity
+            .archetype()
+            .components()
+            .filter_map(|component_id| world.components().get_info(component_id)?.type_id())
+            .filter(|type_id| !excluded.contains(type_id))
+            .filter_map(|type_id| {
+                let reflect_component = registry.get_type_data::<ReflectComponent>(type_id)?;
+                let value = reflect_component.reflect(rig_entity)?.clone_value();
+                Some((reflect_component.clone(), value))
+            })
+            .collect();
+
+        let mut view_camera = world.spawn((
+            XrViewCamera { index },
+            camera,
+            render_graph,
+            view.projection,
+            view.pose,
+            GlobalTransform::default(),
+            Frustum::default(),
+            VisibleEntities::default(),
+        ));
+        for (reflect_component, value) in &components {
+            reflect_component.apply_or_insert(&mut view_camera, value.as_ref(), &registry);
+        }
+        let view_camera = view_camera.id();
+        world.entity_mut(rig).add_child(view_camera);
+    }
+}
+
+/// Updates the [`XrViewCamera`]s from the [`XrViews`] and the [`Camera`] of their logical camera.
+pub fn update_xr_view_cameras(
+    rigs: Query<(Ref<Camera>, Ref<XrViews>), Without<XrViewCamera>>,
+    mut view_cameras: Query<(
+        &XrViewCamera,
+        &Parent,
+        &mut Camera,
+        &mut Transform,
+        &mut XrProjection,
+    )>,
+) {
+    for (view_camera, parent, mut camera, mut transform, mut projection) in &mut view_cameras {
+        let Ok((rig_camera, xr_views)) = rigs.get(parent.get()) else {
+            continue;
+        };
+        let Some(view) = xr_views.views.get(view_camera.index) else {
+            continue;
+        };
+        if rig_camera.is_changed() || xr_views.is_changed() {
+            let mut rig_camera = rig_camera.clone();
+            rig_camera.target = view.target.clone();
+            *camera = rig_camera;
+        }
+        if xr_views.is_changed() {
+            *transform = view.pose;
+            *projection = view.projection;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_math::Vec3;
+
+    #[test]
+    fn symmetric_projection_matches_perspective() {
+        let projection = XrProjection::default();
+        let perspective =
+            Mat4::perspective_infinite_reverse_rh(std::f32::consts::FRAC_PI_2, 1.0, 0.1);
+        assert!(projection
+            .get_projection_matrix()
+            .abs_diff_eq(perspective, 1e-6));
+    }
+
+    #[test]
+    fn asymmetric_projection_maps_edges() {
+        let projection = XrProjection {
+            angle_left: -0.8,
+            angle_right: 0.6,
+            angle_up: 0.7,
+            angle_down: -0.5,
+            ..Default::default()
+        };
+        let matrix = projection.get_projection_matrix();
+        let right_edge = Vec3::new(0.6f32.tan(), 0.7f32.tan(), -1.0);
+        let ndc = matrix.project_point3(right_edge);
+        assert!((ndc.x - 1.0).abs() < 1e-5);
+        assert!((ndc.y - 1.0).abs() < 1e-5);
+    }
+}
@@ -229,6 +229,63 @@ impl LchRepresentation {
     }
 }
 
+pub struct OklabRepresentation;
+#[allow(clippy::excessive_precision)]
+impl OklabRepresentation {
+    // Reference: https://bottosson.github.io/posts/oklab/#converting-from-linear-srgb-to-oklab
+
+    /// converts a color in linear sRGB space to Oklab space
+    #[inline]
+    pub fn linear_srgb_to_oklab([red, green, blue]: [f32; 3]) -> [f32; 3] {
+        let l = 0.4122214708 * red + 0.5363325363 * green + 0.0514459929 * blue;
+        let m = 0.2119034982 * red + 0.6806995451 * green + 0.1073969566 * blue;
+        let s = 0.0883024619 * red + 0.2817188376 * green + 0.6299787005 * blue;
+
+        let l = l.cbrt();
+        let m = m.cbrt();
+        let s = s.cbrt();
+
+        [
+            0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+            1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+            0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+        ]
+    }
+
+    /// converts a color in Oklab space to linear sRGB space
+    #[inline]
+    pub fn oklab_to_linear_srgb([lightness, a, b]: [f32; 3]) -> [f32; 3] {
+        let l = lightness + 0.3963377774 * a + 0.2158037573 * b;
+        let m = lightness - 0.1055613458 * a - 0.0638541728 * b;
+        let s = lightness - 0.0894841775 * a - 1.2914855480 * b;
+
+        let l = l * l * l;
+        let m = m * m * m;
+        let s = s * s * s;
+
+        [
+            4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s,
+            -1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s,
+            -0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s,
+        ]
+    }
+
+    /// converts a color in Oklab space to Oklch space, with the hue in degrees
+    #[inline]
+    pub fn oklab_to_oklch([lightness, a, b]: [f32; 3]) -> [f32; 3] {
+        let chroma = (a * a + b * b).sqrt();
+        let hue = b.atan2(a).to_degrees();
+        [lightness, chroma, hue.rem_euclid(360.0)]
+    }
+
+    /// converts a color in Oklch space, with the hue in degrees, to Oklab space
+    #[inline]
+    pub fn oklch_to_oklab([lightness, chroma, hue]: [f32; 3]) -> [f32; 3] {
+        let (sin, cos) = hue.to_radians().sin_cos();
+        [lightness, chroma * cos, chroma * sin]
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -250,6 +307,24 @@ mod test {
         }
     }
 
+    #[test]
+    fn oklab_roundtrip() {
+        // "truth" from https://bottosson.github.io/posts/oklab/#table-of-example-xyz-and-oklab-pairs
+        let [l, a, b] = OklabRepresentation::linear_srgb_to_oklab([1.0, 1.0, 1.0]);
+        assert!((l - 1.0).abs() < 1e-4 && a.abs() < 1e-4 && b.abs() < 1e-4);
+
+        for rgb in [[1.0, 0.0, 0.0], [0.2, 0.5, 0.8], [0.0, 0.0, 0.0]] {
+            let oklch =
+                OklabRepresentation::oklab_to_oklch(OklabRepresentation::linear_srgb_to_oklab(rgb));
+            let roundtrip = OklabRepresentation::oklab_to_linear_srgb(
+                OklabRepresentation::oklch_to_oklab(oklch),
+            );
+            for (expected, actual) in rgb.iter().zip(roundtrip) {
+                assert!((expected - actual).abs() < 1e-4);
+            }
+        }
+    }
+
     #[test]
     fn hsl_to_srgb() {
         // "truth" from https://en.wikipedia.org/wiki/HSL_and_HSV#Examples
@@ -0,0 +1,250 @@
+use std::marker::PhantomData;
+
+use super::{Color, OklabRepresentation};
+
+/// A color space in which the colors of a [`Gradient`] are interpolated.
+///
+/// The same stops produce different gradients depending on the color space: interpolating in
+/// [`Srgb`] is cheap but darkens the middle of the gradient, while [`Oklab`] and [`Oklch`] give
+/// perceptually even transitions.
+pub trait ColorSpace: Send + Sync + 'static {
+    /// Converts `color` to the components interpolated in this space, with alpha last.
+    fn to_components(color: Color) -> [f32; 4];
+
+    /// Converts components of this space back to a [`Color`].
+    fn from_components(components: [f32; 4]) -> Color;
+
+    /// Interpolates between the components `a` and `b`, with `t` in `[0.0, 1.0]`.
+    fn mix(a: [f32; 4], b: [f32; 4], t: f32) -> [f32; 4] {
+        std::array::from_fn(|i| a[i] + (b[i] - a[i]) * t)
+    }
+}
+
+/// Interpolates the gamma-encoded sRGB components, like most image editors and CSS by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Srgb;
+
+impl ColorSpace for Srgb {
+    fn to_components(color: Color) -> [f32; 4] {
+        color.as_rgba_f32()
+    }
+
+    fn from_components([r, g, b, a]: [f32; 4]) -> Color {
+        Color::rgba(r, g, b, a)
+    }
+}
+
+/// Interpolates the linear sRGB components, which matches how light blends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LinearRgb;
+
+impl ColorSpace for LinearRgb {
+    fn to_components(color: Color) -> [f32; 4] {
+        color.as_linear_rgba_f32()
+    }
+
+    fn from_components([r, g, b, a]: [f32; 4]) -> Color {
+        Color::rgba_linear(r, g, b, a)
+    }
+}
+
+/// Interpolates in the perceptual [Oklab](https://bottosson.github.io/posts/oklab/) space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Oklab;
+
+impl ColorSpace for Oklab {
+    fn to_components(color: Color) -> [f32; 4] {
+        let [r, g, b, a] = color.as_linear_rgba_f32();
+        let [l, a_, b_] = OklabRepresentation::linear_srgb_to_oklab([r, g, b]);
+        [l, a_, b_, a]
+    }
+
+    fn from_components([l, a_, b_, a]: [f32; 4]) -> Color {
+        let [r, g, b] = OklabRepresentation::oklab_to_linear_srgb([l, a_, b_]);
+        Color::rgba_linear(r, g, b, a)
+    }
+}
+
+/// Interpolates in Oklch, the polar form of [`Oklab`], going the shortest way around the hue
+/// circle. Keeps the chroma of saturated colors, at the cost of passing through other hues.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Oklch;
+
+impl ColorSpace for Oklch {
+    fn to_components(color: Color) -> [f32; 4] {
+        let [l, a_, b_, a] = Oklab::to_components(color);
+        let [l, c, h] = OklabRepresentation::oklab_to_oklch([l, a_, b_]);
+        [l, c, h, a]
+    }
+
+    fn from_components([l, c, h, a]: [f32; 4]) -> Color {
+        let [l, a_, b_] = OklabRepresentation::oklch_to_oklab([l, c, h]);
+        Oklab::from_components([l, a_, b_, a])
+    }
+
+    fn mix(a: [f32; 4], b: [f32; 4], t: f32) -> [f32; 4] {
+        const ACHROMATIC: f32 = 1e-4;
+
+        // The hue of a gray is meaningless, so use the hue of the other color
+        let (mut hue_a, mut hue_b) = (a[2], b[2]);
+        if a[1] < ACHROMATIC {
+            hue_a = hue_b;
+        } else if b[1] < ACHROMATIC {
+            hue_b = hue_a;
+        }
+        let delta = (hue_b - hue_a + 180.0).rem_euclid(360.0) - 180.0;
+
+        let mut mixed = Srgb::mix(a, b, t);
+        mixed[2] = (hue_a + delta * t).rem_euclid(360.0);
+        mixed
+    }
+}
+
+/// A color and its position along a [`Gradient`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColorStop {
+    /// The position of the stop, usually in `[0.0, 1.0]`.
+    pub position: f32,
+    /// The color at the position of the stop.
+    pub color: Color,
+}
+
+/// A sequence of [`ColorStop`]s, interpolated in the [`ColorSpace`] `C`.
+///
+/// Before the first stop and after the last one, the gradient has the color of that stop.
+///
+/// ```
+/// # use bevy_render::color::{Color, Gradient, Oklch};
+/// let gradient = Gradient::<Oklch>::new([(0.0, Color::RED), (1.0, Color::BLUE)]);
+/// let purple = gradient.sample(0.5);
+/// let colors: Vec<Color> = gradient.samples(8).collect();
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gradient<C: ColorSpace = Oklab> {
+    stops: Vec<ColorStop>,
+    marker: PhantomData<fn() -> C>,
+}
+
+impl<C: ColorSpace> Default for Gradient<C> {
+    fn default() -> Self {
+        Self {
+            stops: Vec::new(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<C: ColorSpace> Gradient<C> {
+    /// Creates a gradient from `(position, color)` pairs, in any order.
+    pub fn new(stops: impl IntoIterator<Item = (f32, Color)>) -> Self {
+        let mut gradient = Self::default();
+        for (position, color) in stops {
+            gradient.add_stop(position, color);
+        }
+        gradient
+    }
+
+    /// Creates a gradient with evenly spaced stops between `0.0` and `1.0`.
+    pub fn even(colors: impl IntoIterator<Item = Color>) -> Self {
+        let colors: Vec<Color> = colors.into_iter().collect();
+        let step = 1.0 / (colors.len().max(2) - 1) as f32;
+        Self::new(
+            colors
+                .into_iter()
+                .enumerate()
+                .map(|(i, color)| (i as f32 * step, color)),
+        )
+    }
+
+    /// Adds a stop. Stops at the same position as an existing one are placed after it, which
+    /// makes a hard transition.
+    pub fn add_stop(&mut self, position: f32, color: Color) {
+        let index = self.stops.partition_point(|stop| stop.position <= position);
+        self.stops.insert(index, ColorStop { position, color });
+    }
+
+    /// Adds a stop, see [`Gradient::add_stop`].
+    pub fn with_stop(mut self, position: f32, color: Color) -> Self {
+        self.add_stop(position, color);
+        self
+    }
+
+    /// Returns the stops, sorted by position.
+    pub fn stops(&self) -> &[ColorStop] {
+        &self.stops
+    }
+
+    /// Returns the color at `position`, or [`Color::NONE`] if the gradient has no stops.
+    pub fn sample(&self, position: f32) -> Color {
+        let next = self.stops.partition_point(|stop| stop.position <= position);
+        match (
+            next.checked_sub(1).map(|i| self.stops[i]),
+            self.stops.get(next).copied(),
+        ) {
+            (None, None) => Color::NONE,
+            (Some(stop), None) | (None, Some(stop)) => stop.color,
+            (Some(start), Some(end)) => {
+                let t = (position - start.position) / (end.position - start.position);
+                C::from_components(C::mix(
+                    C::to_components(start.color),
+                    C::to_components(end.color),
+                    t,
+                ))
+            }
+        }
+    }
+
+    /// Returns `count` colors sampled at evenly spaced positions, from the first stop to the last.
+    pub fn samples(&self, count: usize) -> impl Iterator<Item = Color> + '_ {
+        let start = self.stops.first().map_or(0.0, |stop| stop.position);
+        let end = self.stops.last().map_or(0.0, |stop| stop.position);
+        let step = (end - start) / (count.max(2) - 1) as f32;
+        (0..count).map(move |i| self.sample(start + i as f32 * step))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_color_eq(a: Color, b: Color) {
+        let (a, b) = (a.as_rgba_f32(), b.as_rgba_f32());
+        assert!(
+            a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-3),
+            "{a:?} != {b:?}"
+        );
+    }
+
+    #[test]
+    fn sample_gradient() {
+        let gradient =
+            Gradient::<Srgb>::new([(1.0, Color::BLUE), (0.0, Color::RED), (0.5, Color::WHITE)]);
+        assert_eq!(gradient.stops()[1].color, Color::WHITE);
+        assert_color_eq(gradient.sample(-1.0), Color::RED);
+        assert_color_eq(gradient.sample(0.25), Color::rgb(1.0, 0.5, 0.5));
+        assert_color_eq(gradient.sample(0.5), Color::WHITE);
+        assert_color_eq(gradient.sample(2.0), Color::BLUE);
+        assert_eq!(gradient.samples(5).count(), 5);
+        assert_eq!(Gradient::<Srgb>::default().sample(0.5), Color::NONE);
+
+        // Endpoints are preserved in every space
+        for color in [
+            Gradient::<Oklab>::even([Color::RED, Color::GREEN]).sample(1.0),
+            Gradient::<Oklch>::even([Color::RED, Color::GREEN]).sample(1.0),
+            Gradient::<LinearRgb>::even([Color::RED, Color::GREEN]).sample(1.0),
+        ] {
+            assert_color_eq(color, Color::GREEN);
+        }
+    }
+
+    #[test]
+    fn oklch_takes_shortest_hue_path() {
+        let a = [0.5, 0.1, 350.0, 1.0];
+        let b = [0.5, 0.1, 10.0, 1.0];
+        assert!((Oklch::mix(a, b, 0.5)[2] - 0.0).abs() < 1e-3);
+
+        // Mixing with a gray keeps the hue of the saturated color
+        let gray = [0.5, 0.0, 0.0, 1.0];
+        assert!((Oklch::mix(gray, b, 0.5)[2] - 10.0).abs() < 1e-3);
+    }
+}
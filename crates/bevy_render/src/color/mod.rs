@@ -1,6 +1,8 @@
 mod colorspace;
+mod gradient;
 
 pub use colorspace::*;
+pub use gradient::*;
 
 use bevy_math::{Vec3, Vec4};
 use bevy_reflect::{Reflect, ReflectDeserialize, ReflectSerialize};
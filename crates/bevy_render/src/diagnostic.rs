@@ -0,0 +1,504 @@
+//! GPU timing and memory diagnostics.
+//!
+//! # GPU timings
+//!
+//! When the [`RenderDiagnosticsPlugin`] is added and the device supports
+//! [`TIMESTAMP_QUERY`](wgpu::Features::TIMESTAMP_QUERY), the time the GPU spends in each
+//! render and compute pass is measured with timestamp queries and recorded as a diagnostic
+//! named after the pass label, such as `gpu/main_opaque_pass_3d`.
+//!
+//! If the device also supports [`TIMESTAMP_QUERY_INSIDE_PASSES`](wgpu::Features::TIMESTAMP_QUERY_INSIDE_PASSES),
+//! every render graph node is measured as well, under `gpu/node/<label>`.
+//!
+//! On devices without timestamp queries, such as WebGL2, no GPU diagnostics are recorded
+//! and rendering is otherwise unaffected.
+//!
+//! Passes begun through [`RenderContext::begin_tracked_render_pass`](crate::renderer::RenderContext::begin_tracked_render_pass)
+//! or [`RenderContext::begin_compute_pass`](crate::renderer::RenderContext::begin_compute_pass)
+//! with a label are measured automatically.
+//! Passes recorded in a command buffer generation task can be measured by moving a
+//! [`GpuTimestamps`] handle, obtained from
+//! [`RenderContext::gpu_timestamps`](crate::renderer::RenderContext::gpu_timestamps), into the task.
+//!
+//! # GPU memory
+//!
+//! The [`GpuMemoryDiagnosticsPlugin`] reports the number and size of the buffers and textures
+//! that are alive, as tracked by [`GpuAllocations`].
+
+use std::{
+    borrow::Cow,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use bevy_app::Update;
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_diagnostic::{
+    bytes_to_mebibytes, Diagnostic, DiagnosticMeasurement, DiagnosticPath, Diagnostics,
+    DiagnosticsStore, RegisterDiagnostic,
+};
+use bevy_ecs::{
+    schedule::IntoSystemConfigs,
+    system::{Res, ResMut, Resource},
+};
+use bevy_time::common_conditions::on_real_timer;
+use bevy_utils::{tracing::info, Duration, HashMap, Instant};
+use wgpu::{
+    BufferAsyncError, BufferDescriptor, BufferUsages, CommandEncoder, ComputePassTimestampWrites,
+    Features, MapMode, QuerySet, QuerySetDescriptor, QueryType, RenderPassTimestampWrites,
+};
+
+use crate::{
+    render_resource::GpuAllocations,
+    renderer::{RenderDevice, RenderQueue},
+    RenderApp,
+};
+
+/// The maximum number of timestamps written per frame.
+/// Spans beyond this limit are not measured.
+const MAX_TIMESTAMP_QUERIES: u32 = 512;
+
+/// The maximum number of frames whose timestamps can be waiting to be read back at once.
+/// Frames beyond this limit are not measured.
+const MAX_PENDING_FRAMES: usize = 4;
+
+/// Records the time the GPU spends in each render pass, compute pass and render graph node
+/// as diagnostics. See the [module-level documentation](self) for details.
+pub struct RenderDiagnosticsPlugin;
+
+impl Plugin for RenderDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        let timings = GpuTimings::default();
+
+        app.init_resource::<DiagnosticsStore>()
+            .insert_resource(timings.clone())
+            .add_systems(PreUpdate, sync_gpu_diagnostics);
+
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.insert_resource(timings);
+        }
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Ok(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        let device = render_app.world.resource::<RenderDevice>().clone();
+        if !device.features().contains(Features::TIMESTAMP_QUERY) {
+            info!("GPU timing diagnostics are disabled: the device does not support timestamp queries");
+            return;
+        }
+
+        let queue = render_app.world.resource::<RenderQueue>();
+        let timings = render_app.world.resource::<GpuTimings>().clone();
+        let recorder = DiagnosticsRecorder::new(device, queue, timings);
+        render_app.insert_resource(recorder);
+    }
+}
+
+/// The GPU timings of the most recently measured frame, in milliseconds,
+/// shared between the main world and the render world.
+#[derive(Resource, Clone, Default)]
+struct GpuTimings(Arc<Mutex<Vec<(Cow<'static, str>, f64)>>>);
+
+/// Copies the latest GPU timings into the [`DiagnosticsStore`],
+/// registering a diagnostic the first time a pass or node is measured.
+fn sync_gpu_diagnostics(timings: Res<GpuTimings>, mut store: ResMut<DiagnosticsStore>) {
+    let timings = std::mem::take(&mut *timings.0.lock().unwrap());
+    let now = Instant::now();
+
+    for (name, milliseconds) in timings {
+        let path = DiagnosticPath::from_components(["gpu", &name]);
+        if store.get(&path).is_none() {
+            store.add(Diagnostic::new(path.clone()).with_suffix("ms"));
+        }
+        if let Some(diagnostic) = store.get_mut(&path).filter(|d| d.is_enabled) {
+            diagnostic.add_measurement(DiagnosticMeasurement {
+                time: now,
+                value: milliseconds,
+            });
+        }
+    }
+}
+
+/// Owns the timestamp queries of the frames being measured, in the render world.
+///
+/// This is only present if the device supports timestamp queries.
+#[derive(Resource)]
+pub struct DiagnosticsRecorder {
+    device: RenderDevice,
+    timings: GpuTimings,
+    /// The number of nanoseconds per timestamp tick.
+    timestamp_period: f64,
+    encoder_timestamps: bool,
+    frames: Vec<FrameTimestamps>,
+    current_frame: Option<usize>,
+}
+
+struct FrameTimestamps {
+    timestamps: GpuTimestamps,
+    resolve_buffer: wgpu::Buffer,
+    readback_buffer: wgpu::Buffer,
+    /// Set once the readback buffer has been mapped, or failed to be.
+    map_result: Arc<Mutex<Option<Result<(), BufferAsyncError>>>>,
+    /// Whether the timestamps of this frame are waiting to be read back.
+    pending: bool,
+}
+
+impl DiagnosticsRecorder {
+    fn new(device: RenderDevice, queue: &RenderQueue, timings: GpuTimings) -> Self {
+        Self {
+            timings,
+            timestamp_period: queue.get_timestamp_period() as f64,
+            encoder_timestamps: device
+                .features()
+                .contains(Features::TIMESTAMP_QUERY_INSIDE_PASSES),
+            device,
+            frames: Vec::new(),
+            current_frame: None,
+        }
+    }
+
+    /// Reads back the timestamps of previous frames that are ready,
+    /// and returns the handle used to measure the current frame.
+    ///
+    /// Returns `None` if too many frames are still waiting to be read back.
+    pub fn begin_frame(&mut self) -> Option<GpuTimestamps> {
+        for frame in &mut self.frames {
+            if !frame.pending {
+                continue;
+            }
+            let map_result = frame.map_result.lock().unwrap().take();
+            match map_result {
+                Some(Ok(())) => {
+                    *self.timings.0.lock().unwrap() = frame.read_back(self.timestamp_period);
+                }
+                // The frame is not measured, and its buffers are reused.
+                Some(Err(_)) => {}
+                None => continue,
+            }
+            frame.pending = false;
+        }
+
+        let index = match self.frames.iter().position(|frame| !frame.pending) {
+            Some(index) => index,
+            None if self.frames.len() < MAX_PENDING_FRAMES => {
+                self.frames
+                    .push(FrameTimestamps::new(&self.device, self.encoder_timestamps));
+                self.frames.len() - 1
+            }
+            None => return None,
+        };
+
+        let frame = &mut self.frames[index];
+        frame.timestamps.reset();
+        self.current_frame = Some(index);
+        Some(frame.timestamps.clone())
+    }
+
+    /// Records the commands that copy the timestamps of the current frame to the readback buffer.
+    ///
+    /// This must be recorded after all of the frame's passes.
+    pub fn resolve(&self, encoder: &mut CommandEncoder) {
+        let Some(frame) = self.current_frame.map(|index| &self.frames[index]) else {
+            return;
+        };
+        let count = frame.timestamps.len();
+        if count == 0 {
+            return;
+        }
+
+        let size = count as u64 * std::mem::size_of::<u64>() as u64;
+        encoder.resolve_query_set(
+            &frame.timestamps.inner.query_set,
+            0..count,
+            &frame.resolve_buffer,
+            0,
+        );
+        encoder.copy_buffer_to_buffer(&frame.resolve_buffer, 0, &frame.readback_buffer, 0, size);
+    }
+
+    /// Starts reading back the timestamps of the current frame.
+    ///
+    /// This must be called after the commands recorded by [`resolve`](Self::resolve) were submitted.
+    pub fn finish_frame(&mut self) {
+        let Some(index) = self.current_frame.take() else {
+            return;
+        };
+        let frame = &mut self.frames[index];
+        let count = frame.timestamps.len();
+        if count == 0 {
+            return;
+        }
+
+        frame.pending = true;
+        let map_result = frame.map_result.clone();
+        let size = count as u64 * std::mem::size_of::<u64>() as u64;
+        self.device.map_buffer(
+            &frame.readback_buffer.slice(..size),
+            MapMode::Read,
+            move |result| *map_result.lock().unwrap() = Some(result),
+        );
+    }
+}
+
+impl FrameTimestamps {
+    fn new(device: &RenderDevice, encoder_timestamps: bool) -> Self {
+        let size = MAX_TIMESTAMP_QUERIES as u64 * std::mem::size_of::<u64>() as u64;
+        let query_set = device.wgpu_device().create_query_set(&QuerySetDescriptor {
+            label: Some("gpu_timestamps_query_set"),
+            ty: QueryType::Timestamp,
+            count: MAX_TIMESTAMP_QUERIES,
+        });
+        let resolve_buffer = device.wgpu_device().create_buffer(&BufferDescriptor {
+            label: Some("gpu_timestamps_resolve_buffer"),
+            size,
+            usage: BufferUsages::QUERY_RESOLVE | BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let readback_buffer = device.wgpu_device().create_buffer(&BufferDescriptor {
+            label: Some("gpu_timestamps_readback_buffer"),
+            size,
+            usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        Self {
+            timestamps: GpuTimestamps {
+                inner: Arc::new(GpuTimestampsInner {
+                    query_set,
+                    encoder_timestamps,
+                    next_query: AtomicU32::new(0),
+                    spans: Mutex::new(Vec::new()),
+                }),
+            },
+            resolve_buffer,
+            readback_buffer,
+            map_result: Arc::new(Mutex::new(None)),
+            pending: false,
+        }
+    }
+
+    /// Returns the total duration of each span, in milliseconds, and unmaps the readback buffer.
+    fn read_back(&mut self, timestamp_period: f64) -> Vec<(Cow<'static, str>, f64)> {
+        let count = self.timestamps.len() as usize;
+        let size = (count * std::mem::size_of::<u64>()) as u64;
+        let ticks = {
+            let data = self.readback_buffer.slice(..size).get_mapped_range();
+            data.chunks_exact(8)
+                .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+                .collect::<Vec<_>>()
+        };
+        self.readback_buffer.unmap();
+
+        let spans = self.timestamps.inner.spans.lock().unwrap();
+        let mut totals = HashMap::<Cow<'static, str>, f64>::default();
+        for span in spans.iter() {
+            let (Some(&begin), Some(&end)) =
+                (ticks.get(span.begin as usize), ticks.get(span.end as usize))
+            else {
+                continue;
+            };
+            // Unwritten queries resolve to zero, and some drivers report out of order timestamps.
+            if begin == 0 || end < begin {
+                continue;
+            }
+            let milliseconds = (end - begin) as f64 * timestamp_period / 1_000_000.0;
+            *totals.entry(span.name.clone()).or_default() += milliseconds;
+        }
+        totals.into_iter().collect()
+    }
+}
+
+/// A handle used to measure the GPU time of passes during the current frame.
+///
+/// This can be cloned and moved into command buffer generation tasks.
+/// The same name can be measured several times per frame, in which case the durations are summed.
+#[derive(Clone)]
+pub struct GpuTimestamps {
+    inner: Arc<GpuTimestampsInner>,
+}
+
+struct GpuTimestampsInner {
+    query_set: QuerySet,
+    encoder_timestamps: bool,
+    next_query: AtomicU32,
+    spans: Mutex<Vec<TimestampSpan>>,
+}
+
+struct TimestampSpan {
+    name: Cow<'static, str>,
+    begin: u32,
+    end: u32,
+}
+
+/// A span measured with [`GpuTimestamps::begin_encoder_span`].
+#[must_use = "the span must be ended with `GpuTimestamps::end_encoder_span`"]
+pub struct EncoderSpan {
+    end: u32,
+}
+
+impl GpuTimestamps {
+    /// Returns the timestamp writes that measure a render pass under the given name,
+    /// or `None` if no more timestamps can be written this frame.
+    pub fn render_pass_writes(
+        &self,
+        name: impl Into<Cow<'static, str>>,
+    ) -> Option<RenderPassTimestampWrites<'_>> {
+        let begin = self.allocate_span(name.into())?;
+        Some(RenderPassTimestampWrites {
+            query_set: &self.inner.query_set,
+            beginning_of_pass_write_index: Some(begin),
+            end_of_pass_write_index: Some(begin + 1),
+        })
+    }
+
+    /// Returns the timestamp writes that measure a compute pass under the given name,
+    /// or `None` if no more timestamps can be written this frame.
+    pub fn compute_pass_writes(
+        &self,
+        name: impl Into<Cow<'static, str>>,
+    ) -> Option<ComputePassTimestampWrites<'_>> {
+        let begin = self.allocate_span(name.into())?;
+        Some(ComputePassTimestampWrites {
+            query_set: &self.inner.query_set,
+            beginning_of_pass_write_index: Some(begin),
+            end_of_pass_write_index: Some(begin + 1),
+        })
+    }
+
+    /// Writes a timestamp to the encoder, starting a span that covers all of the commands
+    /// submitted until [`end_encoder_span`](Self::end_encoder_span) is called.
+    ///
+    /// Returns `None` if the device does not support timestamps outside of passes,
+    /// or if no more timestamps can be written this frame.
+    pub fn begin_encoder_span(
+        &self,
+        encoder: &mut CommandEncoder,
+        name: impl Into<Cow<'static, str>>,
+    ) -> Option<EncoderSpan> {
+        if !self.inner.encoder_timestamps {
+            return None;
+        }
+        let begin = self.allocate_span(name.into())?;
+        encoder.write_timestamp(&self.inner.query_set, begin);
+        Some(EncoderSpan { end: begin + 1 })
+    }
+
+    /// Writes the timestamp ending a span started with [`begin_encoder_span`](Self::begin_encoder_span).
+    pub fn end_encoder_span(&self, encoder: &mut CommandEncoder, span: EncoderSpan) {
+        encoder.write_timestamp(&self.inner.query_set, span.end);
+    }
+
+    /// Reserves two consecutive queries for a span, returning the index of the first.
+    fn allocate_span(&self, name: Cow<'static, str>) -> Option<u32> {
+        let begin = self
+            .inner
+            .next_query
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |next| {
+                (next + 2 <= MAX_TIMESTAMP_QUERIES).then_some(next + 2)
+            })
+            .ok()?;
+        self.inner.spans.lock().unwrap().push(TimestampSpan {
+            name,
+            begin,
+            end: begin + 1,
+        });
+        Some(begin)
+    }
+
+    /// The number of queries reserved this frame.
+    fn len(&self) -> u32 {
+        self.inner.next_query.load(Ordering::Relaxed)
+    }
+
+    fn reset(&self) {
+        self.inner.next_query.store(0, Ordering::Relaxed);
+        self.inner.spans.lock().unwrap().clear();
+    }
+}
+
+/// Adds diagnostics for the GPU memory used by buffers and textures to an App.
+///
+/// Memory is reported in mebibytes. See [`GpuAllocations`] for what is accounted for.
+pub struct GpuMemoryDiagnosticsPlugin {
+    /// How often the memory usage is measured.
+    pub wait_duration: Duration,
+}
+
+impl Default for GpuMemoryDiagnosticsPlugin {
+    fn default() -> Self {
+        Self {
+            wait_duration: Duration::from_secs(1),
+        }
+    }
+}
+
+impl Plugin for GpuMemoryDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_diagnostic(Diagnostic::new(Self::BUFFERS).with_suffix("MiB"))
+            .register_diagnostic(Diagnostic::new(Self::TEXTURES).with_suffix("MiB"))
+            .register_diagnostic(Diagnostic::new(Self::BUFFER_COUNT).with_smoothing_factor(0.0))
+            .register_diagnostic(Diagnostic::new(Self::TEXTURE_COUNT).with_smoothing_factor(0.0))
+            .add_systems(
+                Update,
+                Self::diagnostic_system.run_if(on_real_timer(self.wait_duration)),
+            );
+    }
+}
+
+impl GpuMemoryDiagnosticsPlugin {
+    pub const BUFFERS: DiagnosticPath = DiagnosticPath::const_new("memory/gpu/buffers");
+    pub const TEXTURES: DiagnosticPath = DiagnosticPath::const_new("memory/gpu/textures");
+    pub const BUFFER_COUNT: DiagnosticPath = DiagnosticPath::const_new("memory/gpu/buffer_count");
+    pub const TEXTURE_COUNT: DiagnosticPath = DiagnosticPath::const_new("memory/gpu/texture_count");
+
+    pub fn diagnostic_system(mut diagnostics: Diagnostics) {
+        let allocations = GpuAllocations::current();
+        diagnostics.add_measurement(&Self::BUFFERS, || {
+            bytes_to_mebibytes(allocations.buffer_bytes as usize)
+        });
+        diagnostics.add_measurement(&Self::TEXTURES, || {
+            bytes_to_mebibytes(allocations.texture_bytes as usize)
+        });
+        diagnostics.add_measurement(&Self::BUFFER_COUNT, || allocations.buffer_count as f64);
+        diagnostics.add_measurement(&Self::TEXTURE_COUNT, || allocations.texture_count as f64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::{system::RunSystemOnce, world::World};
+
+    #[test]
+    fn gpu_timings_are_recorded_as_diagnostics() {
+        let mut world = World::new();
+        world.init_resource::<DiagnosticsStore>();
+        let timings = GpuTimings::default();
+        world.insert_resource(timings.clone());
+
+        timings.0.lock().unwrap().push(("main_pass".into(), 1.5));
+        world.run_system_once(sync_gpu_diagnostics);
+
+        let path = DiagnosticPath::new("gpu/main_pass");
+        let store = world.resource::<DiagnosticsStore>();
+        assert_eq!(store.get(&path).and_then(Diagnostic::value), Some(1.5));
+        assert!(timings.0.lock().unwrap().is_empty());
+
+        // Disabled diagnostics are left alone
+        world
+            .resource_mut::<DiagnosticsStore>()
+            .get_mut(&path)
+            .unwrap()
+            .is_enabled = false;
+        timings.0.lock().unwrap().push(("main_pass".into(), 3.0));
+        world.run_system_once(sync_gpu_diagnostics);
+
+        let store = world.resource::<DiagnosticsStore>();
+        assert_eq!(store.get(&path).and_then(Diagnostic::value), Some(1.5));
+    }
+}
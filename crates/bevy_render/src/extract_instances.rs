@@ -3,6 +3,10 @@
 //!
 //! This is essentially the same as the `extract_component` module, but
 //! higher-performance because it avoids the ECS overhead.
+//!
+//! Instances implementing [`ExtractRetainedInstance`] can also be extracted
+//! incrementally: the render world then keeps a mirror of the main world that
+//! is only updated for the entities that changed since the last extraction.
 
 use std::marker::PhantomData;
 
@@ -10,13 +14,18 @@ use bevy_app::{App, Plugin};
 use bevy_asset::{Asset, AssetId, Handle};
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::{
-    prelude::Entity,
-    query::{QueryFilter, QueryItem, ReadOnlyQueryData},
+    component::Component,
+    prelude::{Entity, RemovedComponents},
+    query::{Changed, QueryFilter, QueryItem, ReadOnlyQueryData},
     system::{lifetimeless::Read, Query, ResMut, Resource},
 };
+use bevy_transform::components::GlobalTransform;
 use bevy_utils::EntityHashMap;
 
-use crate::{prelude::ViewVisibility, Extract, ExtractSchedule, RenderApp};
+use crate::{
+    prelude::{InheritedVisibility, ViewVisibility},
+    Extract, ExtractSchedule, RenderApp,
+};
 
 /// Describes how to extract data needed for rendering from a component or
 /// components.
@@ -37,17 +46,27 @@ pub trait ExtractInstance: Send + Sync + Sized + 'static {
     fn extract(item: QueryItem<'_, Self::QueryData>) -> Option<Self>;
 }
 
+/// An [`ExtractInstance`] that can be extracted incrementally, see
+/// [`ExtractInstancesPlugin::retained`].
+pub trait ExtractRetainedInstance: ExtractInstance {
+    /// The component whose changes cause the instance of an entity to be
+    /// extracted again, and whose removal removes it.
+    ///
+    /// The [`QueryFilter`](ExtractInstance::QueryFilter) is only evaluated
+    /// when this component changes.
+    type Source: Component;
+}
+
 /// This plugin extracts one or more components into the "render world" as
 /// extracted instances.
 ///
 /// Therefore it sets up the [`ExtractSchedule`] step for the specified
 /// [`ExtractedInstances`].
-#[derive(Default)]
 pub struct ExtractInstancesPlugin<EI>
 where
     EI: ExtractInstance,
 {
-    only_extract_visible: bool,
+    add_extract_system: fn(&mut App),
     marker: PhantomData<fn() -> EI>,
 }
 
@@ -66,6 +85,15 @@ where
     }
 }
 
+impl<EI> Default for ExtractInstancesPlugin<EI>
+where
+    EI: ExtractInstance,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<EI> ExtractInstancesPlugin<EI>
 where
     EI: ExtractInstance,
@@ -74,7 +102,9 @@ where
     /// the render world, whether the entity is visible or not.
     pub fn new() -> Self {
         Self {
-            only_extract_visible: false,
+            add_extract_system: |render_app| {
+                render_app.add_systems(ExtractSchedule, extract_all::<EI>);
+            },
             marker: PhantomData,
         }
     }
@@ -83,7 +113,31 @@ where
     /// if and only if the entity it's attached to is visible.
     pub fn extract_visible() -> Self {
         Self {
-            only_extract_visible: true,
+            add_extract_system: |render_app| {
+                render_app.add_systems(ExtractSchedule, extract_visible::<EI>);
+            },
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<EI> ExtractInstancesPlugin<EI>
+where
+    EI: ExtractRetainedInstance,
+{
+    /// Creates a new [`ExtractInstancesPlugin`] that keeps the instances of
+    /// all entities in the render world, whether they are visible or not, and
+    /// only extracts the instances whose [`Source`](ExtractRetainedInstance::Source)
+    /// component was added or changed since the last extraction.
+    ///
+    /// This avoids copying the instances of static entities every frame. As
+    /// invisible entities are kept, look the instances up from the visible
+    /// entities of the views.
+    pub fn retained() -> Self {
+        Self {
+            add_extract_system: |render_app| {
+                render_app.add_systems(ExtractSchedule, extract_retained::<EI>);
+            },
             marker: PhantomData,
         }
     }
@@ -96,11 +150,7 @@ where
     fn build(&self, app: &mut App) {
         if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app.init_resource::<ExtractedInstances<EI>>();
-            if self.only_extract_visible {
-                render_app.add_systems(ExtractSchedule, extract_visible::<EI>);
-            } else {
-                render_app.add_systems(ExtractSchedule, extract_all::<EI>);
-            }
+            (self.add_extract_system)(render_app);
         }
     }
 }
@@ -135,6 +185,30 @@ fn extract_visible<EI>(
     }
 }
 
+fn extract_retained<EI>(
+    mut extracted_instances: ResMut<ExtractedInstances<EI>>,
+    query: Extract<Query<(Entity, EI::QueryData), (EI::QueryFilter, Changed<EI::Source>)>>,
+    mut removed: Extract<RemovedComponents<EI::Source>>,
+) where
+    EI: ExtractRetainedInstance,
+{
+    // Removals come first, so that a component removed and inserted again is
+    // extracted.
+    for entity in removed.read() {
+        extracted_instances.remove(&entity);
+    }
+    for (entity, other) in &query {
+        match EI::extract(other) {
+            Some(extract_instance) => {
+                extracted_instances.insert(entity, extract_instance);
+            }
+            None => {
+                extracted_instances.remove(&entity);
+            }
+        }
+    }
+}
+
 impl<A> ExtractInstance for AssetId<A>
 where
     A: Asset,
@@ -146,3 +220,83 @@ where
         Some(item.id())
     }
 }
+
+impl<A> ExtractRetainedInstance for AssetId<A>
+where
+    A: Asset,
+{
+    type Source = Handle<A>;
+}
+
+impl ExtractInstance for GlobalTransform {
+    type QueryData = Read<GlobalTransform>;
+    type QueryFilter = ();
+
+    fn extract(item: QueryItem<'_, Self::QueryData>) -> Option<Self> {
+        Some(*item)
+    }
+}
+
+impl ExtractRetainedInstance for GlobalTransform {
+    type Source = GlobalTransform;
+}
+
+// Only the `InheritedVisibility` can be retained: the `ViewVisibility` is
+// computed again every frame.
+impl ExtractInstance for InheritedVisibility {
+    type QueryData = Read<InheritedVisibility>;
+    type QueryFilter = ();
+
+    fn extract(item: QueryItem<'_, Self::QueryData>) -> Option<Self> {
+        Some(*item)
+    }
+}
+
+impl ExtractRetainedInstance for InheritedVisibility {
+    type Source = InheritedVisibility;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MainWorld;
+    use bevy_ecs::{schedule::Schedule, world::World};
+
+    #[test]
+    fn retained_instances_follow_changes() {
+        let mut main_world = World::new();
+        let entity = main_world.spawn(GlobalTransform::IDENTITY).id();
+        let mut render_world = World::new();
+        render_world.init_resource::<ExtractedInstances<GlobalTransform>>();
+        render_world.insert_resource(MainWorld(main_world));
+        let mut schedule = Schedule::default();
+        schedule.add_systems(extract_retained::<GlobalTransform>);
+
+        let mut extract = |update_main_world: &dyn Fn(&mut World)| {
+            let mut main_world = render_world.resource_mut::<MainWorld>();
+            update_main_world(&mut main_world);
+            main_world.clear_trackers();
+            schedule.run(&mut render_world);
+            render_world
+                .resource::<ExtractedInstances<GlobalTransform>>()
+                .get(&entity)
+                .copied()
+        };
+
+        assert_eq!(extract(&|_| {}), Some(GlobalTransform::IDENTITY));
+        let moved = GlobalTransform::from_xyz(1.0, 2.0, 3.0);
+        assert_eq!(
+            extract(&|world| {
+                world.entity_mut(entity).insert(moved);
+            }),
+            Some(moved)
+        );
+        assert_eq!(extract(&|_| {}), Some(moved));
+        assert_eq!(
+            extract(&|world| {
+                world.entity_mut(entity).remove::<GlobalTransform>();
+            }),
+            None
+        );
+    }
+}
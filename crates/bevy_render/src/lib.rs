@@ -10,6 +10,7 @@ pub mod batching;
 pub mod camera;
 pub mod color;
 pub mod deterministic;
+pub mod diagnostic;
 pub mod extract_component;
 pub mod extract_instances;
 mod extract_param;
@@ -59,13 +60,19 @@ use crate::deterministic::DeterministicRenderingConfig;
 use crate::{
     camera::CameraPlugin,
     mesh::{morph::MorphPlugin, Mesh, MeshPlugin},
-    render_asset::prepare_assets,
-    render_resource::{PipelineCache, Shader, ShaderLoader},
+    render_asset::{
+        prepare_assets, reupload_render_assets_on_resume, ReuploadRenderAssets,
+        ReuploadRenderAssetsOnResume,
+    },
+    render_resource::{
+        create_pipeline_error_channels, receive_pipeline_errors, PipelineCache, PipelineErrorEvent,
+        PipelineErrors, RegisteredShaders, Shader, ShaderLoader,
+    },
     renderer::{render_system, RenderInstance},
     settings::RenderCreation,
     view::{ViewPlugin, WindowRenderPlugin},
 };
-use bevy_app::{App, AppLabel, Plugin, SubApp};
+use bevy_app::{App, AppLabel, First, Plugin, SubApp};
 use bevy_asset::{load_internal_asset, AssetApp, AssetServer, Handle};
 use bevy_ecs::{prelude::*, schedule::ScheduleLabel, system::SystemState};
 use bevy_utils::tracing::debug;
@@ -228,7 +235,17 @@ impl Plugin for RenderPlugin {
         app.init_resource::<DeterministicRenderingConfig>();
 
         app.init_asset::<Shader>()
-            .init_asset_loader::<ShaderLoader>();
+            .init_asset_loader::<ShaderLoader>()
+            .add_event::<PipelineErrorEvent>()
+            .add_event::<ReuploadRenderAssets>()
+            .init_resource::<PipelineErrors>()
+            .init_resource::<RegisteredShaders>()
+            .init_resource::<ReuploadRenderAssetsOnResume>()
+            .add_systems(
+                First,
+                reupload_render_assets_on_resume
+                    .run_if(resource_equals(ReuploadRenderAssetsOnResume(true))),
+            );
 
         match &self.render_creation {
             RenderCreation::Manual(device, queue, adapter_info, adapter, instance) => {
@@ -406,7 +423,13 @@ unsafe fn initialize_render_app(app: &mut App) {
         .add_schedule(Render::base_schedule())
         .init_resource::<render_graph::RenderGraph>()
         .insert_resource(app.world.resource::<AssetServer>().clone())
-        .add_systems(ExtractSchedule, PipelineCache::extract_shaders)
+        .add_systems(
+            ExtractSchedule,
+            (
+                PipelineCache::extract_shaders,
+                PipelineCache::extract_registered_shaders,
+            ),
+        )
         .add_systems(
             Render,
             (
@@ -426,6 +449,11 @@ unsafe fn initialize_render_app(app: &mut App) {
     app.insert_resource(receiver);
     render_app.insert_resource(sender);
 
+    let (sender, receiver) = create_pipeline_error_channels();
+    app.insert_resource(receiver)
+        .add_systems(First, receive_pipeline_errors);
+    render_app.insert_resource(sender);
+
     app.insert_sub_app(RenderApp, SubApp::new(render_app, move |main_world, render_app| {
         #[cfg(feature = "trace")]
         let _render_span = bevy_utils::tracing::info_span!("extract main app to render subapp").entered();
@@ -0,0 +1,559 @@
+//! Building procedural meshes from vertices and triangles, or from 2D profiles.
+
+use std::{f32::consts::TAU, iter};
+
+use bevy_math::{Quat, Vec2, Vec3, Vec3Swizzles};
+use bevy_transform::components::Transform;
+use wgpu::PrimitiveTopology;
+
+use crate::{
+    mesh::{GenerateTangentsError, Indices, Mesh},
+    render_asset::RenderAssetUsages,
+};
+
+/// A builder for procedural [`Mesh`]es, such as level geometry.
+///
+/// Vertices are added with their position, normal and UV with [`vertex`](Self::vertex), which
+/// returns the index used to connect them with [`triangle`](Self::triangle) and
+/// [`quad`](Self::quad). Triangles are front facing when their vertices are counter-clockwise.
+///
+/// Common shapes can be generated from a 2D outline with [`extrude`](Self::extrude),
+/// [`lathe`](Self::lathe) and [`sweep`](Self::sweep), and several builders can be combined with
+/// [`append`](Self::append).
+///
+/// ```
+/// # use bevy_math::{Vec2, Vec3};
+/// # use bevy_render::mesh::{Mesh, MeshBuilder};
+/// # use bevy_transform::components::Transform;
+/// // An L-shaped wall, 3 units high
+/// let outline = [
+///     Vec2::new(0.0, 0.0),
+///     Vec2::new(4.0, 0.0),
+///     Vec2::new(4.0, 0.5),
+///     Vec2::new(0.5, 0.5),
+///     Vec2::new(0.5, 4.0),
+///     Vec2::new(0.0, 4.0),
+/// ];
+/// let mut wall = MeshBuilder::extrude(&outline, 3.0);
+/// wall.transform_by(Transform::from_xyz(0.0, 1.5, 0.0).looking_to(Vec3::Y, Vec3::Z));
+///
+/// // A pillar on top of it
+/// let pillar_profile = [Vec2::new(0.3, 0.0), Vec2::new(0.2, 0.2), Vec2::new(0.2, 2.0)];
+/// let mut pillar = MeshBuilder::lathe(&pillar_profile, 16);
+/// pillar.transform_by(Transform::from_xyz(0.25, 3.0, 0.25));
+///
+/// let mesh: Mesh = wall.append(&pillar).build();
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MeshBuilder {
+    positions: Vec<Vec3>,
+    normals: Vec<Vec3>,
+    uvs: Vec<Vec2>,
+    indices: Vec<u32>,
+}
+
+impl MeshBuilder {
+    /// Creates a builder without any vertices.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a vertex, and returns its index.
+    pub fn vertex(&mut self, position: Vec3, normal: Vec3, uv: Vec2) -> u32 {
+        self.positions.push(position);
+        self.normals.push(normal);
+        self.uvs.push(uv);
+        (self.positions.len() - 1) as u32
+    }
+
+    /// Adds a triangle between the vertices with the given indices, which are counter-clockwise
+    /// when seen from the front.
+    pub fn triangle(&mut self, a: u32, b: u32, c: u32) -> &mut Self {
+        self.indices.extend([a, b, c]);
+        self
+    }
+
+    /// Adds a quad between the vertices with the given indices, which are counter-clockwise when
+    /// seen from the front.
+    ///
+    /// The quad is split into the triangles `a, b, c` and `a, c, d`.
+    pub fn quad(&mut self, a: u32, b: u32, c: u32, d: u32) -> &mut Self {
+        self.indices.extend([a, b, c, a, c, d]);
+        self
+    }
+
+    /// Returns the positions of the vertices.
+    pub fn positions(&self) -> &[Vec3] {
+        &self.positions
+    }
+
+    /// Returns the positions of the vertices, to displace them.
+    ///
+    /// The normals are not updated, see [`compute_smooth_normals`](Self::compute_smooth_normals).
+    pub fn positions_mut(&mut self) -> &mut [Vec3] {
+        &mut self.positions
+    }
+
+    /// Returns the normals of the vertices.
+    pub fn normals(&self) -> &[Vec3] {
+        &self.normals
+    }
+
+    /// Returns the UVs of the vertices.
+    pub fn uvs(&self) -> &[Vec2] {
+        &self.uvs
+    }
+
+    /// Returns the indices of the vertices of each triangle, three by three.
+    pub fn indices(&self) -> &[u32] {
+        &self.indices
+    }
+
+    /// Returns the number of vertices.
+    pub fn vertex_count(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Adds the vertices and triangles of `other` to this builder.
+    pub fn append(&mut self, other: &MeshBuilder) -> &mut Self {
+        let offset = self.positions.len() as u32;
+        self.positions.extend_from_slice(&other.positions);
+        self.normals.extend_from_slice(&other.normals);
+        self.uvs.extend_from_slice(&other.uvs);
+        self.indices
+            .extend(other.indices.iter().map(|index| index + offset));
+        self
+    }
+
+    /// Transforms the positions and normals of the vertices by the given [`Transform`].
+    pub fn transform_by(&mut self, transform: Transform) -> &mut Self {
+        // Normals are scaled by the inverse of the scale, up to a factor removed by normalizing
+        let covector_scale = transform.scale.yzx() * transform.scale.zxy();
+        for position in &mut self.positions {
+            *position = transform.transform_point(*position);
+        }
+        for normal in &mut self.normals {
+            *normal = transform.rotation * (*normal * covector_scale).normalize_or_zero();
+        }
+        self
+    }
+
+    /// Recomputes the normal of each vertex as the average of the normals of the triangles
+    /// using it, weighted by their area.
+    ///
+    /// Vertices that were split to hold different UVs or normals keep their own normal. For
+    /// hard edges everywhere, build the mesh and call [`Mesh::duplicate_vertices`] and
+    /// [`Mesh::compute_flat_normals`] instead.
+    pub fn compute_smooth_normals(&mut self) -> &mut Self {
+        let mut normals = vec![Vec3::ZERO; self.positions.len()];
+        for triangle in self.indices.chunks_exact(3) {
+            let [a, b, c] = [0, 1, 2].map(|i| triangle[i] as usize);
+            // The length of the cross product is twice the area of the triangle
+            let normal = (self.positions[b] - self.positions[a])
+                .cross(self.positions[c] - self.positions[a]);
+            for index in [a, b, c] {
+                normals[index] += normal;
+            }
+        }
+        for normal in &mut normals {
+            *normal = normal.normalize_or_zero();
+        }
+        self.normals = normals;
+        self
+    }
+
+    /// Extrudes a 2D `polygon` in the XY plane along the Z axis, from `-depth / 2` to
+    /// `depth / 2`, and closes both ends.
+    ///
+    /// The polygon can be concave, but its edges must not cross each other. The sides of the
+    /// extrusion have hard edges at each corner of the polygon.
+    ///
+    /// UVs wrap around the sides from the first point of the polygon, and map the bounding box of
+    /// the polygon on the ends.
+    pub fn extrude(polygon: &[Vec2], depth: f32) -> Self {
+        let mut builder = Self::new();
+        if polygon.len() < 3 {
+            return builder;
+        }
+        let polygon = counter_clockwise(polygon);
+        let half_depth = depth / 2.;
+
+        // sides
+
+        let perimeter = closed_lengths(&polygon);
+        let total_length = perimeter[polygon.len()].max(f32::EPSILON);
+        for (i, &start) in polygon.iter().enumerate() {
+            let end = polygon[(i + 1) % polygon.len()];
+            let edge = end - start;
+            let normal = Vec3::new(edge.y, -edge.x, 0.).normalize_or_zero();
+            let start_u = perimeter[i] / total_length;
+            let end_u = perimeter[i + 1] / total_length;
+            let front_start =
+                builder.vertex(start.extend(half_depth), normal, Vec2::new(start_u, 0.));
+            let front_end = builder.vertex(end.extend(half_depth), normal, Vec2::new(end_u, 0.));
+            let back_end = builder.vertex(end.extend(-half_depth), normal, Vec2::new(end_u, 1.));
+            let back_start =
+                builder.vertex(start.extend(-half_depth), normal, Vec2::new(start_u, 1.));
+            builder.quad(front_start, back_start, back_end, front_end);
+        }
+
+        // ends
+
+        let min = polygon.iter().copied().fold(Vec2::MAX, Vec2::min);
+        let size = (polygon.iter().copied().fold(Vec2::MIN, Vec2::max) - min)
+            .max(Vec2::splat(f32::EPSILON));
+        let triangles = triangulate(&polygon);
+        for (z, normal) in [(half_depth, Vec3::Z), (-half_depth, Vec3::NEG_Z)] {
+            let offset = builder.vertex_count() as u32;
+            for &point in &polygon {
+                let uv = (point - min) / size;
+                builder.vertex(point.extend(z), normal, Vec2::new(uv.x, 1. - uv.y));
+            }
+            for [a, b, c] in &triangles {
+                let [a, b, c] = [a, b, c].map(|&i| offset + i as u32);
+                if normal.z > 0. {
+                    builder.triangle(a, b, c);
+                } else {
+                    builder.triangle(a, c, b);
+                }
+            }
+        }
+
+        builder
+    }
+
+    /// Revolves a 2D `profile` around the Y axis, with `segments` steps around it.
+    ///
+    /// The X coordinates of the profile are distances from the axis, and its Y coordinates are
+    /// heights. The right side of the profile faces outward, so a profile going up from the
+    /// bottom of a vase makes its outside. The ends of the profile are left open, and can be
+    /// closed by starting or ending it on the axis.
+    ///
+    /// Normals are smoothed along the profile and around the axis. UVs go around the axis along U,
+    /// and along the profile along V.
+    pub fn lathe(profile: &[Vec2], segments: u32) -> Self {
+        let mut builder = Self::new();
+        if profile.len() < 2 || segments < 3 {
+            return builder;
+        }
+        let lengths = running_lengths(profile.windows(2).map(|pair| pair[0].distance(pair[1])));
+        let total_length = lengths[profile.len() - 1].max(f32::EPSILON);
+        let step_theta = TAU / segments as f32;
+
+        for (i, &point) in profile.iter().enumerate() {
+            let normal = profile_normal(profile, i, false);
+            for segment in 0..=segments {
+                let (sin, cos) = (segment as f32 * step_theta).sin_cos();
+                builder.vertex(
+                    Vec3::new(point.x * cos, point.y, point.x * sin),
+                    Vec3::new(normal.x * cos, normal.y, normal.x * sin),
+                    Vec2::new(segment as f32 / segments as f32, lengths[i] / total_length),
+                );
+            }
+        }
+
+        for i in 0..profile.len() as u32 - 1 {
+            let ring = i * (segments + 1);
+            let next_ring = (i + 1) * (segments + 1);
+            for j in 0..segments {
+                builder.quad(ring + j, next_ring + j, next_ring + j + 1, ring + j + 1);
+            }
+        }
+
+        builder
+    }
+
+    /// Sweeps a closed 2D `profile` along a 3D `path`, like a pipe or a rail.
+    ///
+    /// At each point of the path, the X axis of the profile points to the right of the path and
+    /// its Y axis points up, starting from the world Y axis and turning as little as possible to
+    /// follow the path. The ends of the sweep are left open.
+    ///
+    /// Curves can be swept by sampling them, for example with
+    /// [`CubicCurve::iter_positions`](bevy_math::cubic_splines::CubicCurve::iter_positions).
+    ///
+    /// Normals are smoothed around the profile. UVs go around the profile along U, and along the
+    /// path along V.
+    pub fn sweep(profile: &[Vec2], path: &[Vec3]) -> Self {
+        let mut builder = Self::new();
+        if profile.len() < 3 || path.len() < 2 {
+            return builder;
+        }
+        let profile = counter_clockwise(profile);
+        let profile_lengths = closed_lengths(&profile);
+        let profile_length = profile_lengths[profile.len()].max(f32::EPSILON);
+        let path_lengths = running_lengths(path.windows(2).map(|pair| pair[0].distance(pair[1])));
+        let path_length = path_lengths[path.len() - 1].max(f32::EPSILON);
+
+        let mut up = Vec3::ZERO;
+        let mut previous_forward = Vec3::ZERO;
+        for (k, &center) in path.iter().enumerate() {
+            let forward = (path[(k + 1).min(path.len() - 1)] - path[k.saturating_sub(1)])
+                .try_normalize()
+                .unwrap_or(previous_forward);
+            up = if k == 0 {
+                // Falls back to the X axis when the path starts vertically
+                let reference = if forward.y.abs() < 0.99 {
+                    Vec3::Y
+                } else {
+                    Vec3::X
+                };
+                forward.cross(reference).cross(forward).normalize_or_zero()
+            } else {
+                // Parallel transport, to avoid twisting the profile
+                Quat::from_rotation_arc(previous_forward, forward) * up
+            };
+            let right = forward.cross(up);
+            previous_forward = forward;
+
+            for i in 0..=profile.len() {
+                let point = profile[i % profile.len()];
+                let normal = profile_normal(&profile, i % profile.len(), true);
+                builder.vertex(
+                    center + right * point.x + up * point.y,
+                    (right * normal.x + up * normal.y).normalize_or_zero(),
+                    Vec2::new(
+                        profile_lengths[i] / profile_length,
+                        path_lengths[k] / path_length,
+                    ),
+                );
+            }
+        }
+
+        let ring_size = profile.len() as u32 + 1;
+        for k in 0..path.len() as u32 - 1 {
+            let ring = k * ring_size;
+            let next_ring = (k + 1) * ring_size;
+            for i in 0..ring_size - 1 {
+                builder.quad(ring + i, next_ring + i, next_ring + i + 1, ring + i + 1);
+            }
+        }
+
+        builder
+    }
+
+    /// Builds a [`Mesh`] with the positions, normals, UVs and indices of the builder.
+    pub fn build(&self) -> Mesh {
+        Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            self.positions
+                .iter()
+                .map(|p| p.to_array())
+                .collect::<Vec<_>>(),
+        )
+        .with_inserted_attribute(
+            Mesh::ATTRIBUTE_NORMAL,
+            self.normals
+                .iter()
+                .map(|n| n.to_array())
+                .collect::<Vec<_>>(),
+        )
+        .with_inserted_attribute(
+            Mesh::ATTRIBUTE_UV_0,
+            self.uvs.iter().map(|uv| uv.to_array()).collect::<Vec<_>>(),
+        )
+        .with_inserted_indices(Indices::U32(self.indices.clone()))
+    }
+
+    /// Builds a [`Mesh`] like [`build`](Self::build), and generates its
+    /// [`Mesh::ATTRIBUTE_TANGENT`] for normal mapping.
+    pub fn build_with_tangents(&self) -> Result<Mesh, GenerateTangentsError> {
+        self.build().with_generated_tangents()
+    }
+}
+
+impl From<MeshBuilder> for Mesh {
+    fn from(builder: MeshBuilder) -> Self {
+        builder.build()
+    }
+}
+
+/// Returns the `points` of a polygon in counter-clockwise order.
+fn counter_clockwise(points: &[Vec2]) -> Vec<Vec2> {
+    let doubled_area: f32 = (0..points.len())
+        .map(|i| points[i].perp_dot(points[(i + 1) % points.len()]))
+        .sum();
+    if doubled_area < 0. {
+        points.iter().rev().copied().collect()
+    } else {
+        points.to_vec()
+    }
+}
+
+/// Returns the distance along a closed polygon to each of its `points`, and back to the first one.
+fn closed_lengths(points: &[Vec2]) -> Vec<f32> {
+    running_lengths(
+        points
+            .iter()
+            .zip(points.iter().cycle().skip(1))
+            .map(|(a, b)| a.distance(*b)),
+    )
+}
+
+/// Returns the running totals of `distances`, starting from zero.
+fn running_lengths(distances: impl IntoIterator<Item = f32>) -> Vec<f32> {
+    let mut length = 0.;
+    iter::once(0.)
+        .chain(distances.into_iter().map(|distance| {
+            length += distance;
+            length
+        }))
+        .collect()
+}
+
+/// Returns the normal to the right of the profile at its point `i`, averaging the directions of
+/// the neighboring segments.
+fn profile_normal(profile: &[Vec2], i: usize, closed: bool) -> Vec2 {
+    let n = profile.len();
+    let (previous, next) = if closed {
+        (profile[(i + n - 1) % n], profile[(i + 1) % n])
+    } else {
+        (profile[i.saturating_sub(1)], profile[(i + 1).min(n - 1)])
+    };
+    let tangent = next - previous;
+    Vec2::new(tangent.y, -tangent.x).normalize_or_zero()
+}
+
+/// Triangulates a simple polygon with counter-clockwise `points` by ear clipping, returning the
+/// indices of the points of each triangle.
+fn triangulate(points: &[Vec2]) -> Vec<[usize; 3]> {
+    let mut remaining: Vec<usize> = (0..points.len()).collect();
+    let mut triangles = Vec::with_capacity(points.len().saturating_sub(2));
+    while remaining.len() > 3 {
+        let n = remaining.len();
+        let corner = |i: usize| {
+            [
+                remaining[(i + n - 1) % n],
+                remaining[i],
+                remaining[(i + 1) % n],
+            ]
+        };
+        let is_ear = |i: usize| {
+            let [a, b, c] = corner(i).map(|index| points[index]);
+            // Reflex corners can't be clipped, and neither can corners containing another point
+            (b - a).perp_dot(c - b) >= 0.
+                && remaining.iter().all(|&j| {
+                    let p = points[j];
+                    p == a || p == b || p == c || !in_triangle(p, a, b, c)
+                })
+        };
+        // Only a polygon crossing itself has no ear
+        let ear = (0..n).find(|&i| is_ear(i)).unwrap_or(0);
+        triangles.push(corner(ear));
+        remaining.remove(ear);
+    }
+    if let [a, b, c] = remaining[..] {
+        triangles.push([a, b, c]);
+    }
+    triangles
+}
+
+/// Whether `p` is inside or on the edges of the counter-clockwise triangle `a, b, c`.
+fn in_triangle(p: Vec2, a: Vec2, b: Vec2, c: Vec2) -> bool {
+    (b - a).perp_dot(p - a) >= 0. && (c - b).perp_dot(p - b) >= 0. && (a - c).perp_dot(p - c) >= 0.
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::{Vec2, Vec3};
+
+    use super::{triangulate, MeshBuilder};
+
+    /// Checks that every triangle faces the same way as the normals of its vertices.
+    fn assert_faces_outward(builder: &MeshBuilder) {
+        for triangle in builder.indices().chunks_exact(3) {
+            let [a, b, c] = [0, 1, 2].map(|i| triangle[i] as usize);
+            let positions = builder.positions();
+            let face_normal = (positions[b] - positions[a]).cross(positions[c] - positions[a]);
+            let vertex_normal = builder.normals()[a] + builder.normals()[b] + builder.normals()[c];
+            assert!(
+                face_normal.dot(vertex_normal) >= 0.,
+                "triangle {triangle:?} faces inward"
+            );
+        }
+    }
+
+    #[test]
+    fn triangulate_concave_polygon() {
+        let l_shape = [
+            Vec2::new(0., 0.),
+            Vec2::new(2., 0.),
+            Vec2::new(2., 1.),
+            Vec2::new(1., 1.),
+            Vec2::new(1., 2.),
+            Vec2::new(0., 2.),
+        ];
+        let triangles = triangulate(&l_shape);
+        assert_eq!(triangles.len(), 4);
+        let area: f32 = triangles
+            .iter()
+            .map(|&[a, b, c]| (l_shape[b] - l_shape[a]).perp_dot(l_shape[c] - l_shape[a]) / 2.)
+            .sum();
+        assert!((area - 3.).abs() < 1e-5);
+    }
+
+    #[test]
+    fn extrude() {
+        let square = [
+            Vec2::new(-1., -1.),
+            Vec2::new(-1., 1.),
+            Vec2::new(1., 1.),
+            Vec2::new(1., -1.),
+        ];
+        // Clockwise polygons are handled too
+        let builder = MeshBuilder::extrude(&square, 2.);
+        // 4 vertices for each side and each end
+        assert_eq!(builder.vertex_count(), 24);
+        assert_eq!(builder.indices().len(), 12 * 3);
+        assert_faces_outward(&builder);
+    }
+
+    #[test]
+    fn lathe() {
+        let profile = [Vec2::new(0., -1.), Vec2::new(1., 0.), Vec2::new(0., 1.)];
+        let builder = MeshBuilder::lathe(&profile, 8);
+        assert_eq!(builder.vertex_count(), 3 * 9);
+        assert_faces_outward(&builder);
+    }
+
+    #[test]
+    fn sweep() {
+        let square = [
+            Vec2::new(-0.1, -0.1),
+            Vec2::new(0.1, -0.1),
+            Vec2::new(0.1, 0.1),
+            Vec2::new(-0.1, 0.1),
+        ];
+        let path = [
+            Vec3::ZERO,
+            Vec3::new(0., 0., -1.),
+            Vec3::new(1., 0.5, -2.),
+            Vec3::new(2., 2., -2.),
+        ];
+        let builder = MeshBuilder::sweep(&square, &path);
+        assert_eq!(builder.vertex_count(), 4 * 5);
+        assert_faces_outward(&builder);
+        // The profile starts upright
+        assert!((builder.positions()[2] - Vec3::new(0.1, 0.1, 0.)).length() < 1e-5);
+    }
+
+    #[test]
+    fn append_and_smooth_normals() {
+        let profile = [Vec2::new(0., -1.), Vec2::new(1., 0.), Vec2::new(0., 1.)];
+        let mut builder = MeshBuilder::lathe(&profile, 8);
+        let other = builder.clone();
+        builder.append(&other);
+        assert_eq!(builder.vertex_count(), 2 * 3 * 9);
+        assert_eq!(&builder.indices()[..other.indices().len()], other.indices());
+        assert!(builder.indices()[other.indices().len()..]
+            .iter()
+            .all(|&index| index as usize >= other.vertex_count()));
+
+        builder.compute_smooth_normals();
+        assert_faces_outward(&builder);
+    }
+}
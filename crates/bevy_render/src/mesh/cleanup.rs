@@ -0,0 +1,381 @@
+//! Cleaning up [`Mesh`]es by welding their vertices and removing their degenerate triangles,
+//! at runtime or when processing assets.
+
+use bevy_asset::transformer::{AssetTransformer, TransformedAsset};
+use bevy_core::cast_slice;
+use bevy_math::{IVec3, Vec3};
+use bevy_utils::{BoxedFuture, HashMap};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use wgpu::{PrimitiveTopology, VertexFormat};
+
+use super::{GenerateTangentsError, Indices, Mesh, VertexAttributeValues};
+
+/// Settings for [`Mesh::clean_up`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MeshCleanupSettings {
+    /// If set, vertices whose attributes all differ by at most this tolerance are welded with
+    /// [`Mesh::weld_vertices`].
+    ///
+    /// Defaults to `Some(0.0)`, only welding identical vertices.
+    pub weld_tolerance: Option<f32>,
+    /// If true, triangles with no area are removed with [`Mesh::remove_degenerate_triangles`].
+    ///
+    /// Defaults to `true`.
+    pub remove_degenerate_triangles: bool,
+}
+
+impl Default for MeshCleanupSettings {
+    fn default() -> Self {
+        Self {
+            weld_tolerance: Some(0.),
+            remove_degenerate_triangles: true,
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+/// Failed to clean up the mesh.
+pub enum MeshCleanupError {
+    #[error("cannot remove degenerate triangles of {0:?}")]
+    UnsupportedTopology(PrimitiveTopology),
+    #[error("missing vertex attributes '{0}'")]
+    MissingVertexAttribute(&'static str),
+    #[error("the '{0}' vertex attribute should have {1:?} format")]
+    InvalidVertexAttributeFormat(&'static str, VertexFormat),
+    #[error("cannot change the vertices of a mesh with morph targets")]
+    MorphTargets,
+    #[error(transparent)]
+    GenerateTangents(#[from] GenerateTangentsError),
+}
+
+impl Mesh {
+    /// Welds the vertices whose attributes all differ by at most `tolerance`, and returns the
+    /// number of vertices removed.
+    ///
+    /// Floating point attributes are compared component-wise, other attributes must be equal.
+    /// The same tolerance applies to every attribute, so it should be small compared to both the
+    /// size of the mesh and the range of its other attributes, such as UVs. The mesh becomes
+    /// indexed if it wasn't.
+    ///
+    /// Requires the [`Mesh::ATTRIBUTE_POSITION`] attribute set and no morph targets.
+    pub fn weld_vertices(&mut self, tolerance: f32) -> Result<usize, MeshCleanupError> {
+        let positions = self.cleanup_positions()?;
+
+        // Vertices are bucketed by the cell of a grid of the size of the tolerance that their
+        // position is in, so that they only need to be compared with the vertices in the
+        // neighboring cells. Without tolerance, identical positions share a cell.
+        let (cell_size, neighbors) = if tolerance > 0. {
+            let neighbors = (-1..=1)
+                .flat_map(|x| {
+                    (-1..=1).flat_map(move |y| (-1..=1).map(move |z| IVec3::new(x, y, z)))
+                })
+                .collect();
+            (tolerance, neighbors)
+        } else {
+            (0., vec![IVec3::ZERO])
+        };
+        let cell = |position: Vec3| {
+            if cell_size > 0. {
+                (position / cell_size).floor().as_ivec3()
+            } else {
+                // -0.0 and 0.0 are equal, but don't have the same bits
+                IVec3::from_array(
+                    position
+                        .to_array()
+                        .map(|value| (value + 0.).to_bits() as i32),
+                )
+            }
+        };
+
+        let attributes: Vec<&VertexAttributeValues> =
+            self.attributes().map(|(_, values)| values).collect();
+        let mut cells: HashMap<IVec3, Vec<usize>> = HashMap::new();
+        let mut welded_vertices = Vec::with_capacity(positions.len());
+        let mut source_vertices = Vec::new();
+        for (vertex, &position) in positions.iter().enumerate() {
+            let cell = cell(position);
+            let existing = neighbors.iter().find_map(|&offset| {
+                cells.get(&(cell + offset))?.iter().copied().find(|&other| {
+                    attributes.iter().all(|values| {
+                        values_match(values, source_vertices[other] as usize, vertex, tolerance)
+                    })
+                })
+            });
+            let welded = existing.unwrap_or_else(|| {
+                source_vertices.push(vertex as u32);
+                cells
+                    .entry(cell)
+                    .or_default()
+                    .push(source_vertices.len() - 1);
+                source_vertices.len() - 1
+            });
+            welded_vertices.push(welded as u32);
+        }
+
+        let removed = positions.len() - source_vertices.len();
+        let indices = match self.indices() {
+            Some(indices) => indices.iter().map(|index| welded_vertices[index]).collect(),
+            None => welded_vertices,
+        };
+        self.remap_vertices(source_vertices, Some(indices));
+        Ok(removed)
+    }
+
+    /// Removes the triangles with no area, and the vertices only they used, and returns the
+    /// number of triangles removed.
+    ///
+    /// Triangles using the same vertex several times are removed, as well as the ones with
+    /// positions on a line. Welding the vertices of the mesh first with [`Mesh::weld_vertices`]
+    /// also removes the triangles whose vertices are within its tolerance of each other.
+    ///
+    /// Requires a [`PrimitiveTopology::TriangleList`] topology, the [`Mesh::ATTRIBUTE_POSITION`]
+    /// attribute set and no morph targets.
+    pub fn remove_degenerate_triangles(&mut self) -> Result<usize, MeshCleanupError> {
+        match self.primitive_topology() {
+            PrimitiveTopology::TriangleList => {}
+            other => return Err(MeshCleanupError::UnsupportedTopology(other)),
+        }
+        let positions = self.cleanup_positions()?;
+        let indices: Vec<usize> = match self.indices() {
+            Some(indices) => indices.iter().collect(),
+            None => (0..positions.len()).collect(),
+        };
+        let triangles: Vec<&[usize]> = indices
+            .chunks_exact(3)
+            .filter(|triangle| {
+                let [a, b, c] = [triangle[0], triangle[1], triangle[2]];
+                a != b
+                    && b != c
+                    && c != a
+                    && (positions[b] - positions[a]).cross(positions[c] - positions[a])
+                        != Vec3::ZERO
+            })
+            .collect();
+        let removed = indices.len() / 3 - triangles.len();
+        if removed == 0 {
+            return Ok(0);
+        }
+
+        let mut new_vertices = vec![u32::MAX; positions.len()];
+        let mut source_vertices = Vec::new();
+        let mut new_indices = Vec::with_capacity(triangles.len() * 3);
+        for &vertex in triangles.iter().copied().flatten() {
+            if new_vertices[vertex] == u32::MAX {
+                new_vertices[vertex] = source_vertices.len() as u32;
+                source_vertices.push(vertex as u32);
+            }
+            new_indices.push(new_vertices[vertex]);
+        }
+        // Non-indexed meshes stay non-indexed, as each of their vertices is used once.
+        let indices = self.indices().is_some().then_some(new_indices);
+        self.remap_vertices(source_vertices, indices);
+        Ok(removed)
+    }
+
+    /// Welds the vertices of the mesh and removes its degenerate triangles, as configured by
+    /// `settings`.
+    ///
+    /// Degenerate triangles are only removed from [`PrimitiveTopology::TriangleList`] meshes.
+    /// See [`Mesh::weld_vertices`] and [`Mesh::remove_degenerate_triangles`] for details.
+    pub fn clean_up(&mut self, settings: &MeshCleanupSettings) -> Result<(), MeshCleanupError> {
+        if let Some(tolerance) = settings.weld_tolerance {
+            self.weld_vertices(tolerance)?;
+        }
+        if settings.remove_degenerate_triangles
+            && self.primitive_topology() == PrimitiveTopology::TriangleList
+        {
+            self.remove_degenerate_triangles()?;
+        }
+        Ok(())
+    }
+
+    /// Consumes the mesh and returns it cleaned up as configured by `settings`.
+    ///
+    /// See [`Mesh::clean_up`] for details.
+    pub fn cleaned_up(mut self, settings: &MeshCleanupSettings) -> Result<Mesh, MeshCleanupError> {
+        self.clean_up(settings)?;
+        Ok(self)
+    }
+
+    fn cleanup_positions(&self) -> Result<Vec<Vec3>, MeshCleanupError> {
+        if self.has_morph_targets() {
+            return Err(MeshCleanupError::MorphTargets);
+        }
+        match self.attribute(Mesh::ATTRIBUTE_POSITION) {
+            Some(VertexAttributeValues::Float32x3(positions)) => {
+                Ok(positions.iter().copied().map(Vec3::from).collect())
+            }
+            Some(_) => Err(MeshCleanupError::InvalidVertexAttributeFormat(
+                "Vertex_Position",
+                VertexFormat::Float32x3,
+            )),
+            None => Err(MeshCleanupError::MissingVertexAttribute("Vertex_Position")),
+        }
+    }
+
+    /// Replaces the vertices of the mesh by copies of its `source_vertices`, and its indices by
+    /// `indices`.
+    ///
+    /// 16-bit indices are kept if the mesh had them and the new vertices fit.
+    pub(crate) fn remap_vertices(&mut self, source_vertices: Vec<u32>, indices: Option<Vec<u32>>) {
+        let u16_indices = matches!(self.indices(), Some(Indices::U16(_)))
+            && source_vertices.len() <= u16::MAX as usize + 1;
+        self.insert_indices(Indices::U32(source_vertices));
+        self.duplicate_vertices();
+        if let Some(indices) = indices {
+            self.insert_indices(if u16_indices {
+                Indices::U16(indices.into_iter().map(|index| index as u16).collect())
+            } else {
+                Indices::U32(indices)
+            });
+        }
+    }
+}
+
+/// Returns `true` if the values of vertices `a` and `b` differ by at most `tolerance`, comparing
+/// floating point values component-wise and other values exactly.
+fn values_match(values: &VertexAttributeValues, a: usize, b: usize, tolerance: f32) -> bool {
+    let size = VertexFormat::from(values).size() as usize;
+    let bytes = values.get_bytes();
+    let (a, b) = (
+        &bytes[a * size..(a + 1) * size],
+        &bytes[b * size..(b + 1) * size],
+    );
+    match values {
+        VertexAttributeValues::Float32(_)
+        | VertexAttributeValues::Float32x2(_)
+        | VertexAttributeValues::Float32x3(_)
+        | VertexAttributeValues::Float32x4(_) => cast_slice::<u8, f32>(a)
+            .iter()
+            .zip(cast_slice::<u8, f32>(b))
+            .all(|(a, b)| (a - b).abs() <= tolerance),
+        _ => a == b,
+    }
+}
+
+/// An [`AssetTransformer`] cleaning up [`Mesh`]es when processing assets, with
+/// [`Mesh::clean_up`].
+///
+/// It can be used in a [`LoadTransformAndSave`](bevy_asset::processor::LoadTransformAndSave)
+/// processor along with a loader and a saver of meshes. glTF meshes can instead be cleaned up
+/// when loading them, by setting the `mesh_cleanup` field of the glTF loader settings.
+pub struct MeshCleanupTransformer;
+
+/// Settings for the [`MeshCleanupTransformer`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MeshCleanupTransformerSettings {
+    /// How the mesh is cleaned up.
+    pub cleanup: MeshCleanupSettings,
+    /// If true, the tangents of the cleaned up mesh are generated with
+    /// [`Mesh::generate_tangents`].
+    pub generate_tangents: bool,
+}
+
+impl AssetTransformer for MeshCleanupTransformer {
+    type AssetInput = Mesh;
+    type AssetOutput = Mesh;
+    type Settings = MeshCleanupTransformerSettings;
+    type Error = MeshCleanupError;
+
+    fn transform<'a>(
+        &'a self,
+        mut asset: TransformedAsset<Mesh>,
+        settings: &'a Self::Settings,
+    ) -> BoxedFuture<'a, Result<TransformedAsset<Mesh>, Self::Error>> {
+        Box::pin(async move {
+            asset.clean_up(&settings.cleanup)?;
+            if settings.generate_tangents {
+                asset.generate_tangents()?;
+            }
+            Ok(asset)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render_asset::RenderAssetUsages;
+    use bevy_math::primitives::Cuboid;
+
+    #[test]
+    fn weld_vertices() {
+        let mut mesh =
+            Mesh::from(Cuboid::new(1., 1., 1.)).with_removed_attribute(Mesh::ATTRIBUTE_UV_0);
+        // The faces of the cube don't share normals.
+        assert_eq!(mesh.weld_vertices(0.).unwrap(), 0);
+
+        mesh.remove_attribute(Mesh::ATTRIBUTE_NORMAL);
+        assert_eq!(mesh.weld_vertices(0.).unwrap(), 16);
+        assert_eq!(mesh.count_vertices(), 8);
+        assert_eq!(mesh.indices().map(Indices::len), Some(36));
+    }
+
+    #[test]
+    fn weld_vertices_with_signed_zeros() {
+        let mut mesh = Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            vec![
+                [0., 0., 0.],
+                [1., 0., 0.],
+                [0., 1., 0.],
+                [-0., -0., 0.],
+                [0., 1., -0.],
+                [1., 1., 0.],
+            ],
+        );
+        assert_eq!(mesh.weld_vertices(0.).unwrap(), 2);
+        let indices: Vec<usize> = mesh.indices().unwrap().iter().collect();
+        assert_eq!(indices, [0, 1, 2, 0, 2, 3]);
+    }
+
+    #[test]
+    fn weld_vertices_with_tolerance() {
+        let mut mesh = Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            vec![
+                [0., 0., 0.],
+                [1., 0., 0.],
+                [0., 1., 0.],
+                [1.001, 0., 0.],
+                [1., 1., 0.],
+                [0., 1.001, 0.],
+            ],
+        );
+        assert_eq!(mesh.clone().weld_vertices(0.).unwrap(), 0);
+        assert_eq!(mesh.weld_vertices(0.01).unwrap(), 2);
+        let indices: Vec<usize> = mesh.indices().unwrap().iter().collect();
+        assert_eq!(indices, [0, 1, 2, 1, 3, 2]);
+    }
+
+    #[test]
+    fn remove_degenerate_triangles() {
+        let mut mesh = Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            vec![
+                [0., 0., 0.],
+                [1., 0., 0.],
+                [0., 1., 0.],
+                [2., 0., 0.],
+                [5., 5., 5.],
+            ],
+        )
+        .with_inserted_indices(Indices::U16(vec![0, 1, 2, 0, 1, 3, 2, 4, 4]));
+        assert_eq!(mesh.remove_degenerate_triangles().unwrap(), 2);
+        assert_eq!(mesh.count_vertices(), 3);
+        assert!(matches!(mesh.indices(), Some(Indices::U16(indices)) if indices == &[0, 1, 2]));
+    }
+}
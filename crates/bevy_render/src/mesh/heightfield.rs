@@ -0,0 +1,358 @@
+//! Generating terrain [`Mesh`]es from a grid of heights, such as a heightmap [`Image`].
+
+use bevy_math::{UVec2, Vec2, Vec3};
+use thiserror::Error;
+use wgpu::{PrimitiveTopology, TextureFormat};
+
+use crate::{
+    mesh::{Indices, Mesh},
+    render_asset::RenderAssetUsages,
+    texture::Image,
+};
+
+/// A grid of heights, sampled at regular intervals along the X and Z axes.
+///
+/// The heights are usually read from a grayscale heightmap with [`Heightfield::from_image`], and
+/// turned into a terrain with [`mesh`](Self::mesh). Large terrains can be split into chunks with
+/// [`mesh_chunks`](Self::mesh_chunks), so that each chunk is culled on its own.
+///
+/// ```
+/// # use bevy_math::{UVec2, Vec2};
+/// # use bevy_render::mesh::{Heightfield, HeightfieldMeshSettings};
+/// # use bevy_render::render_asset::RenderAssetUsages;
+/// let heights = (0..64 * 64).map(|i| (i as f32 * 0.1).sin()).collect();
+/// let heightfield = Heightfield::new(UVec2::splat(64), heights);
+///
+/// let chunks = heightfield.mesh_chunks(
+///     16,
+///     &HeightfieldMeshSettings {
+///         size: Vec2::splat(100.0),
+///         height_scale: 5.0,
+///         // The meshes are only needed on the GPU once uploaded.
+///         asset_usage: RenderAssetUsages::RENDER_WORLD,
+///     },
+/// );
+/// assert_eq!(chunks.len(), 16);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Heightfield {
+    size: UVec2,
+    heights: Vec<f32>,
+}
+
+/// How to turn a [`Heightfield`] into a [`Mesh`].
+#[derive(Clone, Debug)]
+pub struct HeightfieldMeshSettings {
+    /// The size of the whole heightfield along the X and Z axes, centered on the origin.
+    ///
+    /// Defaults to `Vec2::ONE`.
+    pub size: Vec2,
+    /// The height of the vertices for a height of 1.
+    ///
+    /// Defaults to `1.0`.
+    pub height_scale: f32,
+    /// The [`RenderAssetUsages`] of the generated meshes.
+    ///
+    /// Use [`RenderAssetUsages::RENDER_WORLD`] to free the vertices from the main world once they
+    /// are uploaded to the GPU.
+    ///
+    /// Defaults to [`RenderAssetUsages::default`].
+    pub asset_usage: RenderAssetUsages,
+}
+
+impl Default for HeightfieldMeshSettings {
+    fn default() -> Self {
+        Self {
+            size: Vec2::ONE,
+            height_scale: 1.,
+            asset_usage: RenderAssetUsages::default(),
+        }
+    }
+}
+
+/// A chunk of a [`Heightfield`], generated by [`Heightfield::mesh_chunks`].
+#[derive(Clone, Debug)]
+pub struct HeightfieldChunk {
+    /// The position of the chunk in the grid of chunks, from the first samples of the
+    /// heightfield.
+    pub coordinates: UVec2,
+    /// The mesh of the chunk, placed relative to the whole heightfield.
+    pub mesh: Mesh,
+}
+
+/// An error reading a [`Heightfield`] from an [`Image`].
+#[derive(Error, Debug)]
+pub enum HeightfieldError {
+    #[error("heightmaps with the texture format {0:?} are not supported")]
+    UnsupportedFormat(TextureFormat),
+    #[error("the heightmap has {actual} bytes of data, but {expected} are needed for its size")]
+    InvalidDataSize { expected: usize, actual: usize },
+}
+
+impl Heightfield {
+    /// Creates a heightfield from the `heights` of `size` samples, row by row along the X axis.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of heights doesn't match `size`, or if `size` is less than 2 along
+    /// either axis.
+    pub fn new(size: UVec2, heights: Vec<f32>) -> Self {
+        assert!(
+            size.x >= 2 && size.y >= 2,
+            "a heightfield needs at least 2x2 samples"
+        );
+        assert_eq!(
+            heights.len(),
+            (size.x * size.y) as usize,
+            "the number of heights doesn't match the size of the heightfield"
+        );
+        Self { size, heights }
+    }
+
+    /// Reads the heights from the first channel of a heightmap, between 0 and 1 for normalized
+    /// formats. The columns of the image are along the X axis, and its rows along the Z axis.
+    ///
+    /// The supported texture formats are `R8Unorm`, `R16Unorm`, `R16Uint`, `R32Float` and their
+    /// RGBA variants. Grayscale PNGs are loaded in one of these formats. Heights are read as they
+    /// are stored, without converting sRGB values to linear.
+    pub fn from_image(image: &Image) -> Result<Self, HeightfieldError> {
+        let (stride, read): (usize, fn(&[u8]) -> f32) = match image.texture_descriptor.format {
+            TextureFormat::R8Unorm => (1, |bytes| bytes[0] as f32 / u8::MAX as f32),
+            TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => {
+                (4, |bytes| bytes[0] as f32 / u8::MAX as f32)
+            }
+            TextureFormat::R16Unorm | TextureFormat::R16Uint => (2, read_u16),
+            TextureFormat::Rgba16Unorm | TextureFormat::Rgba16Uint => (8, read_u16),
+            TextureFormat::R32Float => (4, read_f32),
+            TextureFormat::Rgba32Float => (16, read_f32),
+            format => return Err(HeightfieldError::UnsupportedFormat(format)),
+        };
+        let size = image.size();
+        let expected = (size.x * size.y) as usize * stride;
+        if image.data.len() < expected {
+            return Err(HeightfieldError::InvalidDataSize {
+                expected,
+                actual: image.data.len(),
+            });
+        }
+        let heights = image.data[..expected].chunks_exact(stride).map(read);
+        Ok(Self::new(size, heights.collect()))
+    }
+
+    /// Returns the number of samples along the X and Z axes.
+    pub fn size(&self) -> UVec2 {
+        self.size
+    }
+
+    /// Returns the heights, row by row along the X axis.
+    pub fn heights(&self) -> &[f32] {
+        &self.heights
+    }
+
+    /// Returns the heights mutably, row by row along the X axis.
+    pub fn heights_mut(&mut self) -> &mut [f32] {
+        &mut self.heights
+    }
+
+    /// Returns the height of the sample at `x` and `z`, clamped to the edges of the heightfield.
+    pub fn height(&self, x: u32, z: u32) -> f32 {
+        let x = x.min(self.size.x - 1);
+        let z = z.min(self.size.y - 1);
+        self.heights[(z * self.size.x + x) as usize]
+    }
+
+    /// Builds a [`Mesh`] of the whole heightfield, with a vertex per sample.
+    ///
+    /// The mesh has normals, UVs spanning the whole heightfield, and tangents for normal mapping.
+    pub fn mesh(&self, settings: &HeightfieldMeshSettings) -> Mesh {
+        self.mesh_region(UVec2::ZERO, self.size - 1, settings)
+    }
+
+    /// Builds a [`Mesh`] for each chunk of `chunk_size` by `chunk_size` quads of the heightfield.
+    /// The chunks at the far edges are smaller if the heightfield doesn't divide evenly.
+    ///
+    /// Chunks are placed relative to the whole heightfield, so they all use the same transform.
+    /// Adjacent chunks share the samples along their edges, and their normals and tangents are
+    /// computed from the whole heightfield, so there are no seams between them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    pub fn mesh_chunks(
+        &self,
+        chunk_size: u32,
+        settings: &HeightfieldMeshSettings,
+    ) -> Vec<HeightfieldChunk> {
+        assert!(chunk_size > 0, "heightfield chunks can't be empty");
+        let quads = self.size - 1;
+        let chunks = (quads + chunk_size - 1) / chunk_size;
+        let mut meshes = Vec::with_capacity((chunks.x * chunks.y) as usize);
+        for z in 0..chunks.y {
+            for x in 0..chunks.x {
+                let coordinates = UVec2::new(x, z);
+                let min = coordinates * chunk_size;
+                let max = (min + chunk_size).min(quads);
+                meshes.push(HeightfieldChunk {
+                    coordinates,
+                    mesh: self.mesh_region(min, max, settings),
+                });
+            }
+        }
+        meshes
+    }
+
+    /// Builds a [`Mesh`] of the samples from `min` to `max`, inclusive.
+    fn mesh_region(&self, min: UVec2, max: UVec2, settings: &HeightfieldMeshSettings) -> Mesh {
+        let quads = (self.size - 1).as_vec2();
+        let spacing = settings.size / quads;
+        let origin = -settings.size / 2.;
+
+        let vertex_count = ((max.x - min.x + 1) * (max.y - min.y + 1)) as usize;
+        let mut positions = Vec::with_capacity(vertex_count);
+        let mut normals = Vec::with_capacity(vertex_count);
+        let mut tangents = Vec::with_capacity(vertex_count);
+        let mut uvs = Vec::with_capacity(vertex_count);
+        for z in min.y..=max.y {
+            for x in min.x..=max.x {
+                let sample = UVec2::new(x, z).as_vec2();
+                let horizontal = origin + sample * spacing;
+                let height = self.height(x, z) * settings.height_scale;
+                positions.push([horizontal.x, height, horizontal.y]);
+
+                // The slopes along each axis, from the neighboring samples.
+                let (left, right) = (x.saturating_sub(1), (x + 1).min(self.size.x - 1));
+                let (back, front) = (z.saturating_sub(1), (z + 1).min(self.size.y - 1));
+                let slope_x = (self.height(right, z) - self.height(left, z))
+                    * settings.height_scale
+                    / ((right - left) as f32 * spacing.x);
+                let slope_z = (self.height(x, front) - self.height(x, back))
+                    * settings.height_scale
+                    / ((front - back) as f32 * spacing.y);
+                normals.push(Vec3::new(-slope_x, 1., -slope_z).normalize().to_array());
+                // The tangent follows the U coordinate along the X axis. The V coordinate is
+                // along the Z axis, which is the opposite of the bitangent of a surface facing
+                // up, hence the negative sign.
+                let tangent = Vec3::new(1., slope_x, 0.).normalize();
+                tangents.push(tangent.extend(-1.).to_array());
+                uvs.push((sample / quads).to_array());
+            }
+        }
+
+        let row = max.x - min.x + 1;
+        let mut indices = Vec::with_capacity(((max.x - min.x) * (max.y - min.y) * 6) as usize);
+        for z in 0..max.y - min.y {
+            for x in 0..max.x - min.x {
+                let a = z * row + x;
+                let b = a + row;
+                // Counter-clockwise when seen from above.
+                indices.extend([a, b, b + 1, a, b + 1, a + 1]);
+            }
+        }
+
+        Mesh::new(PrimitiveTopology::TriangleList, settings.asset_usage)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_TANGENT, tangents)
+            .with_inserted_indices(Indices::U32(indices))
+    }
+}
+
+fn read_u16(bytes: &[u8]) -> f32 {
+    u16::from_le_bytes([bytes[0], bytes[1]]) as f32 / u16::MAX as f32
+}
+
+fn read_f32(bytes: &[u8]) -> f32 {
+    f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::Vec4;
+
+    use super::*;
+    use crate::{
+        mesh::{MeshVertexAttribute, VertexAttributeValues},
+        render_resource::Extent3d,
+    };
+
+    fn float3(mesh: &Mesh, attribute: MeshVertexAttribute) -> Vec<Vec3> {
+        mesh.attribute(attribute)
+            .and_then(VertexAttributeValues::as_float3)
+            .unwrap()
+            .iter()
+            .map(|&value| Vec3::from(value))
+            .collect()
+    }
+
+    #[test]
+    fn slope() {
+        // A ramp rising by 1 along the X axis, over 2 units.
+        let heights = (0..9).map(|i| (i % 3) as f32 * 0.5).collect();
+        let heightfield = Heightfield::new(UVec2::splat(3), heights);
+        let mesh = heightfield.mesh(&HeightfieldMeshSettings {
+            size: Vec2::splat(2.),
+            ..Default::default()
+        });
+
+        let positions = float3(&mesh, Mesh::ATTRIBUTE_POSITION);
+        assert_eq!(positions[0], Vec3::new(-1., 0., -1.));
+        assert_eq!(positions[8], Vec3::new(1., 1., 1.));
+        let expected_normal = Vec3::new(-1., 2., 0.).normalize();
+        for normal in float3(&mesh, Mesh::ATTRIBUTE_NORMAL) {
+            assert!(normal.abs_diff_eq(expected_normal, 1e-6));
+        }
+        let Some(VertexAttributeValues::Float32x4(tangents)) =
+            mesh.attribute(Mesh::ATTRIBUTE_TANGENT)
+        else {
+            panic!("missing tangents");
+        };
+        let expected_tangent = Vec3::new(2., 1., 0.).normalize().extend(-1.);
+        assert!(Vec4::from(tangents[4]).abs_diff_eq(expected_tangent, 1e-6));
+
+        // The triangles face up.
+        let Some(Indices::U32(indices)) = mesh.indices() else {
+            panic!("missing indices");
+        };
+        assert_eq!(indices.len(), 4 * 6);
+        for triangle in indices.chunks_exact(3) {
+            let [a, b, c] = [0, 1, 2].map(|i| positions[triangle[i] as usize]);
+            assert!((b - a).cross(c - a).dot(expected_normal) > 0.);
+        }
+    }
+
+    #[test]
+    fn chunks() {
+        let heightfield = Heightfield::new(UVec2::new(6, 4), vec![0.; 24]);
+        let chunks = heightfield.mesh_chunks(2, &HeightfieldMeshSettings::default());
+        // 5 by 3 quads
+        assert_eq!(chunks.len(), 6);
+        assert_eq!(chunks[5].coordinates, UVec2::new(2, 1));
+        // The last chunk is 1 by 1 quad.
+        assert_eq!(chunks[5].mesh.count_vertices(), 4);
+        // Chunks overlap along their edges.
+        let first = float3(&chunks[0].mesh, Mesh::ATTRIBUTE_POSITION);
+        let second = float3(&chunks[1].mesh, Mesh::ATTRIBUTE_POSITION);
+        assert_eq!(first[2], second[0]);
+    }
+
+    #[test]
+    fn from_image() {
+        let image = Image::new(
+            Extent3d {
+                width: 2,
+                height: 2,
+                depth_or_array_layers: 1,
+            },
+            wgpu::TextureDimension::D2,
+            vec![0, 0, 255, 255, 0, 128, 255, 255],
+            TextureFormat::R16Unorm,
+            RenderAssetUsages::default(),
+        );
+        let heightfield = Heightfield::from_image(&image).unwrap();
+        let expected = [0., 1., 0.5, 1.];
+        for (height, expected) in heightfield.heights().iter().zip(expected) {
+            assert!((height - expected).abs() < 1e-4);
+        }
+    }
+}
@@ -0,0 +1,497 @@
+//! Generating non-overlapping lightmap UVs for [`Mesh`]es.
+
+use bevy_math::{Vec2, Vec3};
+use bevy_utils::HashMap;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use wgpu::VertexFormat;
+
+use super::{Mesh, PrimitiveTopology, VertexAttributeValues};
+
+/// Settings for [`Mesh::generate_lightmap_uvs`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LightmapUvSettings {
+    /// The width and height in texels of the lightmaps the UVs are generated for.
+    ///
+    /// Defaults to `512`.
+    pub resolution: u32,
+    /// The number of texels left empty between charts, so that filtering a texel of the
+    /// lightmap doesn't blend the lighting of unrelated surfaces.
+    ///
+    /// Defaults to `2`.
+    pub padding: u32,
+}
+
+impl Default for LightmapUvSettings {
+    fn default() -> Self {
+        Self {
+            resolution: 512,
+            padding: 2,
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+/// Failed to generate lightmap UVs for the mesh.
+pub enum LightmapUvError {
+    #[error("cannot generate lightmap UVs for {0:?}")]
+    UnsupportedTopology(PrimitiveTopology),
+    #[error("missing vertex attributes '{0}'")]
+    MissingVertexAttribute(&'static str),
+    #[error("the '{0}' vertex attribute should have {1:?} format")]
+    InvalidVertexAttributeFormat(&'static str, VertexFormat),
+    #[error("cannot split the vertices of a mesh with morph targets")]
+    MorphTargets,
+    #[error("the {0} charts of the mesh don't fit in the lightmap with the requested padding")]
+    TooManyCharts(usize),
+}
+
+impl Mesh {
+    /// Generates non-overlapping UVs for lightmaps, and sets them as the
+    /// [`Mesh::ATTRIBUTE_UV_1`] attribute.
+    ///
+    /// The triangles are grouped into charts of connected triangles facing roughly the same
+    /// direction, which are projected flat along that direction and packed in the unit square
+    /// with the same texel density. Vertices shared by several charts are duplicated, and the
+    /// mesh becomes indexed if it wasn't.
+    ///
+    /// Requires a [`PrimitiveTopology::TriangleList`] topology, the [`Mesh::ATTRIBUTE_POSITION`]
+    /// attribute set and no morph targets.
+    pub fn generate_lightmap_uvs(
+        &mut self,
+        settings: &LightmapUvSettings,
+    ) -> Result<(), LightmapUvError> {
+        match self.primitive_topology() {
+            PrimitiveTopology::TriangleList => {}
+            other => return Err(LightmapUvError::UnsupportedTopology(other)),
+        }
+        if self.has_morph_targets() {
+            return Err(LightmapUvError::MorphTargets);
+        }
+        let positions = match self.attribute(Mesh::ATTRIBUTE_POSITION) {
+            Some(VertexAttributeValues::Float32x3(positions)) => positions,
+            Some(_) => {
+                return Err(LightmapUvError::InvalidVertexAttributeFormat(
+                    "Vertex_Position",
+                    VertexFormat::Float32x3,
+                ))
+            }
+            None => return Err(LightmapUvError::MissingVertexAttribute("Vertex_Position")),
+        };
+        let positions: Vec<Vec3> = positions.iter().copied().map(Vec3::from).collect();
+        let indices: Vec<usize> = match self.indices() {
+            Some(indices) => indices.iter().collect(),
+            None => (0..positions.len()).collect(),
+        };
+        let triangles: Vec<[usize; 3]> = indices
+            .chunks_exact(3)
+            .map(|triangle| [triangle[0], triangle[1], triangle[2]])
+            .collect();
+
+        let lightmap_uvs = lightmap_uvs(&positions, &triangles, settings)?;
+
+        self.remap_vertices(lightmap_uvs.source_vertices, Some(lightmap_uvs.indices));
+        self.insert_attribute(Mesh::ATTRIBUTE_UV_1, lightmap_uvs.uvs);
+        Ok(())
+    }
+
+    /// Consumes the mesh and returns a mesh with generated lightmap UVs.
+    ///
+    /// See [`Mesh::generate_lightmap_uvs`] for details.
+    pub fn with_generated_lightmap_uvs(
+        mut self,
+        settings: &LightmapUvSettings,
+    ) -> Result<Mesh, LightmapUvError> {
+        self.generate_lightmap_uvs(settings)?;
+        Ok(self)
+    }
+}
+
+/// The vertices of a mesh with lightmap UVs.
+struct LightmapUvs {
+    /// The index of the source vertex of each vertex.
+    source_vertices: Vec<u32>,
+    uvs: Vec<[f32; 2]>,
+    indices: Vec<u32>,
+}
+
+fn lightmap_uvs(
+    positions: &[Vec3],
+    triangles: &[[usize; 3]],
+    settings: &LightmapUvSettings,
+) -> Result<LightmapUvs, LightmapUvError> {
+    let charts = build_charts(positions, triangles);
+    let packed = pack_charts(&charts, settings)?;
+
+    // Each vertex is duplicated for each chart it is part of.
+    let mut chart_vertices = HashMap::new();
+    let mut lightmap_uvs = LightmapUvs {
+        source_vertices: Vec::new(),
+        uvs: Vec::new(),
+        indices: vec![0; triangles.len() * 3],
+    };
+    for (chart, &position) in charts.iter().zip(&packed.positions) {
+        for &triangle in &chart.triangles {
+            for (corner, &vertex) in triangles[triangle].iter().enumerate() {
+                let index = *chart_vertices.entry((vertex, chart.id)).or_insert_with(|| {
+                    let uv =
+                        position + (chart.project(positions[vertex]) - chart.min) * packed.scale;
+                    lightmap_uvs.source_vertices.push(vertex as u32);
+                    lightmap_uvs.uvs.push(uv.to_array());
+                    lightmap_uvs.uvs.len() as u32 - 1
+                });
+                lightmap_uvs.indices[triangle * 3 + corner] = index;
+            }
+        }
+    }
+    Ok(lightmap_uvs)
+}
+
+/// A set of connected triangles facing the same axis whose projections along it don't overlap.
+struct Chart {
+    id: usize,
+    /// The axis the triangles are projected along, from 0 to 2, and whether they face it or its
+    /// opposite.
+    axis: (usize, bool),
+    triangles: Vec<usize>,
+    min: Vec2,
+    max: Vec2,
+}
+
+impl Chart {
+    fn project(&self, position: Vec3) -> Vec2 {
+        project(position, self.axis.0)
+    }
+}
+
+fn project(position: Vec3, axis: usize) -> Vec2 {
+    match axis {
+        0 => Vec2::new(position.y, position.z),
+        1 => Vec2::new(position.z, position.x),
+        _ => Vec2::new(position.x, position.y),
+    }
+}
+
+/// Returns the axis a triangle with the given normal is projected along.
+fn dominant_axis(normal: Vec3) -> (usize, bool) {
+    let abs = normal.abs();
+    let axis = if abs.x >= abs.y && abs.x >= abs.z {
+        0
+    } else if abs.y >= abs.z {
+        1
+    } else {
+        2
+    };
+    (axis, normal[axis] >= 0.)
+}
+
+/// Groups the triangles into charts by flood filling across shared edges.
+///
+/// Triangles are only added to a chart if their projection doesn't overlap the triangles
+/// already in it, which a [`ChartGrid`] speeds up.
+fn build_charts(positions: &[Vec3], triangles: &[[usize; 3]]) -> Vec<Chart> {
+    // Vertices at the same position are welded so that seams of other attributes don't split
+    // charts.
+    let mut welded = HashMap::new();
+    let vertex_ids: Vec<usize> = positions
+        .iter()
+        .map(|position| {
+            let next = welded.len();
+            *welded
+                .entry(position.to_array().map(f32::to_bits))
+                .or_insert(next)
+        })
+        .collect();
+    let mut edges: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+    for (triangle, vertices) in triangles.iter().enumerate() {
+        for corner in 0..3 {
+            let a = vertex_ids[vertices[corner]];
+            let b = vertex_ids[vertices[(corner + 1) % 3]];
+            edges
+                .entry((a.min(b), a.max(b)))
+                .or_default()
+                .push(triangle);
+        }
+    }
+
+    let axes: Vec<(usize, bool)> = triangles
+        .iter()
+        .map(|&[a, b, c]| {
+            dominant_axis((positions[b] - positions[a]).cross(positions[c] - positions[a]))
+        })
+        .collect();
+    let cell_size = grid_cell_size(positions, triangles);
+
+    let mut chart_of = vec![usize::MAX; triangles.len()];
+    let mut charts = Vec::new();
+    for seed in 0..triangles.len() {
+        if chart_of[seed] != usize::MAX {
+            continue;
+        }
+        let axis = axes[seed];
+        let project_triangle =
+            |triangle: usize| triangles[triangle].map(|vertex| project(positions[vertex], axis.0));
+        let mut chart = Chart {
+            id: charts.len(),
+            axis,
+            triangles: vec![seed],
+            min: Vec2::INFINITY,
+            max: Vec2::NEG_INFINITY,
+        };
+        let mut grid = ChartGrid::new(cell_size);
+        grid.insert(project_triangle(seed));
+        chart_of[seed] = chart.id;
+
+        let mut next = 0;
+        while let Some(&triangle) = chart.triangles.get(next) {
+            next += 1;
+            let vertices = triangles[triangle];
+            for corner in 0..3 {
+                let a = vertex_ids[vertices[corner]];
+                let b = vertex_ids[vertices[(corner + 1) % 3]];
+                for &neighbor in &edges[&(a.min(b), a.max(b))] {
+                    if chart_of[neighbor] != usize::MAX || axes[neighbor] != axis {
+                        continue;
+                    }
+                    let projected = project_triangle(neighbor);
+                    if grid.overlaps(projected) {
+                        continue;
+                    }
+                    grid.insert(projected);
+                    chart_of[neighbor] = chart.id;
+                    chart.triangles.push(neighbor);
+                }
+            }
+        }
+
+        for &triangle in &chart.triangles {
+            for point in project_triangle(triangle) {
+                chart.min = chart.min.min(point);
+                chart.max = chart.max.max(point);
+            }
+        }
+        charts.push(chart);
+    }
+    charts
+}
+
+/// Returns the size of the cells of [`ChartGrid`]s, the root mean square of the sizes of the
+/// triangles, so that large triangles span few cells.
+fn grid_cell_size(positions: &[Vec3], triangles: &[[usize; 3]]) -> f32 {
+    let sum: f32 = triangles
+        .iter()
+        .map(|triangle| {
+            let [a, b, c] = triangle.map(|vertex| positions[vertex]);
+            (a.max(b).max(c) - a.min(b).min(c)).max_element().powi(2)
+        })
+        .sum();
+    let size = (sum / triangles.len().max(1) as f32).sqrt();
+    if size > 0. {
+        size
+    } else {
+        1.
+    }
+}
+
+/// The projected triangles of a chart, bucketed by the cells of a grid their bounds overlap.
+struct ChartGrid {
+    cell_size: f32,
+    cells: HashMap<(i32, i32), Vec<usize>>,
+    triangles: Vec<[Vec2; 3]>,
+}
+
+impl ChartGrid {
+    fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size,
+            cells: HashMap::new(),
+            triangles: Vec::new(),
+        }
+    }
+
+    fn cells(&self, triangle: [Vec2; 3]) -> impl Iterator<Item = (i32, i32)> {
+        let [a, b, c] = triangle;
+        let min = (a.min(b).min(c) / self.cell_size).floor().as_ivec2();
+        let max = (a.max(b).max(c) / self.cell_size).floor().as_ivec2();
+        (min.x..=max.x).flat_map(move |x| (min.y..=max.y).map(move |y| (x, y)))
+    }
+
+    fn insert(&mut self, triangle: [Vec2; 3]) {
+        let index = self.triangles.len();
+        self.triangles.push(triangle);
+        for cell in self.cells(triangle) {
+            self.cells.entry(cell).or_default().push(index);
+        }
+    }
+
+    fn overlaps(&self, triangle: [Vec2; 3]) -> bool {
+        let epsilon = self.cell_size * 1e-5;
+        self.cells(triangle).any(|cell| {
+            self.cells.get(&cell).is_some_and(|indices| {
+                indices
+                    .iter()
+                    .any(|&index| triangles_overlap(self.triangles[index], triangle, epsilon))
+            })
+        })
+    }
+}
+
+/// Returns `true` if the interiors of two triangles overlap by more than `epsilon`, using the
+/// separating axis theorem. Triangles sharing an edge or a vertex don't overlap.
+fn triangles_overlap(a: [Vec2; 3], b: [Vec2; 3], epsilon: f32) -> bool {
+    let area = |[p0, p1, p2]: [Vec2; 3]| (p1 - p0).perp_dot(p2 - p0).abs();
+    if area(a) <= epsilon * epsilon || area(b) <= epsilon * epsilon {
+        return false;
+    }
+    let interval = |triangle: [Vec2; 3], axis: Vec2| {
+        let [p0, p1, p2] = triangle.map(|point| point.dot(axis));
+        (p0.min(p1).min(p2), p0.max(p1).max(p2))
+    };
+    for triangle in [a, b] {
+        for corner in 0..3 {
+            let edge = triangle[(corner + 1) % 3] - triangle[corner];
+            let axis = edge.perp().normalize_or_zero();
+            if axis == Vec2::ZERO {
+                continue;
+            }
+            let (min_a, max_a) = interval(a, axis);
+            let (min_b, max_b) = interval(b, axis);
+            if max_a <= min_b + epsilon || max_b <= min_a + epsilon {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+/// The placement of packed charts in the unit square.
+struct PackedCharts {
+    /// The position of the projected minimum of each chart.
+    positions: Vec<Vec2>,
+    /// The scale from world units to UVs of all charts.
+    scale: f32,
+}
+
+/// Packs the charts in rows in the unit square, with the largest scale for which they fit.
+fn pack_charts(
+    charts: &[Chart],
+    settings: &LightmapUvSettings,
+) -> Result<PackedCharts, LightmapUvError> {
+    let padding = settings.padding as f32 / settings.resolution.max(1) as f32;
+    let mut order: Vec<usize> = (0..charts.len()).collect();
+    order.sort_by(|&a, &b| {
+        let height = |chart: &Chart| chart.max.y - chart.min.y;
+        height(&charts[b]).total_cmp(&height(&charts[a]))
+    });
+
+    // Returns the positions of the charts if they fit at the given scale.
+    let pack = |scale: f32| {
+        let mut positions = vec![Vec2::ZERO; charts.len()];
+        let mut cursor = Vec2::ZERO;
+        let mut row_height = 0f32;
+        for &index in &order {
+            let size = (charts[index].max - charts[index].min) * scale + padding;
+            if cursor.x + size.x > 1. {
+                cursor = Vec2::new(0., cursor.y + row_height);
+                row_height = 0.;
+                if size.x > 1. {
+                    return None;
+                }
+            }
+            positions[index] = cursor + padding / 2.;
+            cursor.x += size.x;
+            row_height = row_height.max(size.y);
+        }
+        (cursor.y + row_height <= 1.).then_some(positions)
+    };
+
+    let Some(mut positions) = pack(0.) else {
+        return Err(LightmapUvError::TooManyCharts(charts.len()));
+    };
+    let largest_chart = charts
+        .iter()
+        .map(|chart| (chart.max - chart.min).max_element())
+        .fold(0., f32::max);
+    let mut scale = 0.;
+    if largest_chart > 0. {
+        let mut max_scale = 1. / largest_chart;
+        for _ in 0..32 {
+            let mid = (scale + max_scale) / 2.;
+            match pack(mid) {
+                Some(packed) => {
+                    scale = mid;
+                    positions = packed;
+                }
+                None => max_scale = mid,
+            }
+        }
+    }
+    Ok(PackedCharts { positions, scale })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mesh::Indices, render_asset::RenderAssetUsages};
+    use bevy_math::primitives::Cuboid;
+
+    fn lightmap_triangles(mesh: &Mesh) -> Vec<[Vec2; 3]> {
+        let Some(VertexAttributeValues::Float32x2(uvs)) = mesh.attribute(Mesh::ATTRIBUTE_UV_1)
+        else {
+            panic!("missing lightmap UVs");
+        };
+        let indices: Vec<usize> = mesh.indices().unwrap().iter().collect();
+        indices
+            .chunks_exact(3)
+            .map(|triangle| [0, 1, 2].map(|corner| Vec2::from(uvs[triangle[corner]])))
+            .collect()
+    }
+
+    fn assert_no_overlaps(mesh: &Mesh) {
+        let triangles = lightmap_triangles(mesh);
+        for (i, a) in triangles.iter().enumerate() {
+            for point in a {
+                assert!(point.cmpge(Vec2::ZERO).all() && point.cmple(Vec2::ONE).all());
+            }
+            for b in &triangles[i + 1..] {
+                assert!(!triangles_overlap(*a, *b, 1e-6));
+            }
+        }
+    }
+
+    #[test]
+    fn cube() {
+        let mesh = Mesh::from(Cuboid::new(1., 1., 1.))
+            .with_generated_lightmap_uvs(&LightmapUvSettings::default())
+            .unwrap();
+        assert_eq!(mesh.count_vertices(), 24);
+        assert_no_overlaps(&mesh);
+    }
+
+    #[test]
+    fn overlapping_projections() {
+        // A ramp going around a square twice, all of whose triangles face up.
+        let mut positions = Vec::new();
+        let steps = 32;
+        for step in 0..=steps {
+            let angle = step as f32 / steps as f32 * std::f32::consts::TAU * 2.;
+            let height = step as f32 * 0.1;
+            positions.push([angle.cos(), height, angle.sin()]);
+            positions.push([angle.cos() * 2., height, angle.sin() * 2.]);
+        }
+        let mut indices = Vec::new();
+        for step in 0..steps as u32 {
+            let [inner, outer] = [step * 2, step * 2 + 1];
+            indices.extend([inner, inner + 2, outer, outer, inner + 2, outer + 2]);
+        }
+        let mesh = Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+        .with_inserted_indices(Indices::U32(indices))
+        .with_generated_lightmap_uvs(&LightmapUvSettings::default())
+        .unwrap();
+        assert_no_overlaps(&mesh);
+    }
+}
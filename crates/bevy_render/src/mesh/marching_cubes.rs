@@ -0,0 +1,395 @@
+//! Generating [`Mesh`]es from the isosurfaces of 3D scalar fields, with marching cubes.
+
+use bevy_math::{UVec3, Vec3};
+use bevy_utils::HashMap;
+use wgpu::PrimitiveTopology;
+
+use crate::{
+    mesh::{Indices, Mesh},
+    render_asset::RenderAssetUsages,
+};
+
+/// A grid of values sampled at regular intervals in 3D, such as the density of voxel terrain or
+/// the field of metaballs.
+///
+/// The surface where the field crosses a threshold is turned into a [`Mesh`] with
+/// [`mesh`](Self::mesh), using marching cubes.
+///
+/// ```
+/// # use bevy_math::{UVec3, Vec3};
+/// # use bevy_render::mesh::{MarchingCubesSettings, ScalarField};
+/// // Two metaballs
+/// let centers = [Vec3::new(6.0, 8.0, 8.0), Vec3::new(10.0, 8.0, 8.0)];
+/// let field = ScalarField::from_fn(UVec3::splat(17), |sample| {
+///     let position = sample.as_vec3();
+///     centers
+///         .iter()
+///         .map(|center| 9.0 / position.distance_squared(*center))
+///         .sum()
+/// });
+/// let mesh = field.mesh(&MarchingCubesSettings {
+///     iso_level: 1.0,
+///     ..Default::default()
+/// });
+/// ```
+#[derive(Clone, Debug)]
+pub struct ScalarField {
+    size: UVec3,
+    values: Vec<f32>,
+}
+
+/// How to turn a [`ScalarField`] into a [`Mesh`].
+#[derive(Clone, Debug)]
+pub struct MarchingCubesSettings {
+    /// The value of the field on the surface. Values above it are inside the surface.
+    ///
+    /// Defaults to `0.0`.
+    pub iso_level: f32,
+    /// The distance between samples along each axis. The first sample is at the origin.
+    ///
+    /// Defaults to `Vec3::ONE`.
+    pub spacing: Vec3,
+    /// The [`RenderAssetUsages`] of the generated mesh.
+    ///
+    /// Use [`RenderAssetUsages::RENDER_WORLD`] to free the vertices from the main world once they
+    /// are uploaded to the GPU.
+    ///
+    /// Defaults to [`RenderAssetUsages::default`].
+    pub asset_usage: RenderAssetUsages,
+}
+
+impl Default for MarchingCubesSettings {
+    fn default() -> Self {
+        Self {
+            iso_level: 0.,
+            spacing: Vec3::ONE,
+            asset_usage: RenderAssetUsages::default(),
+        }
+    }
+}
+
+/// The corners of each face of a cube, counter-clockwise when seen from outside the cube.
+///
+/// The bits of a corner index are its offset from the first corner along the X, Y and Z axes.
+const CUBE_FACES: [[usize; 4]; 6] = [
+    [0, 4, 6, 2],
+    [1, 3, 7, 5],
+    [0, 1, 5, 4],
+    [2, 6, 7, 3],
+    [0, 2, 3, 1],
+    [4, 5, 7, 6],
+];
+
+impl ScalarField {
+    /// Creates a field from the `values` of `size` samples, ordered along the X axis, then the Y
+    /// axis, then the Z axis.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of values doesn't match `size`.
+    pub fn new(size: UVec3, values: Vec<f32>) -> Self {
+        assert_eq!(
+            values.len(),
+            (size.x * size.y * size.z) as usize,
+            "the number of values doesn't match the size of the field"
+        );
+        Self { size, values }
+    }
+
+    /// Creates a field of `size` samples, with the value of each sample returned by `f`.
+    pub fn from_fn(size: UVec3, mut f: impl FnMut(UVec3) -> f32) -> Self {
+        let mut values = Vec::with_capacity((size.x * size.y * size.z) as usize);
+        for z in 0..size.z {
+            for y in 0..size.y {
+                for x in 0..size.x {
+                    values.push(f(UVec3::new(x, y, z)));
+                }
+            }
+        }
+        Self { size, values }
+    }
+
+    /// Returns the number of samples along each axis.
+    pub fn size(&self) -> UVec3 {
+        self.size
+    }
+
+    /// Returns the value of the sample at `position`, clamped to the edges of the field.
+    pub fn get(&self, position: UVec3) -> f32 {
+        let position = position.min(self.size - 1);
+        self.values[self.index(position)]
+    }
+
+    /// Sets the value of the sample at `position`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `position` is outside of the field.
+    pub fn set(&mut self, position: UVec3, value: f32) {
+        assert!(
+            position.cmplt(self.size).all(),
+            "{position} is outside of the field"
+        );
+        let index = self.index(position);
+        self.values[index] = value;
+    }
+
+    fn index(&self, position: UVec3) -> usize {
+        ((position.z * self.size.y + position.y) * self.size.x + position.x) as usize
+    }
+
+    /// Builds a [`Mesh`] of the surface where the field crosses
+    /// [`iso_level`](MarchingCubesSettings::iso_level), with normals from the gradient of the
+    /// field.
+    ///
+    /// The surface is closed, except where it reaches the edges of the field. Fields sampled
+    /// from a larger volume in chunks can share their border samples, so that the surfaces of
+    /// adjacent chunks meet without gaps.
+    pub fn mesh(&self, settings: &MarchingCubesSettings) -> Mesh {
+        let mut positions = Vec::new();
+        let mut normals = Vec::new();
+        let mut indices = Vec::new();
+        // The vertex on each edge of the grid crossed by the surface, keyed by the sample at the
+        // start of the edge and the axis of the edge.
+        let mut edge_vertices = HashMap::<(UVec3, u32), u32>::new();
+
+        let cubes = self.size.saturating_sub(UVec3::ONE);
+        for z in 0..cubes.z {
+            for y in 0..cubes.y {
+                for x in 0..cubes.x {
+                    let cube = UVec3::new(x, y, z);
+                    let corners: [UVec3; 8] = std::array::from_fn(|corner| cube + offset(corner));
+                    let values = corners.map(|corner| self.get(corner));
+                    let inside = values.map(|value| value > settings.iso_level);
+                    if inside.iter().all(|&corner| corner == inside[0]) {
+                        continue;
+                    }
+
+                    for polygon in cube_polygons(inside) {
+                        let polygon: Vec<u32> = polygon
+                            .into_iter()
+                            .map(|[a, b]| {
+                                let (a, b) = (a.min(b), a.max(b));
+                                let axis = (a ^ b).trailing_zeros();
+                                *edge_vertices.entry((corners[a], axis)).or_insert_with(|| {
+                                    let t =
+                                        (settings.iso_level - values[a]) / (values[b] - values[a]);
+                                    let position =
+                                        corners[a].as_vec3().lerp(corners[b].as_vec3(), t);
+                                    // The field grows towards the inside of the surface.
+                                    let gradient = self
+                                        .gradient(corners[a], settings.spacing)
+                                        .lerp(self.gradient(corners[b], settings.spacing), t);
+                                    positions.push(position * settings.spacing);
+                                    normals.push(-gradient.normalize_or_zero());
+                                    (positions.len() - 1) as u32
+                                })
+                            })
+                            .collect();
+
+                        if polygon.len() < 6 {
+                            for i in 1..polygon.len() - 1 {
+                                indices.extend([polygon[0], polygon[i], polygon[i + 1]]);
+                            }
+                        } else {
+                            // Longer polygons can go through a face of the cube twice, and some
+                            // triangles of a fan would then lie flat on the face, overlapping
+                            // the neighboring cube. Fanning from the center avoids that.
+                            let count = polygon.len() as f32;
+                            let center: Vec3 = polygon.iter().map(|&i| positions[i as usize]).sum();
+                            let normal: Vec3 = polygon.iter().map(|&i| normals[i as usize]).sum();
+                            positions.push(center / count);
+                            normals.push(normal.normalize_or_zero());
+                            let center = (positions.len() - 1) as u32;
+                            for (i, &vertex) in polygon.iter().enumerate() {
+                                let next = polygon[(i + 1) % polygon.len()];
+                                indices.extend([center, vertex, next]);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        let positions: Vec<[f32; 3]> = positions.iter().map(|p| p.to_array()).collect();
+        let normals: Vec<[f32; 3]> = normals.iter().map(|n| n.to_array()).collect();
+        Mesh::new(PrimitiveTopology::TriangleList, settings.asset_usage)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+            .with_inserted_indices(Indices::U32(indices))
+    }
+
+    /// Returns the gradient of the field at the sample at `position`, from its neighbors.
+    fn gradient(&self, position: UVec3, spacing: Vec3) -> Vec3 {
+        let mut gradient = [0.; 3];
+        for (axis, gradient) in gradient.iter_mut().enumerate() {
+            let step = UVec3::AXES[axis];
+            let previous = position.saturating_sub(step);
+            let next = (position + step).min(self.size - 1);
+            let distance = (next[axis] - previous[axis]) as f32 * spacing[axis];
+            if distance > 0. {
+                *gradient = (self.get(next) - self.get(previous)) / distance;
+            }
+        }
+        Vec3::from(gradient)
+    }
+}
+
+/// Returns the offset of a corner of a cube from its first corner.
+fn offset(corner: usize) -> UVec3 {
+    UVec3::new(
+        corner as u32 & 1,
+        (corner as u32 >> 1) & 1,
+        corner as u32 >> 2,
+    )
+}
+
+/// The polygons of the surface within a cube, with the corners `inside` the surface, as the
+/// edges of the cube their vertices are on. Polygons are counter-clockwise when seen from
+/// outside the surface.
+///
+/// The polygons are found by tracing the surface along the faces of the cube, rather than with
+/// the usual lookup table. When a face has two opposite corners inside the surface, they are
+/// kept apart, which is consistent between the two cubes sharing the face.
+fn cube_polygons(inside: [bool; 8]) -> Vec<Vec<[usize; 2]>> {
+    // The edge where the surface leaves each face, keyed by the edge where it enters the face.
+    // Each edge is shared by two faces, which walk around it in opposite directions, so the
+    // surface enters one of them and leaves the other.
+    let mut next = [None; 64];
+    for face in CUBE_FACES {
+        let mut crossings = [([0; 2], false); 4];
+        let mut count = 0;
+        for i in 0..4 {
+            let (a, b) = (face[i], face[(i + 1) % 4]);
+            if inside[a] != inside[b] {
+                crossings[count] = ([a, b], inside[b]);
+                count += 1;
+            }
+        }
+        // Crossings alternate between entering and leaving the inside of the surface, so each
+        // entering crossing is followed by a leaving one.
+        for i in 0..count {
+            let (edge, enters) = crossings[i];
+            if enters {
+                next[edge_key(edge)] = Some((edge, crossings[(i + 1) % count].0));
+            }
+        }
+    }
+
+    let mut polygons = Vec::new();
+    let mut visited = [false; 64];
+    for start in 0..64 {
+        if visited[start] || next[start].is_none() {
+            continue;
+        }
+        let mut polygon = Vec::new();
+        let mut key = start;
+        while let Some((edge, next_edge)) = next[key] {
+            if visited[key] {
+                break;
+            }
+            visited[key] = true;
+            polygon.push(edge);
+            key = edge_key(next_edge);
+        }
+        polygons.push(polygon);
+    }
+    polygons
+}
+
+/// Identifies an edge of a cube from its corners, in either order.
+fn edge_key([a, b]: [usize; 2]) -> usize {
+    a.min(b) * 8 + a.max(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::PI;
+
+    use bevy_utils::HashSet;
+
+    use super::*;
+    use crate::mesh::VertexAttributeValues;
+
+    fn mesh_data(mesh: &Mesh) -> (Vec<Vec3>, Vec<Vec3>, Vec<u32>) {
+        let float3 = |attribute| -> Vec<Vec3> {
+            mesh.attribute(attribute)
+                .and_then(VertexAttributeValues::as_float3)
+                .unwrap()
+                .iter()
+                .map(|&value| Vec3::from(value))
+                .collect()
+        };
+        let Some(Indices::U32(indices)) = mesh.indices() else {
+            panic!("missing indices");
+        };
+        (
+            float3(Mesh::ATTRIBUTE_POSITION),
+            float3(Mesh::ATTRIBUTE_NORMAL),
+            indices.clone(),
+        )
+    }
+
+    /// Checks that each edge of the triangles is shared by exactly two triangles, which go
+    /// along it in opposite directions.
+    fn assert_closed(indices: &[u32]) {
+        let mut edges = HashSet::new();
+        for triangle in indices.chunks_exact(3) {
+            for i in 0..3 {
+                let edge = (triangle[i], triangle[(i + 1) % 3]);
+                assert!(edges.insert(edge), "repeated edge {edge:?}");
+            }
+        }
+        for &(a, b) in &edges {
+            assert!(edges.contains(&(b, a)), "open edge {:?}", (a, b));
+        }
+    }
+
+    #[test]
+    fn sphere() {
+        let center = Vec3::splat(5.);
+        let radius = 3.5;
+        let field = ScalarField::from_fn(UVec3::splat(11), |sample| {
+            radius - sample.as_vec3().distance(center)
+        });
+        let spacing = Vec3::splat(0.5);
+        let mesh = field.mesh(&MarchingCubesSettings {
+            spacing,
+            ..Default::default()
+        });
+        let (positions, normals, indices) = mesh_data(&mesh);
+        assert_closed(&indices);
+
+        for (position, normal) in positions.iter().zip(&normals) {
+            let outward = (*position / spacing - center).normalize();
+            assert!(normal.dot(outward) > 0.9);
+        }
+        // The triangles face outward, so the volume they enclose is positive.
+        let volume: f32 = indices
+            .chunks_exact(3)
+            .map(|triangle| {
+                let [a, b, c] = [0, 1, 2].map(|i| positions[triangle[i] as usize]);
+                a.dot(b.cross(c)) / 6.
+            })
+            .sum();
+        let expected = 4. / 3. * PI * (radius * 0.5).powi(3);
+        assert!((volume - expected).abs() < expected * 0.05);
+    }
+
+    #[test]
+    fn ambiguous_cases() {
+        // Random-looking values, with a border of samples outside the surface.
+        let mut seed = 12345_u32;
+        let field = ScalarField::from_fn(UVec3::splat(8), |sample| {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            if sample.min_element() == 0 || sample.max_element() == 7 {
+                -1.
+            } else {
+                (seed >> 16) as f32 / u16::MAX as f32 - 0.5
+            }
+        });
+        let (_, _, indices) = mesh_data(&field.mesh(&MarchingCubesSettings::default()));
+        assert!(!indices.is_empty());
+        assert_closed(&indices);
+    }
+}
@@ -4,6 +4,7 @@ use bevy_ecs::{
     entity::{Entity, EntityMapper, MapEntities},
     prelude::ReflectComponent,
     reflect::ReflectMapEntities,
+    world::{FromWorld, World},
 };
 use bevy_math::Mat4;
 use bevy_reflect::{Reflect, TypePath};
@@ -24,6 +25,61 @@ impl MapEntities for SkinnedMesh {
     }
 }
 
+/// Skins a mesh with the joint matrices of another entity's [`SkinnedMesh`], to render crowds of
+/// identical characters cheaply.
+///
+/// The joints of the source are animated once, and all the instances with the same
+/// [`phase_offset`](Self::phase_offset) share its skinning buffer, so they can be drawn in a
+/// single instanced draw call. The pose of the source is applied relative to the
+/// [`GlobalTransform`](bevy_transform::components::GlobalTransform) of each instance, as if the
+/// source mesh was at the position of the instance.
+///
+/// The mesh of the instance must have the same joint attributes as the mesh of the source. The
+/// source is usually a hidden character playing its animation with an `AnimationPlayer`, and
+/// each of its skinned meshes is instanced by a separate entity.
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component, MapEntities)]
+pub struct SkinnedMeshInstance {
+    /// The entity with the [`SkinnedMesh`] whose pose is reused.
+    pub source: Entity,
+    /// How many frames behind the source the instance is posed, so that a crowd doesn't move in
+    /// lockstep.
+    ///
+    /// The poses of the source are kept for [`MAX_SKIN_PHASE_OFFSET`] frames.
+    pub phase_offset: u32,
+}
+
+/// The maximum [`SkinnedMeshInstance::phase_offset`], larger offsets are clamped.
+pub const MAX_SKIN_PHASE_OFFSET: u32 = 32;
+
+impl SkinnedMeshInstance {
+    /// Creates an instance of the [`SkinnedMesh`] of `source`, posed in sync with it.
+    pub fn new(source: Entity) -> Self {
+        Self {
+            source,
+            phase_offset: 0,
+        }
+    }
+
+    /// Returns this instance posed `phase_offset` frames behind its source.
+    pub fn with_phase_offset(mut self, phase_offset: u32) -> Self {
+        self.phase_offset = phase_offset;
+        self
+    }
+}
+
+impl FromWorld for SkinnedMeshInstance {
+    fn from_world(_world: &mut World) -> Self {
+        Self::new(Entity::PLACEHOLDER)
+    }
+}
+
+impl MapEntities for SkinnedMeshInstance {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.source = entity_mapper.map_entity(self.source);
+    }
+}
+
 #[derive(Asset, TypePath, Debug)]
 pub struct SkinnedMeshInverseBindposes(Box<[Mat4]>);
 
@@ -1,15 +1,27 @@
+mod builder;
+mod cleanup;
+mod heightfield;
+mod lightmap_uv;
+mod marching_cubes;
 #[allow(clippy::module_inception)]
 mod mesh;
 pub mod morph;
 pub mod primitives;
+mod raycast;
 /// Generation for some primitive shape meshes.
 pub mod shape;
 
+pub use builder::*;
+pub use cleanup::*;
+pub use heightfield::*;
+pub use lightmap_uv::*;
+pub use marching_cubes::*;
 pub use mesh::*;
 pub use primitives::*;
+pub use raycast::*;
 
 use crate::{prelude::Image, render_asset::RenderAssetPlugin};
-use bevy_app::{App, Plugin};
+use bevy_app::{App, Plugin, PostUpdate};
 use bevy_asset::{AssetApp, Handle};
 use bevy_ecs::entity::Entity;
 
@@ -26,7 +38,11 @@ impl Plugin for MeshPlugin {
             .register_type::<Option<Indices>>()
             .register_type::<Indices>()
             .register_type::<skinning::SkinnedMesh>()
+            .register_type::<skinning::SkinnedMeshInstance>()
             .register_type::<Vec<Entity>>()
+            .register_type::<RaycastShape>()
+            .init_resource::<MeshBvhCache>()
+            .add_systems(PostUpdate, invalidate_mesh_bvhs)
             // 'Mesh' must be prepared after 'Image' as meshes rely on the morph target image being ready
             .add_plugins(RenderAssetPlugin::<Mesh, Image>::default());
     }
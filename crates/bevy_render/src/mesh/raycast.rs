@@ -0,0 +1,431 @@
+//! CPU ray casting against meshes and primitive shapes.
+
+use crate::{
+    mesh::{Mesh, PrimitiveTopology},
+    primitives::Aabb,
+};
+use bevy_asset::{AssetEvent, AssetId, Assets, Handle};
+use bevy_ecs::{prelude::*, system::SystemParam};
+use bevy_math::{
+    bounding::{Aabb3d, BoundingSphere, Bvh3d, RayCast3d},
+    primitives::{Cuboid, Direction3d, Sphere},
+    Affine3A, Vec3,
+};
+use bevy_reflect::Reflect;
+use bevy_transform::components::GlobalTransform;
+use bevy_utils::HashMap;
+use std::sync::{Arc, PoisonError, RwLock};
+
+/// The triangles of a [`Mesh`], organized in a [`Bvh3d`] to quickly find which of them a ray hits.
+#[derive(Debug)]
+pub struct MeshBvh {
+    triangles: Vec<[Vec3; 3]>,
+    bvh: Bvh3d,
+}
+
+impl MeshBvh {
+    /// Builds the hierarchy from the positions and indices of `mesh`.
+    ///
+    /// Returns `None` if the mesh doesn't use the [`PrimitiveTopology::TriangleList`] topology,
+    /// or doesn't have [`Mesh::ATTRIBUTE_POSITION`] in the `Float32x3` format.
+    pub fn from_mesh(mesh: &Mesh) -> Option<Self> {
+        if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+            return None;
+        }
+        let positions = mesh.attribute(Mesh::ATTRIBUTE_POSITION)?.as_float3()?;
+
+        let triangles: Vec<[Vec3; 3]> = match mesh.indices() {
+            Some(indices) => {
+                let position = |index: usize| positions.get(index).copied().map(Vec3::from);
+                let indices: Vec<usize> = indices.iter().collect();
+                indices
+                    .chunks_exact(3)
+                    .filter_map(|triangle| {
+                        Some([
+                            position(triangle[0])?,
+                            position(triangle[1])?,
+                            position(triangle[2])?,
+                        ])
+                    })
+                    .collect()
+            }
+            None => positions
+                .chunks_exact(3)
+                .map(|triangle| [triangle[0], triangle[1], triangle[2]].map(Vec3::from))
+                .collect(),
+        };
+        let volumes: Vec<Aabb3d> = triangles
+            .iter()
+            .map(|[a, b, c]| Aabb3d {
+                min: a.min(*b).min(*c),
+                max: a.max(*b).max(*c),
+            })
+            .collect();
+
+        Some(Self {
+            bvh: Bvh3d::new(&volumes),
+            triangles,
+        })
+    }
+
+    /// Returns the triangle with the given index.
+    pub fn triangle(&self, index: usize) -> Option<[Vec3; 3]> {
+        self.triangles.get(index).copied()
+    }
+
+    /// Returns the number of triangles.
+    pub fn triangle_count(&self) -> usize {
+        self.triangles.len()
+    }
+
+    /// Finds the closest triangle hit by `ray`, in the local space of the mesh.
+    ///
+    /// Returns the index of the triangle along with the distance of the hit.
+    pub fn cast_ray(&self, ray: &RayCast3d) -> Option<(usize, f32)> {
+        self.bvh.cast_ray(ray, |index, ray| {
+            let [a, b, c] = self.triangles[index];
+            ray.triangle_intersection_at(a, b, c)
+        })
+    }
+}
+
+/// Caches the [`MeshBvh`] of each [`Mesh`] ray cast against with [`Raycast`].
+///
+/// Hierarchies are built the first time a mesh is ray cast against,
+/// and dropped when the mesh is modified or removed.
+#[derive(Resource, Default)]
+pub struct MeshBvhCache {
+    bvhs: RwLock<HashMap<AssetId<Mesh>, Option<Arc<MeshBvh>>>>,
+}
+
+impl MeshBvhCache {
+    /// Returns the [`MeshBvh`] of the mesh, building it if needed.
+    ///
+    /// Returns `None` if the mesh isn't loaded, or can't be ray cast against.
+    pub fn get_or_build(&self, id: AssetId<Mesh>, meshes: &Assets<Mesh>) -> Option<Arc<MeshBvh>> {
+        if let Some(bvh) = self
+            .bvhs
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&id)
+        {
+            return bvh.clone();
+        }
+
+        let bvh = MeshBvh::from_mesh(meshes.get(id)?).map(Arc::new);
+        self.bvhs
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(id, bvh.clone());
+        bvh
+    }
+}
+
+/// Drops the cached [`MeshBvh`] of the meshes that were modified or removed.
+pub fn invalidate_mesh_bvhs(
+    mut cache: ResMut<MeshBvhCache>,
+    mut events: EventReader<AssetEvent<Mesh>>,
+) {
+    let bvhs = cache.bvhs.get_mut().unwrap_or_else(PoisonError::into_inner);
+    for event in events.read() {
+        match event {
+            AssetEvent::Modified { id } | AssetEvent::Removed { id } => {
+                bvhs.remove(id);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// A primitive shape that can be hit by [`Raycast`], centered on the [`GlobalTransform`] of its entity.
+///
+/// Ray casting against primitives is much cheaper than against meshes, so this can be used
+/// for invisible hitboxes, or as a cheaper stand-in for detailed meshes.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Component, PartialEq)]
+pub enum RaycastShape {
+    /// A sphere.
+    Sphere(Sphere),
+    /// A box.
+    Cuboid(Cuboid),
+}
+
+/// An entity hit by a ray cast with [`Raycast`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RaycastHit {
+    /// The entity that was hit.
+    pub entity: Entity,
+    /// The distance from the origin of the ray to the hit.
+    pub distance: f32,
+    /// The position of the hit, in world space.
+    pub point: Vec3,
+    /// The normal of the surface at the hit, in world space.
+    pub normal: Vec3,
+    /// The index of the triangle that was hit, for meshes.
+    pub triangle: Option<usize>,
+}
+
+/// System parameter to cast rays against the meshes and [`RaycastShape`]s of the world, on the CPU.
+///
+/// Meshes are hit if they use the [`PrimitiveTopology::TriangleList`] topology and are still
+/// available in the main world. Their [`Aabb`], if any, is used to skip them quickly, and a
+/// [`MeshBvh`] is built and cached the first time they are ray cast against.
+///
+/// Hits are found from the current [`GlobalTransform`]s, so transforms changed since the last
+/// transform propagation are not taken into account.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_math::{bounding::RayCast3d, Ray3d};
+/// # use bevy_render::mesh::Raycast;
+/// #[derive(Component)]
+/// struct Player;
+///
+/// fn line_of_sight(raycast: Raycast, player: Query<Entity, With<Player>>) {
+///     let player = player.single();
+///     let ray = RayCast3d::from_ray(Ray3d::new(bevy_math::Vec3::ZERO, bevy_math::Vec3::X), 100.);
+///     if let Some(hit) = raycast.cast_ray_closest(&ray, |entity| entity != player) {
+///         println!("Blocked by {:?} at {}", hit.entity, hit.point);
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(line_of_sight);
+/// ```
+#[derive(SystemParam)]
+pub struct Raycast<'w, 's> {
+    meshes: Res<'w, Assets<Mesh>>,
+    cache: Res<'w, MeshBvhCache>,
+    mesh_query: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static Handle<Mesh>,
+            &'static GlobalTransform,
+            Option<&'static Aabb>,
+        ),
+    >,
+    shape_query: Query<'w, 's, (Entity, &'static RaycastShape, &'static GlobalTransform)>,
+}
+
+impl<'w, 's> Raycast<'w, 's> {
+    /// Returns every entity hit by `ray` and accepted by `filter`, sorted from closest to farthest.
+    pub fn cast_ray(&self, ray: &RayCast3d, filter: impl Fn(Entity) -> bool) -> Vec<RaycastHit> {
+        let mut hits = Vec::new();
+        for (entity, mesh, transform, aabb) in &self.mesh_query {
+            if filter(entity) {
+                hits.extend(self.cast_ray_mesh(ray, mesh, transform, aabb, entity));
+            }
+        }
+        for (entity, shape, transform) in &self.shape_query {
+            if filter(entity) {
+                hits.extend(cast_ray_shape(ray, shape, transform, entity));
+            }
+        }
+        hits.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        hits
+    }
+
+    /// Returns the closest entity hit by `ray` and accepted by `filter`.
+    pub fn cast_ray_closest(
+        &self,
+        ray: &RayCast3d,
+        filter: impl Fn(Entity) -> bool,
+    ) -> Option<RaycastHit> {
+        // Every hit shortens the ray, so that farther entities are skipped early.
+        let mut ray = ray.clone();
+        let mut closest = None;
+        for (entity, mesh, transform, aabb) in &self.mesh_query {
+            if !filter(entity) {
+                continue;
+            }
+            if let Some(hit) = self.cast_ray_mesh(&ray, mesh, transform, aabb, entity) {
+                ray.max = hit.distance;
+                closest = Some(hit);
+            }
+        }
+        for (entity, shape, transform) in &self.shape_query {
+            if !filter(entity) {
+                continue;
+            }
+            if let Some(hit) = cast_ray_shape(&ray, shape, transform, entity) {
+                ray.max = hit.distance;
+                closest = Some(hit);
+            }
+        }
+        closest
+    }
+
+    fn cast_ray_mesh(
+        &self,
+        ray: &RayCast3d,
+        mesh: &Handle<Mesh>,
+        transform: &GlobalTransform,
+        aabb: Option<&Aabb>,
+        entity: Entity,
+    ) -> Option<RaycastHit> {
+        let local = LocalRay::new(ray, transform)?;
+        if let Some(aabb) = aabb {
+            let aabb = Aabb3d::new(aabb.center.into(), aabb.half_extents.into());
+            local.ray.aabb_intersection_at(&aabb)?;
+        }
+
+        let bvh = self.cache.get_or_build(mesh.id(), &self.meshes)?;
+        let (triangle, distance) = bvh.cast_ray(&local.ray)?;
+        let [a, b, c] = bvh.triangles[triangle];
+        let mut hit = local.hit(ray, entity, distance, (b - a).cross(c - a));
+        hit.triangle = Some(triangle);
+        Some(hit)
+    }
+}
+
+fn cast_ray_shape(
+    ray: &RayCast3d,
+    shape: &RaycastShape,
+    transform: &GlobalTransform,
+    entity: Entity,
+) -> Option<RaycastHit> {
+    let local = LocalRay::new(ray, transform)?;
+    match shape {
+        RaycastShape::Sphere(sphere) => {
+            let sphere = BoundingSphere::new(Vec3::ZERO, sphere.radius);
+            let distance = local.ray.sphere_intersection_at(&sphere)?;
+            let normal = local.ray.ray.get_point(distance);
+            Some(local.hit(ray, entity, distance, normal))
+        }
+        RaycastShape::Cuboid(cuboid) => {
+            let aabb = Aabb3d::new(Vec3::ZERO, cuboid.half_size);
+            let distance = local.ray.aabb_intersection_at(&aabb)?;
+            // The normal is the axis of the face the point is the closest to.
+            let point = local.ray.ray.get_point(distance) / cuboid.half_size;
+            let axis = point.abs().max_element();
+            let normal = Vec3::select(
+                point.abs().cmpeq(Vec3::splat(axis)),
+                point.signum(),
+                Vec3::ZERO,
+            );
+            Some(local.hit(ray, entity, distance, normal))
+        }
+    }
+}
+
+/// A ray transformed into the local space of an entity.
+struct LocalRay {
+    ray: RayCast3d,
+    /// The length of a world space unit along the ray, in local space.
+    scale: f32,
+    world_to_local: Affine3A,
+}
+
+impl LocalRay {
+    fn new(ray: &RayCast3d, transform: &GlobalTransform) -> Option<Self> {
+        let world_to_local = transform.affine().inverse();
+        let origin = world_to_local.transform_point3(ray.ray.origin);
+        let (direction, scale) =
+            Direction3d::new_and_length(world_to_local.transform_vector3(*ray.ray.direction))
+                .ok()?;
+        Some(Self {
+            ray: RayCast3d::new(origin, direction, ray.max * scale),
+            scale,
+            world_to_local,
+        })
+    }
+
+    /// Converts a hit at `distance` along the local ray, on a surface with the local `normal`,
+    /// into a world space [`RaycastHit`].
+    fn hit(&self, ray: &RayCast3d, entity: Entity, distance: f32, normal: Vec3) -> RaycastHit {
+        let distance = distance / self.scale;
+        // Normals are transformed by the inverse transpose of the local to world transform.
+        let normal = self
+            .world_to_local
+            .matrix3
+            .transpose()
+            .mul_vec3(normal)
+            .normalize_or_zero();
+        RaycastHit {
+            entity,
+            distance,
+            point: ray.ray.get_point(distance),
+            normal,
+            triangle: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::Indices;
+    use crate::render_asset::RenderAssetUsages;
+    use bevy_math::{Quat, Ray3d};
+    use bevy_transform::components::Transform;
+
+    fn quad() -> Mesh {
+        Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            vec![[-1., 0., -1.], [1., 0., -1.], [1., 0., 1.], [-1., 0., 1.]],
+        )
+        .with_inserted_indices(Indices::U32(vec![0, 2, 1, 0, 3, 2]))
+    }
+
+    #[test]
+    fn raycast_meshes_and_shapes() {
+        let mut world = World::new();
+        world.init_resource::<MeshBvhCache>();
+        let mut meshes = Assets::<Mesh>::default();
+        let quad = meshes.add(quad());
+        world.insert_resource(meshes);
+
+        let floor = world
+            .spawn((
+                quad,
+                GlobalTransform::from(
+                    Transform::from_scale(Vec3::splat(10.))
+                        .with_rotation(Quat::from_rotation_y(0.3)),
+                ),
+            ))
+            .id();
+        let ball = world
+            .spawn((
+                RaycastShape::Sphere(Sphere::new(1.)),
+                GlobalTransform::from_xyz(0., 2., 0.),
+            ))
+            .id();
+        let crate_ = world
+            .spawn((
+                RaycastShape::Cuboid(Cuboid::new(2., 2., 2.)),
+                GlobalTransform::from_xyz(5., 1., 0.),
+            ))
+            .id();
+
+        let mut state = bevy_ecs::system::SystemState::<Raycast>::new(&mut world);
+        let raycast = state.get(&world);
+
+        let down = |x: f32| RayCast3d::from_ray(Ray3d::new(Vec3::new(x, 10., 0.), -Vec3::Y), 100.);
+
+        let hits = raycast.cast_ray(&down(0.), |_| true);
+        assert_eq!(
+            hits.iter().map(|hit| hit.entity).collect::<Vec<_>>(),
+            [ball, floor]
+        );
+        assert!((hits[0].distance - 7.).abs() < 1e-4);
+        assert!(hits[0].normal.abs_diff_eq(Vec3::Y, 1e-4));
+        assert!((hits[1].distance - 10.).abs() < 1e-4);
+        assert!(hits[1].point.abs_diff_eq(Vec3::ZERO, 1e-4));
+        assert!(hits[1].normal.abs_diff_eq(Vec3::Y, 1e-4));
+
+        let hit = raycast.cast_ray_closest(&down(5.), |_| true).unwrap();
+        assert_eq!(hit.entity, crate_);
+        assert!((hit.distance - 8.).abs() < 1e-4);
+        assert!(hit.normal.abs_diff_eq(Vec3::Y, 1e-4));
+
+        let hit = raycast
+            .cast_ray_closest(&down(5.), |entity| entity != crate_)
+            .unwrap();
+        assert_eq!(hit.entity, floor);
+        assert!(raycast.cast_ray_closest(&down(20.), |_| true).is_none());
+    }
+}
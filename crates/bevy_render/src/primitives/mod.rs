@@ -303,6 +303,61 @@ impl Frustum {
     }
 }
 
+/// The half spaces of a [`Frustum`], transposed so that a sphere is tested against four of them
+/// at once with SIMD instructions.
+///
+/// Build it once per frustum with [`SimdFrustum::new`] before testing many spheres.
+#[derive(Clone, Copy, Debug)]
+pub struct SimdFrustum {
+    // The x, y and z components of the plane normals and the distances, four planes per vector.
+    normal_x: [Vec4; 2],
+    normal_y: [Vec4; 2],
+    normal_z: [Vec4; 2],
+    distance: [Vec4; 2],
+}
+
+impl SimdFrustum {
+    /// Transposes the half spaces of `frustum`, ignoring the far plane unless `intersect_far`
+    /// is `true`.
+    pub fn new(frustum: &Frustum, intersect_far: bool) -> Self {
+        let max = if intersect_far { 6 } else { 5 };
+        // Unused lanes contain half spaces containing everything.
+        let mut planes = [Vec4::new(0.0, 0.0, 0.0, f32::MAX); 8];
+        for (plane, half_space) in planes.iter_mut().zip(&frustum.half_spaces[..max]) {
+            *plane = half_space.normal_d();
+        }
+        let lanes = |f: fn(Vec4) -> f32| {
+            [
+                Vec4::new(f(planes[0]), f(planes[1]), f(planes[2]), f(planes[3])),
+                Vec4::new(f(planes[4]), f(planes[5]), f(planes[6]), f(planes[7])),
+            ]
+        };
+        Self {
+            normal_x: lanes(|plane| plane.x),
+            normal_y: lanes(|plane| plane.y),
+            normal_z: lanes(|plane| plane.z),
+            distance: lanes(|plane| plane.w),
+        }
+    }
+
+    /// Checks if a sphere intersects the frustum, like [`Frustum::intersects_sphere`].
+    #[inline]
+    pub fn intersects_sphere(&self, sphere: &Sphere) -> bool {
+        let x = Vec4::splat(sphere.center.x);
+        let y = Vec4::splat(sphere.center.y);
+        let z = Vec4::splat(sphere.center.z);
+        let radius = Vec4::splat(sphere.radius);
+        (0..2).all(|i| {
+            let distance = self.normal_x[i] * x
+                + self.normal_y[i] * y
+                + self.normal_z[i] * z
+                + self.distance[i]
+                + radius;
+            !distance.cmple(Vec4::ZERO).any()
+        })
+    }
+}
+
 #[derive(Component, Clone, Debug, Default, Reflect)]
 #[reflect(Component)]
 pub struct CubemapFrusta {
@@ -505,4 +560,28 @@ mod tests {
             Aabb::from_min_max(Vec3::new(-1.0, -5.0, 0.0), Vec3::new(2.0, 0.0, 1.0))
         );
     }
+
+    #[test]
+    fn simd_frustum_matches_frustum() {
+        for frustum in [frustum(), big_frustum()] {
+            for intersect_far in [false, true] {
+                let simd_frustum = SimdFrustum::new(&frustum, intersect_far);
+                for i in 0..1000 {
+                    let sphere = Sphere {
+                        center: Vec3A::new(
+                            (i % 10) as f32 - 4.5,
+                            (i / 10 % 10) as f32 - 4.5,
+                            (i / 100) as f32 - 4.5,
+                        ),
+                        radius: (i % 7) as f32 * 0.25,
+                    };
+                    assert_eq!(
+                        simd_frustum.intersects_sphere(&sphere),
+                        frustum.intersects_sphere(&sphere, intersect_far),
+                        "{sphere:?}"
+                    );
+                }
+            }
+        }
+    }
 }
@@ -1,8 +1,9 @@
 use crate::{ExtractSchedule, MainWorld, Render, RenderApp, RenderSet};
 use bevy_app::{App, Plugin};
 use bevy_asset::{Asset, AssetEvent, AssetId, Assets};
+use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::{
-    prelude::{Commands, EventReader, IntoSystemConfigs, ResMut, Resource},
+    prelude::{Commands, Event, EventReader, EventWriter, IntoSystemConfigs, ResMut, Resource},
     schedule::SystemConfigs,
     system::{StaticSystemParam, SystemParam, SystemParamItem, SystemState},
     world::{FromWorld, Mut},
@@ -13,6 +14,7 @@ use bevy_reflect::{
     Typed, ValueInfo,
 };
 use bevy_utils::{thiserror::Error, HashMap, HashSet};
+use bevy_window::ApplicationLifetime;
 use serde::{Deserialize, Serialize};
 use std::marker::PhantomData;
 
@@ -162,6 +164,39 @@ impl FromReflect for RenderAssetUsages {
     }
 }
 
+/// Send this event to extract and prepare all the [`RenderAsset`]s again, for example after the
+/// graphics driver released their GPU resources.
+///
+/// Only the assets that are still in the main world, those whose [`RenderAsset::asset_usage`]
+/// contains [`RenderAssetUsages::MAIN_WORLD`], can be uploaded again: the assets only kept in the
+/// render world must be reloaded.
+///
+/// The [`ReuploadRenderAssetsOnResume`] resource can send this
+/// event when the application is resumed.
+#[derive(Event, Debug, Clone, Copy, Default)]
+pub struct ReuploadRenderAssets;
+
+/// Whether to send [`ReuploadRenderAssets`] when the application is resumed, for the platforms
+/// whose graphics drivers can release the GPU resources of suspended applications.
+///
+/// Disabled by default.
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq, Deref, DerefMut)]
+pub struct ReuploadRenderAssetsOnResume(pub bool);
+
+/// Sends [`ReuploadRenderAssets`] when the application is resumed, if
+/// [`ReuploadRenderAssetsOnResume`] is enabled.
+pub fn reupload_render_assets_on_resume(
+    mut lifetime_events: EventReader<ApplicationLifetime>,
+    mut reupload_events: EventWriter<ReuploadRenderAssets>,
+) {
+    if lifetime_events
+        .read()
+        .any(|event| *event == ApplicationLifetime::Resumed)
+    {
+        reupload_events.send(ReuploadRenderAssets);
+    }
+}
+
 impl Typed for RenderAssetUsages {
     fn type_info() -> &'static TypeInfo {
         static CELL: NonGenericTypeInfoCell = NonGenericTypeInfoCell::new();
@@ -197,7 +232,8 @@ impl<A: RenderAsset, AFTER: RenderAssetDependency + 'static> Plugin
     for RenderAssetPlugin<A, AFTER>
 {
     fn build(&self, app: &mut App) {
-        app.init_resource::<CachedExtractRenderAssetSystemState<A>>();
+        app.add_event::<ReuploadRenderAssets>()
+            .init_resource::<CachedExtractRenderAssetSystemState<A>>();
         if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app
                 .init_resource::<ExtractedAssets<A>>()
@@ -290,6 +326,7 @@ impl<A: RenderAsset> RenderAssets<A> {
 struct CachedExtractRenderAssetSystemState<A: RenderAsset> {
     state: SystemState<(
         EventReader<'static, 'static, AssetEvent<A>>,
+        EventReader<'static, 'static, ReuploadRenderAssets>,
         ResMut<'static, Assets<A>>,
     )>,
 }
@@ -307,11 +344,15 @@ impl<A: RenderAsset> FromWorld for CachedExtractRenderAssetSystemState<A> {
 fn extract_render_asset<A: RenderAsset>(mut commands: Commands, mut main_world: ResMut<MainWorld>) {
     main_world.resource_scope(
         |world, mut cached_state: Mut<CachedExtractRenderAssetSystemState<A>>| {
-            let (mut events, mut assets) = cached_state.state.get_mut(world);
+            let (mut events, mut reupload_events, mut assets) = cached_state.state.get_mut(world);
 
             let mut changed_assets = HashSet::default();
             let mut removed = Vec::new();
 
+            if reupload_events.read().last().is_some() {
+                changed_assets.extend(assets.ids());
+            }
+
             for event in events.read() {
                 #[allow(clippy::match_same_arms)]
                 match event {
@@ -405,3 +446,67 @@ pub fn prepare_assets<A: RenderAsset>(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::{event::Events, system::RunSystemOnce, world::World};
+
+    #[derive(Asset, TypePath, Clone)]
+    struct TestAsset(RenderAssetUsages);
+
+    impl RenderAsset for TestAsset {
+        type PreparedAsset = ();
+        type Param = ();
+
+        fn asset_usage(&self) -> RenderAssetUsages {
+            self.0
+        }
+
+        fn prepare_asset(
+            self,
+            _: &mut SystemParamItem<Self::Param>,
+        ) -> Result<Self::PreparedAsset, PrepareAssetError<Self>> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn reupload_extracts_the_main_world_assets_again() {
+        let mut main_world = World::new();
+        main_world.init_resource::<Assets<TestAsset>>();
+        main_world.init_resource::<Events<AssetEvent<TestAsset>>>();
+        main_world.init_resource::<Events<ReuploadRenderAssets>>();
+        main_world.init_resource::<CachedExtractRenderAssetSystemState<TestAsset>>();
+        let mut assets = main_world.resource_mut::<Assets<TestAsset>>();
+        let kept = assets.add(TestAsset(RenderAssetUsages::default())).id();
+        let render_world_only = assets.add(TestAsset(RenderAssetUsages::RENDER_WORLD)).id();
+        let mut render_world = World::new();
+        render_world.insert_resource(MainWorld(main_world));
+
+        let mut extract = |reupload: bool| {
+            if reupload {
+                render_world
+                    .resource_mut::<MainWorld>()
+                    .send_event(ReuploadRenderAssets);
+            }
+            render_world.run_system_once(extract_render_asset::<TestAsset>);
+            let mut extracted: Vec<_> = render_world
+                .resource::<ExtractedAssets<TestAsset>>()
+                .extracted
+                .iter()
+                .map(|(id, _)| *id)
+                .collect();
+            extracted.sort();
+            extracted
+        };
+
+        assert!(extract(false).is_empty());
+        let mut both = vec![kept, render_world_only];
+        both.sort();
+        assert_eq!(extract(true), both);
+        assert!(extract(false).is_empty());
+        // The render world only asset was moved out of the main world by the first upload.
+        assert_eq!(extract(true), [kept]);
+    }
+}
@@ -0,0 +1,337 @@
+use std::marker::PhantomData;
+
+use bevy_app::App;
+use bevy_ecs::{prelude::*, query::QueryItem};
+#[cfg(feature = "trace")]
+use bevy_utils::tracing::info_span;
+
+use super::{
+    sort_phase_system, CachedRenderPipelinePhaseItem, DrawFunctions, PhaseItem, RenderPhase,
+};
+use crate::{
+    batching::{batch_and_prepare_render_phase, GetBatchData},
+    camera::{Camera, CameraRenderGraph, ExtractedCamera},
+    render_graph::{
+        InternedRenderLabel, InternedRenderSubGraph, NodeRunError, RenderGraphApp,
+        RenderGraphContext, RenderLabel, RenderSubGraph, ViewNode, ViewNodeRunner,
+    },
+    render_resource::{RenderPassDescriptor, StoreOp},
+    renderer::RenderContext,
+    view::{ViewDepthTexture, ViewTarget},
+    Extract, ExtractSchedule, Render, RenderSet,
+};
+
+/// Describes where in the render graph a render phase added with
+/// [`RenderPhaseApp::add_render_phase`] runs, and whether its items are sorted.
+///
+/// ```ignore
+/// render_app.add_render_phase::<Outline3d>(
+///     RenderPhaseSlot::new(Core3d, OutlineLabel)
+///         .after(Node3d::MainOpaquePass)
+///         .before(Node3d::MainTransparentPass),
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct RenderPhaseSlot {
+    sub_graph: InternedRenderSubGraph,
+    label: InternedRenderLabel,
+    after: Vec<InternedRenderLabel>,
+    before: Vec<InternedRenderLabel>,
+    sorted: bool,
+}
+
+impl RenderPhaseSlot {
+    /// Creates a slot for a node with the given `label` in the given `sub_graph`.
+    ///
+    /// Without any ordering constraints the node runs whenever the graph gets to it, so most
+    /// phases will want to call [`RenderPhaseSlot::after`] and [`RenderPhaseSlot::before`].
+    pub fn new(sub_graph: impl RenderSubGraph, label: impl RenderLabel) -> Self {
+        Self {
+            sub_graph: sub_graph.intern(),
+            label: label.intern(),
+            after: Vec::new(),
+            before: Vec::new(),
+            sorted: true,
+        }
+    }
+
+    /// Runs the phase after the node with the given `label`.
+    pub fn after(mut self, label: impl RenderLabel) -> Self {
+        self.after.push(label.intern());
+        self
+    }
+
+    /// Runs the phase before the node with the given `label`.
+    pub fn before(mut self, label: impl RenderLabel) -> Self {
+        self.before.push(label.intern());
+        self
+    }
+
+    /// Draws the items in the order they were queued instead of sorting them with
+    /// [`PhaseItem::sort`].
+    pub fn unsorted(mut self) -> Self {
+        self.sorted = false;
+        self
+    }
+
+    /// The sub graph the phase runs in.
+    pub fn sub_graph(&self) -> InternedRenderSubGraph {
+        self.sub_graph
+    }
+
+    /// The label of the node drawing the phase.
+    pub fn label(&self) -> InternedRenderLabel {
+        self.label
+    }
+}
+
+/// Adds custom [`RenderPhase`]s to the render [`App`], so that plugins can draw their own
+/// phase items without duplicating the nodes of the core pipeline.
+///
+/// A phase added this way:
+/// * gets a [`RenderPhase`] component on every active camera using the slot's sub graph,
+/// * has its [`DrawFunctions`] resource initialized,
+/// * is sorted in [`RenderSet::PhaseSort`], unless the slot is [`RenderPhaseSlot::unsorted`],
+/// * is drawn by a [`ViewPhaseNode`] into the view's [`ViewTarget`] and, if the view has one,
+///   its [`ViewDepthTexture`].
+///
+/// Adding the same phase item to several sub graphs only adds its systems once.
+pub trait RenderPhaseApp {
+    /// Adds a render phase whose items are drawn without automatic batching.
+    fn add_render_phase<I: CachedRenderPipelinePhaseItem>(
+        &mut self,
+        slot: RenderPhaseSlot,
+    ) -> &mut Self;
+
+    /// Adds a render phase whose items are batched using `F`, for example the `MeshPipeline`.
+    ///
+    /// The [`GpuArrayBuffer`](crate::render_resource::GpuArrayBuffer) of `F` must be initialized
+    /// and written by the plugin owning `F`.
+    fn add_batched_render_phase<I: CachedRenderPipelinePhaseItem, F: GetBatchData + 'static>(
+        &mut self,
+        slot: RenderPhaseSlot,
+    ) -> &mut Self;
+}
+
+impl RenderPhaseApp for App {
+    fn add_render_phase<I: CachedRenderPipelinePhaseItem>(
+        &mut self,
+        slot: RenderPhaseSlot,
+    ) -> &mut Self {
+        if !self.world.contains_resource::<RenderPhaseSubGraphs<I>>() {
+            self.init_resource::<RenderPhaseSubGraphs<I>>()
+                .init_resource::<DrawFunctions<I>>()
+                .add_systems(ExtractSchedule, extract_render_phase::<I>);
+        }
+
+        let mut sub_graphs = self.world.resource_mut::<RenderPhaseSubGraphs<I>>();
+        if !sub_graphs.sub_graphs.contains(&slot.sub_graph) {
+            sub_graphs.sub_graphs.push(slot.sub_graph);
+        }
+        let add_sort_system = slot.sorted && !sub_graphs.sorted;
+        sub_graphs.sorted |= slot.sorted;
+        if add_sort_system {
+            self.add_systems(Render, sort_phase_system::<I>.in_set(RenderSet::PhaseSort));
+        }
+
+        self.add_render_graph_node::<ViewNodeRunner<ViewPhaseNode<I>>>(slot.sub_graph, slot.label);
+        for after in slot.after {
+            self.add_render_graph_edge(slot.sub_graph, after, slot.label);
+        }
+        for before in slot.before {
+            self.add_render_graph_edge(slot.sub_graph, slot.label, before);
+        }
+        self
+    }
+
+    fn add_batched_render_phase<I: CachedRenderPipelinePhaseItem, F: GetBatchData + 'static>(
+        &mut self,
+        slot: RenderPhaseSlot,
+    ) -> &mut Self {
+        if !self.world.contains_resource::<RenderPhaseSubGraphs<I>>() {
+            self.add_systems(
+                Render,
+                batch_and_prepare_render_phase::<I, F>.in_set(RenderSet::PrepareResources),
+            );
+        }
+        self.add_render_phase::<I>(slot)
+    }
+}
+
+/// The sub graphs a render phase added with [`RenderPhaseApp`] runs in.
+#[derive(Resource)]
+struct RenderPhaseSubGraphs<I: PhaseItem> {
+    sub_graphs: Vec<InternedRenderSubGraph>,
+    sorted: bool,
+    marker: PhantomData<fn() -> I>,
+}
+
+impl<I: PhaseItem> Default for RenderPhaseSubGraphs<I> {
+    fn default() -> Self {
+        Self {
+            sub_graphs: Vec::new(),
+            sorted: false,
+            marker: PhantomData,
+        }
+    }
+}
+
+fn extract_render_phase<I: PhaseItem>(
+    mut commands: Commands,
+    sub_graphs: Res<RenderPhaseSubGraphs<I>>,
+    cameras: Extract<Query<(Entity, &Camera, &CameraRenderGraph)>>,
+) {
+    for (entity, camera, render_graph) in &cameras {
+        if camera.is_active && sub_graphs.sub_graphs.contains(render_graph) {
+            commands
+                .get_or_spawn(entity)
+                .insert(RenderPhase::<I>::default());
+        }
+    }
+}
+
+/// A [`ViewNode`] that draws the [`RenderPhase`] of `I` of a view into its [`ViewTarget`],
+/// loading its [`ViewDepthTexture`] if it has one.
+pub struct ViewPhaseNode<I: PhaseItem>(PhantomData<fn() -> I>);
+
+impl<I: PhaseItem> Default for ViewPhaseNode<I> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<I: PhaseItem> ViewNode for ViewPhaseNode<I> {
+    type ViewQuery = (
+        &'static ExtractedCamera,
+        &'static RenderPhase<I>,
+        &'static ViewTarget,
+        Option<&'static ViewDepthTexture>,
+    );
+
+    fn run(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        (camera, phase, target, depth): QueryItem<Self::ViewQuery>,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        if phase.items.is_empty() {
+            return Ok(());
+        }
+
+        let label = std::any::type_name::<I>();
+        #[cfg(feature = "trace")]
+        let _view_phase_span = info_span!("view_phase", phase = label).entered();
+
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some(label),
+            color_attachments: &[Some(target.get_color_attachment())],
+            depth_stencil_attachment: depth.map(|depth| depth.get_attachment(StoreOp::Store)),
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        if let Some(viewport) = camera.viewport.as_ref() {
+            render_pass.set_camera_viewport(viewport);
+        }
+        if let Some(stencil_reference) = depth.and_then(ViewDepthTexture::stencil_reference) {
+            render_pass.set_stencil_reference(stencil_reference);
+        }
+
+        phase.render(&mut render_pass, world, graph.view_entity());
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Range;
+
+    use bevy_app::App;
+    use bevy_ecs::entity::Entity;
+    use bevy_utils::nonmax::NonMaxU32;
+
+    use super::{RenderPhaseApp, RenderPhaseSlot};
+    use crate::{
+        render_graph::{Edge, EmptyNode, RenderGraph, RenderGraphApp, RenderLabel, RenderSubGraph},
+        render_phase::{CachedRenderPipelinePhaseItem, DrawFunctionId, PhaseItem},
+        render_resource::CachedRenderPipelineId,
+    };
+
+    #[derive(Debug, Hash, PartialEq, Eq, Clone, RenderSubGraph)]
+    struct TestGraph;
+
+    #[derive(Debug, Hash, PartialEq, Eq, Clone, RenderLabel)]
+    enum TestNode {
+        Opaque,
+        Custom,
+        Transparent,
+    }
+
+    struct TestItem {
+        batch_range: Range<u32>,
+        dynamic_offset: Option<NonMaxU32>,
+    }
+
+    impl PhaseItem for TestItem {
+        type SortKey = ();
+
+        fn entity(&self) -> Entity {
+            Entity::PLACEHOLDER
+        }
+
+        fn sort_key(&self) -> Self::SortKey {}
+
+        fn draw_function(&self) -> DrawFunctionId {
+            unimplemented!()
+        }
+
+        fn batch_range(&self) -> &Range<u32> {
+            &self.batch_range
+        }
+
+        fn batch_range_mut(&mut self) -> &mut Range<u32> {
+            &mut self.batch_range
+        }
+
+        fn dynamic_offset(&self) -> Option<NonMaxU32> {
+            self.dynamic_offset
+        }
+
+        fn dynamic_offset_mut(&mut self) -> &mut Option<NonMaxU32> {
+            &mut self.dynamic_offset
+        }
+    }
+
+    impl CachedRenderPipelinePhaseItem for TestItem {
+        fn cached_pipeline(&self) -> CachedRenderPipelineId {
+            CachedRenderPipelineId::INVALID
+        }
+    }
+
+    #[test]
+    fn render_phase_node_is_ordered_between_passes() {
+        let mut app = App::new();
+        app.init_resource::<RenderGraph>()
+            .add_render_sub_graph(TestGraph)
+            .add_render_graph_node::<EmptyNode>(TestGraph, TestNode::Opaque)
+            .add_render_graph_node::<EmptyNode>(TestGraph, TestNode::Transparent)
+            .add_render_phase::<TestItem>(
+                RenderPhaseSlot::new(TestGraph, TestNode::Custom)
+                    .after(TestNode::Opaque)
+                    .before(TestNode::Transparent),
+            );
+
+        let render_graph = app.world.resource::<RenderGraph>();
+        let graph = render_graph.get_sub_graph(TestGraph).unwrap();
+        assert!(graph.has_edge(&Edge::NodeEdge {
+            output_node: TestNode::Opaque.intern(),
+            input_node: TestNode::Custom.intern(),
+        }));
+        assert!(graph.has_edge(&Edge::NodeEdge {
+            output_node: TestNode::Custom.intern(),
+            input_node: TestNode::Transparent.intern(),
+        }));
+    }
+}
@@ -25,10 +25,12 @@
 //! The [`Draw`] function trait can either be implemented directly or such a function can be
 //! created by composing multiple [`RenderCommand`]s.
 
+mod app;
 mod draw;
 mod draw_state;
 mod rangefinder;
 
+pub use app::*;
 use bevy_utils::nonmax::NonMaxU32;
 pub use draw::*;
 pub use draw_state::*;
@@ -0,0 +1,118 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static BUFFERS: AllocationCounter = AllocationCounter::new();
+static TEXTURES: AllocationCounter = AllocationCounter::new();
+
+/// The number and total size of the [`Buffer`](super::Buffer)s and [`Texture`](super::Texture)s
+/// currently alive.
+///
+/// This only accounts for resources wrapped in bevy's types, and texture sizes are estimated from
+/// their format and dimensions, so this can differ from what the driver actually allocates.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GpuAllocations {
+    /// The number of buffers.
+    pub buffer_count: u64,
+    /// The total size of the buffers, in bytes.
+    pub buffer_bytes: u64,
+    /// The number of textures.
+    pub texture_count: u64,
+    /// The estimated total size of the textures, in bytes.
+    pub texture_bytes: u64,
+}
+
+impl GpuAllocations {
+    /// Returns the allocations of the resources currently alive.
+    pub fn current() -> Self {
+        Self {
+            buffer_count: BUFFERS.count.load(Ordering::Relaxed),
+            buffer_bytes: BUFFERS.bytes.load(Ordering::Relaxed),
+            texture_count: TEXTURES.count.load(Ordering::Relaxed),
+            texture_bytes: TEXTURES.bytes.load(Ordering::Relaxed),
+        }
+    }
+}
+
+struct AllocationCounter {
+    count: AtomicU64,
+    bytes: AtomicU64,
+}
+
+impl AllocationCounter {
+    const fn new() -> Self {
+        Self {
+            count: AtomicU64::new(0),
+            bytes: AtomicU64::new(0),
+        }
+    }
+}
+
+/// Counts a resource in [`GpuAllocations`] until it is dropped.
+#[derive(Debug)]
+pub(crate) struct TrackedAllocation {
+    counter: &'static AllocationCounter,
+    bytes: u64,
+}
+
+impl std::fmt::Debug for AllocationCounter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AllocationCounter").finish_non_exhaustive()
+    }
+}
+
+impl TrackedAllocation {
+    pub(crate) fn buffer(buffer: &wgpu::Buffer) -> Self {
+        Self::new(&BUFFERS, buffer.size())
+    }
+
+    pub(crate) fn texture(texture: &wgpu::Texture) -> Self {
+        Self::new(&TEXTURES, texture_bytes(texture))
+    }
+
+    fn new(counter: &'static AllocationCounter, bytes: u64) -> Self {
+        counter.count.fetch_add(1, Ordering::Relaxed);
+        counter.bytes.fetch_add(bytes, Ordering::Relaxed);
+        Self { counter, bytes }
+    }
+}
+
+impl Drop for TrackedAllocation {
+    fn drop(&mut self) {
+        self.counter.count.fetch_sub(1, Ordering::Relaxed);
+        self.counter.bytes.fetch_sub(self.bytes, Ordering::Relaxed);
+    }
+}
+
+/// Estimates the size of a texture from its format, dimensions, mip levels and sample count.
+fn texture_bytes(texture: &wgpu::Texture) -> u64 {
+    let format = texture.format();
+    let (block_width, block_height) = format.block_dimensions();
+    // Depth formats without a fixed layout, such as `Depth24Plus`, have no block size.
+    let block_size = format.block_copy_size(None).unwrap_or(4) as u64;
+
+    (0..texture.mip_level_count())
+        .map(|level| texture.size().mip_level_size(level, texture.dimension()))
+        .map(|size| {
+            let size = size.physical_size(format);
+            let blocks = (size.width / block_width) as u64
+                * (size.height / block_height) as u64
+                * size.depth_or_array_layers as u64;
+            blocks * block_size
+        })
+        .sum::<u64>()
+        * texture.sample_count() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracked_allocations_are_released_on_drop() {
+        let before = BUFFERS.bytes.load(Ordering::Relaxed);
+        let allocation = TrackedAllocation::new(&BUFFERS, 1024);
+        assert!(GpuAllocations::current().buffer_count >= 1);
+        assert!(BUFFERS.bytes.load(Ordering::Relaxed) >= before + 1024);
+        drop(allocation);
+        assert_eq!(BUFFERS.bytes.load(Ordering::Relaxed), before);
+    }
+}
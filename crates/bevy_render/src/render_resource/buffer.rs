@@ -1,5 +1,11 @@
-use crate::{define_atomic_id, render_resource::resource_macros::render_resource_wrapper};
-use std::ops::{Bound, Deref, RangeBounds};
+use crate::{
+    define_atomic_id,
+    render_resource::{allocation::TrackedAllocation, resource_macros::render_resource_wrapper},
+};
+use std::{
+    ops::{Bound, Deref, RangeBounds},
+    sync::Arc,
+};
 
 define_atomic_id!(BufferId);
 render_resource_wrapper!(ErasedBuffer, wgpu::Buffer);
@@ -8,6 +14,7 @@ render_resource_wrapper!(ErasedBuffer, wgpu::Buffer);
 pub struct Buffer {
     id: BufferId,
     value: ErasedBuffer,
+    _allocation: Arc<TrackedAllocation>,
 }
 
 impl Buffer {
@@ -33,12 +40,33 @@ impl Buffer {
     pub fn unmap(&self) {
         self.value.unmap();
     }
+
+    /// Maps the whole buffer for reading, copies its content to CPU memory and unmaps it.
+    ///
+    /// The buffer must have been created with [`wgpu::BufferUsages::MAP_READ`]. The future
+    /// doesn't block: it completes when the GPU has finished the submitted work writing to the
+    /// buffer, which is polled by the renderer every frame on native platforms and by the browser
+    /// on the web. It can be spawned on the [`AsyncComputeTaskPool`](bevy_tasks::AsyncComputeTaskPool)
+    /// on all platforms, for example to read back screenshots or picking results.
+    pub async fn read(&self) -> Result<Vec<u8>, wgpu::BufferAsyncError> {
+        let (tx, rx) = async_channel::bounded(1);
+        let buffer_slice = self.value.slice(..);
+        buffer_slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = tx.try_send(result);
+        });
+        rx.recv().await.map_err(|_| wgpu::BufferAsyncError)??;
+        // The data is copied immediately to avoid holding the mapped view for long
+        let data = buffer_slice.get_mapped_range().to_vec();
+        self.value.unmap();
+        Ok(data)
+    }
 }
 
 impl From<wgpu::Buffer> for Buffer {
     fn from(value: wgpu::Buffer) -> Self {
         Buffer {
             id: BufferId::new(),
+            _allocation: Arc::new(TrackedAllocation::buffer(&value)),
             value: ErasedBuffer::new(value),
         }
     }
@@ -80,3 +108,37 @@ impl<'a> Deref for BufferSlice<'a> {
         &self.value
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use futures_lite::future;
+
+    use crate::renderer::RenderDevice;
+
+    #[test]
+    fn read_buffer() {
+        let instance = wgpu::Instance::default();
+        let Some(adapter) =
+            future::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions::default()))
+        else {
+            return;
+        };
+        let (device, queue) =
+            future::block_on(adapter.request_device(&Default::default(), None)).unwrap();
+        let render_device = RenderDevice::from(device);
+        let buffer = render_device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 4,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+        queue.write_buffer(&buffer, 0, &[1, 2, 3, 4]);
+        queue.submit([]);
+
+        // The device is polled by the renderer every frame
+        let (data, _) = future::block_on(future::zip(buffer.read(), async {
+            render_device.poll(wgpu::Maintain::Wait);
+        }));
+        assert_eq!(data.unwrap(), [1, 2, 3, 4]);
+    }
+}
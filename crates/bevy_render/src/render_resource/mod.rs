@@ -1,3 +1,4 @@
+mod allocation;
 mod batched_uniform_buffer;
 mod bind_group;
 mod bind_group_entries;
@@ -8,13 +9,16 @@ mod buffer_vec;
 mod gpu_array_buffer;
 mod pipeline;
 mod pipeline_cache;
+mod pipeline_error;
 mod pipeline_specializer;
 pub mod resource_macros;
 mod shader;
+mod shader_registry;
 mod storage_buffer;
 mod texture;
 mod uniform_buffer;
 
+pub use allocation::GpuAllocations;
 pub use bind_group::*;
 pub use bind_group_entries::*;
 pub use bind_group_layout::*;
@@ -24,8 +28,14 @@ pub use buffer_vec::*;
 pub use gpu_array_buffer::*;
 pub use pipeline::*;
 pub use pipeline_cache::*;
+pub(crate) use pipeline_error::{
+    create_pipeline_error_channels, receive_pipeline_errors, PipelineErrorSender,
+};
+pub use pipeline_error::{PipelineError, PipelineErrorEvent, PipelineErrors, ShaderErrorLocation};
 pub use pipeline_specializer::*;
 pub use shader::*;
+pub(crate) use shader_registry::find_unknown_shader_def;
+pub use shader_registry::{RegisteredShaders, ShaderApp};
 pub use storage_buffer::*;
 pub use texture::*;
 pub use uniform_buffer::*;
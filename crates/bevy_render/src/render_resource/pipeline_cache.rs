@@ -1,5 +1,6 @@
 use crate::{render_resource::*, renderer::RenderDevice, Extract};
 use bevy_asset::{AssetEvent, AssetId, Assets};
+use bevy_ecs::change_detection::DetectChanges;
 use bevy_ecs::system::{Res, ResMut};
 use bevy_ecs::{event::EventReader, system::Resource};
 use bevy_tasks::Task;
@@ -81,6 +82,9 @@ impl CachedComputePipelineId {
 pub struct CachedPipeline {
     pub descriptor: PipelineDescriptor,
     pub state: CachedPipelineState,
+    /// The last pipeline created successfully, used while the pipeline is created again after
+    /// one of its shaders changed, and kept if that fails.
+    fallback: Option<Pipeline>,
 }
 
 /// State of a cached pipeline inserted into a [`PipelineCache`].
@@ -135,6 +139,10 @@ struct ShaderCache {
     import_path_shaders: HashMap<ShaderImport, AssetId<Shader>>,
     waiting_on_import: HashMap<ShaderImport, Vec<AssetId<Shader>>>,
     composer: naga_oil::compose::Composer,
+    /// The shader defs registered with [`ShaderApp::register_shader_def`].
+    registered_shader_defs: Vec<ShaderDefVal>,
+    /// The import paths registered with [`ShaderApp::register_virtual_shader`].
+    virtual_shaders: HashSet<ShaderImport>,
 }
 
 #[derive(Clone, PartialEq, Eq, Debug, Hash)]
@@ -156,7 +164,33 @@ impl From<String> for ShaderDefVal {
     }
 }
 
+impl From<(&str, bool)> for ShaderDefVal {
+    fn from((key, value): (&str, bool)) -> Self {
+        ShaderDefVal::Bool(key.to_string(), value)
+    }
+}
+
+impl From<(&str, i32)> for ShaderDefVal {
+    fn from((key, value): (&str, i32)) -> Self {
+        ShaderDefVal::Int(key.to_string(), value)
+    }
+}
+
+impl From<(&str, u32)> for ShaderDefVal {
+    fn from((key, value): (&str, u32)) -> Self {
+        ShaderDefVal::UInt(key.to_string(), value)
+    }
+}
+
 impl ShaderDefVal {
+    pub fn name(&self) -> &str {
+        match self {
+            ShaderDefVal::Bool(name, _)
+            | ShaderDefVal::Int(name, _)
+            | ShaderDefVal::UInt(name, _) => name,
+        }
+    }
+
     pub fn value_as_string(&self) -> String {
         match self {
             ShaderDefVal::Bool(_, def) => def.to_string(),
@@ -213,6 +247,8 @@ impl ShaderCache {
             shaders: Default::default(),
             import_path_shaders: Default::default(),
             waiting_on_import: Default::default(),
+            registered_shader_defs: Default::default(),
+            virtual_shaders: Default::default(),
         }
     }
 
@@ -275,7 +311,13 @@ impl ShaderCache {
         let module = match data.processed_shaders.entry(shader_defs.to_vec()) {
             Entry::Occupied(entry) => entry.into_mut(),
             Entry::Vacant(entry) => {
-                let mut shader_defs = shader_defs.to_vec();
+                // The defs of the pipeline are added last to take precedence.
+                let mut shader_defs: Vec<_> = self
+                    .registered_shader_defs
+                    .iter()
+                    .chain(shader_defs)
+                    .cloned()
+                    .collect();
                 #[cfg(all(feature = "webgl", target_arch = "wasm32", not(feature = "webgpu")))]
                 {
                     shader_defs.push("NO_ARRAY_TEXTURES_SUPPORT".into());
@@ -291,6 +333,13 @@ impl ShaderCache {
                     "processing shader {:?}, with shader defs {:?}",
                     id, shader_defs
                 );
+                Self::check_virtual_shader_defs(
+                    &self.shaders,
+                    &self.import_path_shaders,
+                    &self.virtual_shaders,
+                    shader,
+                    &shader_defs,
+                )?;
                 let shader_source = match &shader.source {
                     #[cfg(feature = "shader_format_spirv")]
                     Source::SpirV(data) => make_spirv(data),
@@ -365,6 +414,66 @@ impl ShaderCache {
         Ok(module.clone())
     }
 
+    /// Checks that the conditional directives of the virtual shaders used by `shader` only test
+    /// the shader defs that are set or registered.
+    #[allow(clippy::result_large_err)]
+    fn check_virtual_shader_defs(
+        shaders: &HashMap<AssetId<Shader>, Shader>,
+        import_path_shaders: &HashMap<ShaderImport, AssetId<Shader>>,
+        virtual_shaders: &HashSet<ShaderImport>,
+        shader: &Shader,
+        shader_defs: &[ShaderDefVal],
+    ) -> Result<(), PipelineCacheError> {
+        let mut visited = HashSet::new();
+        let mut stack = vec![shader];
+        while let Some(shader) = stack.pop() {
+            if !visited.insert(shader.import_path()) {
+                continue;
+            }
+            stack.extend(shader.imports().filter_map(|import| {
+                import_path_shaders
+                    .get(import)
+                    .and_then(|id| shaders.get(id))
+            }));
+            if !virtual_shaders.contains(shader.import_path()) {
+                continue;
+            }
+
+            let known = |name: &str| {
+                shader_defs
+                    .iter()
+                    .chain(&shader.shader_defs)
+                    .any(|def| def.name() == name)
+            };
+            if let Some((name, location)) =
+                find_unknown_shader_def(shader.source.as_str(), &shader.path, known)
+            {
+                return Err(PipelineCacheError::UnknownShaderDef { name, location });
+            }
+        }
+        Ok(())
+    }
+
+    /// Sets the registered shader defs and virtual shaders, returning the pipelines to queue
+    /// again if the shader defs changed.
+    fn set_registered_shaders(&mut self, registered: &RegisteredShaders) -> Vec<CachedPipelineId> {
+        self.virtual_shaders = registered
+            .virtual_shader_paths()
+            .map(|path| ShaderImport::Custom(path.to_string()))
+            .collect();
+        if self.registered_shader_defs == registered.shader_defs() {
+            return Vec::new();
+        }
+
+        self.registered_shader_defs = registered.shader_defs().to_vec();
+        let mut pipelines_to_queue = Vec::new();
+        for data in self.data.values_mut() {
+            data.processed_shaders.clear();
+            pipelines_to_queue.extend(data.pipelines.iter().copied());
+        }
+        pipelines_to_queue
+    }
+
     fn clear(&mut self, id: AssetId<Shader>) -> Vec<CachedPipelineId> {
         let mut shaders_to_clear = vec![id];
         let mut pipelines_to_queue = Vec::new();
@@ -480,6 +589,11 @@ pub struct PipelineCache {
     pipelines: Vec<CachedPipeline>,
     waiting_pipelines: HashSet<CachedPipelineId>,
     new_pipelines: Mutex<Vec<CachedPipeline>>,
+    failed_pipelines: HashSet<CachedPipelineId>,
+    error_events: Vec<PipelineErrorEvent>,
+    placeholders: HashMap<CachedPipelineId, CachedPipelineId>,
+    new_placeholders: Mutex<Vec<(CachedPipelineId, CachedPipelineId)>>,
+    max_creations_per_frame: Option<usize>,
 }
 
 impl PipelineCache {
@@ -496,6 +610,57 @@ impl PipelineCache {
             waiting_pipelines: default(),
             new_pipelines: default(),
             pipelines: default(),
+            failed_pipelines: default(),
+            error_events: default(),
+            placeholders: default(),
+            new_placeholders: default(),
+            max_creations_per_frame: if cfg!(target_arch = "wasm32") {
+                Some(1)
+            } else {
+                None
+            },
+        }
+    }
+
+    /// Returns the maximum number of pipelines whose creation starts each frame, or `None` if
+    /// unlimited.
+    pub fn max_creations_per_frame(&self) -> Option<usize> {
+        self.max_creations_per_frame
+    }
+
+    /// Sets the maximum number of pipelines whose creation starts each frame, or `None` for
+    /// unlimited.
+    ///
+    /// Pipelines are created synchronously on the web, so the frame waits for their creation:
+    /// wgpu doesn't expose the asynchronous pipeline creation of WebGPU, so it can't be awaited
+    /// in the background as on other platforms. The default of one per frame on the web spreads
+    /// the creation of the pipelines needed by new materials over several frames instead of
+    /// stuttering, while [placeholders](Self::set_render_pipeline_placeholder) can be drawn.
+    /// Elsewhere the default is unlimited. Pipelines are also created synchronously on macOS and
+    /// without the `multi-threaded` feature, where setting a limit can help too.
+    pub fn set_max_creations_per_frame(&mut self, max: Option<usize>) {
+        self.max_creations_per_frame = max;
+    }
+
+    /// Uses the render pipeline `placeholder` when the pipeline `id` isn't created yet.
+    ///
+    /// The placeholder must be compatible with the pipeline: it must use the same bind group
+    /// layouts and vertex buffer layouts, and the same render target formats, for example a
+    /// simpler specialization of the same material. Like [`queue_render_pipeline()`], this can be
+    /// called by the systems queuing the pipelines, and takes effect when the queue is processed.
+    /// The placeholder is dropped once the pipeline is created, or if its creation failed.
+    ///
+    /// [`queue_render_pipeline()`]: PipelineCache::queue_render_pipeline
+    pub fn set_render_pipeline_placeholder(
+        &self,
+        id: CachedRenderPipelineId,
+        placeholder: CachedRenderPipelineId,
+    ) {
+        if id != placeholder {
+            self.new_placeholders
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push((id.0, placeholder.0));
         }
     }
 
@@ -550,14 +715,19 @@ impl PipelineCache {
     /// This method returns a successfully created render pipeline if any, or `None` if the pipeline
     /// was not created yet or if there was an error during creation. You can check the actual creation
     /// state with [`PipelineCache::get_render_pipeline_state()`].
+    ///
+    /// While the pipeline is created again after one of its shaders changed, or if that failed,
+    /// the last render pipeline created successfully is returned. Before it is created for the
+    /// first time, its [placeholder](Self::set_render_pipeline_placeholder) is returned if any.
     #[inline]
     pub fn get_render_pipeline(&self, id: CachedRenderPipelineId) -> Option<&RenderPipeline> {
-        if let CachedPipelineState::Ok(Pipeline::RenderPipeline(pipeline)) =
-            &self.pipelines[id.0].state
-        {
-            Some(pipeline)
-        } else {
-            None
+        let pipeline = self.pipelines[id.0].pipeline().or_else(|| {
+            let placeholder = self.placeholders.get(&id.0)?;
+            self.pipelines.get(*placeholder)?.pipeline()
+        });
+        match pipeline? {
+            Pipeline::RenderPipeline(pipeline) => Some(pipeline),
+            Pipeline::ComputePipeline(_) => None,
         }
     }
 
@@ -568,6 +738,13 @@ impl PipelineCache {
             self.process_queue();
         }
 
+        // Start the creation even if the creation budget of the frame is spent
+        if let CachedPipelineState::Queued = self.pipelines[id.0].state {
+            let mut pipelines = mem::take(&mut self.pipelines);
+            self.process_pipeline(&mut pipelines[id.0], id.0);
+            self.pipelines = pipelines;
+        }
+
         let state = &mut self.pipelines[id.0].state;
         if let CachedPipelineState::Creating(task) = state {
             *state = match bevy_tasks::block_on(task) {
@@ -584,14 +761,14 @@ impl PipelineCache {
     /// This method returns a successfully created compute pipeline if any, or `None` if the pipeline
     /// was not created yet or if there was an error during creation. You can check the actual creation
     /// state with [`PipelineCache::get_compute_pipeline_state()`].
+    ///
+    /// While the pipeline is created again after one of its shaders changed, or if that failed,
+    /// the last compute pipeline created successfully is returned.
     #[inline]
     pub fn get_compute_pipeline(&self, id: CachedComputePipelineId) -> Option<&ComputePipeline> {
-        if let CachedPipelineState::Ok(Pipeline::ComputePipeline(pipeline)) =
-            &self.pipelines[id.0].state
-        {
-            Some(pipeline)
-        } else {
-            None
+        match self.pipelines[id.0].pipeline()? {
+            Pipeline::ComputePipeline(pipeline) => Some(pipeline),
+            Pipeline::RenderPipeline(_) => None,
         }
     }
 
@@ -620,6 +797,7 @@ impl PipelineCache {
         new_pipelines.push(CachedPipeline {
             descriptor: PipelineDescriptor::RenderPipelineDescriptor(Box::new(descriptor)),
             state: CachedPipelineState::Queued,
+            fallback: None,
         });
         id
     }
@@ -649,26 +827,49 @@ impl PipelineCache {
         new_pipelines.push(CachedPipeline {
             descriptor: PipelineDescriptor::ComputePipelineDescriptor(Box::new(descriptor)),
             state: CachedPipelineState::Queued,
+            fallback: None,
         });
         id
     }
 
     fn set_shader(&mut self, id: AssetId<Shader>, shader: &Shader) {
-        let mut shader_cache = self.shader_cache.lock().unwrap();
-        let pipelines_to_queue = shader_cache.set_shader(id, shader.clone());
+        let pipelines_to_queue = self
+            .shader_cache
+            .lock()
+            .unwrap()
+            .set_shader(id, shader.clone());
         for cached_pipeline in pipelines_to_queue {
-            self.pipelines[cached_pipeline].state = CachedPipelineState::Queued;
-            self.waiting_pipelines.insert(cached_pipeline);
+            self.requeue_pipeline(cached_pipeline);
         }
     }
 
     fn remove_shader(&mut self, shader: AssetId<Shader>) {
-        let mut shader_cache = self.shader_cache.lock().unwrap();
-        let pipelines_to_queue = shader_cache.remove(shader);
+        let pipelines_to_queue = self.shader_cache.lock().unwrap().remove(shader);
+        for cached_pipeline in pipelines_to_queue {
+            self.requeue_pipeline(cached_pipeline);
+        }
+    }
+
+    fn set_registered_shaders(&mut self, registered: &RegisteredShaders) {
+        let pipelines_to_queue = self
+            .shader_cache
+            .lock()
+            .unwrap()
+            .set_registered_shaders(registered);
         for cached_pipeline in pipelines_to_queue {
-            self.pipelines[cached_pipeline].state = CachedPipelineState::Queued;
-            self.waiting_pipelines.insert(cached_pipeline);
+            self.requeue_pipeline(cached_pipeline);
+        }
+    }
+
+    /// Queues the creation of a pipeline again, keeping the current one as a fallback until the
+    /// new one is created.
+    fn requeue_pipeline(&mut self, id: CachedPipelineId) {
+        let cached_pipeline = &mut self.pipelines[id];
+        let state = mem::replace(&mut cached_pipeline.state, CachedPipelineState::Queued);
+        if let CachedPipelineState::Ok(pipeline) = state {
+            cached_pipeline.fallback = Some(pipeline);
         }
+        self.waiting_pipelines.insert(id);
     }
 
     fn start_create_render_pipeline(
@@ -833,7 +1034,29 @@ impl PipelineCache {
             }
         }
 
+        for (id, placeholder) in self
+            .new_placeholders
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .drain(..)
+        {
+            if pipelines[id].pipeline().is_none() && !self.failed_pipelines.contains(&id) {
+                self.placeholders.insert(id, placeholder);
+            }
+        }
+
+        let mut creations = 0;
         for id in waiting_pipelines {
+            if let CachedPipelineState::Queued = pipelines[id].state {
+                if self
+                    .max_creations_per_frame
+                    .is_some_and(|max| creations >= max)
+                {
+                    self.waiting_pipelines.insert(id);
+                    continue;
+                }
+                creations += 1;
+            }
             self.process_pipeline(&mut pipelines[id], id);
         }
 
@@ -857,6 +1080,7 @@ impl PipelineCache {
                 match bevy_utils::futures::check_ready(task) {
                     Some(Ok(pipeline)) => {
                         cached_pipeline.state = CachedPipelineState::Ok(pipeline);
+                        self.pipeline_created(cached_pipeline, id);
                         return;
                     }
                     Some(Err(err)) => cached_pipeline.state = CachedPipelineState::Err(err),
@@ -874,23 +1098,79 @@ impl PipelineCache {
                     let error_detail =
                         err.emit_to_string(&self.shader_cache.lock().unwrap().composer);
                     error!("failed to process shader:\n{}", error_detail);
+                    let location = ShaderErrorLocation::from_diagnostic(&error_detail);
+                    self.pipeline_failed(cached_pipeline, id, location, error_detail);
+                    return;
+                }
+                PipelineCacheError::UnknownShaderDef { name, location } => {
+                    error!("unknown shader def `{}` at {}", name, location);
+                    let message = format!("unknown shader def `{name}`");
+                    let location = Some(location.clone());
+                    self.pipeline_failed(cached_pipeline, id, location, message);
                     return;
                 }
                 PipelineCacheError::CreateShaderModule(description) => {
                     error!("failed to create shader module: {}", description);
+                    let message = format!("failed to create shader module: {description}");
+                    self.pipeline_failed(cached_pipeline, id, None, message);
                     return;
                 }
             },
 
-            CachedPipelineState::Ok(_) => return,
+            CachedPipelineState::Ok(_) => {
+                self.pipeline_created(cached_pipeline, id);
+                return;
+            }
         }
 
         // Retry
         self.waiting_pipelines.insert(id);
     }
 
-    pub(crate) fn process_pipeline_queue_system(mut cache: ResMut<Self>) {
+    /// Drops the fallback of a pipeline that was created successfully, and reports it as fixed
+    /// if it previously failed.
+    fn pipeline_created(&mut self, cached_pipeline: &mut CachedPipeline, id: CachedPipelineId) {
+        cached_pipeline.fallback = None;
+        self.placeholders.remove(&id);
+        if self.failed_pipelines.remove(&id) {
+            self.error_events
+                .push(PipelineErrorEvent::Fixed { pipeline: id });
+        }
+    }
+
+    fn pipeline_failed(
+        &mut self,
+        cached_pipeline: &CachedPipeline,
+        id: CachedPipelineId,
+        location: Option<ShaderErrorLocation>,
+        message: String,
+    ) {
+        let label = match &cached_pipeline.descriptor {
+            PipelineDescriptor::RenderPipelineDescriptor(descriptor) => descriptor.label.clone(),
+            PipelineDescriptor::ComputePipelineDescriptor(descriptor) => descriptor.label.clone(),
+        };
+        self.failed_pipelines.insert(id);
+        self.placeholders.remove(&id);
+        self.error_events
+            .push(PipelineErrorEvent::Failed(PipelineError {
+                pipeline: id,
+                label,
+                location,
+                message,
+            }));
+    }
+
+    pub(crate) fn process_pipeline_queue_system(
+        mut cache: ResMut<Self>,
+        error_sender: Option<Res<PipelineErrorSender>>,
+    ) {
         cache.process_queue();
+        for event in cache.error_events.drain(..) {
+            if let Some(error_sender) = &error_sender {
+                // The receiver only disconnects when the main world is dropped.
+                let _ = error_sender.0.try_send(event);
+            }
+        }
     }
 
     pub(crate) fn extract_shaders(
@@ -915,6 +1195,25 @@ impl PipelineCache {
             }
         }
     }
+
+    pub(crate) fn extract_registered_shaders(
+        mut cache: ResMut<Self>,
+        registered: Extract<Res<RegisteredShaders>>,
+    ) {
+        if registered.is_changed() {
+            cache.set_registered_shaders(&registered);
+        }
+    }
+}
+
+impl CachedPipeline {
+    /// Returns the created pipeline, or the fallback while it is not available.
+    fn pipeline(&self) -> Option<&Pipeline> {
+        match &self.state {
+            CachedPipelineState::Ok(pipeline) => Some(pipeline),
+            _ => self.fallback.as_ref(),
+        }
+    }
 }
 
 fn create_pipeline_task(
@@ -951,4 +1250,132 @@ pub enum PipelineCacheError {
     ShaderImportNotYetAvailable,
     #[error("Could not create shader module: {0}")]
     CreateShaderModule(String),
+    #[error("Unknown shader def `{name}` at {location}")]
+    UnknownShaderDef {
+        name: String,
+        location: ShaderErrorLocation,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_asset::Handle;
+    use bevy_tasks::{AsyncComputeTaskPool, TaskPool};
+
+    use super::*;
+
+    const TEST_SHADER: Handle<Shader> = Handle::weak_from_u128(131430593470312865340523657814);
+
+    /// Creates a pipeline cache with the test shader, if there is an adapter to create pipelines.
+    fn pipeline_cache() -> Option<PipelineCache> {
+        AsyncComputeTaskPool::get_or_init(TaskPool::default);
+        let instance = wgpu::Instance::default();
+        let adapter = futures_lite::future::block_on(
+            instance.request_adapter(&wgpu::RequestAdapterOptions::default()),
+        )?;
+        let (device, _queue) =
+            futures_lite::future::block_on(adapter.request_device(&default(), None)).ok()?;
+        let mut cache = PipelineCache::new(RenderDevice::from(device));
+        cache.set_shader(
+            TEST_SHADER.id(),
+            &Shader::from_wgsl(
+                "
+                @vertex
+                fn vertex(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+                    return vec4<f32>(f32(index), 0.0, 0.0, 1.0);
+                }
+
+                @fragment
+                fn fragment() -> @location(0) vec4<f32> {
+                    return vec4<f32>(1.0);
+                }
+
+                @fragment
+                fn placeholder() -> @location(0) vec4<f32> {
+                    return vec4<f32>(0.5);
+                }
+                ",
+                "test.wgsl",
+            ),
+        );
+        Some(cache)
+    }
+
+    fn descriptor(fragment_entry_point: &'static str) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            label: None,
+            layout: vec![],
+            push_constant_ranges: vec![],
+            vertex: VertexState {
+                shader: TEST_SHADER,
+                shader_defs: vec![],
+                entry_point: "vertex".into(),
+                buffers: vec![],
+            },
+            primitive: default(),
+            depth_stencil: None,
+            multisample: default(),
+            fragment: Some(FragmentState {
+                shader: TEST_SHADER,
+                shader_defs: vec![],
+                entry_point: fragment_entry_point.into(),
+                targets: vec![Some(TextureFormat::Rgba8Unorm.into())],
+            }),
+        }
+    }
+
+    #[test]
+    fn pipeline_creation_is_throttled() {
+        let Some(mut cache) = pipeline_cache() else {
+            return;
+        };
+        cache.set_max_creations_per_frame(Some(1));
+        let ids: Vec<_> = (0..3)
+            .map(|_| cache.queue_render_pipeline(descriptor("fragment")))
+            .collect();
+
+        for started in 1..=ids.len() {
+            cache.process_queue();
+            let not_queued = ids
+                .iter()
+                .filter(|id| {
+                    !matches!(
+                        cache.get_render_pipeline_state(**id),
+                        CachedPipelineState::Queued
+                    )
+                })
+                .count();
+            assert_eq!(not_queued, started);
+        }
+    }
+
+    #[test]
+    fn placeholder_is_used_until_the_pipeline_is_created() {
+        let Some(mut cache) = pipeline_cache() else {
+            return;
+        };
+        let placeholder = cache.queue_render_pipeline(descriptor("placeholder"));
+        cache.block_on_render_pipeline(placeholder);
+        let placeholder_pipeline = cache.get_render_pipeline(placeholder).unwrap().id();
+
+        cache.set_max_creations_per_frame(Some(0));
+        let id = cache.queue_render_pipeline(descriptor("fragment"));
+        cache.set_render_pipeline_placeholder(id, placeholder);
+        cache.process_queue();
+        assert!(matches!(
+            cache.get_render_pipeline_state(id),
+            CachedPipelineState::Queued
+        ));
+        assert_eq!(
+            cache.get_render_pipeline(id).map(RenderPipeline::id),
+            Some(placeholder_pipeline)
+        );
+
+        cache.block_on_render_pipeline(id);
+        cache.process_queue();
+        let pipeline = cache.get_render_pipeline(id).unwrap().id();
+        assert_ne!(pipeline, placeholder_pipeline);
+        // The placeholder is dropped once the pipeline is created
+        assert!(cache.placeholders.is_empty());
+    }
 }
@@ -0,0 +1,152 @@
+use std::{borrow::Cow, collections::BTreeMap, fmt};
+
+use async_channel::{Receiver, Sender};
+use bevy_ecs::{
+    event::{Event, EventWriter},
+    system::{Res, ResMut, Resource},
+};
+
+/// The position in a shader file where a compilation error was found.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShaderErrorLocation {
+    /// The path of the shader, as loaded by the asset server.
+    pub path: String,
+    /// The line of the error, starting at 1.
+    pub line: usize,
+    /// The column of the error, starting at 1.
+    pub column: usize,
+}
+
+impl fmt::Display for ShaderErrorLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.path, self.line, self.column)
+    }
+}
+
+impl ShaderErrorLocation {
+    /// Finds the location of the first label of a diagnostic emitted by the shader composer,
+    /// such as `┌─ shaders/custom_material.wgsl:12:5`.
+    pub(crate) fn from_diagnostic(diagnostic: &str) -> Option<Self> {
+        let location = diagnostic
+            .lines()
+            .find_map(|line| line.trim_start().strip_prefix("┌─ "))?;
+        let mut parts = location.trim_end().rsplitn(3, ':');
+        let column = parts.next()?.parse().ok()?;
+        let line = parts.next()?.parse().ok()?;
+        let path = parts.next()?.to_string();
+        Some(Self { path, line, column })
+    }
+}
+
+/// A pipeline of the [`PipelineCache`](super::PipelineCache) that could not be created because of
+/// an error in one of its shaders.
+///
+/// While the error isn't fixed, the last version of the pipeline created successfully, if any,
+/// is still used for rendering.
+#[derive(Clone, Debug)]
+pub struct PipelineError {
+    /// The index of the pipeline in the [`PipelineCache`](super::PipelineCache).
+    pub pipeline: usize,
+    /// The label of the pipeline descriptor.
+    pub label: Option<Cow<'static, str>>,
+    /// Where the error was found, if known.
+    pub location: Option<ShaderErrorLocation>,
+    /// The full error message.
+    pub message: String,
+}
+
+/// Sent in the main world when the creation of a pipeline fails, or succeeds after failing.
+///
+/// Pipelines are created again when one of their shaders is modified, so fixing a hot-reloaded
+/// shader sends [`PipelineErrorEvent::Fixed`] for the pipelines it broke.
+#[derive(Event, Clone, Debug)]
+pub enum PipelineErrorEvent {
+    /// The pipeline could not be created.
+    Failed(PipelineError),
+    /// The pipeline was created successfully after failing.
+    Fixed {
+        /// The index of the pipeline in the [`PipelineCache`](super::PipelineCache).
+        pipeline: usize,
+    },
+}
+
+/// The pipelines that currently fail to be created, in the main world.
+#[derive(Resource, Default, Debug)]
+pub struct PipelineErrors {
+    errors: BTreeMap<usize, PipelineError>,
+}
+
+impl PipelineErrors {
+    /// Returns an iterator over the errors, by pipeline index.
+    pub fn iter(&self) -> impl Iterator<Item = &PipelineError> {
+        self.errors.values()
+    }
+
+    /// Returns `true` if all pipelines were created successfully.
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    fn apply(&mut self, event: &PipelineErrorEvent) {
+        match event {
+            PipelineErrorEvent::Failed(error) => {
+                self.errors.insert(error.pipeline, error.clone());
+            }
+            PipelineErrorEvent::Fixed { pipeline } => {
+                self.errors.remove(pipeline);
+            }
+        }
+    }
+}
+
+/// Sends the [`PipelineErrorEvent`]s of the render world to the main world.
+#[derive(Resource)]
+pub(crate) struct PipelineErrorSender(pub Sender<PipelineErrorEvent>);
+
+/// Receives the [`PipelineErrorEvent`]s of the render world in the main world.
+#[derive(Resource)]
+pub(crate) struct PipelineErrorReceiver(pub Receiver<PipelineErrorEvent>);
+
+pub(crate) fn create_pipeline_error_channels() -> (PipelineErrorSender, PipelineErrorReceiver) {
+    let (sender, receiver) = async_channel::unbounded();
+    (PipelineErrorSender(sender), PipelineErrorReceiver(receiver))
+}
+
+/// Sends the [`PipelineErrorEvent`]s received from the render world, and updates the
+/// [`PipelineErrors`].
+pub(crate) fn receive_pipeline_errors(
+    receiver: Res<PipelineErrorReceiver>,
+    mut errors: ResMut<PipelineErrors>,
+    mut events: EventWriter<PipelineErrorEvent>,
+) {
+    while let Ok(event) = receiver.0.try_recv() {
+        errors.apply(&event);
+        events.send(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn location_from_diagnostic() {
+        let diagnostic = "error: expected ';', found '}'\n   \
+                          ┌─ shaders/custom_material.wgsl:12:5\n   \
+                          │\n\
+                          12 │     }\n   \
+                          │     ^ expected ';'\n";
+        assert_eq!(
+            ShaderErrorLocation::from_diagnostic(diagnostic),
+            Some(ShaderErrorLocation {
+                path: "shaders/custom_material.wgsl".to_string(),
+                line: 12,
+                column: 5,
+            })
+        );
+        assert_eq!(
+            ShaderErrorLocation::from_diagnostic("Could not create shader module"),
+            None
+        );
+    }
+}
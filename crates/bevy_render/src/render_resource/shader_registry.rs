@@ -0,0 +1,194 @@
+use std::borrow::Cow;
+
+use bevy_app::App;
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::system::Resource;
+use bevy_utils::{tracing::warn, HashMap};
+
+use super::{Shader, ShaderDefVal, ShaderErrorLocation, Source};
+
+/// The shader defs and virtual shaders registered with [`ShaderApp`].
+#[derive(Resource, Clone, Debug, Default)]
+pub struct RegisteredShaders {
+    shader_defs: Vec<ShaderDefVal>,
+    virtual_shaders: HashMap<String, Handle<Shader>>,
+}
+
+impl RegisteredShaders {
+    /// Returns the shader defs added to every shader compiled by the
+    /// [`PipelineCache`](super::PipelineCache).
+    pub fn shader_defs(&self) -> &[ShaderDefVal] {
+        &self.shader_defs
+    }
+
+    /// Returns the handle of the shader registered at the virtual `import_path`.
+    pub fn virtual_shader(&self, import_path: &str) -> Option<&Handle<Shader>> {
+        self.virtual_shaders.get(import_path)
+    }
+
+    /// Returns an iterator over the import paths of the virtual shaders.
+    pub fn virtual_shader_paths(&self) -> impl Iterator<Item = &str> {
+        self.virtual_shaders.keys().map(String::as_str)
+    }
+
+    /// Adds `shader_def` to every shader, replacing the registered def with the same name.
+    pub fn set_shader_def(&mut self, shader_def: ShaderDefVal) {
+        match self
+            .shader_defs
+            .iter_mut()
+            .find(|def| def.name() == shader_def.name())
+        {
+            Some(def) => *def = shader_def,
+            None => self.shader_defs.push(shader_def),
+        }
+    }
+}
+
+/// Adds shader registration to [`App`], so that plugins can share shader code and configure
+/// the shaders of other plugins.
+pub trait ShaderApp {
+    /// Adds `shader_def` to every shader compiled by the [`PipelineCache`](super::PipelineCache),
+    /// replacing the def with the same name if it was already registered.
+    ///
+    /// The defs of the pipeline descriptors take precedence over registered defs. Registering a
+    /// def also declares it, so that the virtual shaders testing it with `#ifdef` or `#if` are
+    /// checked against it: register a [`ShaderDefVal::Bool`] set to `false` to declare a def
+    /// that is only enabled by some pipelines.
+    ///
+    /// ```
+    /// # use bevy_app::App;
+    /// # use bevy_render::render_resource::{ShaderApp, ShaderDefVal};
+    /// # let mut app = App::new();
+    /// app.register_shader_def(("MAX_DECALS", 64u32))
+    ///     .register_shader_def(("DECAL_FADE", false));
+    /// ```
+    fn register_shader_def(&mut self, shader_def: impl Into<ShaderDefVal>) -> &mut Self;
+
+    /// Registers the WGSL `source` at the virtual `import_path`, so that other shaders can
+    /// `#import` it without it being an asset file.
+    ///
+    /// Registering the same path again is ignored, so that several plugins can register a
+    /// shared library of shader functions. A warning is logged if the sources differ.
+    ///
+    /// The conditional directives of virtual shaders are checked when compiling a pipeline:
+    /// testing a shader def that is neither registered nor set by the pipeline fails with
+    /// [`PipelineCacheError::UnknownShaderDef`](super::PipelineCacheError::UnknownShaderDef)
+    /// instead of silently skipping the code.
+    fn register_virtual_shader(
+        &mut self,
+        import_path: impl Into<String>,
+        source: impl Into<Cow<'static, str>>,
+    ) -> &mut Self;
+}
+
+impl ShaderApp for App {
+    fn register_shader_def(&mut self, shader_def: impl Into<ShaderDefVal>) -> &mut Self {
+        self.world
+            .get_resource_or_insert_with(RegisteredShaders::default)
+            .set_shader_def(shader_def.into());
+        self
+    }
+
+    fn register_virtual_shader(
+        &mut self,
+        import_path: impl Into<String>,
+        source: impl Into<Cow<'static, str>>,
+    ) -> &mut Self {
+        let import_path = import_path.into();
+        let source = source.into();
+        let registered = self
+            .world
+            .get_resource_or_insert_with(RegisteredShaders::default)
+            .virtual_shader(&import_path)
+            .cloned();
+        let mut shaders = self.world.get_resource_mut::<Assets<Shader>>().expect(
+            "Assets<Shader> not found. Make sure to register virtual shaders after the RenderPlugin",
+        );
+        if let Some(handle) = registered {
+            if shaders.get(&handle).is_some_and(|shader| {
+                !matches!(&shader.source, Source::Wgsl(registered) if *registered == source)
+            }) {
+                warn!("The virtual shader `{import_path}` was registered again with a different source, which is ignored");
+            }
+            return self;
+        }
+
+        let handle = shaders
+            .add(Shader::from_wgsl(source, import_path.clone()).with_import_path(&import_path));
+        self.world
+            .resource_mut::<RegisteredShaders>()
+            .virtual_shaders
+            .insert(import_path, handle);
+        self
+    }
+}
+
+/// Returns the first shader def tested by a conditional directive of `source` for which `known`
+/// returns `false`, with its location in the shader at `path`.
+pub(crate) fn find_unknown_shader_def(
+    source: &str,
+    path: &str,
+    known: impl Fn(&str) -> bool,
+) -> Option<(String, ShaderErrorLocation)> {
+    for (index, line) in source.lines().enumerate() {
+        let directive = line.trim_start();
+        let Some(directive) = directive.strip_prefix('#') else {
+            continue;
+        };
+        let directive = directive
+            .strip_prefix("else")
+            .map_or(directive, str::trim_start);
+        let Some(rest) = ["ifdef", "ifndef", "if"]
+            .iter()
+            .find_map(|keyword| directive.strip_prefix(keyword))
+            .filter(|rest| rest.starts_with(char::is_whitespace))
+        else {
+            continue;
+        };
+
+        let rest = rest.trim_start();
+        let name_length = rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+        let name = &rest[..name_length];
+        if name.is_empty() || known(name) {
+            continue;
+        }
+        return Some((
+            name.to_string(),
+            ShaderErrorLocation {
+                path: path.to_string(),
+                line: index + 1,
+                column: line.len() - rest.len() + 1,
+            },
+        ));
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_shader_defs_are_located() {
+        let source = "#define_import_path my_crate::lighting\n\
+                      #ifdef SHADOWS\n\
+                      #else ifdef SOFT_SHADOWS\n\
+                      #endif\n  \
+                      #if MAX_LIGHTS > 4\n\
+                      #endif\n";
+        let known = |name: &str| name == "SHADOWS" || name == "SOFT_SHADOWS";
+        let (name, location) = find_unknown_shader_def(source, "lighting.wgsl", known).unwrap();
+        assert_eq!(name, "MAX_LIGHTS");
+        assert_eq!(
+            location,
+            ShaderErrorLocation {
+                path: "lighting.wgsl".to_string(),
+                line: 5,
+                column: 7,
+            }
+        );
+        assert!(find_unknown_shader_def(source, "lighting.wgsl", |_| true).is_none());
+    }
+}
@@ -1,7 +1,7 @@
 use crate::define_atomic_id;
-use std::ops::Deref;
+use std::{ops::Deref, sync::Arc};
 
-use crate::render_resource::resource_macros::*;
+use crate::render_resource::{allocation::TrackedAllocation, resource_macros::*};
 
 define_atomic_id!(TextureId);
 render_resource_wrapper!(ErasedTexture, wgpu::Texture);
@@ -14,6 +14,7 @@ render_resource_wrapper!(ErasedTexture, wgpu::Texture);
 pub struct Texture {
     id: TextureId,
     value: ErasedTexture,
+    _allocation: Arc<TrackedAllocation>,
 }
 
 impl Texture {
@@ -33,6 +34,7 @@ impl From<wgpu::Texture> for Texture {
     fn from(value: wgpu::Texture) -> Self {
         Texture {
             id: TextureId::new(),
+            _allocation: Arc::new(TrackedAllocation::texture(&value)),
             value: ErasedTexture::new(value),
         }
     }
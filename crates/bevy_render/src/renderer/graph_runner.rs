@@ -10,6 +10,7 @@ use std::{borrow::Cow, collections::VecDeque};
 use thiserror::Error;
 
 use crate::{
+    diagnostic::GpuTimestamps,
     render_graph::{
         Edge, InternedRenderLabel, InternedRenderSubGraph, NodeRunError, NodeState, RenderGraph,
         RenderGraphContext, SlotLabel, SlotType, SlotValue,
@@ -59,9 +60,11 @@ impl RenderGraphRunner {
         queue: &wgpu::Queue,
         adapter: &wgpu::Adapter,
         world: &World,
+        gpu_timestamps: Option<GpuTimestamps>,
         finalizer: impl FnOnce(&mut wgpu::CommandEncoder),
     ) -> Result<(), RenderGraphRunnerError> {
         let mut render_context = RenderContext::new(render_device, adapter.get_info());
+        render_context.set_gpu_timestamps(gpu_timestamps);
         Self::run_graph(graph, None, &mut render_context, world, &[], None)?;
         finalizer(render_context.command_encoder());
 
@@ -193,7 +196,19 @@ impl RenderGraphRunner {
                     #[cfg(feature = "trace")]
                     let _span = info_span!("node", name = node_state.type_name).entered();
 
+                    let gpu_timestamps = render_context.gpu_timestamps().cloned();
+                    let gpu_span = gpu_timestamps.as_ref().and_then(|gpu_timestamps| {
+                        gpu_timestamps.begin_encoder_span(
+                            render_context.command_encoder(),
+                            format!("node/{:?}", node_state.label),
+                        )
+                    });
+
                     node_state.node.run(&mut context, render_context, world)?;
+
+                    if let (Some(gpu_timestamps), Some(gpu_span)) = (gpu_timestamps, gpu_span) {
+                        gpu_timestamps.end_encoder_span(render_context.command_encoder(), gpu_span);
+                    }
                 }
 
                 for run_sub_graph in context.finish() {
@@ -8,6 +8,7 @@ pub use graph_runner::*;
 pub use render_device::*;
 
 use crate::{
+    diagnostic::{DiagnosticsRecorder, GpuTimestamps},
     render_graph::RenderGraph,
     render_phase::TrackedRenderPass,
     render_resource::RenderPassDescriptor,
@@ -19,14 +20,22 @@ use bevy_time::TimeSender;
 use bevy_utils::Instant;
 use std::sync::Arc;
 use wgpu::{
-    Adapter, AdapterInfo, CommandBuffer, CommandEncoder, Instance, Queue, RequestAdapterOptions,
+    Adapter, AdapterInfo, CommandBuffer, CommandEncoder, ComputePass, ComputePassDescriptor,
+    Instance, Queue, RequestAdapterOptions,
 };
 
 /// Updates the [`RenderGraph`] with all of its nodes and then runs it to render the entire frame.
 pub fn render_system(world: &mut World, state: &mut SystemState<Query<Entity, With<ViewTarget>>>) {
     world.resource_scope(|world, mut graph: Mut<RenderGraph>| {
-        graph.update(world);
+        // Updating the nodes doesn't change the structure of the graph, so don't mark it as
+        // changed; systems like `update_texture_cache_system` rely on that to skip work.
+        graph.bypass_change_detection().update(world);
     });
+    let mut diagnostics_recorder = world.remove_resource::<DiagnosticsRecorder>();
+    let gpu_timestamps = diagnostics_recorder
+        .as_mut()
+        .and_then(DiagnosticsRecorder::begin_frame);
+
     let graph = world.resource::<RenderGraph>();
     let render_device = world.resource::<RenderDevice>();
     let render_queue = world.resource::<RenderQueue>();
@@ -38,8 +47,12 @@ pub fn render_system(world: &mut World, state: &mut SystemState<Query<Entity, Wi
         &render_queue.0,
         &render_adapter.0,
         world,
+        gpu_timestamps,
         |encoder| {
             crate::view::screenshot::submit_screenshot_commands(world, encoder);
+            if let Some(recorder) = &diagnostics_recorder {
+                recorder.resolve(encoder);
+            }
         },
     ) {
         error!("Error running render graph:");
@@ -57,6 +70,11 @@ pub fn render_system(world: &mut World, state: &mut SystemState<Query<Entity, Wi
         panic!("Error running render graph: {e}");
     }
 
+    if let Some(mut diagnostics_recorder) = diagnostics_recorder {
+        diagnostics_recorder.finish_frame();
+        world.insert_resource(diagnostics_recorder);
+    }
+
     {
         let _span = info_span!("present_frames").entered();
 
@@ -306,6 +324,7 @@ pub struct RenderContext<'w> {
     command_encoder: Option<CommandEncoder>,
     command_buffer_queue: Vec<QueuedCommandBuffer<'w>>,
     force_serial: bool,
+    gpu_timestamps: Option<GpuTimestamps>,
 }
 
 impl<'w> RenderContext<'w> {
@@ -326,9 +345,23 @@ impl<'w> RenderContext<'w> {
             command_encoder: None,
             command_buffer_queue: Vec::new(),
             force_serial,
+            gpu_timestamps: None,
         }
     }
 
+    pub(crate) fn set_gpu_timestamps(&mut self, gpu_timestamps: Option<GpuTimestamps>) {
+        self.gpu_timestamps = gpu_timestamps;
+    }
+
+    /// Gets the handle used to measure the GPU time of passes this frame.
+    ///
+    /// This is `None` unless the [`RenderDiagnosticsPlugin`](crate::diagnostic::RenderDiagnosticsPlugin)
+    /// was added and the device supports timestamp queries.
+    /// Clone it into command buffer generation tasks to measure the passes they record.
+    pub fn gpu_timestamps(&self) -> Option<&GpuTimestamps> {
+        self.gpu_timestamps.as_ref()
+    }
+
     /// Gets the underlying [`RenderDevice`].
     pub fn render_device(&self) -> &RenderDevice {
         &self.render_device
@@ -344,10 +377,18 @@ impl<'w> RenderContext<'w> {
 
     /// Creates a new [`TrackedRenderPass`] for the context,
     /// configured using the provided `descriptor`.
+    ///
+    /// If the pass is labeled and GPU timing diagnostics are enabled,
+    /// the GPU time spent in the pass is measured under its label.
     pub fn begin_tracked_render_pass<'a>(
         &'a mut self,
-        descriptor: RenderPassDescriptor<'a, '_>,
+        mut descriptor: RenderPassDescriptor<'a, '_>,
     ) -> TrackedRenderPass<'a> {
+        if descriptor.timestamp_writes.is_none() {
+            if let (Some(gpu_timestamps), Some(label)) = (&self.gpu_timestamps, descriptor.label) {
+                descriptor.timestamp_writes = gpu_timestamps.render_pass_writes(label.to_owned());
+            }
+        }
         // Cannot use command_encoder() as we need to split the borrow on self
         let command_encoder = self.command_encoder.get_or_insert_with(|| {
             self.render_device
@@ -357,6 +398,27 @@ impl<'w> RenderContext<'w> {
         TrackedRenderPass::new(&self.render_device, render_pass)
     }
 
+    /// Creates a new [`ComputePass`] for the context,
+    /// configured using the provided `descriptor`.
+    ///
+    /// If the pass is labeled and GPU timing diagnostics are enabled,
+    /// the GPU time spent in the pass is measured under its label.
+    pub fn begin_compute_pass<'a>(
+        &'a mut self,
+        mut descriptor: ComputePassDescriptor<'a>,
+    ) -> ComputePass<'a> {
+        if descriptor.timestamp_writes.is_none() {
+            if let (Some(gpu_timestamps), Some(label)) = (&self.gpu_timestamps, descriptor.label) {
+                descriptor.timestamp_writes = gpu_timestamps.compute_pass_writes(label.to_owned());
+            }
+        }
+        let command_encoder = self.command_encoder.get_or_insert_with(|| {
+            self.render_device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default())
+        });
+        command_encoder.begin_compute_pass(&descriptor)
+    }
+
     /// Append a [`CommandBuffer`] to the command buffer queue.
     ///
     /// If present, this will flush the currently unflushed [`CommandEncoder`]
@@ -54,7 +54,7 @@ impl AssetSaver for CompressedImageSaver {
             writer.write_all(&compressed_basis_data).await?;
             Ok(ImageLoaderSettings {
                 format: ImageFormatSetting::Format(ImageFormat::Basis),
-                is_srgb,
+                color_space: is_srgb.into(),
                 sampler: image.sampler.clone(),
                 asset_usage: image.asset_usage,
             })
@@ -143,7 +143,7 @@ impl ImageSampler {
 /// initialization.
 ///
 /// The [`ImagePlugin`](super::ImagePlugin) can be set during app initialization to change the default
-/// image sampler.
+/// image sampler, and the [`ImageSettings`](super::ImageSettings) resource at runtime.
 #[derive(Resource, Debug, Clone, Deref, DerefMut)]
 pub struct DefaultImageSampler(pub(crate) Sampler);
 
@@ -1,3 +1,5 @@
+use std::path::Path;
+
 use bevy_asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext};
 use bevy_ecs::prelude::{FromWorld, World};
 use thiserror::Error;
@@ -53,10 +55,98 @@ pub enum ImageFormatSetting {
     Format(ImageFormat),
 }
 
+/// The color space of the color data stored in an image file.
+///
+/// Textures holding colors, like base color and emissive textures, are usually sRGB encoded,
+/// while textures holding other data, like normal maps or packed occlusion, roughness and
+/// metallic (ORM) maps, must be linear. Loading a data texture as sRGB makes materials look
+/// washed out or lit incorrectly.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageColorSpace {
+    /// Detects the color space from the file name, see [`ImageColorSpace::detect`].
+    #[default]
+    Auto,
+    /// The image is sRGB encoded.
+    Srgb,
+    /// The image is linear.
+    Linear,
+}
+
+/// Words in file names that identify textures holding non-color data.
+const LINEAR_TEXTURE_NAMES: &[&str] = &[
+    "normal",
+    "normals",
+    "normalmap",
+    "nrm",
+    "orm",
+    "arm",
+    "rma",
+    "roughness",
+    "rough",
+    "metallic",
+    "metalness",
+    "metal",
+    "occlusion",
+    "ao",
+    "height",
+    "displacement",
+    "disp",
+    "bump",
+    "depth",
+    "mask",
+];
+
+impl ImageColorSpace {
+    /// Detects the color space of an image from its file name.
+    ///
+    /// Images with a name containing a word that identifies non-color data, like
+    /// `brick_normal.png`, `rock-ORM.ktx2` or `ground_roughness_2k.jpg`, are [`ImageColorSpace::Linear`],
+    /// and every other image is [`ImageColorSpace::Srgb`].
+    pub fn detect(path: &Path) -> ImageColorSpace {
+        let Some(name) = path.file_stem().and_then(|name| name.to_str()) else {
+            return ImageColorSpace::Srgb;
+        };
+        let is_linear = name
+            .split(|c: char| c == '_' || c == '-' || c == '.' || c == ' ')
+            .any(|word| {
+                LINEAR_TEXTURE_NAMES
+                    .iter()
+                    .any(|linear| word.eq_ignore_ascii_case(linear))
+            });
+        if is_linear {
+            ImageColorSpace::Linear
+        } else {
+            ImageColorSpace::Srgb
+        }
+    }
+
+    /// Returns `true` if an image at `path` with this color space is sRGB encoded.
+    pub fn is_srgb(self, path: &Path) -> bool {
+        match self {
+            ImageColorSpace::Auto => ImageColorSpace::detect(path) == ImageColorSpace::Srgb,
+            ImageColorSpace::Srgb => true,
+            ImageColorSpace::Linear => false,
+        }
+    }
+}
+
+impl From<bool> for ImageColorSpace {
+    /// Converts an `is_srgb` flag to a color space.
+    fn from(is_srgb: bool) -> Self {
+        if is_srgb {
+            ImageColorSpace::Srgb
+        } else {
+            ImageColorSpace::Linear
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ImageLoaderSettings {
     pub format: ImageFormatSetting,
-    pub is_srgb: bool,
+    /// The color space of the image. Can be overridden in the image's `.meta` file when the
+    /// file name doesn't match the contents, with `color_space: Linear` or `color_space: Srgb`.
+    pub color_space: ImageColorSpace,
     pub sampler: ImageSampler,
     pub asset_usage: RenderAssetUsages,
 }
@@ -65,7 +155,7 @@ impl Default for ImageLoaderSettings {
     fn default() -> Self {
         Self {
             format: ImageFormatSetting::default(),
-            is_srgb: true,
+            color_space: ImageColorSpace::default(),
             sampler: ImageSampler::Default,
             asset_usage: RenderAssetUsages::default(),
         }
@@ -107,7 +197,7 @@ impl AssetLoader for ImageLoader {
                 &bytes,
                 image_type,
                 self.supported_compressed_formats,
-                settings.is_srgb,
+                settings.color_space.is_srgb(load_context.path()),
                 settings.sampler.clone(),
                 settings.asset_usage,
             )
@@ -151,3 +241,26 @@ impl std::fmt::Display for FileTextureError {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::ImageColorSpace;
+
+    #[test]
+    fn detect_color_space() {
+        for (path, expected) in [
+            ("textures/brick_normal.png", ImageColorSpace::Linear),
+            ("rock-ORM.ktx2", ImageColorSpace::Linear),
+            ("ground_Roughness_2k.jpg", ImageColorSpace::Linear),
+            ("brick_albedo.png", ImageColorSpace::Srgb),
+            ("abnormal.png", ImageColorSpace::Srgb),
+            ("branding/icon.png", ImageColorSpace::Srgb),
+        ] {
+            assert_eq!(ImageColorSpace::detect(Path::new(path)), expected, "{path}");
+        }
+        assert!(!ImageColorSpace::Linear.is_srgb(Path::new("brick_albedo.png")));
+        assert!(ImageColorSpace::Srgb.is_srgb(Path::new("brick_normal.png")));
+    }
+}
@@ -37,19 +37,44 @@ pub use texture_attachment::*;
 pub use texture_cache::*;
 
 use crate::{
-    render_asset::RenderAssetPlugin, renderer::RenderDevice, Render, RenderApp, RenderSet,
+    extract_resource::{ExtractResource, ExtractResourcePlugin},
+    render_asset::{prepare_assets, RenderAssetPlugin},
+    renderer::RenderDevice,
+    Render, RenderApp, RenderSet,
 };
-use bevy_app::{App, Plugin};
+use bevy_app::{App, Plugin, PostUpdate};
 use bevy_asset::{AssetApp, Assets, Handle};
 use bevy_ecs::prelude::*;
 
 // TODO: replace Texture names with Image names?
 /// Adds the [`Image`] as an asset and makes sure that they are extracted and prepared for the GPU.
+///
+/// The fields of the plugin are the initial value of the [`ImageSettings`] resource, which can
+/// be changed after the plugin is added.
 pub struct ImagePlugin {
     /// The default image sampler to use when [`ImageSampler`] is set to `Default`.
     pub default_sampler: ImageSamplerDescriptor,
 }
 
+/// The settings of the [`ImagePlugin`].
+///
+/// Inserted by the plugin from its fields, unless it already exists. It can be changed before the
+/// plugin is finished, and at runtime: changing the default sampler updates the images using
+/// [`ImageSampler::Default`] that are still in the main world, and the images prepared afterwards.
+#[derive(Resource, ExtractResource, Clone, Debug)]
+pub struct ImageSettings {
+    /// The default image sampler to use when [`ImageSampler`] is set to `Default`.
+    pub default_sampler: ImageSamplerDescriptor,
+}
+
+impl From<&ImagePlugin> for ImageSettings {
+    fn from(plugin: &ImagePlugin) -> Self {
+        Self {
+            default_sampler: plugin.default_sampler.clone(),
+        }
+    }
+}
+
 impl Default for ImagePlugin {
     fn default() -> Self {
         ImagePlugin::default_linear()
@@ -84,10 +109,18 @@ impl Plugin for ImagePlugin {
             app.init_asset_loader::<HdrTextureLoader>();
         }
 
-        app.add_plugins(RenderAssetPlugin::<Image>::default())
-            .register_type::<Image>()
-            .init_asset::<Image>()
-            .register_asset_reflect::<Image>();
+        if !app.world.contains_resource::<ImageSettings>() {
+            app.insert_resource(ImageSettings::from(self));
+        }
+
+        app.add_plugins((
+            RenderAssetPlugin::<Image>::default(),
+            ExtractResourcePlugin::<ImageSettings>::default(),
+        ))
+        .add_systems(PostUpdate, update_default_sampled_images)
+        .register_type::<Image>()
+        .init_asset::<Image>()
+        .register_asset_reflect::<Image>();
         app.world
             .resource_mut::<Assets<Image>>()
             .insert(Handle::default(), Image::default());
@@ -106,7 +139,12 @@ impl Plugin for ImagePlugin {
         if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app.init_resource::<TextureCache>().add_systems(
                 Render,
-                update_texture_cache_system.in_set(RenderSet::Cleanup),
+                (
+                    update_texture_cache_system.in_set(RenderSet::Cleanup),
+                    update_default_image_sampler
+                        .in_set(RenderSet::PrepareAssets)
+                        .before(prepare_assets::<Image>),
+                ),
             );
         }
 
@@ -136,10 +174,11 @@ impl Plugin for ImagePlugin {
             app.init_asset_loader::<ImageLoader>();
         }
 
+        let settings = app.world.resource::<ImageSettings>().clone();
         if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
             let default_sampler = {
                 let device = render_app.world.resource::<RenderDevice>();
-                device.create_sampler(&self.default_sampler.as_wgpu())
+                device.create_sampler(&settings.default_sampler.as_wgpu())
             };
             render_app
                 .insert_resource(DefaultImageSampler(default_sampler))
@@ -151,6 +190,36 @@ impl Plugin for ImagePlugin {
     }
 }
 
+/// Marks the images using [`ImageSampler::Default`] as modified when the default sampler of the
+/// [`ImageSettings`] changes, so that they are prepared again with the new sampler.
+fn update_default_sampled_images(settings: Res<ImageSettings>, mut images: ResMut<Assets<Image>>) {
+    if !settings.is_changed() || settings.is_added() {
+        return;
+    }
+    let ids: Vec<_> = images
+        .iter()
+        .filter(|(_, image)| matches!(image.sampler, ImageSampler::Default))
+        .map(|(id, _)| id)
+        .collect();
+    for id in ids {
+        images.get_mut(id);
+    }
+}
+
+/// Recreates the [`DefaultImageSampler`] when the default sampler of the [`ImageSettings`] changes.
+fn update_default_image_sampler(
+    settings: Option<Res<ImageSettings>>,
+    device: Res<RenderDevice>,
+    mut default_sampler: ResMut<DefaultImageSampler>,
+) {
+    let Some(settings) = settings else {
+        return;
+    };
+    if settings.is_changed() {
+        default_sampler.0 = device.create_sampler(&settings.default_sampler.as_wgpu());
+    }
+}
+
 pub trait BevyDefault {
     fn bevy_default() -> Self;
 }
@@ -160,3 +229,43 @@ impl BevyDefault for wgpu::TextureFormat {
         wgpu::TextureFormat::Rgba8UnormSrgb
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy_asset::AssetEvent;
+
+    use super::*;
+
+    #[test]
+    fn changing_default_sampler_modifies_default_sampled_images() {
+        let mut world = World::new();
+        world.init_resource::<Events<AssetEvent<Image>>>();
+        world.init_resource::<Assets<Image>>();
+        world.insert_resource(ImageSettings {
+            default_sampler: ImageSamplerDescriptor::linear(),
+        });
+        let mut images = world.resource_mut::<Assets<Image>>();
+        let default_sampled = images.add(Image::default());
+        let nearest = images.add(Image {
+            sampler: ImageSampler::nearest(),
+            ..Image::default()
+        });
+
+        let mut schedule = Schedule::default();
+        schedule
+            .add_systems((update_default_sampled_images, Assets::<Image>::asset_events).chain());
+        schedule.run(&mut world);
+        world.resource_mut::<Events<AssetEvent<Image>>>().update();
+
+        world.resource_mut::<ImageSettings>().default_sampler = ImageSamplerDescriptor::nearest();
+        schedule.run(&mut world);
+
+        let events = world.resource::<Events<AssetEvent<Image>>>();
+        assert!(events
+            .iter_current_update_events()
+            .any(|event| event.is_modified(&default_sampled)));
+        assert!(!events
+            .iter_current_update_events()
+            .any(|event| event.is_modified(&nearest)));
+    }
+}
@@ -82,22 +82,47 @@ impl ColorAttachment {
     }
 }
 
-/// A wrapper for a [`TextureView`] that is used as a depth-only [`RenderPassDepthStencilAttachment`].
+/// A wrapper for a [`TextureView`] that is used as a [`RenderPassDepthStencilAttachment`].
+///
+/// Only the depth aspect is used, unless [`DepthAttachment::with_stencil`] is called.
+///
+/// Clones share whether the attachment was cleared, so that render passes drawing to different
+/// regions of the same texture only clear it once.
+#[derive(Clone)]
 pub struct DepthAttachment {
     pub view: TextureView,
     clear_value: Option<f32>,
+    stencil: Option<StencilAttachment>,
     is_first_call: Arc<AtomicBool>,
 }
 
+#[derive(Clone, Copy)]
+struct StencilAttachment {
+    clear_value: Option<u32>,
+}
+
 impl DepthAttachment {
     pub fn new(view: TextureView, clear_value: Option<f32>) -> Self {
         Self {
             view,
             clear_value,
+            stencil: None,
             is_first_call: Arc::new(AtomicBool::new(clear_value.is_some())),
         }
     }
 
+    /// Also use the stencil aspect of the view, which must have a stencil format.
+    ///
+    /// The stencil is cleared with `clear_value` at the same time as the depth if a clear value
+    /// is provided, otherwise it is loaded.
+    pub fn with_stencil(mut self, clear_value: Option<u32>) -> Self {
+        self.stencil = Some(StencilAttachment { clear_value });
+        self.is_first_call = Arc::new(AtomicBool::new(
+            self.clear_value.is_some() || clear_value.is_some(),
+        ));
+        self
+    }
+
     /// Get this texture view as an attachment. The attachment will be cleared with a value of
     /// `clear_value` if this is the first time calling this function with `store` == [`StoreOp::Store`],
     /// and a clear value was provided, otherwise it will be loaded.
@@ -109,15 +134,19 @@ impl DepthAttachment {
         RenderPassDepthStencilAttachment {
             view: &self.view,
             depth_ops: Some(Operations {
-                load: if first_call {
-                    // If first_call is true, then a clear value will always have been provided in the constructor
-                    LoadOp::Clear(self.clear_value.unwrap())
-                } else {
-                    LoadOp::Load
+                load: match self.clear_value {
+                    Some(clear_value) if first_call => LoadOp::Clear(clear_value),
+                    _ => LoadOp::Load,
+                },
+                store,
+            }),
+            stencil_ops: self.stencil.map(|stencil| Operations {
+                load: match stencil.clear_value {
+                    Some(clear_value) if first_call => LoadOp::Clear(clear_value),
+                    _ => LoadOp::Load,
                 },
                 store,
             }),
-            stencil_ops: None,
         }
     }
 }
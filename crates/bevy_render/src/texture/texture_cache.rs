@@ -1,9 +1,17 @@
 use crate::{
+    render_graph::{
+        InternedRenderLabel, InternedRenderSubGraph, RenderGraph, RenderLabel, RenderSubGraph,
+    },
     render_resource::{Texture, TextureView},
     renderer::RenderDevice,
 };
-use bevy_ecs::{prelude::ResMut, system::Resource};
-use bevy_utils::{Entry, HashMap};
+use bevy_ecs::{
+    change_detection::DetectChanges,
+    entity::Entity,
+    prelude::{Res, ResMut},
+    system::Resource,
+};
+use bevy_utils::{Entry, HashMap, HashSet};
 use wgpu::{TextureDescriptor, TextureViewDescriptor};
 
 /// The internal representation of a [`CachedTexture`] used to track whether it was recently used
@@ -15,6 +23,15 @@ struct CachedTextureMeta {
     frames_since_last_use: usize,
 }
 
+/// The internal representation of a transient [`CachedTexture`], used to track the passes it is
+/// used in during the current frame.
+struct TransientTextureMeta {
+    texture: Texture,
+    default_view: TextureView,
+    lifetimes: Vec<TextureLifetime>,
+    frames_since_last_use: usize,
+}
+
 /// A cached GPU [`Texture`] with corresponding [`TextureView`].
 /// This is useful for textures that are created repeatedly (each frame) in the rendering process
 /// to reduce the amount of GPU memory allocations.
@@ -24,11 +41,124 @@ pub struct CachedTexture {
     pub default_view: TextureView,
 }
 
+/// The render graph nodes of a view in which a transient texture is used, from the first node
+/// writing it to the last node reading it.
+///
+/// Transient textures whose lifetimes don't overlap share the same GPU texture, see
+/// [`TextureCache::get_transient`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TextureLifetime {
+    /// The view entity the sub graph is run for.
+    pub view: Entity,
+    /// The sub graph containing the nodes.
+    pub sub_graph: InternedRenderSubGraph,
+    /// The first node using the texture.
+    pub first_node: InternedRenderLabel,
+    /// The last node using the texture.
+    pub last_node: InternedRenderLabel,
+}
+
+impl TextureLifetime {
+    /// Creates the lifetime of a texture used from `first_node` to `last_node` of the
+    /// `sub_graph` run for `view`.
+    pub fn new(
+        view: Entity,
+        sub_graph: impl RenderSubGraph,
+        first_node: impl RenderLabel,
+        last_node: impl RenderLabel,
+    ) -> Self {
+        Self {
+            view,
+            sub_graph: sub_graph.intern(),
+            first_node: first_node.intern(),
+            last_node: last_node.intern(),
+        }
+    }
+
+    /// Creates the lifetime of a texture only used by the `node` of the `sub_graph` run for
+    /// `view`.
+    pub fn node(view: Entity, sub_graph: impl RenderSubGraph, node: impl RenderLabel) -> Self {
+        let node = node.intern();
+        Self {
+            view,
+            sub_graph: sub_graph.intern(),
+            first_node: node,
+            last_node: node,
+        }
+    }
+}
+
+/// The order the nodes of the sub graphs of the [`RenderGraph`] are guaranteed to run in.
+#[derive(Default)]
+struct RenderGraphOrder {
+    /// The nodes that run after each node of each sub graph, following the edges of the graph.
+    successors:
+        HashMap<(InternedRenderSubGraph, InternedRenderLabel), HashSet<InternedRenderLabel>>,
+}
+
+impl RenderGraphOrder {
+    fn new(graph: &RenderGraph) -> Self {
+        let mut order = Self::default();
+        order.add_sub_graphs(graph);
+        order
+    }
+
+    fn add_sub_graphs(&mut self, graph: &RenderGraph) {
+        for (sub_graph_label, sub_graph) in graph.iter_sub_graphs() {
+            for node in sub_graph.iter_nodes() {
+                let mut successors = HashSet::new();
+                let mut stack = vec![node.label];
+                while let Some(label) = stack.pop() {
+                    let Ok(outputs) = sub_graph.iter_node_outputs(label) else {
+                        continue;
+                    };
+                    for (_, output) in outputs {
+                        if successors.insert(output.label) {
+                            stack.push(output.label);
+                        }
+                    }
+                }
+                self.successors
+                    .insert((sub_graph_label, node.label), successors);
+            }
+            self.add_sub_graphs(sub_graph);
+        }
+    }
+
+    /// Returns `true` if `node` always finishes running before `other` starts, in `sub_graph`.
+    fn runs_before(
+        &self,
+        sub_graph: InternedRenderSubGraph,
+        node: InternedRenderLabel,
+        other: InternedRenderLabel,
+    ) -> bool {
+        self.successors
+            .get(&(sub_graph, node))
+            .is_some_and(|successors| successors.contains(&other))
+    }
+
+    /// Returns `true` if the textures used during `a` and `b` can share the same GPU texture.
+    fn are_disjoint(&self, a: &TextureLifetime, b: &TextureLifetime) -> bool {
+        // Sub graphs run for different views may be recorded in any order.
+        a.view == b.view
+            && a.sub_graph == b.sub_graph
+            && (self.runs_before(a.sub_graph, a.last_node, b.first_node)
+                || self.runs_before(a.sub_graph, b.last_node, a.first_node))
+    }
+}
+
 /// This resource caches textures that are created repeatedly in the rendering process and
 /// are only required for one frame.
+///
+/// Transient textures, which are only used by a few nodes of the render graph, are pooled
+/// separately: textures with the same size, format and usage share the same GPU memory when
+/// the nodes using them run one after the other. See [`TextureCache::get_transient`].
 #[derive(Resource, Default)]
 pub struct TextureCache {
     textures: HashMap<TextureDescriptor<'static>, Vec<CachedTextureMeta>>,
+    /// The transient textures, by descriptor without label.
+    transient_textures: HashMap<TextureDescriptor<'static>, Vec<TransientTextureMeta>>,
+    graph_order: RenderGraphOrder,
 }
 
 impl TextureCache {
@@ -82,6 +212,60 @@ impl TextureCache {
         }
     }
 
+    /// Retrieves a texture that matches the `descriptor`, only used during the render graph
+    /// nodes of its `lifetime`. If no matching one is found a new [`CachedTexture`] is created.
+    ///
+    /// Transient textures whose lifetimes don't overlap are aliased: they share the same GPU
+    /// texture, so their content is undefined when the first node of their lifetime runs. The
+    /// label of the `descriptor` is ignored when matching textures, so an aliased texture is
+    /// labeled after the first texture it was created for.
+    ///
+    /// Lifetimes only overlap with lifetimes of the same view and sub graph if the edges of the
+    /// render graph guarantee that one ends before the other starts.
+    pub fn get_transient(
+        &mut self,
+        render_device: &RenderDevice,
+        descriptor: TextureDescriptor<'static>,
+        lifetime: TextureLifetime,
+    ) -> CachedTexture {
+        let textures = self
+            .transient_textures
+            .entry(TextureDescriptor {
+                label: None,
+                ..descriptor.clone()
+            })
+            .or_default();
+
+        let graph_order = &self.graph_order;
+        let available = textures.iter_mut().find(|texture| {
+            texture
+                .lifetimes
+                .iter()
+                .all(|used| graph_order.are_disjoint(used, &lifetime))
+        });
+        if let Some(texture) = available {
+            texture.lifetimes.push(lifetime);
+            texture.frames_since_last_use = 0;
+            return CachedTexture {
+                texture: texture.texture.clone(),
+                default_view: texture.default_view.clone(),
+            };
+        }
+
+        let texture = render_device.create_texture(&descriptor);
+        let default_view = texture.create_view(&TextureViewDescriptor::default());
+        textures.push(TransientTextureMeta {
+            texture: texture.clone(),
+            default_view: default_view.clone(),
+            lifetimes: vec![lifetime],
+            frames_since_last_use: 0,
+        });
+        CachedTexture {
+            texture,
+            default_view,
+        }
+    }
+
     /// Updates the cache and only retains recently used textures.
     pub fn update(&mut self) {
         for textures in self.textures.values_mut() {
@@ -92,10 +276,83 @@ impl TextureCache {
 
             textures.retain(|texture| texture.frames_since_last_use < 3);
         }
+
+        for textures in self.transient_textures.values_mut() {
+            for texture in textures.iter_mut() {
+                texture.frames_since_last_use += 1;
+                texture.lifetimes.clear();
+            }
+
+            textures.retain(|texture| texture.frames_since_last_use < 3);
+        }
+    }
+
+    /// Updates the order of the nodes of the `render_graph`, used to find which transient
+    /// textures can be aliased.
+    pub fn update_render_graph_order(&mut self, render_graph: &RenderGraph) {
+        self.graph_order = RenderGraphOrder::new(render_graph);
     }
 }
 
 /// Updates the [`TextureCache`] to only retains recently used textures.
-pub fn update_texture_cache_system(mut texture_cache: ResMut<TextureCache>) {
+pub fn update_texture_cache_system(
+    mut texture_cache: ResMut<TextureCache>,
+    render_graph: Res<RenderGraph>,
+) {
     texture_cache.update();
+    if render_graph.is_changed() {
+        texture_cache.update_render_graph_order(&render_graph);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render_graph::EmptyNode;
+
+    #[derive(Debug, Hash, PartialEq, Eq, Clone, RenderSubGraph)]
+    struct TestGraph;
+
+    #[derive(Debug, Hash, PartialEq, Eq, Clone, RenderLabel)]
+    enum TestLabels {
+        Prepass,
+        Ssao,
+        MainPass,
+        Bloom,
+        Ui,
+    }
+
+    #[test]
+    fn lifetimes_follow_graph_edges() {
+        use TestLabels::*;
+
+        let mut sub_graph = RenderGraph::default();
+        for label in [Prepass, Ssao, MainPass, Bloom, Ui] {
+            sub_graph.add_node(label, EmptyNode);
+        }
+        sub_graph.add_node_edges((Prepass, Ssao, MainPass, Bloom));
+        sub_graph.add_node_edge(MainPass, Ui);
+        let mut graph = RenderGraph::default();
+        graph.add_sub_graph(TestGraph, sub_graph);
+        let order = RenderGraphOrder::new(&graph);
+
+        let view = Entity::from_raw(0);
+        let node = |label| TextureLifetime::node(view, TestGraph, label);
+        assert!(order.are_disjoint(&node(Ssao), &node(Bloom)));
+        assert!(order.are_disjoint(
+            &TextureLifetime::new(view, TestGraph, Prepass, MainPass),
+            &node(Bloom)
+        ));
+        assert!(!order.are_disjoint(
+            &TextureLifetime::new(view, TestGraph, Prepass, MainPass),
+            &node(Ssao)
+        ));
+        // Neither the UI nor the bloom node is guaranteed to run first.
+        assert!(!order.are_disjoint(&node(Bloom), &node(Ui)));
+        assert!(!order.are_disjoint(&node(Ssao), &node(Ssao)));
+        assert!(!order.are_disjoint(
+            &node(Ssao),
+            &TextureLifetime::node(Entity::from_raw(1), TestGraph, Bloom)
+        ));
+    }
 }
@@ -10,6 +10,7 @@ use crate::{
         CameraMainTextureUsages, ClearColor, ClearColorConfig, ExposureSettings, ExtractedCamera,
         ManualTextureViews, MipBias, TemporalJitter,
     },
+    extract_component::{ExtractComponent, ExtractComponentPlugin},
     extract_resource::{ExtractResource, ExtractResourcePlugin},
     prelude::{Image, Shader},
     primitives::Frustum,
@@ -26,6 +27,7 @@ use bevy_math::{Mat4, UVec4, Vec3, Vec4, Vec4Swizzles};
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 use bevy_transform::components::GlobalTransform;
 use bevy_utils::HashMap;
+use bevy_window::HdrOutput;
 use std::sync::{
     atomic::{AtomicUsize, Ordering},
     Arc,
@@ -51,9 +53,14 @@ impl Plugin for ViewPlugin {
             .register_type::<Visibility>()
             .register_type::<VisibleEntities>()
             .register_type::<ColorGrading>()
+            .register_type::<HdrOutputSettings>()
             .init_resource::<Msaa>()
             // NOTE: windows.is_changed() handles cases where a window was resized
-            .add_plugins((ExtractResourcePlugin::<Msaa>::default(), VisibilityPlugin));
+            .add_plugins((
+                ExtractResourcePlugin::<Msaa>::default(),
+                ExtractComponentPlugin::<HdrOutputSettings>::default(),
+                VisibilityPlugin,
+            ));
 
         if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app.init_resource::<ViewUniforms>().add_systems(
@@ -161,6 +168,34 @@ impl Default for ColorGrading {
     }
 }
 
+/// Configures how a [`Camera`](crate::camera::Camera) maps its image to the brightness of a
+/// display, when it renders to a window with HDR output enabled (see
+/// [`Window::hdr_output`](bevy_window::Window::hdr_output)).
+///
+/// Has no effect on cameras rendering to SDR targets.
+#[derive(Component, ExtractComponent, Reflect, Debug, Copy, Clone, PartialEq)]
+#[reflect(Component, Default)]
+pub struct HdrOutputSettings {
+    /// The luminance, in nits, that a color value of `1.0` is displayed at.
+    ///
+    /// This is the brightness of UI and of a white surface lit by the sun. The default of
+    /// 203 nits is the reference white of ITU-R BT.2408.
+    pub paper_white_nits: f32,
+
+    /// The maximum luminance, in nits, of the display. Highlights are rolled off towards this
+    /// value by tonemapping.
+    pub peak_nits: f32,
+}
+
+impl Default for HdrOutputSettings {
+    fn default() -> Self {
+        Self {
+            paper_white_nits: 203.0,
+            peak_nits: 1000.0,
+        }
+    }
+}
+
 #[derive(Clone, ShaderType)]
 pub struct ViewUniform {
     view_proj: Mat4,
@@ -199,6 +234,7 @@ pub struct ViewTarget {
     main_texture: Arc<AtomicUsize>,
     out_texture: TextureView,
     out_texture_format: TextureFormat,
+    out_hdr_output: HdrOutput,
 }
 
 pub struct PostProcessWrite<'a> {
@@ -314,6 +350,12 @@ impl ViewTarget {
         self.out_texture_format
     }
 
+    /// The [`HdrOutput`] of the final texture this view will render to
+    #[inline]
+    pub fn out_hdr_output(&self) -> HdrOutput {
+        self.out_hdr_output
+    }
+
     /// This will start a new "post process write", which assumes that the caller
     /// will write the [`PostProcessWrite`]'s `source` to the `destination`.
     ///
@@ -344,6 +386,7 @@ impl ViewTarget {
 pub struct ViewDepthTexture {
     pub texture: Texture,
     attachment: DepthAttachment,
+    stencil_reference: Option<u32>,
 }
 
 impl ViewDepthTexture {
@@ -351,9 +394,26 @@ impl ViewDepthTexture {
         Self {
             texture: texture.texture,
             attachment: DepthAttachment::new(texture.default_view, clear_value),
+            stencil_reference: None,
         }
     }
 
+    /// Also use the stencil buffer of the texture, which must have a stencil format.
+    ///
+    /// The stencil is cleared with `clear_value` if provided, and the render passes drawing to
+    /// this texture compare and write `reference` with the stencil state of their pipelines.
+    pub fn with_stencil(mut self, clear_value: Option<u32>, reference: u32) -> Self {
+        self.attachment = self.attachment.with_stencil(clear_value);
+        self.stencil_reference = Some(reference);
+        self
+    }
+
+    /// The stencil reference of the render passes drawing to this texture, if it has a stencil
+    /// buffer.
+    pub fn stencil_reference(&self) -> Option<u32> {
+        self.stencil_reference
+    }
+
     pub fn get_attachment(&self, store: StoreOp) -> RenderPassDepthStencilAttachment {
         self.attachment.get_attachment(store)
     }
@@ -561,6 +621,7 @@ pub fn prepare_view_targets(
                     main_texture_format,
                     out_texture: out_texture_view.clone(),
                     out_texture_format: out_texture_format.add_srgb_suffix(),
+                    out_hdr_output: target.get_hdr_output(&windows),
                 });
             }
         }
@@ -8,18 +8,19 @@ use bevy_asset::{Assets, Handle};
 use bevy_ecs::prelude::*;
 use bevy_hierarchy::{Children, Parent};
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_tasks::{ComputeTaskPool, TaskPool};
 use bevy_transform::{components::GlobalTransform, TransformSystem};
-use std::cell::Cell;
+use std::{cell::Cell, cmp::Reverse, collections::BinaryHeap};
 use thread_local::ThreadLocal;
 
 use crate::deterministic::DeterministicRenderingConfig;
 use crate::{
     camera::{
         camera_system, Camera, CameraProjection, OrthographicProjection, PerspectiveProjection,
-        Projection,
+        Projection, XrProjection,
     },
     mesh::Mesh,
-    primitives::{Aabb, Frustum, Sphere},
+    primitives::{Aabb, Frustum, SimdFrustum, Sphere},
 };
 
 /// User indication of whether an entity is visible. Propagates down the entity hierarchy.
@@ -46,6 +47,46 @@ pub enum Visibility {
     Visible,
 }
 
+impl Visibility {
+    /// Toggles between `Inherited` and `Hidden`, which shows or hides the entity along with its
+    /// descendants set to `Inherited`.
+    ///
+    /// `Visible` is left unchanged.
+    #[inline]
+    pub fn toggle_inherited_hidden(&mut self) {
+        *self = match *self {
+            Visibility::Inherited => Visibility::Hidden,
+            Visibility::Hidden => Visibility::Inherited,
+            Visibility::Visible => Visibility::Visible,
+        };
+    }
+
+    /// Toggles between `Inherited` and `Visible`, which makes the entity visible even
+    /// if its [`Parent`] is hidden, or follow its [`Parent`] again.
+    ///
+    /// `Hidden` is left unchanged.
+    #[inline]
+    pub fn toggle_inherited_visible(&mut self) {
+        *self = match *self {
+            Visibility::Inherited => Visibility::Visible,
+            Visibility::Visible => Visibility::Inherited,
+            Visibility::Hidden => Visibility::Hidden,
+        };
+    }
+
+    /// Toggles between `Visible` and `Hidden`.
+    ///
+    /// `Inherited` is left unchanged.
+    #[inline]
+    pub fn toggle_visible_hidden(&mut self) {
+        *self = match *self {
+            Visibility::Visible => Visibility::Hidden,
+            Visibility::Hidden => Visibility::Visible,
+            Visibility::Inherited => Visibility::Inherited,
+        };
+    }
+}
+
 // Allows `&Visibility == Visibility`
 impl PartialEq<Visibility> for &Visibility {
     #[inline]
@@ -224,7 +265,8 @@ impl Plugin for VisibilityPlugin {
                     // so these systems will run independently of one another.
                     // FIXME: Add an archetype invariant for this https://github.com/bevyengine/bevy/issues/1481.
                     .ambiguous_with(update_frusta::<PerspectiveProjection>)
-                    .ambiguous_with(update_frusta::<Projection>),
+                    .ambiguous_with(update_frusta::<Projection>)
+                    .ambiguous_with(update_frusta::<XrProjection>),
                 update_frusta::<PerspectiveProjection>
                     .in_set(UpdatePerspectiveFrusta)
                     .after(camera_system::<PerspectiveProjection>)
@@ -232,10 +274,16 @@ impl Plugin for VisibilityPlugin {
                     // We assume that no camera will have more than one projection component,
                     // so these systems will run independently of one another.
                     // FIXME: Add an archetype invariant for this https://github.com/bevyengine/bevy/issues/1481.
-                    .ambiguous_with(update_frusta::<Projection>),
+                    .ambiguous_with(update_frusta::<Projection>)
+                    .ambiguous_with(update_frusta::<XrProjection>),
                 update_frusta::<Projection>
                     .in_set(UpdateProjectionFrusta)
                     .after(camera_system::<Projection>)
+                    .after(TransformSystem::TransformPropagate)
+                    .ambiguous_with(update_frusta::<XrProjection>),
+                update_frusta::<XrProjection>
+                    .in_set(UpdateProjectionFrusta)
+                    .after(camera_system::<XrProjection>)
                     .after(TransformSystem::TransformPropagate),
                 (visibility_propagate_system, reset_view_visibility).in_set(VisibilityPropagate),
                 check_visibility
@@ -369,6 +417,10 @@ fn reset_view_visibility(mut query: Query<&mut ViewVisibility>) {
 /// The system is part of the [`VisibilitySystems::CheckVisibility`] set. Each frame, it updates the
 /// [`ViewVisibility`] of all entities, and for each view also compute the [`VisibleEntities`]
 /// for that view.
+///
+/// Entities are tested in parallel, each thread collecting the entities it found visible. If
+/// [`DeterministicRenderingConfig::stable_sort_z_fighting`] is set, these lists are sorted in
+/// parallel and merged, so that the visible entities are sorted by id.
 pub fn check_visibility(
     mut thread_queues: Local<ThreadLocal<Cell<Vec<Entity>>>>,
     mut view_query: Query<(
@@ -394,6 +446,7 @@ pub fn check_visibility(
         }
 
         let view_mask = maybe_view_mask.copied().unwrap_or_default();
+        let simd_frustum = SimdFrustum::new(frustum, false);
 
         visible_entities.entities.clear();
         visible_aabb_query.par_iter_mut().for_each(|query_item| {
@@ -427,7 +480,7 @@ pub fn check_visibility(
                         radius: transform.radius_vec3a(model_aabb.half_extents),
                     };
                     // Do quick sphere-based frustum culling
-                    if !frustum.intersects_sphere(&model_sphere, false) {
+                    if !simd_frustum.intersects_sphere(&model_sphere) {
                         return;
                     }
                     // Do aabb-based frustum culling
@@ -444,13 +497,43 @@ pub fn check_visibility(
             cell.set(queue);
         });
 
-        for cell in &mut thread_queues {
-            visible_entities.entities.append(cell.get_mut());
-        }
         if deterministic_rendering_config.stable_sort_z_fighting {
-            // We can use the faster unstable sort here because
-            // the values (`Entity`) are guaranteed to be unique.
-            visible_entities.entities.sort_unstable();
+            let mut queues: Vec<_> = thread_queues
+                .iter_mut()
+                .map(Cell::get_mut)
+                .filter(|queue| !queue.is_empty())
+                .collect();
+            ComputeTaskPool::get_or_init(TaskPool::default).scope(|scope| {
+                for queue in &mut queues {
+                    // We can use the faster unstable sort here because
+                    // the values (`Entity`) are guaranteed to be unique.
+                    scope.spawn(async move { queue.sort_unstable() });
+                }
+            });
+            merge_sorted(&queues, &mut visible_entities.entities);
+            for queue in queues {
+                queue.clear();
+            }
+        } else {
+            for cell in &mut thread_queues {
+                visible_entities.entities.append(cell.get_mut());
+            }
+        }
+    }
+}
+
+/// Appends the elements of the sorted `lists` to `output`, in order.
+fn merge_sorted<T: Ord + Copy>(lists: &[&mut Vec<T>], output: &mut Vec<T>) {
+    output.reserve(lists.iter().map(|list| list.len()).sum());
+    let mut heads: BinaryHeap<_> = lists
+        .iter()
+        .enumerate()
+        .filter_map(|(list, values)| Some(Reverse((*values.first()?, list, 0))))
+        .collect();
+    while let Some(Reverse((value, list, index))) = heads.pop() {
+        output.push(value);
+        if let Some(&next) = lists[list].get(index + 1) {
+            heads.push(Reverse((next, list, index + 1)));
         }
     }
 }
@@ -713,10 +796,36 @@ mod test {
         assert!(child_visible);
     }
 
+    #[test]
+    fn visibility_toggles() {
+        let mut visibility = Visibility::Inherited;
+        visibility.toggle_inherited_hidden();
+        assert_eq!(visibility, Visibility::Hidden);
+        visibility.toggle_inherited_visible();
+        assert_eq!(visibility, Visibility::Hidden);
+        visibility.toggle_visible_hidden();
+        assert_eq!(visibility, Visibility::Visible);
+        visibility.toggle_inherited_visible();
+        assert_eq!(visibility, Visibility::Inherited);
+        visibility.toggle_inherited_hidden();
+        visibility.toggle_inherited_hidden();
+        assert_eq!(visibility, Visibility::Inherited);
+    }
+
     #[test]
     fn ensure_visibility_enum_size() {
         use std::mem;
         assert_eq!(1, mem::size_of::<Visibility>());
         assert_eq!(1, mem::size_of::<Option<Visibility>>());
     }
+
+    #[test]
+    fn merge_sorted_lists() {
+        let mut a = vec![1, 4, 9];
+        let mut b = vec![];
+        let mut c = vec![2, 3, 10, 11];
+        let mut merged = vec![0];
+        merge_sorted(&[&mut a, &mut b, &mut c], &mut merged);
+        assert_eq!(merged, vec![0, 1, 2, 3, 4, 9, 10, 11]);
+    }
 }
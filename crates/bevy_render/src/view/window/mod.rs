@@ -8,9 +8,14 @@ use crate::{
 };
 use bevy_app::{App, Plugin};
 use bevy_ecs::prelude::*;
-use bevy_utils::{default, tracing::debug, EntityHashMap, HashSet};
+use bevy_utils::{
+    default,
+    tracing::{debug, warn},
+    EntityHashMap, HashSet,
+};
 use bevy_window::{
-    CompositeAlphaMode, PresentMode, PrimaryWindow, RawHandleWrapper, Window, WindowClosed,
+    CompositeAlphaMode, HdrOutput, PresentMode, PrimaryWindow, RawHandleWrapper, Window,
+    WindowClosed,
 };
 use std::{
     ops::{Deref, DerefMut},
@@ -73,6 +78,12 @@ pub struct ExtractedWindow {
     pub size_changed: bool,
     pub present_mode_changed: bool,
     pub alpha_mode: CompositeAlphaMode,
+    /// The [`HdrOutput`] requested by the [`Window`].
+    pub requested_hdr_output: HdrOutput,
+    /// The [`HdrOutput`] the surface was configured with, which is [`HdrOutput::Sdr`] if the
+    /// requested one isn't supported.
+    pub hdr_output: HdrOutput,
+    pub hdr_output_changed: bool,
     pub screenshot_func: Option<screenshot::ScreenshotFn>,
 }
 
@@ -139,6 +150,9 @@ fn extract_windows(
             swap_chain_texture_format: None,
             present_mode_changed: false,
             alpha_mode: window.composite_alpha_mode,
+            requested_hdr_output: window.hdr_output,
+            hdr_output: HdrOutput::Sdr,
+            hdr_output_changed: false,
             screenshot_func: None,
             screenshot_memory: None,
         });
@@ -149,6 +163,8 @@ fn extract_windows(
             || new_height != extracted_window.physical_height;
         extracted_window.present_mode_changed =
             window.present_mode != extracted_window.present_mode;
+        extracted_window.hdr_output_changed =
+            window.hdr_output != extracted_window.requested_hdr_output;
 
         if extracted_window.size_changed {
             debug!(
@@ -169,6 +185,14 @@ fn extract_windows(
             );
             extracted_window.present_mode = window.present_mode;
         }
+
+        if extracted_window.hdr_output_changed {
+            debug!(
+                "Window HDR output changed from {:?} to {:?}",
+                extracted_window.requested_hdr_output, window.hdr_output
+            );
+            extracted_window.requested_hdr_output = window.hdr_output;
+        }
     }
 
     for closed_window in closed.read() {
@@ -199,6 +223,43 @@ struct SurfaceData {
     // TODO: what lifetime should this be?
     surface: wgpu::Surface<'static>,
     format: TextureFormat,
+    hdr_output: HdrOutput,
+    /// The formats supported by the surface
+    formats: Vec<TextureFormat>,
+}
+
+/// Picks the surface format for `hdr_output`, falling back to SDR if the surface doesn't
+/// support it.
+fn select_surface_format(
+    formats: &[TextureFormat],
+    hdr_output: HdrOutput,
+) -> (TextureFormat, HdrOutput) {
+    let hdr_format = match hdr_output {
+        HdrOutput::Sdr => None,
+        HdrOutput::ScRgb => Some(TextureFormat::Rgba16Float),
+        HdrOutput::Hdr10 => Some(TextureFormat::Rgb10a2Unorm),
+    };
+    if let Some(hdr_format) = hdr_format {
+        if formats.contains(&hdr_format) {
+            return (hdr_format, hdr_output);
+        }
+        warn!(
+            "{hdr_output:?} output is not supported by the window's surface, falling back to SDR"
+        );
+    }
+
+    // Prefer sRGB formats for surfaces, but fall back to first available format if no sRGB formats are available.
+    let mut format = *formats.first().expect("No supported formats for surface");
+    for &available_format in formats {
+        // Rgba8UnormSrgb and Bgra8UnormSrgb and the only sRGB formats wgpu exposes that we can use for surfaces.
+        if available_format == TextureFormat::Rgba8UnormSrgb
+            || available_format == TextureFormat::Bgra8UnormSrgb
+        {
+            format = available_format;
+            break;
+        }
+    }
+    (format, HdrOutput::Sdr)
 }
 
 #[derive(Resource, Default)]
@@ -250,10 +311,16 @@ pub fn prepare_windows(
 ) {
     for window in windows.windows.values_mut() {
         let window_surfaces = window_surfaces.deref_mut();
-        let Some(surface_data) = window_surfaces.surfaces.get(&window.entity) else {
+        let Some(surface_data) = window_surfaces.surfaces.get_mut(&window.entity) else {
             continue;
         };
 
+        if window.hdr_output_changed {
+            (surface_data.format, surface_data.hdr_output) =
+                select_surface_format(&surface_data.formats, window.requested_hdr_output);
+        }
+        window.hdr_output = surface_data.hdr_output;
+
         let surface_configuration = wgpu::SurfaceConfiguration {
             format: surface_data.format,
             width: window.physical_width,
@@ -339,36 +406,38 @@ pub fn prepare_windows(
         let not_already_configured = window_surfaces.configured_windows.insert(window.entity);
 
         let surface = &surface_data.surface;
-        if not_already_configured || window.size_changed || window.present_mode_changed {
+        if not_already_configured
+            || window.size_changed
+            || window.present_mode_changed
+            || window.hdr_output_changed
+        {
             render_device.configure_surface(surface, &surface_configuration);
-            let frame = surface
-                .get_current_texture()
-                .expect("Error configuring surface");
-            window.set_swapchain_texture(frame);
-        } else {
-            match surface.get_current_texture() {
-                Ok(frame) => {
-                    window.set_swapchain_texture(frame);
-                }
-                Err(wgpu::SurfaceError::Outdated) => {
-                    render_device.configure_surface(surface, &surface_configuration);
-                    let frame = surface
-                        .get_current_texture()
-                        .expect("Error reconfiguring surface");
-                    window.set_swapchain_texture(frame);
-                }
-                #[cfg(target_os = "linux")]
-                Err(wgpu::SurfaceError::Timeout) if may_erroneously_timeout() => {
-                    bevy_utils::tracing::trace!(
-                        "Couldn't get swap chain texture. This is probably a quirk \
-                        of your Linux GPU driver, so it can be safely ignored."
-                    );
-                }
-                Err(err) => {
-                    panic!("Couldn't get swap chain texture, operation unrecoverable: {err}");
-                }
+        }
+        match acquire_surface_texture(
+            || surface.get_current_texture(),
+            || render_device.configure_surface(surface, &surface_configuration),
+        ) {
+            Ok(frame) => {
+                window.set_swapchain_texture(frame);
             }
-        };
+            Err(err @ (wgpu::SurfaceError::Outdated | wgpu::SurfaceError::Lost)) => {
+                bevy_log::warn!(
+                    "Couldn't get swap chain texture after reconfiguring the surface, \
+                    skipping the frame: {err}"
+                );
+                continue;
+            }
+            #[cfg(target_os = "linux")]
+            Err(wgpu::SurfaceError::Timeout) if may_erroneously_timeout() => {
+                bevy_utils::tracing::trace!(
+                    "Couldn't get swap chain texture. This is probably a quirk \
+                    of your Linux GPU driver, so it can be safely ignored."
+                );
+            }
+            Err(err) => {
+                panic!("Couldn't get swap chain texture, operation unrecoverable: {err}");
+            }
+        }
         window.swap_chain_texture_format = Some(surface_data.format);
 
         if window.screenshot_func.is_some() {
@@ -463,21 +532,92 @@ pub fn create_surfaces(
                 };
                 let caps = surface.get_capabilities(&render_adapter);
                 let formats = caps.formats;
-                // For future HDR output support, we'll need to request a format that supports HDR,
-                // but as of wgpu 0.15 that is not yet supported.
-                // Prefer sRGB formats for surfaces, but fall back to first available format if no sRGB formats are available.
-                let mut format = *formats.first().expect("No supported formats for surface");
-                for available_format in formats {
-                    // Rgba8UnormSrgb and Bgra8UnormSrgb and the only sRGB formats wgpu exposes that we can use for surfaces.
-                    if available_format == TextureFormat::Rgba8UnormSrgb
-                        || available_format == TextureFormat::Bgra8UnormSrgb
-                    {
-                        format = available_format;
-                        break;
-                    }
+                let (format, hdr_output) =
+                    select_surface_format(&formats, window.requested_hdr_output);
+
+                SurfaceData {
+                    surface,
+                    format,
+                    hdr_output,
+                    formats,
                 }
-
-                SurfaceData { surface, format }
             });
     }
 }
+
+/// Gets the current texture of a surface, configuring the surface again once if it changed since
+/// it was configured, which happens often on the web while the canvas is resized, rather than
+/// dropping the frame.
+fn acquire_surface_texture<T>(
+    mut get_current_texture: impl FnMut() -> Result<T, wgpu::SurfaceError>,
+    reconfigure: impl FnOnce(),
+) -> Result<T, wgpu::SurfaceError> {
+    match get_current_texture() {
+        Err(wgpu::SurfaceError::Outdated | wgpu::SurfaceError::Lost) => {
+            reconfigure();
+            get_current_texture()
+        }
+        result => result,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn acquire(
+        results: Vec<Result<u32, wgpu::SurfaceError>>,
+    ) -> (Result<u32, wgpu::SurfaceError>, usize) {
+        let mut results = results.into_iter();
+        let mut reconfigurations = 0;
+        let result = acquire_surface_texture(|| results.next().unwrap(), || reconfigurations += 1);
+        (result, reconfigurations)
+    }
+
+    #[test]
+    fn outdated_or_lost_surfaces_are_reconfigured() {
+        assert_eq!(acquire(vec![Ok(1)]), (Ok(1), 0));
+        assert_eq!(
+            acquire(vec![Err(wgpu::SurfaceError::Outdated), Ok(1)]),
+            (Ok(1), 1)
+        );
+        assert_eq!(
+            acquire(vec![Err(wgpu::SurfaceError::Lost), Ok(1)]),
+            (Ok(1), 1)
+        );
+        // The surface is only reconfigured once per frame
+        assert_eq!(
+            acquire(vec![
+                Err(wgpu::SurfaceError::Lost),
+                Err(wgpu::SurfaceError::Lost)
+            ]),
+            (Err(wgpu::SurfaceError::Lost), 1)
+        );
+        assert_eq!(
+            acquire(vec![Err(wgpu::SurfaceError::Timeout)]),
+            (Err(wgpu::SurfaceError::Timeout), 0)
+        );
+    }
+
+    #[test]
+    fn hdr_surface_format_falls_back_to_sdr() {
+        let formats = [
+            TextureFormat::Bgra8Unorm,
+            TextureFormat::Bgra8UnormSrgb,
+            TextureFormat::Rgba16Float,
+        ];
+        assert_eq!(
+            select_surface_format(&formats, HdrOutput::Sdr),
+            (TextureFormat::Bgra8UnormSrgb, HdrOutput::Sdr)
+        );
+        assert_eq!(
+            select_surface_format(&formats, HdrOutput::ScRgb),
+            (TextureFormat::Rgba16Float, HdrOutput::ScRgb)
+        );
+        // HDR10 needs a 10-bit format
+        assert_eq!(
+            select_surface_format(&formats, HdrOutput::Hdr10),
+            (TextureFormat::Bgra8UnormSrgb, HdrOutput::Sdr)
+        );
+    }
+}
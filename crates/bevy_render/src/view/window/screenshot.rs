@@ -312,28 +312,24 @@ pub(crate) fn collect_screenshots(world: &mut World) {
     let mut windows = world.resource_mut::<ExtractedWindows>();
     for window in windows.values_mut() {
         if let Some(screenshot_func) = window.screenshot_func.take() {
+            let Some(ScreenshotPreparedState { buffer, .. }) = window.screenshot_memory.take()
+            else {
+                error!("Screenshot was not taken because the frame was skipped");
+                continue;
+            };
             let width = window.physical_width;
             let height = window.physical_height;
             let texture_format = window.swap_chain_texture_format.unwrap();
             let pixel_size = texture_format.pixel_size();
-            let ScreenshotPreparedState { buffer, .. } = window.screenshot_memory.take().unwrap();
 
             let finish = async move {
-                let (tx, rx) = async_channel::bounded(1);
-                let buffer_slice = buffer.slice(..);
-                // The polling for this map call is done every frame when the command queue is submitted.
-                buffer_slice.map_async(wgpu::MapMode::Read, move |result| {
-                    let err = result.err();
-                    if err.is_some() {
-                        panic!("{}", err.unwrap().to_string());
+                let mut result = match buffer.read().await {
+                    Ok(result) => result,
+                    Err(err) => {
+                        error!("Failed to read screenshot: {err}");
+                        return;
                     }
-                    tx.try_send(()).unwrap();
-                });
-                rx.recv().await.unwrap();
-                let data = buffer_slice.get_mapped_range();
-                // we immediately move the data to CPU memory to avoid holding the mapped view for long
-                let mut result = Vec::from(&*data);
-                drop(data);
+                };
                 drop(buffer);
 
                 if result.len() != ((width * height) as usize * pixel_size) {
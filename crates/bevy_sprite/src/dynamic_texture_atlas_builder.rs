@@ -5,15 +5,21 @@ use bevy_render::{
     render_asset::{RenderAsset, RenderAssetUsages},
     texture::{Image, TextureFormatPixelInfo},
 };
-use guillotiere::{size2, Allocation, AtlasAllocator};
+use bevy_utils::HashMap;
+use guillotiere::{size2, AllocId, Allocation, AtlasAllocator};
 
 /// Helper utility to update [`TextureAtlasLayout`] on the fly.
 ///
 /// Helpful in cases when texture is created procedurally,
 /// e.g: in a font glyph [`TextureAtlasLayout`], only add the [`Image`] texture for letters to be rendered.
+///
+/// Textures can be removed with [`DynamicTextureAtlasBuilder::remove_texture`] to make room for
+/// new ones, in which case their index in the [`TextureAtlasLayout`] is reused.
 pub struct DynamicTextureAtlasBuilder {
     atlas_allocator: AtlasAllocator,
     padding: i32,
+    allocations: HashMap<usize, AllocId>,
+    free_indices: Vec<usize>,
 }
 
 impl DynamicTextureAtlasBuilder {
@@ -27,6 +33,8 @@ impl DynamicTextureAtlasBuilder {
         Self {
             atlas_allocator: AtlasAllocator::new(to_size2(size)),
             padding,
+            allocations: HashMap::default(),
+            free_indices: Vec::new(),
         }
     }
 
@@ -65,12 +73,39 @@ impl DynamicTextureAtlasBuilder {
             self.place_texture(atlas_texture, allocation, texture);
             let mut rect: Rect = to_rect(allocation.rectangle);
             rect.max -= self.padding as f32;
-            Some(atlas_layout.add_texture(rect))
+            let index = match self.free_indices.pop() {
+                Some(index) => {
+                    atlas_layout.textures[index] = rect;
+                    index
+                }
+                None => atlas_layout.add_texture(rect),
+            };
+            self.allocations.insert(index, allocation.id);
+            Some(index)
         } else {
             None
         }
     }
 
+    /// Removes the texture at `index` from `atlas_layout`, freeing its space in the atlas.
+    ///
+    /// The index will be reused by the next texture added, so it must not be used anymore.
+    /// Returns `false` if there is no texture added by this builder at `index`.
+    pub fn remove_texture(&mut self, atlas_layout: &mut TextureAtlasLayout, index: usize) -> bool {
+        let Some(allocation) = self.allocations.remove(&index) else {
+            return false;
+        };
+        self.atlas_allocator.deallocate(allocation);
+        atlas_layout.textures[index] = Rect::default();
+        self.free_indices.push(index);
+        true
+    }
+
+    /// Returns `true` if no texture added by this builder is in the atlas.
+    pub fn is_empty(&self) -> bool {
+        self.allocations.is_empty()
+    }
+
     fn place_texture(
         &mut self,
         atlas_texture: &mut Image,
@@ -105,3 +140,58 @@ fn to_rect(rectangle: guillotiere::Rectangle) -> Rect {
 fn to_size2(vec2: Vec2) -> guillotiere::Size {
     guillotiere::Size::new(vec2.x as i32, vec2.y as i32)
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy_asset::Assets;
+    use bevy_math::Vec2;
+    use bevy_render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+        texture::Image,
+    };
+
+    use super::DynamicTextureAtlasBuilder;
+    use crate::TextureAtlasLayout;
+
+    fn image(size: u32) -> Image {
+        Image::new_fill(
+            Extent3d {
+                width: size,
+                height: size,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[255, 255, 255, 255],
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::MAIN_WORLD,
+        )
+    }
+
+    #[test]
+    fn removed_texture_frees_space_and_index() {
+        let mut textures = Assets::<Image>::default();
+        let atlas_texture = textures.add(image(16));
+        let mut layout = TextureAtlasLayout::new_empty(Vec2::splat(16.0));
+        let mut builder = DynamicTextureAtlasBuilder::new(Vec2::splat(16.0), 0);
+
+        let full = image(16);
+        let index = builder
+            .add_texture(&mut layout, &mut textures, &full, &atlas_texture)
+            .unwrap();
+        assert!(builder
+            .add_texture(&mut layout, &mut textures, &image(8), &atlas_texture)
+            .is_none());
+
+        assert!(builder.remove_texture(&mut layout, index));
+        assert!(!builder.remove_texture(&mut layout, index));
+        assert!(builder.is_empty());
+
+        let reused = builder
+            .add_texture(&mut layout, &mut textures, &image(8), &atlas_texture)
+            .unwrap();
+        assert_eq!(reused, index);
+        assert_eq!(layout.len(), 1);
+        assert_eq!(layout.textures[reused].size(), Vec2::splat(8.0));
+    }
+}
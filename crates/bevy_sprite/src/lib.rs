@@ -5,11 +5,14 @@
 mod bundle;
 mod dynamic_texture_atlas_builder;
 mod mesh2d;
+#[cfg(feature = "bevy_picking")]
+mod picking_backend;
 mod render;
 mod sprite;
 mod texture_atlas;
 mod texture_atlas_builder;
 mod texture_slice;
+mod vector;
 
 pub mod prelude {
     #[doc(hidden)]
@@ -18,6 +21,7 @@ pub mod prelude {
         sprite::{ImageScaleMode, Sprite},
         texture_atlas::{TextureAtlas, TextureAtlasLayout},
         texture_slice::{BorderRect, SliceScaleMode, TextureSlice, TextureSlicer},
+        vector::{PathOutline, VectorFill, VectorPath, VectorShape, VectorStroke},
         ColorMaterial, ColorMesh2dBundle, TextureAtlasBuilder,
     };
 }
@@ -25,11 +29,14 @@ pub mod prelude {
 pub use bundle::*;
 pub use dynamic_texture_atlas_builder::*;
 pub use mesh2d::*;
+#[cfg(feature = "bevy_picking")]
+pub use picking_backend::*;
 pub use render::*;
 pub use sprite::*;
 pub use texture_atlas::*;
 pub use texture_atlas_builder::*;
 pub use texture_slice::*;
+pub use vector::*;
 
 use bevy_app::prelude::*;
 use bevy_asset::{load_internal_asset, AssetApp, Assets, Handle};
@@ -74,7 +81,7 @@ impl Plugin for SpritePlugin {
             .register_type::<Anchor>()
             .register_type::<TextureAtlas>()
             .register_type::<Mesh2dHandle>()
-            .add_plugins((Mesh2dRenderPlugin, ColorMaterialPlugin))
+            .add_plugins((Mesh2dRenderPlugin, ColorMaterialPlugin, VectorPathPlugin))
             .add_systems(
                 PostUpdate,
                 (
@@ -87,6 +94,9 @@ impl Plugin for SpritePlugin {
                 ),
             );
 
+        #[cfg(feature = "bevy_picking")]
+        app.add_plugins(SpritePickingPlugin);
+
         if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app
                 .init_resource::<ImageBindGroups>()
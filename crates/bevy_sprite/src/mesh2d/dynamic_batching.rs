@@ -0,0 +1,401 @@
+//! Dynamic batching of small 2d meshes.
+//!
+//! Instanced batching only merges the draws of consecutive items using the same mesh. Dynamic
+//! batching goes further: the vertices of consecutive [`Transparent2d`] items sharing the same
+//! pipeline and material are transformed on the CPU and copied into a shared vertex buffer, so
+//! that they are drawn with a single draw call even if their meshes differ.
+
+use std::ops::Range;
+
+use bevy_asset::{AssetEvent, AssetId, Assets};
+use bevy_core_pipeline::core_2d::Transparent2d;
+use bevy_ecs::prelude::*;
+use bevy_math::{Affine3, Affine3A, Vec3};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    extract_resource::ExtractResource,
+    mesh::{Mesh, MeshVertexAttribute, MeshVertexBufferLayout, PrimitiveTopology},
+    render_phase::{DrawFunctionId, RenderPhase},
+    render_resource::{
+        BufferUsages, BufferVec, CachedRenderPipelineId, GpuArrayBuffer, VertexFormat,
+    },
+    renderer::{RenderDevice, RenderQueue},
+    Extract,
+};
+use bevy_utils::HashMap;
+
+use crate::{
+    Material2dBindGroupId, Mesh2dTransforms, Mesh2dUniform, MeshFlags, RenderMesh2dInstances,
+};
+
+/// Configures the dynamic batching of 2d meshes.
+///
+/// Consecutive [`Transparent2d`] items using the same pipeline and material are drawn with a
+/// single draw call, even if their meshes differ, when all their meshes are small triangle
+/// lists. Their vertices are transformed to world space on the CPU, so the mesh shaders of
+/// dynamically batched meshes see world space positions and an identity model transform.
+///
+/// Add [`NoAutomaticBatching`](bevy_render::batching::NoAutomaticBatching) to an entity to
+/// exclude it from both instanced and dynamic batching, for example if its material relies on
+/// local vertex positions.
+#[derive(Resource, ExtractResource, Clone, Debug, Reflect)]
+#[reflect(Resource, Default)]
+pub struct Mesh2dDynamicBatching {
+    /// Whether meshes using different mesh assets are batched together.
+    pub enabled: bool,
+    /// The maximum number of vertices of the meshes that can be batched dynamically.
+    ///
+    /// Changing it only applies to the meshes added or modified afterwards.
+    pub max_vertices: usize,
+}
+
+impl Default for Mesh2dDynamicBatching {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_vertices: 300,
+        }
+    }
+}
+
+/// The CPU copy of a mesh that can be batched dynamically.
+pub struct BatchableMesh2d {
+    layout: MeshVertexBufferLayout,
+    vertex_data: Vec<u8>,
+    indices: Vec<u32>,
+    stride: usize,
+    position_offset: usize,
+    normal_offset: Option<usize>,
+    tangent_offset: Option<usize>,
+}
+
+impl BatchableMesh2d {
+    /// Copies the vertex data of `mesh` if it is a triangle list with at most `max_vertices`
+    /// vertices, whose positions, normals and tangents can be transformed.
+    pub fn new(mesh: &Mesh, max_vertices: usize) -> Option<Self> {
+        let vertex_count = mesh.count_vertices();
+        if mesh.primitive_topology() != PrimitiveTopology::TriangleList
+            || vertex_count == 0
+            || vertex_count > max_vertices
+        {
+            return None;
+        }
+
+        let layout = mesh.get_mesh_vertex_buffer_layout();
+        // Returns `Err` if the attribute has an unexpected format, as it can't be transformed.
+        let offset_of = |attribute: MeshVertexAttribute, format: VertexFormat| {
+            let Some(index) = layout
+                .attribute_ids()
+                .iter()
+                .position(|id| *id == attribute.id)
+            else {
+                return Ok(None);
+            };
+            let attribute = &layout.layout().attributes[index];
+            if attribute.format == format {
+                Ok(Some(attribute.offset as usize))
+            } else {
+                Err(())
+            }
+        };
+        let position_offset =
+            offset_of(Mesh::ATTRIBUTE_POSITION, VertexFormat::Float32x3).ok()??;
+        let normal_offset = offset_of(Mesh::ATTRIBUTE_NORMAL, VertexFormat::Float32x3).ok()?;
+        let tangent_offset = offset_of(Mesh::ATTRIBUTE_TANGENT, VertexFormat::Float32x4).ok()?;
+
+        let indices = match mesh.indices() {
+            Some(indices) => indices.iter().map(|index| index as u32).collect(),
+            None => (0..vertex_count as u32).collect(),
+        };
+        Some(Self {
+            stride: layout.layout().array_stride as usize,
+            layout,
+            vertex_data: mesh.get_vertex_buffer_data(),
+            indices,
+            position_offset,
+            normal_offset,
+            tangent_offset,
+        })
+    }
+
+    /// Appends the vertices of the mesh transformed by `transform` to `vertices`, and its
+    /// indices to `indices`, offset by the number of vertices already in `vertices`.
+    pub fn append_transformed(
+        &self,
+        transform: &Affine3,
+        vertices: &mut Vec<u8>,
+        indices: &mut Vec<u32>,
+    ) {
+        let base_vertex = (vertices.len() / self.stride) as u32;
+        let start = vertices.len();
+        vertices.extend_from_slice(&self.vertex_data);
+
+        let normal_matrix = transform.matrix3.inverse().transpose();
+        for vertex in vertices[start..].chunks_exact_mut(self.stride) {
+            map_vec3(vertex, self.position_offset, |position| {
+                transform.matrix3 * position + transform.translation
+            });
+            if let Some(offset) = self.normal_offset {
+                map_vec3(vertex, offset, |normal| {
+                    (normal_matrix * normal).normalize_or_zero()
+                });
+            }
+            if let Some(offset) = self.tangent_offset {
+                map_vec3(vertex, offset, |tangent| {
+                    (transform.matrix3 * tangent).normalize_or_zero()
+                });
+            }
+        }
+        indices.extend(self.indices.iter().map(|index| base_vertex + index));
+    }
+}
+
+fn map_vec3(vertex: &mut [u8], offset: usize, f: impl FnOnce(Vec3) -> Vec3) {
+    let bytes = &mut vertex[offset..offset + 12];
+    let value = Vec3::from_array(bytemuck::pod_read_unaligned(bytes));
+    bytes.copy_from_slice(bytemuck::cast_slice(&f(value).to_array()));
+}
+
+/// The meshes that can be batched dynamically, in the render world.
+#[derive(Resource, Default)]
+pub struct BatchableMeshes2d(HashMap<AssetId<Mesh>, BatchableMesh2d>);
+
+/// Copies the meshes that can be batched dynamically into the render world.
+pub fn extract_batchable_meshes2d(
+    mut batchable_meshes: ResMut<BatchableMeshes2d>,
+    config: Extract<Res<Mesh2dDynamicBatching>>,
+    meshes: Extract<Res<Assets<Mesh>>>,
+    mut events: Extract<EventReader<AssetEvent<Mesh>>>,
+) {
+    let mut update = |id: AssetId<Mesh>| match meshes
+        .get(id)
+        .and_then(|mesh| BatchableMesh2d::new(mesh, config.max_vertices))
+    {
+        Some(mesh) => {
+            batchable_meshes.0.insert(id, mesh);
+        }
+        None => {
+            batchable_meshes.0.remove(&id);
+        }
+    };
+    for event in events.read() {
+        match event {
+            AssetEvent::Added { id } | AssetEvent::Modified { id } | AssetEvent::Removed { id } => {
+                update(*id);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// A dynamic batch, drawn from the shared buffers of the [`DynamicMesh2dBatches`].
+#[derive(Clone, Debug)]
+pub struct DynamicMesh2dBatch {
+    /// The bytes of the vertices in the shared vertex buffer.
+    pub vertex_range: Range<u64>,
+    /// The indices in the shared index buffer, relative to the first vertex.
+    pub index_range: Range<u32>,
+}
+
+/// The dynamic batches of the current frame, by view and entity of their first phase item.
+#[derive(Resource)]
+pub struct DynamicMesh2dBatches {
+    pub vertices: BufferVec<u8>,
+    pub indices: BufferVec<u32>,
+    pub batches: HashMap<(Entity, Entity), DynamicMesh2dBatch>,
+}
+
+impl Default for DynamicMesh2dBatches {
+    fn default() -> Self {
+        let mut vertices = BufferVec::new(BufferUsages::VERTEX);
+        vertices.set_label(Some("mesh2d_dynamic_batch_vertex_buffer"));
+        let mut indices = BufferVec::new(BufferUsages::INDEX);
+        indices.set_label(Some("mesh2d_dynamic_batch_index_buffer"));
+        Self {
+            vertices,
+            indices,
+            batches: HashMap::default(),
+        }
+    }
+}
+
+impl DynamicMesh2dBatches {
+    /// Returns the dynamic batch starting at the phase item of `entity`, in `view`.
+    pub fn get(&self, view: Entity, entity: Entity) -> Option<&DynamicMesh2dBatch> {
+        self.batches.get(&(view, entity))
+    }
+}
+
+/// Merges consecutive draws of [`Transparent2d`] items with the same pipeline and material
+/// into dynamic batches.
+///
+/// Runs after [`batch_and_prepare_render_phase`](bevy_render::batching::batch_and_prepare_render_phase),
+/// so that each draw covers the phase items batched by instancing.
+pub fn batch_dynamic_meshes2d(
+    config: Res<Mesh2dDynamicBatching>,
+    batchable_meshes: Res<BatchableMeshes2d>,
+    render_mesh_instances: Res<RenderMesh2dInstances>,
+    mut gpu_array_buffer: ResMut<GpuArrayBuffer<Mesh2dUniform>>,
+    mut dynamic_batches: ResMut<DynamicMesh2dBatches>,
+    mut views: Query<(Entity, &mut RenderPhase<Transparent2d>)>,
+) {
+    let dynamic_batches = dynamic_batches.as_mut();
+    dynamic_batches.vertices.clear();
+    dynamic_batches.indices.clear();
+    dynamic_batches.batches.clear();
+    if !config.enabled {
+        return;
+    }
+
+    let batch_key = |item: &Transparent2d| {
+        if item.batch_range.is_empty() {
+            return None;
+        }
+        let mesh_instance = render_mesh_instances.get(&item.entity)?;
+        let mesh = batchable_meshes.0.get(&mesh_instance.mesh_asset_id)?;
+        mesh_instance.automatic_batching.then_some(DynamicBatchKey {
+            pipeline: item.pipeline,
+            draw_function: item.draw_function,
+            material_bind_group_id: mesh_instance.material_bind_group_id,
+            layout: &mesh.layout,
+        })
+    };
+    let identity = Mesh2dUniform::from(&Mesh2dTransforms {
+        transform: (&Affine3A::IDENTITY).into(),
+        flags: MeshFlags::empty().bits(),
+    });
+
+    let mut vertices = Vec::new();
+    let mut indices = Vec::new();
+    for (view, mut phase) in &mut views {
+        let items = &mut phase.items;
+        let mut start = 0;
+        while start < items.len() {
+            let draw_len = |item: &Transparent2d| item.batch_range.len().max(1);
+            let key = batch_key(&items[start]);
+            let mut end = start + draw_len(&items[start]);
+            let mut draws = 1;
+            if key.is_some() {
+                while end < items.len() && batch_key(&items[end]) == key {
+                    end += draw_len(&items[end]);
+                    draws += 1;
+                }
+            }
+            let end = end.min(items.len());
+            if draws < 2 {
+                start = end;
+                continue;
+            }
+
+            vertices.clear();
+            indices.clear();
+            for item in &items[start..end] {
+                let mesh_instance = &render_mesh_instances[&item.entity];
+                batchable_meshes.0[&mesh_instance.mesh_asset_id].append_transformed(
+                    &mesh_instance.transforms.transform,
+                    &mut vertices,
+                    &mut indices,
+                );
+            }
+
+            let vertex_start = dynamic_batches.vertices.len() as u64;
+            let index_start = dynamic_batches.indices.len() as u32;
+            dynamic_batches.vertices.extend(vertices.iter().copied());
+            // Vertex buffer offsets must be multiples of 4 bytes.
+            while dynamic_batches.vertices.len() % 4 != 0 {
+                dynamic_batches.vertices.push(0);
+            }
+            dynamic_batches.indices.extend(indices.iter().copied());
+            dynamic_batches.batches.insert(
+                (view, items[start].entity),
+                DynamicMesh2dBatch {
+                    vertex_range: vertex_start..vertex_start + vertices.len() as u64,
+                    index_range: index_start..index_start + indices.len() as u32,
+                },
+            );
+
+            // The batch is drawn as a single instance with an identity transform, while its
+            // range still covers all its phase items so that they are skipped when rendering.
+            let buffer_index = gpu_array_buffer.push(identity.clone());
+            let index = buffer_index.index.get();
+            let first = &mut items[start];
+            first.batch_range = index..index + (end - start) as u32;
+            first.dynamic_offset = buffer_index.dynamic_offset;
+
+            start = end;
+        }
+    }
+}
+
+#[derive(PartialEq)]
+struct DynamicBatchKey<'a> {
+    pipeline: CachedRenderPipelineId,
+    draw_function: DrawFunctionId,
+    material_bind_group_id: Material2dBindGroupId,
+    layout: &'a MeshVertexBufferLayout,
+}
+
+/// Uploads the shared buffers of the [`DynamicMesh2dBatches`].
+pub fn write_dynamic_mesh2d_batches(
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut dynamic_batches: ResMut<DynamicMesh2dBatches>,
+) {
+    dynamic_batches
+        .vertices
+        .write_buffer(&render_device, &render_queue);
+    dynamic_batches
+        .indices
+        .write_buffer(&render_device, &render_queue);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_math::Quat;
+    use bevy_render::{mesh::Indices, render_asset::RenderAssetUsages};
+
+    #[test]
+    fn batched_vertices_are_transformed() {
+        let mut mesh = Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        );
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+        );
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0, 0.0, 1.0]; 3]);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0, 0.0]; 3]);
+        mesh.insert_indices(Indices::U16(vec![0, 1, 2]));
+        let batchable = BatchableMesh2d::new(&mesh, 300).unwrap();
+        assert!(BatchableMesh2d::new(&mesh, 2).is_none());
+
+        let transform = Affine3A::from_scale_rotation_translation(
+            Vec3::splat(2.0),
+            Quat::from_rotation_z(std::f32::consts::FRAC_PI_2),
+            Vec3::new(10.0, 0.0, 0.0),
+        );
+        let mut vertices = Vec::new();
+        let mut indices = Vec::new();
+        batchable.append_transformed(
+            &Affine3::from(&Affine3A::IDENTITY),
+            &mut vertices,
+            &mut indices,
+        );
+        batchable.append_transformed(&(&transform).into(), &mut vertices, &mut indices);
+
+        assert_eq!(indices, vec![0, 1, 2, 3, 4, 5]);
+        let position = |vertex: usize| {
+            let offset = vertex * batchable.stride + batchable.position_offset;
+            Vec3::from_array(bytemuck::pod_read_unaligned(&vertices[offset..offset + 12]))
+        };
+        assert_eq!(position(1), Vec3::X);
+        assert!(position(4).abs_diff_eq(Vec3::new(10.0, 2.0, 0.0), 1e-5));
+        let normal_offset = 4 * batchable.stride + batchable.normal_offset.unwrap();
+        let normal = Vec3::from_array(bytemuck::pod_read_unaligned(
+            &vertices[normal_offset..normal_offset + 12],
+        ));
+        assert!(normal.abs_diff_eq(Vec3::Z, 1e-5));
+    }
+}
@@ -15,6 +15,7 @@ use bevy_render::{
         batch_and_prepare_render_phase, write_batched_instance_buffer, GetBatchData,
         NoAutomaticBatching,
     },
+    extract_resource::ExtractResourcePlugin,
     globals::{GlobalsBuffer, GlobalsUniform},
     mesh::{GpuBufferInfo, Mesh, MeshVertexBufferLayout},
     render_asset::RenderAssets,
@@ -34,6 +35,11 @@ use bevy_utils::EntityHashMap;
 
 use crate::Material2dBindGroupId;
 
+use super::dynamic_batching::{
+    batch_dynamic_meshes2d, extract_batchable_meshes2d, write_dynamic_mesh2d_batches,
+    BatchableMeshes2d, DynamicMesh2dBatches, Mesh2dDynamicBatching,
+};
+
 /// Component for rendering with meshes in the 2d pipeline, usually with a [2d material](crate::Material2d) such as [`ColorMaterial`](crate::ColorMaterial).
 ///
 /// It wraps a [`Handle<Mesh>`] to differentiate from the 3d pipelines which use the handles directly as components
@@ -92,17 +98,33 @@ impl Plugin for Mesh2dRenderPlugin {
         );
         load_internal_asset!(app, MESH2D_SHADER_HANDLE, "mesh2d.wgsl", Shader::from_wgsl);
 
+        app.register_type::<Mesh2dDynamicBatching>()
+            .init_resource::<Mesh2dDynamicBatching>()
+            .add_plugins(ExtractResourcePlugin::<Mesh2dDynamicBatching>::default());
+
         if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app
                 .init_resource::<RenderMesh2dInstances>()
                 .init_resource::<SpecializedMeshPipelines<Mesh2dPipeline>>()
-                .add_systems(ExtractSchedule, extract_mesh2d)
+                .init_resource::<BatchableMeshes2d>()
+                .init_resource::<DynamicMesh2dBatches>()
+                .add_systems(
+                    ExtractSchedule,
+                    (extract_mesh2d, extract_batchable_meshes2d),
+                )
                 .add_systems(
                     Render,
                     (
-                        batch_and_prepare_render_phase::<Transparent2d, Mesh2dPipeline>
+                        (
+                            batch_and_prepare_render_phase::<Transparent2d, Mesh2dPipeline>,
+                            batch_dynamic_meshes2d,
+                        )
+                            .chain()
                             .in_set(RenderSet::PrepareResources),
-                        write_batched_instance_buffer::<Mesh2dPipeline>
+                        (
+                            write_batched_instance_buffer::<Mesh2dPipeline>,
+                            write_dynamic_mesh2d_batches,
+                        )
                             .in_set(RenderSet::PrepareResourcesFlush),
                         prepare_mesh2d_bind_group.in_set(RenderSet::PrepareBindGroups),
                         prepare_mesh2d_view_bind_groups.in_set(RenderSet::PrepareBindGroups),
@@ -665,20 +687,42 @@ impl<P: PhaseItem, const I: usize> RenderCommand<P> for SetMesh2dBindGroup<I> {
 
 pub struct DrawMesh2d;
 impl<P: PhaseItem> RenderCommand<P> for DrawMesh2d {
-    type Param = (SRes<RenderAssets<Mesh>>, SRes<RenderMesh2dInstances>);
-    type ViewQuery = ();
+    type Param = (
+        SRes<RenderAssets<Mesh>>,
+        SRes<RenderMesh2dInstances>,
+        SRes<DynamicMesh2dBatches>,
+    );
+    type ViewQuery = Entity;
     type ItemQuery = ();
 
     #[inline]
     fn render<'w>(
         item: &P,
-        _view: (),
+        view: Entity,
         _item_query: Option<()>,
-        (meshes, render_mesh2d_instances): SystemParamItem<'w, '_, Self::Param>,
+        (meshes, render_mesh2d_instances, dynamic_batches): SystemParamItem<'w, '_, Self::Param>,
         pass: &mut TrackedRenderPass<'w>,
     ) -> RenderCommandResult {
         let meshes = meshes.into_inner();
         let render_mesh2d_instances = render_mesh2d_instances.into_inner();
+        let dynamic_batches = dynamic_batches.into_inner();
+
+        if let Some(batch) = dynamic_batches.get(view, item.entity()) {
+            let (Some(vertex_buffer), Some(index_buffer)) = (
+                dynamic_batches.vertices.buffer(),
+                dynamic_batches.indices.buffer(),
+            ) else {
+                return RenderCommandResult::Failure;
+            };
+            // Dynamic batches are drawn as a single instance with an identity transform.
+            let instance = item.batch_range().start;
+            pass.set_vertex_buffer(0, vertex_buffer.slice(batch.vertex_range.clone()));
+            #[cfg(all(feature = "webgl", target_arch = "wasm32", not(feature = "webgpu")))]
+            pass.set_push_constants(ShaderStages::VERTEX, 0, &(instance as i32).to_le_bytes());
+            pass.set_index_buffer(index_buffer.slice(..), 0, IndexFormat::Uint32);
+            pass.draw_indexed(batch.index_range.clone(), 0, instance..instance + 1);
+            return RenderCommandResult::Success;
+        }
 
         let Some(RenderMesh2dInstance { mesh_asset_id, .. }) =
             render_mesh2d_instances.get(&item.entity())
@@ -1,7 +1,9 @@
 mod color_material;
+mod dynamic_batching;
 mod material;
 mod mesh;
 
 pub use color_material::*;
+pub use dynamic_batching::*;
 pub use material::*;
 pub use mesh::*;
@@ -0,0 +1,101 @@
+use bevy_app::prelude::*;
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::prelude::*;
+use bevy_math::{Vec2, Vec3};
+use bevy_picking::{
+    backend::{camera_ray, HitData, PointerHits},
+    pointer::{PointerId, PointerLocation},
+    PickSet,
+};
+use bevy_render::{camera::Camera, texture::Image, view::ViewVisibility};
+use bevy_transform::components::GlobalTransform;
+use bevy_window::PrimaryWindow;
+
+use crate::{Sprite, TextureAtlas, TextureAtlasLayout};
+
+/// Adds a picking backend for [`Sprite`]s, hit within the bounds of their image.
+///
+/// Added by the [`SpritePlugin`](crate::SpritePlugin) when the `bevy_picking` feature is enabled.
+#[derive(Default)]
+pub struct SpritePickingPlugin;
+
+impl Plugin for SpritePickingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PreUpdate, sprite_picking.in_set(PickSet::Backend));
+    }
+}
+
+/// Sends the [`PointerHits`] of the visible sprites under each pointer, for each camera.
+#[allow(clippy::too_many_arguments)]
+pub fn sprite_picking(
+    pointers: Query<(&PointerId, &PointerLocation)>,
+    cameras: Query<(Entity, &Camera, &GlobalTransform)>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    images: Res<Assets<Image>>,
+    texture_atlas_layouts: Res<Assets<TextureAtlasLayout>>,
+    sprites: Query<(
+        Entity,
+        &Sprite,
+        &Handle<Image>,
+        Option<&TextureAtlas>,
+        &GlobalTransform,
+        &ViewVisibility,
+    )>,
+    mut output: EventWriter<PointerHits>,
+) {
+    let primary_window = primary_window.iter().next();
+    for (pointer_id, pointer_location) in &pointers {
+        let Some(location) = pointer_location.location() else {
+            continue;
+        };
+        for (camera_entity, camera, camera_transform) in &cameras {
+            let Some(ray) = camera_ray(location, camera, camera_transform, primary_window) else {
+                continue;
+            };
+
+            let picks: Vec<_> = sprites
+                .iter()
+                .filter(|(.., view_visibility)| view_visibility.get())
+                .filter_map(|(entity, sprite, image, atlas, transform, _)| {
+                    let size = sprite
+                        .custom_size
+                        .or_else(|| sprite.rect.map(|rect| rect.size()))
+                        .or_else(|| {
+                            atlas
+                                .and_then(|atlas| atlas.texture_rect(&texture_atlas_layouts))
+                                .map(|rect| rect.size())
+                        })
+                        .or_else(|| images.get(image).map(|image| image.size_f32()))?;
+
+                    // Find where the ray crosses the plane of the sprite, in its local space.
+                    let world_to_local = transform.affine().inverse();
+                    let origin = world_to_local.transform_point3(ray.origin);
+                    let direction = world_to_local.transform_vector3(*ray.direction);
+                    if direction.z.abs() < f32::EPSILON {
+                        return None;
+                    }
+                    let t = -origin.z / direction.z;
+                    if t < 0.0 {
+                        return None;
+                    }
+                    let local_point = (origin + direction * t).truncate();
+
+                    // The sprite covers `[-0.5, 0.5]` around its anchor, scaled by its size.
+                    let anchored = local_point / size + sprite.anchor.as_vec();
+                    if anchored.abs().cmpgt(Vec2::splat(0.5)).any() {
+                        return None;
+                    }
+
+                    let position = transform.transform_point(local_point.extend(0.0));
+                    let depth = ray.origin.distance(position);
+                    let normal = transform.affine().transform_vector3(Vec3::Z).normalize();
+                    let hit = HitData::new(camera_entity, depth, Some(position), Some(normal));
+                    Some((entity, hit))
+                })
+                .collect();
+            if !picks.is_empty() {
+                output.send(PointerHits::new(*pointer_id, picks, camera.order as f32));
+            }
+        }
+    }
+}
@@ -0,0 +1,487 @@
+//! Vector graphics: [`VectorPath`] assets made of filled and stroked bezier outlines.
+//!
+//! A [`VectorPath`] can be drawn in two ways:
+//! - tessellated into a triangle [`Mesh`] with [`VectorPath::tessellate`], drawn as a
+//!   [`Mesh2dHandle`](crate::Mesh2dHandle) with a [`ColorMaterial`](crate::ColorMaterial),
+//! - rasterized into an [`Image`] with analytic anti-aliasing with [`VectorPath::rasterize`],
+//!   which is how `bevy_ui` draws them as the content of UI nodes.
+//!
+//! SVG files are loaded as [`VectorPath`]s by the [`SvgLoader`], which also adds their tessellated
+//! mesh as the `mesh` labeled asset, e.g. `"icons/star.svg#mesh"`.
+
+mod rasterize;
+mod svg;
+mod tessellate;
+
+pub use svg::*;
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{Asset, AssetApp};
+use bevy_math::{Rect, UVec2, Vec2};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect, TypePath};
+use bevy_render::{
+    color::Color,
+    mesh::{Indices, Mesh},
+    render_asset::RenderAssetUsages,
+    render_resource::{Extent3d, PrimitiveTopology, TextureDimension, TextureFormat},
+    texture::Image,
+};
+
+use rasterize::Rasterizer;
+
+/// Adds support for [`VectorPath`] assets and the [`SvgLoader`].
+#[derive(Default)]
+pub struct VectorPathPlugin;
+
+impl Plugin for VectorPathPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<VectorPath>()
+            .register_type::<FillRule>()
+            .init_asset_loader::<SvgLoader>();
+    }
+}
+
+/// A vector graphic: a list of [`VectorShape`]s painted in order, over a view box.
+///
+/// The coordinates of the outlines have their y axis pointing up, like the rest of Bevy's 2D
+/// coordinates.
+///
+/// # Example
+/// ```
+/// # use bevy_sprite::{VectorFill, VectorPath, VectorShape, VectorStroke, PathOutline};
+/// # use bevy_math::prelude::*;
+/// # use bevy_render::prelude::*;
+/// let mut outline = PathOutline::new();
+/// outline
+///     .move_to(Vec2::new(-40., -20.))
+///     .cubic_to(Vec2::new(-20., 40.), Vec2::new(20., 40.), Vec2::new(40., -20.))
+///     .close();
+/// let path = VectorPath::new(Rect::new(-50., -50., 50., 50.)).with_shape(VectorShape {
+///     outline,
+///     fill: Some(VectorFill::color(Color::ORANGE)),
+///     stroke: Some(VectorStroke::new(Color::BLACK, 4.)),
+/// });
+/// let mesh = path.tessellate(0.1);
+/// ```
+#[derive(Asset, TypePath, Clone, Debug, Default, PartialEq)]
+pub struct VectorPath {
+    /// The shapes of the graphic, painted in order.
+    pub shapes: Vec<VectorShape>,
+    /// The area of the graphic, which is mapped to the whole image when rasterized.
+    pub view_box: Rect,
+}
+
+impl VectorPath {
+    /// Creates a graphic without shapes over `view_box`.
+    pub fn new(view_box: Rect) -> Self {
+        Self {
+            shapes: Vec::new(),
+            view_box,
+        }
+    }
+
+    /// Adds a shape painted over the previous ones.
+    pub fn add_shape(&mut self, shape: VectorShape) {
+        self.shapes.push(shape);
+    }
+
+    /// Returns the graphic with `shape` painted over its previous shapes.
+    #[must_use]
+    pub fn with_shape(mut self, shape: VectorShape) -> Self {
+        self.add_shape(shape);
+        self
+    }
+
+    /// Tessellates the fills and strokes of the graphic into a triangle [`Mesh`] with vertex
+    /// colors.
+    ///
+    /// The curves are flattened into segments deviating at most `tolerance` from them, in the
+    /// units of the outlines. The vertices are in the plane `z = 0`.
+    pub fn tessellate(&self, tolerance: f32) -> Mesh {
+        let mut positions: Vec<[f32; 3]> = Vec::new();
+        let mut colors: Vec<[f32; 4]> = Vec::new();
+        let mut indices: Vec<u32> = Vec::new();
+        let mut add_polygon = |points: &[Vec2], color: [f32; 4]| {
+            let start = positions.len() as u32;
+            positions.extend(points.iter().map(|point| [point.x, point.y, 0.]));
+            colors.extend(std::iter::repeat(color).take(points.len()));
+            for i in 1..points.len() as u32 - 1 {
+                indices.extend([start, start + i, start + i + 1]);
+            }
+        };
+
+        for shape in &self.shapes {
+            let polylines = shape.outline.flatten(tolerance);
+            if let Some(fill) = &shape.fill {
+                let contours: Vec<_> = polylines.iter().map(|line| &line.points[..]).collect();
+                let color = fill.color.as_linear_rgba_f32();
+                tessellate::fill_trapezoids(&contours, fill.rule, |trapezoid| {
+                    add_polygon(&trapezoid, color);
+                });
+            }
+            if let Some(stroke) = &shape.stroke {
+                let color = stroke.color.as_linear_rgba_f32();
+                for polyline in &polylines {
+                    tessellate::stroke_triangles(polyline, stroke.width, |triangle| {
+                        add_polygon(&triangle, color);
+                    });
+                }
+            }
+        }
+
+        Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_COLOR, colors)
+        .with_inserted_indices(Indices::U32(indices))
+    }
+
+    /// Rasterizes the graphic into an [`Image`] of `size` pixels covering the view box, with the
+    /// exact coverage of each pixel as anti-aliasing.
+    ///
+    /// The colors are blended in sRGB space and stored with straight alpha in an
+    /// [`TextureFormat::Rgba8UnormSrgb`] texture.
+    pub fn rasterize(&self, size: UVec2) -> Image {
+        let size = size.max(UVec2::ONE);
+        let view_size = self.view_box.size().max(Vec2::splat(f32::EPSILON));
+        let scale = size.as_vec2() / view_size;
+        let view_box = self.view_box;
+        let to_pixels = |point: Vec2| {
+            Vec2::new(
+                (point.x - view_box.min.x) * scale.x,
+                (view_box.max.y - point.y) * scale.y,
+            )
+        };
+        // A quarter of a pixel is not visible once anti-aliased.
+        let tolerance = 0.25 / scale.max_element();
+
+        let mut rasterizer = Rasterizer::new(size.x as usize, size.y as usize);
+        let mut pixels = vec![[0.; 4]; (size.x * size.y) as usize];
+        let mut paint = |rasterizer: &Rasterizer, rule: FillRule, color: Color| {
+            let [r, g, b, a] = color.as_rgba_f32();
+            rasterizer.for_each_coverage(rule, |index, coverage| {
+                let alpha = a * coverage;
+                let pixel = &mut pixels[index];
+                let transparency = 1. - alpha;
+                *pixel = [
+                    r * alpha + pixel[0] * transparency,
+                    g * alpha + pixel[1] * transparency,
+                    b * alpha + pixel[2] * transparency,
+                    alpha + pixel[3] * transparency,
+                ];
+            });
+        };
+
+        for shape in &self.shapes {
+            let polylines = shape.outline.flatten(tolerance);
+            if let Some(fill) = &shape.fill {
+                rasterizer.clear();
+                for polyline in &polylines {
+                    let points: Vec<_> = polyline.points.iter().copied().map(to_pixels).collect();
+                    rasterizer.add_contour(&points);
+                }
+                paint(&rasterizer, fill.rule, fill.color);
+            }
+            if let Some(stroke) = &shape.stroke {
+                rasterizer.clear();
+                for polyline in &polylines {
+                    tessellate::stroke_triangles(polyline, stroke.width, |triangle| {
+                        rasterizer.add_contour(&triangle.map(to_pixels));
+                    });
+                }
+                // The triangles of a stroke overlap at its joins, count them once.
+                paint(&rasterizer, FillRule::NonZero, stroke.color);
+            }
+        }
+
+        let data = pixels
+            .into_iter()
+            .flat_map(|[r, g, b, a]| {
+                // Unpremultiply the blended colors.
+                let unpremultiply = if a > 0. { a.recip() } else { 0. };
+                [r * unpremultiply, g * unpremultiply, b * unpremultiply, a]
+                    .map(|channel| (channel.clamp(0., 1.) * 255.).round() as u8)
+            })
+            .collect();
+        Image::new(
+            Extent3d {
+                width: size.x,
+                height: size.y,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::default(),
+        )
+    }
+}
+
+/// A filled and/or stroked outline of a [`VectorPath`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct VectorShape {
+    /// The outline of the shape.
+    pub outline: PathOutline,
+    /// How the inside of the outline is painted, if it is.
+    pub fill: Option<VectorFill>,
+    /// How the outline itself is painted, if it is.
+    pub stroke: Option<VectorStroke>,
+}
+
+/// How the inside of a [`VectorShape`] is painted.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VectorFill {
+    /// The color of the fill.
+    pub color: Color,
+    /// How the inside of overlapping or nested contours is determined.
+    pub rule: FillRule,
+}
+
+impl VectorFill {
+    /// A fill of `color` with the [`FillRule::NonZero`] rule.
+    pub const fn color(color: Color) -> Self {
+        Self {
+            color,
+            rule: FillRule::NonZero,
+        }
+    }
+}
+
+/// How the inside of the contours of a [`VectorShape`] is determined, as in SVG.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Default)]
+pub enum FillRule {
+    /// A point is inside if the contours wind around it a non-zero number of times, accounting
+    /// for their direction.
+    #[default]
+    NonZero,
+    /// A point is inside if a ray from it crosses the contours an odd number of times.
+    EvenOdd,
+}
+
+impl FillRule {
+    /// Returns `true` if a point the contours wind around `winding` times is inside.
+    fn is_inside(self, winding: i32) -> bool {
+        match self {
+            FillRule::NonZero => winding != 0,
+            FillRule::EvenOdd => winding % 2 != 0,
+        }
+    }
+}
+
+/// How the outline of a [`VectorShape`] is painted.
+///
+/// The segments of a stroke are joined with bevels, and its ends are cut flat.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VectorStroke {
+    /// The color of the stroke.
+    pub color: Color,
+    /// The width of the stroke, in the units of the outline.
+    pub width: f32,
+}
+
+impl VectorStroke {
+    /// A stroke of `color`, `width` units wide.
+    pub const fn new(color: Color, width: f32) -> Self {
+        Self { color, width }
+    }
+}
+
+/// A drawing command of a [`PathOutline`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PathCommand {
+    /// Starts a new contour at a point.
+    MoveTo(Vec2),
+    /// Adds a straight segment to a point.
+    LineTo(Vec2),
+    /// Adds a quadratic bezier curve to a point.
+    QuadraticTo { control: Vec2, to: Vec2 },
+    /// Adds a cubic bezier curve to a point.
+    CubicTo {
+        control1: Vec2,
+        control2: Vec2,
+        to: Vec2,
+    },
+    /// Closes the current contour with a straight segment to its start.
+    Close,
+}
+
+/// The contours of a [`VectorShape`], made of straight segments and bezier curves.
+///
+/// Contours are always closed when filled, and only closed by [`PathOutline::close`] when
+/// stroked.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PathOutline {
+    commands: Vec<PathCommand>,
+}
+
+impl PathOutline {
+    /// Creates an outline without contours.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a closed rectangle outline.
+    pub fn rect(rect: Rect) -> Self {
+        let mut outline = Self::new();
+        outline
+            .move_to(rect.min)
+            .line_to(Vec2::new(rect.max.x, rect.min.y))
+            .line_to(rect.max)
+            .line_to(Vec2::new(rect.min.x, rect.max.y))
+            .close();
+        outline
+    }
+
+    /// Creates a closed ellipse outline, made of four cubic bezier curves.
+    pub fn ellipse(center: Vec2, half_size: Vec2) -> Self {
+        // The distance of the control points approximating a quarter circle.
+        const KAPPA: f32 = 0.552_284_8;
+        let (x, y) = (Vec2::X * half_size.x, Vec2::Y * half_size.y);
+        let (kx, ky) = (x * KAPPA, y * KAPPA);
+        let mut outline = Self::new();
+        outline
+            .move_to(center + x)
+            .cubic_to(center + x + ky, center + y + kx, center + y)
+            .cubic_to(center + y - kx, center - x + ky, center - x)
+            .cubic_to(center - x - ky, center - y - kx, center - y)
+            .cubic_to(center - y + kx, center + x - ky, center + x)
+            .close();
+        outline
+    }
+
+    /// The commands drawing the outline.
+    pub fn commands(&self) -> &[PathCommand] {
+        &self.commands
+    }
+
+    /// Returns `true` if the outline has no commands.
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Adds a command to the outline.
+    pub fn push(&mut self, command: PathCommand) -> &mut Self {
+        self.commands.push(command);
+        self
+    }
+
+    /// Starts a new contour at `to`.
+    pub fn move_to(&mut self, to: Vec2) -> &mut Self {
+        self.push(PathCommand::MoveTo(to))
+    }
+
+    /// Adds a straight segment to `to`.
+    pub fn line_to(&mut self, to: Vec2) -> &mut Self {
+        self.push(PathCommand::LineTo(to))
+    }
+
+    /// Adds a quadratic bezier curve to `to`.
+    pub fn quadratic_to(&mut self, control: Vec2, to: Vec2) -> &mut Self {
+        self.push(PathCommand::QuadraticTo { control, to })
+    }
+
+    /// Adds a cubic bezier curve to `to`.
+    pub fn cubic_to(&mut self, control1: Vec2, control2: Vec2, to: Vec2) -> &mut Self {
+        self.push(PathCommand::CubicTo {
+            control1,
+            control2,
+            to,
+        })
+    }
+
+    /// Closes the current contour with a straight segment to its start.
+    pub fn close(&mut self) -> &mut Self {
+        self.push(PathCommand::Close)
+    }
+
+    /// Flattens the curves of the contours into straight segments deviating at most
+    /// `tolerance` from them.
+    pub fn flatten(&self, tolerance: f32) -> Vec<Polyline> {
+        let tolerance = tolerance.max(f32::EPSILON);
+        let mut polylines = Vec::new();
+        let mut current = Polyline::default();
+        let mut finish = |current: &mut Polyline| {
+            let polyline = std::mem::take(current);
+            if polyline.points.len() >= 2 {
+                polylines.push(polyline);
+            }
+        };
+
+        for command in &self.commands {
+            let from = current.points.last().copied().unwrap_or(Vec2::ZERO);
+            match *command {
+                PathCommand::MoveTo(to) => {
+                    finish(&mut current);
+                    current.points.push(to);
+                }
+                PathCommand::LineTo(to) => current.push(to),
+                PathCommand::QuadraticTo { control, to } => {
+                    // Wang's formula for the number of segments.
+                    let deviation = (from - 2. * control + to).length();
+                    let segments = segment_count(0.25 * deviation / tolerance);
+                    for i in 1..=segments {
+                        let t = i as f32 / segments as f32;
+                        let u = 1. - t;
+                        current.push(u * u * from + 2. * u * t * control + t * t * to);
+                    }
+                }
+                PathCommand::CubicTo {
+                    control1,
+                    control2,
+                    to,
+                } => {
+                    let deviation = (from - 2. * control1 + control2)
+                        .length()
+                        .max((control1 - 2. * control2 + to).length());
+                    let segments = segment_count(0.75 * deviation / tolerance);
+                    for i in 1..=segments {
+                        let t = i as f32 / segments as f32;
+                        let u = 1. - t;
+                        current.push(
+                            u * u * u * from
+                                + 3. * u * u * t * control1
+                                + 3. * u * t * t * control2
+                                + t * t * t * to,
+                        );
+                    }
+                }
+                PathCommand::Close => {
+                    let start = current.points.first().copied();
+                    if current.points.len() >= 2 && start == current.points.last().copied() {
+                        current.points.pop();
+                    }
+                    current.closed = true;
+                    finish(&mut current);
+                    // Commands after a close continue from the start of the closed contour.
+                    current.points.extend(start);
+                }
+            }
+        }
+        finish(&mut current);
+        polylines
+    }
+}
+
+fn segment_count(squared_segments: f32) -> usize {
+    (squared_segments.sqrt().ceil() as usize).clamp(1, 256)
+}
+
+/// A contour of a [`PathOutline`] flattened into straight segments.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Polyline {
+    /// The points of the contour, without repeating the first one at the end when closed.
+    pub points: Vec<Vec2>,
+    /// Whether the last point is connected to the first one when stroked.
+    pub closed: bool,
+}
+
+impl Polyline {
+    fn push(&mut self, point: Vec2) {
+        if self.points.last() != Some(&point) {
+            self.points.push(point);
+        }
+    }
+}
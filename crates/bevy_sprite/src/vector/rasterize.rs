@@ -0,0 +1,190 @@
+//! Scanline rasterization of contours with analytic anti-aliasing.
+
+use bevy_math::Vec2;
+
+use super::FillRule;
+
+/// Accumulates the signed area covered by contours in each pixel of an image.
+///
+/// Each segment adds, to the pixels of each row it crosses, the area of the row between the
+/// segment and the right of the pixel. Summing the accumulation along a row then gives the
+/// exact winding-weighted coverage of each pixel, which is how `font-rs` rasterizes glyphs.
+pub(crate) struct Rasterizer {
+    width: usize,
+    height: usize,
+    /// The accumulation of each row, with two extra columns for the segments on the right edge.
+    accumulation: Vec<f32>,
+}
+
+impl Rasterizer {
+    pub(crate) fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            accumulation: vec![0.; (width + 2) * height],
+        }
+    }
+
+    fn stride(&self) -> usize {
+        self.width + 2
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.accumulation.fill(0.);
+    }
+
+    /// Adds a closed contour, in pixels with the y axis pointing down.
+    pub(crate) fn add_contour(&mut self, points: &[Vec2]) {
+        for (i, &start) in points.iter().enumerate() {
+            self.add_segment(start, points[(i + 1) % points.len()]);
+        }
+    }
+
+    fn add_segment(&mut self, start: Vec2, end: Vec2) {
+        if start.y == end.y || !start.is_finite() || !end.is_finite() {
+            return;
+        }
+        let (direction, top, bottom) = if start.y < end.y {
+            (1., start, end)
+        } else {
+            (-1., end, start)
+        };
+        let dx_dy = (bottom.x - top.x) / (bottom.y - top.y);
+        let y_start = top.y.max(0.);
+        let y_end = bottom.y.min(self.height as f32);
+        if y_start >= y_end {
+            return;
+        }
+
+        let width = self.width as f32;
+        let stride = self.stride();
+        let mut x = top.x + (y_start - top.y) * dx_dy;
+        for row in y_start as usize..y_end.ceil() as usize {
+            let dy = ((row + 1) as f32).min(y_end) - (row as f32).max(y_start);
+            let x_next = x + dx_dy * dy;
+            let area = dy * direction;
+            let line = &mut self.accumulation[row * stride..(row + 1) * stride];
+            // Anything left of the image covers its first column.
+            let x0 = x.min(x_next).clamp(0., width);
+            let x1 = x.max(x_next).clamp(0., width);
+            let x0_floor = x0.floor();
+            let x0_index = x0_floor as usize;
+            let x1_ceil = x1.ceil();
+            let x1_index = x1_ceil as usize;
+
+            if x1_index <= x0_index + 1 {
+                // The segment crosses a single pixel of the row.
+                let middle = 0.5 * (x0 + x1) - x0_floor;
+                line[x0_index] += area * (1. - middle);
+                line[x0_index + 1] += area * middle;
+            } else {
+                let inverse_width = (x1 - x0).recip();
+                let x0_fract = x0 - x0_floor;
+                let first = 0.5 * inverse_width * (1. - x0_fract) * (1. - x0_fract);
+                let x1_fract = x1 - x1_ceil + 1.;
+                let last = 0.5 * inverse_width * x1_fract * x1_fract;
+                line[x0_index] += area * first;
+                if x1_index == x0_index + 2 {
+                    line[x0_index + 1] += area * (1. - first - last);
+                } else {
+                    let second = inverse_width * (1.5 - x0_fract);
+                    line[x0_index + 1] += area * (second - first);
+                    for value in &mut line[x0_index + 2..x1_index - 1] {
+                        *value += area * inverse_width;
+                    }
+                    let before_last = second + (x1_index - x0_index - 3) as f32 * inverse_width;
+                    line[x1_index - 1] += area * (1. - before_last - last);
+                }
+                line[x1_index] += area * last;
+            }
+            x = x_next;
+        }
+    }
+
+    /// Calls `f` with the index and the coverage of each covered pixel, in row-major order.
+    pub(crate) fn for_each_coverage(&self, rule: FillRule, mut f: impl FnMut(usize, f32)) {
+        for (row, line) in self.accumulation.chunks_exact(self.stride()).enumerate() {
+            let mut winding = 0.;
+            for (column, value) in line[..self.width].iter().enumerate() {
+                winding += value;
+                let coverage = match rule {
+                    FillRule::NonZero => f32::abs(winding).min(1.),
+                    FillRule::EvenOdd => {
+                        // A triangle wave: covered by one layer, uncovered by two.
+                        let layers = f32::abs(winding) % 2.;
+                        if layers > 1. {
+                            2. - layers
+                        } else {
+                            layers
+                        }
+                    }
+                };
+                if coverage > 1. / 512. {
+                    f(row * self.width + column, coverage);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coverage(rasterizer: &Rasterizer, rule: FillRule) -> Vec<f32> {
+        let mut coverage = vec![0.; rasterizer.width * rasterizer.height];
+        rasterizer.for_each_coverage(rule, |index, value| coverage[index] = value);
+        coverage
+    }
+
+    fn square(min: f32, max: f32) -> [Vec2; 4] {
+        [
+            Vec2::new(min, min),
+            Vec2::new(max, min),
+            Vec2::new(max, max),
+            Vec2::new(min, max),
+        ]
+    }
+
+    #[test]
+    fn partial_coverage() {
+        let mut rasterizer = Rasterizer::new(6, 6);
+        rasterizer.add_contour(&square(1.5, 4.5));
+        let coverage = coverage(&rasterizer, FillRule::NonZero);
+
+        let total: f32 = coverage.iter().sum();
+        assert!((total - 9.).abs() < 1e-4);
+        assert_eq!(coverage[0], 0.);
+        // The corners cover a quarter of their pixel, the sides half of theirs.
+        assert!((coverage[6 + 1] - 0.25).abs() < 1e-5);
+        assert!((coverage[6 + 2] - 0.5).abs() < 1e-5);
+        assert!((coverage[2 * 6 + 2] - 1.).abs() < 1e-5);
+    }
+
+    #[test]
+    fn diagonal_coverage() {
+        // A triangle cutting the pixels of its diagonal in half.
+        let mut rasterizer = Rasterizer::new(4, 4);
+        rasterizer.add_contour(&[Vec2::ZERO, Vec2::new(4., 4.), Vec2::new(0., 4.)]);
+        let coverage = coverage(&rasterizer, FillRule::NonZero);
+        for i in 0..4 {
+            assert!((coverage[i * 4 + i] - 0.5).abs() < 1e-5);
+        }
+        let total: f32 = coverage.iter().sum();
+        assert!((total - 8.).abs() < 1e-4);
+    }
+
+    #[test]
+    fn fill_rules_and_clipping() {
+        let mut rasterizer = Rasterizer::new(4, 4);
+        // The outer square goes past the edges of the image.
+        rasterizer.add_contour(&square(-2., 6.));
+        rasterizer.add_contour(&square(1., 3.));
+        let even_odd = coverage(&rasterizer, FillRule::EvenOdd);
+        let non_zero = coverage(&rasterizer, FillRule::NonZero);
+        assert_eq!(even_odd[0], 1.);
+        assert_eq!(even_odd[4 + 1], 0.);
+        assert_eq!(non_zero[4 + 1], 1.);
+        assert_eq!(non_zero[3 * 4 + 3], 1.);
+    }
+}
@@ -0,0 +1,936 @@
+//! Loading of a subset of SVG into [`VectorPath`]s.
+
+use std::f32::consts::{PI, TAU};
+
+use bevy_asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext};
+use bevy_math::{Affine2, Rect, Vec2};
+use bevy_render::color::Color;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::{
+    FillRule, PathCommand, PathOutline, VectorFill, VectorPath, VectorShape, VectorStroke,
+};
+
+/// Loads `.svg` files as [`VectorPath`]s, and their tessellation as the `mesh` labeled
+/// [`Mesh`](bevy_render::mesh::Mesh) asset.
+///
+/// The supported subset of SVG is:
+/// - the `path`, `rect`, `circle`, `ellipse`, `line`, `polyline` and `polygon` shapes, with the
+///   whole path data syntax, rounded rectangles excepted,
+/// - `g` groups, and `transform` attributes,
+/// - solid colors for the `fill` and `stroke`, with their opacities, the `fill-rule` and the
+///   `stroke-width`, as attributes or in `style` attributes.
+///
+/// Gradients, patterns, text, clipping, masks, and `<style>` sheets are ignored. The `opacity`
+/// of groups is applied to each of their shapes.
+///
+/// The view box of the loaded path is centered on the origin, with the y axis pointing up.
+#[derive(Default)]
+pub struct SvgLoader;
+
+/// Settings of the [`SvgLoader`].
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct SvgLoaderSettings {
+    /// The maximum distance between the curves and the segments of the `mesh` labeled asset, as
+    /// a fraction of the largest side of the view box.
+    ///
+    /// Defaults to `0.001`.
+    pub tolerance: f32,
+}
+
+impl Default for SvgLoaderSettings {
+    fn default() -> Self {
+        Self { tolerance: 0.001 }
+    }
+}
+
+/// Possible errors that can be produced by [`SvgLoader`]
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum SvgLoaderError {
+    /// An [IO](std::io) Error
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// The file is not valid UTF-8.
+    #[error(transparent)]
+    Utf8(#[from] std::str::Utf8Error),
+    /// The file has no `svg` element.
+    #[error("the file has no `svg` element")]
+    MissingSvgElement,
+    /// The value of an attribute could not be parsed.
+    #[error("invalid value for the `{name}` attribute: `{value}`")]
+    InvalidAttribute { name: String, value: String },
+}
+
+impl AssetLoader for SvgLoader {
+    type Asset = VectorPath;
+    type Settings = SvgLoaderSettings;
+    type Error = SvgLoaderError;
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        settings: &'a SvgLoaderSettings,
+        load_context: &'a mut LoadContext,
+    ) -> bevy_utils::BoxedFuture<'a, Result<VectorPath, Self::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            let path = parse_svg(std::str::from_utf8(&bytes)?)?;
+            let tolerance = settings.tolerance * path.view_box.size().max_element();
+            load_context.add_labeled_asset("mesh".to_string(), path.tessellate(tolerance));
+            Ok(path)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["svg"]
+    }
+}
+
+/// Parses the shapes of an SVG document into a [`VectorPath`].
+///
+/// See [`SvgLoader`] for the supported subset of SVG.
+pub fn parse_svg(source: &str) -> Result<VectorPath, SvgLoaderError> {
+    let mut path = None;
+    let mut styles = vec![Style::default()];
+
+    for tag in Tags(source) {
+        let (name, attributes, has_content) = match tag {
+            Tag::Open { name, attributes } => (name, attributes, true),
+            Tag::Empty { name, attributes } => (name, attributes, false),
+            Tag::Close => {
+                if styles.len() > 1 {
+                    styles.pop();
+                }
+                continue;
+            }
+        };
+
+        let mut style = styles.last().cloned().unwrap_or_default();
+        if name == "svg" && path.is_none() {
+            let (view_box, transform) = root_view_box(&attributes)?;
+            path = Some(VectorPath::new(view_box));
+            style.transform = transform;
+        }
+        style.apply(&attributes)?;
+        // The content of these elements is only drawn when referenced, which isn't supported.
+        if matches!(
+            name,
+            "defs" | "symbol" | "clipPath" | "mask" | "pattern" | "marker"
+        ) {
+            style.hidden = true;
+        }
+        if has_content {
+            styles.push(style.clone());
+        }
+
+        let Some(path) = path.as_mut() else {
+            continue;
+        };
+        if style.hidden {
+            continue;
+        }
+        let Some((outline, can_fill)) = shape_outline(name, &attributes)? else {
+            continue;
+        };
+        let transformed = transform_outline(&outline, style.transform);
+        let fill = style.fill.filter(|_| can_fill).map(|color| VectorFill {
+            color: with_opacity(color, style.fill_opacity * style.opacity),
+            rule: style.fill_rule,
+        });
+        let stroke = style
+            .stroke
+            .filter(|_| style.stroke_width > 0.)
+            .map(|color| {
+                VectorStroke::new(
+                    with_opacity(color, style.stroke_opacity * style.opacity),
+                    style.stroke_width * style.transform.matrix2.determinant().abs().sqrt(),
+                )
+            });
+        if fill.is_some() || stroke.is_some() {
+            path.add_shape(VectorShape {
+                outline: transformed,
+                fill,
+                stroke,
+            });
+        }
+    }
+
+    path.ok_or(SvgLoaderError::MissingSvgElement)
+}
+
+/// The inherited presentation attributes of an element.
+#[derive(Clone, Debug)]
+struct Style {
+    /// From the user space of the element to the space of the [`VectorPath`].
+    transform: Affine2,
+    fill: Option<Color>,
+    fill_opacity: f32,
+    fill_rule: FillRule,
+    stroke: Option<Color>,
+    stroke_opacity: f32,
+    stroke_width: f32,
+    opacity: f32,
+    /// Whether the element is not rendered, like the content of `defs`.
+    hidden: bool,
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Self {
+            transform: Affine2::IDENTITY,
+            fill: Some(Color::BLACK),
+            fill_opacity: 1.,
+            fill_rule: FillRule::NonZero,
+            stroke: None,
+            stroke_opacity: 1.,
+            stroke_width: 1.,
+            opacity: 1.,
+            hidden: false,
+        }
+    }
+}
+
+impl Style {
+    fn apply(&mut self, attributes: &[(&str, &str)]) -> Result<(), SvgLoaderError> {
+        let declarations = attributes
+            .iter()
+            .filter(|(name, _)| *name == "style")
+            .flat_map(|(_, style)| style.split(';'))
+            .filter_map(|declaration| declaration.split_once(':'))
+            .map(|(name, value)| (name.trim(), value.trim()));
+        // Declarations of the `style` attribute override the presentation attributes.
+        let properties: Vec<_> = attributes.iter().copied().chain(declarations).collect();
+
+        for (name, value) in properties {
+            let invalid = || SvgLoaderError::InvalidAttribute {
+                name: name.to_string(),
+                value: value.to_string(),
+            };
+            match name {
+                "transform" => {
+                    self.transform *= parse_transform(value).ok_or_else(invalid)?;
+                }
+                "fill" => self.fill = parse_paint(value, self.fill),
+                "stroke" => self.stroke = parse_paint(value, self.stroke),
+                "fill-opacity" => self.fill_opacity = parse_number(value).ok_or_else(invalid)?,
+                "stroke-opacity" => {
+                    self.stroke_opacity = parse_number(value).ok_or_else(invalid)?;
+                }
+                "opacity" => self.opacity *= parse_number(value).ok_or_else(invalid)?,
+                "stroke-width" => self.stroke_width = parse_length(value).ok_or_else(invalid)?,
+                "fill-rule" => {
+                    self.fill_rule = match value {
+                        "evenodd" => FillRule::EvenOdd,
+                        _ => FillRule::NonZero,
+                    };
+                }
+                "display" if value == "none" => self.hidden = true,
+                "visibility" if value == "hidden" => self.hidden = true,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The view box of the root `svg` element, and the transform from its user space to the
+/// centered, y up space of the [`VectorPath`].
+fn root_view_box(attributes: &[(&str, &str)]) -> Result<(Rect, Affine2), SvgLoaderError> {
+    let attribute = |name: &str| attributes.iter().find(|(n, _)| *n == name).map(|(_, v)| *v);
+    let length = |name: &str| attribute(name).and_then(parse_length);
+    let view_box = match attribute("viewBox") {
+        Some(value) => {
+            let invalid = || SvgLoaderError::InvalidAttribute {
+                name: "viewBox".to_string(),
+                value: value.to_string(),
+            };
+            let numbers = parse_numbers(value).ok_or_else(invalid)?;
+            let &[x, y, width, height] = numbers.as_slice() else {
+                return Err(invalid());
+            };
+            Rect::new(x, y, x + width, y + height)
+        }
+        None => Rect::new(
+            0.,
+            0.,
+            length("width").unwrap_or(100.),
+            length("height").unwrap_or(100.),
+        ),
+    };
+    let size = Vec2::new(
+        length("width").unwrap_or(view_box.width()),
+        length("height").unwrap_or(view_box.height()),
+    );
+    let transform = Affine2::from_scale(Vec2::new(1., -1.))
+        * Affine2::from_translation(-size * 0.5)
+        * Affine2::from_scale(size / view_box.size().max(Vec2::splat(f32::EPSILON)))
+        * Affine2::from_translation(-view_box.min);
+    Ok((Rect::from_center_size(Vec2::ZERO, size), transform))
+}
+
+/// The outline of a shape element in its user space, and whether it can be filled.
+fn shape_outline(
+    name: &str,
+    attributes: &[(&str, &str)],
+) -> Result<Option<(PathOutline, bool)>, SvgLoaderError> {
+    let attribute = |name: &str| attributes.iter().find(|(n, _)| *n == name).map(|(_, v)| *v);
+    let length = |name: &str| attribute(name).and_then(parse_length).unwrap_or(0.);
+    let invalid = |name: &str| SvgLoaderError::InvalidAttribute {
+        name: name.to_string(),
+        value: attribute(name).unwrap_or_default().to_string(),
+    };
+
+    let outline = match name {
+        "path" => {
+            let Some(data) = attribute("d") else {
+                return Ok(None);
+            };
+            parse_path_data(data).ok_or_else(|| invalid("d"))?
+        }
+        "rect" => {
+            let min = Vec2::new(length("x"), length("y"));
+            let size = Vec2::new(length("width"), length("height"));
+            if size.cmple(Vec2::ZERO).any() {
+                return Ok(None);
+            }
+            PathOutline::rect(Rect::from_corners(min, min + size))
+        }
+        "circle" => {
+            let radius = length("r");
+            PathOutline::ellipse(Vec2::new(length("cx"), length("cy")), Vec2::splat(radius))
+        }
+        "ellipse" => PathOutline::ellipse(
+            Vec2::new(length("cx"), length("cy")),
+            Vec2::new(length("rx"), length("ry")),
+        ),
+        "line" => {
+            let mut outline = PathOutline::new();
+            outline
+                .move_to(Vec2::new(length("x1"), length("y1")))
+                .line_to(Vec2::new(length("x2"), length("y2")));
+            return Ok(Some((outline, false)));
+        }
+        "polyline" | "polygon" => {
+            let points = attribute("points").unwrap_or_default();
+            let numbers = parse_numbers(points).ok_or_else(|| invalid("points"))?;
+            let mut outline = PathOutline::new();
+            for (i, point) in numbers.chunks_exact(2).enumerate() {
+                let point = Vec2::new(point[0], point[1]);
+                if i == 0 {
+                    outline.move_to(point);
+                } else {
+                    outline.line_to(point);
+                }
+            }
+            if name == "polygon" {
+                outline.close();
+            }
+            outline
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some((outline, true)))
+}
+
+fn transform_outline(outline: &PathOutline, transform: Affine2) -> PathOutline {
+    let apply = |point| transform.transform_point2(point);
+    let mut transformed = PathOutline::new();
+    for command in outline.commands() {
+        transformed.push(match *command {
+            PathCommand::MoveTo(to) => PathCommand::MoveTo(apply(to)),
+            PathCommand::LineTo(to) => PathCommand::LineTo(apply(to)),
+            PathCommand::QuadraticTo { control, to } => PathCommand::QuadraticTo {
+                control: apply(control),
+                to: apply(to),
+            },
+            PathCommand::CubicTo {
+                control1,
+                control2,
+                to,
+            } => PathCommand::CubicTo {
+                control1: apply(control1),
+                control2: apply(control2),
+                to: apply(to),
+            },
+            PathCommand::Close => PathCommand::Close,
+        });
+    }
+    transformed
+}
+
+fn with_opacity(mut color: Color, opacity: f32) -> Color {
+    let alpha = color.a() * opacity.clamp(0., 1.);
+    color.set_a(alpha);
+    color
+}
+
+/// Parses a `fill` or `stroke` paint, keeping the `inherited` one for unsupported paints.
+fn parse_paint(value: &str, inherited: Option<Color>) -> Option<Color> {
+    match value {
+        "none" => None,
+        "inherit" | "currentColor" => inherited,
+        _ => parse_color(value).or(inherited),
+    }
+}
+
+fn parse_color(value: &str) -> Option<Color> {
+    if value.starts_with('#') {
+        return Color::hex(value).ok();
+    }
+    if let Some(arguments) = value
+        .strip_prefix("rgb(")
+        .or_else(|| value.strip_prefix("rgba("))
+        .and_then(|value| value.strip_suffix(')'))
+    {
+        let mut channels =
+            arguments
+                .split(',')
+                .map(str::trim)
+                .map(|channel| match channel.strip_suffix('%') {
+                    Some(percent) => percent.parse::<f32>().ok().map(|percent| percent / 100.),
+                    None => channel.parse::<f32>().ok().map(|value| value / 255.),
+                });
+        let r = channels.next()??;
+        let g = channels.next()??;
+        let b = channels.next()??;
+        // The alpha channel is a plain number, not scaled to 255.
+        let a = channels.next().flatten().map_or(1., |a| a * 255.);
+        return Some(Color::rgba(r, g, b, a));
+    }
+    Some(match value {
+        "black" => Color::BLACK,
+        "white" => Color::WHITE,
+        "red" => Color::RED,
+        "green" => Color::rgb_u8(0, 128, 0),
+        "lime" => Color::LIME_GREEN,
+        "blue" => Color::BLUE,
+        "yellow" => Color::YELLOW,
+        "cyan" | "aqua" => Color::CYAN,
+        "magenta" | "fuchsia" => Color::FUCHSIA,
+        "gray" | "grey" => Color::GRAY,
+        "silver" => Color::SILVER,
+        "maroon" => Color::MAROON,
+        "navy" => Color::NAVY,
+        "olive" => Color::OLIVE,
+        "orange" => Color::ORANGE,
+        "purple" => Color::PURPLE,
+        "teal" => Color::TEAL,
+        "transparent" => Color::NONE,
+        _ => return None,
+    })
+}
+
+fn parse_number(value: &str) -> Option<f32> {
+    value.trim().parse().ok()
+}
+
+/// Parses a length, ignoring its unit.
+fn parse_length(value: &str) -> Option<f32> {
+    let mut lexer = Lexer::new(value);
+    lexer.number()
+}
+
+fn parse_numbers(value: &str) -> Option<Vec<f32>> {
+    let mut lexer = Lexer::new(value);
+    let mut numbers = Vec::new();
+    while !lexer.is_at_end() {
+        numbers.push(lexer.number()?);
+    }
+    Some(numbers)
+}
+
+/// Parses a list of transform functions into their composition.
+fn parse_transform(value: &str) -> Option<Affine2> {
+    let mut transform = Affine2::IDENTITY;
+    let mut rest = value.trim();
+    while !rest.is_empty() {
+        let (function, after) = rest.split_once('(')?;
+        let (arguments, after) = after.split_once(')')?;
+        let arguments = parse_numbers(arguments)?;
+        let function = match (function.trim(), arguments.as_slice()) {
+            ("matrix", &[a, b, c, d, e, f]) => Affine2::from_cols_array(&[a, b, c, d, e, f]),
+            ("translate", &[x]) => Affine2::from_translation(Vec2::new(x, 0.)),
+            ("translate", &[x, y]) => Affine2::from_translation(Vec2::new(x, y)),
+            ("scale", &[scale]) => Affine2::from_scale(Vec2::splat(scale)),
+            ("scale", &[x, y]) => Affine2::from_scale(Vec2::new(x, y)),
+            ("rotate", &[angle]) => Affine2::from_angle(angle.to_radians()),
+            ("rotate", &[angle, x, y]) => {
+                Affine2::from_angle_translation(angle.to_radians(), Vec2::new(x, y))
+                    * Affine2::from_translation(-Vec2::new(x, y))
+            }
+            ("skewX", &[angle]) => {
+                Affine2::from_cols_array(&[1., 0., angle.to_radians().tan(), 1., 0., 0.])
+            }
+            ("skewY", &[angle]) => {
+                Affine2::from_cols_array(&[1., angle.to_radians().tan(), 0., 1., 0., 0.])
+            }
+            _ => return None,
+        };
+        transform *= function;
+        rest = after.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+    }
+    Some(transform)
+}
+
+/// Parses the `d` attribute of a `path` element.
+fn parse_path_data(data: &str) -> Option<PathOutline> {
+    let mut lexer = Lexer::new(data);
+    let mut outline = PathOutline::new();
+    let mut command = None;
+    let mut current = Vec2::ZERO;
+    let mut start = Vec2::ZERO;
+    // The last control point of the previous command, reflected by the smooth curve commands.
+    let mut last_cubic_control = None;
+    let mut last_quadratic_control = None;
+
+    while !lexer.is_at_end() {
+        if let Some(next) = lexer.command() {
+            command = Some(next);
+        }
+        let name = command?;
+        let origin = if name.is_ascii_lowercase() {
+            current
+        } else {
+            Vec2::ZERO
+        };
+        let mut cubic_control = None;
+        let mut quadratic_control = None;
+
+        match name.to_ascii_uppercase() {
+            b'M' => {
+                current = origin + lexer.point()?;
+                start = current;
+                outline.move_to(current);
+                // Points after a move are lines.
+                command = Some(if name == b'm' { b'l' } else { b'L' });
+            }
+            b'L' => {
+                current = origin + lexer.point()?;
+                outline.line_to(current);
+            }
+            b'H' => {
+                current.x = origin.x + lexer.number()?;
+                outline.line_to(current);
+            }
+            b'V' => {
+                current.y = origin.y + lexer.number()?;
+                outline.line_to(current);
+            }
+            b'C' | b'S' => {
+                let control1 = if name.eq_ignore_ascii_case(&b'C') {
+                    origin + lexer.point()?
+                } else {
+                    last_cubic_control.map_or(current, |control| 2. * current - control)
+                };
+                let control2 = origin + lexer.point()?;
+                current = origin + lexer.point()?;
+                outline.cubic_to(control1, control2, current);
+                cubic_control = Some(control2);
+            }
+            b'Q' | b'T' => {
+                let control = if name.eq_ignore_ascii_case(&b'Q') {
+                    origin + lexer.point()?
+                } else {
+                    last_quadratic_control.map_or(current, |control| 2. * current - control)
+                };
+                current = origin + lexer.point()?;
+                outline.quadratic_to(control, current);
+                quadratic_control = Some(control);
+            }
+            b'A' => {
+                let radii = lexer.point()?;
+                let rotation = lexer.number()?;
+                let large_arc = lexer.flag()?;
+                let sweep = lexer.flag()?;
+                let to = origin + lexer.point()?;
+                arc_to(&mut outline, current, radii, rotation, large_arc, sweep, to);
+                current = to;
+            }
+            b'Z' => {
+                outline.close();
+                current = start;
+                // Numbers can't follow a close.
+                command = None;
+            }
+            _ => return None,
+        }
+        last_cubic_control = cubic_control;
+        last_quadratic_control = quadratic_control;
+    }
+    Some(outline)
+}
+
+/// Adds an elliptical arc to `to` as cubic bezier curves, following the implementation notes of
+/// the SVG specification.
+fn arc_to(
+    outline: &mut PathOutline,
+    from: Vec2,
+    radii: Vec2,
+    rotation: f32,
+    large_arc: bool,
+    sweep: bool,
+    to: Vec2,
+) {
+    if from == to {
+        return;
+    }
+    let mut radii = radii.abs();
+    if radii.x <= f32::EPSILON || radii.y <= f32::EPSILON {
+        outline.line_to(to);
+        return;
+    }
+
+    // The center of the ellipse, from the middle of the chord in the axes of the ellipse.
+    let rotation = Vec2::from_angle(rotation.to_radians());
+    let half_chord = Vec2::new(rotation.x, -rotation.y).rotate((from - to) * 0.5);
+    let scale = (half_chord / radii).length_squared();
+    if scale > 1. {
+        radii *= scale.sqrt();
+    }
+    let (rx2, ry2) = (radii.x * radii.x, radii.y * radii.y);
+    let (x2, y2) = (half_chord.x * half_chord.x, half_chord.y * half_chord.y);
+    let factor = ((rx2 * ry2 - rx2 * y2 - ry2 * x2) / (rx2 * y2 + ry2 * x2))
+        .max(0.)
+        .sqrt();
+    let factor = if large_arc == sweep { -factor } else { factor };
+    let center_offset = factor
+        * Vec2::new(
+            radii.x * half_chord.y / radii.y,
+            -radii.y * half_chord.x / radii.x,
+        );
+    let center = rotation.rotate(center_offset) + (from + to) * 0.5;
+
+    let start_vector = (half_chord - center_offset) / radii;
+    let end_vector = (-half_chord - center_offset) / radii;
+    let start_angle = start_vector.y.atan2(start_vector.x);
+    let mut sweep_angle = start_vector
+        .perp_dot(end_vector)
+        .atan2(start_vector.dot(end_vector));
+    if !sweep && sweep_angle > 0. {
+        sweep_angle -= TAU;
+    } else if sweep && sweep_angle < 0. {
+        sweep_angle += TAU;
+    }
+
+    // One curve per quarter of ellipse at most.
+    let segments = (sweep_angle.abs() / (PI * 0.5)).ceil().max(1.) as usize;
+    let step = sweep_angle / segments as f32;
+    let handle = 4. / 3. * (step * 0.25).tan();
+    let point = |angle: f32| {
+        center + rotation.rotate(Vec2::new(radii.x * angle.cos(), radii.y * angle.sin()))
+    };
+    let tangent =
+        |angle: f32| rotation.rotate(Vec2::new(-radii.x * angle.sin(), radii.y * angle.cos()));
+    for i in 0..segments {
+        let angle = start_angle + step * i as f32;
+        let next_angle = angle + step;
+        let end = if i + 1 == segments {
+            to
+        } else {
+            point(next_angle)
+        };
+        outline.cubic_to(
+            point(angle) + tangent(angle) * handle,
+            end - tangent(next_angle) * handle,
+            end,
+        );
+    }
+}
+
+/// Reads the numbers, flags, and commands of attribute values.
+struct Lexer<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(value: &'a str) -> Self {
+        let mut lexer = Self {
+            bytes: value.as_bytes(),
+            position: 0,
+        };
+        lexer.skip_separators();
+        lexer
+    }
+
+    fn is_at_end(&self) -> bool {
+        self.position >= self.bytes.len()
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.position).copied()
+    }
+
+    fn skip_separators(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r' | b',')) {
+            self.position += 1;
+        }
+    }
+
+    fn skip_digits(&mut self) -> usize {
+        let start = self.position;
+        while matches!(self.peek(), Some(b'0'..=b'9')) {
+            self.position += 1;
+        }
+        self.position - start
+    }
+
+    /// Reads a path command letter, if the next token is one.
+    fn command(&mut self) -> Option<u8> {
+        let next = self.peek().filter(u8::is_ascii_alphabetic)?;
+        self.position += 1;
+        self.skip_separators();
+        Some(next)
+    }
+
+    /// Reads a number, and the separators following it.
+    fn number(&mut self) -> Option<f32> {
+        let start = self.position;
+        if matches!(self.peek(), Some(b'+' | b'-')) {
+            self.position += 1;
+        }
+        let mut digits = self.skip_digits();
+        if self.peek() == Some(b'.') {
+            self.position += 1;
+            digits += self.skip_digits();
+        }
+        if digits == 0 {
+            self.position = start;
+            return None;
+        }
+        if matches!(self.peek(), Some(b'e' | b'E')) {
+            let mantissa_end = self.position;
+            self.position += 1;
+            if matches!(self.peek(), Some(b'+' | b'-')) {
+                self.position += 1;
+            }
+            if self.skip_digits() == 0 {
+                self.position = mantissa_end;
+            }
+        }
+        let number = std::str::from_utf8(&self.bytes[start..self.position])
+            .ok()?
+            .parse()
+            .ok()?;
+        self.skip_separators();
+        Some(number)
+    }
+
+    fn point(&mut self) -> Option<Vec2> {
+        Some(Vec2::new(self.number()?, self.number()?))
+    }
+
+    /// Reads an arc flag, which does not need to be separated from the next number.
+    fn flag(&mut self) -> Option<bool> {
+        let flag = match self.peek()? {
+            b'0' => false,
+            b'1' => true,
+            _ => return None,
+        };
+        self.position += 1;
+        self.skip_separators();
+        Some(flag)
+    }
+}
+
+/// A tag of an XML document.
+enum Tag<'a> {
+    /// An opening tag, like `<g fill="red">`.
+    Open {
+        name: &'a str,
+        attributes: Vec<(&'a str, &'a str)>,
+    },
+    /// A self-closing tag, like `<path d="M0 0 L10 10"/>`.
+    Empty {
+        name: &'a str,
+        attributes: Vec<(&'a str, &'a str)>,
+    },
+    /// A closing tag, like `</g>`.
+    Close,
+}
+
+/// Iterates over the element tags of an XML document, skipping its declarations, comments and
+/// text.
+struct Tags<'a>(&'a str);
+
+impl<'a> Iterator for Tags<'a> {
+    type Item = Tag<'a>;
+
+    fn next(&mut self) -> Option<Tag<'a>> {
+        loop {
+            let rest = &self.0[self.0.find('<')?..];
+            if rest.starts_with("<!--") {
+                self.0 = skip_past(rest, "-->");
+                continue;
+            }
+            if rest.starts_with("<![CDATA[") {
+                self.0 = skip_past(rest, "]]>");
+                continue;
+            }
+            if rest.starts_with("<?") || rest.starts_with("<!") {
+                self.0 = skip_past(rest, ">");
+                continue;
+            }
+
+            // Find the end of the tag, ignoring the `>` in attribute values.
+            let mut quote = None;
+            let end = rest.char_indices().skip(1).find_map(|(i, c)| {
+                match (quote, c) {
+                    (None, '"' | '\'') => quote = Some(c),
+                    (Some(q), _) if q == c => quote = None,
+                    (None, '>') => return Some(i),
+                    _ => {}
+                }
+                None
+            })?;
+            let content = rest[1..end].trim();
+            self.0 = &rest[end + 1..];
+
+            if content.starts_with('/') {
+                return Some(Tag::Close);
+            }
+            let (content, is_empty) = match content.strip_suffix('/') {
+                Some(content) => (content, true),
+                None => (content, false),
+            };
+            let name_end = content
+                .find(|c: char| c.is_whitespace())
+                .unwrap_or(content.len());
+            let name = &content[..name_end];
+            // Ignore namespace prefixes, like in `svg:path`.
+            let name = name.rsplit(':').next().unwrap_or(name);
+            let attributes = parse_attributes(&content[name_end..]);
+            return Some(if is_empty {
+                Tag::Empty { name, attributes }
+            } else {
+                Tag::Open { name, attributes }
+            });
+        }
+    }
+}
+
+fn skip_past<'a>(rest: &'a str, end: &str) -> &'a str {
+    rest.find(end).map_or("", |i| &rest[i + end.len()..])
+}
+
+fn parse_attributes(mut rest: &str) -> Vec<(&str, &str)> {
+    let mut attributes = Vec::new();
+    while let Some((name, after)) = rest.split_once('=') {
+        let after = after.trim_start();
+        let Some(quote) = after.chars().next().filter(|c| *c == '"' || *c == '\'') else {
+            break;
+        };
+        let Some((value, after)) = after[1..].split_once(quote) else {
+            break;
+        };
+        attributes.push((name.trim(), value));
+        rest = after;
+    }
+    attributes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_data() {
+        let outline = parse_path_data("M10-20l5.5.5h1v-1 Q0 0 1 1t2 2zm1e1,0").unwrap();
+        assert_eq!(
+            outline.commands(),
+            &[
+                PathCommand::MoveTo(Vec2::new(10., -20.)),
+                PathCommand::LineTo(Vec2::new(15.5, -19.5)),
+                PathCommand::LineTo(Vec2::new(16.5, -19.5)),
+                PathCommand::LineTo(Vec2::new(16.5, -20.5)),
+                PathCommand::QuadraticTo {
+                    control: Vec2::ZERO,
+                    to: Vec2::ONE,
+                },
+                PathCommand::QuadraticTo {
+                    control: Vec2::splat(2.),
+                    to: Vec2::splat(3.),
+                },
+                PathCommand::Close,
+                PathCommand::MoveTo(Vec2::new(20., -20.)),
+            ]
+        );
+
+        assert!(parse_path_data("M0 0 L1").is_none());
+        assert!(parse_path_data("M0 0 Z 1 1").is_none());
+    }
+
+    #[test]
+    fn arc_path_data() {
+        // A half circle with packed flags.
+        let outline = parse_path_data("M0 0a1 1 0 011 1").unwrap();
+        let Some(&PathCommand::CubicTo { to, .. }) = outline.commands().last() else {
+            panic!("the arc should be made of cubic curves");
+        };
+        assert_eq!(to, Vec2::ONE);
+        assert_eq!(outline.commands().len(), 2);
+
+        // Out of range radii are scaled up to reach the end of the arc.
+        let outline = parse_path_data("M0 0A0.1 0.1 0 1 1 2 0").unwrap();
+        let polylines = outline.flatten(0.001);
+        let highest = polylines[0]
+            .points
+            .iter()
+            .map(|point| point.y.abs())
+            .fold(0., f32::max);
+        assert!((highest - 1.).abs() < 0.01);
+    }
+
+    #[test]
+    fn transforms() {
+        let transform = parse_transform("translate(10 20) scale(2), rotate(90 1 0)").unwrap();
+        let point = transform.transform_point2(Vec2::new(2., 0.));
+        assert!(point.abs_diff_eq(Vec2::new(12., 22.), 1e-5));
+        assert!(parse_transform("translate(1 2 3)").is_none());
+    }
+
+    #[test]
+    fn document() {
+        let path = parse_svg(
+            r##"<?xml version="1.0"?>
+            <!-- an icon -->
+            <svg xmlns="http://www.w3.org/2000/svg" width="20" height="10" viewBox="0 0 40 20">
+                <defs><rect width="5" height="5"/></defs>
+                <g fill="#ff0000" style="stroke: blue; stroke-width: 2" opacity="0.5">
+                    <rect x="0" y="0" width="40" height="20"/>
+                    <circle cx="20" cy="10" r="5" fill="none"/>
+                </g>
+                <line x1="0" y1="0" x2="40" y2="20" stroke="black"/>
+            </svg>"##,
+        )
+        .unwrap();
+
+        assert_eq!(path.view_box, Rect::new(-10., -5., 10., 5.));
+        assert_eq!(path.shapes.len(), 3);
+
+        let rect = &path.shapes[0];
+        let fill = rect.fill.unwrap();
+        assert_eq!(fill.color, Color::rgba(1., 0., 0., 0.5));
+        let stroke = rect.stroke.unwrap();
+        assert_eq!(stroke.color, Color::rgba(0., 0., 1., 0.5));
+        // The stroke width is scaled with the view box.
+        assert!((stroke.width - 1.).abs() < 1e-5);
+        // The top left corner of the view box is mapped to the top left of the path.
+        assert_eq!(
+            rect.outline.commands()[0],
+            PathCommand::MoveTo(Vec2::new(-10., 5.))
+        );
+
+        assert!(path.shapes[1].fill.is_none());
+        // Lines are never filled, even though the fill defaults to black.
+        assert!(path.shapes[2].fill.is_none());
+        assert!(path.shapes[2].stroke.is_some());
+
+        assert!(matches!(
+            parse_svg("<g/>"),
+            Err(SvgLoaderError::MissingSvgElement)
+        ));
+    }
+}
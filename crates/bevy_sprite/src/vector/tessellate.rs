@@ -0,0 +1,234 @@
+//! Tessellation of the fills and strokes of [`VectorShape`](super::VectorShape)s into polygons.
+
+use std::cmp::Ordering;
+
+use bevy_math::Vec2;
+
+use super::{FillRule, Polyline};
+
+/// A non-horizontal segment of a contour, from its lowest to its highest point.
+struct Edge {
+    top: Vec2,
+    bottom: Vec2,
+    /// `1` if the contour goes up along the edge, `-1` if it goes down.
+    winding: i32,
+}
+
+impl Edge {
+    fn x_at(&self, y: f32) -> f32 {
+        let t = (y - self.top.y) / (self.bottom.y - self.top.y);
+        self.top.x + (self.bottom.x - self.top.x) * t
+    }
+
+    /// The height at which two edges cross, if they cross away from their ends.
+    fn crossing_y(&self, other: &Edge) -> Option<f32> {
+        let direction = self.bottom - self.top;
+        let other_direction = other.bottom - other.top;
+        let denominator = direction.perp_dot(other_direction);
+        if denominator.abs() <= f32::EPSILON {
+            return None;
+        }
+        let offset = other.top - self.top;
+        let t = offset.perp_dot(other_direction) / denominator;
+        let u = offset.perp_dot(direction) / denominator;
+        (t > 0. && t < 1. && u > 0. && u < 1.).then_some(self.top.y + direction.y * t)
+    }
+}
+
+/// Splits the inside of the closed `contours` into trapezoids with horizontal top and bottom
+/// sides, passed to `emit` as counter-clockwise quads.
+///
+/// The plane is cut into horizontal slabs at the height of every vertex and every crossing of
+/// two edges, so that edges never cross inside a slab and the inside of each slab is a list of
+/// spans between sorted edges.
+pub(crate) fn fill_trapezoids(
+    contours: &[&[Vec2]],
+    rule: FillRule,
+    mut emit: impl FnMut([Vec2; 4]),
+) {
+    let mut edges = Vec::new();
+    for contour in contours {
+        for (i, &start) in contour.iter().enumerate() {
+            let end = contour[(i + 1) % contour.len()];
+            match start.y.partial_cmp(&end.y) {
+                Some(Ordering::Less) => edges.push(Edge {
+                    top: start,
+                    bottom: end,
+                    winding: 1,
+                }),
+                Some(Ordering::Greater) => edges.push(Edge {
+                    top: end,
+                    bottom: start,
+                    winding: -1,
+                }),
+                _ => {}
+            }
+        }
+    }
+    edges.sort_by(|a, b| a.top.y.total_cmp(&b.top.y));
+
+    let mut heights: Vec<f32> = edges
+        .iter()
+        .flat_map(|edge| [edge.top.y, edge.bottom.y])
+        .collect();
+    for (i, edge) in edges.iter().enumerate() {
+        for other in &edges[i + 1..] {
+            if other.top.y >= edge.bottom.y {
+                break;
+            }
+            heights.extend(edge.crossing_y(other));
+        }
+    }
+    heights.sort_by(f32::total_cmp);
+    heights.dedup();
+
+    let mut next_edge = 0;
+    let mut active: Vec<&Edge> = Vec::new();
+    let mut spans: Vec<(f32, &Edge)> = Vec::new();
+    for slab in heights.windows(2) {
+        let (bottom, top) = (slab[0], slab[1]);
+        let middle = (bottom + top) * 0.5;
+        while next_edge < edges.len() && edges[next_edge].top.y <= middle {
+            active.push(&edges[next_edge]);
+            next_edge += 1;
+        }
+        active.retain(|edge| edge.bottom.y > middle);
+
+        spans.clear();
+        spans.extend(active.iter().map(|edge| (edge.x_at(middle), *edge)));
+        spans.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut winding = 0;
+        let mut left = None;
+        for &(_, edge) in &spans {
+            let was_inside = rule.is_inside(winding);
+            winding += edge.winding;
+            match (was_inside, rule.is_inside(winding)) {
+                (false, true) => left = Some(edge),
+                (true, false) => {
+                    if let Some(left) = left.take() {
+                        emit([
+                            Vec2::new(left.x_at(bottom), bottom),
+                            Vec2::new(edge.x_at(bottom), bottom),
+                            Vec2::new(edge.x_at(top), top),
+                            Vec2::new(left.x_at(top), top),
+                        ]);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Splits a stroke of `width` along `polyline` into counter-clockwise triangles passed to
+/// `emit`: two per segment, and one beveling the outer side of each join.
+pub(crate) fn stroke_triangles(polyline: &Polyline, width: f32, mut emit: impl FnMut([Vec2; 3])) {
+    let points = &polyline.points;
+    if points.len() < 2 || width <= 0. {
+        return;
+    }
+    let half_width = width * 0.5;
+    let segment_count = if polyline.closed {
+        points.len()
+    } else {
+        points.len() - 1
+    };
+    let segment = |i: usize| (points[i], points[(i + 1) % points.len()]);
+    let offset = |(start, end): (Vec2, Vec2)| (end - start).normalize_or_zero().perp() * half_width;
+    let mut emit_ccw = |mut triangle: [Vec2; 3]| {
+        if (triangle[1] - triangle[0]).perp_dot(triangle[2] - triangle[0]) < 0. {
+            triangle.swap(1, 2);
+        }
+        emit(triangle);
+    };
+
+    for i in 0..segment_count {
+        let (start, end) = segment(i);
+        let offset_i = offset((start, end));
+        emit_ccw([start - offset_i, end - offset_i, end + offset_i]);
+        emit_ccw([start - offset_i, end + offset_i, start + offset_i]);
+
+        if i + 1 < segment_count || polyline.closed {
+            let next = segment((i + 1) % segment_count);
+            let next_offset = offset(next);
+            // The outer side of a join is on the right of a left turn, and on the left of a
+            // right turn.
+            let turn = (end - start).perp_dot(next.1 - next.0);
+            let side = if turn > 0. { -1. } else { 1. };
+            emit_ccw([end, end + offset_i * side, end + next_offset * side]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn area(polygon: &[Vec2]) -> f32 {
+        let mut area = 0.;
+        for (i, point) in polygon.iter().enumerate() {
+            area += point.perp_dot(polygon[(i + 1) % polygon.len()]);
+        }
+        area * 0.5
+    }
+
+    fn fill_area(contours: &[&[Vec2]], rule: FillRule) -> f32 {
+        let mut total = 0.;
+        fill_trapezoids(contours, rule, |trapezoid| {
+            let area = area(&trapezoid);
+            assert!(area >= 0., "trapezoids should be counter-clockwise");
+            total += area;
+        });
+        total
+    }
+
+    fn square(min: f32, max: f32) -> [Vec2; 4] {
+        [
+            Vec2::new(min, min),
+            Vec2::new(max, min),
+            Vec2::new(max, max),
+            Vec2::new(min, max),
+        ]
+    }
+
+    #[test]
+    fn fill_rules() {
+        let outer = square(0., 4.);
+        let inner = square(1., 3.);
+        assert_eq!(fill_area(&[&outer, &inner], FillRule::NonZero), 16.);
+        assert_eq!(fill_area(&[&outer, &inner], FillRule::EvenOdd), 12.);
+
+        let mut reversed = inner;
+        reversed.reverse();
+        assert_eq!(fill_area(&[&outer, &reversed], FillRule::NonZero), 12.);
+    }
+
+    #[test]
+    fn fill_crossing_edges() {
+        // A bow tie, made of two triangles of area 1.
+        let bow_tie = [
+            Vec2::new(0., 0.),
+            Vec2::new(2., 2.),
+            Vec2::new(2., 0.),
+            Vec2::new(0., 2.),
+        ];
+        assert!((fill_area(&[&bow_tie], FillRule::NonZero) - 2.).abs() < 1e-5);
+    }
+
+    #[test]
+    fn stroke() {
+        let polyline = Polyline {
+            points: vec![Vec2::ZERO, Vec2::new(2., 0.), Vec2::new(2., 2.)],
+            closed: false,
+        };
+        let mut triangles = Vec::new();
+        stroke_triangles(&polyline, 1., |triangle| triangles.push(triangle));
+        // Two triangles per segment, and one for the join.
+        assert_eq!(triangles.len(), 5);
+        let total: f32 = triangles.iter().map(|triangle| area(triangle)).sum();
+        // Two 2 x 1 segments, and the bevel of a right angle.
+        assert!((total - (4. + 0.125)).abs() < 1e-5);
+        assert!(triangles.iter().all(|triangle| area(triangle) >= 0.));
+    }
+}
@@ -44,6 +44,16 @@ impl TaskPoolBuilder {
         self
     }
 
+    /// No op on the single threaded task pool
+    pub fn priority(self, _priority: crate::ThreadPriority) -> Self {
+        self
+    }
+
+    /// No op on the single threaded task pool
+    pub fn core_affinity(self, _core_affinity: crate::CoreAffinity) -> Self {
+        self
+    }
+
     /// Creates a new [`TaskPool`]
     pub fn build(self) -> TaskPool {
         TaskPool::new_internal()
@@ -159,6 +169,15 @@ impl TaskPool {
         FakeTask
     }
 
+    /// Spawns a static future that is expected to run for a long time.
+    /// This is exactly the same as [`TaskPool::spawn`].
+    pub fn spawn_long_running<T>(&self, future: impl Future<Output = T> + 'static) -> FakeTask
+    where
+        T: 'static,
+    {
+        self.spawn(future)
+    }
+
     /// Spawns a static future on the JS event loop. This is exactly the same as [`TaskPool::spawn`].
     pub fn spawn_local<T>(&self, future: impl Future<Output = T> + 'static) -> FakeTask
     where
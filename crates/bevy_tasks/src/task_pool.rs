@@ -14,7 +14,8 @@ use futures_lite::FutureExt;
 use crate::{
     block_on,
     thread_executor::{ThreadExecutor, ThreadExecutorTicker},
-    Task,
+    thread_options::apply_to_current_thread,
+    CoreAffinity, Task, ThreadPriority,
 };
 
 struct CallOnDrop(Option<Arc<dyn Fn() + Send + Sync + 'static>>);
@@ -39,6 +40,10 @@ pub struct TaskPoolBuilder {
     /// Allows customizing the name of the threads - helpful for debugging. If set, threads will
     /// be named <thread_name> (<thread_index>), i.e. "MyThreadPool (2)"
     thread_name: Option<String>,
+    /// If set, the threads of the pool will be given this scheduling priority
+    priority: ThreadPriority,
+    /// If set, the threads of the pool will be pinned to these cores
+    core_affinity: CoreAffinity,
 
     on_thread_spawn: Option<Arc<dyn Fn() + Send + Sync + 'static>>,
     on_thread_destroy: Option<Arc<dyn Fn() + Send + Sync + 'static>>,
@@ -70,6 +75,23 @@ impl TaskPoolBuilder {
         self
     }
 
+    /// Override the scheduling priority of the threads created for the pool.
+    ///
+    /// This also applies to the threads running [long-running tasks](TaskPool::spawn_long_running).
+    /// This is a hint, see [`ThreadPriority`] for details.
+    pub fn priority(mut self, priority: ThreadPriority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Override the cores the threads created for the pool may run on.
+    ///
+    /// This is a hint, see [`CoreAffinity`] for details.
+    pub fn core_affinity(mut self, core_affinity: CoreAffinity) -> Self {
+        self.core_affinity = core_affinity;
+        self
+    }
+
     /// Sets a callback that is invoked once for every created thread as it starts.
     ///
     /// This is called on the thread itself and has access to all thread-local storage.
@@ -116,6 +138,10 @@ pub struct TaskPool {
     /// Inner state of the pool
     threads: Vec<JoinHandle<()>>,
     shutdown_tx: async_channel::Sender<()>,
+
+    /// The name and priority of the threads running long-running tasks
+    thread_name: String,
+    priority: ThreadPriority,
 }
 
 impl TaskPool {
@@ -161,9 +187,12 @@ impl TaskPool {
 
                 let on_thread_spawn = builder.on_thread_spawn.clone();
                 let on_thread_destroy = builder.on_thread_destroy.clone();
+                let priority = builder.priority;
+                let core_affinity = builder.core_affinity.clone();
 
                 thread_builder
                     .spawn(move || {
+                        apply_to_current_thread(priority, &core_affinity, i);
                         TaskPool::LOCAL_EXECUTOR.with(|local_executor| {
                             if let Some(on_thread_spawn) = on_thread_spawn {
                                 on_thread_spawn();
@@ -195,6 +224,10 @@ impl TaskPool {
             executor,
             threads,
             shutdown_tx,
+            thread_name: builder
+                .thread_name
+                .unwrap_or_else(|| "TaskPool".to_string()),
+            priority: builder.priority,
         }
     }
 
@@ -539,6 +572,49 @@ impl TaskPool {
         Task::new(self.executor.spawn(future))
     }
 
+    /// Spawns a static future that is expected to run for a long time, such as a server loop
+    /// or a blocking computation, on a dedicated thread rather than on the threads of the pool.
+    ///
+    /// This keeps the task from starving other tasks of the pool, at the cost of creating a thread.
+    /// The thread is given the priority of the pool, and exits once the task completes or is
+    /// canceled, even if wakers of the task are still held elsewhere.
+    ///
+    /// The returned [`Task`] behaves like one returned by [`TaskPool::spawn`].
+    pub fn spawn_long_running<T>(&self, future: impl Future<Output = T> + Send + 'static) -> Task<T>
+    where
+        T: Send + 'static,
+    {
+        let (sender, receiver) = async_channel::unbounded::<async_task::Runnable>();
+        // Wakers keep the sender alive, so the channel is closed explicitly once the future
+        // completes, or is dropped because the task was canceled.
+        let closer = sender.clone();
+        let future = async move {
+            let _close = CallOnDrop(Some(Arc::new(move || {
+                closer.close();
+            })));
+            future.await
+        };
+        let (runnable, task) = async_task::spawn(future, move |runnable| {
+            // The channel is only closed once the future is done, so no runnable can be lost.
+            let _ = sender.try_send(runnable);
+        });
+        runnable.schedule();
+
+        let priority = self.priority;
+        thread::Builder::new()
+            .name(format!("{} (long-running)", self.thread_name))
+            .spawn(move || {
+                apply_to_current_thread(priority, &CoreAffinity::Any, 0);
+                // Runs until the task completes or is canceled, which closes the channel.
+                while let Ok(runnable) = receiver.recv_blocking() {
+                    runnable.run();
+                }
+            })
+            .expect("Failed to spawn thread.");
+
+        Task::new(task)
+    }
+
     /// Spawns a static future on the thread-local async executor for the
     /// current thread. The task will run entirely on the thread the task was
     /// spawned on.
@@ -925,6 +1001,92 @@ mod tests {
         assert_eq!(count.load(Ordering::Acquire), 200);
     }
 
+    #[test]
+    fn test_spawn_long_running() {
+        let pool = TaskPoolBuilder::new()
+            .num_threads(1)
+            .priority(ThreadPriority::Low)
+            .thread_name("Test Pool".to_string())
+            .build();
+
+        let (sender, receiver) = async_channel::unbounded::<i32>();
+        let task = pool.spawn_long_running(async move {
+            let name = thread::current().name().map(ToString::to_string);
+            let mut sum = 0;
+            while let Ok(value) = receiver.recv().await {
+                sum += value;
+            }
+            (name, sum)
+        });
+        for value in 1..=10 {
+            sender.send_blocking(value).unwrap();
+        }
+        drop(sender);
+
+        let (name, sum) = block_on(task);
+        assert_eq!(name.as_deref(), Some("Test Pool (long-running)"));
+        assert_eq!(sum, 55);
+    }
+
+    #[test]
+    fn test_spawn_long_running_exits_on_completion() {
+        thread_local! {
+            static ON_EXIT: std::cell::RefCell<Option<std::sync::mpsc::Sender<()>>> =
+                std::cell::RefCell::new(None);
+        }
+
+        let pool = TaskPool::new();
+        let (exit_sender, exit_receiver) = std::sync::mpsc::channel();
+        let waker = Arc::new(std::sync::Mutex::new(None));
+        let task_waker = waker.clone();
+        let task = pool.spawn_long_running(futures_lite::future::poll_fn(move |cx| {
+            // Dropped when the thread exits
+            ON_EXIT.with(|on_exit| *on_exit.borrow_mut() = Some(exit_sender.clone()));
+            // Keep a waker of the completed task alive
+            *task_waker.lock().unwrap() = Some(cx.waker().clone());
+            std::task::Poll::Ready(())
+        }));
+        block_on(task);
+
+        assert_eq!(
+            exit_receiver.recv_timeout(std::time::Duration::from_secs(5)),
+            Err(std::sync::mpsc::RecvTimeoutError::Disconnected)
+        );
+        assert!(waker.lock().unwrap().is_some());
+    }
+
+    #[test]
+    fn test_spawn_long_running_exits_on_cancel() {
+        thread_local! {
+            static ON_EXIT: std::cell::RefCell<Option<std::sync::mpsc::Sender<()>>> =
+                std::cell::RefCell::new(None);
+        }
+
+        let pool = TaskPool::new();
+        let (exit_sender, exit_receiver) = std::sync::mpsc::channel();
+        let (poll_sender, poll_receiver) = std::sync::mpsc::channel();
+        let waker = Arc::new(std::sync::Mutex::new(None));
+        let task_waker = waker.clone();
+        let task = pool.spawn_long_running(futures_lite::future::poll_fn(move |cx| {
+            // Dropped when the thread exits
+            ON_EXIT.with(|on_exit| *on_exit.borrow_mut() = Some(exit_sender.clone()));
+            // Keep a waker of the canceled task alive
+            *task_waker.lock().unwrap() = Some(cx.waker().clone());
+            let _ = poll_sender.send(());
+            std::task::Poll::<()>::Pending
+        }));
+        poll_receiver
+            .recv_timeout(std::time::Duration::from_secs(5))
+            .unwrap();
+        drop(task);
+
+        assert_eq!(
+            exit_receiver.recv_timeout(std::time::Duration::from_secs(5)),
+            Err(std::sync::mpsc::RecvTimeoutError::Disconnected)
+        );
+        assert!(waker.lock().unwrap().is_some());
+    }
+
     // This test will often freeze on other executors.
     #[test]
     fn test_nested_scopes() {
@@ -0,0 +1,86 @@
+/// The scheduling priority of the threads of a [`TaskPool`](crate::TaskPool), relative to the
+/// other threads of the process.
+///
+/// This is a hint: it is ignored unless the `thread-options` feature is enabled, on platforms
+/// that don't support it, or when the process lacks the permission to raise the priority of its threads.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ThreadPriority {
+    /// The lowest priority, for background work that must never get in the way.
+    Min,
+    /// A lower priority than the other threads.
+    Low,
+    /// The priority assigned by the operating system. The priority of the threads is left untouched.
+    #[default]
+    Normal,
+    /// A higher priority than the other threads.
+    High,
+    /// The highest priority.
+    Max,
+}
+
+/// The cores the threads of a [`TaskPool`](crate::TaskPool) may run on.
+///
+/// This is a hint: it is ignored unless the `thread-options` feature is enabled
+/// or on platforms that don't support it, and cores that don't exist are skipped.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum CoreAffinity {
+    /// The threads can run on any core, as decided by the operating system.
+    #[default]
+    Any,
+    /// Each thread is pinned to one of the given cores, by index, in a round-robin fashion:
+    /// the thread `i` of the pool runs on the core `cores[i % cores.len()]`.
+    Cores(Vec<usize>),
+}
+
+/// Applies a [`ThreadPriority`] and [`CoreAffinity`] to the current thread,
+/// which is the thread with the given index in its pool.
+#[cfg(all(not(target_arch = "wasm32"), feature = "thread-options"))]
+pub(crate) fn apply_to_current_thread(
+    priority: ThreadPriority,
+    affinity: &CoreAffinity,
+    thread_index: usize,
+) {
+    use thread_priority::{ThreadPriority as OsPriority, ThreadPriorityValue};
+
+    let os_priority = match priority {
+        ThreadPriority::Min => Some(OsPriority::Min),
+        ThreadPriority::Low => ThreadPriorityValue::try_from(25u8)
+            .ok()
+            .map(OsPriority::Crossplatform),
+        ThreadPriority::Normal => None,
+        ThreadPriority::High => ThreadPriorityValue::try_from(75u8)
+            .ok()
+            .map(OsPriority::Crossplatform),
+        ThreadPriority::Max => Some(OsPriority::Max),
+    };
+    if let Some(os_priority) = os_priority {
+        // Raising the priority can fail without elevated permissions, which is fine for a hint.
+        let _ = thread_priority::set_current_thread_priority(os_priority);
+    }
+
+    if let CoreAffinity::Cores(cores) = affinity {
+        if cores.is_empty() {
+            return;
+        }
+        let core = cores[thread_index % cores.len()];
+        let core_id = core_affinity::get_core_ids()
+            .and_then(|core_ids| core_ids.into_iter().find(|core_id| core_id.id == core));
+        if let Some(core_id) = core_id {
+            core_affinity::set_for_current(core_id);
+        }
+    }
+}
+
+/// Without the `thread-options` feature, threads keep the priority and affinity given by the
+/// operating system.
+#[cfg(all(
+    not(target_arch = "wasm32"),
+    feature = "multi-threaded",
+    not(feature = "thread-options")
+))]
+pub(crate) fn apply_to_current_thread(
+    _priority: ThreadPriority,
+    _affinity: &CoreAffinity,
+    _thread_index: usize,
+) {
+}
@@ -1,4 +1,4 @@
-use ab_glyph::{GlyphId, Point};
+use ab_glyph::Point;
 use bevy_asset::{Assets, Handle};
 use bevy_math::Vec2;
 use bevy_render::{
@@ -7,7 +7,6 @@ use bevy_render::{
     texture::Image,
 };
 use bevy_sprite::{DynamicTextureAtlasBuilder, TextureAtlasLayout};
-use bevy_utils::HashMap;
 
 #[cfg(feature = "subpixel_glyph_atlas")]
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
@@ -40,11 +39,14 @@ impl From<Point> for SubpixelOffset {
     }
 }
 
+/// A page of the glyph atlas stored in [`FontAtlases`](crate::FontAtlases).
+///
+/// Glyphs of any font and size can be stored in a page, and removed to make room for new ones.
 pub struct FontAtlas {
     pub dynamic_texture_atlas_builder: DynamicTextureAtlasBuilder,
-    pub glyph_to_atlas_index: HashMap<(GlyphId, SubpixelOffset), usize>,
     pub texture_atlas: Handle<TextureAtlasLayout>,
     pub texture: Handle<Image>,
+    glyph_count: usize,
 }
 
 impl FontAtlas {
@@ -68,47 +70,48 @@ impl FontAtlas {
         let texture_atlas = TextureAtlasLayout::new_empty(size);
         Self {
             texture_atlas: texture_atlases.add(texture_atlas),
-            glyph_to_atlas_index: HashMap::default(),
             dynamic_texture_atlas_builder: DynamicTextureAtlasBuilder::new(size, 0),
             texture,
+            glyph_count: 0,
         }
     }
 
-    pub fn get_glyph_index(
-        &self,
-        glyph_id: GlyphId,
-        subpixel_offset: SubpixelOffset,
-    ) -> Option<usize> {
-        self.glyph_to_atlas_index
-            .get(&(glyph_id, subpixel_offset))
-            .copied()
-    }
-
-    pub fn has_glyph(&self, glyph_id: GlyphId, subpixel_offset: SubpixelOffset) -> bool {
-        self.glyph_to_atlas_index
-            .contains_key(&(glyph_id, subpixel_offset))
-    }
-
+    /// Adds the texture of a glyph to the page, returning its index in the
+    /// [`TextureAtlasLayout`], or `None` if the page doesn't have enough room left.
     pub fn add_glyph(
         &mut self,
         textures: &mut Assets<Image>,
         texture_atlases: &mut Assets<TextureAtlasLayout>,
-        glyph_id: GlyphId,
-        subpixel_offset: SubpixelOffset,
         texture: &Image,
-    ) -> bool {
+    ) -> Option<usize> {
         let texture_atlas = texture_atlases.get_mut(&self.texture_atlas).unwrap();
-        if let Some(index) = self.dynamic_texture_atlas_builder.add_texture(
+        let glyph_index = self.dynamic_texture_atlas_builder.add_texture(
             texture_atlas,
             textures,
             texture,
             &self.texture,
-        ) {
-            self.glyph_to_atlas_index
-                .insert((glyph_id, subpixel_offset), index);
-            true
-        } else {
-            false
+        )?;
+        self.glyph_count += 1;
+        Some(glyph_index)
+    }
+
+    /// Removes the glyph at `glyph_index` from the page, freeing its space for new glyphs.
+    pub fn remove_glyph(
+        &mut self,
+        texture_atlases: &mut Assets<TextureAtlasLayout>,
+        glyph_index: usize,
+    ) {
+        let texture_atlas = texture_atlases.get_mut(&self.texture_atlas).unwrap();
+        if self
+            .dynamic_texture_atlas_builder
+            .remove_texture(texture_atlas, glyph_index)
+        {
+            self.glyph_count -= 1;
         }
     }
+
+    /// Returns the number of glyphs stored in the page.
+    pub fn glyph_count(&self) -> usize {
+        self.glyph_count
+    }
 }
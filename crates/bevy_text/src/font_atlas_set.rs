@@ -1,4 +1,4 @@
-use crate::{error::TextError, Font, FontAtlas};
+use crate::{error::TextError, Font, FontAtlas, SubpixelOffset, TextSettings};
 use ab_glyph::{GlyphId, OutlinedGlyph, Point};
 use bevy_asset::{AssetEvent, AssetId};
 use bevy_asset::{Assets, Handle};
@@ -7,38 +7,41 @@ use bevy_math::Vec2;
 use bevy_reflect::Reflect;
 use bevy_render::texture::Image;
 use bevy_sprite::TextureAtlasLayout;
-use bevy_utils::FloatOrd;
-use bevy_utils::HashMap;
+use bevy_utils::{warn_once, FloatOrd, HashMap};
 
 type FontSizeKey = FloatOrd;
 
-#[derive(Default, Resource)]
-pub struct FontAtlasSets {
-    // PERF: in theory this could be optimized with Assets storage ... consider making some fast "simple" AssetMap
-    pub(crate) sets: HashMap<AssetId<Font>, FontAtlasSet>,
-}
-
-impl FontAtlasSets {
-    pub fn get(&self, id: impl Into<AssetId<Font>>) -> Option<&FontAtlasSet> {
-        let id: AssetId<Font> = id.into();
-        self.sets.get(&id)
-    }
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+struct GlyphKey {
+    font: AssetId<Font>,
+    font_size: FontSizeKey,
+    glyph_id: GlyphId,
+    subpixel_offset: SubpixelOffset,
 }
 
-pub fn remove_dropped_font_atlas_sets(
-    mut font_atlas_sets: ResMut<FontAtlasSets>,
-    mut font_events: EventReader<AssetEvent<Font>>,
-) {
-    // Clean up font atlas sets for removed fonts
-    for event in font_events.read() {
-        if let AssetEvent::Removed { id } = event {
-            font_atlas_sets.sets.remove(id);
-        }
-    }
+#[derive(Copy, Clone, Debug)]
+struct CachedGlyph {
+    page: usize,
+    glyph_index: usize,
+    last_used: u64,
 }
 
-pub struct FontAtlasSet {
-    font_atlases: HashMap<FontSizeKey, Vec<FontAtlas>>,
+/// The glyph atlas shared by the text of every font and size.
+///
+/// Glyphs are rasterized into [`FontAtlas`] pages the first time they are laid out. When every
+/// page is full and there are already [`TextSettings::soft_max_font_atlases`] pages, the least
+/// recently used glyphs are evicted to make room for new ones, which keeps the memory used by
+/// large character sets, like CJK scripts or emoji, bounded.
+///
+/// Glyphs laid out during the current frame are never evicted. Text laid out in an earlier frame
+/// may reference evicted glyphs, so it must be laid out again when [`FontAtlases::generation`]
+/// changes.
+#[derive(Default, Resource)]
+pub struct FontAtlases {
+    pages: Vec<FontAtlas>,
+    glyphs: HashMap<GlyphKey, CachedGlyph>,
+    frame: u64,
+    generation: u64,
 }
 
 #[derive(Debug, Clone, Reflect)]
@@ -48,127 +51,182 @@ pub struct GlyphAtlasInfo {
     pub glyph_index: usize,
 }
 
-impl Default for FontAtlasSet {
-    fn default() -> Self {
-        FontAtlasSet {
-            font_atlases: HashMap::with_capacity_and_hasher(1, Default::default()),
-        }
+impl FontAtlases {
+    /// Returns the pages of the atlas.
+    pub fn pages(&self) -> &[FontAtlas] {
+        &self.pages
+    }
+
+    /// Returns the number of glyphs in the atlas.
+    pub fn glyph_count(&self) -> usize {
+        self.glyphs.len()
+    }
+
+    /// Returns a counter incremented every time glyphs are evicted from the atlas.
+    pub fn generation(&self) -> u64 {
+        self.generation
     }
-}
 
-impl FontAtlasSet {
-    pub fn iter(&self) -> impl Iterator<Item = (&FontSizeKey, &Vec<FontAtlas>)> {
-        self.font_atlases.iter()
+    pub fn has_glyph(
+        &self,
+        font: AssetId<Font>,
+        font_size: f32,
+        glyph_id: GlyphId,
+        position: Point,
+    ) -> bool {
+        self.glyphs
+            .contains_key(&glyph_key(font, font_size, glyph_id, position))
     }
 
-    pub fn has_glyph(&self, glyph_id: GlyphId, glyph_position: Point, font_size: f32) -> bool {
-        self.font_atlases
-            .get(&FloatOrd(font_size))
-            .map_or(false, |font_atlas| {
-                font_atlas
-                    .iter()
-                    .any(|atlas| atlas.has_glyph(glyph_id, glyph_position.into()))
-            })
+    /// Returns the location of a glyph in the atlas, and marks it as used in the current frame.
+    pub fn get_glyph_atlas_info(
+        &mut self,
+        font: AssetId<Font>,
+        font_size: f32,
+        glyph_id: GlyphId,
+        position: Point,
+    ) -> Option<GlyphAtlasInfo> {
+        let glyph = self
+            .glyphs
+            .get_mut(&glyph_key(font, font_size, glyph_id, position))?;
+        glyph.last_used = self.frame;
+        let glyph = *glyph;
+        Some(self.atlas_info(glyph))
     }
 
+    /// Rasterizes a glyph into the atlas, evicting the least recently used glyphs if needed.
     pub fn add_glyph_to_atlas(
         &mut self,
+        font: AssetId<Font>,
         texture_atlases: &mut Assets<TextureAtlasLayout>,
         textures: &mut Assets<Image>,
         outlined_glyph: OutlinedGlyph,
+        text_settings: &TextSettings,
     ) -> Result<GlyphAtlasInfo, TextError> {
         let glyph = outlined_glyph.glyph();
+        let key = glyph_key(font, glyph.scale.y, glyph.id, glyph.position);
         let glyph_id = glyph.id;
-        let glyph_position = glyph.position;
-        let font_size = glyph.scale.y;
-        let font_atlases = self
-            .font_atlases
-            .entry(FloatOrd(font_size))
-            .or_insert_with(|| {
-                vec![FontAtlas::new(
-                    textures,
-                    texture_atlases,
-                    Vec2::splat(512.0),
-                )]
-            });
 
         let glyph_texture = Font::get_outlined_glyph_texture(outlined_glyph);
-        let add_char_to_font_atlas = |atlas: &mut FontAtlas| -> bool {
-            atlas.add_glyph(
-                textures,
-                texture_atlases,
-                glyph_id,
-                glyph_position.into(),
-                &glyph_texture,
-            )
-        };
-        if !font_atlases.iter_mut().any(add_char_to_font_atlas) {
-            // Find the largest dimension of the glyph, either its width or its height
-            let glyph_max_size: u32 = glyph_texture
-                .texture_descriptor
-                .size
-                .height
-                .max(glyph_texture.width());
-            // Pick the higher of 512 or the smallest power of 2 greater than glyph_max_size
-            let containing = (1u32 << (32 - glyph_max_size.leading_zeros())).max(512) as f32;
-            font_atlases.push(FontAtlas::new(
-                textures,
-                texture_atlases,
-                Vec2::new(containing, containing),
-            ));
-            if !font_atlases.last_mut().unwrap().add_glyph(
-                textures,
-                texture_atlases,
-                glyph_id,
-                glyph_position.into(),
-                &glyph_texture,
-            ) {
-                return Err(TextError::FailedToAddGlyph(glyph_id));
+        // Find the largest dimension of the glyph, either its width or its height
+        let glyph_max_size: u32 = glyph_texture
+            .texture_descriptor
+            .size
+            .height
+            .max(glyph_texture.width());
+        let fits_in_page = glyph_max_size <= text_settings.font_atlas_page_size;
+
+        let mut add_page = false;
+        loop {
+            let added = self.pages.iter_mut().enumerate().find_map(|(page, atlas)| {
+                atlas
+                    .add_glyph(textures, texture_atlases, &glyph_texture)
+                    .map(|glyph_index| (page, glyph_index))
+            });
+            if let Some((page, glyph_index)) = added {
+                return Ok(self.insert_glyph(key, page, glyph_index));
+            }
+
+            if !fits_in_page || self.pages.len() < text_settings.soft_max_font_atlases.get() {
+                break;
+            }
+            if !self.evict_glyph(texture_atlases) {
+                add_page = true;
+                break;
             }
         }
 
-        Ok(self
-            .get_glyph_atlas_info(font_size, glyph_id, glyph_position)
-            .unwrap())
-    }
+        if add_page && !text_settings.allow_dynamic_font_size {
+            warn_once!("warning[B0005]: Number of font atlases has exceeded the maximum of {}. Performance and memory usage may suffer.", text_settings.soft_max_font_atlases.get());
+        }
 
-    pub fn get_glyph_atlas_info(
-        &mut self,
-        font_size: f32,
-        glyph_id: GlyphId,
-        position: Point,
-    ) -> Option<GlyphAtlasInfo> {
-        self.font_atlases
-            .get(&FloatOrd(font_size))
-            .and_then(|font_atlases| {
-                font_atlases
-                    .iter()
-                    .find_map(|atlas| {
-                        atlas
-                            .get_glyph_index(glyph_id, position.into())
-                            .map(|glyph_index| {
-                                (
-                                    glyph_index,
-                                    atlas.texture_atlas.clone_weak(),
-                                    atlas.texture.clone_weak(),
-                                )
-                            })
-                    })
-                    .map(|(glyph_index, texture_atlas, texture)| GlyphAtlasInfo {
-                        texture_atlas,
-                        texture,
-                        glyph_index,
-                    })
-            })
+        // Pick the higher of the page size or the smallest power of 2 greater than glyph_max_size
+        let containing = (1u32 << (32 - glyph_max_size.leading_zeros()))
+            .max(text_settings.font_atlas_page_size) as f32;
+        let mut atlas =
+            FontAtlas::new(textures, texture_atlases, Vec2::new(containing, containing));
+        let Some(glyph_index) = atlas.add_glyph(textures, texture_atlases, &glyph_texture) else {
+            return Err(TextError::FailedToAddGlyph(glyph_id));
+        };
+        self.pages.push(atlas);
+        Ok(self.insert_glyph(key, self.pages.len() - 1, glyph_index))
     }
 
-    /// Returns the number of font atlases in this set
+    /// Returns the number of pages in the atlas
     pub fn len(&self) -> usize {
-        self.font_atlases.len()
+        self.pages.len()
     }
 
-    /// Returns `true` if the font atlas set contains no elements
+    /// Returns `true` if the atlas has no pages
     pub fn is_empty(&self) -> bool {
-        self.font_atlases.is_empty()
+        self.pages.is_empty()
+    }
+
+    fn insert_glyph(&mut self, key: GlyphKey, page: usize, glyph_index: usize) -> GlyphAtlasInfo {
+        let glyph = CachedGlyph {
+            page,
+            glyph_index,
+            last_used: self.frame,
+        };
+        self.glyphs.insert(key, glyph);
+        self.atlas_info(glyph)
+    }
+
+    fn atlas_info(&self, glyph: CachedGlyph) -> GlyphAtlasInfo {
+        let atlas = &self.pages[glyph.page];
+        GlyphAtlasInfo {
+            texture_atlas: atlas.texture_atlas.clone_weak(),
+            texture: atlas.texture.clone_weak(),
+            glyph_index: glyph.glyph_index,
+        }
+    }
+
+    /// Evicts the least recently used glyph that wasn't used in the current frame.
+    fn evict_glyph(&mut self, texture_atlases: &mut Assets<TextureAtlasLayout>) -> bool {
+        let Some((&key, &glyph)) = self
+            .glyphs
+            .iter()
+            .filter(|(_, glyph)| glyph.last_used < self.frame)
+            .min_by_key(|(_, glyph)| glyph.last_used)
+        else {
+            return false;
+        };
+        self.glyphs.remove(&key);
+        self.pages[glyph.page].remove_glyph(texture_atlases, glyph.glyph_index);
+        self.generation += 1;
+        true
+    }
+}
+
+fn glyph_key(font: AssetId<Font>, font_size: f32, glyph_id: GlyphId, position: Point) -> GlyphKey {
+    GlyphKey {
+        font,
+        font_size: FloatOrd(font_size),
+        glyph_id,
+        subpixel_offset: position.into(),
+    }
+}
+
+/// Starts a new frame of glyph usage in [`FontAtlases`], and removes the glyphs of removed fonts.
+pub fn remove_dropped_font_glyphs(
+    mut font_atlases: ResMut<FontAtlases>,
+    mut texture_atlases: ResMut<Assets<TextureAtlasLayout>>,
+    mut font_events: EventReader<AssetEvent<Font>>,
+) {
+    let font_atlases = font_atlases.bypass_change_detection();
+    font_atlases.frame += 1;
+
+    // Clean up the glyphs of removed fonts
+    for event in font_events.read() {
+        if let AssetEvent::Removed { id } = event {
+            let pages = &mut font_atlases.pages;
+            font_atlases.glyphs.retain(|key, glyph| {
+                if key.font != *id {
+                    return true;
+                }
+                pages[glyph.page].remove_glyph(&mut texture_atlases, glyph.glyph_index);
+                false
+            });
+        }
     }
 }
@@ -4,15 +4,14 @@ use bevy_math::{Rect, Vec2};
 use bevy_reflect::Reflect;
 use bevy_render::texture::Image;
 use bevy_sprite::TextureAtlasLayout;
-use bevy_utils::warn_once;
 use glyph_brush_layout::{
     BuiltInLineBreaker, FontId, GlyphPositioner, Layout, SectionGeometry, SectionGlyph,
     SectionText, ToSectionText,
 };
 
 use crate::{
-    error::TextError, BreakLineOn, Font, FontAtlasSet, FontAtlasSets, GlyphAtlasInfo, JustifyText,
-    TextSettings, YAxisOrientation,
+    error::TextError, BreakLineOn, Font, FontAtlases, GlyphAtlasInfo, JustifyText, TextSettings,
+    YAxisOrientation,
 };
 
 pub struct GlyphBrush {
@@ -58,7 +57,7 @@ impl GlyphBrush {
         &self,
         glyphs: Vec<SectionGlyph>,
         sections: &[SectionText],
-        font_atlas_sets: &mut FontAtlasSets,
+        font_atlases: &mut FontAtlases,
         fonts: &Assets<Font>,
         texture_atlases: &mut Assets<TextureAtlasLayout>,
         textures: &mut Assets<Image>,
@@ -100,24 +99,19 @@ impl GlyphBrush {
             let section_data = sections_data[sg.section_index];
             if let Some(outlined_glyph) = section_data.1.font.outline_glyph(glyph) {
                 let bounds = outlined_glyph.px_bounds();
-                let font_atlas_set = font_atlas_sets
-                    .sets
-                    .entry(*section_data.0)
-                    .or_insert_with(FontAtlasSet::default);
-
-                let atlas_info = font_atlas_set
-                    .get_glyph_atlas_info(section_data.2, glyph_id, glyph_position)
+                let atlas_info = font_atlases
+                    .get_glyph_atlas_info(*section_data.0, section_data.2, glyph_id, glyph_position)
                     .map(Ok)
                     .unwrap_or_else(|| {
-                        font_atlas_set.add_glyph_to_atlas(texture_atlases, textures, outlined_glyph)
+                        font_atlases.add_glyph_to_atlas(
+                            *section_data.0,
+                            texture_atlases,
+                            textures,
+                            outlined_glyph,
+                            text_settings,
+                        )
                     })?;
 
-                if !text_settings.allow_dynamic_font_size
-                    && font_atlas_set.len() > text_settings.soft_max_font_atlases.get()
-                {
-                    warn_once!("warning[B0005]: Number of font atlases has exceeded the maximum of {}. Performance and memory usage may suffer.", text_settings.soft_max_font_atlases.get());
-                }
-
                 let texture_atlas = texture_atlases.get(&atlas_info.texture_atlas).unwrap();
                 let glyph_rect = texture_atlas.textures[atlas_info.glyph_index];
                 let size = Vec2::new(glyph_rect.width(), glyph_rect.height());
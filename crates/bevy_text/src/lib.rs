@@ -7,9 +7,11 @@ mod font_atlas;
 mod font_atlas_set;
 mod font_loader;
 mod glyph_brush;
+mod localization;
 mod pipeline;
 mod text;
 mod text2d;
+mod text3d;
 
 pub use error::*;
 pub use font::*;
@@ -17,13 +19,18 @@ pub use font_atlas::*;
 pub use font_atlas_set::*;
 pub use font_loader::*;
 pub use glyph_brush::*;
+pub use localization::*;
 pub use pipeline::*;
 pub use text::*;
 pub use text2d::*;
+pub use text3d::*;
 
 pub mod prelude {
     #[doc(hidden)]
-    pub use crate::{Font, JustifyText, Text, Text2dBundle, TextError, TextSection, TextStyle};
+    pub use crate::{
+        Font, JustifyText, Localization, LocalizedText, Text, Text2dBundle, Text3d, Text3dBundle,
+        TextError, TextSection, TextStyle,
+    };
 }
 
 use bevy_app::prelude::*;
@@ -45,12 +52,17 @@ pub struct TextPlugin;
 /// Settings used to configure the [`TextPlugin`].
 #[derive(Resource)]
 pub struct TextSettings {
-    /// Soft maximum number of font atlases supported in a [`FontAtlasSet`]. When this is exceeded,
-    /// a warning will be emitted a single time.
+    /// Soft maximum number of pages of the [`FontAtlases`]. Once it is reached, the least recently
+    /// used glyphs are evicted to make room for new ones. If every glyph is in use, a new page is
+    /// added anyway and a warning will be emitted a single time.
     pub soft_max_font_atlases: NonZeroUsize,
     /// Allows font size to be set dynamically exceeding the amount set in `soft_max_font_atlases`.
     /// Note each font size has to be generated which can have a strong performance impact.
     pub allow_dynamic_font_size: bool,
+    /// Width and height in pixels of the pages of the [`FontAtlases`].
+    ///
+    /// Glyphs larger than this get a page of their own.
+    pub font_atlas_page_size: u32,
 }
 
 impl Default for TextSettings {
@@ -58,6 +70,7 @@ impl Default for TextSettings {
         Self {
             soft_max_font_atlases: NonZeroUsize::new(16).unwrap(),
             allow_dynamic_font_size: false,
+            font_atlas_page_size: 512,
         }
     }
 }
@@ -73,6 +86,7 @@ pub enum YAxisOrientation {
 impl Plugin for TextPlugin {
     fn build(&self, app: &mut App) {
         app.init_asset::<Font>()
+            .init_asset::<Locale>()
             .register_type::<Text>()
             .register_type::<Text2dBounds>()
             .register_type::<TextSection>()
@@ -80,21 +94,28 @@ impl Plugin for TextPlugin {
             .register_type::<TextStyle>()
             .register_type::<JustifyText>()
             .register_type::<BreakLineOn>()
+            .register_type::<LocalizedText>()
+            .register_type::<LocalizedSection>()
             .init_asset_loader::<FontLoader>()
+            .init_asset_loader::<LocaleLoader>()
             .init_resource::<TextSettings>()
-            .init_resource::<FontAtlasSets>()
+            .init_resource::<Localization>()
+            .init_resource::<FontAtlases>()
             .insert_resource(TextPipeline::default())
+            .add_plugins(Text3dPlugin)
             .add_systems(
                 PostUpdate,
                 (
+                    update_localized_text.in_set(LocalizationSystem),
                     update_text2d_layout
-                        .after(font_atlas_set::remove_dropped_font_atlas_sets)
+                        .after(font_atlas_set::remove_dropped_font_glyphs)
+                        .after(LocalizationSystem)
                         // Potential conflict: `Assets<Image>`
                         // In practice, they run independently since `bevy_render::camera_update_system`
                         // will only ever observe its own render target, and `update_text2d_layout`
                         // will never modify a pre-existing `Image` asset.
                         .ambiguous_with(CameraUpdateSystem),
-                    remove_dropped_font_atlas_sets,
+                    remove_dropped_font_glyphs,
                 ),
             );
 
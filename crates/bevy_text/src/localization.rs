@@ -0,0 +1,403 @@
+use bevy_asset::{
+    io::Reader, Asset, AssetEvent, AssetId, AssetLoader, Assets, AsyncReadExt, Handle, LoadContext,
+};
+use bevy_ecs::{prelude::*, reflect::ReflectComponent};
+use bevy_reflect::prelude::*;
+use bevy_utils::HashMap;
+use thiserror::Error;
+
+use crate::Text;
+
+/// The translated messages of a language, loaded from a `.ftl` file.
+///
+/// The files use a subset of the [Fluent](https://projectfluent.org/) syntax:
+///
+/// ```ftl
+/// # Comments start with `#`.
+/// greeting = Hello, { $name }!
+/// intro =
+///     Messages can span
+///     several lines.
+/// ```
+///
+/// Each message has an identifier and a value. Lines indented after a message continue its
+/// value. `{ $name }` placeables are replaced with the arguments given when formatting the
+/// message, `{ other-message }` placeables with the value of another message of the locale, and
+/// `{ "{" }` with a literal brace.
+#[derive(Asset, TypePath, Debug, Clone, Default)]
+pub struct Locale {
+    messages: HashMap<String, String>,
+}
+
+impl Locale {
+    /// Parses a locale from the content of a `.ftl` file.
+    pub fn from_ftl(source: &str) -> Result<Self, LocaleParseError> {
+        let mut messages: HashMap<String, String> = HashMap::default();
+        let mut current: Option<String> = None;
+
+        for (index, line) in source.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim_end();
+            if line.trim_start().is_empty() {
+                continue;
+            }
+
+            if line.starts_with(char::is_whitespace) {
+                let Some(id) = current.as_ref() else {
+                    return Err(LocaleParseError::UnexpectedIndentation(line_number));
+                };
+                let value = messages.get_mut(id).unwrap();
+                if !value.is_empty() {
+                    value.push('\n');
+                }
+                value.push_str(line.trim_start());
+                continue;
+            }
+
+            current = None;
+            if line.starts_with('#') {
+                continue;
+            }
+
+            let Some((id, value)) = line.split_once('=') else {
+                return Err(LocaleParseError::ExpectedMessage(line_number));
+            };
+            let id = id.trim();
+            if !is_identifier(id) {
+                return Err(LocaleParseError::InvalidIdentifier(line_number));
+            }
+            if messages
+                .insert(id.to_string(), value.trim().to_string())
+                .is_some()
+            {
+                return Err(LocaleParseError::DuplicateMessage(id.to_string()));
+            }
+            current = Some(id.to_string());
+        }
+
+        Ok(Self { messages })
+    }
+
+    /// Returns the unformatted value of the message `id`.
+    pub fn get(&self, id: &str) -> Option<&str> {
+        self.messages.get(id).map(String::as_str)
+    }
+
+    /// Formats the message `id`, replacing its placeables with `args`.
+    ///
+    /// Arguments missing from `args` are written as `{$name}`.
+    pub fn format(&self, id: &str, args: &[(String, String)]) -> Option<String> {
+        let mut output = String::new();
+        self.format_into(id, args, &mut output, 0).then_some(output)
+    }
+
+    fn format_into(
+        &self,
+        id: &str,
+        args: &[(String, String)],
+        output: &mut String,
+        depth: usize,
+    ) -> bool {
+        const MAX_DEPTH: usize = 16;
+
+        let Some(mut value) = self.get(id) else {
+            return false;
+        };
+
+        while let Some(start) = value.find('{') {
+            output.push_str(&value[..start]);
+            let Some(end) = value[start..].find('}') else {
+                value = &value[start..];
+                break;
+            };
+            let placeable = value[start + 1..start + end].trim();
+            if let Some(name) = placeable.strip_prefix('$') {
+                match args.iter().find(|(arg, _)| arg == name) {
+                    Some((_, arg)) => output.push_str(arg),
+                    None => {
+                        output.push_str("{$");
+                        output.push_str(name);
+                        output.push('}');
+                    }
+                }
+            } else if let Some(literal) = placeable
+                .strip_prefix('"')
+                .and_then(|placeable| placeable.strip_suffix('"'))
+            {
+                output.push_str(literal);
+            } else if depth >= MAX_DEPTH || !self.format_into(placeable, args, output, depth + 1) {
+                output.push('{');
+                output.push_str(placeable);
+                output.push('}');
+            }
+            value = &value[start + end + 1..];
+        }
+        output.push_str(value);
+        true
+    }
+}
+
+fn is_identifier(id: &str) -> bool {
+    let mut chars = id.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Errors that occur when parsing a [`Locale`]
+#[derive(Debug, Eq, PartialEq, Clone, Error)]
+pub enum LocaleParseError {
+    #[error("Line {0} is indented but doesn't continue a message")]
+    UnexpectedIndentation(usize),
+    #[error("Expected a message `identifier = value` on line {0}")]
+    ExpectedMessage(usize),
+    #[error("Invalid message identifier on line {0}")]
+    InvalidIdentifier(usize),
+    #[error("Message `{0}` is defined several times")]
+    DuplicateMessage(String),
+}
+
+#[derive(Default)]
+pub struct LocaleLoader;
+
+/// Possible errors that can be produced by [`LocaleLoader`]
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum LocaleLoaderError {
+    /// An [IO](std::io) Error
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// The file isn't valid UTF-8
+    #[error(transparent)]
+    Utf8(#[from] std::str::Utf8Error),
+    /// A [`LocaleParseError`]
+    #[error(transparent)]
+    Parse(#[from] LocaleParseError),
+}
+
+impl AssetLoader for LocaleLoader {
+    type Asset = Locale;
+    type Settings = ();
+    type Error = LocaleLoaderError;
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext,
+    ) -> bevy_utils::BoxedFuture<'a, Result<Locale, Self::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            Ok(Locale::from_ftl(std::str::from_utf8(&bytes)?)?)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ftl"]
+    }
+}
+
+/// The locales used to resolve [`LocalizedText`], from the most to the least preferred.
+///
+/// A message missing from a locale, or a locale that isn't loaded yet, falls back to the next
+/// locale of the chain. Messages missing from every locale are displayed as their identifier.
+///
+/// ```
+/// # use bevy_asset::AssetServer;
+/// # use bevy_ecs::system::{Res, ResMut};
+/// # use bevy_text::Localization;
+/// fn use_french(asset_server: Res<AssetServer>, mut localization: ResMut<Localization>) {
+///     localization.set_locales([
+///         asset_server.load("locales/fr-CA.ftl"),
+///         asset_server.load("locales/fr.ftl"),
+///         asset_server.load("locales/en.ftl"),
+///     ]);
+/// }
+/// ```
+#[derive(Resource, Debug, Clone, Default)]
+pub struct Localization {
+    locales: Vec<Handle<Locale>>,
+}
+
+impl Localization {
+    /// Replaces the fallback chain of locales.
+    pub fn set_locales(&mut self, locales: impl IntoIterator<Item = Handle<Locale>>) {
+        self.locales = locales.into_iter().collect();
+    }
+
+    /// The fallback chain of locales.
+    pub fn locales(&self) -> &[Handle<Locale>] {
+        &self.locales
+    }
+
+    /// Formats the message `id` with the first locale of the chain defining it.
+    pub fn format(
+        &self,
+        locales: &Assets<Locale>,
+        id: &str,
+        args: &[(String, String)],
+    ) -> Option<String> {
+        self.locales
+            .iter()
+            .filter_map(|handle| locales.get(handle))
+            .find_map(|locale| locale.format(id, args))
+    }
+
+    fn contains(&self, id: AssetId<Locale>) -> bool {
+        self.locales.iter().any(|handle| handle.id() == id)
+    }
+}
+
+/// Keeps the sections of a [`Text`] up to date with the messages of the current [`Localization`].
+///
+/// The value of each section of the [`Text`] is replaced with the formatted message of the
+/// [`LocalizedSection`] at the same index. Sections without a [`LocalizedSection`] are left as is.
+///
+/// ```
+/// # use bevy_text::LocalizedText;
+/// let score = LocalizedText::new("score").with_arg("points", 42.to_string());
+/// ```
+#[derive(Component, Debug, Clone, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct LocalizedText {
+    pub sections: Vec<LocalizedSection>,
+}
+
+/// A message of a [`LocalizedText`] and its arguments.
+#[derive(Debug, Clone, Default, Reflect)]
+pub struct LocalizedSection {
+    /// The identifier of the message.
+    pub id: String,
+    /// The values of the `{ $name }` placeables of the message.
+    pub args: Vec<(String, String)>,
+}
+
+impl LocalizedSection {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            args: Vec::new(),
+        }
+    }
+
+    /// Sets the value of the `{ $name }` placeables of the message.
+    pub fn with_arg(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.set_arg(name, value);
+        self
+    }
+
+    /// Sets the value of the `{ $name }` placeables of the message.
+    pub fn set_arg(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        let name = name.into();
+        let value = value.into();
+        match self.args.iter_mut().find(|(arg, _)| *arg == name) {
+            Some((_, arg)) => *arg = value,
+            None => self.args.push((name, value)),
+        }
+    }
+}
+
+impl LocalizedText {
+    /// Constructs a [`LocalizedText`] for a [`Text`] with a single section.
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            sections: vec![LocalizedSection::new(id)],
+        }
+    }
+
+    /// Constructs a [`LocalizedText`] from a list of sections.
+    pub fn from_sections(sections: impl IntoIterator<Item = LocalizedSection>) -> Self {
+        Self {
+            sections: sections.into_iter().collect(),
+        }
+    }
+
+    /// Sets an argument of the last section.
+    pub fn with_arg(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        if let Some(section) = self.sections.last_mut() {
+            section.set_arg(name, value);
+        }
+        self
+    }
+}
+
+/// System set containing [`update_localized_text`], which runs before text layout.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
+pub struct LocalizationSystem;
+
+/// Resolves [`LocalizedText`] into the sections of [`Text`] when the text, the
+/// [`Localization`] or one of its locales changes.
+pub fn update_localized_text(
+    localization: Res<Localization>,
+    locales: Res<Assets<Locale>>,
+    mut locale_events: EventReader<AssetEvent<Locale>>,
+    mut text_query: Query<(Ref<LocalizedText>, &mut Text)>,
+) {
+    let locales_changed = locale_events.read().any(|event| match event {
+        AssetEvent::Added { id }
+        | AssetEvent::Modified { id }
+        | AssetEvent::Removed { id }
+        | AssetEvent::LoadedWithDependencies { id } => localization.contains(*id),
+        AssetEvent::Unused { .. } => false,
+    });
+    let localization_changed = localization.is_changed() || locales_changed;
+
+    for (localized, mut text) in &mut text_query {
+        if !(localization_changed || localized.is_changed()) {
+            continue;
+        }
+
+        let sections = text.bypass_change_detection().sections.iter_mut();
+        let mut changed = false;
+        for (section, localized_section) in sections.zip(&localized.sections) {
+            let value = localization
+                .format(&locales, &localized_section.id, &localized_section.args)
+                .unwrap_or_else(|| localized_section.id.clone());
+            if section.value != value {
+                section.value = value;
+                changed = true;
+            }
+        }
+        if changed {
+            text.set_changed();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_locale_messages() {
+        let locale = Locale::from_ftl(
+            "# Menu\n\
+             app-name = Bevy\n\
+             greeting = Welcome to { app-name }, { $name }!\n\
+             multiline =\n    first line\n    second line\n\
+             braces = { \"{\" }literal }\n",
+        )
+        .unwrap();
+
+        let args = [("name".to_string(), "Alice".to_string())];
+        assert_eq!(
+            locale.format("greeting", &args).as_deref(),
+            Some("Welcome to Bevy, Alice!")
+        );
+        assert_eq!(
+            locale.format("greeting", &[]).as_deref(),
+            Some("Welcome to Bevy, {$name}!")
+        );
+        assert_eq!(locale.get("multiline"), Some("first line\nsecond line"));
+        assert_eq!(locale.format("braces", &[]).as_deref(), Some("{literal }"));
+        assert_eq!(locale.format("missing", &[]), None);
+
+        assert_eq!(
+            Locale::from_ftl("a = 1\na = 2").unwrap_err(),
+            LocaleParseError::DuplicateMessage("a".to_string())
+        );
+        assert_eq!(
+            Locale::from_ftl("  indented").unwrap_err(),
+            LocaleParseError::UnexpectedIndentation(1)
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     compute_text_bounds, error::TextError, glyph_brush::GlyphBrush, scale_value, BreakLineOn, Font,
-    FontAtlasSets, JustifyText, PositionedGlyph, Text, TextSection, TextSettings, YAxisOrientation,
+    FontAtlases, JustifyText, PositionedGlyph, Text, TextSection, TextSettings, YAxisOrientation,
 };
 use ab_glyph::PxScale;
 use bevy_asset::{AssetId, Assets, Handle};
@@ -49,7 +49,7 @@ impl TextPipeline {
         text_alignment: JustifyText,
         linebreak_behavior: BreakLineOn,
         bounds: Vec2,
-        font_atlas_sets: &mut FontAtlasSets,
+        font_atlases: &mut FontAtlases,
         texture_atlases: &mut Assets<TextureAtlasLayout>,
         textures: &mut Assets<Image>,
         text_settings: &TextSettings,
@@ -90,7 +90,7 @@ impl TextPipeline {
         let glyphs = self.brush.process_glyphs(
             section_glyphs,
             &sections,
-            font_atlas_sets,
+            font_atlases,
             fonts,
             texture_atlases,
             textures,
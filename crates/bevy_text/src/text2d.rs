@@ -1,5 +1,5 @@
 use crate::{
-    BreakLineOn, Font, FontAtlasSets, PositionedGlyph, Text, TextError, TextLayoutInfo,
+    BreakLineOn, Font, FontAtlases, PositionedGlyph, Text, Text3d, TextError, TextLayoutInfo,
     TextPipeline, TextSettings, YAxisOrientation,
 };
 use bevy_asset::Assets;
@@ -9,7 +9,7 @@ use bevy_ecs::{
     component::Component,
     entity::Entity,
     event::EventReader,
-    prelude::With,
+    prelude::{With, Without},
     reflect::ReflectComponent,
     system::{Commands, Local, Query, Res, ResMut},
 };
@@ -86,14 +86,17 @@ pub fn extract_text2d_sprite(
     texture_atlases: Extract<Res<Assets<TextureAtlasLayout>>>,
     windows: Extract<Query<&Window, With<PrimaryWindow>>>,
     text2d_query: Extract<
-        Query<(
-            Entity,
-            &ViewVisibility,
-            &Text,
-            &TextLayoutInfo,
-            &Anchor,
-            &GlobalTransform,
-        )>,
+        Query<
+            (
+                Entity,
+                &ViewVisibility,
+                &Text,
+                &TextLayoutInfo,
+                &Anchor,
+                &GlobalTransform,
+            ),
+            Without<Text3d>,
+        >,
     >,
 ) {
     // TODO: Support window-independent scaling: https://github.com/bevyengine/bevy/issues/5621
@@ -160,18 +163,22 @@ pub fn extract_text2d_sprite(
 pub fn update_text2d_layout(
     // Text items which should be reprocessed again, generally when the font hasn't loaded yet.
     mut queue: Local<HashSet<Entity>>,
+    mut last_atlas_generation: Local<u64>,
     mut textures: ResMut<Assets<Image>>,
     fonts: Res<Assets<Font>>,
     text_settings: Res<TextSettings>,
     windows: Query<&Window, With<PrimaryWindow>>,
     mut scale_factor_changed: EventReader<WindowScaleFactorChanged>,
     mut texture_atlases: ResMut<Assets<TextureAtlasLayout>>,
-    mut font_atlas_sets: ResMut<FontAtlasSets>,
+    mut font_atlases: ResMut<FontAtlases>,
     mut text_pipeline: ResMut<TextPipeline>,
     mut text_query: Query<(Entity, Ref<Text>, Ref<Text2dBounds>, &mut TextLayoutInfo)>,
 ) {
     // We need to consume the entire iterator, hence `last`
     let factor_changed = scale_factor_changed.read().last().is_some();
+    // Text laid out before glyphs were evicted from the atlas may reference the evicted glyphs
+    let glyphs_evicted = *last_atlas_generation != font_atlases.generation();
+    *last_atlas_generation = font_atlases.generation();
 
     // TODO: Support window-independent scaling: https://github.com/bevyengine/bevy/issues/5621
     let scale_factor = windows
@@ -182,7 +189,12 @@ pub fn update_text2d_layout(
     let inverse_scale_factor = scale_factor.recip();
 
     for (entity, text, bounds, mut text_layout_info) in &mut text_query {
-        if factor_changed || text.is_changed() || bounds.is_changed() || queue.remove(&entity) {
+        if factor_changed
+            || glyphs_evicted
+            || text.is_changed()
+            || bounds.is_changed()
+            || queue.remove(&entity)
+        {
             let text_bounds = Vec2::new(
                 if text.linebreak_behavior == BreakLineOn::NoWrap {
                     f32::INFINITY
@@ -198,7 +210,7 @@ pub fn update_text2d_layout(
                 text.justify,
                 text.linebreak_behavior,
                 text_bounds,
-                &mut font_atlas_sets,
+                &mut font_atlases,
                 &mut texture_atlases,
                 &mut textures,
                 text_settings.as_ref(),
@@ -0,0 +1,767 @@
+use std::ops::Range;
+
+use crate::{PositionedGlyph, Text, Text2dBounds, TextLayoutInfo};
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, AssetEvent, AssetId, Assets, Handle};
+use bevy_core_pipeline::{
+    core_3d::{AlphaMask3d, Camera3d, Transparent3d, CORE_3D_DEPTH_FORMAT},
+    tonemapping::{
+        get_lut_bind_group_layout_entries, get_lut_bindings, DebandDither, Tonemapping,
+        TonemappingLuts,
+    },
+};
+use bevy_ecs::{
+    prelude::*,
+    system::{lifetimeless::*, SystemParamItem},
+};
+use bevy_math::{Vec3, Vec4};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    prelude::Color,
+    render_asset::RenderAssets,
+    render_phase::{
+        AddRenderCommand, DrawFunctions, PhaseItem, RenderCommand, RenderCommandResult,
+        RenderPhase, SetItemPipeline, TrackedRenderPass,
+    },
+    render_resource::{
+        binding_types::{sampler, texture_2d, uniform_buffer},
+        BindGroupEntries, *,
+    },
+    renderer::{RenderDevice, RenderQueue},
+    texture::{BevyDefault, Image},
+    view::{
+        ExtractedView, InheritedVisibility, Msaa, ViewTarget, ViewUniform, ViewUniformOffset,
+        ViewUniforms, ViewVisibility, Visibility, VisibleEntities,
+    },
+    Extract, ExtractSchedule, Render, RenderApp, RenderSet,
+};
+use bevy_sprite::{Anchor, SpriteAssetEvents, TextureAtlasLayout};
+use bevy_transform::prelude::{GlobalTransform, Transform};
+use bevy_utils::{EntityHashSet, HashMap};
+use bevy_window::{PrimaryWindow, Window};
+use bytemuck::{Pod, Zeroable};
+
+pub const TEXT3D_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(9130714382570621143);
+
+/// Renders [`Text`] as quads in world space, tested against the depth of the 3D scene, via a
+/// 3D `Camera3dBundle`.
+///
+/// Unlike meshes, text isn't lit. The layout of the text is computed like a [`Text2dBundle`],
+/// in logical pixels, and scaled to world units by [`Text3d::world_scale`] and the transform of
+/// the entity.
+///
+/// [`Text2dBundle`]: crate::Text2dBundle
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+#[reflect(Component, Default)]
+pub struct Text3d {
+    /// The size of a logical pixel of the text in world units.
+    ///
+    /// Defaults to `0.01`, so that a font size of 100 is one world unit high.
+    pub world_scale: f32,
+    /// Whether the text faces the camera.
+    pub billboard: Text3dBillboard,
+    /// How the edges of the glyphs are rendered.
+    pub alpha_mode: Text3dAlphaMode,
+}
+
+impl Default for Text3d {
+    fn default() -> Self {
+        Self {
+            world_scale: 0.01,
+            billboard: Text3dBillboard::None,
+            alpha_mode: Text3dAlphaMode::default(),
+        }
+    }
+}
+
+/// Whether a [`Text3d`] faces the camera.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Default)]
+pub enum Text3dBillboard {
+    /// The text lies on the XY plane of its transform.
+    #[default]
+    None,
+    /// The text always faces the camera, keeping the translation and scale of its transform.
+    Full,
+    /// The text rotates around the world Y axis to face the camera, staying upright.
+    AxisY,
+}
+
+/// How the glyphs of a [`Text3d`] are blended with the scene.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Default)]
+pub enum Text3dAlphaMode {
+    /// The texels of the glyphs with an alpha below the threshold are discarded, and the others
+    /// are opaque and write depth. The text is sorted front to back with the alpha masked meshes.
+    Mask(f32),
+    /// The glyphs are blended with the scene behind them and don't write depth. The text is
+    /// sorted back to front with the transparent meshes.
+    Blend,
+}
+
+impl Default for Text3dAlphaMode {
+    fn default() -> Self {
+        Self::Mask(0.5)
+    }
+}
+
+/// The bundle of components needed to draw text in a 3D scene via a 3D `Camera3dBundle`.
+#[derive(Bundle, Clone, Debug, Default)]
+pub struct Text3dBundle {
+    /// Contains the text.
+    pub text: Text,
+    /// How the text is positioned relative to its transform.
+    pub text_anchor: Anchor,
+    /// The maximum width and height of the text, in logical pixels.
+    pub text_2d_bounds: Text2dBounds,
+    /// How the text is rendered in world space.
+    pub text_3d: Text3d,
+    /// The transform of the text.
+    pub transform: Transform,
+    /// The global transform of the text.
+    pub global_transform: GlobalTransform,
+    /// The visibility properties of the text.
+    pub visibility: Visibility,
+    /// Inherited visibility of an entity.
+    pub inherited_visibility: InheritedVisibility,
+    /// Algorithmically-computed indication of whether an entity is visible and should be extracted for rendering
+    pub view_visibility: ViewVisibility,
+    /// Contains the size of the text and its glyph's position and scale data. Generated via [`TextPipeline::queue_text`](crate::TextPipeline::queue_text)
+    pub text_layout_info: TextLayoutInfo,
+}
+
+/// Renders the [`Text3d`] entities, laid out by [`update_text2d_layout`](crate::update_text2d_layout).
+///
+/// Added by the [`TextPlugin`](crate::TextPlugin).
+pub struct Text3dPlugin;
+
+impl Plugin for Text3dPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(app, TEXT3D_SHADER_HANDLE, "text3d.wgsl", Shader::from_wgsl);
+
+        app.register_type::<Text3d>()
+            .register_type::<Text3dBillboard>()
+            .register_type::<Text3dAlphaMode>();
+
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .init_resource::<Text3dMeta>()
+                .init_resource::<Text3dImageBindGroups>()
+                .init_resource::<SpecializedRenderPipelines<Text3dPipeline>>()
+                .add_render_command::<AlphaMask3d, DrawText3d>()
+                .add_render_command::<Transparent3d, DrawText3d>()
+                .add_systems(ExtractSchedule, extract_text3d)
+                .add_systems(
+                    Render,
+                    (
+                        queue_text3d.in_set(RenderSet::Queue),
+                        prepare_text3d.in_set(RenderSet::PrepareBindGroups),
+                    ),
+                );
+        }
+    }
+
+    fn finish(&self, app: &mut App) {
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.init_resource::<Text3dPipeline>();
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct Text3dPipeline {
+    view_layout: BindGroupLayout,
+    material_layout: BindGroupLayout,
+}
+
+impl FromWorld for Text3dPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let [lut_texture, lut_sampler] = get_lut_bind_group_layout_entries();
+
+        let view_layout = render_device.create_bind_group_layout(
+            "text3d_view_layout",
+            &BindGroupLayoutEntries::with_indices(
+                ShaderStages::VERTEX_FRAGMENT,
+                (
+                    (0, uniform_buffer::<ViewUniform>(true)),
+                    (18, lut_texture),
+                    (19, lut_sampler),
+                ),
+            ),
+        );
+
+        let material_layout = render_device.create_bind_group_layout(
+            "text3d_material_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    sampler(SamplerBindingType::Filtering),
+                ),
+            ),
+        );
+
+        Text3dPipeline {
+            view_layout,
+            material_layout,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Text3dPipelineKey {
+    pub msaa_samples: u32,
+    pub hdr: bool,
+    /// The tonemapping applied in the shader, for views that aren't HDR.
+    pub tonemapping: Option<Tonemapping>,
+    pub deband_dither: bool,
+    pub billboard: Text3dBillboard,
+    pub alpha_mask: bool,
+    /// The format of the depth texture of the view.
+    pub depth_format: TextureFormat,
+}
+
+impl SpecializedRenderPipeline for Text3dPipeline {
+    type Key = Text3dPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let mut shader_defs = Vec::new();
+        if let Some(tonemapping) = key.tonemapping {
+            shader_defs.push("TONEMAP_IN_SHADER".into());
+            shader_defs.push(
+                match tonemapping {
+                    Tonemapping::None => "TONEMAP_METHOD_NONE",
+                    Tonemapping::Reinhard => "TONEMAP_METHOD_REINHARD",
+                    Tonemapping::ReinhardLuminance => "TONEMAP_METHOD_REINHARD_LUMINANCE",
+                    Tonemapping::AcesFitted => "TONEMAP_METHOD_ACES_FITTED",
+                    Tonemapping::AgX => "TONEMAP_METHOD_AGX",
+                    Tonemapping::SomewhatBoringDisplayTransform => {
+                        "TONEMAP_METHOD_SOMEWHAT_BORING_DISPLAY_TRANSFORM"
+                    }
+                    Tonemapping::TonyMcMapface => "TONEMAP_METHOD_TONY_MC_MAPFACE",
+                    Tonemapping::BlenderFilmic => "TONEMAP_METHOD_BLENDER_FILMIC",
+                }
+                .into(),
+            );
+
+            // Debanding is tied to tonemapping in the shader, cannot run without it.
+            if key.deband_dither {
+                shader_defs.push("DEBAND_DITHER".into());
+            }
+        }
+        match key.billboard {
+            Text3dBillboard::None => {}
+            Text3dBillboard::Full => shader_defs.push("BILLBOARD".into()),
+            Text3dBillboard::AxisY => shader_defs.push("BILLBOARD_AXIS_Y".into()),
+        }
+        if key.alpha_mask {
+            shader_defs.push("ALPHA_MASK".into());
+        }
+
+        let format = match key.hdr {
+            true => ViewTarget::TEXTURE_FORMAT_HDR,
+            false => TextureFormat::bevy_default(),
+        };
+
+        let instance_rate_vertex_buffer_layout = VertexBufferLayout::from_vertex_formats(
+            VertexStepMode::Instance,
+            [
+                // i_model_transpose_col0, i_model_transpose_col1, i_model_transpose_col2
+                VertexFormat::Float32x4,
+                VertexFormat::Float32x4,
+                VertexFormat::Float32x4,
+                // i_rect
+                VertexFormat::Float32x4,
+                // i_uv_offset_scale
+                VertexFormat::Float32x4,
+                // i_color
+                VertexFormat::Float32x4,
+                // i_alpha_cutoff, padded to the 16 byte alignment of the instance
+                VertexFormat::Float32x4,
+            ],
+        );
+
+        RenderPipelineDescriptor {
+            vertex: VertexState {
+                shader: TEXT3D_SHADER_HANDLE,
+                entry_point: "vertex".into(),
+                shader_defs: shader_defs.clone(),
+                buffers: vec![instance_rate_vertex_buffer_layout],
+            },
+            fragment: Some(FragmentState {
+                shader: TEXT3D_SHADER_HANDLE,
+                shader_defs,
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: (!key.alpha_mask).then_some(BlendState::ALPHA_BLENDING),
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            layout: vec![self.view_layout.clone(), self.material_layout.clone()],
+            primitive: PrimitiveState {
+                front_face: FrontFace::Ccw,
+                cull_mode: None,
+                unclipped_depth: false,
+                polygon_mode: PolygonMode::Fill,
+                conservative: false,
+                topology: PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+            },
+            depth_stencil: Some(DepthStencilState {
+                format: key.depth_format,
+                depth_write_enabled: key.alpha_mask,
+                depth_compare: CompareFunction::GreaterEqual,
+                stencil: StencilState::default(),
+                bias: DepthBiasState::default(),
+            }),
+            multisample: MultisampleState {
+                count: key.msaa_samples,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            label: Some("text3d_pipeline".into()),
+            push_constant_ranges: Vec::new(),
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct Text3dInstance {
+    // Affine 4x3 transposed to 3x4
+    i_model_transpose: [Vec4; 3],
+    i_rect: [f32; 4],
+    i_uv_offset_scale: [f32; 4],
+    i_color: [f32; 4],
+    i_alpha_cutoff: f32,
+    // Fills the tail padding up to the alignment of `Vec4`, which `Pod` doesn't allow.
+    _padding: [f32; 3],
+}
+
+/// The glyphs of a [`Text3d`] stored in the same font atlas texture, drawn together.
+#[derive(Component)]
+pub struct ExtractedText3d {
+    /// The entity of the [`Text3d`], used to determine its visibility.
+    pub original_entity: Entity,
+    pub translation: Vec3,
+    pub billboard: Text3dBillboard,
+    pub alpha_mode: Text3dAlphaMode,
+    /// Asset ID of the font atlas [`Image`] of the glyphs
+    pub image_handle_id: AssetId<Image>,
+    instances: Vec<Text3dInstance>,
+    /// The range of the instances in the instance buffer, set in [`prepare_text3d`].
+    range: Range<u32>,
+}
+
+/// This system extracts the glyphs of the [`Text3d`] components to the "render world", with one
+/// [`ExtractedText3d`] entity per text and font atlas texture.
+pub fn extract_text3d(
+    mut commands: Commands,
+    mut previous_len: Local<usize>,
+    texture_atlases: Extract<Res<Assets<TextureAtlasLayout>>>,
+    windows: Extract<Query<&Window, With<PrimaryWindow>>>,
+    text3d_query: Extract<
+        Query<(
+            Entity,
+            &ViewVisibility,
+            &Text,
+            &Text3d,
+            &TextLayoutInfo,
+            &Anchor,
+            &GlobalTransform,
+        )>,
+    >,
+) {
+    // Glyphs are laid out in physical pixels, like 2D text.
+    let scale_factor = windows
+        .get_single()
+        .map(|window| window.resolution.scale_factor())
+        .unwrap_or(1.0);
+
+    let mut extracted = Vec::with_capacity(*previous_len);
+    let mut atlas_texts = HashMap::<AssetId<Image>, usize>::new();
+    for (
+        original_entity,
+        view_visibility,
+        text,
+        text_3d,
+        text_layout_info,
+        anchor,
+        global_transform,
+    ) in text3d_query.iter()
+    {
+        if !view_visibility.get() {
+            continue;
+        }
+
+        let transform = global_transform.affine();
+        let model_transpose_3x3 = transform.matrix3.transpose();
+        let model_transpose = [
+            model_transpose_3x3.x_axis.extend(transform.translation.x),
+            model_transpose_3x3.y_axis.extend(transform.translation.y),
+            model_transpose_3x3.z_axis.extend(transform.translation.z),
+        ];
+        let alignment_translation =
+            text_layout_info.logical_size * -(anchor.as_vec() + 0.5) * text_3d.world_scale;
+        let glyph_scale = text_3d.world_scale / scale_factor;
+        let alpha_cutoff = match text_3d.alpha_mode {
+            Text3dAlphaMode::Mask(cutoff) => cutoff,
+            Text3dAlphaMode::Blend => 0.0,
+        };
+
+        atlas_texts.clear();
+        let mut color = Color::WHITE;
+        let mut current_section = usize::MAX;
+        for PositionedGlyph {
+            position,
+            atlas_info,
+            section_index,
+            ..
+        } in &text_layout_info.glyphs
+        {
+            if *section_index != current_section {
+                color = text.sections[*section_index].style.color.as_rgba_linear();
+                current_section = *section_index;
+            }
+            let atlas = texture_atlases.get(&atlas_info.texture_atlas).unwrap();
+            let rect = atlas.textures[atlas_info.glyph_index];
+            let size = rect.size() * glyph_scale;
+            let min = alignment_translation + *position * glyph_scale - size / 2.0;
+
+            let image_handle_id = atlas_info.texture.id();
+            let index = *atlas_texts.entry(image_handle_id).or_insert_with(|| {
+                extracted.push((
+                    commands.spawn_empty().id(),
+                    ExtractedText3d {
+                        original_entity,
+                        translation: transform.translation.into(),
+                        billboard: text_3d.billboard,
+                        alpha_mode: text_3d.alpha_mode,
+                        image_handle_id,
+                        instances: Vec::new(),
+                        range: 0..0,
+                    },
+                ));
+                extracted.len() - 1
+            });
+            extracted[index].1.instances.push(Text3dInstance {
+                i_model_transpose: model_transpose,
+                i_rect: [min.x, min.y, size.x, size.y],
+                i_uv_offset_scale: [
+                    rect.min.x / atlas.size.x,
+                    rect.max.y / atlas.size.y,
+                    rect.width() / atlas.size.x,
+                    -rect.height() / atlas.size.y,
+                ],
+                i_color: color.as_linear_rgba_f32(),
+                i_alpha_cutoff: alpha_cutoff,
+                _padding: [0.0; 3],
+            });
+        }
+    }
+    *previous_len = extracted.len();
+    commands.insert_or_spawn_batch(extracted);
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn queue_text3d(
+    alpha_mask_draw_functions: Res<DrawFunctions<AlphaMask3d>>,
+    transparent_draw_functions: Res<DrawFunctions<Transparent3d>>,
+    text3d_pipeline: Res<Text3dPipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<Text3dPipeline>>,
+    pipeline_cache: Res<PipelineCache>,
+    msaa: Res<Msaa>,
+    texts: Query<(Entity, &ExtractedText3d)>,
+    mut views: Query<(
+        &mut RenderPhase<AlphaMask3d>,
+        &mut RenderPhase<Transparent3d>,
+        &VisibleEntities,
+        &ExtractedView,
+        Option<&Tonemapping>,
+        Option<&DebandDither>,
+        Option<&Camera3d>,
+    )>,
+    mut visible_entities: Local<EntityHashSet<Entity>>,
+) {
+    let draw_alpha_mask = alpha_mask_draw_functions.read().id::<DrawText3d>();
+    let draw_transparent = transparent_draw_functions.read().id::<DrawText3d>();
+
+    for (
+        mut alpha_mask_phase,
+        mut transparent_phase,
+        view_visible_entities,
+        view,
+        tonemapping,
+        dither,
+        camera_3d,
+    ) in &mut views
+    {
+        visible_entities.clear();
+        visible_entities.extend(view_visible_entities.entities.iter().copied());
+        let rangefinder = view.rangefinder3d();
+
+        for (entity, text) in &texts {
+            if !visible_entities.contains(&text.original_entity) {
+                continue;
+            }
+
+            let alpha_mask = matches!(text.alpha_mode, Text3dAlphaMode::Mask(_));
+            let pipeline = pipelines.specialize(
+                &pipeline_cache,
+                &text3d_pipeline,
+                Text3dPipelineKey {
+                    msaa_samples: msaa.samples(),
+                    hdr: view.hdr,
+                    tonemapping: tonemapping.copied().filter(|_| !view.hdr),
+                    deband_dither: dither == Some(&DebandDither::Enabled),
+                    billboard: text.billboard,
+                    alpha_mask,
+                    depth_format: camera_3d
+                        .map_or(CORE_3D_DEPTH_FORMAT, Camera3d::depth_texture_format),
+                },
+            );
+            let distance = rangefinder.distance_translation(&text.translation);
+
+            // batch_range will be set in prepare_text3d
+            if alpha_mask {
+                alpha_mask_phase.add(AlphaMask3d {
+                    distance,
+                    pipeline,
+                    entity,
+                    draw_function: draw_alpha_mask,
+                    batch_range: 0..1,
+                    dynamic_offset: None,
+                });
+            } else {
+                transparent_phase.add(Transparent3d {
+                    distance,
+                    pipeline,
+                    entity,
+                    draw_function: draw_transparent,
+                    batch_range: 0..1,
+                    dynamic_offset: None,
+                });
+            }
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct Text3dMeta {
+    instance_buffer: BufferVec<Text3dInstance>,
+    index_buffer: BufferVec<u32>,
+}
+
+impl Default for Text3dMeta {
+    fn default() -> Self {
+        Self {
+            instance_buffer: BufferVec::new(BufferUsages::VERTEX),
+            index_buffer: BufferVec::new(BufferUsages::INDEX),
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct Text3dImageBindGroups {
+    values: HashMap<AssetId<Image>, BindGroup>,
+}
+
+/// The bind group of the view uniform and tonemapping LUT of a view drawing [`Text3d`].
+#[derive(Component)]
+pub struct Text3dViewBindGroup {
+    pub value: BindGroup,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn prepare_text3d(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut text3d_meta: ResMut<Text3dMeta>,
+    text3d_pipeline: Res<Text3dPipeline>,
+    view_uniforms: Res<ViewUniforms>,
+    mut image_bind_groups: ResMut<Text3dImageBindGroups>,
+    gpu_images: Res<RenderAssets<Image>>,
+    tonemapping_luts: Res<TonemappingLuts>,
+    events: Res<SpriteAssetEvents>,
+    mut texts: Query<&mut ExtractedText3d>,
+    views: Query<(Entity, Option<&Tonemapping>), With<RenderPhase<Transparent3d>>>,
+) {
+    // Font atlas textures are modified when glyphs are added to them
+    for event in &events.images {
+        if let AssetEvent::Modified { id } | AssetEvent::Removed { id } = event {
+            image_bind_groups.values.remove(id);
+        }
+    }
+
+    let Some(view_binding) = view_uniforms.uniforms.binding() else {
+        return;
+    };
+    for (entity, tonemapping) in &views {
+        let lut_bindings = get_lut_bindings(
+            &gpu_images,
+            &tonemapping_luts,
+            tonemapping.unwrap_or(&Tonemapping::None),
+        );
+        commands.entity(entity).insert(Text3dViewBindGroup {
+            value: render_device.create_bind_group(
+                "text3d_view_bind_group",
+                &text3d_pipeline.view_layout,
+                &BindGroupEntries::with_indices((
+                    (0, view_binding.clone()),
+                    (18, lut_bindings.0),
+                    (19, lut_bindings.1),
+                )),
+            ),
+        });
+    }
+
+    text3d_meta.instance_buffer.clear();
+    for mut text in &mut texts {
+        if !image_bind_groups.values.contains_key(&text.image_handle_id) {
+            let Some(gpu_image) = gpu_images.get(text.image_handle_id) else {
+                continue;
+            };
+            image_bind_groups.values.insert(
+                text.image_handle_id,
+                render_device.create_bind_group(
+                    "text3d_material_bind_group",
+                    &text3d_pipeline.material_layout,
+                    &BindGroupEntries::sequential((&gpu_image.texture_view, &gpu_image.sampler)),
+                ),
+            );
+        }
+
+        let start = text3d_meta.instance_buffer.len() as u32;
+        for instance in &text.instances {
+            text3d_meta.instance_buffer.push(*instance);
+        }
+        text.range = start..text3d_meta.instance_buffer.len() as u32;
+    }
+    text3d_meta
+        .instance_buffer
+        .write_buffer(&render_device, &render_queue);
+
+    if text3d_meta.index_buffer.len() != 6 {
+        text3d_meta.index_buffer.clear();
+
+        // NOTE: Like sprites, the 4 corners of a glyph quad are given by the two least
+        // significant bits of the vertex index.
+        // See bevy_text/src/text3d.wgsl for the details.
+        for index in [2, 0, 1, 1, 3, 2] {
+            text3d_meta.index_buffer.push(index);
+        }
+
+        text3d_meta
+            .index_buffer
+            .write_buffer(&render_device, &render_queue);
+    }
+}
+
+/// [`RenderCommand`] for [`Text3d`] rendering.
+pub type DrawText3d = (
+    SetItemPipeline,
+    SetText3dViewBindGroup<0>,
+    SetText3dTextureBindGroup<1>,
+    DrawText3dGlyphs,
+);
+
+pub struct SetText3dViewBindGroup<const I: usize>;
+impl<P: PhaseItem, const I: usize> RenderCommand<P> for SetText3dViewBindGroup<I> {
+    type Param = ();
+    type ViewQuery = (Read<ViewUniformOffset>, Read<Text3dViewBindGroup>);
+    type ItemQuery = ();
+
+    fn render<'w>(
+        _item: &P,
+        (view_uniform, bind_group): (&'w ViewUniformOffset, &'w Text3dViewBindGroup),
+        _entity: Option<()>,
+        _param: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        pass.set_bind_group(I, &bind_group.value, &[view_uniform.offset]);
+        RenderCommandResult::Success
+    }
+}
+
+pub struct SetText3dTextureBindGroup<const I: usize>;
+impl<P: PhaseItem, const I: usize> RenderCommand<P> for SetText3dTextureBindGroup<I> {
+    type Param = SRes<Text3dImageBindGroups>;
+    type ViewQuery = ();
+    type ItemQuery = Read<ExtractedText3d>;
+
+    fn render<'w>(
+        _item: &P,
+        _view: (),
+        text: Option<&'w ExtractedText3d>,
+        image_bind_groups: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let Some(bind_group) = text.and_then(|text| {
+            image_bind_groups
+                .into_inner()
+                .values
+                .get(&text.image_handle_id)
+        }) else {
+            return RenderCommandResult::Failure;
+        };
+        pass.set_bind_group(I, bind_group, &[]);
+        RenderCommandResult::Success
+    }
+}
+
+pub struct DrawText3dGlyphs;
+impl<P: PhaseItem> RenderCommand<P> for DrawText3dGlyphs {
+    type Param = SRes<Text3dMeta>;
+    type ViewQuery = ();
+    type ItemQuery = Read<ExtractedText3d>;
+
+    fn render<'w>(
+        _item: &P,
+        _view: (),
+        text: Option<&'w ExtractedText3d>,
+        text3d_meta: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let text3d_meta = text3d_meta.into_inner();
+        let (Some(text), Some(index_buffer), Some(instance_buffer)) = (
+            text,
+            text3d_meta.index_buffer.buffer(),
+            text3d_meta.instance_buffer.buffer(),
+        ) else {
+            return RenderCommandResult::Failure;
+        };
+
+        pass.set_index_buffer(index_buffer.slice(..), 0, IndexFormat::Uint32);
+        pass.set_vertex_buffer(0, instance_buffer.slice(..));
+        pass.draw_indexed(0..6, 0, text.range.clone());
+        RenderCommandResult::Success
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn instance_matches_vertex_layout() {
+        let layout = VertexBufferLayout::from_vertex_formats(
+            VertexStepMode::Instance,
+            [
+                VertexFormat::Float32x4,
+                VertexFormat::Float32x4,
+                VertexFormat::Float32x4,
+                VertexFormat::Float32x4,
+                VertexFormat::Float32x4,
+                VertexFormat::Float32x4,
+                VertexFormat::Float32x4,
+            ],
+        );
+        assert_eq!(
+            layout.array_stride,
+            std::mem::size_of::<Text3dInstance>() as u64
+        );
+    }
+}
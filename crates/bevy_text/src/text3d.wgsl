@@ -0,0 +1,95 @@
+#ifdef TONEMAP_IN_SHADER
+#import bevy_core_pipeline::tonemapping
+#endif
+
+#import bevy_render::{
+    maths::affine_to_square,
+    view::View,
+}
+
+@group(0) @binding(0) var<uniform> view: View;
+
+struct VertexInput {
+    @builtin(vertex_index) index: u32,
+    // NOTE: Instance-rate vertex buffer members prefixed with i_
+    // NOTE: i_model_transpose_colN are the 3 columns of a 3x4 matrix that is the transpose of the
+    // affine 4x3 model matrix of the text.
+    @location(0) i_model_transpose_col0: vec4<f32>,
+    @location(1) i_model_transpose_col1: vec4<f32>,
+    @location(2) i_model_transpose_col2: vec4<f32>,
+    // The position and size of the glyph quad on the plane of the text.
+    @location(3) i_rect: vec4<f32>,
+    @location(4) i_uv_offset_scale: vec4<f32>,
+    @location(5) i_color: vec4<f32>,
+    // Only `x` is used, the other components pad the instance.
+    @location(6) i_alpha_cutoff: vec4<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) @interpolate(flat) color: vec4<f32>,
+    @location(2) @interpolate(flat) alpha_cutoff: f32,
+};
+
+@vertex
+fn vertex(in: VertexInput) -> VertexOutput {
+    var out: VertexOutput;
+
+    let vertex_position = vec2<f32>(
+        f32(in.index & 0x1u),
+        f32((in.index & 0x2u) >> 1u),
+    );
+    let position = in.i_rect.xy + vertex_position * in.i_rect.zw;
+
+    let model = affine_to_square(mat3x4<f32>(
+        in.i_model_transpose_col0,
+        in.i_model_transpose_col1,
+        in.i_model_transpose_col2,
+    ));
+
+#ifdef BILLBOARD
+    // Keep the scale of the text, but face the camera.
+    let scale = vec2<f32>(length(model[0].xyz), length(model[1].xyz));
+    let right = view.inverse_view[0].xyz;
+    let up = view.inverse_view[1].xyz;
+    let world_position = model[3].xyz + right * position.x * scale.x + up * position.y * scale.y;
+#else ifdef BILLBOARD_AXIS_Y
+    // Rotate around the vertical axis to face the camera.
+    let scale = vec2<f32>(length(model[0].xyz), length(model[1].xyz));
+    let to_camera = view.world_position - model[3].xyz;
+    let right = normalize(vec3<f32>(to_camera.z, 0.0, -to_camera.x));
+    let up = vec3<f32>(0.0, 1.0, 0.0);
+    let world_position = model[3].xyz + right * position.x * scale.x + up * position.y * scale.y;
+#else
+    let world_position = (model * vec4<f32>(position, 0.0, 1.0)).xyz;
+#endif
+
+    out.clip_position = view.view_proj * vec4<f32>(world_position, 1.0);
+    out.uv = vertex_position * in.i_uv_offset_scale.zw + in.i_uv_offset_scale.xy;
+    out.color = in.i_color;
+    out.alpha_cutoff = in.i_alpha_cutoff.x;
+
+    return out;
+}
+
+@group(1) @binding(0) var glyph_texture: texture_2d<f32>;
+@group(1) @binding(1) var glyph_sampler: sampler;
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    var color = in.color * textureSample(glyph_texture, glyph_sampler, in.uv);
+
+#ifdef ALPHA_MASK
+    if color.a < in.alpha_cutoff {
+        discard;
+    }
+    color.a = 1.0;
+#endif
+
+#ifdef TONEMAP_IN_SHADER
+    color = tonemapping::tone_mapping(color, view.color_grading);
+#endif
+
+    return color;
+}
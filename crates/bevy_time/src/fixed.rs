@@ -1,9 +1,9 @@
 use bevy_app::FixedMain;
-use bevy_ecs::world::World;
+use bevy_ecs::{schedule::Schedule, world::World};
 use bevy_reflect::Reflect;
 use bevy_utils::Duration;
 
-use crate::{time::Time, virt::Virtual};
+use crate::{rollback, time::Time, virt::Virtual};
 
 /// The fixed timestep game clock following virtual time.
 ///
@@ -232,21 +232,50 @@ impl Default for Fixed {
 
 /// Runs [`FixedMain`] zero or more times based on delta of
 /// [`Time<Virtual>`](Virtual) and [`Time::overstep`]
+///
+/// The fixed steps rewound with [`Rollback`](crate::Rollback) are simulated again first.
 pub fn run_fixed_main_schedule(world: &mut World) {
     let delta = world.resource::<Time<Virtual>>().delta();
     world.resource_mut::<Time<Fixed>>().accumulate(delta);
 
-    // Run the schedule until we run out of accumulated time
     let _ = world.try_schedule_scope(FixedMain, |world, schedule| {
-        while world.resource_mut::<Time<Fixed>>().expend() {
-            *world.resource_mut::<Time>() = world.resource::<Time<Fixed>>().as_generic();
-            schedule.run(world);
+        // Simulate the rewound steps again
+        if let Some((steps, time)) = rollback::restore_rewound_step(world) {
+            let mut fixed = world.resource_mut::<Time<Fixed>>();
+            let overstep = fixed.overstep();
+            *fixed = time;
+            fixed.context_mut().overstep = overstep;
+            for _ in 0..steps {
+                let mut fixed = world.resource_mut::<Time<Fixed>>();
+                let time = *fixed;
+                let timestep = fixed.timestep();
+                fixed.advance_by(timestep);
+                run_fixed_step(world, schedule, time);
+            }
+            rollback::finish_resimulation(world);
+        }
+
+        // Run the schedule until we run out of accumulated time
+        loop {
+            let time = *world.resource::<Time<Fixed>>();
+            if !world.resource_mut::<Time<Fixed>>().expend() {
+                break;
+            }
+            run_fixed_step(world, schedule, time);
         }
     });
 
     *world.resource_mut::<Time>() = world.resource::<Time<Virtual>>().as_generic();
 }
 
+/// Runs a fixed step, `time` being the fixed time before it was advanced by the timestep.
+fn run_fixed_step(world: &mut World, schedule: &mut Schedule, time: Time<Fixed>) {
+    *world.resource_mut::<Time>() = world.resource::<Time<Fixed>>().as_generic();
+    rollback::save_step(world, time);
+    schedule.run(world);
+    rollback::finish_step(world);
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -4,23 +4,27 @@
 pub mod common_conditions;
 mod fixed;
 mod real;
+mod rollback;
 mod stopwatch;
 #[allow(clippy::module_inception)]
 mod time;
 mod timer;
+mod timers;
 mod virt;
 
 pub use fixed::*;
 pub use real::*;
+pub use rollback::*;
 pub use stopwatch::*;
 pub use time::*;
 pub use timer::*;
+pub use timers::*;
 pub use virt::*;
 
 pub mod prelude {
     //! The Bevy Time Prelude.
     #[doc(hidden)]
-    pub use crate::{Fixed, Real, Time, Timer, TimerMode, Virtual};
+    pub use crate::{Fixed, Real, Time, Timer, TimerFinished, TimerMode, Timers, Virtual};
 }
 
 use bevy_app::{prelude::*, RunFixedMainLoop};
@@ -52,10 +56,14 @@ impl Plugin for TimePlugin {
             .register_type::<Time<Fixed>>()
             .register_type::<Timer>()
             .register_type::<Stopwatch>()
+            .register_type::<Timers>()
+            .add_event::<TimerFinished>()
             .add_systems(
                 First,
                 (time_system, virtual_time_system.after(time_system)).in_set(TimeSystem),
             )
+            .add_systems(First, tick_timers_system.after(TimeSystem))
+            .add_systems(FixedFirst, tick_fixed_timers_system)
             .add_systems(RunFixedMainLoop, run_fixed_main_schedule);
 
         // ensure the events are not dropped until `FixedMain` systems can observe them
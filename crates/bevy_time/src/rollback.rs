@@ -0,0 +1,410 @@
+use crate::{Fixed, Time};
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_utils::EntityHashSet;
+use std::{any::Any, collections::VecDeque};
+use thiserror::Error;
+
+/// Saves snapshots of the registered components and resources at each fixed step, so that the
+/// fixed steps can be rewound and simulated again, for example with corrected inputs received
+/// late from the network.
+///
+/// Register what should be rolled back with [`RollbackApp`], then rewind with
+/// [`Rollback::rewind`]: at the next run of [`FixedMain`](bevy_app::FixedMain), the state is
+/// restored and the rewound steps are simulated again before the new ones.
+pub struct RollbackPlugin {
+    /// The number of fixed steps that can be rewound.
+    pub max_steps: usize,
+}
+
+impl Default for RollbackPlugin {
+    fn default() -> Self {
+        Self { max_steps: 16 }
+    }
+}
+
+impl Plugin for RollbackPlugin {
+    fn build(&self, app: &mut App) {
+        app.world
+            .get_resource_or_insert_with(Rollback::default)
+            .max_steps = self.max_steps;
+    }
+}
+
+/// An error when rewinding fixed steps with [`Rollback`].
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RollbackError {
+    /// There is no snapshot of the step, because it is too old or hasn't been simulated yet.
+    #[error("no snapshot of fixed step {step}, the oldest snapshot is of step {oldest:?}")]
+    StepUnavailable {
+        /// The requested step.
+        step: u64,
+        /// The oldest step that can be rewound to.
+        oldest: Option<u64>,
+    },
+}
+
+/// The snapshots of the fixed steps that can be rewound, inserted by the [`RollbackPlugin`].
+#[derive(Resource)]
+pub struct Rollback {
+    max_steps: usize,
+    step: u64,
+    resimulating: bool,
+    rewind_to: Option<u64>,
+    registry: Vec<Box<dyn RollbackState>>,
+    snapshots: VecDeque<Snapshot>,
+}
+
+impl Default for Rollback {
+    fn default() -> Self {
+        Self {
+            max_steps: RollbackPlugin::default().max_steps,
+            step: 0,
+            resimulating: false,
+            rewind_to: None,
+            registry: Vec::new(),
+            snapshots: VecDeque::new(),
+        }
+    }
+}
+
+struct Snapshot {
+    step: u64,
+    /// The fixed time at the start of the step, before it was advanced by the timestep.
+    time: Time<Fixed>,
+    states: Vec<Box<dyn Any + Send + Sync>>,
+}
+
+impl Rollback {
+    /// Returns the fixed step being simulated, or the next one outside of
+    /// [`FixedMain`](bevy_app::FixedMain).
+    pub fn step(&self) -> u64 {
+        self.step
+    }
+
+    /// Returns `true` while rewound steps are simulated again.
+    ///
+    /// Systems with side effects that shouldn't be repeated, like playing sounds, can check this.
+    pub fn is_resimulating(&self) -> bool {
+        self.resimulating
+    }
+
+    /// Returns the oldest step that can be rewound to.
+    pub fn oldest_step(&self) -> Option<u64> {
+        self.snapshots.front().map(|snapshot| snapshot.step)
+    }
+
+    /// Rewinds to the start of `step` at the next run of [`FixedMain`](bevy_app::FixedMain),
+    /// then simulates the steps from `step` again.
+    ///
+    /// If several rewinds are requested before the next run, the oldest step is used.
+    pub fn rewind_to(&mut self, step: u64) -> Result<(), RollbackError> {
+        if step >= self.step || !self.snapshots.iter().any(|s| s.step == step) {
+            return Err(RollbackError::StepUnavailable {
+                step,
+                oldest: self.oldest_step(),
+            });
+        }
+        self.rewind_to = Some(self.rewind_to.map_or(step, |rewind_to| rewind_to.min(step)));
+        Ok(())
+    }
+
+    /// Rewinds `steps` fixed steps at the next run of [`FixedMain`](bevy_app::FixedMain), see
+    /// [`Rollback::rewind_to`].
+    pub fn rewind(&mut self, steps: u64) -> Result<(), RollbackError> {
+        let step = self
+            .step
+            .checked_sub(steps)
+            .ok_or(RollbackError::StepUnavailable {
+                step: 0,
+                oldest: self.oldest_step(),
+            })?;
+        self.rewind_to(step)
+    }
+}
+
+/// Restores the snapshot of the requested [`Rollback::rewind_to`] step, returning the number of
+/// steps to simulate again and the saved fixed time.
+pub(crate) fn restore_rewound_step(world: &mut World) -> Option<(u64, Time<Fixed>)> {
+    let step = world.get_resource_mut::<Rollback>()?.rewind_to.take()?;
+    world.resource_scope(|world, mut rollback: Mut<Rollback>| {
+        let snapshot = rollback.snapshots.iter().find(|s| s.step == step)?;
+        for (state, saved) in rollback.registry.iter().zip(&snapshot.states) {
+            state.restore(world, saved.as_ref());
+        }
+        let time = snapshot.time;
+        let steps = rollback.step - step;
+        rollback.step = step;
+        rollback.resimulating = true;
+        Some((steps, time))
+    })
+}
+
+/// Saves the snapshot of the step about to be simulated, with the fixed `time` from before the
+/// step was advanced.
+pub(crate) fn save_step(world: &mut World, time: Time<Fixed>) {
+    if !world.contains_resource::<Rollback>() {
+        return;
+    }
+    world.resource_scope(|world, mut rollback: Mut<Rollback>| {
+        let step = rollback.step;
+        // Snapshots of resimulated steps are replaced
+        while rollback
+            .snapshots
+            .back()
+            .is_some_and(|snapshot| snapshot.step >= step)
+        {
+            rollback.snapshots.pop_back();
+        }
+        while rollback.snapshots.len() >= rollback.max_steps.max(1) {
+            rollback.snapshots.pop_front();
+        }
+        let states = rollback
+            .registry
+            .iter()
+            .map(|state| state.save(world))
+            .collect();
+        rollback
+            .snapshots
+            .push_back(Snapshot { step, time, states });
+    });
+}
+
+/// Ends the step that was simulated.
+pub(crate) fn finish_step(world: &mut World) {
+    if let Some(mut rollback) = world.get_resource_mut::<Rollback>() {
+        rollback.step += 1;
+    }
+}
+
+/// Ends the simulation of rewound steps.
+pub(crate) fn finish_resimulation(world: &mut World) {
+    if let Some(mut rollback) = world.get_resource_mut::<Rollback>() {
+        rollback.resimulating = false;
+    }
+}
+
+trait RollbackState: Send + Sync {
+    fn save(&self, world: &mut World) -> Box<dyn Any + Send + Sync>;
+
+    fn restore(&self, world: &mut World, saved: &(dyn Any + Send + Sync));
+}
+
+struct ComponentState<C>(std::marker::PhantomData<fn() -> C>);
+
+impl<C: Component + Clone> RollbackState for ComponentState<C> {
+    fn save(&self, world: &mut World) -> Box<dyn Any + Send + Sync> {
+        let saved: Vec<(Entity, C)> = world
+            .query::<(Entity, &C)>()
+            .iter(world)
+            .map(|(entity, component)| (entity, component.clone()))
+            .collect();
+        Box::new(saved)
+    }
+
+    fn restore(&self, world: &mut World, saved: &(dyn Any + Send + Sync)) {
+        let Some(saved) = saved.downcast_ref::<Vec<(Entity, C)>>() else {
+            return;
+        };
+        let saved_entities: EntityHashSet<Entity> =
+            saved.iter().map(|(entity, _)| *entity).collect();
+        let added: Vec<Entity> = world
+            .query_filtered::<Entity, With<C>>()
+            .iter(world)
+            .filter(|entity| !saved_entities.contains(entity))
+            .collect();
+        for entity in added {
+            world.entity_mut(entity).remove::<C>();
+        }
+        for (entity, component) in saved {
+            if let Some(mut entity) = world.get_entity_mut(*entity) {
+                entity.insert(component.clone());
+            }
+        }
+    }
+}
+
+struct ResourceState<R>(std::marker::PhantomData<fn() -> R>);
+
+impl<R: Resource + Clone> RollbackState for ResourceState<R> {
+    fn save(&self, world: &mut World) -> Box<dyn Any + Send + Sync> {
+        Box::new(world.get_resource::<R>().cloned())
+    }
+
+    fn restore(&self, world: &mut World, saved: &(dyn Any + Send + Sync)) {
+        match saved.downcast_ref::<Option<R>>() {
+            Some(Some(resource)) => world.insert_resource(resource.clone()),
+            Some(None) => {
+                world.remove_resource::<R>();
+            }
+            None => {}
+        }
+    }
+}
+
+/// The values of a component at the two last fixed steps, to interpolate the visual state
+/// between them with [`Time::overstep_fraction`].
+///
+/// Inserted and updated for the component types added with
+/// [`RollbackApp::add_fixed_step_history`].
+#[derive(Component, Debug, Clone)]
+pub struct FixedStepHistory<C: Component + Clone> {
+    /// The value at the end of the previous fixed step.
+    pub previous: C,
+    /// The value at the end of the last fixed step.
+    pub current: C,
+}
+
+fn update_fixed_step_history<C: Component + Clone>(
+    mut commands: Commands,
+    mut query: Query<(Entity, &C, Option<&mut FixedStepHistory<C>>)>,
+) {
+    for (entity, component, history) in &mut query {
+        if let Some(mut history) = history {
+            history.previous = std::mem::replace(&mut history.current, component.clone());
+        } else {
+            commands.entity(entity).insert(FixedStepHistory {
+                previous: component.clone(),
+                current: component.clone(),
+            });
+        }
+    }
+}
+
+/// Adds rollback functionality to an [`App`].
+pub trait RollbackApp {
+    /// Saves the components of type `C` in the [`Rollback`] snapshots.
+    ///
+    /// On rewind, the components are restored on the entities that still exist, and removed from
+    /// the entities that didn't have one. Entities despawned since the snapshot aren't respawned.
+    fn register_rollback_component<C: Component + Clone>(&mut self) -> &mut Self;
+
+    /// Saves the resource of type `R` in the [`Rollback`] snapshots, for example the inputs of
+    /// the fixed steps.
+    fn register_rollback_resource<R: Resource + Clone>(&mut self) -> &mut Self;
+
+    /// Keeps the values of the components of type `C` at the two last fixed steps in a
+    /// [`FixedStepHistory`], to interpolate the visual state between fixed steps.
+    fn add_fixed_step_history<C: Component + Clone>(&mut self) -> &mut Self;
+}
+
+impl RollbackApp for App {
+    fn register_rollback_component<C: Component + Clone>(&mut self) -> &mut Self {
+        self.world
+            .get_resource_or_insert_with(Rollback::default)
+            .registry
+            .push(Box::new(ComponentState::<C>(Default::default())));
+        self
+    }
+
+    fn register_rollback_resource<R: Resource + Clone>(&mut self) -> &mut Self {
+        self.world
+            .get_resource_or_insert_with(Rollback::default)
+            .registry
+            .push(Box::new(ResourceState::<R>(Default::default())));
+        self
+    }
+
+    fn add_fixed_step_history<C: Component + Clone>(&mut self) -> &mut Self {
+        self.add_systems(FixedLast, update_fixed_step_history::<C>)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{run_fixed_main_schedule, TimePlugin, Virtual};
+    use bevy_app::FixedUpdate;
+    use bevy_utils::Duration;
+
+    #[derive(Component, Clone, Debug, PartialEq)]
+    struct Position(i32);
+
+    #[derive(Resource, Clone, Default)]
+    struct Velocity(i32);
+
+    #[test]
+    fn rewind_and_resimulate() {
+        let mut app = App::new();
+        app.add_plugins((TimePlugin, RollbackPlugin::default()))
+            .register_rollback_component::<Position>()
+            .add_systems(
+                FixedUpdate,
+                |velocity: Res<Velocity>, mut positions: Query<&mut Position>| {
+                    for mut position in &mut positions {
+                        position.0 += velocity.0;
+                    }
+                },
+            )
+            .init_resource::<Velocity>();
+        let entity = app.world.spawn(Position(0)).id();
+
+        let step = |app: &mut App| {
+            let timestep = app.world.resource::<Time<Fixed>>().timestep();
+            app.world
+                .resource_mut::<Time<Virtual>>()
+                .advance_by(timestep);
+            run_fixed_main_schedule(&mut app.world);
+        };
+
+        app.world.resource_mut::<Velocity>().0 = 1;
+        for _ in 0..3 {
+            step(&mut app);
+        }
+        assert_eq!(app.world.get::<Position>(entity), Some(&Position(3)));
+        assert_eq!(app.world.resource::<Rollback>().step(), 3);
+
+        // The velocity should have been 2 since step 1
+        app.world.resource_mut::<Velocity>().0 = 2;
+        app.world.resource_mut::<Rollback>().rewind(2).unwrap();
+        step(&mut app);
+        assert_eq!(app.world.get::<Position>(entity), Some(&Position(7)));
+        assert_eq!(app.world.resource::<Rollback>().step(), 4);
+        assert!(!app.world.resource::<Rollback>().is_resimulating());
+
+        assert!(app.world.resource_mut::<Rollback>().rewind(100).is_err());
+    }
+
+    #[derive(Resource, Default)]
+    struct ElapsedAtStep(Vec<(u64, Duration)>);
+
+    #[test]
+    fn rewind_restores_fixed_time() {
+        let mut app = App::new();
+        app.add_plugins((TimePlugin, RollbackPlugin::default()))
+            .init_resource::<ElapsedAtStep>()
+            .add_systems(
+                FixedUpdate,
+                |rollback: Res<Rollback>, time: Res<Time>, mut elapsed: ResMut<ElapsedAtStep>| {
+                    elapsed.0.push((rollback.step(), time.elapsed()));
+                },
+            );
+
+        let timestep = app.world.resource::<Time<Fixed>>().timestep();
+        let step = |app: &mut App| {
+            app.world
+                .resource_mut::<Time<Virtual>>()
+                .advance_by(timestep);
+            run_fixed_main_schedule(&mut app.world);
+        };
+
+        for _ in 0..3 {
+            step(&mut app);
+        }
+        app.world.resource_mut::<Rollback>().rewind(2).unwrap();
+        step(&mut app);
+
+        let elapsed = &app.world.resource::<ElapsedAtStep>().0;
+        assert_eq!(
+            elapsed,
+            &[
+                (0, timestep),
+                (1, timestep * 2),
+                (2, timestep * 3),
+                (1, timestep * 2),
+                (2, timestep * 3),
+                (3, timestep * 4),
+            ]
+        );
+    }
+}
@@ -0,0 +1,222 @@
+use crate::{Fixed, Real, Time, Timer, Virtual};
+use bevy_ecs::prelude::*;
+use bevy_reflect::prelude::*;
+use bevy_utils::Duration;
+use std::borrow::Cow;
+
+/// The clock used to tick the [`Timer`]s of a [`Timers`] component.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Deserialize, serde::Serialize))]
+pub enum TimerClock {
+    /// Ticked with [`Time<Virtual>`] in [`First`](bevy_app::First), so the timers pause and
+    /// speed up along with the game.
+    #[default]
+    Virtual,
+    /// Ticked with [`Time<Real>`] in [`First`](bevy_app::First), so the timers keep running
+    /// when the game is paused.
+    Real,
+    /// Ticked with [`Time<Fixed>`] in [`FixedFirst`](bevy_app::FixedFirst), once per fixed timestep.
+    Fixed,
+}
+
+/// A set of labeled [`Timer`]s on an entity, ticked automatically by the [`TimePlugin`](crate::TimePlugin).
+///
+/// Each time one of the timers finishes, a [`TimerFinished`] event is sent.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_time::prelude::*;
+/// # use bevy_time::{Timers, TimerFinished};
+/// #[derive(Component)]
+/// struct Bomb;
+///
+/// fn spawn_bomb(mut commands: Commands) {
+///     commands.spawn((
+///         Bomb,
+///         Timers::default().with("fuse", Timer::from_seconds(3.0, TimerMode::Once)),
+///     ));
+/// }
+///
+/// fn explode(mut commands: Commands, mut finished: EventReader<TimerFinished>) {
+///     for event in finished.read().filter(|event| event.label == "fuse") {
+///         commands.entity(event.entity).despawn();
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(spawn_bomb);
+/// # bevy_ecs::system::assert_is_system(explode);
+/// ```
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct Timers {
+    /// The clock used to tick the timers.
+    pub clock: TimerClock,
+    timers: Vec<(Cow<'static, str>, Timer)>,
+}
+
+impl Timers {
+    /// Creates an empty set of timers ticked with the given clock.
+    pub fn new(clock: TimerClock) -> Self {
+        Self {
+            clock,
+            timers: Vec::new(),
+        }
+    }
+
+    /// Adds a timer with the given label, replacing any timer with the same label.
+    pub fn with(mut self, label: impl Into<Cow<'static, str>>, timer: Timer) -> Self {
+        self.insert(label, timer);
+        self
+    }
+
+    /// Adds a timer with the given label, returning the timer it replaced, if any.
+    pub fn insert(&mut self, label: impl Into<Cow<'static, str>>, timer: Timer) -> Option<Timer> {
+        let label = label.into();
+        match self.get_mut(&label) {
+            Some(existing) => Some(std::mem::replace(existing, timer)),
+            None => {
+                self.timers.push((label, timer));
+                None
+            }
+        }
+    }
+
+    /// Removes the timer with the given label.
+    pub fn remove(&mut self, label: &str) -> Option<Timer> {
+        let index = self.timers.iter().position(|(l, _)| l == label)?;
+        Some(self.timers.remove(index).1)
+    }
+
+    /// Returns the timer with the given label.
+    pub fn get(&self, label: &str) -> Option<&Timer> {
+        self.timers
+            .iter()
+            .find_map(|(l, timer)| (l == label).then_some(timer))
+    }
+
+    /// Returns the timer with the given label mutably.
+    pub fn get_mut(&mut self, label: &str) -> Option<&mut Timer> {
+        self.timers
+            .iter_mut()
+            .find_map(|(l, timer)| (l == label).then_some(timer))
+    }
+
+    /// Iterates over the labels and timers.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Timer)> {
+        self.timers.iter().map(|(label, timer)| (&**label, timer))
+    }
+
+    /// Returns the number of timers.
+    pub fn len(&self) -> usize {
+        self.timers.len()
+    }
+
+    /// Returns `true` if there are no timers.
+    pub fn is_empty(&self) -> bool {
+        self.timers.is_empty()
+    }
+}
+
+/// Sent when a timer of a [`Timers`] component finishes.
+///
+/// Repeating timers that finish several times in a single tick send one event per completion.
+#[derive(Event, Clone, Debug, PartialEq, Eq)]
+pub struct TimerFinished {
+    /// The entity the timer belongs to.
+    pub entity: Entity,
+    /// The label of the timer.
+    pub label: Cow<'static, str>,
+}
+
+/// Ticks the [`Timers`] using [`Time<Virtual>`] or [`Time<Real>`].
+pub fn tick_timers_system(
+    virtual_time: Res<Time<Virtual>>,
+    real_time: Res<Time<Real>>,
+    mut timers: Query<(Entity, &mut Timers)>,
+    mut finished: EventWriter<TimerFinished>,
+) {
+    for (entity, mut timers) in &mut timers {
+        let delta = match timers.clock {
+            TimerClock::Virtual => virtual_time.delta(),
+            TimerClock::Real => real_time.delta(),
+            TimerClock::Fixed => continue,
+        };
+        tick(entity, &mut timers, delta, &mut finished);
+    }
+}
+
+/// Ticks the [`Timers`] using [`Time<Fixed>`].
+pub fn tick_fixed_timers_system(
+    fixed_time: Res<Time<Fixed>>,
+    mut timers: Query<(Entity, &mut Timers)>,
+    mut finished: EventWriter<TimerFinished>,
+) {
+    for (entity, mut timers) in &mut timers {
+        if timers.clock == TimerClock::Fixed {
+            tick(entity, &mut timers, fixed_time.delta(), &mut finished);
+        }
+    }
+}
+
+fn tick(
+    entity: Entity,
+    timers: &mut Timers,
+    delta: Duration,
+    finished: &mut EventWriter<TimerFinished>,
+) {
+    for (label, timer) in &mut timers.timers {
+        timer.tick(delta);
+        for _ in 0..timer.times_finished_this_tick() {
+            finished.send(TimerFinished {
+                entity,
+                label: label.clone(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{TimePlugin, TimeUpdateStrategy, TimerMode};
+    use bevy_app::App;
+    use bevy_ecs::event::Events;
+
+    #[test]
+    fn timers_send_finished_events() {
+        let mut app = App::new();
+        app.add_plugins(TimePlugin)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                100,
+            )));
+        let entity = app
+            .world
+            .spawn(
+                Timers::default()
+                    .with("once", Timer::from_seconds(0.25, TimerMode::Once))
+                    .with(
+                        "repeating",
+                        Timer::new(Duration::from_millis(100), TimerMode::Repeating),
+                    ),
+            )
+            .id();
+
+        let mut labels = Vec::new();
+        for _ in 0..5 {
+            app.update();
+            let mut events = app.world.resource_mut::<Events<TimerFinished>>();
+            labels.extend(events.drain().map(|event| {
+                assert_eq!(event.entity, entity);
+                event.label
+            }));
+        }
+
+        let elapsed = app.world.resource::<Time<Virtual>>().elapsed();
+        let repeated = (elapsed.as_millis() / 100) as usize;
+        assert!(elapsed >= Duration::from_millis(300));
+        assert_eq!(labels.iter().filter(|label| *label == "once").count(), 1);
+        assert_eq!(
+            labels.iter().filter(|label| *label == "repeating").count(),
+            repeated
+        );
+    }
+}
@@ -0,0 +1,198 @@
+use super::Transform;
+use bevy_ecs::{component::Component, reflect::ReflectComponent};
+use bevy_math::{Affine2, Quat, Vec2};
+use bevy_reflect::prelude::*;
+use bevy_reflect::Reflect;
+
+/// Describe the position of an entity in 2d. If the entity has a parent, the position is relative
+/// to its parent position.
+///
+/// This is an optional, 2d-only alternative to setting the [`Transform`] directly: when an entity
+/// has both components, its [`Transform`] is overwritten from its [`Transform2d`] whenever the
+/// latter changes, by systems in the [`SyncTransform2d`](crate::TransformSystem::SyncTransform2d)
+/// set, which runs during [`PostUpdate`](bevy_app::PostUpdate) before the transforms are propagated.
+///
+/// The rotation is a single angle around the z axis, so it can't accidentally tilt the entity
+/// out of the 2d plane, and the draw order is set by a separate `z_layer` rather than the
+/// translation, so it isn't affected by moving the entity around.
+///
+/// [`Transform2d`] only replaces the authoring of the [`Transform`], there is no dedicated 2d
+/// propagation: the hierarchy is propagated into [`GlobalTransform`](super::GlobalTransform) and
+/// rendered like any other entity, so 2d and 3d entities can be parented to each other. The
+/// [`Transform2d`] is authoritative: changes made directly to the [`Transform`] of the entity are
+/// overwritten.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_math::Vec2;
+/// # use bevy_transform::prelude::*;
+/// fn spin(mut query: Query<&mut Transform2d>) {
+///     for mut transform in &mut query {
+///         transform.rotation += 0.1;
+///         transform.translation += Vec2::X;
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(spin);
+/// ```
+#[derive(Component, Debug, PartialEq, Clone, Copy, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component, Default, PartialEq)]
+pub struct Transform2d {
+    /// Position of the entity.
+    pub translation: Vec2,
+    /// Counterclockwise rotation of the entity around the z axis, in radians.
+    pub rotation: f32,
+    /// Scale of the entity.
+    pub scale: Vec2,
+    /// Draw order of the entity: higher layers are in front of lower layers.
+    ///
+    /// Becomes the `z` component of the [`Transform`] translation.
+    pub z_layer: f32,
+}
+
+impl Transform2d {
+    /// An identity [`Transform2d`] with no translation, rotation, and a scale of 1 on all axes,
+    /// on the layer 0.
+    pub const IDENTITY: Self = Transform2d {
+        translation: Vec2::ZERO,
+        rotation: 0.0,
+        scale: Vec2::ONE,
+        z_layer: 0.0,
+    };
+
+    /// Creates a new [`Transform2d`] at the position `(x, y)`.
+    #[inline]
+    pub const fn from_xy(x: f32, y: f32) -> Self {
+        Self::from_translation(Vec2::new(x, y))
+    }
+
+    /// Creates a new [`Transform2d`], with `translation`. Rotation will be 0 and scale 1 on
+    /// all axes.
+    #[inline]
+    pub const fn from_translation(translation: Vec2) -> Self {
+        Transform2d {
+            translation,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Creates a new [`Transform2d`], with a counterclockwise `rotation` in radians.
+    /// Translation will be 0 and scale 1 on all axes.
+    #[inline]
+    pub const fn from_rotation(rotation: f32) -> Self {
+        Transform2d {
+            rotation,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Creates a new [`Transform2d`], with `scale`. Translation will be 0 and rotation 0.
+    #[inline]
+    pub const fn from_scale(scale: Vec2) -> Self {
+        Transform2d {
+            scale,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Returns this [`Transform2d`] with a new translation.
+    #[inline]
+    #[must_use]
+    pub const fn with_translation(mut self, translation: Vec2) -> Self {
+        self.translation = translation;
+        self
+    }
+
+    /// Returns this [`Transform2d`] with a new counterclockwise rotation, in radians.
+    #[inline]
+    #[must_use]
+    pub const fn with_rotation(mut self, rotation: f32) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Returns this [`Transform2d`] with a new scale.
+    #[inline]
+    #[must_use]
+    pub const fn with_scale(mut self, scale: Vec2) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Returns this [`Transform2d`] on a new layer.
+    #[inline]
+    #[must_use]
+    pub const fn with_z_layer(mut self, z_layer: f32) -> Self {
+        self.z_layer = z_layer;
+        self
+    }
+
+    /// Returns the 2d affine transformation matrix from this transform's translation,
+    /// rotation, and scale.
+    #[inline]
+    pub fn compute_affine(&self) -> Affine2 {
+        Affine2::from_scale_angle_translation(self.scale, self.rotation, self.translation)
+    }
+
+    /// Returns the equivalent 3d [`Transform`].
+    #[inline]
+    pub fn compute_transform(&self) -> Transform {
+        Transform {
+            translation: self.translation.extend(self.z_layer),
+            rotation: Quat::from_rotation_z(self.rotation),
+            scale: self.scale.extend(1.0),
+        }
+    }
+
+    /// Translates this [`Transform2d`] by `translation`.
+    #[inline]
+    pub fn translate(&mut self, translation: Vec2) {
+        self.translation += translation;
+    }
+
+    /// Rotates this [`Transform2d`] counterclockwise by `angle`, in radians.
+    #[inline]
+    pub fn rotate(&mut self, angle: f32) {
+        self.rotation += angle;
+    }
+
+    /// Transforms the given `point`, applying scale, rotation and translation.
+    #[inline]
+    pub fn transform_point(&self, point: Vec2) -> Vec2 {
+        self.compute_affine().transform_point2(point)
+    }
+}
+
+impl Default for Transform2d {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl From<Transform2d> for Transform {
+    fn from(transform: Transform2d) -> Self {
+        transform.compute_transform()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::FRAC_PI_2;
+
+    #[test]
+    fn matches_3d_transform() {
+        let transform = Transform2d::from_xy(1.0, 2.0)
+            .with_rotation(FRAC_PI_2)
+            .with_scale(Vec2::new(2.0, 3.0))
+            .with_z_layer(5.0);
+        let transform_3d = transform.compute_transform();
+
+        let point = Vec2::new(1.0, 1.0);
+        let expected = transform_3d.transform_point(point.extend(0.0));
+        assert!(transform
+            .transform_point(point)
+            .abs_diff_eq(expected.truncate(), 1e-5));
+        assert_eq!(expected.z, 5.0);
+    }
+}
@@ -46,6 +46,21 @@ impl<'w, 's> TransformHelper<'w, 's> {
 
         Ok(global_transform)
     }
+
+    /// Computes the [`Transform`] the given entity would need to keep its current
+    /// [`GlobalTransform`] as a child of `new_parent`.
+    ///
+    /// Unlike [`GlobalTransform::reparented_to`], this takes into account the changes made to any
+    /// [`Transform`]s since the last time the transform propagation systems ran.
+    pub fn compute_reparented_transform(
+        &self,
+        entity: Entity,
+        new_parent: Entity,
+    ) -> Result<Transform, ComputeGlobalTransformError> {
+        let global_transform = self.compute_global_transform(entity)?;
+        let parent_global_transform = self.compute_global_transform(new_parent)?;
+        Ok(global_transform.reparented_to(&parent_global_transform))
+    }
 }
 
 fn map_error(err: QueryEntityError, ancestor: bool) -> ComputeGlobalTransformError {
@@ -63,7 +78,8 @@ fn map_error(err: QueryEntityError, ancestor: bool) -> ComputeGlobalTransformErr
     }
 }
 
-/// Error returned by [`TransformHelper::compute_global_transform`].
+/// Error returned by [`TransformHelper::compute_global_transform`]
+/// and [`TransformHelper::compute_reparented_transform`].
 #[derive(Debug, Error)]
 pub enum ComputeGlobalTransformError {
     /// The entity or one of its ancestors is missing the [`Transform`] component.
@@ -114,6 +130,44 @@ mod tests {
         ]);
     }
 
+    #[test]
+    fn reparented_transform_keeps_global_transform() {
+        let mut app = App::new();
+        app.add_plugins(TransformPlugin);
+
+        let parent = app
+            .world
+            .spawn(TransformBundle::from(
+                Transform::from_translation(Vec3::X).with_scale(Vec3::splat(2.)),
+            ))
+            .id();
+        let entity = app
+            .world
+            .spawn(TransformBundle::from(
+                Transform::from_translation(Vec3::Y).with_rotation(Quat::from_rotation_z(TAU / 4.)),
+            ))
+            .id();
+        // Not propagated yet.
+        app.world.get_mut::<Transform>(parent).unwrap().translation = Vec3::Z;
+
+        let mut state = SystemState::<TransformHelper>::new(&mut app.world);
+        let helper = state.get(&app.world);
+        let transform = helper.compute_reparented_transform(entity, parent).unwrap();
+        let global_transform = helper.compute_global_transform(entity).unwrap();
+
+        app.world
+            .entity_mut(entity)
+            .set_parent(parent)
+            .insert(transform);
+        app.update();
+
+        approx::assert_abs_diff_eq!(
+            app.world.get::<GlobalTransform>(entity).unwrap().affine(),
+            global_transform.affine(),
+            epsilon = 1e-5
+        );
+    }
+
     fn match_transform_propagation_systems_inner(transforms: Vec<Transform>) {
         let mut app = App::new();
         app.add_plugins(TransformPlugin);
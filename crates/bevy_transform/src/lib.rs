@@ -21,8 +21,8 @@ use bevy_ecs::prelude::*;
 use bevy_hierarchy::ValidParentCheckPlugin;
 use bevy_math::{Affine3A, Mat4, Vec3};
 
-use prelude::{GlobalTransform, Transform};
-use systems::{propagate_transforms, sync_simple_transforms};
+use prelude::{GlobalTransform, Transform, Transform2d};
+use systems::{propagate_transforms, sync_simple_transforms, sync_transform_2d};
 
 /// A [`Bundle`] of the [`Transform`] and [`GlobalTransform`]
 /// [`Component`]s, which describe the position of an entity.
@@ -84,6 +84,9 @@ impl From<Transform> for TransformBundle {
 pub enum TransformSystem {
     /// Propagates changes in transform to children's [`GlobalTransform`]
     TransformPropagate,
+    /// Updates [`Transform`] from [`Transform2d`](components::Transform2d), before
+    /// [`TransformPropagate`](TransformSystem::TransformPropagate)
+    SyncTransform2d,
 }
 
 /// The base plugin for handling [`Transform`] components
@@ -98,16 +101,21 @@ impl Plugin for TransformPlugin {
         struct PropagateTransformsSet;
 
         app.register_type::<Transform>()
+            .register_type::<Transform2d>()
             .register_type::<GlobalTransform>()
             .add_plugins(ValidParentCheckPlugin::<GlobalTransform>::default())
             .configure_sets(
                 PostStartup,
-                PropagateTransformsSet.in_set(TransformSystem::TransformPropagate),
+                (
+                    TransformSystem::SyncTransform2d.before(TransformSystem::TransformPropagate),
+                    PropagateTransformsSet.in_set(TransformSystem::TransformPropagate),
+                ),
             )
             // add transform systems to startup so the first update is "correct"
             .add_systems(
                 PostStartup,
                 (
+                    sync_transform_2d.in_set(TransformSystem::SyncTransform2d),
                     sync_simple_transforms
                         .in_set(TransformSystem::TransformPropagate)
                         // FIXME: https://github.com/bevyengine/bevy/issues/4381
@@ -119,11 +127,15 @@ impl Plugin for TransformPlugin {
             )
             .configure_sets(
                 PostUpdate,
-                PropagateTransformsSet.in_set(TransformSystem::TransformPropagate),
+                (
+                    TransformSystem::SyncTransform2d.before(TransformSystem::TransformPropagate),
+                    PropagateTransformsSet.in_set(TransformSystem::TransformPropagate),
+                ),
             )
             .add_systems(
                 PostUpdate,
                 (
+                    sync_transform_2d.in_set(TransformSystem::SyncTransform2d),
                     sync_simple_transforms
                         .in_set(TransformSystem::TransformPropagate)
                         .ambiguous_with(PropagateTransformsSet),
@@ -1,12 +1,35 @@
-use crate::components::{GlobalTransform, Transform};
+use crate::components::{GlobalTransform, Transform, Transform2d};
 use bevy_ecs::{
-    change_detection::Ref,
+    change_detection::{DetectChangesMut, Ref},
     prelude::{Changed, DetectChanges, Entity, Query, With, Without},
     query::{Added, Or},
     removal_detection::RemovedComponents,
     system::{Local, ParamSet},
 };
 use bevy_hierarchy::{Children, Parent};
+use bevy_tasks::{ComputeTaskPool, TaskPool};
+use bevy_utils::EntityHashSet;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// Update [`Transform`] component of entities from their [`Transform2d`] when either changed.
+///
+/// The [`Transform2d`] is authoritative: a [`Transform`] modified directly is reset from it, so
+/// that they can't diverge.
+///
+/// Runs before the transform propagation systems, in the
+/// [`SyncTransform2d`](crate::TransformSystem::SyncTransform2d) set.
+pub fn sync_transform_2d(
+    mut query: Query<
+        (&Transform2d, &mut Transform),
+        Or<(Changed<Transform2d>, Changed<Transform>)>,
+    >,
+) {
+    query
+        .par_iter_mut()
+        .for_each(|(transform_2d, mut transform)| {
+            transform.set_if_neq(transform_2d.compute_transform());
+        });
+}
 
 /// Update [`GlobalTransform`] component of entities that aren't in the hierarchy
 ///
@@ -42,9 +65,22 @@ pub fn sync_simple_transforms(
     }
 }
 
+/// A subtree waiting to be propagated: its root entity, the [`GlobalTransform`] of its parent,
+/// and whether the transforms of all of its entities must be recomputed.
+type QueuedSubtree = (Entity, GlobalTransform, bool);
+
+/// Entities with at least this many children hand them over to the other threads
+/// instead of propagating them on the current one.
+const SPLIT_CHILDREN_THRESHOLD: usize = 32;
+
 /// Update [`GlobalTransform`] component of entities based on entity hierarchy and
 /// [`Transform`] component.
 ///
+/// Subtrees in which no [`Transform`] or [`Parent`] changed are skipped entirely: the ancestors
+/// of every changed entity are marked dirty first, and only dirty or changed entities are visited.
+/// The remaining subtrees are propagated in parallel on the [`ComputeTaskPool`], with large
+/// subtrees split between threads as they are traversed.
+///
 /// Third party plugins should ensure that this is used in concert with [`sync_simple_transforms`].
 pub fn propagate_transforms(
     mut root_query: Query<
@@ -52,48 +88,172 @@ pub fn propagate_transforms(
         Without<Parent>,
     >,
     mut orphaned: RemovedComponents<Parent>,
-    transform_query: Query<(Ref<Transform>, &mut GlobalTransform, Option<&Children>), With<Parent>>,
+    mut transform_queries: ParamSet<(
+        Query<
+            Entity,
+            (
+                With<Parent>,
+                Or<(Changed<Transform>, Added<GlobalTransform>, Changed<Parent>)>,
+            ),
+        >,
+        Query<(Ref<Transform>, &mut GlobalTransform, Option<&Children>), With<Parent>>,
+    )>,
     parent_query: Query<(Entity, Ref<Parent>)>,
     mut orphaned_entities: Local<Vec<Entity>>,
+    mut dirty: Local<EntityHashSet<Entity>>,
+    mut queue: Local<Vec<QueuedSubtree>>,
 ) {
     orphaned_entities.clear();
     orphaned_entities.extend(orphaned.read());
     orphaned_entities.sort_unstable();
-    root_query.par_iter_mut().for_each(
-        |(entity, children, transform, mut global_transform)| {
-            let changed = transform.is_changed() || global_transform.is_added() || orphaned_entities.binary_search(&entity).is_ok();
-            if changed {
-                *global_transform = GlobalTransform::from(*transform);
-            }
 
-            for (child, actual_parent) in parent_query.iter_many(children) {
-                assert_eq!(
-                    actual_parent.get(), entity,
-                    "Malformed hierarchy. This probably means that your hierarchy has been improperly maintained, or contains a cycle"
-                );
-                // SAFETY:
-                // - `child` must have consistent parentage, or the above assertion would panic.
-                // Since `child` is parented to a root entity, the entire hierarchy leading to it is consistent.
-                // - We may operate as if all descendants are consistent, since `propagate_recursive` will panic before 
-                //   continuing to propagate if it encounters an entity with inconsistent parentage.
-                // - Since each root entity is unique and the hierarchy is consistent and forest-like,
-                //   other root entities' `propagate_recursive` calls will not conflict with this one.
-                // - Since this is the only place where `transform_query` gets used, there will be no conflicting fetches elsewhere.
-                unsafe {
-                    propagate_recursive(
-                        &global_transform,
-                        &transform_query,
-                        &parent_query,
-                        child,
-                        changed || actual_parent.is_changed(),
-                    );
-                }
+    // Mark every changed entity and its ancestors as dirty, stopping at already dirty ancestors.
+    dirty.clear();
+    for entity in transform_queries.p0().iter() {
+        let mut current = entity;
+        while dirty.insert(current) {
+            let Ok((_, parent)) = parent_query.get(current) else {
+                break;
+            };
+            current = parent.get();
+        }
+    }
+
+    queue.clear();
+    for (entity, children, transform, mut global_transform) in &mut root_query {
+        let changed = transform.is_changed()
+            || global_transform.is_added()
+            || orphaned_entities.binary_search(&entity).is_ok();
+        if changed {
+            *global_transform = GlobalTransform::from(*transform);
+        } else if !dirty.contains(&entity) {
+            continue;
+        }
+        queue_children(
+            &mut queue,
+            entity,
+            *global_transform,
+            children,
+            &parent_query,
+            &dirty,
+            changed,
+        );
+    }
+    if queue.is_empty() {
+        return;
+    }
+
+    let transform_query = transform_queries.p1();
+    let shared_queue = Mutex::new(std::mem::take(&mut *queue));
+    let task_pool = ComputeTaskPool::get_or_init(TaskPool::default);
+    // SAFETY:
+    // - The queued entities must have consistent parentage, or `queue_children` would have panicked
+    //   before queuing them.
+    // - Since the hierarchy is consistent and forest-like, each entity is queued at most once,
+    //   so the subtrees propagated by concurrent calls to `propagate_queued` never overlap.
+    // - Since this is the only place where `transform_query` gets used, there will be no
+    //   conflicting fetches elsewhere.
+    if task_pool.thread_num() <= 1 {
+        // SAFETY: See above.
+        unsafe { propagate_queued(&transform_query, &parent_query, &dirty, &shared_queue) };
+    } else {
+        let transform_query = &transform_query;
+        let parent_query = &parent_query;
+        let dirty = &*dirty;
+        let shared_queue = &shared_queue;
+        task_pool.scope(|scope| {
+            for _ in 0..task_pool.thread_num() {
+                scope.spawn(async move {
+                    // SAFETY: See above.
+                    unsafe { propagate_queued(transform_query, parent_query, dirty, shared_queue) };
+                });
             }
-        },
-    );
+        });
+    }
+    // Keep the allocation around for the next run.
+    *queue = shared_queue
+        .into_inner()
+        .unwrap_or_else(PoisonError::into_inner);
+}
+
+/// Propagates the subtrees in `queue` until it is empty.
+///
+/// # Safety
+///
+/// - While this function is running, `transform_query` must not have any fetches for the queued entities,
+/// nor any of their descendants, except by other calls to this function with the same `queue`.
+/// - The caller must ensure that the hierarchy leading to the queued entities
+/// is well-formed and must remain as a tree or a forest. Each entity must have at most one parent.
+unsafe fn propagate_queued(
+    transform_query: &Query<
+        (Ref<Transform>, &mut GlobalTransform, Option<&Children>),
+        With<Parent>,
+    >,
+    parent_query: &Query<(Entity, Ref<Parent>)>,
+    dirty: &EntityHashSet<Entity>,
+    queue: &Mutex<Vec<QueuedSubtree>>,
+) {
+    loop {
+        // The lock must be released before propagating, which may queue more subtrees.
+        let next = lock(queue).pop();
+        let Some((entity, parent, changed)) = next else {
+            return;
+        };
+        // SAFETY:
+        // - We may operate as if all descendants are consistent, since `propagate_recursive` will
+        //   panic before continuing to propagate if it encounters an entity with inconsistent parentage.
+        // - The caller ensures that no other fetches happen for `entity` and its descendants,
+        //   and each entity is popped from the queue only once.
+        unsafe {
+            propagate_recursive(
+                &parent,
+                transform_query,
+                parent_query,
+                dirty,
+                queue,
+                entity,
+                changed,
+            );
+        }
+    }
+}
+
+fn lock(queue: &Mutex<Vec<QueuedSubtree>>) -> MutexGuard<'_, Vec<QueuedSubtree>> {
+    // The queue is always left in a valid state, even if a task panicked while holding the lock.
+    queue.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Queues the children of `parent` that are dirty, or all of them if `changed`.
+///
+/// # Panics
+///
+/// If one of the children doesn't have `parent` as its [`Parent`].
+fn queue_children(
+    queue: &mut Vec<QueuedSubtree>,
+    parent: Entity,
+    parent_transform: GlobalTransform,
+    children: &Children,
+    parent_query: &Query<(Entity, Ref<Parent>)>,
+    dirty: &EntityHashSet<Entity>,
+    changed: bool,
+) {
+    for (child, actual_parent) in parent_query.iter_many(children) {
+        assert_eq!(
+            actual_parent.get(), parent,
+            "Malformed hierarchy. This probably means that your hierarchy has been improperly maintained, or contains a cycle"
+        );
+        let changed = changed || actual_parent.is_changed();
+        if changed || dirty.contains(&child) {
+            queue.push((child, parent_transform, changed));
+        }
+    }
 }
 
-/// Recursively propagates the transforms for `entity` and all of its descendants.
+/// Recursively propagates the transforms for `entity` and all of its dirty descendants,
+/// or all of its descendants if `changed`.
+///
+/// The children of entities with many children are pushed to `queue`
+/// to be propagated by other tasks.
 ///
 /// # Panics
 ///
@@ -113,13 +273,15 @@ unsafe fn propagate_recursive(
         With<Parent>,
     >,
     parent_query: &Query<(Entity, Ref<Parent>)>,
+    dirty: &EntityHashSet<Entity>,
+    queue: &Mutex<Vec<QueuedSubtree>>,
     entity: Entity,
     mut changed: bool,
 ) {
     let (global_matrix, children) = {
         let Ok((transform, mut global_transform, children)) =
             // SAFETY: This call cannot create aliased mutable references.
-            //   - The top level iteration parallelizes on the roots of the hierarchy.
+            //   - Each subtree is only propagated by a single task.
             //   - The caller ensures that each child has one and only one unique parent throughout the entire
             //     hierarchy.
             //
@@ -156,11 +318,28 @@ unsafe fn propagate_recursive(
     };
 
     let Some(children) = children else { return };
+    if children.len() >= SPLIT_CHILDREN_THRESHOLD {
+        // Let idle tasks steal some of the work.
+        queue_children(
+            &mut lock(queue),
+            entity,
+            global_matrix,
+            children,
+            parent_query,
+            dirty,
+            changed,
+        );
+        return;
+    }
     for (child, actual_parent) in parent_query.iter_many(children) {
         assert_eq!(
             actual_parent.get(), entity,
             "Malformed hierarchy. This probably means that your hierarchy has been improperly maintained, or contains a cycle"
         );
+        let changed = changed || actual_parent.is_changed();
+        if !changed && !dirty.contains(&child) {
+            continue;
+        }
         // SAFETY: The caller guarantees that `transform_query` will not be fetched
         // for any descendants of `entity`, so it is safe to call `propagate_recursive` for each child.
         //
@@ -171,8 +350,10 @@ unsafe fn propagate_recursive(
                 &global_matrix,
                 transform_query,
                 parent_query,
+                dirty,
+                queue,
                 child,
-                changed || actual_parent.is_changed(),
+                changed,
             );
         }
     }
@@ -186,9 +367,9 @@ mod test {
     use bevy_math::{vec3, Vec3};
     use bevy_tasks::{ComputeTaskPool, TaskPool};
 
-    use crate::components::{GlobalTransform, Transform};
+    use crate::components::{GlobalTransform, Transform, Transform2d};
     use crate::systems::*;
-    use crate::TransformBundle;
+    use crate::{TransformBundle, TransformPlugin};
     use bevy_hierarchy::{BuildChildren, BuildWorldChildren, Children, Parent};
 
     #[test]
@@ -443,6 +624,129 @@ mod test {
         }
     }
 
+    #[test]
+    fn clean_subtrees_are_skipped() {
+        ComputeTaskPool::get_or_init(TaskPool::default);
+        let mut world = World::default();
+        let mut schedule = Schedule::default();
+        schedule.add_systems((sync_simple_transforms, propagate_transforms));
+
+        let offset = Transform::from_xyz(1.0, 0.0, 0.0);
+        let root = world.spawn(TransformBundle::IDENTITY).id();
+        let static_child = world.spawn(TransformBundle::from_transform(offset)).id();
+        let moving_child = world.spawn(TransformBundle::from_transform(offset)).id();
+        world
+            .entity_mut(root)
+            .push_children(&[static_child, moving_child]);
+        // Enough grandchildren for them to be split between tasks.
+        let grandchildren: Vec<_> = (0..64)
+            .map(|_| {
+                world
+                    .spawn(TransformBundle::from_transform(Transform::from_xyz(
+                        0.0, 1.0, 0.0,
+                    )))
+                    .id()
+            })
+            .collect();
+        world.entity_mut(moving_child).push_children(&grandchildren);
+        schedule.run(&mut world);
+
+        // Tamper with the static subtree to detect whether it gets recomputed.
+        let sentinel = GlobalTransform::from_xyz(42.0, 42.0, 42.0);
+        *world.get_mut::<GlobalTransform>(static_child).unwrap() = sentinel;
+        world
+            .get_mut::<Transform>(moving_child)
+            .unwrap()
+            .translation
+            .x = 2.0;
+        schedule.run(&mut world);
+
+        assert_eq!(
+            *world.get::<GlobalTransform>(static_child).unwrap(),
+            sentinel
+        );
+        for grandchild in grandchildren {
+            assert_eq!(
+                world
+                    .get::<GlobalTransform>(grandchild)
+                    .unwrap()
+                    .translation(),
+                vec3(2.0, 1.0, 0.0)
+            );
+        }
+    }
+
+    #[test]
+    fn transform_2d_drives_transform() {
+        ComputeTaskPool::get_or_init(TaskPool::default);
+        let mut app = App::new();
+        app.add_plugins(TransformPlugin);
+
+        let parent = app
+            .world
+            .spawn((
+                Transform2d::from_xy(1.0, 2.0).with_z_layer(3.0),
+                TransformBundle::IDENTITY,
+            ))
+            .id();
+        let child = app
+            .world
+            .spawn((
+                Transform2d::from_xy(1.0, 0.0).with_z_layer(1.0),
+                TransformBundle::IDENTITY,
+            ))
+            .set_parent(parent)
+            .id();
+        app.update();
+
+        assert_eq!(
+            app.world
+                .get::<GlobalTransform>(child)
+                .unwrap()
+                .translation(),
+            vec3(2.0, 2.0, 4.0)
+        );
+
+        app.world
+            .get_mut::<Transform2d>(parent)
+            .unwrap()
+            .translation
+            .x = 5.0;
+        app.update();
+
+        assert_eq!(
+            *app.world.get::<Transform>(parent).unwrap(),
+            Transform::from_xyz(5.0, 2.0, 3.0)
+        );
+        assert_eq!(
+            app.world
+                .get::<GlobalTransform>(child)
+                .unwrap()
+                .translation(),
+            vec3(6.0, 2.0, 4.0)
+        );
+
+        // Writing the `Transform` directly doesn't make it diverge from the `Transform2d`
+        app.world
+            .get_mut::<Transform>(parent)
+            .unwrap()
+            .translation
+            .y = 7.0;
+        app.update();
+
+        assert_eq!(
+            *app.world.get::<Transform>(parent).unwrap(),
+            Transform::from_xyz(5.0, 2.0, 3.0)
+        );
+        assert_eq!(
+            app.world
+                .get::<GlobalTransform>(child)
+                .unwrap()
+                .translation(),
+            vec3(6.0, 2.0, 4.0)
+        );
+    }
+
     #[test]
     #[should_panic]
     fn panic_when_hierarchy_cycle() {
@@ -3,7 +3,7 @@ use crate::{
     Node, UiImage,
 };
 use bevy_a11y::{
-    accesskit::{NodeBuilder, Rect, Role},
+    accesskit::{Action, NodeBuilder, Rect, Role},
     AccessibilityNode,
 };
 use bevy_app::{App, Plugin, PostUpdate};
@@ -66,6 +66,7 @@ fn button_changed(
         let name = calc_name(&texts, children);
         if let Some(mut accessible) = accessible {
             accessible.set_role(Role::Button);
+            accessible.add_action(Action::Focus);
             if let Some(name) = name {
                 accessible.set_name(name);
             } else {
@@ -73,6 +74,7 @@ fn button_changed(
             }
         } else {
             let mut node = NodeBuilder::new(Role::Button);
+            node.add_action(Action::Focus);
             if let Some(name) = name {
                 node.set_name(name);
             }
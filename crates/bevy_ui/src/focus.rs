@@ -1,4 +1,4 @@
-use crate::{CalculatedClip, DefaultUiCamera, Node, TargetCamera, UiScale, UiStack};
+use crate::{CalculatedClip, DefaultUiCamera, Node, TargetCamera, UiScale, UiStack, VirtualCursor};
 use bevy_ecs::{
     change_detection::DetectChangesMut,
     entity::Entity,
@@ -121,10 +121,11 @@ impl Default for FocusPolicy {
     }
 }
 
-/// Contains entities whose Interaction should be set to None
+/// Contains entities whose Interaction should be set to None, and the pressed [`VirtualCursor`]s
 #[derive(Default)]
 pub struct State {
     entities_to_reset: SmallVec<[Entity; 1]>,
+    pressed_virtual_cursors: SmallVec<[Entity; 1]>,
 }
 
 /// Main query for [`ui_focus_system`]
@@ -144,6 +145,8 @@ pub struct NodeQuery {
 
 /// The system that sets Interaction for all UI elements based on the mouse cursor activity
 ///
+/// [`VirtualCursor`]s act as the mouse cursor of their window while they are shown.
+///
 /// Entities with a hidden [`ViewVisibility`] are always treated as released.
 #[allow(clippy::too_many_arguments)]
 pub fn ui_focus_system(
@@ -154,6 +157,7 @@ pub fn ui_focus_system(
     windows: Query<&Window>,
     mouse_button_input: Res<ButtonInput<MouseButton>>,
     touches_input: Res<Touches>,
+    virtual_cursors: Query<(Entity, &VirtualCursor)>,
     ui_scale: Res<UiScale>,
     ui_stack: Res<UiStack>,
     mut node_query: Query<NodeQuery>,
@@ -171,8 +175,25 @@ pub fn ui_focus_system(
         }
     }
 
-    let mouse_released =
-        mouse_button_input.just_released(MouseButton::Left) || touches_input.any_just_released();
+    // Virtual cursors that were released or despawned are removed from the pressed ones
+    let mut virtual_cursor_pressed = false;
+    let pressed_cursor_count = state.pressed_virtual_cursors.len();
+    state.pressed_virtual_cursors.retain(|entity| {
+        virtual_cursors
+            .get(*entity)
+            .is_ok_and(|(_, cursor)| cursor.pressed)
+    });
+    let virtual_cursor_released = state.pressed_virtual_cursors.len() < pressed_cursor_count;
+    for (entity, cursor) in &virtual_cursors {
+        if cursor.pressed && !state.pressed_virtual_cursors.contains(&entity) {
+            virtual_cursor_pressed = true;
+            state.pressed_virtual_cursors.push(entity);
+        }
+    }
+
+    let mouse_released = mouse_button_input.just_released(MouseButton::Left)
+        || touches_input.any_just_released()
+        || virtual_cursor_released;
     if mouse_released {
         for node in &mut node_query {
             if let Some(mut interaction) = node.interaction {
@@ -183,8 +204,9 @@ pub fn ui_focus_system(
         }
     }
 
-    let mouse_clicked =
-        mouse_button_input.just_pressed(MouseButton::Left) || touches_input.any_just_pressed();
+    let mouse_clicked = mouse_button_input.just_pressed(MouseButton::Left)
+        || touches_input.any_just_pressed()
+        || virtual_cursor_pressed;
 
     let camera_cursor_positions: HashMap<Entity, Vec2> = camera_query
         .iter()
@@ -200,10 +222,18 @@ pub fn ui_focus_system(
                 .logical_viewport_rect()
                 .map(|rect| rect.min)
                 .unwrap_or_default();
-            windows
-                .get(window_ref.entity())
-                .ok()
-                .and_then(|window| window.cursor_position())
+            let virtual_cursor_position = virtual_cursors.iter().find_map(|(_, cursor)| {
+                (cursor.window.normalize(primary_window) == Some(window_ref))
+                    .then_some(cursor.position)
+                    .flatten()
+            });
+            virtual_cursor_position
+                .or_else(|| {
+                    windows
+                        .get(window_ref.entity())
+                        .ok()
+                        .and_then(|window| window.cursor_position())
+                })
                 .or_else(|| touches_input.first_pressed_position())
                 .map(|cursor_position| (entity, cursor_position - viewport_position))
         })
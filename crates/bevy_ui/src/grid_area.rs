@@ -0,0 +1,236 @@
+//! This module contains the components and systems placing grid items into named grid areas
+
+use crate::{GridPlacement, Style};
+use bevy_ecs::{prelude::*, world::Ref};
+use bevy_hierarchy::Parent;
+use bevy_log::warn;
+use bevy_reflect::prelude::*;
+use thiserror::Error;
+
+/// Named areas of a grid container, assignable to its children with [`GridArea`].
+///
+/// The areas are described like the CSS `grid-template-areas` property: one string per row,
+/// with one whitespace-separated area name per column. A `.` marks a cell that doesn't belong
+/// to any area. Each named area must form a rectangle.
+///
+/// The areas only place items; the sizes of the rows and columns are still defined by
+/// [`Style::grid_template_rows`] and [`Style::grid_template_columns`].
+///
+/// ```
+/// # use bevy_ui::GridTemplateAreas;
+/// let areas = GridTemplateAreas::new([
+///     "header header",
+///     "sidebar main",
+///     ". footer",
+/// ])
+/// .unwrap();
+/// assert_eq!(areas.get("header").unwrap().column.get_span(), Some(2));
+/// ```
+///
+/// <https://developer.mozilla.org/en-US/docs/Web/CSS/grid-template-areas>
+#[derive(Component, Clone, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct GridTemplateAreas {
+    areas: Vec<GridTemplateArea>,
+}
+
+/// A named area of a [`GridTemplateAreas`].
+#[derive(Clone, Debug, PartialEq, Reflect)]
+#[reflect(PartialEq)]
+pub struct GridTemplateArea {
+    /// The name of the area.
+    pub name: String,
+    /// The rows covered by the area.
+    pub row: GridPlacement,
+    /// The columns covered by the area.
+    pub column: GridPlacement,
+}
+
+impl GridTemplateAreas {
+    /// Parses the named areas from one string per grid row.
+    pub fn new<'a>(
+        rows: impl IntoIterator<Item = &'a str>,
+    ) -> Result<Self, GridTemplateAreasError> {
+        let mut areas: Vec<(String, [usize; 2], [usize; 2], usize)> = Vec::new();
+        let mut columns = None;
+
+        for (row, row_str) in rows.into_iter().enumerate() {
+            let names: Vec<&str> = row_str.split_whitespace().collect();
+            let expected = *columns.get_or_insert(names.len());
+            if names.len() != expected {
+                return Err(GridTemplateAreasError::MismatchedRowLength {
+                    row,
+                    expected,
+                    found: names.len(),
+                });
+            }
+
+            for (column, name) in names.into_iter().enumerate() {
+                if name.chars().all(|c| c == '.') {
+                    continue;
+                }
+                match areas.iter_mut().find(|(area, ..)| area == name) {
+                    Some((_, rows, columns, cells)) => {
+                        rows[0] = rows[0].min(row);
+                        rows[1] = rows[1].max(row);
+                        columns[0] = columns[0].min(column);
+                        columns[1] = columns[1].max(column);
+                        *cells += 1;
+                    }
+                    None => areas.push((name.to_string(), [row, row], [column, column], 1)),
+                }
+            }
+        }
+
+        let areas = areas
+            .into_iter()
+            .map(|(name, rows, columns, cells)| {
+                let row_span = rows[1] - rows[0] + 1;
+                let column_span = columns[1] - columns[0] + 1;
+                if row_span * column_span != cells {
+                    return Err(GridTemplateAreasError::NonRectangularArea(name));
+                }
+                Ok(GridTemplateArea {
+                    name,
+                    row: grid_lines(rows[0], row_span)?,
+                    column: grid_lines(columns[0], column_span)?,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { areas })
+    }
+
+    /// Returns the area with the given `name`.
+    pub fn get(&self, name: &str) -> Option<&GridTemplateArea> {
+        self.areas.iter().find(|area| area.name == name)
+    }
+
+    /// Iterates over the named areas.
+    pub fn iter(&self) -> impl Iterator<Item = &GridTemplateArea> {
+        self.areas.iter()
+    }
+}
+
+fn grid_lines(start: usize, span: usize) -> Result<GridPlacement, GridTemplateAreasError> {
+    let start = i16::try_from(start + 1).map_err(|_| GridTemplateAreasError::TooLarge)?;
+    let span = u16::try_from(span).map_err(|_| GridTemplateAreasError::TooLarge)?;
+    Ok(GridPlacement::start_span(start, span))
+}
+
+/// Errors that occur when parsing [`GridTemplateAreas`]
+#[derive(Debug, Eq, PartialEq, Clone, Error)]
+pub enum GridTemplateAreasError {
+    #[error("Row {row} has {found} columns, but the previous rows have {expected}")]
+    MismatchedRowLength {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    #[error("Grid area `{0}` is not a rectangle")]
+    NonRectangularArea(String),
+    #[error("Grid areas must fit in the range of grid lines")]
+    TooLarge,
+}
+
+/// Places a grid item into the named area of its parent's [`GridTemplateAreas`].
+///
+/// The item's [`Style::grid_row`] and [`Style::grid_column`] are overwritten by
+/// [`update_grid_areas_system`] whenever the area, the parent or the parent's areas change.
+///
+/// <https://developer.mozilla.org/en-US/docs/Web/CSS/grid-area>
+#[derive(Component, Clone, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct GridArea(pub String);
+
+impl GridArea {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self(name.into())
+    }
+}
+
+/// Updates the grid placement of items with a [`GridArea`] from their parent's [`GridTemplateAreas`].
+pub fn update_grid_areas_system(
+    mut item_query: Query<(Entity, Ref<GridArea>, Ref<Parent>, &mut Style)>,
+    container_query: Query<Ref<GridTemplateAreas>>,
+) {
+    for (entity, grid_area, parent, mut style) in &mut item_query {
+        let Ok(areas) = container_query.get(parent.get()) else {
+            continue;
+        };
+        if !(grid_area.is_changed()
+            || parent.is_changed()
+            || areas.is_changed()
+            || style.is_changed())
+        {
+            continue;
+        }
+
+        let Some(area) = areas.get(&grid_area.0) else {
+            warn!(
+                "Grid item {entity:?} is placed in the area `{}`, which its parent {:?} doesn't define",
+                grid_area.0,
+                parent.get()
+            );
+            continue;
+        };
+        if style.grid_row != area.row || style.grid_column != area.column {
+            style.grid_row = area.row;
+            style.grid_column = area.column;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_hierarchy::BuildWorldChildren;
+
+    #[test]
+    fn parse_grid_template_areas() {
+        let areas =
+            GridTemplateAreas::new(["header header header", "nav main main", ". main main"])
+                .unwrap();
+
+        let header = areas.get("header").unwrap();
+        assert_eq!(header.row, GridPlacement::start_span(1, 1));
+        assert_eq!(header.column, GridPlacement::start_span(1, 3));
+        let main = areas.get("main").unwrap();
+        assert_eq!(main.row, GridPlacement::start_span(2, 2));
+        assert_eq!(main.column, GridPlacement::start_span(2, 2));
+        assert_eq!(areas.iter().count(), 3);
+
+        assert_eq!(
+            GridTemplateAreas::new(["a a", "a"]),
+            Err(GridTemplateAreasError::MismatchedRowLength {
+                row: 1,
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            GridTemplateAreas::new(["a a", "a b"]),
+            Err(GridTemplateAreasError::NonRectangularArea("a".to_string()))
+        );
+    }
+
+    #[test]
+    fn grid_area_sets_item_placement() {
+        let mut world = World::new();
+        let container = world
+            .spawn(GridTemplateAreas::new(["sidebar main"]).unwrap())
+            .id();
+        let item = world
+            .spawn((GridArea::new("main"), Style::default()))
+            .set_parent(container)
+            .id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(update_grid_areas_system);
+        schedule.run(&mut world);
+
+        let style = world.get::<Style>(item).unwrap();
+        assert_eq!(style.grid_row, GridPlacement::start_span(1, 1));
+        assert_eq!(style.grid_column, GridPlacement::start_span(2, 1));
+    }
+}
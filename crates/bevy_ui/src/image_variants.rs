@@ -0,0 +1,206 @@
+//! This module contains the systems selecting resolution variants of UI images for the scale factor
+
+use crate::{DefaultUiCamera, TargetCamera, UiImage, UiScale};
+use bevy_asset::{AssetPath, AssetServer};
+use bevy_ecs::prelude::*;
+use bevy_reflect::prelude::*;
+use bevy_render::camera::Camera;
+use bevy_utils::EntityHashMap;
+
+/// Sent when the effective UI scale factor of a camera changes.
+///
+/// The effective scale factor is the scale factor of the camera's render target multiplied by
+/// [`UiScale`]. Custom widgets can use this event to swap their assets for ones matching the
+/// new scale factor.
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct UiScaleFactorChanged {
+    /// The camera whose UI scale factor changed.
+    pub camera: Entity,
+    /// The new effective scale factor.
+    pub scale_factor: f32,
+}
+
+/// The effective UI scale factor of each camera, see [`UiScaleFactorChanged`].
+#[derive(Resource, Debug, Default)]
+pub struct UiScaleFactors {
+    scale_factors: EntityHashMap<Entity, f32>,
+}
+
+impl UiScaleFactors {
+    /// Returns the effective UI scale factor of `camera`.
+    pub fn get(&self, camera: Entity) -> Option<f32> {
+        self.scale_factors.get(&camera).copied()
+    }
+}
+
+/// Selects the [`UiImage`] of a node between resolution variants of the same image, based on
+/// the effective scale factor of the node's camera.
+///
+/// Variants follow the `@Nx` naming convention: with a `path` of `icons/sword.png` and `scales`
+/// of `[2, 3]`, `icons/sword.png`, `icons/sword@2x.png` and `icons/sword@3x.png` are used.
+/// The smallest variant whose scale is at least the scale factor is selected, or the largest
+/// variant if there is none. The node is sized as if the 1x image was used.
+///
+/// ```
+/// # use bevy_ui::UiImageVariants;
+/// let variants = UiImageVariants::new("icons/sword.png", [2, 3]);
+/// assert_eq!(variants.variant_path(2).to_string(), "icons/sword@2x.png");
+/// assert_eq!(variants.scale_for(1.5), 2);
+/// ```
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component)]
+pub struct UiImageVariants {
+    /// The path of the 1x image.
+    pub path: AssetPath<'static>,
+    /// The scales of the variants available besides the 1x image.
+    pub scales: Vec<u32>,
+    /// The scale of the variant currently used by the node's [`UiImage`].
+    #[reflect(ignore)]
+    selected_scale: u32,
+}
+
+impl UiImageVariants {
+    pub fn new(path: impl Into<AssetPath<'static>>, scales: impl Into<Vec<u32>>) -> Self {
+        Self {
+            path: path.into(),
+            scales: scales.into(),
+            selected_scale: 1,
+        }
+    }
+
+    /// Returns the path of the variant with the given `scale`.
+    pub fn variant_path(&self, scale: u32) -> AssetPath<'static> {
+        if scale == 1 {
+            return self.path.clone();
+        }
+
+        let path = self.path.path();
+        let mut file_name = path.file_stem().unwrap_or_default().to_os_string();
+        file_name.push(format!("@{scale}x"));
+        if let Some(extension) = path.extension() {
+            file_name.push(".");
+            file_name.push(extension);
+        }
+
+        let variant = AssetPath::from(path.with_file_name(file_name))
+            .with_source(self.path.source().clone_owned());
+        match self.path.label_cow() {
+            Some(label) => variant.with_label(label),
+            None => variant,
+        }
+    }
+
+    /// Returns the scale of the variant to use for the given scale factor.
+    pub fn scale_for(&self, scale_factor: f32) -> u32 {
+        let scales = self.scales.iter().copied().chain(std::iter::once(1));
+        scales
+            .clone()
+            .filter(|&scale| scale as f32 >= scale_factor)
+            .min()
+            .or_else(|| scales.max())
+            .unwrap_or(1)
+    }
+
+    /// The scale of the variant currently used by the node's [`UiImage`].
+    pub fn selected_scale(&self) -> u32 {
+        self.selected_scale
+    }
+}
+
+/// Updates [`UiScaleFactors`] and sends [`UiScaleFactorChanged`] events.
+pub fn update_ui_scale_factors_system(
+    cameras: Query<(Entity, &Camera)>,
+    ui_scale: Res<UiScale>,
+    mut scale_factors: ResMut<UiScaleFactors>,
+    mut events: EventWriter<UiScaleFactorChanged>,
+) {
+    let scale_factors = scale_factors.bypass_change_detection();
+    scale_factors
+        .scale_factors
+        .retain(|camera, _| cameras.contains(*camera));
+
+    for (camera_entity, camera) in &cameras {
+        let Some(target_scale_factor) = camera.target_scaling_factor() else {
+            continue;
+        };
+        let scale_factor = target_scale_factor * ui_scale.0;
+        if scale_factors
+            .scale_factors
+            .insert(camera_entity, scale_factor)
+            != Some(scale_factor)
+        {
+            events.send(UiScaleFactorChanged {
+                camera: camera_entity,
+                scale_factor,
+            });
+        }
+    }
+}
+
+/// Selects the [`UiImage`] of nodes with [`UiImageVariants`].
+pub fn select_ui_image_variants_system(
+    asset_server: Res<AssetServer>,
+    scale_factors: Res<UiScaleFactors>,
+    default_ui_camera: DefaultUiCamera,
+    mut events: EventReader<UiScaleFactorChanged>,
+    mut query: Query<(
+        &mut UiImageVariants,
+        Option<Ref<TargetCamera>>,
+        &mut UiImage,
+    )>,
+) {
+    let scale_factor_changed = events.read().count() > 0;
+    let default_camera = default_ui_camera.get();
+
+    for (mut variants, target_camera, mut image) in &mut query {
+        let camera_changed = target_camera
+            .as_ref()
+            .is_some_and(|target_camera| target_camera.is_changed());
+        if !(scale_factor_changed || camera_changed || variants.is_changed()) {
+            continue;
+        }
+
+        let Some(scale_factor) = target_camera
+            .map(|target_camera| target_camera.entity())
+            .or(default_camera)
+            .and_then(|camera| scale_factors.get(camera))
+        else {
+            continue;
+        };
+
+        let scale = variants.scale_for(scale_factor);
+        let texture = asset_server.load(variants.variant_path(scale));
+        if image.texture != texture {
+            image.texture = texture;
+        }
+        if variants.selected_scale != scale {
+            variants.bypass_change_detection().selected_scale = scale;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UiImageVariants;
+
+    #[test]
+    fn select_variant_for_scale_factor() {
+        let variants = UiImageVariants::new("icons/sword.png", [2, 4]);
+        assert_eq!(variants.scale_for(1.0), 1);
+        assert_eq!(variants.scale_for(1.25), 2);
+        assert_eq!(variants.scale_for(2.0), 2);
+        assert_eq!(variants.scale_for(3.0), 4);
+        assert_eq!(variants.scale_for(8.0), 4);
+
+        assert_eq!(
+            variants.variant_path(4).to_string(),
+            "icons/sword@4x.png".to_string()
+        );
+        assert_eq!(
+            UiImageVariants::new("atlas.gltf#Texture0", [2])
+                .variant_path(2)
+                .to_string(),
+            "atlas@2x.gltf#Texture0".to_string()
+        );
+    }
+}
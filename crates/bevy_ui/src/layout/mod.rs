@@ -1,7 +1,9 @@
 mod convert;
 pub mod debug;
 
-use crate::{ContentSize, DefaultUiCamera, Node, Outline, Style, TargetCamera, UiScale};
+use crate::{
+    ContentSize, DefaultUiCamera, Node, Outline, Style, TargetCamera, UiPixelSnapping, UiScale,
+};
 use bevy_ecs::{
     change_detection::{DetectChanges, DetectChangesMut},
     entity::Entity,
@@ -262,7 +264,10 @@ pub fn ui_layout_system(
     mut scale_factor_events: EventReader<WindowScaleFactorChanged>,
     mut resize_events: EventReader<bevy_window::WindowResized>,
     mut ui_surface: ResMut<UiSurface>,
-    root_node_query: Query<(Entity, Option<&TargetCamera>), (With<Node>, Without<Parent>)>,
+    root_node_query: Query<
+        (Entity, Option<&TargetCamera>, Option<&UiPixelSnapping>),
+        (With<Node>, Without<Parent>),
+    >,
     style_query: Query<(Entity, Ref<Style>, Option<&TargetCamera>), With<Node>>,
     mut measure_query: Query<(Entity, &mut ContentSize)>,
     children_query: Query<(Entity, Ref<Children>), With<Node>>,
@@ -276,7 +281,7 @@ pub fn ui_layout_system(
         size: UVec2,
         resized: bool,
         scale_factor: f32,
-        root_nodes: Vec<Entity>,
+        root_nodes: Vec<(Entity, UiPixelSnapping)>,
     }
 
     let camera_with_default = |target_camera: Option<&TargetCamera>| {
@@ -305,7 +310,7 @@ pub fn ui_layout_system(
 
     // Precalculate the layout info for each camera, so we have fast access to it for each node
     let mut camera_layout_info: HashMap<Entity, CameraLayoutInfo> = HashMap::new();
-    for (entity, target_camera) in &root_node_query {
+    for (entity, target_camera, pixel_snapping) in &root_node_query {
         match camera_with_default(target_camera) {
             Some(camera_entity) => {
                 let Ok((_, camera)) = cameras.get(camera_entity) else {
@@ -318,7 +323,9 @@ pub fn ui_layout_system(
                 let layout_info = camera_layout_info
                     .entry(camera_entity)
                     .or_insert_with(|| calculate_camera_layout_info(camera));
-                layout_info.root_nodes.push(entity);
+                layout_info
+                    .root_nodes
+                    .push((entity, pixel_snapping.copied().unwrap_or_default()));
             }
             None => {
                 if cameras.is_empty() {
@@ -370,7 +377,7 @@ pub fn ui_layout_system(
 
     // update camera children
     for (camera_id, CameraLayoutInfo { root_nodes, .. }) in &camera_layout_info {
-        ui_surface.set_camera_children(*camera_id, root_nodes.iter().cloned());
+        ui_surface.set_camera_children(*camera_id, root_nodes.iter().map(|(entity, _)| *entity));
     }
 
     // update and remove children
@@ -387,25 +394,28 @@ pub fn ui_layout_system(
         let inverse_target_scale_factor = camera.scale_factor.recip();
 
         ui_surface.compute_camera_layout(*camera_id, camera.size);
-        for root in &camera.root_nodes {
+        for &(root, pixel_snapping) in &camera.root_nodes {
             update_uinode_geometry_recursive(
-                *root,
+                root,
                 &ui_surface,
                 &mut node_transform_query,
                 &just_children_query,
                 inverse_target_scale_factor,
+                pixel_snapping,
                 Vec2::ZERO,
                 Vec2::ZERO,
             );
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn update_uinode_geometry_recursive(
         entity: Entity,
         ui_surface: &UiSurface,
         node_transform_query: &mut Query<(&mut Node, &mut Transform)>,
         children_query: &Query<&Children>,
         inverse_target_scale_factor: f32,
+        pixel_snapping: UiPixelSnapping,
         parent_size: Vec2,
         mut absolute_location: Vec2,
     ) {
@@ -416,13 +426,19 @@ pub fn ui_layout_system(
             let layout_location =
                 inverse_target_scale_factor * Vec2::new(layout.location.x, layout.location.y);
 
+            let parent_location = absolute_location;
             absolute_location += layout_location;
 
-            let rounded_size = round_layout_coords(absolute_location + layout_size)
-                - round_layout_coords(absolute_location);
+            let snap =
+                |value| snap_layout_coords(value, pixel_snapping, inverse_target_scale_factor);
+            let rounded_size = snap(absolute_location + layout_size) - snap(absolute_location);
 
-            let rounded_location =
-                round_layout_coords(layout_location) + 0.5 * (rounded_size - parent_size);
+            let rounded_location = match pixel_snapping {
+                UiPixelSnapping::Logical => round_layout_coords(layout_location),
+                // Place the node relative to its parent's snapped position, so that its snapped
+                // edges are the ones it is drawn at
+                _ => snap(absolute_location) - snap(parent_location),
+            } + 0.5 * (rounded_size - parent_size);
 
             // only trigger change detection when the new values are different
             if node.calculated_size != rounded_size || node.unrounded_size != layout_size {
@@ -440,6 +456,7 @@ pub fn ui_layout_system(
                         node_transform_query,
                         children_query,
                         inverse_target_scale_factor,
+                        pixel_snapping,
                         rounded_size,
                         absolute_location,
                     );
@@ -505,9 +522,24 @@ fn round_layout_coords(value: Vec2) -> Vec2 {
     }
 }
 
+/// Snaps absolute layout coordinates, given in logical pixels, according to `pixel_snapping`.
+fn snap_layout_coords(
+    value: Vec2,
+    pixel_snapping: UiPixelSnapping,
+    inverse_target_scale_factor: f32,
+) -> Vec2 {
+    match pixel_snapping {
+        UiPixelSnapping::Logical => round_layout_coords(value),
+        UiPixelSnapping::Physical => {
+            round_layout_coords(value / inverse_target_scale_factor) * inverse_target_scale_factor
+        }
+        UiPixelSnapping::None => value,
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::layout::round_layout_coords;
+    use crate::layout::{round_layout_coords, snap_layout_coords};
     use crate::prelude::*;
     use crate::ui_layout_system;
     use crate::update::update_target_camera_system;
@@ -545,6 +577,30 @@ mod tests {
         assert_eq!(round_layout_coords(vec2(-50.5, 49.5)), vec2(-50., 50.));
     }
 
+    #[test]
+    fn physical_pixel_snapping_rounds_to_physical_pixels() {
+        // With a scale factor of 1.5, a logical pixel is 1.5 physical pixels
+        let inverse_scale_factor = 1.5f32.recip();
+        let snapped = snap_layout_coords(
+            vec2(10.5, 3.2),
+            UiPixelSnapping::Physical,
+            inverse_scale_factor,
+        );
+        assert!((snapped * 1.5 - vec2(16., 5.)).abs().max_element() < 1e-4);
+        assert_eq!(
+            snap_layout_coords(
+                vec2(10.5, 3.2),
+                UiPixelSnapping::Logical,
+                inverse_scale_factor
+            ),
+            vec2(11., 3.)
+        );
+        assert_eq!(
+            snap_layout_coords(vec2(10.5, 3.2), UiPixelSnapping::None, inverse_scale_factor),
+            vec2(10.5, 3.2)
+        );
+    }
+
     // these window dimensions are easy to convert to and from percentage values
     const WINDOW_WIDTH: f32 = 1000.;
     const WINDOW_HEIGHT: f32 = 100.;
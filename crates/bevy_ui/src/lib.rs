@@ -18,27 +18,44 @@ use bevy_reflect::Reflect;
 mod accessibility;
 mod focus;
 mod geometry;
+mod grid_area;
+mod image_variants;
 mod layout;
+#[cfg(feature = "bevy_picking")]
+mod picking_backend;
 mod render;
 mod stack;
 mod texture_slice;
 mod ui_node;
+mod virtual_cursor;
 
 pub use focus::*;
 pub use geometry::*;
+pub use grid_area::*;
+pub use image_variants::*;
 pub use layout::*;
 pub use measurement::*;
+#[cfg(feature = "bevy_picking")]
+pub use picking_backend::*;
 pub use render::*;
 pub use ui_material::*;
 pub use ui_node::*;
+pub use virtual_cursor::*;
 use widget::UiImageSize;
 
 #[doc(hidden)]
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        geometry::*, node_bundles::*, ui_material::*, ui_node::*, widget::Button, widget::Label,
-        Interaction, UiMaterialPlugin, UiScale,
+        geometry::*,
+        grid_area::{GridArea, GridTemplateAreas},
+        node_bundles::*,
+        ui_material::*,
+        ui_node::*,
+        widget::Button,
+        widget::Label,
+        widget::UiVectorPath,
+        Interaction, UiMaterialPlugin, UiScale, VirtualCursor, VirtualCursorPlugin,
     };
     // `bevy_sprite` re-exports for texture slicing
     #[doc(hidden)]
@@ -48,7 +65,7 @@ pub mod prelude {
 use bevy_app::prelude::*;
 use bevy_ecs::prelude::*;
 use bevy_input::InputSystem;
-use bevy_render::RenderApp;
+use bevy_render::{camera::CameraUpdateSystem, RenderApp};
 use bevy_transform::TransformSystem;
 use stack::ui_stack_system;
 pub use stack::UiStack;
@@ -97,6 +114,8 @@ impl Plugin for UiPlugin {
         app.init_resource::<UiSurface>()
             .init_resource::<UiScale>()
             .init_resource::<UiStack>()
+            .init_resource::<UiScaleFactors>()
+            .add_event::<UiScaleFactorChanged>()
             .register_type::<AlignContent>()
             .register_type::<AlignItems>()
             .register_type::<AlignSelf>()
@@ -108,8 +127,10 @@ impl Plugin for UiPlugin {
             .register_type::<FlexDirection>()
             .register_type::<FlexWrap>()
             .register_type::<FocusPolicy>()
+            .register_type::<GridArea>()
             .register_type::<GridAutoFlow>()
             .register_type::<GridPlacement>()
+            .register_type::<GridTemplateAreas>()
             .register_type::<GridTrack>()
             .register_type::<Interaction>()
             .register_type::<JustifyContent>()
@@ -125,12 +146,15 @@ impl Plugin for UiPlugin {
             .register_type::<TargetCamera>()
             .register_type::<UiImage>()
             .register_type::<UiImageSize>()
+            .register_type::<UiImageVariants>()
+            .register_type::<UiPixelSnapping>()
             .register_type::<UiRect>()
             .register_type::<UiScale>()
             .register_type::<Val>()
             .register_type::<BorderColor>()
             .register_type::<widget::Button>()
             .register_type::<widget::Label>()
+            .register_type::<widget::UiVectorPath>()
             .register_type::<ZIndex>()
             .register_type::<Outline>()
             .add_systems(
@@ -142,6 +166,7 @@ impl Plugin for UiPlugin {
             PostUpdate,
             (
                 update_target_camera_system.before(UiSystem::Layout),
+                update_grid_areas_system.before(UiSystem::Layout),
                 apply_deferred
                     .after(update_target_camera_system)
                     .before(UiSystem::Layout),
@@ -162,11 +187,13 @@ impl Plugin for UiPlugin {
                     .ambiguous_with(ui_layout_system)
                     .in_set(AmbiguousWithTextSystem),
                 update_clipping_system.after(TransformSystem::TransformPropagate),
+                update_ui_scale_factors_system.after(CameraUpdateSystem),
                 // Potential conflicts: `Assets<Image>`
                 // They run independently since `widget::image_node_system` will only ever observe
                 // its own UiImage, and `widget::text_system` & `bevy_text::update_text2d_layout`
                 // will never modify a pre-existing `Image` asset.
                 (
+                    select_ui_image_variants_system.after(update_ui_scale_factors_system),
                     widget::update_image_content_size_system
                         .before(UiSystem::Layout)
                         .in_set(AmbiguousWithTextSystem)
@@ -177,12 +204,27 @@ impl Plugin for UiPlugin {
                     ),
                 )
                     .chain(),
+                widget::update_vector_path_content_size_system
+                    .before(UiSystem::Layout)
+                    .in_set(AmbiguousWithTextSystem)
+                    .in_set(AmbiguousWithUpdateText2DLayout),
+                // Potential conflict: `Assets<Image>`
+                // The images of vector graphics are only observed by their own nodes.
+                widget::update_vector_path_images_system
+                    .after(UiSystem::Layout)
+                    .before(texture_slice::compute_slices_on_image_change)
+                    .ambiguous_with(bevy_render::camera::CameraUpdateSystem)
+                    .in_set(AmbiguousWithTextSystem)
+                    .in_set(AmbiguousWithUpdateText2DLayout),
             ),
         );
 
         #[cfg(feature = "bevy_text")]
         build_text_interop(app);
 
+        #[cfg(feature = "bevy_picking")]
+        app.add_plugins(UiPickingPlugin);
+
         build_ui_render(app);
     }
 
@@ -209,6 +251,7 @@ fn build_text_interop(app: &mut App) {
         (
             widget::measure_text_system
                 .before(UiSystem::Layout)
+                .after(bevy_text::LocalizationSystem)
                 // Potential conflict: `Assets<Image>`
                 // In practice, they run independently since `bevy_render::camera_update_system`
                 // will only ever observe its own render target, and `widget::measure_text_system`
@@ -223,7 +266,7 @@ fn build_text_interop(app: &mut App) {
                 .ambiguous_with(widget::update_image_content_size_system),
             widget::text_system
                 .after(UiSystem::Layout)
-                .after(bevy_text::remove_dropped_font_atlas_sets)
+                .after(bevy_text::remove_dropped_font_glyphs)
                 // Text2d and bevy_ui text are entirely on separate entities
                 .ambiguous_with(bevy_text::update_text2d_layout),
         ),
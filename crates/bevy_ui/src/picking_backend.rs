@@ -0,0 +1,178 @@
+use crate::{CalculatedClip, DefaultUiCamera, Node, TargetCamera, UiScale, UiStack, VirtualCursor};
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_math::Vec2;
+use bevy_picking::{
+    backend::{HitData, PointerHits},
+    pointer::{
+        Location, PointerAction, PointerBundle, PointerButton, PointerId, PointerInput,
+        PointerLocation,
+    },
+    PickSet,
+};
+use bevy_render::{
+    camera::{Camera, NormalizedRenderTarget},
+    view::ViewVisibility,
+};
+use bevy_transform::components::GlobalTransform;
+use bevy_utils::{EntityHashMap, HashMap};
+use bevy_window::PrimaryWindow;
+
+/// Adds a picking backend for UI [`Node`]s, hit within their visible area.
+///
+/// Added by the [`UiPlugin`](crate::UiPlugin) when the `bevy_picking` feature is enabled.
+#[derive(Default)]
+pub struct UiPickingPlugin;
+
+impl Plugin for UiPickingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PreUpdate, ui_picking.in_set(PickSet::Backend));
+    }
+}
+
+/// Sends the [`PointerHits`] of the visible UI nodes under each pointer, for each UI camera.
+///
+/// The nodes are drawn on top of everything else rendered by their camera, so their hits are
+/// ordered just in front of the other hits of the camera.
+#[allow(clippy::too_many_arguments)]
+pub fn ui_picking(
+    pointers: Query<(&PointerId, &PointerLocation)>,
+    cameras: Query<(Entity, &Camera)>,
+    default_ui_camera: DefaultUiCamera,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    ui_scale: Res<UiScale>,
+    ui_stack: Res<UiStack>,
+    nodes: Query<(
+        &Node,
+        &GlobalTransform,
+        &ViewVisibility,
+        Option<&CalculatedClip>,
+        Option<&TargetCamera>,
+    )>,
+    mut output: EventWriter<PointerHits>,
+) {
+    let primary_window = primary_window.iter().next();
+    let default_camera = default_ui_camera.get();
+
+    for (pointer_id, pointer_location) in &pointers {
+        let Some(location) = pointer_location.location() else {
+            continue;
+        };
+
+        // The position of the pointer in the UI coordinates of each camera it is over.
+        let cursor_positions: HashMap<Entity, _> = cameras
+            .iter()
+            .filter(|(_, camera)| {
+                camera.is_active && location.is_in_viewport(camera, primary_window)
+            })
+            .filter_map(|(entity, camera)| {
+                let viewport_min = camera.logical_viewport_rect()?.min;
+                Some((entity, (location.position - viewport_min) / ui_scale.0))
+            })
+            .collect();
+        if cursor_positions.is_empty() {
+            continue;
+        }
+
+        let mut picks: HashMap<Entity, Vec<(Entity, HitData)>> = HashMap::new();
+        // From the top node to the bottom one.
+        for (depth, &entity) in ui_stack.uinodes.iter().rev().enumerate() {
+            let Ok((node, transform, view_visibility, clip, target_camera)) = nodes.get(entity)
+            else {
+                continue;
+            };
+            if !view_visibility.get() {
+                continue;
+            }
+            let Some(camera) = target_camera.map(TargetCamera::entity).or(default_camera) else {
+                continue;
+            };
+            let Some(cursor_position) = cursor_positions.get(&camera) else {
+                continue;
+            };
+
+            let node_rect = node.logical_rect(transform);
+            let visible_rect = clip.map_or(node_rect, |clip| node_rect.intersect(clip.clip));
+            if visible_rect.contains(*cursor_position) {
+                picks
+                    .entry(camera)
+                    .or_default()
+                    .push((entity, HitData::new(camera, depth as f32, None, None)));
+            }
+        }
+
+        for (camera, picks) in picks {
+            let Ok((_, camera)) = cameras.get(camera) else {
+                continue;
+            };
+            output.send(PointerHits::new(
+                *pointer_id,
+                picks,
+                camera.order as f32 + 0.5,
+            ));
+        }
+    }
+}
+
+/// Sends the [`PointerInput`]s of the [`VirtualCursor`]s, which are picking pointers with a
+/// [`PointerId::Custom`] id.
+pub fn virtual_cursor_pick_events(
+    mut commands: Commands,
+    cursors: Query<(Entity, &VirtualCursor, Option<&PointerId>)>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    mut last_states: Local<EntityHashMap<Entity, (Location, bool)>>,
+    mut pointer_inputs: EventWriter<PointerInput>,
+) {
+    let primary_window = primary_window.iter().next();
+    last_states.retain(|entity, _| cursors.contains(*entity));
+
+    for (entity, cursor, pointer_id) in &cursors {
+        let Some(&pointer_id) = pointer_id else {
+            // The cursor entity is its own pointer
+            commands
+                .entity(entity)
+                .insert(PointerBundle::new(PointerId::Custom(entity.to_bits())));
+            continue;
+        };
+
+        let location = cursor.position.and_then(|position| {
+            Some(Location {
+                target: NormalizedRenderTarget::Window(cursor.window.normalize(primary_window)?),
+                position,
+            })
+        });
+        let Some(location) = location else {
+            if let Some((location, _)) = last_states.remove(&entity) {
+                pointer_inputs.send(PointerInput::new(
+                    pointer_id,
+                    location,
+                    PointerAction::Canceled,
+                ));
+            }
+            continue;
+        };
+
+        let (last_position, was_pressed) = last_states
+            .get(&entity)
+            .map(|(location, pressed)| (Some(location.position), *pressed))
+            .unwrap_or((None, false));
+        if last_position != Some(location.position) {
+            pointer_inputs.send(PointerInput::new(
+                pointer_id,
+                location.clone(),
+                PointerAction::Moved {
+                    delta: last_position.map_or(Vec2::ZERO, |last| location.position - last),
+                },
+            ));
+        }
+        if cursor.pressed != was_pressed {
+            let action = if cursor.pressed {
+                PointerAction::Pressed(PointerButton::Primary)
+            } else {
+                PointerAction::Released(PointerButton::Primary)
+            };
+            pointer_inputs.send(PointerInput::new(pointer_id, location.clone(), action));
+        }
+        last_states.insert(entity, (location, cursor.pressed));
+    }
+}
@@ -7,7 +7,9 @@ use bevy_core_pipeline::core_3d::graph::{Labels3d, SubGraph3d};
 use bevy_core_pipeline::{core_2d::Camera2d, core_3d::Camera3d};
 use bevy_hierarchy::Parent;
 use bevy_render::{
-    render_phase::PhaseItem, render_resource::BindGroupEntries, view::ViewVisibility,
+    render_phase::PhaseItem,
+    render_resource::BindGroupEntries,
+    view::{InheritedVisibility, ViewVisibility},
     ExtractSchedule, Render,
 };
 use bevy_sprite::{SpriteAssetEvents, TextureAtlas};
@@ -18,12 +20,13 @@ pub use ui_material_pipeline::*;
 use crate::graph::{LabelsUi, SubGraphUi};
 use crate::{
     texture_slice::ComputedTextureSlices, BackgroundColor, BorderColor, CalculatedClip,
-    ContentSize, DefaultUiCamera, Node, Outline, Style, TargetCamera, UiImage, UiScale, Val,
+    ContentSize, DefaultUiCamera, Node, Outline, Style, TargetCamera, UiImage, UiScale, UiStack,
+    Val,
 };
 
 use bevy_app::prelude::*;
 use bevy_asset::{load_internal_asset, AssetEvent, AssetId, Assets, Handle};
-use bevy_ecs::prelude::*;
+use bevy_ecs::{prelude::*, query::QueryItem};
 use bevy_math::{Mat4, Rect, URect, UVec4, Vec2, Vec3, Vec4Swizzles};
 use bevy_render::{
     camera::Camera,
@@ -41,7 +44,7 @@ use bevy_sprite::TextureAtlasLayout;
 #[cfg(feature = "bevy_text")]
 use bevy_text::{PositionedGlyph, Text, TextLayoutInfo};
 use bevy_transform::components::GlobalTransform;
-use bevy_utils::{EntityHashMap, FloatOrd, HashMap};
+use bevy_utils::{EntityHashMap, EntityHashSet, FloatOrd, HashMap};
 use bytemuck::{Pod, Zeroable};
 use std::ops::Range;
 
@@ -147,7 +150,101 @@ pub struct ExtractedUiNode {
 
 #[derive(Resource, Default)]
 pub struct ExtractedUiNodes {
+    /// Nodes extracted for the current frame only, such as borders, outlines, text glyphs and
+    /// texture slices. They are cleared after being prepared.
     pub uinodes: EntityHashMap<Entity, ExtractedUiNode>,
+    /// Background and image nodes, kept between frames.
+    pub retained: RetainedUiNodes,
+}
+
+impl ExtractedUiNodes {
+    /// Returns the node extracted for `entity` this frame, or the retained one.
+    pub fn get(&self, entity: Entity) -> Option<&ExtractedUiNode> {
+        self.uinodes
+            .get(&entity)
+            .or_else(|| self.retained.get(entity))
+    }
+}
+
+/// UI nodes kept in the render world between frames, keyed by their main world entity.
+///
+/// [`extract_uinodes`] only updates the nodes whose components changed, and the nodes are only
+/// sorted by stack index again when a node is added, removed or moved in the [`UiStack`].
+#[derive(Default)]
+pub struct RetainedUiNodes {
+    uinodes: EntityHashMap<Entity, ExtractedUiNode>,
+    /// The retained nodes, ordered by ascending stack index.
+    sorted: Vec<Entity>,
+    /// Whether `sorted` needs to be rebuilt.
+    unsorted: bool,
+    /// The default UI camera the nodes were extracted with.
+    default_camera: Option<Entity>,
+}
+
+impl RetainedUiNodes {
+    pub fn get(&self, entity: Entity) -> Option<&ExtractedUiNode> {
+        self.uinodes.get(&entity)
+    }
+
+    pub fn len(&self) -> usize {
+        self.uinodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.uinodes.is_empty()
+    }
+
+    pub fn insert(&mut self, entity: Entity, uinode: ExtractedUiNode) {
+        let stack_index = uinode.stack_index;
+        match self.uinodes.insert(entity, uinode) {
+            Some(previous) if previous.stack_index == stack_index => {}
+            _ => self.unsorted = true,
+        }
+    }
+
+    pub fn remove(&mut self, entity: Entity) {
+        if self.uinodes.remove(&entity).is_some() {
+            self.unsorted = true;
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.uinodes.clear();
+        self.sorted.clear();
+        self.unsorted = false;
+    }
+
+    /// Iterates over the retained nodes in back-to-front order.
+    ///
+    /// Nodes inserted or moved since the last extraction are only ordered correctly once
+    /// [`extract_uinodes`] has run.
+    pub fn iter_sorted(&self) -> impl Iterator<Item = (Entity, &ExtractedUiNode)> {
+        self.sorted
+            .iter()
+            .filter_map(|entity| Some((*entity, self.uinodes.get(entity)?)))
+    }
+
+    fn update_stack_indices(&mut self, ui_stack: &[Entity]) {
+        for (stack_index, entity) in ui_stack.iter().enumerate() {
+            if let Some(uinode) = self.uinodes.get_mut(entity) {
+                if uinode.stack_index != stack_index as u32 {
+                    uinode.stack_index = stack_index as u32;
+                    self.unsorted = true;
+                }
+            }
+        }
+    }
+
+    fn sort(&mut self) {
+        if !self.unsorted {
+            return;
+        }
+        self.sorted.clear();
+        self.sorted.extend(self.uinodes.keys().copied());
+        self.sorted
+            .sort_unstable_by_key(|entity| self.uinodes[entity].stack_index);
+        self.unsorted = false;
+    }
 }
 
 pub(crate) fn resolve_border_thickness(value: Val, parent_width: f32, viewport_size: Vec2) -> f32 {
@@ -375,41 +472,127 @@ pub fn extract_uinode_outlines(
     }
 }
 
+/// The main world data [`extract_uinodes`] reads to extract a node.
+type ExtractUiNodeQueryData = (
+    Entity,
+    &'static Node,
+    &'static GlobalTransform,
+    &'static BackgroundColor,
+    Option<&'static UiImage>,
+    &'static ViewVisibility,
+    Option<&'static CalculatedClip>,
+    Option<&'static TextureAtlas>,
+    Option<&'static TargetCamera>,
+);
+
+/// Components whose changes require a retained node to be extracted again.
+type ChangedUiNodeFilter = Or<(
+    Changed<Node>,
+    Changed<GlobalTransform>,
+    Changed<BackgroundColor>,
+    Changed<UiImage>,
+    Changed<InheritedVisibility>,
+    Changed<CalculatedClip>,
+    Changed<TextureAtlas>,
+    Changed<TargetCamera>,
+)>;
+
+/// Extracts background and image nodes into [`ExtractedUiNodes::retained`].
+///
+/// Only nodes whose components changed since the last extraction are extracted again, and the
+/// retained nodes are only sorted again when the [`UiStack`] or the set of retained nodes changes.
+/// Nodes with [`ComputedTextureSlices`] are extracted every frame into [`ExtractedUiNodes::uinodes`].
+#[allow(clippy::too_many_arguments)]
 pub fn extract_uinodes(
     mut commands: Commands,
     mut extracted_uinodes: ResMut<ExtractedUiNodes>,
     texture_atlases: Extract<Res<Assets<TextureAtlasLayout>>>,
     default_ui_camera: Extract<DefaultUiCamera>,
-    uinode_query: Extract<
+    ui_stack: Extract<Res<UiStack>>,
+    uinode_query: Extract<Query<ExtractUiNodeQueryData, Without<ComputedTextureSlices>>>,
+    changed_uinode_query: Extract<
+        Query<Entity, (ChangedUiNodeFilter, Without<ComputedTextureSlices>)>,
+    >,
+    sliced_uinode_query: Extract<
         Query<(
             Entity,
             &Node,
             &GlobalTransform,
             &BackgroundColor,
-            Option<&UiImage>,
+            &UiImage,
             &ViewVisibility,
             Option<&CalculatedClip>,
-            Option<&TextureAtlas>,
             Option<&TargetCamera>,
-            Option<&ComputedTextureSlices>,
+            &ComputedTextureSlices,
         )>,
     >,
+    mut removed: Extract<(
+        RemovedComponents<Node>,
+        RemovedComponents<BackgroundColor>,
+        RemovedComponents<UiImage>,
+        RemovedComponents<CalculatedClip>,
+        RemovedComponents<TextureAtlas>,
+        RemovedComponents<TargetCamera>,
+        RemovedComponents<ComputedTextureSlices>,
+    )>,
 ) {
-    for (
-        entity,
-        uinode,
-        transform,
-        color,
-        maybe_image,
-        view_visibility,
-        clip,
-        atlas,
-        camera,
-        slices,
-    ) in uinode_query.iter()
+    let default_camera = default_ui_camera.get();
+    let ExtractedUiNodes { uinodes, retained } = &mut *extracted_uinodes;
+
+    // Changing the default camera can affect any node, so everything is extracted again
+    let mut changed: EntityHashSet<Entity> = if retained.default_camera != default_camera {
+        retained.default_camera = default_camera;
+        retained.clear();
+        uinode_query.iter().map(|(entity, ..)| entity).collect()
+    } else {
+        changed_uinode_query.iter().collect()
+    };
+
+    let (
+        removed_nodes,
+        removed_colors,
+        removed_images,
+        removed_clips,
+        removed_atlases,
+        removed_cameras,
+        removed_slices,
+    ) = &mut *removed;
+    changed.extend(removed_nodes.read());
+    changed.extend(removed_colors.read());
+    changed.extend(removed_images.read());
+    changed.extend(removed_clips.read());
+    changed.extend(removed_atlases.read());
+    changed.extend(removed_cameras.read());
+    changed.extend(removed_slices.read());
+
+    // A modified layout can move the atlas rect of any node using it
+    if texture_atlases.is_changed() {
+        changed.extend(
+            uinode_query
+                .iter()
+                .filter(|(.., atlas, _)| atlas.is_some())
+                .map(|(entity, ..)| entity),
+        );
+    }
+
+    for entity in changed {
+        let extracted_uinode = uinode_query
+            .get(entity)
+            .ok()
+            .and_then(|item| extract_uinode(item, &texture_atlases, default_camera));
+        match extracted_uinode {
+            Some(extracted_uinode) => retained.insert(entity, extracted_uinode),
+            None => retained.remove(entity),
+        }
+    }
+
+    for (entity, uinode, transform, color, image, view_visibility, clip, camera, slices) in
+        &sliced_uinode_query
     {
-        let Some(camera_entity) = camera.map(TargetCamera::entity).or(default_ui_camera.get())
-        else {
+        // Nodes which gained texture slices are no longer retained
+        retained.remove(entity);
+
+        let Some(camera_entity) = camera.map(TargetCamera::entity).or(default_camera) else {
             continue;
         };
         // Skip invisible and completely transparent nodes
@@ -417,60 +600,73 @@ pub fn extract_uinodes(
             continue;
         }
 
-        if let Some((image, slices)) = maybe_image.zip(slices) {
-            extracted_uinodes.uinodes.extend(
-                slices
-                    .extract_ui_nodes(transform, uinode, color, image, clip, camera_entity)
-                    .map(|e| (commands.spawn_empty().id(), e)),
-            );
-            continue;
-        }
+        uinodes.extend(
+            slices
+                .extract_ui_nodes(transform, uinode, color, image, clip, camera_entity)
+                .map(|e| (commands.spawn_empty().id(), e)),
+        );
+    }
 
-        let (image, flip_x, flip_y) = if let Some(image) = maybe_image {
-            (image.texture.id(), image.flip_x, image.flip_y)
-        } else {
-            (AssetId::default(), false, false)
-        };
+    if ui_stack.is_changed() {
+        retained.update_stack_indices(&ui_stack.uinodes);
+    }
+    retained.sort();
+}
 
-        let (rect, atlas_size) = match atlas {
-            Some(atlas) => {
-                let Some(layout) = texture_atlases.get(&atlas.layout) else {
-                    // Atlas not present in assets resource (should this warn the user?)
-                    continue;
-                };
-                let mut atlas_rect = layout.textures[atlas.index];
-                let mut atlas_size = layout.size;
-                let scale = uinode.size() / atlas_rect.size();
-                atlas_rect.min *= scale;
-                atlas_rect.max *= scale;
-                atlas_size *= scale;
-                (atlas_rect, Some(atlas_size))
-            }
-            None => (
-                Rect {
-                    min: Vec2::ZERO,
-                    max: uinode.calculated_size,
-                },
-                None,
-            ),
-        };
+/// Extracts a single background or image node, returning `None` if it is not rendered.
+fn extract_uinode(
+    (_, uinode, transform, color, maybe_image, view_visibility, clip, atlas, camera): QueryItem<
+        '_,
+        ExtractUiNodeQueryData,
+    >,
+    texture_atlases: &Assets<TextureAtlasLayout>,
+    default_camera: Option<Entity>,
+) -> Option<ExtractedUiNode> {
+    let camera_entity = camera.map(TargetCamera::entity).or(default_camera)?;
+    // Skip invisible and completely transparent nodes
+    if !view_visibility.get() || color.0.is_fully_transparent() {
+        return None;
+    }
 
-        extracted_uinodes.uinodes.insert(
-            entity,
-            ExtractedUiNode {
-                stack_index: uinode.stack_index,
-                transform: transform.compute_matrix(),
-                color: color.0,
-                rect,
-                clip: clip.map(|clip| clip.clip),
-                image,
-                atlas_size,
-                flip_x,
-                flip_y,
-                camera_entity,
+    let (image, flip_x, flip_y) = if let Some(image) = maybe_image {
+        (image.texture.id(), image.flip_x, image.flip_y)
+    } else {
+        (AssetId::default(), false, false)
+    };
+
+    let (rect, atlas_size) = match atlas {
+        Some(atlas) => {
+            // Atlas not present in assets resource (should this warn the user?)
+            let layout = texture_atlases.get(&atlas.layout)?;
+            let mut atlas_rect = layout.textures[atlas.index];
+            let mut atlas_size = layout.size;
+            let scale = uinode.size() / atlas_rect.size();
+            atlas_rect.min *= scale;
+            atlas_rect.max *= scale;
+            atlas_size *= scale;
+            (atlas_rect, Some(atlas_size))
+        }
+        None => (
+            Rect {
+                min: Vec2::ZERO,
+                max: uinode.calculated_size,
             },
-        );
-    }
+            None,
+        ),
+    };
+
+    Some(ExtractedUiNode {
+        stack_index: uinode.stack_index,
+        transform: transform.compute_matrix(),
+        color: color.0,
+        rect,
+        clip: clip.map(|clip| clip.clip),
+        image,
+        atlas_size,
+        flip_x,
+        flip_y,
+        camera_entity,
+    })
 }
 
 /// The UI camera is "moved back" by this many units (plus the [`UI_CAMERA_TRANSFORM_OFFSET`]) and also has a view
@@ -691,7 +887,15 @@ pub fn queue_uinodes(
     draw_functions: Res<DrawFunctions<TransparentUi>>,
 ) {
     let draw_function = draw_functions.read().id::<DrawUi>();
-    for (entity, extracted_uinode) in extracted_uinodes.uinodes.iter() {
+    // Retained nodes are queued first and already in stack order, so that the stable sort of
+    // the phase only has to merge in the nodes extracted this frame.
+    let uinodes = extracted_uinodes.retained.iter_sorted().chain(
+        extracted_uinodes
+            .uinodes
+            .iter()
+            .map(|(entity, extracted_uinode)| (*entity, extracted_uinode)),
+    );
+    for (entity, extracted_uinode) in uinodes {
         let Ok((view, mut transparent_phase)) = views.get_mut(extracted_uinode.camera_entity)
         else {
             continue;
@@ -705,7 +909,7 @@ pub fn queue_uinodes(
         transparent_phase.add(TransparentUi {
             draw_function,
             pipeline,
-            entity: *entity,
+            entity,
             sort_key: (
                 FloatOrd(extracted_uinode.stack_index as f32),
                 entity.index(),
@@ -768,7 +972,7 @@ pub fn prepare_uinodes(
 
             for item_index in 0..ui_phase.items.len() {
                 let item = &mut ui_phase.items[item_index];
-                if let Some(extracted_uinode) = extracted_uinodes.uinodes.get(&item.entity) {
+                if let Some(extracted_uinode) = extracted_uinodes.get(item.entity) {
                     let mut existing_batch = batches.last_mut();
 
                     if batch_image_handle == AssetId::invalid()
@@ -954,6 +1158,14 @@ pub fn prepare_uinodes(
                 }
             }
         }
+        // Grow the vertex buffer geometrically, so that UIs growing a few nodes at a time don't
+        // reallocate it every frame
+        let vertex_count = ui_meta.vertices.len();
+        if vertex_count > ui_meta.vertices.capacity() {
+            ui_meta
+                .vertices
+                .reserve(vertex_count.next_power_of_two(), &render_device);
+        }
         ui_meta.vertices.write_buffer(&render_device, &render_queue);
         *previous_len = batches.len();
         commands.insert_or_spawn_batch(batches);
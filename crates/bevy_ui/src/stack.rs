@@ -9,6 +9,8 @@ use crate::{Node, ZIndex};
 ///
 /// The first entry is the furthest node from the camera and is the first one to get rendered
 /// while the last entry is the first node to receive interactions.
+///
+/// Change detection is only triggered when the order of the nodes changes.
 #[derive(Debug, Resource, Default)]
 pub struct UiStack {
     /// List of UI nodes ordered from back-to-front
@@ -53,9 +55,11 @@ pub fn ui_stack_system(
     }
 
     // Flatten `StackingContext` into `UiStack`
-    ui_stack.uinodes.clear();
-    ui_stack.uinodes.reserve(total_entry_count);
-    fill_stack_recursively(&mut ui_stack.uinodes, &mut global_context);
+    let mut uinodes = Vec::with_capacity(total_entry_count);
+    fill_stack_recursively(&mut uinodes, &mut global_context);
+    if ui_stack.uinodes != uinodes {
+        ui_stack.uinodes = uinodes;
+    }
 
     for (i, entity) in ui_stack.uinodes.iter().enumerate() {
         if let Ok(mut node) = update_query.get_mut(*entity) {
@@ -235,4 +239,25 @@ mod tests {
         ];
         assert_eq!(actual_result, expected_result);
     }
+
+    #[test]
+    fn ui_stack_only_changes_when_order_changes() {
+        let mut world = World::default();
+        world.init_resource::<UiStack>();
+        let first = world.spawn(node_with_zindex("0", ZIndex::Global(1))).id();
+        world.spawn(node_without_zindex("1"));
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(ui_stack_system);
+        schedule.run(&mut world);
+
+        world.clear_trackers();
+        schedule.run(&mut world);
+        assert!(!world.is_resource_changed::<UiStack>());
+
+        world.clear_trackers();
+        world.entity_mut(first).insert(ZIndex::Global(-1));
+        schedule.run(&mut world);
+        assert!(world.is_resource_changed::<UiStack>());
+    }
 }
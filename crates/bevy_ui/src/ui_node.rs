@@ -375,6 +375,7 @@ pub struct Style {
     /// The size of the gutters between items in a vertical flexbox layout or between rows in a grid layout.
     ///
     /// Note: Values of `Val::Auto` are not valid and are treated as zero.
+    /// `Val::Percent` values are relative to the height of the container's content box.
     ///
     /// <https://developer.mozilla.org/en-US/docs/Web/CSS/row-gap>
     pub row_gap: Val,
@@ -382,6 +383,7 @@ pub struct Style {
     /// The size of the gutters between items in a horizontal flexbox layout or between column in a grid layout.
     ///
     /// Note: Values of `Val::Auto` are not valid and are treated as zero.
+    /// `Val::Percent` values are relative to the width of the container's content box.
     ///
     /// <https://developer.mozilla.org/en-US/docs/Web/CSS/column-gap>
     pub column_gap: Val,
@@ -1803,6 +1805,30 @@ impl Default for ZIndex {
     }
 }
 
+/// Controls how the layout of a root UI node and its descendants is snapped to pixels.
+///
+/// Node edges are rounded from their absolute, unrounded positions, so the rounding error of a node
+/// is carried over to its siblings and children instead of accumulating into 1px gaps or overlaps
+/// between adjacent nodes.
+///
+/// Setting this component on a non-root node will have no effect.
+/// Root nodes without this component use [`UiPixelSnapping::Logical`].
+#[derive(Component, Copy, Clone, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub enum UiPixelSnapping {
+    /// Round node positions and sizes to logical pixels.
+    ///
+    /// At fractional scale factors (including [`UiScale`](crate::UiScale)) node edges can end
+    /// up between physical pixels, which makes them and their text look blurry.
+    #[default]
+    Logical,
+    /// Round node positions and sizes to the physical pixels of the render target, taking both
+    /// the target's scale factor and [`UiScale`](crate::UiScale) into account.
+    Physical,
+    /// Use the unrounded layout.
+    None,
+}
+
 #[cfg(test)]
 mod tests {
     use crate::GridPlacement;
@@ -0,0 +1,248 @@
+use crate::{DefaultUiCamera, Interaction, Node, TargetCamera, UiScale, UiSystem};
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_ecs::reflect::ReflectComponent;
+use bevy_input::{
+    gamepad::{Gamepad, GamepadAxis, GamepadAxisType, GamepadButton, GamepadButtonType, Gamepads},
+    Axis, ButtonInput, InputSystem,
+};
+use bevy_math::Vec2;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    camera::{Camera, NormalizedRenderTarget},
+    view::ViewVisibility,
+};
+use bevy_time::{Real, Time};
+use bevy_transform::components::GlobalTransform;
+use bevy_window::{CursorMoved, PrimaryWindow, Window, WindowRef};
+
+/// Adds support for [`VirtualCursor`]s.
+///
+/// Not added by the [`UiPlugin`](crate::UiPlugin).
+#[derive(Default)]
+pub struct VirtualCursorPlugin;
+
+impl Plugin for VirtualCursorPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<VirtualCursor>().add_systems(
+            PreUpdate,
+            update_virtual_cursors
+                .after(InputSystem)
+                .before(UiSystem::Focus),
+        );
+
+        #[cfg(feature = "bevy_picking")]
+        app.add_systems(
+            PreUpdate,
+            crate::picking_backend::virtual_cursor_pick_events
+                .after(update_virtual_cursors)
+                .in_set(bevy_picking::PickSet::Input)
+                .before(bevy_picking::pointer::update_pointers),
+        );
+    }
+}
+
+/// A cursor moved with the right stick of a gamepad, for console-style UIs.
+///
+/// The cursor hovers and presses UI nodes like the mouse: it updates their [`Interaction`], and
+/// is a picking pointer when the `bevy_picking` feature is enabled. It appears the first time
+/// the stick is moved and disappears when the mouse moves, so gamepad and mouse can be used
+/// interchangeably.
+///
+/// When the stick is released near a node with an [`Interaction`], the cursor snaps to its
+/// center.
+///
+/// The cursor isn't drawn, use [`VirtualCursor::position`] to place an image at the cursor.
+/// Requires the [`VirtualCursorPlugin`].
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct VirtualCursor {
+    /// The gamepad controlling the cursor, or `None` to use any connected gamepad.
+    pub gamepad: Option<Gamepad>,
+    /// The window the cursor is on.
+    pub window: WindowRef,
+    /// The position of the cursor in the window, in logical pixels, or `None` if it is hidden.
+    pub position: Option<Vec2>,
+    /// Whether [`VirtualCursor::button`] is pressed.
+    pub pressed: bool,
+    /// The speed of the cursor when the stick is fully tilted, in logical pixels per second.
+    pub max_speed: f32,
+    /// The exponent of the acceleration curve, applied to how far the stick is tilted.
+    ///
+    /// `1.0` gives a speed proportional to the tilt, while higher values give more precision
+    /// for small tilts.
+    pub acceleration_exponent: f32,
+    /// Stick tilts below this value are ignored.
+    pub dead_zone: f32,
+    /// The button that presses the cursor, like the left mouse button.
+    pub button: GamepadButtonType,
+    /// The maximum distance, in logical pixels, of the center of a node the cursor snaps to.
+    ///
+    /// `0.0` disables snapping.
+    pub snap_distance: f32,
+}
+
+impl Default for VirtualCursor {
+    fn default() -> Self {
+        Self {
+            gamepad: None,
+            window: WindowRef::Primary,
+            position: None,
+            pressed: false,
+            max_speed: 1200.0,
+            acceleration_exponent: 2.0,
+            dead_zone: 0.15,
+            button: GamepadButtonType::South,
+            snap_distance: 48.0,
+        }
+    }
+}
+
+impl VirtualCursor {
+    /// Returns the speed of the cursor for a stick tilted by `tilt`, using the dead zone and the
+    /// acceleration curve.
+    pub fn speed(&self, tilt: f32) -> f32 {
+        if tilt <= self.dead_zone {
+            return 0.0;
+        }
+        let tilt = ((tilt - self.dead_zone) / (1.0 - self.dead_zone)).min(1.0);
+        self.max_speed * tilt.powf(self.acceleration_exponent)
+    }
+}
+
+/// How quickly a cursor approaches the node it snaps to, per second.
+const SNAP_RATE: f32 = 20.0;
+
+/// Moves the [`VirtualCursor`]s with their gamepad, and updates their pressed state.
+#[allow(clippy::too_many_arguments)]
+pub fn update_virtual_cursors(
+    mut cursors: Query<&mut VirtualCursor>,
+    gamepads: Res<Gamepads>,
+    axes: Res<Axis<GamepadAxis>>,
+    buttons: Res<ButtonInput<GamepadButton>>,
+    time: Res<Time<Real>>,
+    mut cursor_moves: EventReader<CursorMoved>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    windows: Query<&Window>,
+    cameras: Query<&Camera>,
+    default_ui_camera: DefaultUiCamera,
+    ui_scale: Res<UiScale>,
+    nodes: Query<
+        (
+            &Node,
+            &GlobalTransform,
+            &ViewVisibility,
+            Option<&TargetCamera>,
+        ),
+        With<Interaction>,
+    >,
+) {
+    let primary_window = primary_window.iter().next();
+    let mouse_moved_windows: Vec<Entity> = cursor_moves.read().map(|event| event.window).collect();
+
+    for mut cursor in &mut cursors {
+        let Some(window_entity) = cursor
+            .window
+            .normalize(primary_window)
+            .map(|window| window.entity())
+        else {
+            continue;
+        };
+        let Ok(window) = windows.get(window_entity) else {
+            continue;
+        };
+
+        // The mouse takes over
+        if mouse_moved_windows.contains(&window_entity) {
+            cursor.position = None;
+            cursor.pressed = false;
+            continue;
+        }
+
+        let Some(gamepad) = cursor
+            .gamepad
+            .or_else(|| gamepads.iter().min_by_key(|gamepad| gamepad.id))
+        else {
+            continue;
+        };
+
+        let stick = Vec2::new(
+            axes.get(GamepadAxis::new(gamepad, GamepadAxisType::RightStickX))
+                .unwrap_or_default(),
+            // The stick points up, the window's y axis points down
+            -axes
+                .get(GamepadAxis::new(gamepad, GamepadAxisType::RightStickY))
+                .unwrap_or_default(),
+        );
+        let speed = cursor.speed(stick.length());
+        let window_size = Vec2::new(window.width(), window.height());
+        let delta_seconds = time.delta_seconds();
+
+        if speed > 0.0 {
+            let position = cursor.position.unwrap_or(window_size / 2.0);
+            let position = position + stick.normalize() * speed * delta_seconds;
+            cursor.position = Some(position.clamp(Vec2::ZERO, window_size));
+        } else if let Some(position) = cursor.position {
+            if cursor.snap_distance > 0.0 {
+                let target = nodes
+                    .iter()
+                    .filter(|(.., view_visibility, _)| view_visibility.get())
+                    .filter_map(|(node, transform, _, target_camera)| {
+                        let camera = cameras
+                            .get(
+                                target_camera
+                                    .map(TargetCamera::entity)
+                                    .or(default_ui_camera.get())?,
+                            )
+                            .ok()?;
+                        let Some(NormalizedRenderTarget::Window(camera_window)) =
+                            camera.target.normalize(primary_window)
+                        else {
+                            return None;
+                        };
+                        if camera_window.entity() != window_entity {
+                            return None;
+                        }
+                        let viewport_min = camera.logical_viewport_rect()?.min;
+                        Some(node.logical_rect(transform).center() * ui_scale.0 + viewport_min)
+                    })
+                    .map(|center| (center, center.distance(position)))
+                    .filter(|(_, distance)| *distance <= cursor.snap_distance)
+                    .min_by(|(_, a), (_, b)| a.total_cmp(b));
+                if let Some((center, _)) = target {
+                    let t = 1.0 - (-SNAP_RATE * delta_seconds).exp();
+                    let snapped = position.lerp(center, t);
+                    if snapped != position {
+                        cursor.position = Some(snapped);
+                    }
+                }
+            }
+        }
+
+        let pressed = cursor.position.is_some()
+            && buttons.pressed(GamepadButton::new(gamepad, cursor.button));
+        if cursor.pressed != pressed {
+            cursor.pressed = pressed;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::VirtualCursor;
+
+    #[test]
+    fn virtual_cursor_speed() {
+        let cursor = VirtualCursor {
+            max_speed: 100.0,
+            acceleration_exponent: 2.0,
+            dead_zone: 0.2,
+            ..Default::default()
+        };
+        assert_eq!(cursor.speed(0.1), 0.0);
+        assert_eq!(cursor.speed(0.2), 0.0);
+        assert!((cursor.speed(0.6) - 25.0).abs() < 1e-4);
+        assert_eq!(cursor.speed(1.0), 100.0);
+        assert_eq!(cursor.speed(1.5), 100.0);
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{measurement::AvailableSpace, ContentSize, Measure, Node, UiImage, UiScale};
+use crate::{
+    measurement::AvailableSpace, widget::UiVectorPath, ContentSize, Measure, Node, UiImage,
+    UiImageVariants, UiScale,
+};
 use bevy_asset::Assets;
 use bevy_ecs::prelude::*;
 use bevy_math::Vec2;
@@ -61,10 +64,11 @@ impl Measure for ImageMeasure {
     }
 }
 
+// The images of vector graphics are sized after their node, not the other way around.
 #[cfg(feature = "bevy_text")]
-type UpdateImageFilter = (With<Node>, Without<bevy_text::Text>);
+type UpdateImageFilter = (With<Node>, Without<bevy_text::Text>, Without<UiVectorPath>);
 #[cfg(not(feature = "bevy_text"))]
-type UpdateImageFilter = With<Node>;
+type UpdateImageFilter = (With<Node>, Without<UiVectorPath>);
 
 /// Updates content size of the node based on the image provided
 pub fn update_image_content_size_system(
@@ -79,6 +83,7 @@ pub fn update_image_content_size_system(
             &UiImage,
             &mut UiImageSize,
             Option<&TextureAtlas>,
+            Option<&UiImageVariants>,
         ),
         UpdateImageFilter,
     >,
@@ -89,7 +94,7 @@ pub fn update_image_content_size_system(
         .unwrap_or(1.)
         * ui_scale.0;
 
-    for (mut content_size, image, mut image_size, atlas_image) in &mut query {
+    for (mut content_size, image, mut image_size, atlas_image, variants) in &mut query {
         if let Some(size) = match atlas_image {
             Some(atlas) => atlas.texture_rect(&atlases).map(|t| t.size()),
             None => textures.get(&image.texture).map(|t| t.size_f32()),
@@ -100,9 +105,11 @@ pub fn update_image_content_size_system(
                 || content_size.is_added()
             {
                 image_size.size = size;
+                // size resolution variants like their 1x image
+                let variant_scale = variants.map_or(1, UiImageVariants::selected_scale) as f32;
                 content_size.set(ImageMeasure {
                     // multiply the image size by the scale factor to get the physical size
-                    size: size * combined_scale_factor,
+                    size: size * combined_scale_factor / variant_scale,
                 });
             }
         }
@@ -12,8 +12,8 @@ use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 use bevy_render::texture::Image;
 use bevy_sprite::TextureAtlasLayout;
 use bevy_text::{
-    scale_value, BreakLineOn, Font, FontAtlasSets, Text, TextError, TextLayoutInfo,
-    TextMeasureInfo, TextPipeline, TextSettings, YAxisOrientation,
+    scale_value, BreakLineOn, Font, FontAtlases, Text, TextError, TextLayoutInfo, TextMeasureInfo,
+    TextPipeline, TextSettings, YAxisOrientation,
 };
 use bevy_window::{PrimaryWindow, Window};
 use taffy::style::AvailableSpace;
@@ -153,7 +153,7 @@ pub fn measure_text_system(
 fn queue_text(
     fonts: &Assets<Font>,
     text_pipeline: &mut TextPipeline,
-    font_atlas_sets: &mut FontAtlasSets,
+    font_atlases: &mut FontAtlases,
     texture_atlases: &mut Assets<TextureAtlasLayout>,
     textures: &mut Assets<Image>,
     text_settings: &TextSettings,
@@ -184,7 +184,7 @@ fn queue_text(
             text.justify,
             text.linebreak_behavior,
             physical_node_size,
-            font_atlas_sets,
+            font_atlases,
             texture_atlases,
             textures,
             text_settings,
@@ -219,12 +219,13 @@ fn queue_text(
 pub fn text_system(
     mut textures: ResMut<Assets<Image>>,
     mut last_scale_factor: Local<f32>,
+    mut last_atlas_generation: Local<u64>,
     fonts: Res<Assets<Font>>,
     windows: Query<&Window, With<PrimaryWindow>>,
     text_settings: Res<TextSettings>,
     ui_scale: Res<UiScale>,
     mut texture_atlases: ResMut<Assets<TextureAtlasLayout>>,
-    mut font_atlas_sets: ResMut<FontAtlasSets>,
+    mut font_atlases: ResMut<FontAtlases>,
     mut text_pipeline: ResMut<TextPipeline>,
     mut text_query: Query<(Ref<Node>, &Text, &mut TextLayoutInfo, &mut TextFlags)>,
 ) {
@@ -236,14 +237,17 @@ pub fn text_system(
 
     let scale_factor = ui_scale.0 * window_scale_factor;
     let inverse_scale_factor = scale_factor.recip();
-    if *last_scale_factor == scale_factor {
+    // Text laid out before glyphs were evicted from the atlas may reference the evicted glyphs
+    let glyphs_evicted = *last_atlas_generation != font_atlases.generation();
+    *last_atlas_generation = font_atlases.generation();
+    if *last_scale_factor == scale_factor && !glyphs_evicted {
         // Scale factor unchanged, only recompute text for modified text nodes
         for (node, text, text_layout_info, text_flags) in &mut text_query {
             if node.is_changed() || text_flags.needs_recompute {
                 queue_text(
                     &fonts,
                     &mut text_pipeline,
-                    &mut font_atlas_sets,
+                    &mut font_atlases,
                     &mut texture_atlases,
                     &mut textures,
                     &text_settings,
@@ -257,14 +261,14 @@ pub fn text_system(
             }
         }
     } else {
-        // Scale factor changed, recompute text for all text nodes
+        // Scale factor changed or glyphs were evicted, recompute text for all text nodes
         *last_scale_factor = scale_factor;
 
         for (node, text, text_layout_info, text_flags) in &mut text_query {
             queue_text(
                 &fonts,
                 &mut text_pipeline,
-                &mut font_atlas_sets,
+                &mut font_atlases,
                 &mut texture_atlases,
                 &mut textures,
                 &text_settings,
@@ -0,0 +1,127 @@
+use crate::{widget::ImageMeasure, ContentSize, Node, UiImage, UiScale};
+use bevy_asset::{AssetEvent, Assets, Handle};
+use bevy_ecs::prelude::*;
+use bevy_math::{UVec2, Vec2};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::texture::Image;
+use bevy_sprite::VectorPath;
+use bevy_utils::HashSet;
+use bevy_window::{PrimaryWindow, Window};
+
+/// Draws a [`VectorPath`] as the content of a UI node.
+///
+/// The path is rasterized into the [`UiImage`] of the node at the physical size of the node, and
+/// again whenever the node is resized or the path modified. Unless its [`Style`](crate::Style)
+/// sizes it, the node is as large as the view box of the path, in logical pixels.
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct UiVectorPath {
+    /// The vector graphic drawn by the node.
+    pub path: Handle<VectorPath>,
+    /// The size of the view box the content of the node was last measured with.
+    #[reflect(ignore)]
+    measured_size: Vec2,
+}
+
+impl UiVectorPath {
+    pub fn new(path: Handle<VectorPath>) -> Self {
+        Self {
+            path,
+            measured_size: Vec2::ZERO,
+        }
+    }
+}
+
+impl From<Handle<VectorPath>> for UiVectorPath {
+    fn from(path: Handle<VectorPath>) -> Self {
+        Self::new(path)
+    }
+}
+
+/// Updates content size of the node based on the view box of its vector graphic
+pub fn update_vector_path_content_size_system(
+    mut previous_combined_scale_factor: Local<f32>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    ui_scale: Res<UiScale>,
+    paths: Res<Assets<VectorPath>>,
+    mut query: Query<(&mut ContentSize, &mut UiVectorPath), With<Node>>,
+) {
+    let combined_scale_factor = windows
+        .get_single()
+        .map(|window| window.resolution.scale_factor())
+        .unwrap_or(1.)
+        * ui_scale.0;
+
+    for (mut content_size, mut vector_path) in &mut query {
+        let Some(path) = paths.get(&vector_path.path) else {
+            continue;
+        };
+        let size = path.view_box.size();
+        // Update only if size or scale factor has changed to avoid needless layout calculations
+        if size != vector_path.measured_size
+            || combined_scale_factor != *previous_combined_scale_factor
+            || content_size.is_added()
+        {
+            // Measuring the path doesn't change what is drawn.
+            vector_path.bypass_change_detection().measured_size = size;
+            content_size.set(ImageMeasure {
+                // multiply the view box size by the scale factor to get the physical size
+                size: size * combined_scale_factor,
+            });
+        }
+    }
+
+    *previous_combined_scale_factor = combined_scale_factor;
+}
+
+/// Rasterizes the vector graphic of each node into its [`UiImage`], at the physical size of the
+/// node.
+pub fn update_vector_path_images_system(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    ui_scale: Res<UiScale>,
+    mut events: EventReader<AssetEvent<VectorPath>>,
+    paths: Res<Assets<VectorPath>>,
+    mut images: ResMut<Assets<Image>>,
+    mut query: Query<(&Node, Ref<UiVectorPath>, &mut UiImage)>,
+) {
+    let scale_factor = windows
+        .get_single()
+        .map(|window| window.resolution.scale_factor())
+        .unwrap_or(1.);
+    let modified: HashSet<_> = events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+
+    for (node, vector_path, mut ui_image) in &mut query {
+        let Some(path) = paths.get(&vector_path.path) else {
+            continue;
+        };
+        let size = node
+            .physical_size(scale_factor, ui_scale.0)
+            .round()
+            .as_uvec2();
+        if size.cmpeq(UVec2::ZERO).any() {
+            continue;
+        }
+        let is_up_to_date = !vector_path.is_changed()
+            && !modified.contains(&vector_path.path.id())
+            && images
+                .get(&ui_image.texture)
+                .is_some_and(|image| image.size() == size);
+        if is_up_to_date {
+            continue;
+        }
+
+        let image = path.rasterize(size);
+        // Reuse the image rasterized for this node before.
+        if let Handle::Strong(_) = ui_image.texture {
+            images.insert(ui_image.texture.id(), image);
+        } else {
+            ui_image.texture = images.add(image);
+        }
+    }
+}
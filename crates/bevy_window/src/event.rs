@@ -2,15 +2,16 @@ use std::path::PathBuf;
 
 use bevy_ecs::entity::Entity;
 use bevy_ecs::event::Event;
-use bevy_math::{IVec2, Vec2};
+use bevy_math::IVec2;
 use bevy_reflect::Reflect;
-use smol_str::SmolStr;
 
 #[cfg(feature = "serialize")]
 use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
 
 use crate::WindowTheme;
 
+pub use bevy_input::{keyboard::ReceivedCharacter, mouse::CursorMoved};
+
 /// A window event that is sent whenever a window's logical size has changed.
 #[derive(Event, Debug, Clone, PartialEq, Reflect)]
 #[reflect(Debug, PartialEq)]
@@ -112,39 +113,6 @@ pub struct WindowDestroyed {
     pub window: Entity,
 }
 
-/// An event reporting that the mouse cursor has moved inside a window.
-///
-/// The event is sent only if the cursor is over one of the application's windows.
-/// It is the translated version of [`WindowEvent::CursorMoved`] from the `winit` crate with the addition of `delta`.
-///
-/// Not to be confused with the [`MouseMotion`] event from `bevy_input`.
-///
-/// Because the range of data is limited by the window area and it may have been transformed by the OS to implement certain effects like acceleration,
-/// you should not use it for non-cursor-like behaviour such as 3D camera control. Please see [`MouseMotion`] instead.
-///
-/// [`WindowEvent::CursorMoved`]: https://docs.rs/winit/latest/winit/event/enum.WindowEvent.html#variant.CursorMoved
-/// [`MouseMotion`]: bevy_input::mouse::MouseMotion
-#[derive(Event, Debug, Clone, PartialEq, Reflect)]
-#[reflect(Debug, PartialEq)]
-#[cfg_attr(
-    feature = "serialize",
-    derive(serde::Serialize, serde::Deserialize),
-    reflect(Serialize, Deserialize)
-)]
-pub struct CursorMoved {
-    /// Window that the cursor moved inside.
-    pub window: Entity,
-    /// The cursor position in logical pixels.
-    pub position: Vec2,
-    /// The change in the position of the cursor since the last event was sent.
-    /// This value is `None` if the cursor was outside the window area during the last frame.
-    //
-    // Because the range of this data is limited by the display area and it may have been
-    //  transformed by the OS to implement effects such as cursor acceleration, it should
-    // not be used to implement non-cursor-like interactions such as 3D camera control.
-    pub delta: Option<Vec2>,
-}
-
 /// An event that is sent whenever the user's cursor enters a window.
 #[derive(Event, Debug, Clone, PartialEq, Eq, Reflect)]
 #[reflect(Debug, PartialEq)]
@@ -171,21 +139,6 @@ pub struct CursorLeft {
     pub window: Entity,
 }
 
-/// An event that is sent whenever a window receives a character from the OS or underlying system.
-#[derive(Event, Debug, Clone, PartialEq, Eq, Reflect)]
-#[reflect(Debug, PartialEq)]
-#[cfg_attr(
-    feature = "serialize",
-    derive(serde::Serialize, serde::Deserialize),
-    reflect(Serialize, Deserialize)
-)]
-pub struct ReceivedCharacter {
-    /// Window that received the character.
-    pub window: Entity,
-    /// Received character.
-    pub char: SmolStr,
-}
-
 /// A Input Method Editor event.
 ///
 /// This event is the translated version of the `WindowEvent::Ime` from the `winit` crate.
@@ -382,4 +335,9 @@ pub enum ApplicationLifetime {
     Suspended,
     /// The application was resumed.
     Resumed,
+    /// The system is running low on memory.
+    ///
+    /// On mobile platforms, the application may be killed if it doesn't free memory, for example
+    /// by unloading assets that aren't needed.
+    LowMemory,
 }
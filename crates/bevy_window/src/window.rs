@@ -26,7 +26,7 @@ pub struct PrimaryWindow;
 /// Reference to a [`Window`], whether it be a direct link to a specific entity or
 /// a more vague defaulting choice.
 #[repr(C)]
-#[derive(Default, Copy, Clone, Debug, Reflect)]
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq, Reflect)]
 #[cfg_attr(
     feature = "serialize",
     derive(serde::Serialize, serde::Deserialize),
@@ -155,6 +155,11 @@ pub struct Window {
     pub name: Option<String>,
     /// How the alpha channel of textures should be handled while compositing.
     pub composite_alpha_mode: CompositeAlphaMode,
+    /// The dynamic range of the window's output.
+    ///
+    /// HDR output is only used if the window's surface supports it, and falls back to
+    /// [`HdrOutput::Sdr`] otherwise.
+    pub hdr_output: HdrOutput,
     /// The limits of the window's logical size
     /// (found in its [`resolution`](WindowResolution)) when resizing.
     pub resize_constraints: WindowResizeConstraints,
@@ -265,6 +270,7 @@ impl Default for Window {
             resolution: Default::default(),
             internal: Default::default(),
             composite_alpha_mode: Default::default(),
+            hdr_output: Default::default(),
             resize_constraints: Default::default(),
             ime_enabled: Default::default(),
             ime_position: Default::default(),
@@ -978,6 +984,33 @@ pub enum CompositeAlphaMode {
     Inherit = 4,
 }
 
+/// Specifies the dynamic range and encoding of the output of a [`Window`].
+///
+/// The brightness of HDR output can be configured per camera with
+/// `bevy_render::view::HdrOutputSettings`.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+#[reflect(Debug, PartialEq, Hash)]
+pub enum HdrOutput {
+    /// Standard dynamic range output in the sRGB color space.
+    #[default]
+    Sdr,
+    /// Linear output in the extended sRGB color space, where 1.0 is 80 nits, using a 16-bit
+    /// floating point swapchain.
+    ///
+    /// Supported on Windows and macOS.
+    ScRgb,
+    /// Output encoded with the PQ transfer function in the Rec. 2020 color space, using a
+    /// 10-bit swapchain.
+    ///
+    /// Requires a surface configured for the HDR10 color space by the platform.
+    Hdr10,
+}
+
 /// Defines the way a [`Window`] is displayed.
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
 #[cfg_attr(
@@ -13,7 +13,7 @@ use bevy_a11y::{
     AccessibilityNode, AccessibilityRequested, AccessibilitySystem, Focus,
 };
 use bevy_a11y::{ActionRequest as ActionRequestWrapper, ManageAccessibilityUpdates};
-use bevy_app::{App, Plugin, PostUpdate};
+use bevy_app::{App, Plugin, PostUpdate, PreUpdate};
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::{
     prelude::{DetectChanges, Entity, EventReader, EventWriter},
@@ -185,14 +185,15 @@ impl Plugin for AccessKitPlugin {
         app.init_non_send_resource::<AccessKitAdapters>()
             .init_resource::<WinitActionHandlers>()
             .add_event::<ActionRequestWrapper>()
+            .add_systems(
+                PreUpdate,
+                poll_receivers.before(AccessibilitySystem::HandleRequests),
+            )
             .add_systems(
                 PostUpdate,
                 (
-                    poll_receivers,
                     update_accessibility_nodes.run_if(should_update_accessibility_nodes),
-                    window_closed
-                        .before(poll_receivers)
-                        .before(update_accessibility_nodes),
+                    window_closed.before(update_accessibility_nodes),
                 )
                     .in_set(AccessibilitySystem::Update),
             );
@@ -7,6 +7,7 @@
 
 pub mod accessibility;
 mod converters;
+mod lifecycle;
 mod system;
 mod winit_config;
 mod winit_windows;
@@ -25,21 +26,21 @@ use bevy_ecs::prelude::*;
 use bevy_ecs::system::SystemState;
 use bevy_input::{
     mouse::{MouseButtonInput, MouseMotion, MouseScrollUnit, MouseWheel},
+    timestamp::send_input_event,
     touchpad::{TouchpadMagnify, TouchpadRotate},
 };
 use bevy_math::{ivec2, DVec2, Vec2};
 #[cfg(not(target_arch = "wasm32"))]
 use bevy_tasks::tick_global_task_pools_on_main_thread;
 use bevy_utils::tracing::{error, trace, warn};
+#[cfg(target_os = "android")]
+use bevy_window::RawHandleWrapper;
 use bevy_window::{
-    exit_on_all_closed, ApplicationLifetime, CursorEntered, CursorLeft, CursorMoved,
-    FileDragAndDrop, Ime, ReceivedCharacter, RequestRedraw, Window,
-    WindowBackendScaleFactorChanged, WindowCloseRequested, WindowCreated, WindowDestroyed,
-    WindowFocused, WindowMoved, WindowOccluded, WindowResized, WindowScaleFactorChanged,
-    WindowThemeChanged,
+    exit_on_all_closed, CursorEntered, CursorLeft, CursorMoved, FileDragAndDrop, Ime,
+    ReceivedCharacter, RequestRedraw, Window, WindowBackendScaleFactorChanged,
+    WindowCloseRequested, WindowCreated, WindowDestroyed, WindowFocused, WindowMoved,
+    WindowOccluded, WindowResized, WindowScaleFactorChanged, WindowThemeChanged,
 };
-#[cfg(target_os = "android")]
-use bevy_window::{PrimaryWindow, RawHandleWrapper};
 
 #[cfg(target_os = "android")]
 pub use winit::platform::android::activity as android_activity;
@@ -117,6 +118,7 @@ impl Plugin for WinitPlugin {
 
         app.init_non_send_resource::<WinitWindows>()
             .init_resource::<WinitSettings>()
+            .init_resource::<SuspendSettings>()
             .set_runner(winit_runner)
             .add_systems(
                 Last,
@@ -185,6 +187,8 @@ struct WinitAppRunnerState {
     scheduled_update: Option<Instant>,
     /// Number of "forced" updates to trigger on application start
     startup_forced_updates: u32,
+    /// Is `true` if [`Time<Virtual>`](bevy_time::Virtual) was paused when the app was suspended.
+    paused_time: bool,
 }
 
 impl WinitAppRunnerState {
@@ -226,6 +230,7 @@ impl Default for WinitAppRunnerState {
             scheduled_update: None,
             // 3 seems to be enough, 5 is a safe margin
             startup_forced_updates: 5,
+            paused_time: false,
         }
     }
 }
@@ -437,10 +442,13 @@ fn handle_winit_event(
                     if event.state.is_pressed() {
                         if let Some(char) = &event.text {
                             let char = char.clone();
-                            app.send_event(ReceivedCharacter { window, char });
+                            send_input_event(&mut app.world, ReceivedCharacter { window, char });
                         }
                     }
-                    app.send_event(converters::convert_keyboard_input(event, window));
+                    send_input_event(
+                        &mut app.world,
+                        converters::convert_keyboard_input(event, window),
+                    );
                 }
                 WindowEvent::CursorMoved { position, .. } => {
                     let physical_position = DVec2::new(position.x, position.y);
@@ -453,11 +461,14 @@ fn handle_winit_event(
                     win.set_physical_cursor_position(Some(physical_position));
                     let position =
                         (physical_position / win.resolution.scale_factor() as f64).as_vec2();
-                    app.send_event(CursorMoved {
-                        window,
-                        position,
-                        delta,
-                    });
+                    send_input_event(
+                        &mut app.world,
+                        CursorMoved {
+                            window,
+                            position,
+                            delta,
+                        },
+                    );
                 }
                 WindowEvent::CursorEntered { .. } => {
                     app.send_event(CursorEntered { window });
@@ -467,41 +478,53 @@ fn handle_winit_event(
                     app.send_event(CursorLeft { window });
                 }
                 WindowEvent::MouseInput { state, button, .. } => {
-                    app.send_event(MouseButtonInput {
-                        button: converters::convert_mouse_button(button),
-                        state: converters::convert_element_state(state),
-                        window,
-                    });
+                    send_input_event(
+                        &mut app.world,
+                        MouseButtonInput {
+                            button: converters::convert_mouse_button(button),
+                            state: converters::convert_element_state(state),
+                            window,
+                        },
+                    );
                 }
                 WindowEvent::TouchpadMagnify { delta, .. } => {
-                    app.send_event(TouchpadMagnify(delta as f32));
+                    send_input_event(&mut app.world, TouchpadMagnify(delta as f32));
                 }
                 WindowEvent::TouchpadRotate { delta, .. } => {
-                    app.send_event(TouchpadRotate(delta));
+                    send_input_event(&mut app.world, TouchpadRotate(delta));
                 }
                 WindowEvent::MouseWheel { delta, .. } => match delta {
                     event::MouseScrollDelta::LineDelta(x, y) => {
-                        app.send_event(MouseWheel {
-                            unit: MouseScrollUnit::Line,
-                            x,
-                            y,
-                            window,
-                        });
+                        send_input_event(
+                            &mut app.world,
+                            MouseWheel {
+                                unit: MouseScrollUnit::Line,
+                                x,
+                                y,
+                                window,
+                            },
+                        );
                     }
                     event::MouseScrollDelta::PixelDelta(p) => {
-                        app.send_event(MouseWheel {
-                            unit: MouseScrollUnit::Pixel,
-                            x: p.x as f32,
-                            y: p.y as f32,
-                            window,
-                        });
+                        send_input_event(
+                            &mut app.world,
+                            MouseWheel {
+                                unit: MouseScrollUnit::Pixel,
+                                x: p.x as f32,
+                                y: p.y as f32,
+                                window,
+                            },
+                        );
                     }
                 },
                 WindowEvent::Touch(touch) => {
                     let location = touch
                         .location
                         .to_logical(win.resolution.scale_factor() as f64);
-                    app.send_event(converters::convert_touch_input(touch, location, window));
+                    send_input_event(
+                        &mut app.world,
+                        converters::convert_touch_input(touch, location, window),
+                    );
                 }
                 WindowEvent::ScaleFactorChanged {
                     scale_factor,
@@ -628,15 +651,18 @@ fn handle_winit_event(
             runner_state.device_event_received = true;
             if let DeviceEvent::MouseMotion { delta: (x, y) } = event {
                 let delta = Vec2::new(x as f32, y as f32);
-                app.send_event(MouseMotion { delta });
+                send_input_event(&mut app.world, MouseMotion { delta });
             }
         }
         Event::Suspended => {
-            app.send_event(ApplicationLifetime::Suspended);
+            runner_state.paused_time |= lifecycle::suspend(&mut app.world);
             // Mark the state as `WillSuspend`. This will let the schedule run one last time
             // before actually suspending to let the application react
             runner_state.active = ActiveState::WillSuspend;
         }
+        Event::MemoryWarning => {
+            lifecycle::warn_low_memory(&mut app.world);
+        }
         Event::Resumed => {
             #[cfg(any(target_os = "android", target_os = "ios", target_os = "macos"))]
             {
@@ -646,10 +672,11 @@ fn handle_winit_event(
                 }
             }
 
-            match runner_state.active {
-                ActiveState::NotYetStarted => app.send_event(ApplicationLifetime::Started),
-                _ => app.send_event(ApplicationLifetime::Resumed),
-            }
+            lifecycle::resume(
+                &mut app.world,
+                runner_state.active != ActiveState::NotYetStarted,
+                std::mem::take(&mut runner_state.paused_time),
+            );
             runner_state.active = ActiveState::Active;
             runner_state.redraw_requested = true;
             #[cfg(target_os = "android")]
@@ -659,9 +686,12 @@ fn handle_winit_event(
                 let mut query = app
                         .world
                         .query_filtered::<(Entity, &Window), (With<CachedWindow>, Without<bevy_window::RawHandleWrapper>)>();
-                if let Ok((entity, window)) = query.get_single(&app.world) {
+                let windows: Vec<_> = query
+                    .iter(&app.world)
+                    .map(|(entity, window)| (entity, window.clone()))
+                    .collect();
+                for (entity, window) in windows {
                     use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
-                    let window = window.clone();
 
                     let (
                         ..,
@@ -712,11 +742,13 @@ fn run_app_update_if_should(
         runner_state.active = ActiveState::Suspended;
         #[cfg(target_os = "android")]
         {
-            // Remove the `RawHandleWrapper` from the primary window.
-            // This will trigger the surface destruction.
-            let mut query = app.world.query_filtered::<Entity, With<PrimaryWindow>>();
-            let entity = query.single(&app.world);
-            app.world.entity_mut(entity).remove::<RawHandleWrapper>();
+            // Remove the `RawHandleWrapper` from the windows.
+            // This will trigger the destruction of their surfaces, which are recreated on resume.
+            let mut query = app.world.query_filtered::<Entity, With<RawHandleWrapper>>();
+            let entities: Vec<_> = query.iter(&app.world).collect();
+            for entity in entities {
+                app.world.entity_mut(entity).remove::<RawHandleWrapper>();
+            }
             event_loop.set_control_flow(ControlFlow::Wait);
         }
     }
@@ -0,0 +1,116 @@
+//! Handling of the application being suspended and resumed, for example when a mobile application
+//! is sent to the background.
+
+use bevy_ecs::world::World;
+use bevy_time::{Time, Virtual};
+use bevy_window::ApplicationLifetime;
+
+use crate::SuspendSettings;
+
+/// Sends [`ApplicationLifetime::Suspended`], and pauses [`Time<Virtual>`] if the
+/// [`SuspendSettings`] ask for it.
+///
+/// Returns whether the time was paused, so that [`resume`] unpauses it.
+pub(crate) fn suspend(world: &mut World) -> bool {
+    world.send_event(ApplicationLifetime::Suspended);
+
+    let pause_time = world
+        .get_resource::<SuspendSettings>()
+        .map_or(true, |settings| settings.pause_time);
+    let Some(mut time) = world.get_resource_mut::<Time<Virtual>>() else {
+        return false;
+    };
+    if !pause_time || time.is_paused() {
+        return false;
+    }
+    time.pause();
+    true
+}
+
+/// Sends [`ApplicationLifetime::Started`] the first time the application is resumed and
+/// [`ApplicationLifetime::Resumed`] afterwards, and unpauses [`Time<Virtual>`] if `paused_time`.
+pub(crate) fn resume(world: &mut World, started: bool, paused_time: bool) {
+    if paused_time {
+        if let Some(mut time) = world.get_resource_mut::<Time<Virtual>>() {
+            time.unpause();
+        }
+    }
+
+    world.send_event(if started {
+        ApplicationLifetime::Resumed
+    } else {
+        ApplicationLifetime::Started
+    });
+}
+
+/// Sends [`ApplicationLifetime::LowMemory`], when the system warns that it's running low on
+/// memory.
+pub(crate) fn warn_low_memory(world: &mut World) {
+    world.send_event(ApplicationLifetime::LowMemory);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::event::Events;
+
+    fn world() -> World {
+        let mut world = World::new();
+        world.init_resource::<Events<ApplicationLifetime>>();
+        world.init_resource::<Time<Virtual>>();
+        world
+    }
+
+    fn lifetime_events(world: &World) -> Vec<ApplicationLifetime> {
+        let events = world.resource::<Events<ApplicationLifetime>>();
+        events.get_reader().read(events).copied().collect()
+    }
+
+    #[test]
+    fn time_is_paused_while_suspended() {
+        let mut world = world();
+        resume(&mut world, false, false);
+
+        let paused_time = suspend(&mut world);
+        assert!(paused_time);
+        assert!(world.resource::<Time<Virtual>>().is_paused());
+
+        resume(&mut world, true, paused_time);
+        assert!(!world.resource::<Time<Virtual>>().is_paused());
+        assert_eq!(
+            lifetime_events(&world),
+            [
+                ApplicationLifetime::Started,
+                ApplicationLifetime::Suspended,
+                ApplicationLifetime::Resumed,
+            ]
+        );
+    }
+
+    #[test]
+    fn paused_time_stays_paused() {
+        let mut world = world();
+        world.resource_mut::<Time<Virtual>>().pause();
+
+        let paused_time = suspend(&mut world);
+        assert!(!paused_time);
+        resume(&mut world, true, paused_time);
+        assert!(world.resource::<Time<Virtual>>().is_paused());
+    }
+
+    #[test]
+    fn time_keeps_running_if_disabled() {
+        let mut world = world();
+        world.insert_resource(SuspendSettings { pause_time: false });
+
+        assert!(!suspend(&mut world));
+        assert!(!world.resource::<Time<Virtual>>().is_paused());
+    }
+
+    #[test]
+    fn low_memory_warning() {
+        let mut world = world();
+        warn_low_memory(&mut world);
+        assert_eq!(lifetime_events(&world), [ApplicationLifetime::LowMemory]);
+    }
+}
@@ -111,7 +111,7 @@ pub struct CachedWindow {
 ///
 /// # Notes
 ///
-/// - [`Window::present_mode`] and [`Window::composite_alpha_mode`] changes are handled by the `bevy_render` crate.
+/// - [`Window::present_mode`], [`Window::composite_alpha_mode`] and [`Window::hdr_output`] changes are handled by the `bevy_render` crate.
 /// - [`Window::transparent`] cannot be changed after the window is created.
 /// - [`Window::canvas`] cannot be changed after the window is created.
 /// - [`Window::focused`] cannot be manually changed to `false` after the window is created.
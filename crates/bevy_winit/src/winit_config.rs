@@ -97,3 +97,20 @@ pub enum UpdateMode {
         wait: Duration,
     },
 }
+
+/// Settings for when the application is suspended, for example when a mobile application is sent
+/// to the background.
+#[derive(Debug, Clone, Resource)]
+pub struct SuspendSettings {
+    /// Whether [`Time<Virtual>`](bevy_time::Virtual) is paused while the application is
+    /// suspended.
+    ///
+    /// Time that was already paused isn't unpaused when the application is resumed.
+    pub pause_time: bool,
+}
+
+impl Default for SuspendSettings {
+    fn default() -> Self {
+        Self { pause_time: true }
+    }
+}
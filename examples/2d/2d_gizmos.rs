@@ -90,6 +90,12 @@ fn draw_example_collection(
     let sin = time.elapsed_seconds().sin() * 50.;
     gizmos.line_2d(Vec2::Y * -sin, Vec2::splat(-80.), Color::RED);
     gizmos.ray_2d(Vec2::Y * sin, Vec2::splat(80.), Color::GREEN);
+    gizmos.text_2d(
+        Vec2::new(0., -sin - 16.),
+        format!("{:.0}", -sin),
+        16.,
+        Color::RED,
+    );
 
     // Triangle
     gizmos.linestrip_gradient_2d([
@@ -123,11 +129,48 @@ fn draw_example_collection(
     // 1 and 32, using the arc length as scalar.
     my_gizmos.arc_2d(Vec2::ZERO, sin / 10., PI / 2., 350., Color::ORANGE_RED);
 
+    // Filled shapes are drawn below the lines, translucent colors keep overlaps readable.
+    gizmos.sector_2d(
+        Vec2::ZERO,
+        sin / 10. + PI,
+        PI / 2.,
+        350.,
+        Color::ORANGE_RED.with_a(0.2),
+    );
+
+    // A projectile trajectory, colored by speed from blue (slow) to red (fast).
+    let velocity = Vec2::new(120., 260.);
+    let gravity = Vec2::new(0., -200.);
+    let times = (0..16).map(|i| i as f32 / 10.);
+    let trajectory =
+        Polyline2d::<16>::new(times.clone().map(|t| velocity * t + gravity * t * t / 2.));
+    let colors = times.map(|t| {
+        let speed = (velocity + gravity * t).length();
+        Color::hsl((300. - speed).clamp(0., 200.) * 1.2, 1., 0.5)
+    });
+    gizmos.primitive_2d_gradient(trajectory, Vec2::new(-550., -300.), 0., colors);
+
     gizmos.arrow_2d(
         Vec2::ZERO,
         Vec2::from_angle(sin / -10. + PI / 2.) * 50.,
         Color::YELLOW,
     );
+
+    // The silhouettes of tumbling 3D primitives, as seen down the Z axis.
+    let t = time.elapsed_seconds();
+    let rotation = Quat::from_euler(EulerRot::XYZ, t / 2., t / 3., 0.);
+    gizmos.primitive_projection(
+        Cuboid::new(80., 80., 80.),
+        Vec2::new(-450., 200.),
+        rotation,
+        Color::TEAL,
+    );
+    gizmos.primitive_projection(
+        Torus::new(25., 60.),
+        Vec2::new(450., 200.),
+        rotation,
+        Color::TEAL,
+    );
 }
 
 fn draw_primitives(
@@ -0,0 +1,115 @@
+//! Shows how to draw vector graphics, built in code or loaded from SVG files,
+//! as 2D meshes and as the content of UI nodes.
+
+use bevy::{prelude::*, sprite::MaterialMesh2dBundle};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_systems(Startup, setup)
+        .add_systems(Update, rotate)
+        .run();
+}
+
+#[derive(Component)]
+struct Rotate;
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    commands.spawn(Camera2dBundle::default());
+
+    // A vector graphic built in code: a leaf with an outline and a vein
+    let mut leaf = PathOutline::new();
+    leaf.move_to(Vec2::new(0.0, -100.0))
+        .cubic_to(
+            Vec2::new(90.0, -60.0),
+            Vec2::new(80.0, 60.0),
+            Vec2::new(0.0, 110.0),
+        )
+        .cubic_to(
+            Vec2::new(-80.0, 60.0),
+            Vec2::new(-90.0, -60.0),
+            Vec2::new(0.0, -100.0),
+        )
+        .close();
+    let mut vein = PathOutline::new();
+    vein.move_to(Vec2::new(0.0, -130.0))
+        .quadratic_to(Vec2::new(10.0, 0.0), Vec2::new(0.0, 90.0));
+    let path = VectorPath::new(Rect::new(-100.0, -130.0, 100.0, 110.0))
+        .with_shape(VectorShape {
+            outline: leaf,
+            fill: Some(VectorFill::color(Color::rgb(0.3, 0.65, 0.25))),
+            stroke: Some(VectorStroke::new(Color::rgb(0.1, 0.3, 0.1), 6.0)),
+        })
+        .with_shape(VectorShape {
+            outline: vein,
+            fill: None,
+            stroke: Some(VectorStroke::new(Color::rgb(0.1, 0.3, 0.1), 4.0)),
+        });
+
+    // Vector graphics are tessellated into meshes with vertex colors,
+    // drawn with a white `ColorMaterial` to keep their colors.
+    let white = materials.add(Color::WHITE);
+    commands.spawn((
+        MaterialMesh2dBundle {
+            mesh: meshes.add(path.tessellate(0.1)).into(),
+            material: white.clone(),
+            transform: Transform::from_xyz(-180.0, 0.0, 0.0),
+            ..default()
+        },
+        Rotate,
+    ));
+
+    // SVG files are tessellated when loaded, into their `mesh` labeled asset
+    commands.spawn((
+        MaterialMesh2dBundle {
+            mesh: asset_server.load("vector/badge.svg#mesh").into(),
+            material: white,
+            transform: Transform::from_xyz(180.0, 0.0, 0.0).with_scale(Vec3::splat(1.5)),
+            ..default()
+        },
+        Rotate,
+    ));
+
+    // UI nodes rasterize their vector graphic at their size, with anti-aliasing
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(12.0),
+                left: Val::Px(12.0),
+                column_gap: Val::Px(12.0),
+                align_items: AlignItems::End,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            // Sized after the view box of the SVG file
+            parent.spawn(VectorPathBundle {
+                vector_path: asset_server.load("vector/badge.svg").into(),
+                ..default()
+            });
+            for size in [32.0, 64.0] {
+                parent.spawn(VectorPathBundle {
+                    style: Style {
+                        width: Val::Px(size),
+                        height: Val::Px(size),
+                        ..default()
+                    },
+                    vector_path: asset_server.load("vector/badge.svg").into(),
+                    ..default()
+                });
+            }
+        });
+}
+
+fn rotate(time: Res<Time>, mut query: Query<&mut Transform, With<Rotate>>) {
+    for mut transform in &mut query {
+        transform.rotate_z(time.delta_seconds() * 0.5);
+    }
+}
@@ -84,6 +84,7 @@ fn setup(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
+    mut gizmo_assets: ResMut<Assets<GizmoAsset>>,
 ) {
     commands.spawn(Camera3dBundle {
         transform: Transform::from_xyz(0., 1.5, 6.).looking_at(Vec3::ZERO, Vec3::Y),
@@ -102,6 +103,27 @@ fn setup(
         transform: Transform::from_xyz(0.0, 0.5, 0.0),
         ..default()
     });
+    // retained gizmo outlining the plane, built once instead of every frame
+    let mut outline = GizmoAsset::new();
+    outline.linestrip(
+        [
+            Vec3::new(-2.5, 0.0, -2.5),
+            Vec3::new(2.5, 0.0, -2.5),
+            Vec3::new(2.5, 0.0, 2.5),
+            Vec3::new(-2.5, 0.0, 2.5),
+            Vec3::new(-2.5, 0.0, -2.5),
+        ],
+        Color::YELLOW,
+    );
+    commands.spawn(GizmoBundle {
+        gizmo: Gizmo {
+            handle: gizmo_assets.add(outline),
+            line_width: 3.0,
+            ..default()
+        },
+        transform: Transform::from_xyz(0.0, 0.01, 0.0),
+        ..default()
+    });
     // light
     commands.spawn(PointLightBundle {
         point_light: PointLight {
@@ -118,6 +140,8 @@ fn setup(
         TextBundle::from_section(
             "Press 'D' to toggle drawing gizmos on top of everything else in the scene\n\
             Press 'P' to toggle perspective for line gizmos\n\
+            Press 'S' to cycle through solid, dashed and dotted straight gizmos\n\
+            Press 'O' to cycle through depth tested, always on top and x-ray straight gizmos\n\
             Hold 'Left' or 'Right' to change the line width of straight gizmos\n\
             Hold 'Up' or 'Down' to change the line width of round gizmos\n\
             Press '1' or '2' to toggle the visibility of straight gizmos or round gizmos\n\
@@ -161,6 +185,8 @@ fn draw_example_collection(
     );
 
     my_gizmos.sphere(Vec3::new(1., 0.5, 0.), Quat::IDENTITY, 0.5, Color::RED);
+    // Labels face the camera
+    my_gizmos.text_3d(Vec3::new(1., 1.25, 0.), "sphere", 0.2, Color::RED);
 
     for y in [0., 0.5, 1.] {
         gizmos.ray(
@@ -363,6 +389,30 @@ fn update_config(
     if keyboard.just_pressed(KeyCode::Digit1) {
         config.enabled ^= true;
     }
+    if keyboard.just_pressed(KeyCode::KeyS) {
+        config.line_style = match config.line_style {
+            GizmoLineStyle::Solid => GizmoLineStyle::Dashed {
+                dash_length: 12.,
+                gap_length: 6.,
+            },
+            GizmoLineStyle::Dashed { .. } => GizmoLineStyle::Dotted { gap_length: 4. },
+            GizmoLineStyle::Dotted { .. } => GizmoLineStyle::Solid,
+        };
+    }
+    if keyboard.just_pressed(KeyCode::KeyO) {
+        config.occlusion = match config.occlusion {
+            GizmoOcclusion::DepthTested => GizmoOcclusion::AlwaysOnTop,
+            // Hidden lines are drawn faded and dashed behind the geometry covering them
+            GizmoOcclusion::AlwaysOnTop => GizmoOcclusion::XRay {
+                occluded_alpha: 0.3,
+                occluded_style: GizmoLineStyle::Dashed {
+                    dash_length: 8.,
+                    gap_length: 8.,
+                },
+            },
+            GizmoOcclusion::XRay { .. } => GizmoOcclusion::DepthTested,
+        };
+    }
 
     let (my_config, _) = config_store.config_mut::<MyRoundGizmos>();
     if keyboard.pressed(KeyCode::ArrowUp) {
@@ -1,5 +1,6 @@
 //! This interactive example shows how to use distance fog,
 //! and allows playing around with different fog settings.
+//! The pillars also enclose a fog volume of local haze.
 //!
 //! ## Controls
 //!
@@ -65,6 +66,17 @@ fn setup_pyramid_scene(
         });
     }
 
+    // haze between the pillars
+    commands.spawn(FogVolumeBundle {
+        fog_volume: FogVolume {
+            shape: FogVolumeShape::Box,
+            color: Color::rgb(0.35, 0.5, 0.35),
+            density: 0.6,
+        },
+        transform: Transform::from_xyz(0.0, 1.5, 0.0).with_scale(Vec3::new(4.0, 3.0, 4.0)),
+        ..default()
+    });
+
     // orb
     commands.spawn((
         PbrBundle {
@@ -1,11 +1,12 @@
-//! Load a cubemap texture onto a cube like a skybox and cycle through different compressed texture formats
+//! Load a cubemap texture onto a cube like a skybox and cycle through different compressed texture formats,
+//! with a procedural cloud layer drifting over the slowly turning sky.
 
 #[path = "../helpers/camera_controller.rs"]
 mod camera_controller;
 
 use bevy::{
     asset::LoadState,
-    core_pipeline::Skybox,
+    core_pipeline::{SkyCloudLayer, Skybox},
     prelude::*,
     render::{
         render_resource::{TextureViewDescriptor, TextureViewDimension},
@@ -46,6 +47,7 @@ fn main() {
                 cycle_cubemap_asset,
                 asset_loaded.after(cycle_cubemap_asset),
                 animate_light_direction,
+                animate_sky,
             ),
         )
         .run();
@@ -81,6 +83,13 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
         Skybox {
             image: skybox_handle.clone(),
             brightness: 150.0,
+            clouds: Some(SkyCloudLayer {
+                color: Color::rgba(0.9, 0.9, 0.95, 0.8),
+                intensity: 150.0,
+                coverage: 0.4,
+                ..default()
+            }),
+            ..default()
         },
     ));
 
@@ -175,3 +184,10 @@ fn animate_light_direction(
         transform.rotate_y(time.delta_seconds() * 0.5);
     }
 }
+
+fn animate_sky(time: Res<Time>, mut skyboxes: Query<&mut Skybox>) {
+    for mut skybox in &mut skyboxes {
+        // Turn the sky slowly, leaving the clouds to the wind.
+        skybox.rotation = Quat::from_rotation_y(time.elapsed_seconds() * 0.02);
+    }
+}
@@ -1,16 +1,25 @@
 //! Renders two cameras to the same window to accomplish "split screen".
+//!
+//! The right camera uses its own shadow cascades.
+//! Press C to tint the scene by shadow cascade.
 
 use std::f32::consts::PI;
 
 use bevy::{
-    pbr::CascadeShadowConfigBuilder, prelude::*, render::camera::Viewport, window::WindowResized,
+    pbr::{CascadeDebugVisualization, CascadeShadowConfigBuilder},
+    prelude::*,
+    render::camera::Viewport,
+    window::WindowResized,
 };
 
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
         .add_systems(Startup, setup)
-        .add_systems(Update, (set_camera_viewports, button_system))
+        .add_systems(
+            Update,
+            (set_camera_viewports, button_system, toggle_cascade_debug),
+        )
         .run();
 }
 
@@ -76,6 +85,14 @@ fn setup(
                 },
                 ..default()
             },
+            // Overrides the cascades of the light for this camera only
+            CascadeShadowConfigBuilder {
+                num_cascades: 3,
+                first_cascade_far_bound: 100.0,
+                maximum_distance: 300.0,
+                ..default()
+            }
+            .build(),
             RightCamera,
         ))
         .id();
@@ -248,3 +265,12 @@ fn button_system(
         }
     }
 }
+
+fn toggle_cascade_debug(
+    input: Res<ButtonInput<KeyCode>>,
+    mut cascade_debug_visualization: ResMut<CascadeDebugVisualization>,
+) {
+    if input.just_pressed(KeyCode::KeyC) {
+        cascade_debug_visualization.enabled = !cascade_debug_visualization.enabled;
+    }
+}
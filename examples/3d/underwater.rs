@@ -0,0 +1,158 @@
+//! This example shows how to render a camera diving under water,
+//! with the fog and distortion of the water and caustics on the bottom of a pool.
+//!
+//! ## Controls
+//!
+//! | Key Binding        | Action                              |
+//! |:-------------------|:------------------------------------|
+//! | `Up` / `Down`      | Move the camera up or down          |
+//! | `C`                | Toggle the caustics                 |
+
+use bevy::prelude::*;
+
+const WATER_SURFACE_HEIGHT: f32 = 0.0;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_systems(Startup, setup)
+        .add_systems(
+            Update,
+            (move_camera, update_medium, toggle_caustics).chain(),
+        )
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(-6.0, 1.0, 6.0)
+                .looking_at(Vec3::new(0.0, -2.0, 0.0), Vec3::Y),
+            ..default()
+        },
+        FogSettings {
+            color: Color::rgb(0.6, 0.7, 0.8),
+            falloff: FogFalloff::Linear {
+                start: 20.0,
+                end: 60.0,
+            },
+            ..default()
+        },
+        WaterSettings {
+            surface_height: WATER_SURFACE_HEIGHT,
+            ..default()
+        },
+        CameraMedium::Air,
+    ));
+
+    commands.spawn(DirectionalLightBundle {
+        directional_light: DirectionalLight {
+            illuminance: 10_000.0,
+            shadows_enabled: true,
+            ..default()
+        },
+        transform: Transform::from_xyz(1.0, 3.0, 2.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..default()
+    });
+
+    // The bottom of the pool and a few rocks, lit by the caustics
+    let sand = materials.add(StandardMaterial {
+        base_color: Color::rgb(0.76, 0.7, 0.5),
+        perceptual_roughness: 1.0,
+        ..default()
+    });
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Plane3d::default().mesh().size(40.0, 40.0)),
+        material: sand,
+        transform: Transform::from_xyz(0.0, -4.0, 0.0),
+        ..default()
+    });
+    let rock = materials.add(StandardMaterial {
+        base_color: Color::rgb(0.4, 0.4, 0.38),
+        perceptual_roughness: 0.9,
+        ..default()
+    });
+    for (x, z, size) in [(-2.0, -1.0, 1.5), (1.5, -2.5, 1.0), (2.5, 1.5, 2.0)] {
+        commands.spawn(PbrBundle {
+            mesh: meshes.add(Cuboid::new(size, size, size)),
+            material: rock.clone(),
+            transform: Transform::from_xyz(x, -4.0 + size * 0.5, z)
+                .with_rotation(Quat::from_rotation_y(x)),
+            ..default()
+        });
+    }
+
+    // The water surface, visible from both sides
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Plane3d::default().mesh().size(40.0, 40.0)),
+        material: materials.add(StandardMaterial {
+            base_color: Color::rgba(0.1, 0.35, 0.45, 0.4),
+            alpha_mode: AlphaMode::Blend,
+            perceptual_roughness: 0.1,
+            cull_mode: None,
+            ..default()
+        }),
+        transform: Transform::from_xyz(0.0, WATER_SURFACE_HEIGHT, 0.0),
+        ..default()
+    });
+
+    commands.spawn(
+        TextBundle::from_section(
+            "Press Up or Down to move the camera\nPress C to toggle the caustics",
+            TextStyle {
+                font_size: 20.0,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(12.0),
+            left: Val::Px(12.0),
+            ..default()
+        }),
+    );
+}
+
+fn move_camera(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+    mut cameras: Query<&mut Transform, With<Camera>>,
+) {
+    let mut direction = 0.0;
+    if keyboard.pressed(KeyCode::ArrowUp) {
+        direction += 1.0;
+    }
+    if keyboard.pressed(KeyCode::ArrowDown) {
+        direction -= 1.0;
+    }
+    for mut transform in &mut cameras {
+        transform.translation.y =
+            (transform.translation.y + direction * 2.0 * time.delta_seconds()).clamp(-3.5, 4.0);
+    }
+}
+
+fn update_medium(mut cameras: Query<(&Transform, &WaterSettings, &mut CameraMedium)>) {
+    for (transform, water, mut medium) in &mut cameras {
+        let new_medium = water.medium_at(transform.translation);
+        if *medium != new_medium {
+            *medium = new_medium;
+        }
+    }
+}
+
+fn toggle_caustics(keyboard: Res<ButtonInput<KeyCode>>, mut waters: Query<&mut WaterSettings>) {
+    if !keyboard.just_pressed(KeyCode::KeyC) {
+        return;
+    }
+    for mut water in &mut waters {
+        water.caustics_intensity = if water.caustics_intensity > 0.0 {
+            0.0
+        } else {
+            WaterSettings::default().caustics_intensity
+        };
+    }
+}
@@ -76,8 +76,11 @@ fn animate_cube(time: Res<Time>, mut query: Query<(&mut Transform, &Curve)>, mut
     let t = (time.elapsed_seconds().sin() + 1.) / 2.;
 
     for (mut transform, cubic_curve) in &mut query {
-        // Draw the curve
-        gizmos.linestrip(cubic_curve.0.iter_positions(50), Color::WHITE);
+        // Draw the curve, with its control points and a few tangents
+        gizmos
+            .curve_3d(&cubic_curve.0, Color::WHITE)
+            .control_points(Color::GRAY)
+            .tangents(5, Color::ORANGE);
         // position takes a point from the curve where 0 is the initial point
         // and 1 is the last point
         transform.translation = cubic_curve.0.position(t);
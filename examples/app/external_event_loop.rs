@@ -0,0 +1,57 @@
+//! This example shows how to drive an app from the event loop of another application, for example
+//! to embed Bevy in an existing native application or engine.
+//!
+//! Instead of calling [`App::run`], the host forwards its events to the app and updates it with
+//! [`App::update_with_events`], until the app exits.
+//!
+//! To render into a window owned by the host, disable the `WinitPlugin` and spawn a [`Window`]
+//! entity with a [`RawHandleWrapper`](bevy::window::RawHandleWrapper) built from the native
+//! handles of the host's window, then keep its resolution up to date when the host resizes it.
+
+use bevy::{app::AppExit, prelude::*};
+use std::{sync::mpsc, thread, time::Duration};
+
+/// A message from the host application, forwarded to the app as an event.
+#[derive(Event, Debug)]
+enum HostMessage {
+    Text(String),
+    Quit,
+}
+
+fn main() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_event::<HostMessage>()
+        .add_systems(Update, handle_host_messages);
+
+    // Stand-in for the host's own event source, like a native message loop
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for text in ["hello", "from", "the host"] {
+            sender.send(HostMessage::Text(text.to_string())).unwrap();
+            thread::sleep(Duration::from_millis(100));
+        }
+        sender.send(HostMessage::Quit).unwrap();
+    });
+
+    // The host's event loop: forward the pending messages, then update the app once
+    loop {
+        let messages: Vec<HostMessage> = receiver.try_iter().collect();
+        if app.update_with_events(messages).is_some() {
+            break;
+        }
+        thread::sleep(Duration::from_millis(16));
+    }
+    println!("The app exited, the host keeps running");
+}
+
+fn handle_host_messages(mut messages: EventReader<HostMessage>, mut exit: EventWriter<AppExit>) {
+    for message in messages.read() {
+        match message {
+            HostMessage::Text(text) => println!("Received: {text}"),
+            HostMessage::Quit => {
+                exit.send(AppExit);
+            }
+        }
+    }
+}
@@ -0,0 +1,71 @@
+//! This example shows how to capture audio from a microphone, and use its level to drive a
+//! meter on screen.
+
+use bevy::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, MicrophonePlugin::default()))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (update_meter, toggle_capture))
+        .run();
+}
+
+#[derive(Component)]
+struct Meter;
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2dBundle::default());
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: Color::rgb(0.3, 0.8, 0.4),
+                custom_size: Some(Vec2::new(600.0, 40.0)),
+                ..default()
+            },
+            ..default()
+        },
+        Meter,
+    ));
+    commands.spawn(
+        TextBundle::from_section(
+            "Speak into your microphone\nPress 'Space' to start or stop capturing",
+            TextStyle::default(),
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(12.0),
+            left: Val::Px(12.0),
+            ..default()
+        }),
+    );
+}
+
+fn update_meter(
+    microphone: Res<MicrophoneInput>,
+    time: Res<Time>,
+    mut meter: Query<&mut Transform, With<Meter>>,
+    mut level: Local<f32>,
+) {
+    // The root mean square of the last 50ms of audio
+    let samples = microphone.latest(microphone.sample_rate() as usize / 20);
+    let rms = if samples.is_empty() {
+        0.0
+    } else {
+        (samples.iter().map(|sample| sample * sample).sum::<f32>() / samples.len() as f32).sqrt()
+    };
+    // Falls back slowly, so the meter doesn't flicker
+    *level = rms.max(*level - time.delta_seconds());
+
+    let mut transform = meter.single_mut();
+    transform.scale.x = (*level * 4.0).clamp(0.0, 1.0);
+}
+
+fn toggle_capture(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut settings: ResMut<MicrophoneSettings>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Space) {
+        settings.enabled ^= true;
+    }
+}
@@ -0,0 +1,110 @@
+//! Moves a character around a small level with the keyboard, using a kinematic character
+//! controller that slides along walls, climbs steps and jumps.
+
+use bevy::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, CharacterControllerPlugin::default()))
+        .add_systems(Startup, setup)
+        .add_systems(Update, move_player)
+        .run();
+}
+
+#[derive(Component, Default)]
+struct Player {
+    vertical_speed: f32,
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // The level: a floor, a wall and a few steps.
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Cuboid::new(20.0, 1.0, 20.0)),
+        material: materials.add(Color::rgb(0.3, 0.5, 0.3)),
+        transform: Transform::from_xyz(0.0, -0.5, 0.0),
+        ..default()
+    });
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Cuboid::new(1.0, 3.0, 10.0)),
+        material: materials.add(Color::rgb(0.8, 0.7, 0.6)),
+        transform: Transform::from_xyz(-4.0, 1.5, 0.0),
+        ..default()
+    });
+    for i in 1..=4 {
+        let height = i as f32 * 0.25;
+        commands.spawn(PbrBundle {
+            mesh: meshes.add(Cuboid::new(1.0, height, 4.0)),
+            material: materials.add(Color::rgb(0.6, 0.6, 0.7)),
+            transform: Transform::from_xyz(i as f32 + 1.0, height / 2.0, 0.0),
+            ..default()
+        });
+    }
+
+    let controller = CharacterController::default();
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(controller.shape),
+            material: materials.add(Color::rgb(0.8, 0.2, 0.2)),
+            transform: Transform::from_xyz(0.0, 2.0, 0.0),
+            ..default()
+        },
+        controller,
+        Player::default(),
+    ));
+
+    commands.spawn(PointLightBundle {
+        point_light: PointLight {
+            intensity: 250_000.0,
+            shadows_enabled: true,
+            ..default()
+        },
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..default()
+    });
+    commands.spawn(Camera3dBundle {
+        transform: Transform::from_xyz(0.0, 10.0, 12.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..default()
+    });
+}
+
+fn move_player(
+    time: Res<Time>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut players: Query<(&mut CharacterController, &mut Player)>,
+) {
+    const SPEED: f32 = 4.0;
+    const JUMP_SPEED: f32 = 5.0;
+    const GRAVITY: f32 = 9.81;
+
+    let mut direction = Vec3::ZERO;
+    if keyboard_input.pressed(KeyCode::ArrowLeft) {
+        direction.x -= 1.0;
+    }
+    if keyboard_input.pressed(KeyCode::ArrowRight) {
+        direction.x += 1.0;
+    }
+    if keyboard_input.pressed(KeyCode::ArrowUp) {
+        direction.z -= 1.0;
+    }
+    if keyboard_input.pressed(KeyCode::ArrowDown) {
+        direction.z += 1.0;
+    }
+
+    for (mut controller, mut player) in &mut players {
+        if controller.is_grounded() {
+            player.vertical_speed = if keyboard_input.just_pressed(KeyCode::Space) {
+                JUMP_SPEED
+            } else {
+                0.0
+            };
+        }
+        player.vertical_speed -= GRAVITY * time.delta_seconds();
+        controller.translation = (direction.normalize_or_zero() * SPEED
+            + Vec3::Y * player.vertical_speed)
+            * time.delta_seconds();
+    }
+}
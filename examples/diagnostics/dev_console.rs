@@ -0,0 +1,92 @@
+//! This example shows how to add an in-game developer console with custom commands.
+//!
+//! Press ` to open or close the console, and type `help` to list the commands. Try:
+//! - `set_state GameState Paused`
+//! - `get <entity> Transform.translation`, with the entity printed at startup, like `4v1`
+//! - `set <entity> Orbit.speed 4`
+//! - `orbit_radius 200`
+//! - `diagnostics`
+
+use bevy::{
+    dev_tools::{
+        console::{AppConsoleBuilder, ConsoleCommand, DevConsolePlugin},
+        diagnostics_overlay::{DiagnosticsOverlayConfig, DiagnosticsOverlayPlugin},
+    },
+    prelude::*,
+};
+
+#[derive(States, Reflect, Default, Debug, Clone, PartialEq, Eq, Hash)]
+enum GameState {
+    #[default]
+    Playing,
+    Paused,
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct Orbit {
+    speed: f32,
+    radius: f32,
+}
+
+fn main() {
+    App::new()
+        .add_plugins((
+            DefaultPlugins,
+            DevConsolePlugin::default(),
+            DiagnosticsOverlayPlugin {
+                config: DiagnosticsOverlayConfig {
+                    enabled: false,
+                    ..default()
+                },
+            },
+        ))
+        .init_state::<GameState>()
+        .register_type::<Orbit>()
+        // Lets `state` and `set_state` read and change the state.
+        .add_console_state::<GameState>()
+        // Arguments are parsed with the text format registered for their type.
+        .add_console_command(
+            ConsoleCommand::new("orbit_radius", |world, args| {
+                let radius = *args.arg::<f32>(0);
+                let mut orbits = world.query::<&mut Orbit>();
+                for mut orbit in orbits.iter_mut(world) {
+                    orbit.radius = radius;
+                }
+                Ok(format!("Orbit radius set to {radius}"))
+            })
+            .with_description("Sets the radius of every orbit")
+            .with_arg::<f32>("radius"),
+        )
+        .add_systems(Startup, setup)
+        .add_systems(Update, move_orbits.run_if(in_state(GameState::Playing)))
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2dBundle::default());
+    let entity = commands
+        .spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: Color::rgb(0.3, 0.6, 0.9),
+                    custom_size: Some(Vec2::splat(50.0)),
+                    ..default()
+                },
+                ..default()
+            },
+            Orbit {
+                speed: 1.0,
+                radius: 100.0,
+            },
+        ))
+        .id();
+    info!("The orbiting square is {entity:?}");
+}
+
+fn move_orbits(time: Res<Time>, mut orbits: Query<(&mut Transform, &Orbit)>) {
+    for (mut transform, orbit) in &mut orbits {
+        let angle = time.elapsed_seconds() * orbit.speed;
+        transform.translation = (Vec2::from_angle(angle) * orbit.radius).extend(0.0);
+    }
+}
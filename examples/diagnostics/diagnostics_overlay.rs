@@ -0,0 +1,46 @@
+//! This example shows how to display diagnostics in an in-game overlay.
+//!
+//! Press F12 to show or hide the overlay.
+
+use bevy::{
+    dev_tools::diagnostics_overlay::{
+        DiagnosticGraph, DiagnosticsOverlayConfig, DiagnosticsOverlayPlugin, OverlayDiagnostic,
+    },
+    diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic},
+    prelude::*,
+};
+
+// All diagnostics should have a unique DiagnosticPath.
+const WAVE: DiagnosticPath = DiagnosticPath::const_new("wave");
+
+fn main() {
+    App::new()
+        .add_plugins((
+            DefaultPlugins,
+            // The default configuration shows the FPS, the frame time and the entity count.
+            // Custom diagnostics can be added on top, with or without a history graph.
+            DiagnosticsOverlayPlugin {
+                config: DiagnosticsOverlayConfig::default().with_diagnostic(
+                    OverlayDiagnostic::new(WAVE)
+                        .with_label("Wave")
+                        .with_graph(DiagnosticGraph {
+                            color: Color::rgb(0.3, 0.5, 1.0),
+                            max: Some(2.0),
+                            ..default()
+                        }),
+                ),
+            },
+        ))
+        .register_diagnostic(Diagnostic::new(WAVE))
+        .add_systems(Startup, setup)
+        .add_systems(Update, measure_wave)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2dBundle::default());
+}
+
+fn measure_wave(mut diagnostics: Diagnostics, time: Res<Time>) {
+    diagnostics.add_measurement(&WAVE, || time.elapsed_seconds_f64().sin() + 1.0);
+}
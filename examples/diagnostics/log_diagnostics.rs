@@ -16,9 +16,14 @@ fn main() {
             // Any plugin can register diagnostics. Uncomment this to add an entity count diagnostics:
             // bevy::diagnostic::EntityCountDiagnosticsPlugin::default(),
             // Uncomment this to add an asset count diagnostics:
-            // bevy::asset::diagnostic::AssetCountDiagnosticsPlugin::<Texture>::default(),
+            // bevy::asset::diagnostic::AssetCountDiagnosticsPlugin::<Image>::default(),
             // Uncomment this to add system info diagnostics:
-            // bevy::diagnostic::SystemInformationDiagnosticsPlugin::default()
+            // bevy::diagnostic::SystemInformationDiagnosticsPlugin::default(),
+            // Uncomment this to add GPU timing diagnostics for each render pass, where supported:
+            // bevy::render::diagnostic::RenderDiagnosticsPlugin,
+            // Uncomment this to add ECS and GPU memory diagnostics:
+            // bevy::diagnostic::MemoryDiagnosticsPlugin::default(),
+            // bevy::render::diagnostic::GpuMemoryDiagnosticsPlugin::default(),
         ))
         .run();
 }
@@ -0,0 +1,95 @@
+//! Bakes a navigation mesh from the level geometry, and finds paths on it around a moving
+//! obstacle.
+
+use bevy::{
+    navigation::gizmos::{NavMeshGizmoConfigGroup, NavPathGizmo},
+    prelude::*,
+};
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, NavigationPlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (move_obstacle, find_path))
+        .run();
+}
+
+#[derive(Component)]
+struct Obstacle;
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut config_store: ResMut<GizmoConfigStore>,
+) {
+    config_store
+        .config_mut::<NavMeshGizmoConfigGroup>()
+        .1
+        .draw_navmesh = true;
+
+    // The level geometry the navigation mesh is baked from.
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(Plane3d::default().mesh().size(20.0, 20.0)),
+            material: materials.add(Color::rgb(0.3, 0.5, 0.3)),
+            ..default()
+        },
+        NavMeshSource,
+    ));
+    for (x, z, length) in [(-3.0, 2.0, 12.0), (3.0, -2.0, 12.0)] {
+        commands.spawn((
+            PbrBundle {
+                mesh: meshes.add(Cuboid::new(1.0, 2.0, length)),
+                material: materials.add(Color::rgb(0.8, 0.7, 0.6)),
+                transform: Transform::from_xyz(x, 1.0, z),
+                ..default()
+            },
+            NavMeshSource,
+        ));
+    }
+
+    // A moving obstacle: only the tiles around it are rebuilt when it moves.
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(Cuboid::new(2.0, 2.0, 2.0)),
+            material: materials.add(Color::rgb(0.8, 0.2, 0.2)),
+            transform: Transform::from_xyz(0.0, 1.0, 0.0),
+            ..default()
+        },
+        NavMeshObstacle {
+            half_extents: Vec3::splat(1.0),
+        },
+        Obstacle,
+    ));
+
+    commands.spawn(NavPathGizmo::default());
+
+    commands.spawn(PointLightBundle {
+        point_light: PointLight {
+            intensity: 250_000.0,
+            shadows_enabled: true,
+            ..default()
+        },
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..default()
+    });
+    commands.spawn(Camera3dBundle {
+        transform: Transform::from_xyz(0.0, 18.0, 14.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..default()
+    });
+}
+
+fn move_obstacle(time: Res<Time>, mut obstacles: Query<&mut Transform, With<Obstacle>>) {
+    for mut transform in &mut obstacles {
+        transform.translation.z = (time.elapsed_seconds() * 0.5).sin() * 6.0;
+    }
+}
+
+fn find_path(navmesh: Res<NavMesh>, mut paths: Query<&mut NavPathGizmo>) {
+    let start = Vec3::new(-8.0, 0.0, -8.0);
+    let end = Vec3::new(8.0, 0.0, 8.0);
+    for mut gizmo in &mut paths {
+        gizmo.path = navmesh.find_path(start, end).unwrap_or_default();
+    }
+}
@@ -0,0 +1,127 @@
+//! Demonstrates how to react to the mouse and touch pointers hovering, clicking and dragging
+//! sprites and UI nodes.
+
+use bevy::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_systems(Startup, setup)
+        .add_systems(Update, (drag_sprites, highlight_hovered))
+        .run();
+}
+
+#[derive(Component)]
+struct Counter(u32);
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2dBundle::default());
+
+    // Sprites can be dragged around, and despawned with a right click.
+    for i in 0..3 {
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: Color::GRAY,
+                    custom_size: Some(Vec2::splat(100.0)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(i as f32 * 150.0 - 150.0, 0.0, i as f32),
+                ..default()
+            },
+            On::<Pointer<Click>>::run(|event, commands| {
+                if event.button == PointerButton::Secondary {
+                    commands.entity(event.target).despawn();
+                }
+            }),
+        ));
+    }
+
+    // Clicks on the text of the button bubble up to the button.
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: Style {
+                            margin: UiRect::all(Val::Px(20.0)),
+                            padding: UiRect::all(Val::Px(10.0)),
+                            ..default()
+                        },
+                        background_color: Color::DARK_GRAY.into(),
+                        ..default()
+                    },
+                    Counter(0),
+                    On::<Pointer<Click>>::run(|event, commands| {
+                        commands.add(increment_counter(event.listener()));
+                    }),
+                ))
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        "Clicked 0 times",
+                        TextStyle {
+                            font_size: 30.0,
+                            ..default()
+                        },
+                    ));
+                });
+        });
+}
+
+fn increment_counter(button: Entity) -> impl FnOnce(&mut World) + Send + 'static {
+    move |world: &mut World| {
+        let Some(mut counter) = world.get_mut::<Counter>(button) else {
+            return;
+        };
+        counter.0 += 1;
+        let count = counter.0;
+        let Some(children) = world.get::<Children>(button) else {
+            return;
+        };
+        let text = children[0];
+        if let Some(mut text) = world.get_mut::<Text>(text) {
+            text.sections[0].value = format!("Clicked {count} times");
+        }
+    }
+}
+
+/// Pointer events can also be read like any other event.
+fn drag_sprites(
+    mut drags: EventReader<Pointer<Drag>>,
+    mut sprites: Query<&mut Transform, With<Sprite>>,
+) {
+    for drag in drags.read() {
+        if drag.button != PointerButton::Primary {
+            continue;
+        }
+        if let Ok(mut transform) = sprites.get_mut(drag.target) {
+            // The y axis of the screen points down, while the y axis of the world points up.
+            transform.translation += Vec3::new(drag.delta.x, -drag.delta.y, 0.0);
+        }
+    }
+}
+
+fn highlight_hovered(
+    mut overs: EventReader<Pointer<Over>>,
+    mut outs: EventReader<Pointer<Out>>,
+    mut sprites: Query<&mut Sprite>,
+) {
+    for over in overs.read() {
+        if let Ok(mut sprite) = sprites.get_mut(over.target) {
+            sprite.color = Color::WHITE;
+        }
+    }
+    for out in outs.read() {
+        if let Ok(mut sprite) = sprites.get_mut(out.target) {
+            sprite.color = Color::GRAY;
+        }
+    }
+}
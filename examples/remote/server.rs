@@ -0,0 +1,73 @@
+//! A Bevy app that can be inspected and modified remotely using the Bevy Remote Protocol.
+//!
+//! While the app is running, send it requests with any HTTP client, for example:
+//!
+//! ```sh
+//! curl -X POST http://127.0.0.1:15702 -d '{
+//!     "jsonrpc": "2.0",
+//!     "id": 0,
+//!     "method": "bevy/query",
+//!     "params": { "data": { "components": ["server::Cube"] } }
+//! }'
+//! ```
+
+use bevy::{
+    prelude::*,
+    remote::{http::RemoteHttpPlugin, RemotePlugin},
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(RemotePlugin::default())
+        .add_plugins(RemoteHttpPlugin::default())
+        .register_type::<Cube>()
+        .add_systems(Startup, setup)
+        .add_systems(Update, rotate)
+        .run();
+}
+
+/// A cube that spins at the given speed, in radians per second.
+///
+/// Try changing the speed remotely with `bevy/mutate_component`,
+/// using `"component": "server::Cube"` and `"path": ".0"`.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct Cube(f32);
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(Cuboid::new(1.0, 1.0, 1.0)),
+            material: materials.add(Color::rgb_u8(124, 144, 255)),
+            transform: Transform::from_xyz(0.0, 0.5, 0.0),
+            ..default()
+        },
+        Cube(1.0),
+    ));
+
+    commands.spawn(PointLightBundle {
+        point_light: PointLight {
+            intensity: 250_000.0,
+            shadows_enabled: true,
+            ..default()
+        },
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..default()
+    });
+
+    commands.spawn(Camera3dBundle {
+        transform: Transform::from_xyz(-2.5, 4.5, 9.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..default()
+    });
+}
+
+fn rotate(mut cubes: Query<(&mut Transform, &Cube)>, time: Res<Time>) {
+    for (mut transform, cube) in &mut cubes {
+        transform.rotate_y(cube.0 * time.delta_seconds());
+    }
+}
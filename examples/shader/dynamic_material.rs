@@ -0,0 +1,50 @@
+//! A material defined entirely by an asset file, with a uniform animated at runtime.
+//!
+//! The shader, texture and uniform values are read from `assets/materials/pulse.material`, so
+//! they can be changed without recompiling.
+
+use bevy::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_systems(Startup, setup)
+        .add_systems(Update, animate_pulse)
+        .run();
+}
+
+#[derive(Resource)]
+struct PulseMaterial(Handle<DynamicMaterial>);
+
+/// set up a simple 3D scene
+fn setup(mut commands: Commands, mut meshes: ResMut<Assets<Mesh>>, asset_server: Res<AssetServer>) {
+    let material = asset_server.load("materials/pulse.material");
+
+    // cube
+    commands.spawn(MaterialMeshBundle {
+        mesh: meshes.add(Cuboid::default()),
+        transform: Transform::from_xyz(0.0, 0.5, 0.0),
+        material: material.clone(),
+        ..default()
+    });
+    commands.insert_resource(PulseMaterial(material));
+
+    // camera
+    commands.spawn(Camera3dBundle {
+        transform: Transform::from_xyz(-2.0, 2.5, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..default()
+    });
+}
+
+fn animate_pulse(
+    time: Res<Time>,
+    pulse_material: Res<PulseMaterial>,
+    mut materials: ResMut<Assets<DynamicMaterial>>,
+) {
+    // The material is not available until it has loaded
+    let Some(material) = materials.get_mut(&pulse_material.0) else {
+        return;
+    };
+    let pulse = time.elapsed_seconds().sin() * 0.5 + 0.5;
+    material.set_uniform("pulse", &pulse).unwrap();
+}
@@ -14,6 +14,7 @@ use argh::FromArgs;
 use bevy::{
     diagnostic::{FrameTimeDiagnosticsPlugin, LogDiagnosticsPlugin},
     math::{DVec2, DVec3},
+    pbr::GpuShadowCulling,
     prelude::*,
     render::{
         render_asset::RenderAssetUsages,
@@ -42,6 +43,14 @@ struct Args {
     /// the number of different textures from which to randomly select the material base color. 0 means no textures.
     #[argh(option, default = "0")]
     material_texture_count: usize,
+
+    /// whether the directional light casts shadows.
+    #[argh(switch)]
+    shadows: bool,
+
+    /// whether to cull the shadow casters of the directional light on the GPU.
+    #[argh(switch)]
+    gpu_shadow_culling: bool,
 }
 
 #[derive(Default, Clone)]
@@ -190,7 +199,16 @@ fn setup(
         }
     }
 
-    commands.spawn(DirectionalLightBundle { ..default() });
+    let mut directional_light = commands.spawn(DirectionalLightBundle {
+        directional_light: DirectionalLight {
+            shadows_enabled: args.shadows,
+            ..default()
+        },
+        ..default()
+    });
+    if args.gpu_shadow_culling {
+        directional_light.insert(GpuShadowCulling);
+    }
 }
 
 fn init_textures(args: &Args, images: &mut Assets<Image>) -> Vec<Handle<Image>> {
@@ -1,7 +1,7 @@
 //! This example illustrates how `FontAtlas`'s are populated.
 //! Bevy uses `FontAtlas`'s under the hood to optimize text rendering.
 
-use bevy::{prelude::*, text::FontAtlasSets};
+use bevy::{prelude::*, text::FontAtlases};
 
 fn main() {
     App::new()
@@ -16,7 +16,6 @@ fn main() {
 #[derive(Resource)]
 struct State {
     atlas_count: u32,
-    handle: Handle<Font>,
     timer: Timer,
 }
 
@@ -24,7 +23,6 @@ impl Default for State {
     fn default() -> Self {
         Self {
             atlas_count: 0,
-            handle: Handle::default(),
             timer: Timer::from_seconds(0.05, TimerMode::Repeating),
         }
     }
@@ -33,28 +31,24 @@ impl Default for State {
 fn atlas_render_system(
     mut commands: Commands,
     mut state: ResMut<State>,
-    font_atlas_sets: Res<FontAtlasSets>,
+    font_atlases: Res<FontAtlases>,
 ) {
-    if let Some(set) = font_atlas_sets.get(&state.handle) {
-        if let Some((_size, font_atlas)) = set.iter().next() {
-            let x_offset = state.atlas_count as f32;
-            if state.atlas_count == font_atlas.len() as u32 {
-                return;
-            }
-            let font_atlas = &font_atlas[state.atlas_count as usize];
-            state.atlas_count += 1;
-            commands.spawn(ImageBundle {
-                image: font_atlas.texture.clone().into(),
-                style: Style {
-                    position_type: PositionType::Absolute,
-                    top: Val::ZERO,
-                    left: Val::Px(512.0 * x_offset),
-                    ..default()
-                },
-                ..default()
-            });
-        }
+    if state.atlas_count == font_atlases.len() as u32 {
+        return;
     }
+    let x_offset = state.atlas_count as f32;
+    let font_atlas = &font_atlases.pages()[state.atlas_count as usize];
+    state.atlas_count += 1;
+    commands.spawn(ImageBundle {
+        image: font_atlas.texture.clone().into(),
+        style: Style {
+            position_type: PositionType::Absolute,
+            top: Val::ZERO,
+            left: Val::Px(512.0 * x_offset),
+            ..default()
+        },
+        ..default()
+    });
 }
 
 fn text_update_system(mut state: ResMut<State>, time: Res<Time>, mut query: Query<&mut Text>) {
@@ -71,9 +65,8 @@ fn text_update_system(mut state: ResMut<State>, time: Res<Time>, mut query: Quer
     }
 }
 
-fn setup(mut commands: Commands, asset_server: Res<AssetServer>, mut state: ResMut<State>) {
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
     let font_handle = asset_server.load("fonts/FiraSans-Bold.ttf");
-    state.handle = font_handle.clone();
     commands.spawn(Camera2dBundle::default());
     commands
         .spawn(NodeBundle {